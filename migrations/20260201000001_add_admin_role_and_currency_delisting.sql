@@ -0,0 +1,33 @@
+-- ============================================================================
+-- Migration: Admin role and graceful currency delisting
+-- Created: 2026-02-01
+-- Description: Add user roles for the admin module, a scheduled delisting
+--              date on currencies, and a queue of user notifications
+-- ============================================================================
+
+ALTER TABLE users
+ADD COLUMN role ENUM('user', 'admin') NOT NULL DEFAULT 'user' AFTER two_factor_secret;
+
+-- When set, the currency is announced as delisting and new swaps are blocked
+-- once the date has passed. In-flight swaps are unaffected.
+ALTER TABLE currencies
+ADD COLUMN delisting_at TIMESTAMP NULL AFTER is_active;
+
+CREATE INDEX idx_currencies_delisting_at ON currencies(delisting_at);
+
+-- Pending user-facing notices, drained by the notification senders
+CREATE TABLE IF NOT EXISTS user_notifications (
+    id BIGINT PRIMARY KEY AUTO_INCREMENT,
+    user_id VARCHAR(36) NOT NULL,
+    kind VARCHAR(50) NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    body TEXT NOT NULL,
+    status ENUM('pending', 'sent', 'failed') NOT NULL DEFAULT 'pending',
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    sent_at TIMESTAMP NULL,
+
+    INDEX idx_user_notifications_user (user_id),
+    INDEX idx_user_notifications_status (status),
+
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,19 @@
+-- ============================================================================
+-- Migration: Sync run history
+-- Created: 2026-02-02
+-- Description: Record the outcome of each currency/provider sync run
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS sync_runs (
+    id BIGINT PRIMARY KEY AUTO_INCREMENT,
+    kind ENUM('currencies', 'providers') NOT NULL,
+    status ENUM('success', 'failed', 'timeout') NOT NULL,
+    rows_fetched INT NOT NULL DEFAULT 0,
+    rows_changed INT NOT NULL DEFAULT 0,
+    duration_ms BIGINT NOT NULL DEFAULT 0,
+    error TEXT,
+    started_at TIMESTAMP NOT NULL,
+    finished_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    INDEX idx_sync_runs_kind_started (kind, started_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
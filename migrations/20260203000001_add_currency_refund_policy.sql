@@ -0,0 +1,11 @@
+-- ============================================================================
+-- Migration: Refund address policy per currency
+-- Created: 2026-02-03
+-- Description: Flag currency/network pairs where a refund is impossible
+--              without a refund address. create_swap rejects swaps from
+--              these currencies when refund_address is missing.
+-- ============================================================================
+
+-- Admin-managed; not touched by the Trocador sync
+ALTER TABLE currencies
+ADD COLUMN requires_refund_address BOOLEAN NOT NULL DEFAULT FALSE AFTER extra_id_name;
@@ -0,0 +1,15 @@
+-- ============================================================================
+-- Migration: Link retried swaps to the swap they replace
+-- Created: 2026-02-04
+-- Description: POST /swap/{id}/retry creates a fresh swap from a failed or
+--              expired one; retried_from points back at the original and is
+--              unique, so a swap is retried at most once. retry_claimed_at
+--              marks the original while its retry is being created, so a
+--              second request is refused before it reaches the provider; a
+--              claim older than five minutes is treated as abandoned.
+-- ============================================================================
+
+ALTER TABLE swaps
+ADD COLUMN retried_from VARCHAR(36) NULL AFTER provider_swap_id,
+ADD COLUMN retry_claimed_at TIMESTAMP NULL AFTER retried_from,
+ADD UNIQUE INDEX uq_swaps_retried_from (retried_from);
@@ -0,0 +1,20 @@
+-- ============================================================================
+-- Migration: Raw provider payloads per swap
+-- Created: 2026-02-05
+-- Description: Gzip-compressed copies of what Trocador returned when a trade
+--              was created and whenever a status poll changed the swap's
+--              status. Bodies over 64 KiB are truncated before compression.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS swap_provider_payloads (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    swap_id VARCHAR(36) NOT NULL,
+    call_type ENUM('create_trade', 'trade_status') NOT NULL,
+    payload_gz MEDIUMBLOB NOT NULL,
+    original_bytes INT UNSIGNED NOT NULL,
+    truncated BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    INDEX idx_swap_provider_payloads_swap (swap_id, created_at),
+    FOREIGN KEY (swap_id) REFERENCES swaps(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
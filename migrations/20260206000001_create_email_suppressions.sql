@@ -0,0 +1,16 @@
+-- ============================================================================
+-- Migration: Email suppression list
+-- Created: 2026-02-06
+-- Description: Addresses that hard-bounced or filed a complaint, fed by the
+--              /webhooks/email endpoints. EmailService refuses to send to
+--              anything listed here.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS email_suppressions (
+    email VARCHAR(255) PRIMARY KEY, -- stored lowercased
+    reason ENUM('bounce', 'complaint') NOT NULL,
+    source VARCHAR(20) NOT NULL,    -- backend that reported it: ses, smtp, log
+    detail TEXT NULL,               -- diagnostic code or complaint feedback type
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,25 @@
+-- ============================================================================
+-- Migration: Swap funnel analytics events
+-- Created: 2026-02-07
+-- Description: Events written by the `db` analytics sink (ANALYTICS_SINK=db):
+--              quote_viewed, swap_created, deposit_detected, completed, failed.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS analytics_events (
+    id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    event VARCHAR(32) NOT NULL,
+    swap_id VARCHAR(36) NULL,       -- NULL for quote_viewed
+    user_id VARCHAR(36) NULL,
+    session_id VARCHAR(255) NULL,   -- X-Session-Id header
+    utm_source VARCHAR(255) NULL,
+    utm_medium VARCHAR(255) NULL,
+    utm_campaign VARCHAR(255) NULL,
+    referrer VARCHAR(255) NULL,
+    properties JSON NOT NULL,
+    occurred_at TIMESTAMP(3) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    INDEX idx_analytics_event_time (event, occurred_at),
+    INDEX idx_analytics_swap (swap_id),
+    INDEX idx_analytics_session (session_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,21 @@
+-- ============================================================================
+-- Migration: Domain event outbox
+-- Created: 2026-02-08
+-- Description: Swap lifecycle, sync and alert events waiting to be relayed to
+--              the event bus (EVENT_BUS=kafka|nats). Rows are only written
+--              while an event bus is configured.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS event_outbox (
+    id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    event_type VARCHAR(64) NOT NULL,      -- e.g. swap.status_changed
+    schema_version SMALLINT UNSIGNED NOT NULL,
+    aggregate_id VARCHAR(64) NOT NULL,    -- swap id, sync kind, ...; used as the message key
+    payload JSON NOT NULL,
+    attempts INT UNSIGNED NOT NULL DEFAULT 0,
+    last_error TEXT NULL,
+    created_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    published_at TIMESTAMP(3) NULL,
+
+    INDEX idx_outbox_pending (published_at, id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
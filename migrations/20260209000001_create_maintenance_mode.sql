@@ -0,0 +1,17 @@
+-- ============================================================================
+-- Migration: Maintenance mode
+-- Created: 2026-02-09
+-- Description: Single-row flag toggled through PUT /admin/maintenance. While
+--              enabled, write endpoints answer 503 with Retry-After. The row is
+--              mirrored in Redis (maintenance:state).
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS maintenance_mode (
+    id TINYINT UNSIGNED PRIMARY KEY,      -- always 1
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    messages JSON NULL,                   -- {"en": "...", "es": "..."}; built-in text when absent
+    retry_after_secs INT UNSIGNED NOT NULL DEFAULT 300,
+    ends_at TIMESTAMP NULL,               -- expected end; drives Retry-After when set
+    updated_by VARCHAR(36) NULL,          -- admin user id
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
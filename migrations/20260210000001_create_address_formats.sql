@@ -0,0 +1,43 @@
+-- ============================================================================
+-- Migration: Address format registry
+-- Created: 2026-02-10
+-- Description: Per-network address and memo rules managed through
+--              /admin/address-formats. Address validation rejects addresses
+--              that fail these rules before asking the provider; memo checks
+--              and payment URIs are driven by the same rows. ticker '*'
+--              applies to every token on the network. Cached in Redis
+--              (address_formats:all).
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS address_formats (
+    id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    ticker VARCHAR(20) NOT NULL,              -- lowercase, or '*' for the whole network
+    network VARCHAR(50) NOT NULL,
+    address_regex VARCHAR(500) NOT NULL,      -- matched against the whole address
+    checksum VARCHAR(30) NOT NULL DEFAULT 'none', -- none, base58check, bech32, base58check_or_bech32, eip55
+    memo_name VARCHAR(50) NULL,               -- e.g. "Destination Tag"
+    memo_required BOOLEAN NOT NULL DEFAULT FALSE,
+    memo_format VARCHAR(20) NOT NULL DEFAULT 'text', -- text, integer, integer_or_text
+    memo_max_length INT UNSIGNED NULL,        -- bytes, for text memos
+    memo_max_value BIGINT UNSIGNED NULL,      -- for numeric memos
+    uri_scheme VARCHAR(30) NULL,              -- BIP21-style scheme; no payment URI when NULL
+    uri_memo_param VARCHAR(30) NULL,          -- query parameter carrying the memo
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    UNIQUE KEY uk_address_formats_ticker_network (ticker, network)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- Seed the networks we see most; memo rules match what create_swap enforced before
+INSERT IGNORE INTO address_formats
+    (ticker, network, address_regex, checksum, memo_name, memo_required, memo_format, memo_max_length, memo_max_value, uri_scheme, uri_memo_param)
+VALUES
+    ('btc', 'Mainnet', '(bc1[02-9ac-hj-np-z]{11,71}|[13][1-9A-HJ-NP-Za-km-z]{25,34})', 'base58check_or_bech32', NULL, FALSE, 'text', 256, NULL, 'bitcoin', NULL),
+    ('ltc', 'Mainnet', '(ltc1[02-9ac-hj-np-z]{11,71}|[LM3][1-9A-HJ-NP-Za-km-z]{26,33})', 'base58check_or_bech32', NULL, FALSE, 'text', 256, NULL, 'litecoin', NULL),
+    ('eth', 'Mainnet', '0x[0-9a-fA-F]{40}', 'eip55', NULL, FALSE, 'text', 256, NULL, 'ethereum', NULL),
+    ('*', 'ERC20', '0x[0-9a-fA-F]{40}', 'eip55', NULL, FALSE, 'text', 256, NULL, NULL, NULL),
+    ('*', 'BEP20', '0x[0-9a-fA-F]{40}', 'eip55', NULL, FALSE, 'text', 256, NULL, NULL, NULL),
+    ('*', 'TRC20', 'T[1-9A-HJ-NP-Za-km-z]{33}', 'base58check', NULL, FALSE, 'text', 256, NULL, NULL, NULL),
+    ('xmr', 'Mainnet', '[48][0-9A-Za-z]{94}([0-9A-Za-z]{11})?', 'none', NULL, FALSE, 'text', 256, NULL, 'monero', NULL),
+    ('xrp', 'Mainnet', 'r[1-9A-HJ-NP-Za-km-z]{24,34}', 'none', 'Destination Tag', FALSE, 'integer', NULL, 4294967295, 'xrpl', 'dt'),
+    ('xlm', 'Mainnet', 'G[A-Z2-7]{55}', 'none', 'Memo', FALSE, 'integer_or_text', 28, 18446744073709551615, 'web+stellar', 'memo');
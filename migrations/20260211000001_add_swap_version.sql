@@ -0,0 +1,10 @@
+-- ============================================================================
+-- Migration: Optimistic concurrency for swaps
+-- Created: 2026-02-11
+-- Description: Every swap update bumps `version` and only applies when the row
+--              still has the version the writer read (compare-and-swap), so
+--              concurrent writers can no longer silently overwrite each other.
+-- ============================================================================
+
+ALTER TABLE swaps
+ADD COLUMN version INT UNSIGNED NOT NULL DEFAULT 0 AFTER status;
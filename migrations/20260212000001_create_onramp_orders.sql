@@ -0,0 +1,34 @@
+-- ============================================================================
+-- Migration: Fiat on-ramp orders
+-- Created: 2026-02-12
+-- Description: Card-to-crypto purchases placed through /onramp/orders. Status
+--              follows the provider's webhooks. When chain_swap is set, a swap
+--              from the purchased currency is created once the order completes
+--              and linked through swap_id.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS onramp_orders (
+    id VARCHAR(36) PRIMARY KEY,
+    user_id VARCHAR(36) NOT NULL,
+    provider VARCHAR(50) NOT NULL,
+    provider_order_id VARCHAR(255) NULL,      -- Set once the provider accepts the order
+    fiat_currency VARCHAR(10) NOT NULL,
+    fiat_amount DECIMAL(20, 2) NOT NULL,
+    crypto_currency VARCHAR(20) NOT NULL,
+    crypto_network VARCHAR(50) NOT NULL,
+    crypto_amount DECIMAL(20, 8) NULL,        -- Quoted, then the delivered amount
+    wallet_address VARCHAR(255) NOT NULL,
+    payment_url TEXT NULL,                    -- Provider checkout page
+    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- pending, processing, completed, failed, expired
+    tx_hash VARCHAR(255) NULL,
+    chain_swap TEXT NULL,                     -- JSON swap request to run after delivery
+    swap_id VARCHAR(36) NULL,
+    error TEXT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    UNIQUE KEY uk_onramp_orders_provider_order (provider, provider_order_id),
+    INDEX idx_onramp_orders_user (user_id, created_at),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (swap_id) REFERENCES swaps(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
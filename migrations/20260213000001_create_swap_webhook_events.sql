@@ -0,0 +1,30 @@
+-- ============================================================================
+-- Migration: Swap webhook deliveries
+-- Created: 2026-02-13
+-- Description: Every callback received at /swap/webhook/{provider}, kept with
+--              its raw body and signature so deliveries can be replayed and
+--              debugged. Rejected deliveries (bad signature, unknown trade)
+--              are recorded too, but one whose signature does not verify can
+--              come from anyone, so only its metadata is kept: when it came,
+--              from which address, its size and why it was refused.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS swap_webhook_events (
+    id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    provider VARCHAR(50) NOT NULL,            -- e.g. "trocador"
+    source_ip VARCHAR(45) NULL,
+    provider_swap_id VARCHAR(255) NULL,       -- Trade id from the payload, when it parsed
+    swap_id VARCHAR(36) NULL,                 -- Matched local swap
+    provider_status VARCHAR(100) NULL,
+    mapped_status VARCHAR(20) NULL,
+    signature VARCHAR(128) NULL,
+    signature_valid BOOLEAN NOT NULL,
+    outcome VARCHAR(20) NOT NULL,             -- applied, unchanged, superseded, rejected, invalid, unknown_swap, failed
+    error TEXT NULL,
+    payload_bytes INT UNSIGNED NOT NULL DEFAULT 0,
+    payload MEDIUMTEXT NULL,                  -- NULL unless signature_valid
+    received_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    INDEX idx_swap_webhook_events_swap (swap_id, received_at),
+    INDEX idx_swap_webhook_events_trade (provider, provider_swap_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
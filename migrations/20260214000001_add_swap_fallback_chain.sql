@@ -0,0 +1,11 @@
+-- ============================================================================
+-- Migration: Provider fallback chain on swaps
+-- Created: 2026-02-14
+-- Description: When a swap is created with allow_fallback and the requested
+--              provider rejects the trade, the providers tried before the one
+--              that accepted it are kept here as a JSON array of
+--              {provider, error}. NULL when no fallback happened.
+-- ============================================================================
+
+ALTER TABLE swaps
+ADD COLUMN fallback_chain TEXT NULL AFTER retried_from;
@@ -0,0 +1,12 @@
+-- ============================================================================
+-- Migration: Swap status poll tracking
+-- Created: 2026-02-15
+-- Description: When the background status poller last asked the provider
+--              about a swap. Polls that find no change leave updated_at
+--              alone, so this keeps the poller rotating through every
+--              in-flight swap instead of re-polling the same oldest batch.
+-- ============================================================================
+
+ALTER TABLE swaps
+ADD COLUMN last_polled_at TIMESTAMP NULL AFTER updated_at,
+ADD INDEX idx_swaps_status_polled (status, last_polled_at);
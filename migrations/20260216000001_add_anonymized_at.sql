@@ -0,0 +1,13 @@
+-- ============================================================================
+-- Migration: Data retention markers
+-- Created: 2026-02-16
+-- Description: When the retention engine scrubbed a row's personal fields
+--              (addresses, extra ids). Amounts, statuses and timestamps are
+--              kept for accounting; NULL means the row is untouched.
+-- ============================================================================
+
+ALTER TABLE swaps
+ADD COLUMN anonymized_at TIMESTAMP NULL AFTER completed_at;
+
+ALTER TABLE onramp_orders
+ADD COLUMN anonymized_at TIMESTAMP NULL AFTER updated_at;
@@ -0,0 +1,31 @@
+-- ============================================================================
+-- Migration: White-label brands
+-- Created: 2026-02-17
+-- Description: Partner brands managed through /admin/brands. A request is
+--              matched to a brand by its X-API-Key header (stored as a
+--              SHA-256 hash) or its Host; the brand sets the name and support
+--              address shown to users, the Trocador markup on quotes and
+--              trades, and which providers and pairs are offered. Cached in
+--              Redis (brands:all). swaps.brand records the brand a swap was
+--              created under.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS brands (
+    id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    slug VARCHAR(50) NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    support_email VARCHAR(255) NOT NULL,
+    api_key_hash CHAR(64) NULL,               -- hex SHA-256 of the partner's API key
+    domains JSON NOT NULL,                    -- lowercase hostnames, e.g. ["swap.partner.com"]
+    markup_percent DECIMAL(5, 2) NULL,        -- NULL uses the Trocador account default
+    enabled_providers JSON NOT NULL,          -- provider names; [] offers every provider
+    allowed_pairs JSON NOT NULL,              -- [{"from": "btc", "to": "*"}]; [] allows every pair
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    UNIQUE KEY uk_brands_slug (slug),
+    UNIQUE KEY uk_brands_api_key_hash (api_key_hash)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+ALTER TABLE swaps
+ADD COLUMN brand VARCHAR(50) NULL AFTER user_id;
@@ -0,0 +1,28 @@
+-- ============================================================================
+-- Migration: Platform fee rules
+-- Created: 2026-02-18
+-- Description: Platform fees taken from quotes and swaps, managed through
+--              /admin/fee-rules. Each rule can be narrowed to a pair, a
+--              provider and a user fee tier (NULL matches anything); the most
+--              specific matching rule sets the fee as a percentage of the
+--              receive amount plus a flat amount in the receive currency;
+--              between equally specific rules the newest wins.
+--              Cached in Redis (fee_rules:all). users.fee_tier places a user
+--              in a tier.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS fee_rules (
+    id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    from_currency VARCHAR(20) NULL,
+    to_currency VARCHAR(20) NULL,
+    provider VARCHAR(50) NULL,
+    user_tier VARCHAR(20) NULL,
+    fee_percent DECIMAL(6, 3) NOT NULL DEFAULT 0,
+    flat_fee DECIMAL(20, 8) NOT NULL DEFAULT 0,   -- in to_currency; requires to_currency
+    note VARCHAR(255) NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+ALTER TABLE users
+ADD COLUMN fee_tier VARCHAR(20) NOT NULL DEFAULT 'standard' AFTER role;
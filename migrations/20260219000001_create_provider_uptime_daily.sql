@@ -0,0 +1,22 @@
+-- ============================================================================
+-- Migration: Daily provider uptime
+-- Created: 2026-02-19
+-- Description: One row per provider per UTC day. The provider prober adds a
+--              probe every PROVIDER_PROBE_INTERVAL_SECS (up when Trocador
+--              lists the provider), and every new_trade attempt is counted
+--              with its outcome. Served by GET /swap/providers/{id}/uptime;
+--              rows older than the 90-day window are pruned by the prober.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS provider_uptime_daily (
+    provider VARCHAR(100) NOT NULL,           -- providers.name, as quoted
+    day DATE NOT NULL,                        -- UTC
+    probes INT UNSIGNED NOT NULL DEFAULT 0,
+    probes_up INT UNSIGNED NOT NULL DEFAULT 0,
+    trades INT UNSIGNED NOT NULL DEFAULT 0,
+    trades_failed INT UNSIGNED NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (provider, day),
+    INDEX idx_provider_uptime_day (day)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,23 @@
+-- ============================================================================
+-- Migration: Idempotency keys
+-- Created: 2026-02-20
+-- Description: Idempotency-Key headers seen on POST /swap/create. The first
+--              request inserts the row (claiming the key) and fills in the
+--              response once the swap exists; replays within 24 hours get
+--              that response back instead of creating a second trade.
+--              Expired rows are removed by the retention job.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    endpoint VARCHAR(50) NOT NULL,            -- e.g. swap_create
+    scope VARCHAR(64) NOT NULL,               -- users.id, or 'anonymous'
+    idempotency_key VARCHAR(255) NOT NULL,
+    request_hash CHAR(64) NOT NULL,           -- SHA-256 of the request body
+    resource_id VARCHAR(36) NULL,             -- Swap created by the first request
+    response JSON NULL,                       -- NULL while the first request runs
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    completed_at TIMESTAMP NULL,
+
+    PRIMARY KEY (endpoint, scope, idempotency_key),
+    INDEX idx_idempotency_keys_created_at (created_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,32 @@
+-- ============================================================================
+-- Migration: Shadow quotes
+-- Created: 2026-02-21
+-- Description: One row per rates request answered while an aggregator is in
+--              shadow mode (SHADOW_AGGREGATORS): it is asked for quotes like
+--              the live aggregators, but its quotes are only recorded here
+--              next to the best quote users were served. Summarized by
+--              GET /admin/providers/shadow-quotes; pruned by the retention
+--              job after RETENTION_ANALYTICS_DAYS.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS shadow_quotes (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    aggregator VARCHAR(50) NOT NULL,          -- Shadowed aggregator tag, e.g. changenow
+    from_currency VARCHAR(20) NOT NULL,
+    from_network VARCHAR(50) NOT NULL,
+    to_currency VARCHAR(20) NOT NULL,
+    to_network VARCHAR(50) NOT NULL,
+    amount DECIMAL(20, 8) NOT NULL,
+    succeeded BOOLEAN NOT NULL,
+    error VARCHAR(500) NULL,
+    quote_count INT NOT NULL DEFAULT 0,
+    best_provider VARCHAR(100) NULL,          -- Shadow aggregator's best quote
+    best_amount DECIMAL(30, 12) NULL,
+    served_provider VARCHAR(100) NULL,        -- Best quote users were served
+    served_amount DECIMAL(30, 12) NULL,
+    latency_ms INT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    INDEX idx_shadow_quotes_created_at (created_at),
+    INDEX idx_shadow_quotes_aggregator (aggregator, created_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
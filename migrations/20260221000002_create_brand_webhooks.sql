@@ -0,0 +1,43 @@
+-- ============================================================================
+-- Migration: Brand webhooks
+-- Created: 2026-02-21
+-- Description: Status changes of a partner brand's swaps are POSTed to the
+--              webhook_url it sets at /brand/webhook, signed with HMAC-SHA256.
+--              Signing secrets are stored AES-256-GCM encrypted under
+--              BRAND_WEBHOOKS_KEY. A rotation adds a new secret and gives the
+--              old one a retires_at; until then deliveries carry a signature
+--              for each, so receivers can switch keys without a hard cutover.
+--              brand_webhook_deliveries is the queue senders drain; a sender
+--              claims rows (claimed_by) before delivering them.
+-- ============================================================================
+
+ALTER TABLE brands
+    ADD COLUMN webhook_url VARCHAR(512) NULL AFTER api_key_hash;
+
+CREATE TABLE IF NOT EXISTS brand_webhook_secrets (
+    id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    brand VARCHAR(50) NOT NULL,               -- brands.slug
+    secret_encrypted VARCHAR(255) NOT NULL,   -- base64 nonce + ciphertext, brand slug as associated data
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    retires_at TIMESTAMP NULL,                -- set by a rotation; signs nothing after
+
+    INDEX idx_brand_webhook_secrets_brand (brand, id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS brand_webhook_deliveries (
+    id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    brand VARCHAR(50) NOT NULL,               -- brands.slug
+    event VARCHAR(50) NOT NULL,               -- e.g. swap.status_changed
+    payload TEXT NOT NULL,                    -- JSON body, signed when sent
+    status ENUM('pending', 'sending', 'sent', 'failed') NOT NULL DEFAULT 'pending',
+    claimed_by CHAR(36) NULL,                 -- drain holding a 'sending' row
+    claimed_at TIMESTAMP NULL,                -- stale claims are taken over
+    attempts INT NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP, -- backs off after failures
+    last_error VARCHAR(500) NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    sent_at TIMESTAMP NULL,
+
+    INDEX idx_brand_webhook_deliveries_due (status, next_attempt_at),
+    INDEX idx_brand_webhook_deliveries_claim (claimed_by)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,28 @@
+-- ============================================================================
+-- Migration: Reserved quotes
+-- Created: 2026-02-22
+-- Description: Fixed-rate quotes reserved through POST /swap/quote. A swap
+--              created with the quote_id uses the reserved trade and rate
+--              until expires_at; used_at marks the reservation as spent.
+--              Also cached in Redis for the reservation's lifetime.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS quotes (
+    id VARCHAR(36) PRIMARY KEY,
+    user_id VARCHAR(36) NULL,
+    trade_id VARCHAR(100) NOT NULL,           -- Trocador new_rate id the rate is held under
+    provider VARCHAR(100) NOT NULL,
+    from_currency VARCHAR(20) NOT NULL,
+    from_network VARCHAR(50) NOT NULL,
+    to_currency VARCHAR(20) NOT NULL,
+    to_network VARCHAR(50) NOT NULL,
+    amount DECIMAL(20, 8) NOT NULL,
+    rate DECIMAL(30, 12) NOT NULL,
+    estimated_amount DECIMAL(30, 12) NOT NULL, -- After platform fees
+    expires_at TIMESTAMP NOT NULL,
+    used_at TIMESTAMP NULL,
+    swap_id VARCHAR(36) NULL,                 -- Swap created from the reservation
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    INDEX idx_quotes_expires_at (expires_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
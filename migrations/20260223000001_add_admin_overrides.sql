@@ -0,0 +1,15 @@
+-- ============================================================================
+-- Migration: Admin overrides
+-- Created: 2026-02-23
+-- Description: Lets operations switch currencies off and pin provider
+--              metadata without the next sync undoing it. A currency with
+--              admin_disabled stays inactive when Trocador lists it again;
+--              provider overrides win over the synced kyc_rating/eta_minutes.
+-- ============================================================================
+
+ALTER TABLE currencies
+    ADD COLUMN admin_disabled BOOLEAN NOT NULL DEFAULT FALSE AFTER is_active;
+
+ALTER TABLE providers
+    ADD COLUMN kyc_rating_override ENUM('A', 'B', 'C', 'D') NULL AFTER kyc_rating,
+    ADD COLUMN eta_minutes_override INT NULL AFTER eta_minutes;
@@ -0,0 +1,20 @@
+-- ============================================================================
+-- Migration: Per-brand API usage
+-- Created: 2026-02-24
+-- Description: Hourly request counts for partner brands, by endpoint
+--              (method plus route template). Written by the api_usage_rollup
+--              job, which adds each instance's buffered counts to the row,
+--              and served to integrators by GET /account/analytics.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS api_usage_hourly (
+    brand VARCHAR(50) NOT NULL,
+    hour_start DATETIME NOT NULL,
+    endpoint VARCHAR(191) NOT NULL,
+    requests INT UNSIGNED NOT NULL DEFAULT 0,
+    client_errors INT UNSIGNED NOT NULL DEFAULT 0,
+    server_errors INT UNSIGNED NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (brand, hour_start, endpoint),
+    INDEX idx_api_usage_hour (hour_start)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,15 @@
+-- ============================================================================
+-- Migration: High-value swaps
+-- Created: 2026-02-25
+-- Description: Tag swaps worth more than HIGH_VALUE_SWAP_USD at creation so
+--              they are polled more often, listed in the admin high-value
+--              queue and alerted on when they stall. usd_value is the
+--              estimate used for the tag (NULL when neither side had a
+--              reference price); stall_alerted_at keeps one alert per stall.
+-- ============================================================================
+
+ALTER TABLE swaps
+    ADD COLUMN high_value BOOLEAN NOT NULL DEFAULT FALSE AFTER is_sandbox,
+    ADD COLUMN usd_value DOUBLE NULL AFTER high_value,
+    ADD COLUMN stall_alerted_at TIMESTAMP NULL AFTER last_polled_at,
+    ADD INDEX idx_swaps_high_value_status (high_value, status);
@@ -0,0 +1,30 @@
+-- ============================================================================
+-- Migration: Tenants
+-- Created: 2026-02-26
+-- Description: Isolate white-label partners sharing one deployment. Each
+--              brand belongs to a tenant, and users, swaps, fee rules and
+--              analytics events record the tenant they were created under;
+--              requests only see rows of their brand's tenant. Existing rows
+--              belong to the 'default' tenant. Emails are unique per tenant,
+--              so the same address can register with two partners.
+-- ============================================================================
+
+ALTER TABLE brands
+    ADD COLUMN tenant_id VARCHAR(50) NOT NULL DEFAULT 'default' AFTER slug;
+
+ALTER TABLE users
+    ADD COLUMN tenant_id VARCHAR(50) NOT NULL DEFAULT 'default' AFTER id,
+    DROP INDEX email,
+    ADD UNIQUE KEY uk_users_tenant_email (tenant_id, email);
+
+ALTER TABLE swaps
+    ADD COLUMN tenant_id VARCHAR(50) NOT NULL DEFAULT 'default' AFTER id,
+    ADD INDEX idx_swaps_tenant_user (tenant_id, user_id, created_at);
+
+ALTER TABLE fee_rules
+    ADD COLUMN tenant_id VARCHAR(50) NOT NULL DEFAULT 'default' AFTER id,
+    ADD INDEX idx_fee_rules_tenant (tenant_id);
+
+ALTER TABLE analytics_events
+    ADD COLUMN tenant_id VARCHAR(50) NOT NULL DEFAULT 'default' AFTER event,
+    ADD INDEX idx_analytics_tenant_event_time (tenant_id, event, occurred_at);
@@ -0,0 +1,26 @@
+-- ============================================================================
+-- Migration: Swap refunds
+-- Created: 2026-02-27
+-- Description: What the provider reported about a refunded swap, so support
+--              can see where the money went without the provider's
+--              dashboard. One row per swap, filled from status polls and
+--              webhooks; later reports complete earlier ones (the refund
+--              transaction hash usually arrives last). amount is what the
+--              provider reports as sent in, in the swap's from currency.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS swap_refunds (
+    swap_id VARCHAR(36) PRIMARY KEY,
+    refund_address VARCHAR(255) NULL,
+    refund_extra_id VARCHAR(255) NULL,
+    amount DOUBLE NULL,
+    currency VARCHAR(20) NOT NULL,
+    network VARCHAR(50) NOT NULL,
+    tx_hash VARCHAR(255) NULL,
+    source ENUM('poll', 'webhook') NOT NULL,
+    anonymized_at TIMESTAMP NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (swap_id) REFERENCES swaps(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,30 @@
+-- ============================================================================
+-- Migration: Notification preferences
+-- Created: 2026-02-28
+-- Description: Per-user channels (email, Telegram) and event toggles for the
+--              notification sender, which drains user_notifications. Users
+--              without a row get email for every event. Notifications now
+--              remember their swap and brand (for branded email) and count
+--              delivery attempts; 'skipped' marks ones nobody opted into.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    user_id VARCHAR(36) PRIMARY KEY,
+    email_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    telegram_enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    telegram_chat_id VARCHAR(64) NULL,
+    swap_completed BOOLEAN NOT NULL DEFAULT TRUE,
+    swap_failed BOOLEAN NOT NULL DEFAULT TRUE,
+    action_required BOOLEAN NOT NULL DEFAULT TRUE, -- Expired without a deposit, deposit lost
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+ALTER TABLE user_notifications
+    ADD COLUMN swap_id VARCHAR(36) NULL AFTER user_id,
+    ADD COLUMN brand VARCHAR(64) NULL AFTER swap_id,
+    ADD COLUMN attempts INT NOT NULL DEFAULT 0 AFTER status,
+    ADD COLUMN last_error VARCHAR(500) NULL AFTER attempts,
+    MODIFY COLUMN status ENUM('pending', 'sent', 'failed', 'skipped') NOT NULL DEFAULT 'pending';
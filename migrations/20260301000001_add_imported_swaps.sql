@@ -0,0 +1,12 @@
+-- ============================================================================
+-- Migration: Imported swaps
+-- Created: 2026-03-01
+-- Description: Swaps created outside the platform and added by their owner
+--              with POST /swap/import (provider name + trade id). They are
+--              watch-only: polled and shown in history like any other swap,
+--              but never expired by us, since the deposit window is the
+--              provider's.
+-- ============================================================================
+
+ALTER TABLE swaps
+    ADD COLUMN imported BOOLEAN NOT NULL DEFAULT FALSE AFTER is_sandbox;
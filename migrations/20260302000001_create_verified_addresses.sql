@@ -0,0 +1,23 @@
+-- ============================================================================
+-- Migration: Verified payout addresses
+-- Created: 2026-03-02
+-- Description: Addresses a user proved control of by signing a server-issued
+--              challenge (BIP-137 on Bitcoin, EIP-191 on EVM chains). Swaps
+--              paying out to one of them get the higher VERIFIED_SWAP_MAX_USD
+--              limit. Addresses are stored normalized (EVM and bech32 in
+--              lowercase) and per chain, so one EVM address covers every
+--              EVM network.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS verified_addresses (
+    id VARCHAR(36) PRIMARY KEY,
+    user_id VARCHAR(36) NOT NULL,
+    chain VARCHAR(16) NOT NULL, -- Chain::as_str(): 'bitcoin', 'evm'
+    address VARCHAR(128) NOT NULL,
+    message TEXT NOT NULL,      -- The signed challenge
+    signature VARCHAR(255) NOT NULL,
+    verified_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE KEY uq_verified_address (user_id, chain, address),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,11 @@
+-- ============================================================================
+-- Migration: Sandbox provider
+-- Created: 2026-03-03
+-- Description: Provider row for sandbox swaps, which are opened and
+--              progressed by the mock provider instead of Trocador. Inactive,
+--              so it is never listed; swaps still need it for their
+--              provider_id foreign key.
+-- ============================================================================
+
+INSERT IGNORE INTO providers (id, name, slug, is_active, kyc_required, rating)
+VALUES ('sandbox', 'Sandbox', 'sandbox', FALSE, FALSE, 0);
@@ -0,0 +1,17 @@
+-- ============================================================================
+-- Migration: Reconciliation reports
+-- Created: 2026-03-04
+-- Description: One end-of-day reconciliation report per UTC day: swap counts,
+--              platform fee earned against the status ledger, provider trade
+--              counts against stored swaps, and the discrepancies found.
+--              Written by the nightly reconciliation worker (a rerun
+--              replaces the day's report) and served by
+--              GET /admin/reconciliation.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS reconciliation_reports (
+    day DATE PRIMARY KEY,                     -- UTC
+    discrepancies INT UNSIGNED NOT NULL DEFAULT 0,
+    report JSON NOT NULL,
+    generated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,10 @@
+-- ============================================================================
+-- Migration: Swap provider selection
+-- Created: 2026-03-05
+-- Description: How the provider of a swap created without one was picked:
+--              the selection policy and the winning quote, as JSON. NULL
+--              when the client named the provider.
+-- ============================================================================
+
+ALTER TABLE swaps
+ADD COLUMN provider_selection TEXT NULL AFTER fallback_chain;
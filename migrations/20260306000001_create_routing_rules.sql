@@ -0,0 +1,26 @@
+-- ============================================================================
+-- Migration: Provider routing rules
+-- Created: 2026-03-06
+-- Description: Admin-managed routing, served by /admin/routing-rules. A rule
+--              is narrowed to pairs touching one of its currencies, swaps
+--              worth at least min_usd and callers from one of its countries
+--              (NULL matches anything). block and prefer act on provider;
+--              prefer_fixed and prefer_floating pick the rate type when the
+--              caller left it open. Cached in Redis (routing_rules:all).
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS routing_rules (
+    id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    tenant_id VARCHAR(50) NOT NULL DEFAULT 'default',
+    name VARCHAR(100) NOT NULL,
+    currencies VARCHAR(255) NULL,              -- comma-separated tickers
+    min_usd DECIMAL(20, 2) NULL,
+    countries VARCHAR(255) NULL,               -- comma-separated ISO codes or EU
+    provider VARCHAR(50) NULL,
+    action VARCHAR(20) NOT NULL,               -- block, prefer, prefer_fixed, prefer_floating
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    note VARCHAR(255) NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+    INDEX idx_routing_rules_tenant (tenant_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
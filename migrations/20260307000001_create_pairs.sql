@@ -0,0 +1,22 @@
+-- ============================================================================
+-- Migration: Pairs
+-- Created: 2026-03-07
+-- Description: Pairs each provider quoted, with the amount limits it gave,
+--              recorded from every live rates response. Served by
+--              GET /swap/pairs; rows not quoted for a week are left out.
+--              max_amount is NULL when the provider quoted no maximum.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS pairs (
+    from_currency VARCHAR(20) NOT NULL,
+    from_network VARCHAR(50) NOT NULL,
+    to_currency VARCHAR(20) NOT NULL,
+    to_network VARCHAR(50) NOT NULL,
+    provider VARCHAR(50) NOT NULL,
+    min_amount DECIMAL(30, 8) NOT NULL DEFAULT 0,
+    max_amount DECIMAL(30, 8) NULL,
+    last_quoted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (from_currency, from_network, to_currency, to_network, provider),
+    INDEX idx_pairs_last_quoted (last_quoted_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
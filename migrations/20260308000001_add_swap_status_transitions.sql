@@ -0,0 +1,13 @@
+-- ============================================================================
+-- Migration: Swap status transitions
+-- Created: 2026-03-08
+-- Description: Each swap_status_history row records a transition accepted
+--              by the swap state machine: the status it replaced (NULL for
+--              the first status of a new swap) and where the new one came
+--              from (create, import, poll, webhook or expiry). Rows written
+--              before this migration have neither.
+-- ============================================================================
+
+ALTER TABLE swap_status_history
+ADD COLUMN from_status ENUM('waiting', 'confirming', 'exchanging', 'sending', 'completed', 'failed', 'refunded', 'expired') NULL AFTER swap_id,
+ADD COLUMN source VARCHAR(16) NULL AFTER status;
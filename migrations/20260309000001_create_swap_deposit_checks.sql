@@ -0,0 +1,20 @@
+-- ============================================================================
+-- Migration: Swap deposit checks
+-- Created: 2026-03-09
+-- Description: What a public block explorer showed for a swap's deposit
+--              address, looked up when the provider reports the swap
+--              underpaid or when support asks for it. One row per swap,
+--              replaced by each new check. evidence holds the amounts
+--              received, unspent outputs and shortfall as JSON; shortfall
+--              (in the swap's from currency) is kept alongside for queries.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS swap_deposit_checks (
+    swap_id VARCHAR(36) PRIMARY KEY,
+    explorer VARCHAR(50) NOT NULL,
+    shortfall DOUBLE NOT NULL,
+    evidence JSON NOT NULL,
+    checked_at TIMESTAMP NOT NULL,
+
+    FOREIGN KEY (swap_id) REFERENCES swaps(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,30 @@
+-- ============================================================================
+-- Migration: Audit log
+-- Created: 2026-03-10
+-- Description: One row per mutating request (POST, PUT, PATCH, DELETE):
+--              who made it, from which IP, the method and route, the
+--              concrete path, the response status and a SHA-256 of the body.
+--              Rows are never changed or removed; the triggers below refuse
+--              updates and deletes.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    actor VARCHAR(64) NOT NULL,
+    ip VARCHAR(45),
+    action VARCHAR(255) NOT NULL,
+    path VARCHAR(512) NOT NULL,
+    status SMALLINT UNSIGNED NOT NULL,
+    payload_hash CHAR(64),
+    created_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    INDEX idx_audit_actor (actor, id),
+    INDEX idx_audit_action (action, id),
+    INDEX idx_audit_created (created_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TRIGGER audit_log_no_update BEFORE UPDATE ON audit_log
+FOR EACH ROW SIGNAL SQLSTATE '45000' SET MESSAGE_TEXT = 'audit_log is append-only';
+
+CREATE TRIGGER audit_log_no_delete BEFORE DELETE ON audit_log
+FOR EACH ROW SIGNAL SQLSTATE '45000' SET MESSAGE_TEXT = 'audit_log is append-only';
@@ -0,0 +1,20 @@
+-- ============================================================================
+-- Migration: Provider credentials
+-- Created: 2026-03-11
+-- Description: Provider API keys managed from the admin API, one row per
+--              provider, so a key can be rotated without a redeploy.
+--              api_key_encrypted is base64 of an AES-256-GCM nonce and
+--              ciphertext under PROVIDER_CREDENTIALS_KEY; key_hint keeps the
+--              last characters for display. base_url overrides the provider's
+--              API root when set.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS provider_credentials (
+    provider VARCHAR(50) PRIMARY KEY,
+    api_key_encrypted TEXT NOT NULL,
+    key_hint VARCHAR(16) NOT NULL,
+    base_url VARCHAR(255),
+    rotated_by VARCHAR(36),
+    rotated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
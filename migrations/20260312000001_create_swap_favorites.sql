@@ -0,0 +1,43 @@
+-- ============================================================================
+-- Migration: Swap favourites
+-- Created: 2026-03-12
+-- Description: What the swap form is prefilled from. favorite_pairs holds the
+--              pairs a user starred; saved_addresses the recipient addresses
+--              they saved (optionally labelled) or swapped to while signed in.
+--              Removing either sets deleted_at instead of dropping the row,
+--              so later swaps to a removed address do not bring it back.
+--              extra_id is '' rather than NULL so it can be part of the
+--              unique key.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS favorite_pairs (
+    id VARCHAR(36) PRIMARY KEY,
+    user_id VARCHAR(36) NOT NULL,
+    from_currency VARCHAR(20) NOT NULL,
+    from_network VARCHAR(50) NOT NULL,
+    to_currency VARCHAR(20) NOT NULL,
+    to_network VARCHAR(50) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    deleted_at TIMESTAMP NULL,
+
+    UNIQUE KEY uq_favorite_pair (user_id, from_currency, from_network, to_currency, to_network),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS saved_addresses (
+    id VARCHAR(36) PRIMARY KEY,
+    user_id VARCHAR(36) NOT NULL,
+    currency VARCHAR(20) NOT NULL,
+    network VARCHAR(50) NOT NULL,
+    address VARCHAR(255) NOT NULL,
+    extra_id VARCHAR(128) NOT NULL DEFAULT '',
+    label VARCHAR(64),
+    times_used INT UNSIGNED NOT NULL DEFAULT 0, -- Swaps created to it
+    last_used_at TIMESTAMP NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    deleted_at TIMESTAMP NULL,
+
+    UNIQUE KEY uq_saved_address (user_id, currency, network, address, extra_id),
+    INDEX idx_saved_addresses_deleted (deleted_at),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
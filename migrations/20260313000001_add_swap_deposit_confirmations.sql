@@ -0,0 +1,11 @@
+-- ============================================================================
+-- Migration: Swap deposit confirmations
+-- Created: 2026-03-13
+-- Description: Confirmations of the deposit transaction as last reported by
+--              the provider, next to the deposit (tx_hash_in) and payout
+--              (tx_hash_out) hashes, which status polls and webhooks now
+--              fill in. NULL until the provider reports a count.
+-- ============================================================================
+
+ALTER TABLE swaps
+ADD COLUMN deposit_confirmations INT UNSIGNED NULL AFTER tx_hash_out;
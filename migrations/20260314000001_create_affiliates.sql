@@ -0,0 +1,44 @@
+-- ============================================================================
+-- Migration: Affiliates
+-- Created: 2026-03-14
+-- Description: Referral partners managed through /admin/affiliates. Swaps
+--              created with ?ref={ref_code} record the affiliate and its
+--              commission: commission_percent of the swap's platform fee, in
+--              the receive currency. Partners read their figures from
+--              /affiliate/stats and /affiliate/payouts with their X-API-Key
+--              (stored as a SHA-256 hash). affiliate_payouts records what was
+--              paid out to them; nothing is sent from here.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS affiliates (
+    id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    ref_code VARCHAR(32) NOT NULL,            -- lowercase letters, digits or '-'
+    tenant_id VARCHAR(50) NOT NULL DEFAULT 'default',
+    name VARCHAR(100) NOT NULL,
+    api_key_hash CHAR(64) NULL,               -- hex SHA-256 of the partner's API key
+    commission_percent DECIMAL(5, 2) NOT NULL, -- share of the platform fee
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,  -- inactive: new swaps are not attributed
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    UNIQUE KEY uk_affiliates_ref_code (ref_code),
+    UNIQUE KEY uk_affiliates_api_key_hash (api_key_hash)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+ALTER TABLE swaps
+    ADD COLUMN affiliate VARCHAR(32) NULL AFTER brand,
+    ADD COLUMN affiliate_commission DECIMAL(20, 8) NULL AFTER platform_fee,
+    ADD INDEX idx_swaps_affiliate (affiliate, created_at);
+
+CREATE TABLE IF NOT EXISTS affiliate_payouts (
+    id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    affiliate VARCHAR(32) NOT NULL,           -- affiliates.ref_code
+    currency VARCHAR(20) NOT NULL,
+    amount DECIMAL(20, 8) NOT NULL,
+    tx_hash VARCHAR(128) NULL,
+    note VARCHAR(255) NULL,
+    created_by VARCHAR(36) NOT NULL,          -- admin who recorded it
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    INDEX idx_affiliate_payouts_affiliate (affiliate, created_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,25 @@
+-- ============================================================================
+-- Migration: Widget keys
+-- Created: 2026-03-15
+-- Description: Public keys for the embeddable swap widget, managed through
+--              /admin/widget-keys. A key is sent as ?key= to /widget/* and
+--              only works from its allowed_origins, at rate_limit_per_minute
+--              per visitor. Keys are not secret; they are shown in the
+--              partner's page source. Swaps are created under the key's brand
+--              and attributed to its affiliate.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS widget_keys (
+    id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    public_key VARCHAR(40) NOT NULL,          -- wk_ followed by 32 hex characters
+    name VARCHAR(100) NOT NULL,
+    brand VARCHAR(50) NULL,                   -- brands.slug; NULL serves the deployment's own brand
+    affiliate VARCHAR(32) NULL,               -- affiliates.ref_code credited with the swaps
+    allowed_origins JSON NOT NULL,            -- e.g. ["https://partner.example", "https://*.partner.example"]
+    rate_limit_per_minute INT UNSIGNED NOT NULL DEFAULT 20,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    UNIQUE KEY uk_widget_keys_public_key (public_key)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
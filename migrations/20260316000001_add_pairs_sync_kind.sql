@@ -0,0 +1,9 @@
+-- ============================================================================
+-- Migration: Pairs sync runs
+-- Created: 2026-03-16
+-- Description: The sync worker also prunes and re-caches the pairs listing;
+--              record those runs alongside currency and provider syncs
+-- ============================================================================
+
+ALTER TABLE sync_runs
+    MODIFY kind ENUM('currencies', 'providers', 'pairs') NOT NULL;
@@ -0,0 +1,34 @@
+-- ============================================================================
+-- Migration: Multi-leg swap routes
+-- Created: 2026-03-17
+-- Description: Swaps through an intermediate currency, created with
+--              POST /swap/routes for pairs no provider quotes directly. The
+--              second leg is opened first; the first leg pays out to its
+--              deposit address. status rolls up both legs' statuses and is
+--              refreshed whenever the route is read.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS swap_routes (
+    id VARCHAR(36) PRIMARY KEY,
+    tenant_id VARCHAR(50) NOT NULL DEFAULT 'default',
+    user_id VARCHAR(36) NULL,
+    from_currency VARCHAR(20) NOT NULL,
+    from_network VARCHAR(50) NOT NULL,
+    via_currency VARCHAR(20) NOT NULL,
+    via_network VARCHAR(50) NOT NULL,
+    to_currency VARCHAR(20) NOT NULL,
+    to_network VARCHAR(50) NOT NULL,
+    amount DECIMAL(20, 8) NOT NULL,
+    estimated_receive DECIMAL(20, 8) NOT NULL,  -- Second leg's estimate when created
+    first_swap_id VARCHAR(36) NOT NULL,         -- from -> via, paid out to the second leg
+    second_swap_id VARCHAR(36) NOT NULL,        -- via -> to, paid out to the recipient
+    status VARCHAR(20) NOT NULL DEFAULT 'waiting', -- waiting, processing, completed, failed, refunded, expired
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    INDEX idx_swap_routes_user (user_id, created_at),
+    INDEX idx_swap_routes_first_swap (first_swap_id),
+    INDEX idx_swap_routes_second_swap (second_swap_id),
+    FOREIGN KEY (first_swap_id) REFERENCES swaps(id) ON DELETE CASCADE,
+    FOREIGN KEY (second_swap_id) REFERENCES swaps(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
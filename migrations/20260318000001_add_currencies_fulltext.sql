@@ -0,0 +1,10 @@
+-- ============================================================================
+-- Migration: Currency search
+-- Created: 2026-03-18
+-- Description: Full-text index over symbol and name for
+--              GET /swap/currencies?search=. Words shorter than
+--              innodb_ft_min_token_size are not indexed, so the query also
+--              matches tickers by prefix.
+-- ============================================================================
+
+ALTER TABLE currencies ADD FULLTEXT INDEX ft_currencies_symbol_name (symbol, name);
@@ -0,0 +1,12 @@
+-- ============================================================================
+-- Migration: Per-user KYC preference
+-- Created: 2026-03-19
+-- Description: The worst provider KYC rating (A best to D worst) a user
+--              accepts, set at /auth/swap-preferences. Quotes from providers
+--              rated worse are left out of the user's rates and provider
+--              selection, and swaps naming such a provider are refused.
+--              NULL accepts any rating.
+-- ============================================================================
+
+ALTER TABLE users
+ADD COLUMN max_kyc_rating CHAR(1) NULL AFTER fee_tier;
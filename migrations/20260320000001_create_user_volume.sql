@@ -0,0 +1,21 @@
+-- ============================================================================
+-- Migration: Per-user swap volume
+-- Created: 2026-03-20
+-- Description: USD volume each user created and completed, per hour, for the
+--              rolling 24-hour and 30-day caps (USER_VOLUME_24H_USD,
+--              USER_VOLUME_30D_USD) checked by POST /swap/create and shown at
+--              GET /swap/limits. Rows are added to on swap creation and
+--              completion; rows older than 30 days are no longer read.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS user_volume (
+    user_id VARCHAR(36) NOT NULL,
+    hour_start DATETIME NOT NULL,                         -- Truncated to the hour
+    created_usd DECIMAL(20, 2) NOT NULL DEFAULT 0,
+    completed_usd DECIMAL(20, 2) NOT NULL DEFAULT 0,
+    swaps_created INT UNSIGNED NOT NULL DEFAULT 0,
+    swaps_completed INT UNSIGNED NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (user_id, hour_start),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
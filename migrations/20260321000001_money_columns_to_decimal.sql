@@ -0,0 +1,19 @@
+-- ============================================================================
+-- Migration: Amounts as DECIMAL
+-- Created: 2026-03-21
+-- Description: The last amount columns still stored as DOUBLE become DECIMAL,
+--              so amounts read back exactly as written. Amounts, rates and
+--              fees are rust_decimal::Decimal in the code and decimal strings
+--              in the API; currency limits keep the aggregator's places,
+--              swap-side amounts the 8 places of the swaps table.
+-- ============================================================================
+
+ALTER TABLE currencies
+    MODIFY COLUMN min_amount DECIMAL(30, 12) DEFAULT NULL,
+    MODIFY COLUMN max_amount DECIMAL(30, 12) DEFAULT NULL;
+
+ALTER TABLE swap_refunds
+    MODIFY COLUMN amount DECIMAL(20, 8) NULL;
+
+ALTER TABLE swap_deposit_checks
+    MODIFY COLUMN shortfall DECIMAL(20, 8) NOT NULL;
@@ -0,0 +1,29 @@
+-- ============================================================================
+-- Migration: Provider swap outcomes
+-- Created: 2026-03-22
+-- Description: How our own swaps ended, per provider, UTC day and outcome:
+--              completed, failed, refunded, or rejected when the provider
+--              refused to create the trade. Swaps that expire without a
+--              deposit say nothing about the provider and are not counted.
+--              GET /swap/rates scores each provider over the last
+--              PROVIDER_HEALTH_WINDOW_DAYS and demotes or hides the quotes
+--              of failing ones, unless the provider's health_override says
+--              otherwise. Served by GET /admin/providers/health; rows older
+--              than the window are no longer read.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS provider_stats (
+    provider VARCHAR(100) NOT NULL,                       -- swaps.provider_id, as quoted
+    day DATE NOT NULL,                                    -- UTC
+    outcome VARCHAR(20) NOT NULL,                         -- completed, failed, refunded, rejected
+    swaps INT UNSIGNED NOT NULL DEFAULT 0,
+    completion_secs BIGINT UNSIGNED NOT NULL DEFAULT 0,   -- Creation to completion, summed over completed swaps
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (provider, day, outcome),
+    INDEX idx_provider_stats_day (day)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- Admin pin over the score; NULL follows it
+ALTER TABLE providers
+    ADD COLUMN health_override ENUM('trusted', 'demoted', 'hidden') NULL AFTER eta_minutes_override;
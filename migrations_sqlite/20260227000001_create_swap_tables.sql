@@ -0,0 +1,108 @@
+-- =============================================================================
+-- SQLITE SCHEMA (lightweight mode, feature "sqlite")
+-- Only what SqliteSwapRepository stores; columns match the swap models
+-- =============================================================================
+
+-- =============================================================================
+-- PROVIDERS TABLE
+-- =============================================================================
+
+CREATE TABLE IF NOT EXISTS providers (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    slug TEXT NOT NULL UNIQUE,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    kyc_rating TEXT NOT NULL DEFAULT 'C',
+    insurance_percentage REAL,
+    eta_minutes INTEGER,
+    markup_enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    api_url TEXT,
+    logo_url TEXT,
+    website_url TEXT,
+    last_synced_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_providers_is_active ON providers (is_active);
+
+-- =============================================================================
+-- CURRENCIES TABLE
+-- =============================================================================
+
+CREATE TABLE IF NOT EXISTS currencies (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    symbol TEXT NOT NULL COLLATE NOCASE,
+    name TEXT NOT NULL,
+    network TEXT NOT NULL COLLATE NOCASE,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    delisting_at TEXT,
+    logo_url TEXT,
+    contract_address TEXT,
+    decimals INTEGER NOT NULL DEFAULT 8,
+    requires_extra_id BOOLEAN NOT NULL DEFAULT FALSE,
+    extra_id_name TEXT,
+    requires_refund_address BOOLEAN NOT NULL DEFAULT FALSE,
+    min_amount REAL,
+    max_amount REAL,
+    last_synced_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+
+    UNIQUE (symbol, network)
+);
+
+CREATE INDEX IF NOT EXISTS idx_currencies_is_active ON currencies (is_active);
+
+-- =============================================================================
+-- SWAPS TABLE
+-- =============================================================================
+
+CREATE TABLE IF NOT EXISTS swaps (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL DEFAULT 'default',
+    user_id TEXT,
+    brand TEXT,
+    provider_id TEXT NOT NULL,
+    provider_swap_id TEXT,
+    retried_from TEXT,
+
+    from_currency TEXT NOT NULL,
+    from_network TEXT NOT NULL,
+    to_currency TEXT NOT NULL,
+    to_network TEXT NOT NULL,
+
+    amount REAL NOT NULL,
+    estimated_receive REAL NOT NULL,
+    actual_receive REAL,
+    rate REAL NOT NULL,
+
+    network_fee REAL NOT NULL DEFAULT 0,
+    provider_fee REAL NOT NULL DEFAULT 0,
+    platform_fee REAL NOT NULL DEFAULT 0,
+    total_fee REAL NOT NULL DEFAULT 0,
+
+    deposit_address TEXT NOT NULL,
+    deposit_extra_id TEXT,
+    recipient_address TEXT NOT NULL,
+    recipient_extra_id TEXT,
+    refund_address TEXT,
+    refund_extra_id TEXT,
+
+    tx_hash_in TEXT,
+    tx_hash_out TEXT,
+
+    status TEXT NOT NULL DEFAULT 'waiting',
+    rate_type TEXT NOT NULL DEFAULT 'floating',
+    is_sandbox BOOLEAN NOT NULL DEFAULT FALSE,
+    error TEXT,
+    version INTEGER NOT NULL DEFAULT 0,
+
+    expires_at TEXT,
+    completed_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_swaps_status ON swaps (status);
+CREATE INDEX IF NOT EXISTS idx_swaps_provider_swap_id ON swaps (provider_swap_id);
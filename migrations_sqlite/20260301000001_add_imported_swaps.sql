@@ -0,0 +1,8 @@
+-- ============================================================================
+-- Migration: Imported swaps
+-- Created: 2026-03-01
+-- Description: Mirrors the MySQL migration so the shared Swap row decodes;
+--              the lightweight server does not import swaps itself.
+-- ============================================================================
+
+ALTER TABLE swaps ADD COLUMN imported BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,7 @@
+-- ============================================================================
+-- Migration: Swap deposit confirmations
+-- Created: 2026-03-13
+-- Description: Mirrors the MySQL migration so the shared Swap row decodes.
+-- ============================================================================
+
+ALTER TABLE swaps ADD COLUMN deposit_confirmations INTEGER;
@@ -0,0 +1,45 @@
+-- ============================================================================
+-- Migration: Later swap, currency and provider columns
+-- Created: 2026-03-23
+-- Description: Mirrors the MySQL migrations from retried_from onwards so the
+--              SQLite schema carries the same columns, and adds fee_rules,
+--              which the lightweight server applies to rates and swaps.
+--              There is no admin API for fee rules here; insert rows
+--              directly (fee_percent is a percentage, flat_fee is in
+--              to_currency).
+-- ============================================================================
+
+ALTER TABLE swaps ADD COLUMN retry_claimed_at TEXT;
+ALTER TABLE swaps ADD COLUMN fallback_chain TEXT;
+ALTER TABLE swaps ADD COLUMN provider_selection TEXT;
+ALTER TABLE swaps ADD COLUMN affiliate TEXT;
+ALTER TABLE swaps ADD COLUMN affiliate_commission REAL;
+ALTER TABLE swaps ADD COLUMN high_value BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE swaps ADD COLUMN usd_value REAL;
+ALTER TABLE swaps ADD COLUMN last_polled_at TEXT;
+ALTER TABLE swaps ADD COLUMN stall_alerted_at TEXT;
+ALTER TABLE swaps ADD COLUMN anonymized_at TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_swaps_retried_from ON swaps (retried_from);
+
+ALTER TABLE currencies ADD COLUMN admin_disabled BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE providers ADD COLUMN kyc_rating_override TEXT;
+ALTER TABLE providers ADD COLUMN eta_minutes_override INTEGER;
+ALTER TABLE providers ADD COLUMN health_override TEXT;
+
+CREATE TABLE IF NOT EXISTS fee_rules (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tenant_id TEXT NOT NULL DEFAULT 'default',
+    from_currency TEXT,
+    to_currency TEXT,
+    provider TEXT,
+    user_tier TEXT,
+    fee_percent REAL NOT NULL DEFAULT 0,
+    flat_fee REAL NOT NULL DEFAULT 0,
+    note TEXT,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_fee_rules_tenant ON fee_rules (tenant_id);
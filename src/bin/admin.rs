@@ -0,0 +1,160 @@
+//! Operator CLI for tasks that otherwise need raw SQL or redis-cli.
+//!
+//! Reads the same environment as the server (`DATABASE_URL`, `REDIS_URL`, ...).
+//!
+//!     cargo run --bin admin -- sync all
+//!     cargo run --bin admin -- swap refresh <swap_id>
+//!     cargo run --bin admin -- rate-limit show api_calls:trocador:rates
+
+use clap::{Parser, Subcommand, ValueEnum};
+use exchange_shared::config::{environment::Config, init_db};
+use exchange_shared::modules::swap::crud::SwapCrud;
+use exchange_shared::modules::swap::schema::{SyncKind, SyncRunStatus};
+use exchange_shared::modules::swap::sync_worker::run_once;
+use exchange_shared::services::outbox::Outbox;
+use exchange_shared::services::redis_cache::{CacheBackend, RedisService};
+use exchange_shared::services::trocador::TrocadorClient;
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(name = "admin", about = "Exchange platform admin tasks")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a currency/provider/pairs sync now (recorded in sync_runs)
+    Sync {
+        #[arg(value_enum, default_value = "all")]
+        target: SyncTarget,
+    },
+    /// Swap maintenance
+    Swap {
+        #[command(subcommand)]
+        command: SwapCommand,
+    },
+    /// Inspect or clear rate-limit counters in Redis
+    RateLimit {
+        #[command(subcommand)]
+        command: RateLimitCommand,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SyncTarget {
+    Currencies,
+    Providers,
+    Pairs,
+    All,
+}
+
+#[derive(Subcommand)]
+enum SwapCommand {
+    /// Re-poll the provider for a swap's status and persist any change
+    Refresh { swap_id: String },
+}
+
+#[derive(Subcommand)]
+enum RateLimitCommand {
+    /// Print the stored value and TTL of a rate-limit key
+    Show { key: String },
+    /// Delete a rate-limit key, lifting the limit immediately
+    Reset { key: String },
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let config = match Config::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Configuration error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let redis = RedisService::new(&config.redis_url);
+
+    let result = match cli.command {
+        Command::Sync { target } => sync(&config, redis, target).await,
+        Command::Swap { command: SwapCommand::Refresh { swap_id } } => refresh_swap(&config, redis, &swap_id).await,
+        Command::RateLimit { command } => rate_limit(&redis, command).await,
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn sync(config: &Config, redis: RedisService, target: SyncTarget) -> Result<(), String> {
+    let db = init_db().await;
+    let crud = SwapCrud::new(db.clone(), Some(redis.clone())).with_outbox(Outbox::from_config(&config.event_bus, db));
+    let client = TrocadorClient::new(config.trocador_api_key().to_string());
+
+    let kinds: &[SyncKind] = match target {
+        SyncTarget::Currencies => &[SyncKind::Currencies],
+        SyncTarget::Providers => &[SyncKind::Providers],
+        SyncTarget::Pairs => &[SyncKind::Pairs],
+        SyncTarget::All => &[SyncKind::Currencies, SyncKind::Providers, SyncKind::Pairs],
+    };
+
+    let mut failed = false;
+    for &kind in kinds {
+        match run_once(&crud, &client, &redis, &config.sync_worker, kind).await {
+            Some(status) => {
+                println!("{:?}: {:?}", kind, status);
+                failed |= status != SyncRunStatus::Success;
+            }
+            None => println!("{:?}: skipped (another sync holds the lock)", kind),
+        }
+    }
+
+    if failed {
+        Err("one or more syncs did not succeed, see sync_runs for details".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+async fn refresh_swap(config: &Config, redis: RedisService, swap_id: &str) -> Result<(), String> {
+    let db = init_db().await;
+    let crud = SwapCrud::new(db.clone(), Some(redis))
+        .with_outbox(Outbox::from_config(&config.event_bus, db))
+        .with_trocador(Some(TrocadorClient::new(config.trocador_api_key().to_string())));
+
+    let swap = crud.get_swap_status(swap_id).await.map_err(|e| e.to_string())?;
+
+    println!("{} {:?} (updated {})", swap.swap_id, swap.status, swap.updated_at);
+    Ok(())
+}
+
+async fn rate_limit(redis: &RedisService, command: RateLimitCommand) -> Result<(), String> {
+    // The degraded fallback would only show this process's empty store
+    if !redis.is_in_memory() && redis.check_health().await != CacheBackend::Redis {
+        return Err("Redis is unreachable".to_string());
+    }
+
+    match command {
+        RateLimitCommand::Show { key } => {
+            let value = redis.get_string(&key).await.map_err(|e| e.to_string())?;
+            let ttl = redis.ttl(&key).await.map_err(|e| e.to_string())?;
+
+            match value {
+                Some(value) => println!("{} = {} (ttl {}s)", key, value, ttl),
+                None => println!("{} is not set", key),
+            }
+        }
+        RateLimitCommand::Reset { key } => {
+            redis.delete(&key).await.map_err(|e| e.to_string())?;
+            println!("{} cleared", key);
+        }
+    }
+    Ok(())
+}
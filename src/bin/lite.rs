@@ -0,0 +1,60 @@
+//! Single-binary swap server for self-hosting, without MySQL or Redis.
+//!
+//! Stores everything in one SQLite file and caches in memory unless
+//! `REDIS_URL` points at a Redis server. Serves the core swap flow; see
+//! `modules::swap::lite` for what is left to the full server.
+//!
+//!     TROCADOR_API_KEY=... cargo run --features sqlite --bin exchange-lite
+
+use exchange_shared::config::environment::{AddressVerificationConfig, HighValueConfig, LiteConfig};
+use exchange_shared::modules::swap::lite::{lite_app, spawn_listing_sync, LiteState};
+use exchange_shared::modules::swap::repository::SqliteSwapRepository;
+use exchange_shared::services::redis_cache::RedisService;
+use exchange_shared::services::tenant::TenantId;
+use exchange_shared::services::trocador::TrocadorClient;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "exchange_shared=info,exchange_lite=info,tower_http=info".into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let config = LiteConfig::from_env().expect("Failed to load environment configuration");
+
+    let repo = SqliteSwapRepository::connect(&config.database_url)
+        .await
+        .expect("Failed to open the SQLite database");
+    tracing::info!("Using SQLite database {}", config.database_url);
+
+    let redis = RedisService::new(&config.redis_url);
+    if redis.is_in_memory() {
+        tracing::info!("Using the in-memory cache");
+    }
+
+    let trocador = TrocadorClient::from_env()
+        .unwrap_or_else(|| TrocadorClient::new(config.trocador_api_key.clone()));
+
+    let state = Arc::new(LiteState {
+        repo: Arc::new(repo),
+        redis,
+        trocador,
+        tenant: TenantId::from_env(),
+        address_verification: AddressVerificationConfig::from_env(),
+        high_value: HighValueConfig::from_env(),
+    });
+    spawn_listing_sync(state.clone(), config.sync_interval);
+
+    let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
+    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    tracing::info!("Lightweight server running on http://localhost:{}", config.port);
+    axum::serve(listener, lite_app(state).into_make_service_with_connect_info::<SocketAddr>())
+        .await
+        .unwrap();
+}
@@ -0,0 +1,490 @@
+//! Typed HTTP client for the exchange API.
+//!
+//! Enabled with the `client` feature. Request and response types are the same
+//! schema structs the server uses, so the two cannot drift apart.
+
+use reqwest::{Method, RequestBuilder, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::modules::account::schema::{AccountAnalyticsResponse, AnalyticsQuery};
+use crate::modules::admin::schema::{
+    CacheStatsResponse, CurrencyPolicyResponse, DelistingResponse, HighValueSwapResponse, ProviderAdminResponse, ScheduleDelistingRequest,
+    ShadowQuotesQuery, UpdateCurrencyPolicyRequest, UpdateFeeTierRequest, UpdateMaintenanceRequest,
+    UpdateProviderRequest, UpsertAddressFormatRequest, UpsertBrandRequest, UserFeeTierResponse,
+};
+use crate::modules::auth::schema::{LoginRequest, LoginResponse, RegisterRequest, RegisterResponse, SwapPreferences};
+use crate::modules::onramp::schema::{
+    CreateOnrampOrderRequest, OnrampOrderResponse, OnrampQuoteQuery, OnrampQuoteResponse,
+};
+use crate::modules::swap::schema::{
+    BatchSwapStatusRequest, BatchSwapStatusResponse, CreateSwapRequest, CreateSwapResponse, CurrenciesPage,
+    CurrenciesQuery, CurrencyResponse, DepthQuery, DepthResponse, GroupedCurrencyResponse, HistoryQuery, ProviderResponse,
+    ProviderUptimeResponse, ProvidersQuery, QuoteRequest, QuoteReservation,
+    RatesQuery, RatesResponse, RefundAddressQuery, RefundAddressSuggestionsResponse, RetrySwapRequest,
+    ShadowQuoteReport, SwapDraft, SwapDraftResponse, SwapHistoryResponse, SwapLimitsResponse, SwapPreviewResponse, SwapStatusResponse, SyncStatusResponse, ValidateAddressRequest, ValidateAddressResponse,
+};
+use crate::services::address_format::AddressFormat;
+use crate::services::branding::{Brand, PublicBrand};
+use crate::services::fees::{FeeRule, FeeRuleInput};
+use crate::services::jobs::JobStatus;
+use crate::services::maintenance::MaintenanceState;
+use crate::services::provider_health::ProviderHealth;
+use crate::services::retention::RetentionReport;
+use crate::services::schema_drift::SchemaDriftSnapshot;
+
+// =============================================================================
+// CLIENT ERROR
+// =============================================================================
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// Transport failure or undecodable response body
+    HttpError(String),
+    /// The server answered with a non-success status
+    ApiError {
+        status: StatusCode,
+        error: String,
+        code: Option<String>,
+    },
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::HttpError(e) => write!(f, "HTTP error: {}", e),
+            ClientError::ApiError { status, error, code: Some(code) } => {
+                write!(f, "API error ({}, {}): {}", status, code, error)
+            }
+            ClientError::ApiError { status, error, code: None } => {
+                write!(f, "API error ({}): {}", status, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> Self {
+        ClientError::HttpError(err.to_string())
+    }
+}
+
+/// Common shape of the auth, swap and admin error bodies
+#[derive(Debug, Deserialize)]
+struct ApiErrorBody {
+    error: String,
+    #[serde(default)]
+    code: Option<String>,
+}
+
+// =============================================================================
+// EXCHANGE CLIENT
+// =============================================================================
+
+#[derive(Clone)]
+pub struct ExchangeClient {
+    http: reqwest::Client,
+    base_url: String,
+    access_token: Option<String>,
+    api_key: Option<String>,
+}
+
+impl ExchangeClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            access_token: None,
+            api_key: None,
+        }
+    }
+
+    /// Send `Authorization: Bearer <token>` on every request
+    pub fn with_access_token(mut self, token: impl Into<String>) -> Self {
+        self.access_token = Some(token.into());
+        self
+    }
+
+    /// Send `X-API-Key` on every request so the server applies the partner's brand
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    // =========================================================================
+    // HEALTH
+    // =========================================================================
+
+    /// True when `/ready` reports the service can take traffic
+    pub async fn is_ready(&self) -> Result<bool, ClientError> {
+        let response = self.request(Method::GET, "/ready").send().await?;
+        Ok(response.status().is_success())
+    }
+
+    /// Name, support contact and offering of the brand this client is served under
+    pub async fn get_brand(&self) -> Result<PublicBrand, ClientError> {
+        self.send(self.request(Method::GET, "/brand")).await
+    }
+
+    // =========================================================================
+    // AUTH
+    // =========================================================================
+
+    pub async fn register(&self, request: &RegisterRequest) -> Result<RegisterResponse, ClientError> {
+        self.send(self.request(Method::POST, "/auth/register").json(request)).await
+    }
+
+    pub async fn login(&self, request: &LoginRequest) -> Result<LoginResponse, ClientError> {
+        self.send(self.request(Method::POST, "/auth/login").json(request)).await
+    }
+
+    pub async fn get_swap_preferences(&self) -> Result<SwapPreferences, ClientError> {
+        self.send(self.request(Method::GET, "/auth/swap-preferences")).await
+    }
+
+    pub async fn update_swap_preferences(&self, preferences: &SwapPreferences) -> Result<SwapPreferences, ClientError> {
+        self.send(self.request(Method::PUT, "/auth/swap-preferences").json(preferences)).await
+    }
+
+    // =========================================================================
+    // SWAP
+    // =========================================================================
+
+    pub async fn get_currencies(&self, query: &CurrenciesQuery) -> Result<Vec<CurrencyResponse>, ClientError> {
+        self.send(self.request(Method::GET, "/swap/currencies").query(query)).await
+    }
+
+    /// GET /swap/currencies as a page; `query` needs one of per_page, sort, search or fields
+    pub async fn get_currencies_page(&self, query: &CurrenciesQuery) -> Result<CurrenciesPage, ClientError> {
+        self.send(self.request(Method::GET, "/swap/currencies").query(query)).await
+    }
+
+    pub async fn get_currencies_grouped(
+        &self,
+        query: &CurrenciesQuery,
+    ) -> Result<Vec<GroupedCurrencyResponse>, ClientError> {
+        self.send(self.request(Method::GET, "/swap/currencies/grouped").query(query)).await
+    }
+
+    pub async fn get_providers(&self, query: &ProvidersQuery) -> Result<Vec<ProviderResponse>, ClientError> {
+        self.send(self.request(Method::GET, "/swap/providers").query(query)).await
+    }
+
+    pub async fn get_provider_uptime(&self, provider_id: &str) -> Result<ProviderUptimeResponse, ClientError> {
+        self.send(self.request(Method::GET, &format!("/swap/providers/{}/uptime", provider_id))).await
+    }
+
+    pub async fn get_rates(&self, query: &RatesQuery) -> Result<RatesResponse, ClientError> {
+        self.send(self.request(Method::GET, "/swap/rates").query(query)).await
+    }
+
+    /// Best rate for a pair at increasing sizes
+    pub async fn get_depth(&self, query: &DepthQuery) -> Result<DepthResponse, ClientError> {
+        self.send(self.request(Method::GET, "/swap/depth").query(query)).await
+    }
+
+    /// Hold a fixed-rate quote; pass its `quote_id` to `create_swap` before it expires
+    pub async fn reserve_quote(&self, request: &QuoteRequest) -> Result<QuoteReservation, ClientError> {
+        self.send(self.request(Method::POST, "/swap/quote").json(request)).await
+    }
+
+    pub async fn create_swap(&self, request: &CreateSwapRequest) -> Result<CreateSwapResponse, ClientError> {
+        self.send(self.request(Method::POST, "/swap/create").json(request)).await
+    }
+
+    /// Create a swap under an `Idempotency-Key`; retrying with the same key and
+    /// request returns the original swap instead of creating another
+    pub async fn create_swap_idempotent(
+        &self,
+        request: &CreateSwapRequest,
+        idempotency_key: &str,
+    ) -> Result<CreateSwapResponse, ClientError> {
+        let builder = self
+            .request(Method::POST, "/swap/create")
+            .header("Idempotency-Key", idempotency_key)
+            .json(request);
+        self.send(builder).await
+    }
+
+    /// Validate and quote `request` as a dry run, whatever its `dry_run` flag says
+    pub async fn preview_swap(&self, request: &CreateSwapRequest) -> Result<SwapPreviewResponse, ClientError> {
+        let mut body = serde_json::to_value(request).map_err(|e| ClientError::HttpError(e.to_string()))?;
+        body["dry_run"] = serde_json::Value::Bool(true);
+        self.send(self.request(Method::POST, "/swap/create").json(&body)).await
+    }
+
+    pub async fn create_swap_draft(&self, draft: &SwapDraft) -> Result<SwapDraftResponse, ClientError> {
+        self.send(self.request(Method::POST, "/swap/drafts").json(draft)).await
+    }
+
+    pub async fn get_swap_draft(&self, token: &str) -> Result<SwapDraftResponse, ClientError> {
+        self.send(self.request(Method::GET, &format!("/swap/drafts/{}", token))).await
+    }
+
+    /// Replaces the draft's contents and restarts its expiry
+    pub async fn update_swap_draft(&self, token: &str, draft: &SwapDraft) -> Result<SwapDraftResponse, ClientError> {
+        let path = format!("/swap/drafts/{}", token);
+        self.send(self.request(Method::PUT, &path).json(draft)).await
+    }
+
+    pub async fn delete_swap_draft(&self, token: &str) -> Result<SwapDraftResponse, ClientError> {
+        self.send(self.request(Method::DELETE, &format!("/swap/drafts/{}", token))).await
+    }
+
+    pub async fn get_swap_status(&self, swap_id: &str) -> Result<SwapStatusResponse, ClientError> {
+        self.send(self.request(Method::GET, &format!("/swap/{}", swap_id))).await
+    }
+
+    /// Requires an access token; only the caller's own swaps are returned
+    pub async fn get_swap_statuses(
+        &self,
+        request: &BatchSwapStatusRequest,
+    ) -> Result<BatchSwapStatusResponse, ClientError> {
+        self.send(self.request(Method::POST, "/swap/status/batch").json(request)).await
+    }
+
+    /// Requires an access token; pass `next_cursor` back as `cursor` for the next page
+    pub async fn get_swap_history(&self, query: &HistoryQuery) -> Result<SwapHistoryResponse, ClientError> {
+        self.send(self.request(Method::GET, "/swap/history").query(query)).await
+    }
+
+    /// Requires an access token
+    pub async fn get_swap_limits(&self) -> Result<SwapLimitsResponse, ClientError> {
+        self.send(self.request(Method::GET, "/swap/limits")).await
+    }
+
+    /// Requires an access token
+    pub async fn get_refund_address_suggestions(
+        &self,
+        query: &RefundAddressQuery,
+    ) -> Result<RefundAddressSuggestionsResponse, ClientError> {
+        self.send(self.request(Method::GET, "/swap/refund-addresses").query(query)).await
+    }
+
+    pub async fn retry_swap(
+        &self,
+        swap_id: &str,
+        request: &RetrySwapRequest,
+    ) -> Result<CreateSwapResponse, ClientError> {
+        let path = format!("/swap/{}/retry", swap_id);
+        self.send(self.request(Method::POST, &path).json(request)).await
+    }
+
+    pub async fn validate_address(
+        &self,
+        request: &ValidateAddressRequest,
+    ) -> Result<ValidateAddressResponse, ClientError> {
+        self.send(self.request(Method::POST, "/swap/validate-address").json(request)).await
+    }
+
+    // =========================================================================
+    // ONRAMP
+    // =========================================================================
+
+    pub async fn get_onramp_quote(&self, query: &OnrampQuoteQuery) -> Result<OnrampQuoteResponse, ClientError> {
+        self.send(self.request(Method::GET, "/onramp/quote").query(query)).await
+    }
+
+    /// Requires an access token
+    pub async fn create_onramp_order(
+        &self,
+        request: &CreateOnrampOrderRequest,
+    ) -> Result<OnrampOrderResponse, ClientError> {
+        self.send(self.request(Method::POST, "/onramp/orders").json(request)).await
+    }
+
+    /// Requires an access token; only the caller's own orders are returned
+    pub async fn get_onramp_order(&self, order_id: &str) -> Result<OnrampOrderResponse, ClientError> {
+        self.send(self.request(Method::GET, &format!("/onramp/orders/{}", order_id))).await
+    }
+
+    // =========================================================================
+    // ACCOUNT (requires the partner API key)
+    // =========================================================================
+
+    pub async fn get_account_analytics(&self, query: &AnalyticsQuery) -> Result<AccountAnalyticsResponse, ClientError> {
+        self.send(self.request(Method::GET, "/account/analytics").query(query)).await
+    }
+
+    // =========================================================================
+    // ADMIN (requires an admin access token)
+    // =========================================================================
+
+    pub async fn schedule_currency_delisting(
+        &self,
+        currency_id: i64,
+        request: &ScheduleDelistingRequest,
+    ) -> Result<DelistingResponse, ClientError> {
+        let path = format!("/admin/currencies/{}/delisting", currency_id);
+        self.send(self.request(Method::POST, &path).json(request)).await
+    }
+
+    pub async fn cancel_currency_delisting(&self, currency_id: i64) -> Result<DelistingResponse, ClientError> {
+        let path = format!("/admin/currencies/{}/delisting", currency_id);
+        self.send(self.request(Method::DELETE, &path)).await
+    }
+
+    pub async fn update_currency_policy(
+        &self,
+        currency_id: i64,
+        request: &UpdateCurrencyPolicyRequest,
+    ) -> Result<CurrencyPolicyResponse, ClientError> {
+        let path = format!("/admin/currencies/{}/policy", currency_id);
+        self.send(self.request(Method::PATCH, &path).json(request)).await
+    }
+
+    pub async fn list_high_value_swaps(&self) -> Result<Vec<HighValueSwapResponse>, ClientError> {
+        self.send(self.request(Method::GET, "/admin/swaps/high-value")).await
+    }
+
+    pub async fn list_providers_admin(&self) -> Result<Vec<ProviderAdminResponse>, ClientError> {
+        self.send(self.request(Method::GET, "/admin/providers")).await
+    }
+
+    pub async fn update_provider(
+        &self,
+        provider_id: &str,
+        request: &UpdateProviderRequest,
+    ) -> Result<ProviderAdminResponse, ClientError> {
+        let path = format!("/admin/providers/{}", provider_id);
+        self.send(self.request(Method::PATCH, &path).json(request)).await
+    }
+
+    pub async fn clear_provider_overrides(&self, provider_id: &str) -> Result<ProviderAdminResponse, ClientError> {
+        let path = format!("/admin/providers/{}/overrides", provider_id);
+        self.send(self.request(Method::DELETE, &path)).await
+    }
+
+    pub async fn get_provider_health(&self) -> Result<Vec<ProviderHealth>, ClientError> {
+        self.send(self.request(Method::GET, "/admin/providers/health")).await
+    }
+
+    pub async fn get_sync_status(&self) -> Result<SyncStatusResponse, ClientError> {
+        self.send(self.request(Method::GET, "/admin/sync/status")).await
+    }
+
+    pub async fn get_cache_stats(&self) -> Result<CacheStatsResponse, ClientError> {
+        self.send(self.request(Method::GET, "/admin/cache/stats")).await
+    }
+
+    pub async fn get_provider_schema_drift(&self) -> Result<SchemaDriftSnapshot, ClientError> {
+        self.send(self.request(Method::GET, "/admin/providers/schema-drift")).await
+    }
+
+    pub async fn get_shadow_quotes(&self, query: &ShadowQuotesQuery) -> Result<ShadowQuoteReport, ClientError> {
+        self.send(self.request(Method::GET, "/admin/providers/shadow-quotes").query(query)).await
+    }
+
+    pub async fn get_maintenance(&self) -> Result<MaintenanceState, ClientError> {
+        self.send(self.request(Method::GET, "/admin/maintenance")).await
+    }
+
+    pub async fn update_maintenance(&self, request: &UpdateMaintenanceRequest) -> Result<MaintenanceState, ClientError> {
+        self.send(self.request(Method::PUT, "/admin/maintenance").json(request)).await
+    }
+
+    pub async fn list_address_formats(&self) -> Result<Vec<AddressFormat>, ClientError> {
+        self.send(self.request(Method::GET, "/admin/address-formats")).await
+    }
+
+    pub async fn upsert_address_format(
+        &self,
+        ticker: &str,
+        network: &str,
+        request: &UpsertAddressFormatRequest,
+    ) -> Result<AddressFormat, ClientError> {
+        let path = format!("/admin/address-formats/{}/{}", ticker, network);
+        self.send(self.request(Method::PUT, &path).json(request)).await
+    }
+
+    pub async fn delete_address_format(&self, ticker: &str, network: &str) -> Result<AddressFormat, ClientError> {
+        let path = format!("/admin/address-formats/{}/{}", ticker, network);
+        self.send(self.request(Method::DELETE, &path)).await
+    }
+
+    pub async fn list_brands(&self) -> Result<Vec<Brand>, ClientError> {
+        self.send(self.request(Method::GET, "/admin/brands")).await
+    }
+
+    pub async fn upsert_brand(&self, slug: &str, request: &UpsertBrandRequest) -> Result<Brand, ClientError> {
+        let path = format!("/admin/brands/{}", slug);
+        self.send(self.request(Method::PUT, &path).json(request)).await
+    }
+
+    pub async fn delete_brand(&self, slug: &str) -> Result<Brand, ClientError> {
+        let path = format!("/admin/brands/{}", slug);
+        self.send(self.request(Method::DELETE, &path)).await
+    }
+
+    pub async fn list_fee_rules(&self) -> Result<Vec<FeeRule>, ClientError> {
+        self.send(self.request(Method::GET, "/admin/fee-rules")).await
+    }
+
+    pub async fn create_fee_rule(&self, rule: &FeeRuleInput) -> Result<FeeRule, ClientError> {
+        self.send(self.request(Method::POST, "/admin/fee-rules").json(rule)).await
+    }
+
+    pub async fn update_fee_rule(&self, id: u64, rule: &FeeRuleInput) -> Result<FeeRule, ClientError> {
+        let path = format!("/admin/fee-rules/{}", id);
+        self.send(self.request(Method::PUT, &path).json(rule)).await
+    }
+
+    pub async fn delete_fee_rule(&self, id: u64) -> Result<FeeRule, ClientError> {
+        let path = format!("/admin/fee-rules/{}", id);
+        self.send(self.request(Method::DELETE, &path)).await
+    }
+
+    pub async fn update_user_fee_tier(&self, user_id: &str, fee_tier: &str) -> Result<UserFeeTierResponse, ClientError> {
+        let path = format!("/admin/users/{}/fee-tier", user_id);
+        let request = UpdateFeeTierRequest { fee_tier: fee_tier.to_string() };
+        self.send(self.request(Method::PUT, &path).json(&request)).await
+    }
+
+    pub async fn list_jobs(&self) -> Result<Vec<JobStatus>, ClientError> {
+        self.send(self.request(Method::GET, "/admin/jobs")).await
+    }
+
+    pub async fn run_job(&self, name: &str) -> Result<JobStatus, ClientError> {
+        let path = format!("/admin/jobs/{}/run", name);
+        self.send(self.request(Method::POST, &path)).await
+    }
+
+    /// Dry run: what the retention policies would scrub right now
+    pub async fn get_retention_report(&self) -> Result<RetentionReport, ClientError> {
+        self.send(self.request(Method::GET, "/admin/retention/report")).await
+    }
+
+    // =========================================================================
+    // HELPERS
+    // =========================================================================
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let mut builder = self.http.request(method, format!("{}{}", self.base_url, path));
+        if let Some(api_key) = &self.api_key {
+            builder = builder.header("X-API-Key", api_key);
+        }
+        match &self.access_token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(&self, builder: RequestBuilder) -> Result<T, ClientError> {
+        let response = builder.send().await?;
+        let status = response.status();
+
+        if status.is_success() {
+            return Ok(response.json::<T>().await?);
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        let (error, code) = match serde_json::from_str::<ApiErrorBody>(&body) {
+            Ok(parsed) => (parsed.error, parsed.code),
+            Err(_) => (body, None),
+        };
+
+        Err(ClientError::ApiError { status, error, code })
+    }
+}
+
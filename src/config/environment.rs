@@ -1,4 +1,11 @@
+use std::collections::HashMap;
 use std::env;
+use std::str::FromStr;
+use std::time::Duration;
+
+use rust_decimal::Decimal;
+
+use crate::services::money;
 
 /// Environment configuration
 /// Loads and validates environment variables
@@ -7,6 +14,1295 @@ pub struct Config {
     pub redis_url: String,
     pub jwt_secret: String,
     pub trocador_api_key: String,
+    pub sync_worker: SyncWorkerConfig,
+    pub status_poller: StatusPollerConfig,
+    pub provider_prober: ProviderProberConfig,
+    pub cache_consistency: CacheConsistencyConfig,
+    pub retention: RetentionConfig,
+    pub cache_warmup: CacheWarmupConfig,
+    pub event_bus: EventBusConfig,
+    pub notifications: NotificationConfig,
+    pub brand_webhooks: BrandWebhookConfig,
+    pub reconciliation: ReconciliationConfig,
+    pub grpc: GrpcConfig,
+}
+
+/// Scheduling knobs for the background currency/provider sync worker
+#[derive(Debug, Clone)]
+pub struct SyncWorkerConfig {
+    pub enabled: bool,
+    pub interval: Duration,      // Base delay between successful runs
+    pub jitter: Duration,        // Random extra delay so instances don't align
+    pub run_timeout: Duration,   // Upper bound for a single sync run
+    pub max_backoff: Duration,   // Cap for the exponential delay after failures
+}
+
+impl SyncWorkerConfig {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: env_or("SYNC_WORKER_ENABLED", true),
+            interval: Duration::from_secs(env_or("SYNC_INTERVAL_SECS", 300)),
+            jitter: Duration::from_secs(env_or("SYNC_JITTER_SECS", 30)),
+            run_timeout: Duration::from_secs(env_or("SYNC_RUN_TIMEOUT_SECS", 60)),
+            max_backoff: Duration::from_secs(env_or("SYNC_MAX_BACKOFF_SECS", 1800)),
+        }
+    }
+}
+
+impl Default for SyncWorkerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: Duration::from_secs(300),
+            jitter: Duration::from_secs(30),
+            run_timeout: Duration::from_secs(60),
+            max_backoff: Duration::from_secs(1800),
+        }
+    }
+}
+
+/// Scheduling knobs for the background poller that advances in-flight swaps
+#[derive(Debug, Clone)]
+pub struct StatusPollerConfig {
+    pub enabled: bool,
+    pub interval: Duration,      // Delay between poll passes
+    pub batch_size: u32,         // Swaps polled per pass, least recently polled first
+    pub min_poll_age: Duration,  // A swap is not polled again within this window
+    pub abandon_after: Duration, // Waiting swaps older than this (without an expiry) are expired
+    pub run_timeout: Duration,   // Upper bound for a single pass
+    pub high_value: HighValueConfig,
+}
+
+impl StatusPollerConfig {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: env_or("SWAP_POLLER_ENABLED", true),
+            interval: Duration::from_secs(env_or("SWAP_POLL_INTERVAL_SECS", 60)),
+            batch_size: env_or("SWAP_POLL_BATCH_SIZE", 50),
+            min_poll_age: Duration::from_secs(env_or("SWAP_POLL_MIN_AGE_SECS", 120)),
+            abandon_after: Duration::from_secs(env_or("SWAP_ABANDON_AFTER_SECS", 86400)),
+            run_timeout: Duration::from_secs(env_or("SWAP_POLL_RUN_TIMEOUT_SECS", 300)),
+            high_value: HighValueConfig::from_env(),
+        }
+    }
+}
+
+impl Default for StatusPollerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: Duration::from_secs(60),
+            batch_size: 50,
+            min_poll_age: Duration::from_secs(120),
+            abandon_after: Duration::from_secs(86400),
+            run_timeout: Duration::from_secs(300),
+            high_value: HighValueConfig::default(),
+        }
+    }
+}
+
+/// Swaps worth at least `threshold_usd` are tagged high value at creation,
+/// polled more often and alerted on when they stall
+#[derive(Debug, Clone)]
+pub struct HighValueConfig {
+    pub threshold_usd: f64,               // 0 disables tagging
+    pub usd_prices: HashMap<String, f64>, // Reference USD price per ticker (lowercase)
+    pub min_poll_age: Duration,           // Replaces SWAP_POLL_MIN_AGE_SECS for high-value swaps
+    pub stall_after: Duration,            // Alert once a funded swap keeps one status this long
+}
+
+impl HighValueConfig {
+    pub fn from_env() -> Self {
+        // HIGH_VALUE_USD_PRICES="usdt=1,btc=60000"; limits do not follow the price feed, so keep these roughly current
+        let usd_prices = env_list("HIGH_VALUE_USD_PRICES", "usdt=1,usdc=1,dai=1")
+            .into_iter()
+            .filter_map(|rule| {
+                let (ticker, price) = rule.split_once('=')?;
+                let price: f64 = price.trim().parse().ok()?;
+                (price > 0.0).then(|| (ticker.trim().to_lowercase(), price))
+            })
+            .collect();
+
+        Self {
+            threshold_usd: env_or("HIGH_VALUE_SWAP_USD", 10_000.0),
+            usd_prices,
+            min_poll_age: Duration::from_secs(env_or("HIGH_VALUE_POLL_MIN_AGE_SECS", 30)),
+            stall_after: Duration::from_secs(env_or("HIGH_VALUE_STALL_AFTER_SECS", 1800)),
+        }
+    }
+
+    /// USD estimate for a swap, from whichever side has a reference price
+    pub fn usd_value(&self, from: &str, amount: Decimal, to: &str, receive: Decimal) -> Option<f64> {
+        self.usd_amount(from, amount).or_else(|| self.usd_amount(to, receive))
+    }
+
+    /// USD estimate of `amount` of `ticker`, if it has a reference price
+    pub fn usd_amount(&self, ticker: &str, amount: Decimal) -> Option<f64> {
+        self.usd_price(ticker).map(|price| price * money::to_f64(amount))
+    }
+
+    /// Reference USD price of one unit of `ticker`
+    pub fn usd_price(&self, ticker: &str) -> Option<f64> {
+        self.usd_prices.get(&ticker.to_lowercase()).copied()
+    }
+
+    pub fn is_high_value(&self, usd_value: Option<f64>) -> bool {
+        self.threshold_usd > 0.0 && usd_value.is_some_and(|v| v >= self.threshold_usd)
+    }
+}
+
+impl Default for HighValueConfig {
+    fn default() -> Self {
+        Self {
+            threshold_usd: 10_000.0,
+            usd_prices: [("usdt", 1.0), ("usdc", 1.0), ("dai", 1.0)]
+                .into_iter()
+                .map(|(ticker, price)| (ticker.to_string(), price))
+                .collect(),
+            min_poll_age: Duration::from_secs(30),
+            stall_after: Duration::from_secs(1800),
+        }
+    }
+}
+
+/// Scheduling knobs for the provider availability prober
+#[derive(Debug, Clone)]
+pub struct ProviderProberConfig {
+    pub enabled: bool,
+    pub interval: Duration,    // Delay between probes; one instance probes per interval
+    pub run_timeout: Duration, // Upper bound for a single probe
+}
+
+impl ProviderProberConfig {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: env_or("PROVIDER_PROBER_ENABLED", true),
+            interval: Duration::from_secs(env_or("PROVIDER_PROBE_INTERVAL_SECS", 300)),
+            run_timeout: Duration::from_secs(env_or("PROVIDER_PROBE_TIMEOUT_SECS", 30)),
+        }
+    }
+}
+
+impl Default for ProviderProberConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: Duration::from_secs(300),
+            run_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Scheduling knobs for the cache/database consistency checker
+#[derive(Debug, Clone)]
+pub struct CacheConsistencyConfig {
+    pub enabled: bool,
+    pub interval: Duration, // Delay between checks; one instance checks per interval
+    pub sample_size: u32,   // Most recently updated swaps whose cached status is checked
+    pub repair: bool,       // Drop divergent entries instead of only reporting them
+}
+
+impl CacheConsistencyConfig {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: env_or("CACHE_CONSISTENCY_ENABLED", true),
+            interval: Duration::from_secs(env_or("CACHE_CONSISTENCY_INTERVAL_SECS", 600)),
+            sample_size: env_or("CACHE_CONSISTENCY_SAMPLE_SIZE", 100),
+            repair: env_or("CACHE_CONSISTENCY_REPAIR", true),
+        }
+    }
+}
+
+impl Default for CacheConsistencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: Duration::from_secs(600),
+            sample_size: 100,
+            repair: true,
+        }
+    }
+}
+
+/// Data retention windows; a window of 0 days turns that policy off
+#[derive(Debug, Clone)]
+pub struct RetentionConfig {
+    pub enabled: bool,       // Run the scheduled scrub; the admin report works either way
+    pub interval: Duration,  // Delay between scheduled runs
+    pub swap_days: u32,      // Finished swaps/on-ramp orders, provider payloads, webhook deliveries
+    pub analytics_days: u32, // Session, referrer and user ids on funnel events
+    pub batch_size: u32,     // Rows changed per statement
+}
+
+impl RetentionConfig {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: env_or("RETENTION_ENABLED", false),
+            interval: Duration::from_secs(env_or("RETENTION_INTERVAL_SECS", 86400)),
+            swap_days: env_or("RETENTION_SWAP_DAYS", 365),
+            analytics_days: env_or("RETENTION_ANALYTICS_DAYS", 90),
+            batch_size: env_or("RETENTION_BATCH_SIZE", 1000),
+        }
+    }
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: Duration::from_secs(86400),
+            swap_days: 365,
+            analytics_days: 90,
+            batch_size: 1000,
+        }
+    }
+}
+
+/// Startup cache priming; `/ready` reports `warming` until it finishes
+#[derive(Debug, Clone)]
+pub struct CacheWarmupConfig {
+    pub enabled: bool,
+    pub timeout: Duration, // Give up priming (and report ready) after this long
+}
+
+impl CacheWarmupConfig {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: env_or("CACHE_WARMUP_ENABLED", true),
+            timeout: Duration::from_secs(env_or("CACHE_WARMUP_TIMEOUT_SECS", 30)),
+        }
+    }
+}
+
+impl Default for CacheWarmupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Traffic exempt from the public rate limit (still counted in rate-limit metrics)
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitBypassConfig {
+    pub internal_tokens: Vec<String>, // Accepted values for the X-Internal-Service-Token header
+    pub exempt_paths: Vec<String>,    // Exact paths, e.g. health checks
+}
+
+impl RateLimitBypassConfig {
+    pub fn from_env() -> Self {
+        Self {
+            internal_tokens: env_list("RATE_LIMIT_INTERNAL_TOKENS", ""),
+            exempt_paths: env_list(
+                "RATE_LIMIT_EXEMPT_PATHS",
+                "/health,/ready,/metrics,/webhooks/email/ses,/webhooks/email/events,/onramp/webhook,/swap/webhook/trocador",
+            ),
+        }
+    }
+}
+
+/// What the rate guard does with a quote too far from the reference rate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RateGuardAction {
+    Reject, // Drop the quote and refuse swaps at that rate
+    Flag,   // Serve the quote with rate_warning set
+}
+
+impl FromStr for RateGuardAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "reject" => Ok(RateGuardAction::Reject),
+            "flag" => Ok(RateGuardAction::Flag),
+            other => Err(format!("Unknown rate guard action '{}'", other)),
+        }
+    }
+}
+
+/// Sanity bounds on provider rates, measured against the median quote for the pair
+#[derive(Debug, Clone)]
+pub struct RateGuardConfig {
+    pub enabled: bool,
+    pub max_deviation_pct: f64, // Allowed distance from the reference rate, either direction
+    pub action: RateGuardAction,
+    pub min_quotes: usize,      // Fewer quotes than this give no reference, so nothing is judged
+}
+
+impl RateGuardConfig {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: env_or("RATE_GUARD_ENABLED", true),
+            max_deviation_pct: env_or("RATE_GUARD_MAX_DEVIATION_PCT", 10.0),
+            action: env_or("RATE_GUARD_ACTION", RateGuardAction::Reject),
+            min_quotes: env_or("RATE_GUARD_MIN_QUOTES", 3),
+        }
+    }
+}
+
+impl Default for RateGuardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_deviation_pct: 10.0,
+            action: RateGuardAction::Reject,
+            min_quotes: 3,
+        }
+    }
+}
+
+/// Per-caller token-bucket limits for route groups, on top of the global limit
+#[derive(Debug, Clone, Default)]
+pub struct RouteRateLimitConfig {
+    pub enabled: bool,
+    pub trust_forwarded_for: bool, // Key anonymous callers by X-Forwarded-For (only behind a proxy that sets it)
+    pub routes: Vec<(String, u32)>, // Path prefix and requests per minute; longest match wins
+}
+
+impl RouteRateLimitConfig {
+    pub fn from_env() -> Self {
+        // ROUTE_RATE_LIMITS="/swap/create=10,/swap/rates=60"
+        let routes = env_list("ROUTE_RATE_LIMITS", "/swap/create=10,/swap/rates=60")
+            .into_iter()
+            .filter_map(|rule| {
+                let (prefix, per_minute) = rule.split_once('=')?;
+                let per_minute: u32 = per_minute.trim().parse().ok()?;
+                (per_minute > 0).then(|| (prefix.trim().to_string(), per_minute))
+            })
+            .collect();
+
+        Self {
+            enabled: env_or("ROUTE_RATE_LIMITS_ENABLED", true),
+            trust_forwarded_for: env_or("RATE_LIMIT_TRUST_FORWARDED_FOR", false),
+            routes,
+        }
+    }
+
+    /// The route group and its per-minute limit covering `path`, if any
+    pub fn limit_for(&self, path: &str) -> Option<(&str, u32)> {
+        if !self.enabled {
+            return None;
+        }
+
+        self.routes
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(prefix, per_minute)| (prefix.as_str(), *per_minute))
+    }
+}
+
+/// Admission control for POST /swap/create (see services::admission)
+#[derive(Debug, Clone)]
+pub struct AdmissionConfig {
+    pub enabled: bool,
+    pub max_concurrent: usize, // Creates handled at once per instance
+    pub max_queued: usize,     // Creates waiting for a slot; any more are refused
+    pub max_wait: Duration,    // Longest a create waits for a slot
+}
+
+impl AdmissionConfig {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: env_or("SWAP_CREATE_ADMISSION_ENABLED", true),
+            max_concurrent: env_or("SWAP_CREATE_MAX_CONCURRENT", 32usize).max(1),
+            max_queued: env_or("SWAP_CREATE_MAX_QUEUED", 64),
+            max_wait: Duration::from_millis(env_or("SWAP_CREATE_MAX_WAIT_MS", 2000)),
+        }
+    }
+}
+
+impl Default for AdmissionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_concurrent: 32,
+            max_queued: 64,
+            max_wait: Duration::from_millis(2000),
+        }
+    }
+}
+
+/// How much of a request/response the logging middleware records for a route
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RouteLogLevel {
+    Off,
+    Basic,  // Method, path, status, latency, caller id
+    Bodies, // Basic plus redacted, truncated bodies (only when body logging is enabled)
+}
+
+impl FromStr for RouteLogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(RouteLogLevel::Off),
+            "basic" => Ok(RouteLogLevel::Basic),
+            "bodies" => Ok(RouteLogLevel::Bodies),
+            other => Err(format!("Unknown route log level '{}'", other)),
+        }
+    }
+}
+
+/// HTTP request/response logging settings
+#[derive(Debug, Clone)]
+pub struct RequestLogConfig {
+    pub enabled: bool,
+    pub log_bodies: bool,                       // Master switch for body capture (debug environments)
+    pub max_body_bytes: usize,                  // Logged bodies are truncated to this size
+    pub default_level: RouteLogLevel,
+    pub routes: Vec<(String, RouteLogLevel)>,   // Path prefix overrides; longest match wins
+}
+
+impl RequestLogConfig {
+    pub fn from_env() -> Self {
+        // REQUEST_LOG_ROUTES="/health=off,/swap/create=bodies"
+        let routes = env_list("REQUEST_LOG_ROUTES", "")
+            .into_iter()
+            .filter_map(|rule| {
+                let (prefix, level) = rule.split_once('=')?;
+                Some((prefix.trim().to_string(), level.trim().parse().ok()?))
+            })
+            .collect();
+
+        Self {
+            enabled: env_or("REQUEST_LOG_ENABLED", true),
+            log_bodies: env_or("REQUEST_LOG_BODIES", false),
+            max_body_bytes: env_or("REQUEST_LOG_MAX_BODY_BYTES", 2048),
+            default_level: env_or("REQUEST_LOG_DEFAULT_LEVEL", RouteLogLevel::Basic),
+            routes,
+        }
+    }
+
+    /// Effective level for a request path
+    pub fn level_for(&self, path: &str) -> RouteLogLevel {
+        if !self.enabled {
+            return RouteLogLevel::Off;
+        }
+
+        let level = self
+            .routes
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default_level);
+
+        match level {
+            RouteLogLevel::Bodies if !self.log_bodies => RouteLogLevel::Basic,
+            other => other,
+        }
+    }
+}
+
+/// Response time objective for one route: `percentile`% of its requests
+/// finish within `threshold` without a server error
+#[derive(Debug, Clone, PartialEq)]
+pub struct SloObjective {
+    pub method: String,  // Uppercase, e.g. GET
+    pub route: String,   // Route as registered, e.g. /swap/{id}
+    pub percentile: f64, // 95.0 for p95
+    pub threshold: Duration,
+}
+
+impl SloObjective {
+    /// "GET /swap/rates", as reported and alerted on
+    pub fn name(&self) -> String {
+        format!("{} {}", self.method, self.route)
+    }
+}
+
+impl FromStr for SloObjective {
+    type Err = String;
+
+    /// "GET /swap/rates=p95:800", threshold in milliseconds
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid SLO '{}'; expected e.g. 'GET /swap/rates=p95:800'", s);
+        let (target, objective) = s.split_once('=').ok_or_else(invalid)?;
+        let (method, route) = target.trim().split_once(' ').ok_or_else(invalid)?;
+        let (percentile, threshold_ms) = objective.trim().split_once(':').ok_or_else(invalid)?;
+
+        let percentile: f64 = percentile
+            .trim()
+            .strip_prefix('p')
+            .and_then(|p| p.parse().ok())
+            .filter(|p| *p > 0.0 && *p < 100.0)
+            .ok_or_else(invalid)?;
+        let threshold_ms: u64 = threshold_ms.trim().trim_end_matches("ms").parse().map_err(|_| invalid())?;
+
+        Ok(Self {
+            method: method.trim().to_uppercase(),
+            route: route.trim().to_string(),
+            percentile,
+            threshold: Duration::from_millis(threshold_ms),
+        })
+    }
+}
+
+/// Response time SLOs and when their error-budget burn raises an alert
+#[derive(Debug, Clone)]
+pub struct SloConfig {
+    pub enabled: bool,
+    pub objectives: Vec<SloObjective>,
+    pub window: Duration,         // Compliance and the error budget are measured over this rolling window
+    pub burn_rate_alert: f64,     // Alert when the last hour and the last 5 minutes both burn this fast
+    pub check_interval: Duration, // Delay between burn-rate checks
+}
+
+impl SloConfig {
+    pub fn from_env() -> Self {
+        // SLOS="GET /swap/rates=p95:800,POST /swap/create=p95:2000"
+        let objectives = env_list("SLOS", "GET /swap/rates=p95:800,POST /swap/create=p95:2000")
+            .into_iter()
+            .filter_map(|rule| {
+                rule.parse()
+                    .map_err(|e: String| tracing::warn!("{}; ignoring it", e))
+                    .ok()
+            })
+            .collect();
+
+        Self {
+            enabled: env_or("SLO_ENABLED", true),
+            objectives,
+            window: Duration::from_secs(env_or("SLO_WINDOW_HOURS", 24) * 3600),
+            burn_rate_alert: env_or("SLO_BURN_RATE_ALERT", 14.4),
+            check_interval: Duration::from_secs(env_or("SLO_CHECK_INTERVAL_SECS", 60)),
+        }
+    }
+}
+
+impl Default for SloConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            objectives: Vec::new(),
+            window: Duration::from_secs(24 * 3600),
+            burn_rate_alert: 14.4,
+            check_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Read an optional variable, falling back to `default` when unset or unparsable
+fn env_or<T: FromStr>(key: &str, default: T) -> T {
+    env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Comma-separated list; empty entries are dropped
+fn env_list(key: &str, default: &str) -> Vec<String> {
+    env::var(key)
+        .unwrap_or_else(|_| default.to_string())
+        .split(',')
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+/// Outgoing email backend, selected with EMAIL_BACKEND
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EmailBackend {
+    Log, // Writes messages to the log instead of sending (development)
+    Smtp,
+    Ses,
+}
+
+impl EmailBackend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EmailBackend::Log => "log",
+            EmailBackend::Smtp => "smtp",
+            EmailBackend::Ses => "ses",
+        }
+    }
+
+    /// Messages per minute when EMAIL_RATE_LIMIT_PER_MINUTE is unset
+    fn default_rate_limit(&self) -> u32 {
+        match self {
+            EmailBackend::Log => 600,
+            EmailBackend::Smtp => 60,
+            EmailBackend::Ses => 600, // SES default sending quota is 14/s
+        }
+    }
+}
+
+impl FromStr for EmailBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "log" => Ok(EmailBackend::Log),
+            "smtp" => Ok(EmailBackend::Smtp),
+            "ses" => Ok(EmailBackend::Ses),
+            other => Err(format!("Unknown email backend '{}'", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub starttls: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct SesConfig {
+    pub region: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+    pub configuration_set: Option<String>, // Needed for SES to publish bounce/complaint events
+}
+
+/// Outgoing email settings
+#[derive(Debug, Clone)]
+pub struct EmailConfig {
+    pub backend: EmailBackend,
+    pub from_address: String,
+    pub rate_limit_per_minute: u32,    // Per backend, shared by all instances through Redis
+    pub webhook_token: Option<String>, // Required `?token=` on bounce/complaint webhooks; unset disables them
+    pub smtp: SmtpConfig,
+    pub ses: SesConfig,
+}
+
+impl EmailConfig {
+    pub fn from_env() -> Self {
+        let backend = env_or("EMAIL_BACKEND", EmailBackend::Log);
+
+        Self {
+            backend,
+            from_address: env::var("EMAIL_FROM").unwrap_or_else(|_| "no-reply@localhost".to_string()),
+            rate_limit_per_minute: env_or("EMAIL_RATE_LIMIT_PER_MINUTE", backend.default_rate_limit()),
+            webhook_token: env::var("EMAIL_WEBHOOK_TOKEN").ok().filter(|t| !t.is_empty()),
+            smtp: SmtpConfig {
+                host: env::var("SMTP_HOST").unwrap_or_default(),
+                port: env_or("SMTP_PORT", 587),
+                username: env::var("SMTP_USERNAME").ok(),
+                password: env::var("SMTP_PASSWORD").ok(),
+                starttls: env_or("SMTP_STARTTLS", true),
+            },
+            ses: SesConfig {
+                region: env::var("AWS_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+                access_key_id: env::var("AWS_ACCESS_KEY_ID").unwrap_or_default(),
+                secret_access_key: env::var("AWS_SECRET_ACCESS_KEY").unwrap_or_default(),
+                session_token: env::var("AWS_SESSION_TOKEN").ok(),
+                configuration_set: env::var("SES_CONFIGURATION_SET").ok(),
+            },
+        }
+    }
+}
+
+/// Destination for swap funnel analytics events, selected with ANALYTICS_SINK
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnalyticsSinkKind {
+    Off,
+    Db,      // `analytics_events` table
+    Kafka,   // Through a Kafka REST Proxy
+    Webhook, // JSON batches POSTed to ANALYTICS_WEBHOOK_URL
+}
+
+impl FromStr for AnalyticsSinkKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" | "none" => Ok(AnalyticsSinkKind::Off),
+            "db" => Ok(AnalyticsSinkKind::Db),
+            "kafka" => Ok(AnalyticsSinkKind::Kafka),
+            "webhook" => Ok(AnalyticsSinkKind::Webhook),
+            other => Err(format!("Unknown analytics sink '{}'", other)),
+        }
+    }
+}
+
+/// Delivery of queued user notifications (swap completed, failed, action required)
+#[derive(Debug, Clone)]
+pub struct NotificationConfig {
+    pub enabled: bool,
+    pub interval: Duration,                 // Delay between drains of the queue
+    pub batch_size: u32,                    // Notifications sent per drain
+    pub max_attempts: u32,                  // Then the notification is marked failed
+    pub telegram_bot_token: Option<String>, // Unset disables the Telegram channel
+    pub telegram_api_url: String,
+}
+
+impl NotificationConfig {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: env_or("NOTIFICATIONS_ENABLED", true),
+            interval: Duration::from_secs(env_or("NOTIFICATIONS_INTERVAL_SECS", 30)),
+            batch_size: env_or("NOTIFICATIONS_BATCH_SIZE", 100),
+            max_attempts: env_or("NOTIFICATIONS_MAX_ATTEMPTS", 5),
+            telegram_bot_token: env::var("TELEGRAM_BOT_TOKEN").ok().filter(|t| !t.is_empty()),
+            telegram_api_url: env::var("TELEGRAM_API_URL").unwrap_or_else(|_| "https://api.telegram.org".to_string()),
+        }
+    }
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: Duration::from_secs(30),
+            batch_size: 100,
+            max_attempts: 5,
+            telegram_bot_token: None,
+            telegram_api_url: "https://api.telegram.org".to_string(),
+        }
+    }
+}
+
+/// Swaps created with `allow_fallback` move to the next-best quoted provider
+/// when the requested one rejects the trade
+#[derive(Debug, Clone)]
+pub struct ProviderFallbackConfig {
+    pub tolerance_pct: f64, // How much worse than the rejected quote a fallback quote may be
+}
+
+impl ProviderFallbackConfig {
+    pub fn from_env() -> Self {
+        Self {
+            tolerance_pct: Some(env_or("SWAP_FALLBACK_TOLERANCE_PCT", 1.0))
+                .filter(|pct: &f64| pct.is_finite() && (0.0..=100.0).contains(pct))
+                .unwrap_or(1.0),
+        }
+    }
+}
+
+impl Default for ProviderFallbackConfig {
+    fn default() -> Self {
+        Self { tolerance_pct: 1.0 }
+    }
+}
+
+/// Policy for swaps created without a provider (or with `"best"`)
+#[derive(Debug, Clone, Default)]
+pub struct ProviderSelectionConfig {
+    pub min_kyc_rating: Option<char>,  // Worst acceptable rating, A (best) to D; None accepts any
+    pub max_eta_minutes: Option<u32>,  // None accepts any
+}
+
+impl ProviderSelectionConfig {
+    pub fn from_env() -> Self {
+        Self {
+            min_kyc_rating: env::var("SELECTION_MIN_KYC_RATING")
+                .ok()
+                .and_then(|r| r.trim().chars().next())
+                .map(|r| r.to_ascii_uppercase())
+                .filter(|r| ('A'..='D').contains(r)),
+            max_eta_minutes: Some(env_or("SELECTION_MAX_ETA_MINUTES", 0)).filter(|m| *m > 0),
+        }
+    }
+}
+
+/// Demotion and hiding of quotes from providers whose swaps keep failing,
+/// scored on our own swap outcomes (see services::provider_health)
+#[derive(Debug, Clone)]
+pub struct ProviderHealthConfig {
+    pub enabled: bool,
+    pub window_days: u32,  // Outcomes older than this are not scored
+    pub min_swaps: u32,    // Fewer outcomes than this leave a provider unscored
+    pub demote_below: f64, // Score (0-100) under which quotes are ranked last
+    pub hide_below: f64,   // Score under which quotes are not served; 0 never hides
+}
+
+impl ProviderHealthConfig {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: env_or("PROVIDER_HEALTH_ENABLED", true),
+            window_days: env_or("PROVIDER_HEALTH_WINDOW_DAYS", 7).max(1),
+            min_swaps: env_or("PROVIDER_HEALTH_MIN_SWAPS", 20),
+            demote_below: env_or("PROVIDER_HEALTH_DEMOTE_BELOW", 80.0),
+            hide_below: env_or("PROVIDER_HEALTH_HIDE_BELOW", 50.0),
+        }
+    }
+}
+
+impl Default for ProviderHealthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_days: 7,
+            min_swaps: 20,
+            demote_below: 80.0,
+            hide_below: 50.0,
+        }
+    }
+}
+
+/// Intermediate currencies for multi-leg routes (see /swap/routes)
+#[derive(Debug, Clone)]
+pub struct SwapRouteConfig {
+    pub via: Vec<(String, String)>, // (ticker lowercase, network), tried concurrently
+}
+
+impl SwapRouteConfig {
+    pub fn from_env() -> Self {
+        // SWAP_ROUTE_VIA="btc=Mainnet,eth=Mainnet"
+        let via = env_list("SWAP_ROUTE_VIA", "btc=Mainnet,eth=Mainnet,usdt=TRC20")
+            .into_iter()
+            .filter_map(|entry| {
+                let (ticker, network) = entry.split_once('=')?;
+                Some((ticker.trim().to_lowercase(), network.trim().to_string()))
+            })
+            .filter(|(ticker, network)| !ticker.is_empty() && !network.is_empty())
+            .collect();
+
+        Self { via }
+    }
+}
+
+/// Nightly reconciliation report, sent to the ops mailbox and channel
+#[derive(Debug, Clone)]
+pub struct ReconciliationConfig {
+    pub enabled: bool,
+    pub hour_utc: u32,               // The previous UTC day is reconciled at this hour
+    pub emails: Vec<String>,         // Ops addresses the report is mailed to
+    pub webhook_url: Option<String>, // Slack-compatible incoming webhook for the ops channel
+}
+
+impl ReconciliationConfig {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: env_or("RECONCILIATION_ENABLED", false),
+            hour_utc: env_or::<u32>("RECONCILIATION_HOUR_UTC", 1).min(23),
+            emails: env_list("RECONCILIATION_EMAILS", ""),
+            webhook_url: env::var("RECONCILIATION_WEBHOOK_URL").ok().filter(|u| !u.is_empty()),
+        }
+    }
+}
+
+impl Default for ReconciliationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hour_utc: 1,
+            emails: Vec::new(),
+            webhook_url: None,
+        }
+    }
+}
+
+/// Swap funnel analytics settings
+#[derive(Debug, Clone)]
+pub struct AnalyticsConfig {
+    pub sink: AnalyticsSinkKind,
+    pub webhook_url: String,
+    pub kafka_rest_url: String,
+    pub kafka_topic: String,
+    pub batch_size: usize,        // Events per publish
+    pub flush_interval: Duration, // Longest an event waits for its batch to fill
+    pub queue_capacity: usize,    // Events buffered before new ones are dropped
+}
+
+impl AnalyticsConfig {
+    pub fn from_env() -> Self {
+        let webhook_url = env::var("ANALYTICS_WEBHOOK_URL").unwrap_or_default();
+        let kafka_rest_url = env::var("ANALYTICS_KAFKA_REST_URL").unwrap_or_default();
+        let mut sink = env_or("ANALYTICS_SINK", AnalyticsSinkKind::Db);
+
+        let missing = match sink {
+            AnalyticsSinkKind::Webhook if webhook_url.is_empty() => Some("ANALYTICS_WEBHOOK_URL"),
+            AnalyticsSinkKind::Kafka if kafka_rest_url.is_empty() => Some("ANALYTICS_KAFKA_REST_URL"),
+            _ => None,
+        };
+        if let Some(var) = missing {
+            tracing::warn!("{} is not set; analytics events are disabled", var);
+            sink = AnalyticsSinkKind::Off;
+        }
+
+        Self {
+            sink,
+            webhook_url,
+            kafka_rest_url,
+            kafka_topic: env::var("ANALYTICS_KAFKA_TOPIC").unwrap_or_else(|_| "swap-funnel".to_string()),
+            batch_size: env_or("ANALYTICS_BATCH_SIZE", 100),
+            flush_interval: Duration::from_millis(env_or("ANALYTICS_FLUSH_INTERVAL_MS", 2000)),
+            queue_capacity: env_or("ANALYTICS_QUEUE_CAPACITY", 10_000),
+        }
+    }
+}
+
+/// Broker for domain events relayed from the outbox, selected with EVENT_BUS
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EventBusKind {
+    Off,   // No outbox rows are written
+    Kafka, // Through a Kafka REST Proxy
+    Nats,  // Requires the `nats` feature
+}
+
+impl FromStr for EventBusKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" | "none" => Ok(EventBusKind::Off),
+            "kafka" => Ok(EventBusKind::Kafka),
+            "nats" => Ok(EventBusKind::Nats),
+            other => Err(format!("Unknown event bus '{}'", other)),
+        }
+    }
+}
+
+/// Domain event publishing settings
+#[derive(Debug, Clone)]
+pub struct EventBusConfig {
+    pub kind: EventBusKind,
+    pub kafka_rest_url: String,
+    pub nats_url: String,
+    pub topic_prefix: String,                  // Topic/subject is prefix + event type unless overridden
+    pub topic_overrides: Vec<(String, String)>, // EVENT_BUS_TOPICS=swap.created=swaps,sync.completed=ops
+    pub relay_interval: Duration,              // Delay between outbox polls when idle
+    pub batch_size: u32,                       // Outbox rows published per poll
+}
+
+impl EventBusConfig {
+    pub fn from_env() -> Self {
+        Self {
+            kind: env_or("EVENT_BUS", EventBusKind::Off),
+            kafka_rest_url: env::var("EVENT_BUS_KAFKA_REST_URL").unwrap_or_default(),
+            nats_url: env::var("EVENT_BUS_NATS_URL").unwrap_or_else(|_| "nats://127.0.0.1:4222".to_string()),
+            topic_prefix: env::var("EVENT_BUS_TOPIC_PREFIX").unwrap_or_else(|_| "exchange.".to_string()),
+            topic_overrides: env_list("EVENT_BUS_TOPICS", "")
+                .into_iter()
+                .filter_map(|entry| {
+                    let (event_type, topic) = entry.split_once('=')?;
+                    Some((event_type.trim().to_string(), topic.trim().to_string()))
+                })
+                .collect(),
+            relay_interval: Duration::from_millis(env_or("EVENT_BUS_RELAY_INTERVAL_MS", 1000)),
+            batch_size: env_or("EVENT_BUS_BATCH_SIZE", 100),
+        }
+    }
+
+    /// Topic (Kafka) or subject (NATS) for an event type
+    pub fn topic_for(&self, event_type: &str) -> String {
+        self.topic_overrides
+            .iter()
+            .find(|(t, _)| t == event_type)
+            .map(|(_, topic)| topic.clone())
+            .unwrap_or_else(|| format!("{}{}", self.topic_prefix, event_type))
+    }
+}
+
+/// Card-to-crypto on-ramp settings; the on-ramp is off until ONRAMP_API_URL and ONRAMP_API_KEY are set
+#[derive(Debug, Clone)]
+pub struct OnrampConfig {
+    pub provider: String,               // Recorded on every order, e.g. "transak"
+    pub api_url: String,
+    pub api_key: String,
+    pub webhook_secret: Option<String>, // HMAC-SHA256 key for status webhooks; unset disables them
+    pub min_fiat_amount: Decimal,
+    pub max_fiat_amount: Decimal,
+}
+
+impl OnrampConfig {
+    pub fn from_env() -> Self {
+        Self {
+            provider: env::var("ONRAMP_PROVIDER").unwrap_or_else(|_| "transak".to_string()),
+            api_url: env::var("ONRAMP_API_URL").unwrap_or_default(),
+            api_key: env::var("ONRAMP_API_KEY").unwrap_or_default(),
+            webhook_secret: env::var("ONRAMP_WEBHOOK_SECRET").ok().filter(|s| !s.is_empty()),
+            min_fiat_amount: env_or("ONRAMP_MIN_FIAT_AMOUNT", Decimal::from(20)),
+            max_fiat_amount: env_or("ONRAMP_MAX_FIAT_AMOUNT", Decimal::from(5000)),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.api_url.is_empty() && !self.api_key.is_empty()
+    }
+}
+
+/// The deployment's own branding, used for requests that match no partner brand
+#[derive(Debug, Clone)]
+pub struct BrandingConfig {
+    pub name: String,
+    pub support_email: String,
+}
+
+impl BrandingConfig {
+    pub fn from_env() -> Self {
+        Self {
+            name: env::var("BRAND_NAME").unwrap_or_else(|_| "Exchange Platform".to_string()),
+            support_email: env::var("BRAND_SUPPORT_EMAIL").unwrap_or_else(|_| "support@localhost".to_string()),
+        }
+    }
+}
+
+impl Default for BrandingConfig {
+    fn default() -> Self {
+        Self {
+            name: "Exchange Platform".to_string(),
+            support_email: "support@localhost".to_string(),
+        }
+    }
+}
+
+/// Outgoing brand webhooks (status changes of a partner brand's swaps)
+#[derive(Debug, Clone)]
+pub struct BrandWebhookConfig {
+    pub secrets_key: Option<String>, // Base64 AES-256 key sealing signing secrets; unset disables webhooks
+    pub interval: Duration,          // Delay between drains of the queue
+    pub batch_size: u32,             // Deliveries claimed per drain
+    pub max_attempts: u32,           // Then the delivery is marked failed
+    pub timeout: Duration,           // Per delivery request
+    pub allow_private_targets: bool, // http and loopback/private addresses; local development only
+}
+
+impl BrandWebhookConfig {
+    pub fn from_env() -> Self {
+        Self {
+            secrets_key: env::var("BRAND_WEBHOOKS_KEY").ok().filter(|s| !s.is_empty()),
+            interval: Duration::from_secs(env_or("BRAND_WEBHOOKS_INTERVAL_SECS", 15)),
+            batch_size: env_or("BRAND_WEBHOOKS_BATCH_SIZE", 100),
+            max_attempts: env_or("BRAND_WEBHOOKS_MAX_ATTEMPTS", 8),
+            timeout: Duration::from_secs(env_or("BRAND_WEBHOOKS_TIMEOUT_SECS", 10)),
+            allow_private_targets: env_or("BRAND_WEBHOOKS_ALLOW_PRIVATE", false),
+        }
+    }
+}
+
+impl Default for BrandWebhookConfig {
+    fn default() -> Self {
+        Self {
+            secrets_key: None,
+            interval: Duration::from_secs(15),
+            batch_size: 100,
+            max_attempts: 8,
+            timeout: Duration::from_secs(10),
+            allow_private_targets: false,
+        }
+    }
+}
+
+/// Signed swap status links (POST /swap/{id}/share)
+#[derive(Debug, Clone)]
+pub struct ShareLinkConfig {
+    pub secret: Option<String>, // SHARE_LINK_SECRET; unset disables sharing
+    pub default_ttl_hours: u32,
+    pub max_ttl_hours: u32,     // Longest validity a caller may ask for
+}
+
+impl ShareLinkConfig {
+    pub fn from_env() -> Self {
+        Self {
+            // Never JWT_SECRET: a leaked link key must not be able to sign sessions
+            secret: env::var("SHARE_LINK_SECRET").ok().filter(|s| !s.is_empty()),
+            default_ttl_hours: env_or("SHARE_LINK_TTL_HOURS", 24),
+            max_ttl_hours: env_or("SHARE_LINK_MAX_TTL_HOURS", 168),
+        }
+    }
+}
+
+impl Default for ShareLinkConfig {
+    fn default() -> Self {
+        Self {
+            secret: None,
+            default_ttl_hours: 24,
+            max_ttl_hours: 168,
+        }
+    }
+}
+
+/// Sandbox swaps (`sandbox: true`), served by the mock provider instead of
+/// Trocador
+#[derive(Debug, Clone)]
+pub struct SandboxConfig {
+    pub enabled: bool,  // Off refuses new sandbox rates and swaps; existing ones still progress
+    pub step: Duration, // Time spent in each simulated status before the next
+}
+
+impl SandboxConfig {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: env_or("SANDBOX_ENABLED", true),
+            step: Duration::from_secs(env_or("SANDBOX_STEP_SECS", 30)),
+        }
+    }
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            step: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Payout address ownership proofs (POST /swap/addresses/challenge) and the
+/// per-swap USD limits they relax. Swaps are valued on the sending side with
+/// HIGH_VALUE_USD_PRICES; swaps from unpriced currencies are not limited.
+#[derive(Debug, Clone)]
+pub struct AddressVerificationConfig {
+    pub challenge_ttl: Duration,    // How long a challenge can be signed
+    pub max_swap_usd: f64,          // Per swap; 0 = no limit
+    pub verified_max_swap_usd: f64, // Per swap to a verified recipient; 0 = no limit
+}
+
+impl AddressVerificationConfig {
+    pub fn from_env() -> Self {
+        Self {
+            challenge_ttl: Duration::from_secs(env_or("ADDRESS_CHALLENGE_TTL_SECS", 600)),
+            max_swap_usd: env_or("SWAP_MAX_USD", 0.0),
+            verified_max_swap_usd: env_or("VERIFIED_SWAP_MAX_USD", 0.0),
+        }
+    }
+
+    /// Limit for a swap, given whether its recipient is a verified address
+    pub fn swap_limit_usd(&self, recipient_verified: bool) -> Option<f64> {
+        let limit = if recipient_verified { self.verified_max_swap_usd } else { self.max_swap_usd };
+        (limit > 0.0).then_some(limit)
+    }
+}
+
+impl Default for AddressVerificationConfig {
+    fn default() -> Self {
+        Self {
+            challenge_ttl: Duration::from_secs(600),
+            max_swap_usd: 0.0,
+            verified_max_swap_usd: 0.0,
+        }
+    }
+}
+
+/// Rolling per-user volume caps, enforced on `POST /swap/create` and shown at
+/// `GET /swap/limits`. Volume is kept per user and hour in `user_volume`,
+/// valued on the sending side at price-feed USD prices (HIGH_VALUE_USD_PRICES
+/// when the feed has none). Anonymous, sandbox and unpriced swaps are not
+/// counted.
+#[derive(Debug, Clone, Default)]
+pub struct VolumeLimitConfig {
+    pub daily_usd: f64,   // Swaps created in the last 24 hours; 0 = no limit
+    pub monthly_usd: f64, // Swaps created in the last 30 days; 0 = no limit
+}
+
+impl VolumeLimitConfig {
+    pub fn from_env() -> Self {
+        Self {
+            daily_usd: env_or("USER_VOLUME_24H_USD", 0.0),
+            monthly_usd: env_or("USER_VOLUME_30D_USD", 0.0),
+        }
+    }
+
+    pub fn daily_limit(&self) -> Option<f64> {
+        (self.daily_usd > 0.0).then_some(self.daily_usd)
+    }
+
+    pub fn monthly_limit(&self) -> Option<f64> {
+        (self.monthly_usd > 0.0).then_some(self.monthly_usd)
+    }
+}
+
+/// Retries of statements that hit a transient MySQL error (deadlock, lock
+/// wait timeout, dropped connection); see services::db_retry
+#[derive(Debug, Clone)]
+pub struct DbRetryConfig {
+    pub max_attempts: u32,    // Including the first; 1 disables retries
+    pub base_delay: Duration, // Before the first retry, doubled for each further one
+    pub max_delay: Duration,
+}
+
+impl DbRetryConfig {
+    pub fn from_env() -> Self {
+        Self {
+            max_attempts: env_or::<u32>("DB_RETRY_MAX_ATTEMPTS", 3).max(1),
+            base_delay: Duration::from_millis(env_or("DB_RETRY_BASE_DELAY_MS", 50)),
+            max_delay: Duration::from_millis(env_or("DB_RETRY_MAX_DELAY_MS", 1000)),
+        }
+    }
+}
+
+impl Default for DbRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(50),
+            max_delay: Duration::from_millis(1000),
+        }
+    }
+}
+
+/// Explorer lookups of a deposit address when a provider reports a swap
+/// underpaid (see services::deposit_check). An empty URL leaves that chain
+/// unchecked.
+#[derive(Debug, Clone)]
+pub struct DepositCheckConfig {
+    pub enabled: bool,
+    pub btc_url: Option<String>, // Esplora API base for Bitcoin
+    pub ltc_url: Option<String>, // Esplora API base for Litecoin
+    pub timeout: Duration,       // Per explorer request
+}
+
+impl DepositCheckConfig {
+    pub fn from_env() -> Self {
+        let url = |key: &str, default: &str| Some(env::var(key).unwrap_or_else(|_| default.to_string())).filter(|u| !u.is_empty());
+        Self {
+            enabled: env_or("DEPOSIT_CHECK_ENABLED", false),
+            btc_url: url("DEPOSIT_CHECK_BTC_URL", "https://blockstream.info/api"),
+            ltc_url: url("DEPOSIT_CHECK_LTC_URL", "https://litecoinspace.org/api"),
+            timeout: Duration::from_secs(env_or("DEPOSIT_CHECK_TIMEOUT_SECS", 10)),
+        }
+    }
+}
+
+/// The SwapService gRPC server (feature `grpc`), run next to the HTTP API
+/// on its own port
+#[derive(Debug, Clone)]
+pub struct GrpcConfig {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl GrpcConfig {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: env_or("GRPC_ENABLED", false),
+            port: env_or("GRPC_PORT", 50051),
+        }
+    }
+}
+
+/// Settings for the single-binary lightweight server (`exchange-lite`,
+/// feature `sqlite`): SQLite instead of MySQL, in-memory cache unless
+/// REDIS_URL is set
+#[derive(Debug, Clone)]
+pub struct LiteConfig {
+    pub database_url: String,   // sqlite:// URL; the file is created if missing
+    pub redis_url: String,      // memory:// keeps the cache in process
+    pub trocador_api_key: String,
+    pub port: u16,
+    pub sync_interval: Duration, // Delay between currency/provider syncs
+}
+
+impl LiteConfig {
+    pub fn from_env() -> Result<Self, String> {
+        dotenvy::dotenv().ok();
+
+        let database_url = env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite://exchange-lite.db".to_string());
+        if !database_url.starts_with("sqlite:") {
+            return Err("DATABASE_URL must be a sqlite: URL in lightweight mode".to_string());
+        }
+
+        let trocador_api_key = env::var("TROCADOR_API_KEY")
+            .ok()
+            .filter(|k| !k.is_empty())
+            .ok_or_else(|| "TROCADOR_API_KEY must be set".to_string())?;
+
+        Ok(Self {
+            database_url,
+            redis_url: env::var("REDIS_URL").unwrap_or_else(|_| "memory://".to_string()),
+            trocador_api_key,
+            port: env_or("PORT", 3000),
+            sync_interval: Duration::from_secs(env_or("SYNC_INTERVAL_SECS", 3600)),
+        })
+    }
 }
 
 impl Config {
@@ -22,13 +1318,26 @@ impl Config {
             .map_err(|_| "JWT_SECRET must be set".to_string())?;
 
         let trocador_api_key = env::var("TROCADOR_API_KEY")
-            .map_err(|_| "TROCADOR_API_KEY must be set".to_string())?;
+            .ok()
+            .filter(|k| !k.is_empty())
+            .ok_or_else(|| "TROCADOR_API_KEY must be set".to_string())?;
 
         Ok(Self {
             database_url,
             redis_url,
             jwt_secret,
             trocador_api_key,
+            sync_worker: SyncWorkerConfig::from_env(),
+            status_poller: StatusPollerConfig::from_env(),
+            provider_prober: ProviderProberConfig::from_env(),
+            cache_consistency: CacheConsistencyConfig::from_env(),
+            retention: RetentionConfig::from_env(),
+            cache_warmup: CacheWarmupConfig::from_env(),
+            event_bus: EventBusConfig::from_env(),
+            notifications: NotificationConfig::from_env(),
+            brand_webhooks: BrandWebhookConfig::from_env(),
+            reconciliation: ReconciliationConfig::from_env(),
+            grpc: GrpcConfig::from_env(),
         })
     }
 
@@ -36,3 +1345,184 @@ impl Config {
         &self.trocador_api_key
     }
 }
+
+/// Provider API keys kept in the database (see services::provider_credentials).
+/// Without a key, stored credentials are neither read nor written and
+/// providers use their environment keys.
+#[derive(Debug, Clone)]
+pub struct ProviderCredentialsConfig {
+    pub key: Option<String>, // Base64 of 32 random bytes; encrypts keys at rest
+    pub refresh_interval: Duration, // How often each instance picks up keys rotated elsewhere
+}
+
+impl ProviderCredentialsConfig {
+    pub fn from_env() -> Self {
+        Self {
+            key: env::var("PROVIDER_CREDENTIALS_KEY").ok().filter(|k| !k.is_empty()),
+            refresh_interval: Duration::from_secs(env_or("PROVIDER_CREDENTIALS_REFRESH_SECS", 60).max(1)),
+        }
+    }
+}
+
+/// Where cache calls go while Redis is unreachable
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DegradedMode {
+    Memory,   // A bounded in-process store; limits and locks hold per instance only
+    Disabled, // Reads miss, writes are dropped, rate limits and locks let everyone through
+}
+
+impl FromStr for DegradedMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "memory" => Ok(DegradedMode::Memory),
+            "disabled" | "off" => Ok(DegradedMode::Disabled),
+            other => Err(format!("Unknown degraded mode '{}'", other)),
+        }
+    }
+}
+
+/// Redis degradation policy and command timeout (see services::redis_cache)
+#[derive(Debug, Clone)]
+pub struct CacheDegradationConfig {
+    pub mode: DegradedMode,
+    pub retry_after: Duration,    // Time on the fallback before Redis is tried again
+    pub fallback_max_keys: usize, // Memory fallback size; least recently used keys go first
+    pub command_timeout: Duration, // Per command, connecting included; a timeout counts as unreachable
+}
+
+impl CacheDegradationConfig {
+    pub fn from_env() -> Self {
+        Self {
+            mode: env_or("REDIS_DEGRADED_MODE", DegradedMode::Memory),
+            retry_after: Duration::from_secs(env_or("REDIS_HEALTH_CHECK_SECS", 5).max(1)),
+            fallback_max_keys: env_or("REDIS_FALLBACK_MAX_KEYS", 10_000),
+            command_timeout: Duration::from_millis(env_or("REDIS_COMMAND_TIMEOUT_MS", 1000).max(1)),
+        }
+    }
+}
+
+impl Default for CacheDegradationConfig {
+    fn default() -> Self {
+        Self {
+            mode: DegradedMode::Memory,
+            retry_after: Duration::from_secs(5),
+            fallback_max_keys: 10_000,
+            command_timeout: Duration::from_millis(1000),
+        }
+    }
+}
+
+/// Fiat prices for the USD/EUR amounts on rates, swaps and history (see
+/// services::price_feed)
+#[derive(Debug, Clone)]
+pub struct PriceFeedConfig {
+    pub enabled: bool,
+    pub base_url: String,                  // CoinGecko-compatible API root
+    pub api_key: Option<String>,           // Sent as x-cg-demo-api-key
+    pub coin_ids: HashMap<String, String>, // Ticker (lowercase) -> coin id, e.g. btc -> bitcoin
+    pub cache_ttl: Duration,               // How long fetched prices are served
+    pub timeout: Duration,                 // Per request to the feed
+}
+
+impl PriceFeedConfig {
+    pub fn from_env() -> Self {
+        // PRICE_FEED_IDS="btc=bitcoin,xmr=monero"; tickers not listed get no fiat amounts
+        let coin_ids = env_list("PRICE_FEED_IDS", DEFAULT_PRICE_FEED_IDS)
+            .into_iter()
+            .filter_map(|entry| {
+                let (ticker, id) = entry.split_once('=')?;
+                Some((ticker.trim().to_lowercase(), id.trim().to_string()))
+            })
+            .filter(|(ticker, id)| !ticker.is_empty() && !id.is_empty())
+            .collect();
+
+        Self {
+            enabled: env_or("PRICE_FEED_ENABLED", false),
+            base_url: env::var("PRICE_FEED_URL").unwrap_or_else(|_| "https://api.coingecko.com/api/v3".to_string()),
+            api_key: env::var("PRICE_FEED_API_KEY").ok().filter(|k| !k.is_empty()),
+            coin_ids,
+            cache_ttl: Duration::from_secs(env_or("PRICE_FEED_CACHE_SECS", 60).max(1)),
+            timeout: Duration::from_millis(env_or("PRICE_FEED_TIMEOUT_MS", 2000).max(1)),
+        }
+    }
+}
+
+const DEFAULT_PRICE_FEED_IDS: &str = "btc=bitcoin,eth=ethereum,xmr=monero,ltc=litecoin,bch=bitcoin-cash,\
+doge=dogecoin,sol=solana,trx=tron,xrp=ripple,bnb=binancecoin,ada=cardano,dot=polkadot,\
+usdt=tether,usdc=usd-coin,dai=dai";
+
+/// Network fee estimates on quotes (see services::fee_estimator). Mempool
+/// APIs are keyed by ticker, EVM JSON-RPC endpoints by network.
+#[derive(Debug, Clone)]
+pub struct FeeEstimatorConfig {
+    pub enabled: bool,
+    pub mempool_urls: HashMap<String, String>, // Ticker (lowercase) -> mempool.space-compatible API root
+    pub evm_rpc_urls: HashMap<String, String>, // Network (lowercase) -> JSON-RPC URL
+    pub cache_ttl: Duration,                   // How long fetched fee rates are served
+    pub timeout: Duration,                     // Per request to a source
+}
+
+impl FeeEstimatorConfig {
+    pub fn from_env() -> Self {
+        // FEE_ESTIMATOR_MEMPOOL_URLS="btc=https://mempool.space/api"
+        // FEE_ESTIMATOR_EVM_RPC_URLS="erc20=https://cloudflare-eth.com,bep20=https://bsc-dataseed.binance.org"
+        let urls = |key: &str, default: &str| -> HashMap<String, String> {
+            env_list(key, default)
+                .into_iter()
+                .filter_map(|entry| {
+                    let (name, url) = entry.split_once('=')?;
+                    Some((name.trim().to_lowercase(), url.trim().to_string()))
+                })
+                .filter(|(name, url)| !name.is_empty() && !url.is_empty())
+                .collect()
+        };
+
+        Self {
+            enabled: env_or("FEE_ESTIMATOR_ENABLED", false),
+            mempool_urls: urls("FEE_ESTIMATOR_MEMPOOL_URLS", DEFAULT_FEE_MEMPOOL_URLS),
+            evm_rpc_urls: urls("FEE_ESTIMATOR_EVM_RPC_URLS", DEFAULT_FEE_EVM_RPC_URLS),
+            cache_ttl: Duration::from_secs(env_or("FEE_ESTIMATOR_CACHE_SECS", 60).max(1)),
+            timeout: Duration::from_millis(env_or("FEE_ESTIMATOR_TIMEOUT_MS", 2000).max(1)),
+        }
+    }
+}
+
+const DEFAULT_FEE_MEMPOOL_URLS: &str = "btc=https://mempool.space/api,ltc=https://litecoinspace.org/api";
+
+const DEFAULT_FEE_EVM_RPC_URLS: &str = "erc20=https://cloudflare-eth.com,bep20=https://bsc-dataseed.binance.org,\
+polygon=https://polygon-rpc.com";
+
+/// Block explorer links for swap transactions (see services::block_explorer).
+/// Native coins are keyed by ticker, tokens by network; `{tx}` in a template
+/// is replaced with the transaction hash.
+#[derive(Debug, Clone)]
+pub struct BlockExplorerConfig {
+    pub tx_urls: HashMap<String, String>, // Ticker or network (lowercase) -> URL template
+}
+
+impl BlockExplorerConfig {
+    pub fn from_env() -> Self {
+        // BLOCK_EXPLORER_URLS="btc=https://mempool.space/tx/{tx},erc20=https://etherscan.io/tx/{tx}"
+        let tx_urls = env_list("BLOCK_EXPLORER_URLS", DEFAULT_BLOCK_EXPLORER_URLS)
+            .into_iter()
+            .filter_map(|entry| {
+                let (key, template) = entry.split_once('=')?;
+                Some((key.trim().to_lowercase(), template.trim().to_string()))
+            })
+            .filter(|(key, template)| !key.is_empty() && template.contains("{tx}"))
+            .collect();
+
+        Self { tx_urls }
+    }
+}
+
+const DEFAULT_BLOCK_EXPLORER_URLS: &str = "btc=https://mempool.space/tx/{tx},\
+ltc=https://litecoinspace.org/tx/{tx},eth=https://etherscan.io/tx/{tx},xmr=https://xmrchain.net/tx/{tx},\
+bch=https://blockchair.com/bitcoin-cash/transaction/{tx},doge=https://blockchair.com/dogecoin/transaction/{tx},\
+sol=https://solscan.io/tx/{tx},trx=https://tronscan.org/#/transaction/{tx},xrp=https://livenet.xrpl.org/transactions/{tx},\
+erc20=https://etherscan.io/tx/{tx},bep20=https://bscscan.com/tx/{tx},bsc=https://bscscan.com/tx/{tx},\
+trc20=https://tronscan.org/#/transaction/{tx},polygon=https://polygonscan.com/tx/{tx},\
+arbitrum=https://arbiscan.io/tx/{tx},optimism=https://optimistic.etherscan.io/tx/{tx},base=https://basescan.org/tx/{tx},\
+spl=https://solscan.io/tx/{tx}";
@@ -1,48 +1,244 @@
+#[cfg(feature = "client")]
+pub mod client;
 pub mod config;
 pub mod modules;
 pub mod services;
 
-use axum::{middleware, routing::get, Json, Router};
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    middleware,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
 use serde::Serialize;
 use std::sync::Arc;
 use tower_http::{cors::CorsLayer, limit::RequestBodyLimitLayer, trace::TraceLayer};
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
 
 use config::DbPool;
+use modules::account::account_routes;
+use modules::admin::admin_routes;
+use modules::affiliate::affiliate_routes;
 use modules::auth::auth_routes;
+use modules::brand::brand_routes;
+use modules::brand::webhooks::cipher_from_config;
+use modules::email::email_routes;
+use modules::onramp::onramp_routes;
+use modules::onramp::provider::{HttpOnrampProvider, OnrampProvider};
+use modules::swap::crud::SwapCrud;
+use modules::swap::openapi::{SwapApiDoc, OPENAPI_JSON_PATH, SWAGGER_UI_PATH};
+use modules::swap::schema::SyncStatusResponse;
 use modules::swap::swap_routes;
+use modules::swap::worker::spawn_status_poller;
+use modules::widget::{widget_cors, widget_routes};
 use services::jwt::JwtService;
-use services::rate_limit::{create_rate_limiter, RateLimitLayer};
+use config::environment::{
+    AddressVerificationConfig, AdmissionConfig, AnalyticsConfig, BrandWebhookConfig, BrandingConfig, EmailConfig,
+    EventBusConfig, FeeEstimatorConfig, HighValueConfig, OnrampConfig, PriceFeedConfig, ProviderCredentialsConfig,
+    ProviderFallbackConfig, ProviderSelectionConfig, RateLimitBypassConfig, RequestLogConfig, RetentionConfig,
+    RouteRateLimitConfig, ShareLinkConfig, SloConfig, StatusPollerConfig, VolumeLimitConfig,
+};
+use services::admission::{admit_swap_creates, AdmissionController};
+use services::analytics::Analytics;
+use services::api_usage::{track_api_usage, ApiUsage};
+use services::audit::audit_mutations;
+use services::branding::{CurrentBrand, PublicBrand};
+use services::cache_warmup::{self, WarmupStatus, WarmupSnapshot};
+use services::email::{EmailService, LogSender};
+use services::encryption::SecretCipher;
+use services::outbox::Outbox;
+use services::fee_estimator::FeeEstimator;
+use services::price_feed::PriceFeed;
+use services::rate_guard::RateGuard;
+use services::rate_limit::{create_rate_limiter, RateLimitLayer, RateLimitMetrics};
+use services::rate_limiter::{limit_by_route, RouteRateLimiter};
+use services::request_id::propagate_request_id;
+use services::request_logging::log_requests;
 use services::security::security_headers;
-use services::redis_cache::RedisService;
+use services::slo::{spawn_slo_monitor, track_latency, SloTracker};
+use services::tenant::TenantId;
+use services::provider_credentials::{spawn_credential_refresh, ProviderCredentials};
+use services::trocador::TrocadorClient;
+use services::redis_cache::{CacheBackend, RedisService};
 
 pub struct AppState {
     pub db: DbPool,
     pub redis: RedisService, // Changed from redis::Client
     pub http_client: reqwest::Client,
+    pub trocador: Option<TrocadorClient>, // Shared by every request; None without a stored key or TROCADOR_API_KEY
+    pub provider_credentials: ProviderCredentials, // Stored provider keys, rotated at /admin/provider-credentials
+    pub price_feed: PriceFeed, // USD/EUR prices for rates, swaps and history; disabled unless PRICE_FEED_ENABLED
+    pub fee_estimator: FeeEstimator, // Network fees on rates; disabled unless FEE_ESTIMATOR_ENABLED
+    pub rate_guard: RateGuard, // Sanity bounds on provider rates (RATE_GUARD_*)
+    pub provider_selection: ProviderSelectionConfig, // Policy for swaps created without a provider
+    pub provider_fallback: ProviderFallbackConfig, // SWAP_FALLBACK_TOLERANCE_PCT for allow_fallback swaps
+    pub high_value: HighValueConfig, // High-value threshold and reference USD prices (HIGH_VALUE_*)
     pub jwt_service: JwtService,
+    pub rate_limit_metrics: Arc<RateLimitMetrics>,
+    pub rate_limit_bypass: RateLimitBypassConfig, // Also exempts callers from per-route limits
+    pub route_limiter: RouteRateLimiter,
+    pub admission: AdmissionController, // Bounds concurrent swap creates
+    pub request_log: RequestLogConfig,
+    pub email: EmailService,
+    pub email_webhook_token: Option<String>,
+    pub trocador_webhook_secret: Option<String>, // HMAC key for /swap/webhook/trocador; unset disables it
+    pub analytics: Analytics,
+    pub api_usage: ApiUsage, // Per-brand request counts behind GET /account/analytics
+    pub outbox: Outbox,
+    pub onramp: Option<Arc<dyn OnrampProvider>>, // None until the on-ramp is configured
+    pub onramp_config: OnrampConfig,
+    pub retention_config: RetentionConfig, // Windows shown by GET /admin/retention/report
+    pub branding: BrandingConfig,          // Brand for requests that match no partner brand
+    pub share_links: ShareLinkConfig,      // Key and validity of swap status share links
+    pub address_verification: AddressVerificationConfig, // Ownership challenges and per-swap limits
+    pub volume_limits: VolumeLimitConfig,  // Rolling per-user swap volume caps
+    pub slo: Arc<SloTracker>,              // Response time SLOs behind GET /admin/slo
+    pub tenant: TenantId,                  // Tenant for requests that match no partner brand
+    pub brand_webhooks: BrandWebhookConfig,
+    pub brand_webhook_cipher: Option<SecretCipher>, // None until BRAND_WEBHOOKS_KEY is set
 }
 
+/// Largest accepted request body
+pub const MAX_BODY_BYTES: usize = 1024 * 100;
+
 pub async fn create_app(db: DbPool, redis: RedisService, jwt_service: JwtService) -> Router {
+    create_router(create_state(db, redis, jwt_service).await)
+}
+
+/// Shared state of the HTTP API and the gRPC SwapService; starts the status
+/// poller and SLO monitor that work on it
+pub async fn create_state(db: DbPool, redis: RedisService, jwt_service: JwtService) -> Arc<AppState> {
+    let email_config = EmailConfig::from_env();
+    let email = EmailService::from_config(&email_config, db.clone(), redis.clone()).unwrap_or_else(|e| {
+        tracing::error!("{}; falling back to the log email backend", e);
+        EmailService::with_sender(Arc::new(LogSender), &email_config, db.clone(), redis.clone())
+    });
+
+    let analytics = Analytics::from_config(&AnalyticsConfig::from_env(), db.clone());
+    let api_usage = ApiUsage::spawn(db.clone());
+    let outbox = Outbox::from_config(&EventBusConfig::from_env(), db.clone());
+    services::schema_drift::monitor().set_outbox(outbox.clone());
+
+    let slo_config = SloConfig::from_env();
+
+    let credentials_config = ProviderCredentialsConfig::from_env();
+    let provider_credentials = ProviderCredentials::from_config(&credentials_config, db.clone());
+    let trocador = provider_credentials.trocador_client().await;
+    if let (true, Some(client)) = (provider_credentials.is_enabled(), &trocador) {
+        spawn_credential_refresh(provider_credentials.clone(), client.clone(), credentials_config.refresh_interval);
+    }
+
+    let http_client = reqwest::Client::new();
+    let onramp_config = OnrampConfig::from_env();
+    let onramp = onramp_config
+        .is_enabled()
+        .then(|| Arc::new(HttpOnrampProvider::new(http_client.clone(), &onramp_config)) as Arc<dyn OnrampProvider>);
+
+    let brand_webhooks = BrandWebhookConfig::from_env();
+    let brand_webhook_cipher = cipher_from_config(&brand_webhooks);
+
     let state = Arc::new(AppState {
         db,
-        redis,
-        http_client: reqwest::Client::new(),
+        redis: redis.clone(),
+        http_client,
+        trocador,
+        provider_credentials,
+        price_feed: PriceFeed::from_config(&PriceFeedConfig::from_env(), Some(redis.clone())),
+        fee_estimator: FeeEstimator::from_config(&FeeEstimatorConfig::from_env(), Some(redis.clone())),
+        rate_guard: RateGuard::from_env(),
+        provider_selection: ProviderSelectionConfig::from_env(),
+        provider_fallback: ProviderFallbackConfig::from_env(),
+        high_value: HighValueConfig::from_env(),
         jwt_service,
+        rate_limit_metrics: Arc::new(RateLimitMetrics::default()),
+        rate_limit_bypass: RateLimitBypassConfig::from_env(),
+        route_limiter: RouteRateLimiter::new(redis.clone(), RouteRateLimitConfig::from_env()),
+        admission: AdmissionController::new(AdmissionConfig::from_env()),
+        request_log: RequestLogConfig::from_env(),
+        email,
+        email_webhook_token: email_config.webhook_token,
+        trocador_webhook_secret: std::env::var("TROCADOR_WEBHOOK_SECRET").ok().filter(|s| !s.is_empty()),
+        analytics,
+        api_usage,
+        outbox,
+        onramp,
+        onramp_config,
+        retention_config: RetentionConfig::from_env(),
+        branding: BrandingConfig::from_env(),
+        share_links: ShareLinkConfig::from_env(),
+        address_verification: AddressVerificationConfig::from_env(),
+        volume_limits: VolumeLimitConfig::from_env(),
+        slo: Arc::new(SloTracker::new(&slo_config)),
+        tenant: TenantId::from_env(),
+        brand_webhooks,
+        brand_webhook_cipher,
     });
 
-    // Rate limit: burst of 10, then 1 per minute
+    let poller_config = StatusPollerConfig::from_env();
+    if poller_config.enabled {
+        spawn_status_poller(
+            state.db.clone(),
+            state.redis.clone(),
+            state.outbox.clone(),
+            state.analytics.clone(),
+            state.trocador.clone(),
+            poller_config,
+        );
+    } else {
+        tracing::info!("Status poller disabled");
+    }
+
+    if slo_config.enabled && !slo_config.objectives.is_empty() {
+        spawn_slo_monitor(state.slo.clone(), state.outbox.clone(), slo_config.check_interval);
+    }
+
+    state
+}
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+
+    // Rate limit: burst of 10, then 1 per minute (allowlisted internal traffic is exempt).
+    // Route groups such as /swap/create also get per-caller limits (ROUTE_RATE_LIMITS).
     let rate_limiter = create_rate_limiter(10);
+    let rate_limit_layer = RateLimitLayer::new(rate_limiter)
+        .with_bypass(state.rate_limit_bypass.clone())
+        .with_metrics(state.rate_limit_metrics.clone());
 
     Router::new()
         .route("/", get(root))
         .route("/health", get(health_check))
+        .route("/ready", get(readiness_check))
+        .route("/version", get(version_info))
+        .route("/metrics", get(prometheus_metrics))
+        .route("/brand", get(brand_info))
+        .nest("/brand", brand_routes())
         .nest("/auth", auth_routes())
+        .nest("/account", account_routes())
         .nest("/swap", swap_routes())
+        .nest("/admin", admin_routes())
+        .nest("/webhooks/email", email_routes())
+        .nest("/onramp", onramp_routes())
+        .nest("/affiliate", affiliate_routes())
+        .nest("/widget", widget_routes())
+        .merge(SwaggerUi::new(SWAGGER_UI_PATH).url(OPENAPI_JSON_PATH, SwapApiDoc::openapi()))
+        .layer(middleware::from_fn_with_state(state.clone(), admit_swap_creates))
+        .layer(middleware::from_fn_with_state(state.clone(), track_latency))
+        .layer(middleware::from_fn_with_state(state.clone(), audit_mutations))
         .layer(middleware::from_fn(security_headers))
-        .layer(RequestBodyLimitLayer::new(1024 * 100)) // 100KB max body
-        .layer(RateLimitLayer::new(rate_limiter))
+        .layer(RequestBodyLimitLayer::new(MAX_BODY_BYTES)) // 100KB max body
+        .layer(middleware::from_fn_with_state(state.clone(), limit_by_route))
+        .layer(rate_limit_layer)
+        .layer(middleware::from_fn_with_state(state.clone(), track_api_usage))
+        .layer(middleware::from_fn_with_state(state.clone(), log_requests))
         .layer(TraceLayer::new_for_http())
         .layer(CorsLayer::permissive())
+        // Widget keys only answer their allowed origins, whatever the layer above allows
+        .layer(middleware::from_fn_with_state(state.clone(), widget_cors))
+        // Outermost, so every layer above logs and answers under the request's ID
+        .layer(middleware::from_fn(propagate_request_id))
         .with_state(state)
 }
 
@@ -62,3 +258,105 @@ async fn health_check() -> Json<HealthResponse> {
         version: env!("CARGO_PKG_VERSION"),
     })
 }
+
+#[derive(Serialize)]
+struct ReadinessResponse {
+    status: &'static str,
+    database: bool,
+    cache: CacheBackend, // Redis, or what stands in for it while Redis is down
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sync: Option<SyncStatusResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_warmup: Option<WarmupSnapshot>,
+}
+
+/// Ready once the database answers and startup cache priming is over;
+/// reports the last sync runs alongside
+async fn readiness_check(
+    State(state): State<Arc<AppState>>,
+) -> (StatusCode, Json<ReadinessResponse>) {
+    let database = sqlx::query("SELECT 1").execute(&state.db).await.is_ok();
+    // A degraded cache keeps serving, so it does not make the instance unready
+    let cache = state.redis.check_health().await;
+
+    if !database {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ReadinessResponse { status: "unavailable", database, cache, sync: None, cache_warmup: None }),
+        );
+    }
+
+    let warmup = cache_warmup::warmup().snapshot();
+    let cache_warmup = (warmup.status != WarmupStatus::NotStarted).then_some(warmup);
+    if cache_warmup.as_ref().is_some_and(|w| w.status == WarmupStatus::Warming) {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ReadinessResponse { status: "warming", database, cache, sync: None, cache_warmup }),
+        );
+    }
+
+    let sync = SwapCrud::new(state.db.clone(), None).get_sync_status().await.ok();
+
+    (StatusCode::OK, Json(ReadinessResponse { status: "ready", database, cache, sync, cache_warmup }))
+}
+
+#[derive(Serialize)]
+struct VersionResponse {
+    version: &'static str,
+    git_commit: &'static str,
+    built_at: Option<chrono::DateTime<chrono::Utc>>,
+    features: Vec<&'static str>,
+    integrations: Vec<&'static str>,
+}
+
+/// What is deployed: crate version, commit, build time, compiled features and configured providers
+async fn version_info(State(state): State<Arc<AppState>>) -> Json<VersionResponse> {
+    let built_at = env!("BUILD_TIMESTAMP")
+        .parse::<i64>()
+        .ok()
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0));
+
+    let features = [
+        ("client", cfg!(feature = "client")),
+        ("grpc", cfg!(feature = "grpc")),
+        ("nats", cfg!(feature = "nats")),
+        ("sqlite", cfg!(feature = "sqlite")),
+    ]
+    .into_iter()
+    .filter_map(|(name, enabled)| enabled.then_some(name))
+    .collect();
+
+    // What this instance is actually running with, stored keys included
+    let integrations = [
+        ("trocador", state.trocador.is_some()),
+        ("provider_credentials", state.provider_credentials.is_enabled()),
+        ("trocador_webhook", state.trocador_webhook_secret.is_some()),
+        ("onramp", state.onramp.is_some()),
+        ("price_feed", state.price_feed.is_enabled()),
+        ("fee_estimator", state.fee_estimator.is_enabled()),
+    ]
+    .into_iter()
+    .filter_map(|(name, configured)| configured.then_some(name))
+    .collect();
+
+    Json(VersionResponse {
+        version: env!("CARGO_PKG_VERSION"),
+        git_commit: env!("GIT_COMMIT"),
+        built_at,
+        features,
+        integrations,
+    })
+}
+
+/// Process metrics in the Prometheus text exposition format
+async fn prometheus_metrics() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        services::metrics::metrics().render(),
+    )
+}
+
+/// Name, support contact and offering of the brand this request is served under
+async fn brand_info(CurrentBrand(brand): CurrentBrand) -> Json<PublicBrand> {
+    Json(brand.public())
+}
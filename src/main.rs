@@ -1,17 +1,36 @@
-use exchange_shared::config::{environment::Config, init_db};
+use exchange_shared::config::environment::{BrandingConfig, Config, EmailConfig};
+use exchange_shared::config::init_db;
+use exchange_shared::modules::brand::webhooks::{cipher_from_config, spawn_brand_webhook_sender, BrandWebhookSender};
+use exchange_shared::modules::swap::consistency::spawn_consistency_checker;
+use exchange_shared::modules::swap::prober::spawn_provider_prober;
+use exchange_shared::modules::swap::sync_worker::spawn_sync_worker;
+use exchange_shared::services::cache_warmup::spawn_cache_warmup;
+use exchange_shared::services::email::{EmailService, LogSender};
+use exchange_shared::services::event_bus::publisher_from_config;
+use exchange_shared::services::notifications::{spawn_notification_sender, NotificationSender};
+use exchange_shared::services::outbox::{spawn_outbox_relay, Outbox};
+use exchange_shared::services::reconciliation::{spawn_reconciliation_worker, ReportDelivery};
+use exchange_shared::services::retention::spawn_retention_worker;
+use exchange_shared::services::trocador::TrocadorClient;
 use exchange_shared::services::{jwt::JwtService, redis_cache::RedisService};
+use std::net::SocketAddr;
+use std::sync::Arc;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
 async fn main() {
     dotenvy::dotenv().ok();
 
+    // LOG_FORMAT=json switches to structured JSON output
+    let json_logs = std::env::var("LOG_FORMAT").is_ok_and(|f| f.eq_ignore_ascii_case("json"));
+
     tracing_subscriber::registry()
         .with(
             tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "exchange_shared=debug,tower_http=debug".into()),
+                .unwrap_or_else(|_| "exchange_shared=debug,tower_http=debug,http_request=info".into()),
         )
-        .with(tracing_subscriber::fmt::layer())
+        .with(json_logs.then(|| tracing_subscriber::fmt::layer().json()))
+        .with((!json_logs).then(tracing_subscriber::fmt::layer))
         .init();
 
     // Load configuration
@@ -24,11 +43,109 @@ async fn main() {
     let redis_service = RedisService::new(&config.redis_url);
     tracing::info!("Connected to Redis");
 
-    let jwt_service = JwtService::new(config.jwt_secret);
+    let jwt_service = JwtService::new(config.jwt_secret.clone());
+
+    let state = exchange_shared::create_state(db.clone(), redis_service.clone(), jwt_service).await;
+
+    // The workers share the state's client, so a key rotated at
+    // /admin/provider-credentials reaches them too
+    let trocador = state
+        .trocador
+        .clone()
+        .unwrap_or_else(|| TrocadorClient::new(config.trocador_api_key().to_string()));
+
+    if config.sync_worker.enabled {
+        let outbox = Outbox::from_config(&config.event_bus, db.clone());
+        spawn_sync_worker(db.clone(), redis_service.clone(), outbox, trocador.clone(), config.sync_worker.clone());
+    } else {
+        tracing::info!("Sync worker disabled");
+    }
+
+    if config.provider_prober.enabled {
+        spawn_provider_prober(db.clone(), redis_service.clone(), trocador, config.provider_prober.clone());
+    } else {
+        tracing::info!("Provider prober disabled");
+    }
+
+    if config.cache_consistency.enabled {
+        spawn_consistency_checker(db.clone(), redis_service.clone(), config.cache_consistency.clone());
+    } else {
+        tracing::info!("Cache consistency checker disabled");
+    }
+
+    if config.retention.enabled {
+        spawn_retention_worker(db.clone(), redis_service.clone(), config.retention.clone());
+    } else {
+        tracing::info!("Retention worker disabled");
+    }
+
+    match publisher_from_config(&config.event_bus).await {
+        Ok(Some(publisher)) => {
+            spawn_outbox_relay(db.clone(), redis_service.clone(), publisher, config.event_bus.clone());
+        }
+        Ok(None) => tracing::info!("Event bus disabled"),
+        Err(e) => tracing::error!("Event bus not started: {}", e),
+    }
+
+    // Falls back to logging messages when the configured backend cannot start
+    let email_service = || {
+        let email_config = EmailConfig::from_env();
+        EmailService::from_config(&email_config, db.clone(), redis_service.clone()).unwrap_or_else(|e| {
+            tracing::error!("{}; falling back to the log email backend", e);
+            EmailService::with_sender(Arc::new(LogSender), &email_config, db.clone(), redis_service.clone())
+        })
+    };
+
+    if config.notifications.enabled {
+        let email = email_service();
+        let sender = NotificationSender::from_config(
+            db.clone(),
+            redis_service.clone(),
+            email,
+            &BrandingConfig::from_env(),
+            &config.notifications,
+        );
+        spawn_notification_sender(sender, redis_service.clone(), config.notifications.clone());
+    } else {
+        tracing::info!("Notification sender disabled");
+    }
+
+    match cipher_from_config(&config.brand_webhooks) {
+        Some(cipher) => {
+            let sender = BrandWebhookSender::from_config(db.clone(), cipher, &config.brand_webhooks);
+            spawn_brand_webhook_sender(sender, config.brand_webhooks.clone());
+        }
+        None => tracing::info!("Brand webhook sender disabled"),
+    }
+
+    if config.reconciliation.enabled {
+        let delivery = ReportDelivery::new(email_service(), &config.reconciliation);
+        spawn_reconciliation_worker(db.clone(), redis_service.clone(), delivery, config.reconciliation.clone());
+    } else {
+        tracing::info!("Reconciliation worker disabled");
+    }
+
+    if config.cache_warmup.enabled {
+        spawn_cache_warmup(db.clone(), redis_service.clone(), config.cache_warmup.timeout);
+    }
+
+    #[cfg(feature = "grpc")]
+    if config.grpc.enabled {
+        exchange_shared::modules::swap::grpc::spawn_grpc_server(state.clone(), config.grpc.port);
+    } else {
+        tracing::info!("gRPC server disabled");
+    }
+    #[cfg(not(feature = "grpc"))]
+    if config.grpc.enabled {
+        tracing::warn!("GRPC_ENABLED is set but this build has no gRPC support (feature grpc)");
+    }
 
-    let app = exchange_shared::create_app(db, redis_service, jwt_service).await;
+    let app = exchange_shared::create_router(state);
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
     tracing::info!("Server running on http://localhost:3000");
-    axum::serve(listener, app).await.unwrap();
+    // Peer addresses key anonymous callers' per-route rate limits
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+        .unwrap();
 }
@@ -0,0 +1,55 @@
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use std::sync::Arc;
+
+use crate::AppState;
+use crate::services::branding::BrandRegistry;
+use super::crud::{AccountCrud, AccountError};
+use super::schema::{AccountAnalyticsResponse, AccountErrorResponse, AnalyticsQuery};
+
+type AccountResult<T> = Result<Json<T>, (StatusCode, Json<AccountErrorResponse>)>;
+
+/// Default reporting window, in days
+const DEFAULT_ANALYTICS_DAYS: u32 = 30;
+
+fn error_response(e: AccountError) -> (StatusCode, Json<AccountErrorResponse>) {
+    let status = match e {
+        AccountError::Unauthorized => StatusCode::UNAUTHORIZED,
+        AccountError::InvalidInput(_) => StatusCode::BAD_REQUEST,
+        AccountError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(AccountErrorResponse::new(e.to_string())))
+}
+
+// =============================================================================
+// GET /account/analytics - The calling integrator's API usage and swap funnel
+// =============================================================================
+
+pub async fn get_analytics(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<AnalyticsQuery>,
+) -> AccountResult<AccountAnalyticsResponse> {
+    // Only the key identifies an integrator here; a matching Host is not enough
+    let api_key = headers
+        .get("x-api-key")
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| error_response(AccountError::Unauthorized))?;
+
+    let brand = BrandRegistry::new(state.db.clone(), Some(state.redis.clone()))
+        .resolve(Some(api_key), None)
+        .await
+        .ok_or_else(|| error_response(AccountError::Unauthorized))?;
+
+    let response = AccountCrud::new(state.db.clone())
+        .get_analytics(&brand.slug, query.days.unwrap_or(DEFAULT_ANALYTICS_DAYS))
+        .await
+        .map_err(error_response)?;
+
+    Ok(Json(response))
+}
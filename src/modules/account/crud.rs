@@ -0,0 +1,174 @@
+use chrono::{Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sqlx::{MySql, Pool};
+use std::collections::BTreeMap;
+
+use super::schema::{AccountAnalyticsResponse, DailyUsage, DailyVolume, EndpointUsage, SwapConversion};
+
+/// Longest reporting window; usage rows are kept for the analytics retention period
+pub const MAX_ANALYTICS_DAYS: u32 = 90;
+
+/// Endpoints counted as asking for a quote
+const QUOTE_ENDPOINTS: [&str; 2] = ["GET /swap/rates", "POST /swap/quote"];
+
+// =============================================================================
+// ACCOUNT ERROR
+// =============================================================================
+
+#[derive(Debug)]
+pub enum AccountError {
+    Unauthorized,
+    InvalidInput(String),
+    DatabaseError(String),
+}
+
+impl std::fmt::Display for AccountError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccountError::Unauthorized => write!(f, "A valid X-API-Key is required"),
+            AccountError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
+            AccountError::DatabaseError(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl From<sqlx::Error> for AccountError {
+    fn from(err: sqlx::Error) -> Self {
+        AccountError::DatabaseError(err.to_string())
+    }
+}
+
+// =============================================================================
+// ACCOUNT CRUD
+// =============================================================================
+
+pub struct AccountCrud {
+    pool: Pool<MySql>,
+}
+
+impl AccountCrud {
+    pub fn new(pool: Pool<MySql>) -> Self {
+        Self { pool }
+    }
+
+    /// A brand's request counts, swap conversion and volume over the last `days` days.
+    /// Request counts lag by up to a minute while instances buffer them.
+    pub async fn get_analytics(&self, brand: &str, days: u32) -> Result<AccountAnalyticsResponse, AccountError> {
+        if days == 0 || days > MAX_ANALYTICS_DAYS {
+            return Err(AccountError::InvalidInput(format!(
+                "days must be between 1 and {}",
+                MAX_ANALYTICS_DAYS
+            )));
+        }
+        let since = Utc::now() - Duration::days(days as i64);
+
+        let endpoint_rows: Vec<(String, i64, i64, i64)> = sqlx::query_as(
+            "SELECT endpoint, CAST(SUM(requests) AS SIGNED), CAST(SUM(client_errors) AS SIGNED),
+                    CAST(SUM(server_errors) AS SIGNED)
+             FROM api_usage_hourly
+             WHERE brand = ? AND hour_start >= ?
+             GROUP BY endpoint
+             ORDER BY SUM(requests) DESC, endpoint",
+        )
+        .bind(brand)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let endpoints: Vec<EndpointUsage> = endpoint_rows
+            .into_iter()
+            .map(|(endpoint, requests, client_errors, server_errors)| EndpointUsage {
+                endpoint,
+                requests: requests as u64,
+                client_errors: client_errors as u64,
+                server_errors: server_errors as u64,
+                error_rate: ratio((client_errors + server_errors) as u64, requests as u64),
+            })
+            .collect();
+
+        let usage_rows: Vec<(NaiveDate, i64, i64)> = sqlx::query_as(
+            "SELECT DATE(hour_start), CAST(SUM(requests) AS SIGNED),
+                    CAST(SUM(client_errors + server_errors) AS SIGNED)
+             FROM api_usage_hourly
+             WHERE brand = ? AND hour_start >= ?
+             GROUP BY DATE(hour_start)",
+        )
+        .bind(brand)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let swap_rows: Vec<(NaiveDate, i64, i64)> = sqlx::query_as(
+            "SELECT DATE(created_at), COUNT(*), CAST(SUM(status = 'completed') AS SIGNED)
+             FROM swaps
+             WHERE brand = ? AND created_at >= ?
+             GROUP BY DATE(created_at)",
+        )
+        .bind(brand)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut daily: BTreeMap<NaiveDate, DailyUsage> = BTreeMap::new();
+        let day = |date| DailyUsage { date, requests: 0, errors: 0, swaps_created: 0, swaps_completed: 0 };
+        for (date, requests, errors) in usage_rows {
+            let entry = daily.entry(date).or_insert_with(|| day(date));
+            entry.requests = requests as u64;
+            entry.errors = errors as u64;
+        }
+        for (date, created, completed) in swap_rows {
+            let entry = daily.entry(date).or_insert_with(|| day(date));
+            entry.swaps_created = created as u64;
+            entry.swaps_completed = completed as u64;
+        }
+
+        let quote_requests = endpoints
+            .iter()
+            .filter(|e| QUOTE_ENDPOINTS.contains(&e.endpoint.as_str()))
+            .map(|e| e.requests)
+            .sum();
+        let swaps_created = daily.values().map(|d| d.swaps_created).sum();
+        let swaps_completed = daily.values().map(|d| d.swaps_completed).sum();
+
+        let volume_rows: Vec<(NaiveDate, String, i64, Decimal)> = sqlx::query_as(
+            "SELECT DATE(created_at), LOWER(from_currency), COUNT(*), SUM(amount)
+             FROM swaps
+             WHERE brand = ? AND created_at >= ?
+             GROUP BY DATE(created_at), LOWER(from_currency)
+             ORDER BY DATE(created_at), LOWER(from_currency)",
+        )
+        .bind(brand)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let volume = volume_rows
+            .into_iter()
+            .map(|(date, currency, swaps, amount)| DailyVolume { date, currency, swaps: swaps as u64, amount })
+            .collect();
+
+        Ok(AccountAnalyticsResponse {
+            brand: brand.to_string(),
+            days,
+            since,
+            endpoints,
+            conversion: SwapConversion {
+                quote_requests,
+                swaps_created,
+                swaps_completed,
+                quote_to_swap_rate: ratio(swaps_created, quote_requests),
+                completion_rate: ratio(swaps_completed, swaps_created),
+            },
+            daily: daily.into_values().collect(),
+            volume,
+        })
+    }
+}
+
+fn ratio(part: u64, whole: u64) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
@@ -0,0 +1,6 @@
+pub mod schema;
+pub mod crud;
+pub mod controller;
+pub mod routes;
+
+pub use routes::account_routes;
@@ -0,0 +1,10 @@
+use axum::{routing::get, Router};
+use std::sync::Arc;
+
+use crate::AppState;
+use super::controller::get_analytics;
+
+/// Self-serve endpoints for integrators, authenticated by their X-API-Key
+pub fn account_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/analytics", get(get_analytics))
+}
@@ -0,0 +1,76 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+// =============================================================================
+// ANALYTICS
+// =============================================================================
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AnalyticsQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub days: Option<u32>, // Reporting window, default 30
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EndpointUsage {
+    pub endpoint: String, // Method and route, e.g. "GET /swap/rates"
+    pub requests: u64,
+    pub client_errors: u64,
+    pub server_errors: u64,
+    pub error_rate: f64, // Share of requests answered with 4xx or 5xx
+}
+
+/// Quotes asked for against swaps created and completed
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SwapConversion {
+    pub quote_requests: u64,
+    pub swaps_created: u64,
+    pub swaps_completed: u64,
+    pub quote_to_swap_rate: f64,
+    pub completion_rate: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DailyUsage {
+    pub date: NaiveDate,
+    pub requests: u64,
+    pub errors: u64,
+    pub swaps_created: u64,
+    pub swaps_completed: u64,
+}
+
+/// Amount sent into swaps, per day and source currency
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DailyVolume {
+    pub date: NaiveDate,
+    pub currency: String,
+    pub swaps: u64,
+    pub amount: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountAnalyticsResponse {
+    pub brand: String,
+    pub days: u32,
+    pub since: DateTime<Utc>,
+    pub endpoints: Vec<EndpointUsage>,
+    pub conversion: SwapConversion,
+    pub daily: Vec<DailyUsage>,
+    pub volume: Vec<DailyVolume>,
+}
+
+// =============================================================================
+// ERROR RESPONSE
+// =============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct AccountErrorResponse {
+    pub error: String,
+}
+
+impl AccountErrorResponse {
+    pub fn new(error: impl Into<String>) -> Self {
+        Self { error: error.into() }
+    }
+}
@@ -0,0 +1,1061 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+
+use crate::AppState;
+use crate::config::environment::DepositCheckConfig;
+use crate::modules::affiliate::crud::{AffiliateCrud, AffiliateError};
+use crate::modules::affiliate::model::{Affiliate, AffiliatePayout};
+use crate::modules::affiliate::schema::{RecordPayoutRequest, UpsertAffiliateRequest};
+use crate::modules::auth::interface::AdminUser;
+use crate::modules::widget::crud::{WidgetCrud, WidgetError};
+use crate::modules::widget::model::WidgetKey;
+use crate::modules::widget::schema::WidgetKeyRequest;
+use super::crud::{AdminCrud, AdminError};
+use super::schema::{
+    AdminErrorResponse, CacheStatsResponse, CacheStatsWindow, CurrencyPolicyResponse, DelistingResponse,
+    HighValueSwapResponse, ProviderAdminResponse, ProviderPayloadsResponse, ReconciliationQuery, RotateProviderCredentialRequest,
+    ScheduleDelistingRequest,
+    ShadowQuotesQuery,
+    TenantQuery, UpdateCurrencyPolicyRequest, UpdateMaintenanceRequest, UpdateFeeTierRequest, UpdateProviderRequest, UpsertAddressFormatRequest, UpsertBrandRequest, UserFeeTierResponse,
+};
+use crate::modules::swap::schema::{ShadowQuoteReport, SyncStatusResponse};
+use crate::services::address_format::{AddressFormat, AddressFormatRegistry};
+use crate::services::audit::{AuditEntry, AuditLog, AuditQuery};
+use crate::services::branding::{Brand, BrandRegistry, MIN_API_KEY_LENGTH};
+use crate::services::deposit_check::{DepositChecker, DepositEvidence};
+use crate::services::fees::{self, FeeRule, FeeRuleInput, FeeRules};
+use crate::services::jobs::{self, JobStatus};
+use crate::services::maintenance::{MaintenanceService, MaintenanceState};
+use crate::services::rate_limit::RateLimitMetricsSnapshot;
+use crate::services::reconciliation::{ReconciliationReport, ReconciliationService, ReconciliationSummary};
+use crate::services::provider_credentials::{CredentialError, ProviderCredentialSummary};
+use crate::services::provider_health::ProviderHealth;
+use crate::services::retention::{RetentionReport, RetentionService};
+use crate::services::routing::{self, RoutingDryRunRequest, RoutingDryRunResponse, RoutingRule, RoutingRuleInput, RoutingRules};
+use crate::services::schema_drift::{self, SchemaDriftSnapshot};
+use crate::services::slo::SloReport;
+
+type AdminResult<T> = Result<Json<T>, (StatusCode, Json<AdminErrorResponse>)>;
+
+fn error_response(e: AdminError) -> (StatusCode, Json<AdminErrorResponse>) {
+    let status = match e {
+        AdminError::NotFound(_) => StatusCode::NOT_FOUND,
+        AdminError::InvalidInput(_) => StatusCode::BAD_REQUEST,
+        AdminError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        AdminError::UpstreamError(_) => StatusCode::BAD_GATEWAY,
+    };
+    (status, Json(AdminErrorResponse::new(e.to_string())))
+}
+
+// =============================================================================
+// POST /admin/currencies/{id}/delisting - Schedule a currency delisting
+// =============================================================================
+
+pub async fn schedule_currency_delisting(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path(currency_id): Path<i64>,
+    Json(payload): Json<ScheduleDelistingRequest>,
+) -> AdminResult<DelistingResponse> {
+    let crud = AdminCrud::new(state.db.clone(), Some(state.redis.clone()));
+
+    tracing::info!("Admin {} scheduling delisting for currency {}", admin.id, currency_id);
+
+    let response = crud
+        .schedule_currency_delisting(currency_id, payload.delisting_at, payload.reason.as_deref())
+        .await
+        .map_err(error_response)?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// DELETE /admin/currencies/{id}/delisting - Cancel a scheduled delisting
+// =============================================================================
+
+pub async fn cancel_currency_delisting(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path(currency_id): Path<i64>,
+) -> AdminResult<DelistingResponse> {
+    let crud = AdminCrud::new(state.db.clone(), Some(state.redis.clone()));
+
+    tracing::info!("Admin {} cancelling delisting for currency {}", admin.id, currency_id);
+
+    let response = crud
+        .cancel_currency_delisting(currency_id)
+        .await
+        .map_err(error_response)?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// PATCH /admin/currencies/{id}/policy - Update refund/memo rules for a currency
+// =============================================================================
+
+pub async fn update_currency_policy(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path(currency_id): Path<i64>,
+    Json(payload): Json<UpdateCurrencyPolicyRequest>,
+) -> AdminResult<CurrencyPolicyResponse> {
+    let crud = AdminCrud::new(state.db.clone(), Some(state.redis.clone()));
+
+    tracing::info!("Admin {} updating policy for currency {}: {:?}", admin.id, currency_id, payload);
+
+    let response = crud
+        .update_currency_policy(currency_id, &payload)
+        .await
+        .map_err(error_response)?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// GET /admin/providers - Every provider, disabled ones included
+// =============================================================================
+
+pub async fn list_providers(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+) -> AdminResult<Vec<ProviderAdminResponse>> {
+    let crud = AdminCrud::new(state.db.clone(), Some(state.redis.clone()));
+
+    let response = crud.list_providers().await.map_err(error_response)?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// PATCH /admin/providers/{id} - Enable/disable a provider or override its metadata
+// =============================================================================
+
+pub async fn update_provider(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path(provider_id): Path<String>,
+    Json(payload): Json<UpdateProviderRequest>,
+) -> AdminResult<ProviderAdminResponse> {
+    let crud = AdminCrud::new(state.db.clone(), Some(state.redis.clone()));
+
+    tracing::info!("Admin {} updating provider {}: {:?}", admin.id, provider_id, payload);
+
+    let response = crud
+        .update_provider(&provider_id, &payload)
+        .await
+        .map_err(error_response)?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// DELETE /admin/providers/{id}/overrides - Go back to the synced metadata
+// =============================================================================
+
+pub async fn clear_provider_overrides(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path(provider_id): Path<String>,
+) -> AdminResult<ProviderAdminResponse> {
+    let crud = AdminCrud::new(state.db.clone(), Some(state.redis.clone()));
+
+    tracing::info!("Admin {} clearing overrides for provider {}", admin.id, provider_id);
+
+    let response = crud
+        .clear_provider_overrides(&provider_id)
+        .await
+        .map_err(error_response)?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// GET /admin/providers/health - Provider health scores from our swap outcomes
+// =============================================================================
+
+pub async fn get_provider_health(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+) -> AdminResult<Vec<ProviderHealth>> {
+    let crud = AdminCrud::new(state.db.clone(), Some(state.redis.clone()));
+
+    let response = crud.get_provider_health().await.map_err(error_response)?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// GET /admin/swaps/high-value - Priority queue of in-flight high-value swaps
+// =============================================================================
+
+pub async fn list_high_value_swaps(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+) -> AdminResult<Vec<HighValueSwapResponse>> {
+    let crud = AdminCrud::new(state.db.clone(), Some(state.redis.clone()));
+
+    let swaps = crud
+        .list_high_value_swaps(state.high_value.stall_after)
+        .await
+        .map_err(error_response)?;
+
+    Ok(Json(swaps))
+}
+
+// =============================================================================
+// GET /admin/sync/status - Last currency/provider/pairs sync runs
+// =============================================================================
+
+pub async fn get_sync_status(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+) -> AdminResult<SyncStatusResponse> {
+    let crud = AdminCrud::new(state.db.clone(), Some(state.redis.clone()));
+
+    let response = crud.get_sync_status().await.map_err(error_response)?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// GET /admin/providers/shadow-quotes - Shadowed aggregators against the served quotes
+// =============================================================================
+
+/// Default reporting window, in hours
+const SHADOW_REPORT_HOURS: u32 = 24;
+
+pub async fn get_shadow_quotes(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<ShadowQuotesQuery>,
+) -> AdminResult<ShadowQuoteReport> {
+    let crud = AdminCrud::new(state.db.clone(), Some(state.redis.clone()));
+
+    let report = crud
+        .get_shadow_quote_report(query.hours.unwrap_or(SHADOW_REPORT_HOURS))
+        .await
+        .map_err(error_response)?;
+
+    Ok(Json(report))
+}
+
+// =============================================================================
+// GET /admin/swaps/{id}/provider-payloads - Raw provider responses for a swap
+// =============================================================================
+
+pub async fn get_provider_payloads(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+    Path(swap_id): Path<String>,
+) -> AdminResult<ProviderPayloadsResponse> {
+    let crud = AdminCrud::new(state.db.clone(), Some(state.redis.clone()));
+
+    let response = crud.get_provider_payloads(&swap_id).await.map_err(error_response)?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// GET /admin/swaps/{id}/deposit-check - Explorer evidence of a swap's deposit
+// =============================================================================
+
+pub async fn get_deposit_check(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+    Path(swap_id): Path<String>,
+) -> AdminResult<DepositEvidence> {
+    let crud = AdminCrud::new(state.db.clone(), Some(state.redis.clone()));
+    let checker = DepositChecker::from_config(&DepositCheckConfig::from_env(), state.db.clone());
+
+    let evidence = crud.get_deposit_check(&checker, &swap_id).await.map_err(error_response)?;
+
+    Ok(Json(evidence))
+}
+
+// =============================================================================
+// POST /admin/swaps/{id}/deposit-check - Look the deposit address up now
+// =============================================================================
+
+pub async fn run_deposit_check(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+    Path(swap_id): Path<String>,
+) -> AdminResult<DepositEvidence> {
+    let crud = AdminCrud::new(state.db.clone(), Some(state.redis.clone()));
+    let checker = DepositChecker::from_config(&DepositCheckConfig::from_env(), state.db.clone());
+
+    let evidence = crud.run_deposit_check(&checker, &swap_id).await.map_err(error_response)?;
+
+    Ok(Json(evidence))
+}
+
+// =============================================================================
+// GET /admin/cache/stats - Per-prefix cache hit rates for this instance
+// =============================================================================
+
+/// Reporting windows, in minutes
+const CACHE_STATS_WINDOWS: [u64; 4] = [1, 5, 15, 60];
+
+pub async fn get_cache_stats(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+) -> AdminResult<CacheStatsResponse> {
+    let stats = state.redis.stats();
+
+    let windows = CACHE_STATS_WINDOWS
+        .iter()
+        .map(|&window_minutes| CacheStatsWindow {
+            window_minutes,
+            prefixes: stats.snapshot(window_minutes),
+        })
+        .collect();
+
+    Ok(Json(CacheStatsResponse { windows }))
+}
+
+// =============================================================================
+// GET /admin/rate-limit/stats - Global limiter counters for this instance
+// =============================================================================
+
+pub async fn get_rate_limit_stats(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+) -> AdminResult<RateLimitMetricsSnapshot> {
+    Ok(Json(state.rate_limit_metrics.snapshot()))
+}
+
+// =============================================================================
+// GET /admin/providers/schema-drift - Unmapped fields and parse fallbacks per Trocador endpoint
+// =============================================================================
+
+pub async fn get_provider_schema_drift(AdminUser(_admin): AdminUser) -> AdminResult<SchemaDriftSnapshot> {
+    Ok(Json(schema_drift::monitor().snapshot()))
+}
+
+// =============================================================================
+// GET /admin/slo - Response time SLO compliance, error budget and burn rates
+// =============================================================================
+
+pub async fn get_slo_report(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+) -> AdminResult<SloReport> {
+    Ok(Json(state.slo.report()))
+}
+
+// =============================================================================
+// GET /admin/maintenance - Current maintenance mode
+// =============================================================================
+
+pub async fn get_maintenance(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+) -> AdminResult<MaintenanceState> {
+    let service = MaintenanceService::new(state.db.clone(), state.redis.clone());
+
+    Ok(Json(service.current().await))
+}
+
+// =============================================================================
+// PUT /admin/maintenance - Turn maintenance mode on or off
+// =============================================================================
+
+/// Longest Retry-After we hand out
+const MAX_RETRY_AFTER_SECS: u32 = 86400;
+
+pub async fn update_maintenance(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Json(payload): Json<UpdateMaintenanceRequest>,
+) -> AdminResult<MaintenanceState> {
+    if payload.retry_after_secs.is_some_and(|secs| secs == 0 || secs > MAX_RETRY_AFTER_SECS) {
+        return Err(error_response(AdminError::InvalidInput(format!(
+            "retry_after_secs must be between 1 and {}",
+            MAX_RETRY_AFTER_SECS
+        ))));
+    }
+    if payload.messages.values().any(|m| m.trim().is_empty()) {
+        return Err(error_response(AdminError::InvalidInput("messages must not be empty".to_string())));
+    }
+
+    tracing::warn!("Admin {} set maintenance mode enabled={}", admin.id, payload.enabled);
+
+    let service = MaintenanceService::new(state.db.clone(), state.redis.clone());
+    let response = service
+        .set(payload.enabled, payload.messages, payload.retry_after_secs, payload.ends_at, &admin.id)
+        .await
+        .map_err(|e| error_response(AdminError::from(e)))?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// GET /admin/address-formats - Registered address formats
+// =============================================================================
+
+pub async fn list_address_formats(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+) -> AdminResult<Vec<AddressFormat>> {
+    let registry = AddressFormatRegistry::new(state.db.clone(), Some(state.redis.clone()));
+
+    let formats = registry.all().await.map_err(|e| error_response(AdminError::from(e)))?;
+
+    Ok(Json(formats))
+}
+
+// =============================================================================
+// PUT /admin/address-formats/{ticker}/{network} - Create or replace a format
+// =============================================================================
+
+pub async fn upsert_address_format(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path((ticker, network)): Path<(String, String)>,
+    Json(payload): Json<UpsertAddressFormatRequest>,
+) -> AdminResult<AddressFormat> {
+    let format = AddressFormat {
+        ticker,
+        network,
+        address_regex: payload.address_regex,
+        checksum: payload.checksum,
+        memo_name: payload.memo_name,
+        memo_required: payload.memo_required,
+        memo_format: payload.memo_format,
+        memo_max_length: payload.memo_max_length,
+        memo_max_value: payload.memo_max_value,
+        uri_scheme: payload.uri_scheme,
+        uri_memo_param: payload.uri_memo_param,
+        updated_at: None,
+    };
+    format.check_rules().map_err(|e| error_response(AdminError::InvalidInput(e)))?;
+
+    tracing::info!("Admin {} updating address format for {} on {}", admin.id, format.ticker, format.network);
+
+    let registry = AddressFormatRegistry::new(state.db.clone(), Some(state.redis.clone()));
+    let response = registry
+        .upsert(&format)
+        .await
+        .map_err(|e| error_response(AdminError::from(e)))?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// DELETE /admin/address-formats/{ticker}/{network} - Remove a format
+// =============================================================================
+
+pub async fn delete_address_format(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path((ticker, network)): Path<(String, String)>,
+) -> AdminResult<AddressFormat> {
+    let registry = AddressFormatRegistry::new(state.db.clone(), Some(state.redis.clone()));
+
+    let deleted = registry
+        .delete(&ticker, &network)
+        .await
+        .map_err(|e| error_response(AdminError::from(e)))?
+        .ok_or_else(|| error_response(AdminError::NotFound(format!("Address format for {} on {}", ticker, network))))?;
+
+    tracing::info!("Admin {} removed address format for {} on {}", admin.id, ticker, network);
+
+    Ok(Json(deleted))
+}
+
+// =============================================================================
+// GET /admin/brands - White-label brands
+// =============================================================================
+
+pub async fn list_brands(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+) -> AdminResult<Vec<Brand>> {
+    let registry = BrandRegistry::new(state.db.clone(), Some(state.redis.clone()));
+
+    let brands = registry.all().await.map_err(|e| error_response(AdminError::from(e)))?;
+
+    Ok(Json(brands))
+}
+
+// =============================================================================
+// PUT /admin/brands/{slug} - Create or replace a brand
+// =============================================================================
+
+pub async fn upsert_brand(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path(slug): Path<String>,
+    Json(payload): Json<UpsertBrandRequest>,
+) -> AdminResult<Brand> {
+    let brand = Brand {
+        slug,
+        name: payload.name,
+        support_email: payload.support_email,
+        tenant: payload.tenant,
+        domains: payload.domains,
+        markup_percent: payload.markup_percent,
+        enabled_providers: payload.enabled_providers,
+        allowed_pairs: payload.allowed_pairs,
+        has_api_key: false,
+        updated_at: None,
+    };
+    brand.check_rules().map_err(|e| error_response(AdminError::InvalidInput(e)))?;
+    if payload.api_key.as_deref().is_some_and(|k| k.len() < MIN_API_KEY_LENGTH) {
+        return Err(error_response(AdminError::InvalidInput(format!(
+            "api_key must be at least {} characters",
+            MIN_API_KEY_LENGTH
+        ))));
+    }
+
+    tracing::info!("Admin {} updating brand {}", admin.id, brand.slug);
+
+    let registry = BrandRegistry::new(state.db.clone(), Some(state.redis.clone()));
+    let response = registry
+        .upsert(&brand, payload.api_key.as_deref())
+        .await
+        .map_err(|e| error_response(AdminError::from(e)))?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// DELETE /admin/brands/{slug} - Remove a brand
+// =============================================================================
+
+pub async fn delete_brand(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path(slug): Path<String>,
+) -> AdminResult<Brand> {
+    let registry = BrandRegistry::new(state.db.clone(), Some(state.redis.clone()));
+
+    let deleted = registry
+        .delete(&slug)
+        .await
+        .map_err(|e| error_response(AdminError::from(e)))?
+        .ok_or_else(|| error_response(AdminError::NotFound(format!("Brand {}", slug))))?;
+
+    tracing::info!("Admin {} removed brand {}", admin.id, slug);
+
+    Ok(Json(deleted))
+}
+
+// =============================================================================
+// /admin/affiliates - Referral partners and their payouts
+// =============================================================================
+
+fn affiliate_error(e: AffiliateError) -> (StatusCode, Json<AdminErrorResponse>) {
+    error_response(match e {
+        AffiliateError::AffiliateNotFound | AffiliateError::Unauthorized => AdminError::NotFound("Affiliate".to_string()),
+        AffiliateError::InvalidInput(reason) => AdminError::InvalidInput(reason),
+        AffiliateError::DatabaseError(e) => AdminError::DatabaseError(e),
+    })
+}
+
+pub async fn list_affiliates(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+) -> AdminResult<Vec<Affiliate>> {
+    let affiliates = AffiliateCrud::new(state.db.clone()).all().await.map_err(affiliate_error)?;
+
+    Ok(Json(affiliates))
+}
+
+pub async fn upsert_affiliate(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path(ref_code): Path<String>,
+    Json(payload): Json<UpsertAffiliateRequest>,
+) -> AdminResult<Affiliate> {
+    tracing::info!("Admin {} updating affiliate {}", admin.id, ref_code);
+
+    let affiliate = AffiliateCrud::new(state.db.clone())
+        .upsert(&ref_code, &payload)
+        .await
+        .map_err(affiliate_error)?;
+
+    Ok(Json(affiliate))
+}
+
+pub async fn record_affiliate_payout(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path(ref_code): Path<String>,
+    Json(payload): Json<RecordPayoutRequest>,
+) -> Result<(StatusCode, Json<AffiliatePayout>), (StatusCode, Json<AdminErrorResponse>)> {
+    let payout = AffiliateCrud::new(state.db.clone())
+        .record_payout(&ref_code, &payload, &admin.id)
+        .await
+        .map_err(affiliate_error)?;
+
+    tracing::info!(
+        "Admin {} recorded payout {} of {} {} to affiliate {}",
+        admin.id, payout.id, payout.amount, payout.currency, ref_code
+    );
+
+    Ok((StatusCode::CREATED, Json(payout)))
+}
+
+// =============================================================================
+// /admin/widget-keys - Public keys for the embeddable swap widget
+// =============================================================================
+
+fn widget_error(e: WidgetError) -> (StatusCode, Json<AdminErrorResponse>) {
+    error_response(match e {
+        WidgetError::KeyNotFound | WidgetError::Unauthorized => AdminError::NotFound("Widget key".to_string()),
+        WidgetError::InvalidInput(reason) => AdminError::InvalidInput(reason),
+        WidgetError::DatabaseError(e) => AdminError::DatabaseError(e),
+        WidgetError::OriginNotAllowed | WidgetError::RateLimited(_) => AdminError::InvalidInput(e.to_string()),
+    })
+}
+
+fn widget_crud(state: &AppState) -> WidgetCrud {
+    WidgetCrud::new(state.db.clone(), Some(state.redis.clone()))
+}
+
+pub async fn list_widget_keys(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+) -> AdminResult<Vec<WidgetKey>> {
+    let keys = widget_crud(&state).all().await.map_err(widget_error)?;
+
+    Ok(Json(keys))
+}
+
+pub async fn create_widget_key(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Json(payload): Json<WidgetKeyRequest>,
+) -> Result<(StatusCode, Json<WidgetKey>), (StatusCode, Json<AdminErrorResponse>)> {
+    let key = widget_crud(&state).create(&payload).await.map_err(widget_error)?;
+
+    tracing::info!("Admin {} issued widget key {} ({})", admin.id, key.public_key, key.name);
+
+    Ok((StatusCode::CREATED, Json(key)))
+}
+
+pub async fn update_widget_key(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path(public_key): Path<String>,
+    Json(payload): Json<WidgetKeyRequest>,
+) -> AdminResult<WidgetKey> {
+    tracing::info!("Admin {} updating widget key {}", admin.id, public_key);
+
+    let key = widget_crud(&state).update(&public_key, &payload).await.map_err(widget_error)?;
+
+    Ok(Json(key))
+}
+
+pub async fn delete_widget_key(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path(public_key): Path<String>,
+) -> AdminResult<WidgetKey> {
+    tracing::info!("Admin {} revoking widget key {}", admin.id, public_key);
+
+    let key = widget_crud(&state).delete(&public_key).await.map_err(widget_error)?;
+
+    Ok(Json(key))
+}
+
+// =============================================================================
+// GET /admin/fee-rules - Platform fee rules
+// =============================================================================
+
+/// Fee rules of the tenant named by `?tenant=`
+fn fee_rules(state: &AppState, query: TenantQuery) -> FeeRules {
+    FeeRules::new(state.db.clone(), Some(state.redis.clone())).with_tenant(query.tenant.unwrap_or_default())
+}
+
+pub async fn list_fee_rules(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<TenantQuery>,
+) -> AdminResult<Vec<FeeRule>> {
+    let rules = fee_rules(&state, query);
+
+    let response = rules.all().await.map_err(|e| error_response(AdminError::from(e)))?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// POST /admin/fee-rules - Add a fee rule
+// =============================================================================
+
+pub async fn create_fee_rule(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Query(query): Query<TenantQuery>,
+    Json(payload): Json<FeeRuleInput>,
+) -> Result<(StatusCode, Json<FeeRule>), (StatusCode, Json<AdminErrorResponse>)> {
+    let input = payload.normalized();
+    input.check_rules().map_err(|e| error_response(AdminError::InvalidInput(e)))?;
+
+    let rules = fee_rules(&state, query);
+    let rule = rules.create(&input).await.map_err(|e| error_response(AdminError::from(e)))?;
+
+    tracing::info!("Admin {} added fee rule {} for tenant {}", admin.id, rule.id, rule.tenant_id);
+
+    Ok((StatusCode::CREATED, Json(rule)))
+}
+
+// =============================================================================
+// PUT /admin/fee-rules/{id} - Replace a fee rule
+// =============================================================================
+
+pub async fn update_fee_rule(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<u64>,
+    Query(query): Query<TenantQuery>,
+    Json(payload): Json<FeeRuleInput>,
+) -> AdminResult<FeeRule> {
+    let input = payload.normalized();
+    input.check_rules().map_err(|e| error_response(AdminError::InvalidInput(e)))?;
+
+    let rules = fee_rules(&state, query);
+    let rule = rules
+        .update(id, &input)
+        .await
+        .map_err(|e| error_response(AdminError::from(e)))?
+        .ok_or_else(|| error_response(AdminError::NotFound(format!("Fee rule {}", id))))?;
+
+    tracing::info!("Admin {} updated fee rule {}", admin.id, id);
+
+    Ok(Json(rule))
+}
+
+// =============================================================================
+// DELETE /admin/fee-rules/{id} - Remove a fee rule
+// =============================================================================
+
+pub async fn delete_fee_rule(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<u64>,
+    Query(query): Query<TenantQuery>,
+) -> AdminResult<FeeRule> {
+    let rules = fee_rules(&state, query);
+
+    let deleted = rules
+        .delete(id)
+        .await
+        .map_err(|e| error_response(AdminError::from(e)))?
+        .ok_or_else(|| error_response(AdminError::NotFound(format!("Fee rule {}", id))))?;
+
+    tracing::info!("Admin {} removed fee rule {}", admin.id, id);
+
+    Ok(Json(deleted))
+}
+
+// =============================================================================
+// GET /admin/routing-rules - Provider routing rules
+// =============================================================================
+
+/// Routing rules of the tenant named by `?tenant=`
+fn routing_rules(state: &AppState, query: TenantQuery) -> RoutingRules {
+    RoutingRules::new(state.db.clone(), Some(state.redis.clone())).with_tenant(query.tenant.unwrap_or_default())
+}
+
+pub async fn list_routing_rules(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<TenantQuery>,
+) -> AdminResult<Vec<RoutingRule>> {
+    let rules = routing_rules(&state, query);
+
+    let response = rules.all().await.map_err(|e| error_response(AdminError::from(e)))?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// POST /admin/routing-rules - Add a routing rule
+// =============================================================================
+
+pub async fn create_routing_rule(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Query(query): Query<TenantQuery>,
+    Json(payload): Json<RoutingRuleInput>,
+) -> Result<(StatusCode, Json<RoutingRule>), (StatusCode, Json<AdminErrorResponse>)> {
+    let input = payload.normalized();
+    input.check_rules().map_err(|e| error_response(AdminError::InvalidInput(e)))?;
+
+    let rules = routing_rules(&state, query);
+    let rule = rules.create(&input).await.map_err(|e| error_response(AdminError::from(e)))?;
+
+    tracing::info!("Admin {} added routing rule {} for tenant {}", admin.id, rule.id, rule.tenant_id);
+
+    Ok((StatusCode::CREATED, Json(rule)))
+}
+
+// =============================================================================
+// PUT /admin/routing-rules/{id} - Replace a routing rule
+// =============================================================================
+
+pub async fn update_routing_rule(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<u64>,
+    Query(query): Query<TenantQuery>,
+    Json(payload): Json<RoutingRuleInput>,
+) -> AdminResult<RoutingRule> {
+    let input = payload.normalized();
+    input.check_rules().map_err(|e| error_response(AdminError::InvalidInput(e)))?;
+
+    let rules = routing_rules(&state, query);
+    let rule = rules
+        .update(id, &input)
+        .await
+        .map_err(|e| error_response(AdminError::from(e)))?
+        .ok_or_else(|| error_response(AdminError::NotFound(format!("Routing rule {}", id))))?;
+
+    tracing::info!("Admin {} updated routing rule {}", admin.id, id);
+
+    Ok(Json(rule))
+}
+
+// =============================================================================
+// DELETE /admin/routing-rules/{id} - Remove a routing rule
+// =============================================================================
+
+pub async fn delete_routing_rule(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<u64>,
+    Query(query): Query<TenantQuery>,
+) -> AdminResult<RoutingRule> {
+    let rules = routing_rules(&state, query);
+
+    let deleted = rules
+        .delete(id)
+        .await
+        .map_err(|e| error_response(AdminError::from(e)))?
+        .ok_or_else(|| error_response(AdminError::NotFound(format!("Routing rule {}", id))))?;
+
+    tracing::info!("Admin {} removed routing rule {}", admin.id, id);
+
+    Ok(Json(deleted))
+}
+
+// =============================================================================
+// POST /admin/routing-rules/dry-run - Route a request without serving it
+// =============================================================================
+
+/// Evaluate the stored rules, or the unsaved `rules` of the request, for a
+/// pair, amount and country
+pub async fn dry_run_routing_rules(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<TenantQuery>,
+    Json(payload): Json<RoutingDryRunRequest>,
+) -> AdminResult<RoutingDryRunResponse> {
+    let rules = match payload.rules.clone() {
+        Some(candidates) => {
+            let mut rules = Vec::with_capacity(candidates.len());
+            for (index, input) in candidates.into_iter().enumerate() {
+                let input = input.normalized();
+                input.check_rules().map_err(|e| error_response(AdminError::InvalidInput(format!("rules[{}]: {}", index, e))))?;
+                // Unsaved rules are numbered from 1 in request order
+                rules.push(input.preview(index as u64 + 1));
+            }
+            rules
+        }
+        None => routing_rules(&state, query).all().await.map_err(|e| error_response(AdminError::from(e)))?,
+    };
+
+    Ok(Json(routing::dry_run(&rules, &payload, &state.high_value)))
+}
+
+// =============================================================================
+// PUT /admin/users/{id}/fee-tier - Move a user to a fee tier
+// =============================================================================
+
+pub async fn update_user_fee_tier(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path(user_id): Path<String>,
+    Json(payload): Json<UpdateFeeTierRequest>,
+) -> AdminResult<UserFeeTierResponse> {
+    let fee_tier = payload.fee_tier.trim().to_lowercase();
+    fees::check_tier(&fee_tier).map_err(|e| error_response(AdminError::InvalidInput(e)))?;
+
+    let rules = FeeRules::new(state.db.clone(), Some(state.redis.clone()));
+    let found = rules
+        .set_user_tier(&user_id, &fee_tier)
+        .await
+        .map_err(|e| error_response(AdminError::from(e)))?;
+    if !found {
+        return Err(error_response(AdminError::NotFound(format!("User {}", user_id))));
+    }
+
+    tracing::info!("Admin {} moved user {} to fee tier {}", admin.id, user_id, fee_tier);
+
+    Ok(Json(UserFeeTierResponse { user_id, fee_tier }))
+}
+
+// =============================================================================
+// GET /admin/jobs - Background jobs running in this instance
+// =============================================================================
+
+pub async fn list_jobs(AdminUser(_admin): AdminUser) -> AdminResult<Vec<JobStatus>> {
+    Ok(Json(jobs::registry().list()))
+}
+
+// =============================================================================
+// POST /admin/jobs/{name}/run - Run a background job now
+// =============================================================================
+
+pub async fn run_job(AdminUser(admin): AdminUser, Path(name): Path<String>) -> AdminResult<JobStatus> {
+    let status = jobs::registry()
+        .trigger(&name)
+        .ok_or_else(|| error_response(AdminError::NotFound(format!("Job {}", name))))?;
+
+    tracing::info!("Admin {} triggered job {}", admin.id, name);
+
+    Ok(Json(status))
+}
+
+// =============================================================================
+// GET /admin/retention/report - Dry run of the data retention policies
+// =============================================================================
+
+pub async fn get_retention_report(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+) -> AdminResult<RetentionReport> {
+    let report = RetentionService::new(state.db.clone(), state.retention_config.clone())
+        .report()
+        .await
+        .map_err(|e| error_response(AdminError::DatabaseError(e.to_string())))?;
+
+    Ok(Json(report))
+}
+
+// =============================================================================
+// GET /admin/reconciliation - Past end-of-day reconciliation reports
+// =============================================================================
+
+pub async fn list_reconciliation_reports(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<ReconciliationQuery>,
+) -> AdminResult<Vec<ReconciliationSummary>> {
+    let reports = ReconciliationService::new(state.db.clone())
+        .list(query.limit.unwrap_or(30).clamp(1, 366))
+        .await
+        .map_err(|e| error_response(AdminError::DatabaseError(e.to_string())))?;
+
+    Ok(Json(reports))
+}
+
+// =============================================================================
+// GET /admin/reconciliation/{day} - One day's reconciliation report
+// =============================================================================
+
+pub async fn get_reconciliation_report(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+    Path(day): Path<String>,
+) -> AdminResult<ReconciliationReport> {
+    let day = chrono::NaiveDate::parse_from_str(&day, "%Y-%m-%d")
+        .map_err(|_| error_response(AdminError::InvalidInput("day must be YYYY-MM-DD".to_string())))?;
+
+    let report = ReconciliationService::new(state.db.clone())
+        .get(day)
+        .await
+        .map_err(|e| error_response(AdminError::DatabaseError(e.to_string())))?
+        .ok_or_else(|| error_response(AdminError::NotFound(format!("Reconciliation report for {}", day))))?;
+
+    Ok(Json(report))
+}
+
+// =============================================================================
+// GET /admin/audit - Who changed what, newest first
+// =============================================================================
+
+pub async fn list_audit_entries(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<AuditQuery>,
+) -> AdminResult<Vec<AuditEntry>> {
+    if let (Some(since), Some(until)) = (query.since, query.until) {
+        if since >= until {
+            return Err(error_response(AdminError::InvalidInput("since must be before until".to_string())));
+        }
+    }
+
+    let entries = AuditLog::new(state.db.clone())
+        .list(&query)
+        .await
+        .map_err(|e| error_response(AdminError::DatabaseError(e.to_string())))?;
+
+    Ok(Json(entries))
+}
+
+// =============================================================================
+// GET /admin/provider-credentials - Stored provider keys, masked
+// =============================================================================
+
+fn credential_error(e: CredentialError) -> (StatusCode, Json<AdminErrorResponse>) {
+    error_response(match e {
+        CredentialError::Disabled => AdminError::InvalidInput(e.to_string()),
+        CredentialError::UnknownProvider(provider) => AdminError::NotFound(format!("Provider {}", provider)),
+        other => AdminError::DatabaseError(other.to_string()),
+    })
+}
+
+pub async fn list_provider_credentials(
+    State(state): State<Arc<AppState>>,
+    AdminUser(_admin): AdminUser,
+) -> AdminResult<Vec<ProviderCredentialSummary>> {
+    let credentials = state.provider_credentials.list().await.map_err(credential_error)?;
+
+    Ok(Json(credentials))
+}
+
+// =============================================================================
+// PUT /admin/provider-credentials/{provider} - Rotate a provider's key
+// =============================================================================
+
+pub async fn rotate_provider_credential(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin): AdminUser,
+    Path(provider): Path<String>,
+    Json(payload): Json<RotateProviderCredentialRequest>,
+) -> AdminResult<ProviderCredentialSummary> {
+    let api_key = payload.api_key.trim();
+    if api_key.is_empty() {
+        return Err(error_response(AdminError::InvalidInput("api_key is required".to_string())));
+    }
+    let base_url = payload.base_url.as_deref().map(|u| u.trim().trim_end_matches('/')).filter(|u| !u.is_empty());
+    if base_url.is_some_and(|u| !u.starts_with("https://") && !u.starts_with("http://")) {
+        return Err(error_response(AdminError::InvalidInput("base_url must be an http(s) URL".to_string())));
+    }
+
+    let summary = state
+        .provider_credentials
+        .rotate(&provider, api_key, base_url, Some(&admin.id))
+        .await
+        .map_err(credential_error)?;
+
+    tracing::info!("Admin {} rotated the {} API key ({})", admin.id, provider, summary.key_hint);
+
+    // This instance switches now; others within PROVIDER_CREDENTIALS_REFRESH_SECS
+    match &state.trocador {
+        Some(client) if provider == "trocador" => client.rotate(api_key.to_string(), base_url.map(str::to_string)),
+        None if provider == "trocador" => tracing::warn!("Trocador key stored; it takes effect on restart"),
+        _ => {}
+    }
+
+    Ok(Json(summary))
+}
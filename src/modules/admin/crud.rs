@@ -0,0 +1,458 @@
+use chrono::{DateTime, Utc};
+use sqlx::{MySql, Pool};
+use std::time::Duration;
+
+use super::schema::{
+    CurrencyPolicyResponse, DelistingResponse, HighValueSwapResponse, ProviderAdminResponse, ProviderPayloadResponse,
+    ProviderPayloadsResponse, UpdateCurrencyPolicyRequest, UpdateProviderRequest,
+};
+use crate::modules::swap::crud::SwapCrud;
+use crate::modules::swap::model::SwapProviderPayload;
+use crate::modules::swap::schema::{ShadowQuoteReport, SwapStatus, SyncStatusResponse};
+use crate::services::deposit_check::{CheckTrigger, DepositCheckError, DepositChecker, DepositEvidence};
+use crate::services::payload_codec;
+use crate::services::provider_health::{HealthOverride, ProviderHealth};
+use crate::services::redis_cache::RedisService;
+
+// =============================================================================
+// ADMIN ERROR
+// =============================================================================
+
+#[derive(Debug)]
+pub enum AdminError {
+    NotFound(String),
+    InvalidInput(String),
+    DatabaseError(String),
+    UpstreamError(String), // An outside service we read from failed
+}
+
+impl std::fmt::Display for AdminError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdminError::NotFound(what) => write!(f, "{} not found", what),
+            AdminError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
+            AdminError::DatabaseError(e) => write!(f, "Database error: {}", e),
+            AdminError::UpstreamError(e) => write!(f, "Upstream error: {}", e),
+        }
+    }
+}
+
+impl From<sqlx::Error> for AdminError {
+    fn from(err: sqlx::Error) -> Self {
+        AdminError::DatabaseError(err.to_string())
+    }
+}
+
+// =============================================================================
+// ADMIN CRUD
+// =============================================================================
+
+pub struct AdminCrud {
+    pool: Pool<MySql>,
+    redis_service: Option<RedisService>,
+}
+
+impl AdminCrud {
+    pub fn new(pool: Pool<MySql>, redis_service: Option<RedisService>) -> Self {
+        Self { pool, redis_service }
+    }
+
+    fn swap_crud(&self) -> SwapCrud {
+        SwapCrud::new(self.pool.clone(), self.redis_service.clone())
+    }
+
+    // =========================================================================
+    // CURRENCY DELISTING
+    // =========================================================================
+
+    /// Announce a delisting date for a currency and queue a notice for every
+    /// user who has swapped it, kept it in a favorite pair or saved an address
+    /// for it. New swaps are blocked once the date passes.
+    pub async fn schedule_currency_delisting(
+        &self,
+        currency_id: i64,
+        delisting_at: DateTime<Utc>,
+        reason: Option<&str>,
+    ) -> Result<DelistingResponse, AdminError> {
+        if delisting_at <= Utc::now() {
+            return Err(AdminError::InvalidInput("delisting_at must be in the future".to_string()));
+        }
+
+        let (ticker, network) = self.find_currency(currency_id).await?;
+
+        sqlx::query("UPDATE currencies SET delisting_at = ? WHERE id = ?")
+            .bind(delisting_at)
+            .bind(currency_id)
+            .execute(&self.pool)
+            .await?;
+
+        let title = format!("{} ({}) is being delisted", ticker.to_uppercase(), network);
+        let mut body = format!(
+            "{} on {} will no longer be available for new swaps after {}. Swaps created before then will complete normally.",
+            ticker.to_uppercase(),
+            network,
+            delisting_at.format("%Y-%m-%d %H:%M UTC")
+        );
+        if let Some(reason) = reason {
+            body.push_str(&format!(" Reason: {}", reason));
+        }
+
+        let notified = sqlx::query(
+            r#"
+            INSERT INTO user_notifications (user_id, kind, title, body)
+            SELECT user_id, 'currency_delisting', ?, ?
+            FROM (
+                SELECT user_id FROM swaps
+                WHERE user_id IS NOT NULL
+                  AND ((LOWER(from_currency) = LOWER(?) AND from_network = ?)
+                    OR (LOWER(to_currency) = LOWER(?) AND to_network = ?))
+                UNION
+                SELECT user_id FROM favorite_pairs
+                WHERE deleted_at IS NULL
+                  AND ((LOWER(from_currency) = LOWER(?) AND from_network = ?)
+                    OR (LOWER(to_currency) = LOWER(?) AND to_network = ?))
+                UNION
+                SELECT user_id FROM saved_addresses
+                WHERE deleted_at IS NULL AND LOWER(currency) = LOWER(?) AND network = ?
+            ) AS holders
+            "#
+        )
+        .bind(&title)
+        .bind(&body)
+        .bind(&ticker)
+        .bind(&network)
+        .bind(&ticker)
+        .bind(&network)
+        .bind(&ticker)
+        .bind(&network)
+        .bind(&ticker)
+        .bind(&network)
+        .bind(&ticker)
+        .bind(&network)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+
+        self.swap_crud().invalidate_currency_cache().await;
+
+        tracing::info!(
+            "Scheduled delisting of {} ({}) at {}, notified {} users",
+            ticker, network, delisting_at, notified
+        );
+
+        Ok(DelistingResponse {
+            currency_id,
+            ticker,
+            network,
+            delisting_at: Some(delisting_at),
+            notified_users: notified,
+        })
+    }
+
+    /// Withdraw a previously scheduled delisting
+    pub async fn cancel_currency_delisting(&self, currency_id: i64) -> Result<DelistingResponse, AdminError> {
+        let (ticker, network) = self.find_currency(currency_id).await?;
+
+        sqlx::query("UPDATE currencies SET delisting_at = NULL WHERE id = ?")
+            .bind(currency_id)
+            .execute(&self.pool)
+            .await?;
+
+        self.swap_crud().invalidate_currency_cache().await;
+
+        Ok(DelistingResponse {
+            currency_id,
+            ticker,
+            network,
+            delisting_at: None,
+            notified_users: 0,
+        })
+    }
+
+    // =========================================================================
+    // CURRENCY POLICY
+    // =========================================================================
+
+    /// Update admin-managed swap rules for a currency/network
+    pub async fn update_currency_policy(
+        &self,
+        currency_id: i64,
+        request: &UpdateCurrencyPolicyRequest,
+    ) -> Result<CurrencyPolicyResponse, AdminError> {
+        let (ticker, network) = self.find_currency(currency_id).await?;
+
+        if let Some(required) = request.requires_refund_address {
+            sqlx::query("UPDATE currencies SET requires_refund_address = ? WHERE id = ?")
+                .bind(required)
+                .bind(currency_id)
+                .execute(&self.pool)
+                .await?;
+        }
+
+        // admin_disabled keeps the currency off when the next sync lists it again
+        if let Some(active) = request.is_active {
+            sqlx::query("UPDATE currencies SET is_active = ?, admin_disabled = ? WHERE id = ?")
+                .bind(active)
+                .bind(!active)
+                .bind(currency_id)
+                .execute(&self.pool)
+                .await?;
+        }
+
+        let (requires_refund_address, is_active): (bool, bool) =
+            sqlx::query_as("SELECT requires_refund_address, is_active FROM currencies WHERE id = ?")
+                .bind(currency_id)
+                .fetch_one(&self.pool)
+                .await?;
+
+        self.swap_crud().invalidate_currency_cache().await;
+
+        Ok(CurrencyPolicyResponse {
+            currency_id,
+            ticker,
+            network,
+            requires_refund_address,
+            is_active,
+        })
+    }
+
+    // =========================================================================
+    // PROVIDERS
+    // =========================================================================
+
+    /// Every provider, disabled ones included, with any admin overrides
+    pub async fn list_providers(&self) -> Result<Vec<ProviderAdminResponse>, AdminError> {
+        let providers = sqlx::query_as(&format!("{} ORDER BY name", PROVIDER_ADMIN_SELECT))
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(providers)
+    }
+
+    /// Switch a provider on or off, pin its KYC rating or ETA over the synced
+    /// values, or pin how its quotes are served over its health score
+    pub async fn update_provider(
+        &self,
+        provider_id: &str,
+        request: &UpdateProviderRequest,
+    ) -> Result<ProviderAdminResponse, AdminError> {
+        if let Some(rating) = &request.kyc_rating {
+            if !KYC_RATINGS.contains(&rating.as_str()) {
+                return Err(AdminError::InvalidInput("kyc_rating must be A, B, C or D".to_string()));
+            }
+        }
+        if matches!(request.eta_minutes, Some(eta) if eta < 0) {
+            return Err(AdminError::InvalidInput("eta_minutes must not be negative".to_string()));
+        }
+        let health_override = match &request.health_override {
+            Some(pin) => Some(pin.parse::<HealthOverride>().map_err(|_| {
+                AdminError::InvalidInput("health_override must be trusted, demoted or hidden".to_string())
+            })?),
+            None => None,
+        };
+
+        self.find_provider(provider_id).await?;
+
+        if let Some(active) = request.is_active {
+            sqlx::query("UPDATE providers SET is_active = ? WHERE id = ?")
+                .bind(active)
+                .bind(provider_id)
+                .execute(&self.pool)
+                .await?;
+        }
+        if let Some(rating) = &request.kyc_rating {
+            sqlx::query("UPDATE providers SET kyc_rating = ?, kyc_rating_override = ? WHERE id = ?")
+                .bind(rating)
+                .bind(rating)
+                .bind(provider_id)
+                .execute(&self.pool)
+                .await?;
+        }
+        if let Some(eta) = request.eta_minutes {
+            sqlx::query("UPDATE providers SET eta_minutes = ?, eta_minutes_override = ? WHERE id = ?")
+                .bind(eta)
+                .bind(eta)
+                .bind(provider_id)
+                .execute(&self.pool)
+                .await?;
+        }
+        if let Some(pin) = health_override {
+            sqlx::query("UPDATE providers SET health_override = ? WHERE id = ?")
+                .bind(pin.as_str())
+                .bind(provider_id)
+                .execute(&self.pool)
+                .await?;
+        }
+
+        self.swap_crud().invalidate_provider_cache().await;
+        self.find_provider(provider_id).await
+    }
+
+    /// Drop a provider's overrides; the synced values return with the next
+    /// provider sync, and its health score decides again
+    pub async fn clear_provider_overrides(&self, provider_id: &str) -> Result<ProviderAdminResponse, AdminError> {
+        self.find_provider(provider_id).await?;
+
+        sqlx::query("UPDATE providers SET kyc_rating_override = NULL, eta_minutes_override = NULL, health_override = NULL WHERE id = ?")
+            .bind(provider_id)
+            .execute(&self.pool)
+            .await?;
+
+        self.swap_crud().invalidate_provider_cache().await;
+        self.find_provider(provider_id).await
+    }
+
+    // =========================================================================
+    // HIGH-VALUE SWAPS
+    // =========================================================================
+
+    /// In-flight high-value swaps, stalled ones first, then longest in their status
+    pub async fn list_high_value_swaps(&self, stall_after: Duration) -> Result<Vec<HighValueSwapResponse>, AdminError> {
+        let mut swaps: Vec<HighValueSwapResponse> = sqlx::query_as(
+            "SELECT id, user_id, brand, provider_id, provider_swap_id,
+                    from_currency, from_network, to_currency, to_network,
+                    amount, estimated_receive, usd_value, status, stall_alerted_at, created_at, updated_at
+             FROM swaps
+             WHERE high_value = TRUE
+               AND status IN ('waiting', 'confirming', 'exchanging', 'sending')
+             ORDER BY updated_at ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let stalled_before = Utc::now() - stall_after;
+        for swap in &mut swaps {
+            // A waiting swap has no deposit yet; only funded swaps can stall
+            swap.stalled = swap.status != SwapStatus::Waiting && swap.updated_at < stalled_before;
+        }
+        swaps.sort_by_key(|swap| !swap.stalled);
+
+        Ok(swaps)
+    }
+
+    // =========================================================================
+    // SYNC STATUS
+    // =========================================================================
+
+    /// Last recorded currency and provider sync runs
+    pub async fn get_sync_status(&self) -> Result<SyncStatusResponse, AdminError> {
+        self.swap_crud()
+            .get_sync_status()
+            .await
+            .map_err(|e| AdminError::DatabaseError(e.to_string()))
+    }
+
+    // =========================================================================
+    // PROVIDER HEALTH
+    // =========================================================================
+
+    /// Every provider's success rate, completion time, failures and score
+    /// over the scoring window, worst first
+    pub async fn get_provider_health(&self) -> Result<Vec<ProviderHealth>, AdminError> {
+        self.swap_crud()
+            .provider_health()
+            .await
+            .map_err(|e| AdminError::DatabaseError(e.to_string()))
+    }
+
+    // =========================================================================
+    // SHADOW QUOTES
+    // =========================================================================
+
+    /// Shadowed aggregators against the served quotes over the last `hours` hours
+    pub async fn get_shadow_quote_report(&self, hours: u32) -> Result<ShadowQuoteReport, AdminError> {
+        self.swap_crud()
+            .get_shadow_quote_report(hours)
+            .await
+            .map_err(|e| AdminError::DatabaseError(e.to_string()))
+    }
+
+    // =========================================================================
+    // PROVIDER PAYLOADS
+    // =========================================================================
+
+    /// Every stored provider response for a swap, oldest first
+    pub async fn get_provider_payloads(&self, swap_id: &str) -> Result<ProviderPayloadsResponse, AdminError> {
+        let exists: Option<(String,)> = sqlx::query_as("SELECT id FROM swaps WHERE id = ?")
+            .bind(swap_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        if exists.is_none() {
+            return Err(AdminError::NotFound("Swap".to_string()));
+        }
+
+        let rows: Vec<SwapProviderPayload> = sqlx::query_as(
+            "SELECT id, swap_id, call_type, payload_gz, original_bytes, truncated, created_at
+             FROM swap_provider_payloads WHERE swap_id = ? ORDER BY created_at, id",
+        )
+        .bind(swap_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let payloads = rows
+            .into_iter()
+            .map(|row| {
+                let raw = payload_codec::decode(&row.payload_gz).map_err(AdminError::DatabaseError)?;
+                let payload = serde_json::from_str(&raw).unwrap_or(serde_json::Value::String(raw));
+                Ok(ProviderPayloadResponse {
+                    id: row.id,
+                    call_type: row.call_type,
+                    payload,
+                    original_bytes: row.original_bytes,
+                    truncated: row.truncated,
+                    created_at: row.created_at,
+                })
+            })
+            .collect::<Result<Vec<_>, AdminError>>()?;
+
+        Ok(ProviderPayloadsResponse { swap_id: swap_id.to_string(), payloads })
+    }
+
+    // =========================================================================
+    // DEPOSIT CHECKS
+    // =========================================================================
+
+    /// The latest explorer check of a swap's deposit address
+    pub async fn get_deposit_check(&self, checker: &DepositChecker, swap_id: &str) -> Result<DepositEvidence, AdminError> {
+        checker
+            .get(swap_id)
+            .await?
+            .ok_or_else(|| AdminError::NotFound("Deposit check".to_string()))
+    }
+
+    /// Check a swap's deposit address now, replacing the stored evidence
+    pub async fn run_deposit_check(&self, checker: &DepositChecker, swap_id: &str) -> Result<DepositEvidence, AdminError> {
+        let swap = self
+            .swap_crud()
+            .find_swap(swap_id)
+            .await
+            .map_err(|e| AdminError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| AdminError::NotFound("Swap".to_string()))?;
+
+        checker.check(&swap, CheckTrigger::Admin).await.map_err(|e| match e {
+            DepositCheckError::Disabled | DepositCheckError::Unsupported(..) => AdminError::InvalidInput(e.to_string()),
+            DepositCheckError::Explorer(msg) => AdminError::UpstreamError(msg),
+            DepositCheckError::Database(e) => AdminError::DatabaseError(e.to_string()),
+        })
+    }
+
+    async fn find_currency(&self, currency_id: i64) -> Result<(String, String), AdminError> {
+        sqlx::query_as("SELECT symbol, network FROM currencies WHERE id = ?")
+            .bind(currency_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| AdminError::NotFound("Currency".to_string()))
+    }
+
+    async fn find_provider(&self, provider_id: &str) -> Result<ProviderAdminResponse, AdminError> {
+        sqlx::query_as(&format!("{} WHERE id = ?", PROVIDER_ADMIN_SELECT))
+            .bind(provider_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| AdminError::NotFound("Provider".to_string()))
+    }
+}
+
+const PROVIDER_ADMIN_SELECT: &str = "SELECT id, name, slug, is_active, kyc_rating, eta_minutes,
+    kyc_rating_override, eta_minutes_override, health_override, last_synced_at FROM providers";
+
+const KYC_RATINGS: [&str; 4] = ["A", "B", "C", "D"];
@@ -0,0 +1,64 @@
+use axum::{routing::{delete, get, patch, post, put}, Router};
+use std::sync::Arc;
+
+use crate::AppState;
+use super::controller::{
+    cancel_currency_delisting, clear_provider_overrides, list_affiliates, record_affiliate_payout, upsert_affiliate,
+    list_widget_keys, create_widget_key, update_widget_key, delete_widget_key, list_audit_entries, list_provider_credentials,
+    rotate_provider_credential, create_fee_rule, delete_address_format, delete_brand,
+    delete_fee_rule, get_cache_stats, get_deposit_check, get_maintenance, get_provider_health, get_provider_payloads, get_provider_schema_drift,
+    get_rate_limit_stats, get_reconciliation_report, get_retention_report, get_shadow_quotes, get_slo_report, get_sync_status, list_address_formats, list_brands,
+    list_fee_rules, list_high_value_swaps, list_jobs, list_providers, list_reconciliation_reports, run_deposit_check, run_job,
+    create_routing_rule, delete_routing_rule, dry_run_routing_rules, list_routing_rules, update_routing_rule,
+    schedule_currency_delisting, update_currency_policy,
+    update_fee_rule, update_maintenance, update_provider, update_user_fee_tier, upsert_address_format, upsert_brand,
+};
+
+pub fn admin_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/currencies/{id}/delisting",
+            post(schedule_currency_delisting).delete(cancel_currency_delisting),
+        )
+        .route("/currencies/{id}/policy", patch(update_currency_policy))
+        .route("/sync/status", get(get_sync_status))
+        .route("/swaps/high-value", get(list_high_value_swaps))
+        .route("/swaps/{id}/provider-payloads", get(get_provider_payloads))
+        .route("/swaps/{id}/deposit-check", get(get_deposit_check).post(run_deposit_check))
+        .route("/cache/stats", get(get_cache_stats))
+        .route("/rate-limit/stats", get(get_rate_limit_stats))
+        .route("/slo", get(get_slo_report))
+        .route("/providers", get(list_providers))
+        .route("/providers/{id}", patch(update_provider))
+        .route("/providers/{id}/overrides", delete(clear_provider_overrides))
+        .route("/providers/health", get(get_provider_health))
+        .route("/providers/schema-drift", get(get_provider_schema_drift))
+        .route("/providers/shadow-quotes", get(get_shadow_quotes))
+        .route("/provider-credentials", get(list_provider_credentials))
+        .route("/provider-credentials/{provider}", put(rotate_provider_credential))
+        .route("/maintenance", get(get_maintenance).put(update_maintenance))
+        .route("/address-formats", get(list_address_formats))
+        .route(
+            "/address-formats/{ticker}/{network}",
+            put(upsert_address_format).delete(delete_address_format),
+        )
+        .route("/brands", get(list_brands))
+        .route("/brands/{slug}", put(upsert_brand).delete(delete_brand))
+        .route("/affiliates", get(list_affiliates))
+        .route("/affiliates/{ref_code}", put(upsert_affiliate))
+        .route("/affiliates/{ref_code}/payouts", post(record_affiliate_payout))
+        .route("/widget-keys", get(list_widget_keys).post(create_widget_key))
+        .route("/widget-keys/{key}", put(update_widget_key).delete(delete_widget_key))
+        .route("/fee-rules", get(list_fee_rules).post(create_fee_rule))
+        .route("/fee-rules/{id}", put(update_fee_rule).delete(delete_fee_rule))
+        .route("/routing-rules", get(list_routing_rules).post(create_routing_rule))
+        .route("/routing-rules/dry-run", post(dry_run_routing_rules))
+        .route("/routing-rules/{id}", put(update_routing_rule).delete(delete_routing_rule))
+        .route("/users/{id}/fee-tier", put(update_user_fee_tier))
+        .route("/jobs", get(list_jobs))
+        .route("/jobs/{name}/run", post(run_job))
+        .route("/retention/report", get(get_retention_report))
+        .route("/reconciliation", get(list_reconciliation_reports))
+        .route("/reconciliation/{day}", get(get_reconciliation_report))
+        .route("/audit", get(list_audit_entries))
+}
@@ -0,0 +1,278 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+use crate::modules::swap::schema::{ProviderCallType, SwapStatus};
+use crate::services::address_format::{ChecksumAlgorithm, MemoFormat};
+use crate::services::branding::PairRule;
+use crate::services::cache_stats::PrefixCacheStats;
+use crate::services::tenant::TenantId;
+
+// =============================================================================
+// CURRENCY DELISTING
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScheduleDelistingRequest {
+    pub delisting_at: DateTime<Utc>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DelistingResponse {
+    pub currency_id: i64,
+    pub ticker: String,
+    pub network: String,
+    pub delisting_at: Option<DateTime<Utc>>,
+    pub notified_users: u64,
+}
+
+// =============================================================================
+// CURRENCY POLICY
+// =============================================================================
+
+// Omitted fields are left unchanged
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateCurrencyPolicyRequest {
+    #[serde(default)]
+    pub requires_refund_address: Option<bool>,
+    #[serde(default)]
+    pub is_active: Option<bool>, // false keeps the currency off through later syncs
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CurrencyPolicyResponse {
+    pub currency_id: i64,
+    pub ticker: String,
+    pub network: String,
+    pub requires_refund_address: bool,
+    pub is_active: bool,
+}
+
+// =============================================================================
+// PROVIDERS
+// =============================================================================
+
+// Omitted fields are left unchanged; overrides hold until cleared
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateProviderRequest {
+    #[serde(default)]
+    pub is_active: Option<bool>,
+    #[serde(default)]
+    pub kyc_rating: Option<String>, // A, B, C or D
+    #[serde(default)]
+    pub eta_minutes: Option<i32>,
+    #[serde(default)]
+    pub health_override: Option<String>, // trusted, demoted or hidden
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ProviderAdminResponse {
+    pub id: String,
+    pub name: String,
+    pub slug: String,
+    pub is_active: bool,
+    pub kyc_rating: String,   // Effective rating, override included
+    pub eta_minutes: Option<i32>,
+    pub kyc_rating_override: Option<String>,
+    pub eta_minutes_override: Option<i32>,
+    pub health_override: Option<String>, // Pinned over the health score
+    pub last_synced_at: Option<DateTime<Utc>>,
+}
+
+// =============================================================================
+// HIGH-VALUE SWAPS
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct HighValueSwapResponse {
+    pub id: String,
+    pub user_id: Option<String>,
+    pub brand: Option<String>,
+    pub provider_id: String,
+    pub provider_swap_id: Option<String>,
+    pub from_currency: String,
+    pub from_network: String,
+    pub to_currency: String,
+    pub to_network: String,
+    pub amount: Decimal,
+    pub estimated_receive: Decimal,
+    pub usd_value: Option<f64>,
+    pub status: SwapStatus,
+    #[sqlx(skip)]
+    pub stalled: bool, // Funded and in the same status for longer than the stall window
+    pub stall_alerted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// =============================================================================
+// CACHE STATS
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CacheStatsWindow {
+    pub window_minutes: u64,
+    pub prefixes: Vec<PrefixCacheStats>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CacheStatsResponse {
+    pub windows: Vec<CacheStatsWindow>,
+}
+
+// =============================================================================
+// PROVIDER PAYLOADS
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProviderPayloadResponse {
+    pub id: i64,
+    pub call_type: ProviderCallType,
+    /// Parsed JSON when the stored body is valid JSON, otherwise the raw text
+    pub payload: serde_json::Value,
+    pub original_bytes: u32,
+    pub truncated: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProviderPayloadsResponse {
+    pub swap_id: String,
+    pub payloads: Vec<ProviderPayloadResponse>,
+}
+
+// =============================================================================
+// MAINTENANCE MODE
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateMaintenanceRequest {
+    pub enabled: bool,
+    #[serde(default)]
+    pub messages: HashMap<String, String>, // Language code -> message shown to clients
+    #[serde(default)]
+    pub retry_after_secs: Option<u32>,
+    #[serde(default)]
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+// =============================================================================
+// ADDRESS FORMATS
+// =============================================================================
+
+// Ticker and network come from the path; ticker "*" covers the whole network
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpsertAddressFormatRequest {
+    pub address_regex: String,
+    #[serde(default)]
+    pub checksum: ChecksumAlgorithm,
+    #[serde(default)]
+    pub memo_name: Option<String>,
+    #[serde(default)]
+    pub memo_required: bool,
+    #[serde(default)]
+    pub memo_format: MemoFormat,
+    #[serde(default)]
+    pub memo_max_length: Option<u32>,
+    #[serde(default)]
+    pub memo_max_value: Option<u64>,
+    #[serde(default)]
+    pub uri_scheme: Option<String>,
+    #[serde(default)]
+    pub uri_memo_param: Option<String>,
+}
+
+// =============================================================================
+// BRANDS
+// =============================================================================
+
+// Slug comes from the path
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpsertBrandRequest {
+    pub name: String,
+    pub support_email: String,
+    #[serde(default)]
+    pub tenant: TenantId, // Omitted puts the brand in the default tenant
+    #[serde(default)]
+    pub domains: Vec<String>,
+    #[serde(default)]
+    pub markup_percent: Option<f64>,
+    #[serde(default)]
+    pub enabled_providers: Vec<String>,
+    #[serde(default)]
+    pub allowed_pairs: Vec<PairRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>, // Replaces the brand's key; omitted keeps the current one
+}
+
+// =============================================================================
+// FEES
+// =============================================================================
+
+/// `?tenant=` on fee and routing rule endpoints; omitted means the default tenant
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TenantQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<TenantId>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateFeeTierRequest {
+    pub fee_tier: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserFeeTierResponse {
+    pub user_id: String,
+    pub fee_tier: String,
+}
+
+// =============================================================================
+// SHADOW QUOTES
+// =============================================================================
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ShadowQuotesQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hours: Option<u32>, // Reporting window, default 24
+}
+
+// =============================================================================
+// RECONCILIATION
+// =============================================================================
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReconciliationQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>, // Reports listed, newest first; default 30
+}
+
+// =============================================================================
+// PROVIDER CREDENTIALS
+// =============================================================================
+
+// Provider comes from the path
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RotateProviderCredentialRequest {
+    pub api_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>, // API root; omitted uses the provider's default
+}
+
+// =============================================================================
+// ERROR RESPONSE
+// =============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct AdminErrorResponse {
+    pub error: String,
+}
+
+impl AdminErrorResponse {
+    pub fn new(error: impl Into<String>) -> Self {
+        Self { error: error.into() }
+    }
+}
@@ -0,0 +1,76 @@
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use std::sync::Arc;
+
+use crate::AppState;
+use super::crud::{AffiliateCrud, AffiliateError};
+use super::model::Affiliate;
+use super::schema::{
+    AffiliateErrorResponse, AffiliatePayoutsQuery, AffiliatePayoutsResponse, AffiliateStatsQuery,
+    AffiliateStatsResponse,
+};
+
+type AffiliateResult<T> = Result<Json<T>, (StatusCode, Json<AffiliateErrorResponse>)>;
+
+fn error_response(e: AffiliateError) -> (StatusCode, Json<AffiliateErrorResponse>) {
+    let status = match e {
+        AffiliateError::Unauthorized => StatusCode::UNAUTHORIZED,
+        AffiliateError::AffiliateNotFound => StatusCode::NOT_FOUND,
+        AffiliateError::InvalidInput(_) => StatusCode::BAD_REQUEST,
+        AffiliateError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(AffiliateErrorResponse::new(e.to_string())))
+}
+
+/// The affiliate owning the request's X-API-Key
+async fn authenticate(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<Affiliate, (StatusCode, Json<AffiliateErrorResponse>)> {
+    let api_key = headers
+        .get("x-api-key")
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty());
+
+    AffiliateCrud::new(state.db.clone()).authenticate(api_key).await.map_err(error_response)
+}
+
+// =============================================================================
+// GET /affiliate/stats - Referred swaps, commission earned and balances
+// =============================================================================
+
+pub async fn get_stats(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<AffiliateStatsQuery>,
+) -> AffiliateResult<AffiliateStatsResponse> {
+    let affiliate = authenticate(&state, &headers).await?;
+    let stats = AffiliateCrud::new(state.db.clone())
+        .stats(&affiliate, &query)
+        .await
+        .map_err(error_response)?;
+
+    Ok(Json(stats))
+}
+
+// =============================================================================
+// GET /affiliate/payouts - Commission paid out so far
+// =============================================================================
+
+pub async fn get_payouts(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<AffiliatePayoutsQuery>,
+) -> AffiliateResult<AffiliatePayoutsResponse> {
+    let affiliate = authenticate(&state, &headers).await?;
+    let payouts = AffiliateCrud::new(state.db.clone())
+        .payouts(&affiliate.ref_code, &query)
+        .await
+        .map_err(error_response)?;
+
+    Ok(Json(AffiliatePayoutsResponse { payouts }))
+}
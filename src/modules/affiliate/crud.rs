@@ -0,0 +1,285 @@
+use chrono::Utc;
+use rust_decimal::Decimal;
+use sqlx::{MySql, Pool};
+use std::collections::BTreeMap;
+
+use super::model::{Affiliate, AffiliatePayout};
+use super::schema::{
+    AffiliatePayoutsQuery, AffiliateStatsQuery, AffiliateStatsResponse, CommissionBalance, CommissionTotal,
+    RecordPayoutRequest, UpsertAffiliateRequest, DEFAULT_PAYOUTS_LIMIT, DEFAULT_STATS_DAYS, MAX_PAYOUTS_LIMIT,
+    MAX_STATS_DAYS,
+};
+use crate::services::branding::hash_api_key;
+use crate::services::tenant::TenantId;
+
+// =============================================================================
+// AFFILIATE ERROR
+// =============================================================================
+
+#[derive(Debug)]
+pub enum AffiliateError {
+    Unauthorized, // Missing or unknown X-API-Key
+    AffiliateNotFound,
+    InvalidInput(String),
+    DatabaseError(String),
+}
+
+impl std::fmt::Display for AffiliateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AffiliateError::Unauthorized => write!(f, "A valid affiliate X-API-Key is required"),
+            AffiliateError::AffiliateNotFound => write!(f, "Affiliate not found"),
+            AffiliateError::InvalidInput(reason) => write!(f, "Invalid input: {}", reason),
+            AffiliateError::DatabaseError(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl From<sqlx::Error> for AffiliateError {
+    fn from(err: sqlx::Error) -> Self {
+        AffiliateError::DatabaseError(err.to_string())
+    }
+}
+
+// =============================================================================
+// AFFILIATE CRUD
+// =============================================================================
+
+pub struct AffiliateCrud {
+    pool: Pool<MySql>,
+}
+
+impl AffiliateCrud {
+    pub fn new(pool: Pool<MySql>) -> Self {
+        Self { pool }
+    }
+
+    /// The active affiliate of `tenant` a swap with `?ref=ref_code` is
+    /// attributed to. Unknown codes and lookup failures attribute nothing:
+    /// a stale referral link must not stop the swap.
+    pub async fn referrer(&self, ref_code: &str, tenant: &TenantId) -> Option<Affiliate> {
+        let ref_code = ref_code.trim().to_lowercase();
+        let found = sqlx::query_as::<_, Affiliate>(&format!(
+            "{} WHERE ref_code = ? AND tenant_id = ? AND is_active = TRUE",
+            AFFILIATE_SELECT
+        ))
+        .bind(&ref_code)
+        .bind(tenant.as_str())
+        .fetch_optional(&self.pool)
+        .await;
+
+        match found {
+            Ok(affiliate) => affiliate,
+            Err(e) => {
+                tracing::warn!("Failed to look up affiliate {}: {}", ref_code, e);
+                None
+            }
+        }
+    }
+
+    /// The affiliate holding `api_key`, active or not
+    pub async fn authenticate(&self, api_key: Option<&str>) -> Result<Affiliate, AffiliateError> {
+        let api_key = api_key.ok_or(AffiliateError::Unauthorized)?;
+        sqlx::query_as::<_, Affiliate>(&format!("{} WHERE api_key_hash = ?", AFFILIATE_SELECT))
+            .bind(hash_api_key(api_key))
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or(AffiliateError::Unauthorized)
+    }
+
+    pub async fn find(&self, ref_code: &str) -> Result<Option<Affiliate>, AffiliateError> {
+        Ok(sqlx::query_as::<_, Affiliate>(&format!("{} WHERE ref_code = ?", AFFILIATE_SELECT))
+            .bind(ref_code)
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    /// Every affiliate, ordered by ref code
+    pub async fn all(&self) -> Result<Vec<Affiliate>, AffiliateError> {
+        Ok(sqlx::query_as::<_, Affiliate>(&format!("{} ORDER BY ref_code", AFFILIATE_SELECT))
+            .fetch_all(&self.pool)
+            .await?)
+    }
+
+    /// Create or replace an affiliate; `api_key` replaces the stored key,
+    /// `None` keeps it
+    pub async fn upsert(&self, ref_code: &str, request: &UpsertAffiliateRequest) -> Result<Affiliate, AffiliateError> {
+        request.check_rules(ref_code).map_err(AffiliateError::InvalidInput)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO affiliates (ref_code, tenant_id, name, api_key_hash, commission_percent, is_active)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                tenant_id = VALUES(tenant_id), name = VALUES(name),
+                api_key_hash = COALESCE(VALUES(api_key_hash), api_key_hash),
+                commission_percent = VALUES(commission_percent), is_active = VALUES(is_active)
+            "#,
+        )
+        .bind(ref_code)
+        .bind(request.tenant.as_str())
+        .bind(request.name.trim())
+        .bind(request.api_key.as_deref().map(hash_api_key))
+        .bind(request.commission_percent)
+        .bind(request.is_active)
+        .execute(&self.pool)
+        .await?;
+
+        self.find(ref_code).await?.ok_or(AffiliateError::AffiliateNotFound)
+    }
+
+    // =========================================================================
+    // STATS
+    // =========================================================================
+
+    /// Swaps and commission of the last `days`, and balances over all time.
+    /// Commission counts once a swap completes; failed, refunded and expired
+    /// swaps earn nothing.
+    pub async fn stats(
+        &self,
+        affiliate: &Affiliate,
+        query: &AffiliateStatsQuery,
+    ) -> Result<AffiliateStatsResponse, AffiliateError> {
+        let days = query.days.unwrap_or(DEFAULT_STATS_DAYS).clamp(1, MAX_STATS_DAYS);
+        let since = Utc::now() - chrono::Duration::days(days as i64);
+
+        let (swaps, completed_swaps): (i64, i64) = sqlx::query_as(
+            "SELECT COUNT(*), CAST(COALESCE(SUM(status = 'completed'), 0) AS SIGNED)
+             FROM swaps
+             WHERE affiliate = ? AND is_sandbox = FALSE AND created_at >= ?",
+        )
+        .bind(&affiliate.ref_code)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let commissions = sqlx::query_as::<_, CommissionTotal>(
+            "SELECT to_currency AS currency,
+                    COALESCE(SUM(IF(status = 'completed', COALESCE(actual_receive, estimated_receive), 0)), 0) AS volume,
+                    COALESCE(SUM(IF(status = 'completed', affiliate_commission, 0)), 0) AS earned,
+                    COALESCE(SUM(IF(status IN ('waiting', 'confirming', 'exchanging', 'sending'), affiliate_commission, 0)), 0) AS pending
+             FROM swaps
+             WHERE affiliate = ? AND is_sandbox = FALSE AND created_at >= ?
+             GROUP BY to_currency
+             ORDER BY to_currency",
+        )
+        .bind(&affiliate.ref_code)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(AffiliateStatsResponse {
+            ref_code: affiliate.ref_code.clone(),
+            name: affiliate.name.clone(),
+            commission_percent: affiliate.commission_percent,
+            since,
+            swaps: swaps as u64,
+            completed_swaps: completed_swaps as u64,
+            commissions,
+            balances: self.balances(&affiliate.ref_code).await?,
+        })
+    }
+
+    /// Earned and paid commission per currency, over all time
+    async fn balances(&self, ref_code: &str) -> Result<Vec<CommissionBalance>, AffiliateError> {
+        let earned: Vec<(String, Decimal)> = sqlx::query_as(
+            "SELECT to_currency, SUM(affiliate_commission)
+             FROM swaps
+             WHERE affiliate = ? AND is_sandbox = FALSE AND status = 'completed'
+             GROUP BY to_currency",
+        )
+        .bind(ref_code)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let paid: Vec<(String, Decimal)> = sqlx::query_as(
+            "SELECT currency, SUM(amount) FROM affiliate_payouts WHERE affiliate = ? GROUP BY currency",
+        )
+        .bind(ref_code)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(commission_balances(&earned, &paid))
+    }
+
+    // =========================================================================
+    // PAYOUTS
+    // =========================================================================
+
+    /// The affiliate's payouts, newest first
+    pub async fn payouts(
+        &self,
+        ref_code: &str,
+        query: &AffiliatePayoutsQuery,
+    ) -> Result<Vec<AffiliatePayout>, AffiliateError> {
+        let limit = query.limit.unwrap_or(DEFAULT_PAYOUTS_LIMIT).clamp(1, MAX_PAYOUTS_LIMIT);
+        Ok(sqlx::query_as::<_, AffiliatePayout>(&format!(
+            "{} WHERE affiliate = ? AND id < ? ORDER BY id DESC LIMIT ?",
+            PAYOUT_SELECT
+        ))
+        .bind(ref_code)
+        .bind(query.before_id.unwrap_or(u64::MAX))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Record commission paid out of band; nothing is sent from here
+    pub async fn record_payout(
+        &self,
+        ref_code: &str,
+        request: &RecordPayoutRequest,
+        admin_id: &str,
+    ) -> Result<AffiliatePayout, AffiliateError> {
+        request.check_rules().map_err(AffiliateError::InvalidInput)?;
+        self.find(ref_code).await?.ok_or(AffiliateError::AffiliateNotFound)?;
+
+        let id = sqlx::query(
+            "INSERT INTO affiliate_payouts (affiliate, currency, amount, tx_hash, note, created_by)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(ref_code)
+        .bind(request.currency.trim().to_lowercase())
+        .bind(request.amount)
+        .bind(request.tx_hash.as_deref().map(str::trim).filter(|h| !h.is_empty()))
+        .bind(request.note.as_deref().map(str::trim).filter(|n| !n.is_empty()))
+        .bind(admin_id)
+        .execute(&self.pool)
+        .await?
+        .last_insert_id();
+
+        Ok(sqlx::query_as::<_, AffiliatePayout>(&format!("{} WHERE id = ?", PAYOUT_SELECT))
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?)
+    }
+}
+
+/// Earned and paid amounts per currency merged into balances, by currency
+pub fn commission_balances(earned: &[(String, Decimal)], paid: &[(String, Decimal)]) -> Vec<CommissionBalance> {
+    let mut totals: BTreeMap<String, (Decimal, Decimal)> = BTreeMap::new();
+    for (currency, amount) in earned {
+        totals.entry(currency.to_lowercase()).or_default().0 += amount;
+    }
+    for (currency, amount) in paid {
+        totals.entry(currency.to_lowercase()).or_default().1 += amount;
+    }
+
+    totals
+        .into_iter()
+        .map(|(currency, (earned, paid))| CommissionBalance { currency, earned, paid, owed: earned - paid })
+        .collect()
+}
+
+const AFFILIATE_SELECT: &str = r#"
+    SELECT ref_code, tenant_id, name,
+           commission_percent,
+           is_active, api_key_hash IS NOT NULL AS has_api_key,
+           created_at, updated_at
+    FROM affiliates
+"#;
+
+const PAYOUT_SELECT: &str = r#"
+    SELECT id, affiliate, currency, amount, tx_hash, note, created_at
+    FROM affiliate_payouts
+"#;
@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::{Decimal, RoundingStrategy};
+use sqlx::FromRow;
+use serde::{Deserialize, Serialize};
+
+// =============================================================================
+// AFFILIATE
+// =============================================================================
+
+/// Referral partner; swaps created with `?ref={ref_code}` are attributed to it
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Affiliate {
+    pub ref_code: String,
+    pub tenant_id: String, // Only swaps of this tenant are attributed
+    pub name: String,
+    pub commission_percent: Decimal, // Share of the platform fee
+    pub is_active: bool,
+    pub has_api_key: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Affiliate {
+    /// The affiliate's share of a swap's platform fee, in the same currency,
+    /// rounded down to the 8 places swaps.affiliate_commission keeps
+    pub fn commission_on(&self, platform_fee: Decimal) -> Decimal {
+        (platform_fee.max(Decimal::ZERO) * self.commission_percent / Decimal::ONE_HUNDRED)
+            .round_dp_with_strategy(8, RoundingStrategy::ToZero)
+            .normalize()
+    }
+}
+
+// =============================================================================
+// AFFILIATE PAYOUT
+// =============================================================================
+
+/// Commission paid out to an affiliate, as recorded by an admin
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct AffiliatePayout {
+    pub id: u64,
+    pub affiliate: String, // ref_code
+    pub currency: String,
+    pub amount: Decimal,
+    pub tx_hash: Option<String>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
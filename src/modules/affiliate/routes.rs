@@ -0,0 +1,12 @@
+use axum::{routing::get, Router};
+use std::sync::Arc;
+
+use crate::AppState;
+use super::controller::{get_payouts, get_stats};
+
+/// Referral partner figures, mounted under /affiliate
+pub fn affiliate_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/stats", get(get_stats))
+        .route("/payouts", get(get_payouts))
+}
@@ -0,0 +1,191 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use super::model::AffiliatePayout;
+use crate::services::branding::MIN_API_KEY_LENGTH;
+use crate::services::money;
+use crate::services::tenant::TenantId;
+
+/// Days covered by GET /affiliate/stats when `days` is omitted
+pub const DEFAULT_STATS_DAYS: u32 = 30;
+
+/// Longest window GET /affiliate/stats reports on
+pub const MAX_STATS_DAYS: u32 = 365;
+
+/// Payouts listed when `limit` is omitted, and the most listed at once
+pub const DEFAULT_PAYOUTS_LIMIT: u32 = 50;
+pub const MAX_PAYOUTS_LIMIT: u32 = 200;
+
+// =============================================================================
+// REFERRALS
+// =============================================================================
+
+/// `?ref=` on POST /swap/create
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReferralQuery {
+    #[serde(default, rename = "ref")]
+    pub ref_code: Option<String>,
+}
+
+// =============================================================================
+// GET /affiliate/stats
+// =============================================================================
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AffiliateStatsQuery {
+    #[serde(default)]
+    pub days: Option<u32>, // Default DEFAULT_STATS_DAYS, at most MAX_STATS_DAYS
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AffiliateStatsResponse {
+    pub ref_code: String,
+    pub name: String,
+    pub commission_percent: Decimal,
+    pub since: DateTime<Utc>,
+    /// Attributed swaps created since `since`, sandbox swaps excluded
+    pub swaps: u64,
+    pub completed_swaps: u64,
+    /// Per receive currency, for the swaps since `since`
+    pub commissions: Vec<CommissionTotal>,
+    /// Per currency, over all time
+    pub balances: Vec<CommissionBalance>,
+}
+
+/// Commission on an affiliate's swaps in one currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CommissionTotal {
+    pub currency: String,
+    pub volume: Decimal,  // Received by users of completed swaps
+    pub earned: Decimal,  // Commission on completed swaps
+    pub pending: Decimal, // Commission on swaps still in flight
+}
+
+/// Commission earned against what was paid out, in one currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommissionBalance {
+    pub currency: String,
+    pub earned: Decimal,
+    pub paid: Decimal,
+    pub owed: Decimal, // earned - paid; negative after an advance
+}
+
+// =============================================================================
+// GET /affiliate/payouts
+// =============================================================================
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AffiliatePayoutsQuery {
+    #[serde(default)]
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub before_id: Option<u64>, // Page back from this payout
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AffiliatePayoutsResponse {
+    pub payouts: Vec<AffiliatePayout>, // Newest first
+}
+
+// =============================================================================
+// ADMIN
+// =============================================================================
+
+/// Longest ref code accepted
+pub const MAX_REF_CODE_LENGTH: usize = 32;
+
+/// Highest share of the platform fee an affiliate may get
+pub const MAX_COMMISSION_PERCENT: Decimal = dec!(100);
+
+/// PUT /admin/affiliates/{ref_code}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpsertAffiliateRequest {
+    pub name: String,
+    #[serde(default)]
+    pub tenant: TenantId, // Omitted puts the affiliate in the default tenant
+    pub commission_percent: Decimal,
+    #[serde(default = "default_active")]
+    pub is_active: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>, // Replaces the affiliate's key; omitted keeps the current one
+}
+
+fn default_active() -> bool {
+    true
+}
+
+impl UpsertAffiliateRequest {
+    pub fn check_rules(&self, ref_code: &str) -> Result<(), String> {
+        check_ref_code(ref_code)?;
+        if self.name.trim().is_empty() {
+            return Err("name is required".to_string());
+        }
+        if !(Decimal::ZERO..=MAX_COMMISSION_PERCENT).contains(&self.commission_percent) {
+            return Err(format!("commission_percent must be between 0 and {}", MAX_COMMISSION_PERCENT));
+        }
+        if self.api_key.as_deref().is_some_and(|k| k.len() < MIN_API_KEY_LENGTH) {
+            return Err(format!("api_key must be at least {} characters", MIN_API_KEY_LENGTH));
+        }
+        Ok(())
+    }
+}
+
+pub fn check_ref_code(ref_code: &str) -> Result<(), String> {
+    let valid = !ref_code.is_empty()
+        && ref_code.len() <= MAX_REF_CODE_LENGTH
+        && ref_code.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("ref code must be 1-{} lowercase letters, digits or '-'", MAX_REF_CODE_LENGTH))
+    }
+}
+
+/// Places affiliate_payouts.amount keeps
+pub const PAYOUT_DECIMALS: u32 = 8;
+
+/// POST /admin/affiliates/{ref_code}/payouts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordPayoutRequest {
+    pub currency: String,
+    pub amount: Decimal,
+    #[serde(default)]
+    pub tx_hash: Option<String>,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+impl RecordPayoutRequest {
+    pub fn check_rules(&self) -> Result<(), String> {
+        if self.currency.trim().is_empty() {
+            return Err("currency is required".to_string());
+        }
+        if self.amount <= Decimal::ZERO {
+            return Err("amount must be positive".to_string());
+        }
+        if money::exceeds_decimals(self.amount, PAYOUT_DECIMALS) {
+            return Err(format!("amount must have at most {} decimal places", PAYOUT_DECIMALS));
+        }
+        if self.note.as_deref().is_some_and(|n| n.chars().count() > 255) {
+            return Err("note must be at most 255 characters".to_string());
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// ERRORS
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AffiliateErrorResponse {
+    pub error: String,
+}
+
+impl AffiliateErrorResponse {
+    pub fn new(error: impl Into<String>) -> Self {
+        Self { error: error.into() }
+    }
+}
@@ -8,12 +8,21 @@ use crate::AppState;
 use crate::modules::auth::{
     crud::{AuthError, UserCrud},
     model::User,
-    schema::{LoginRequest, LoginResponse, RegisterRequest, RegisterResponse, UserResponse, ErrorResponse},
+    interface::AuthUser,
+    schema::{
+        LoginRequest, LoginResponse, RegisterRequest, RegisterResponse, SwapPreferences,
+        UpdateNotificationPreferencesRequest, UserResponse, ErrorResponse,
+    },
 };
+use crate::modules::swap::crud::parse_kyc_rating;
+use crate::services::fees::DEFAULT_FEE_TIER;
 use crate::services::hashing;
+use crate::services::notifications::NotificationPreferences;
+use crate::services::tenant::CurrentTenant;
 
 pub async fn register(
     State(state): State<Arc<AppState>>,
+    CurrentTenant(tenant): CurrentTenant,
     Json(req): Json<RegisterRequest>,
 ) -> Result<(StatusCode, Json<RegisterResponse>), (StatusCode, Json<ErrorResponse>)> {
     if let Err(e) = req.validate() {
@@ -37,7 +46,7 @@ pub async fn register(
         ));
     }
 
-    let crud = UserCrud::new(state.db.clone(), &state.jwt_service);
+    let crud = UserCrud::new(state.db.clone(), &state.jwt_service).with_tenant(tenant.clone());
 
     if crud.email_exists(&req.email).await.map_err(|e| {
         (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse::new(e.to_string())))
@@ -55,11 +64,15 @@ pub async fn register(
     let now = Utc::now();
     let user = User {
         id: Uuid::new_v4().to_string(),
+        tenant_id: tenant.to_string(),
         email: req.email.clone(),
         password_hash,
         email_verified: false,
         two_factor_enabled: false,
         two_factor_secret: None,
+        role: "user".to_string(),
+        fee_tier: DEFAULT_FEE_TIER.to_string(),
+        max_kyc_rating: None,
         created_at: now,
         updated_at: now,
     };
@@ -96,9 +109,10 @@ pub async fn register(
 
 pub async fn login(
     State(state): State<Arc<AppState>>,
+    CurrentTenant(tenant): CurrentTenant,
     Json(req): Json<LoginRequest>,
 ) -> Result<(StatusCode, Json<LoginResponse>), (StatusCode, Json<ErrorResponse>)> {
-    let crud = UserCrud::new(state.db.clone(), &state.jwt_service);
+    let crud = UserCrud::new(state.db.clone(), &state.jwt_service).with_tenant(tenant);
 
     let result = crud.login(&req.email, &req.password).await.map_err(|e| {
         match e {
@@ -118,8 +132,90 @@ pub async fn login(
         Json(LoginResponse {
             access_token: result.access_token,
             refresh_token: result.refresh_token,
-            token_type: "Bearer",
+            token_type: "Bearer".to_string(),
             expires_in: result.expires_in,
         }),
     ))
 }
+
+// =============================================================================
+// NOTIFICATION PREFERENCES
+// =============================================================================
+
+pub async fn get_notification_preferences(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+) -> Result<Json<NotificationPreferences>, (StatusCode, Json<ErrorResponse>)> {
+    let preferences = NotificationPreferences::load(&state.db, &user.id).await.map_err(|e| {
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse::new(e.to_string())))
+    })?;
+
+    Ok(Json(preferences))
+}
+
+pub async fn update_notification_preferences(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+    Json(req): Json<UpdateNotificationPreferencesRequest>,
+) -> Result<Json<NotificationPreferences>, (StatusCode, Json<ErrorResponse>)> {
+    let mut preferences = NotificationPreferences::load(&state.db, &user.id).await.map_err(|e| {
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse::new(e.to_string())))
+    })?;
+
+    if let Some(chat_id) = req.telegram_chat_id {
+        let chat_id = chat_id.trim();
+        if chat_id.len() > 64 {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::new("Telegram chat id is too long")),
+            ));
+        }
+        preferences.telegram_chat_id = (!chat_id.is_empty()).then(|| chat_id.to_string());
+    }
+    preferences.email_enabled = req.email_enabled.unwrap_or(preferences.email_enabled);
+    preferences.telegram_enabled = req.telegram_enabled.unwrap_or(preferences.telegram_enabled);
+    preferences.swap_completed = req.swap_completed.unwrap_or(preferences.swap_completed);
+    preferences.swap_failed = req.swap_failed.unwrap_or(preferences.swap_failed);
+    preferences.action_required = req.action_required.unwrap_or(preferences.action_required);
+
+    if preferences.telegram_enabled && preferences.telegram_chat_id.is_none() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new("Telegram notifications need a telegram_chat_id")),
+        ));
+    }
+
+    preferences.save(&state.db, &user.id).await.map_err(|e| {
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse::new(e.to_string())))
+    })?;
+
+    Ok(Json(preferences))
+}
+
+// =============================================================================
+// SWAP PREFERENCES
+// =============================================================================
+
+pub async fn get_swap_preferences(AuthUser(user): AuthUser) -> Json<SwapPreferences> {
+    Json(SwapPreferences { max_kyc_rating: user.kyc_limit().map(String::from) })
+}
+
+pub async fn update_swap_preferences(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+    Json(req): Json<SwapPreferences>,
+) -> Result<Json<SwapPreferences>, (StatusCode, Json<ErrorResponse>)> {
+    let max_kyc_rating = req
+        .max_kyc_rating
+        .as_deref()
+        .map(parse_kyc_rating)
+        .transpose()
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse::new(e.to_string()))))?;
+
+    let crud = UserCrud::new(state.db.clone(), &state.jwt_service);
+    crud.set_max_kyc_rating(&user.id, max_kyc_rating).await.map_err(|e| {
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse::new(e.to_string())))
+    })?;
+
+    Ok(Json(SwapPreferences { max_kyc_rating: max_kyc_rating.map(String::from) }))
+}
@@ -1,10 +1,11 @@
 use sqlx::{MySql, Pool};
 use crate::modules::auth::model::User;
-use crate::services::{hashing, jwt::JwtService};
+use crate::services::{hashing, jwt::JwtService, tenant::TenantId};
 
 pub struct UserCrud<'a> {
     pool: Pool<MySql>,
     jwt_service: &'a JwtService,
+    tenant: TenantId, // Scopes lookups by email; ids are unique across tenants
 }
 
 #[derive(Debug)]
@@ -40,22 +41,31 @@ impl<'a> UserCrud<'a> {
         Self {
             pool,
             jwt_service,
+            tenant: TenantId::default(),
         }
     }
 
+    /// Look up and register users by email within `tenant`
+    pub fn with_tenant(mut self, tenant: TenantId) -> Self {
+        self.tenant = tenant;
+        self
+    }
+
     pub async fn create(&self, user: &User) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
-            INSERT INTO users (id, email, password_hash, email_verified, two_factor_enabled, two_factor_secret, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO users (id, tenant_id, email, password_hash, email_verified, two_factor_enabled, two_factor_secret, role, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&user.id)
+        .bind(&user.tenant_id)
         .bind(&user.email)
         .bind(&user.password_hash)
         .bind(user.email_verified)
         .bind(user.two_factor_enabled)
         .bind(&user.two_factor_secret)
+        .bind(&user.role)
         .bind(user.created_at)
         .bind(user.updated_at)
         .execute(&self.pool)
@@ -72,14 +82,16 @@ impl<'a> UserCrud<'a> {
     }
 
     pub async fn find_by_email(&self, email: &str) -> Result<Option<User>, sqlx::Error> {
-        sqlx::query_as::<_, User>("SELECT * FROM users WHERE email = ?")
+        sqlx::query_as::<_, User>("SELECT * FROM users WHERE tenant_id = ? AND email = ?")
+            .bind(self.tenant.as_str())
             .bind(email)
             .fetch_optional(&self.pool)
             .await
     }
 
     pub async fn email_exists(&self, email: &str) -> Result<bool, sqlx::Error> {
-        let result: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM users WHERE email = ?")
+        let result: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM users WHERE tenant_id = ? AND email = ?")
+            .bind(self.tenant.as_str())
             .bind(email)
             .fetch_one(&self.pool)
             .await?;
@@ -87,6 +99,16 @@ impl<'a> UserCrud<'a> {
         Ok(result.0 > 0)
     }
 
+    /// Set or clear (None) the worst provider KYC rating the user accepts
+    pub async fn set_max_kyc_rating(&self, user_id: &str, max_kyc_rating: Option<char>) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE users SET max_kyc_rating = ? WHERE id = ?")
+            .bind(max_kyc_rating.map(String::from))
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
     pub async fn login(&self, email: &str, password: &str) -> Result<LoginResult, AuthError> {
         let user = self.find_by_email(email)
             .await
@@ -1,12 +1,14 @@
 use async_trait::async_trait;
 use axum::{
     extract::{FromRequestParts, FromRef},
-    http::request::Parts,
+    http::{request::Parts, StatusCode},
+    Json,
 };
 use std::sync::Arc;
 
 use crate::AppState;
 use super::model::{BackupCode, EmailVerification, PasswordReset, RefreshToken, User};
+use super::schema::ErrorResponse;
 
 // =============================================================================
 // EXTRACTORS
@@ -30,8 +32,7 @@ where
             .and_then(|h| h.to_str().ok());
 
         if let Some(auth_header) = auth_header {
-            if auth_header.starts_with("Bearer ") {
-                let token = &auth_header[7..];
+            if let Some(token) = auth_header.strip_prefix("Bearer ") {
                 if let Ok(claims) = state.jwt_service.verify_access_token(token) {
                     let user_id = claims.claims.sub;
                     let crud = super::crud::UserCrud::new(state.db.clone(), &state.jwt_service);
@@ -46,6 +47,67 @@ where
     }
 }
 
+/// Requires a valid access token
+pub struct AuthUser(pub User);
+
+impl<S> FromRequestParts<S> for AuthUser
+where
+    Arc<AppState>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<ErrorResponse>);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> std::result::Result<Self, Self::Rejection> {
+        let OptionalUser(user) = match OptionalUser::from_request_parts(parts, state).await {
+            Ok(user) => user,
+            Err(never) => match never {},
+        };
+
+        user.map(AuthUser).ok_or_else(|| {
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(ErrorResponse::new("Authentication required")),
+            )
+        })
+    }
+}
+
+/// Requires a valid access token belonging to a user with the admin role
+pub struct AdminUser(pub User);
+
+impl<S> FromRequestParts<S> for AdminUser
+where
+    Arc<AppState>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<ErrorResponse>);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> std::result::Result<Self, Self::Rejection> {
+        let OptionalUser(user) = match OptionalUser::from_request_parts(parts, state).await {
+            Ok(user) => user,
+            Err(never) => match never {},
+        };
+
+        match user {
+            Some(user) if user.is_admin() => Ok(AdminUser(user)),
+            Some(_) => Err((
+                StatusCode::FORBIDDEN,
+                Json(ErrorResponse::new("Admin access required")),
+            )),
+            None => Err((
+                StatusCode::UNAUTHORIZED,
+                Json(ErrorResponse::new("Authentication required")),
+            )),
+        }
+    }
+}
+
 // =============================================================================
 // REPOSITORY TRAITS
 // =============================================================================
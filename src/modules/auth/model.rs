@@ -4,15 +4,30 @@ use sqlx::FromRow;
 #[derive(Debug, Clone, FromRow)]
 pub struct User {
     pub id: String,
+    pub tenant_id: String, // Emails are unique per tenant
     pub email: String,
     pub password_hash: String,
     pub email_verified: bool,
     pub two_factor_enabled: bool,
     pub two_factor_secret: Option<String>,
+    pub role: String, // "user" or "admin"
+    pub fee_tier: String, // Selects tier-specific fee rules
+    pub max_kyc_rating: Option<String>, // Worst provider KYC rating (A-D) to swap with; None accepts any
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl User {
+    pub fn is_admin(&self) -> bool {
+        self.role == "admin"
+    }
+
+    /// The user's KYC limit as a rating letter, ignoring unreadable values
+    pub fn kyc_limit(&self) -> Option<char> {
+        self.max_kyc_rating.as_deref().and_then(|r| crate::modules::swap::crud::parse_kyc_rating(r).ok())
+    }
+}
+
 #[derive(Debug, Clone, FromRow)]
 pub struct RefreshToken {
     pub id: String,
@@ -1,4 +1,4 @@
-use axum::{routing::post, Router};
+use axum::{routing::{get, post}, Router};
 use std::sync::Arc;
 
 use crate::AppState;
@@ -8,4 +8,12 @@ pub fn auth_routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/register", post(controller::register))
         .route("/login", post(controller::login))
+        .route(
+            "/notifications",
+            get(controller::get_notification_preferences).put(controller::update_notification_preferences),
+        )
+        .route(
+            "/swap-preferences",
+            get(controller::get_swap_preferences).put(controller::update_swap_preferences),
+        )
 }
@@ -5,7 +5,7 @@ use validator::Validate;
 // REGISTER
 // =============================================================================
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct RegisterRequest {
     #[validate(email(message = "Invalid email format"))]
     pub email: String,
@@ -13,7 +13,7 @@ pub struct RegisterRequest {
     pub password_confirm: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RegisterResponse {
     pub user: UserResponse,
 }
@@ -22,7 +22,7 @@ pub struct RegisterResponse {
 // LOGIN
 // =============================================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LoginRequest {
     pub email: String,
     pub password: String,
@@ -32,11 +32,11 @@ pub struct LoginRequest {
     pub backup_code: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LoginResponse {
     pub access_token: String,
     pub refresh_token: String,
-    pub token_type: &'static str,
+    pub token_type: String,
     pub expires_in: i64,
 }
 
@@ -81,7 +81,7 @@ pub struct RefreshTokenResponse {
 // ME (Current User)
 // =============================================================================
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct UserResponse {
     pub id: String,
     pub email: String,
@@ -91,6 +91,33 @@ pub struct UserResponse {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+// =============================================================================
+// NOTIFICATION PREFERENCES
+// =============================================================================
+
+/// PUT /auth/notifications; omitted fields keep their current value
+#[derive(Debug, Default, Deserialize)]
+pub struct UpdateNotificationPreferencesRequest {
+    pub email_enabled: Option<bool>,
+    pub telegram_enabled: Option<bool>,
+    pub telegram_chat_id: Option<String>, // "" unlinks the chat
+    pub swap_completed: Option<bool>,
+    pub swap_failed: Option<bool>,
+    pub action_required: Option<bool>,
+}
+
+// =============================================================================
+// SWAP PREFERENCES
+// =============================================================================
+
+/// GET/PUT /auth/swap-preferences
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SwapPreferences {
+    /// Worst provider KYC rating to quote and swap with, A (best) to D;
+    /// null accepts any. "A" keeps to providers that never ask for KYC.
+    pub max_kyc_rating: Option<String>,
+}
+
 // =============================================================================
 // PASSWORD RESET
 // =============================================================================
@@ -0,0 +1,117 @@
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use std::sync::Arc;
+
+use crate::AppState;
+use crate::services::branding::{Brand, BrandRegistry};
+use super::crud::{BrandWebhookCrud, BrandWebhookError};
+use super::schema::{BrandErrorResponse, SetWebhookRequest, StartRotationRequest, WebhookSettingsResponse};
+
+type BrandResult<T> = Result<Json<T>, (StatusCode, Json<BrandErrorResponse>)>;
+
+fn error_response(e: BrandWebhookError) -> (StatusCode, Json<BrandErrorResponse>) {
+    let status = match e {
+        BrandWebhookError::Unauthorized => StatusCode::UNAUTHORIZED,
+        BrandWebhookError::NotConfigured => StatusCode::SERVICE_UNAVAILABLE,
+        BrandWebhookError::BrandNotFound => StatusCode::NOT_FOUND,
+        BrandWebhookError::InvalidInput(_) => StatusCode::BAD_REQUEST,
+        BrandWebhookError::RotationConflict(_) => StatusCode::CONFLICT,
+        BrandWebhookError::SecretUnreadable(_) | BrandWebhookError::DatabaseError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    (status, Json(BrandErrorResponse::new(e.to_string())))
+}
+
+/// The brand whose key is in X-API-Key; the host never counts here
+async fn authenticate(state: &AppState, headers: &HeaderMap) -> Result<Brand, (StatusCode, Json<BrandErrorResponse>)> {
+    let api_key = headers
+        .get("x-api-key")
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .ok_or_else(|| error_response(BrandWebhookError::Unauthorized))?;
+
+    BrandRegistry::new(state.db.clone(), Some(state.redis.clone()))
+        .find_by_api_key(api_key)
+        .await
+        .map_err(|e| error_response(e.into()))?
+        .ok_or_else(|| error_response(BrandWebhookError::Unauthorized))
+}
+
+fn crud(state: &AppState) -> Result<BrandWebhookCrud, (StatusCode, Json<BrandErrorResponse>)> {
+    let cipher = state
+        .brand_webhook_cipher
+        .clone()
+        .ok_or_else(|| error_response(BrandWebhookError::NotConfigured))?;
+    Ok(BrandWebhookCrud::new(state.db.clone(), cipher).with_private_targets(state.brand_webhooks.allow_private_targets))
+}
+
+// =============================================================================
+// GET /brand/webhook - Webhook URL and the secrets deliveries are signed with
+// =============================================================================
+
+pub async fn get_webhook(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> BrandResult<WebhookSettingsResponse> {
+    let brand = authenticate(&state, &headers).await?;
+    let webhook = crud(&state)?.webhook(&brand.slug).await.map_err(error_response)?;
+
+    Ok(Json(webhook))
+}
+
+// =============================================================================
+// PUT /brand/webhook - Set or clear the webhook URL
+// =============================================================================
+
+pub async fn set_webhook(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<SetWebhookRequest>,
+) -> BrandResult<WebhookSettingsResponse> {
+    let brand = authenticate(&state, &headers).await?;
+    let webhook = crud(&state)?.set_webhook(&brand.slug, &request).await.map_err(error_response)?;
+
+    Ok(Json(webhook))
+}
+
+// =============================================================================
+// POST /brand/webhook/rotation - Add a new signing secret; the old one
+// keeps signing for the grace period
+// =============================================================================
+
+pub async fn start_secret_rotation(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    payload: Option<Json<StartRotationRequest>>,
+) -> BrandResult<WebhookSettingsResponse> {
+    let brand = authenticate(&state, &headers).await?;
+    let request = payload.map(|Json(p)| p).unwrap_or_default();
+    let webhook = crud(&state)?
+        .start_secret_rotation(&brand.slug, &request)
+        .await
+        .map_err(error_response)?;
+
+    Ok(Json(webhook))
+}
+
+// =============================================================================
+// POST /brand/webhook/rotation/complete - Stop signing with the old secret
+// =============================================================================
+
+pub async fn complete_secret_rotation(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> BrandResult<WebhookSettingsResponse> {
+    let brand = authenticate(&state, &headers).await?;
+    let webhook = crud(&state)?
+        .complete_secret_rotation(&brand.slug)
+        .await
+        .map_err(error_response)?;
+
+    Ok(Json(webhook))
+}
@@ -0,0 +1,220 @@
+use sqlx::{MySql, Pool};
+
+use super::model::{BrandWebhookSecret, StoredWebhookSecret};
+use super::schema::{SetWebhookRequest, StartRotationRequest, WebhookSecretInfo, WebhookSettingsResponse};
+use crate::services::encryption::{CipherError, SecretCipher};
+
+// =============================================================================
+// BRAND WEBHOOK ERROR
+// =============================================================================
+
+#[derive(Debug)]
+pub enum BrandWebhookError {
+    Unauthorized,  // Missing X-API-Key, or one no brand holds
+    NotConfigured, // BRAND_WEBHOOKS_KEY is not set
+    BrandNotFound,
+    InvalidInput(String),
+    RotationConflict(String), // Secret rotation started twice, or completed when none is running
+    SecretUnreadable(String), // Stored secret does not decrypt under the current key
+    DatabaseError(String),
+}
+
+impl std::fmt::Display for BrandWebhookError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BrandWebhookError::Unauthorized => write!(f, "A valid brand X-API-Key is required"),
+            BrandWebhookError::NotConfigured => write!(f, "Brand webhooks are not configured"),
+            BrandWebhookError::BrandNotFound => write!(f, "Brand not found"),
+            BrandWebhookError::InvalidInput(reason) => write!(f, "Invalid input: {}", reason),
+            BrandWebhookError::RotationConflict(reason) => write!(f, "{}", reason),
+            BrandWebhookError::SecretUnreadable(reason) => write!(f, "{}", reason),
+            BrandWebhookError::DatabaseError(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl From<sqlx::Error> for BrandWebhookError {
+    fn from(err: sqlx::Error) -> Self {
+        BrandWebhookError::DatabaseError(err.to_string())
+    }
+}
+
+impl From<CipherError> for BrandWebhookError {
+    fn from(err: CipherError) -> Self {
+        BrandWebhookError::SecretUnreadable(err.to_string())
+    }
+}
+
+// =============================================================================
+// BRAND WEBHOOK CRUD
+// =============================================================================
+
+pub struct BrandWebhookCrud {
+    pool: Pool<MySql>,
+    cipher: SecretCipher,
+    allow_private_targets: bool,
+}
+
+impl BrandWebhookCrud {
+    pub fn new(pool: Pool<MySql>, cipher: SecretCipher) -> Self {
+        Self { pool, cipher, allow_private_targets: false }
+    }
+
+    /// Accept http and loopback/private webhook URLs (local development)
+    pub fn with_private_targets(mut self, allow: bool) -> Self {
+        self.allow_private_targets = allow;
+        self
+    }
+
+    /// The brand's webhook URL and the secrets deliveries are signed with
+    pub async fn webhook(&self, brand: &str) -> Result<WebhookSettingsResponse, BrandWebhookError> {
+        let (url,): (Option<String>,) = sqlx::query_as("SELECT webhook_url FROM brands WHERE slug = ?")
+            .bind(brand)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or(BrandWebhookError::BrandNotFound)?;
+        let secrets = self.webhook_secrets(brand).await?;
+
+        Ok(WebhookSettingsResponse {
+            url,
+            secrets: secrets.iter().map(WebhookSecretInfo::from).collect(),
+            secret: None,
+        })
+    }
+
+    /// Secrets that still sign deliveries, newest first: the current one,
+    /// and during a rotation the one it replaces
+    pub async fn webhook_secrets(&self, brand: &str) -> Result<Vec<BrandWebhookSecret>, BrandWebhookError> {
+        let stored = sqlx::query_as::<_, StoredWebhookSecret>(
+            "SELECT id, brand, secret_encrypted, created_at, retires_at
+             FROM brand_webhook_secrets
+             WHERE brand = ? AND (retires_at IS NULL OR retires_at > NOW())
+             ORDER BY id DESC",
+        )
+        .bind(brand)
+        .fetch_all(&self.pool)
+        .await?;
+
+        stored
+            .into_iter()
+            .map(|s| {
+                Ok(BrandWebhookSecret {
+                    secret: self.cipher.decrypt(&s.brand, &s.secret_encrypted)?,
+                    id: s.id,
+                    brand: s.brand,
+                    created_at: s.created_at,
+                    retires_at: s.retires_at,
+                })
+            })
+            .collect()
+    }
+
+    /// Set or clear the webhook URL. The first URL set also creates the
+    /// signing secret, returned in `secret` this once.
+    pub async fn set_webhook(
+        &self,
+        brand: &str,
+        request: &SetWebhookRequest,
+    ) -> Result<WebhookSettingsResponse, BrandWebhookError> {
+        let url = request.url(self.allow_private_targets).map_err(BrandWebhookError::InvalidInput)?;
+
+        let mut tx = self.pool.begin().await?;
+        // Also locks the brand row, serializing with rotations
+        sqlx::query("UPDATE brands SET webhook_url = ? WHERE slug = ?")
+            .bind(&url)
+            .bind(brand)
+            .execute(&mut *tx)
+            .await?;
+        let (signing,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM brand_webhook_secrets
+             WHERE brand = ? AND (retires_at IS NULL OR retires_at > NOW())",
+        )
+        .bind(brand)
+        .fetch_one(&mut *tx)
+        .await?;
+        let created = match url {
+            Some(_) if signing == 0 => Some(self.insert_secret(&mut tx, brand).await?),
+            _ => None,
+        };
+        tx.commit().await?;
+
+        Ok(WebhookSettingsResponse { secret: created, ..self.webhook(brand).await? })
+    }
+
+    /// Start a rotation: a new secret signs from now on, and the current one
+    /// keeps signing next to it for `grace_hours`. The new secret is
+    /// returned in `secret` this once.
+    pub async fn start_secret_rotation(
+        &self,
+        brand: &str,
+        request: &StartRotationRequest,
+    ) -> Result<WebhookSettingsResponse, BrandWebhookError> {
+        let grace_hours = request.grace_hours().map_err(BrandWebhookError::InvalidInput)?;
+
+        let mut tx = self.pool.begin().await?;
+        // Serializes rotations of the same brand
+        sqlx::query("SELECT slug FROM brands WHERE slug = ? FOR UPDATE")
+            .bind(brand)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(BrandWebhookError::BrandNotFound)?;
+        let (current, retiring): (i64, i64) = sqlx::query_as(
+            "SELECT CAST(COALESCE(SUM(retires_at IS NULL), 0) AS SIGNED),
+                    CAST(COALESCE(SUM(retires_at > NOW()), 0) AS SIGNED)
+             FROM brand_webhook_secrets WHERE brand = ?",
+        )
+        .bind(brand)
+        .fetch_one(&mut *tx)
+        .await?;
+        if current == 0 {
+            return Err(BrandWebhookError::InvalidInput("set a webhook url before rotating its secret".to_string()));
+        }
+        if retiring > 0 {
+            return Err(BrandWebhookError::RotationConflict(
+                "A secret rotation is already in progress; complete it first".to_string(),
+            ));
+        }
+
+        sqlx::query(
+            "UPDATE brand_webhook_secrets SET retires_at = NOW() + INTERVAL ? HOUR
+             WHERE brand = ? AND retires_at IS NULL",
+        )
+        .bind(grace_hours)
+        .bind(brand)
+        .execute(&mut *tx)
+        .await?;
+        let secret = self.insert_secret(&mut tx, brand).await?;
+        tx.commit().await?;
+
+        Ok(WebhookSettingsResponse { secret: Some(secret), ..self.webhook(brand).await? })
+    }
+
+    /// End a rotation before its grace period runs out: the old secret stops
+    /// signing now
+    pub async fn complete_secret_rotation(&self, brand: &str) -> Result<WebhookSettingsResponse, BrandWebhookError> {
+        let retired = sqlx::query(
+            "UPDATE brand_webhook_secrets SET retires_at = NOW()
+             WHERE brand = ? AND retires_at > NOW()",
+        )
+        .bind(brand)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+        if retired == 0 {
+            return Err(BrandWebhookError::RotationConflict("No secret rotation is in progress".to_string()));
+        }
+
+        self.webhook(brand).await
+    }
+
+    /// Store a new random signing secret for `brand`, encrypted, and return it
+    async fn insert_secret(&self, tx: &mut sqlx::Transaction<'_, MySql>, brand: &str) -> Result<String, sqlx::Error> {
+        let secret: String = rand::random::<[u8; 32]>().iter().map(|b| format!("{:02x}", b)).collect();
+        sqlx::query("INSERT INTO brand_webhook_secrets (brand, secret_encrypted) VALUES (?, ?)")
+            .bind(brand)
+            .bind(self.cipher.encrypt(brand, &secret))
+            .execute(&mut **tx)
+            .await?;
+        Ok(secret)
+    }
+}
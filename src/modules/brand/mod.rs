@@ -0,0 +1,8 @@
+pub mod schema;
+pub mod model;
+pub mod crud;
+pub mod controller;
+pub mod routes;
+pub mod webhooks;
+
+pub use routes::brand_routes;
@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+// =============================================================================
+// BRAND WEBHOOK SECRET
+// =============================================================================
+
+/// HMAC key brand webhook deliveries are signed with, as stored
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredWebhookSecret {
+    pub id: u64,
+    pub brand: String,            // brands.slug
+    pub secret_encrypted: String, // See services::encryption; the slug is the associated data
+    pub created_at: DateTime<Utc>,
+    pub retires_at: Option<DateTime<Utc>>, // Signs nothing after this; None until a rotation replaces it
+}
+
+/// A signing secret, decrypted
+#[derive(Debug, Clone)]
+pub struct BrandWebhookSecret {
+    pub id: u64,
+    pub brand: String,
+    pub secret: String,
+    pub created_at: DateTime<Utc>,
+    pub retires_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,16 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use std::sync::Arc;
+
+use crate::AppState;
+use super::controller::{complete_secret_rotation, get_webhook, set_webhook, start_secret_rotation};
+
+/// Partner brand webhook settings, mounted under /brand
+pub fn brand_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/webhook", get(get_webhook).put(set_webhook))
+        .route("/webhook/rotation", post(start_secret_rotation))
+        .route("/webhook/rotation/complete", post(complete_secret_rotation))
+}
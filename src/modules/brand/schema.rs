@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::model::BrandWebhookSecret;
+use super::webhooks::check_target;
+
+// =============================================================================
+// /brand/webhook
+// =============================================================================
+
+/// Longest webhook URL accepted
+pub const MAX_WEBHOOK_URL_LENGTH: usize = 512;
+
+/// How long the old secret keeps signing after a rotation starts, when
+/// `grace_hours` is omitted, and the longest allowed
+pub const DEFAULT_ROTATION_GRACE_HOURS: u32 = 72;
+pub const MAX_ROTATION_GRACE_HOURS: u32 = 720;
+
+/// PUT /brand/webhook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetWebhookRequest {
+    #[serde(default)]
+    pub url: Option<String>, // Omitted or empty stops deliveries
+}
+
+impl SetWebhookRequest {
+    /// The URL to store; None when deliveries are turned off. Only https to
+    /// a public host unless `allow_private` (local development).
+    pub fn url(&self, allow_private: bool) -> Result<Option<String>, String> {
+        let Some(url) = self.url.as_deref().map(str::trim).filter(|u| !u.is_empty()) else {
+            return Ok(None);
+        };
+        if url.len() > MAX_WEBHOOK_URL_LENGTH {
+            return Err(format!("url must be at most {} characters", MAX_WEBHOOK_URL_LENGTH));
+        }
+        check_target(url, allow_private)?;
+        Ok(Some(url.to_string()))
+    }
+}
+
+/// POST /brand/webhook/rotation
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct StartRotationRequest {
+    #[serde(default)]
+    pub grace_hours: Option<u32>, // Default DEFAULT_ROTATION_GRACE_HOURS, 1 to MAX_ROTATION_GRACE_HOURS
+}
+
+impl StartRotationRequest {
+    pub fn grace_hours(&self) -> Result<u32, String> {
+        match self.grace_hours {
+            None => Ok(DEFAULT_ROTATION_GRACE_HOURS),
+            Some(hours) if (1..=MAX_ROTATION_GRACE_HOURS).contains(&hours) => Ok(hours),
+            Some(_) => Err(format!("grace_hours must be between 1 and {}", MAX_ROTATION_GRACE_HOURS)),
+        }
+    }
+}
+
+/// A signing secret as listed back; only its last characters are shown
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSecretInfo {
+    pub id: u64,
+    pub hint: String, // Last 4 characters
+    pub created_at: DateTime<Utc>,
+    pub retires_at: Option<DateTime<Utc>>, // Set while a rotation is in progress
+}
+
+impl From<&BrandWebhookSecret> for WebhookSecretInfo {
+    fn from(secret: &BrandWebhookSecret) -> Self {
+        Self {
+            id: secret.id,
+            hint: secret.secret.chars().skip(secret.secret.chars().count().saturating_sub(4)).collect(),
+            created_at: secret.created_at,
+            retires_at: secret.retires_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookSettingsResponse {
+    pub url: Option<String>,
+    /// Secrets deliveries are signed with, newest first
+    pub secrets: Vec<WebhookSecretInfo>,
+    /// A secret created by this request; shown only once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+// =============================================================================
+// ERROR RESPONSE
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BrandErrorResponse {
+    pub error: String,
+}
+
+impl BrandErrorResponse {
+    pub fn new(error: impl Into<String>) -> Self {
+        Self { error: error.into() }
+    }
+}
@@ -0,0 +1,395 @@
+//! Outgoing brand webhooks.
+//!
+//! Status changes of a partner brand's swaps are queued in
+//! `brand_webhook_deliveries` with `enqueue_swap_status`; senders claim due
+//! rows and POST each JSON body to the brand's webhook URL. Every secret
+//! still signing for the brand signs the body (hex HMAC-SHA256): the newest
+//! in `X-Webhook-Signature`, and while a rotation is in progress the one it
+//! replaces in `X-Webhook-Signature-Previous`, so a receiver accepts either
+//! until it has switched keys. A failed delivery is retried with exponential
+//! backoff, up to BRAND_WEBHOOKS_MAX_ATTEMPTS.
+//!
+//! Webhook URLs are partner input, so deliveries only go to public
+//! addresses: URLs must be https to a host that is not loopback, private or
+//! link-local, checked when the URL is set and again on every address the
+//! host resolves to when delivering. Redirects are not followed.
+//! BRAND_WEBHOOKS_ALLOW_PRIVATE lifts this for local development.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use super::crud::BrandWebhookCrud;
+use crate::config::environment::BrandWebhookConfig;
+use crate::config::DbPool;
+use crate::services::encryption::SecretCipher;
+use crate::services::jobs::{self, JobKind, JobOutcome};
+use crate::services::security::hmac_sha256_hex;
+
+/// Signature by the newest secret
+pub const SIGNATURE_HEADER: &str = "x-webhook-signature";
+
+/// Signature by the secret being rotated out; only sent during a rotation
+pub const PREVIOUS_SIGNATURE_HEADER: &str = "x-webhook-signature-previous";
+
+pub const EVENT_HEADER: &str = "x-webhook-event";
+pub const DELIVERY_HEADER: &str = "x-webhook-delivery";
+
+pub const SWAP_STATUS_EVENT: &str = "swap.status_changed";
+
+/// Longest delay between two attempts at the same delivery
+const MAX_BACKOFF: Duration = Duration::from_secs(3600);
+
+/// Signature headers for `body`, given the signing secrets newest first
+pub fn signature_headers(secrets: &[&str], body: &[u8]) -> Vec<(&'static str, String)> {
+    [SIGNATURE_HEADER, PREVIOUS_SIGNATURE_HEADER]
+        .into_iter()
+        .zip(secrets)
+        .map(|(header, secret)| (header, hmac_sha256_hex(secret, body)))
+        .collect()
+}
+
+/// Cipher for BRAND_WEBHOOKS_KEY; `None` (webhooks off) when unset or invalid
+pub fn cipher_from_config(config: &BrandWebhookConfig) -> Option<SecretCipher> {
+    let key = config.secrets_key.as_deref()?;
+    SecretCipher::from_base64(key)
+        .map_err(|e| tracing::error!("BRAND_WEBHOOKS_KEY: {}; brand webhooks disabled", e))
+        .ok()
+}
+
+/// Queue `payload` for `brand`, if it has a webhook. Failures are logged
+/// only: a lost notification must not undo the change.
+pub async fn enqueue_swap_status(pool: &DbPool, brand: &str, swap_id: &str, payload: &serde_json::Value) {
+    let result = sqlx::query(
+        "INSERT INTO brand_webhook_deliveries (brand, event, payload)
+         SELECT slug, ?, ? FROM brands WHERE slug = ? AND webhook_url IS NOT NULL",
+    )
+    .bind(SWAP_STATUS_EVENT)
+    .bind(payload.to_string())
+    .bind(brand)
+    .execute(pool)
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!("Failed to queue brand webhook for swap {}: {}", swap_id, e);
+    }
+}
+
+// =============================================================================
+// TARGETS
+// =============================================================================
+
+/// Whether deliveries may reach `ip`: public unicast addresses only
+pub fn is_public_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            !(v4.is_loopback()
+                || v4.is_private()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_multicast()
+                || v4.is_documentation()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b)) // Shared address space (RFC 6598)
+                || a >= 240)
+        }
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_public_address(IpAddr::V4(v4)),
+            None => {
+                !(v6.is_loopback()
+                    || v6.is_unspecified()
+                    || v6.is_multicast()
+                    || v6.is_unique_local()
+                    || v6.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+/// Check a webhook URL: absolute https, and a host that is neither a
+/// loopback name nor a non-public address. Names are checked again once
+/// resolved, at delivery. `allow_private` only requires http(s).
+pub fn check_target(url: &str, allow_private: bool) -> Result<(), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|_| "url must be an absolute https URL".to_string())?;
+    let host = parsed.host_str().ok_or_else(|| "url must be an absolute https URL".to_string())?;
+    if allow_private {
+        return match parsed.scheme() {
+            "http" | "https" => Ok(()),
+            _ => Err("url must be an absolute http(s) URL".to_string()),
+        };
+    }
+    if parsed.scheme() != "https" {
+        return Err("url must use https".to_string());
+    }
+
+    let name = host.trim_start_matches('[').trim_end_matches(']').trim_end_matches('.').to_ascii_lowercase();
+    let internal = match name.parse::<IpAddr>() {
+        Ok(ip) => !is_public_address(ip),
+        Err(_) => name == "localhost" || name.ends_with(".localhost"),
+    };
+    if internal {
+        return Err("url must not point at a loopback, private or link-local address".to_string());
+    }
+    Ok(())
+}
+
+/// Resolves webhook hosts, keeping only public addresses, so a name that
+/// points inside the network fails instead of connecting
+pub struct PublicResolver;
+
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_address(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} resolves to no public address", name.as_str()).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+// =============================================================================
+// SENDER
+// =============================================================================
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct DeliveryStats {
+    pub sent: usize,
+    pub retrying: usize, // Backing off before the next attempt
+    pub failed: usize,   // Out of attempts, or the webhook was removed
+}
+
+#[derive(sqlx::FromRow)]
+struct ClaimedDelivery {
+    id: u64,
+    brand: String,
+    event: String,
+    payload: String,
+    attempts: i32,
+    webhook_url: Option<String>,
+}
+
+pub struct BrandWebhookSender {
+    pool: DbPool,
+    cipher: SecretCipher,
+    http: reqwest::Client,
+    max_attempts: u32,
+    base_backoff: Duration,
+    timeout: Duration,
+    allow_private_targets: bool,
+}
+
+impl BrandWebhookSender {
+    pub fn from_config(pool: DbPool, cipher: SecretCipher, config: &BrandWebhookConfig) -> Self {
+        let mut http = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .redirect(reqwest::redirect::Policy::none());
+        if !config.allow_private_targets {
+            // A proxy would resolve the host itself, past the address check
+            http = http.dns_resolver(Arc::new(PublicResolver)).no_proxy();
+        }
+
+        Self {
+            pool,
+            cipher,
+            http: http.build().unwrap_or_default(),
+            max_attempts: config.max_attempts.max(1),
+            base_backoff: config.interval.max(Duration::from_secs(1)),
+            timeout: config.timeout,
+            allow_private_targets: config.allow_private_targets,
+        }
+    }
+
+    /// Claim up to `batch_size` due deliveries, oldest first, and deliver
+    /// them. Claimed rows are invisible to other senders until finished, or
+    /// until the claim outlives a whole batch of timeouts (a sender died).
+    pub async fn drain(&self, batch_size: u32) -> Result<DeliveryStats, sqlx::Error> {
+        let claim = uuid::Uuid::new_v4().to_string();
+        let stale_after = self.timeout.saturating_mul(batch_size.max(1)) + Duration::from_secs(60);
+
+        sqlx::query(
+            "UPDATE brand_webhook_deliveries
+             SET status = 'sending', claimed_by = ?, claimed_at = NOW()
+             WHERE (status = 'pending' AND next_attempt_at <= NOW())
+                OR (status = 'sending' AND claimed_at < NOW() - INTERVAL ? SECOND)
+             ORDER BY id
+             LIMIT ?",
+        )
+        .bind(&claim)
+        .bind(stale_after.as_secs())
+        .bind(batch_size)
+        .execute(&self.pool)
+        .await?;
+
+        let claimed = sqlx::query_as::<_, ClaimedDelivery>(
+            "SELECT d.id, d.brand, d.event, d.payload, d.attempts, b.webhook_url
+             FROM brand_webhook_deliveries d
+             LEFT JOIN brands b ON b.slug = d.brand
+             WHERE d.claimed_by = ? AND d.status = 'sending'
+             ORDER BY d.id",
+        )
+        .bind(&claim)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let crud = BrandWebhookCrud::new(self.pool.clone(), self.cipher.clone());
+        let mut stats = DeliveryStats::default();
+        let mut secrets: HashMap<String, Result<Vec<String>, String>> = HashMap::new();
+
+        for row in claimed {
+            let Some(url) = &row.webhook_url else {
+                self.mark_failed(&row, &claim, "webhook url was removed").await?;
+                stats.failed += 1;
+                continue;
+            };
+            if !secrets.contains_key(&row.brand) {
+                let signing = crud
+                    .webhook_secrets(&row.brand)
+                    .await
+                    .map(|s| s.into_iter().map(|s| s.secret).collect())
+                    .map_err(|e| e.to_string());
+                secrets.insert(row.brand.clone(), signing);
+            }
+
+            let result = match &secrets[&row.brand] {
+                Ok(signing) => {
+                    let signing: Vec<&str> = signing.iter().map(String::as_str).collect();
+                    self.deliver(&row, url, &signing).await
+                }
+                Err(e) => Err(e.clone()),
+            };
+
+            match result {
+                Ok(()) => {
+                    sqlx::query(
+                        "UPDATE brand_webhook_deliveries
+                         SET status = 'sent', attempts = attempts + 1, last_error = NULL, sent_at = NOW()
+                         WHERE id = ? AND claimed_by = ?",
+                    )
+                    .bind(row.id)
+                    .bind(&claim)
+                    .execute(&self.pool)
+                    .await?;
+                    stats.sent += 1;
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to deliver brand webhook {} to {}: {}", row.id, row.brand, e);
+                    if row.attempts as u32 + 1 >= self.max_attempts {
+                        self.mark_failed(&row, &claim, &e).await?;
+                        stats.failed += 1;
+                    } else {
+                        self.retry_later(&row, &claim, &e).await?;
+                        stats.retrying += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(stats)
+    }
+
+    async fn deliver(&self, row: &ClaimedDelivery, url: &str, secrets: &[&str]) -> Result<(), String> {
+        if secrets.is_empty() {
+            return Err("no signing secret".to_string());
+        }
+        // IP literals never reach the resolver; the URL may also predate a tightened config
+        check_target(url, self.allow_private_targets)?;
+
+        let mut request = self
+            .http
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, &row.event)
+            .header(DELIVERY_HEADER, row.id.to_string());
+        for (header, signature) in signature_headers(secrets, row.payload.as_bytes()) {
+            request = request.header(header, signature);
+        }
+
+        request
+            .body(row.payload.clone())
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    async fn retry_later(&self, row: &ClaimedDelivery, claim: &str, error: &str) -> Result<(), sqlx::Error> {
+        let delay = self.base_backoff.saturating_mul(2u32.saturating_pow(row.attempts as u32)).min(MAX_BACKOFF);
+        sqlx::query(
+            "UPDATE brand_webhook_deliveries
+             SET status = 'pending', claimed_by = NULL, claimed_at = NULL, attempts = attempts + 1,
+                 last_error = LEFT(?, 500), next_attempt_at = NOW() + INTERVAL ? SECOND
+             WHERE id = ? AND claimed_by = ?",
+        )
+        .bind(error)
+        .bind(delay.as_secs())
+        .bind(row.id)
+        .bind(claim)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn mark_failed(&self, row: &ClaimedDelivery, claim: &str, error: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE brand_webhook_deliveries
+             SET status = 'failed', attempts = attempts + 1, last_error = LEFT(?, 500)
+             WHERE id = ? AND claimed_by = ?",
+        )
+        .bind(error)
+        .bind(row.id)
+        .bind(claim)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+/// Spawn the loop that drains the brand webhook queue. Senders claim rows,
+/// so every instance can run one.
+pub fn spawn_brand_webhook_sender(sender: BrandWebhookSender, config: BrandWebhookConfig) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        tracing::info!("Brand webhook sender started");
+        let job = jobs::registry().register(
+            "brand_webhook_sender",
+            "Deliver queued swap status webhooks to partner brands",
+            JobKind::Scheduled,
+        );
+
+        loop {
+            let run = job.start();
+            let mut drained = 0;
+            match sender.drain(config.batch_size).await {
+                Ok(stats) => {
+                    drained = stats.sent + stats.retrying + stats.failed;
+                    if drained > 0 {
+                        tracing::info!(
+                            "Brand webhooks: {} sent, {} retrying, {} failed",
+                            stats.sent, stats.retrying, stats.failed
+                        );
+                    }
+                    run.finish(JobOutcome::Success, None);
+                }
+                Err(e) => {
+                    tracing::error!("Brand webhook drain failed: {}", e);
+                    run.finish(JobOutcome::Failed, Some(e.to_string()));
+                }
+            }
+
+            // Keep draining while there is a backlog
+            if drained < config.batch_size as usize {
+                job.wait(config.interval).await;
+            }
+        }
+    })
+}
@@ -0,0 +1,151 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+
+use crate::AppState;
+use crate::config::environment::EmailBackend;
+use crate::services::email::{EmailService, SuppressionReason};
+use crate::services::security::constant_time_eq;
+use super::schema::{
+    EmailErrorResponse, EmailEventType, EmailEventsRequest, EmailWebhookResponse, SesNotification, SnsEnvelope,
+    WebhookQuery,
+};
+
+type WebhookResult = Result<Json<EmailWebhookResponse>, (StatusCode, Json<EmailErrorResponse>)>;
+
+fn error(status: StatusCode, message: impl Into<String>) -> (StatusCode, Json<EmailErrorResponse>) {
+    (status, Json(EmailErrorResponse::new(message)))
+}
+
+/// Webhooks are disabled until EMAIL_WEBHOOK_TOKEN is set
+fn authorize(state: &AppState, query: &WebhookQuery) -> Result<(), (StatusCode, Json<EmailErrorResponse>)> {
+    let Some(expected) = &state.email_webhook_token else {
+        return Err(error(StatusCode::NOT_FOUND, "Email webhooks are not configured"));
+    };
+
+    match &query.token {
+        Some(token) if constant_time_eq(expected.as_bytes(), token.as_bytes()) => Ok(()),
+        _ => Err(error(StatusCode::UNAUTHORIZED, "Invalid webhook token")),
+    }
+}
+
+async fn suppress_all(
+    email: &EmailService,
+    source: EmailBackend,
+    entries: Vec<(String, SuppressionReason, Option<String>)>,
+) -> WebhookResult {
+    let mut suppressed = 0;
+    for (address, reason, detail) in entries {
+        email
+            .suppress(&address, reason, source, detail.as_deref())
+            .await
+            .map_err(|e| error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        suppressed += 1;
+    }
+
+    Ok(Json(EmailWebhookResponse { suppressed }))
+}
+
+// =============================================================================
+// POST /webhooks/email/ses - SES bounce/complaint notifications via SNS
+// =============================================================================
+
+pub async fn ses_webhook(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<WebhookQuery>,
+    body: String, // SNS posts JSON with Content-Type: text/plain
+) -> WebhookResult {
+    authorize(&state, &query)?;
+
+    let envelope: SnsEnvelope = serde_json::from_str(&body)
+        .map_err(|e| error(StatusCode::BAD_REQUEST, format!("Invalid SNS message: {}", e)))?;
+
+    match envelope.kind.as_str() {
+        "SubscriptionConfirmation" => {
+            confirm_subscription(&state.http_client, envelope.subscribe_url.as_deref()).await?;
+            Ok(Json(EmailWebhookResponse { suppressed: 0 }))
+        }
+        "Notification" => {
+            let notification: SesNotification = serde_json::from_str(&envelope.message)
+                .map_err(|e| error(StatusCode::BAD_REQUEST, format!("Invalid SES notification: {}", e)))?;
+            suppress_all(&state.email, EmailBackend::Ses, ses_suppressions(notification)).await
+        }
+        _ => Ok(Json(EmailWebhookResponse { suppressed: 0 })),
+    }
+}
+
+/// Hard bounces and complaints; transient bounces are left alone
+fn ses_suppressions(notification: SesNotification) -> Vec<(String, SuppressionReason, Option<String>)> {
+    match notification.notification_type.as_str() {
+        "Bounce" => notification
+            .bounce
+            .filter(|b| b.bounce_type == "Permanent")
+            .map(|b| {
+                b.bounced_recipients
+                    .into_iter()
+                    .map(|r| (r.email_address, SuppressionReason::Bounce, r.diagnostic_code))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        "Complaint" => notification
+            .complaint
+            .map(|c| {
+                c.complained_recipients
+                    .into_iter()
+                    .map(|r| (r.email_address, SuppressionReason::Complaint, c.feedback_type.clone()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// Visit the SubscribeURL so SNS starts delivering; only AWS SNS hosts are followed
+async fn confirm_subscription(
+    http: &reqwest::Client,
+    subscribe_url: Option<&str>,
+) -> Result<(), (StatusCode, Json<EmailErrorResponse>)> {
+    let url = subscribe_url
+        .and_then(|u| reqwest::Url::parse(u).ok())
+        .filter(|u| {
+            u.scheme() == "https"
+                && u.host_str().is_some_and(|h| h.starts_with("sns.") && h.ends_with(".amazonaws.com"))
+        })
+        .ok_or_else(|| error(StatusCode::BAD_REQUEST, "Missing or untrusted SubscribeURL"))?;
+
+    http.get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| error(StatusCode::BAD_GATEWAY, format!("Subscription confirmation failed: {}", e)))?;
+
+    tracing::info!(target: "email", "Confirmed SNS subscription for SES notifications");
+    Ok(())
+}
+
+// =============================================================================
+// POST /webhooks/email/events - Generic bounce/complaint events
+// =============================================================================
+
+pub async fn email_events_webhook(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<WebhookQuery>,
+    Json(payload): Json<EmailEventsRequest>,
+) -> WebhookResult {
+    authorize(&state, &query)?;
+
+    let entries = payload
+        .events
+        .into_iter()
+        .filter_map(|event| match event.kind {
+            EmailEventType::Bounce if event.permanent => Some((event.email, SuppressionReason::Bounce, event.detail)),
+            EmailEventType::Bounce => None,
+            EmailEventType::Complaint => Some((event.email, SuppressionReason::Complaint, event.detail)),
+        })
+        .collect();
+
+    suppress_all(&state.email, state.email.backend(), entries).await
+}
@@ -0,0 +1,5 @@
+pub mod schema;
+pub mod controller;
+pub mod routes;
+
+pub use routes::email_routes;
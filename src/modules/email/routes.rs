@@ -0,0 +1,12 @@
+use axum::{routing::post, Router};
+use std::sync::Arc;
+
+use crate::AppState;
+use super::controller::{email_events_webhook, ses_webhook};
+
+/// Bounce/complaint ingestion, mounted under /webhooks/email
+pub fn email_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/ses", post(ses_webhook))
+        .route("/events", post(email_events_webhook))
+}
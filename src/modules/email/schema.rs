@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+// =============================================================================
+// AMAZON SES (delivered through an SNS HTTPS subscription)
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct SnsEnvelope {
+    #[serde(rename = "Type")]
+    pub kind: String, // "SubscriptionConfirmation" | "Notification" | ...
+    #[serde(rename = "Message", default)]
+    pub message: String,
+    #[serde(rename = "SubscribeURL", default)]
+    pub subscribe_url: Option<String>,
+}
+
+// Feedback notifications use notificationType, configuration-set events use eventType
+#[derive(Debug, Deserialize)]
+pub struct SesNotification {
+    #[serde(rename = "notificationType", alias = "eventType")]
+    pub notification_type: String,
+    #[serde(default)]
+    pub bounce: Option<SesBounce>,
+    #[serde(default)]
+    pub complaint: Option<SesComplaint>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SesBounce {
+    #[serde(rename = "bounceType")]
+    pub bounce_type: String, // "Permanent" | "Transient" | "Undetermined"
+    #[serde(rename = "bouncedRecipients", default)]
+    pub bounced_recipients: Vec<SesRecipient>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SesComplaint {
+    #[serde(rename = "complainedRecipients", default)]
+    pub complained_recipients: Vec<SesRecipient>,
+    #[serde(rename = "complaintFeedbackType", default)]
+    pub feedback_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SesRecipient {
+    #[serde(rename = "emailAddress")]
+    pub email_address: String,
+    #[serde(rename = "diagnosticCode", default)]
+    pub diagnostic_code: Option<String>,
+}
+
+// =============================================================================
+// GENERIC EVENTS (SMTP relays and anything else that can POST JSON)
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmailEventType {
+    Bounce,
+    Complaint,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EmailEvent {
+    #[serde(rename = "type")]
+    pub kind: EmailEventType,
+    pub email: String,
+    /// Only permanent (hard) bounces suppress the address
+    #[serde(default = "default_permanent")]
+    pub permanent: bool,
+    #[serde(default)]
+    pub detail: Option<String>,
+}
+
+fn default_permanent() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EmailEventsRequest {
+    pub events: Vec<EmailEvent>,
+}
+
+// =============================================================================
+// RESPONSES
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct WebhookQuery {
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EmailWebhookResponse {
+    pub suppressed: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EmailErrorResponse {
+    pub error: String,
+}
+
+impl EmailErrorResponse {
+    pub fn new(error: impl Into<String>) -> Self {
+        Self { error: error.into() }
+    }
+}
@@ -1,2 +1,9 @@
+pub mod account;
+pub mod admin;
+pub mod affiliate;
 pub mod auth;
+pub mod brand;
+pub mod email;
+pub mod onramp;
 pub mod swap;
+pub mod widget;
@@ -0,0 +1,137 @@
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use std::sync::Arc;
+
+use crate::AppState;
+use crate::modules::auth::interface::AuthUser;
+use crate::modules::swap::controller::swap_crud;
+use crate::services::maintenance::WritesAllowed;
+use crate::services::security::verify_hmac_sha256;
+use super::crud::{OnrampCrud, OnrampError};
+use super::provider::OnrampProvider;
+use super::schema::{
+    CreateOnrampOrderRequest, OnrampErrorResponse, OnrampOrderResponse, OnrampQuoteQuery, OnrampQuoteResponse,
+    OnrampWebhookPayload, OnrampWebhookResponse,
+};
+
+type OnrampResult<T> = Result<T, (StatusCode, Json<OnrampErrorResponse>)>;
+
+/// Header carrying the hex HMAC-SHA256 of the webhook body
+const SIGNATURE_HEADER: &str = "x-signature";
+
+fn error_response(e: OnrampError) -> (StatusCode, Json<OnrampErrorResponse>) {
+    let status = match e {
+        OnrampError::NotConfigured => StatusCode::SERVICE_UNAVAILABLE,
+        OnrampError::OrderNotFound => StatusCode::NOT_FOUND,
+        OnrampError::AmountOutOfRange { .. }
+        | OnrampError::InvalidAddress(_)
+        | OnrampError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+        OnrampError::ProviderError(_) | OnrampError::SwapFailed(_) => StatusCode::BAD_GATEWAY,
+        OnrampError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(OnrampErrorResponse::new(e.to_string())))
+}
+
+fn provider(state: &AppState) -> OnrampResult<&dyn OnrampProvider> {
+    state.onramp.as_deref().ok_or_else(|| error_response(OnrampError::NotConfigured))
+}
+
+fn crud(state: &AppState) -> OnrampCrud {
+    OnrampCrud::new(state.db.clone(), Some(state.redis.clone()))
+        .with_fiat_limits(state.onramp_config.min_fiat_amount, state.onramp_config.max_fiat_amount)
+}
+
+// =============================================================================
+// GET /onramp/quote - Price a card purchase
+// =============================================================================
+
+pub async fn get_quote(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<OnrampQuoteQuery>,
+) -> OnrampResult<Json<OnrampQuoteResponse>> {
+    let provider = provider(&state)?;
+    let quote = crud(&state).quote(provider, &query).await.map_err(error_response)?;
+    Ok(Json(quote))
+}
+
+// =============================================================================
+// POST /onramp/orders - Start a card purchase
+// =============================================================================
+
+pub async fn create_order(
+    State(state): State<Arc<AppState>>,
+    _writes: WritesAllowed,
+    AuthUser(user): AuthUser,
+    Json(payload): Json<CreateOnrampOrderRequest>,
+) -> OnrampResult<(StatusCode, Json<OnrampOrderResponse>)> {
+    let provider = provider(&state)?;
+    let order = crud(&state)
+        .create_order(provider, &user.id, &payload)
+        .await
+        .map_err(error_response)?;
+
+    Ok((StatusCode::CREATED, Json(order.into())))
+}
+
+// =============================================================================
+// GET /onramp/orders/{id} - Order status for its owner
+// =============================================================================
+
+pub async fn get_order(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+    Path(order_id): Path<String>,
+) -> OnrampResult<Json<OnrampOrderResponse>> {
+    let order = crud(&state).get_order(&user.id, &order_id).await.map_err(error_response)?;
+    Ok(Json(order.into()))
+}
+
+// =============================================================================
+// POST /onramp/webhook - Provider order status updates
+// =============================================================================
+
+pub async fn order_webhook(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> OnrampResult<Json<OnrampWebhookResponse>> {
+    // Webhooks are disabled until ONRAMP_WEBHOOK_SECRET is set
+    let Some(secret) = &state.onramp_config.webhook_secret else {
+        return Err((StatusCode::NOT_FOUND, Json(OnrampErrorResponse::new("On-ramp webhooks are not configured"))));
+    };
+
+    let signature = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if !verify_hmac_sha256(secret, &body, signature) {
+        return Err((StatusCode::UNAUTHORIZED, Json(OnrampErrorResponse::new("Invalid webhook signature"))));
+    }
+
+    let payload: OnrampWebhookPayload = serde_json::from_slice(&body).map_err(|e| {
+        (StatusCode::BAD_REQUEST, Json(OnrampErrorResponse::new(format!("Invalid webhook payload: {}", e))))
+    })?;
+
+    let crud = crud(&state);
+    let order = crud.apply_webhook(&payload).await.map_err(error_response)?;
+
+    // A failed chained swap is recorded on the order and retried on the next delivery
+    let swaps = swap_crud(&state).with_outbox(state.outbox.clone());
+    let swap_id = match crud.chain_swap(&order, &swaps).await {
+        Ok(swap_id) => swap_id,
+        Err(e) => {
+            tracing::warn!(order_id = %order.id, "{}", e);
+            None
+        }
+    };
+
+    Ok(Json(OnrampWebhookResponse {
+        order_id: order.id,
+        status: order.status,
+        swap_id,
+    }))
+}
@@ -0,0 +1,341 @@
+use rust_decimal::Decimal;
+use sqlx::{MySql, Pool};
+
+use super::model::OnrampOrder;
+use super::provider::{OnrampProvider, ProviderOrderRequest};
+use super::schema::{
+    CreateOnrampOrderRequest, OnrampQuoteQuery, OnrampQuoteResponse, OnrampStatus, OnrampWebhookPayload,
+};
+use crate::modules::swap::crud::SwapCrud;
+use crate::modules::swap::schema::CreateSwapRequest;
+use crate::services::address_format::AddressFormatRegistry;
+use crate::services::money;
+use crate::services::redis_cache::RedisService;
+
+/// Seconds one instance holds the right to create an order's chained swap
+const CHAIN_LOCK_TTL_SECS: u64 = 60;
+
+/// Places onramp_orders.fiat_amount keeps
+const FIAT_DECIMALS: u32 = 2;
+
+// =============================================================================
+// ONRAMP ERROR
+// =============================================================================
+
+#[derive(Debug)]
+pub enum OnrampError {
+    NotConfigured,
+    OrderNotFound,
+    AmountOutOfRange { min: Decimal, max: Decimal },
+    InvalidAddress(String),
+    InvalidRequest(String),
+    ProviderError(String),
+    SwapFailed(String),
+    DatabaseError(String),
+}
+
+impl std::fmt::Display for OnrampError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OnrampError::NotConfigured => write!(f, "Fiat on-ramp is not configured"),
+            OnrampError::OrderNotFound => write!(f, "Order not found"),
+            OnrampError::AmountOutOfRange { min, max } => {
+                write!(f, "Amount out of range: min={}, max={}", min, max)
+            }
+            OnrampError::InvalidAddress(reason) => write!(f, "Invalid wallet address: {}", reason),
+            OnrampError::InvalidRequest(reason) => write!(f, "Invalid request: {}", reason),
+            OnrampError::ProviderError(e) => write!(f, "On-ramp provider error: {}", e),
+            OnrampError::SwapFailed(e) => write!(f, "Chained swap failed: {}", e),
+            OnrampError::DatabaseError(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl From<sqlx::Error> for OnrampError {
+    fn from(err: sqlx::Error) -> Self {
+        OnrampError::DatabaseError(err.to_string())
+    }
+}
+
+// =============================================================================
+// ONRAMP CRUD
+// =============================================================================
+
+pub struct OnrampCrud {
+    pool: Pool<MySql>,
+    redis: Option<RedisService>,
+    fiat_limits: (Decimal, Decimal),
+}
+
+impl OnrampCrud {
+    pub fn new(pool: Pool<MySql>, redis: Option<RedisService>) -> Self {
+        Self {
+            pool,
+            redis,
+            fiat_limits: (Decimal::ZERO, Decimal::MAX),
+        }
+    }
+
+    /// Bounds for fiat_amount on quotes and orders
+    pub fn with_fiat_limits(mut self, min: Decimal, max: Decimal) -> Self {
+        self.fiat_limits = (min, max);
+        self
+    }
+
+    fn check_amount(&self, amount: Decimal) -> Result<(), OnrampError> {
+        let (min, max) = self.fiat_limits;
+        if amount < min || amount > max {
+            return Err(OnrampError::AmountOutOfRange { min, max });
+        }
+        if money::exceeds_decimals(amount, FIAT_DECIMALS) {
+            return Err(OnrampError::InvalidRequest(format!(
+                "fiat_amount must have at most {} decimal places",
+                FIAT_DECIMALS
+            )));
+        }
+        Ok(())
+    }
+
+    pub async fn quote(
+        &self,
+        provider: &dyn OnrampProvider,
+        query: &OnrampQuoteQuery,
+    ) -> Result<OnrampQuoteResponse, OnrampError> {
+        self.check_amount(query.fiat_amount)?;
+
+        let quote = provider
+            .quote(query)
+            .await
+            .map_err(|e| OnrampError::ProviderError(e.to_string()))?;
+
+        Ok(OnrampQuoteResponse {
+            provider: provider.name().to_string(),
+            fiat_currency: query.fiat_currency.to_uppercase(),
+            fiat_amount: query.fiat_amount,
+            crypto_currency: query.crypto_currency.to_lowercase(),
+            network: query.network.clone(),
+            crypto_amount: quote.crypto_amount,
+            rate: money::rate(quote.crypto_amount, query.fiat_amount),
+            fee: quote.fee,
+        })
+    }
+
+    /// Record the order, then place it with the provider
+    pub async fn create_order(
+        &self,
+        provider: &dyn OnrampProvider,
+        user_id: &str,
+        request: &CreateOnrampOrderRequest,
+    ) -> Result<OnrampOrder, OnrampError> {
+        self.check_amount(request.fiat_amount)?;
+
+        let wallet_address = request.wallet_address.trim();
+        if wallet_address.is_empty() {
+            return Err(OnrampError::InvalidAddress("wallet_address is required".to_string()));
+        }
+        let registry = AddressFormatRegistry::new(self.pool.clone(), self.redis.clone());
+        if let Some(format) = registry.lookup(&request.crypto_currency.to_lowercase(), &request.network).await {
+            format.check_address(wallet_address).map_err(OnrampError::InvalidAddress)?;
+        }
+
+        let chain_swap = match &request.swap {
+            Some(swap) if swap.recipient_address.trim().is_empty() => {
+                return Err(OnrampError::InvalidRequest("swap.recipient_address is required".to_string()));
+            }
+            Some(swap) => Some(serde_json::to_string(swap).map_err(|e| OnrampError::InvalidRequest(e.to_string()))?),
+            None => None,
+        };
+
+        let order_id = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO onramp_orders
+                (id, user_id, provider, fiat_currency, fiat_amount, crypto_currency, crypto_network,
+                 wallet_address, status, chain_swap)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, 'pending', ?)",
+        )
+        .bind(&order_id)
+        .bind(user_id)
+        .bind(provider.name())
+        .bind(request.fiat_currency.to_uppercase())
+        .bind(request.fiat_amount)
+        .bind(request.crypto_currency.to_lowercase())
+        .bind(&request.network)
+        .bind(wallet_address)
+        .bind(&chain_swap)
+        .execute(&self.pool)
+        .await?;
+
+        let placed = provider
+            .create_order(&ProviderOrderRequest {
+                partner_order_id: order_id.clone(),
+                fiat_currency: request.fiat_currency.to_uppercase(),
+                fiat_amount: request.fiat_amount,
+                crypto_currency: request.crypto_currency.to_lowercase(),
+                network: request.network.clone(),
+                wallet_address: wallet_address.to_string(),
+                redirect_url: request.redirect_url.clone(),
+            })
+            .await;
+
+        match placed {
+            Ok(placed) => {
+                sqlx::query(
+                    "UPDATE onramp_orders SET provider_order_id = ?, payment_url = ?, crypto_amount = ? WHERE id = ?",
+                )
+                .bind(&placed.order_id)
+                .bind(&placed.payment_url)
+                .bind(placed.crypto_amount)
+                .bind(&order_id)
+                .execute(&self.pool)
+                .await?;
+            }
+            Err(e) => {
+                sqlx::query("UPDATE onramp_orders SET status = 'failed', error = ? WHERE id = ?")
+                    .bind(e.to_string())
+                    .bind(&order_id)
+                    .execute(&self.pool)
+                    .await?;
+                return Err(OnrampError::ProviderError(e.to_string()));
+            }
+        }
+
+        self.find(&order_id).await?.ok_or(OnrampError::OrderNotFound)
+    }
+
+    /// An order owned by `user_id`
+    pub async fn get_order(&self, user_id: &str, order_id: &str) -> Result<OnrampOrder, OnrampError> {
+        match self.find(order_id).await? {
+            Some(order) if order.user_id == user_id => Ok(order),
+            _ => Err(OnrampError::OrderNotFound),
+        }
+    }
+
+    /// Apply a verified status webhook; deliveries for finished orders change nothing
+    pub async fn apply_webhook(&self, payload: &OnrampWebhookPayload) -> Result<OnrampOrder, OnrampError> {
+        let status = OnrampStatus::from_provider(&payload.status)
+            .ok_or_else(|| OnrampError::InvalidRequest(format!("Unknown order status '{}'", payload.status)))?;
+
+        let order = match &payload.partner_order_id {
+            Some(id) => self.find(id).await?,
+            None => None,
+        };
+        let order = match order {
+            Some(order) => order,
+            None => self
+                .find_by_provider_order(&payload.order_id)
+                .await?
+                .ok_or(OnrampError::OrderNotFound)?,
+        };
+
+        if order.status.is_final() {
+            return Ok(order);
+        }
+
+        let error = matches!(status, OnrampStatus::Failed | OnrampStatus::Expired)
+            .then(|| payload.reason.clone().unwrap_or_else(|| payload.status.clone()));
+
+        sqlx::query(
+            "UPDATE onramp_orders
+             SET status = ?, provider_order_id = COALESCE(provider_order_id, ?),
+                 crypto_amount = COALESCE(?, crypto_amount), tx_hash = COALESCE(?, tx_hash), error = ?
+             WHERE id = ? AND status NOT IN ('completed', 'failed', 'expired')",
+        )
+        .bind(status)
+        .bind(&payload.order_id)
+        .bind(payload.crypto_amount)
+        .bind(&payload.tx_hash)
+        .bind(&error)
+        .bind(&order.id)
+        .execute(&self.pool)
+        .await?;
+
+        self.find(&order.id).await?.ok_or(OnrampError::OrderNotFound)
+    }
+
+    /// Create the chained swap for a completed order, once. Returns the swap id,
+    /// or `None` when the order has no chained swap or it is not due yet.
+    pub async fn chain_swap(&self, order: &OnrampOrder, swaps: &SwapCrud) -> Result<Option<String>, OnrampError> {
+        let Some(chain) = order.chain_swap() else {
+            return Ok(None);
+        };
+        if order.status != OnrampStatus::Completed {
+            return Ok(None);
+        }
+        if order.swap_id.is_some() {
+            return Ok(order.swap_id.clone());
+        }
+        let Some(amount) = order.crypto_amount else {
+            return Err(OnrampError::SwapFailed("delivered amount is unknown".to_string()));
+        };
+
+        // Webhooks are retried, so two deliveries can race here
+        if let Some(redis) = &self.redis {
+            let lock_key = format!("onramp:chain:{}", order.id);
+            if !redis.try_lock(&lock_key, CHAIN_LOCK_TTL_SECS).await.unwrap_or(true) {
+                return Ok(None);
+            }
+        }
+
+        let request = CreateSwapRequest {
+            trade_id: None,
+            from: order.crypto_currency.clone(),
+            network_from: order.crypto_network.clone(),
+            to: chain.to,
+            network_to: chain.network_to,
+            amount,
+            amount_to: None,
+            provider: chain.provider,
+            recipient_address: chain.recipient_address,
+            recipient_extra_id: chain.recipient_extra_id,
+            refund_address: Some(order.wallet_address.clone()),
+            refund_extra_id: None,
+            rate_type: chain.rate_type,
+            sandbox: false,
+            allow_fallback: chain.allow_fallback,
+            dry_run: false,
+            quote_id: None,
+        };
+
+        let swap = match swaps.create_swap(&request, Some(order.user_id.clone())).await {
+            Ok(swap) => swap,
+            Err(e) => {
+                sqlx::query("UPDATE onramp_orders SET error = ? WHERE id = ?")
+                    .bind(format!("Chained swap failed: {}", e))
+                    .bind(&order.id)
+                    .execute(&self.pool)
+                    .await?;
+                return Err(OnrampError::SwapFailed(e.to_string()));
+            }
+        };
+
+        sqlx::query("UPDATE onramp_orders SET swap_id = ?, error = NULL WHERE id = ? AND swap_id IS NULL")
+            .bind(&swap.swap_id)
+            .bind(&order.id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(Some(swap.swap_id))
+    }
+
+    async fn find(&self, order_id: &str) -> Result<Option<OnrampOrder>, OnrampError> {
+        Ok(sqlx::query_as::<_, OnrampOrder>(&format!("{} WHERE id = ?", ORDER_SELECT))
+            .bind(order_id)
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    async fn find_by_provider_order(&self, provider_order_id: &str) -> Result<Option<OnrampOrder>, OnrampError> {
+        Ok(sqlx::query_as::<_, OnrampOrder>(&format!("{} WHERE provider_order_id = ?", ORDER_SELECT))
+            .bind(provider_order_id)
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+}
+
+const ORDER_SELECT: &str = r#"
+    SELECT id, user_id, provider, provider_order_id,
+           fiat_currency, fiat_amount, crypto_currency, crypto_network, crypto_amount,
+           wallet_address, payment_url, status, tx_hash, chain_swap, swap_id, error,
+           created_at, updated_at
+    FROM onramp_orders
+"#;
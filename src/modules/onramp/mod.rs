@@ -0,0 +1,8 @@
+pub mod schema;
+pub mod model;
+pub mod provider;
+pub mod crud;
+pub mod controller;
+pub mod routes;
+
+pub use routes::onramp_routes;
@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+use serde::{Deserialize, Serialize};
+
+use super::schema::{ChainSwapRequest, OnrampOrderResponse, OnrampStatus};
+
+// =============================================================================
+// ONRAMP ORDER
+// =============================================================================
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct OnrampOrder {
+    pub id: String,
+    pub user_id: String,
+    pub provider: String,
+    pub provider_order_id: Option<String>,
+    pub fiat_currency: String,
+    pub fiat_amount: Decimal,
+    pub crypto_currency: String,
+    pub crypto_network: String,
+    pub crypto_amount: Option<Decimal>,
+    pub wallet_address: String,
+    pub payment_url: Option<String>,
+    pub status: OnrampStatus,
+    pub tx_hash: Option<String>,
+    pub chain_swap: Option<String>, // JSON ChainSwapRequest
+    pub swap_id: Option<String>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OnrampOrder {
+    pub fn chain_swap(&self) -> Option<ChainSwapRequest> {
+        self.chain_swap.as_deref().and_then(|json| serde_json::from_str(json).ok())
+    }
+}
+
+impl From<OnrampOrder> for OnrampOrderResponse {
+    fn from(order: OnrampOrder) -> Self {
+        let swap = order.chain_swap();
+        Self {
+            order_id: order.id,
+            provider: order.provider,
+            status: order.status,
+            fiat_currency: order.fiat_currency,
+            fiat_amount: order.fiat_amount,
+            crypto_currency: order.crypto_currency,
+            network: order.crypto_network,
+            crypto_amount: order.crypto_amount,
+            wallet_address: order.wallet_address,
+            payment_url: order.payment_url,
+            tx_hash: order.tx_hash,
+            swap,
+            swap_id: order.swap_id,
+            error: order.error,
+            created_at: order.created_at,
+            updated_at: order.updated_at,
+        }
+    }
+}
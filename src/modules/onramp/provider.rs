@@ -0,0 +1,143 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::config::environment::OnrampConfig;
+use super::schema::OnrampQuoteQuery;
+
+// =============================================================================
+// PROVIDER ERROR
+// =============================================================================
+
+#[derive(Debug)]
+pub enum OnrampProviderError {
+    HttpError(String),
+    ParseError(String),
+    ApiError(String),
+}
+
+impl std::fmt::Display for OnrampProviderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OnrampProviderError::HttpError(e) => write!(f, "HTTP error: {}", e),
+            OnrampProviderError::ParseError(e) => write!(f, "Parse error: {}", e),
+            OnrampProviderError::ApiError(e) => write!(f, "API error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for OnrampProviderError {}
+
+// =============================================================================
+// PROVIDER INTERFACE
+// =============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProviderQuote {
+    pub crypto_amount: Decimal,
+    #[serde(default)]
+    pub fee: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderOrderRequest {
+    pub partner_order_id: String,
+    pub fiat_currency: String,
+    pub fiat_amount: Decimal,
+    pub crypto_currency: String,
+    pub network: String,
+    pub wallet_address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirect_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProviderOrder {
+    pub order_id: String,
+    pub payment_url: String,
+    #[serde(default)]
+    pub crypto_amount: Option<Decimal>,
+}
+
+/// A card-to-crypto provider
+#[async_trait]
+pub trait OnrampProvider: Send + Sync {
+    /// Recorded on orders and returned with quotes
+    fn name(&self) -> &str;
+
+    async fn quote(&self, query: &OnrampQuoteQuery) -> Result<ProviderQuote, OnrampProviderError>;
+
+    /// Place an order; the user completes payment at the returned URL
+    async fn create_order(&self, request: &ProviderOrderRequest) -> Result<ProviderOrder, OnrampProviderError>;
+}
+
+// =============================================================================
+// HTTP PROVIDER
+// =============================================================================
+
+/// Partner REST API in the shape shared by Transak/Mercuryo-style providers:
+/// `GET /quote`, `POST /orders`, authenticated with an `X-Api-Key` header
+pub struct HttpOnrampProvider {
+    client: Client,
+    name: String,
+    api_url: String,
+    api_key: String,
+}
+
+impl HttpOnrampProvider {
+    pub fn new(client: Client, config: &OnrampConfig) -> Self {
+        Self {
+            client,
+            name: config.provider.clone(),
+            api_url: config.api_url.trim_end_matches('/').to_string(),
+            api_key: config.api_key.clone(),
+        }
+    }
+
+    async fn parse<T: serde::de::DeserializeOwned>(response: reqwest::Response) -> Result<T, OnrampProviderError> {
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(OnrampProviderError::ApiError(format!("API returned {}: {}", status, error_text)));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| OnrampProviderError::ParseError(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl OnrampProvider for HttpOnrampProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn quote(&self, query: &OnrampQuoteQuery) -> Result<ProviderQuote, OnrampProviderError> {
+        let response = self
+            .client
+            .get(format!("{}/quote", self.api_url))
+            .header("X-Api-Key", &self.api_key)
+            .query(query)
+            .send()
+            .await
+            .map_err(|e| OnrampProviderError::HttpError(e.to_string()))?;
+
+        Self::parse(response).await
+    }
+
+    async fn create_order(&self, request: &ProviderOrderRequest) -> Result<ProviderOrder, OnrampProviderError> {
+        let response = self
+            .client
+            .post(format!("{}/orders", self.api_url))
+            .header("X-Api-Key", &self.api_key)
+            .json(request)
+            .send()
+            .await
+            .map_err(|e| OnrampProviderError::HttpError(e.to_string()))?;
+
+        Self::parse(response).await
+    }
+}
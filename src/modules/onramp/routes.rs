@@ -0,0 +1,14 @@
+use axum::{routing::{get, post}, Router};
+use std::sync::Arc;
+
+use crate::AppState;
+use super::controller::{create_order, get_order, get_quote, order_webhook};
+
+/// Card-to-crypto purchases, mounted under /onramp
+pub fn onramp_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/quote", get(get_quote))
+        .route("/orders", post(create_order))
+        .route("/orders/{id}", get(get_order))
+        .route("/webhook", post(order_webhook))
+}
@@ -0,0 +1,157 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::modules::swap::schema::RateType;
+
+// =============================================================================
+// ORDER STATUS
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum OnrampStatus {
+    #[default]
+    Pending,    // Waiting for the card payment
+    Processing, // Paid; the provider is sending the crypto
+    Completed,  // Crypto delivered to the wallet address
+    Failed,
+    Expired,
+}
+
+impl OnrampStatus {
+    /// No further status changes are expected
+    pub fn is_final(&self) -> bool {
+        matches!(self, OnrampStatus::Completed | OnrampStatus::Failed | OnrampStatus::Expired)
+    }
+
+    /// Map the provider's status vocabulary (Transak/Mercuryo-style) onto ours
+    pub fn from_provider(status: &str) -> Option<Self> {
+        match status.to_lowercase().as_str() {
+            "new" | "created" | "pending" | "awaiting_payment" | "awaiting_payment_from_user" => {
+                Some(OnrampStatus::Pending)
+            }
+            "paid" | "processing" | "payment_received" | "pending_delivery" | "order_processing" => {
+                Some(OnrampStatus::Processing)
+            }
+            "completed" | "complete" | "success" | "succeeded" => Some(OnrampStatus::Completed),
+            "failed" | "cancelled" | "canceled" | "declined" | "refunded" => Some(OnrampStatus::Failed),
+            "expired" => Some(OnrampStatus::Expired),
+            _ => None,
+        }
+    }
+}
+
+// =============================================================================
+// GET /onramp/quote
+// =============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnrampQuoteQuery {
+    pub fiat_currency: String, // e.g. "USD"
+    pub fiat_amount: Decimal,
+    pub crypto_currency: String, // Ticker as used by /swap, e.g. "btc"
+    pub network: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OnrampQuoteResponse {
+    pub provider: String,
+    pub fiat_currency: String,
+    pub fiat_amount: Decimal,
+    pub crypto_currency: String,
+    pub network: String,
+    pub crypto_amount: Decimal,
+    pub rate: Decimal, // Crypto per unit of fiat, after fees
+    pub fee: Decimal,  // In fiat
+}
+
+// =============================================================================
+// POST /onramp/orders
+// =============================================================================
+
+/// Swap to create once purchased funds arrive; the source side is the order's crypto
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainSwapRequest {
+    pub to: String,
+    pub network_to: String,
+    pub provider: String,
+    pub recipient_address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient_extra_id: Option<String>,
+    #[serde(default)]
+    pub rate_type: RateType,
+    #[serde(default)]
+    pub allow_fallback: bool, // See CreateSwapRequest::allow_fallback
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateOnrampOrderRequest {
+    pub fiat_currency: String,
+    pub fiat_amount: Decimal,
+    pub crypto_currency: String,
+    pub network: String,
+    pub wallet_address: String, // Where the provider delivers the purchased crypto
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirect_url: Option<String>, // Where the checkout page sends the user afterwards
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swap: Option<ChainSwapRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OnrampOrderResponse {
+    pub order_id: String,
+    pub provider: String,
+    pub status: OnrampStatus,
+    pub fiat_currency: String,
+    pub fiat_amount: Decimal,
+    pub crypto_currency: String,
+    pub network: String,
+    pub crypto_amount: Option<Decimal>,
+    pub wallet_address: String,
+    pub payment_url: Option<String>,
+    pub tx_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swap: Option<ChainSwapRequest>,
+    pub swap_id: Option<String>, // Set once the chained swap has been created
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// =============================================================================
+// POST /onramp/webhook
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct OnrampWebhookPayload {
+    pub order_id: String, // Provider's order id
+    #[serde(default)]
+    pub partner_order_id: Option<String>, // Our order id, echoed back
+    pub status: String,
+    #[serde(default)]
+    pub crypto_amount: Option<Decimal>,
+    #[serde(default)]
+    pub tx_hash: Option<String>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OnrampWebhookResponse {
+    pub order_id: String,
+    pub status: OnrampStatus,
+    pub swap_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OnrampErrorResponse {
+    pub error: String,
+}
+
+impl OnrampErrorResponse {
+    pub fn new(error: impl Into<String>) -> Self {
+        Self { error: error.into() }
+    }
+}
@@ -0,0 +1,80 @@
+use sqlx::{MySql, Pool};
+use tokio::task::JoinHandle;
+
+use super::crud::{ConsistencyStats, SwapCrud, SwapError};
+use crate::config::environment::CacheConsistencyConfig;
+use crate::services::jobs::{self, JobKind, JobOutcome};
+use crate::services::redis_cache::RedisService;
+
+// =============================================================================
+// CACHE CONSISTENCY CHECKER
+// Periodically compares the cached currency list, provider list and a sample
+// of swap status entries with the database. Divergence is counted in
+// `exchange_cache_divergence_total` and, unless CACHE_CONSISTENCY_REPAIR is
+// off, the stale entries are dropped. Like the prober, the lock is left to
+// expire so across all instances a check runs once per interval.
+// =============================================================================
+
+const LOCK_KEY: &str = "lock:cache_consistency";
+
+/// Spawn the periodic consistency check loop on the tokio runtime
+pub fn spawn_consistency_checker(
+    pool: Pool<MySql>,
+    redis: RedisService,
+    config: CacheConsistencyConfig,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        tracing::info!(
+            "Cache consistency checker started (interval {:?}, sample {}, repair {})",
+            config.interval,
+            config.sample_size,
+            config.repair
+        );
+
+        let crud = SwapCrud::new(pool, Some(redis.clone()));
+        let job = jobs::registry().register(
+            "cache_consistency",
+            "Compare cached currencies, providers and swap statuses with the database",
+            JobKind::Scheduled,
+        );
+
+        loop {
+            let run = job.start();
+            match run_once(&crud, &redis, &config).await {
+                Ok(Some(stats)) => {
+                    if stats.diverged > 0 {
+                        tracing::warn!(
+                            "Cache consistency: {}/{} entries diverged, {} repaired",
+                            stats.diverged,
+                            stats.checked,
+                            stats.repaired
+                        );
+                    }
+                    run.finish(JobOutcome::Success, None);
+                }
+                Ok(None) => run.finish(JobOutcome::Skipped, None),
+                Err(e) => {
+                    tracing::warn!("Cache consistency check failed: {}", e);
+                    run.finish(JobOutcome::Failed, Some(e.to_string()));
+                }
+            }
+
+            job.wait(config.interval).await;
+        }
+    })
+}
+
+/// Run a single check. Returns `None` when another instance checked within
+/// the interval.
+pub async fn run_once(
+    crud: &SwapCrud,
+    redis: &RedisService,
+    config: &CacheConsistencyConfig,
+) -> Result<Option<ConsistencyStats>, SwapError> {
+    let ttl = config.interval.as_secs().saturating_sub(1).max(1);
+    if !matches!(redis.try_lock(LOCK_KEY, ttl).await, Ok(true)) {
+        return Ok(None);
+    }
+
+    crud.check_cache_consistency(config.sample_size, config.repair).await.map(Some)
+}
@@ -1,74 +1,248 @@
 use axum::{
     extract::{Query, State, Path},
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
     response::{Response, IntoResponse},
     Json,
 };
 use std::sync::Arc;
 
 use crate::AppState;
-use super::crud::{SwapCrud, CurrenciesResult};
+use super::crud::{SwapCrud, SwapError, CurrenciesResult, GroupedCurrenciesResult};
+use super::share::verify_share;
 use super::schema::{
-    CurrenciesQuery, ProvidersQuery, SwapErrorResponse,
-    CreateSwapRequest, CreateSwapResponse, SwapStatusResponse, ValidateAddressRequest, ValidateAddressResponse,
+    AddressChallengeRequest, AddressChallengeResponse, CurrenciesQuery, CurrencyResponse, DepthQuery, DepthResponse, GroupedCurrencyResponse, ProviderResponse,
+    PairResponse, PairsQuery, ProviderUptimeResponse, ProvidersQuery, QuoteRequest, QuoteReservation, RatesQuery, RatesResponse, SwapErrorResponse,
+    SwapPreviewResponse, CreateRouteRequest, RoutePlanQuery, RoutePlanResponse, SwapRouteResponse,
+    BatchSwapStatusRequest, BatchSwapStatusResponse, CreateSwapRequest, CreateSwapResponse, HistoryQuery, RetrySwapRequest,
+    FavoritePairResponse, FavoritesResponse, SaveFavoriteRequest, SavedAddressResponse, SavedFavorite,
+    RefundAddressQuery, RefundAddressSuggestionsResponse, SwapDraft, SwapRefundResponse, CreateShareLinkRequest,
+    ShareLinkResponse, SharedSwapQuery, SharedSwapStatusResponse, ImportSwapRequest, SwapDraftResponse, SwapHistoryResponse, SwapLimitsResponse, SwapStatusResponse, ValidateAddressRequest,
+    ValidateAddressResponse, VerifiedAddressResponse, VerifiedAddressesResponse, VerifyAddressRequest,
 };
-use crate::modules::auth::interface::OptionalUser;
+use crate::modules::affiliate::crud::AffiliateCrud;
+use crate::modules::affiliate::schema::ReferralQuery;
+use crate::modules::auth::interface::{AuthUser, OptionalUser};
+use crate::services::analytics::AnalyticsContext;
+use crate::services::branding::CurrentBrand;
+use crate::services::idempotency::{Claim, IdempotencyError, IdempotencyStore, ANONYMOUS_SCOPE, IDEMPOTENCY_HEADER};
+use crate::services::maintenance::{MaintenanceService, WritesAllowed};
+use crate::services::routing::ClientCountry;
+use crate::services::tenant::CurrentTenant;
 
-// ... (existing handlers)
+/// SwapCrud over the app's database, cache, shared Trocador client and price
+/// feed, with the swap policies loaded once in create_state
+pub(crate) fn swap_crud(state: &AppState) -> SwapCrud {
+    SwapCrud::new(state.db.clone(), Some(state.redis.clone()))
+        .with_trocador(state.trocador.clone())
+        .with_price_feed(state.price_feed.clone())
+        .with_fee_estimator(state.fee_estimator.clone())
+        .with_rate_guard(state.rate_guard.clone())
+        .with_provider_selection(state.provider_selection.clone())
+        .with_provider_fallback(state.provider_fallback.clone())
+        .with_high_value(state.high_value.clone())
+        .with_address_verification(state.address_verification.clone())
+        .with_volume_limits(state.volume_limits.clone())
+}
+
+// =============================================================================
+// ERROR RESPONSES
+// =============================================================================
+
+/// One status and `code` per error, whichever endpoint it comes from
+impl IntoResponse for SwapError {
+    fn into_response(self) -> Response {
+        let status = self.status_code();
+        let mut body = SwapErrorResponse::with_code(self.to_string(), self.error_code());
+        match self {
+            SwapError::AmountOutOfRange { min, max } => {
+                body.min_amount = Some(min);
+                body.max_amount = Some(max);
+            }
+            SwapError::ExtraIdRequired { extra_id_name, .. } | SwapError::InvalidExtraId { extra_id_name, .. } => {
+                body.extra_id_name = extra_id_name;
+            }
+            _ => {}
+        }
+        (status, Json(body)).into_response()
+    }
+}
 
 // =============================================================================
 // POST /swap/create - Create a new swap
 // =============================================================================
 
+#[utoipa::path(
+    post,
+    path = "/swap/create",
+    tag = "swap",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Replays of a key return the first response"),
+        ("ref" = Option<String>, Query, description = "Affiliate ref code the swap is attributed to; unknown codes are ignored"),
+    ),
+    request_body = CreateSwapRequest,
+    responses(
+        (status = 201, description = "Swap created", body = CreateSwapResponse),
+        (status = 200, description = "dry_run preview; nothing was created", body = SwapPreviewResponse),
+        (status = 400, description = "Invalid pair, amount, address or extra id", body = SwapErrorResponse),
+        (status = 409, description = "Idempotency-Key in use or quote already used", body = SwapErrorResponse),
+        (status = 502, description = "Provider error", body = SwapErrorResponse),
+    ),
+    security((), ("bearer_auth" = [])),
+)]
+#[allow(clippy::too_many_arguments)]
 pub async fn create_swap(
     State(state): State<Arc<AppState>>,
+    _writes: WritesAllowed,
     user: OptionalUser,
+    context: AnalyticsContext,
+    CurrentBrand(brand): CurrentBrand,
+    headers: HeaderMap,
+    Query(referral): Query<ReferralQuery>,
     Json(payload): Json<CreateSwapRequest>,
-) -> Result<(StatusCode, Json<CreateSwapResponse>), (StatusCode, Json<SwapErrorResponse>)> {
-    let crud = SwapCrud::new(state.db.clone(), Some(state.redis.clone()));
-
-    let response = crud.create_swap(&payload, user.0.map(|u| u.id)).await.map_err(|e| {
-        let status = match e {
-            super::crud::SwapError::AmountOutOfRange { .. } => StatusCode::BAD_REQUEST,
-            super::crud::SwapError::InvalidAddress => StatusCode::BAD_REQUEST,
-            _ => StatusCode::INTERNAL_SERVER_ERROR,
-        };
-        (status, Json(SwapErrorResponse::new(e.to_string())))
-    })?;
+) -> Result<Response, Response> {
+    let max_kyc_rating = user.0.as_ref().and_then(|u| u.kyc_limit());
+    let (user_id, fee_tier) = user.0.map(|u| (u.id, u.fee_tier)).unzip();
+    let tenant = brand.tenant.clone();
+    let affiliate = match referral.ref_code.as_deref() {
+        Some(ref_code) => AffiliateCrud::new(state.db.clone()).referrer(ref_code, &tenant).await,
+        None => None,
+    };
+    let crud = swap_crud(&state)
+        .with_analytics(state.analytics.clone(), context.with_user(user_id.clone()))
+        .with_outbox(state.outbox.clone())
+        .with_brand(brand)
+        .with_fee_tier(fee_tier)
+        .with_max_kyc_rating(max_kyc_rating)
+        .with_country(ClientCountry::from_headers(&headers).0)
+        .with_affiliate(affiliate);
 
-    Ok((StatusCode::CREATED, Json(response)))
+    // Dry run: same checks against a fresh quote, nothing is created
+    if payload.dry_run {
+        let preview = crud.preview_swap(&payload).await.map_err(IntoResponse::into_response)?;
+        return Ok((StatusCode::OK, Json(preview)).into_response());
+    }
+
+    let Some(key) = idempotency_key(&headers).map_err(idempotency_error_response)? else {
+        let response = crud.create_swap(&payload, user_id).await.map_err(IntoResponse::into_response)?;
+        return Ok((StatusCode::CREATED, Json(response)).into_response());
+    };
+
+    // Replays of a key return the first response instead of opening a second trade
+    let store = IdempotencyStore::new(state.db.clone(), Some(state.redis.clone()), "swap_create");
+    // Anonymous callers of different tenants must not replay each other's swaps
+    let scope = user_id.clone().unwrap_or_else(|| tenant.cache_key(ANONYMOUS_SCOPE));
+    let request_hash = IdempotencyStore::request_hash(&payload);
+    let claim = store
+        .claim::<CreateSwapResponse>(&scope, &key, &request_hash)
+        .await
+        .map_err(idempotency_error_response)?;
+    if let Claim::Replay(response) = claim {
+        return Ok((StatusCode::CREATED, [(IDEMPOTENT_REPLAYED_HEADER, "true")], Json(response)).into_response());
+    }
+
+    match crud.create_swap(&payload, user_id).await {
+        Ok(response) => {
+            if let Err(e) = store.complete(&scope, &key, &response.swap_id, &response).await {
+                tracing::warn!("Failed to store idempotent response for swap {}: {}", response.swap_id, e);
+            }
+            Ok((StatusCode::CREATED, Json(response)).into_response())
+        }
+        Err(e) => {
+            // Nothing was created, so the client may retry with the same key
+            if let Err(release_error) = store.release(&scope, &key).await {
+                tracing::warn!("Failed to release idempotency key: {}", release_error);
+            }
+            Err(e.into_response())
+        }
+    }
+}
+
+/// Set on responses replayed from an earlier request with the same Idempotency-Key
+const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+fn idempotency_key(headers: &HeaderMap) -> Result<Option<String>, IdempotencyError> {
+    let Some(value) = headers.get(IDEMPOTENCY_HEADER) else {
+        return Ok(None);
+    };
+    let key = value.to_str().map_err(|_| IdempotencyError::InvalidKey)?;
+    IdempotencyStore::check_key(key)?;
+    Ok(Some(key.to_string()))
+}
+
+fn idempotency_error_response(e: IdempotencyError) -> Response {
+    let (status, code) = match e {
+        IdempotencyError::InvalidKey => (StatusCode::BAD_REQUEST, Some("INVALID_IDEMPOTENCY_KEY")),
+        IdempotencyError::KeyReused => (StatusCode::UNPROCESSABLE_ENTITY, Some("IDEMPOTENCY_KEY_REUSED")),
+        IdempotencyError::InProgress => (StatusCode::CONFLICT, Some("IDEMPOTENCY_KEY_IN_PROGRESS")),
+        IdempotencyError::Storage(_) => (StatusCode::INTERNAL_SERVER_ERROR, None),
+    };
+    let body = match code {
+        Some(code) => SwapErrorResponse::with_code(e.to_string(), code),
+        None => SwapErrorResponse::new(e.to_string()),
+    };
+    (status, Json(body)).into_response()
 }
 
+#[utoipa::path(
+    get,
+    path = "/swap/currencies",
+    tag = "swap",
+    params(CurrenciesQuery),
+    responses(
+        (
+            status = 200,
+            description = "Supported currencies; a CurrenciesPage when per_page, sort, search or fields is given",
+            body = [CurrencyResponse]
+        ),
+        (status = 304, description = "The full list is unchanged since the ETag sent in If-None-Match"),
+    ),
+)]
 pub async fn get_currencies(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Query(query): Query<CurrenciesQuery>,
-) -> Result<Response, (StatusCode, Json<SwapErrorResponse>)> {
-    let crud = SwapCrud::new(state.db.clone(), Some(state.redis.clone()));
+) -> Result<Response, SwapError> {
+    let crud = swap_crud(&state);
 
     // The CRUD layer now handles caching, pagination, raw JSON, and background synchronization
-    let result = crud.get_currencies_optimized(query).await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(SwapErrorResponse::new(e.to_string())),
-        )
-    })?;
+    let result = crud.get_currencies_optimized(query).await?;
 
     match result {
         CurrenciesResult::Structured(responses) => {
             // Standard JSON response
             Ok(Json(responses).into_response())
         },
-        CurrenciesResult::RawJson(json_string) => {
-            // Optimized raw JSON response (avoids serialization overhead)
+        CurrenciesResult::Page(page) => Ok(Json(page).into_response()),
+        CurrenciesResult::RawJson { json, etag } => raw_json_response(&headers, json, &etag),
+    }
+}
+
+// =============================================================================
+// GET /swap/currencies/grouped - List currencies grouped by asset
+// =============================================================================
+
+#[utoipa::path(
+    get,
+    path = "/swap/currencies/grouped",
+    tag = "swap",
+    params(CurrenciesQuery),
+    responses((status = 200, description = "Currencies grouped by asset", body = [GroupedCurrencyResponse])),
+)]
+pub async fn get_currencies_grouped(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<CurrenciesQuery>,
+) -> Result<Response, SwapError> {
+    let crud = swap_crud(&state);
+
+    let result = crud.get_currencies_grouped(query).await?;
+
+    match result {
+        GroupedCurrenciesResult::Structured(responses) => Ok(Json(responses).into_response()),
+        GroupedCurrenciesResult::RawJson(json_string) => {
             let response = Response::builder()
                 .header("content-type", "application/json")
                 .body(axum::body::Body::from(json_string))
-                .map_err(|e| {
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        Json(SwapErrorResponse::new(e.to_string())),
-                    )
-                })?;
+                .map_err(|e| SwapError::Internal(e.to_string()))?;
             Ok(response)
         }
     }
@@ -78,57 +252,314 @@ pub async fn get_currencies(
 // GET /swap/providers - List all exchange providers
 // =============================================================================
 
+#[utoipa::path(
+    get,
+    path = "/swap/providers",
+    tag = "swap",
+    params(ProvidersQuery),
+    responses(
+        (status = 200, description = "Exchange providers", body = [ProviderResponse]),
+        (status = 304, description = "The full list is unchanged since the ETag sent in If-None-Match"),
+    ),
+)]
 pub async fn get_providers(
     State(state): State<Arc<AppState>>,
+    CurrentBrand(brand): CurrentBrand,
+    headers: HeaderMap,
     Query(query): Query<ProvidersQuery>,
-) -> Result<Response, (StatusCode, Json<SwapErrorResponse>)> {
-    let crud = SwapCrud::new(state.db.clone(), Some(state.redis.clone()));
+) -> Result<Response, SwapError> {
+    let crud = swap_crud(&state).with_brand(brand);
 
     // The CRUD layer now handles caching, optimized filtering, and background synchronization
-    let result = crud.get_providers_optimized(query).await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(SwapErrorResponse::new(e.to_string())),
-        )
-    })?;
+    let result = crud.get_providers_optimized(query).await?;
 
     match result {
         super::crud::ProvidersResult::Structured(responses) => {
             // Standard JSON response
             Ok(Json(responses).into_response())
         },
-        super::crud::ProvidersResult::RawJson(json_string) => {
-            // Optimized raw JSON response (avoids serialization overhead)
-            let response = Response::builder()
-                .header("content-type", "application/json")
-                .body(axum::body::Body::from(json_string))
-                .map_err(|e| {
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        Json(SwapErrorResponse::new(e.to_string())),
-                    )
-                })?;
-            Ok(response)
-        }
+        super::crud::ProvidersResult::RawJson { json, etag } => raw_json_response(&headers, json, &etag),
     }
 }
 
+/// Optimized raw JSON response (avoids serialization overhead), tagged with
+/// its ETag; 304 without a body when the client already has this version
+fn raw_json_response(headers: &HeaderMap, json: String, etag: &str) -> Result<Response, SwapError> {
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| etag_matches(value, etag));
+
+    let builder = Response::builder().header(header::ETAG, etag);
+    let response = if not_modified {
+        builder.status(StatusCode::NOT_MODIFIED).body(axum::body::Body::empty())
+    } else {
+        builder.header(header::CONTENT_TYPE, "application/json").body(axum::body::Body::from(json))
+    };
+    response.map_err(|e| SwapError::Internal(e.to_string()))
+}
+
+/// Whether an If-None-Match value (a list of tags, or `*`) names `etag`.
+/// Weak comparison, as RFC 9110 asks for this header.
+pub fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let etag = etag.trim_start_matches("W/");
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+// =============================================================================
+// GET /swap/providers/{id}/uptime - 30/90-day provider availability
+// =============================================================================
+
+#[utoipa::path(
+    get,
+    path = "/swap/providers/{id}/uptime",
+    tag = "swap",
+    params(("id" = String, Path, description = "Provider name")),
+    responses(
+        (status = 200, description = "Provider availability", body = ProviderUptimeResponse),
+        (status = 404, description = "Unknown provider", body = SwapErrorResponse),
+    ),
+)]
+pub async fn get_provider_uptime(
+    State(state): State<Arc<AppState>>,
+    Path(provider_id): Path<String>,
+) -> Result<Json<super::schema::ProviderUptimeResponse>, SwapError> {
+    let crud = swap_crud(&state);
+
+    let response = crud.get_provider_uptime(&provider_id).await?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// GET /swap/pairs - Swappable pairs with limits per provider
+// =============================================================================
+
+#[utoipa::path(
+    get,
+    path = "/swap/pairs",
+    tag = "swap",
+    params(PairsQuery),
+    responses((status = 200, description = "Pairs quoted in the last week, with each provider's limits", body = [PairResponse])),
+)]
+pub async fn get_pairs(
+    State(state): State<Arc<AppState>>,
+    CurrentBrand(brand): CurrentBrand,
+    Query(query): Query<PairsQuery>,
+) -> Result<Json<Vec<PairResponse>>, SwapError> {
+    let crud = swap_crud(&state).with_brand(brand);
+
+    let response = crud.get_pairs(&query).await?;
+
+    Ok(Json(response))
+}
+
 // =============================================================================
 // GET /swap/rates - Get live rates from all providers
 // =============================================================================
 
+#[utoipa::path(
+    get,
+    path = "/swap/rates",
+    tag = "swap",
+    params(RatesQuery),
+    responses(
+        (status = 200, description = "Live quotes, best first", body = RatesResponse),
+        (status = 400, description = "Invalid pair or amount", body = SwapErrorResponse),
+    ),
+    security((), ("bearer_auth" = [])),
+)]
 pub async fn get_rates(
     State(state): State<Arc<AppState>>,
+    user: OptionalUser,
+    context: AnalyticsContext,
+    CurrentBrand(brand): CurrentBrand,
+    ClientCountry(country): ClientCountry,
     Query(query): Query<super::schema::RatesQuery>,
-) -> Result<Json<super::schema::RatesResponse>, (StatusCode, Json<super::schema::SwapErrorResponse>)> {
-    let crud = SwapCrud::new(state.db.clone(), Some(state.redis.clone()));
+) -> Result<Json<super::schema::RatesResponse>, SwapError> {
+    let max_kyc_rating = user.0.as_ref().and_then(|u| u.kyc_limit());
+    let (user_id, fee_tier) = user.0.map(|u| (u.id, u.fee_tier)).unzip();
+    let crud = swap_crud(&state)
+        .with_analytics(state.analytics.clone(), context.with_user(user_id))
+        .with_brand(brand)
+        .with_fee_tier(fee_tier)
+        .with_max_kyc_rating(max_kyc_rating)
+        .with_country(country);
 
-    let response = crud.get_rates_optimized(&query).await.map_err(|e| {
-        (
-            StatusCode::BAD_GATEWAY,
-            Json(super::schema::SwapErrorResponse::new(e.to_string())),
-        )
-    })?;
+    let response = crud.get_rates_optimized(&query).await?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// POST /swap/quote - Reserve a fixed-rate quote for a later swap
+// =============================================================================
+
+#[utoipa::path(
+    post,
+    path = "/swap/quote",
+    tag = "swap",
+    request_body = QuoteRequest,
+    responses(
+        (status = 201, description = "Quote reserved", body = QuoteReservation),
+        (status = 400, description = "Provider does not quote the pair", body = SwapErrorResponse),
+    ),
+    security((), ("bearer_auth" = [])),
+)]
+pub async fn reserve_quote(
+    State(state): State<Arc<AppState>>,
+    _writes: WritesAllowed,
+    user: OptionalUser,
+    CurrentBrand(brand): CurrentBrand,
+    Json(payload): Json<QuoteRequest>,
+) -> Result<(StatusCode, Json<QuoteReservation>), SwapError> {
+    let max_kyc_rating = user.0.as_ref().and_then(|u| u.kyc_limit());
+    let (user_id, fee_tier) = user.0.map(|u| (u.id, u.fee_tier)).unzip();
+    let crud = swap_crud(&state)
+        .with_brand(brand)
+        .with_fee_tier(fee_tier)
+        .with_max_kyc_rating(max_kyc_rating);
+
+    let response = crud.reserve_quote(&payload, user_id).await?;
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+// =============================================================================
+// GET /swap/depth - Best rate at increasing sizes
+// =============================================================================
+
+#[utoipa::path(
+    get,
+    path = "/swap/depth",
+    tag = "swap",
+    params(DepthQuery),
+    responses(
+        (status = 200, description = "Best rate at increasing sizes", body = DepthResponse),
+        (status = 400, description = "Invalid pair or amount", body = SwapErrorResponse),
+    ),
+    security((), ("bearer_auth" = [])),
+)]
+pub async fn get_depth(
+    State(state): State<Arc<AppState>>,
+    user: OptionalUser,
+    CurrentBrand(brand): CurrentBrand,
+    Query(query): Query<DepthQuery>,
+) -> Result<Json<DepthResponse>, SwapError> {
+    let max_kyc_rating = user.0.as_ref().and_then(|u| u.kyc_limit());
+    let fee_tier = user.0.map(|u| u.fee_tier);
+    let crud = swap_crud(&state)
+        .with_brand(brand)
+        .with_fee_tier(fee_tier)
+        .with_max_kyc_rating(max_kyc_rating);
+
+    let response = crud.get_depth(&query).await?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// GET /swap/routes/plan - Direct and two-leg ways to swap a pair
+// =============================================================================
+
+#[utoipa::path(
+    get,
+    path = "/swap/routes/plan",
+    tag = "swap",
+    params(RoutePlanQuery),
+    responses(
+        (status = 200, description = "Quoted routes, best receive amount first", body = RoutePlanResponse),
+        (status = 400, description = "No route is quoted, or invalid amount or intermediate", body = SwapErrorResponse),
+    ),
+    security((), ("bearer_auth" = [])),
+)]
+pub async fn plan_routes(
+    State(state): State<Arc<AppState>>,
+    user: OptionalUser,
+    CurrentBrand(brand): CurrentBrand,
+    ClientCountry(country): ClientCountry,
+    Query(query): Query<RoutePlanQuery>,
+) -> Result<Json<RoutePlanResponse>, SwapError> {
+    let max_kyc_rating = user.0.as_ref().and_then(|u| u.kyc_limit());
+    let fee_tier = user.0.map(|u| u.fee_tier);
+    let crud = swap_crud(&state)
+        .with_brand(brand)
+        .with_fee_tier(fee_tier)
+        .with_max_kyc_rating(max_kyc_rating)
+        .with_country(country);
+
+    let response = crud.plan_routes(&query).await?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// POST /swap/routes - Swap through an intermediate currency
+// =============================================================================
+
+#[utoipa::path(
+    post,
+    path = "/swap/routes",
+    tag = "swap",
+    request_body = CreateRouteRequest,
+    responses(
+        (status = 201, description = "Both legs created; send the deposit to the route's deposit_address", body = SwapRouteResponse),
+        (status = 400, description = "No two-leg route is quoted, or a leg was refused", body = SwapErrorResponse),
+        (status = 502, description = "Provider error", body = SwapErrorResponse),
+    ),
+    security((), ("bearer_auth" = [])),
+)]
+pub async fn create_route(
+    State(state): State<Arc<AppState>>,
+    _writes: WritesAllowed,
+    user: OptionalUser,
+    context: AnalyticsContext,
+    CurrentBrand(brand): CurrentBrand,
+    ClientCountry(country): ClientCountry,
+    Json(payload): Json<CreateRouteRequest>,
+) -> Result<(StatusCode, Json<SwapRouteResponse>), SwapError> {
+    let max_kyc_rating = user.0.as_ref().and_then(|u| u.kyc_limit());
+    let (user_id, fee_tier) = user.0.map(|u| (u.id, u.fee_tier)).unzip();
+    let crud = swap_crud(&state)
+        .with_analytics(state.analytics.clone(), context.with_user(user_id.clone()))
+        .with_outbox(state.outbox.clone())
+        .with_brand(brand)
+        .with_fee_tier(fee_tier)
+        .with_max_kyc_rating(max_kyc_rating)
+        .with_country(country);
+
+    let response = crud.create_route(&payload, user_id).await?;
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+// =============================================================================
+// GET /swap/routes/{id} - Route status with both legs
+// =============================================================================
+
+#[utoipa::path(
+    get,
+    path = "/swap/routes/{id}",
+    tag = "swap",
+    params(("id" = String, Path, description = "Route id")),
+    responses(
+        (status = 200, description = "Rolled-up status and each leg's status", body = SwapRouteResponse),
+        (status = 404, description = "Unknown route", body = SwapErrorResponse),
+    ),
+)]
+pub async fn get_route(
+    State(state): State<Arc<AppState>>,
+    CurrentTenant(tenant): CurrentTenant,
+    Path(route_id): Path<String>,
+) -> Result<Json<SwapRouteResponse>, SwapError> {
+    let crud = swap_crud(&state)
+        .with_outbox(state.outbox.clone())
+        .with_tenant(tenant);
+
+    let response = crud.get_route(&route_id).await?;
 
     Ok(Json(response))
 }
@@ -137,43 +568,646 @@ pub async fn get_rates(
 // GET /swap/:id - Get swap status by ID
 // =============================================================================
 
+#[utoipa::path(
+    get,
+    path = "/swap/{id}",
+    tag = "swap",
+    params(("id" = String, Path, description = "Swap id")),
+    responses(
+        (status = 200, description = "Current status", body = SwapStatusResponse),
+        (status = 404, description = "Unknown swap", body = SwapErrorResponse),
+    ),
+)]
 pub async fn get_swap_status(
     State(state): State<Arc<AppState>>,
+    context: AnalyticsContext,
+    CurrentTenant(tenant): CurrentTenant,
     Path(swap_id): Path<String>,
-) -> Result<Json<SwapStatusResponse>, (StatusCode, Json<SwapErrorResponse>)> {
-    let crud = SwapCrud::new(state.db.clone(), Some(state.redis.clone()));
+) -> Result<Json<SwapStatusResponse>, SwapError> {
+    let crud = swap_crud(&state)
+        .with_analytics(state.analytics.clone(), context)
+        .with_outbox(state.outbox.clone())
+        .with_tenant(tenant);
+
+    // During maintenance serve what we have instead of polling the provider
+    let maintenance = MaintenanceService::new(state.db.clone(), state.redis.clone()).current().await;
+    let response = if maintenance.enabled {
+        crud.get_stored_swap_status(&swap_id).await?
+    } else {
+        crud.get_swap_status(&swap_id).await?
+    };
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// POST /swap/{id}/share - Signed, expiring link to a redacted status view
+// =============================================================================
+
+#[utoipa::path(
+    post,
+    path = "/swap/{id}/share",
+    tag = "swap",
+    params(("id" = String, Path, description = "Swap id")),
+    request_body = Option<CreateShareLinkRequest>,
+    responses(
+        (status = 201, description = "Share link created", body = ShareLinkResponse),
+        (status = 404, description = "Unknown swap, or another user's", body = SwapErrorResponse),
+        (status = 503, description = "Sharing is not configured", body = SwapErrorResponse),
+    ),
+    security((), ("bearer_auth" = [])),
+)]
+pub async fn create_share_link(
+    State(state): State<Arc<AppState>>,
+    user: OptionalUser,
+    CurrentTenant(tenant): CurrentTenant,
+    Path(swap_id): Path<String>,
+    payload: Option<Json<CreateShareLinkRequest>>,
+) -> Result<(StatusCode, Json<ShareLinkResponse>), SwapError> {
+    let crud = swap_crud(&state).with_tenant(tenant);
+    let user_id = user.0.map(|u| u.id);
+    let options = payload.map(|Json(p)| p).unwrap_or_default();
+
+    let response = crud
+        .create_share_link(&swap_id, user_id.as_deref(), &state.share_links, &options)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+// =============================================================================
+// GET /swap/shared/{id} - Status view behind a share link
+// =============================================================================
+
+#[utoipa::path(
+    get,
+    path = "/swap/shared/{id}",
+    tag = "swap",
+    params(("id" = String, Path, description = "Swap id"), SharedSwapQuery),
+    responses(
+        (status = 200, description = "Redacted status", body = SharedSwapStatusResponse),
+        (status = 403, description = "Bad signature", body = SwapErrorResponse),
+        (status = 410, description = "Link expired", body = SwapErrorResponse),
+    ),
+)]
+pub async fn get_shared_swap(
+    State(state): State<Arc<AppState>>,
+    CurrentTenant(tenant): CurrentTenant,
+    Path(swap_id): Path<String>,
+    Query(query): Query<SharedSwapQuery>,
+) -> Result<Json<SharedSwapStatusResponse>, SwapError> {
+    let secret = state.share_links.secret.as_deref().ok_or(SwapError::SharingDisabled)?;
+    verify_share(secret, &swap_id, query.expires, &query.sig)?;
+
+    // Link holders only read what is stored; they never trigger provider calls
+    let crud = swap_crud(&state).with_tenant(tenant);
+    let response = crud.get_stored_swap_status(&swap_id).await?;
+
+    Ok(Json(response.into()))
+}
+
+// =============================================================================
+// GET /swap/{id}/refund - Refund details of a refunded swap
+// =============================================================================
+
+#[utoipa::path(
+    get,
+    path = "/swap/{id}/refund",
+    tag = "swap",
+    params(("id" = String, Path, description = "Swap id")),
+    responses(
+        (status = 200, description = "Refund details", body = SwapRefundResponse),
+        (status = 404, description = "Unknown swap or no refund recorded", body = SwapErrorResponse),
+    ),
+)]
+pub async fn get_swap_refund(
+    State(state): State<Arc<AppState>>,
+    CurrentTenant(tenant): CurrentTenant,
+    Path(swap_id): Path<String>,
+) -> Result<Json<SwapRefundResponse>, SwapError> {
+    let crud = swap_crud(&state).with_tenant(tenant);
+
+    let response = crud.get_swap_refund(&swap_id).await?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// POST /swap/status/batch - Cached statuses for several of the caller's swaps
+// =============================================================================
+
+#[utoipa::path(
+    post,
+    path = "/swap/status/batch",
+    tag = "swap",
+    request_body = BatchSwapStatusRequest,
+    responses(
+        (status = 200, description = "Cached statuses of the caller's swaps", body = BatchSwapStatusResponse),
+        (status = 400, description = "Too many swap ids", body = SwapErrorResponse),
+        (status = 401, description = "Not signed in"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_swap_statuses(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+    CurrentTenant(tenant): CurrentTenant,
+    Json(payload): Json<BatchSwapStatusRequest>,
+) -> Result<Json<BatchSwapStatusResponse>, SwapError> {
+    let crud = swap_crud(&state).with_tenant(tenant);
+
+    let response = crud.get_swap_statuses(&payload.swap_ids, &user.id).await?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// GET /swap/history - The caller's swaps, newest first
+// =============================================================================
+
+#[utoipa::path(
+    get,
+    path = "/swap/history",
+    tag = "swap",
+    params(HistoryQuery),
+    responses(
+        (status = 200, description = "The caller's swaps, newest first", body = SwapHistoryResponse),
+        (status = 400, description = "Invalid filter or cursor", body = SwapErrorResponse),
+        (status = 401, description = "Not signed in"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_swap_history(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+    CurrentTenant(tenant): CurrentTenant,
+    Query(query): Query<HistoryQuery>,
+) -> Result<Json<SwapHistoryResponse>, SwapError> {
+    let crud = swap_crud(&state).with_tenant(tenant);
+
+    let response = crud.get_user_swaps(&user.id, &query).await?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// GET /swap/limits - What the caller may still swap
+// =============================================================================
+
+#[utoipa::path(
+    get,
+    path = "/swap/limits",
+    tag = "swap",
+    responses(
+        (status = 200, description = "Per-swap limits and the remaining rolling volume", body = SwapLimitsResponse),
+        (status = 401, description = "Not signed in"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_swap_limits(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+) -> Result<Json<SwapLimitsResponse>, SwapError> {
+    let crud = swap_crud(&state);
 
-    let response = crud.get_swap_status(&swap_id).await.map_err(|e| {
-        let status = match e {
-            super::crud::SwapError::SwapNotFound => StatusCode::NOT_FOUND,
-            super::crud::SwapError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            super::crud::SwapError::ExternalApiError(_) => StatusCode::BAD_GATEWAY,
-            _ => StatusCode::INTERNAL_SERVER_ERROR,
-        };
-        (status, Json(SwapErrorResponse::new(e.to_string())))
-    })?;
+    let response = crud.get_swap_limits(&user.id).await?;
 
     Ok(Json(response))
 }
 
+// =============================================================================
+// /swap/drafts - Save and resume a swap form part way through
+// =============================================================================
+
+#[utoipa::path(
+    post,
+    path = "/swap/drafts",
+    tag = "swap",
+    request_body = SwapDraft,
+    responses(
+        (status = 201, description = "Draft saved", body = SwapDraftResponse),
+        (status = 400, description = "Invalid draft", body = SwapErrorResponse),
+    ),
+)]
+pub async fn create_swap_draft(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<SwapDraft>,
+) -> Result<(StatusCode, Json<SwapDraftResponse>), SwapError> {
+    let crud = swap_crud(&state);
+
+    let response = crud.create_swap_draft(payload).await?;
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/swap/drafts/{token}",
+    tag = "swap",
+    params(("token" = String, Path, description = "Draft token")),
+    responses(
+        (status = 200, description = "Saved draft", body = SwapDraftResponse),
+        (status = 404, description = "Unknown or expired draft", body = SwapErrorResponse),
+    ),
+)]
+pub async fn get_swap_draft(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+) -> Result<Json<SwapDraftResponse>, SwapError> {
+    let crud = swap_crud(&state);
+
+    let response = crud.get_swap_draft(&token).await?;
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    put,
+    path = "/swap/drafts/{token}",
+    tag = "swap",
+    params(("token" = String, Path, description = "Draft token")),
+    request_body = SwapDraft,
+    responses(
+        (status = 200, description = "Draft replaced", body = SwapDraftResponse),
+        (status = 400, description = "Invalid draft", body = SwapErrorResponse),
+        (status = 404, description = "Unknown or expired draft", body = SwapErrorResponse),
+    ),
+)]
+pub async fn update_swap_draft(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+    Json(payload): Json<SwapDraft>,
+) -> Result<Json<SwapDraftResponse>, SwapError> {
+    let crud = swap_crud(&state);
+
+    let response = crud.update_swap_draft(&token, payload).await?;
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/swap/drafts/{token}",
+    tag = "swap",
+    params(("token" = String, Path, description = "Draft token")),
+    responses(
+        (status = 200, description = "Deleted draft", body = SwapDraftResponse),
+        (status = 404, description = "Unknown or expired draft", body = SwapErrorResponse),
+    ),
+)]
+pub async fn delete_swap_draft(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+) -> Result<Json<SwapDraftResponse>, SwapError> {
+    let crud = swap_crud(&state);
+
+    let response = crud.delete_swap_draft(&token).await?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// GET /swap/refund-addresses - The caller's previously used addresses for a currency
+// =============================================================================
+
+#[utoipa::path(
+    get,
+    path = "/swap/refund-addresses",
+    tag = "swap",
+    params(RefundAddressQuery),
+    responses(
+        (status = 200, description = "Previously used addresses", body = RefundAddressSuggestionsResponse),
+        (status = 401, description = "Not signed in"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_refund_address_suggestions(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+    Query(query): Query<RefundAddressQuery>,
+) -> Result<Json<RefundAddressSuggestionsResponse>, SwapError> {
+    let crud = swap_crud(&state);
+
+    let response = crud.get_refund_address_suggestions(&user.id, &query).await?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// POST /swap/:id/retry - Re-create a failed or expired swap
+// =============================================================================
+
+#[utoipa::path(
+    post,
+    path = "/swap/{id}/retry",
+    tag = "swap",
+    params(("id" = String, Path, description = "Failed or expired swap")),
+    request_body = Option<RetrySwapRequest>,
+    responses(
+        (status = 201, description = "Replacement swap", body = CreateSwapResponse),
+        (status = 404, description = "Unknown swap", body = SwapErrorResponse),
+        (status = 409, description = "Swap cannot be retried or already was", body = SwapErrorResponse),
+    ),
+    security((), ("bearer_auth" = [])),
+)]
+pub async fn retry_swap(
+    State(state): State<Arc<AppState>>,
+    _writes: WritesAllowed,
+    user: OptionalUser,
+    context: AnalyticsContext,
+    CurrentBrand(brand): CurrentBrand,
+    Path(swap_id): Path<String>,
+    payload: Option<Json<RetrySwapRequest>>,
+) -> Result<(StatusCode, Json<CreateSwapResponse>), SwapError> {
+    let max_kyc_rating = user.0.as_ref().and_then(|u| u.kyc_limit());
+    let (user_id, fee_tier) = user.0.map(|u| (u.id, u.fee_tier)).unzip();
+    let crud = swap_crud(&state)
+        .with_analytics(state.analytics.clone(), context.with_user(user_id.clone()))
+        .with_outbox(state.outbox.clone())
+        .with_brand(brand)
+        .with_fee_tier(fee_tier)
+        .with_max_kyc_rating(max_kyc_rating);
+    let options = payload.map(|Json(p)| p).unwrap_or_default();
+
+    let response = crud.retry_swap(&swap_id, user_id, &options).await?;
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+// =============================================================================
+// POST /swap/import - Watch a swap made directly with a provider
+// =============================================================================
+
+#[utoipa::path(
+    post,
+    path = "/swap/import",
+    tag = "swap",
+    request_body = ImportSwapRequest,
+    responses(
+        (status = 201, description = "Swap imported", body = SwapStatusResponse),
+        (status = 400, description = "Invalid provider or trade id", body = SwapErrorResponse),
+        (status = 404, description = "The provider has no such trade", body = SwapErrorResponse),
+        (status = 409, description = "Trade already tracked", body = SwapErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn import_swap(
+    State(state): State<Arc<AppState>>,
+    _writes: WritesAllowed,
+    AuthUser(user): AuthUser,
+    CurrentBrand(brand): CurrentBrand,
+    Json(payload): Json<ImportSwapRequest>,
+) -> Result<(StatusCode, Json<SwapStatusResponse>), SwapError> {
+    let crud = swap_crud(&state)
+        .with_outbox(state.outbox.clone())
+        .with_brand(brand);
+
+    let response = crud.import_swap(&payload, &user.id).await?;
+    crud.cache_swap_status(&response).await;
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
 // =============================================================================
 // POST /swap/validate-address - Validate cryptocurrency address
 // =============================================================================
 
+#[utoipa::path(
+    post,
+    path = "/swap/validate-address",
+    tag = "swap",
+    request_body = ValidateAddressRequest,
+    responses((status = 200, description = "Validation result", body = ValidateAddressResponse)),
+)]
 pub async fn validate_address(
     State(state): State<Arc<AppState>>,
     Json(payload): Json<ValidateAddressRequest>,
-) -> Result<Json<ValidateAddressResponse>, (StatusCode, Json<SwapErrorResponse>)> {
-    let crud = SwapCrud::new(state.db.clone(), Some(state.redis.clone()));
+) -> Result<Json<ValidateAddressResponse>, SwapError> {
+    let crud = swap_crud(&state);
+
+    let response = crud.validate_address(&payload).await?;
+
+    Ok(Json(response))
+}
+// =============================================================================
+// POST /swap/addresses/challenge - Message proving control of a payout address
+// =============================================================================
+
+#[utoipa::path(
+    post,
+    path = "/swap/addresses/challenge",
+    tag = "swap",
+    request_body = AddressChallengeRequest,
+    responses(
+        (status = 201, description = "Message to sign with the address's key", body = AddressChallengeResponse),
+        (status = 400, description = "Invalid address or chain without message signing", body = SwapErrorResponse),
+        (status = 401, description = "Not signed in"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn create_address_challenge(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+    Json(payload): Json<AddressChallengeRequest>,
+) -> Result<(StatusCode, Json<AddressChallengeResponse>), SwapError> {
+    let crud = swap_crud(&state);
+
+    let response = crud
+        .create_address_challenge(&user.id, &payload, state.address_verification.challenge_ttl)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+// =============================================================================
+// POST /swap/addresses/verify - Submit the signed challenge
+// =============================================================================
+
+#[utoipa::path(
+    post,
+    path = "/swap/addresses/verify",
+    tag = "swap",
+    request_body = VerifyAddressRequest,
+    responses(
+        (status = 200, description = "Address verified", body = VerifiedAddressResponse),
+        (status = 400, description = "Bad or foreign signature", body = SwapErrorResponse),
+        (status = 401, description = "Not signed in"),
+        (status = 404, description = "No pending challenge", body = SwapErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn verify_address(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+    Json(payload): Json<VerifyAddressRequest>,
+) -> Result<Json<VerifiedAddressResponse>, SwapError> {
+    let crud = swap_crud(&state);
+
+    let response = crud.verify_address(&user.id, &payload).await?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// GET /swap/addresses/verified - The caller's verified payout addresses
+// =============================================================================
+
+#[utoipa::path(
+    get,
+    path = "/swap/addresses/verified",
+    tag = "swap",
+    responses(
+        (status = 200, description = "Verified addresses", body = VerifiedAddressesResponse),
+        (status = 401, description = "Not signed in"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_verified_addresses(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+) -> Result<Json<VerifiedAddressesResponse>, SwapError> {
+    let crud = swap_crud(&state);
+
+    let response = crud.get_verified_addresses(&user.id).await?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// DELETE /swap/addresses/verified/{id} - Forget a verified address
+// =============================================================================
+
+#[utoipa::path(
+    delete,
+    path = "/swap/addresses/verified/{id}",
+    tag = "swap",
+    params(("id" = String, Path, description = "Verified address id")),
+    responses(
+        (status = 200, description = "The removed address", body = VerifiedAddressResponse),
+        (status = 401, description = "Not signed in"),
+        (status = 404, description = "Unknown address", body = SwapErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn delete_verified_address(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<VerifiedAddressResponse>, SwapError> {
+    let crud = swap_crud(&state);
 
-    let response = crud.validate_address(&payload).await.map_err(|e| {
-        let status = match e {
-            super::crud::SwapError::InvalidAddress => StatusCode::BAD_REQUEST,
-            super::crud::SwapError::ExternalApiError(_) => StatusCode::BAD_GATEWAY,
-            _ => StatusCode::INTERNAL_SERVER_ERROR,
-        };
-        (status, Json(SwapErrorResponse::new(e.to_string())))
-    })?;
+    let response = crud.delete_verified_address(&user.id, &id).await?;
 
     Ok(Json(response))
-}
\ No newline at end of file
+}
+
+// =============================================================================
+// GET /swap/favorites - Starred pairs, recent pairs and saved addresses
+// =============================================================================
+
+#[utoipa::path(
+    get,
+    path = "/swap/favorites",
+    tag = "swap",
+    responses(
+        (status = 200, description = "What the swap form can be prefilled from", body = FavoritesResponse),
+        (status = 401, description = "Not signed in"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_favorites(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+) -> Result<Json<FavoritesResponse>, SwapError> {
+    let crud = swap_crud(&state);
+
+    let response = crud.get_favorites(&user.id).await?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// POST /swap/favorites - Star a pair or save a recipient address
+// =============================================================================
+
+#[utoipa::path(
+    post,
+    path = "/swap/favorites",
+    tag = "swap",
+    request_body = SaveFavoriteRequest,
+    responses(
+        (status = 200, description = "The saved pair or address", body = SavedFavorite),
+        (status = 400, description = "Invalid pair or address, or too many saved", body = SwapErrorResponse),
+        (status = 401, description = "Not signed in"),
+        (status = 404, description = "Unknown currency", body = SwapErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn save_favorite(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+    CurrentBrand(brand): CurrentBrand,
+    Json(request): Json<SaveFavoriteRequest>,
+) -> Result<Json<SavedFavorite>, SwapError> {
+    let crud = swap_crud(&state).with_brand(brand);
+
+    let response = crud.save_favorite(&user.id, &request).await?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// DELETE /swap/favorites/pairs/{id} - Unstar a pair
+// =============================================================================
+
+#[utoipa::path(
+    delete,
+    path = "/swap/favorites/pairs/{id}",
+    tag = "swap",
+    params(("id" = String, Path, description = "Favorite pair id")),
+    responses(
+        (status = 200, description = "The removed pair", body = FavoritePairResponse),
+        (status = 401, description = "Not signed in"),
+        (status = 404, description = "Unknown pair", body = SwapErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn delete_favorite_pair(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<FavoritePairResponse>, SwapError> {
+    let crud = swap_crud(&state);
+
+    let response = crud.delete_favorite_pair(&user.id, &id).await?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// DELETE /swap/favorites/addresses/{id} - Forget a saved address
+// =============================================================================
+
+#[utoipa::path(
+    delete,
+    path = "/swap/favorites/addresses/{id}",
+    tag = "swap",
+    params(("id" = String, Path, description = "Saved address id")),
+    responses(
+        (status = 200, description = "The removed address", body = SavedAddressResponse),
+        (status = 401, description = "Not signed in"),
+        (status = 404, description = "Unknown address", body = SwapErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn delete_saved_address(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<SavedAddressResponse>, SwapError> {
+    let crud = swap_crud(&state);
+
+    let response = crud.delete_saved_address(&user.id, &id).await?;
+
+    Ok(Json(response))
+}
@@ -1,19 +1,136 @@
-use chrono::Utc;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sha2::{Digest, Sha256};
 use sqlx::{MySql, Pool};
+use std::sync::{Arc, LazyLock};
 use std::time::Duration;
 
-use super::model::{Currency, Provider};
-use super::schema::{CurrenciesQuery, ProvidersQuery, TrocadorCurrency, TrocadorProvider, CurrencyResponse, ProviderResponse};
-use crate::services::trocador::{TrocadorClient, TrocadorError};
-use crate::services::redis_cache::RedisService;
+use super::model::{Currency, PairQuote, Provider, SyncRun};
+use super::state::{StatusSource, SwapStateMachine, Transition};
+use super::schema::{
+    CurrenciesQuery, ProvidersQuery, TrocadorCurrency, TrocadorProvider, CurrencyResponse, ProviderResponse,
+    GroupedCurrencyResponse, SyncKind, SyncRunStatus, SyncStatusResponse,
+};
+use crate::modules::affiliate::model::Affiliate;
+use crate::config::environment::{
+    AddressVerificationConfig, BrandingConfig, DbRetryConfig, DepositCheckConfig, HighValueConfig,
+    ProviderFallbackConfig, ProviderHealthConfig, ProviderSelectionConfig, RateGuardConfig, SandboxConfig,
+    ShareLinkConfig, SwapRouteConfig, VolumeLimitConfig,
+};
+use crate::modules::brand::webhooks as brand_webhooks;
+use crate::services::address_format::{self, AddressFormatRegistry};
+use crate::services::address_validator::Chain;
+use crate::services::analytics::{Analytics, AnalyticsContext, FunnelEvent};
+use crate::services::block_explorer::explorers;
+use crate::services::branding::Brand;
+use crate::services::circuit_breaker::{self, CircuitBreaker};
+use crate::services::db_retry::retry_transient;
+use crate::services::deposit_check::{CheckTrigger, DepositCheckError, DepositChecker};
+use crate::services::fee_estimator::FeeEstimator;
+use crate::services::fees::{self, FeeRules, FeeScope};
+use crate::services::message_signing::{normalize_address, verify_signed_message, SignatureError};
+use crate::services::metrics::metrics;
+use crate::services::mock_provider::{MockProviderClient, SANDBOX_AGGREGATOR};
+use crate::services::money;
+use crate::services::notifications;
+use crate::services::outbox::{DomainEventType, Outbox};
+use crate::services::price_feed::{fiat_value, PriceFeed};
+use crate::services::provider_health::{self, HealthAction, HealthOverride, OutcomeCount, ProviderHealth, SwapOutcome};
+use crate::services::rate_guard::RateGuard;
+use crate::services::routing::{self, RoutingDecision, RoutingRules, RoutingScope};
+use crate::services::single_flight::SingleFlight;
+use crate::services::swap_provider::{configured_aggregators, is_shadowed, AggregatorQuotes, SwapProviderClient};
+use crate::services::tenant::TenantId;
+use crate::services::trocador::{TradeAmount, TrocadorClient, TrocadorError};
+use crate::services::redis_cache::{RedisService, RedisServiceError};
 
 pub enum CurrenciesResult {
-    RawJson(String),
+    RawJson { json: String, etag: String },
     Structured(Vec<CurrencyResponse>),
+    Page(super::schema::CurrenciesPage),
 }
 
-pub enum ProvidersResult {
+pub enum GroupedCurrenciesResult {
     RawJson(String),
+    Structured(Vec<GroupedCurrencyResponse>),
+}
+
+/// Row counts produced by a single sync run
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SyncStats {
+    pub fetched: usize,
+    pub changed: u64,
+}
+
+/// Result of a single provider probe
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProbeStats {
+    pub probed: usize,
+    pub up: usize,
+}
+
+/// Result of a single cache consistency pass over every checked cache
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConsistencyStats {
+    pub checked: usize,  // Cached entries (or entry groups) compared with the database
+    pub diverged: usize, // Of those, entries that no longer matched
+    pub repaired: usize, // Divergent entries dropped so the next read reloads them
+}
+
+impl ConsistencyStats {
+    fn record(&mut self, cache: &'static str, diverged: bool, repaired: bool) {
+        metrics().cache_consistency_checks.inc(cache);
+        self.checked += 1;
+        if diverged {
+            metrics().cache_divergence.inc(cache);
+            tracing::warn!("Cache '{}' diverged from the database{}", cache, if repaired { ", repaired" } else { "" });
+            self.diverged += 1;
+            self.repaired += usize::from(repaired);
+        }
+    }
+}
+
+/// History note on a swap whose deposit disappeared
+const DEPOSIT_LOST_MESSAGE: &str = "Deposit no longer seen by the provider (chain reorganization or double-spend)";
+
+/// Days of uptime history kept and served
+pub const UPTIME_HISTORY_DAYS: u32 = 90;
+
+/// Multiples of the base amount quoted by GET /swap/depth
+pub const DEPTH_LADDER: [Decimal; 6] = [dec!(1), dec!(2), dec!(5), dec!(10), dec!(25), dec!(50)];
+
+/// Depth moves slower than a single quote, so the ladder is cached longer
+const DEPTH_CACHE_SECS: u64 = 60;
+
+/// Pairs not quoted for this long are no longer served by GET /swap/pairs
+pub const PAIR_MAX_AGE_DAYS: u32 = 7;
+
+/// The pairs table only changes as rates are fetched, so listings are cached
+const PAIRS_CACHE_SECS: u64 = 300;
+/// Cached pairs listings, one key per filter combination
+const PAIRS_CACHE_PREFIX: &str = "pairs:";
+
+/// Providers an admin disabled, checked on every quote
+const DISABLED_PROVIDERS_KEY: &str = "providers:disabled";
+const DISABLED_PROVIDERS_CACHE_SECS: u64 = 60;
+
+/// Currency decimals only change by admin edit
+const CURRENCY_DECIMALS_CACHE_SECS: u64 = 600;
+
+/// USD a user created and completed over the last `?` hours
+const USER_VOLUME_SQL: &str =
+    "SELECT CAST(COALESCE(SUM(created_usd), 0) AS DOUBLE), CAST(COALESCE(SUM(completed_usd), 0) AS DOUBLE)
+     FROM user_volume
+     WHERE user_id = ? AND hour_start > DATE_SUB(NOW(), INTERVAL ? HOUR)";
+
+/// Provider health scores, checked on every quote; outcomes trickle in, so a
+/// minute of staleness is harmless
+const PROVIDER_HEALTH_KEY: &str = "providers:health";
+const PROVIDER_HEALTH_CACHE_SECS: u64 = 60;
+
+pub enum ProvidersResult {
+    RawJson { json: String, etag: String },
     Structured(Vec<ProviderResponse>),
 }
 
@@ -21,42 +138,311 @@ pub enum ProvidersResult {
 // SWAP ERROR
 // =============================================================================
 
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum SwapError {
+    #[error("Provider not found")]
     ProviderNotFound,
+
+    #[error("{0} is not quoting this pair")]
+    ProviderNotQuoting(String),
+
+    #[error("Currency not found")]
     CurrencyNotFound,
+
+    #[error("Trading pair not available")]
     PairNotAvailable,
-    AmountOutOfRange { min: f64, max: f64 },
+
+    #[error("Amount out of range: min={min}, max={max}")]
+    AmountOutOfRange { min: Decimal, max: Decimal },
+
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+
+    #[error("Invalid address")]
     InvalidAddress,
+
+    #[error("Swap not found")]
     SwapNotFound,
+
+    #[error("No refund recorded for this swap")]
+    RefundNotFound,
+
+    #[error("Provider unavailable: {0}")]
     ProviderUnavailable(String),
-    DatabaseError(String),
+
+    #[error("Currency {0} has been delisted")]
+    CurrencyDelisted(String),
+
+    #[error("A refund address is required when swapping from {0}")]
+    RefundAddressRequired(String),
+
+    #[error("Invalid refund address")]
+    InvalidRefundAddress,
+
+    #[error("{} is required when sending {ticker}", extra_id_name.as_deref().unwrap_or("Memo"))]
+    ExtraIdRequired { ticker: String, extra_id_name: Option<String> },
+
+    #[error("Only failed or expired swaps can be retried (status: {0:?})")]
+    SwapNotRetryable(super::schema::SwapStatus),
+
+    #[error("Swap was already retried as {0}")]
+    AlreadyRetried(String),
+
+    #[error("Swap {0} is already being retried")]
+    RetryInProgress(String),
+
+    #[error("At most {0} swap ids can be requested at once")]
+    TooManySwapIds(usize),
+
+    #[error("Invalid history query: {0}")]
+    InvalidHistoryQuery(String),
+
+    #[error("Invalid depth query: {0}")]
+    InvalidDepthQuery(String),
+
+    #[error("Swaps from {from} to {to} are not offered")]
+    PairNotAllowed { from: String, to: String }, // Outside the brand's allowed pairs
+
+    #[error("Provider {0} is not offered")]
+    ProviderNotAllowed(String), // Not among the brand's enabled providers
+
+    #[error("Invalid {} for {ticker}: {reason}", extra_id_name.as_deref().unwrap_or("memo"))]
+    InvalidExtraId { ticker: String, extra_id_name: Option<String>, reason: String },
+
+    #[error("Swap draft not found or expired")]
+    DraftNotFound, // Unknown or expired draft token
+
+    #[error("Invalid swap draft: {0}")]
+    InvalidDraft(String),
+
+    #[error("Quote not found")]
+    QuoteNotFound,
+
+    #[error("Reserved rate expired at {0}, request a new quote")]
+    RateExpired(DateTime<Utc>),
+
+    #[error("Quote was reserved for a different pair or amount")]
+    QuoteMismatch,
+
+    #[error("Quote was already used for a swap")]
+    QuoteAlreadyUsed,
+
+    #[error("{provider}'s rate is {deviation_pct:+.1}% from the market rate")]
+    RateOutOfBounds { provider: String, deviation_pct: f64 }, // Refused by the rate guard
+
+    #[error("Swap {0} was modified concurrently, please retry")]
+    ConcurrentUpdate(String), // Compare-and-swap kept losing to other writers
+
+    #[error("Invalid import: {0}")]
+    InvalidImport(String),
+
+    #[error("No trade {0} found at that provider")]
+    TradeNotFound(String),
+
+    #[error("Trade {0} is already tracked")]
+    TradeAlreadyTracked(String),
+
+    #[error("Share links are not configured")]
+    SharingDisabled, // SHARE_LINK_SECRET is not set
+
+    #[error("Invalid share link")]
+    InvalidShareLink,
+
+    #[error("Share link expired at {0}")]
+    ShareLinkExpired(DateTime<Utc>),
+
+    #[error("Address ownership cannot be verified for {0}")]
+    VerificationNotSupported(String),
+
+    #[error("Verified address not found")]
+    VerifiedAddressNotFound,
+
+    #[error("No pending challenge for this address, request a new one")]
+    ChallengeNotFound, // Never issued, expired or already used
+
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+
+    #[error("Favorite not found")]
+    FavoriteNotFound, // Unknown, another user's or already removed
+
+    #[error("Invalid favorite: {0}")]
+    InvalidFavorite(String),
+
+    #[error("At most {0} favorites of this kind can be saved, remove one first")]
+    TooManyFavorites(usize),
+
+    #[error(
+        "Swaps are limited to {limit} USD{}",
+        if *verified { "" } else { "; verify the recipient address to raise the limit" }
+    )]
+    SwapLimitExceeded { limit: f64, verified: bool }, // SWAP_MAX_USD / VERIFIED_SWAP_MAX_USD
+
+    #[error("Swaps are limited to {limit} USD per {window}; {remaining:.2} USD remains")]
+    VolumeLimitExceeded { window: &'static str, limit: f64, remaining: f64 }, // USER_VOLUME_24H_USD / USER_VOLUME_30D_USD
+
+    #[error("No provider quote meets the selection policy")]
+    NoQuoteMatchesPolicy,
+
+    #[error("Invalid KYC rating {0:?}, expected A, B, C or D")]
+    InvalidKycRating(String),
+
+    #[error("{provider} has KYC rating {rating}, worse than the {max} you accept")]
+    KycRatingNotAccepted { provider: String, rating: String, max: char }, // Against the user's max_kyc_rating
+
+    #[error("No route from {from} to {to} is quoted")]
+    NoRouteFound { from: String, to: String }, // Neither directly nor through an intermediate
+
+    #[error("Invalid route: {0}")]
+    InvalidRoute(String),
+
+    #[error("Route not found")]
+    RouteNotFound,
+
+    #[error("Sandbox mode is disabled")]
+    SandboxDisabled, // SANDBOX_ENABLED=false
+
+    #[error("{0} is not available in lightweight mode")]
+    NotSupported(&'static str), // Feature of the full server only; see modules::swap::lite
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("External API error: {0}")]
+    Trocador(#[from] TrocadorError),
+
+    #[error("External API error: {0}")]
     ExternalApiError(String),
-    RedisError(String), // Added RedisError
+
+    #[error("Redis error: {0}")]
+    RedisError(#[from] RedisServiceError),
+
+    #[error("Internal error: {0}")]
+    Internal(String),
 }
 
-impl std::fmt::Display for SwapError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl SwapError {
+    pub fn status_code(&self) -> axum::http::StatusCode {
+        use axum::http::StatusCode;
         match self {
-            SwapError::ProviderNotFound => write!(f, "Provider not found"),
-            SwapError::CurrencyNotFound => write!(f, "Currency not found"),
-            SwapError::PairNotAvailable => write!(f, "Trading pair not available"),
-            SwapError::AmountOutOfRange { min, max } => {
-                write!(f, "Amount out of range: min={}, max={}", min, max)
-            }
-            SwapError::InvalidAddress => write!(f, "Invalid address"),
-            SwapError::SwapNotFound => write!(f, "Swap not found"),
-            SwapError::ProviderUnavailable(msg) => write!(f, "Provider unavailable: {}", msg),
-            SwapError::DatabaseError(e) => write!(f, "Database error: {}", e),
-            SwapError::ExternalApiError(e) => write!(f, "External API error: {}", e),
-            SwapError::RedisError(e) => write!(f, "Redis error: {}", e),
+            Self::ProviderNotFound
+            | Self::CurrencyNotFound
+            | Self::SwapNotFound
+            | Self::RefundNotFound
+            | Self::TradeNotFound(_)
+            | Self::DraftNotFound
+            | Self::QuoteNotFound
+            | Self::VerifiedAddressNotFound
+            | Self::FavoriteNotFound
+            | Self::RouteNotFound
+            | Self::ChallengeNotFound => StatusCode::NOT_FOUND,
+            Self::ProviderNotQuoting(_)
+            | Self::PairNotAvailable
+            | Self::AmountOutOfRange { .. }
+            | Self::InvalidAmount(_)
+            | Self::InvalidAddress
+            | Self::CurrencyDelisted(_)
+            | Self::RefundAddressRequired(_)
+            | Self::InvalidRefundAddress
+            | Self::ExtraIdRequired { .. }
+            | Self::TooManySwapIds(_)
+            | Self::InvalidHistoryQuery(_)
+            | Self::InvalidDepthQuery(_)
+            | Self::PairNotAllowed { .. }
+            | Self::ProviderNotAllowed(_)
+            | Self::InvalidExtraId { .. }
+            | Self::InvalidDraft(_)
+            | Self::InvalidImport(_)
+            | Self::VerificationNotSupported(_)
+            | Self::InvalidSignature(_)
+            | Self::InvalidFavorite(_)
+            | Self::TooManyFavorites(_)
+            | Self::SwapLimitExceeded { .. }
+            | Self::VolumeLimitExceeded { .. }
+            | Self::SandboxDisabled
+            | Self::NoQuoteMatchesPolicy
+            | Self::InvalidKycRating(_)
+            | Self::NoRouteFound { .. }
+            | Self::InvalidRoute(_)
+            | Self::QuoteMismatch => StatusCode::BAD_REQUEST,
+            Self::RateExpired(_) | Self::ShareLinkExpired(_) => StatusCode::GONE,
+            Self::InvalidShareLink => StatusCode::FORBIDDEN,
+            Self::RateOutOfBounds { .. } | Self::KycRatingNotAccepted { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::SwapNotRetryable(_)
+            | Self::AlreadyRetried(_)
+            | Self::RetryInProgress(_)
+            | Self::TradeAlreadyTracked(_)
+            | Self::ConcurrentUpdate(_)
+            | Self::QuoteAlreadyUsed => StatusCode::CONFLICT,
+            Self::ProviderUnavailable(_) | Self::SharingDisabled => StatusCode::SERVICE_UNAVAILABLE,
+            Self::NotSupported(_) => StatusCode::NOT_IMPLEMENTED,
+            Self::Trocador(_) | Self::ExternalApiError(_) => StatusCode::BAD_GATEWAY,
+            Self::Database(_) | Self::RedisError(_) | Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
-}
 
-impl From<TrocadorError> for SwapError {
-    fn from(err: TrocadorError) -> Self {
-        SwapError::ExternalApiError(err.to_string())
+    /// Stable machine-readable code served as `code` in error responses
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            Self::ProviderNotFound => "PROVIDER_NOT_FOUND",
+            Self::ProviderNotQuoting(_) => "PROVIDER_NOT_QUOTING",
+            Self::CurrencyNotFound => "CURRENCY_NOT_FOUND",
+            Self::PairNotAvailable => "PAIR_NOT_AVAILABLE",
+            Self::AmountOutOfRange { .. } => "AMOUNT_OUT_OF_RANGE",
+            Self::InvalidAmount(_) => "INVALID_AMOUNT",
+            Self::InvalidAddress => "INVALID_ADDRESS",
+            Self::SwapNotFound => "SWAP_NOT_FOUND",
+            Self::RefundNotFound => "REFUND_NOT_FOUND",
+            Self::ProviderUnavailable(_) => "PROVIDER_UNAVAILABLE",
+            Self::CurrencyDelisted(_) => "CURRENCY_DELISTED",
+            Self::RefundAddressRequired(_) => "REFUND_ADDRESS_REQUIRED",
+            Self::InvalidRefundAddress => "INVALID_REFUND_ADDRESS",
+            Self::ExtraIdRequired { .. } => "EXTRA_ID_REQUIRED",
+            Self::SwapNotRetryable(_) => "SWAP_NOT_RETRYABLE",
+            Self::AlreadyRetried(_) => "ALREADY_RETRIED",
+            Self::RetryInProgress(_) => "RETRY_IN_PROGRESS",
+            Self::TooManySwapIds(_) => "TOO_MANY_SWAP_IDS",
+            Self::InvalidHistoryQuery(_) => "INVALID_HISTORY_QUERY",
+            Self::InvalidDepthQuery(_) => "INVALID_DEPTH_QUERY",
+            Self::PairNotAllowed { .. } => "PAIR_NOT_ALLOWED",
+            Self::ProviderNotAllowed(_) => "PROVIDER_NOT_ALLOWED",
+            Self::InvalidExtraId { .. } => "INVALID_EXTRA_ID",
+            Self::DraftNotFound => "DRAFT_NOT_FOUND",
+            Self::InvalidDraft(_) => "INVALID_DRAFT",
+            Self::QuoteNotFound => "QUOTE_NOT_FOUND",
+            Self::RateExpired(_) => "RATE_EXPIRED",
+            Self::QuoteMismatch => "QUOTE_MISMATCH",
+            Self::QuoteAlreadyUsed => "QUOTE_ALREADY_USED",
+            Self::RateOutOfBounds { .. } => "RATE_OUT_OF_BOUNDS",
+            Self::ConcurrentUpdate(_) => "CONCURRENT_UPDATE",
+            Self::InvalidImport(_) => "INVALID_IMPORT",
+            Self::TradeNotFound(_) => "TRADE_NOT_FOUND",
+            Self::TradeAlreadyTracked(_) => "TRADE_ALREADY_TRACKED",
+            Self::SharingDisabled => "SHARING_DISABLED",
+            Self::InvalidShareLink => "INVALID_SHARE_LINK",
+            Self::ShareLinkExpired(_) => "SHARE_LINK_EXPIRED",
+            Self::VerificationNotSupported(_) => "VERIFICATION_NOT_SUPPORTED",
+            Self::VerifiedAddressNotFound => "VERIFIED_ADDRESS_NOT_FOUND",
+            Self::ChallengeNotFound => "CHALLENGE_NOT_FOUND",
+            Self::InvalidSignature(_) => "INVALID_SIGNATURE",
+            Self::FavoriteNotFound => "FAVORITE_NOT_FOUND",
+            Self::InvalidFavorite(_) => "INVALID_FAVORITE",
+            Self::TooManyFavorites(_) => "TOO_MANY_FAVORITES",
+            Self::SwapLimitExceeded { .. } => "SWAP_LIMIT_EXCEEDED",
+            Self::VolumeLimitExceeded { .. } => "VOLUME_LIMIT_EXCEEDED",
+            Self::SandboxDisabled => "SANDBOX_DISABLED",
+            Self::NoQuoteMatchesPolicy => "NO_QUOTE_MATCHES_POLICY",
+            Self::InvalidKycRating(_) => "INVALID_KYC_RATING",
+            Self::KycRatingNotAccepted { .. } => "KYC_RATING_NOT_ACCEPTED",
+            Self::NoRouteFound { .. } => "NO_ROUTE_FOUND",
+            Self::InvalidRoute(_) => "INVALID_ROUTE",
+            Self::RouteNotFound => "ROUTE_NOT_FOUND",
+            Self::NotSupported(_) => "NOT_SUPPORTED",
+            Self::Database(_) => "DATABASE_ERROR",
+            Self::Trocador(_) | Self::ExternalApiError(_) => "EXTERNAL_API_ERROR",
+            Self::RedisError(_) => "REDIS_ERROR",
+            Self::Internal(_) => "INTERNAL_ERROR",
+        }
     }
 }
 
@@ -67,97 +453,504 @@ impl From<TrocadorError> for SwapError {
 pub struct SwapCrud {
     pool: Pool<MySql>,
     redis_service: Option<RedisService>, // Changed to RedisService
+    analytics: Analytics,
+    analytics_context: AnalyticsContext,
+    outbox: Outbox,
+    brand: Brand,
+    fee_tier: Option<String>,
+    max_kyc_rating: Option<char>, // Signed-in user's worst acceptable provider KYC rating
+    country: Option<String>, // Caller's ISO country code, for routing rules
+    tenant: Option<TenantId>, // None reaches every tenant's swaps (background jobs, webhooks)
+    trocador: Option<TrocadorClient>, // Shared client from AppState; None fails Trocador calls
+    db_retry: DbRetryConfig,
+    price_feed: PriceFeed, // Fiat amounts on rates, swaps and history
+    fee_estimator: FeeEstimator, // Network fees on rates
+    rate_guard: RateGuard,       // Screens quotes against the median rate for the pair
+    provider_selection: ProviderSelectionConfig, // Policy for swaps without a named provider
+    provider_fallback: ProviderFallbackConfig, // Which quotes a rejected trade may fall back to
+    high_value: HighValueConfig, // Threshold and reference USD prices for valuing swaps
+    address_verification: AddressVerificationConfig, // Per-swap USD limits, raised for verified recipients
+    affiliate: Option<Affiliate>, // Referrer new swaps are attributed to
+    volume_limits: VolumeLimitConfig, // Rolling per-user caps checked on create
+}
+
+/// USD a pending create holds against its user's volume for one hour;
+/// released if the swap is not created
+struct VolumeReservation {
+    user_id: String,
+    hour_start: NaiveDateTime,
+    usd: f64,
 }
 
 impl SwapCrud {
     pub fn new(pool: Pool<MySql>, redis_service: Option<RedisService>) -> Self {
-        Self { pool, redis_service }
+        Self {
+            pool,
+            redis_service,
+            analytics: Analytics::disabled(),
+            analytics_context: AnalyticsContext::default(),
+            outbox: Outbox::disabled(),
+            brand: Brand::from_config(&BrandingConfig::default()),
+            fee_tier: None,
+            max_kyc_rating: None,
+            country: None,
+            tenant: None,
+            trocador: None,
+            db_retry: DbRetryConfig::from_env(),
+            price_feed: PriceFeed::disabled(),
+            fee_estimator: FeeEstimator::disabled(),
+            rate_guard: RateGuard::new(RateGuardConfig::default()),
+            provider_selection: ProviderSelectionConfig::default(),
+            provider_fallback: ProviderFallbackConfig::default(),
+            high_value: HighValueConfig::default(),
+            address_verification: AddressVerificationConfig::default(),
+            affiliate: None,
+            volume_limits: VolumeLimitConfig::default(),
+        }
     }
 
-    // =========================================================================
-    // CURRENCIES
-    // =========================================================================
+    /// Reach Trocador through `client`, shared rather than built per call
+    pub fn with_trocador(mut self, client: Option<TrocadorClient>) -> Self {
+        self.trocador = client;
+        self
+    }
 
-    /// Check if currencies cache needs refresh using Probabilistic Early Recomputation (PER)
-    pub async fn should_sync_currencies(&self) -> Result<bool, SwapError> {
-        let result: Option<(Option<chrono::DateTime<Utc>>,)> = sqlx::query_as(
-            "SELECT MAX(last_synced_at) FROM currencies"
-        )
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| SwapError::DatabaseError(e.to_string()))?;
-
-        match result {
-            Some((Some(last_sync),)) => {
-                let now = Utc::now();
-                let cache_age = now - last_sync;
-                let ttl_seconds = 300.0; // 5 minutes
-
-                // Get the last sync duration (Delta) from Redis, default to 2.0s if missing
-                let delta = if let Some(service) = &self.redis_service {
-                    service.get_string("currencies:sync_duration")
-                        .await
-                        .ok()
-                        .flatten()
-                        .and_then(|s| s.parse::<f64>().ok())
-                        .unwrap_or(2.0)
-                } else {
-                    2.0
-                };
-
-                let beta = 1.0;
-                let rand: f64 = rand::random(); // 0.0 to 1.0
-                
-                // Avoid log(0) which is -infinity
-                let safe_rand = if rand < 0.0001 { 0.0001 } else { rand };
-                
-                // PER Formula: TimeToRefresh = TTL - (Delta * Beta * -ln(rand))
-                // Note: -ln(rand) is positive because ln(0..1) is negative
-                let early_expire_margin = delta * beta * (-safe_rand.ln());
-                
-                let effective_ttl = ttl_seconds - early_expire_margin;
+    fn trocador(&self) -> Result<&TrocadorClient, SwapError> {
+        self.trocador
+            .as_ref()
+            .ok_or_else(|| SwapError::ExternalApiError("TROCADOR_API_KEY not set".to_string()))
+    }
+
+    /// Mock provider for new sandbox rates and swaps, unless SANDBOX_ENABLED is off
+    fn sandbox(&self) -> Result<MockProviderClient, SwapError> {
+        let config = SandboxConfig::from_env();
+        if !config.enabled {
+            return Err(SwapError::SandboxDisabled);
+        }
+        Ok(MockProviderClient::new(&config, self.high_value.usd_prices.clone()))
+    }
+
+    /// Price quotes and swaps with the fee rules for a user's tier; without
+    /// one only rules that apply to every tier are used
+    pub fn with_fee_tier(mut self, fee_tier: Option<String>) -> Self {
+        self.fee_tier = fee_tier;
+        self
+    }
+
+    /// Only quote, select and create swaps with providers rated `max_kyc_rating`
+    /// or better (A best, D worst), as the signed-in user asked
+    pub fn with_max_kyc_rating(mut self, max_kyc_rating: Option<char>) -> Self {
+        self.max_kyc_rating = max_kyc_rating;
+        self
+    }
+
+    /// Route quotes and swaps for a caller in `country`; without one only
+    /// rules that apply to every country are used
+    pub fn with_country(mut self, country: Option<String>) -> Self {
+        self.country = country;
+        self
+    }
+
+    /// Serve quotes and create swaps under a white-label brand, in its tenant
+    pub fn with_brand(mut self, brand: Brand) -> Self {
+        self.tenant = Some(brand.tenant.clone());
+        self.brand = brand;
+        self
+    }
+
+    /// Only find, list and create swaps of `tenant`
+    pub fn with_tenant(mut self, tenant: TenantId) -> Self {
+        self.tenant = Some(tenant);
+        self
+    }
+
+    /// Tenant new swaps, fee rules and events belong to
+    fn tenant(&self) -> TenantId {
+        self.tenant.clone().unwrap_or_default()
+    }
+
+    /// ` AND tenant_id = ?` when scoped to a tenant
+    fn push_tenant_scope(&self, builder: &mut sqlx::QueryBuilder<'_, MySql>) {
+        if let Some(tenant) = &self.tenant {
+            builder.push(" AND tenant_id = ").push_bind(tenant.to_string());
+        }
+    }
+
+    /// Add USD and EUR amounts from `price_feed` to rates, swaps and history
+    pub fn with_price_feed(mut self, price_feed: PriceFeed) -> Self {
+        self.price_feed = price_feed;
+        self
+    }
+
+    /// Estimate each quote's payout network fee with `fee_estimator`
+    pub fn with_fee_estimator(mut self, fee_estimator: FeeEstimator) -> Self {
+        self.fee_estimator = fee_estimator;
+        self
+    }
+
+    /// Screen quotes, and swaps at a quoted rate, with `rate_guard`
+    pub fn with_rate_guard(mut self, rate_guard: RateGuard) -> Self {
+        self.rate_guard = rate_guard;
+        self
+    }
+
+    /// Pick the provider of swaps without one under `provider_selection`
+    pub fn with_provider_selection(mut self, provider_selection: ProviderSelectionConfig) -> Self {
+        self.provider_selection = provider_selection;
+        self
+    }
+
+    /// Fall back from a rejected trade to quotes within `provider_fallback`
+    pub fn with_provider_fallback(mut self, provider_fallback: ProviderFallbackConfig) -> Self {
+        self.provider_fallback = provider_fallback;
+        self
+    }
+
+    /// Value swaps and tag high-value ones with `high_value`
+    pub fn with_high_value(mut self, high_value: HighValueConfig) -> Self {
+        self.high_value = high_value;
+        self
+    }
+
+    /// Limit each swap's USD value under `address_verification`
+    pub fn with_address_verification(mut self, address_verification: AddressVerificationConfig) -> Self {
+        self.address_verification = address_verification;
+        self
+    }
+
+    /// Refuse creates that would take a user past the `volume_limits` caps
+    pub fn with_volume_limits(mut self, volume_limits: VolumeLimitConfig) -> Self {
+        self.volume_limits = volume_limits;
+        self
+    }
+
+    /// Attribute new swaps to `affiliate`, with its share of the platform fee
+    pub fn with_affiliate(mut self, affiliate: Option<Affiliate>) -> Self {
+        self.affiliate = affiliate;
+        self
+    }
+
+    /// Record domain events for the event bus relay
+    pub fn with_outbox(mut self, outbox: Outbox) -> Self {
+        self.outbox = outbox;
+        self
+    }
+
+    /// Emit funnel events for the caller described by `context`
+    pub fn with_analytics(mut self, analytics: Analytics, context: AnalyticsContext) -> Self {
+        self.analytics = analytics;
+        self.analytics_context = context;
+        self
+    }
+
+    fn track(&self, event: FunnelEvent, swap_id: Option<&str>, properties: serde_json::Value) {
+        let context = self.analytics_context.clone().with_tenant(self.tenant());
+        self.analytics.track(event, swap_id, &context, properties);
+    }
+
+    fn address_formats(&self) -> AddressFormatRegistry {
+        AddressFormatRegistry::new(self.pool.clone(), self.redis_service.clone())
+    }
+
+    /// Fee rules, or none when they can't be loaded (quotes are then priced
+    /// without a platform fee rather than failing)
+    async fn fee_rules(&self) -> Vec<fees::FeeRule> {
+        FeeRules::new(self.pool.clone(), self.redis_service.clone())
+            .with_tenant(self.tenant())
+            .all()
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to load fee rules: {}", e);
+                Vec::new()
+            })
+    }
+
+    /// What the routing rules decide for `amount` of `from` swapped to `to`;
+    /// nothing when they can't be loaded, so quotes are served unrouted
+    async fn routing(&self, from: &str, to: &str, amount: Decimal) -> RoutingDecision {
+        let rules = RoutingRules::new(self.pool.clone(), self.redis_service.clone())
+            .with_tenant(self.tenant())
+            .all()
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to load routing rules: {}", e);
+                Vec::new()
+            });
+        routing::evaluate(&rules, &RoutingScope::new(from, to, amount, self.country.as_deref(), &self.high_value))
+    }
+
+    /// Platform fee on a provider's receive amount, in the receive currency
+    async fn platform_fee(&self, from: &str, to: &str, provider: &str, receive_amount: Decimal) -> Decimal {
+        let rules = self.fee_rules().await;
+        let scope = FeeScope { from, to, provider, user_tier: self.fee_tier.as_deref() };
+        fees::platform_fee(&rules, &scope, receive_amount)
+    }
+
+    /// Take the platform fee out of each quote and re-rank by what the user
+    /// receives. Applied after the rates cache, which is shared across tiers.
+    async fn apply_platform_fees(&self, rates: &mut super::schema::RatesResponse) {
+        if rates.rates.is_empty() {
+            return;
+        }
+        let rules = self.fee_rules().await;
+        if rules.is_empty() {
+            return;
+        }
+        apply_fee_rules(rates, &rules, self.fee_tier.as_deref());
+    }
+
+    /// Round each quote to the places its currencies keep: what the user
+    /// receives and the maximum down, what they send, the minimum and the
+    /// fees up. The rate is recomputed from the rounded amounts.
+    async fn round_quotes(&self, rates: &mut super::schema::RatesResponse) {
+        if rates.rates.is_empty() {
+            return;
+        }
+        let (send, receive) = tokio::join!(
+            self.currency_decimals(&rates.from, &rates.network_from),
+            self.currency_decimals(&rates.to, &rates.network_to)
+        );
+
+        for rate in rates.rates.iter_mut() {
+            rate.estimated_amount = money::round_receive(rate.estimated_amount, receive);
+            rate.amount_from = rate.amount_from.map(|amount| money::round_send(amount, send));
+            rate.min_amount = money::round_send(rate.min_amount, send);
+            rate.max_amount = money::round_receive(rate.max_amount, send);
+            rate.provider_fee = money::round_send(rate.provider_fee, receive);
+            rate.platform_fee = money::round_send(rate.platform_fee, receive);
+            rate.total_fee = money::round_send(rate.total_fee, receive);
+            let amount_from = rate.amount_from.unwrap_or(rates.amount);
+            if amount_from > Decimal::ZERO {
+                rate.rate = money::rate(rate.estimated_amount, amount_from);
+            }
+        }
+    }
+
+    /// Providers an admin switched off, by name and slug
+    async fn disabled_providers(&self) -> Vec<String> {
+        if let Some(service) = &self.redis_service {
+            if let Ok(Some(cached)) = service.get_json::<Vec<String>>(DISABLED_PROVIDERS_KEY).await {
+                return cached;
+            }
+        }
+
+        let rows: Vec<(String, String)> =
+            match sqlx::query_as("SELECT name, slug FROM providers WHERE is_active = FALSE")
+                .fetch_all(&self.pool)
+                .await
+            {
+                Ok(rows) => rows,
+                Err(e) => {
+                    tracing::warn!("Failed to load disabled providers: {}", e);
+                    return Vec::new();
+                }
+            };
+        let disabled: Vec<String> = rows.into_iter().flat_map(|(name, slug)| [name, slug]).collect();
+
+        if let Some(service) = &self.redis_service {
+            let _ = service.set_json(DISABLED_PROVIDERS_KEY, &disabled, DISABLED_PROVIDERS_CACHE_SECS).await;
+        }
+        disabled
+    }
+
+    /// Whether quotes and trades may go to `provider`: the brand offers it
+    /// and no admin has disabled it
+    async fn provider_allowed(&self, provider: &str) -> bool {
+        self.brand.allows_provider(provider)
+            && !self.disabled_providers().await.iter().any(|d| d.eq_ignore_ascii_case(provider))
+    }
+
+    /// Value the amount sent and each quote's receive amount at current prices
+    async fn add_fiat_values(&self, rates: &mut super::schema::RatesResponse) {
+        let prices = self.price_feed.prices().await;
+        rates.amount_fiat = fiat_value(&prices, &rates.from, rates.amount);
+        for rate in &mut rates.rates {
+            rate.estimated_amount_fiat = fiat_value(&prices, &rates.to, rate.estimated_amount);
+        }
+    }
+
+    /// Break each quote's fees down, with the estimated network fee of its
+    /// payout. `network_fee` is that fee in the receive currency: as is when
+    /// paid in it, else converted at current prices, else left at 0.
+    async fn add_network_fees(&self, rates: &mut super::schema::RatesResponse) {
+        let network = self.fee_estimator.estimate(&rates.to, &rates.network_to).await;
+        let network_fee = match &network {
+            Some(fee) if fee.currency.eq_ignore_ascii_case(&rates.to) => fee.amount,
+            Some(fee) => {
+                let prices = self.price_feed.prices().await;
+                match (prices.get(&fee.currency), prices.get(&rates.to.to_lowercase())) {
+                    (Some(fee_price), Some(to_price)) if to_price.usd > 0.0 => {
+                        (fee.amount * money::from_f64(fee_price.usd / to_price.usd)).round_dp(8).normalize()
+                    }
+                    _ => Decimal::ZERO,
+                }
+            }
+            None => Decimal::ZERO,
+        };
+
+        for rate in &mut rates.rates {
+            rate.network_fee = network_fee;
+            rate.fee_breakdown = Some(super::schema::FeeBreakdown {
+                provider_fee: rate.provider_fee,
+                platform_fee: rate.platform_fee,
+                network: network.clone(),
+            });
+        }
+    }
 
-                // If cache age exceeds our probabilistic TTL, we sync
-                Ok(cache_age.num_seconds() as f64 >= effective_ttl)
+    /// History rows with their amounts valued at current prices
+    async fn with_fiat_values(&self, swaps: Vec<super::model::Swap>) -> Vec<super::schema::SwapSummary> {
+        let prices = self.price_feed.prices().await;
+        swaps
+            .into_iter()
+            .map(|swap| {
+                let amount_fiat = fiat_value(&prices, &swap.from_currency, swap.amount);
+                let estimated_receive_fiat = fiat_value(&prices, &swap.to_currency, swap.estimated_receive);
+                super::schema::SwapSummary {
+                    amount_fiat,
+                    estimated_receive_fiat,
+                    ..super::schema::SwapSummary::from(swap)
+                }
+            })
+            .collect()
+    }
+
+    /// Keep the quotes this caller may be served and the routing rules
+    /// allow, demote or hide failing providers, screen them against the
+    /// reference rate, then take the platform fee out and rank preferred
+    /// providers first
+    async fn serve_quotes(&self, rates: &mut super::schema::RatesResponse) {
+        let disabled = self.disabled_providers().await;
+        let routing = self.routing(&rates.from, &rates.to, rates.amount).await;
+        rates.rates.retain(|r| {
+            (r.aggregator == SANDBOX_AGGREGATOR || self.brand.allows_provider(&r.provider))
+                && !disabled.iter().any(|d| d.eq_ignore_ascii_case(&r.provider))
+                && routing.allows(&r.provider)
+                && self.max_kyc_rating.is_none_or(|max| kyc_rating_allows(r.kyc_rating.as_deref(), max))
+        });
+        self.apply_provider_health(rates).await;
+        self.rate_guard.screen_quotes(rates);
+        self.apply_platform_fees(rates).await;
+        self.round_quotes(rates).await;
+
+        if !routing.preferred.is_empty() {
+            for rate in rates.rates.iter_mut() {
+                rate.preferred = routing.prefers(&rate.provider);
+            }
+            sort_quotes(&mut rates.rates);
+        }
+    }
+
+    /// Drop every cached provider listing so the next read goes to the database
+    pub async fn invalidate_provider_cache(&self) {
+        if let Some(service) = &self.redis_service {
+            for key in [
+                "providers:all",
+                "providers:response:all",
+                "providers:response:all:etag",
+                DISABLED_PROVIDERS_KEY,
+                PROVIDER_HEALTH_KEY,
+            ] {
+                let _ = service.delete(key).await;
             }
-            _ => Ok(true), // No sync found, need to sync
         }
     }
 
+    // =========================================================================
+    // CURRENCIES
+    // =========================================================================
+
     /// Sync currencies from Trocador API and upsert into database
     pub async fn sync_currencies_from_trocador(
         &self,
         trocador_client: &TrocadorClient,
-    ) -> Result<usize, SwapError> {
-        let start_time = std::time::Instant::now();
-
+    ) -> Result<SyncStats, SwapError> {
         // Fetch from Trocador API
         let trocador_currencies = trocador_client.get_currencies().await?;
-        let total_count = trocador_currencies.len();
+        let mut stats = SyncStats { fetched: trocador_currencies.len(), changed: 0 };
+
+        // One transaction, so readers never see a half-applied sync
+        let mut tx = self.pool.begin().await?;
+        let (sync_started,): (DateTime<Utc>,) = sqlx::query_as("SELECT NOW()")
+            .fetch_one(&mut *tx)
+            .await?;
 
         // Process in chunks of 500 to avoid hitting packet size limits
         for chunk in trocador_currencies.chunks(500) {
-            self.upsert_currencies_batch(chunk).await?;
+            stats.changed += self.upsert_currencies_batch(&mut tx, chunk).await?;
         }
 
-        let duration = start_time.elapsed().as_secs_f64();
-        
-        // Store the sync duration (Delta) for PER and invalidate response cache
-        if let Some(service) = &self.redis_service {
-            let _ = service.set_string("currencies:sync_duration", &duration.to_string(), 3600).await;
-            let _ = service.set_string("currencies:response:all", "", 0).await;
+        // Everything Trocador still lists was just stamped; the rest is gone.
+        // An empty listing is treated as a bad response, not a mass delisting.
+        if trocador_currencies.is_empty() {
+            tracing::warn!("Trocador listed no currencies; keeping existing currencies active");
+        } else {
+            let deactivated = sqlx::query(
+                "UPDATE currencies SET is_active = FALSE
+                 WHERE is_active = TRUE AND (last_synced_at IS NULL OR last_synced_at < ?)",
+            )
+            .bind(sync_started)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+            if deactivated > 0 {
+                tracing::info!("Marked {} currencies no longer listed by Trocador inactive", deactivated);
+            }
+            stats.changed += deactivated;
+
+            // Their pairs go with them; a relisted coin's pairs come back once quoted again
+            let removed_pairs = sqlx::query(
+                "DELETE p FROM pairs p
+                 JOIN currencies c
+                   ON (LOWER(c.symbol) = p.from_currency AND c.network = p.from_network)
+                   OR (LOWER(c.symbol) = p.to_currency AND c.network = p.to_network)
+                 WHERE c.last_synced_at IS NULL OR c.last_synced_at < ?",
+            )
+            .bind(sync_started)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+            if removed_pairs > 0 {
+                tracing::info!("Removed {} pairs of currencies no longer listed by Trocador", removed_pairs);
+            }
+        }
+
+        tx.commit().await?;
+
+        self.invalidate_currency_cache().await;
+        // Rebuild the full listing and its ETag now rather than on the next read
+        if let Err(e) = self.get_currencies_optimized(CurrenciesQuery::default()).await {
+            tracing::warn!("Failed to rebuild the currency listing after sync: {}", e);
         }
 
-        Ok(total_count)
+        Ok(stats)
+    }
+
+    /// Drop every cached currency listing so the next read goes to the database
+    pub async fn invalidate_currency_cache(&self) {
+        if let Some(service) = &self.redis_service {
+            for key in [
+                "currencies:all",
+                "currencies:response:all",
+                "currencies:response:all:etag",
+                "currencies:response:grouped",
+            ] {
+                let _ = service.delete(key).await;
+            }
+            // Pairs show whether both of their currencies are active
+            let _ = service.delete_prefix(PAIRS_CACHE_PREFIX).await;
+        }
     }
 
-    /// Upsert a batch of currencies
+    /// Upsert a batch of currencies, returning the number of rows MySQL reports as affected.
+    /// Listed currencies are (re)activated unless an admin disabled them.
     async fn upsert_currencies_batch(
         &self,
+        tx: &mut sqlx::Transaction<'_, MySql>,
         currencies: &[TrocadorCurrency],
-    ) -> Result<(), SwapError> {
+    ) -> Result<u64, SwapError> {
         if currencies.is_empty() {
-            return Ok(());
+            return Ok(0);
         }
 
         let mut query_builder = sqlx::QueryBuilder::new(
@@ -182,6 +975,7 @@ impl SwapCrud {
         query_builder.push(
             " ON DUPLICATE KEY UPDATE
                 name = VALUES(name),
+                is_active = NOT admin_disabled,
                 logo_url = VALUES(logo_url),
                 min_amount = VALUES(min_amount),
                 max_amount = VALUES(max_amount),
@@ -189,9 +983,9 @@ impl SwapCrud {
         );
 
         let query = query_builder.build();
-        query.execute(&self.pool).await.map_err(|e| SwapError::DatabaseError(e.to_string()))?;
+        let result = query.execute(&mut **tx).await?;
 
-        Ok(())
+        Ok(result.rows_affected())
     }
 
     /// Get currencies with optimized caching and raw response support
@@ -199,6 +993,10 @@ impl SwapCrud {
         &self,
         query: CurrenciesQuery,
     ) -> Result<CurrenciesResult, SwapError> {
+        if query.wants_page() {
+            return Ok(CurrenciesResult::Page(self.get_currencies_page(&query).await?));
+        }
+
         let is_standard_query = query.ticker.is_none() && query.network.is_none() && query.memo.is_none();
         // Separate cache key for the PRE-SERIALIZED response
         let cache_key = "currencies:response:all";
@@ -206,9 +1004,8 @@ impl SwapCrud {
         // 1. FAST PATH: Try to get Raw JSON from Redis (Zero Serialization)
         if is_standard_query && query.page.is_none() && query.limit.is_none() {
             if let Some(service) = &self.redis_service {
-                if let Ok(Some(raw_json)) = service.get_string(cache_key).await {
-                    self.trigger_background_sync_if_needed().await;
-                    return Ok(CurrenciesResult::RawJson(raw_json));
+                if let Some((json, etag)) = cached_raw_json(service, cache_key).await {
+                    return Ok(CurrenciesResult::RawJson { json, etag });
                 }
             }
         }
@@ -219,11 +1016,9 @@ impl SwapCrud {
         if is_standard_query {
             if let Some(service) = &self.redis_service {
                 if let Ok(Some(cached_models)) = service.get_json::<Vec<Currency>>(model_cache_key).await {
-                    self.trigger_background_sync_if_needed().await;
-
                     // Handle Pagination in Memory
                     let sliced_models = if let (Some(page), Some(limit)) = (query.page, query.limit) {
-                        let start = (page - 1) * limit;
+                        let start = page_offset(page, limit);
                         if start >= cached_models.len() {
                             Vec::new()
                         } else {
@@ -247,224 +1042,182 @@ impl SwapCrud {
         // 4. Cache Population (Self-Healing)
         // If this was a full standard query, we cache BOTH the model list and the serialized response
         if is_standard_query && query.page.is_none() && query.limit.is_none() && !currencies.is_empty() {
+            // We serialize the DTOs here once, so we don't have to do it on every read
+            let json = serde_json::to_string(&responses).map_err(|e| SwapError::Internal(e.to_string()))?;
+            let etag = content_etag(&json);
             if let Some(service) = &self.redis_service {
                 // Cache Model List (for pagination reuse)
                 let _ = service.set_json(model_cache_key, &currencies, 300).await;
-                
-                // Cache Raw Response (for fast full-list access)
-                // We serialize the DTOs here once, so we don't have to do it on every read
-                if let Ok(json_string) = serde_json::to_string(&responses) {
-                    let _ = service.set_string(cache_key, &json_string, 300).await;
-                }
+
+                // Cache Raw Response and its ETag (for fast full-list access)
+                store_raw_json(service, cache_key, &json, &etag, 300).await;
             }
+            return Ok(CurrenciesResult::RawJson { json, etag });
         }
 
-        self.trigger_background_sync_if_needed().await;
-
         Ok(CurrenciesResult::Structured(responses))
     }
 
-    /// Internal helper to fetch from DB with filters
-    async fn fetch_currencies_from_db(&self, query: &CurrenciesQuery) -> Result<Vec<Currency>, SwapError> {
-        let mut sql = String::from(
-            "SELECT id, symbol, name, network, is_active, logo_url, contract_address, 
-             decimals, requires_extra_id, extra_id_name, min_amount, max_amount, 
-             last_synced_at, created_at, updated_at 
-             FROM currencies 
-             WHERE is_active = TRUE"
-        );
-
-        let mut sql_parts = Vec::new();
-
-        if let Some(ref ticker) = query.ticker {
-            sql_parts.push(format!("LOWER(symbol) = LOWER('{}')", ticker.replace("'", "''")));
-        }
+    /// One page of currencies, searched, sorted and sliced in the database,
+    /// with the total across pages. Unknown sort keys keep the default order
+    /// (ticker, then network); unknown fields are left out.
+    async fn get_currencies_page(&self, query: &CurrenciesQuery) -> Result<super::schema::CurrenciesPage, SwapError> {
+        let page = query.page.unwrap_or(1).max(1);
+        let per_page = query.per_page.unwrap_or(DEFAULT_CURRENCIES_PER_PAGE).clamp(1, MAX_CURRENCIES_PER_PAGE);
 
-        if let Some(ref network) = query.network {
-            sql_parts.push(format!("network = '{}'", network.replace("'", "''")));
-        }
+        let mut count = sqlx::QueryBuilder::<MySql>::new("SELECT COUNT(*) FROM currencies WHERE is_active = TRUE");
+        push_currency_filters(&mut count, query);
+        let total: i64 = count.build_query_scalar().fetch_one(&self.pool).await?;
 
-        if let Some(memo) = query.memo {
-            sql_parts.push(format!("requires_extra_id = {}", memo));
-        }
+        let mut builder = sqlx::QueryBuilder::<MySql>::new(CURRENCY_COLUMNS);
+        push_currency_filters(&mut builder, query);
+        builder
+            .push(currency_order(query.sort.as_deref()))
+            .push(" LIMIT ")
+            .push_bind(per_page as u64)
+            .push(" OFFSET ")
+            .push_bind(page_offset(page, per_page) as u64);
+        let currencies = builder.build_query_as::<Currency>().fetch_all(&self.pool).await?;
 
-        if !sql_parts.is_empty() {
-            sql.push_str(" AND ");
-            sql.push_str(&sql_parts.join(" AND "));
-        }
+        let fields: Vec<&str> = query
+            .fields
+            .as_deref()
+            .map(|fields| fields.split(',').map(str::trim).filter(|f| !f.is_empty()).collect())
+            .unwrap_or_default();
+        let items = currencies
+            .into_iter()
+            .map(|currency| project_fields(&CurrencyResponse::from(currency), &fields))
+            .collect();
 
-        sql.push_str(" ORDER BY symbol, network");
+        let total = total.max(0) as u64;
+        Ok(super::schema::CurrenciesPage {
+            page,
+            per_page,
+            total,
+            total_pages: total.div_ceil(per_page as u64),
+            items,
+        })
+    }
 
-        if let (Some(page), Some(limit)) = (query.page, query.limit) {
-            let offset = (page - 1) * limit;
-            sql.push_str(&format!(" LIMIT {} OFFSET {}", limit, offset));
-        }
+    /// Get currencies grouped by asset, with each asset's networks nested
+    pub async fn get_currencies_grouped(
+        &self,
+        query: CurrenciesQuery,
+    ) -> Result<GroupedCurrenciesResult, SwapError> {
+        let is_standard_query =
+            query.ticker.is_none() && query.network.is_none() && query.memo.is_none() && query.search.is_none();
+        let cache_key = "currencies:response:grouped";
 
-        sqlx::query_as::<_, Currency>(&sql)
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| SwapError::DatabaseError(e.to_string()))
-    }
-
-    /// Triggers the background sync process if the cache is stale
-    async fn trigger_background_sync_if_needed(&self) {
-        if let Ok(true) = self.should_sync_currencies().await {
-            if let Some(redis) = &self.redis_service {
-                let redis = redis.clone();
-                let pool = self.pool.clone();
-                
-                tokio::spawn(async move {
-                    if let Ok(true) = redis.try_lock("lock:sync_currencies", 60).await {
-                        tracing::info!("Acquired sync lock, starting background update...");
-                        let api_key = std::env::var("TROCADOR_API_KEY").unwrap_or_default();
-                        if !api_key.is_empty() {
-                            let client = TrocadorClient::new(api_key);
-                            let bg_crud = SwapCrud::new(pool, Some(redis.clone()));
-                            
-                            match bg_crud.sync_currencies_from_trocador(&client).await {
-                                Ok(count) => {
-                                    tracing::info!("Background sync complete. Updated {} currencies.", count);
-                                    // Invalidate/Update cache immediately after sync
-                                    // This requires fetching fresh data and setting it
-                                    // For simplicity, we just let the next read repopulate or TTL expire
-                                    // But optimally, we would:
-                                    // let fresh = bg_crud.fetch_currencies_from_db(&Default::default()).await...;
-                                    // redis.set_json("currencies:all", &fresh, 300).await...;
-                                },
-                                Err(e) => tracing::error!("Background sync failed: {}", e),
-                            }
-                        }
-                    }
-                });
+        // 1. FAST PATH: pre-serialized grouped response (the full, unpaginated list)
+        if is_standard_query && query.page.is_none() && query.limit.is_none() {
+            if let Some(service) = &self.redis_service {
+                if let Ok(Some(raw_json)) = service.get_string(cache_key).await {
+                    return Ok(GroupedCurrenciesResult::RawJson(raw_json));
+                }
             }
         }
-    }
 
-    /// Get currencies from database with optional filtering
-    pub async fn get_currencies(
-        &self,
-        query: CurrenciesQuery,
-    ) -> Result<Vec<Currency>, SwapError> {
-        // Redirect to new optimized method
-        // Warning: This is a compatibility shim. The controller should now handle CurrenciesResult.
-        // If this is called, we force unpack Structured result.
-        match self.get_currencies_optimized(query).await? {
-            CurrenciesResult::Structured(_res) => {
-                // We need to map back to Currency model if this function signature is strict, 
-                // but the current get_currencies returns Vec<Currency>.
-                // Wait, get_currencies_optimized returns Vec<CurrencyResponse> inside Structured variant.
-                // The original get_currencies returned Vec<Currency>.
-                // This means I broke the signature compatibility for this shim.
-                // Let's just fix the controller to use get_currencies_optimized directly.
-                // For this shim, I will return an error or basic implementation if needed, 
-                // but better to rely on fetch_currencies_from_db for raw access.
-                
-                // Ideally this method should be deprecated or removed if controller is updated.
-                // For now, let's just return a database fetch to be safe.
-                Err(SwapError::DatabaseError("Use get_currencies_optimized instead".to_string()))
+        // 2. SLOW PATH: group the flat rows from the database.
+        // Pagination applies to assets, not rows, so it is handled after grouping.
+        let db_query = CurrenciesQuery { page: None, limit: None, ..query.clone() };
+        let currencies = self.fetch_currencies_from_db(&db_query).await?;
+        let mut grouped = Self::group_currencies(currencies);
+
+        if is_standard_query && !grouped.is_empty() {
+            if let Some(service) = &self.redis_service {
+                if let Ok(json_string) = serde_json::to_string(&grouped) {
+                    let _ = service.set_string(cache_key, &json_string, 300).await;
+                }
             }
-            CurrenciesResult::RawJson(_) => Err(SwapError::DatabaseError("Raw JSON not supported in legacy method".to_string())),
         }
+
+        if let (Some(page), Some(limit)) = (query.page, query.limit) {
+            let start = page.saturating_sub(1) * limit;
+            grouped = grouped.into_iter().skip(start).take(limit).collect();
+        }
+
+        Ok(GroupedCurrenciesResult::Structured(grouped))
     }
 
-    // =========================================================================
-    // PROVIDERS
-    // =========================================================================
+    /// Collapse flat (symbol, network) rows into one entry per ticker.
+    /// Expects rows ordered by symbol, as returned by `fetch_currencies_from_db`.
+    fn group_currencies(currencies: Vec<Currency>) -> Vec<GroupedCurrencyResponse> {
+        let mut grouped: Vec<GroupedCurrencyResponse> = Vec::new();
 
-    /// Check if providers cache needs refresh (>5 minutes old)
-    pub async fn should_sync_providers(&self) -> Result<bool, SwapError> {
-        let result: Option<(Option<chrono::DateTime<Utc>>,)> = sqlx::query_as(
-            "SELECT MAX(last_synced_at) FROM providers"
-        )
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| SwapError::DatabaseError(e.to_string()))?;
+        for currency in currencies {
+            let ticker = currency.symbol.to_lowercase();
 
-        match result {
-            Some((Some(last_sync),)) => {
-                let cache_age = Utc::now() - last_sync;
-                Ok(cache_age.num_minutes() > 5)
+            match grouped.last_mut() {
+                Some(last) if last.ticker == ticker => {
+                    if last.image.is_empty() {
+                        last.image = currency.logo_url.clone().unwrap_or_default();
+                    }
+                    last.networks.push(currency.into());
+                }
+                _ => grouped.push(GroupedCurrencyResponse {
+                    name: currency.name.clone(),
+                    ticker,
+                    image: currency.logo_url.clone().unwrap_or_default(),
+                    networks: vec![currency.into()],
+                }),
             }
-            _ => Ok(true),
         }
+
+        grouped
     }
 
-    /// Check if providers cache needs refresh using Probabilistic Early Recomputation (PER)
-    pub async fn should_sync_providers_per(&self) -> Result<bool, SwapError> {
-        let stats_key = "providers:sync_stats";
-        
-        let stats = if let Some(service) = &self.redis_service {
-            service.get_json::<serde_json::Value>(stats_key).await.unwrap_or(None)
-        } else {
-            None
-        };
+    /// Internal helper to fetch from DB with filters
+    async fn fetch_currencies_from_db(&self, query: &CurrenciesQuery) -> Result<Vec<Currency>, SwapError> {
+        let mut builder = sqlx::QueryBuilder::<MySql>::new(CURRENCY_COLUMNS);
+        push_currency_filters(&mut builder, query);
 
-        if let Some(stats) = stats {
-            let last_sync = stats["last_sync"].as_i64().unwrap_or(0);
-            let duration = stats["duration"].as_f64().unwrap_or(2.0);
-            
-            let now = Utc::now().timestamp();
-            let age = (now - last_sync) as f64;
-            let ttl = 3600.0; // 1 hour hard TTL
-            let beta = 1.0;
-            
-            // PER Formula: Refresh if time_remaining <= delta * beta * -ln(rand)
-            let time_remaining = ttl - age;
-            let rand: f64 = rand::random();
-            // Avoid log(0)
-            let safe_rand = if rand < 0.0001 { 0.0001 } else { rand };
-            let x_fetch = duration * beta * (-safe_rand.ln());
-            
-            return Ok(time_remaining <= x_fetch);
+        builder.push(" ORDER BY symbol, network");
+
+        if let (Some(page), Some(limit)) = (query.page, query.limit) {
+            builder
+                .push(" LIMIT ")
+                .push_bind(limit as u64)
+                .push(" OFFSET ")
+                .push_bind(page_offset(page, limit) as u64);
         }
-        
-        Ok(true) // No stats, sync needed
+
+        builder
+            .build_query_as::<Currency>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(SwapError::Database)
     }
 
-    /// Triggers the background sync process if the cache is stale (PER)
-    async fn trigger_background_provider_sync(&self) {
-        if let Ok(true) = self.should_sync_providers_per().await {
-            if let Some(redis) = &self.redis_service {
-                let redis = redis.clone();
-                let pool = self.pool.clone();
-                
-                tokio::spawn(async move {
-                    if let Ok(true) = redis.try_lock("lock:sync_providers", 60).await {
-                        tracing::info!("Acquired sync lock, starting background provider update...");
-                        let api_key = std::env::var("TROCADOR_API_KEY").unwrap_or_default();
-                        if !api_key.is_empty() {
-                            let client = TrocadorClient::new(api_key);
-                            let bg_crud = SwapCrud::new(pool, Some(redis.clone()));
-                            
-                            match bg_crud.sync_providers_from_trocador(&client).await {
-                                Ok(count) => {
-                                    tracing::info!("Background sync complete. Updated {} providers.", count);
-                                    // Cache refresh happens inside sync_providers_from_trocador
-                                },
-                                Err(e) => tracing::error!("Background sync failed: {}", e),
-                            }
-                        }
-                    }
-                });
-            }
-        }
+    /// Get currencies from database with optional filtering
+    pub async fn get_currencies(
+        &self,
+        query: CurrenciesQuery,
+    ) -> Result<Vec<Currency>, SwapError> {
+        self.fetch_currencies_from_db(&query).await
     }
 
+    // =========================================================================
+    // PROVIDERS
+    // =========================================================================
+
     /// Get providers with optimized caching and raw response support
     pub async fn get_providers_optimized(
         &self,
         query: ProvidersQuery,
     ) -> Result<ProvidersResult, SwapError> {
-        let is_standard_query = query.rating.is_none() && query.markup_enabled.is_none() && query.sort.is_none();
+        // The shared raw response lists every provider, so restricted brands filter instead
+        let is_standard_query = query.rating.is_none()
+            && query.markup_enabled.is_none()
+            && query.sort.is_none()
+            && self.brand.enabled_providers.is_empty();
         let cache_key = "providers:response:all";
         let model_cache_key = "providers:all";
 
         // 1. FAST PATH: Raw JSON (Zero Serialization)
         if is_standard_query {
             if let Some(service) = &self.redis_service {
-                if let Ok(Some(raw_json)) = service.get_string(cache_key).await {
-                    self.trigger_background_provider_sync().await;
-                    return Ok(ProvidersResult::RawJson(raw_json));
+                if let Some((json, etag)) = cached_raw_json(service, cache_key).await {
+                    return Ok(ProvidersResult::RawJson { json, etag });
                 }
             }
         }
@@ -472,7 +1225,6 @@ impl SwapCrud {
         // 2. MEDIUM PATH: Structured Cache (In-Memory Filtering)
         if let Some(service) = &self.redis_service {
             if let Ok(Some(cached_models)) = service.get_json::<Vec<Provider>>(model_cache_key).await {
-                self.trigger_background_provider_sync().await;
                 
                 // Filter in memory
                 let filtered = self.filter_providers_in_memory(cached_models, &query);
@@ -490,19 +1242,21 @@ impl SwapCrud {
         
         // Populate Cache
         let all_responses: Vec<ProviderResponse> = all_providers.clone().into_iter().map(|p| p.into()).collect();
-        
+        let json = serde_json::to_string(&all_responses).map_err(|e| SwapError::Internal(e.to_string()))?;
+        let etag = content_etag(&json);
+
         if let Some(service) = &self.redis_service {
             let _ = service.set_json(model_cache_key, &all_providers, 3600).await;
-            if let Ok(json_string) = serde_json::to_string(&all_responses) {
-                let _ = service.set_string(cache_key, &json_string, 3600).await;
-            }
+            store_raw_json(service, cache_key, &json, &etag, 3600).await;
+        }
+
+        if is_standard_query {
+            return Ok(ProvidersResult::RawJson { json, etag });
         }
         
         // Return filtered result
         let filtered = self.filter_providers_in_memory(all_providers, &query);
         let responses: Vec<ProviderResponse> = filtered.into_iter().map(|p| p.into()).collect();
-        
-        self.trigger_background_provider_sync().await;
 
         Ok(ProvidersResult::Structured(responses))
     }
@@ -510,7 +1264,9 @@ impl SwapCrud {
     /// Helper to filter providers in memory
     fn filter_providers_in_memory(&self, providers: Vec<Provider>, query: &ProvidersQuery) -> Vec<Provider> {
         let mut filtered = providers;
-        
+
+        filtered.retain(|p| self.brand.allows_provider(&p.name) || self.brand.allows_provider(&p.slug));
+
         if let Some(ref rating) = query.rating {
             filtered.retain(|p| p.kyc_rating == *rating);
         }
@@ -523,7 +1279,7 @@ impl SwapCrud {
         match query.sort.as_deref() {
             Some("name") => filtered.sort_by(|a, b| a.name.cmp(&b.name)),
             Some("rating") => filtered.sort_by(|a, b| a.kyc_rating.cmp(&b.kyc_rating).then(a.name.cmp(&b.name))),
-            Some("eta") => filtered.sort_by(|a, b| a.eta_minutes.unwrap_or(0).cmp(&b.eta_minutes.unwrap_or(0))),
+            Some("eta") => filtered.sort_by_key(|p| p.eta_minutes.unwrap_or(0)),
             _ => filtered.sort_by(|a, b| a.name.cmp(&b.name)),
         }
         
@@ -534,108 +1290,158 @@ impl SwapCrud {
     pub async fn sync_providers_from_trocador(
         &self,
         trocador_client: &TrocadorClient,
-    ) -> Result<usize, SwapError> {
-        let start_time = std::time::Instant::now();
-
+    ) -> Result<SyncStats, SwapError> {
         let trocador_providers = trocador_client.get_providers().await?;
 
-        let mut synced_count = 0;
+        let mut stats = SyncStats { fetched: trocador_providers.len(), changed: 0 };
 
-        for trocador_provider in trocador_providers {
-            self.upsert_provider_from_trocador(&trocador_provider).await?;
-            synced_count += 1;
+        let mut tx = self.pool.begin().await?;
+        for chunk in trocador_providers.chunks(500) {
+            stats.changed += self.upsert_providers_batch(&mut tx, chunk).await?;
         }
+        tx.commit().await?;
 
-        let duration = start_time.elapsed().as_secs_f64();
-        
-        // Store the sync duration (Delta) for PER and invalidate response cache
-        if let Some(service) = &self.redis_service {
-            let stats = serde_json::json!({
-                "last_sync": Utc::now().timestamp(),
-                "duration": duration
-            });
-            let _ = service.set_json("providers:sync_stats", &stats, 3600 * 24).await;
-            let _ = service.set_string("providers:response:all", "", 0).await;
+        self.invalidate_provider_cache().await;
+        // Rebuild the full listing and its ETag now rather than on the next read
+        if let Err(e) = self.get_providers_optimized(ProvidersQuery { rating: None, markup_enabled: None, sort: None }).await {
+            tracing::warn!("Failed to rebuild the provider listing after sync: {}", e);
+        }
+
+        Ok(stats)
+    }
+
+    /// Upsert a batch of providers, returning the number of rows MySQL reports as affected.
+    /// Rows are keyed by slug (the lowercased, dash-joined name), which is also the id.
+    /// is_active and admin overrides of kyc_rating/eta_minutes survive the sync.
+    async fn upsert_providers_batch(
+        &self,
+        tx: &mut sqlx::Transaction<'_, MySql>,
+        providers: &[TrocadorProvider],
+    ) -> Result<u64, SwapError> {
+        if providers.is_empty() {
+            return Ok(0);
         }
 
-        Ok(synced_count)
+        let mut query_builder = sqlx::QueryBuilder::<MySql>::new(
+            "INSERT INTO providers (
+                id, name, slug, is_active, kyc_rating,
+                insurance_percentage, eta_minutes, markup_enabled, last_synced_at
+            ) "
+        );
+
+        query_builder.push_values(providers, |mut b, provider| {
+            let slug = provider.name.to_lowercase().replace(' ', "-");
+            b.push_bind(slug.clone()) // id
+             .push_bind(&provider.name)
+             .push_bind(slug)
+             .push("TRUE") // is_active
+             .push_bind(&provider.rating)
+             .push_bind(provider.insurance)
+             .push_bind(provider.eta as i32) // Convert f64 to i32
+             .push_bind(provider.enabled_markup)
+             .push("NOW()");
+        });
+
+        query_builder.push(
+            " ON DUPLICATE KEY UPDATE
+                name = VALUES(name),
+                kyc_rating = COALESCE(kyc_rating_override, VALUES(kyc_rating)),
+                insurance_percentage = VALUES(insurance_percentage),
+                eta_minutes = COALESCE(eta_minutes_override, VALUES(eta_minutes)),
+                markup_enabled = VALUES(markup_enabled),
+                last_synced_at = VALUES(last_synced_at)"
+        );
+
+        let result = query_builder
+            .build()
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(result.rows_affected())
     }
 
-    /// Upsert a single provider from Trocador data
-    async fn upsert_provider_from_trocador(
+    // =========================================================================
+    // SYNC RUN HISTORY
+    // =========================================================================
+
+    /// Persist the outcome of a sync run
+    pub async fn record_sync_run(
         &self,
-        trocador_provider: &TrocadorProvider,
+        kind: SyncKind,
+        status: SyncRunStatus,
+        stats: SyncStats,
+        started_at: chrono::DateTime<Utc>,
+        error: Option<String>,
     ) -> Result<(), SwapError> {
-        // Generate slug from name
-        let slug = trocador_provider.name.to_lowercase().replace(" ", "-");
-        let id = slug.clone();
+        let duration_ms = (Utc::now() - started_at).num_milliseconds();
 
-        // First, try to find existing provider by name (case-insensitive)
-        let existing: Option<(String,)> = sqlx::query_as(
-            "SELECT id FROM providers WHERE LOWER(name) = LOWER(?) LIMIT 1"
+        sqlx::query(
+            r#"
+            INSERT INTO sync_runs (kind, status, rows_fetched, rows_changed, duration_ms, error, started_at, finished_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, NOW())
+            "#
         )
-        .bind(&trocador_provider.name)
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| SwapError::DatabaseError(e.to_string()))?;
+        .bind(kind)
+        .bind(status)
+        .bind(stats.fetched as i32)
+        .bind(stats.changed as i32)
+        .bind(duration_ms)
+        .bind(&error)
+        .bind(started_at)
+        .execute(&self.pool)
+        .await?;
 
-        if let Some((existing_id,)) = existing {
-            // Update existing provider
-            sqlx::query(
-                r#"
-                UPDATE providers SET
-                    name = ?,
-                    slug = ?,
-                    kyc_rating = ?,
-                    insurance_percentage = ?,
-                    eta_minutes = ?,
-                    markup_enabled = ?,
-                    last_synced_at = NOW()
-                WHERE id = ?
-                "#
-            )
-            .bind(&trocador_provider.name)
-            .bind(&slug)
-            .bind(&trocador_provider.rating)
-            .bind(trocador_provider.insurance)
-            .bind(trocador_provider.eta as i32)  // Convert f64 to i32
-            .bind(trocador_provider.enabled_markup)
-            .bind(&existing_id)
-            .execute(&self.pool)
-            .await
-            .map_err(|e| SwapError::DatabaseError(e.to_string()))?;
-        } else {
-            // Insert new provider
-            sqlx::query(
-                r#"
-                INSERT INTO providers (
-                    id, name, slug, is_active, kyc_rating,
-                    insurance_percentage, eta_minutes, markup_enabled, last_synced_at
+        let kind_name = serde_json::to_value(kind).unwrap_or_default();
+        let data = serde_json::json!({
+            "kind": kind_name,
+            "status": status,
+            "rows_fetched": stats.fetched,
+            "rows_changed": stats.changed,
+            "duration_ms": duration_ms,
+            "error": error,
+        });
+        let aggregate_id = kind_name.as_str().unwrap_or_default().to_string();
+
+        if status != SyncRunStatus::Success {
+            self.outbox
+                .record(
+                    DomainEventType::AlertRaised,
+                    &aggregate_id,
+                    serde_json::json!({ "alert": "sync_failed", "severity": "warning", "sync": data }),
                 )
-                VALUES (?, ?, ?, TRUE, ?, ?, ?, ?, NOW())
-                "#
-            )
-            .bind(&id)
-            .bind(&trocador_provider.name)
-            .bind(&slug)
-            .bind(&trocador_provider.rating)
-            .bind(trocador_provider.insurance)
-            .bind(trocador_provider.eta as i32)  // Convert f64 to i32
-            .bind(trocador_provider.enabled_markup)
-            .execute(&self.pool)
-            .await
-            .map_err(|e| SwapError::DatabaseError(e.to_string()))?;
+                .await;
         }
+        self.outbox.record(DomainEventType::SyncCompleted, &aggregate_id, data).await;
 
         Ok(())
     }
 
+    /// Most recent run of each sync kind
+    pub async fn get_sync_status(&self) -> Result<SyncStatusResponse, SwapError> {
+        Ok(SyncStatusResponse {
+            currencies: self.latest_sync_run(SyncKind::Currencies).await?,
+            providers: self.latest_sync_run(SyncKind::Providers).await?,
+            pairs: self.latest_sync_run(SyncKind::Pairs).await?,
+        })
+    }
+
+    async fn latest_sync_run(&self, kind: SyncKind) -> Result<Option<SyncRun>, SwapError> {
+        sqlx::query_as::<_, SyncRun>(
+            "SELECT id, kind, status, rows_fetched, rows_changed, duration_ms, error, started_at, finished_at
+             FROM sync_runs WHERE kind = ? ORDER BY started_at DESC, id DESC LIMIT 1"
+        )
+        .bind(kind)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(SwapError::Database)
+    }
+
     /// Get providers from database with optional filtering
     pub async fn get_providers(
         &self,
         query: ProvidersQuery,
     ) -> Result<Vec<Provider>, SwapError> {
-        let mut sql = String::from(
+        let mut builder = sqlx::QueryBuilder::<MySql>::new(
             "SELECT id, name, slug, is_active, kyc_rating, insurance_percentage,
              eta_minutes, markup_enabled, api_url, logo_url, website_url,
              last_synced_at, created_at, updated_at
@@ -643,33 +1449,25 @@ impl SwapCrud {
              WHERE is_active = TRUE"
         );
 
-        let mut sql_parts = Vec::new();
-
         if let Some(ref rating) = query.rating {
-            sql_parts.push(format!("kyc_rating = '{}'", rating.replace("'", "''")));
+            builder.push(" AND kyc_rating = ").push_bind(rating);
         }
 
         if let Some(markup_enabled) = query.markup_enabled {
-            sql_parts.push(format!("markup_enabled = {}", markup_enabled));
+            builder.push(" AND markup_enabled = ").push_bind(markup_enabled);
         }
 
-        if !sql_parts.is_empty() {
-            sql.push_str(" AND ");
-            sql.push_str(&sql_parts.join(" AND "));
-        }
-
-        // Apply sorting
-        match query.sort.as_deref() {
-            Some("name") => sql.push_str(" ORDER BY name ASC"),
-            Some("rating") => sql.push_str(" ORDER BY kyc_rating ASC, name ASC"),
-            Some("eta") => sql.push_str(" ORDER BY eta_minutes ASC"),
-            _ => sql.push_str(" ORDER BY name ASC"), // Default
-        }
+        // Sort keys map onto fixed clauses; the value itself never reaches the SQL
+        builder.push(match query.sort.as_deref() {
+            Some("rating") => " ORDER BY kyc_rating ASC, name ASC",
+            Some("eta") => " ORDER BY eta_minutes ASC",
+            _ => " ORDER BY name ASC", // Default, and "name"
+        });
 
-        let providers = sqlx::query_as::<_, Provider>(&sql)
+        let providers = builder
+            .build_query_as::<Provider>()
             .fetch_all(&self.pool)
-            .await
-            .map_err(|e| SwapError::DatabaseError(e.to_string()))?;
+            .await?;
 
         Ok(providers)
     }
@@ -684,51 +1482,403 @@ impl SwapCrud {
         &self,
         query: &super::schema::RatesQuery,
     ) -> Result<super::schema::RatesResponse, SwapError> {
+        if !self.brand.allows_pair(&query.from, &query.to) {
+            return Err(SwapError::PairNotAllowed { from: query.from.clone(), to: query.to.clone() });
+        }
+        let max_kyc_rating = query.max_kyc_rating.as_deref().map(parse_kyc_rating).transpose()?;
+        self.check_amount_decimals("amount", &query.from, &query.network_from, query.amount).await?;
+        if let Some(amount_to) = query.amount_to {
+            self.check_amount_decimals("amount_to", &query.to, &query.network_to, amount_to).await?;
+        }
+
+        // Quotes by receive amount are fixed-rate; otherwise a routing rule
+        // may pick the rate type the caller left open
+        let routed;
+        let query = match query.amount_to {
+            Some(amount_to) => {
+                check_amount_to(query.amount, amount_to)?;
+                routed = super::schema::RatesQuery { rate_type: Some(super::schema::RateType::Fixed), ..query.clone() };
+                &routed
+            }
+            None => match self.routing(&query.from, &query.to, query.amount).await.rate_type {
+                Some(rate_type) if query.rate_type.is_none() => {
+                    routed = super::schema::RatesQuery { rate_type: Some(rate_type), ..query.clone() };
+                    &routed
+                }
+                _ => query,
+            },
+        };
+
+        let mut rates = self.get_rates_cached(query).await?;
+        self.serve_quotes(&mut rates).await;
+        if let Some(max) = max_kyc_rating {
+            rates.rates.retain(|r| kyc_rating_allows(r.kyc_rating.as_deref(), max));
+        }
+        self.add_fiat_values(&mut rates).await;
+        self.add_network_fees(&mut rates).await;
+
+        self.track(
+            FunnelEvent::QuoteViewed,
+            None,
+            serde_json::json!({
+                "from": rates.from,
+                "network_from": rates.network_from,
+                "to": rates.to,
+                "network_to": rates.network_to,
+                "amount": rates.amount,
+                "quotes": rates.rates.len(),
+                "best_provider": rates.rates.first().map(|r| &r.provider),
+                "timed_out": rates.meta.timed_out.len(),
+            }),
+        );
+
+        Ok(rates)
+    }
+
+    /// Pairs quoted within PAIR_MAX_AGE_DAYS with each provider's limits,
+    /// narrowed by `query`. The unfiltered listing for a query is cached for
+    /// PAIRS_CACHE_SECS; brand and disabled providers are filtered after.
+    pub async fn get_pairs(&self, query: &super::schema::PairsQuery) -> Result<Vec<super::schema::PairResponse>, SwapError> {
+        let filter = |v: &Option<String>| v.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_lowercase);
+        let (from, to) = (filter(&query.from), filter(&query.to));
+        let (from_network, to_network) = (
+            query.from_network.as_deref().map(str::trim).filter(|v| !v.is_empty()),
+            query.to_network.as_deref().map(str::trim).filter(|v| !v.is_empty()),
+        );
+
         let cache_key = format!(
+            "{}{}:{}:{}:{}",
+            PAIRS_CACHE_PREFIX,
+            from.as_deref().unwrap_or("*"),
+            from_network.unwrap_or("*"),
+            to.as_deref().unwrap_or("*"),
+            to_network.unwrap_or("*")
+        );
+        let cached = match &self.redis_service {
+            Some(service) => service.get_json::<Vec<super::schema::PairResponse>>(&cache_key).await.ok().flatten(),
+            None => None,
+        };
+
+        let pairs = match cached {
+            Some(pairs) => pairs,
+            None => {
+                let mut builder = sqlx::QueryBuilder::<MySql>::new(
+                    "SELECT p.from_currency, p.from_network, p.to_currency, p.to_network, p.provider,
+                            p.min_amount, p.max_amount,
+                            (COALESCE(f.is_active AND NOT f.admin_disabled, FALSE)
+                             AND COALESCE(t.is_active AND NOT t.admin_disabled, FALSE)
+                             AND (f.delisting_at IS NULL OR f.delisting_at > NOW())
+                             AND (t.delisting_at IS NULL OR t.delisting_at > NOW())) AS is_active,
+                            p.last_quoted_at
+                     FROM pairs p
+                     LEFT JOIN currencies f ON LOWER(f.symbol) = p.from_currency AND f.network = p.from_network
+                     LEFT JOIN currencies t ON LOWER(t.symbol) = p.to_currency AND t.network = p.to_network
+                     WHERE p.last_quoted_at >= NOW() - INTERVAL ",
+                );
+                builder.push_bind(PAIR_MAX_AGE_DAYS).push(" DAY");
+                if let Some(from) = &from {
+                    builder.push(" AND p.from_currency = ").push_bind(from.clone());
+                }
+                if let Some(network) = from_network {
+                    builder.push(" AND p.from_network = ").push_bind(network.to_string());
+                }
+                if let Some(to) = &to {
+                    builder.push(" AND p.to_currency = ").push_bind(to.clone());
+                }
+                if let Some(network) = to_network {
+                    builder.push(" AND p.to_network = ").push_bind(network.to_string());
+                }
+                builder.push(" ORDER BY p.from_currency, p.from_network, p.to_currency, p.to_network, p.provider");
+
+                let rows = builder.build_query_as::<PairQuote>().fetch_all(&self.pool).await?;
+                let pairs = group_pairs(rows);
+                if let Some(service) = &self.redis_service {
+                    let _ = service.set_json(&cache_key, &pairs, PAIRS_CACHE_SECS).await;
+                }
+                pairs
+            }
+        };
+
+        let disabled = self.disabled_providers().await;
+        let provider_allowed =
+            |p: &str| self.brand.allows_provider(p) && !disabled.iter().any(|d| d.eq_ignore_ascii_case(p));
+        Ok(pairs
+            .into_iter()
+            .filter(|pair| self.brand.allows_pair(&pair.from, &pair.to))
+            .filter_map(|mut pair| {
+                pair.providers.retain(|p| provider_allowed(&p.provider));
+                (pair.min_amount, pair.max_amount) = pair_limits(&pair.providers)?;
+                Some(pair)
+            })
+            .collect())
+    }
+
+    /// Drop pairs not quoted within PAIR_MAX_AGE_DAYS and rebuild the cached
+    /// unfiltered listing, so `/swap/pairs` reads never wait on the database
+    pub async fn refresh_pairs(&self) -> Result<SyncStats, SwapError> {
+        let removed = sqlx::query("DELETE FROM pairs WHERE last_quoted_at < NOW() - INTERVAL ? DAY")
+            .bind(PAIR_MAX_AGE_DAYS)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+
+        if let Some(service) = &self.redis_service {
+            let _ = service.delete_prefix(PAIRS_CACHE_PREFIX).await;
+        }
+        let pairs = self.get_pairs(&super::schema::PairsQuery::default()).await?;
+
+        Ok(SyncStats { fetched: pairs.len(), changed: removed })
+    }
+
+    /// Best quote at each rung of an amount ladder, fetched concurrently, so
+    /// large traders can see how the rate degrades with size. The raw ladder
+    /// is cached for DEPTH_CACHE_SECS; brand filtering and fees apply after.
+    pub async fn get_depth(&self, query: &super::schema::DepthQuery) -> Result<super::schema::DepthResponse, SwapError> {
+        if !self.brand.allows_pair(&query.from, &query.to) {
+            return Err(SwapError::PairNotAllowed { from: query.from.clone(), to: query.to.clone() });
+        }
+
+        let currency = self
+            .find_currency(&query.from, &query.network_from)
+            .await?
+            .ok_or(SwapError::CurrencyNotFound)?;
+        let base = match query.amount.or(currency.min_amount) {
+            Some(amount) if amount > Decimal::ZERO => amount,
+            Some(_) => return Err(SwapError::InvalidDepthQuery("amount must be positive".to_string())),
+            None => return Err(SwapError::InvalidDepthQuery(format!("no minimum known for {}, pass amount", query.from))),
+        };
+        let amounts: Vec<Decimal> = DEPTH_LADDER
+            .iter()
+            .map(|multiple| base * multiple)
+            .filter(|amount| currency.max_amount.is_none_or(|max| max <= Decimal::ZERO || *amount <= max))
+            .collect();
+
+        let mut cache_key = format!(
+            "depth:{}:{}:{}:{}:{}",
+            query.from, query.to, query.network_from, query.network_to, base
+        );
+        if let Some(markup) = self.brand.markup_percent {
+            cache_key.push_str(&format!(":m{}", markup));
+        }
+
+        let cached = match &self.redis_service {
+            Some(service) => service.get_json::<Vec<super::schema::RatesResponse>>(&cache_key).await.ok().flatten(),
+            None => None,
+        };
+        let ladder = match cached {
+            Some(ladder) => ladder,
+            None => {
+                let ladder = futures_util::future::try_join_all(amounts.iter().map(|amount| {
+                    let rates_query = super::schema::RatesQuery {
+                        from: query.from.clone(),
+                        network_from: query.network_from.clone(),
+                        to: query.to.clone(),
+                        network_to: query.network_to.clone(),
+                        amount: *amount,
+                        amount_to: None,
+                        max_kyc_rating: None,
+                        rate_type: None,
+                        provider: None,
+                        sandbox: false,
+                    };
+                    async move { self.get_rates_cached(&rates_query).await }
+                }))
+                .await?;
+
+                // A rung that ran out of budget would pin an empty level for the whole TTL
+                if let Some(service) = &self.redis_service {
+                    if ladder.iter().all(|rates| rates.meta.timed_out.is_empty()) {
+                        let _ = service.set_json(&cache_key, &ladder, DEPTH_CACHE_SECS).await;
+                    }
+                }
+                ladder
+            }
+        };
+
+        let mut levels = Vec::with_capacity(ladder.len());
+        for mut rates in ladder {
+            self.serve_quotes(&mut rates).await;
+
+            let best = rates.rates.first();
+            levels.push(super::schema::DepthLevel {
+                amount: rates.amount,
+                providers: rates.rates.len(),
+                best_provider: best.map(|r| r.provider.clone()),
+                rate: best.map(|r| r.rate),
+                estimated_amount: best.map(|r| r.estimated_amount),
+                rate_change_percent: None,
+            });
+        }
+
+        let first_rate = levels.first().and_then(|level| level.rate).filter(|rate| *rate > Decimal::ZERO);
+        for level in levels.iter_mut() {
+            level.rate_change_percent =
+                first_rate.zip(level.rate).map(|(first, rate)| money::to_f64((rate - first) / first * Decimal::ONE_HUNDRED));
+        }
+
+        Ok(super::schema::DepthResponse {
+            from: query.from.clone(),
+            network_from: query.network_from.clone(),
+            to: query.to.clone(),
+            network_to: query.network_to.clone(),
+            levels,
+        })
+    }
+
+    async fn get_rates_cached(
+        &self,
+        query: &super::schema::RatesQuery,
+    ) -> Result<super::schema::RatesResponse, SwapError> {
+        let started = tokio::time::Instant::now();
+        let budget = rates_budget();
+        let deadline = started + budget;
+
+        // Quotes by receive amount ignore `amount`
+        let amount = match query.amount_to {
+            Some(amount_to) => format!("to{}", amount_to),
+            None => query.amount.to_string(),
+        };
+        let mut cache_key = format!(
             "rates:{}:{}:{}:{}:{}",
-            query.from, query.to, query.network_from, query.network_to, query.amount
+            query.from, query.to, query.network_from, query.network_to, amount
         );
-        
-        let lock_key = format!("lock:{}", cache_key);
+        // Marked-up quotes differ per brand; unrestricted provider lists are filtered after the cache
+        if let Some(markup) = self.brand.markup_percent {
+            cache_key.push_str(&format!(":m{}", markup));
+        }
+        if query.sandbox {
+            cache_key.push_str(":sandbox");
+        }
+        if query.rate_type == Some(super::schema::RateType::Fixed) {
+            cache_key.push_str(":fixed");
+        }
 
         // 1. Try Cache First (Fast Path)
         if let Some(service) = &self.redis_service {
             if let Ok(Some(cached)) = service.get_json::<super::schema::RatesResponse>(&cache_key).await {
-                return Ok(cached);
+                metrics().rates_cache.inc("hit");
+                return Ok(with_rates_meta(cached, budget, started));
             }
         }
 
-        // 2. Singleflight Logic (Coalescing)
+        // 2. Coalesce identical queries: one fetch per key on this instance, and
+        // across instances only the Redis lock holder asks the aggregators. The
+        // flight runs in its own task so a response that misses the budget
+        // still lands in the cache for the next caller.
+        let flight = {
+            let crud = SwapCrud::new(self.pool.clone(), self.redis_service.clone())
+                .with_brand(self.brand.clone())
+                .with_trocador(self.trocador.clone());
+            let query = query.clone();
+            let cache_key = cache_key.clone();
+
+            async move { crud.fetch_rates_coalesced(&query, &cache_key).await.map_err(Arc::new) }
+        };
+        let (flight, joined) = RATES_FLIGHTS.join(&cache_key, flight);
+        if joined {
+            metrics().rates_cache.inc("coalesced");
+        }
+
+        match tokio::time::timeout_at(deadline, flight).await {
+            Ok(Some(result)) => result
+                .map(|rates| with_rates_meta(rates, budget, started))
+                .map_err(|e| unshare_error(&e)),
+            Ok(None) => Err(SwapError::ExternalApiError("Rates fetch failed".to_string())),
+            Err(_) => Ok(self.timed_out_rates(query, budget, started).await),
+        }
+    }
+
+    /// Rates for one cache key, fetched by the instance holding its Redis
+    /// lock; other instances wait for the holder to fill the cache
+    async fn fetch_rates_coalesced(
+        &self,
+        query: &super::schema::RatesQuery,
+        cache_key: &str,
+    ) -> Result<super::schema::RatesResponse, SwapError> {
+        let lock_key = format!("lock:{}", cache_key);
+
         if let Some(service) = &self.redis_service {
-            // Try to acquire lock for 15 seconds (cover long API calls)
-            // If try_lock returns true, we are the LEADER.
-            // If returns false, we are a FOLLOWER.
-            if !service.try_lock(&lock_key, 15).await.unwrap_or(false) {
-                // FOLLOWER: Wait for the leader to populate the cache
-                // Poll every 200ms for up to 5 seconds
+            // The lock covers long API calls; whoever takes it fetches. A lock
+            // Redis refuses to check is treated as taken by us, as everywhere else.
+            if !service.try_lock(&lock_key, 15).await.unwrap_or(true) {
+                // Poll every 200ms for up to 5 seconds; callers stop waiting at their budget
                 for _ in 0..25 {
                     tokio::time::sleep(Duration::from_millis(200)).await;
-                    if let Ok(Some(cached)) = service.get_json::<super::schema::RatesResponse>(&cache_key).await {
+                    if let Ok(Some(cached)) = service.get_json::<super::schema::RatesResponse>(cache_key).await {
+                        metrics().rates_cache.inc("coalesced");
                         return Ok(cached);
                     }
                 }
-                // If timeout, fall through and fetch ourselves
+                // The holder never filled the cache; fetch ourselves
             }
         }
 
-        // 3. Fetch from API (Leader Execution)
-        let result = self.fetch_rates_from_api(query).await?;
+        metrics().rates_cache.inc("miss");
+
+        // 3. Fetch from API
+        let result = self.fetch_rates_from_api(query).await;
 
-        // 4. Cache Result (Short TTL: 15s for volatility)
         if let Some(service) = &self.redis_service {
-            let _ = service.set_json(&cache_key, &result, 15).await;
-            // Lock will auto-expire, letting it sit ensures we don't spam if API is slow
+            match &result {
+                // 4. Cache Result (Short TTL: 15s for volatility). The lock
+                // expires on its own so a slow API is not hit again at once.
+                Ok(rates) => {
+                    let _ = service.set_json(cache_key, rates, 15).await;
+                }
+                // Nothing will reach the cache; let the next caller try at once
+                Err(_) => {
+                    let _ = service.delete(&lock_key).await;
+                }
+            }
         }
 
-        Ok(result)
+        result
+    }
+
+    /// Response for a budget that ran out before any quotes arrived: no
+    /// quotes, with every active provider marked as timed out
+    async fn timed_out_rates(
+        &self,
+        query: &super::schema::RatesQuery,
+        budget: Duration,
+        started: tokio::time::Instant,
+    ) -> super::schema::RatesResponse {
+        let timed_out = sqlx::query_scalar::<_, String>("SELECT name FROM providers WHERE is_active = TRUE ORDER BY name")
+            .fetch_all(&self.pool)
+            .await
+            .unwrap_or_default();
+
+        tracing::warn!(
+            "Rates for {}/{} -> {}/{} exceeded the {}ms budget",
+            query.from,
+            query.network_from,
+            query.to,
+            query.network_to,
+            budget.as_millis()
+        );
+
+        let rates = super::schema::RatesResponse {
+            trade_id: String::new(),
+            from: query.from.clone(),
+            network_from: query.network_from.clone(),
+            to: query.to.clone(),
+            network_to: query.network_to.clone(),
+            amount: query.amount,
+            amount_to: query.amount_to,
+            amount_fiat: None,
+            rates: Vec::new(),
+            meta: super::schema::RatesMeta {
+                timed_out,
+                ..Default::default()
+            },
+        };
+
+        with_rates_meta(rates, budget, started)
     }
 
-    /// Internal helper to fetch rates from Trocador
+    /// Ask every configured aggregator for quotes at once and merge them,
+    /// each quote tagged with the aggregator it came from. One failing
+    /// aggregator only drops its own quotes.
     async fn fetch_rates_from_api(
         &self,
         query: &super::schema::RatesQuery,
@@ -739,296 +1889,4518 @@ impl SwapCrud {
             let _ = service.check_rate_limit(rate_limit_key, 5, 60).await;
         }
 
-        let api_key = std::env::var("TROCADOR_API_KEY")
-            .map_err(|_| SwapError::ExternalApiError("TROCADOR_API_KEY not set".to_string()))?;
+        // Sandbox quotes come from the mock provider alone
+        let configured: Vec<Box<dyn SwapProviderClient>> = if query.sandbox {
+            vec![Box::new(self.sandbox()?)]
+        } else {
+            configured_aggregators(self.trocador.as_ref(), self.brand.markup_percent)
+        };
+        let (aggregators, shadowed): (Vec<_>, Vec<_>) =
+            configured.into_iter().partition(|aggregator| !is_shadowed(aggregator.aggregator()));
+        if aggregators.is_empty() {
+            return Err(SwapError::ExternalApiError("TROCADOR_API_KEY not set".to_string()));
+        }
 
-        let trocador_client = TrocadorClient::new(api_key);
+        // Shadowed aggregators are asked at the same time, off the request path
+        let served_tx = (!shadowed.is_empty()).then(|| self.spawn_shadow_quotes(shadowed, query.clone()));
 
-        let trocador_res = self.call_trocador_with_retry(|| async {
-            trocador_client
-                .get_rates(
-                    &query.from,
-                    &query.network_from,
-                    &query.to,
-                    &query.network_to,
-                    query.amount,
-                )
-                .await
-        })
-        .await?;
+        let results = futures_util::future::join_all(aggregators.iter().map(|aggregator| async move {
+            let quotes = self.call_with_retry(aggregator.aggregator(), || aggregator.get_quotes(query)).await;
+            (aggregator.aggregator(), quotes)
+        }))
+        .await;
 
-        // Transform and sort quotes
-        let mut rates: Vec<super::schema::RateResponse> = trocador_res
-            .quotes
-            .quotes
-            .into_iter()
-            .map(|quote| {
-                let amount_to = quote.amount_to.parse::<f64>().unwrap_or(0.0);
-                let waste = quote.waste.as_deref().unwrap_or("0.0").parse::<f64>().unwrap_or(0.0);
-                let total_fee = waste;
-                
-                super::schema::RateResponse {
-                    provider: quote.provider.clone(),
-                    provider_name: quote.provider.clone(),
-                    rate: amount_to / query.amount,
-                    estimated_amount: amount_to,
-                    min_amount: quote.min_amount.unwrap_or(0.0),
-                    max_amount: quote.max_amount.unwrap_or(0.0),
-                    network_fee: 0.0,
-                    provider_fee: total_fee,
-                    platform_fee: 0.0, 
-                    total_fee,
-                    rate_type: query.rate_type.clone().unwrap_or(super::schema::RateType::Floating),
-                    kyc_required: quote.kycrating.as_deref().unwrap_or("D") != "A",
-                    kyc_rating: quote.kycrating,
-                    eta_minutes: quote.eta.map(|e| e as u32).or(Some(15)),
+        let mut trade_id = None;
+        let mut rates = Vec::new();
+        let mut last_error = None;
+        for (aggregator, result) in results {
+            let quotes = match result {
+                Ok(quotes) => quotes,
+                Err(e) => {
+                    tracing::warn!("Rates from {} failed: {}", aggregator, e);
+                    last_error = Some(e);
+                    continue;
                 }
-            })
+            };
+            trade_id = trade_id.or(quotes.trade_id);
+
+            // By receive amount, a quote without what to send for it is no quote
+            let quotes = quotes.quotes.into_iter().filter(|q| query.amount_to.is_none() || q.amount_from.is_some_and(|a| a > Decimal::ZERO));
+            rates.extend(quotes.map(|quote| super::schema::RateResponse {
+                provider: quote.provider.clone(),
+                provider_name: quote.provider,
+                aggregator: aggregator.to_string(),
+                rate: money::rate(quote.amount_to, quote.amount_from.unwrap_or(query.amount)),
+                estimated_amount: quote.amount_to,
+                amount_from: quote.amount_from,
+                min_amount: quote.min_amount,
+                max_amount: quote.max_amount,
+                network_fee: Decimal::ZERO,
+                provider_fee: quote.provider_fee,
+                platform_fee: Decimal::ZERO,
+                total_fee: quote.provider_fee,
+                rate_type: query.rate_type.clone().unwrap_or(super::schema::RateType::Floating),
+                kyc_required: quote.kyc_rating.as_deref().unwrap_or("D") != "A",
+                kyc_rating: quote.kyc_rating,
+                eta_minutes: quote.eta_minutes.or(Some(15)),
+                recent_failures: 0,
+                demoted: false,
+                health_score: None,
+                rate_warning: false,
+                preferred: false,
+                estimated_amount_fiat: None,
+                fee_breakdown: None,
+            }));
+        }
+
+        // Nothing came back and an aggregator failed: report the failure
+        if let Some(e) = last_error.filter(|_| rates.is_empty() && trade_id.is_none()) {
+            return Err(e);
+        }
+
+        // Every live quote tells which providers serve the pair, for GET /swap/pairs
+        if !query.sandbox && !rates.is_empty() {
+            let pool = self.pool.clone();
+            let query = query.clone();
+            let limits: Vec<(String, Decimal, Decimal)> =
+                rates.iter().map(|r| (r.provider.clone(), r.min_amount, r.max_amount)).collect();
+            tokio::spawn(async move {
+                if let Err(e) = record_pairs(&pool, &query, &limits).await {
+                    tracing::warn!("Failed to record pairs for {}->{}: {}", query.from, query.to, e);
+                }
+            });
+        }
+
+        self.annotate_provider_failures(query, &mut rates).await;
+
+        sort_quotes(&mut rates);
+
+        if let Some(served_tx) = served_tx {
+            let _ = served_tx.send(rates.first().map(|best| (best.provider.clone(), best.estimated_amount)));
+        }
+
+        Ok(super::schema::RatesResponse {
+            trade_id: trade_id.unwrap_or_default(),
+            from: query.from.clone(),
+            network_from: query.network_from.clone(),
+            to: query.to.clone(),
+            network_to: query.network_to.clone(),
+            amount: query.amount,
+            amount_to: query.amount_to,
+            amount_fiat: None,
+            rates,
+            meta: super::schema::RatesMeta::default(),
+        })
+    }
+
+    /// Ask shadowed aggregators for quotes in the background and record each
+    /// result against the best served quote, which is sent on the returned
+    /// channel once known (dropping it records no served quote)
+    fn spawn_shadow_quotes(
+        &self,
+        aggregators: Vec<Box<dyn SwapProviderClient>>,
+        query: super::schema::RatesQuery,
+    ) -> tokio::sync::oneshot::Sender<Option<(String, Decimal)>> {
+        let (served_tx, served_rx) = tokio::sync::oneshot::channel();
+        let pool = self.pool.clone();
+
+        tokio::spawn(async move {
+            let calls = futures_util::future::join_all(aggregators.iter().map(|aggregator| {
+                let query = &query;
+                async move {
+                    let started = std::time::Instant::now();
+                    let result = aggregator.get_quotes(query).await;
+                    (aggregator.aggregator(), result, started.elapsed())
+                }
+            }));
+            let (results, served) = tokio::join!(calls, served_rx);
+            let served = served.ok().flatten();
+
+            for (aggregator, result, latency) in results {
+                if let Err(e) = record_shadow_quote(&pool, &query, aggregator, &result, latency, served.as_ref()).await {
+                    tracing::warn!("Failed to record shadow quote from {}: {}", aggregator, e);
+                }
+            }
+        });
+
+        served_tx
+    }
+
+    /// Attach recent trade-creation failures to each quote and demote
+    /// providers that failed often enough for this pair and amount band
+    async fn annotate_provider_failures(
+        &self,
+        query: &super::schema::RatesQuery,
+        rates: &mut [super::schema::RateResponse],
+    ) {
+        let Some(service) = &self.redis_service else {
+            return;
+        };
+
+        for rate in rates.iter_mut() {
+            let key = provider_failure_key(
+                &rate.provider,
+                &query.from,
+                &query.network_from,
+                &query.to,
+                &query.network_to,
+                query.amount,
+            );
+            rate.recent_failures = match service.get_string(&key).await {
+                Ok(Some(count)) => count.parse().unwrap_or(0),
+                _ => 0,
+            };
+            rate.demoted = rate.recent_failures >= PROVIDER_FAILURE_DEMOTE_THRESHOLD;
+        }
+    }
+
+    /// Count a failed trade creation against the provider, or clear its
+    /// record for this pair and band once it honors a quote again
+    async fn record_trade_outcome(&self, request: &super::schema::CreateSwapRequest, provider: &str, succeeded: bool) {
+        self.record_provider_trade(provider, succeeded).await;
+        if !succeeded {
+            self.record_provider_outcome(provider, SwapOutcome::Rejected, None).await;
+        }
+
+        let Some(service) = &self.redis_service else {
+            return;
+        };
+
+        let key = provider_failure_key(
+            provider,
+            &request.from,
+            &request.network_from,
+            &request.to,
+            &request.network_to,
+            request.amount,
+        );
+
+        let result = if succeeded {
+            service.delete(&key).await
+        } else {
+            service.incr_with_ttl(&key, PROVIDER_FAILURE_WINDOW_SECS).await.map(|_| ())
+        };
+
+        if let Err(e) = result {
+            tracing::warn!("Failed to record trade outcome for {}: {}", provider, e);
+        }
+    }
+
+    // =========================================================================
+    // PROVIDER UPTIME
+    // =========================================================================
+
+    /// Probe every known provider once: up when Trocador currently lists it.
+    /// A failed listing records nothing, since it says nothing about any one provider.
+    pub async fn probe_providers(&self, client: &TrocadorClient) -> Result<ProbeStats, SwapError> {
+        let listed = client.get_providers().await?;
+
+        let mut names: Vec<String> = sqlx::query_scalar("SELECT name FROM providers")
+            .fetch_all(&self.pool)
+            .await?;
+        for provider in &listed {
+            if !names.iter().any(|n| n.eq_ignore_ascii_case(&provider.name)) {
+                names.push(provider.name.clone());
+            }
+        }
+
+        let is_up = |name: &str| listed.iter().any(|p| p.name.eq_ignore_ascii_case(name));
+        let stats = ProbeStats { probed: names.len(), up: names.iter().filter(|n| is_up(n)).count() };
+        if names.is_empty() {
+            return Ok(stats);
+        }
+
+        let today = Utc::now().date_naive();
+        let mut builder = sqlx::QueryBuilder::<MySql>::new(
+            "INSERT INTO provider_uptime_daily (provider, day, probes, probes_up) ",
+        );
+        builder.push_values(&names, |mut row, name| {
+            row.push_bind(name).push_bind(today).push_bind(1u32).push_bind(u32::from(is_up(name)));
+        });
+        builder.push(" ON DUPLICATE KEY UPDATE probes = probes + 1, probes_up = probes_up + VALUES(probes_up)");
+        builder
+            .build()
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("DELETE FROM provider_uptime_daily WHERE day < ?")
+            .bind(today - chrono::Duration::days(i64::from(UPTIME_HISTORY_DAYS)))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(stats)
+    }
+
+    /// Count a new_trade attempt towards the provider's daily uptime
+    async fn record_provider_trade(&self, provider: &str, succeeded: bool) {
+        let result = sqlx::query(
+            "INSERT INTO provider_uptime_daily (provider, day, trades, trades_failed)
+             VALUES (?, ?, 1, ?)
+             ON DUPLICATE KEY UPDATE trades = trades + 1, trades_failed = trades_failed + VALUES(trades_failed)",
+        )
+        .bind(provider)
+        .bind(Utc::now().date_naive())
+        .bind(u32::from(!succeeded))
+        .execute(&self.pool)
+        .await;
+
+        if let Err(e) = result {
+            tracing::warn!("Failed to record trade attempt for {}: {}", provider, e);
+        }
+    }
+
+    /// 30- and 90-day availability for a provider, by id or name
+    pub async fn get_provider_uptime(
+        &self,
+        provider_id: &str,
+    ) -> Result<super::schema::ProviderUptimeResponse, SwapError> {
+        let provider: String = sqlx::query_scalar("SELECT name FROM providers WHERE id = ? OR name = ? LIMIT 1")
+            .bind(provider_id)
+            .bind(provider_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or(SwapError::ProviderNotFound)?;
+
+        let today = Utc::now().date_naive();
+        let daily = sqlx::query_as::<_, super::schema::DailyUptime>(
+            "SELECT day, probes, probes_up, trades, trades_failed
+             FROM provider_uptime_daily
+             WHERE provider = ? AND day > ?
+             ORDER BY day",
+        )
+        .bind(&provider)
+        .bind(today - chrono::Duration::days(i64::from(UPTIME_HISTORY_DAYS)))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(super::schema::ProviderUptimeResponse {
+            last_30_days: super::schema::UptimeWindow::from_daily(30, today, &daily),
+            last_90_days: super::schema::UptimeWindow::from_daily(UPTIME_HISTORY_DAYS, today, &daily),
+            provider,
+            daily,
+        })
+    }
+
+    // =========================================================================
+    // PROVIDER HEALTH
+    // =========================================================================
+
+    /// Count how a swap ended towards its provider's health score
+    async fn record_provider_outcome(&self, provider: &str, outcome: SwapOutcome, completion_secs: Option<u64>) {
+        let result = sqlx::query(
+            "INSERT INTO provider_stats (provider, day, outcome, swaps, completion_secs)
+             VALUES (?, ?, ?, 1, ?)
+             ON DUPLICATE KEY UPDATE swaps = swaps + 1, completion_secs = completion_secs + VALUES(completion_secs)",
+        )
+        .bind(provider)
+        .bind(Utc::now().date_naive())
+        .bind(outcome.as_str())
+        .bind(completion_secs.unwrap_or(0))
+        .execute(&self.pool)
+        .await;
+
+        if let Err(e) = result {
+            tracing::warn!("Failed to record {} swap for {}: {}", outcome.as_str(), provider, e);
+        }
+    }
+
+    /// Every provider's health over the scoring window, worst first
+    pub async fn provider_health(&self) -> Result<Vec<ProviderHealth>, SwapError> {
+        let config = ProviderHealthConfig::from_env();
+        let since = Utc::now().date_naive() - chrono::Duration::days(i64::from(config.window_days));
+
+        let counts: Vec<OutcomeCount> = sqlx::query_as(
+            "SELECT provider, outcome,
+                    CAST(SUM(swaps) AS UNSIGNED) AS swaps,
+                    CAST(SUM(completion_secs) AS UNSIGNED) AS completion_secs
+             FROM provider_stats
+             WHERE day > ?
+             GROUP BY provider, outcome",
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let overrides: Vec<(String, String)> =
+            sqlx::query_as("SELECT name, health_override FROM providers WHERE health_override IS NOT NULL")
+                .fetch_all(&self.pool)
+                .await?;
+        let overrides: Vec<(String, HealthOverride)> = overrides
+            .into_iter()
+            .filter_map(|(name, pin)| Some((name, pin.parse().ok()?)))
             .collect();
 
-        rates.sort_by(|a, b| {
-            b.estimated_amount
-                .partial_cmp(&a.estimated_amount)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+        Ok(provider_health::summarize(&counts, &overrides, &config))
+    }
+
+    /// Provider health for serving quotes; unknown when it can't be loaded,
+    /// which serves every quote as usual
+    async fn cached_provider_health(&self) -> Vec<ProviderHealth> {
+        if let Some(service) = &self.redis_service {
+            if let Ok(Some(cached)) = service.get_json::<Vec<ProviderHealth>>(PROVIDER_HEALTH_KEY).await {
+                return cached;
+            }
+        }
+
+        let health = match self.provider_health().await {
+            Ok(health) => health,
+            Err(e) => {
+                tracing::warn!("Failed to load provider health: {}", e);
+                return Vec::new();
+            }
+        };
+
+        if let Some(service) = &self.redis_service {
+            let _ = service.set_json(PROVIDER_HEALTH_KEY, &health, PROVIDER_HEALTH_CACHE_SECS).await;
+        }
+        health
+    }
+
+    /// Attach each provider's health score to its quotes, rank failing
+    /// providers last and drop the quotes of those hidden. Sandbox quotes
+    /// are left alone.
+    async fn apply_provider_health(&self, rates: &mut super::schema::RatesResponse) {
+        if rates.rates.iter().all(|r| r.aggregator == SANDBOX_AGGREGATOR) {
+            return;
+        }
+        let health = self.cached_provider_health().await;
+        if health.is_empty() {
+            return;
+        }
+
+        let mut demoted = false;
+        rates.rates.retain_mut(|quote| {
+            if quote.aggregator == SANDBOX_AGGREGATOR {
+                return true;
+            }
+            let Some(provider) = health.iter().find(|h| h.provider.eq_ignore_ascii_case(&quote.provider)) else {
+                return true;
+            };
+
+            quote.health_score = provider.score;
+            match provider.action {
+                HealthAction::Serve => true,
+                HealthAction::Demote => {
+                    demoted |= !quote.demoted;
+                    quote.demoted = true;
+                    true
+                }
+                HealthAction::Hide => {
+                    metrics().provider_health_hidden.inc(&quote.provider.to_lowercase());
+                    false
+                }
+            }
+        });
+
+        if demoted {
+            sort_quotes(&mut rates.rates);
+        }
+    }
+
+    // =========================================================================
+    // CREATE SWAP
+    // =========================================================================
+
+    /// Create a new swap by calling Trocador new_trade and saving to database
+    pub async fn create_swap(
+        &self,
+        request: &super::schema::CreateSwapRequest,
+        user_id: Option<String>,
+    ) -> Result<super::schema::CreateSwapResponse, SwapError> {
+        if request.amount_to.is_some() && request.quote_id.is_some() {
+            return Err(SwapError::InvalidAmount("a reserved quote is for its amount, pass amount".to_string()));
+        }
+        let resolved = self.resolve_amount_to(request).await?;
+        let request = &resolved;
+
+        let Some(quote_id) = request.quote_id.as_deref() else {
+            if request.wants_best_provider() {
+                let (selected, selection) = self.select_provider(request).await?;
+                return self.create_swap_linked(&selected, user_id, None, Some(selection)).await;
+            }
+            return self.create_swap_linked(request, user_id, None, None).await;
+        };
+
+        // A reserved quote pins the provider, trade and fixed rate; falling
+        // back to another provider would not honour it
+        let quote = self.claim_quote(quote_id, request).await?;
+        let reserved = super::schema::CreateSwapRequest {
+            trade_id: Some(quote.trade_id),
+            provider: quote.provider,
+            rate_type: super::schema::RateType::Fixed,
+            allow_fallback: false,
+            ..request.clone()
+        };
+
+        match self.create_swap_linked(&reserved, user_id, None, None).await {
+            Ok(response) => {
+                let _ = sqlx::query("UPDATE quotes SET swap_id = ? WHERE id = ?")
+                    .bind(&response.swap_id)
+                    .bind(quote_id)
+                    .execute(&self.pool)
+                    .await;
+                if let Some(redis) = &self.redis_service {
+                    let _ = redis.delete(&quote_key(quote_id)).await;
+                }
+                Ok(response)
+            }
+            Err(e) => {
+                // Nothing was created, so the reservation can be used again while it lasts
+                let _ = sqlx::query("UPDATE quotes SET used_at = NULL WHERE id = ? AND swap_id IS NULL")
+                    .bind(quote_id)
+                    .execute(&self.pool)
+                    .await;
+                Err(e)
+            }
+        }
+    }
+
+    /// A request by receive amount with `amount` set to what its provider's
+    /// fixed-rate quote asks to be sent (the best quote's under the selection
+    /// policy when no provider is named); other requests as they are
+    async fn resolve_amount_to(
+        &self,
+        request: &super::schema::CreateSwapRequest,
+    ) -> Result<super::schema::CreateSwapRequest, SwapError> {
+        let Some(amount_to) = request.amount_to else {
+            return Ok(request.clone());
+        };
+        check_amount_to(request.amount, amount_to)?;
+
+        let mut rates = self
+            .get_rates_cached(&super::schema::RatesQuery {
+                from: request.from.clone(),
+                network_from: request.network_from.clone(),
+                to: request.to.clone(),
+                network_to: request.network_to.clone(),
+                amount: Decimal::ZERO,
+                amount_to: Some(amount_to),
+                max_kyc_rating: None,
+                rate_type: Some(super::schema::RateType::Fixed),
+                provider: None,
+                sandbox: request.sandbox,
+            })
+            .await?;
+        self.serve_quotes(&mut rates).await;
+
+        let quote = if request.wants_best_provider() {
+            select_best_quote(&rates.rates, &self.provider_selection, Decimal::ZERO)
+                .ok_or(SwapError::NoQuoteMatchesPolicy)?
+        } else {
+            match rates.rates.iter().find(|r| r.provider.eq_ignore_ascii_case(&request.provider)) {
+                Some(quote) => quote,
+                None if rates.rates.is_empty() => return Err(SwapError::PairNotAvailable),
+                None => return Err(SwapError::ProviderNotQuoting(request.provider.clone())),
+            }
+        };
+
+        Ok(super::schema::CreateSwapRequest {
+            amount: quote.amount_from.unwrap_or_default(),
+            rate_type: super::schema::RateType::Fixed,
+            ..request.clone()
+        })
+    }
+
+    /// create_swap, optionally recording the swap this one replaces and how
+    /// its provider was selected
+    async fn create_swap_linked(
+        &self,
+        request: &super::schema::CreateSwapRequest,
+        user_id: Option<String>,
+        retried_from: Option<&str>,
+        provider_selection: Option<super::schema::ProviderSelection>,
+    ) -> Result<super::schema::CreateSwapResponse, SwapError> {
+        self.check_swap_request(request).await?;
+        let recipient_verified = self.check_swap_limit(request, user_id.as_deref()).await?;
+        let reservation = match (&user_id, request.sandbox) {
+            (Some(user_id), false) => self.reserve_volume(user_id, &request.from, request.amount).await?,
+            _ => None,
+        };
+
+        // 1. Open the trade; sandbox swaps are simulated and never reach Trocador
+        let mut fallback_chain = Vec::new();
+        let (provider, trocador_res, raw_trade) = if request.sandbox {
+            let (trade, raw_trade) = self.sandbox()?.create_trade(request);
+            (trade.provider.clone(), trade, raw_trade)
+        } else {
+            match self.create_live_trade(request, &mut fallback_chain).await {
+                Ok(opened) => opened,
+                Err(e) => {
+                    self.release_volume(reservation).await;
+                    return Err(e);
+                }
+            }
+        };
+
+        // By receive amount, what to send is whatever the provider that took the
+        // trade asks. Amounts are rounded as quotes are.
+        let (send_decimals, receive_decimals) = tokio::join!(
+            self.currency_decimals(&request.from, &request.network_from),
+            self.currency_decimals(&request.to, &request.network_to)
+        );
+        let amount = match request.amount_to {
+            Some(_) => money::round_send(trocador_res.amount_from, send_decimals),
+            None => request.amount,
+        };
+
+        // 2. Map Trocador status to our internal SwapStatus
+        let status = SwapStateMachine::map_provider_status(&trocador_res.status);
+
+        // 3. Price the platform fee with the same rules as the quote
+        let platform_fee = self
+            .platform_fee(&request.from, &request.to, &provider, trocador_res.amount_to)
+            .await;
+        let platform_fee = money::round_send(platform_fee, receive_decimals);
+        let estimated_receive = money::round_receive(trocador_res.amount_to - platform_fee, receive_decimals);
+        // Sandbox swaps earn no commission
+        let affiliate = self.affiliate.as_ref().filter(|_| !request.sandbox);
+        let affiliate_commission = affiliate.map(|a| a.commission_on(platform_fee));
+
+        // 4. Generate local ID and save to database
+        let swap_id = uuid::Uuid::new_v4().to_string();
+        let usd_value = self.high_value.usd_value(&request.from, amount, &request.to, estimated_receive);
+        let high_value = self.high_value.is_high_value(usd_value);
+
+        // The trade is already open, so a deadlock or dropped connection here
+        // is retried. A retry after an insert that did land finds its own row.
+        let tenant = self.tenant();
+        let insert = || {
+            sqlx::query(
+                r#"
+                INSERT INTO swaps (
+                    id, tenant_id, user_id, brand, affiliate, provider_id, provider_swap_id, retried_from,
+                    fallback_chain, provider_selection, from_currency, from_network, to_currency, to_network,
+                    amount, estimated_receive, rate, platform_fee, affiliate_commission, total_fee,
+                    deposit_address, deposit_extra_id,
+                    recipient_address, recipient_extra_id,
+                    refund_address, refund_extra_id,
+                    status, rate_type, is_sandbox, high_value, usd_value,
+                    created_at, updated_at
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, NOW(), NOW())
+                ON DUPLICATE KEY UPDATE id = id
+                "#
+            )
+            .bind(&swap_id)
+            .bind(tenant.as_str())
+            .bind(&user_id)
+            .bind(self.brand.stored_slug())
+            .bind(affiliate.map(|a| a.ref_code.as_str()))
+            .bind(&provider)
+            .bind(&trocador_res.trade_id)
+            .bind(retried_from)
+            .bind((!fallback_chain.is_empty()).then(|| serde_json::to_string(&fallback_chain).unwrap_or_default()))
+            .bind(provider_selection.as_ref().map(|s| serde_json::to_string(s).unwrap_or_default()))
+            .bind(&request.from)
+            .bind(&request.network_from)
+            .bind(&request.to)
+            .bind(&request.network_to)
+            .bind(amount)
+            .bind(estimated_receive)
+            .bind(money::rate(estimated_receive, amount))
+            .bind(platform_fee)
+            .bind(affiliate_commission)
+            .bind(platform_fee) // total_fee; the provider's share is already out of amount_to
+            .bind(&trocador_res.address_provider)
+            .bind(&trocador_res.address_provider_memo)
+            .bind(&request.recipient_address)
+            .bind(&request.recipient_extra_id)
+            .bind(&request.refund_address)
+            .bind(&request.refund_extra_id)
+            .bind(status.clone())
+            .bind(&request.rate_type)
+            .bind(request.sandbox)
+            .bind(high_value)
+            .bind(usd_value)
+            .execute(&self.pool)
+        };
+        if let Err(e) = retry_transient(&self.db_retry, "insert swap", insert).await {
+            self.release_volume(reservation).await;
+            // uq_swaps_retried_from: another retry of the same swap got there first
+            if let (Some(original), sqlx::Error::Database(db)) = (retried_from, &e) {
+                if db.is_unique_violation() {
+                    let retry_id = self.find_retry_of(original).await?.unwrap_or_default();
+                    return Err(SwapError::AlreadyRetried(retry_id));
+                }
+            }
+            return Err(e.into());
+        }
+        metrics().swaps_created.inc(status.as_str());
+        if let (Some(user_id), false) = (&user_id, request.sandbox) {
+            if let Err(e) = self.record_recipient_use(user_id, request).await {
+                tracing::warn!("Failed to record recipient address of swap {}: {}", swap_id, e);
+            }
+        }
+        // The trade is open either way, so a missing history row must not fail the create
+        if let Err(e) = self.record_transition(&swap_id, None, &status, StatusSource::Create, None).await {
+            tracing::warn!("Failed to record initial status of swap {}: {}", swap_id, e);
+        }
+
+        if high_value {
+            tracing::info!(swap_id = %swap_id, usd_value = ?usd_value, "High-value swap created");
+        }
+
+        self.store_provider_payload(&swap_id, super::schema::ProviderCallType::CreateTrade, &raw_trade)
+            .await;
+
+        self.outbox
+            .record(
+                DomainEventType::SwapCreated,
+                &swap_id,
+                serde_json::json!({
+                    "swap_id": swap_id,
+                    "tenant": self.tenant(),
+                    "user_id": user_id,
+                    "brand": self.brand.stored_slug(),
+                    "affiliate": affiliate.map(|a| &a.ref_code),
+                    "affiliate_commission": affiliate_commission,
+                    "provider": provider,
+                    "from": request.from,
+                    "network_from": request.network_from,
+                    "to": request.to,
+                    "network_to": request.network_to,
+                    "amount": amount,
+                    "estimated_receive": estimated_receive,
+                    "platform_fee": platform_fee,
+                    "rate_type": request.rate_type,
+                    "status": status,
+                    "high_value": high_value,
+                    "recipient_verified": recipient_verified,
+                    "retried_from": retried_from,
+                    "fallback_chain": fallback_chain,
+                    "provider_selection": provider_selection,
+                }),
+            )
+            .await;
+
+        self.track(
+            FunnelEvent::SwapCreated,
+            Some(&swap_id),
+            serde_json::json!({
+                "provider": provider,
+                "from": request.from,
+                "network_from": request.network_from,
+                "to": request.to,
+                "network_to": request.network_to,
+                "amount": amount,
+                "rate_type": request.rate_type,
+                "retried_from": retried_from,
+            }),
+        );
+
+        let deposit_uri = self
+            .address_formats()
+            .lookup(&request.from, &request.network_from)
+            .await
+            .and_then(|f| {
+                f.payment_uri(
+                    &trocador_res.address_provider,
+                    Some(amount),
+                    trocador_res.address_provider_memo.as_deref(),
+                )
+            });
+
+        // 5. Transform to response
+        let prices = self.price_feed.prices().await;
+        Ok(super::schema::CreateSwapResponse {
+            swap_id,
+            provider: trocador_res.provider,
+            from: request.from.clone(),
+            to: request.to.clone(),
+            deposit_address: trocador_res.address_provider,
+            deposit_extra_id: trocador_res.address_provider_memo,
+            deposit_amount: amount,
+            deposit_uri,
+            recipient_address: request.recipient_address.clone(),
+            estimated_receive,
+            rate: money::rate(estimated_receive, amount),
+            status,
+            rate_type: request.rate_type.clone(),
+            is_sandbox: request.sandbox,
+            expires_at: Utc::now() + chrono::Duration::minutes(60), // Default expiry if not provided
+            created_at: Utc::now(),
+            retried_from: retried_from.map(str::to_string),
+            fallback_chain,
+            recipient_verified,
+            provider_selection,
+            deposit_amount_fiat: fiat_value(&prices, &request.from, amount),
+            estimated_receive_fiat: fiat_value(&prices, &request.to, estimated_receive),
+        })
+    }
+
+    /// Open the trade at Trocador with retry logic, moving on to other quoted
+    /// providers when allowed; each provider that refused is added to
+    /// `fallback_chain`
+    async fn create_live_trade(
+        &self,
+        request: &super::schema::CreateSwapRequest,
+        fallback_chain: &mut Vec<super::schema::FallbackAttempt>,
+    ) -> Result<(String, super::schema::TrocadorTradeResponse, String), SwapError> {
+        // Trocador calls POST /swap/webhook/trocador on status changes when a URL is configured
+        let trocador_client = self.trocador()?.clone().with_markup(self.brand.markup_percent);
+
+        let first_attempt = match self.guard_requested_rate(request).await {
+            Ok(()) => {
+                self.create_trade(&trocador_client, request, &request.provider, request.trade_id.as_deref())
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        match first_attempt {
+            Ok((trocador_res, raw_trade)) => Ok((request.provider.clone(), trocador_res, raw_trade)),
+            Err(e @ SwapError::RateOutOfBounds { .. }) if request.allow_fallback => {
+                tracing::warn!("{}, trying fallback providers", e);
+                fallback_chain.push(super::schema::FallbackAttempt { provider: request.provider.clone(), error: e.to_string() });
+                self.create_trade_with_fallback(&trocador_client, request, fallback_chain).await
+            }
+            Err(SwapError::ExternalApiError(error)) if request.allow_fallback => {
+                tracing::warn!("{} rejected the trade, trying fallback providers: {}", request.provider, error);
+                fallback_chain.push(super::schema::FallbackAttempt { provider: request.provider.clone(), error });
+                self.create_trade_with_fallback(&trocador_client, request, fallback_chain).await
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// new_trade with one provider; the outcome feeds the provider failure counters
+    async fn create_trade(
+        &self,
+        client: &TrocadorClient,
+        request: &super::schema::CreateSwapRequest,
+        provider: &str,
+        trade_id: Option<&str>,
+    ) -> Result<(super::schema::TrocadorTradeResponse, String), SwapError> {
+        if request.sandbox {
+            return Err(SwapError::Internal("Sandbox swap sent to a live provider".to_string()));
+        }
+        let fixed = matches!(request.rate_type, super::schema::RateType::Fixed);
+        let amount = match request.amount_to {
+            Some(amount_to) => TradeAmount::To(amount_to),
+            None => TradeAmount::From(request.amount),
+        };
+
+        let trade_result = self.call_with_retry(TROCADOR, || async {
+            client
+                .create_trade(
+                    trade_id,
+                    &request.from,
+                    &request.network_from,
+                    &request.to,
+                    &request.network_to,
+                    amount,
+                    &request.recipient_address,
+                    request.refund_address.as_deref(),
+                    provider,
+                    fixed,
+                )
+                .await
+        })
+        .await;
+        // A fast-failed call never reached the provider
+        if !matches!(trade_result, Err(SwapError::ProviderUnavailable(_))) {
+            self.record_trade_outcome(request, provider, trade_result.is_ok()).await;
+        }
+        trade_result
+    }
+
+    /// Refuse the requested provider when the rate guard rejects its current
+    /// quote. Reserved quotes were screened when reserved; without a quote or
+    /// a reference rate there is nothing to judge.
+    async fn guard_requested_rate(&self, request: &super::schema::CreateSwapRequest) -> Result<(), SwapError> {
+        let guard = &self.rate_guard;
+        if !guard.rejects() || request.trade_id.is_some() {
+            return Ok(());
+        }
+
+        let rates = self
+            .get_rates_cached(&super::schema::RatesQuery {
+                from: request.from.clone(),
+                network_from: request.network_from.clone(),
+                to: request.to.clone(),
+                network_to: request.network_to.clone(),
+                amount: request.amount,
+                amount_to: request.amount_to,
+                max_kyc_rating: None,
+                rate_type: Some(request.rate_type.clone()),
+                provider: None,
+                sandbox: request.sandbox,
+            })
+            .await;
+        let rates = match rates {
+            Ok(rates) => rates,
+            Err(e) => {
+                tracing::warn!("Rate guard skipped, no quotes to compare: {}", e);
+                return Ok(());
+            }
+        };
+
+        guard
+            .check_provider(&rates, &request.provider)
+            .map_err(|deviation_pct| SwapError::RateOutOfBounds { provider: request.provider.clone(), deviation_pct })
+    }
+
+    /// Resolve a request without a provider: the best current quote under
+    /// the selection policy, and the record of how it was chosen
+    async fn select_provider(
+        &self,
+        request: &super::schema::CreateSwapRequest,
+    ) -> Result<(super::schema::CreateSwapRequest, super::schema::ProviderSelection), SwapError> {
+        // Terms are left to the service, so a routing rule's rate type wins,
+        // except over the fixed rate of a swap by receive amount
+        let rate_type = match request.amount_to {
+            Some(_) => super::schema::RateType::Fixed,
+            None => self
+                .routing(&request.from, &request.to, request.amount)
+                .await
+                .rate_type
+                .unwrap_or_else(|| request.rate_type.clone()),
+        };
+        let mut rates = self
+            .get_rates_cached(&super::schema::RatesQuery {
+                from: request.from.clone(),
+                network_from: request.network_from.clone(),
+                to: request.to.clone(),
+                network_to: request.network_to.clone(),
+                amount: request.amount,
+                amount_to: request.amount_to,
+                max_kyc_rating: None,
+                rate_type: Some(rate_type.clone()),
+                provider: None,
+                sandbox: request.sandbox,
+            })
+            .await?;
+        self.serve_quotes(&mut rates).await;
+
+        let mut policy = self.provider_selection.clone();
+        // The stricter of the service's and the user's KYC limits
+        policy.min_kyc_rating = policy.min_kyc_rating.into_iter().chain(self.max_kyc_rating).min();
+        let quote = select_best_quote(&rates.rates, &policy, request.amount).ok_or(SwapError::NoQuoteMatchesPolicy)?;
+        let candidates = rates.rates.iter().filter(|r| meets_selection_policy(r, &policy, request.amount)).count();
+        tracing::info!("Selected {} for {} -> {} out of {} eligible quote(s)", quote.provider, request.from, request.to, candidates);
+
+        let selection = super::schema::ProviderSelection {
+            policy: SELECTION_POLICY.to_string(),
+            min_kyc_rating: policy.min_kyc_rating.map(String::from),
+            max_eta_minutes: policy.max_eta_minutes,
+            candidates,
+            quote: super::schema::SelectedQuote {
+                trade_id: rates.trade_id.clone(),
+                provider: quote.provider.clone(),
+                estimated_amount: quote.estimated_amount,
+                rate: quote.rate,
+                total_fee: quote.total_fee,
+                kyc_rating: quote.kyc_rating.clone(),
+                eta_minutes: quote.eta_minutes,
+            },
+        };
+        let selected = super::schema::CreateSwapRequest {
+            provider: quote.provider.clone(),
+            trade_id: Some(rates.trade_id.clone()).filter(|id| !id.is_empty()),
+            rate_type,
+            ..request.clone()
+        };
+
+        Ok((selected, selection))
+    }
+
+    /// After the requested provider rejected the trade, try the next-best
+    /// quotes within SWAP_FALLBACK_TOLERANCE_PCT of its quote (or of the best
+    /// quote when it no longer quotes). Rejections are appended to `chain`.
+    async fn create_trade_with_fallback(
+        &self,
+        client: &TrocadorClient,
+        request: &super::schema::CreateSwapRequest,
+        chain: &mut Vec<super::schema::FallbackAttempt>,
+    ) -> Result<(String, super::schema::TrocadorTradeResponse, String), SwapError> {
+        let mut rates = self
+            .get_rates_cached(&super::schema::RatesQuery {
+                from: request.from.clone(),
+                network_from: request.network_from.clone(),
+                to: request.to.clone(),
+                network_to: request.network_to.clone(),
+                amount: request.amount,
+                amount_to: request.amount_to,
+                max_kyc_rating: None,
+                rate_type: Some(request.rate_type.clone()),
+                provider: None,
+                sandbox: request.sandbox,
+            })
+            .await?;
+        self.serve_quotes(&mut rates).await;
+
+        let reference = rates
+            .rates
+            .iter()
+            .find(|r| r.provider.eq_ignore_ascii_case(&request.provider))
+            .or_else(|| rates.rates.first())
+            .map(|r| r.estimated_amount)
+            .unwrap_or_default();
+        let floor = reference * money::from_f64(1.0 - self.provider_fallback.tolerance_pct / 100.0);
+
+        // Rates are sorted best-first with demoted providers last
+        let candidates: Vec<String> = rates
+            .rates
+            .iter()
+            .filter(|r| !r.demoted && r.estimated_amount >= floor)
+            .filter(|r| !chain.iter().any(|a| a.provider.eq_ignore_ascii_case(&r.provider)))
+            .take(MAX_FALLBACK_PROVIDERS)
+            .map(|r| r.provider.clone())
+            .collect();
+
+        for provider in candidates {
+            match self.create_trade(client, request, &provider, Some(&rates.trade_id)).await {
+                Ok((trocador_res, raw_trade)) => {
+                    tracing::info!("Swap fell back from {} to {}", request.provider, provider);
+                    return Ok((provider, trocador_res, raw_trade));
+                }
+                Err(SwapError::ExternalApiError(error)) => {
+                    tracing::warn!("Fallback provider {} rejected the trade: {}", provider, error);
+                    chain.push(super::schema::FallbackAttempt { provider, error });
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let tried: Vec<&str> = chain.iter().map(|a| a.provider.as_str()).collect();
+        Err(SwapError::ProviderUnavailable(format!(
+            "no provider within the price tolerance accepted the trade (tried {})",
+            tried.join(", ")
+        )))
+    }
+
+    /// Checks shared by create_swap and its dry run, before any quote is used
+    async fn check_swap_request(&self, request: &super::schema::CreateSwapRequest) -> Result<(), SwapError> {
+        if !self.brand.allows_pair(&request.from, &request.to) {
+            return Err(SwapError::PairNotAllowed { from: request.from.clone(), to: request.to.clone() });
+        }
+        // Sandbox swaps always go to the mock provider, whichever one was picked;
+        // a selected provider comes from the served, hence allowed, quotes
+        if !request.sandbox && !request.wants_best_provider() {
+            let routing = self.routing(&request.from, &request.to, request.amount).await;
+            if !routing.allows(&request.provider) || !self.provider_allowed(&request.provider).await {
+                return Err(SwapError::ProviderNotAllowed(request.provider.clone()));
+            }
+            if let Some(max) = self.max_kyc_rating {
+                self.check_provider_kyc_rating(&request.provider, max).await?;
+            }
+        }
+
+        // Currencies past their delisting date, or disabled by an admin, accept no new swaps
+        self.ensure_not_delisted(&request.from, &request.network_from).await?;
+        self.ensure_not_delisted(&request.to, &request.network_to).await?;
+
+        let registry = self.address_formats();
+        let from = self.find_currency(&request.from, &request.network_from).await?;
+        let to = self.find_currency(&request.to, &request.network_to).await?;
+        let to_format = registry.lookup(&request.to, &request.network_to).await;
+        let from_format = match non_empty(request.refund_extra_id.as_deref()) {
+            Some(_) => registry.lookup(&request.from, &request.network_from).await,
+            None => None,
+        };
+        check_request_currencies(
+            request,
+            &RequestCurrencies {
+                from: from.as_ref(),
+                to: to.as_ref(),
+                from_format: from_format.as_ref(),
+                to_format: to_format.as_ref(),
+            },
+        )?;
+        self.check_refund_address(request).await
+    }
+
+    /// Refuse a provider whose current KYC rating (admin override included)
+    /// is worse than `max`; one without a rating counts as D
+    async fn check_provider_kyc_rating(&self, provider: &str, max: char) -> Result<(), SwapError> {
+        let rating: Option<String> =
+            sqlx::query_scalar("SELECT kyc_rating FROM providers WHERE LOWER(name) = LOWER(?) OR slug = LOWER(?) LIMIT 1")
+                .bind(provider)
+                .bind(provider)
+                .fetch_optional(&self.pool)
+                .await?;
+        let rating = rating.unwrap_or_else(|| "D".to_string());
+
+        if !kyc_rating_allows(Some(&rating), max) {
+            return Err(SwapError::KycRatingNotAccepted { provider: provider.to_string(), rating, max });
+        }
+        Ok(())
+    }
+
+    /// Refuse a swap worth more than its per-swap USD limit, which is higher
+    /// when the caller proved control of the recipient address. Returns
+    /// whether they did.
+    async fn check_swap_limit(
+        &self,
+        request: &super::schema::CreateSwapRequest,
+        user_id: Option<&str>,
+    ) -> Result<bool, SwapError> {
+        let recipient_verified = match (user_id, Chain::detect(&request.to, &request.network_to)) {
+            (Some(user_id), Some(chain)) => self.is_verified_address(user_id, chain, &request.recipient_address).await?,
+            _ => false,
+        };
+
+        check_usd_limit(request, &self.address_verification, &self.high_value, recipient_verified)?;
+        Ok(recipient_verified)
+    }
+
+    // =========================================================================
+    // VOLUME LIMITS
+    // =========================================================================
+
+    /// `amount` of `ticker` in USD at the price feed's price, or the
+    /// HIGH_VALUE_USD_PRICES one when the feed has none
+    async fn usd_value(&self, ticker: &str, amount: Decimal) -> Option<f64> {
+        let prices = self.price_feed.prices().await;
+        fiat_value(&prices, ticker, amount)
+            .map(|value| value.usd)
+            .or_else(|| self.high_value.usd_amount(ticker, amount))
+    }
+
+    /// USD volume the user created and completed in the last `hours` hours
+    async fn user_volume(&self, user_id: &str, hours: u32) -> Result<(f64, f64), SwapError> {
+        let volume: (f64, f64) = sqlx::query_as(USER_VOLUME_SQL)
+            .bind(user_id)
+            .bind(hours)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(volume)
+    }
+
+    /// Count a swap against the user's rolling volume, or refuse it when it
+    /// would take them past a cap. The user's row stays locked from reading
+    /// the windows to adding the swap, so concurrent creates by one user are
+    /// counted one after another. Swaps that can't be priced are let through
+    /// and not counted.
+    async fn reserve_volume(
+        &self,
+        user_id: &str,
+        ticker: &str,
+        amount: Decimal,
+    ) -> Result<Option<VolumeReservation>, SwapError> {
+        let Some(usd) = self.usd_value(ticker, amount).await else {
+            return Ok(None);
+        };
+        let windows = [
+            ("24 hours", 24, self.volume_limits.daily_limit()),
+            ("30 days", 720, self.volume_limits.monthly_limit()),
+        ];
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SELECT id FROM users WHERE id = ? FOR UPDATE")
+            .bind(user_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        for (window, hours, limit) in windows {
+            let Some(limit) = limit else { continue };
+            let (used, _): (f64, f64) = sqlx::query_as(USER_VOLUME_SQL)
+                .bind(user_id)
+                .bind(hours)
+                .fetch_one(&mut *tx)
+                .await?;
+            if used + usd > limit {
+                return Err(SwapError::VolumeLimitExceeded { window, limit, remaining: (limit - used).max(0.0) });
+            }
+        }
+
+        let hour_start: NaiveDateTime =
+            sqlx::query_scalar("SELECT CAST(DATE_FORMAT(NOW(), '%Y-%m-%d %H:00:00') AS DATETIME)")
+                .fetch_one(&mut *tx)
+                .await?;
+        sqlx::query(
+            "INSERT INTO user_volume (user_id, hour_start, created_usd, swaps_created)
+             VALUES (?, ?, ?, 1)
+             ON DUPLICATE KEY UPDATE created_usd = created_usd + VALUES(created_usd), swaps_created = swaps_created + 1",
+        )
+        .bind(user_id)
+        .bind(hour_start)
+        .bind(usd)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(Some(VolumeReservation { user_id: user_id.to_string(), hour_start, usd }))
+    }
+
+    /// Take back a reservation for a swap that was not created; failures are
+    /// logged and leave the volume counted
+    async fn release_volume(&self, reservation: Option<VolumeReservation>) {
+        let Some(reservation) = reservation else { return };
+        let result = sqlx::query(
+            "UPDATE user_volume
+             SET created_usd = GREATEST(created_usd - ?, 0), swaps_created = GREATEST(swaps_created, 1) - 1
+             WHERE user_id = ? AND hour_start = ?",
+        )
+        .bind(reservation.usd)
+        .bind(&reservation.user_id)
+        .bind(reservation.hour_start)
+        .execute(&self.pool)
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to release swap volume of user {}: {}", reservation.user_id, e);
+        }
+    }
+
+    /// Add a completed swap to the user's volume for this hour; failures are
+    /// logged, the status change goes ahead either way
+    async fn record_completed_volume(&self, user_id: &str, ticker: &str, amount: Decimal) {
+        let Some(usd_value) = self.usd_value(ticker, amount).await else {
+            return;
+        };
+
+        let result = sqlx::query(
+            "INSERT INTO user_volume (user_id, hour_start, completed_usd, swaps_completed)
+             VALUES (?, DATE_FORMAT(NOW(), '%Y-%m-%d %H:00:00'), ?, 1)
+             ON DUPLICATE KEY UPDATE
+                completed_usd = completed_usd + VALUES(completed_usd),
+                swaps_completed = swaps_completed + 1",
+        )
+        .bind(user_id)
+        .bind(usd_value)
+        .execute(&self.pool)
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to record swap volume of user {}: {}", user_id, e);
+        }
+    }
+
+    /// Per-swap limits and what is left of the rolling volume caps
+    pub async fn get_swap_limits(&self, user_id: &str) -> Result<super::schema::SwapLimitsResponse, SwapError> {
+        let config = &self.volume_limits;
+        let address_config = &self.address_verification;
+        let (used_24h, completed_24h) = self.user_volume(user_id, 24).await?;
+        let (used_30d, completed_30d) = self.user_volume(user_id, 720).await?;
+
+        Ok(super::schema::SwapLimitsResponse {
+            max_swap_usd: address_config.swap_limit_usd(false),
+            verified_max_swap_usd: address_config.swap_limit_usd(true),
+            rolling_24h: super::schema::VolumeAllowance::new(24, config.daily_limit(), used_24h, completed_24h),
+            rolling_30d: super::schema::VolumeAllowance::new(720, config.monthly_limit(), used_30d, completed_30d),
+        })
+    }
+
+    // =========================================================================
+    // MULTI-LEG ROUTES
+    // =========================================================================
+
+    /// Ways to swap `query.amount` of `from` to `to`, best receive amount
+    /// first: the direct pair when it is quoted, and two legs through each
+    /// SWAP_ROUTE_VIA currency (only `via` when given). Each leg takes the
+    /// best quote under the provider selection policy.
+    pub async fn plan_routes(
+        &self,
+        query: &super::schema::RoutePlanQuery,
+    ) -> Result<super::schema::RoutePlanResponse, SwapError> {
+        if query.amount <= Decimal::ZERO {
+            return Err(SwapError::InvalidRoute("amount must be positive".to_string()));
+        }
+        let (from, to) = (query.from.to_lowercase(), query.to.to_lowercase());
+        let (from, to) = (from.as_str(), to.as_str());
+        let same = |ticker: &str, network: &str, other: &str, other_network: &str| {
+            ticker.eq_ignore_ascii_case(other) && network.eq_ignore_ascii_case(other_network)
+        };
+
+        let via = match (&query.via, &query.via_network) {
+            (Some(via), Some(network)) => vec![(via.trim().to_lowercase(), network.trim().to_string())],
+            (Some(_), None) => return Err(SwapError::InvalidRoute("via_network is required with via".to_string())),
+            _ => SwapRouteConfig::from_env().via,
+        };
+        let via: Vec<_> = via
+            .into_iter()
+            .filter(|(ticker, network)| {
+                !same(ticker, network, from, &query.network_from) && !same(ticker, network, to, &query.network_to)
+            })
+            .collect();
+
+        let direct = async {
+            if query.via.is_some() {
+                return None;
+            }
+            let leg = self
+                .best_leg(from, &query.network_from, to, &query.network_to, query.amount, &query.rate_type)
+                .await?;
+            Some(super::schema::RoutePlan {
+                via: None,
+                via_network: None,
+                estimated_receive: leg.estimated_amount,
+                legs: vec![leg],
+            })
+        };
+        let through = futures_util::future::join_all(via.iter().map(|(ticker, network)| async move {
+            let first = self
+                .best_leg(from, &query.network_from, ticker, network, query.amount, &query.rate_type)
+                .await?;
+            // The first leg's payout is only known once it arrives, so the second floats
+            let second = self
+                .best_leg(ticker, network, to, &query.network_to, first.estimated_amount, &super::schema::RateType::Floating)
+                .await?;
+            Some(super::schema::RoutePlan {
+                via: Some(ticker.clone()),
+                via_network: Some(network.clone()),
+                estimated_receive: second.estimated_amount,
+                legs: vec![first, second],
+            })
+        }));
+        let (direct, through) = tokio::join!(direct, through);
+
+        let mut routes: Vec<_> = direct.into_iter().chain(through.into_iter().flatten()).collect();
+        if routes.is_empty() {
+            return Err(SwapError::NoRouteFound { from: query.from.clone(), to: query.to.clone() });
+        }
+        routes.sort_by_key(|route| std::cmp::Reverse(route.estimated_receive));
+
+        Ok(super::schema::RoutePlanResponse {
+            from: query.from.clone(),
+            network_from: query.network_from.clone(),
+            to: query.to.clone(),
+            network_to: query.network_to.clone(),
+            amount: query.amount,
+            routes,
+        })
+    }
+
+    /// The quote select_best_quote picks for one leg; None when the pair is
+    /// not offered or nothing meets the policy
+    async fn best_leg(
+        &self,
+        from: &str,
+        network_from: &str,
+        to: &str,
+        network_to: &str,
+        amount: Decimal,
+        rate_type: &super::schema::RateType,
+    ) -> Option<super::schema::RouteLegQuote> {
+        if !self.brand.allows_pair(from, to) {
+            return None;
+        }
+        let query = super::schema::RatesQuery {
+            from: from.to_string(),
+            network_from: network_from.to_string(),
+            to: to.to_string(),
+            network_to: network_to.to_string(),
+            amount,
+            amount_to: None,
+            max_kyc_rating: None,
+            rate_type: Some(rate_type.clone()),
+            provider: None,
+            sandbox: false,
+        };
+        let mut rates = match self.get_rates_cached(&query).await {
+            Ok(rates) => rates,
+            Err(e) => {
+                tracing::debug!("No {} -> {} quotes for a route leg: {}", from, to, e);
+                return None;
+            }
+        };
+        self.serve_quotes(&mut rates).await;
+
+        let quote = select_best_quote(&rates.rates, &self.provider_selection, amount)?;
+        Some(super::schema::RouteLegQuote {
+            from: query.from,
+            network_from: query.network_from,
+            to: query.to,
+            network_to: query.network_to,
+            amount,
+            provider: quote.provider.clone(),
+            estimated_amount: quote.estimated_amount,
+            rate: quote.rate,
+            total_fee: quote.total_fee,
+        })
+    }
+
+    /// Swap through an intermediate currency along the best two-leg plan.
+    /// The second leg is opened first so the first can pay out to its
+    /// deposit address; its amount is the first leg's estimate. Both legs
+    /// are ordinary swaps of the caller's and are tracked as such.
+    pub async fn create_route(
+        &self,
+        request: &super::schema::CreateRouteRequest,
+        user_id: Option<String>,
+    ) -> Result<super::schema::SwapRouteResponse, SwapError> {
+        let plan = self
+            .plan_routes(&request.plan_query())
+            .await?
+            .routes
+            .into_iter()
+            .find(|route| route.legs.len() == 2)
+            .ok_or_else(|| SwapError::NoRouteFound { from: request.from.clone(), to: request.to.clone() })?;
+        let (via, via_network) = (plan.via.clone().unwrap_or_default(), plan.via_network.clone().unwrap_or_default());
+        let (first_leg, second_leg) = (&plan.legs[0], &plan.legs[1]);
+
+        let second = self
+            .create_swap(
+                &super::schema::CreateSwapRequest {
+                    trade_id: None,
+                    from: via.clone(),
+                    network_from: via_network.clone(),
+                    to: request.to.clone(),
+                    network_to: request.network_to.clone(),
+                    amount: first_leg.estimated_amount,
+                    amount_to: None,
+                    provider: second_leg.provider.clone(),
+                    recipient_address: request.recipient_address.clone(),
+                    recipient_extra_id: request.recipient_extra_id.clone(),
+                    refund_address: None,
+                    refund_extra_id: None,
+                    rate_type: super::schema::RateType::Floating,
+                    sandbox: false,
+                    allow_fallback: true,
+                    dry_run: false,
+                    quote_id: None,
+                },
+                user_id.clone(),
+            )
+            .await?;
+
+        let first = self
+            .create_swap(
+                &super::schema::CreateSwapRequest {
+                    trade_id: None,
+                    from: request.from.clone(),
+                    network_from: request.network_from.clone(),
+                    to: via.clone(),
+                    network_to: via_network.clone(),
+                    amount: request.amount,
+                    amount_to: None,
+                    provider: first_leg.provider.clone(),
+                    recipient_address: second.deposit_address.clone(),
+                    recipient_extra_id: second.deposit_extra_id.clone(),
+                    refund_address: request.refund_address.clone(),
+                    refund_extra_id: request.refund_extra_id.clone(),
+                    rate_type: request.rate_type.clone(),
+                    sandbox: false,
+                    allow_fallback: true,
+                    dry_run: false,
+                    quote_id: None,
+                },
+                user_id.clone(),
+            )
+            .await;
+        let first = match first {
+            Ok(first) => first,
+            Err(e) => {
+                // Nothing will be deposited to it, so it expires like any abandoned swap
+                tracing::warn!("First leg of a route failed, leaving second leg {} to expire: {}", second.swap_id, e);
+                return Err(e);
+            }
+        };
+
+        let route_id = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            r#"
+            INSERT INTO swap_routes (
+                id, tenant_id, user_id, from_currency, from_network, via_currency, via_network,
+                to_currency, to_network, amount, estimated_receive, first_swap_id, second_swap_id, status
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&route_id)
+        .bind(self.tenant().as_str())
+        .bind(&user_id)
+        .bind(&request.from)
+        .bind(&request.network_from)
+        .bind(&via)
+        .bind(&via_network)
+        .bind(&request.to)
+        .bind(&request.network_to)
+        .bind(request.amount)
+        .bind(second.estimated_receive)
+        .bind(&first.swap_id)
+        .bind(&second.swap_id)
+        .bind(super::schema::RouteStatus::rollup(&first.status, &second.status))
+        .execute(&self.pool)
+        .await?;
+
+        tracing::info!(
+            route_id = %route_id,
+            "Route {} -> {} -> {} created with swaps {} and {}",
+            request.from,
+            via,
+            request.to,
+            first.swap_id,
+            second.swap_id
+        );
+
+        self.get_route(&route_id).await
+    }
+
+    /// A route with its legs' current statuses, storing the rolled-up status
+    /// when it changed
+    pub async fn get_route(&self, route_id: &str) -> Result<super::schema::SwapRouteResponse, SwapError> {
+        let mut query = sqlx::QueryBuilder::<MySql>::new(SWAP_ROUTE_SELECT);
+        query.push(" WHERE id = ").push_bind(route_id.to_string());
+        self.push_tenant_scope(&mut query);
+        let route = query
+            .build_query_as::<super::model::SwapRoute>()
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or(SwapError::RouteNotFound)?;
+
+        let (first, second) = tokio::try_join!(
+            self.get_swap_status(&route.first_swap_id),
+            self.get_swap_status(&route.second_swap_id),
+        )?;
+
+        let status = super::schema::RouteStatus::rollup(&first.status, &second.status);
+        if status != route.status {
+            sqlx::query("UPDATE swap_routes SET status = ? WHERE id = ?")
+                .bind(status)
+                .bind(&route.id)
+                .execute(&self.pool)
+                .await?;
+        }
+
+        Ok(super::schema::SwapRouteResponse {
+            route_id: route.id,
+            status,
+            from: route.from_currency,
+            network_from: route.from_network,
+            via: route.via_currency,
+            via_network: route.via_network,
+            to: route.to_currency,
+            network_to: route.to_network,
+            amount: route.amount,
+            estimated_receive: route.estimated_receive,
+            deposit_address: first.deposit_address.clone(),
+            deposit_extra_id: first.deposit_extra_id.clone(),
+            legs: vec![first, second],
+            created_at: route.created_at,
+            updated_at: route.updated_at,
+        })
+    }
+
+    // =========================================================================
+    // DRY RUN
+    // =========================================================================
+
+    /// Run every check create_swap would, plus the recipient address and the
+    /// quote's amount limits, and report the swap it would create from the
+    /// current quote. No trade is opened and nothing is written.
+    pub async fn preview_swap(
+        &self,
+        request: &super::schema::CreateSwapRequest,
+    ) -> Result<super::schema::SwapPreviewResponse, SwapError> {
+        let resolved = self.resolve_amount_to(request).await?;
+        let request = &resolved;
+        self.check_swap_request(request).await?;
+
+        let recipient = self
+            .validate_address(&super::schema::ValidateAddressRequest {
+                ticker: request.to.clone(),
+                network: request.network_to.clone(),
+                address: request.recipient_address.trim().to_string(),
+            })
+            .await?;
+        if !recipient.valid {
+            return Err(SwapError::InvalidAddress);
+        }
+
+        let mut rates = self
+            .get_rates_cached(&super::schema::RatesQuery {
+                from: request.from.clone(),
+                network_from: request.network_from.clone(),
+                to: request.to.clone(),
+                network_to: request.network_to.clone(),
+                amount: request.amount,
+                amount_to: request.amount_to,
+                max_kyc_rating: None,
+                rate_type: Some(request.rate_type.clone()),
+                provider: None,
+                sandbox: request.sandbox,
+            })
+            .await?;
+        if rates.rates.is_empty() && !rates.meta.timed_out.is_empty() {
+            return Err(SwapError::ExternalApiError("Quotes did not arrive in time".to_string()));
+        }
+
+        self.serve_quotes(&mut rates).await;
+
+        // Rates are sorted best-first with demoted providers last
+        let quotes: Vec<&super::schema::RateResponse> = rates.rates.iter().collect();
+        let requested = if request.wants_best_provider() {
+            Some(select_best_quote(&rates.rates, &self.provider_selection, request.amount).ok_or(SwapError::NoQuoteMatchesPolicy)?)
+        } else {
+            quotes.iter().find(|r| r.provider.eq_ignore_ascii_case(&request.provider)).copied()
+        };
+        let (quote, fallback_from) = match requested {
+            Some(quote) => (quote, None),
+            None if request.allow_fallback => {
+                let best = quotes.iter().find(|r| !r.demoted).or(quotes.first()).ok_or(SwapError::PairNotAvailable)?;
+                (*best, Some(request.provider.clone()))
+            }
+            None if quotes.is_empty() => return Err(SwapError::PairNotAvailable),
+            None => return Err(SwapError::ProviderNotQuoting(request.provider.clone())),
+        };
+
+        let above_max = quote.max_amount > Decimal::ZERO && request.amount > quote.max_amount;
+        if request.amount < quote.min_amount || above_max {
+            return Err(SwapError::AmountOutOfRange { min: quote.min_amount, max: quote.max_amount });
+        }
+
+        Ok(super::schema::SwapPreviewResponse {
+            dry_run: true,
+            provider: quote.provider.clone(),
+            from: request.from.clone(),
+            network_from: request.network_from.clone(),
+            to: request.to.clone(),
+            network_to: request.network_to.clone(),
+            amount: request.amount,
+            estimated_receive: quote.estimated_amount,
+            rate: quote.rate,
+            rate_type: request.rate_type.clone(),
+            network_fee: quote.network_fee,
+            provider_fee: quote.provider_fee,
+            platform_fee: quote.platform_fee,
+            total_fee: quote.total_fee,
+            min_amount: quote.min_amount,
+            max_amount: quote.max_amount,
+            kyc_rating: quote.kyc_rating.clone(),
+            eta_minutes: quote.eta_minutes,
+            quote_id: rates.trade_id.clone(),
+            quote_stale: request.trade_id.as_deref().is_some_and(|id| id != rates.trade_id),
+            fallback_from,
+        })
+    }
+
+    // =========================================================================
+    // RETRY SWAP
+    // =========================================================================
+
+    /// Re-create a failed or expired swap from its stored parameters using a
+    /// fresh quote. Swaps owned by a user can only be retried by that user.
+    pub async fn retry_swap(
+        &self,
+        swap_id: &str,
+        user_id: Option<String>,
+        options: &super::schema::RetrySwapRequest,
+    ) -> Result<super::schema::CreateSwapResponse, SwapError> {
+        let swap = self.find_swap(swap_id).await?.ok_or(SwapError::SwapNotFound)?;
+
+        if swap.user_id.is_some() && swap.user_id != user_id {
+            return Err(SwapError::SwapNotFound);
+        }
+
+        if !matches!(swap.status, super::schema::SwapStatus::Failed | super::schema::SwapStatus::Expired) {
+            return Err(SwapError::SwapNotRetryable(swap.status));
+        }
+
+        if let Some(retry_id) = self.find_retry_of(swap_id).await? {
+            return Err(SwapError::AlreadyRetried(retry_id));
+        }
+
+        // Claim the original before calling the provider, so a concurrent
+        // retry of the same swap is refused instead of opening a second trade
+        let claimed = sqlx::query(
+            r#"
+            UPDATE swaps SET retry_claimed_at = NOW()
+            WHERE id = ? AND (retry_claimed_at IS NULL OR retry_claimed_at < NOW() - INTERVAL 5 MINUTE)
+            "#,
+        )
+        .bind(swap_id)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+        if claimed == 0 {
+            return Err(match self.find_retry_of(swap_id).await? {
+                Some(retry_id) => SwapError::AlreadyRetried(retry_id),
+                None => SwapError::RetryInProgress(swap_id.to_string()),
+            });
+        }
+
+        let result = self.create_retry(swap, swap_id, options).await;
+        if result.is_err() {
+            // Nothing was created, so the swap may be retried again
+            if let Err(e) = sqlx::query("UPDATE swaps SET retry_claimed_at = NULL WHERE id = ?")
+                .bind(swap_id)
+                .execute(&self.pool)
+                .await
+            {
+                tracing::warn!("Failed to release retry claim on swap {}: {}", swap_id, e);
+            }
+        }
+        result
+    }
+
+    /// Id of the swap that retried `swap_id`, if any
+    async fn find_retry_of(&self, swap_id: &str) -> Result<Option<String>, SwapError> {
+        let existing: Option<(String,)> = sqlx::query_as("SELECT id FROM swaps WHERE retried_from = ? LIMIT 1")
+            .bind(swap_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(existing.map(|(id,)| id))
+    }
+
+    /// Quote and create the replacement for a claimed swap
+    async fn create_retry(
+        &self,
+        swap: super::model::Swap,
+        swap_id: &str,
+        options: &super::schema::RetrySwapRequest,
+    ) -> Result<super::schema::CreateSwapResponse, SwapError> {
+        let rates = self
+            .get_rates_optimized(&super::schema::RatesQuery {
+                from: swap.from_currency.clone(),
+                network_from: swap.from_network.clone(),
+                to: swap.to_currency.clone(),
+                network_to: swap.to_network.clone(),
+                amount: swap.amount,
+                amount_to: None,
+                max_kyc_rating: None,
+                rate_type: Some(swap.rate_type.clone()),
+                provider: None,
+                sandbox: swap.is_sandbox,
+            })
+            .await?;
+
+        // Rates are sorted best-first with demoted providers last
+        let same_provider = |r: &&super::schema::RateResponse| r.provider.eq_ignore_ascii_case(&swap.provider_id);
+        let quote = if options.switch_provider {
+            rates.rates.iter().find(|r| !same_provider(r))
+        } else {
+            rates.rates.iter().find(same_provider).or_else(|| rates.rates.first())
+        }
+        .ok_or(SwapError::PairNotAvailable)?;
+
+        let request = super::schema::CreateSwapRequest {
+            trade_id: Some(rates.trade_id.clone()),
+            from: swap.from_currency,
+            network_from: swap.from_network,
+            to: swap.to_currency,
+            network_to: swap.to_network,
+            amount: swap.amount,
+            amount_to: None,
+            provider: quote.provider.clone(),
+            recipient_address: swap.recipient_address,
+            recipient_extra_id: swap.recipient_extra_id,
+            refund_address: swap.refund_address,
+            refund_extra_id: swap.refund_extra_id,
+            rate_type: swap.rate_type,
+            sandbox: swap.is_sandbox,
+            allow_fallback: false,
+            dry_run: false,
+            quote_id: None,
+        };
+
+        self.create_swap_linked(&request, swap.user_id, Some(swap_id), None).await
+    }
+
+    /// Track a swap created outside the platform: fetch the trade from the
+    /// provider and store it for `user_id`, flagged as imported. From then on
+    /// it is polled, listed in history and notified on like any other swap.
+    pub async fn import_swap(
+        &self,
+        request: &super::schema::ImportSwapRequest,
+        user_id: &str,
+    ) -> Result<super::schema::SwapStatusResponse, SwapError> {
+        let trade_id = request.trade_id.trim();
+        let provider = request.provider.trim();
+        if trade_id.is_empty() || trade_id.len() > 100 {
+            return Err(SwapError::InvalidImport("trade_id must be 1-100 characters".to_string()));
+        }
+        if provider.is_empty() {
+            return Err(SwapError::InvalidImport("provider is required".to_string()));
+        }
+
+        if self.find_swap_by_provider_swap_id(trade_id).await?.is_some() {
+            return Err(SwapError::TradeAlreadyTracked(trade_id.to_string()));
+        }
+
+        // An API error means the provider does not know the trade; anything
+        // else (transport, parsing) is our problem and surfaces as such
+        let trocador_client = self.trocador()?;
+        let (trade, raw_trade) = self
+            .call_with_retry(TROCADOR, || async {
+                match trocador_client.get_trade_status(trade_id).await {
+                    Err(TrocadorError::ApiError(e)) => Ok(Err(e)),
+                    other => other.map(Ok),
+                }
+            })
+            .await?
+            .map_err(|e| {
+                tracing::info!("Import of trade {} refused by provider: {}", trade_id, e);
+                SwapError::TradeNotFound(trade_id.to_string())
+            })?;
+
+        // The trade must be the named provider's, so a guessed id gets nothing
+        if !trade.provider.eq_ignore_ascii_case(provider) {
+            return Err(SwapError::TradeNotFound(trade_id.to_string()));
+        }
+
+        let status = SwapStateMachine::map_provider_status(&trade.status);
+        let swap_id = uuid::Uuid::new_v4().to_string();
+        let rate = money::rate(trade.amount_to, trade.amount_from);
+
+        sqlx::query(
+            r#"
+            INSERT INTO swaps (
+                id, tenant_id, user_id, brand, provider_id, provider_swap_id,
+                from_currency, from_network, to_currency, to_network,
+                amount, estimated_receive, rate, platform_fee, total_fee,
+                deposit_address, deposit_extra_id,
+                recipient_address, recipient_extra_id,
+                refund_address, refund_extra_id,
+                status, rate_type, is_sandbox, imported,
+                completed_at, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0, 0, ?, ?, ?, ?, ?, ?, ?, 'floating', FALSE, TRUE,
+                    IF(? = 'completed', NOW(), NULL), NOW(), NOW())
+            "#
+        )
+        .bind(&swap_id)
+        .bind(self.tenant().as_str())
+        .bind(user_id)
+        .bind(self.brand.stored_slug())
+        .bind(&trade.provider)
+        .bind(&trade.trade_id)
+        .bind(trade.ticker_from.to_lowercase())
+        .bind(&trade.network_from)
+        .bind(trade.ticker_to.to_lowercase())
+        .bind(&trade.network_to)
+        .bind(trade.amount_from)
+        .bind(trade.amount_to)
+        .bind(rate)
+        .bind(&trade.address_provider)
+        .bind(&trade.address_provider_memo)
+        .bind(&trade.address_user)
+        .bind(&trade.address_user_memo)
+        .bind(&trade.refund_address)
+        .bind(&trade.refund_address_memo)
+        .bind(&status)
+        .bind(&status)
+        .execute(&self.pool)
+        .await?;
+
+        self.record_transition(&swap_id, None, &status, StatusSource::Import, Some("Imported from the provider".to_string()))
+            .await?;
+        self.store_provider_payload(&swap_id, super::schema::ProviderCallType::TradeStatus, &raw_trade)
+            .await;
+
+        self.outbox
+            .record(
+                DomainEventType::SwapCreated,
+                &swap_id,
+                serde_json::json!({
+                    "swap_id": swap_id,
+                    "tenant": self.tenant(),
+                    "user_id": user_id,
+                    "brand": self.brand.stored_slug(),
+                    "provider": trade.provider,
+                    "from": trade.ticker_from,
+                    "network_from": trade.network_from,
+                    "to": trade.ticker_to,
+                    "network_to": trade.network_to,
+                    "amount": trade.amount_from,
+                    "estimated_receive": trade.amount_to,
+                    "status": status,
+                    "imported": true,
+                }),
+            )
+            .await;
+
+        let swap = self.find_swap(&swap_id).await?.ok_or(SwapError::SwapNotFound)?;
+        Ok(super::schema::SwapStatusResponse::from(swap))
+    }
+
+    /// Load a full swap row; swaps of other tenants are not found
+    pub async fn find_swap(&self, swap_id: &str) -> Result<Option<super::model::Swap>, SwapError> {
+        retry_transient(&self.db_retry, "find swap", || {
+            let mut query = sqlx::QueryBuilder::<MySql>::new(SWAP_SELECT);
+            query.push(" WHERE id = ").push_bind(swap_id.to_string());
+            self.push_tenant_scope(&mut query);
+            async move { query.build_query_as::<super::model::Swap>().fetch_optional(&self.pool).await }
+        })
+        .await
+        .map_err(SwapError::Database)
+    }
+
+    /// Load a swap by the provider's trade id
+    pub async fn find_swap_by_provider_swap_id(
+        &self,
+        provider_swap_id: &str,
+    ) -> Result<Option<super::model::Swap>, SwapError> {
+        sqlx::query_as::<_, super::model::Swap>(&format!("{} WHERE provider_swap_id = ?", SWAP_SELECT))
+            .bind(provider_swap_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(SwapError::Database)
+    }
+
+    /// Reject currencies whose scheduled delisting date has passed or that an
+    /// admin disabled, and those Trocador no longer lists as if unknown
+    async fn ensure_not_delisted(&self, ticker: &str, network: &str) -> Result<(), SwapError> {
+        let flags: Option<(bool, bool)> = sqlx::query_as(
+            "SELECT admin_disabled = TRUE OR (delisting_at IS NOT NULL AND delisting_at <= NOW()), is_active
+             FROM currencies
+             WHERE LOWER(symbol) = LOWER(?) AND network = ?
+             LIMIT 1"
+        )
+        .bind(ticker)
+        .bind(network)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match flags {
+            Some((true, _)) => Err(SwapError::CurrencyDelisted(ticker.to_string())),
+            Some((false, false)) => Err(SwapError::CurrencyNotFound),
+            _ => Ok(()),
+        }
+    }
+
+    /// Places amounts of a currency are kept to; DEFAULT_DECIMALS when it
+    /// isn't listed or can't be read
+    async fn currency_decimals(&self, ticker: &str, network: &str) -> u32 {
+        let cache_key = format!("currency_decimals:{}:{}", ticker.to_lowercase(), network);
+        if let Some(service) = &self.redis_service {
+            if let Ok(Some(decimals)) = service.get_json::<u32>(&cache_key).await {
+                return decimals;
+            }
+        }
+
+        let decimals: Option<i32> = match sqlx::query_scalar(
+            "SELECT decimals FROM currencies WHERE LOWER(symbol) = LOWER(?) AND network = ? LIMIT 1",
+        )
+        .bind(ticker)
+        .bind(network)
+        .fetch_optional(&self.pool)
+        .await
+        {
+            Ok(decimals) => decimals,
+            Err(e) => {
+                tracing::warn!("Failed to load decimals of {} on {}: {}", ticker, network, e);
+                return money::DEFAULT_DECIMALS;
+            }
+        };
+        let decimals = decimals.map_or(money::DEFAULT_DECIMALS, money::scale_of);
+
+        if let Some(service) = &self.redis_service {
+            let _ = service.set_json(&cache_key, &decimals, CURRENCY_DECIMALS_CACHE_SECS).await;
+        }
+        decimals
+    }
+
+    /// Refuse an amount with more places than its currency keeps
+    async fn check_amount_decimals(
+        &self,
+        field: &str,
+        ticker: &str,
+        network: &str,
+        amount: Decimal,
+    ) -> Result<(), SwapError> {
+        check_decimals(field, ticker, amount, self.currency_decimals(ticker, network).await)
+    }
+
+    /// Look up a single currency/network row
+    async fn find_currency(&self, ticker: &str, network: &str) -> Result<Option<Currency>, SwapError> {
+        sqlx::query_as::<_, Currency>(
+            "SELECT id, symbol, name, network, is_active, delisting_at, logo_url, contract_address,
+                    decimals, requires_extra_id, extra_id_name, requires_refund_address, min_amount, max_amount,
+                    last_synced_at, created_at, updated_at
+             FROM currencies
+             WHERE LOWER(symbol) = LOWER(?) AND network = ?
+             LIMIT 1"
+        )
+        .bind(ticker)
+        .bind(network)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(SwapError::Database)
+    }
+
+    /// Validate a supplied refund address with the provider's rules (refunds
+    /// go back on the source network); the policy requiring one is checked
+    /// in check_request_currencies
+    async fn check_refund_address(&self, request: &super::schema::CreateSwapRequest) -> Result<(), SwapError> {
+        let Some(refund_address) = non_empty(request.refund_address.as_deref()) else {
+            return Ok(());
+        };
+
+        let validation = self
+            .validate_address(&super::schema::ValidateAddressRequest {
+                ticker: request.from.clone(),
+                network: request.network_from.clone(),
+                address: refund_address.to_string(),
+            })
+            .await?;
+
+        if validation.valid {
+            Ok(())
+        } else {
+            Err(SwapError::InvalidRefundAddress)
+        }
+    }
+
+    // =========================================================================
+    // SWAP STATUS
+    // =========================================================================
+
+    /// Get swap status by ID
+    /// 1. Look up swap in database by local swap_id
+    /// 2. Get provider_swap_id (Trocador's trade_id)
+    /// 3. Call Trocador API to get latest status
+    /// 4. Update local database with new status
+    /// 5. Return status to user (and refresh the status cache)
+    pub async fn get_swap_status(
+        &self,
+        swap_id: &str,
+    ) -> Result<super::schema::SwapStatusResponse, SwapError> {
+        let response = self.fetch_swap_status(swap_id).await?;
+        self.cache_swap_status(&response).await;
+        Ok(response)
+    }
+
+    /// Status without polling the provider: the status cache, then the stored
+    /// row. Used while maintenance mode is on.
+    pub async fn get_stored_swap_status(
+        &self,
+        swap_id: &str,
+    ) -> Result<super::schema::SwapStatusResponse, SwapError> {
+        if let Some(cached) = self.cached_swap_status(swap_id).await {
+            return Ok(cached);
+        }
+
+        let swap = self.find_swap(swap_id).await?.ok_or(SwapError::SwapNotFound)?;
+        let response = super::schema::SwapStatusResponse::from(swap);
+        self.cache_swap_status(&response).await;
+        Ok(response)
+    }
+
+    /// Signed link to the redacted status view of a swap. Swaps linked to an
+    /// account can only be shared by that account.
+    pub async fn create_share_link(
+        &self,
+        swap_id: &str,
+        user_id: Option<&str>,
+        config: &ShareLinkConfig,
+        options: &super::schema::CreateShareLinkRequest,
+    ) -> Result<super::schema::ShareLinkResponse, SwapError> {
+        let secret = config.secret.as_deref().ok_or(SwapError::SharingDisabled)?;
+
+        let swap = self.find_swap(swap_id).await?.ok_or(SwapError::SwapNotFound)?;
+        if swap.user_id.is_some() && swap.user_id.as_deref() != user_id {
+            return Err(SwapError::SwapNotFound);
+        }
+
+        let hours = options
+            .expires_in_hours
+            .unwrap_or(config.default_ttl_hours)
+            .clamp(1, config.max_ttl_hours.max(1));
+        let expires_at = Utc::now() + chrono::Duration::hours(hours as i64);
+
+        Ok(super::schema::ShareLinkResponse {
+            url: super::share::share_path(secret, &swap.id, expires_at),
+            expires_at,
+        })
+    }
+
+    // =========================================================================
+    // BACKGROUND POLLING
+    // =========================================================================
+
+    /// In-flight swaps with a provider trade, least recently polled first,
+    /// skipping any polled within `min_age`
+    pub async fn swaps_due_for_poll(
+        &self,
+        min_age: Duration,
+        high_value_min_age: Duration,
+        limit: u32,
+    ) -> Result<Vec<(String, super::schema::SwapStatus)>, SwapError> {
+        // High-value swaps come due sooner and go first
+        sqlx::query_as(
+            "SELECT id, status FROM swaps
+             WHERE status IN ('waiting', 'confirming', 'exchanging', 'sending')
+               AND provider_swap_id IS NOT NULL
+               AND (last_polled_at IS NULL OR last_polled_at < NOW() - INTERVAL IF(high_value, ?, ?) SECOND)
+             ORDER BY high_value DESC, last_polled_at ASC, created_at ASC
+             LIMIT ?",
+        )
+        .bind(high_value_min_age.as_secs())
+        .bind(min_age.as_secs())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(SwapError::Database)
+    }
+
+    /// Refresh one swap from the provider, as `get_swap_status` does, and
+    /// note the poll so the swap goes to the back of the queue
+    pub async fn poll_swap_status(
+        &self,
+        swap_id: &str,
+    ) -> Result<super::schema::SwapStatusResponse, SwapError> {
+        // updated_at is kept as is; only status changes should move it
+        sqlx::query("UPDATE swaps SET last_polled_at = NOW(), updated_at = updated_at WHERE id = ?")
+            .bind(swap_id)
+            .execute(&self.pool)
+            .await?;
+
+        self.get_swap_status(swap_id).await
+    }
+
+    /// Raise an alert for each funded high-value swap that has kept its status
+    /// for `stall_after`. A swap is alerted on once per stall: a later status
+    /// change re-arms it. Returns how many alerts were raised.
+    pub async fn report_stalled_high_value_swaps(&self, stall_after: Duration, limit: u32) -> Result<u64, SwapError> {
+        let stalled: Vec<super::model::StalledSwap> = sqlx::query_as(
+            "SELECT id, brand, provider_id, status, usd_value, updated_at FROM swaps
+             WHERE high_value = TRUE
+               AND status IN ('confirming', 'exchanging', 'sending')
+               AND updated_at < NOW() - INTERVAL ? SECOND
+               AND (stall_alerted_at IS NULL OR stall_alerted_at < updated_at)
+             ORDER BY updated_at ASC
+             LIMIT ?",
+        )
+        .bind(stall_after.as_secs())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut alerted = 0;
+        for swap in stalled {
+            // Claim the alert first so a concurrent pass can't raise it twice
+            let claimed = sqlx::query(
+                "UPDATE swaps SET stall_alerted_at = NOW(), updated_at = updated_at
+                 WHERE id = ? AND (stall_alerted_at IS NULL OR stall_alerted_at < updated_at)",
+            )
+            .bind(&swap.id)
+            .execute(&self.pool)
+            .await?;
+            if claimed.rows_affected() == 0 {
+                continue;
+            }
+
+            let stalled_minutes = (Utc::now() - swap.updated_at).num_minutes();
+            tracing::error!(
+                target: "alert",
+                swap_id = %swap.id,
+                provider = %swap.provider_id,
+                status = ?swap.status,
+                usd_value = ?swap.usd_value,
+                stalled_minutes,
+                "High-value swap stalled"
+            );
+
+            self.outbox
+                .record(
+                    DomainEventType::AlertRaised,
+                    &swap.id,
+                    serde_json::json!({
+                        "alert": "high_value_swap_stalled",
+                        "severity": "warning",
+                        "swap_id": swap.id,
+                        "brand": swap.brand,
+                        "provider": swap.provider_id,
+                        "status": swap.status,
+                        "usd_value": swap.usd_value,
+                        "stalled_since": swap.updated_at,
+                        "stalled_minutes": stalled_minutes,
+                    }),
+                )
+                .await;
+            alerted += 1;
+        }
+
+        Ok(alerted)
+    }
+
+    /// Mark waiting swaps that never received a deposit as expired: past their
+    /// `expires_at`, or older than `abandon_after` when no expiry was stored.
+    /// Returns how many were expired by this call.
+    pub async fn expire_abandoned_swaps(
+        &self,
+        abandon_after: Duration,
+        limit: u32,
+    ) -> Result<u64, SwapError> {
+        let swaps = sqlx::query_as::<_, super::model::Swap>(&format!(
+            "{} WHERE status = 'waiting' AND imported = FALSE
+               AND COALESCE(expires_at, created_at + INTERVAL ? SECOND) < NOW()
+             ORDER BY created_at ASC
+             LIMIT ?",
+            SWAP_SELECT
+        ))
+        .bind(abandon_after.as_secs())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut expired = 0;
+        for swap in swaps {
+            if let StatusUpdate::Applied(updated) = self
+                .apply_status_change(&swap, &super::schema::SwapStatus::Expired, None, StatusSource::Expiry)
+                .await?
+            {
+                self.cache_swap_status(&super::schema::SwapStatusResponse::from(updated)).await;
+                expired += 1;
+            }
+        }
+
+        Ok(expired)
+    }
+
+    async fn fetch_swap_status(
+        &self,
+        swap_id: &str,
+    ) -> Result<super::schema::SwapStatusResponse, SwapError> {
+        // 1. Get swap from database
+        let mut swap = self.find_swap(swap_id).await?.ok_or(SwapError::SwapNotFound)?;
+
+        // 2. If we have a provider_swap_id, fetch latest status from Trocador
+        if let Some(trocador_id) = swap.provider_swap_id.clone() {
+            let trade_status = if swap.is_sandbox {
+                // Simulated; a sandbox trade is unknown to Trocador
+                let sandbox = MockProviderClient::new(&SandboxConfig::from_env(), self.high_value.usd_prices.clone());
+                Ok(sandbox.trade_status(&swap))
+            } else {
+                // Call Trocador API with retry logic
+                let trocador_client = self.trocador()?;
+                self.call_with_retry(TROCADOR, || async { trocador_client.get_trade_status(&trocador_id).await })
+                    .await
+            };
+
+            match trade_status {
+                Ok((trocador_status, raw_status)) => {
+                    // 3. Map Trocador status to our internal status
+                    let new_status = SwapStateMachine::map_provider_status(&trocador_status.status);
+                    let report = super::schema::TransactionReport::from(&trocador_status);
+                    let transactions_changed = self.record_transactions(&mut swap, &report).await;
+                    if new_status == super::schema::SwapStatus::Refunded {
+                        let report = super::schema::RefundReport::from(&trocador_status);
+                        self.record_refund(&swap, &report, super::schema::RefundSource::Poll).await;
+                    }
+                    if SwapStateMachine::is_underpaid(&trocador_status.status) && new_status != swap.status {
+                        self.check_underpaid_deposit(&swap);
+                    }
+
+                    // 4. Update database if status changed
+                    if new_status == swap.status {
+                        let mut response = super::schema::SwapStatusResponse::from(swap);
+                        response.actual_receive = Some(trocador_status.amount_to);
+                        if transactions_changed {
+                            self.publish_swap_status(&response).await;
+                        }
+                        return Ok(response);
+                    }
+
+                    self.store_provider_payload(swap_id, super::schema::ProviderCallType::TradeStatus, &raw_status)
+                        .await;
+
+                    // 5. Return updated status (or the newer one a concurrent writer stored)
+                    let current = match self
+                        .apply_status_change(&swap, &new_status, Some(trocador_status.amount_to), StatusSource::Poll)
+                        .await?
+                    {
+                        StatusUpdate::Applied(updated)
+                        | StatusUpdate::Superseded(updated)
+                        | StatusUpdate::Refused(updated) => updated,
+                    };
+                    return Ok(super::schema::SwapStatusResponse::from(current));
+                }
+                Err(e) => {
+                    // If Trocador API fails, return cached status from database
+                    tracing::warn!("Failed to fetch status from Trocador for swap {}: {}", swap_id, e);
+                }
+            }
+        }
+
+        // 6. Return status from database (if no provider_swap_id or Trocador call failed)
+        Ok(super::schema::SwapStatusResponse::from(swap))
+    }
+
+    /// Record a status change the state machine allows: compare-and-swap the
+    /// row with its history, then funnel events and the outbox event when
+    /// our write landed
+    pub(super) async fn apply_status_change(
+        &self,
+        swap: &super::model::Swap,
+        new_status: &super::schema::SwapStatus,
+        amount_to: Option<Decimal>,
+        source: StatusSource,
+    ) -> Result<StatusUpdate, SwapError> {
+        let deposit_lost = match SwapStateMachine::transition(&swap.status, new_status, source) {
+            Ok(Transition::Unchanged) => return Ok(StatusUpdate::Superseded(swap.clone())),
+            Ok(transition) => transition == Transition::DepositLost,
+            Err(illegal) => {
+                tracing::warn!("Ignoring status for swap {}: {}", swap.id, illegal);
+                return Ok(StatusUpdate::Refused(swap.clone()));
+            }
+        };
+
+        let message = deposit_lost.then(|| DEPOSIT_LOST_MESSAGE.to_string());
+        let update = self.update_swap_status(swap, new_status, amount_to, source, message).await?;
+
+        let StatusUpdate::Applied(updated) = &update else {
+            // A concurrent writer already recorded this or a later status
+            return Ok(update);
+        };
+
+        if deposit_lost {
+            self.report_lost_deposit(swap).await;
+        }
+
+        self.track_status_change(swap, new_status);
+        metrics().swap_status_changes.inc(new_status.as_str());
+        if let Some(outcome) = SwapOutcome::from_status(new_status).filter(|_| !swap.is_sandbox) {
+            let took = updated.completed_at.map(|at| (at - swap.created_at).num_seconds().max(0) as u64);
+            self.record_provider_outcome(&swap.provider_id, outcome, took).await;
+        }
+        self.publish_swap_status(&super::schema::SwapStatusResponse::from(updated.clone())).await;
+
+        self.outbox
+            .record(
+                DomainEventType::SwapStatusChanged,
+                &swap.id,
+                serde_json::json!({
+                    "swap_id": swap.id,
+                    "brand": swap.brand,
+                    "from_status": swap.status,
+                    "status": new_status,
+                    "amount_to": amount_to,
+                    "version": updated.version,
+                }),
+            )
+            .await;
+
+        if let Some(brand) = &swap.brand {
+            brand_webhooks::enqueue_swap_status(
+                &self.pool,
+                brand,
+                &swap.id,
+                &serde_json::json!({
+                    "event": brand_webhooks::SWAP_STATUS_EVENT,
+                    "swap_id": swap.id,
+                    "from_status": swap.status,
+                    "status": new_status,
+                    "amount_to": amount_to,
+                    "occurred_at": Utc::now(),
+                }),
+            )
+            .await;
+        }
+
+        if let (Some(user_id), Some(notice)) = (&swap.user_id, notifications::swap_status_notice(swap, new_status)) {
+            self.notify_user(user_id, swap, &notice).await;
+        }
+
+        Ok(update)
+    }
+
+    /// Queue a notification for the swap's owner; failures are logged only
+    async fn notify_user(&self, user_id: &str, swap: &super::model::Swap, notice: &notifications::Notice) {
+        let result = notifications::enqueue(&self.pool, user_id, Some(&swap.id), swap.brand.as_deref(), notice).await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to queue {} notification for swap {}: {}", notice.kind, swap.id, e);
+        }
+    }
+
+    /// Tell the swap's owner their deposit is gone and raise an internal alert
+    async fn report_lost_deposit(&self, swap: &super::model::Swap) {
+        tracing::error!(
+            target: "alert",
+            swap_id = %swap.id,
+            provider = %swap.provider_id,
+            from_status = ?swap.status,
+            "Swap deposit disappeared, reverted to waiting"
+        );
+
+        self.outbox
+            .record(
+                DomainEventType::AlertRaised,
+                &swap.id,
+                serde_json::json!({
+                    "alert": "deposit_lost",
+                    "severity": "critical",
+                    "swap_id": swap.id,
+                    "brand": swap.brand,
+                    "provider": swap.provider_id,
+                    "from_status": swap.status,
+                    "from_currency": swap.from_currency,
+                    "from_network": swap.from_network,
+                    "amount": swap.amount,
+                    "tx_hash_in": swap.tx_hash_in,
+                }),
+            )
+            .await;
+
+        let Some(user_id) = &swap.user_id else {
+            return;
+        };
+        let body = format!(
+            "The {} {} deposit for swap {} is no longer confirmed on the {} network. This happens when a \
+             transaction is dropped by a chain reorganization or replaced by another one. The swap is back to \
+             waiting for a deposit; if you did not replace the transaction, contact support.",
+            swap.amount,
+            swap.from_currency.to_uppercase(),
+            swap.id,
+            swap.from_network
+        );
+        let notice = notifications::Notice {
+            kind: "deposit_lost".to_string(),
+            title: "Your swap deposit is no longer confirmed".to_string(),
+            body,
+        };
+        self.notify_user(user_id, swap, &notice).await;
+    }
+
+    /// Funnel events for a status transition seen while polling the provider
+    fn track_status_change(&self, swap: &super::model::Swap, new_status: &super::schema::SwapStatus) {
+        use super::schema::SwapStatus;
+
+        let properties = serde_json::json!({
+            "provider": swap.provider_id,
+            "from_status": swap.status,
+            "status": new_status,
+        });
+
+        // Status polls are often anonymous; attribute them to the swap's owner
+        let mut context = self.analytics_context.clone();
+        if context.user_id.is_none() {
+            context.user_id = swap.user_id.clone();
+        }
+        context.tenant = TenantId::new(&swap.tenant_id).unwrap_or_default();
+
+        let deposit_seen = !matches!(
+            new_status,
+            SwapStatus::Waiting | SwapStatus::Failed | SwapStatus::Expired
+        );
+        if swap.status == SwapStatus::Waiting && deposit_seen {
+            self.analytics.track(FunnelEvent::DepositDetected, Some(&swap.id), &context, properties.clone());
+        }
+
+        let event = match new_status {
+            SwapStatus::Completed => FunnelEvent::Completed,
+            SwapStatus::Failed | SwapStatus::Expired => FunnelEvent::Failed,
+            _ => return,
+        };
+        self.analytics.track(event, Some(&swap.id), &context, properties);
+    }
+
+    // =========================================================================
+    // PROVIDER PAYLOADS
+    // =========================================================================
+
+    /// Keep a compressed copy of a provider response; failures are logged, never surfaced
+    async fn store_provider_payload(&self, swap_id: &str, call_type: super::schema::ProviderCallType, raw: &str) {
+        let payload = match crate::services::payload_codec::encode(raw) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::warn!("Failed to compress provider payload for swap {}: {}", swap_id, e);
+                return;
+            }
+        };
+
+        let result = sqlx::query(
+            "INSERT INTO swap_provider_payloads (swap_id, call_type, payload_gz, original_bytes, truncated)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(swap_id)
+        .bind(call_type)
+        .bind(&payload.compressed)
+        .bind(payload.original_bytes as u32)
+        .bind(payload.truncated)
+        .execute(&self.pool)
+        .await;
+
+        if let Err(e) = result {
+            tracing::warn!("Failed to store provider payload for swap {}: {}", swap_id, e);
+        }
+    }
+
+    // =========================================================================
+    // HISTORY
+    // =========================================================================
+
+    /// A page of the user's swaps, newest first. Pages are keyed on
+    /// (created_at, id) so swaps created while paging don't shift later pages.
+    pub async fn get_user_swaps(
+        &self,
+        user_id: &str,
+        query: &super::schema::HistoryQuery,
+    ) -> Result<super::schema::SwapHistoryResponse, SwapError> {
+        let limit = query.limit.clamp(1, MAX_HISTORY_LIMIT);
+        let filters = HistoryFilters::parse(query)?;
+        let cursor = query.cursor.as_deref().map(decode_history_cursor).transpose()?;
+
+        let mut count = sqlx::QueryBuilder::<sqlx::MySql>::new("SELECT COUNT(*) FROM swaps");
+        filters.push_where(&mut count, user_id);
+        self.push_tenant_scope(&mut count);
+        let total: i64 = count
+            .build_query_scalar()
+            .fetch_one(&self.pool)
+            .await?;
+
+        let mut page = sqlx::QueryBuilder::<sqlx::MySql>::new(SWAP_SELECT);
+        filters.push_where(&mut page, user_id);
+        self.push_tenant_scope(&mut page);
+        if let Some((created_at, id)) = &cursor {
+            page.push(" AND (created_at < ")
+                .push_bind(*created_at)
+                .push(" OR (created_at = ")
+                .push_bind(*created_at)
+                .push(" AND id < ")
+                .push_bind(id.clone())
+                .push("))");
+        }
+        // One extra row tells us whether another page exists
+        page.push(" ORDER BY created_at DESC, id DESC LIMIT ").push_bind(limit + 1);
+
+        let mut swaps: Vec<super::model::Swap> = page
+            .build_query_as()
+            .fetch_all(&self.pool)
+            .await?;
+
+        let next_cursor = if swaps.len() > limit as usize {
+            swaps.truncate(limit as usize);
+            swaps.last().map(|last| encode_history_cursor(last.created_at, &last.id))
+        } else {
+            None
+        };
+
+        Ok(super::schema::SwapHistoryResponse {
+            swaps: self.with_fiat_values(swaps).await,
+            limit,
+            total: total.max(0) as u64,
+            next_cursor,
+        })
+    }
+
+    // =========================================================================
+    // SWAP DRAFTS
+    // =========================================================================
+
+    fn draft_store(&self) -> Result<&RedisService, SwapError> {
+        self.redis_service
+            .as_ref()
+            .ok_or_else(|| RedisServiceError::Connection("Swap drafts need Redis".to_string()).into())
+    }
+
+    /// Store a partly filled swap under a new resumable token
+    pub async fn create_swap_draft(
+        &self,
+        draft: super::schema::SwapDraft,
+    ) -> Result<super::schema::SwapDraftResponse, SwapError> {
+        check_swap_draft(&draft)?;
+        let token: String = rand::random::<[u8; 24]>().iter().map(|b| format!("{:02x}", b)).collect();
+        let now = Utc::now();
+        let stored = super::schema::SwapDraftResponse {
+            token,
+            draft,
+            created_at: now,
+            updated_at: now,
+            expires_at: now + chrono::Duration::seconds(SWAP_DRAFT_TTL_SECS as i64),
+        };
+
+        self.draft_store()?
+            .set_json(&swap_draft_key(&stored.token), &stored, SWAP_DRAFT_TTL_SECS)
+            .await
+            .map_err(SwapError::RedisError)?;
+
+        Ok(stored)
+    }
+
+    pub async fn get_swap_draft(&self, token: &str) -> Result<super::schema::SwapDraftResponse, SwapError> {
+        self.draft_store()?
+            .get_json(&swap_draft_key(token))
+            .await
+            .map_err(SwapError::RedisError)?
+            .ok_or(SwapError::DraftNotFound)
+    }
+
+    /// Replace a draft's contents, keeping its token and restarting its TTL
+    pub async fn update_swap_draft(
+        &self,
+        token: &str,
+        draft: super::schema::SwapDraft,
+    ) -> Result<super::schema::SwapDraftResponse, SwapError> {
+        check_swap_draft(&draft)?;
+        let mut stored = self.get_swap_draft(token).await?;
+        let now = Utc::now();
+        stored.draft = draft;
+        stored.updated_at = now;
+        stored.expires_at = now + chrono::Duration::seconds(SWAP_DRAFT_TTL_SECS as i64);
+
+        self.draft_store()?
+            .set_json(&swap_draft_key(token), &stored, SWAP_DRAFT_TTL_SECS)
+            .await
+            .map_err(SwapError::RedisError)?;
+
+        Ok(stored)
+    }
+
+    /// Discard a draft, returning what it held
+    pub async fn delete_swap_draft(&self, token: &str) -> Result<super::schema::SwapDraftResponse, SwapError> {
+        let stored = self.get_swap_draft(token).await?;
+        self.draft_store()?
+            .delete(&swap_draft_key(token))
+            .await
+            .map_err(SwapError::RedisError)?;
+        Ok(stored)
+    }
+
+    // =========================================================================
+    // QUOTE RESERVATION
+    // =========================================================================
+
+    /// Fetch a fresh fixed-rate quote from `request.provider` and hold it for
+    /// QUOTE_RESERVATION_SECS, in MySQL and in Redis for the same lifetime
+    pub async fn reserve_quote(
+        &self,
+        request: &super::schema::QuoteRequest,
+        user_id: Option<String>,
+    ) -> Result<super::schema::QuoteReservation, SwapError> {
+        if !self.brand.allows_pair(&request.from, &request.to) {
+            return Err(SwapError::PairNotAllowed { from: request.from.clone(), to: request.to.clone() });
+        }
+        if !self.provider_allowed(&request.provider).await {
+            return Err(SwapError::ProviderNotAllowed(request.provider.clone()));
+        }
+        self.ensure_not_delisted(&request.from, &request.network_from).await?;
+        self.ensure_not_delisted(&request.to, &request.network_to).await?;
+
+        // Reservations are never served from the rates cache
+        let mut rates = self
+            .fetch_rates_from_api(&super::schema::RatesQuery {
+                from: request.from.clone(),
+                network_from: request.network_from.clone(),
+                to: request.to.clone(),
+                network_to: request.network_to.clone(),
+                amount: request.amount,
+                amount_to: None,
+                max_kyc_rating: None,
+                rate_type: Some(super::schema::RateType::Fixed),
+                provider: None,
+                sandbox: false,
+            })
+            .await?;
+        self.serve_quotes(&mut rates).await;
+
+        let quote = match rates.rates.iter().find(|r| r.provider.eq_ignore_ascii_case(&request.provider)) {
+            Some(quote) => quote,
+            None if rates.rates.is_empty() => return Err(SwapError::PairNotAvailable),
+            None => return Err(SwapError::ProviderNotQuoting(request.provider.clone())),
+        };
+        if request.amount < quote.min_amount || (quote.max_amount > Decimal::ZERO && request.amount > quote.max_amount) {
+            return Err(SwapError::AmountOutOfRange { min: quote.min_amount, max: quote.max_amount });
+        }
+
+        let now = Utc::now();
+        let reservation = super::schema::QuoteReservation {
+            quote_id: uuid::Uuid::new_v4().to_string(),
+            trade_id: rates.trade_id.clone(),
+            provider: quote.provider.clone(),
+            from: request.from.clone(),
+            network_from: request.network_from.clone(),
+            to: request.to.clone(),
+            network_to: request.network_to.clone(),
+            amount: request.amount,
+            rate: quote.rate,
+            estimated_amount: quote.estimated_amount,
+            created_at: now,
+            expires_at: now + chrono::Duration::seconds(QUOTE_RESERVATION_SECS as i64),
+        };
+
+        sqlx::query(
+            "INSERT INTO quotes (id, user_id, trade_id, provider, from_currency, from_network, to_currency, to_network,
+                                 amount, rate, estimated_amount, expires_at, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&reservation.quote_id)
+        .bind(&user_id)
+        .bind(&reservation.trade_id)
+        .bind(&reservation.provider)
+        .bind(&reservation.from)
+        .bind(&reservation.network_from)
+        .bind(&reservation.to)
+        .bind(&reservation.network_to)
+        .bind(reservation.amount)
+        .bind(reservation.rate)
+        .bind(reservation.estimated_amount)
+        .bind(reservation.expires_at)
+        .bind(reservation.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        if let Some(redis) = &self.redis_service {
+            let _ = redis
+                .set_json(&quote_key(&reservation.quote_id), &reservation, QUOTE_RESERVATION_SECS)
+                .await;
+        }
+
+        Ok(reservation)
+    }
+
+    /// Look a reservation up, in Redis first
+    async fn find_quote(&self, quote_id: &str) -> Result<Option<super::schema::QuoteReservation>, SwapError> {
+        if let Some(redis) = &self.redis_service {
+            if let Ok(Some(reservation)) = redis.get_json(&quote_key(quote_id)).await {
+                return Ok(Some(reservation));
+            }
+        }
+
+        let row: Option<QuoteRow> = sqlx::query_as(
+            "SELECT id, trade_id, provider, from_currency, from_network, to_currency, to_network,
+                    amount, rate, estimated_amount, created_at, expires_at
+             FROM quotes WHERE id = ?",
+        )
+        .bind(quote_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(
+            |(quote_id, trade_id, provider, from, network_from, to, network_to, amount, rate, estimated_amount, created_at, expires_at)| {
+                super::schema::QuoteReservation {
+                    quote_id,
+                    trade_id,
+                    provider,
+                    from,
+                    network_from,
+                    to,
+                    network_to,
+                    amount,
+                    rate,
+                    estimated_amount,
+                    created_at,
+                    expires_at,
+                }
+            },
+        ))
+    }
+
+    /// Check a reservation against the swap request and mark it used, so two
+    /// swaps can't be opened on one quote
+    async fn claim_quote(
+        &self,
+        quote_id: &str,
+        request: &super::schema::CreateSwapRequest,
+    ) -> Result<super::schema::QuoteReservation, SwapError> {
+        let quote = self.find_quote(quote_id).await?.ok_or(SwapError::QuoteNotFound)?;
+        if quote.expires_at <= Utc::now() {
+            return Err(SwapError::RateExpired(quote.expires_at));
+        }
+
+        let same_pair = quote.from.eq_ignore_ascii_case(&request.from)
+            && quote.network_from == request.network_from
+            && quote.to.eq_ignore_ascii_case(&request.to)
+            && quote.network_to == request.network_to;
+        if !same_pair || quote.amount != request.amount {
+            return Err(SwapError::QuoteMismatch);
+        }
+
+        let claimed = sqlx::query(
+            "UPDATE quotes SET used_at = NOW() WHERE id = ? AND used_at IS NULL AND expires_at > NOW()",
+        )
+        .bind(quote_id)
+        .execute(&self.pool)
+        .await?
+        .rows_affected()
+            == 1;
+        if !claimed {
+            // Lost to expiry between the check and the claim, or already spent
+            return Err(if quote.expires_at <= Utc::now() {
+                SwapError::RateExpired(quote.expires_at)
+            } else {
+                SwapError::QuoteAlreadyUsed
+            });
+        }
+
+        Ok(quote)
+    }
+
+    // =========================================================================
+    // SHADOW QUOTES
+    // =========================================================================
+
+    /// How each shadowed aggregator compared with the served quotes over the
+    /// last `hours` hours
+    pub async fn get_shadow_quote_report(&self, hours: u32) -> Result<super::schema::ShadowQuoteReport, SwapError> {
+        let hours = hours.clamp(1, MAX_SHADOW_REPORT_HOURS);
+        let aggregators: Vec<super::schema::ShadowAggregatorSummary> = sqlx::query_as(
+            "SELECT aggregator,
+                    COUNT(*) AS requests,
+                    CAST(SUM(NOT succeeded) AS SIGNED) AS failures,
+                    CAST(SUM(succeeded AND quote_count = 0) AS SIGNED) AS no_quotes,
+                    CAST(SUM(best_amount IS NOT NULL AND served_amount IS NOT NULL) AS SIGNED) AS compared,
+                    CAST(COALESCE(SUM(best_amount > served_amount), 0) AS SIGNED) AS beat_served,
+                    CAST(AVG((best_amount - served_amount) / served_amount * 100) AS DOUBLE) AS avg_diff_percent,
+                    CAST(AVG(latency_ms) AS DOUBLE) AS avg_latency_ms
+             FROM shadow_quotes
+             WHERE created_at >= NOW() - INTERVAL ? HOUR
+             GROUP BY aggregator
+             ORDER BY aggregator",
+        )
+        .bind(hours)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(super::schema::ShadowQuoteReport { hours, aggregators })
+    }
+
+    // =========================================================================
+    // REFUND ADDRESS SUGGESTIONS
+    // =========================================================================
+
+    /// Addresses on `ticker`/`network` the user has used before: refund
+    /// addresses given when sending that currency (validated at creation) and
+    /// recipient addresses that received a completed swap. Sandbox and
+    /// anonymized swaps are skipped, as are addresses that no longer pass the
+    /// network's registered format.
+    pub async fn get_refund_address_suggestions(
+        &self,
+        user_id: &str,
+        query: &super::schema::RefundAddressQuery,
+    ) -> Result<super::schema::RefundAddressSuggestionsResponse, SwapError> {
+        use super::schema::{AddressSource, RefundAddressSuggestion};
+
+        let ticker = query.ticker.trim();
+        let network = query.network.trim();
+        let mut suggestions = Vec::new();
+
+        if !ticker.is_empty() && !network.is_empty() {
+            let rows: Vec<AddressUseRow> = sqlx::query_as(
+                "SELECT address, extra_id, MAX(source = 'refund'), MAX(source = 'recipient'), COUNT(*), MAX(used_at)
+                 FROM (
+                     SELECT refund_address AS address, NULLIF(refund_extra_id, '') AS extra_id,
+                            'refund' AS source, created_at AS used_at
+                     FROM swaps
+                     WHERE user_id = ? AND from_currency = ? AND from_network = ?
+                       AND refund_address IS NOT NULL AND refund_address <> ''
+                       AND is_sandbox = FALSE AND anonymized_at IS NULL
+                     UNION ALL
+                     SELECT recipient_address, NULLIF(recipient_extra_id, ''), 'recipient', created_at
+                     FROM swaps
+                     WHERE user_id = ? AND to_currency = ? AND to_network = ? AND status = 'completed'
+                       AND is_sandbox = FALSE AND anonymized_at IS NULL
+                 ) AS used
+                 GROUP BY address, extra_id
+                 ORDER BY MAX(used_at) DESC
+                 LIMIT ?",
+            )
+            .bind(user_id)
+            .bind(ticker)
+            .bind(network)
+            .bind(user_id)
+            .bind(ticker)
+            .bind(network)
+            .bind(MAX_REFUND_SUGGESTIONS)
+            .fetch_all(&self.pool)
+            .await?;
+
+            let format = self.address_formats().lookup(ticker, network).await;
+            for (address, extra_id, as_refund, as_recipient, times_used, last_used_at) in rows {
+                if let Some(format) = &format {
+                    if format.check_address(&address).is_err() {
+                        continue;
+                    }
+                }
+                if extra_id.as_deref().is_some_and(|memo| check_extra_id_format(format.as_ref(), memo).is_err()) {
+                    continue;
+                }
+
+                let mut sources = Vec::new();
+                if as_refund > 0 {
+                    sources.push(AddressSource::Refund);
+                }
+                if as_recipient > 0 {
+                    sources.push(AddressSource::Recipient);
+                }
+                suggestions.push(RefundAddressSuggestion {
+                    address,
+                    extra_id,
+                    sources,
+                    times_used: times_used.max(0) as u32,
+                    last_used_at,
+                });
+            }
+        }
+
+        Ok(super::schema::RefundAddressSuggestionsResponse {
+            ticker: query.ticker.clone(),
+            network: query.network.clone(),
+            suggestions,
+        })
+    }
+
+    // =========================================================================
+    // ADDRESS OWNERSHIP
+    // =========================================================================
+
+    fn challenge_store(&self) -> Result<&RedisService, SwapError> {
+        self.redis_service
+            .as_ref()
+            .ok_or_else(|| RedisServiceError::Connection("Address verification needs Redis".to_string()).into())
+    }
+
+    /// Issue a message for `user_id` to sign with the key behind a payout
+    /// address. Asking again replaces the pending challenge for that address.
+    pub async fn create_address_challenge(
+        &self,
+        user_id: &str,
+        request: &super::schema::AddressChallengeRequest,
+        ttl: Duration,
+    ) -> Result<super::schema::AddressChallengeResponse, SwapError> {
+        let (chain, address) = ownership_target(&request.ticker, &request.network, &request.address)?;
+        let nonce: String = rand::random::<[u8; 16]>().iter().map(|b| format!("{:02x}", b)).collect();
+        let now = Utc::now();
+        let challenge = super::schema::AddressChallengeResponse {
+            chain: chain.as_str().to_string(),
+            message: format!(
+                "Sign this message to prove you control this address.\n\nAddress: {}\nNonce: {}\nIssued: {}",
+                request.address.trim(),
+                nonce,
+                now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            ),
+            expires_at: now + chrono::Duration::seconds(ttl.as_secs() as i64),
+            address,
+        };
+
+        self.challenge_store()?
+            .set_json(&address_challenge_key(user_id, chain, &challenge.address), &challenge, ttl.as_secs().max(1))
+            .await
+            .map_err(SwapError::RedisError)?;
+
+        Ok(challenge)
+    }
+
+    /// Check a signature over the pending challenge and record the address as
+    /// verified for `user_id`. The challenge is used up once it succeeds.
+    pub async fn verify_address(
+        &self,
+        user_id: &str,
+        request: &super::schema::VerifyAddressRequest,
+    ) -> Result<super::schema::VerifiedAddressResponse, SwapError> {
+        let (chain, address) = ownership_target(&request.ticker, &request.network, &request.address)?;
+        let key = address_challenge_key(user_id, chain, &address);
+        let store = self.challenge_store()?;
+        let challenge: super::schema::AddressChallengeResponse = store
+            .get_json(&key)
+            .await
+            .map_err(SwapError::RedisError)?
+            .ok_or(SwapError::ChallengeNotFound)?;
+
+        verify_signed_message(chain, request.address.trim(), &challenge.message, &request.signature).map_err(|e| {
+            match e {
+                SignatureError::Unsupported(what) => SwapError::VerificationNotSupported(what),
+                SignatureError::Malformed(reason) => SwapError::InvalidSignature(reason),
+                SignatureError::Mismatch => SwapError::InvalidSignature("not made by this address's key".to_string()),
+            }
+        })?;
+
+        sqlx::query(
+            "INSERT INTO verified_addresses (id, user_id, chain, address, message, signature, verified_at)
+             VALUES (?, ?, ?, ?, ?, ?, NOW())
+             ON DUPLICATE KEY UPDATE
+                message = VALUES(message), signature = VALUES(signature), verified_at = VALUES(verified_at)",
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(user_id)
+        .bind(chain.as_str())
+        .bind(&address)
+        .bind(&challenge.message)
+        .bind(request.signature.trim())
+        .execute(&self.pool)
+        .await?;
+        let _ = store.delete(&key).await;
+
+        tracing::info!(user_id = %user_id, chain = chain.as_str(), "Payout address verified");
+
+        let verified: super::model::VerifiedAddress = sqlx::query_as(
+            "SELECT id, user_id, chain, address, message, signature, verified_at
+             FROM verified_addresses WHERE user_id = ? AND chain = ? AND address = ?",
+        )
+        .bind(user_id)
+        .bind(chain.as_str())
+        .bind(&address)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(verified.into())
+    }
+
+    pub async fn get_verified_addresses(
+        &self,
+        user_id: &str,
+    ) -> Result<super::schema::VerifiedAddressesResponse, SwapError> {
+        let rows: Vec<super::model::VerifiedAddress> = sqlx::query_as(
+            "SELECT id, user_id, chain, address, message, signature, verified_at
+             FROM verified_addresses WHERE user_id = ?
+             ORDER BY verified_at DESC",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(super::schema::VerifiedAddressesResponse { addresses: rows.into_iter().map(Into::into).collect() })
+    }
+
+    /// Forget a verified address, returning it; swaps to it fall back to the
+    /// default limit
+    pub async fn delete_verified_address(
+        &self,
+        user_id: &str,
+        id: &str,
+    ) -> Result<super::schema::VerifiedAddressResponse, SwapError> {
+        let verified: super::model::VerifiedAddress = sqlx::query_as(
+            "SELECT id, user_id, chain, address, message, signature, verified_at
+             FROM verified_addresses WHERE id = ? AND user_id = ?",
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(SwapError::VerifiedAddressNotFound)?;
+
+        sqlx::query("DELETE FROM verified_addresses WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(verified.into())
+    }
+
+    async fn is_verified_address(&self, user_id: &str, chain: Chain, address: &str) -> Result<bool, SwapError> {
+        let found: Option<(String,)> =
+            sqlx::query_as("SELECT id FROM verified_addresses WHERE user_id = ? AND chain = ? AND address = ?")
+                .bind(user_id)
+                .bind(chain.as_str())
+                .bind(normalize_address(chain, address))
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(found.is_some())
+    }
+
+    // =========================================================================
+    // FAVORITES
+    // =========================================================================
+
+    /// Starred pairs, the pairs of recent swaps and saved recipient
+    /// addresses, for prefilling the swap form
+    pub async fn get_favorites(&self, user_id: &str) -> Result<super::schema::FavoritesResponse, SwapError> {
+        let pairs: Vec<super::model::FavoritePair> = sqlx::query_as(
+            "SELECT id, user_id, from_currency, from_network, to_currency, to_network, created_at, deleted_at
+             FROM favorite_pairs WHERE user_id = ? AND deleted_at IS NULL
+             ORDER BY created_at DESC",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let recent: Vec<RecentPairRow> = sqlx::query_as(
+            "SELECT from_currency, from_network, to_currency, to_network, COUNT(*), MAX(created_at)
+             FROM swaps
+             WHERE user_id = ? AND is_sandbox = FALSE
+             GROUP BY from_currency, from_network, to_currency, to_network
+             ORDER BY MAX(created_at) DESC
+             LIMIT ?",
+        )
+        .bind(user_id)
+        .bind(MAX_RECENT_PAIRS)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let addresses: Vec<super::model::SavedAddress> = sqlx::query_as(&format!(
+            "{} WHERE user_id = ? AND deleted_at IS NULL
+             ORDER BY label IS NULL, COALESCE(last_used_at, created_at) DESC
+             LIMIT ?",
+            SAVED_ADDRESS_SELECT
+        ))
+        .bind(user_id)
+        .bind(MAX_SAVED_ADDRESSES as u32)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(super::schema::FavoritesResponse {
+            pairs: pairs.into_iter().map(Into::into).collect(),
+            recent_pairs: recent
+                .into_iter()
+                .map(|(from, network_from, to, network_to, times_used, last_used_at)| super::schema::RecentPair {
+                    from,
+                    network_from,
+                    to,
+                    network_to,
+                    times_used: times_used.max(0) as u32,
+                    last_used_at,
+                })
+                .collect(),
+            addresses: addresses.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Star a pair or save a recipient address. Saving one that is already
+    /// saved updates its label; saving a removed one restores it.
+    pub async fn save_favorite(
+        &self,
+        user_id: &str,
+        request: &super::schema::SaveFavoriteRequest,
+    ) -> Result<super::schema::SavedFavorite, SwapError> {
+        use super::schema::{SaveFavoriteRequest, SavedFavorite};
+
+        match request {
+            SaveFavoriteRequest::Pair { from, network_from, to, network_to } => {
+                let pair = self.save_favorite_pair(user_id, from, network_from, to, network_to).await?;
+                Ok(SavedFavorite::Pair(pair.into()))
+            }
+            SaveFavoriteRequest::Address { ticker, network, address, extra_id, label } => {
+                let saved = self
+                    .save_address(user_id, ticker, network, address, extra_id.as_deref(), label.as_deref())
+                    .await?;
+                Ok(SavedFavorite::Address(saved.into()))
+            }
+        }
+    }
+
+    async fn save_favorite_pair(
+        &self,
+        user_id: &str,
+        from: &str,
+        network_from: &str,
+        to: &str,
+        network_to: &str,
+    ) -> Result<super::model::FavoritePair, SwapError> {
+        let (from, to) = (from.trim().to_lowercase(), to.trim().to_lowercase());
+        let (network_from, network_to) = (network_from.trim(), network_to.trim());
+        if [from.as_str(), network_from, to.as_str(), network_to].iter().any(|field| field.is_empty()) {
+            return Err(SwapError::InvalidFavorite("from, network_from, to and network_to are required".to_string()));
+        }
+        if !self.brand.allows_pair(&from, &to) {
+            return Err(SwapError::PairNotAllowed { from, to });
+        }
+        for (ticker, network) in [(&from, network_from), (&to, network_to)] {
+            self.find_currency(ticker, network).await?.ok_or(SwapError::CurrencyNotFound)?;
+        }
+
+        let find = || {
+            sqlx::query_as::<_, super::model::FavoritePair>(
+                "SELECT id, user_id, from_currency, from_network, to_currency, to_network, created_at, deleted_at
+                 FROM favorite_pairs
+                 WHERE user_id = ? AND from_currency = ? AND from_network = ? AND to_currency = ? AND to_network = ?",
+            )
+            .bind(user_id)
+            .bind(&from)
+            .bind(network_from)
+            .bind(&to)
+            .bind(network_to)
+        };
+        if let Some(existing) = find().fetch_optional(&self.pool).await? {
+            if existing.deleted_at.is_none() {
+                return Ok(existing);
+            }
+        }
+        self.ensure_favorite_room("favorite_pairs", user_id, MAX_FAVORITE_PAIRS).await?;
+
+        // A restored pair is starred anew, so it lists first again
+        sqlx::query(
+            "INSERT INTO favorite_pairs (id, user_id, from_currency, from_network, to_currency, to_network, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, NOW())
+             ON DUPLICATE KEY UPDATE created_at = NOW(), deleted_at = NULL",
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(user_id)
+        .bind(&from)
+        .bind(network_from)
+        .bind(&to)
+        .bind(network_to)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(find().fetch_one(&self.pool).await?)
+    }
+
+    async fn save_address(
+        &self,
+        user_id: &str,
+        ticker: &str,
+        network: &str,
+        address: &str,
+        extra_id: Option<&str>,
+        label: Option<&str>,
+    ) -> Result<super::model::SavedAddress, SwapError> {
+        let (ticker, network, address) = (ticker.trim().to_lowercase(), network.trim(), address.trim());
+        let extra_id = non_empty(extra_id).unwrap_or("");
+        let label = label.map(str::trim);
+        if ticker.is_empty() || network.is_empty() || address.is_empty() {
+            return Err(SwapError::InvalidFavorite("ticker, network and address are required".to_string()));
+        }
+        if label.is_some_and(|label| label.chars().count() > MAX_ADDRESS_LABEL_CHARS) {
+            return Err(SwapError::InvalidFavorite(format!(
+                "labels are limited to {} characters",
+                MAX_ADDRESS_LABEL_CHARS
+            )));
+        }
+
+        // The same checks a swap to this address would get
+        let currency = self.find_currency(&ticker, network).await?.ok_or(SwapError::CurrencyNotFound)?;
+        if let Some(chain) = Chain::detect(&ticker, network) {
+            chain.check_address(address).map_err(|_| SwapError::InvalidAddress)?;
+        }
+        let format = self.address_formats().lookup(&ticker, network).await;
+        if let Some(format) = &format {
+            format.check_address(address).map_err(|_| SwapError::InvalidAddress)?;
+        }
+        if !extra_id.is_empty() {
+            check_extra_id_format(format.as_ref(), extra_id).map_err(|reason| SwapError::InvalidExtraId {
+                ticker: ticker.clone(),
+                extra_id_name: currency.extra_id_name.clone(),
+                reason,
+            })?;
+        }
+
+        let find_sql = format!(
+            "{} WHERE user_id = ? AND currency = ? AND network = ? AND address = ? AND extra_id = ?",
+            SAVED_ADDRESS_SELECT
+        );
+        let find = || {
+            sqlx::query_as::<_, super::model::SavedAddress>(&find_sql)
+                .bind(user_id)
+                .bind(&ticker)
+                .bind(network)
+                .bind(address)
+                .bind(extra_id)
+        };
+        let existing = find().fetch_optional(&self.pool).await?;
+        if existing.is_none_or(|row| row.deleted_at.is_some()) {
+            self.ensure_favorite_room("saved_addresses", user_id, MAX_SAVED_ADDRESSES).await?;
+        }
+
+        sqlx::query(
+            "INSERT INTO saved_addresses (id, user_id, currency, network, address, extra_id, label, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, NOW())
+             ON DUPLICATE KEY UPDATE label = IF(?, VALUES(label), label), deleted_at = NULL",
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(user_id)
+        .bind(&ticker)
+        .bind(network)
+        .bind(address)
+        .bind(extra_id)
+        .bind(label.filter(|label| !label.is_empty()))
+        .bind(label.is_some())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(find().fetch_one(&self.pool).await?)
+    }
+
+    /// Refuse a new favorite once `table` holds `max` of the user's
+    async fn ensure_favorite_room(&self, table: &str, user_id: &str, max: usize) -> Result<(), SwapError> {
+        let saved: i64 =
+            sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {} WHERE user_id = ? AND deleted_at IS NULL", table))
+                .bind(user_id)
+                .fetch_one(&self.pool)
+                .await?;
+        if saved >= max as i64 {
+            return Err(SwapError::TooManyFavorites(max));
+        }
+        Ok(())
+    }
+
+    /// Count a swap to its recipient address among the user's saved
+    /// addresses. An address the user removed stays removed.
+    async fn record_recipient_use(
+        &self,
+        user_id: &str,
+        request: &super::schema::CreateSwapRequest,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO saved_addresses (id, user_id, currency, network, address, extra_id, times_used, last_used_at, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, 1, NOW(), NOW())
+             ON DUPLICATE KEY UPDATE times_used = times_used + 1, last_used_at = NOW()",
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(user_id)
+        .bind(request.to.trim().to_lowercase())
+        .bind(request.network_to.trim())
+        .bind(request.recipient_address.trim())
+        .bind(non_empty(request.recipient_extra_id.as_deref()).unwrap_or(""))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Remove a starred pair, returning it
+    pub async fn delete_favorite_pair(
+        &self,
+        user_id: &str,
+        id: &str,
+    ) -> Result<super::schema::FavoritePairResponse, SwapError> {
+        let pair: super::model::FavoritePair = sqlx::query_as(
+            "SELECT id, user_id, from_currency, from_network, to_currency, to_network, created_at, deleted_at
+             FROM favorite_pairs WHERE id = ? AND user_id = ? AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(SwapError::FavoriteNotFound)?;
+
+        sqlx::query("UPDATE favorite_pairs SET deleted_at = NOW() WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(pair.into())
+    }
+
+    /// Remove a saved address, returning it; later swaps to it do not bring
+    /// it back
+    pub async fn delete_saved_address(
+        &self,
+        user_id: &str,
+        id: &str,
+    ) -> Result<super::schema::SavedAddressResponse, SwapError> {
+        let saved: super::model::SavedAddress = sqlx::query_as(&format!(
+            "{} WHERE id = ? AND user_id = ? AND deleted_at IS NULL",
+            SAVED_ADDRESS_SELECT
+        ))
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(SwapError::FavoriteNotFound)?;
+
+        sqlx::query("UPDATE saved_addresses SET deleted_at = NOW() WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(saved.into())
+    }
+
+    // =========================================================================
+    // BATCH STATUS
+    // =========================================================================
+
+    /// Statuses for up to `MAX_BATCH_STATUS_IDS` swaps owned by `user_id`.
+    /// Served from the status cache, falling back to the stored row; does not
+    /// poll the provider. Unknown or foreign ids are returned in `not_found`.
+    pub async fn get_swap_statuses(
+        &self,
+        swap_ids: &[String],
+        user_id: &str,
+    ) -> Result<super::schema::BatchSwapStatusResponse, SwapError> {
+        let mut ids: Vec<&str> = Vec::new();
+        for id in swap_ids {
+            if !ids.contains(&id.as_str()) {
+                ids.push(id);
+            }
+        }
+
+        if ids.len() > MAX_BATCH_STATUS_IDS {
+            return Err(SwapError::TooManySwapIds(MAX_BATCH_STATUS_IDS));
+        }
+        if ids.is_empty() {
+            return Ok(super::schema::BatchSwapStatusResponse { swaps: Vec::new(), not_found: Vec::new() });
+        }
+
+        let mut builder = sqlx::QueryBuilder::<MySql>::new("SELECT id FROM swaps WHERE user_id = ");
+        builder.push_bind(user_id).push(" AND id IN ");
+        push_bind_list(&mut builder, &ids);
+        self.push_tenant_scope(&mut builder);
+        let owned: Vec<String> = builder
+            .build_query_as::<(String,)>()
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|(id,)| id)
+            .collect();
+
+        let mut swaps = Vec::with_capacity(owned.len());
+        let mut misses = Vec::new();
+        for id in ids.iter().filter(|id| owned.iter().any(|o| o == *id)) {
+            match self.cached_swap_status(id).await {
+                Some(cached) => swaps.push(cached),
+                None => misses.push(*id),
+            }
+        }
+
+        if !misses.is_empty() {
+            let mut builder = sqlx::QueryBuilder::<MySql>::new(SWAP_SELECT);
+            builder.push(" WHERE id IN ");
+            push_bind_list(&mut builder, &misses);
+            let rows = builder
+                .build_query_as::<super::model::Swap>()
+                .fetch_all(&self.pool)
+                .await?;
+
+            for row in rows {
+                let response = super::schema::SwapStatusResponse::from(row);
+                self.cache_swap_status(&response).await;
+                swaps.push(response);
+            }
+        }
+
+        // Keep the caller's order
+        swaps.sort_by_key(|s| ids.iter().position(|id| *id == s.swap_id));
+        let not_found = ids
+            .iter()
+            .filter(|id| !swaps.iter().any(|s| s.swap_id == **id))
+            .map(|id| id.to_string())
+            .collect();
+
+        Ok(super::schema::BatchSwapStatusResponse { swaps, not_found })
+    }
+
+    async fn cached_swap_status(&self, swap_id: &str) -> Option<super::schema::SwapStatusResponse> {
+        let service = self.redis_service.as_ref()?;
+        let mut cached: super::schema::SwapStatusResponse =
+            service.get_json(&swap_status_cache_key(&self.tenant(), swap_id)).await.ok().flatten()?;
+        cached.tenant = self.tenant();
+        Some(cached)
+    }
+
+    /// Push a stored status change to live subscribers on every instance
+    /// (GET /swap/{id}/ws); failures are logged, never surfaced
+    pub(super) async fn publish_swap_status(&self, response: &super::schema::SwapStatusResponse) {
+        let Some(service) = &self.redis_service else {
+            return;
+        };
+
+        let published = match serde_json::to_string(response) {
+            Ok(json) => service.publish(&swap_status_channel(&response.swap_id), &json).await,
+            Err(e) => Err(RedisServiceError::from(e)),
+        };
+        if let Err(e) = published {
+            tracing::warn!("Failed to publish status for swap {}: {}", response.swap_id, e);
+        }
+    }
+
+    /// Finished swaps stay cached for a day, in-flight ones only briefly
+    pub(super) async fn cache_swap_status(&self, response: &super::schema::SwapStatusResponse) {
+        let Some(service) = &self.redis_service else {
+            return;
+        };
+
+        let ttl = if response.status.is_final() { 86400 } else { 30 };
+        let key = swap_status_cache_key(&response.tenant, &response.swap_id);
+        let _ = service.set_json(&key, response, ttl).await;
+    }
+
+    /// Move a swap to `status` with compare-and-swap on its version, adding
+    /// the history row once the write lands. When another writer got there
+    /// first the row is re-read and merged: unless ours moves the fresh
+    /// status forward, that write wins; otherwise ours is retried on the
+    /// fresh version.
+    async fn update_swap_status(
+        &self,
+        swap: &super::model::Swap,
+        status: &super::schema::SwapStatus,
+        actual_receive: Option<Decimal>,
+        source: StatusSource,
+        message: Option<String>,
+    ) -> Result<StatusUpdate, SwapError> {
+        let completed_at = if *status == super::schema::SwapStatus::Completed {
+            Some(Utc::now())
+        } else {
+            None
+        };
+
+        let mut expected_version = swap.version;
+        let mut previous = swap.status.clone();
+        for attempt in 1..=MAX_STATUS_UPDATE_ATTEMPTS {
+            // Guarded by the version, so running it again cannot apply it twice
+            let update = || {
+                sqlx::query(
+                    r#"
+                    UPDATE swaps
+                    SET status = ?,
+                        actual_receive = COALESCE(?, actual_receive),
+                        completed_at = COALESCE(?, completed_at),
+                        version = version + 1,
+                        updated_at = NOW()
+                    WHERE id = ? AND version = ?
+                    "#
+                )
+                .bind(status)
+                .bind(actual_receive)
+                .bind(completed_at)
+                .bind(&swap.id)
+                .bind(expected_version)
+                .execute(&self.pool)
+            };
+            let result = retry_transient(&self.db_retry, "update swap status", update).await?;
+
+            let current = self.find_swap(&swap.id).await?.ok_or(SwapError::SwapNotFound)?;
+            if result.rows_affected() == 1 {
+                self.record_transition(&swap.id, Some(&previous), status, source, message).await?;
+                if let (super::schema::SwapStatus::Completed, Some(user_id), false) =
+                    (status, &swap.user_id, swap.is_sandbox)
+                {
+                    self.record_completed_volume(user_id, &swap.from_currency, swap.amount).await;
+                }
+                return Ok(StatusUpdate::Applied(current));
+            }
+
+            tracing::debug!(
+                "Swap {} changed under us (version {} -> {}, attempt {})",
+                swap.id,
+                expected_version,
+                current.version,
+                attempt
+            );
+            if !SwapStateMachine::advances(&current.status, status, source) {
+                return Ok(StatusUpdate::Superseded(current));
+            }
+            expected_version = current.version;
+            previous = current.status;
+        }
+
+        Err(SwapError::ConcurrentUpdate(swap.id.clone()))
+    }
+
+    /// Add a swap_status_history row; `from` is None for a new swap
+    async fn record_transition(
+        &self,
+        swap_id: &str,
+        from: Option<&super::schema::SwapStatus>,
+        status: &super::schema::SwapStatus,
+        source: StatusSource,
+        message: Option<String>,
+    ) -> Result<(), SwapError> {
+        sqlx::query(
+            r#"
+            INSERT INTO swap_status_history (swap_id, from_status, status, source, message, created_at)
+            VALUES (?, ?, ?, ?, ?, NOW())
+            "#
+        )
+        .bind(swap_id)
+        .bind(from)
+        .bind(status)
+        .bind(source)
+        .bind(message)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    // =========================================================================
+    // TRANSACTIONS
+    // =========================================================================
+
+    /// Store the deposit and payout hashes and the deposit confirmations a
+    /// provider reported, updating `swap` to match. Reported values replace
+    /// stored ones, missing ones leave them. Returns whether anything
+    /// changed; failures are logged, not returned, like refund bookkeeping.
+    pub(super) async fn record_transactions(
+        &self,
+        swap: &mut super::model::Swap,
+        report: &super::schema::TransactionReport,
+    ) -> bool {
+        let deposit_tx_hash = report.deposit_tx_hash.clone().or_else(|| swap.tx_hash_in.clone());
+        let payout_tx_hash = report.payout_tx_hash.clone().or_else(|| swap.tx_hash_out.clone());
+        let confirmations = report.deposit_confirmations.or(swap.deposit_confirmations);
+        if deposit_tx_hash == swap.tx_hash_in
+            && payout_tx_hash == swap.tx_hash_out
+            && confirmations == swap.deposit_confirmations
+        {
+            return false;
+        }
+
+        // updated_at is kept as is; only status changes should move it
+        let result = sqlx::query(
+            "UPDATE swaps
+             SET tx_hash_in = ?, tx_hash_out = ?, deposit_confirmations = ?,
+                 version = version + 1, updated_at = updated_at
+             WHERE id = ?",
+        )
+        .bind(&deposit_tx_hash)
+        .bind(&payout_tx_hash)
+        .bind(confirmations)
+        .bind(&swap.id)
+        .execute(&self.pool)
+        .await;
+
+        if let Err(e) = result {
+            tracing::warn!("Failed to record transactions for swap {}: {}", swap.id, e);
+            return false;
+        }
+
+        swap.tx_hash_in = deposit_tx_hash;
+        swap.tx_hash_out = payout_tx_hash;
+        swap.deposit_confirmations = confirmations;
+        swap.version += 1;
+        true
+    }
+
+    // =========================================================================
+    // REFUNDS
+    // =========================================================================
+
+    /// Store what the provider reported about a refund. Reports fill in over
+    /// time (the tx hash usually arrives after the status), so a field is only
+    /// overwritten by a non-null value. Failures are logged, not returned: the
+    /// status update must not fail because of the refund bookkeeping.
+    pub(super) async fn record_refund(
+        &self,
+        swap: &super::model::Swap,
+        report: &super::schema::RefundReport,
+        source: super::schema::RefundSource,
+    ) {
+        let refund_address = report.refund_address.as_ref().or(swap.refund_address.as_ref());
+        let refund_extra_id = report.refund_extra_id.as_ref().or(swap.refund_extra_id.as_ref());
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO swap_refunds (
+                swap_id, refund_address, refund_extra_id, amount,
+                currency, network, tx_hash, source, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, NOW(), NOW())
+            ON DUPLICATE KEY UPDATE
+                refund_address = IF(anonymized_at IS NULL, COALESCE(VALUES(refund_address), refund_address), NULL),
+                refund_extra_id = IF(anonymized_at IS NULL, COALESCE(VALUES(refund_extra_id), refund_extra_id), NULL),
+                amount = COALESCE(VALUES(amount), amount),
+                tx_hash = COALESCE(VALUES(tx_hash), tx_hash),
+                source = VALUES(source),
+                updated_at = NOW()
+            "#
+        )
+        .bind(&swap.id)
+        .bind(refund_address)
+        .bind(refund_extra_id)
+        .bind(report.amount)
+        .bind(&swap.from_currency)
+        .bind(&swap.from_network)
+        .bind(&report.tx_hash)
+        .bind(source)
+        .execute(&self.pool)
+        .await;
+
+        if let Err(e) = result {
+            tracing::warn!("Failed to record refund for swap {}: {}", swap.id, e);
+        }
+    }
+
+    /// Look up on an explorer what reached the deposit address of a swap the
+    /// provider reports underpaid; runs in the background and only records
+    /// the evidence
+    pub(super) fn check_underpaid_deposit(&self, swap: &super::model::Swap) {
+        let config = DepositCheckConfig::from_env();
+        if !config.enabled || swap.is_sandbox {
+            return;
+        }
+
+        let checker = DepositChecker::from_config(&config, self.pool.clone());
+        let swap = swap.clone();
+        tokio::spawn(async move {
+            match checker.check(&swap, CheckTrigger::Underpayment).await {
+                Ok(evidence) if evidence.is_short() => tracing::info!(
+                    swap_id = %swap.id,
+                    shortfall = %evidence.shortfall,
+                    "Underpayment confirmed by {}",
+                    evidence.explorer
+                ),
+                Ok(evidence) => tracing::warn!(
+                    swap_id = %swap.id,
+                    "Provider reports an underpayment, {} shows the full amount",
+                    evidence.explorer
+                ),
+                Err(DepositCheckError::Unsupported(..)) => {}
+                Err(e) => tracing::warn!("Deposit check for swap {} failed: {}", swap.id, e),
+            }
+        });
+    }
+
+    /// Refund details of a refunded swap; swaps of other tenants are not found
+    pub async fn get_swap_refund(&self, swap_id: &str) -> Result<super::schema::SwapRefundResponse, SwapError> {
+        let swap = self.find_swap(swap_id).await?.ok_or(SwapError::SwapNotFound)?;
+
+        let refund = sqlx::query_as::<_, super::model::SwapRefund>(
+            r#"
+            SELECT swap_id, refund_address, refund_extra_id, amount, currency, network,
+                   tx_hash, source, created_at, updated_at
+            FROM swap_refunds
+            WHERE swap_id = ?
+            "#
+        )
+        .bind(&swap.id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(SwapError::RefundNotFound)?;
+
+        Ok(refund.into())
+    }
+
+    // =========================================================================
+    // ADDRESS VALIDATION
+    // =========================================================================
+
+    /// Validate cryptocurrency address against the address format registry,
+    /// then the Trocador API
+    pub async fn validate_address(
+        &self,
+        request: &super::schema::ValidateAddressRequest,
+    ) -> Result<super::schema::ValidateAddressResponse, SwapError> {
+        // 1. Validate input
+        if request.ticker.trim().is_empty() {
+            return Err(SwapError::InvalidAddress);
+        }
+
+        if request.network.trim().is_empty() {
+            return Err(SwapError::InvalidAddress);
+        }
+
+        if request.address.trim().is_empty() {
+            return Err(SwapError::InvalidAddress);
+        }
+
+        // 2. Reject locally when the address breaks the network's registered format
+        if let Some(format) = self.address_formats().lookup(&request.ticker, &request.network).await {
+            if let Err(reason) = format.check_address(request.address.trim()) {
+                tracing::debug!("Address rejected by {} format for {}: {}", request.network, request.ticker, reason);
+                return Ok(super::schema::ValidateAddressResponse {
+                    valid: false,
+                    ticker: request.ticker.clone(),
+                    network: request.network.clone(),
+                    address: request.address.clone(),
+                });
+            }
+        }
+
+        // 3. Major chains are checked in full locally; only exotic networks need the provider
+        if let Some(chain) = Chain::detect(&request.ticker, &request.network) {
+            return Ok(super::schema::ValidateAddressResponse {
+                valid: chain.check_address(request.address.trim()).is_ok(),
+                ticker: request.ticker.clone(),
+                network: request.network.clone(),
+                address: request.address.clone(),
+            });
+        }
+
+        // 4. Shared Trocador client
+        let trocador_client = self.trocador()?;
+
+        // 5. Call Trocador API with retry logic
+        let is_valid = self.call_with_retry(TROCADOR, || async {
+            trocador_client
+                .validate_address(&request.ticker, &request.network, &request.address)
+                .await
+        })
+        .await?;
+
+        // 6. Return response
+        Ok(super::schema::ValidateAddressResponse {
+            valid: is_valid,
+            ticker: request.ticker.clone(),
+            network: request.network.clone(),
+            address: request.address.clone(),
+        })
+    }
+
+    // =========================================================================
+    // RETRY LOGIC FOR RATE LIMITING
+    // =========================================================================
+
+    /// Call an aggregator API with exponential backoff retry logic
+    /// Handles rate limiting gracefully by retrying with increasing delays.
+    /// Calls go through `provider`'s circuit breaker: while it is open they
+    /// fail fast with `ProviderUnavailable`.
+    async fn call_with_retry<F, Fut, T, E>(
+        &self,
+        provider: &str,
+        f: F,
+    ) -> Result<T, SwapError>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<T, E>>,
+        E: std::fmt::Display,
+    {
+        let breaker = CircuitBreaker::new(self.redis_service.clone(), provider);
+        let permit = breaker
+            .acquire()
+            .await
+            .map_err(|state| SwapError::ProviderUnavailable(format!("{} circuit is {}", provider, state)))?;
+
+        let max_retries = 5;
+        let mut retries = 0;
+
+        loop {
+            match f().await {
+                Ok(result) => {
+                    breaker.record_success(permit).await;
+                    return Ok(result);
+                }
+                Err(e) => {
+                    let error_msg = e.to_string();
+                    
+                    // Check if it's a rate limit error
+                    let is_rate_limit = error_msg.contains("Rate limit")
+                        || error_msg.contains("rate limit")
+                        || error_msg.contains("429")
+                        || error_msg.contains("Too Many Requests");
+
+                    if is_rate_limit && retries < max_retries {
+                        retries += 1;
+                        metrics().provider_retries.inc(provider);
+                        // Exponential backoff: 2s, 4s, 8s, 16s, 32s
+                        let delay_secs = 2u64.pow(retries);
+                        
+                        tracing::warn!(
+                            "Rate limit hit, retrying in {}s (attempt {}/{})",
+                            delay_secs,
+                            retries,
+                            max_retries
+                        );
+                        
+                        tokio::time::sleep(Duration::from_secs(delay_secs)).await;
+                        continue;
+                    }
+
+                    // Not a rate limit error or max retries exceeded
+                    if circuit_breaker::is_outage(&error_msg) {
+                        breaker.record_failure(permit).await;
+                    } else {
+                        breaker.record_success(permit).await;
+                    }
+                    return Err(SwapError::ExternalApiError(error_msg));
+                }
+            }
+        }
+    }
+
+    /// Compare the cached currency list, provider list and the status cache
+    /// of the `sample_size` most recently updated swaps with the database.
+    /// Divergent entries are counted in metrics and, with `repair`, dropped so
+    /// the next read reloads them. Catches update paths that forgot to
+    /// invalidate.
+    pub async fn check_cache_consistency(&self, sample_size: u32, repair: bool) -> Result<ConsistencyStats, SwapError> {
+        let mut stats = ConsistencyStats::default();
+        let Some(service) = &self.redis_service else {
+            return Ok(stats);
+        };
+
+        // Currencies: model list and both pre-serialized responses
+        let currencies = self.fetch_currencies_from_db(&CurrenciesQuery::default()).await?;
+        let responses: Vec<CurrencyResponse> = currencies.iter().cloned().map(Into::into).collect();
+        let grouped = Self::group_currencies(currencies.clone());
+        let expected = [
+            ("currencies:all", serde_json::to_value(&currencies)),
+            ("currencies:response:all", serde_json::to_value(&responses)),
+            ("currencies:response:grouped", serde_json::to_value(&grouped)),
+        ];
+        if let Some(diverged) = cache_diverges(service, &expected).await {
+            if diverged && repair {
+                self.invalidate_currency_cache().await;
+            }
+            stats.record("currencies", diverged, diverged && repair);
+        }
+
+        // Providers: model list and pre-serialized response
+        let providers = self.get_providers(ProvidersQuery { rating: None, markup_enabled: None, sort: None }).await?;
+        let responses: Vec<ProviderResponse> = providers.iter().cloned().map(Into::into).collect();
+        let expected = [
+            ("providers:all", serde_json::to_value(&providers)),
+            ("providers:response:all", serde_json::to_value(&responses)),
+        ];
+        if let Some(diverged) = cache_diverges(service, &expected).await {
+            if diverged && repair {
+                self.invalidate_provider_cache().await;
+            }
+            stats.record("providers", diverged, diverged && repair);
+        }
+
+        // Swap statuses: recently updated swaps are the ones likely to be cached
+        let swaps = sqlx::query_as::<_, super::model::Swap>(&format!("{} ORDER BY updated_at DESC LIMIT ?", SWAP_SELECT))
+            .bind(sample_size)
+            .fetch_all(&self.pool)
+            .await?;
+        for swap in swaps {
+            let tenant = TenantId::new(&swap.tenant_id).unwrap_or_default();
+            let key = swap_status_cache_key(&tenant, &swap.id);
+            let Ok(Some(cached)) = service.get_json::<super::schema::SwapStatusResponse>(&key).await else {
+                continue;
+            };
+
+            let diverged = cached.status != swap.status;
+            if diverged && repair {
+                let _ = service.delete(&key).await;
+            }
+            stats.record("swap_status", diverged, diverged && repair);
+        }
+
+        Ok(stats)
+    }
+}
+
+// =============================================================================
+// RATES BUDGET
+// =============================================================================
+
+/// RATES_BUDGET_MS (default 3000): total time /swap/rates waits for quotes
+fn rates_budget() -> Duration {
+    let millis = std::env::var("RATES_BUDGET_MS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|ms| *ms > 0)
+        .unwrap_or(3000);
+    Duration::from_millis(millis)
+}
+
+/// Name recorded for the only selection policy: highest net amount wins
+const SELECTION_POLICY: &str = "best_net_amount";
+
+/// Whether a quote can be selected: it accepts `amount` (or, quoted by
+/// receive amount, what it asks to be sent), meets the KYC and
+/// ETA limits (a quote without a rating or ETA fails a limit on it), and is
+/// neither demoted nor flagged by the rate guard
+fn meets_selection_policy(
+    quote: &super::schema::RateResponse,
+    policy: &ProviderSelectionConfig,
+    amount: Decimal,
+) -> bool {
+    let amount = quote.amount_from.unwrap_or(amount);
+    let in_range = amount >= quote.min_amount && (quote.max_amount <= Decimal::ZERO || amount <= quote.max_amount);
+    let kyc_ok = policy.min_kyc_rating.is_none_or(|min| kyc_rating_allows(quote.kyc_rating.as_deref(), min));
+    let eta_ok = policy.max_eta_minutes.is_none_or(|max| quote.eta_minutes.is_some_and(|eta| eta <= max));
+
+    in_range && kyc_ok && eta_ok && !quote.demoted && !quote.rate_warning
+}
+
+/// Whether a provider rated `rating` is at most `max` (A best, D worst);
+/// an unrated provider never is
+pub fn kyc_rating_allows(rating: Option<&str>, max: char) -> bool {
+    rating
+        .and_then(|r| r.trim().chars().next())
+        .is_some_and(|rating| rating.to_ascii_uppercase() <= max)
+}
+
+/// A KYC rating as given by a caller: one letter, A to D, in either case
+pub fn parse_kyc_rating(value: &str) -> Result<char, SwapError> {
+    let mut chars = value.trim().chars();
+    match (chars.next().map(|c| c.to_ascii_uppercase()), chars.next()) {
+        (Some(rating @ 'A'..='D'), None) => Ok(rating),
+        _ => Err(SwapError::InvalidKycRating(value.to_string())),
+    }
+}
+
+/// The quote with the highest receive amount after every fee (best rate,
+/// quoted by receive amount) among those meeting the selection policy, from
+/// a provider preferred by the routing rules when one qualifies
+pub fn select_best_quote<'a>(
+    rates: &'a [super::schema::RateResponse],
+    policy: &ProviderSelectionConfig,
+    amount: Decimal,
+) -> Option<&'a super::schema::RateResponse> {
+    rates
+        .iter()
+        .filter(|r| meets_selection_policy(r, policy, amount))
+        .max_by(|a, b| a.preferred.cmp(&b.preferred).then_with(|| payout_cmp(a, b)))
+}
+
+/// Fallback providers tried after the requested one rejects a trade
+const MAX_FALLBACK_PROVIDERS: usize = 3;
+
+/// Upsert the limits each provider quoted for a pair; a maximum of 0 is
+/// stored as none
+async fn record_pairs(
+    pool: &Pool<MySql>,
+    query: &super::schema::RatesQuery,
+    limits: &[(String, Decimal, Decimal)],
+) -> Result<(), sqlx::Error> {
+    let mut builder = sqlx::QueryBuilder::<MySql>::new(
+        "INSERT INTO pairs (from_currency, from_network, to_currency, to_network, provider, min_amount, max_amount) ",
+    );
+    builder.push_values(limits, |mut row, (provider, min, max)| {
+        row.push_bind(query.from.to_lowercase())
+            .push_bind(&query.network_from)
+            .push_bind(query.to.to_lowercase())
+            .push_bind(&query.network_to)
+            .push_bind(provider)
+            .push_bind((*min).max(Decimal::ZERO))
+            .push_bind((*max > Decimal::ZERO).then_some(*max));
+    });
+    builder.push(
+        " ON DUPLICATE KEY UPDATE min_amount = VALUES(min_amount), max_amount = VALUES(max_amount),
+          last_quoted_at = CURRENT_TIMESTAMP",
+    );
+    builder.build().execute(pool).await?;
+    Ok(())
+}
+
+/// Widest limits among a pair's providers; None without providers
+fn pair_limits(providers: &[super::schema::PairProviderResponse]) -> Option<(Decimal, Option<Decimal>)> {
+    let min = providers.iter().map(|p| p.min_amount).min()?;
+    // Any provider without a maximum leaves the pair without one
+    let max = providers.iter().map(|p| p.max_amount).reduce(|a, b| a.zip(b).map(|(a, b)| a.max(b)))?;
+    Some((min, max))
+}
+
+/// Fold provider rows, ordered by pair, into one entry per pair with the
+/// widest limits any provider offers
+pub fn group_pairs(rows: Vec<PairQuote>) -> Vec<super::schema::PairResponse> {
+    let mut pairs: Vec<super::schema::PairResponse> = Vec::new();
+    for row in rows {
+        let provider = super::schema::PairProviderResponse {
+            provider: row.provider,
+            min_amount: row.min_amount,
+            max_amount: row.max_amount,
+            last_quoted_at: row.last_quoted_at,
+        };
+        match pairs.last_mut() {
+            Some(pair)
+                if pair.from == row.from_currency
+                    && pair.from_network == row.from_network
+                    && pair.to == row.to_currency
+                    && pair.to_network == row.to_network =>
+            {
+                pair.providers.push(provider)
+            }
+            _ => pairs.push(super::schema::PairResponse {
+                from: row.from_currency,
+                to: row.to_currency,
+                from_network: row.from_network,
+                to_network: row.to_network,
+                is_active: row.is_active,
+                min_amount: Decimal::ZERO,
+                max_amount: None,
+                providers: vec![provider],
+            }),
+        }
+    }
+    for pair in pairs.iter_mut() {
+        if let Some((min, max)) = pair_limits(&pair.providers) {
+            (pair.min_amount, pair.max_amount) = (min, max);
+        }
+    }
+    pairs
+}
+
+/// Store one shadowed aggregator's answer to a rates request next to the
+/// best served quote
+async fn record_shadow_quote(
+    pool: &Pool<MySql>,
+    query: &super::schema::RatesQuery,
+    aggregator: &str,
+    result: &Result<AggregatorQuotes, String>,
+    latency: Duration,
+    served: Option<&(String, Decimal)>,
+) -> Result<(), sqlx::Error> {
+    let best = result.as_ref().ok().and_then(|quotes| {
+        quotes.quotes.iter().filter(|q| q.amount_to > Decimal::ZERO).max_by_key(|q| q.amount_to)
+    });
+
+    match (result, best, served) {
+        (Err(e), _, _) => tracing::info!("Shadow {} failed for {}->{}: {}", aggregator, query.from, query.to, e),
+        (Ok(_), Some(best), Some((served_provider, served_amount))) => tracing::info!(
+            "Shadow {} for {}->{}: {} {} vs served {} {} ({:+.2}%)",
+            aggregator,
+            query.from,
+            query.to,
+            best.provider,
+            best.amount_to,
+            served_provider,
+            served_amount,
+            (best.amount_to - served_amount)
+                .checked_div(*served_amount)
+                .map_or(0.0, |diff| money::to_f64(diff * Decimal::ONE_HUNDRED))
+        ),
+        (Ok(_), _, _) => tracing::info!("Shadow {} for {}->{}: no comparable quote", aggregator, query.from, query.to),
+    }
+
+    let error = result.as_ref().err().map(|e| e.chars().take(500).collect::<String>());
+    sqlx::query(
+        "INSERT INTO shadow_quotes (
+            aggregator, from_currency, from_network, to_currency, to_network, amount, succeeded, error,
+            quote_count, best_provider, best_amount, served_provider, served_amount, latency_ms
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(aggregator)
+    .bind(&query.from)
+    .bind(&query.network_from)
+    .bind(&query.to)
+    .bind(&query.network_to)
+    .bind(query.amount)
+    .bind(result.is_ok())
+    .bind(error)
+    .bind(result.as_ref().map(|quotes| quotes.quotes.len() as i32).unwrap_or(0))
+    .bind(best.map(|q| q.provider.clone()))
+    .bind(best.map(|q| q.amount_to))
+    .bind(served.map(|(provider, _)| provider.clone()))
+    .bind(served.map(|(_, amount)| *amount))
+    .bind(latency.as_millis().min(i32::MAX as u128) as i32)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Best payout first, providers that keep failing similar trades last
+pub(super) fn sort_quotes(rates: &mut [super::schema::RateResponse]) {
+    rates.sort_by(|a, b| {
+        b.preferred.cmp(&a.preferred).then_with(|| a.demoted.cmp(&b.demoted)).then_with(|| payout_cmp(b, a))
+    });
+}
+
+/// Which of two quotes pays more: by receive amount, or by rate between
+/// quotes by receive amount, which differ in what they take instead
+fn payout_cmp(a: &super::schema::RateResponse, b: &super::schema::RateResponse) -> std::cmp::Ordering {
+    if a.amount_from.is_some() && b.amount_from.is_some() {
+        a.rate.cmp(&b.rate)
+    } else {
+        a.estimated_amount.cmp(&b.estimated_amount)
+    }
+}
+
+/// A receive amount to quote or swap for, given instead of `amount`
+fn check_amount_to(amount: Decimal, amount_to: Decimal) -> Result<(), SwapError> {
+    if !amount.is_zero() {
+        return Err(SwapError::InvalidAmount("pass amount or amount_to, not both".to_string()));
+    }
+    if amount_to <= Decimal::ZERO {
+        return Err(SwapError::InvalidAmount("amount_to must be positive".to_string()));
+    }
+    Ok(())
+}
+
+/// Rates fetches in flight on this instance, by cache key
+static RATES_FLIGHTS: LazyLock<SingleFlight<Result<super::schema::RatesResponse, Arc<SwapError>>>> =
+    LazyLock::new(SingleFlight::new);
+
+/// A coalesced fetch's error for one of the callers sharing it. The errors a
+/// rates fetch returns are rebuilt as they were; any other is reported as a
+/// provider error.
+fn unshare_error(e: &SwapError) -> SwapError {
+    match e {
+        SwapError::SandboxDisabled => SwapError::SandboxDisabled,
+        SwapError::ProviderUnavailable(msg) => SwapError::ProviderUnavailable(msg.clone()),
+        SwapError::ExternalApiError(msg) => SwapError::ExternalApiError(msg.clone()),
+        other => SwapError::ExternalApiError(other.to_string()),
+    }
+}
+
+fn with_rates_meta(
+    mut rates: super::schema::RatesResponse,
+    budget: Duration,
+    started: tokio::time::Instant,
+) -> super::schema::RatesResponse {
+    rates.meta.budget_ms = budget.as_millis() as u64;
+    rates.meta.elapsed_ms = started.elapsed().as_millis() as u64;
+    rates
+}
+
+// =============================================================================
+// SWAP ROW
+// =============================================================================
+
+/// Columns for `model::SwapRoute`
+const SWAP_ROUTE_SELECT: &str = r#"
+    SELECT id, tenant_id, user_id, from_currency, from_network, via_currency, via_network,
+           to_currency, to_network, amount, estimated_receive,
+           first_swap_id, second_swap_id, status, created_at, updated_at
+    FROM swap_routes
+"#;
+
+/// Columns for `model::Swap`
+const SWAP_SELECT: &str = r#"
+    SELECT id, tenant_id, user_id, brand, provider_id, provider_swap_id, retried_from,
+           from_currency, from_network, to_currency, to_network,
+           amount, estimated_receive, actual_receive, rate,
+           network_fee, provider_fee, platform_fee, total_fee,
+           deposit_address, deposit_extra_id,
+           recipient_address, recipient_extra_id,
+           refund_address, refund_extra_id,
+           tx_hash_in, tx_hash_out, deposit_confirmations,
+           status, version, rate_type, is_sandbox, imported, error,
+           expires_at, completed_at, created_at, updated_at
+    FROM swaps
+"#;
+
+/// Compare-and-swap attempts before a status update gives up
+const MAX_STATUS_UPDATE_ATTEMPTS: u32 = 3;
+
+/// Result of a status update; each carries the stored row
+pub(super) enum StatusUpdate {
+    Applied(super::model::Swap),
+    Superseded(super::model::Swap), // Another writer's status was kept
+    Refused(super::model::Swap),    // Not a transition the state machine allows
+}
+
+/// Rows skipped before a 1-based page; page 0 is treated as page 1
+/// Active currencies, every column; filters are appended with AND
+const CURRENCY_COLUMNS: &str = "SELECT id, symbol, name, network, is_active, delisting_at, logo_url, contract_address,
+     decimals, requires_extra_id, extra_id_name, requires_refund_address, min_amount, max_amount,
+     last_synced_at, created_at, updated_at
+     FROM currencies
+     WHERE is_active = TRUE";
+
+/// Page size of GET /swap/currencies when `per_page` is left out
+const DEFAULT_CURRENCIES_PER_PAGE: usize = 50;
+const MAX_CURRENCIES_PER_PAGE: usize = 500;
+
+/// The ticker, network, memo and search conditions of a currencies query
+fn push_currency_filters(builder: &mut sqlx::QueryBuilder<'_, MySql>, query: &CurrenciesQuery) {
+    if let Some(ref ticker) = query.ticker {
+        builder.push(" AND LOWER(symbol) = LOWER(").push_bind(ticker.clone()).push(")");
+    }
+
+    if let Some(ref network) = query.network {
+        builder.push(" AND network = ").push_bind(network.clone());
+    }
+
+    if let Some(memo) = query.memo {
+        builder.push(" AND requires_extra_id = ").push_bind(memo);
+    }
+
+    // Every word must start a word of the ticker or name. The full-text
+    // index skips words shorter than innodb_ft_min_token_size, so tickers
+    // are also matched by prefix.
+    let words: Vec<String> = query
+        .search
+        .as_deref()
+        .unwrap_or_default()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    if let Some(first) = words.first() {
+        let boolean: Vec<String> = words.iter().map(|w| format!("+{}*", w)).collect();
+        builder
+            .push(" AND (MATCH(symbol, name) AGAINST (")
+            .push_bind(boolean.join(" "))
+            .push(" IN BOOLEAN MODE) OR LOWER(symbol) LIKE ")
+            .push_bind(format!("{}%", first))
+            .push(")");
+    }
+}
+
+/// ORDER BY for a `sort` key, "-" first for descending. Keys map onto fixed
+/// columns; the value itself never reaches the SQL.
+fn currency_order(sort: Option<&str>) -> String {
+    let sort = sort.unwrap_or_default().trim();
+    let (key, direction) = match sort.strip_prefix('-') {
+        Some(key) => (key, "DESC"),
+        None => (sort, "ASC"),
+    };
+    let column = match key {
+        "name" => "name",
+        "network" => "network",
+        "minimum" => "min_amount",
+        "maximum" => "max_amount",
+        "ticker" => "symbol",
+        _ => return " ORDER BY symbol, network".to_string(),
+    };
+    format!(" ORDER BY {} {}, symbol, network", column, direction)
+}
+
+/// A currency as served, with only `fields` when any are given
+fn project_fields(currency: &CurrencyResponse, fields: &[&str]) -> serde_json::Value {
+    let mut value = serde_json::to_value(currency).unwrap_or_default();
+    if let (false, Some(object)) = (fields.is_empty(), value.as_object_mut()) {
+        object.retain(|key, _| fields.contains(&key.as_str()));
+    }
+    value
+}
+
+fn page_offset(page: usize, limit: usize) -> usize {
+    page.saturating_sub(1).saturating_mul(limit)
+}
+
+/// Strong ETag of a pre-serialized listing: the quoted, truncated SHA-256
+/// of its body, so every instance derives the same tag from the same bytes
+pub fn content_etag(json: &str) -> String {
+    let digest = Sha256::digest(json.as_bytes());
+    format!("\"{}\"", digest[..16].iter().map(|b| format!("{:02x}", b)).collect::<String>())
+}
+
+/// Stored next to the raw response under `cache_key`
+fn etag_key(cache_key: &str) -> String {
+    format!("{}:etag", cache_key)
+}
+
+/// A cached raw response with its ETag; one cached before ETags were
+/// stored is hashed on the spot
+async fn cached_raw_json(service: &RedisService, cache_key: &str) -> Option<(String, String)> {
+    let json = service.get_string(cache_key).await.ok()??;
+    if json.is_empty() {
+        return None;
+    }
+    let etag = match service.get_string(&etag_key(cache_key)).await {
+        Ok(Some(etag)) => etag,
+        _ => content_etag(&json),
+    };
+    Some((json, etag))
+}
+
+async fn store_raw_json(service: &RedisService, cache_key: &str, json: &str, etag: &str, ttl_seconds: u64) {
+    let _ = service.set_string(&etag_key(cache_key), etag, ttl_seconds).await;
+    let _ = service.set_string(cache_key, json, ttl_seconds).await;
+}
+
+/// Append `(?, ?, ...)` with one bound value per item; `items` must not be empty
+fn push_bind_list(builder: &mut sqlx::QueryBuilder<'_, MySql>, items: &[&str]) {
+    builder.push("(");
+    let mut separated = builder.separated(", ");
+    for item in items {
+        separated.push_bind(item.to_string());
+    }
+    separated.push_unseparated(")");
+}
+
+/// Largest page served by GET /swap/history
+pub const MAX_HISTORY_LIMIT: u32 = 100;
+
+/// Validated GET /swap/history filters
+struct HistoryFilters {
+    status: Option<super::schema::SwapStatus>,
+    provider: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    from_date: Option<DateTime<Utc>>,
+    to_date: Option<DateTime<Utc>>, // Exclusive
+    sandbox: Option<bool>,
+}
+
+impl HistoryFilters {
+    fn parse(query: &super::schema::HistoryQuery) -> Result<Self, SwapError> {
+        let from_date = query.from_date.as_deref().map(|d| parse_history_date(d, false)).transpose()?;
+        let to_date = query.to_date.as_deref().map(|d| parse_history_date(d, true)).transpose()?;
+        if let (Some(from), Some(to)) = (from_date, to_date) {
+            if from >= to {
+                return Err(SwapError::InvalidHistoryQuery("from_date must be before to_date".to_string()));
+            }
+        }
+
+        let non_empty = |v: &Option<String>| v.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_lowercase);
+        Ok(Self {
+            status: query.status.clone(),
+            provider: non_empty(&query.provider),
+            from: non_empty(&query.from),
+            to: non_empty(&query.to),
+            from_date,
+            to_date,
+            sandbox: query.sandbox,
+        })
+    }
+
+    fn push_where(&self, builder: &mut sqlx::QueryBuilder<'_, sqlx::MySql>, user_id: &str) {
+        builder.push(" WHERE user_id = ").push_bind(user_id.to_string());
+        if let Some(status) = &self.status {
+            builder.push(" AND status = ").push_bind(status.clone());
+        }
+        if let Some(provider) = &self.provider {
+            builder.push(" AND LOWER(provider_id) = ").push_bind(provider.clone());
+        }
+        if let Some(from) = &self.from {
+            builder.push(" AND LOWER(from_currency) = ").push_bind(from.clone());
+        }
+        if let Some(to) = &self.to {
+            builder.push(" AND LOWER(to_currency) = ").push_bind(to.clone());
+        }
+        if let Some(from_date) = self.from_date {
+            builder.push(" AND created_at >= ").push_bind(from_date);
+        }
+        if let Some(to_date) = self.to_date {
+            builder.push(" AND created_at < ").push_bind(to_date);
+        }
+        if let Some(sandbox) = self.sandbox {
+            builder.push(" AND is_sandbox = ").push_bind(sandbox);
+        }
+    }
+}
+
+/// RFC 3339 timestamp or `YYYY-MM-DD`; a plain day used as an upper bound
+/// covers that whole day
+fn parse_history_date(value: &str, end_of_range: bool) -> Result<DateTime<Utc>, SwapError> {
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Ok(ts.with_timezone(&Utc));
+    }
+    let day = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| SwapError::InvalidHistoryQuery(format!("unrecognised date '{}'", value)))?;
+    let day = if end_of_range { day.succ_opt().unwrap_or(day) } else { day };
+    Ok(day.and_time(chrono::NaiveTime::MIN).and_utc())
+}
+
+/// Opaque to clients: `<created_at unix seconds>.<swap id>`
+fn encode_history_cursor(created_at: DateTime<Utc>, id: &str) -> String {
+    format!("{}.{}", created_at.timestamp(), id)
+}
+
+fn decode_history_cursor(cursor: &str) -> Result<(DateTime<Utc>, String), SwapError> {
+    let invalid = || SwapError::InvalidHistoryQuery("invalid cursor".to_string());
+    let (secs, id) = cursor.split_once('.').ok_or_else(invalid)?;
+    let created_at = secs
+        .parse::<i64>()
+        .ok()
+        .and_then(|s| DateTime::from_timestamp(s, 0))
+        .ok_or_else(invalid)?;
+    if id.is_empty() {
+        return Err(invalid());
+    }
+    Ok((created_at, id.to_string()))
+}
+
+impl From<super::model::Swap> for super::schema::SwapSummary {
+    fn from(swap: super::model::Swap) -> Self {
+        Self {
+            swap_id: swap.id,
+            provider: swap.provider_id,
+            from: swap.from_currency,
+            to: swap.to_currency,
+            amount: swap.amount,
+            estimated_receive: swap.estimated_receive,
+            actual_receive: swap.actual_receive,
+            status: swap.status,
+            rate_type: swap.rate_type,
+            is_sandbox: swap.is_sandbox,
+            imported: swap.imported,
+            created_at: swap.created_at,
+            completed_at: swap.completed_at,
+            amount_fiat: None,
+            estimated_receive_fiat: None,
+        }
+    }
+}
+
+/// Upper bound on ids accepted by POST /swap/status/batch
+pub const MAX_BATCH_STATUS_IDS: usize = 50;
+
+/// How long a reserved fixed-rate quote holds
+pub const QUOTE_RESERVATION_SECS: u64 = 300;
+
+type QuoteRow = (
+    String,
+    String,
+    String,
+    String,
+    String,
+    String,
+    String,
+    Decimal,
+    Decimal,
+    Decimal,
+    DateTime<Utc>,
+    DateTime<Utc>,
+);
+
+fn quote_key(quote_id: &str) -> String {
+    format!("swap_quote:{}", quote_id)
+}
+
+/// How long a swap draft can be resumed after it was last saved
+pub const SWAP_DRAFT_TTL_SECS: u64 = 3600;
+
+/// Longest address, memo or ticker a draft may hold
+const MAX_DRAFT_FIELD_LEN: usize = 255;
 
-        Ok(super::schema::RatesResponse {
-            trade_id: trocador_res.trade_id,
-            from: query.from.clone(),
-            network_from: query.network_from.clone(),
-            to: query.to.clone(),
-            network_to: query.network_to.clone(),
-            amount: query.amount,
-            rates,
-        })
+fn swap_draft_key(token: &str) -> String {
+    format!("swap_draft:{}", token)
+}
+
+/// Chain and normalized form of a payout address whose ownership can be
+/// proven by signing; only Bitcoin and EVM wallets sign messages alike
+fn ownership_target(ticker: &str, network: &str, address: &str) -> Result<(Chain, String), SwapError> {
+    let chain = Chain::detect(ticker, network)
+        .filter(|chain| matches!(chain, Chain::Bitcoin | Chain::Evm))
+        .ok_or_else(|| SwapError::VerificationNotSupported(format!("{} on {}", ticker.trim(), network.trim())))?;
+    chain.check_address(address.trim()).map_err(|_| SwapError::InvalidAddress)?;
+    Ok((chain, normalize_address(chain, address)))
+}
+
+fn address_challenge_key(user_id: &str, chain: Chain, address: &str) -> String {
+    format!("address_challenge:{}:{}:{}", user_id, chain.as_str(), address)
+}
+
+fn check_swap_draft(draft: &super::schema::SwapDraft) -> Result<(), SwapError> {
+    let required = [&draft.from, &draft.network_from, &draft.to, &draft.network_to];
+    if required.iter().any(|field| field.trim().is_empty()) {
+        return Err(SwapError::InvalidDraft("from, network_from, to and network_to are required".to_string()));
     }
 
-    // =========================================================================
-    // CREATE SWAP
-    // =========================================================================
+    let optional = [
+        &draft.recipient_address,
+        &draft.recipient_extra_id,
+        &draft.refund_address,
+        &draft.refund_extra_id,
+    ];
+    let quote = draft.quote.iter().flat_map(|q| [&q.trade_id, &q.provider]);
+    if required
+        .into_iter()
+        .chain(optional.into_iter().flatten())
+        .chain(quote)
+        .any(|field| field.len() > MAX_DRAFT_FIELD_LEN)
+    {
+        return Err(SwapError::InvalidDraft(format!("fields are limited to {} characters", MAX_DRAFT_FIELD_LEN)));
+    }
 
-    /// Create a new swap by calling Trocador new_trade and saving to database
-    pub async fn create_swap(
-        &self,
-        request: &super::schema::CreateSwapRequest,
-        user_id: Option<String>,
-    ) -> Result<super::schema::CreateSwapResponse, SwapError> {
-        let api_key = std::env::var("TROCADOR_API_KEY")
-            .map_err(|_| SwapError::ExternalApiError("TROCADOR_API_KEY not set".to_string()))?;
+    if draft.amount.is_some_and(|amount| amount <= Decimal::ZERO) {
+        return Err(SwapError::InvalidDraft("amount must be positive".to_string()));
+    }
 
-        let trocador_client = TrocadorClient::new(api_key);
+    Ok(())
+}
 
-        // 1. Call Trocador API with retry logic
-        let fixed = matches!(request.rate_type, super::schema::RateType::Fixed);
+/// Circuit breaker name for calls made straight to Trocador
+const TROCADOR: &str = "trocador";
 
-        let trocador_res = self.call_trocador_with_retry(|| async {
-            trocador_client
-                .create_trade(
-                    request.trade_id.as_deref(),
-                    &request.from,
-                    &request.network_from,
-                    &request.to,
-                    &request.network_to,
-                    request.amount,
-                    &request.recipient_address,
-                    request.refund_address.as_deref(),
-                    &request.provider,
-                    fixed,
-                )
-                .await
-        })
-        .await?;
+/// Longest window GET /admin/providers/shadow-quotes reports on
+pub const MAX_SHADOW_REPORT_HOURS: u32 = 24 * 30;
 
-        // 2. Map Trocador status to our internal SwapStatus
-        let status = match trocador_res.status.as_str() {
-            "new" | "waiting" => super::schema::SwapStatus::Waiting,
-            "confirming" => super::schema::SwapStatus::Confirming,
-            "sending" => super::schema::SwapStatus::Sending,
-            "finished" => super::schema::SwapStatus::Completed,
-            "failed" | "halted" => super::schema::SwapStatus::Failed,
-            "refunded" => super::schema::SwapStatus::Refunded,
-            "expired" => super::schema::SwapStatus::Expired,
-            _ => super::schema::SwapStatus::Waiting,
-        };
-
-        // 3. Generate local ID and save to database
-        let swap_id = uuid::Uuid::new_v4().to_string();
-        
-        sqlx::query(
-            r#"
-            INSERT INTO swaps (
-                id, user_id, provider_id, provider_swap_id,
-                from_currency, from_network, to_currency, to_network,
-                amount, estimated_receive, rate,
-                deposit_address, deposit_extra_id,
-                recipient_address, recipient_extra_id,
-                refund_address, refund_extra_id,
-                status, rate_type, is_sandbox,
-                created_at, updated_at
-            )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, NOW(), NOW())
-            "#
-        )
-        .bind(&swap_id)
-        .bind(user_id)
-        .bind(&request.provider)
-        .bind(&trocador_res.trade_id)
-        .bind(&request.from)
-        .bind(&request.network_from)
-        .bind(&request.to)
-        .bind(&request.network_to)
-        .bind(request.amount)
-        .bind(trocador_res.amount_to)
-        .bind(trocador_res.amount_to / request.amount) // rate
-        .bind(&trocador_res.address_provider)
-        .bind(&trocador_res.address_provider_memo)
-        .bind(&request.recipient_address)
-        .bind(&request.recipient_extra_id)
-        .bind(&request.refund_address)
-        .bind(&request.refund_extra_id)
-        .bind(status.clone())
-        .bind(&request.rate_type)
-        .bind(request.sandbox)
-        .execute(&self.pool)
-        .await
-        .map_err(|e| SwapError::DatabaseError(e.to_string()))?;
+/// Most refund address suggestions returned for one currency
+pub const MAX_REFUND_SUGGESTIONS: u32 = 10;
 
-        // 4. Transform to response
-        Ok(super::schema::CreateSwapResponse {
-            swap_id,
-            provider: trocador_res.provider,
-            from: request.from.clone(),
-            to: request.to.clone(),
-            deposit_address: trocador_res.address_provider,
-            deposit_extra_id: trocador_res.address_provider_memo,
-            deposit_amount: request.amount,
-            recipient_address: request.recipient_address.clone(),
-            estimated_receive: trocador_res.amount_to,
-            rate: trocador_res.amount_to / request.amount,
-            status,
-            rate_type: request.rate_type.clone(),
-            is_sandbox: request.sandbox,
-            expires_at: Utc::now() + chrono::Duration::minutes(60), // Default expiry if not provided
-            created_at: Utc::now(),
-        })
-    }
+/// address, extra id, used as refund, received a swap, times used, last used
+type AddressUseRow = (String, Option<String>, i64, i64, i64, DateTime<Utc>);
 
-    // =========================================================================
-    // SWAP STATUS
-    // =========================================================================
+/// Most pairs a user can star, and most recent pairs returned
+pub const MAX_FAVORITE_PAIRS: usize = 50;
+pub const MAX_RECENT_PAIRS: u32 = 10;
 
-    /// Get swap status by ID
-    /// 1. Look up swap in database by local swap_id
-    /// 2. Get provider_swap_id (Trocador's trade_id)
-    /// 3. Call Trocador API to get latest status
-    /// 4. Update local database with new status
-    /// 5. Return status to user
-    pub async fn get_swap_status(
-        &self,
-        swap_id: &str,
-    ) -> Result<super::schema::SwapStatusResponse, SwapError> {
-        // 1. Get swap from database - cast DECIMAL to DOUBLE for f64 compatibility
-        let swap = sqlx::query!(
-            r#"
-            SELECT id, user_id, provider_id, provider_swap_id,
-                   from_currency, from_network, to_currency, to_network,
-                   CAST(amount AS DOUBLE) as "amount!: f64",
-                   CAST(estimated_receive AS DOUBLE) as "estimated_receive!: f64",
-                   CAST(actual_receive AS DOUBLE) as "actual_receive: f64",
-                   CAST(rate AS DOUBLE) as "rate!: f64",
-                   CAST(network_fee AS DOUBLE) as "network_fee!: f64",
-                   CAST(provider_fee AS DOUBLE) as "provider_fee!: f64",
-                   CAST(platform_fee AS DOUBLE) as "platform_fee!: f64",
-                   CAST(total_fee AS DOUBLE) as "total_fee!: f64",
-                   deposit_address, deposit_extra_id,
-                   recipient_address, recipient_extra_id,
-                   refund_address, refund_extra_id,
-                   tx_hash_in, tx_hash_out,
-                   status as "status!: super::schema::SwapStatus",
-                   rate_type as "rate_type!: super::schema::RateType",
-                   is_sandbox, error,
-                   expires_at, completed_at, created_at, updated_at
-            FROM swaps
-            WHERE id = ?
-            "#,
-            swap_id
-        )
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| SwapError::DatabaseError(e.to_string()))?
-        .ok_or(SwapError::SwapNotFound)?;
+/// Most recipient addresses a user can save, and most returned; swaps to
+/// new addresses are recorded past it
+pub const MAX_SAVED_ADDRESSES: usize = 100;
 
-        // 2. If we have a provider_swap_id, fetch latest status from Trocador
-        if let Some(ref trocador_id) = swap.provider_swap_id {
-            let api_key = std::env::var("TROCADOR_API_KEY")
-                .map_err(|_| SwapError::ExternalApiError("TROCADOR_API_KEY not set".to_string()))?;
+pub const MAX_ADDRESS_LABEL_CHARS: usize = 64;
 
-            let trocador_client = TrocadorClient::new(api_key);
+const SAVED_ADDRESS_SELECT: &str = "SELECT id, user_id, currency, network, address, extra_id, label, times_used,
+       last_used_at, created_at, deleted_at
+FROM saved_addresses";
 
-            // Call Trocador API with retry logic
-            match self.call_trocador_with_retry(|| async {
-                trocador_client.get_trade_status(trocador_id).await
-            }).await {
-                Ok(trocador_status) => {
-                    // 3. Map Trocador status to our internal status
-                    let new_status = self.map_trocador_status(&trocador_status.status);
-                    
-                    // 4. Update database if status changed
-                    if new_status != swap.status {
-                        self.update_swap_status(
-                            swap_id,
-                            &new_status,
-                            trocador_status.amount_to,
-                            None, // tx_hash_in from Trocador if available
-                            None, // tx_hash_out from Trocador if available
-                        ).await?;
-
-                        // Log status change to history
-                        self.log_status_change(swap_id, &new_status, None).await?;
-                    }
+/// from, network, to, network, swaps, last swap
+type RecentPairRow = (String, String, String, String, i64, DateTime<Utc>);
 
-                    // 5. Return updated status
-                    return Ok(super::schema::SwapStatusResponse {
-                        swap_id: swap.id.clone(),
-                        provider: swap.provider_id.clone(),
-                        provider_swap_id: swap.provider_swap_id.clone(),
-                        status: new_status.clone(),
-                        from: swap.from_currency.clone(),
-                        to: swap.to_currency.clone(),
-                        amount: swap.amount,
-                        deposit_address: swap.deposit_address.clone(),
-                        deposit_extra_id: swap.deposit_extra_id.clone(),
-                        recipient_address: swap.recipient_address.clone(),
-                        recipient_extra_id: swap.recipient_extra_id.clone(),
-                        rate: swap.rate,
-                        estimated_receive: swap.estimated_receive,
-                        actual_receive: Some(trocador_status.amount_to),
-                        network_fee: swap.network_fee,
-                        total_fee: swap.total_fee,
-                        rate_type: swap.rate_type.clone(),
-                        is_sandbox: swap.is_sandbox != 0,
-                        tx_hash_in: swap.tx_hash_in.clone(),
-                        tx_hash_out: swap.tx_hash_out.clone(),
-                        error: swap.error.clone(),
-                        created_at: swap.created_at,
-                        updated_at: Utc::now(),
-                        expires_at: swap.expires_at,
-                        completed_at: if new_status == super::schema::SwapStatus::Completed {
-                            Some(Utc::now())
-                        } else {
-                            swap.completed_at
-                        },
-                    });
-                }
-                Err(e) => {
-                    // If Trocador API fails, return cached status from database
-                    tracing::warn!("Failed to fetch status from Trocador for swap {}: {}", swap_id, e);
-                }
-            }
+/// Whether any of `entries` that is cached differs from its expected value;
+/// `None` when none of them is cached. Unreadable entries count as divergent.
+async fn cache_diverges(
+    service: &RedisService,
+    entries: &[(&str, serde_json::Result<serde_json::Value>)],
+) -> Option<bool> {
+    let mut cached_any = false;
+    for (key, expected) in entries {
+        let Ok(Some(raw)) = service.get_string(key).await else {
+            continue;
+        };
+        cached_any = true;
+
+        let matches = match (serde_json::from_str::<serde_json::Value>(&raw), expected) {
+            (Ok(cached), Ok(expected)) => cached == *expected,
+            _ => false,
+        };
+        if !matches {
+            return Some(true);
         }
+    }
+    cached_any.then_some(false)
+}
 
-        // 6. Return status from database (if no provider_swap_id or Trocador call failed)
-        Ok(super::schema::SwapStatusResponse {
+/// Keyed by the swap's tenant, so a scoped lookup never sees another tenant's swap
+fn swap_status_cache_key(tenant: &TenantId, swap_id: &str) -> String {
+    tenant.cache_key(&format!("swap_status:{}", swap_id))
+}
+
+/// Redis pub/sub channel carrying a swap's status changes
+pub fn swap_status_channel(swap_id: &str) -> String {
+    format!("swap_status_updates:{}", swap_id)
+}
+
+impl From<super::model::Swap> for super::schema::SwapStatusResponse {
+    fn from(swap: super::model::Swap) -> Self {
+        let deposit_tx_url = swap
+            .tx_hash_in
+            .as_deref()
+            .and_then(|hash| explorers().tx_url(&swap.from_currency, &swap.from_network, hash));
+        let payout_tx_url = swap
+            .tx_hash_out
+            .as_deref()
+            .and_then(|hash| explorers().tx_url(&swap.to_currency, &swap.to_network, hash));
+
+        Self {
             swap_id: swap.id,
             provider: swap.provider_id,
             provider_swap_id: swap.provider_swap_id,
@@ -1046,189 +6418,238 @@ impl SwapCrud {
             network_fee: swap.network_fee,
             total_fee: swap.total_fee,
             rate_type: swap.rate_type,
-            is_sandbox: swap.is_sandbox != 0,
-            tx_hash_in: swap.tx_hash_in,
-            tx_hash_out: swap.tx_hash_out,
+            is_sandbox: swap.is_sandbox,
+            imported: swap.imported,
+            deposit_tx_hash: swap.tx_hash_in,
+            deposit_tx_url,
+            deposit_confirmations: swap.deposit_confirmations,
+            payout_tx_hash: swap.tx_hash_out,
+            payout_tx_url,
             error: swap.error,
             created_at: swap.created_at,
             updated_at: swap.updated_at,
             expires_at: swap.expires_at,
             completed_at: swap.completed_at,
-        })
-    }
-
-    /// Map Trocador status string to our SwapStatus enum
-    fn map_trocador_status(&self, trocador_status: &str) -> super::schema::SwapStatus {
-        match trocador_status {
-            "new" | "waiting" => super::schema::SwapStatus::Waiting,
-            "confirming" => super::schema::SwapStatus::Confirming,
-            "exchanging" => super::schema::SwapStatus::Exchanging,
-            "sending" => super::schema::SwapStatus::Sending,
-            "finished" | "paid partially" => super::schema::SwapStatus::Completed,
-            "failed" | "halted" => super::schema::SwapStatus::Failed,
-            "refunded" => super::schema::SwapStatus::Refunded,
-            "expired" => super::schema::SwapStatus::Expired,
-            _ => super::schema::SwapStatus::Waiting,
+            version: swap.version,
+            tenant: TenantId::new(&swap.tenant_id).unwrap_or_default(),
         }
     }
+}
 
-    /// Update swap status in database
-    async fn update_swap_status(
-        &self,
-        swap_id: &str,
-        status: &super::schema::SwapStatus,
-        actual_receive: f64,
-        tx_hash_in: Option<String>,
-        tx_hash_out: Option<String>,
-    ) -> Result<(), SwapError> {
-        let completed_at = if *status == super::schema::SwapStatus::Completed {
-            Some(Utc::now())
-        } else {
-            None
-        };
+// =============================================================================
+// PROVIDER FAILURE TRACKING
+// =============================================================================
 
-        sqlx::query(
-            r#"
-            UPDATE swaps
-            SET status = ?,
-                actual_receive = ?,
-                tx_hash_in = COALESCE(?, tx_hash_in),
-                tx_hash_out = COALESCE(?, tx_hash_out),
-                completed_at = COALESCE(?, completed_at),
-                updated_at = NOW()
-            WHERE id = ?
-            "#
-        )
-        .bind(status)
-        .bind(actual_receive)
-        .bind(tx_hash_in)
-        .bind(tx_hash_out)
-        .bind(completed_at)
-        .bind(swap_id)
-        .execute(&self.pool)
-        .await
-        .map_err(|e| SwapError::DatabaseError(e.to_string()))?;
+/// How long a failed trade creation counts against a provider
+const PROVIDER_FAILURE_WINDOW_SECS: u64 = 3600;
 
-        Ok(())
+/// Failures within the window after which a provider's quotes are ranked last
+const PROVIDER_FAILURE_DEMOTE_THRESHOLD: u32 = 2;
+
+/// Amounts are grouped by order of magnitude (0.01-0.1, 0.1-1, 1-10, ...)
+fn amount_band(amount: Decimal) -> i32 {
+    if amount > Decimal::ZERO {
+        money::to_f64(amount).log10().floor() as i32
+    } else {
+        i32::MIN
     }
+}
 
-    /// Log status change to swap_status_history table
-    async fn log_status_change(
-        &self,
-        swap_id: &str,
-        status: &super::schema::SwapStatus,
-        message: Option<String>,
-    ) -> Result<(), SwapError> {
-        sqlx::query(
-            r#"
-            INSERT INTO swap_status_history (swap_id, status, message, created_at)
-            VALUES (?, ?, ?, NOW())
-            "#
-        )
-        .bind(swap_id)
-        .bind(status)
-        .bind(message)
-        .execute(&self.pool)
-        .await
-        .map_err(|e| SwapError::DatabaseError(e.to_string()))?;
+fn provider_failure_key(
+    provider: &str,
+    from: &str,
+    network_from: &str,
+    to: &str,
+    network_to: &str,
+    amount: Decimal,
+) -> String {
+    format!(
+        "provider_failures:{}:{}:{}:{}:{}:{}",
+        provider.to_lowercase(),
+        from.to_lowercase(),
+        network_from.to_lowercase(),
+        to.to_lowercase(),
+        network_to.to_lowercase(),
+        amount_band(amount)
+    )
+}
 
-        Ok(())
-    }
+// =============================================================================
+// MEMO / EXTRA ID FORMAT
+// =============================================================================
 
-    // =========================================================================
-    // ADDRESS VALIDATION
-    // =========================================================================
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|v| !v.is_empty())
+}
 
-    /// Validate cryptocurrency address using Trocador API
-    pub async fn validate_address(
-        &self,
-        request: &super::schema::ValidateAddressRequest,
-    ) -> Result<super::schema::ValidateAddressResponse, SwapError> {
-        // 1. Validate input
-        if request.ticker.trim().is_empty() {
-            return Err(SwapError::InvalidAddress);
+/// Reject recipient and refund addresses, and XRP destination tags, that fail
+/// the built-in checks for their chain before anything reaches the provider
+pub(super) fn check_addresses_locally(request: &super::schema::CreateSwapRequest) -> Result<(), SwapError> {
+    let invalid_extra_id = |ticker: &str, reason| SwapError::InvalidExtraId {
+        ticker: ticker.to_string(),
+        extra_id_name: None,
+        reason,
+    };
+
+    if let Some(chain) = Chain::detect(&request.to, &request.network_to) {
+        let address = request.recipient_address.trim();
+        chain.check_address(address).map_err(|_| SwapError::InvalidAddress)?;
+        if let Some(extra_id) = non_empty(request.recipient_extra_id.as_deref()) {
+            chain.check_extra_id(address, extra_id).map_err(|reason| invalid_extra_id(&request.to, reason))?;
         }
+    }
 
-        if request.network.trim().is_empty() {
-            return Err(SwapError::InvalidAddress);
+    let refund_address = non_empty(request.refund_address.as_deref());
+    if let (Some(chain), Some(address)) = (Chain::detect(&request.from, &request.network_from), refund_address) {
+        chain.check_address(address).map_err(|_| SwapError::InvalidRefundAddress)?;
+        if let Some(extra_id) = non_empty(request.refund_extra_id.as_deref()) {
+            chain.check_extra_id(address, extra_id).map_err(|reason| invalid_extra_id(&request.from, reason))?;
         }
+    }
 
-        if request.address.trim().is_empty() {
-            return Err(SwapError::InvalidAddress);
+    Ok(())
+}
+
+/// A swap request's currencies as stored, with their registered address
+/// formats where there is a registry (the lightweight server has none)
+pub(super) struct RequestCurrencies<'a> {
+    pub from: Option<&'a Currency>,
+    pub to: Option<&'a Currency>,
+    pub from_format: Option<&'a address_format::AddressFormat>,
+    pub to_format: Option<&'a address_format::AddressFormat>,
+}
+
+/// Checks of a swap request against its currencies, before anything reaches
+/// the provider: delisting dates, decimal places, the built-in address
+/// checks, the refund address policy and memos. Shared by SwapCrud and the
+/// lightweight server (modules::swap::lite).
+pub(super) fn check_request_currencies(
+    request: &super::schema::CreateSwapRequest,
+    currencies: &RequestCurrencies,
+) -> Result<(), SwapError> {
+    let now = Utc::now();
+    for (ticker, currency) in [(&request.from, currencies.from), (&request.to, currencies.to)] {
+        if currency.and_then(|c| c.delisting_at).is_some_and(|at| at <= now) {
+            return Err(SwapError::CurrencyDelisted(ticker.clone()));
         }
+    }
+
+    let decimals =
+        |currency: Option<&Currency>| currency.map_or(money::DEFAULT_DECIMALS, |c| money::scale_of(c.decimals));
+    check_decimals("amount", &request.from, request.amount, decimals(currencies.from))?;
+    if let Some(amount_to) = request.amount_to {
+        check_decimals("amount_to", &request.to, amount_to, decimals(currencies.to))?;
+    }
+
+    check_addresses_locally(request)?;
+    let refund_required = currencies.from.is_some_and(|c| c.requires_refund_address);
+    if refund_required && non_empty(request.refund_address.as_deref()).is_none() {
+        return Err(SwapError::RefundAddressRequired(request.from.clone()));
+    }
+    check_extra_ids(request, currencies)
+}
 
-        // 2. Get API key
-        let api_key = std::env::var("TROCADOR_API_KEY")
-            .map_err(|_| SwapError::ExternalApiError("TROCADOR_API_KEY not set".to_string()))?;
+/// Refuse an amount with more decimal places than its currency keeps
+fn check_decimals(field: &str, ticker: &str, amount: Decimal, decimals: u32) -> Result<(), SwapError> {
+    if money::exceeds_decimals(amount, decimals) {
+        return Err(SwapError::InvalidAmount(format!(
+            "{} has more than {} decimal places for {}",
+            field, decimals, ticker
+        )));
+    }
+    Ok(())
+}
 
-        let trocador_client = TrocadorClient::new(api_key);
+/// Require a recipient memo/tag where the destination currency needs one and
+/// check the format of any memo supplied for recipient or refund against
+/// the network's registered address format
+fn check_extra_ids(
+    request: &super::schema::CreateSwapRequest,
+    currencies: &RequestCurrencies,
+) -> Result<(), SwapError> {
+    let recipient_extra_id = non_empty(request.recipient_extra_id.as_deref());
+    let (to_currency, to_format) = (currencies.to, currencies.to_format);
 
-        // 3. Call Trocador API with retry logic
-        let is_valid = self.call_trocador_with_retry(|| async {
-            trocador_client
-                .validate_address(&request.ticker, &request.network, &request.address)
-                .await
-        })
-        .await?;
+    let required = to_currency.is_some_and(|c| c.requires_extra_id) || to_format.is_some_and(|f| f.memo_required);
+    let extra_id_name = || {
+        to_currency
+            .and_then(|c| c.extra_id_name.clone())
+            .or_else(|| to_format.and_then(|f| f.memo_name.clone()))
+    };
 
-        // 4. Return response
-        Ok(super::schema::ValidateAddressResponse {
-            valid: is_valid,
-            ticker: request.ticker.clone(),
-            network: request.network.clone(),
-            address: request.address.clone(),
-        })
+    if required && recipient_extra_id.is_none() {
+        return Err(SwapError::ExtraIdRequired {
+            ticker: request.to.clone(),
+            extra_id_name: extra_id_name(),
+        });
     }
 
-    // =========================================================================
-    // RETRY LOGIC FOR RATE LIMITING
-    // =========================================================================
+    if let Some(extra_id) = recipient_extra_id {
+        check_extra_id_format(to_format, extra_id).map_err(|reason| SwapError::InvalidExtraId {
+            ticker: request.to.clone(),
+            extra_id_name: extra_id_name(),
+            reason,
+        })?;
+    }
 
-    /// Call Trocador API with exponential backoff retry logic
-    /// Handles rate limiting gracefully by retrying with increasing delays
-    async fn call_trocador_with_retry<F, Fut, T>(
-        &self,
-        f: F,
-    ) -> Result<T, SwapError>
-    where
-        F: Fn() -> Fut,
-        Fut: std::future::Future<Output = Result<T, TrocadorError>>,
-    {
-        let max_retries = 5;
-        let mut retries = 0;
+    if let Some(extra_id) = non_empty(request.refund_extra_id.as_deref()) {
+        check_extra_id_format(currencies.from_format, extra_id).map_err(|reason| SwapError::InvalidExtraId {
+            ticker: request.from.clone(),
+            extra_id_name: currencies
+                .from
+                .and_then(|c| c.extra_id_name.clone())
+                .or_else(|| currencies.from_format.and_then(|f| f.memo_name.clone())),
+            reason,
+        })?;
+    }
 
-        loop {
-            match f().await {
-                Ok(result) => return Ok(result),
-                Err(e) => {
-                    let error_msg = e.to_string();
-                    
-                    // Check if it's a rate limit error
-                    let is_rate_limit = error_msg.contains("Rate limit")
-                        || error_msg.contains("rate limit")
-                        || error_msg.contains("429")
-                        || error_msg.contains("Too Many Requests");
+    Ok(())
+}
 
-                    if is_rate_limit && retries < max_retries {
-                        retries += 1;
-                        // Exponential backoff: 2s, 4s, 8s, 16s, 32s
-                        let delay_secs = 2u64.pow(retries);
-                        
-                        tracing::warn!(
-                            "Rate limit hit, retrying in {}s (attempt {}/{})",
-                            delay_secs,
-                            retries,
-                            max_retries
-                        );
-                        
-                        tokio::time::sleep(Duration::from_secs(delay_secs)).await;
-                        continue;
-                    }
+/// Refuse a swap worth more than its per-swap USD limit, valued on the
+/// sending side at the HIGH_VALUE_USD_PRICES reference prices (before the
+/// provider quotes what it receives). Swaps that can't be priced pass.
+pub(super) fn check_usd_limit(
+    request: &super::schema::CreateSwapRequest,
+    address_verification: &AddressVerificationConfig,
+    high_value: &HighValueConfig,
+    recipient_verified: bool,
+) -> Result<(), SwapError> {
+    if let Some(limit) = address_verification.swap_limit_usd(recipient_verified) {
+        let usd_value = high_value.usd_amount(&request.from, request.amount);
+        if usd_value.is_some_and(|value| value > limit) {
+            return Err(SwapError::SwapLimitExceeded { limit, verified: recipient_verified });
+        }
+    }
+    Ok(())
+}
 
-                    // Not a rate limit error or max retries exceeded
-                    return Err(SwapError::from(e));
-                }
-            }
+/// Take the platform fee under `rules` out of each quote and re-rank by what
+/// the user receives
+pub(super) fn apply_fee_rules(
+    rates: &mut super::schema::RatesResponse,
+    rules: &[fees::FeeRule],
+    user_tier: Option<&str>,
+) {
+    for rate in rates.rates.iter_mut() {
+        let scope = FeeScope { from: &rates.from, to: &rates.to, provider: &rate.provider, user_tier };
+        let fee = fees::platform_fee(rules, &scope, rate.estimated_amount);
+        rate.platform_fee = fee;
+        rate.total_fee += fee;
+        rate.estimated_amount -= fee;
+        let amount_from = rate.amount_from.unwrap_or(rates.amount);
+        if amount_from > Decimal::ZERO {
+            rate.rate = money::rate(rate.estimated_amount, amount_from);
         }
     }
+    sort_quotes(&mut rates.rates);
+}
+
+/// Memo rules come from the address format registry; currencies without a
+/// registered format only get the generic length limit
+fn check_extra_id_format(format: Option<&address_format::AddressFormat>, extra_id: &str) -> Result<(), String> {
+    match format {
+        Some(format) => format.check_memo(extra_id),
+        None => address_format::check_memo_default(extra_id),
+    }
 }
@@ -0,0 +1,428 @@
+//! SwapService over gRPC for internal services (feature `grpc`).
+//!
+//! Serves `proto/swap.proto` on GRPC_PORT next to the HTTP API, sharing its
+//! `AppState`, so rates, swaps and statuses come from the same `SwapCrud`
+//! calls and caches as `GET /swap/rates`, `POST /swap/create` and
+//! `GET /swap/{id}`. Call metadata goes through the HTTP extractors:
+//! `authorization` links the caller's account, `x-api-key` picks a partner
+//! brand. `WatchSwap` follows the Redis status channel the way
+//! `GET /swap/{id}/ws` does. Errors map to the closest gRPC code and carry
+//! the HTTP error's `code` in the `error-code` metadata key.
+
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use rust_decimal::Decimal;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+use tonic::metadata::{MetadataMap, MetadataValue};
+use tonic::{Code, Request, Response, Status};
+
+use super::controller::swap_crud;
+use super::crud::{swap_status_channel, SwapError};
+use super::schema::{CreateSwapRequest, CreateSwapResponse, RateResponse, RateType, RatesQuery, RatesResponse, SwapStatusResponse};
+use crate::modules::auth::interface::OptionalUser;
+use crate::modules::auth::model::User;
+use crate::services::branding::{Brand, CurrentBrand};
+use crate::services::maintenance::MaintenanceService;
+use crate::services::metrics::metrics;
+use crate::services::money;
+use crate::services::routing::ClientCountry;
+use crate::AppState;
+
+pub mod proto {
+    tonic::include_proto!("exchange.swap.v1");
+}
+
+use proto::swap_service_server::{SwapService, SwapServiceServer};
+
+/// Status updates buffered per WatchSwap call before the sender waits
+const WATCH_BUFFER: usize = 16;
+
+pub struct GrpcSwapService {
+    state: Arc<AppState>,
+}
+
+impl GrpcSwapService {
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self { state }
+    }
+
+    pub fn into_server(self) -> SwapServiceServer<Self> {
+        SwapServiceServer::new(self)
+    }
+
+    /// The signed-in user and brand of a call, from its metadata
+    async fn caller(&self, metadata: &MetadataMap) -> (Option<User>, Brand, Parts) {
+        let mut parts = request_parts(metadata);
+        let user = match OptionalUser::from_request_parts(&mut parts, &self.state).await {
+            Ok(OptionalUser(user)) => user,
+            Err(never) => match never {},
+        };
+        let brand = match CurrentBrand::from_request_parts(&mut parts, &self.state).await {
+            Ok(CurrentBrand(brand)) => brand,
+            Err(never) => match never {},
+        };
+        (user, brand, parts)
+    }
+}
+
+/// Serve SwapService on `port` until the process exits
+pub fn spawn_grpc_server(state: Arc<AppState>, port: u16) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let addr = std::net::SocketAddr::from(([0, 0, 0, 0], port));
+        tracing::info!("gRPC SwapService listening on {}", addr);
+        let server = tonic::transport::Server::builder()
+            .add_service(GrpcSwapService::new(state).into_server())
+            .serve(addr);
+        if let Err(e) = server.await {
+            tracing::error!("gRPC server stopped: {}", e);
+        }
+    })
+}
+
+#[tonic::async_trait]
+impl SwapService for GrpcSwapService {
+    async fn get_rates(
+        &self,
+        request: Request<proto::GetRatesRequest>,
+    ) -> Result<Response<proto::GetRatesResponse>, Status> {
+        let (user, brand, parts) = self.caller(request.metadata()).await;
+        let max_kyc_rating = user.as_ref().and_then(|u| u.kyc_limit());
+        let crud = swap_crud(&self.state)
+            .with_brand(brand)
+            .with_fee_tier(user.map(|u| u.fee_tier))
+            .with_max_kyc_rating(max_kyc_rating)
+            .with_country(ClientCountry::from_headers(&parts.headers).0);
+
+        let query = rates_query(request.into_inner())?;
+        let response = crud.get_rates_optimized(&query).await.map_err(swap_status)?;
+
+        Ok(Response::new(response.into()))
+    }
+
+    async fn create_swap(
+        &self,
+        request: Request<proto::CreateSwapRequest>,
+    ) -> Result<Response<proto::CreateSwapResponse>, Status> {
+        let maintenance = MaintenanceService::new(self.state.db.clone(), self.state.redis.clone()).current().await;
+        if maintenance.enabled {
+            return Err(with_error_code(
+                Status::unavailable(maintenance.message_for(None)),
+                "MAINTENANCE_MODE",
+            ));
+        }
+
+        let (user, brand, parts) = self.caller(request.metadata()).await;
+        let max_kyc_rating = user.as_ref().and_then(|u| u.kyc_limit());
+        let (user_id, fee_tier) = user.map(|u| (u.id, u.fee_tier)).unzip();
+        let crud = swap_crud(&self.state)
+            .with_outbox(self.state.outbox.clone())
+            .with_brand(brand)
+            .with_fee_tier(fee_tier)
+            .with_max_kyc_rating(max_kyc_rating)
+            .with_country(ClientCountry::from_headers(&parts.headers).0);
+
+        let payload = create_swap_request(request.into_inner())?;
+
+        // Same concurrency limit as POST /swap/create
+        let admitted = match self.state.admission.enabled() {
+            true => match self.state.admission.admit().await {
+                Ok(admitted) => Some(admitted),
+                Err(_) => {
+                    metrics().swap_admission.inc("rejected");
+                    return Err(Status::resource_exhausted("Too many swaps being created, try again shortly"));
+                }
+            },
+            false => None,
+        };
+        let response = crud.create_swap(&payload, user_id).await;
+        if let Some(admitted) = admitted {
+            self.state.admission.finish(admitted);
+        }
+
+        Ok(Response::new(response.map_err(swap_status)?.into()))
+    }
+
+    async fn get_swap_status(
+        &self,
+        request: Request<proto::GetSwapStatusRequest>,
+    ) -> Result<Response<proto::SwapStatus>, Status> {
+        let (_, brand, _) = self.caller(request.metadata()).await;
+        let crud = swap_crud(&self.state).with_outbox(self.state.outbox.clone()).with_tenant(brand.tenant);
+        let swap_id = request.into_inner().swap_id;
+
+        // During maintenance serve what we have instead of polling the provider
+        let maintenance = MaintenanceService::new(self.state.db.clone(), self.state.redis.clone()).current().await;
+        let response = if maintenance.enabled {
+            crud.get_stored_swap_status(&swap_id).await
+        } else {
+            crud.get_swap_status(&swap_id).await
+        };
+
+        Ok(Response::new(response.map_err(swap_status)?.into()))
+    }
+
+    type WatchSwapStream = Pin<Box<dyn Stream<Item = Result<proto::SwapStatus, Status>> + Send>>;
+
+    async fn watch_swap(
+        &self,
+        request: Request<proto::GetSwapStatusRequest>,
+    ) -> Result<Response<Self::WatchSwapStream>, Status> {
+        let (_, brand, _) = self.caller(request.metadata()).await;
+        let crud = swap_crud(&self.state).with_tenant(brand.tenant);
+        let swap_id = request.into_inner().swap_id;
+
+        // Subscribe before reading the snapshot so no change falls between the two
+        let mut pubsub = self
+            .state
+            .redis
+            .subscribe(&swap_status_channel(&swap_id))
+            .await
+            .map_err(|e| Status::unavailable(format!("Status updates unavailable: {}", e)))?;
+        let snapshot = crud.get_stored_swap_status(&swap_id).await.map_err(swap_status)?;
+
+        let (sender, receiver) = mpsc::channel(WATCH_BUFFER);
+        tokio::spawn(async move {
+            let finished = snapshot.status.is_final();
+            let mut last_version = snapshot.version;
+            if sender.send(Ok(snapshot.into())).await.is_err() || finished {
+                return;
+            }
+
+            let mut updates = pubsub.on_message();
+            loop {
+                tokio::select! {
+                    update = updates.next() => {
+                        let Some(update) = update else {
+                            let _ = sender.send(Err(Status::unavailable("Status updates unavailable"))).await;
+                            return;
+                        };
+                        let Some(update) = update
+                            .get_payload::<String>()
+                            .ok()
+                            .and_then(|payload| serde_json::from_str::<SwapStatusResponse>(&payload).ok())
+                        else {
+                            continue;
+                        };
+
+                        // Pub/sub can deliver a change the snapshot already included
+                        if update.version <= last_version {
+                            continue;
+                        }
+                        last_version = update.version;
+
+                        let finished = update.status.is_final();
+                        if sender.send(Ok(update.into())).await.is_err() || finished {
+                            return;
+                        }
+                    }
+                    _ = sender.closed() => return,
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(receiver))))
+    }
+}
+
+// =============================================================================
+// CONVERSIONS
+// =============================================================================
+
+/// The call's metadata as HTTP request parts, for the HTTP extractors
+fn request_parts(metadata: &MetadataMap) -> Parts {
+    let mut request = axum::http::Request::new(());
+    *request.headers_mut() = metadata.clone().into_headers();
+    request.into_parts().0
+}
+
+/// gRPC status for a swap error, closest to its HTTP status
+pub fn swap_status(error: SwapError) -> Status {
+    use axum::http::StatusCode;
+    let code = match error.status_code() {
+        StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => Code::InvalidArgument,
+        StatusCode::NOT_FOUND => Code::NotFound,
+        StatusCode::FORBIDDEN => Code::PermissionDenied,
+        StatusCode::CONFLICT | StatusCode::GONE => Code::FailedPrecondition,
+        StatusCode::TOO_MANY_REQUESTS => Code::ResourceExhausted,
+        StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT => Code::Unavailable,
+        _ => Code::Internal,
+    };
+    let error_code = error.error_code();
+    with_error_code(Status::new(code, error.to_string()), error_code)
+}
+
+fn with_error_code(mut status: Status, error_code: &'static str) -> Status {
+    status.metadata_mut().insert("error-code", MetadataValue::from_static(error_code));
+    status
+}
+
+fn parse_rate_type(rate_type: Option<&str>) -> Result<Option<RateType>, Status> {
+    match rate_type.map(|r| r.trim().to_lowercase()).as_deref() {
+        None | Some("") => Ok(None),
+        Some("fixed") => Ok(Some(RateType::Fixed)),
+        Some("floating") => Ok(Some(RateType::Floating)),
+        Some(other) => Err(Status::invalid_argument(format!("Unknown rate_type {}", other))),
+    }
+}
+
+/// A decimal string field; empty counts as zero, as an unset proto3 scalar would
+fn parse_decimal(field: &str, value: &str) -> Result<Decimal, Status> {
+    if value.trim().is_empty() {
+        return Ok(Decimal::ZERO);
+    }
+    money::parse_amount(value).ok_or_else(|| Status::invalid_argument(format!("{} must be a decimal number", field)))
+}
+
+fn rate_type_name(rate_type: &RateType) -> String {
+    match rate_type {
+        RateType::Fixed => "fixed",
+        RateType::Floating => "floating",
+    }
+    .to_string()
+}
+
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339()
+}
+
+pub fn rates_query(request: proto::GetRatesRequest) -> Result<RatesQuery, Status> {
+    Ok(RatesQuery {
+        from: request.from,
+        network_from: request.network_from,
+        to: request.to,
+        network_to: request.network_to,
+        amount: parse_decimal("amount", &request.amount)?,
+        amount_to: request.amount_to.as_deref().map(|a| parse_decimal("amount_to", a)).transpose()?,
+        rate_type: parse_rate_type(request.rate_type.as_deref())?,
+        provider: request.provider.filter(|p| !p.is_empty()),
+        max_kyc_rating: request.max_kyc_rating.filter(|r| !r.is_empty()),
+        sandbox: request.sandbox,
+    })
+}
+
+pub fn create_swap_request(request: proto::CreateSwapRequest) -> Result<CreateSwapRequest, Status> {
+    Ok(CreateSwapRequest {
+        trade_id: request.trade_id,
+        from: request.from,
+        network_from: request.network_from,
+        to: request.to,
+        network_to: request.network_to,
+        amount: parse_decimal("amount", &request.amount)?,
+        amount_to: request.amount_to.as_deref().map(|a| parse_decimal("amount_to", a)).transpose()?,
+        provider: request.provider,
+        recipient_address: request.recipient_address,
+        recipient_extra_id: request.recipient_extra_id,
+        refund_address: request.refund_address,
+        refund_extra_id: request.refund_extra_id,
+        rate_type: parse_rate_type(request.rate_type.as_deref())?.unwrap_or_default(),
+        sandbox: request.sandbox,
+        allow_fallback: request.allow_fallback,
+        dry_run: false,
+        quote_id: request.quote_id,
+    })
+}
+
+impl From<RateResponse> for proto::Rate {
+    fn from(rate: RateResponse) -> Self {
+        Self {
+            provider: rate.provider,
+            provider_name: rate.provider_name,
+            aggregator: rate.aggregator,
+            rate: rate.rate.to_string(),
+            estimated_amount: rate.estimated_amount.to_string(),
+            amount_from: rate.amount_from.map(|a| a.to_string()),
+            min_amount: rate.min_amount.to_string(),
+            max_amount: rate.max_amount.to_string(),
+            network_fee: rate.network_fee.to_string(),
+            provider_fee: rate.provider_fee.to_string(),
+            platform_fee: rate.platform_fee.to_string(),
+            total_fee: rate.total_fee.to_string(),
+            rate_type: rate_type_name(&rate.rate_type),
+            kyc_required: rate.kyc_required,
+            kyc_rating: rate.kyc_rating,
+            eta_minutes: rate.eta_minutes,
+            demoted: rate.demoted,
+            rate_warning: rate.rate_warning,
+            preferred: rate.preferred,
+        }
+    }
+}
+
+impl From<RatesResponse> for proto::GetRatesResponse {
+    fn from(rates: RatesResponse) -> Self {
+        Self {
+            trade_id: rates.trade_id,
+            from: rates.from,
+            network_from: rates.network_from,
+            to: rates.to,
+            network_to: rates.network_to,
+            amount: rates.amount.to_string(),
+            amount_to: rates.amount_to.map(|a| a.to_string()),
+            rates: rates.rates.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<CreateSwapResponse> for proto::CreateSwapResponse {
+    fn from(swap: CreateSwapResponse) -> Self {
+        Self {
+            swap_id: swap.swap_id,
+            provider: swap.provider,
+            from: swap.from,
+            to: swap.to,
+            deposit_address: swap.deposit_address,
+            deposit_extra_id: swap.deposit_extra_id,
+            deposit_amount: swap.deposit_amount.to_string(),
+            deposit_uri: swap.deposit_uri,
+            recipient_address: swap.recipient_address,
+            estimated_receive: swap.estimated_receive.to_string(),
+            rate: swap.rate.to_string(),
+            status: swap.status.as_str().to_string(),
+            rate_type: rate_type_name(&swap.rate_type),
+            is_sandbox: swap.is_sandbox,
+            expires_at: timestamp(swap.expires_at),
+            created_at: timestamp(swap.created_at),
+            recipient_verified: swap.recipient_verified,
+        }
+    }
+}
+
+impl From<SwapStatusResponse> for proto::SwapStatus {
+    fn from(swap: SwapStatusResponse) -> Self {
+        Self {
+            swap_id: swap.swap_id,
+            provider: swap.provider,
+            provider_swap_id: swap.provider_swap_id,
+            status: swap.status.as_str().to_string(),
+            from: swap.from,
+            to: swap.to,
+            amount: swap.amount.to_string(),
+            deposit_address: swap.deposit_address,
+            deposit_extra_id: swap.deposit_extra_id,
+            recipient_address: swap.recipient_address,
+            recipient_extra_id: swap.recipient_extra_id,
+            rate: swap.rate.to_string(),
+            estimated_receive: swap.estimated_receive.to_string(),
+            actual_receive: swap.actual_receive.map(|a| a.to_string()),
+            network_fee: swap.network_fee.to_string(),
+            total_fee: swap.total_fee.to_string(),
+            rate_type: rate_type_name(&swap.rate_type),
+            is_sandbox: swap.is_sandbox,
+            deposit_tx_hash: swap.deposit_tx_hash,
+            payout_tx_hash: swap.payout_tx_hash,
+            error: swap.error,
+            created_at: timestamp(swap.created_at),
+            updated_at: timestamp(swap.updated_at),
+            expires_at: swap.expires_at.map(timestamp),
+            completed_at: swap.completed_at.map(timestamp),
+            version: swap.version,
+        }
+    }
+}
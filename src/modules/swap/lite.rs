@@ -0,0 +1,447 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware,
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tower_http::{cors::CorsLayer, limit::RequestBodyLimitLayer, trace::TraceLayer};
+
+use super::crud::{
+    apply_fee_rules, check_request_currencies, check_usd_limit, kyc_rating_allows, parse_kyc_rating, sort_quotes,
+    RequestCurrencies, SwapError, SyncStats,
+};
+use super::model::Swap;
+use super::repository::SwapRepository;
+use super::state::{StatusSource, SwapStateMachine, Transition};
+use super::schema::{
+    CreateSwapRequest, CreateSwapResponse, CurrenciesQuery, CurrencyResponse, ProviderResponse, RateResponse, RateType,
+    RatesMeta, RatesQuery, RatesResponse, SwapStatus, SwapStatusResponse,
+};
+use crate::config::environment::{AddressVerificationConfig, HighValueConfig};
+use crate::services::fees::{self, FeeScope};
+use crate::services::jobs::{self, JobKind, JobOutcome};
+use crate::services::metrics::metrics;
+use crate::services::money;
+use crate::services::rate_limit::{create_rate_limiter, RateLimitLayer};
+use crate::services::redis_cache::RedisService;
+use crate::services::security::security_headers;
+use crate::services::swap_provider::SwapProviderClient;
+use crate::services::tenant::TenantId;
+use crate::services::trocador::{TradeAmount, TrocadorClient};
+use crate::MAX_BODY_BYTES;
+
+// =============================================================================
+// LIGHTWEIGHT MODE
+// The core swap flow for single-instance self-hosted deployments (the
+// `exchange-lite` binary): currency and provider listings, rates, swap
+// creation and status. Storage goes through SwapRepository (SQLite in the
+// binary) and the cache can be the in-memory one, so neither MySQL nor
+// Redis is needed. Swap requests go through the same currency and USD
+// limit checks as the full server, and fee rules stored in the database
+// apply to rates and swaps (there is no admin API to manage them here).
+// Accounts, admin, brands, quotes, drafts, webhooks and the background
+// workers other than the listing sync are only in the full server.
+// =============================================================================
+
+const CURRENCIES_CACHE_KEY: &str = "currencies:response:all";
+const PROVIDERS_CACHE_KEY: &str = "providers:response:all";
+const LISTING_CACHE_TTL: u64 = 300;
+
+pub struct LiteState {
+    pub repo: Arc<dyn SwapRepository>,
+    pub redis: RedisService,
+    pub trocador: TrocadorClient,
+    pub tenant: TenantId,
+    /// Per-swap USD limits; lite has no accounts, so recipients are never verified
+    pub address_verification: AddressVerificationConfig,
+    /// Reference USD prices the limits are checked at
+    pub high_value: HighValueConfig,
+}
+
+/// Router for the lightweight server
+pub fn lite_app(state: Arc<LiteState>) -> Router {
+    // Same global limit as the full server: burst of 10, then 1 per minute
+    let rate_limit_layer = RateLimitLayer::new(create_rate_limiter(10));
+
+    Router::new()
+        .route("/", get(root))
+        .route("/health", get(health_check))
+        .route("/swap/currencies", get(get_currencies))
+        .route("/swap/providers", get(get_providers))
+        .route("/swap/rates", get(get_rates))
+        .route("/swap/create", post(create_swap))
+        .route("/swap/{id}", get(get_swap_status))
+        .layer(middleware::from_fn(security_headers))
+        .layer(RequestBodyLimitLayer::new(MAX_BODY_BYTES))
+        .layer(rate_limit_layer)
+        .layer(TraceLayer::new_for_http())
+        .layer(CorsLayer::permissive())
+        .with_state(state)
+}
+
+async fn root() -> &'static str {
+    "Exchange Platform API (lite)"
+}
+
+async fn health_check() -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "status": "ok", "version": env!("CARGO_PKG_VERSION") }))
+}
+
+// =============================================================================
+// LISTING SYNC
+// =============================================================================
+
+/// Pull Trocador's currency and provider listings into the repository
+pub async fn sync_listings(state: &LiteState) -> Result<SyncStats, SwapError> {
+    let currencies = state.trocador.get_currencies().await?;
+    let providers = state.trocador.get_providers().await?;
+
+    let mut stats = SyncStats { fetched: currencies.len() + providers.len(), changed: 0 };
+    stats.changed += state.repo.sync_currencies(&currencies).await?;
+    stats.changed += state.repo.sync_providers(&providers).await?;
+
+    let _ = state.redis.delete(CURRENCIES_CACHE_KEY).await;
+    let _ = state.redis.delete(PROVIDERS_CACHE_KEY).await;
+    Ok(stats)
+}
+
+/// Sync the listings now and then every `interval`
+pub fn spawn_listing_sync(state: Arc<LiteState>, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        tracing::info!("Listing sync started (interval {:?})", interval);
+
+        let job = jobs::registry().register(
+            "lite_listing_sync",
+            "Sync currencies and providers from Trocador into the local database",
+            JobKind::Scheduled,
+        );
+
+        loop {
+            let run = job.start();
+            match sync_listings(&state).await {
+                Ok(stats) => {
+                    tracing::info!("Synced {} listings ({} rows changed)", stats.fetched, stats.changed);
+                    run.finish(JobOutcome::Success, None);
+                }
+                Err(e) => {
+                    tracing::warn!("Listing sync failed: {}", e);
+                    run.finish(JobOutcome::Failed, Some(e.to_string()));
+                }
+            }
+
+            job.wait(interval).await;
+        }
+    })
+}
+
+// =============================================================================
+// GET /swap/currencies, GET /swap/providers
+// =============================================================================
+
+async fn get_currencies(
+    State(state): State<Arc<LiteState>>,
+    Query(query): Query<CurrenciesQuery>,
+) -> Result<Json<Vec<CurrencyResponse>>, SwapError> {
+    if query.wants_page() {
+        return Err(SwapError::NotSupported("currency search and paging"));
+    }
+    let currencies: Vec<CurrencyResponse> = match state.redis.get_json(CURRENCIES_CACHE_KEY).await {
+        Ok(Some(cached)) => cached,
+        _ => {
+            let currencies: Vec<CurrencyResponse> =
+                state.repo.active_currencies().await?.into_iter().map(CurrencyResponse::from).collect();
+            let _ = state.redis.set_json(CURRENCIES_CACHE_KEY, &currencies, LISTING_CACHE_TTL).await;
+            currencies
+        }
+    };
+
+    let filtered = currencies.into_iter().filter(|c| {
+        query.ticker.as_ref().is_none_or(|t| c.ticker.eq_ignore_ascii_case(t))
+            && query.network.as_ref().is_none_or(|n| c.network.eq_ignore_ascii_case(n))
+            && query.memo.is_none_or(|memo| c.memo == memo)
+    });
+
+    let currencies = match query.limit {
+        Some(limit) => {
+            let page = query.page.unwrap_or(1).max(1);
+            filtered.skip((page - 1).saturating_mul(limit)).take(limit).collect()
+        }
+        None => filtered.collect(),
+    };
+    Ok(Json(currencies))
+}
+
+async fn get_providers(State(state): State<Arc<LiteState>>) -> Result<Json<Vec<ProviderResponse>>, SwapError> {
+    if let Ok(Some(cached)) = state.redis.get_json(PROVIDERS_CACHE_KEY).await {
+        return Ok(Json(cached));
+    }
+
+    let providers: Vec<ProviderResponse> =
+        state.repo.active_providers().await?.into_iter().map(ProviderResponse::from).collect();
+    let _ = state.redis.set_json(PROVIDERS_CACHE_KEY, &providers, LISTING_CACHE_TTL).await;
+    Ok(Json(providers))
+}
+
+// =============================================================================
+// GET /swap/rates
+// =============================================================================
+
+async fn get_rates(
+    State(state): State<Arc<LiteState>>,
+    Query(query): Query<RatesQuery>,
+) -> Result<Json<RatesResponse>, SwapError> {
+    if query.sandbox {
+        return Err(SwapError::NotSupported("sandbox"));
+    }
+    if query.amount_to.is_some() {
+        return Err(SwapError::NotSupported("amount_to"));
+    }
+    let max_kyc_rating = query.max_kyc_rating.as_deref().map(parse_kyc_rating).transpose()?;
+    check_pair(&state, &query.from, &query.network_from, &query.to, &query.network_to).await?;
+
+    let started = std::time::Instant::now();
+    let quotes = state.trocador.get_quotes(&query).await.map_err(SwapError::ExternalApiError)?;
+
+    let rate_type = query.rate_type.clone().unwrap_or(RateType::Floating);
+    let mut rates: Vec<RateResponse> = quotes
+        .quotes
+        .into_iter()
+        .filter(|quote| query.provider.as_ref().is_none_or(|p| quote.provider.eq_ignore_ascii_case(p)))
+        .filter(|quote| max_kyc_rating.is_none_or(|max| kyc_rating_allows(quote.kyc_rating.as_deref(), max)))
+        .map(|quote| RateResponse {
+            provider: quote.provider.clone(),
+            provider_name: quote.provider,
+            aggregator: state.trocador.aggregator().to_string(),
+            rate: money::rate(quote.amount_to, query.amount),
+            estimated_amount: quote.amount_to,
+            amount_from: None,
+            min_amount: quote.min_amount,
+            max_amount: quote.max_amount,
+            network_fee: Decimal::ZERO,
+            provider_fee: quote.provider_fee,
+            platform_fee: Decimal::ZERO,
+            total_fee: quote.provider_fee,
+            rate_type: rate_type.clone(),
+            kyc_required: quote.kyc_rating.as_deref().unwrap_or("D") != "A",
+            kyc_rating: quote.kyc_rating,
+            eta_minutes: quote.eta_minutes.or(Some(15)),
+            recent_failures: 0,
+            demoted: false,
+            health_score: None,
+            rate_warning: false,
+            preferred: false,
+            estimated_amount_fiat: None,
+            fee_breakdown: None,
+        })
+        .collect();
+    sort_quotes(&mut rates);
+
+    let mut rates = RatesResponse {
+        trade_id: quotes.trade_id.unwrap_or_default(),
+        from: query.from,
+        network_from: query.network_from,
+        to: query.to,
+        network_to: query.network_to,
+        amount: query.amount,
+        amount_to: None,
+        amount_fiat: None,
+        rates,
+        meta: RatesMeta {
+            budget_ms: 0,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            timed_out: Vec::new(),
+        },
+    };
+    let rules = state.repo.fee_rules(&state.tenant).await?;
+    if !rules.is_empty() {
+        apply_fee_rules(&mut rates, &rules, None);
+    }
+    Ok(Json(rates))
+}
+
+// =============================================================================
+// POST /swap/create
+// =============================================================================
+
+async fn create_swap(
+    State(state): State<Arc<LiteState>>,
+    Json(request): Json<CreateSwapRequest>,
+) -> Result<(StatusCode, Json<CreateSwapResponse>), SwapError> {
+    if request.dry_run {
+        return Err(SwapError::NotSupported("dry_run"));
+    }
+    if request.quote_id.is_some() {
+        return Err(SwapError::NotSupported("quote_id"));
+    }
+    if request.sandbox {
+        return Err(SwapError::NotSupported("sandbox"));
+    }
+    if request.wants_best_provider() {
+        return Err(SwapError::NotSupported("provider selection"));
+    }
+    if request.amount_to.is_some() {
+        return Err(SwapError::NotSupported("amount_to"));
+    }
+
+    let (from, to) = check_pair(&state, &request.from, &request.network_from, &request.to, &request.network_to).await?;
+
+    let min = from.min_amount.unwrap_or_default();
+    let max = from.max_amount.unwrap_or_default();
+    if request.amount <= Decimal::ZERO || request.amount < min || (max > Decimal::ZERO && request.amount > max) {
+        return Err(SwapError::AmountOutOfRange { min, max });
+    }
+
+    let currencies = RequestCurrencies { from: Some(&from), to: Some(&to), from_format: None, to_format: None };
+    check_request_currencies(&request, &currencies)?;
+    check_usd_limit(&request, &state.address_verification, &state.high_value, false)?;
+    let rules = state.repo.fee_rules(&state.tenant).await?;
+
+    let (trade, _raw) = state
+        .trocador
+        .create_trade(
+            request.trade_id.as_deref(),
+            &request.from,
+            &request.network_from,
+            &request.to,
+            &request.network_to,
+            TradeAmount::From(request.amount),
+            &request.recipient_address,
+            request.refund_address.as_deref(),
+            &request.provider,
+            request.rate_type == RateType::Fixed,
+        )
+        .await?;
+
+    // Same pricing as the full server: the platform fee comes off the receive amount
+    let receive_decimals = money::scale_of(to.decimals);
+    let scope = FeeScope { from: &request.from, to: &request.to, provider: &request.provider, user_tier: None };
+    let platform_fee = money::round_send(fees::platform_fee(&rules, &scope, trade.amount_to), receive_decimals);
+    let estimated_receive = money::round_receive(trade.amount_to - platform_fee, receive_decimals);
+
+    let now = Utc::now();
+    let status = SwapStateMachine::map_provider_status(&trade.status);
+    let swap = Swap {
+        id: uuid::Uuid::new_v4().to_string(),
+        tenant_id: state.tenant.as_str().to_string(),
+        user_id: None,
+        brand: None,
+        provider_id: request.provider.clone(),
+        provider_swap_id: Some(trade.trade_id),
+        retried_from: None,
+        from_currency: request.from.clone(),
+        from_network: request.network_from.clone(),
+        to_currency: request.to.clone(),
+        to_network: request.network_to.clone(),
+        amount: request.amount,
+        estimated_receive,
+        actual_receive: None,
+        rate: money::rate(estimated_receive, request.amount),
+        network_fee: Decimal::ZERO,
+        provider_fee: Decimal::ZERO,
+        platform_fee,
+        total_fee: platform_fee,
+        deposit_address: trade.address_provider,
+        deposit_extra_id: trade.address_provider_memo,
+        recipient_address: request.recipient_address.clone(),
+        recipient_extra_id: request.recipient_extra_id.clone(),
+        refund_address: request.refund_address.clone(),
+        refund_extra_id: request.refund_extra_id.clone(),
+        tx_hash_in: None,
+        tx_hash_out: None,
+        deposit_confirmations: None,
+        status,
+        rate_type: request.rate_type.clone(),
+        is_sandbox: request.sandbox,
+        imported: false,
+        error: None,
+        version: 0,
+        expires_at: Some(now + chrono::Duration::minutes(60)),
+        completed_at: None,
+        created_at: now,
+        updated_at: now,
+    };
+    state.repo.insert_swap(&swap).await?;
+    metrics().swaps_created.inc(swap.status.as_str());
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateSwapResponse {
+            swap_id: swap.id,
+            provider: trade.provider,
+            from: swap.from_currency,
+            to: swap.to_currency,
+            deposit_address: swap.deposit_address,
+            deposit_extra_id: swap.deposit_extra_id,
+            deposit_amount: swap.amount,
+            deposit_uri: None,
+            recipient_address: swap.recipient_address,
+            estimated_receive: swap.estimated_receive,
+            rate: swap.rate,
+            status: swap.status,
+            rate_type: swap.rate_type,
+            is_sandbox: swap.is_sandbox,
+            expires_at: now + chrono::Duration::minutes(60),
+            created_at: now,
+            retried_from: None,
+            fallback_chain: Vec::new(),
+            recipient_verified: false,
+            provider_selection: None,
+            deposit_amount_fiat: None,
+            estimated_receive_fiat: None,
+        }),
+    ))
+}
+
+// =============================================================================
+// GET /swap/{id}
+// =============================================================================
+
+/// The stored swap, first refreshed from Trocador while it is in flight
+async fn get_swap_status(
+    State(state): State<Arc<LiteState>>,
+    Path(swap_id): Path<String>,
+) -> Result<Json<SwapStatusResponse>, SwapError> {
+    let swap = state.repo.find_swap(&swap_id).await?.ok_or(SwapError::SwapNotFound)?;
+
+    let Some(trade_id) = swap.provider_swap_id.as_deref().filter(|_| !swap.status.is_final()) else {
+        return Ok(Json(SwapStatusResponse::from(swap)));
+    };
+
+    match state.trocador.get_trade_status(trade_id).await {
+        Ok((trade, _raw)) => {
+            let status = SwapStateMachine::map_provider_status(&trade.status);
+            if SwapStateMachine::transition(&swap.status, &status, StatusSource::Poll).is_ok_and(|t| t != Transition::Unchanged) {
+                let actual_receive = (status == SwapStatus::Completed).then_some(trade.amount_to);
+                state.repo.update_swap_status(&swap.id, &status, actual_receive).await?;
+                let swap = state.repo.find_swap(&swap_id).await?.ok_or(SwapError::SwapNotFound)?;
+                return Ok(Json(SwapStatusResponse::from(swap)));
+            }
+        }
+        // The stored status is still the best answer
+        Err(e) => tracing::warn!("Failed to refresh swap {} from Trocador: {}", swap_id, e),
+    }
+
+    Ok(Json(SwapStatusResponse::from(swap)))
+}
+
+/// Both currencies, which must be listed and active
+async fn check_pair(
+    state: &LiteState,
+    from: &str,
+    network_from: &str,
+    to: &str,
+    network_to: &str,
+) -> Result<(super::model::Currency, super::model::Currency), SwapError> {
+    let from = state.repo.find_currency(from, network_from).await?.ok_or(SwapError::CurrencyNotFound)?;
+    let to = state.repo.find_currency(to, network_to).await?.ok_or(SwapError::CurrencyNotFound)?;
+    if !from.is_active || !to.is_active {
+        return Err(SwapError::PairNotAvailable);
+    }
+    Ok((from, to))
+}
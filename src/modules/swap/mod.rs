@@ -1,7 +1,20 @@
 pub mod schema;
 pub mod model;
 pub mod crud;
+pub mod consistency;
 pub mod controller;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod lite;
+pub mod openapi;
 pub mod routes;
+pub mod prober;
+pub mod repository;
+pub mod share;
+pub mod state;
+pub mod stream;
+pub mod sync_worker;
+pub mod webhooks;
+pub mod worker;
 
 pub use routes::swap_routes;
@@ -1,8 +1,10 @@
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use sqlx::FromRow;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-use super::schema::{RateType, SwapStatus};
+use super::schema::{ProviderCallType, RateType, RefundSource, RouteStatus, SwapStatus, SyncKind, SyncRunStatus};
 
 // =============================================================================
 // PROVIDER
@@ -37,13 +39,15 @@ pub struct Currency {
     pub name: String,
     pub network: String,
     pub is_active: bool,
+    pub delisting_at: Option<DateTime<Utc>>, // Scheduled delisting date, if announced
     pub logo_url: Option<String>,       // Maps to "image" in Trocador
     pub contract_address: Option<String>,
     pub decimals: i32,
     pub requires_extra_id: bool,        // Maps to "memo" in Trocador
     pub extra_id_name: Option<String>,  // e.g., "Destination Tag", "Memo"
-    pub min_amount: Option<f64>,        // NEW: Global minimum from Trocador
-    pub max_amount: Option<f64>,        // NEW: Global maximum from Trocador
+    pub requires_refund_address: bool,  // Admin policy: swaps from this currency need a refund address
+    pub min_amount: Option<Decimal>,        // NEW: Global minimum from Trocador
+    pub max_amount: Option<Decimal>,        // NEW: Global maximum from Trocador
     pub last_synced_at: Option<DateTime<Utc>>, // NEW: Cache timestamp
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -74,6 +78,20 @@ pub struct TradingPairView {
     pub is_active: bool,
 }
 
+/// One provider's limits for a pair, as last quoted
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct PairQuote {
+    pub from_currency: String,
+    pub from_network: String,
+    pub to_currency: String,
+    pub to_network: String,
+    pub provider: String,
+    pub min_amount: Decimal,
+    pub max_amount: Option<Decimal>, // None: no maximum
+    pub is_active: bool,         // Both currencies listed and active
+    pub last_quoted_at: DateTime<Utc>,
+}
+
 // =============================================================================
 // SWAP
 // =============================================================================
@@ -81,9 +99,12 @@ pub struct TradingPairView {
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct Swap {
     pub id: String,
+    pub tenant_id: String,
     pub user_id: Option<String>,
+    pub brand: Option<String>, // White-label brand slug; None for the deployment's own brand
     pub provider_id: String,
     pub provider_swap_id: Option<String>, // This stores Trocador's trade_id
+    pub retried_from: Option<String>, // Original swap when created via /swap/{id}/retry
 
     // Currencies
     pub from_currency: String,
@@ -92,16 +113,16 @@ pub struct Swap {
     pub to_network: String,
 
     // Amounts
-    pub amount: f64,
-    pub estimated_receive: f64,
-    pub actual_receive: Option<f64>,
-    pub rate: f64,
+    pub amount: Decimal,
+    pub estimated_receive: Decimal,
+    pub actual_receive: Option<Decimal>,
+    pub rate: Decimal,
 
     // Fees
-    pub network_fee: f64,
-    pub provider_fee: f64,
-    pub platform_fee: f64,
-    pub total_fee: f64,
+    pub network_fee: Decimal,
+    pub provider_fee: Decimal,
+    pub platform_fee: Decimal,
+    pub total_fee: Decimal,
 
     // Addresses
     pub deposit_address: String,
@@ -114,12 +135,15 @@ pub struct Swap {
     // Transaction hashes
     pub tx_hash_in: Option<String>,
     pub tx_hash_out: Option<String>,
+    pub deposit_confirmations: Option<u32>, // As last reported by the provider
 
     // Status
     pub status: SwapStatus,
     pub rate_type: RateType,
     pub is_sandbox: bool,
+    pub imported: bool, // Created elsewhere, added with POST /swap/import; watch-only
     pub error: Option<String>,
+    pub version: u32, // Bumped on every update; writers compare-and-swap on it
 
     // Timestamps
     pub expires_at: Option<DateTime<Utc>>,
@@ -128,6 +152,100 @@ pub struct Swap {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Funded high-value swap that has kept its status past the stall window
+#[derive(Debug, Clone, FromRow)]
+pub struct StalledSwap {
+    pub id: String,
+    pub brand: Option<String>,
+    pub provider_id: String,
+    pub status: SwapStatus,
+    pub usd_value: Option<f64>,
+    pub updated_at: DateTime<Utc>, // When it entered its current status
+}
+
+// =============================================================================
+// SWAP PROVIDER PAYLOAD
+// =============================================================================
+
+#[derive(Debug, Clone, FromRow)]
+pub struct SwapProviderPayload {
+    pub id: i64,
+    pub swap_id: String,
+    pub call_type: ProviderCallType,
+    pub payload_gz: Vec<u8>, // gzip; see services::payload_codec
+    pub original_bytes: u32,
+    pub truncated: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+// =============================================================================
+// SWAP REFUND
+// =============================================================================
+
+/// What the provider reported about a refunded swap
+#[derive(Debug, Clone, FromRow)]
+pub struct SwapRefund {
+    pub swap_id: String,
+    pub refund_address: Option<String>,
+    pub refund_extra_id: Option<String>,
+    pub amount: Option<Decimal>, // In the swap's from currency
+    pub currency: String,
+    pub network: String,
+    pub tx_hash: Option<String>,
+    pub source: RefundSource, // Where the latest report came from
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// =============================================================================
+// VERIFIED ADDRESS
+// =============================================================================
+
+/// Payout address a user proved control of by signing a challenge
+#[derive(Debug, Clone, FromRow)]
+pub struct VerifiedAddress {
+    pub id: String,
+    pub user_id: String,
+    pub chain: String,   // Chain::as_str()
+    pub address: String, // Normalized; see message_signing::normalize_address
+    pub message: String,
+    pub signature: String,
+    pub verified_at: DateTime<Utc>,
+}
+
+// =============================================================================
+// FAVORITES
+// =============================================================================
+
+/// Pair a user starred for the swap form
+#[derive(Debug, Clone, FromRow)]
+pub struct FavoritePair {
+    pub id: String,
+    pub user_id: String,
+    pub from_currency: String,
+    pub from_network: String,
+    pub to_currency: String,
+    pub to_network: String,
+    pub created_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>, // Removed by the user
+}
+
+/// Recipient address a user saved or swapped to
+#[derive(Debug, Clone, FromRow)]
+pub struct SavedAddress {
+    pub id: String,
+    pub user_id: String,
+    pub currency: String,
+    pub network: String,
+    pub address: String,
+    pub extra_id: String, // '' when there is none
+    pub label: Option<String>,
+    pub times_used: u32,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>, // Removed by the user
+}
+
 // =============================================================================
 // SWAP STATUS HISTORY
 // =============================================================================
@@ -166,13 +284,54 @@ pub struct RateCache {
     pub provider_id: String,
     pub from_currency: String,
     pub to_currency: String,
-    pub amount: f64,
-    pub rate: f64,
-    pub estimated_amount: f64,
-    pub min_amount: f64,
-    pub max_amount: f64,
-    pub network_fee: f64,
+    pub amount: Decimal,
+    pub rate: Decimal,
+    pub estimated_amount: Decimal,
+    pub min_amount: Decimal,
+    pub max_amount: Decimal,
+    pub network_fee: Decimal,
     pub rate_type: RateType,
     pub expires_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
 }
+
+// =============================================================================
+// SWAP ROUTE (two swaps through an intermediate currency)
+// =============================================================================
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SwapRoute {
+    pub id: String,
+    pub tenant_id: String,
+    pub user_id: Option<String>,
+    pub from_currency: String,
+    pub from_network: String,
+    pub via_currency: String,
+    pub via_network: String,
+    pub to_currency: String,
+    pub to_network: String,
+    pub amount: Decimal,
+    pub estimated_receive: Decimal,
+    pub first_swap_id: String,
+    pub second_swap_id: String,
+    pub status: RouteStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// =============================================================================
+// SYNC RUN (outcome of one currency/provider sync)
+// =============================================================================
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct SyncRun {
+    pub id: i64,
+    pub kind: SyncKind,
+    pub status: SyncRunStatus,
+    pub rows_fetched: i32,
+    pub rows_changed: i32,
+    pub duration_ms: i64,
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+}
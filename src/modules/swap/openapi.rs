@@ -0,0 +1,80 @@
+//! OpenAPI description of the swap routes.
+//!
+//! Served as `/api-docs/openapi.json`, with Swagger UI at `/swagger-ui`.
+//! Request and response shapes come from the `ToSchema` derives in
+//! `schema.rs`, so they follow the structs as they change; a handler only
+//! shows up here once it has a `#[utoipa::path]` and is listed below.
+
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+use super::schema::{CurrenciesPage, EstimateRequest, EstimateResponse, ShadowQuoteReport, SyncStatusResponse};
+use super::{controller, stream, webhooks};
+
+/// Path to the generated document
+pub const OPENAPI_JSON_PATH: &str = "/api-docs/openapi.json";
+
+/// Where Swagger UI is mounted
+pub const SWAGGER_UI_PATH: &str = "/swagger-ui";
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Exchange Platform API", description = "Swap quotes, creation and tracking"),
+    paths(
+        controller::get_currencies,
+        controller::get_currencies_grouped,
+        controller::get_providers,
+        controller::get_provider_uptime,
+        controller::get_pairs,
+        controller::get_rates,
+        controller::get_depth,
+        controller::reserve_quote,
+        controller::create_swap,
+        controller::plan_routes,
+        controller::create_route,
+        controller::get_route,
+        controller::import_swap,
+        controller::create_swap_draft,
+        controller::get_swap_draft,
+        controller::update_swap_draft,
+        controller::delete_swap_draft,
+        controller::get_swap_statuses,
+        controller::get_swap_history,
+        controller::get_swap_limits,
+        controller::get_refund_address_suggestions,
+        controller::get_swap_status,
+        controller::get_swap_refund,
+        controller::retry_swap,
+        controller::create_share_link,
+        controller::get_shared_swap,
+        stream::swap_status_ws,
+        controller::validate_address,
+        controller::create_address_challenge,
+        controller::verify_address,
+        controller::get_verified_addresses,
+        controller::delete_verified_address,
+        controller::get_favorites,
+        controller::save_favorite,
+        controller::delete_favorite_pair,
+        controller::delete_saved_address,
+        webhooks::trocador_webhook,
+    ),
+    // Types served outside the swap routes (/ready, /admin) or kept for clients
+    components(schemas(CurrenciesPage, EstimateRequest, EstimateResponse, ShadowQuoteReport, SyncStatusResponse)),
+    modifiers(&BearerAuth),
+    tags((name = "swap", description = "Currencies, providers, rates and swaps")),
+)]
+pub struct SwapApiDoc;
+
+/// The `bearer_auth` scheme referenced by routes that take a session token
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+        );
+    }
+}
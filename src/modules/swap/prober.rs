@@ -0,0 +1,75 @@
+use sqlx::{MySql, Pool};
+use tokio::task::JoinHandle;
+
+use super::crud::{ProbeStats, SwapCrud, SwapError};
+use crate::config::environment::ProviderProberConfig;
+use crate::services::jobs::{self, JobKind, JobOutcome};
+use crate::services::redis_cache::RedisService;
+use crate::services::trocador::TrocadorClient;
+
+// =============================================================================
+// PROVIDER PROBER
+// Periodically checks which providers Trocador lists and adds the result to
+// each provider's daily uptime row (`provider_uptime_daily`), alongside the
+// new_trade outcomes counted as swaps are created. The lock is left to expire
+// rather than released, so across all instances a probe runs once per interval.
+// =============================================================================
+
+const LOCK_KEY: &str = "lock:provider_prober";
+
+/// Spawn the periodic probe loop on the tokio runtime
+pub fn spawn_provider_prober(
+    pool: Pool<MySql>,
+    redis: RedisService,
+    trocador: TrocadorClient,
+    config: ProviderProberConfig,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        tracing::info!("Provider prober started (interval {:?})", config.interval);
+
+        let crud = SwapCrud::new(pool, Some(redis.clone()));
+        let job = jobs::registry().register(
+            "provider_prober",
+            "Record provider availability for uptime history",
+            JobKind::Scheduled,
+        );
+
+        loop {
+            let run = job.start();
+            match tokio::time::timeout(config.run_timeout, run_once(&crud, &trocador, &redis, &config)).await {
+                Ok(Ok(Some(stats))) => {
+                    tracing::debug!("Provider probe: {}/{} providers up", stats.up, stats.probed);
+                    run.finish(JobOutcome::Success, None);
+                }
+                Ok(Ok(None)) => run.finish(JobOutcome::Skipped, None),
+                Ok(Err(e)) => {
+                    tracing::warn!("Provider probe failed: {}", e);
+                    run.finish(JobOutcome::Failed, Some(e.to_string()));
+                }
+                Err(_) => {
+                    let error = format!("Provider probe exceeded {:?}", config.run_timeout);
+                    tracing::warn!("{}", error);
+                    run.finish(JobOutcome::Timeout, Some(error));
+                }
+            }
+
+            job.wait(config.interval).await;
+        }
+    })
+}
+
+/// Run a single probe. Returns `None` when another instance probed within
+/// the interval.
+pub async fn run_once(
+    crud: &SwapCrud,
+    client: &TrocadorClient,
+    redis: &RedisService,
+    config: &ProviderProberConfig,
+) -> Result<Option<ProbeStats>, SwapError> {
+    let ttl = config.interval.as_secs().saturating_sub(1).max(1);
+    if !matches!(redis.try_lock(LOCK_KEY, ttl).await, Ok(true)) {
+        return Ok(None);
+    }
+
+    crud.probe_providers(client).await.map(Some)
+}
@@ -0,0 +1,429 @@
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+
+use super::crud::SwapError;
+use super::model::{Currency, Provider, Swap};
+use super::schema::{SwapStatus, TrocadorCurrency, TrocadorProvider};
+use crate::services::fees::FeeRule;
+use crate::services::tenant::TenantId;
+
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteSwapRepository;
+
+// =============================================================================
+// SWAP REPOSITORY
+// The storage the lightweight server (modules::swap::lite) needs: the
+// currency and provider listings synced from Trocador, the platform fee
+// rules and the swaps themselves. The full server keeps using SwapCrud over
+// MySQL; both validate swap requests with the same checks (see
+// crud::check_request_currencies).
+// =============================================================================
+
+#[async_trait]
+pub trait SwapRepository: Send + Sync {
+    /// Upsert Trocador's currency listing; currencies it no longer lists are
+    /// deactivated unless the listing is empty. Returns the rows changed.
+    async fn sync_currencies(&self, currencies: &[TrocadorCurrency]) -> Result<u64, SwapError>;
+    async fn active_currencies(&self) -> Result<Vec<Currency>, SwapError>;
+    async fn find_currency(&self, symbol: &str, network: &str) -> Result<Option<Currency>, SwapError>;
+
+    /// Upsert Trocador's provider listing; returns the rows changed
+    async fn sync_providers(&self, providers: &[TrocadorProvider]) -> Result<u64, SwapError>;
+    async fn active_providers(&self) -> Result<Vec<Provider>, SwapError>;
+
+    /// A tenant's platform fee rules, in any order (fees::platform_fee picks)
+    async fn fee_rules(&self, tenant: &TenantId) -> Result<Vec<FeeRule>, SwapError>;
+
+    async fn insert_swap(&self, swap: &Swap) -> Result<(), SwapError>;
+    async fn find_swap(&self, swap_id: &str) -> Result<Option<Swap>, SwapError>;
+    /// Record a status reported by the provider, bumping the row version
+    async fn update_swap_status(
+        &self,
+        swap_id: &str,
+        status: &SwapStatus,
+        actual_receive: Option<Decimal>,
+    ) -> Result<(), SwapError>;
+}
+
+// =============================================================================
+// SQLITE (feature "sqlite")
+// =============================================================================
+
+// SQLite has no DECIMAL type, so amounts are stored as REAL and cross over
+// as f64: values are exact to about 15 significant digits here, against the
+// full DECIMAL precision of the MySQL server.
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+    use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions, SqliteRow};
+    use sqlx::Row;
+    use std::str::FromStr;
+
+    use super::SwapRepository;
+    use crate::modules::swap::crud::SwapError;
+    use crate::modules::swap::model::{Currency, Provider, Swap};
+    use crate::modules::swap::schema::{SwapStatus, TrocadorCurrency, TrocadorProvider};
+    use crate::services::fees::FeeRule;
+    use crate::services::money;
+    use crate::services::tenant::TenantId;
+
+    /// A REAL amount column
+    fn amount(row: &SqliteRow, column: &str) -> Result<Decimal, sqlx::Error> {
+        Ok(money::from_f64(row.try_get(column)?))
+    }
+
+    /// A nullable REAL amount column
+    fn optional_amount(row: &SqliteRow, column: &str) -> Result<Option<Decimal>, sqlx::Error> {
+        Ok(row.try_get::<Option<f64>, _>(column)?.map(money::from_f64))
+    }
+
+    fn real(amount: Decimal) -> f64 {
+        money::to_f64(amount)
+    }
+
+    fn currency_from_row(row: SqliteRow) -> Result<Currency, sqlx::Error> {
+        Ok(Currency {
+            id: row.try_get("id")?,
+            symbol: row.try_get("symbol")?,
+            name: row.try_get("name")?,
+            network: row.try_get("network")?,
+            is_active: row.try_get("is_active")?,
+            delisting_at: row.try_get("delisting_at")?,
+            logo_url: row.try_get("logo_url")?,
+            contract_address: row.try_get("contract_address")?,
+            decimals: row.try_get("decimals")?,
+            requires_extra_id: row.try_get("requires_extra_id")?,
+            extra_id_name: row.try_get("extra_id_name")?,
+            requires_refund_address: row.try_get("requires_refund_address")?,
+            min_amount: optional_amount(&row, "min_amount")?,
+            max_amount: optional_amount(&row, "max_amount")?,
+            last_synced_at: row.try_get("last_synced_at")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    fn fee_rule_from_row(row: SqliteRow) -> Result<FeeRule, sqlx::Error> {
+        Ok(FeeRule {
+            id: row.try_get::<i64, _>("id")? as u64,
+            tenant_id: row.try_get("tenant_id")?,
+            from_currency: row.try_get("from_currency")?,
+            to_currency: row.try_get("to_currency")?,
+            provider: row.try_get("provider")?,
+            user_tier: row.try_get("user_tier")?,
+            fee_percent: amount(&row, "fee_percent")?,
+            flat_fee: amount(&row, "flat_fee")?,
+            note: row.try_get("note")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    fn swap_from_row(row: SqliteRow) -> Result<Swap, sqlx::Error> {
+        Ok(Swap {
+            id: row.try_get("id")?,
+            tenant_id: row.try_get("tenant_id")?,
+            user_id: row.try_get("user_id")?,
+            brand: row.try_get("brand")?,
+            provider_id: row.try_get("provider_id")?,
+            provider_swap_id: row.try_get("provider_swap_id")?,
+            retried_from: row.try_get("retried_from")?,
+            from_currency: row.try_get("from_currency")?,
+            from_network: row.try_get("from_network")?,
+            to_currency: row.try_get("to_currency")?,
+            to_network: row.try_get("to_network")?,
+            amount: amount(&row, "amount")?,
+            estimated_receive: amount(&row, "estimated_receive")?,
+            actual_receive: optional_amount(&row, "actual_receive")?,
+            rate: amount(&row, "rate")?,
+            network_fee: amount(&row, "network_fee")?,
+            provider_fee: amount(&row, "provider_fee")?,
+            platform_fee: amount(&row, "platform_fee")?,
+            total_fee: amount(&row, "total_fee")?,
+            deposit_address: row.try_get("deposit_address")?,
+            deposit_extra_id: row.try_get("deposit_extra_id")?,
+            recipient_address: row.try_get("recipient_address")?,
+            recipient_extra_id: row.try_get("recipient_extra_id")?,
+            refund_address: row.try_get("refund_address")?,
+            refund_extra_id: row.try_get("refund_extra_id")?,
+            tx_hash_in: row.try_get("tx_hash_in")?,
+            tx_hash_out: row.try_get("tx_hash_out")?,
+            deposit_confirmations: row.try_get("deposit_confirmations")?,
+            status: row.try_get("status")?,
+            rate_type: row.try_get("rate_type")?,
+            is_sandbox: row.try_get("is_sandbox")?,
+            imported: row.try_get("imported")?,
+            error: row.try_get("error")?,
+            version: row.try_get("version")?,
+            expires_at: row.try_get("expires_at")?,
+            completed_at: row.try_get("completed_at")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    /// Swap storage in a single SQLite file, with its own migrations
+    /// (migrations_sqlite/) applied on connect
+    #[derive(Clone)]
+    pub struct SqliteSwapRepository {
+        pool: SqlitePool,
+    }
+
+    impl SqliteSwapRepository {
+        /// Open (creating it if missing) and migrate the database at `url`,
+        /// e.g. `sqlite://exchange-lite.db` or `sqlite::memory:`
+        pub async fn connect(url: &str) -> Result<Self, SwapError> {
+            let options = SqliteConnectOptions::from_str(url)?.create_if_missing(true);
+            // An in-memory database lives as long as its one connection
+            let max_connections = if url.contains(":memory:") { 1 } else { 5 };
+            let pool = SqlitePoolOptions::new()
+                .max_connections(max_connections)
+                .connect_with(options)
+                .await?;
+
+            sqlx::migrate!("./migrations_sqlite")
+                .run(&pool)
+                .await
+                .map_err(|e| SwapError::Internal(format!("SQLite migration failed: {}", e)))?;
+
+            Ok(Self { pool })
+        }
+
+        pub fn pool(&self) -> &SqlitePool {
+            &self.pool
+        }
+    }
+
+    #[async_trait]
+    impl SwapRepository for SqliteSwapRepository {
+        async fn sync_currencies(&self, currencies: &[TrocadorCurrency]) -> Result<u64, SwapError> {
+            let synced_at = Utc::now();
+            let mut changed = 0;
+
+            let mut tx = self.pool.begin().await?;
+            for currency in currencies {
+                changed += sqlx::query(
+                    "INSERT INTO currencies (
+                        symbol, name, network, is_active, logo_url,
+                        requires_extra_id, min_amount, max_amount, last_synced_at, created_at, updated_at
+                    )
+                    VALUES (?, ?, ?, TRUE, ?, ?, ?, ?, ?, ?, ?)
+                    ON CONFLICT (symbol, network) DO UPDATE SET
+                        name = excluded.name,
+                        is_active = TRUE,
+                        logo_url = excluded.logo_url,
+                        min_amount = excluded.min_amount,
+                        max_amount = excluded.max_amount,
+                        last_synced_at = excluded.last_synced_at,
+                        updated_at = excluded.updated_at",
+                )
+                .bind(&currency.ticker)
+                .bind(&currency.name)
+                .bind(&currency.network)
+                .bind(&currency.image)
+                .bind(currency.memo)
+                .bind(real(currency.minimum))
+                .bind(real(currency.maximum))
+                .bind(synced_at)
+                .bind(synced_at)
+                .bind(synced_at)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+            }
+
+            // Same rule as the MySQL sync: an empty listing is a bad response
+            if currencies.is_empty() {
+                tracing::warn!("Trocador listed no currencies; keeping existing currencies active");
+            } else {
+                changed += sqlx::query(
+                    "UPDATE currencies SET is_active = FALSE, updated_at = ?
+                     WHERE is_active = TRUE AND (last_synced_at IS NULL OR last_synced_at <> ?)",
+                )
+                .bind(synced_at)
+                .bind(synced_at)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+            }
+
+            tx.commit().await?;
+            Ok(changed)
+        }
+
+        async fn active_currencies(&self) -> Result<Vec<Currency>, SwapError> {
+            let currencies = sqlx::query("SELECT * FROM currencies WHERE is_active = TRUE ORDER BY symbol, network")
+                .try_map(currency_from_row)
+                .fetch_all(&self.pool)
+                .await?;
+            Ok(currencies)
+        }
+
+        async fn find_currency(&self, symbol: &str, network: &str) -> Result<Option<Currency>, SwapError> {
+            let currency = sqlx::query("SELECT * FROM currencies WHERE symbol = ? AND network = ?")
+                .bind(symbol)
+                .bind(network)
+                .try_map(currency_from_row)
+                .fetch_optional(&self.pool)
+                .await?;
+            Ok(currency)
+        }
+
+        async fn sync_providers(&self, providers: &[TrocadorProvider]) -> Result<u64, SwapError> {
+            let synced_at = Utc::now();
+            let mut changed = 0;
+
+            let mut tx = self.pool.begin().await?;
+            for provider in providers {
+                // Keyed by slug (the lowercased, dash-joined name), which is also the id
+                let slug = provider.name.to_lowercase().replace(' ', "-");
+                changed += sqlx::query(
+                    "INSERT INTO providers (
+                        id, name, slug, is_active, kyc_rating, insurance_percentage,
+                        eta_minutes, markup_enabled, last_synced_at, created_at, updated_at
+                    )
+                    VALUES (?, ?, ?, TRUE, ?, ?, ?, ?, ?, ?, ?)
+                    ON CONFLICT (id) DO UPDATE SET
+                        name = excluded.name,
+                        kyc_rating = excluded.kyc_rating,
+                        insurance_percentage = excluded.insurance_percentage,
+                        eta_minutes = excluded.eta_minutes,
+                        markup_enabled = excluded.markup_enabled,
+                        last_synced_at = excluded.last_synced_at,
+                        updated_at = excluded.updated_at",
+                )
+                .bind(&slug)
+                .bind(&provider.name)
+                .bind(&slug)
+                .bind(&provider.rating)
+                .bind(provider.insurance)
+                .bind(provider.eta as i32)
+                .bind(provider.enabled_markup)
+                .bind(synced_at)
+                .bind(synced_at)
+                .bind(synced_at)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+            }
+            tx.commit().await?;
+
+            Ok(changed)
+        }
+
+        async fn active_providers(&self) -> Result<Vec<Provider>, SwapError> {
+            let providers = sqlx::query_as::<_, Provider>(
+                "SELECT * FROM providers WHERE is_active = TRUE ORDER BY name",
+            )
+            .fetch_all(&self.pool)
+            .await?;
+            Ok(providers)
+        }
+
+        async fn fee_rules(&self, tenant: &TenantId) -> Result<Vec<FeeRule>, SwapError> {
+            let rules = sqlx::query("SELECT * FROM fee_rules WHERE tenant_id = ? ORDER BY id")
+                .bind(tenant.as_str())
+                .try_map(fee_rule_from_row)
+                .fetch_all(&self.pool)
+                .await?;
+            Ok(rules)
+        }
+
+        async fn insert_swap(&self, swap: &Swap) -> Result<(), SwapError> {
+            sqlx::query(
+                "INSERT INTO swaps (
+                    id, tenant_id, user_id, brand, provider_id, provider_swap_id, retried_from,
+                    from_currency, from_network, to_currency, to_network,
+                    amount, estimated_receive, actual_receive, rate,
+                    network_fee, provider_fee, platform_fee, total_fee,
+                    deposit_address, deposit_extra_id,
+                    recipient_address, recipient_extra_id,
+                    refund_address, refund_extra_id,
+                    tx_hash_in, tx_hash_out, deposit_confirmations,
+                    status, rate_type, is_sandbox, imported, error, version,
+                    expires_at, completed_at, created_at, updated_at
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&swap.id)
+            .bind(&swap.tenant_id)
+            .bind(&swap.user_id)
+            .bind(&swap.brand)
+            .bind(&swap.provider_id)
+            .bind(&swap.provider_swap_id)
+            .bind(&swap.retried_from)
+            .bind(&swap.from_currency)
+            .bind(&swap.from_network)
+            .bind(&swap.to_currency)
+            .bind(&swap.to_network)
+            .bind(real(swap.amount))
+            .bind(real(swap.estimated_receive))
+            .bind(swap.actual_receive.map(real))
+            .bind(real(swap.rate))
+            .bind(real(swap.network_fee))
+            .bind(real(swap.provider_fee))
+            .bind(real(swap.platform_fee))
+            .bind(real(swap.total_fee))
+            .bind(&swap.deposit_address)
+            .bind(&swap.deposit_extra_id)
+            .bind(&swap.recipient_address)
+            .bind(&swap.recipient_extra_id)
+            .bind(&swap.refund_address)
+            .bind(&swap.refund_extra_id)
+            .bind(&swap.tx_hash_in)
+            .bind(&swap.tx_hash_out)
+            .bind(swap.deposit_confirmations)
+            .bind(&swap.status)
+            .bind(&swap.rate_type)
+            .bind(swap.is_sandbox)
+            .bind(swap.imported)
+            .bind(&swap.error)
+            .bind(swap.version)
+            .bind(swap.expires_at)
+            .bind(swap.completed_at)
+            .bind(swap.created_at)
+            .bind(swap.updated_at)
+            .execute(&self.pool)
+            .await?;
+            Ok(())
+        }
+
+        async fn find_swap(&self, swap_id: &str) -> Result<Option<Swap>, SwapError> {
+            let swap = sqlx::query("SELECT * FROM swaps WHERE id = ?")
+                .bind(swap_id)
+                .try_map(swap_from_row)
+                .fetch_optional(&self.pool)
+                .await?;
+            Ok(swap)
+        }
+
+        async fn update_swap_status(
+            &self,
+            swap_id: &str,
+            status: &SwapStatus,
+            actual_receive: Option<Decimal>,
+        ) -> Result<(), SwapError> {
+            let now = Utc::now();
+            let completed_at = (*status == SwapStatus::Completed).then_some(now);
+            sqlx::query(
+                "UPDATE swaps SET
+                    status = ?,
+                    actual_receive = COALESCE(?, actual_receive),
+                    completed_at = COALESCE(completed_at, ?),
+                    version = version + 1,
+                    updated_at = ?
+                 WHERE id = ?",
+            )
+            .bind(status)
+            .bind(actual_receive.map(real))
+            .bind(completed_at)
+            .bind(now)
+            .bind(swap_id)
+            .execute(&self.pool)
+            .await?;
+            Ok(())
+        }
+    }
+}
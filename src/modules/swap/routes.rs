@@ -1,15 +1,51 @@
-use axum::{routing::{get, post}, Router};
+use axum::{routing::{delete, get, post}, Router};
 use std::sync::Arc;
 
 use crate::AppState;
-use super::controller::{get_currencies, get_providers, get_rates, create_swap, get_swap_status, validate_address};
+use super::controller::{
+    create_address_challenge, create_share_link, create_swap, create_swap_draft, delete_swap_draft,
+    delete_favorite_pair, delete_saved_address, delete_verified_address, get_currencies, get_favorites, get_currencies_grouped, get_depth, get_pairs, get_provider_uptime, get_providers,
+    create_route, get_rates, get_refund_address_suggestions, get_route, plan_routes, get_shared_swap, get_swap_draft, get_swap_history, get_swap_limits, get_swap_refund,
+    get_swap_status, get_swap_statuses, get_verified_addresses, import_swap, reserve_quote, retry_swap,
+    save_favorite, update_swap_draft, validate_address, verify_address,
+};
+use super::stream::swap_status_ws;
+use super::webhooks::trocador_webhook;
 
 pub fn swap_routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/currencies", get(get_currencies))
+        .route("/currencies/grouped", get(get_currencies_grouped))
         .route("/providers", get(get_providers))
+        .route("/providers/{id}/uptime", get(get_provider_uptime))
+        .route("/pairs", get(get_pairs))
         .route("/rates", get(get_rates))
+        .route("/depth", get(get_depth))
+        .route("/quote", post(reserve_quote))
         .route("/create", post(create_swap))
+        .route("/routes", post(create_route))
+        .route("/routes/plan", get(plan_routes))
+        .route("/routes/{id}", get(get_route))
+        .route("/import", post(import_swap))
+        .route("/drafts", post(create_swap_draft))
+        .route("/drafts/{token}", get(get_swap_draft).put(update_swap_draft).delete(delete_swap_draft))
+        .route("/status/batch", post(get_swap_statuses))
+        .route("/history", get(get_swap_history))
+        .route("/limits", get(get_swap_limits))
+        .route("/refund-addresses", get(get_refund_address_suggestions))
+        .route("/addresses/challenge", post(create_address_challenge))
+        .route("/addresses/verify", post(verify_address))
+        .route("/addresses/verified", get(get_verified_addresses))
+        .route("/addresses/verified/{id}", delete(delete_verified_address))
+        .route("/favorites", get(get_favorites).post(save_favorite))
+        .route("/favorites/pairs/{id}", delete(delete_favorite_pair))
+        .route("/favorites/addresses/{id}", delete(delete_saved_address))
         .route("/{id}", get(get_swap_status))
+        .route("/{id}/refund", get(get_swap_refund))
+        .route("/{id}/retry", post(retry_swap))
+        .route("/{id}/share", post(create_share_link))
+        .route("/shared/{id}", get(get_shared_swap))
+        .route("/{id}/ws", get(swap_status_ws))
         .route("/validate-address", post(validate_address))
+        .route("/webhook/trocador", post(trocador_webhook))
 }
@@ -1,12 +1,21 @@
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc};
+use utoipa::{IntoParams, ToSchema};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+
+use crate::services::fee_estimator::NetworkFeeEstimate;
+use crate::services::price_feed::FiatAmount;
+use crate::services::request_id::current_request_id;
+use crate::services::schema_drift::{unknown_keys, UnknownFields};
+use crate::services::tenant::TenantId;
 
 // =============================================================================
 // PROVIDERS
 // =============================================================================
 
 // Request query parameters for /swap/providers
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ProvidersQuery {
     pub rating: Option<String>,         // Filter by KYC rating (A, B, C, D)
     pub markup_enabled: Option<bool>,   // Filter by markup support
@@ -14,7 +23,7 @@ pub struct ProvidersQuery {
 }
 
 // Response DTO matching Trocador's /exchanges format EXACTLY
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ProviderResponse {
     pub name: String,
     pub rating: String,           // Maps from kyc_rating (A/B/C/D)
@@ -23,6 +32,58 @@ pub struct ProviderResponse {
     pub eta: i32,                 // Maps from eta_minutes
 }
 
+/// Long-term availability for GET /swap/providers/{id}/uptime
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProviderUptimeResponse {
+    pub provider: String,
+    pub last_30_days: UptimeWindow,
+    pub last_90_days: UptimeWindow,
+    pub daily: Vec<DailyUptime>, // Oldest first; days without data are omitted
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct UptimeWindow {
+    pub days: u32,
+    pub availability_percent: Option<f64>, // Share of probes that found the provider up; None before any probe
+    pub probes: u64,
+    pub trade_success_percent: Option<f64>, // Share of new_trade attempts accepted; None without attempts
+    pub trades: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct DailyUptime {
+    pub day: NaiveDate, // UTC
+    pub probes: u32,
+    pub probes_up: u32,
+    pub trades: u32,
+    pub trades_failed: u32,
+}
+
+impl UptimeWindow {
+    /// Totals over the `days` ending today (UTC)
+    pub fn from_daily(days: u32, today: NaiveDate, daily: &[DailyUptime]) -> Self {
+        let since = today - chrono::Duration::days(i64::from(days) - 1);
+        let rows = daily.iter().filter(|d| d.day >= since);
+        let (probes, probes_up, trades, trades_failed) = rows.fold((0u64, 0u64, 0u64, 0u64), |acc, d| {
+            (
+                acc.0 + u64::from(d.probes),
+                acc.1 + u64::from(d.probes_up),
+                acc.2 + u64::from(d.trades),
+                acc.3 + u64::from(d.trades_failed),
+            )
+        });
+        let percent = |part: u64, total: u64| (total > 0).then(|| (part as f64 * 10000.0 / total as f64).round() / 100.0);
+
+        Self {
+            days,
+            availability_percent: percent(probes_up, probes),
+            probes,
+            trade_success_percent: percent(trades.saturating_sub(trades_failed), trades),
+            trades,
+        }
+    }
+}
+
 // Trocador's /exchanges response format (what we GET from them)
 #[derive(Debug, Deserialize)]
 pub struct TrocadorProvider {
@@ -32,6 +93,14 @@ pub struct TrocadorProvider {
     #[serde(rename = "enabledmarkup")]
     pub enabled_markup: bool,     // Note the different naming
     pub eta: f64,                 // Trocador returns this as float, we'll convert to i32
+    #[serde(flatten)]
+    pub unknown: serde_json::Map<String, serde_json::Value>, // Fields we don't map yet (schema drift)
+}
+
+impl UnknownFields for TrocadorProvider {
+    fn unknown_fields(&self) -> Vec<String> {
+        unknown_keys("", &self.unknown)
+    }
 }
 
 impl From<crate::modules::swap::model::Provider> for ProviderResponse {
@@ -51,25 +120,53 @@ impl From<crate::modules::swap::model::Provider> for ProviderResponse {
 // =============================================================================
 
 // Request query parameters for /swap/currencies
-#[derive(Debug, Deserialize, Default, Clone)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct CurrenciesQuery {
     pub ticker: Option<String>,         // Filter by ticker (e.g., "btc")
     pub network: Option<String>,        // Filter by network (e.g., "Mainnet")
     pub memo: Option<bool>,             // Filter by memo required
     pub page: Option<usize>,            // Pagination: Page number (1-based)
     pub limit: Option<usize>,           // Pagination: Items per page
+    pub per_page: Option<usize>,        // Paginated envelope: Items per page (default 50, max 500)
+    pub sort: Option<String>,           // Sort by: ticker, name, network, minimum, maximum; "-" for descending
+    pub search: Option<String>,         // Full-text search over ticker and name
+    pub fields: Option<String>,         // Comma-separated fields to return (e.g., "ticker,network")
+}
+
+impl CurrenciesQuery {
+    /// Paging by `per_page`, sorting, searching or picking fields answers
+    /// with a CurrenciesPage instead of the plain list
+    pub fn wants_page(&self) -> bool {
+        self.per_page.is_some() || self.sort.is_some() || self.search.is_some() || self.fields.is_some()
+    }
+}
+
+/// One page of GET /swap/currencies
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CurrenciesPage {
+    pub page: usize,
+    pub per_page: usize,
+    pub total: u64, // Currencies matching the filters and search, across all pages
+    pub total_pages: u64,
+    /// CurrencyResponse objects, with only the requested `fields` when given
+    pub items: Vec<serde_json::Value>,
 }
 
 // Response DTO matching Trocador's /coins format EXACTLY
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CurrencyResponse {
     pub name: String,
     pub ticker: String,       // Maps from symbol
     pub network: String,
     pub memo: bool,           // Maps from requires_extra_id
     pub image: String,        // Maps from logo_url
-    pub minimum: f64,         // Maps from min_amount
-    pub maximum: f64,         // Maps from max_amount
+    pub minimum: Decimal,         // Maps from min_amount
+    pub maximum: Decimal,         // Maps from max_amount
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delisting_at: Option<DateTime<Utc>>, // Only present for currencies scheduled for delisting
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub refund_address_required: bool,       // Only present when swaps from this currency need a refund address
 }
 
 // Trocador's /coins response format (what we GET from them)
@@ -80,8 +177,16 @@ pub struct TrocadorCurrency {
     pub network: String,
     pub memo: bool,
     pub image: String,
-    pub minimum: f64,
-    pub maximum: f64,
+    pub minimum: Decimal,
+    pub maximum: Decimal,
+    #[serde(flatten)]
+    pub unknown: serde_json::Map<String, serde_json::Value>,
+}
+
+impl UnknownFields for TrocadorCurrency {
+    fn unknown_fields(&self) -> Vec<String> {
+        unknown_keys("", &self.unknown)
+    }
 }
 
 impl From<crate::modules::swap::model::Currency> for CurrencyResponse {
@@ -92,8 +197,49 @@ impl From<crate::modules::swap::model::Currency> for CurrencyResponse {
             network: c.network,
             memo: c.requires_extra_id,
             image: c.logo_url.unwrap_or_else(|| String::from("")),
-            minimum: c.min_amount.unwrap_or(0.0),
-            maximum: c.max_amount.unwrap_or(0.0),
+            minimum: c.min_amount.unwrap_or_default(),
+            maximum: c.max_amount.unwrap_or_default(),
+            delisting_at: c.delisting_at,
+            refund_address_required: c.requires_refund_address,
+        }
+    }
+}
+
+// Response DTO for /swap/currencies/grouped: one entry per asset, networks nested
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct GroupedCurrencyResponse {
+    pub name: String,
+    pub ticker: String,
+    pub image: String,
+    pub networks: Vec<CurrencyNetworkResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CurrencyNetworkResponse {
+    pub network: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_address: Option<String>,
+    pub memo: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_id_name: Option<String>,
+    pub minimum: Decimal,
+    pub maximum: Decimal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delisting_at: Option<DateTime<Utc>>,
+    pub refund_address_required: bool,
+}
+
+impl From<crate::modules::swap::model::Currency> for CurrencyNetworkResponse {
+    fn from(c: crate::modules::swap::model::Currency) -> Self {
+        Self {
+            network: c.network,
+            contract_address: c.contract_address,
+            memo: c.requires_extra_id,
+            extra_id_name: c.extra_id_name,
+            minimum: c.min_amount.unwrap_or_default(),
+            maximum: c.max_amount.unwrap_or_default(),
+            delisting_at: c.delisting_at,
+            refund_address_required: c.requires_refund_address,
         }
     }
 }
@@ -102,7 +248,8 @@ impl From<crate::modules::swap::model::Currency> for CurrencyResponse {
 // PAIRS
 // =============================================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct PairsQuery {
     pub from: Option<String>,
     pub to: Option<String>,
@@ -110,72 +257,226 @@ pub struct PairsQuery {
     pub to_network: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PairResponse {
     pub from: String,
     pub to: String,
     pub from_network: String,
     pub to_network: String,
+    /// Both currencies are listed and accept new swaps
     pub is_active: bool,
+    /// Smallest amount any provider accepts, in `from`
+    pub min_amount: Decimal,
+    /// Largest amount any provider accepts, in `from`; None when one has no maximum
+    pub max_amount: Option<Decimal>,
+    pub providers: Vec<PairProviderResponse>,
+}
+
+/// Limits one provider quoted for a pair
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PairProviderResponse {
+    pub provider: String,
+    pub min_amount: Decimal,
+    /// None when the provider quoted no maximum
+    pub max_amount: Option<Decimal>,
+    pub last_quoted_at: DateTime<Utc>,
 }
 
 // =============================================================================
 // RATES
 // =============================================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct RatesQuery {
     pub from: String,
     pub network_from: String,
     pub to: String,
     pub network_to: String,
-    pub amount: f64,
+    #[serde(default)]
+    pub amount: Decimal,
+    /// Quote by receive amount instead of `amount`: fixed-rate quotes, each
+    /// with the `amount_from` its provider asks to pay out exactly this much
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_to: Option<Decimal>,
     pub rate_type: Option<RateType>,
     pub provider: Option<String>,
+    /// Worst provider KYC rating to quote, A (best) to D; a signed-in
+    /// user's own setting applies as well, whichever is stricter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_kyc_rating: Option<String>,
+    /// Mock quotes from the sandbox provider, for testing integrations
+    #[serde(default)]
+    pub sandbox: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, sqlx::Type)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default, sqlx::Type, ToSchema)]
 #[serde(rename_all = "lowercase")]
 #[sqlx(rename_all = "lowercase")]
 pub enum RateType {
     Fixed,
+    #[default]
     Floating,
 }
 
-impl Default for RateType {
-    fn default() -> Self {
-        RateType::Floating
-    }
-}
-
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct RateResponse {
     pub provider: String,
     pub provider_name: String,
-    pub rate: f64,
-    pub estimated_amount: f64,
-    pub min_amount: f64,
-    pub max_amount: f64,
-    pub network_fee: f64,
-    pub provider_fee: f64,
-    pub platform_fee: f64,
-    pub total_fee: f64,
+    /// Aggregator the quote came from: "trocador" or "changenow"
+    #[serde(default)]
+    pub aggregator: String,
+    pub rate: Decimal,
+    pub estimated_amount: Decimal,
+    /// What to send for this quote; only on quotes by `amount_to`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_from: Option<Decimal>,
+    pub min_amount: Decimal,
+    pub max_amount: Decimal,
+    pub network_fee: Decimal,
+    pub provider_fee: Decimal,
+    pub platform_fee: Decimal,
+    pub total_fee: Decimal,
     pub rate_type: RateType,
     pub kyc_required: bool,
     pub kyc_rating: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub eta_minutes: Option<u32>,
+    /// Trade creations with this provider that failed recently for a similar pair and amount
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub recent_failures: u32,
+    /// Quote ranked last because the provider has repeatedly failed to honor
+    /// similar quotes, or its swaps keep failing (see `health_score`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub demoted: bool,
+    /// Share of the provider's recent swaps with us that completed, 0-100;
+    /// left out until it has enough swaps to score
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_score: Option<f64>,
+    /// Rate is further from the other providers' quotes than RATE_GUARD_MAX_DEVIATION_PCT
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub rate_warning: bool,
+    /// Quote ranked first because an admin routing rule prefers the provider
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub preferred: bool,
+    /// `estimated_amount` at current prices; left out when the price feed has none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_amount_fiat: Option<FiatAmount>,
+    /// The fees above, with the payout's network fee in the coin it is paid in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_breakdown: Option<FeeBreakdown>,
+}
+
+/// Fees behind a quote. Provider and platform fees are in the receive
+/// currency; the network fee is an estimate, already included in the
+/// provider's quote
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FeeBreakdown {
+    pub provider_fee: Decimal,
+    pub platform_fee: Decimal,
+    /// Left out for chains without a fee source (FEE_ESTIMATOR_*)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<NetworkFeeEstimate>,
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct RatesResponse {
     pub trade_id: String, // Trocador trade ID
     pub from: String,
     pub network_from: String,
     pub to: String,
     pub network_to: String,
-    pub amount: f64,
+    pub amount: Decimal, // 0 when quoted by `amount_to`
+    /// The receive amount quoted for, when quoted by receive amount
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_to: Option<Decimal>,
+    /// `amount` at current prices; left out when the price feed has none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_fiat: Option<FiatAmount>,
     pub rates: Vec<RateResponse>,
+    #[serde(default)]
+    pub meta: RatesMeta,
+}
+
+/// How the quotes were gathered; see RATES_BUDGET_MS
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct RatesMeta {
+    pub budget_ms: u64,
+    pub elapsed_ms: u64,
+    /// Providers whose quotes did not arrive within the budget
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timed_out: Vec<String>,
+}
+
+// =============================================================================
+// QUOTE RESERVATION
+// =============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct QuoteRequest {
+    pub from: String,
+    pub network_from: String,
+    pub to: String,
+    pub network_to: String,
+    pub amount: Decimal,
+    pub provider: String,
+}
+
+/// A fixed-rate quote held for one swap until `expires_at`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct QuoteReservation {
+    pub quote_id: String,
+    pub trade_id: String,
+    pub provider: String,
+    pub from: String,
+    pub network_from: String,
+    pub to: String,
+    pub network_to: String,
+    pub amount: Decimal,
+    pub rate: Decimal,
+    pub estimated_amount: Decimal,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+// =============================================================================
+// DEPTH
+// =============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DepthQuery {
+    pub from: String,
+    pub network_from: String,
+    pub to: String,
+    pub network_to: String,
+    /// First rung of the ladder; defaults to the currency's minimum
+    pub amount: Option<Decimal>,
+}
+
+/// Best quote at one size
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DepthLevel {
+    pub amount: Decimal,
+    pub providers: usize, // Providers quoting this size
+    pub best_provider: Option<String>,
+    pub rate: Option<Decimal>,
+    pub estimated_amount: Option<Decimal>,
+    /// Rate relative to the first level's, in percent; negative is worse
+    pub rate_change_percent: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DepthResponse {
+    pub from: String,
+    pub network_from: String,
+    pub to: String,
+    pub network_to: String,
+    pub levels: Vec<DepthLevel>,
 }
 
 // Trocador's internal rate response
@@ -183,17 +484,29 @@ pub struct RatesResponse {
 pub struct TrocadorQuote {
     pub provider: String,
     pub amount_to: String, // String in Trocador JSON
-    pub min_amount: Option<f64>,
-    pub max_amount: Option<f64>,
+    #[serde(default)]
+    pub amount_from: Option<String>, // Payment quotes only; string in Trocador JSON
+    pub min_amount: Option<Decimal>,
+    pub max_amount: Option<Decimal>,
     pub kycrating: Option<String>,
     pub waste: Option<String>, // String in Trocador JSON
     pub eta: Option<f64>,
+    #[serde(flatten)]
+    pub unknown: serde_json::Map<String, serde_json::Value>,
+}
+
+impl UnknownFields for TrocadorQuote {
+    fn unknown_fields(&self) -> Vec<String> {
+        unknown_keys("", &self.unknown)
+    }
 }
 
 #[derive(Debug, Deserialize)]
 pub struct TrocadorQuotesWrapper {
     pub markup: bool,
     pub quotes: Vec<TrocadorQuote>,
+    #[serde(flatten)]
+    pub unknown: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -203,40 +516,51 @@ pub struct TrocadorRatesResponse {
     pub network_from: String,
     pub ticker_to: String,
     pub network_to: String,
-    pub amount_from: f64,
+    pub amount_from: Decimal,
     pub provider: String,
-    pub amount_to: f64,
+    pub amount_to: Decimal,
     pub quotes: TrocadorQuotesWrapper,
+    #[serde(flatten)]
+    pub unknown: serde_json::Map<String, serde_json::Value>,
+}
+
+// Quotes are parsed (and checked) separately so one bad quote can be skipped
+impl UnknownFields for TrocadorRatesResponse {
+    fn unknown_fields(&self) -> Vec<String> {
+        let mut fields = unknown_keys("", &self.unknown);
+        fields.extend(unknown_keys("quotes.", &self.quotes.unknown));
+        fields
+    }
 }
 
 // =============================================================================
 // ESTIMATE
 // =============================================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct EstimateRequest {
     pub from: String,
     pub network_from: String,
     pub to: String,
     pub network_to: String,
-    pub amount: f64,
+    pub amount: Decimal,
     pub provider: String,
     #[serde(default)]
     pub rate_type: RateType,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct EstimateResponse {
     pub from: String,
     pub to: String,
-    pub amount: f64,
+    pub amount: Decimal,
     pub provider: String,
-    pub rate: f64,
-    pub estimated_amount: f64,
-    pub min_amount: f64,
-    pub max_amount: f64,
-    pub network_fee: f64,
-    pub total_fee: f64,
+    pub rate: Decimal,
+    pub estimated_amount: Decimal,
+    pub min_amount: Decimal,
+    pub max_amount: Decimal,
+    pub network_fee: Decimal,
+    pub total_fee: Decimal,
     pub rate_type: RateType,
     pub valid_until: DateTime<Utc>,
 }
@@ -245,14 +569,21 @@ pub struct EstimateResponse {
 // CREATE SWAP
 // =============================================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateSwapRequest {
     pub trade_id: Option<String>, // ID from new_rate
     pub from: String,
     pub network_from: String,
     pub to: String,
     pub network_to: String,
-    pub amount: f64,
+    #[serde(default)]
+    pub amount: Decimal,
+    /// Receive exactly this much instead of sending `amount`: the provider's
+    /// fixed-rate quote for it sets how much to send
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_to: Option<Decimal>,
+    /// Omitted, empty or "best": picked from current quotes by the selection policy
+    #[serde(default)]
     pub provider: String,
     pub recipient_address: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -265,9 +596,62 @@ pub struct CreateSwapRequest {
     pub rate_type: RateType,
     #[serde(default)]
     pub sandbox: bool,
+    /// Retry with the next-best quoted provider when this one rejects the trade
+    #[serde(default)]
+    pub allow_fallback: bool,
+    /// Validate and quote only: answers with a SwapPreviewResponse, creates nothing
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+    /// Reservation from POST /swap/quote; its provider, trade and fixed rate are used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_id: Option<String>,
+}
+
+/// `provider` value asking for the best quote under the selection policy
+pub const BEST_PROVIDER: &str = "best";
+
+impl CreateSwapRequest {
+    /// The provider is left to the selection policy
+    pub fn wants_best_provider(&self) -> bool {
+        let provider = self.provider.trim();
+        provider.is_empty() || provider.eq_ignore_ascii_case(BEST_PROVIDER)
+    }
 }
 
-#[derive(Debug, Serialize)]
+/// How the provider of a swap created without one was chosen
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProviderSelection {
+    pub policy: String, // "best_net_amount"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_kyc_rating: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_eta_minutes: Option<u32>,
+    pub candidates: usize, // Quotes that met the policy
+    pub quote: SelectedQuote,
+}
+
+/// The winning quote, as it stood when the swap was created
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SelectedQuote {
+    pub trade_id: String,
+    pub provider: String,
+    pub estimated_amount: Decimal,
+    pub rate: Decimal,
+    pub total_fee: Decimal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kyc_rating: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eta_minutes: Option<u32>,
+}
+
+/// A provider that rejected trade creation before the next one was tried
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FallbackAttempt {
+    pub provider: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CreateSwapResponse {
     pub swap_id: String,
     pub provider: String,
@@ -276,15 +660,81 @@ pub struct CreateSwapResponse {
     pub deposit_address: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deposit_extra_id: Option<String>,
-    pub deposit_amount: f64,
+    pub deposit_amount: Decimal,
+    /// Wallet payment URI for the deposit, when the network has a URI scheme
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit_uri: Option<String>,
     pub recipient_address: String,
-    pub estimated_receive: f64,
-    pub rate: f64,
+    pub estimated_receive: Decimal,
+    pub rate: Decimal,
     pub status: SwapStatus,
     pub rate_type: RateType,
     pub is_sandbox: bool,
     pub expires_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retried_from: Option<String>,
+    /// Providers that rejected the trade before `provider` accepted it (allow_fallback)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_chain: Vec<FallbackAttempt>,
+    /// The caller proved control of the recipient address (POST /swap/addresses/verify)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub recipient_verified: bool,
+    /// Policy and quote behind `provider` when the request left it to the backend
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_selection: Option<ProviderSelection>,
+    /// `deposit_amount` and `estimated_receive` at current prices; left out
+    /// when the price feed has none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit_amount_fiat: Option<FiatAmount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_receive_fiat: Option<FiatAmount>,
+}
+
+/// What POST /swap/create would do for a `dry_run` request
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SwapPreviewResponse {
+    pub dry_run: bool, // Always true; nothing was created
+    pub provider: String, // Provider the swap would be created with
+    pub from: String,
+    pub network_from: String,
+    pub to: String,
+    pub network_to: String,
+    pub amount: Decimal,
+    pub estimated_receive: Decimal,
+    pub rate: Decimal,
+    pub rate_type: RateType,
+    pub network_fee: Decimal,
+    pub provider_fee: Decimal,
+    pub platform_fee: Decimal,
+    pub total_fee: Decimal,
+    pub min_amount: Decimal,
+    pub max_amount: Decimal,
+    pub kyc_rating: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eta_minutes: Option<u32>,
+    pub quote_id: String, // Pass as trade_id to create the swap at this quote
+    /// The request's trade_id is no longer the current quote; the figures above are fresh
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub quote_stale: bool,
+    /// Requested provider, when it no longer quotes the pair and allow_fallback picked `provider`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_from: Option<String>,
+}
+
+/// POST /swap/import: a trade made directly with a provider
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ImportSwapRequest {
+    pub provider: String, // Exchange name as the provider reports it, e.g. "ChangeNow"
+    pub trade_id: String,
+}
+
+// Omitted body = retry with the original provider if it still quotes the pair
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct RetrySwapRequest {
+    /// Move to the best-quoting provider other than the one that failed
+    #[serde(default)]
+    pub switch_provider: bool,
 }
 
 // Trocador's internal trade response
@@ -296,8 +746,8 @@ pub struct TrocadorTradeResponse {
     pub network_from: String,
     pub ticker_to: String,
     pub network_to: String,
-    pub amount_from: f64,
-    pub amount_to: f64,
+    pub amount_from: Decimal,
+    pub amount_to: Decimal,
     pub provider: String,
     pub address_provider: String,
     pub address_provider_memo: Option<String>,
@@ -307,33 +757,25 @@ pub struct TrocadorTradeResponse {
     pub refund_address_memo: Option<String>,
     pub id_provider: Option<String>,
     pub date: Option<String>,
+    #[serde(default)]
+    pub details: Option<serde_json::Value>, // Only `hashin`, `hashout` and `confirmations` are read
+    #[serde(flatten)]
+    pub unknown: serde_json::Map<String, serde_json::Value>,
+}
+
+impl UnknownFields for TrocadorTradeResponse {
+    fn unknown_fields(&self) -> Vec<String> {
+        unknown_keys("", &self.unknown)
+    }
 }
 
 // =============================================================================
 // SWAP STATUS
 // =============================================================================
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, sqlx::Type)]
-#[serde(rename_all = "lowercase")]
-#[sqlx(rename_all = "lowercase")]
-pub enum SwapStatus {
-    Waiting,
-    Confirming,
-    Exchanging,
-    Sending,
-    Completed,
-    Failed,
-    Refunded,
-    Expired,
-}
-
-impl Default for SwapStatus {
-    fn default() -> Self {
-        SwapStatus::Waiting
-    }
-}
+pub use super::state::SwapStatus;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SwapStatusResponse {
     pub swap_id: String,
     pub provider: String,
@@ -341,25 +783,35 @@ pub struct SwapStatusResponse {
     pub status: SwapStatus,
     pub from: String,
     pub to: String,
-    pub amount: f64,
+    pub amount: Decimal,
     pub deposit_address: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deposit_extra_id: Option<String>,
     pub recipient_address: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub recipient_extra_id: Option<String>,
-    pub rate: f64,
-    pub estimated_receive: f64,
+    pub rate: Decimal,
+    pub estimated_receive: Decimal,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub actual_receive: Option<f64>,
-    pub network_fee: f64,
-    pub total_fee: f64,
+    pub actual_receive: Option<Decimal>,
+    pub network_fee: Decimal,
+    pub total_fee: Decimal,
     pub rate_type: RateType,
     pub is_sandbox: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub tx_hash_in: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub tx_hash_out: Option<String>,
+    #[serde(default)]
+    pub imported: bool, // Added with POST /swap/import; no deposit window of ours
+    /// The user's deposit transaction, once the provider reports it
+    #[serde(default, alias = "tx_hash_in", skip_serializing_if = "Option::is_none")]
+    pub deposit_tx_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit_tx_url: Option<String>, // Block explorer page, when the chain has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit_confirmations: Option<u32>,
+    /// The provider's payout to the recipient
+    #[serde(default, alias = "tx_hash_out", skip_serializing_if = "Option::is_none")]
+    pub payout_tx_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payout_tx_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
     pub created_at: DateTime<Utc>,
@@ -368,67 +820,800 @@ pub struct SwapStatusResponse {
     pub expires_at: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub completed_at: Option<DateTime<Utc>>,
+    /// Row version the status was read at
+    #[serde(default)]
+    pub version: u32,
+    /// Tenant the swap belongs to; keys the status cache, never served
+    #[serde(skip)]
+    pub tenant: TenantId,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BatchSwapStatusRequest {
+    pub swap_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BatchSwapStatusResponse {
+    pub swaps: Vec<SwapStatusResponse>,
+    /// Requested ids that don't exist or belong to another user
+    pub not_found: Vec<String>,
+}
+
+// =============================================================================
+// SHARE LINKS
+// =============================================================================
+
+/// POST /swap/{id}/share; an omitted body gets SHARE_LINK_TTL_HOURS
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct CreateShareLinkRequest {
+    #[serde(default)]
+    pub expires_in_hours: Option<u32>, // Capped at SHARE_LINK_MAX_TTL_HOURS
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ShareLinkResponse {
+    pub url: String, // Path of the read-only status view, signature included
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SharedSwapQuery {
+    pub expires: i64,
+    pub sig: String,
+}
+
+/// GET /swap/shared/{id}: progress only, without addresses, transaction
+/// hashes, provider ids or anything tied to the owner's account
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SharedSwapStatusResponse {
+    pub swap_id: String,
+    pub status: SwapStatus,
+    pub from: String,
+    pub to: String,
+    pub amount: Decimal,
+    pub estimated_receive: Decimal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_receive: Option<Decimal>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl From<SwapStatusResponse> for SharedSwapStatusResponse {
+    fn from(s: SwapStatusResponse) -> Self {
+        Self {
+            swap_id: s.swap_id,
+            status: s.status,
+            from: s.from,
+            to: s.to,
+            amount: s.amount,
+            estimated_receive: s.estimated_receive,
+            actual_receive: s.actual_receive,
+            created_at: s.created_at,
+            updated_at: s.updated_at,
+            completed_at: s.completed_at,
+        }
+    }
+}
+
+// =============================================================================
+// SWAP DRAFTS
+// =============================================================================
+
+/// A swap form saved part way through; everything after the pair is optional
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SwapDraft {
+    pub from: String,
+    pub network_from: String,
+    pub to: String,
+    pub network_to: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<Decimal>,
+    #[serde(default)]
+    pub rate_type: RateType,
+    /// The quote the user picked, kept so resuming doesn't re-quote
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote: Option<DraftQuote>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient_extra_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refund_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refund_extra_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DraftQuote {
+    pub trade_id: String, // Pass as trade_id when creating the swap
+    pub provider: String,
+    pub estimated_amount: Decimal,
+    pub rate: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SwapDraftResponse {
+    pub token: String, // Resumes the draft on any device; treat as a secret
+    pub draft: SwapDraft,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
 }
 
 // =============================================================================
 // SWAP HISTORY
 // =============================================================================
 
-#[derive(Debug, Deserialize)]
+/// Filters for GET /swap/history. Dates are RFC 3339 timestamps or plain
+/// `YYYY-MM-DD` days (a plain `to_date` includes that whole day).
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct HistoryQuery {
-    #[serde(default = "default_page")]
-    pub page: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>, // next_cursor from the previous page
     #[serde(default = "default_limit")]
     pub limit: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<SwapStatus>,
-    pub from: Option<String>,
-    pub to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>, // Ticker sent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>, // Ticker received
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub from_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub to_date: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sandbox: Option<bool>,
 }
 
-fn default_page() -> u32 { 1 }
+impl Default for HistoryQuery {
+    fn default() -> Self {
+        Self {
+            cursor: None,
+            limit: default_limit(),
+            status: None,
+            provider: None,
+            from: None,
+            to: None,
+            from_date: None,
+            to_date: None,
+            sandbox: None,
+        }
+    }
+}
+
 fn default_limit() -> u32 { 20 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SwapSummary {
     pub swap_id: String,
     pub provider: String,
     pub from: String,
     pub to: String,
-    pub amount: f64,
-    pub estimated_receive: f64,
+    pub amount: Decimal,
+    pub estimated_receive: Decimal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_receive: Option<Decimal>,
     pub status: SwapStatus,
     pub rate_type: RateType,
     pub is_sandbox: bool,
+    #[serde(default)]
+    pub imported: bool,
     pub created_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub completed_at: Option<DateTime<Utc>>,
+    /// `amount` and `estimated_receive` at current (not historical) prices
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_fiat: Option<FiatAmount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_receive_fiat: Option<FiatAmount>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct HistoryResponse {
-    pub swaps: Vec<SwapSummary>,
-    pub page: u32,
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SwapHistoryResponse {
+    pub swaps: Vec<SwapSummary>, // Newest first
     pub limit: u32,
-    pub total: u64,
-    pub total_pages: u32,
+    pub total: u64, // Swaps matching the filters, across all pages
+    pub next_cursor: Option<String>, // None on the last page
+}
+
+// =============================================================================
+// REFUND ADDRESS SUGGESTIONS
+// =============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RefundAddressQuery {
+    pub ticker: String, // Currency the swap sends, i.e. the one refunds come back in
+    pub network: String,
+}
+
+/// Where a suggested address was seen in the caller's history
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressSource {
+    Refund,    // Given as the refund address of an earlier swap
+    Recipient, // Received a completed swap
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RefundAddressSuggestion {
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_id: Option<String>,
+    pub sources: Vec<AddressSource>,
+    pub times_used: u32,
+    pub last_used_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RefundAddressSuggestionsResponse {
+    pub ticker: String,
+    pub network: String,
+    pub suggestions: Vec<RefundAddressSuggestion>, // Most recently used first
+}
+
+// =============================================================================
+// ADDRESS OWNERSHIP
+// =============================================================================
+
+/// Ask for a message to sign with the key behind a payout address
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AddressChallengeRequest {
+    pub ticker: String,
+    pub network: String,
+    pub address: String,
+}
+
+/// Message to sign; also what is kept until it is signed or expires
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AddressChallengeResponse {
+    pub chain: String, // "bitcoin" or "evm"; one EVM proof covers every EVM network
+    pub address: String,
+    pub message: String, // Sign exactly this, e.g. with "Sign message" or personal_sign
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct VerifyAddressRequest {
+    pub ticker: String,
+    pub network: String,
+    pub address: String,
+    /// Base64 BIP-137 signature on Bitcoin, 0x-prefixed hex on EVM chains
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct VerifiedAddressResponse {
+    pub id: String,
+    pub chain: String,
+    pub address: String, // Normalized: EVM and bech32 addresses in lowercase
+    pub verified_at: DateTime<Utc>,
+}
+
+impl From<crate::modules::swap::model::VerifiedAddress> for VerifiedAddressResponse {
+    fn from(row: crate::modules::swap::model::VerifiedAddress) -> Self {
+        Self {
+            id: row.id,
+            chain: row.chain,
+            address: row.address,
+            verified_at: row.verified_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct VerifiedAddressesResponse {
+    pub addresses: Vec<VerifiedAddressResponse>, // Most recently verified first
+}
+
+// =============================================================================
+// VOLUME LIMITS
+// =============================================================================
+
+/// USD volume counted against one rolling window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct VolumeAllowance {
+    pub window_hours: u32,          // 24 or 720 (30 days), to the hour
+    pub limit_usd: Option<f64>,     // None when the window is not capped
+    pub used_usd: f64,              // Swaps created in the window
+    pub completed_usd: f64,         // Swaps completed in the window
+    pub remaining_usd: Option<f64>, // What new swaps may still be worth; None when not capped
+}
+
+impl VolumeAllowance {
+    pub fn new(window_hours: u32, limit_usd: Option<f64>, used_usd: f64, completed_usd: f64) -> Self {
+        Self {
+            window_hours,
+            limit_usd,
+            used_usd,
+            completed_usd,
+            remaining_usd: limit_usd.map(|limit| (limit - used_usd).max(0.0)),
+        }
+    }
+}
+
+/// GET /swap/limits: what the caller may still swap
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SwapLimitsResponse {
+    pub max_swap_usd: Option<f64>,          // Per swap (SWAP_MAX_USD)
+    pub verified_max_swap_usd: Option<f64>, // Per swap to a verified recipient (VERIFIED_SWAP_MAX_USD)
+    pub rolling_24h: VolumeAllowance,
+    pub rolling_30d: VolumeAllowance,
+}
+
+// =============================================================================
+// FAVORITES
+// =============================================================================
+
+/// Star a pair or save a recipient address; saving one again restores it if
+/// it was removed
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SaveFavoriteRequest {
+    Pair {
+        from: String,
+        network_from: String,
+        to: String,
+        network_to: String,
+    },
+    Address {
+        ticker: String,
+        network: String,
+        address: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        extra_id: Option<String>,
+        /// Omitted keeps the current label, "" clears it
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FavoritePairResponse {
+    pub id: String,
+    pub from: String,
+    pub network_from: String,
+    pub to: String,
+    pub network_to: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<crate::modules::swap::model::FavoritePair> for FavoritePairResponse {
+    fn from(row: crate::modules::swap::model::FavoritePair) -> Self {
+        Self {
+            id: row.id,
+            from: row.from_currency,
+            network_from: row.from_network,
+            to: row.to_currency,
+            network_to: row.to_network,
+            created_at: row.created_at,
+        }
+    }
+}
+
+/// Pair of the caller's recent swaps
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RecentPair {
+    pub from: String,
+    pub network_from: String,
+    pub to: String,
+    pub network_to: String,
+    pub times_used: u32,
+    pub last_used_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SavedAddressResponse {
+    pub id: String,
+    pub ticker: String,
+    pub network: String,
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub times_used: u32, // Swaps created to it while signed in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<crate::modules::swap::model::SavedAddress> for SavedAddressResponse {
+    fn from(row: crate::modules::swap::model::SavedAddress) -> Self {
+        Self {
+            id: row.id,
+            ticker: row.currency,
+            network: row.network,
+            address: row.address,
+            extra_id: Some(row.extra_id).filter(|memo| !memo.is_empty()),
+            label: row.label,
+            times_used: row.times_used,
+            last_used_at: row.last_used_at,
+            created_at: row.created_at,
+        }
+    }
+}
+
+/// What was saved by POST /swap/favorites
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SavedFavorite {
+    Pair(FavoritePairResponse),
+    Address(SavedAddressResponse),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FavoritesResponse {
+    pub pairs: Vec<FavoritePairResponse>,  // Most recently starred first
+    pub recent_pairs: Vec<RecentPair>,     // Most recently swapped first
+    pub addresses: Vec<SavedAddressResponse>, // Labelled first, then most recently used
+}
+
+// =============================================================================
+// PROVIDER PAYLOADS
+// =============================================================================
+
+/// Which Trocador call a stored raw payload came from
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(rename_all = "snake_case")]
+pub enum ProviderCallType {
+    CreateTrade,
+    TradeStatus,
+}
+
+// =============================================================================
+// REFUNDS
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum RefundSource {
+    Poll,    // Status poll (GET /swap/{id} or the background poller)
+    Webhook, // POST /swap/webhook/trocador
+}
+
+/// Refund details in a provider trade report; any of them may be missing
+#[derive(Debug, Default, Clone)]
+pub struct RefundReport {
+    pub refund_address: Option<String>,
+    pub refund_extra_id: Option<String>,
+    pub amount: Option<Decimal>,
+    pub tx_hash: Option<String>,
+}
+
+impl From<&TrocadorTradeResponse> for RefundReport {
+    fn from(trade: &TrocadorTradeResponse) -> Self {
+        Self {
+            refund_address: non_blank(trade.refund_address.as_deref()),
+            refund_extra_id: non_blank(trade.refund_address_memo.as_deref()),
+            amount: Some(trade.amount_from).filter(|a| *a > Decimal::ZERO),
+            tx_hash: details_hashout(trade.details.as_ref()),
+        }
+    }
+}
+
+impl From<&TrocadorWebhookPayload> for RefundReport {
+    fn from(payload: &TrocadorWebhookPayload) -> Self {
+        Self {
+            refund_address: non_blank(payload.refund_address.as_deref()),
+            refund_extra_id: non_blank(payload.refund_address_memo.as_deref()),
+            amount: payload.amount_from.filter(|a| *a > Decimal::ZERO),
+            tx_hash: details_hashout(payload.details.as_ref()),
+        }
+    }
+}
+
+/// Transactions of a trade as the provider reports them; any may be missing
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TransactionReport {
+    pub deposit_tx_hash: Option<String>,
+    pub payout_tx_hash: Option<String>, // Never the refund: see details_hashout
+    pub deposit_confirmations: Option<u32>,
+}
+
+impl TransactionReport {
+    /// From the `details` of a trade in `status`; for a refunded trade
+    /// `hashout` is the refund, which is kept with the refund instead
+    pub fn from_details(status: &str, details: Option<&serde_json::Value>) -> Self {
+        let refunded = super::state::SwapStateMachine::map_provider_status(status) == SwapStatus::Refunded;
+        let confirmations = details.and_then(|d| d.get("confirmations")).and_then(|c| match c {
+            serde_json::Value::Number(n) => n.as_u64(),
+            serde_json::Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        });
+        Self {
+            deposit_tx_hash: non_blank(details.and_then(|d| d.get("hashin")?.as_str())),
+            payout_tx_hash: if refunded { None } else { details_hashout(details) },
+            deposit_confirmations: confirmations.map(|c| c.min(u32::MAX as u64) as u32),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl From<&TrocadorTradeResponse> for TransactionReport {
+    fn from(trade: &TrocadorTradeResponse) -> Self {
+        Self::from_details(&trade.status, trade.details.as_ref())
+    }
+}
+
+impl From<&TrocadorWebhookPayload> for TransactionReport {
+    fn from(payload: &TrocadorWebhookPayload) -> Self {
+        Self::from_details(&payload.status, payload.details.as_ref())
+    }
+}
+
+/// `details.hashout` of a Trocador trade: the provider's outgoing
+/// transaction, which for a refunded trade is the refund
+pub fn details_hashout(details: Option<&serde_json::Value>) -> Option<String> {
+    non_blank(details?.get("hashout")?.as_str())
+}
+
+/// Trocador sends "" (and "0" for memos) for unset values
+fn non_blank(value: Option<&str>) -> Option<String> {
+    value.map(str::trim).filter(|v| !v.is_empty() && *v != "0").map(str::to_string)
+}
+
+/// GET /swap/{id}/refund
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SwapRefundResponse {
+    pub swap_id: String,
+    pub refund_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refund_extra_id: Option<String>,
+    pub amount: Option<Decimal>,
+    pub currency: String,
+    pub network: String,
+    pub tx_hash: Option<String>, // None until the provider reports the refund transaction
+    pub source: RefundSource,
+    pub recorded_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<crate::modules::swap::model::SwapRefund> for SwapRefundResponse {
+    fn from(r: crate::modules::swap::model::SwapRefund) -> Self {
+        Self {
+            swap_id: r.swap_id,
+            refund_address: r.refund_address,
+            refund_extra_id: r.refund_extra_id,
+            amount: r.amount,
+            currency: r.currency,
+            network: r.network,
+            tx_hash: r.tx_hash,
+            source: r.source,
+            recorded_at: r.created_at,
+            updated_at: r.updated_at,
+        }
+    }
+}
+
+// =============================================================================
+// MULTI-LEG ROUTES
+// =============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RoutePlanQuery {
+    pub from: String,
+    pub network_from: String,
+    pub to: String,
+    pub network_to: String,
+    pub amount: Decimal,
+    /// Only route through this currency; by default every SWAP_ROUTE_VIA currency is tried
+    #[serde(default)]
+    pub via: Option<String>,
+    #[serde(default)]
+    pub via_network: Option<String>, // Required with `via`
+    #[serde(default)]
+    pub rate_type: RateType, // Of the first leg; the second is always floating
+}
+
+/// The best quote for one leg of a route
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RouteLegQuote {
+    pub from: String,
+    pub network_from: String,
+    pub to: String,
+    pub network_to: String,
+    pub amount: Decimal,
+    pub provider: String,
+    pub estimated_amount: Decimal,
+    pub rate: Decimal,
+    pub total_fee: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RoutePlan {
+    /// Intermediate currency; None for the direct pair
+    pub via: Option<String>,
+    pub via_network: Option<String>,
+    pub legs: Vec<RouteLegQuote>,
+    pub estimated_receive: Decimal, // Last leg's estimate
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RoutePlanResponse {
+    pub from: String,
+    pub network_from: String,
+    pub to: String,
+    pub network_to: String,
+    pub amount: Decimal,
+    pub routes: Vec<RoutePlan>, // Highest estimated_receive first
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateRouteRequest {
+    pub from: String,
+    pub network_from: String,
+    pub to: String,
+    pub network_to: String,
+    pub amount: Decimal,
+    /// Intermediate currency; by default the best-paying SWAP_ROUTE_VIA currency
+    #[serde(default)]
+    pub via: Option<String>,
+    #[serde(default)]
+    pub via_network: Option<String>,
+    pub recipient_address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient_extra_id: Option<String>,
+    /// Refunds of the first leg; the second leg has no refund address of the caller's
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refund_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refund_extra_id: Option<String>,
+    #[serde(default)]
+    pub rate_type: RateType, // Of the first leg
+}
+
+impl CreateRouteRequest {
+    /// Quote query for the route this request asks for
+    pub fn plan_query(&self) -> RoutePlanQuery {
+        RoutePlanQuery {
+            from: self.from.clone(),
+            network_from: self.network_from.clone(),
+            to: self.to.clone(),
+            network_to: self.network_to.clone(),
+            amount: self.amount,
+            via: self.via.clone(),
+            via_network: self.via_network.clone(),
+            rate_type: self.rate_type.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum RouteStatus {
+    #[default]
+    Waiting,    // For the deposit of the first leg
+    Processing, // Funds are moving through either leg
+    Completed,  // The second leg paid out to the recipient
+    Failed,     // Either leg failed, or the second expired holding the first leg's payout
+    Refunded,   // Either leg refunded its deposit
+    Expired,    // The first leg expired without a deposit
+}
+
+impl RouteStatus {
+    /// A route's status from those of its legs
+    pub fn rollup(first: &SwapStatus, second: &SwapStatus) -> Self {
+        match (first, second) {
+            (SwapStatus::Waiting, _) => RouteStatus::Waiting,
+            (SwapStatus::Failed, _) => RouteStatus::Failed,
+            (SwapStatus::Refunded, _) => RouteStatus::Refunded,
+            (SwapStatus::Expired, _) => RouteStatus::Expired,
+            (_, SwapStatus::Completed) => RouteStatus::Completed,
+            (_, SwapStatus::Refunded) => RouteStatus::Refunded,
+            (_, SwapStatus::Failed | SwapStatus::Expired) => RouteStatus::Failed,
+            _ => RouteStatus::Processing,
+        }
+    }
+
+    /// No further status changes are expected
+    pub fn is_final(&self) -> bool {
+        !matches!(self, RouteStatus::Waiting | RouteStatus::Processing)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SwapRouteResponse {
+    pub route_id: String,
+    pub status: RouteStatus,
+    pub from: String,
+    pub network_from: String,
+    pub via: String,
+    pub via_network: String,
+    pub to: String,
+    pub network_to: String,
+    pub amount: Decimal,
+    pub estimated_receive: Decimal,
+    /// Where to send `amount` of `from`: the first leg's deposit address
+    pub deposit_address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deposit_extra_id: Option<String>,
+    pub legs: Vec<SwapStatusResponse>, // First leg, then second
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// =============================================================================
+// SYNC RUNS
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum SyncKind {
+    Currencies,
+    Providers,
+    Pairs,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum SyncRunStatus {
+    Success,
+    Failed,
+    Timeout,
+}
+
+// Last recorded run for each sync kind (served by /ready and /admin/sync/status)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SyncStatusResponse {
+    pub currencies: Option<crate::modules::swap::model::SyncRun>,
+    pub providers: Option<crate::modules::swap::model::SyncRun>,
+    pub pairs: Option<crate::modules::swap::model::SyncRun>,
+}
+
+// =============================================================================
+// SHADOW QUOTES
+// =============================================================================
+
+/// How shadowed aggregators compared with served quotes (GET /admin/providers/shadow-quotes)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ShadowQuoteReport {
+    pub hours: u32,
+    pub aggregators: Vec<ShadowAggregatorSummary>,
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct ShadowAggregatorSummary {
+    pub aggregator: String,
+    pub requests: i64,
+    pub failures: i64,  // Calls that errored
+    pub no_quotes: i64, // Calls that answered with no quote for the pair
+    pub compared: i64,  // Requests where both sides had a quote
+    pub beat_served: i64, // Compared requests where the shadow quote paid more
+    /// Mean of (shadow best - served best) / served best, in percent
+    pub avg_diff_percent: Option<f64>,
+    pub avg_latency_ms: Option<f64>,
 }
 
 // =============================================================================
 // ADDRESS VALIDATION
 // =============================================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ValidateAddressRequest {
     pub ticker: String,
     pub network: String,
     pub address: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ValidateAddressResponse {
     pub valid: bool,
     pub ticker: String,
@@ -440,15 +1625,63 @@ pub struct ValidateAddressResponse {
 // ERROR RESPONSE
 // =============================================================================
 
-#[derive(Debug, Serialize)]
+// =============================================================================
+// POST /swap/webhook/trocador
+// =============================================================================
+
+/// Trade status callback; Trocador posts the trade object, only these fields are used
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TrocadorWebhookPayload {
+    pub trade_id: String,
+    pub status: String,
+    #[serde(default)]
+    pub amount_to: Option<Decimal>,
+    #[serde(default)]
+    pub amount_from: Option<Decimal>,
+    #[serde(default)]
+    pub refund_address: Option<String>,
+    #[serde(default)]
+    pub refund_address_memo: Option<String>,
+    #[serde(default)]
+    pub details: Option<serde_json::Value>,
+}
+
+/// What a webhook delivery did, as recorded in swap_webhook_events
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(rename_all = "snake_case")]
+pub enum WebhookOutcome {
+    Applied,
+    Unchanged,   // Status already stored
+    Superseded,  // Stale delivery; the swap is already further along
+    Rejected,    // Bad signature
+    Invalid,     // Body did not parse
+    UnknownSwap, // No swap with this trade id
+    Failed,      // Processing error; the provider should retry
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SwapWebhookResponse {
+    pub event_id: Option<u64>,
+    pub outcome: WebhookOutcome,
+    pub swap_id: String,
+    pub status: SwapStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SwapErrorResponse {
     pub error: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub code: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub min_amount: Option<f64>,
+    pub min_amount: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_amount: Option<Decimal>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub max_amount: Option<f64>,
+    pub extra_id_name: Option<String>, // e.g. "Destination Tag" when a memo is missing or invalid
+    /// X-Request-Id of the failed request, for support and log searches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 impl SwapErrorResponse {
@@ -458,24 +1691,23 @@ impl SwapErrorResponse {
             code: None,
             min_amount: None,
             max_amount: None,
+            extra_id_name: None,
+            request_id: current_request_id(),
         }
     }
 
     pub fn with_code(error: impl Into<String>, code: impl Into<String>) -> Self {
         Self {
-            error: error.into(),
             code: Some(code.into()),
-            min_amount: None,
-            max_amount: None,
+            ..Self::new(error)
         }
     }
 
-    pub fn with_limits(error: impl Into<String>, min: f64, max: f64) -> Self {
+    pub fn with_limits(error: impl Into<String>, min: Decimal, max: Decimal) -> Self {
         Self {
-            error: error.into(),
-            code: None,
             min_amount: Some(min),
             max_amount: Some(max),
+            ..Self::new(error)
         }
     }
 }
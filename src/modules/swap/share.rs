@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+
+use super::crud::SwapError;
+use crate::services::security::{hmac_sha256_hex, verify_hmac_sha256};
+
+// =============================================================================
+// SHARE LINKS
+// A share link is /swap/shared/{id}?expires={unix seconds}&sig={hex}, where
+// sig is the HMAC-SHA256 of "{id}.{expires}". Nothing is stored: a link stays
+// valid until it expires or the key changes.
+// =============================================================================
+
+fn signed_message(swap_id: &str, expires: i64) -> String {
+    format!("{}.{}", swap_id, expires)
+}
+
+/// Path of the read-only status view of `swap_id`, valid until `expires_at`
+pub fn share_path(secret: &str, swap_id: &str, expires_at: DateTime<Utc>) -> String {
+    let expires = expires_at.timestamp();
+    let sig = hmac_sha256_hex(secret, signed_message(swap_id, expires).as_bytes());
+    format!("/swap/shared/{}?expires={}&sig={}", swap_id, expires, sig)
+}
+
+/// Check a share link's signature, then its expiry
+pub fn verify_share(secret: &str, swap_id: &str, expires: i64, sig: &str) -> Result<(), SwapError> {
+    if !verify_hmac_sha256(secret, signed_message(swap_id, expires).as_bytes(), sig) {
+        return Err(SwapError::InvalidShareLink);
+    }
+
+    let expires_at = DateTime::from_timestamp(expires, 0).ok_or(SwapError::InvalidShareLink)?;
+    if expires_at <= Utc::now() {
+        return Err(SwapError::ShareLinkExpired(expires_at));
+    }
+
+    Ok(())
+}
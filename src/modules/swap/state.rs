@@ -0,0 +1,145 @@
+//! Swap state machine.
+//!
+//! The one place provider statuses are mapped to a `SwapStatus` and the one
+//! place that decides whether a swap may move from one status to another.
+//! A swap only moves forward through its lifecycle (see `progress`):
+//! waiting, confirming, exchanging, sending, then completed. Failed and
+//! expired can end it early, and a failed or expired swap can still be
+//! completed or refunded by the provider. Nothing leaves completed or
+//! refunded.
+//!
+//! The one way back is a lost deposit: a provider that had seen the deposit
+//! reports the swap waiting again. Only a status we polled is believed for
+//! that; a webhook delivery saying "waiting" may simply be old.
+//!
+//! Every accepted change is written to `swap_status_history` with the status
+//! it replaced, where it came from and when.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum SwapStatus {
+    #[default]
+    Waiting,
+    Confirming,
+    Exchanging,
+    Sending,
+    Completed,
+    Failed,
+    Refunded,
+    Expired,
+}
+
+impl SwapStatus {
+    /// Lowercase name, as stored and serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SwapStatus::Waiting => "waiting",
+            SwapStatus::Confirming => "confirming",
+            SwapStatus::Exchanging => "exchanging",
+            SwapStatus::Sending => "sending",
+            SwapStatus::Completed => "completed",
+            SwapStatus::Failed => "failed",
+            SwapStatus::Refunded => "refunded",
+            SwapStatus::Expired => "expired",
+        }
+    }
+
+    /// No further status changes are expected
+    pub fn is_final(&self) -> bool {
+        matches!(self, SwapStatus::Completed | SwapStatus::Failed | SwapStatus::Refunded | SwapStatus::Expired)
+    }
+
+    /// Position in the swap lifecycle. When concurrent updates race, a write
+    /// never replaces a status that is at least as far along as its own.
+    pub fn progress(&self) -> u8 {
+        match self {
+            SwapStatus::Waiting => 0,
+            SwapStatus::Confirming => 1,
+            SwapStatus::Exchanging => 2,
+            SwapStatus::Sending => 3,
+            SwapStatus::Failed | SwapStatus::Expired => 4,
+            SwapStatus::Completed | SwapStatus::Refunded => 5,
+        }
+    }
+
+    /// The provider had seen a deposit and now reports it missing: the
+    /// transaction was dropped by a chain reorganization or double-spent
+    pub fn deposit_lost(&self, next: &SwapStatus) -> bool {
+        matches!(self, SwapStatus::Confirming | SwapStatus::Exchanging) && *next == SwapStatus::Waiting
+    }
+}
+
+/// Where a status came from, as recorded in `swap_status_history.source`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(rename_all = "snake_case")]
+pub enum StatusSource {
+    Create,  // Reported by the provider when the trade was opened
+    Import,  // Reported by the provider when an existing trade was imported
+    Poll,    // Fetched from the provider's status endpoint
+    Webhook, // Pushed by the provider; deliveries may arrive out of order
+    Expiry,  // Set by us when no deposit arrived in time
+}
+
+/// Kind of an allowed status change
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transition {
+    Unchanged,   // Same status; nothing to write
+    Advance,     // Further along the lifecycle
+    DepositLost, // Back to waiting after the deposit disappeared
+}
+
+/// A status change the state machine refuses
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("swap cannot move from {} to {} ({origin:?})", from.as_str(), to.as_str())]
+pub struct IllegalTransition {
+    pub from: SwapStatus,
+    pub to: SwapStatus,
+    pub origin: StatusSource,
+}
+
+pub struct SwapStateMachine;
+
+impl SwapStateMachine {
+    /// Status for a provider's trade status; unknown ones count as waiting
+    pub fn map_provider_status(status: &str) -> SwapStatus {
+        match status {
+            "new" | "waiting" => SwapStatus::Waiting,
+            "confirming" => SwapStatus::Confirming,
+            "exchanging" => SwapStatus::Exchanging,
+            "sending" => SwapStatus::Sending,
+            "finished" | "paid partially" => SwapStatus::Completed,
+            "failed" | "halted" => SwapStatus::Failed,
+            "refunded" => SwapStatus::Refunded,
+            "expired" => SwapStatus::Expired,
+            _ => SwapStatus::Waiting,
+        }
+    }
+
+    /// The provider received less than the swap expects
+    pub fn is_underpaid(status: &str) -> bool {
+        status == "paid partially"
+    }
+
+    /// Whether a swap in `from` may move to `to` on a status from `source`
+    pub fn transition(from: &SwapStatus, to: &SwapStatus, source: StatusSource) -> Result<Transition, IllegalTransition> {
+        if from == to {
+            Ok(Transition::Unchanged)
+        } else if to.progress() > from.progress() {
+            Ok(Transition::Advance)
+        } else if source == StatusSource::Poll && from.deposit_lost(to) {
+            Ok(Transition::DepositLost)
+        } else {
+            Err(IllegalTransition { from: from.clone(), to: to.clone(), origin: source })
+        }
+    }
+
+    /// The change is allowed and moves the swap forward
+    pub fn advances(from: &SwapStatus, to: &SwapStatus, source: StatusSource) -> bool {
+        matches!(Self::transition(from, to, source), Ok(Transition::Advance))
+    }
+}
@@ -0,0 +1,146 @@
+//! Live swap status over WebSocket.
+//!
+//! `GET /swap/{id}/ws` sends the current `SwapStatusResponse` on connect and
+//! then every stored status change, until the swap reaches a final status.
+//! Changes are fanned out through Redis pub/sub (see `publish_swap_status`),
+//! so a client connected to one instance sees updates written by any other:
+//! the status endpoint, provider webhooks or the background poller.
+
+use axum::{
+    extract::{
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Path, State,
+    },
+    response::Response,
+};
+use futures_util::StreamExt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::AppState;
+use super::controller::swap_crud;
+use super::crud::{swap_status_channel, SwapCrud, SwapError};
+use super::schema::{SwapErrorResponse, SwapStatusResponse};
+use crate::services::tenant::CurrentTenant;
+
+/// Keeps idle connections open through proxies that drop silent sockets
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Close code sent when the update feed is unavailable (RFC 6455 "internal error")
+const CLOSE_INTERNAL_ERROR: u16 = 1011;
+
+// =============================================================================
+// GET /swap/{id}/ws - Stream status changes for a swap
+// =============================================================================
+
+#[utoipa::path(
+    get,
+    path = "/swap/{id}/ws",
+    tag = "swap",
+    params(("id" = String, Path, description = "Swap id")),
+    responses(
+        (status = 101, description = "WebSocket of SwapStatusResponse messages, one per status change", body = SwapStatusResponse),
+        (status = 404, description = "Unknown swap", body = SwapErrorResponse),
+    ),
+)]
+pub async fn swap_status_ws(
+    State(state): State<Arc<AppState>>,
+    CurrentTenant(tenant): CurrentTenant,
+    Path(swap_id): Path<String>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, SwapError> {
+    let crud = swap_crud(&state).with_tenant(tenant);
+
+    // Unknown swaps are rejected before upgrading
+    crud.get_stored_swap_status(&swap_id).await?;
+
+    Ok(ws.on_upgrade(move |socket| stream_status(socket, state, crud, swap_id)))
+}
+
+async fn stream_status(mut socket: WebSocket, state: Arc<AppState>, crud: SwapCrud, swap_id: String) {
+    // Subscribe before reading the snapshot so no change falls between the two
+    let mut pubsub = match state.redis.subscribe(&swap_status_channel(&swap_id)).await {
+        Ok(pubsub) => pubsub,
+        Err(e) => {
+            tracing::warn!("Status stream for swap {} unavailable: {}", swap_id, e);
+            close(&mut socket, CLOSE_INTERNAL_ERROR, "Status updates unavailable").await;
+            return;
+        }
+    };
+
+    let snapshot = match crud.get_stored_swap_status(&swap_id).await {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            tracing::warn!("Failed to load status for swap {}: {}", swap_id, e);
+            close(&mut socket, CLOSE_INTERNAL_ERROR, "Failed to load swap status").await;
+            return;
+        }
+    };
+    if !send_status(&mut socket, &snapshot).await {
+        return;
+    }
+    if snapshot.status.is_final() {
+        close(&mut socket, 1000, "Swap finished").await;
+        return;
+    }
+
+    let mut last_version = snapshot.version;
+    let mut updates = pubsub.on_message();
+    let mut ping = tokio::time::interval(PING_INTERVAL);
+    ping.tick().await; // The first tick is immediate
+
+    loop {
+        tokio::select! {
+            update = updates.next() => {
+                let Some(update) = update else {
+                    close(&mut socket, CLOSE_INTERNAL_ERROR, "Status updates unavailable").await;
+                    return;
+                };
+                let Some(update) = update
+                    .get_payload::<String>()
+                    .ok()
+                    .and_then(|payload| serde_json::from_str::<SwapStatusResponse>(&payload).ok())
+                else {
+                    continue;
+                };
+
+                // Pub/sub can deliver a change the snapshot already included
+                if update.version <= last_version {
+                    continue;
+                }
+                last_version = update.version;
+
+                if !send_status(&mut socket, &update).await {
+                    return;
+                }
+                if update.status.is_final() {
+                    close(&mut socket, 1000, "Swap finished").await;
+                    return;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                // Client messages are ignored; pongs are answered by axum
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+            _ = ping.tick() => {
+                if socket.send(Message::Ping(Default::default())).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// False once the client is gone
+async fn send_status(socket: &mut WebSocket, status: &SwapStatusResponse) -> bool {
+    let Ok(json) = serde_json::to_string(status) else {
+        return true;
+    };
+    socket.send(Message::Text(json.into())).await.is_ok()
+}
+
+async fn close(socket: &mut WebSocket, code: u16, reason: &str) {
+    let frame = CloseFrame { code, reason: reason.into() };
+    let _ = socket.send(Message::Close(Some(frame))).await;
+}
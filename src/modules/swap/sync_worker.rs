@@ -0,0 +1,159 @@
+use chrono::Utc;
+use sqlx::{MySql, Pool};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use super::crud::{SwapCrud, SyncStats};
+use super::schema::{SyncKind, SyncRunStatus};
+use crate::config::environment::SyncWorkerConfig;
+use crate::services::jobs::{self, JobKind, JobOutcome};
+use crate::services::outbox::Outbox;
+use crate::services::redis_cache::RedisService;
+use crate::services::trocador::TrocadorClient;
+
+// =============================================================================
+// SYNC WORKER
+// Periodically refreshes currencies and providers from Trocador and prunes
+// and re-caches the pairs listing. This is the only place listings are
+// synced; read paths serve the database and cache as they are. Runs are
+// serialized across instances with Redis locks, and every attempt is
+// recorded in `sync_runs`.
+// =============================================================================
+
+/// Spawn the periodic sync loop on the tokio runtime
+pub fn spawn_sync_worker(
+    pool: Pool<MySql>,
+    redis: RedisService,
+    outbox: Outbox,
+    trocador: TrocadorClient,
+    config: SyncWorkerConfig,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        tracing::info!(
+            "Sync worker started (interval {:?}, jitter {:?}, timeout {:?})",
+            config.interval,
+            config.jitter,
+            config.run_timeout
+        );
+
+        let crud = SwapCrud::new(pool, Some(redis.clone())).with_outbox(outbox);
+        let job = jobs::registry().register("sync", "Refresh currencies, providers and pairs", JobKind::Scheduled);
+        let mut consecutive_failures: u32 = 0;
+
+        loop {
+            let run = job.start();
+            let currencies = run_once(&crud, &trocador, &redis, &config, SyncKind::Currencies).await;
+            let providers = run_once(&crud, &trocador, &redis, &config, SyncKind::Providers).await;
+            let pairs = run_once(&crud, &trocador, &redis, &config, SyncKind::Pairs).await;
+            let statuses = [currencies, providers, pairs];
+            run.finish(job_outcome(&statuses), None);
+
+            if statuses.iter().all(|status| status.is_none_or(|s| s == SyncRunStatus::Success)) {
+                consecutive_failures = 0;
+            } else {
+                consecutive_failures = consecutive_failures.saturating_add(1);
+            }
+
+            let delay = next_delay(&config, consecutive_failures);
+            if consecutive_failures > 0 {
+                tracing::warn!(
+                    "Sync worker backing off for {:?} after {} consecutive failure(s)",
+                    delay,
+                    consecutive_failures
+                );
+            }
+
+            job.wait(delay).await;
+        }
+    })
+}
+
+/// Worst outcome across the kinds synced in one pass; details are in `sync_runs`
+fn job_outcome(statuses: &[Option<SyncRunStatus>]) -> JobOutcome {
+    if statuses.contains(&Some(SyncRunStatus::Timeout)) {
+        JobOutcome::Timeout
+    } else if statuses.contains(&Some(SyncRunStatus::Failed)) {
+        JobOutcome::Failed
+    } else if statuses.iter().all(Option::is_none) {
+        JobOutcome::Skipped
+    } else {
+        JobOutcome::Success
+    }
+}
+
+/// Run a single sync of the given kind and record it.
+/// Returns `None` when the run was skipped (lock held elsewhere).
+pub async fn run_once(
+    crud: &SwapCrud,
+    client: &TrocadorClient,
+    redis: &RedisService,
+    config: &SyncWorkerConfig,
+    kind: SyncKind,
+) -> Option<SyncRunStatus> {
+    let lock_key = match kind {
+        SyncKind::Currencies => "lock:sync_currencies",
+        SyncKind::Providers => "lock:sync_providers",
+        SyncKind::Pairs => "lock:sync_pairs",
+    };
+
+    // Another instance is already running this kind
+    if !matches!(redis.try_lock(lock_key, config.run_timeout.as_secs().max(1)).await, Ok(true)) {
+        tracing::debug!("Skipping {:?} sync, lock held elsewhere", kind);
+        return None;
+    }
+
+    let started_at = Utc::now();
+    let outcome = tokio::time::timeout(config.run_timeout, async {
+        match kind {
+            SyncKind::Currencies => crud.sync_currencies_from_trocador(client).await,
+            SyncKind::Providers => crud.sync_providers_from_trocador(client).await,
+            SyncKind::Pairs => crud.refresh_pairs().await,
+        }
+    })
+    .await;
+
+    let (status, stats, error) = match outcome {
+        Ok(Ok(stats)) => (SyncRunStatus::Success, stats, None),
+        Ok(Err(e)) => (SyncRunStatus::Failed, SyncStats::default(), Some(e.to_string())),
+        Err(_) => (
+            SyncRunStatus::Timeout,
+            SyncStats::default(),
+            Some(format!("Sync exceeded {:?}", config.run_timeout)),
+        ),
+    };
+
+    match &error {
+        None => tracing::info!(
+            "{:?} sync complete: {} fetched, {} changed",
+            kind,
+            stats.fetched,
+            stats.changed
+        ),
+        Some(e) => tracing::error!("{:?} sync failed: {}", kind, e),
+    }
+
+    if let Err(e) = crud.record_sync_run(kind, status, stats, started_at, error).await {
+        tracing::error!("Failed to record {:?} sync run: {}", kind, e);
+    }
+
+    Some(status)
+}
+
+/// Base interval doubled per consecutive failure (capped at max_backoff), plus random jitter
+fn next_delay(config: &SyncWorkerConfig, consecutive_failures: u32) -> Duration {
+    let base = if consecutive_failures == 0 {
+        config.interval
+    } else {
+        let factor = 2u32.saturating_pow(consecutive_failures.min(16));
+        config.interval.saturating_mul(factor).min(config.max_backoff)
+    };
+
+    let jitter_secs = config.jitter.as_secs();
+    let jitter = if jitter_secs > 0 {
+        Duration::from_secs(rand::random_range(0..=jitter_secs))
+    } else {
+        Duration::ZERO
+    };
+
+    base + jitter
+}
@@ -0,0 +1,244 @@
+//! Provider status callbacks.
+//!
+//! Trocador calls `POST /swap/webhook/trocador` whenever a trade changes
+//! status (the URL is passed on new_trade when TROCADOR_WEBHOOK_URL is set).
+//! Deliveries are signed with an HMAC-SHA256 of the body in `X-Signature`.
+//! Each one is stored in `swap_webhook_events`, accepted or not, so it can be
+//! replayed or inspected later. Deliveries with a bad signature keep only
+//! their metadata (time, source address, size, reason), never the body.
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{Extensions, HeaderMap, StatusCode},
+    Json,
+};
+use sqlx::{MySql, Pool};
+use std::sync::Arc;
+
+use crate::AppState;
+use crate::services::analytics::AnalyticsContext;
+use crate::services::security::verify_hmac_sha256;
+use super::controller::swap_crud;
+use super::crud::StatusUpdate;
+use super::schema::{
+    RefundReport, RefundSource, SwapErrorResponse, SwapStatus, SwapStatusResponse, SwapWebhookResponse,
+    TransactionReport, TrocadorWebhookPayload, WebhookOutcome,
+};
+use super::state::{StatusSource, SwapStateMachine, Transition};
+
+/// Header carrying the hex HMAC-SHA256 of the webhook body
+const SIGNATURE_HEADER: &str = "x-signature";
+
+const TROCADOR: &str = "trocador";
+
+/// Signature header as stored; longer values cannot be valid anyway
+const MAX_STORED_SIGNATURE_CHARS: usize = 128;
+
+/// One delivery, filled in as it is processed and then recorded
+struct WebhookDelivery {
+    provider: &'static str,
+    source_ip: Option<String>,
+    payload: String, // Only stored once the signature verifies
+    signature: Option<String>,
+    signature_valid: bool,
+    provider_swap_id: Option<String>,
+    swap_id: Option<String>,
+    provider_status: Option<String>,
+    mapped_status: Option<SwapStatus>,
+    outcome: WebhookOutcome,
+    error: Option<String>,
+}
+
+impl WebhookDelivery {
+    fn reject(&mut self, outcome: WebhookOutcome, status: StatusCode, error: String) -> (StatusCode, String) {
+        self.outcome = outcome;
+        self.error = Some(error.clone());
+        (status, error)
+    }
+}
+
+// =============================================================================
+// POST /swap/webhook/trocador - Trade status callbacks
+// =============================================================================
+
+#[utoipa::path(
+    post,
+    path = "/swap/webhook/trocador",
+    tag = "swap",
+    params(("X-Signature" = String, Header, description = "Hex HMAC-SHA256 of the body")),
+    request_body = TrocadorWebhookPayload,
+    responses(
+        (status = 200, description = "Delivery recorded", body = SwapWebhookResponse),
+        (status = 400, description = "Body did not parse", body = SwapErrorResponse),
+        (status = 401, description = "Bad signature", body = SwapErrorResponse),
+        (status = 404, description = "Webhooks not configured, or unknown trade", body = SwapErrorResponse),
+    ),
+)]
+pub async fn trocador_webhook(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    extensions: Extensions,
+    body: Bytes,
+) -> Result<Json<SwapWebhookResponse>, (StatusCode, Json<SwapErrorResponse>)> {
+    // Webhooks are disabled until TROCADOR_WEBHOOK_SECRET is set
+    let Some(secret) = &state.trocador_webhook_secret else {
+        return Err((StatusCode::NOT_FOUND, Json(SwapErrorResponse::new("Swap webhooks are not configured"))));
+    };
+
+    let signature = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+
+    let mut delivery = WebhookDelivery {
+        provider: TROCADOR,
+        source_ip: state.route_limiter.client_ip(&headers, &extensions),
+        payload: String::from_utf8_lossy(&body).into_owned(),
+        signature_valid: signature.as_deref().is_some_and(|s| verify_hmac_sha256(secret, &body, s)),
+        signature: signature.map(|s| s.chars().take(MAX_STORED_SIGNATURE_CHARS).collect()),
+        provider_swap_id: None,
+        swap_id: None,
+        provider_status: None,
+        mapped_status: None,
+        outcome: WebhookOutcome::Failed,
+        error: None,
+    };
+
+    let result = process_trocador(&state, &mut delivery).await;
+    let event_id = record_delivery(&state.db, &delivery).await;
+
+    match result {
+        Ok((swap_id, status)) => Ok(Json(SwapWebhookResponse {
+            event_id,
+            outcome: delivery.outcome,
+            swap_id,
+            status,
+        })),
+        Err((status, error)) => Err((status, Json(SwapErrorResponse::new(error)))),
+    }
+}
+
+async fn process_trocador(
+    state: &AppState,
+    delivery: &mut WebhookDelivery,
+) -> Result<(String, SwapStatus), (StatusCode, String)> {
+    if !delivery.signature_valid {
+        return Err(delivery.reject(
+            WebhookOutcome::Rejected,
+            StatusCode::UNAUTHORIZED,
+            "Invalid webhook signature".to_string(),
+        ));
+    }
+
+    let payload: TrocadorWebhookPayload = match serde_json::from_str(&delivery.payload) {
+        Ok(payload) => payload,
+        Err(e) => {
+            return Err(delivery.reject(
+                WebhookOutcome::Invalid,
+                StatusCode::BAD_REQUEST,
+                format!("Invalid webhook payload: {}", e),
+            ))
+        }
+    };
+    delivery.provider_swap_id = Some(payload.trade_id.clone());
+    delivery.provider_status = Some(payload.status.clone());
+
+    // Status changes are attributed to the swap's owner when tracked
+    let crud = swap_crud(state)
+        .with_analytics(state.analytics.clone(), AnalyticsContext::default())
+        .with_outbox(state.outbox.clone());
+
+    let failed = |delivery: &mut WebhookDelivery, e: super::crud::SwapError| {
+        delivery.reject(WebhookOutcome::Failed, StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+    };
+
+    let mut swap = match crud.find_swap_by_provider_swap_id(&payload.trade_id).await {
+        // Sandbox trades only exist in the mock provider, so a live webhook never applies
+        Ok(Some(swap)) if !swap.is_sandbox => swap,
+        Ok(_) => {
+            return Err(delivery.reject(
+                WebhookOutcome::UnknownSwap,
+                StatusCode::NOT_FOUND,
+                format!("No swap for trade {}", payload.trade_id),
+            ))
+        }
+        Err(e) => return Err(failed(delivery, e)),
+    };
+    delivery.swap_id = Some(swap.id.clone());
+
+    let new_status = SwapStateMachine::map_provider_status(&payload.status);
+    delivery.mapped_status = Some(new_status.clone());
+    if new_status == SwapStatus::Refunded {
+        crud.record_refund(&swap, &RefundReport::from(&payload), RefundSource::Webhook).await;
+    }
+    if SwapStateMachine::is_underpaid(&payload.status) && new_status != swap.status {
+        crud.check_underpaid_deposit(&swap);
+    }
+
+    // Deliveries can arrive out of order; never move a swap backwards, nor
+    // take transactions from a stale one
+    match SwapStateMachine::transition(&swap.status, &new_status, StatusSource::Webhook) {
+        Ok(Transition::Unchanged) => {
+            delivery.outcome = WebhookOutcome::Unchanged;
+            if crud.record_transactions(&mut swap, &TransactionReport::from(&payload)).await {
+                let response = SwapStatusResponse::from(swap.clone());
+                crud.cache_swap_status(&response).await;
+                crud.publish_swap_status(&response).await;
+            }
+            return Ok((swap.id, swap.status));
+        }
+        Err(_) => {
+            delivery.outcome = WebhookOutcome::Superseded;
+            return Ok((swap.id, swap.status));
+        }
+        Ok(_) => {
+            crud.record_transactions(&mut swap, &TransactionReport::from(&payload)).await;
+        }
+    }
+
+    let update = crud.apply_status_change(&swap, &new_status, payload.amount_to, StatusSource::Webhook).await;
+    let (outcome, current) = match update {
+        Ok(StatusUpdate::Applied(current)) => (WebhookOutcome::Applied, current),
+        Ok(StatusUpdate::Superseded(current) | StatusUpdate::Refused(current)) => (WebhookOutcome::Superseded, current),
+        Err(e) => return Err(failed(delivery, e)),
+    };
+    delivery.outcome = outcome;
+
+    let response = SwapStatusResponse::from(current);
+    crud.cache_swap_status(&response).await;
+    Ok((response.swap_id, response.status))
+}
+
+/// Store a delivery, without its body when the signature did not verify;
+/// failures are logged, never surfaced
+async fn record_delivery(pool: &Pool<MySql>, delivery: &WebhookDelivery) -> Option<u64> {
+    let result = sqlx::query(
+        "INSERT INTO swap_webhook_events
+            (provider, source_ip, provider_swap_id, swap_id, provider_status, mapped_status, signature,
+             signature_valid, outcome, error, payload_bytes, payload)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(delivery.provider)
+    .bind(&delivery.source_ip)
+    .bind(&delivery.provider_swap_id)
+    .bind(&delivery.swap_id)
+    .bind(&delivery.provider_status)
+    .bind(&delivery.mapped_status)
+    .bind(&delivery.signature)
+    .bind(delivery.signature_valid)
+    .bind(delivery.outcome)
+    .bind(&delivery.error)
+    .bind(delivery.payload.len() as u32)
+    .bind(delivery.signature_valid.then_some(&delivery.payload))
+    .execute(pool)
+    .await;
+
+    match result {
+        Ok(done) => Some(done.last_insert_id()),
+        Err(e) => {
+            tracing::warn!("Failed to record {} webhook delivery: {}", delivery.provider, e);
+            None
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use sqlx::{MySql, Pool};
+use tokio::task::JoinHandle;
+
+use super::crud::{SwapCrud, SwapError};
+use crate::config::environment::StatusPollerConfig;
+use crate::services::analytics::{Analytics, AnalyticsContext};
+use crate::services::jobs::{self, JobKind, JobOutcome};
+use crate::services::outbox::Outbox;
+use crate::services::redis_cache::RedisService;
+use crate::services::trocador::TrocadorClient;
+
+// =============================================================================
+// STATUS POLLER
+// Periodically asks Trocador about in-flight swaps so they advance without
+// anyone calling GET /swap/{id}, and expires waiting swaps that never got a
+// deposit. High-value swaps are polled more often and alerted on when they
+// stall. Status writes go through the same compare-and-swap path as the
+// status endpoint and webhooks, so the three never overwrite each other.
+// =============================================================================
+
+const LOCK_KEY: &str = "lock:swap_status_poller";
+
+/// Counts from a single poll pass
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PollStats {
+    pub polled: usize,
+    pub changed: usize,
+    pub failed: usize,
+    pub expired: u64,
+    pub stalled: u64, // High-value swaps newly alerted on as stalled
+}
+
+/// Spawn the periodic status poll loop on the tokio runtime
+pub fn spawn_status_poller(
+    pool: Pool<MySql>,
+    redis: RedisService,
+    outbox: Outbox,
+    analytics: Analytics,
+    trocador: Option<TrocadorClient>,
+    config: StatusPollerConfig,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        tracing::info!(
+            "Status poller started (interval {:?}, batch {}, abandon after {:?})",
+            config.interval,
+            config.batch_size,
+            config.abandon_after
+        );
+
+        let crud = SwapCrud::new(pool, Some(redis.clone()))
+            .with_analytics(analytics, AnalyticsContext::default())
+            .with_outbox(outbox)
+            .with_trocador(trocador)
+            .with_high_value(config.high_value.clone());
+        let job = jobs::registry().register(
+            "status_poller",
+            "Advance in-flight swaps from Trocador and expire abandoned ones",
+            JobKind::Scheduled,
+        );
+
+        loop {
+            let run = job.start();
+            match tokio::time::timeout(config.run_timeout, run_once(&crud, &redis, &config)).await {
+                Ok(Ok(Some(_))) => run.finish(JobOutcome::Success, None),
+                Ok(Ok(None)) => run.finish(JobOutcome::Skipped, None),
+                Ok(Err(e)) => {
+                    tracing::error!("Status poll failed: {}", e);
+                    run.finish(JobOutcome::Failed, Some(e.to_string()));
+                }
+                Err(_) => {
+                    let error = format!("Status poll exceeded {:?}", config.run_timeout);
+                    tracing::error!("{}", error);
+                    run.finish(JobOutcome::Timeout, Some(error));
+                }
+            }
+
+            job.wait(config.interval).await;
+        }
+    })
+}
+
+/// Run a single poll pass. Returns `None` when another instance holds the lock.
+pub async fn run_once(
+    crud: &SwapCrud,
+    redis: &RedisService,
+    config: &StatusPollerConfig,
+) -> Result<Option<PollStats>, SwapError> {
+    if !matches!(redis.try_lock(LOCK_KEY, config.run_timeout.as_secs().max(1)).await, Ok(true)) {
+        tracing::debug!("Skipping status poll, lock held elsewhere");
+        return Ok(None);
+    }
+
+    let result = poll_and_expire(crud, config).await;
+    if let Err(e) = redis.delete(LOCK_KEY).await {
+        tracing::warn!("Failed to release status poller lock: {}", e);
+    }
+    result.map(Some)
+}
+
+async fn poll_and_expire(crud: &SwapCrud, config: &StatusPollerConfig) -> Result<PollStats, SwapError> {
+    let mut stats = PollStats::default();
+
+    // Poll first: a swap the provider reports as funded must not be expired
+    for (swap_id, status) in crud.swaps_due_for_poll(config.min_poll_age, config.high_value.min_poll_age, config.batch_size).await? {
+        stats.polled += 1;
+        match crud.poll_swap_status(&swap_id).await {
+            Ok(response) if response.status != status => stats.changed += 1,
+            Ok(_) => {}
+            Err(e) => {
+                stats.failed += 1;
+                tracing::warn!("Failed to poll swap {}: {}", swap_id, e);
+            }
+        }
+    }
+
+    stats.expired = crud.expire_abandoned_swaps(config.abandon_after, config.batch_size).await?;
+    stats.stalled = crud
+        .report_stalled_high_value_swaps(config.high_value.stall_after, config.batch_size)
+        .await?;
+
+    if stats.polled > 0 || stats.expired > 0 || stats.stalled > 0 {
+        tracing::info!(
+            "Status poll complete: {} polled, {} changed, {} failed, {} expired, {} stalled",
+            stats.polled,
+            stats.changed,
+            stats.failed,
+            stats.expired,
+            stats.stalled
+        );
+    }
+
+    Ok(stats)
+}
@@ -0,0 +1,129 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::sync::Arc;
+
+use crate::AppState;
+use super::crud::WidgetError;
+use super::interface::{CorsDenied, WidgetClient};
+use crate::modules::affiliate::crud::AffiliateCrud;
+use crate::modules::swap::controller::swap_crud;
+use crate::modules::swap::schema::{CreateSwapRequest, RatesQuery, RatesResponse, SwapErrorResponse, SwapStatusResponse};
+use crate::services::analytics::AnalyticsContext;
+use crate::services::maintenance::{MaintenanceService, WritesAllowed};
+use crate::services::routing::ClientCountry;
+
+// =============================================================================
+// ERROR RESPONSES
+// =============================================================================
+
+impl IntoResponse for WidgetError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            WidgetError::Unauthorized => StatusCode::UNAUTHORIZED,
+            WidgetError::OriginNotAllowed => StatusCode::FORBIDDEN,
+            WidgetError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            WidgetError::KeyNotFound => StatusCode::NOT_FOUND,
+            WidgetError::InvalidInput(_) => StatusCode::BAD_REQUEST,
+            WidgetError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        let body = Json(SwapErrorResponse::with_code(self.to_string(), self.error_code()));
+
+        match self {
+            WidgetError::RateLimited(retry_after) => {
+                (status, [(header::RETRY_AFTER, retry_after.to_string())], body).into_response()
+            }
+            WidgetError::Unauthorized | WidgetError::OriginNotAllowed => {
+                let mut response = (status, body).into_response();
+                response.extensions_mut().insert(CorsDenied);
+                response
+            }
+            _ => (status, body).into_response(),
+        }
+    }
+}
+
+// =============================================================================
+// GET /widget/rates - Live rates under the key's brand
+// =============================================================================
+
+pub async fn get_rates(
+    State(state): State<Arc<AppState>>,
+    widget: WidgetClient,
+    context: AnalyticsContext,
+    ClientCountry(country): ClientCountry,
+    Query(query): Query<RatesQuery>,
+) -> Result<Json<RatesResponse>, Response> {
+    let crud = swap_crud(&state)
+        .with_analytics(state.analytics.clone(), context)
+        .with_brand(widget.brand)
+        .with_country(country);
+
+    let response = crud.get_rates_optimized(&query).await.map_err(IntoResponse::into_response)?;
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// POST /widget/swap - Create a swap credited to the key's affiliate
+// =============================================================================
+
+pub async fn create_swap(
+    State(state): State<Arc<AppState>>,
+    widget: WidgetClient,
+    _writes: WritesAllowed,
+    context: AnalyticsContext,
+    ClientCountry(country): ClientCountry,
+    Json(payload): Json<CreateSwapRequest>,
+) -> Result<Response, Response> {
+    let affiliate = match widget.key.affiliate.as_deref() {
+        Some(ref_code) => AffiliateCrud::new(state.db.clone()).referrer(ref_code, &widget.brand.tenant).await,
+        None => None,
+    };
+    let crud = swap_crud(&state)
+        .with_analytics(state.analytics.clone(), context)
+        .with_outbox(state.outbox.clone())
+        .with_brand(widget.brand)
+        .with_country(country)
+        .with_affiliate(affiliate);
+
+    // Dry run: same checks against a fresh quote, nothing is created
+    if payload.dry_run {
+        let preview = crud.preview_swap(&payload).await.map_err(IntoResponse::into_response)?;
+        return Ok((StatusCode::OK, Json(preview)).into_response());
+    }
+
+    let response = crud.create_swap(&payload, None).await.map_err(IntoResponse::into_response)?;
+    tracing::info!("Widget {} created swap {}", widget.key.public_key, response.swap_id);
+
+    Ok((StatusCode::CREATED, Json(response)).into_response())
+}
+
+// =============================================================================
+// GET /widget/swap/{id} - Status of a swap
+// =============================================================================
+
+pub async fn get_swap_status(
+    State(state): State<Arc<AppState>>,
+    widget: WidgetClient,
+    context: AnalyticsContext,
+    Path(swap_id): Path<String>,
+) -> Result<Json<SwapStatusResponse>, Response> {
+    let crud = swap_crud(&state)
+        .with_analytics(state.analytics.clone(), context)
+        .with_outbox(state.outbox.clone())
+        .with_tenant(widget.brand.tenant);
+
+    // During maintenance serve what we have instead of polling the provider
+    let maintenance = MaintenanceService::new(state.db.clone(), state.redis.clone()).current().await;
+    let response = if maintenance.enabled {
+        crud.get_stored_swap_status(&swap_id).await
+    } else {
+        crud.get_swap_status(&swap_id).await
+    };
+
+    Ok(Json(response.map_err(IntoResponse::into_response)?))
+}
@@ -0,0 +1,250 @@
+use chrono::{DateTime, Utc};
+use sqlx::{MySql, Pool};
+
+use super::model::WidgetKey;
+use super::schema::{WidgetKeyRequest, WIDGET_KEY_PREFIX};
+use crate::modules::affiliate::crud::AffiliateCrud;
+use crate::services::branding::BrandRegistry;
+use crate::services::redis_cache::RedisService;
+
+/// How long an instance serves a key from Redis before reading it again
+const CACHE_TTL_SECS: u64 = 60;
+
+// =============================================================================
+// WIDGET ERROR
+// =============================================================================
+
+#[derive(Debug)]
+pub enum WidgetError {
+    Unauthorized, // Missing, unknown or inactive ?key=
+    OriginNotAllowed,
+    RateLimited(u64), // Seconds until the visitor may retry
+    KeyNotFound,
+    InvalidInput(String),
+    DatabaseError(String),
+}
+
+impl std::fmt::Display for WidgetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WidgetError::Unauthorized => write!(f, "A valid widget key is required"),
+            WidgetError::OriginNotAllowed => write!(f, "This widget key may not be used from this origin"),
+            WidgetError::RateLimited(_) => write!(f, "Too many requests"),
+            WidgetError::KeyNotFound => write!(f, "Widget key not found"),
+            WidgetError::InvalidInput(reason) => write!(f, "Invalid input: {}", reason),
+            WidgetError::DatabaseError(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl WidgetError {
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            WidgetError::Unauthorized => "INVALID_WIDGET_KEY",
+            WidgetError::OriginNotAllowed => "ORIGIN_NOT_ALLOWED",
+            WidgetError::RateLimited(_) => "RATE_LIMITED",
+            WidgetError::KeyNotFound => "WIDGET_KEY_NOT_FOUND",
+            WidgetError::InvalidInput(_) => "INVALID_INPUT",
+            WidgetError::DatabaseError(_) => "INTERNAL_ERROR",
+        }
+    }
+}
+
+impl From<sqlx::Error> for WidgetError {
+    fn from(err: sqlx::Error) -> Self {
+        WidgetError::DatabaseError(err.to_string())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct WidgetKeyRow {
+    public_key: String,
+    name: String,
+    brand: Option<String>,
+    affiliate: Option<String>,
+    allowed_origins: String,
+    rate_limit_per_minute: u32,
+    is_active: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<WidgetKeyRow> for WidgetKey {
+    fn from(row: WidgetKeyRow) -> Self {
+        Self {
+            public_key: row.public_key,
+            name: row.name,
+            brand: row.brand,
+            affiliate: row.affiliate,
+            allowed_origins: serde_json::from_str(&row.allowed_origins).unwrap_or_default(),
+            rate_limit_per_minute: row.rate_limit_per_minute,
+            is_active: row.is_active,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+// =============================================================================
+// WIDGET CRUD
+// =============================================================================
+
+pub struct WidgetCrud {
+    pool: Pool<MySql>,
+    redis: Option<RedisService>,
+}
+
+impl WidgetCrud {
+    pub fn new(pool: Pool<MySql>, redis: Option<RedisService>) -> Self {
+        Self { pool, redis }
+    }
+
+    /// The active key `public_key`, from Redis when cached
+    pub async fn find_active(&self, public_key: &str) -> Result<WidgetKey, WidgetError> {
+        if !public_key.starts_with(WIDGET_KEY_PREFIX) {
+            return Err(WidgetError::Unauthorized);
+        }
+
+        let cache_key = format!("widget_key:{}", public_key);
+        if let Some(redis) = &self.redis {
+            if let Ok(Some(key)) = redis.get_json::<WidgetKey>(&cache_key).await {
+                return Ok(key);
+            }
+        }
+
+        let key = self.find(public_key).await?.filter(|k| k.is_active).ok_or(WidgetError::Unauthorized)?;
+
+        if let Some(redis) = &self.redis {
+            let _ = redis.set_json(&cache_key, &key, CACHE_TTL_SECS).await;
+        }
+
+        Ok(key)
+    }
+
+    pub async fn find(&self, public_key: &str) -> Result<Option<WidgetKey>, WidgetError> {
+        let row = sqlx::query_as::<_, WidgetKeyRow>(&format!("{} WHERE public_key = ?", WIDGET_KEY_SELECT))
+            .bind(public_key)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(WidgetKey::from))
+    }
+
+    /// Every key, newest first
+    pub async fn all(&self) -> Result<Vec<WidgetKey>, WidgetError> {
+        Ok(sqlx::query_as::<_, WidgetKeyRow>(&format!("{} ORDER BY id DESC", WIDGET_KEY_SELECT))
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(WidgetKey::from)
+            .collect())
+    }
+
+    /// Issue a new key
+    pub async fn create(&self, request: &WidgetKeyRequest) -> Result<WidgetKey, WidgetError> {
+        self.check_request(request).await?;
+
+        let public_key = format!(
+            "{}{}",
+            WIDGET_KEY_PREFIX,
+            rand::random::<[u8; 16]>().iter().map(|b| format!("{:02x}", b)).collect::<String>()
+        );
+
+        sqlx::query(
+            r#"
+            INSERT INTO widget_keys (public_key, name, brand, affiliate, allowed_origins, rate_limit_per_minute, is_active)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&public_key)
+        .bind(request.name.trim())
+        .bind(&request.brand)
+        .bind(&request.affiliate)
+        .bind(serde_json::to_string(&request.allowed_origins).unwrap_or_else(|_| "[]".to_string()))
+        .bind(request.rate_limit_per_minute)
+        .bind(request.is_active)
+        .execute(&self.pool)
+        .await?;
+
+        self.find(&public_key).await?.ok_or(WidgetError::KeyNotFound)
+    }
+
+    /// Replace a key's settings; the key itself stays the same
+    pub async fn update(&self, public_key: &str, request: &WidgetKeyRequest) -> Result<WidgetKey, WidgetError> {
+        self.check_request(request).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE widget_keys
+            SET name = ?, brand = ?, affiliate = ?, allowed_origins = ?, rate_limit_per_minute = ?, is_active = ?
+            WHERE public_key = ?
+            "#,
+        )
+        .bind(request.name.trim())
+        .bind(&request.brand)
+        .bind(&request.affiliate)
+        .bind(serde_json::to_string(&request.allowed_origins).unwrap_or_else(|_| "[]".to_string()))
+        .bind(request.rate_limit_per_minute)
+        .bind(request.is_active)
+        .bind(public_key)
+        .execute(&self.pool)
+        .await?;
+
+        self.invalidate(public_key).await;
+
+        self.find(public_key).await?.ok_or(WidgetError::KeyNotFound)
+    }
+
+    /// Revoke a key, returning it
+    pub async fn delete(&self, public_key: &str) -> Result<WidgetKey, WidgetError> {
+        let existing = self.find(public_key).await?.ok_or(WidgetError::KeyNotFound)?;
+
+        sqlx::query("DELETE FROM widget_keys WHERE public_key = ?")
+            .bind(public_key)
+            .execute(&self.pool)
+            .await?;
+
+        self.invalidate(public_key).await;
+        Ok(existing)
+    }
+
+    /// Field rules, plus the brand and affiliate must exist
+    async fn check_request(&self, request: &WidgetKeyRequest) -> Result<(), WidgetError> {
+        request.check_rules().map_err(WidgetError::InvalidInput)?;
+
+        if let Some(slug) = &request.brand {
+            let brands = BrandRegistry::new(self.pool.clone(), self.redis.clone()).all().await?;
+            if !brands.iter().any(|b| &b.slug == slug) {
+                return Err(WidgetError::InvalidInput(format!("unknown brand '{}'", slug)));
+            }
+        }
+
+        if let Some(ref_code) = &request.affiliate {
+            let affiliate = AffiliateCrud::new(self.pool.clone())
+                .find(ref_code)
+                .await
+                .map_err(|e| WidgetError::DatabaseError(e.to_string()))?;
+            if affiliate.is_none() {
+                return Err(WidgetError::InvalidInput(format!("unknown affiliate '{}'", ref_code)));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn invalidate(&self, public_key: &str) {
+        if let Some(redis) = &self.redis {
+            if let Err(e) = redis.delete(&format!("widget_key:{}", public_key)).await {
+                // Other instances keep the old settings until the cached copy expires
+                tracing::warn!("Failed to invalidate widget key cache: {}", e);
+            }
+        }
+    }
+}
+
+const WIDGET_KEY_SELECT: &str = r#"
+    SELECT public_key, name, brand, affiliate,
+           CAST(allowed_origins AS CHAR) AS allowed_origins,
+           rate_limit_per_minute, is_active, created_at, updated_at
+    FROM widget_keys
+"#;
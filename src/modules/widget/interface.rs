@@ -0,0 +1,155 @@
+use axum::{
+    body::Body,
+    extract::{FromRef, FromRequestParts, Query, State},
+    http::{header, request::Parts, HeaderMap, HeaderValue, Method, Request, StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::AppState;
+use super::crud::{WidgetCrud, WidgetError};
+use super::model::WidgetKey;
+use super::schema::WidgetKeyQuery;
+use crate::services::branding::{Brand, BrandRegistry};
+use crate::services::metrics::metrics;
+use crate::services::rate_limiter::DistributedRateLimiter;
+
+/// Path prefix the widget routes are mounted under
+pub const WIDGET_PATH_PREFIX: &str = "/widget/";
+
+/// How long browsers may reuse a widget preflight answer
+const PREFLIGHT_MAX_AGE_SECS: u64 = 600;
+
+/// Set on responses refused for their key or origin; `widget_cors` sends
+/// them without Access-Control-Allow-Origin so the page cannot read them
+#[derive(Debug, Clone, Copy)]
+pub struct CorsDenied;
+
+// =============================================================================
+// EXTRACTOR
+// =============================================================================
+
+/// A request from a page allowed to use the widget key in `?key=`, within
+/// the key's per-visitor rate limit
+pub struct WidgetClient {
+    pub key: WidgetKey,
+    pub brand: Brand, // The key's brand, or the deployment's own
+}
+
+impl<S> FromRequestParts<S> for WidgetClient
+where
+    Arc<AppState>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = WidgetError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let state = Arc::from_ref(state);
+        let key = authorize(&state, &parts.uri, &parts.headers).await?;
+
+        // Each visitor of the partner's page gets their own bucket
+        let visitor = state
+            .route_limiter
+            .client_ip(&parts.headers, &parts.extensions)
+            .or_else(|| origin(&parts.headers).map(str::to_string))
+            .unwrap_or_default();
+        let limiter = DistributedRateLimiter::new(state.redis.clone())
+            .with_limit(key.rate_limit_per_minute, key.rate_limit_per_minute);
+        let bucket = format!("widget:{}:{}", key.public_key, visitor);
+        match limiter.try_acquire(&bucket, 1).await {
+            Ok(true) => {}
+            Ok(false) => {
+                metrics().route_rate_limited.inc("/widget");
+                let wait = limiter.get_wait_time(&bucket).await.unwrap_or(Duration::from_secs(1));
+                return Err(WidgetError::RateLimited(wait.as_secs_f64().ceil().max(1.0) as u64));
+            }
+            Err(e) => tracing::warn!("Widget rate limit check failed for {}: {}", bucket, e),
+        }
+
+        let brand = match &key.brand {
+            Some(slug) => BrandRegistry::new(state.db.clone(), Some(state.redis.clone()))
+                .all()
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .find(|b| &b.slug == slug),
+            None => None,
+        };
+        let brand = brand.unwrap_or_else(|| Brand {
+            tenant: state.tenant.clone(),
+            ..Brand::from_config(&state.branding)
+        });
+
+        Ok(WidgetClient { key, brand })
+    }
+}
+
+/// The active key named in the `?key=` of `uri`, if the request's Origin may use it
+async fn authorize(state: &AppState, uri: &Uri, headers: &HeaderMap) -> Result<WidgetKey, WidgetError> {
+    let Query(WidgetKeyQuery { key }) = Query::try_from_uri(uri).map_err(|_| WidgetError::Unauthorized)?;
+    let public_key = key.as_deref().map(str::trim).filter(|k| !k.is_empty()).ok_or(WidgetError::Unauthorized)?;
+
+    let key = WidgetCrud::new(state.db.clone(), Some(state.redis.clone())).find_active(public_key).await?;
+
+    // Widgets run in browsers, which always send Origin on these requests
+    match origin(headers) {
+        Some(origin) if key.allows_origin(origin) => Ok(key),
+        _ => Err(WidgetError::OriginNotAllowed),
+    }
+}
+
+fn origin(headers: &HeaderMap) -> Option<&str> {
+    headers.get(header::ORIGIN).and_then(|v| v.to_str().ok()).filter(|v| !v.is_empty())
+}
+
+// =============================================================================
+// CORS
+// =============================================================================
+
+/// CORS for /widget/*: only origins allowed by the request's key get an
+/// Access-Control-Allow-Origin. Runs outside the app-wide permissive CORS
+/// layer so its headers win; other paths pass through untouched.
+pub async fn widget_cors(State(state): State<Arc<AppState>>, request: Request<Body>, next: Next) -> Response {
+    if !request.uri().path().starts_with(WIDGET_PATH_PREFIX) {
+        return next.run(request).await;
+    }
+
+    let origin = origin(request.headers()).and_then(|o| HeaderValue::from_str(o).ok());
+    let is_preflight = request.method() == Method::OPTIONS
+        && request.headers().contains_key(header::ACCESS_CONTROL_REQUEST_METHOD);
+
+    if is_preflight {
+        let allowed = authorize(&state, request.uri(), request.headers()).await.is_ok();
+        let mut response = StatusCode::NO_CONTENT.into_response();
+        let headers = response.headers_mut();
+        headers.insert(header::VARY, HeaderValue::from_static("origin"));
+        match origin.filter(|_| allowed) {
+            Some(origin) => {
+                headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+                headers.insert(header::ACCESS_CONTROL_ALLOW_METHODS, HeaderValue::from_static("GET, POST"));
+                headers.insert(header::ACCESS_CONTROL_ALLOW_HEADERS, HeaderValue::from_static("content-type"));
+                headers.insert(header::ACCESS_CONTROL_MAX_AGE, HeaderValue::from(PREFLIGHT_MAX_AGE_SECS));
+            }
+            None => *response.status_mut() = StatusCode::FORBIDDEN,
+        }
+        return response;
+    }
+
+    let mut response = next.run(request).await;
+    let denied = response.extensions().get::<CorsDenied>().is_some();
+    let headers = response.headers_mut();
+    headers.remove(header::ACCESS_CONTROL_ALLOW_CREDENTIALS);
+    headers.remove(header::ACCESS_CONTROL_EXPOSE_HEADERS);
+    headers.insert(header::VARY, HeaderValue::from_static("origin"));
+    match origin.filter(|_| !denied) {
+        Some(origin) => {
+            headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+        }
+        None => {
+            headers.remove(header::ACCESS_CONTROL_ALLOW_ORIGIN);
+        }
+    }
+    response
+}
@@ -0,0 +1,9 @@
+pub mod schema;
+pub mod model;
+pub mod crud;
+pub mod interface;
+pub mod controller;
+pub mod routes;
+
+pub use interface::widget_cors;
+pub use routes::widget_routes;
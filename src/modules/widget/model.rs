@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// =============================================================================
+// WIDGET KEY
+// =============================================================================
+
+/// Public key of an embedded swap widget. Not a secret: it only works from
+/// `allowed_origins`, at `rate_limit_per_minute` per visitor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WidgetKey {
+    pub public_key: String,
+    pub name: String,
+    pub brand: Option<String>,     // Swaps are created under this brand; None uses the deployment's own
+    pub affiliate: Option<String>, // Ref code credited with the widget's swaps
+    pub allowed_origins: Vec<String>,
+    pub rate_limit_per_minute: u32,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WidgetKey {
+    /// Whether a page served from `origin` may use this key. Entries match
+    /// exactly, or any subdomain when written as `https://*.example.com`.
+    pub fn allows_origin(&self, origin: &str) -> bool {
+        let origin = origin.trim().trim_end_matches('/').to_lowercase();
+        self.allowed_origins.iter().any(|allowed| origin_matches(allowed, &origin))
+    }
+}
+
+fn origin_matches(allowed: &str, origin: &str) -> bool {
+    let Some((scheme, host)) = allowed.split_once("://*.") else {
+        return allowed == origin;
+    };
+
+    origin
+        .strip_prefix(scheme)
+        .and_then(|rest| rest.strip_prefix("://"))
+        .and_then(|rest| rest.strip_suffix(host))
+        .is_some_and(|subdomain| subdomain.len() > 1 && subdomain.ends_with('.'))
+}
@@ -0,0 +1,13 @@
+use axum::{routing::{get, post}, Router};
+use std::sync::Arc;
+
+use crate::AppState;
+use super::controller::{create_swap, get_rates, get_swap_status};
+
+/// The swap box partners embed, mounted under /widget; every route takes `?key=`
+pub fn widget_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/rates", get(get_rates))
+        .route("/swap", post(create_swap))
+        .route("/swap/{id}", get(get_swap_status))
+}
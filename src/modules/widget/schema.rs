@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+
+/// Requests per minute per visitor when a key sets no limit
+pub const DEFAULT_WIDGET_RATE_LIMIT: u32 = 20;
+
+/// Highest per-visitor limit a key may get
+pub const MAX_WIDGET_RATE_LIMIT: u32 = 120;
+
+/// Most origins one key may be used from
+pub const MAX_ALLOWED_ORIGINS: usize = 20;
+
+/// Prefix of generated widget keys
+pub const WIDGET_KEY_PREFIX: &str = "wk_";
+
+// =============================================================================
+// WIDGET REQUESTS
+// =============================================================================
+
+/// `?key=` on every /widget request. The key travels in the query rather
+/// than a header so CORS preflights carry it too.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct WidgetKeyQuery {
+    #[serde(default)]
+    pub key: Option<String>,
+}
+
+// =============================================================================
+// ADMIN
+// =============================================================================
+
+/// POST /admin/widget-keys and PUT /admin/widget-keys/{key}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WidgetKeyRequest {
+    pub name: String,
+    #[serde(default)]
+    pub brand: Option<String>,
+    #[serde(default)]
+    pub affiliate: Option<String>,
+    pub allowed_origins: Vec<String>,
+    #[serde(default = "default_rate_limit")]
+    pub rate_limit_per_minute: u32,
+    #[serde(default = "default_active")]
+    pub is_active: bool,
+}
+
+fn default_rate_limit() -> u32 {
+    DEFAULT_WIDGET_RATE_LIMIT
+}
+
+fn default_active() -> bool {
+    true
+}
+
+impl WidgetKeyRequest {
+    pub fn check_rules(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("name is required".to_string());
+        }
+        if self.allowed_origins.is_empty() || self.allowed_origins.len() > MAX_ALLOWED_ORIGINS {
+            return Err(format!("allowed_origins needs 1-{} origins", MAX_ALLOWED_ORIGINS));
+        }
+        if let Some(origin) = self.allowed_origins.iter().find(|o| check_origin(o).is_err()) {
+            return Err(format!(
+                "origin '{}' must be a lowercase scheme://host[:port], optionally with a leading '*.' on the host",
+                origin
+            ));
+        }
+        if !(1..=MAX_WIDGET_RATE_LIMIT).contains(&self.rate_limit_per_minute) {
+            return Err(format!("rate_limit_per_minute must be between 1 and {}", MAX_WIDGET_RATE_LIMIT));
+        }
+        Ok(())
+    }
+}
+
+/// An allowed origin: `http(s)://host[:port]` in lowercase, no path, with
+/// `*.` in front of the host to allow its subdomains
+pub fn check_origin(origin: &str) -> Result<(), String> {
+    let host = origin
+        .strip_prefix("https://")
+        .or_else(|| origin.strip_prefix("http://"))
+        .ok_or_else(|| "scheme must be http or https".to_string())?;
+    let host = host.strip_prefix("*.").unwrap_or(host);
+    let name = match host.rsplit_once(':') {
+        Some((name, port)) if !port.is_empty() && port.chars().all(|c| c.is_ascii_digit()) => name,
+        _ => host,
+    };
+
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && !name.ends_with('.')
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '.');
+    if valid {
+        Ok(())
+    } else {
+        Err("host must be a lowercase hostname".to_string())
+    }
+}
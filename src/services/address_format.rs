@@ -0,0 +1,410 @@
+//! Per-network address format registry.
+//!
+//! Rows in `address_formats` describe what an address and memo look like for a
+//! (ticker, network) pair and how to build a payment URI for it; a ticker of
+//! `*` covers every token on the network. Address validation rejects addresses
+//! that fail these rules before asking the provider, so supporting a new
+//! network is a row managed through `/admin/address-formats`, not a release.
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use crate::config::DbPool;
+use crate::services::address_validator::{base58check_valid, bech32_valid, eip55_valid};
+use crate::services::redis_cache::RedisService;
+
+const CACHE_KEY: &str = "address_formats:all";
+const CACHE_TTL_SECS: u64 = 600;
+
+/// Ticker that matches every token on a network
+pub const ANY_TICKER: &str = "*";
+
+/// Memo length enforced when no format is registered for a currency
+pub const DEFAULT_MEMO_MAX_LENGTH: u32 = 256;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksumAlgorithm {
+    #[default]
+    None,
+    Base58check,
+    Bech32,              // Bech32 or bech32m
+    Base58checkOrBech32, // Bitcoin-style chains with legacy and segwit addresses
+    Eip55,               // All-lowercase / all-uppercase hex carries no checksum and is accepted
+}
+
+impl ChecksumAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::None => "none",
+            ChecksumAlgorithm::Base58check => "base58check",
+            ChecksumAlgorithm::Bech32 => "bech32",
+            ChecksumAlgorithm::Base58checkOrBech32 => "base58check_or_bech32",
+            ChecksumAlgorithm::Eip55 => "eip55",
+        }
+    }
+
+    fn from_db(value: &str) -> Self {
+        match value {
+            "base58check" => ChecksumAlgorithm::Base58check,
+            "bech32" => ChecksumAlgorithm::Bech32,
+            "base58check_or_bech32" => ChecksumAlgorithm::Base58checkOrBech32,
+            "eip55" => ChecksumAlgorithm::Eip55,
+            _ => ChecksumAlgorithm::None,
+        }
+    }
+
+    fn verify(&self, address: &str) -> bool {
+        match self {
+            ChecksumAlgorithm::None => true,
+            ChecksumAlgorithm::Base58check => base58check_valid(address),
+            ChecksumAlgorithm::Bech32 => bech32_valid(address),
+            ChecksumAlgorithm::Base58checkOrBech32 => bech32_valid(address) || base58check_valid(address),
+            ChecksumAlgorithm::Eip55 => eip55_valid(address),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoFormat {
+    #[default]
+    Text,
+    Integer,
+    IntegerOrText, // A numeric ID up to memo_max_value, or text up to memo_max_length
+}
+
+impl MemoFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MemoFormat::Text => "text",
+            MemoFormat::Integer => "integer",
+            MemoFormat::IntegerOrText => "integer_or_text",
+        }
+    }
+
+    fn from_db(value: &str) -> Self {
+        match value {
+            "integer" => MemoFormat::Integer,
+            "integer_or_text" => MemoFormat::IntegerOrText,
+            _ => MemoFormat::Text,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressFormat {
+    pub ticker: String,
+    pub network: String,
+    pub address_regex: String, // Anchored: must match the whole address
+    pub checksum: ChecksumAlgorithm,
+    pub memo_name: Option<String>,
+    pub memo_required: bool,
+    pub memo_format: MemoFormat,
+    pub memo_max_length: Option<u32>,
+    pub memo_max_value: Option<u64>,
+    pub uri_scheme: Option<String>,
+    pub uri_memo_param: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl AddressFormat {
+    /// Reject rules that could never match or would break URIs
+    pub fn check_rules(&self) -> Result<(), String> {
+        if self.ticker.trim().is_empty() || self.network.trim().is_empty() {
+            return Err("ticker and network are required".to_string());
+        }
+        self.compiled_regex().map_err(|e| format!("invalid address_regex: {}", e))?;
+
+        let valid_token = |value: &str| {
+            !value.is_empty()
+                && value.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+                && value.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.' | '_'))
+        };
+        if self.uri_scheme.as_deref().is_some_and(|s| !valid_token(s)) {
+            return Err("uri_scheme must start with a letter and contain only letters, digits, '+', '-' or '.'".to_string());
+        }
+        if self.uri_memo_param.as_deref().is_some_and(|p| !valid_token(p)) {
+            return Err("uri_memo_param must be a plain query parameter name".to_string());
+        }
+
+        if self.memo_format != MemoFormat::Text && self.memo_max_value.is_none() {
+            return Err("memo_max_value is required for numeric memo formats".to_string());
+        }
+        if self.memo_format != MemoFormat::Integer && self.memo_max_length == Some(0) {
+            return Err("memo_max_length must be at least 1".to_string());
+        }
+
+        Ok(())
+    }
+
+    fn compiled_regex(&self) -> Result<Regex, regex::Error> {
+        Regex::new(&format!("^(?:{})$", self.address_regex))
+    }
+
+    /// Check the address shape and checksum; `Err` carries the reason
+    pub fn check_address(&self, address: &str) -> Result<(), String> {
+        let regex = self
+            .compiled_regex()
+            .map_err(|e| format!("address format for {} on {} is broken: {}", self.ticker, self.network, e))?;
+
+        if !regex.is_match(address) {
+            return Err(format!("not a valid {} address", self.network));
+        }
+        if !self.checksum.verify(address) {
+            return Err(format!("{} checksum mismatch", self.checksum.as_str()));
+        }
+
+        Ok(())
+    }
+
+    /// Check a memo against this network's rules; `Err` is shown to the client
+    pub fn check_memo(&self, memo: &str) -> Result<(), String> {
+        let name = self.memo_name.as_deref().unwrap_or("memo").to_lowercase();
+        let max_length = self.memo_max_length.unwrap_or(DEFAULT_MEMO_MAX_LENGTH) as usize;
+        let max_value = self.memo_max_value.unwrap_or(u64::MAX);
+        let numeric_ok = memo.parse::<u64>().is_ok_and(|n| n <= max_value);
+
+        match self.memo_format {
+            MemoFormat::Integer if !numeric_ok => {
+                Err(format!("{} must be a number between 0 and {}", name, max_value))
+            }
+            MemoFormat::IntegerOrText if !numeric_ok && memo.len() > max_length => {
+                Err(format!("text {} must be at most {} bytes", name, max_length))
+            }
+            MemoFormat::Text if memo.len() > max_length => {
+                Err(format!("{} must be at most {} characters", name, max_length))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// BIP21-style `scheme:address?amount=..&memo=..`; `None` when the network
+    /// has no URI scheme registered
+    pub fn payment_uri(&self, address: &str, amount: Option<Decimal>, memo: Option<&str>) -> Option<String> {
+        let scheme = self.uri_scheme.as_deref()?;
+
+        let mut params = Vec::new();
+        if let Some(amount) = amount.filter(|a| *a > Decimal::ZERO) {
+            params.push(format!("amount={}", amount.normalize()));
+        }
+        if let (Some(param), Some(memo)) = (self.uri_memo_param.as_deref(), memo.filter(|m| !m.is_empty())) {
+            params.push(format!("{}={}", param, percent_encode(memo)));
+        }
+
+        let mut uri = format!("{}:{}", scheme, percent_encode(address));
+        if !params.is_empty() {
+            uri.push('?');
+            uri.push_str(&params.join("&"));
+        }
+        Some(uri)
+    }
+}
+
+/// Memo rules used when no format is registered for a currency
+pub fn check_memo_default(memo: &str) -> Result<(), String> {
+    if memo.len() > DEFAULT_MEMO_MAX_LENGTH as usize {
+        Err(format!("memo must be at most {} characters", DEFAULT_MEMO_MAX_LENGTH))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct AddressFormatRow {
+    ticker: String,
+    network: String,
+    address_regex: String,
+    checksum: String,
+    memo_name: Option<String>,
+    memo_required: bool,
+    memo_format: String,
+    memo_max_length: Option<u32>,
+    memo_max_value: Option<u64>,
+    uri_scheme: Option<String>,
+    uri_memo_param: Option<String>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<AddressFormatRow> for AddressFormat {
+    fn from(row: AddressFormatRow) -> Self {
+        Self {
+            ticker: row.ticker,
+            network: row.network,
+            address_regex: row.address_regex,
+            checksum: ChecksumAlgorithm::from_db(&row.checksum),
+            memo_name: row.memo_name,
+            memo_required: row.memo_required,
+            memo_format: MemoFormat::from_db(&row.memo_format),
+            memo_max_length: row.memo_max_length,
+            memo_max_value: row.memo_max_value,
+            uri_scheme: row.uri_scheme,
+            uri_memo_param: row.uri_memo_param,
+            updated_at: Some(row.updated_at),
+        }
+    }
+}
+
+// =============================================================================
+// REGISTRY
+// =============================================================================
+
+#[derive(Clone)]
+pub struct AddressFormatRegistry {
+    pool: DbPool,
+    redis: Option<RedisService>,
+}
+
+impl AddressFormatRegistry {
+    pub fn new(pool: DbPool, redis: Option<RedisService>) -> Self {
+        Self { pool, redis }
+    }
+
+    /// Every registered format, from Redis when cached
+    pub async fn all(&self) -> Result<Vec<AddressFormat>, sqlx::Error> {
+        if let Some(redis) = &self.redis {
+            if let Ok(Some(formats)) = redis.get_json::<Vec<AddressFormat>>(CACHE_KEY).await {
+                return Ok(formats);
+            }
+        }
+
+        let formats: Vec<AddressFormat> = sqlx::query_as::<_, AddressFormatRow>(
+            r#"
+            SELECT ticker, network, address_regex, checksum, memo_name, memo_required, memo_format,
+                   memo_max_length, memo_max_value, uri_scheme, uri_memo_param, updated_at
+            FROM address_formats
+            ORDER BY network, ticker
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(AddressFormat::from)
+        .collect();
+
+        if let Some(redis) = &self.redis {
+            let _ = redis.set_json(CACHE_KEY, &formats, CACHE_TTL_SECS).await;
+        }
+
+        Ok(formats)
+    }
+
+    /// The format for a currency, falling back to the network-wide `*` entry.
+    /// Lookup failures are logged and treated as "no format registered".
+    pub async fn lookup(&self, ticker: &str, network: &str) -> Option<AddressFormat> {
+        let formats = match self.all().await {
+            Ok(formats) => formats,
+            Err(e) => {
+                tracing::warn!("Failed to load address formats: {}", e);
+                return None;
+            }
+        };
+
+        let on_network = |f: &&AddressFormat| f.network.eq_ignore_ascii_case(network);
+        formats
+            .iter()
+            .filter(on_network)
+            .find(|f| f.ticker.eq_ignore_ascii_case(ticker))
+            .or_else(|| formats.iter().filter(on_network).find(|f| f.ticker == ANY_TICKER))
+            .cloned()
+    }
+
+    /// Create or replace the format for `format.ticker` on `format.network`
+    pub async fn upsert(&self, format: &AddressFormat) -> Result<AddressFormat, sqlx::Error> {
+        let ticker = normalize_ticker(&format.ticker);
+
+        sqlx::query(
+            r#"
+            INSERT INTO address_formats (
+                ticker, network, address_regex, checksum, memo_name, memo_required, memo_format,
+                memo_max_length, memo_max_value, uri_scheme, uri_memo_param
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                address_regex = VALUES(address_regex), checksum = VALUES(checksum),
+                memo_name = VALUES(memo_name), memo_required = VALUES(memo_required),
+                memo_format = VALUES(memo_format), memo_max_length = VALUES(memo_max_length),
+                memo_max_value = VALUES(memo_max_value), uri_scheme = VALUES(uri_scheme),
+                uri_memo_param = VALUES(uri_memo_param)
+            "#,
+        )
+        .bind(&ticker)
+        .bind(format.network.trim())
+        .bind(&format.address_regex)
+        .bind(format.checksum.as_str())
+        .bind(&format.memo_name)
+        .bind(format.memo_required)
+        .bind(format.memo_format.as_str())
+        .bind(format.memo_max_length)
+        .bind(format.memo_max_value)
+        .bind(&format.uri_scheme)
+        .bind(&format.uri_memo_param)
+        .execute(&self.pool)
+        .await?;
+
+        self.invalidate().await;
+
+        self.find(&ticker, &format.network)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Remove a format, returning it; `None` when none was registered
+    pub async fn delete(&self, ticker: &str, network: &str) -> Result<Option<AddressFormat>, sqlx::Error> {
+        let Some(existing) = self.find(ticker, network).await? else {
+            return Ok(None);
+        };
+
+        sqlx::query("DELETE FROM address_formats WHERE ticker = ? AND network = ?")
+            .bind(normalize_ticker(ticker))
+            .bind(network.trim())
+            .execute(&self.pool)
+            .await?;
+
+        self.invalidate().await;
+        Ok(Some(existing))
+    }
+
+    async fn find(&self, ticker: &str, network: &str) -> Result<Option<AddressFormat>, sqlx::Error> {
+        let row = sqlx::query_as::<_, AddressFormatRow>(
+            r#"
+            SELECT ticker, network, address_regex, checksum, memo_name, memo_required, memo_format,
+                   memo_max_length, memo_max_value, uri_scheme, uri_memo_param, updated_at
+            FROM address_formats
+            WHERE ticker = ? AND network = ?
+            "#,
+        )
+        .bind(normalize_ticker(ticker))
+        .bind(network.trim())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(AddressFormat::from))
+    }
+
+    async fn invalidate(&self) {
+        if let Some(redis) = &self.redis {
+            if let Err(e) = redis.delete(CACHE_KEY).await {
+                // Instances keep the old rules until the cached copy expires
+                tracing::warn!("Failed to invalidate address format cache: {}", e);
+            }
+        }
+    }
+}
+
+fn normalize_ticker(ticker: &str) -> String {
+    ticker.trim().to_lowercase()
+}
+
+/// Percent-encode everything outside RFC 3986 unreserved characters
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
@@ -0,0 +1,356 @@
+//! Built-in address validation for major chains.
+//!
+//! Bitcoin, EVM chains, Monero, Solana, Tron and XRP addresses are checked
+//! locally, checksum included, so a bad address is rejected without a
+//! provider round-trip and a good one needs none either. Currencies on other
+//! networks return `None` from [`Chain::detect`] and are still validated by
+//! the provider. Formats registered in `address_formats` apply on top of
+//! these checks.
+//!
+//! The encoding primitives (base58, base58check, bech32, EIP-55) are shared
+//! with the address format registry.
+
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+/// Chains whose addresses are validated without asking the provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chain {
+    Bitcoin,
+    Evm, // Ethereum and EVM-compatible networks (BEP20, Polygon, Arbitrum, ...)
+    Monero,
+    Solana,
+    Tron,
+    Ripple,
+}
+
+/// Networks carrying EVM tokens, whatever the ticker
+const EVM_NETWORKS: &[&str] = &["erc20", "bep20", "bsc", "polygon", "matic", "arbitrum", "optimism", "base", "avaxc"];
+
+/// Highest XRP destination tag (an unsigned 32-bit integer)
+const MAX_DESTINATION_TAG: u64 = u32::MAX as u64;
+
+impl Chain {
+    /// The chain a currency lives on, when it is one checked locally.
+    /// Native coins use the `Mainnet` network; tokens name their network.
+    pub fn detect(ticker: &str, network: &str) -> Option<Self> {
+        let ticker = ticker.trim().to_lowercase();
+        let network = network.trim().to_lowercase();
+
+        if EVM_NETWORKS.contains(&network.as_str()) {
+            return Some(Chain::Evm);
+        }
+        match network.as_str() {
+            "trc20" => return Some(Chain::Tron),
+            "sol" | "spl" | "solana" => return Some(Chain::Solana),
+            "mainnet" => {}
+            _ => return None,
+        }
+
+        match ticker.as_str() {
+            "btc" => Some(Chain::Bitcoin),
+            "eth" => Some(Chain::Evm),
+            "xmr" => Some(Chain::Monero),
+            "sol" => Some(Chain::Solana),
+            "trx" => Some(Chain::Tron),
+            "xrp" => Some(Chain::Ripple),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Chain::Bitcoin => "bitcoin",
+            Chain::Evm => "evm",
+            Chain::Monero => "monero",
+            Chain::Solana => "solana",
+            Chain::Tron => "tron",
+            Chain::Ripple => "xrp",
+        }
+    }
+
+    /// Check the address shape and checksum; `Err` carries the reason
+    pub fn check_address(&self, address: &str) -> Result<(), String> {
+        let valid = match self {
+            Chain::Bitcoin => bitcoin_valid(address),
+            Chain::Evm => eip55_valid(address),
+            Chain::Monero => monero_valid(address),
+            Chain::Solana => base58_decode(address, BASE58_ALPHABET).is_some_and(|key| key.len() == 32),
+            Chain::Tron => base58check_decode(address, BASE58_ALPHABET).is_some_and(|p| p.len() == 21 && p[0] == 0x41),
+            Chain::Ripple => ripple_classic_valid(address) || ripple_x_address(address).is_some(),
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("not a valid {} address", self.as_str()))
+        }
+    }
+
+    /// Check the memo sent along with `address`. XRP destination tags are
+    /// numbers up to 2^32 - 1, and X-addresses that embed a tag take no
+    /// separate one; other chains defer to the address format registry.
+    pub fn check_extra_id(&self, address: &str, extra_id: &str) -> Result<(), String> {
+        if *self != Chain::Ripple {
+            return Ok(());
+        }
+
+        if ripple_x_address(address).is_some_and(|has_tag| has_tag) {
+            return Err("destination tag is already part of the X-address".to_string());
+        }
+        match extra_id.parse::<u64>() {
+            Ok(tag) if tag <= MAX_DESTINATION_TAG => Ok(()),
+            _ => Err(format!("destination tag must be a number between 0 and {}", MAX_DESTINATION_TAG)),
+        }
+    }
+}
+
+// =============================================================================
+// CHAINS
+// =============================================================================
+
+/// A decoded Bitcoin mainnet address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BitcoinAddress {
+    P2pkh([u8; 20]),                          // Hash160 of the public key
+    P2sh([u8; 20]),                           // Hash160 of the redeem script
+    Segwit { version: u8, program: Vec<u8> }, // Witness version and program
+}
+
+/// P2PKH/P2SH (base58check, version 0 or 5) or segwit: witness v0 with a
+/// bech32 checksum and a 20 or 32 byte program, v1+ with bech32m
+pub(crate) fn decode_bitcoin_address(address: &str) -> Option<BitcoinAddress> {
+    if let Some(payload) = base58check_decode(address, BASE58_ALPHABET) {
+        if payload.len() != 21 {
+            return None;
+        }
+        let hash: [u8; 20] = payload[1..].try_into().ok()?;
+        return match payload[0] {
+            0x00 => Some(BitcoinAddress::P2pkh(hash)),
+            0x05 => Some(BitcoinAddress::P2sh(hash)),
+            _ => None,
+        };
+    }
+
+    let (hrp, data, constant) = bech32_decode(address)?;
+    let (&version, program) = data.split_first()?;
+    let program = convert_bits(program, 5, 8)?;
+
+    let valid = hrp == "bc"
+        && match version {
+            0 => constant == BECH32_CONST && matches!(program.len(), 20 | 32),
+            1..=16 => constant == BECH32M_CONST && (2..=40).contains(&program.len()),
+            _ => false,
+        };
+    valid.then_some(BitcoinAddress::Segwit { version, program })
+}
+
+fn bitcoin_valid(address: &str) -> bool {
+    decode_bitcoin_address(address).is_some()
+}
+
+/// Standard (4...), integrated (4..., with payment id) and subaddresses
+/// (8...): network byte, spend and view keys, Keccak-256 checksum
+fn monero_valid(address: &str) -> bool {
+    let Some(decoded) = monero_base58_decode(address) else {
+        return false;
+    };
+    let expected_len = match decoded.first() {
+        Some(18) | Some(42) => 69, // Standard, subaddress
+        Some(19) => 77,            // Integrated
+        _ => return false,
+    };
+    if decoded.len() != expected_len {
+        return false;
+    }
+
+    let (data, checksum) = decoded.split_at(decoded.len() - 4);
+    &Keccak256::digest(data)[..4] == checksum
+}
+
+/// Classic `r...` address: base58check over the XRP alphabet, version 0
+fn ripple_classic_valid(address: &str) -> bool {
+    base58check_decode(address, RIPPLE_ALPHABET).is_some_and(|p| p.len() == 21 && p[0] == 0x00)
+}
+
+/// Mainnet X-address (account and optional destination tag in one); `Some`
+/// tells whether it carries a tag
+fn ripple_x_address(address: &str) -> Option<bool> {
+    let payload = base58check_decode(address, RIPPLE_ALPHABET)?;
+    if payload.len() != 31 || payload[..2] != [0x05, 0x44] {
+        return None;
+    }
+    match payload[22] {
+        0 => Some(false),
+        1 => Some(true),
+        _ => None,
+    }
+}
+
+// =============================================================================
+// ENCODINGS
+// =============================================================================
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const RIPPLE_ALPHABET: &[u8] = b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz";
+const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+/// Base58 with the given alphabet; its first character encodes a zero byte
+fn base58_decode(input: &str, alphabet: &[u8]) -> Option<Vec<u8>> {
+    let mut bytes: Vec<u8> = Vec::new(); // Big-endian, without leading zeros
+    for c in input.bytes() {
+        let mut carry = alphabet.iter().position(|&b| b == c)? as u32;
+        for byte in bytes.iter_mut().rev() {
+            carry += (*byte as u32) * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.insert(0, (carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+
+    let leading_zeros = input.bytes().take_while(|&c| c == alphabet[0]).count();
+    let mut decoded = vec![0u8; leading_zeros];
+    decoded.extend(bytes);
+    Some(decoded)
+}
+
+/// Payload of a base58check string: the last four bytes are the first four
+/// of double SHA-256 over the rest
+fn base58check_decode(input: &str, alphabet: &[u8]) -> Option<Vec<u8>> {
+    let decoded = base58_decode(input, alphabet)?;
+    if decoded.len() < 5 {
+        return None;
+    }
+
+    let (payload, checksum) = decoded.split_at(decoded.len() - 4);
+    let hash = Sha256::digest(Sha256::digest(payload));
+    (&hash[..4] == checksum).then(|| payload.to_vec())
+}
+
+pub(crate) fn base58check_valid(address: &str) -> bool {
+    base58check_decode(address, BASE58_ALPHABET).is_some()
+}
+
+/// Monero's base58: 8-byte blocks encoded as 11 characters each, the last
+/// block shorter
+fn monero_base58_decode(input: &str) -> Option<Vec<u8>> {
+    // Encoded length of a block of 0..=8 bytes
+    const ENCODED_BLOCK_SIZES: [usize; 9] = [0, 2, 3, 5, 6, 7, 9, 10, 11];
+
+    let mut decoded = Vec::new();
+    for block in input.as_bytes().chunks(11) {
+        let size = ENCODED_BLOCK_SIZES.iter().position(|&s| s == block.len())?;
+        let mut value: u128 = 0;
+        for c in block {
+            value = value * 58 + BASE58_ALPHABET.iter().position(|b| b == c)? as u128;
+        }
+        if value >> (8 * size) != 0 {
+            return None;
+        }
+        decoded.extend_from_slice(&value.to_be_bytes()[16 - size..]);
+    }
+    Some(decoded)
+}
+
+fn bech32_polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+
+    let mut chk: u32 = 1;
+    for value in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x01ff_ffff) << 5) ^ *value as u32;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+/// BIP173 / BIP350: human-readable part, 5-bit data without the checksum,
+/// and which checksum constant matched
+fn bech32_decode(address: &str) -> Option<(String, Vec<u8>, u32)> {
+    if address.len() > 90 || (address.to_lowercase() != address && address.to_uppercase() != address) {
+        return None;
+    }
+
+    let address = address.to_lowercase();
+    let separator = address.rfind('1')?;
+    if separator == 0 || separator + 7 > address.len() {
+        return None;
+    }
+
+    let (hrp, data) = (&address[..separator], &address[separator + 1..]);
+    let data: Vec<u8> = data
+        .bytes()
+        .map(|c| BECH32_CHARSET.iter().position(|&b| b == c).map(|v| v as u8))
+        .collect::<Option<_>>()?;
+
+    let mut values: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    values.push(0);
+    values.extend(hrp.bytes().map(|b| b & 31));
+    values.extend(&data);
+
+    let constant = bech32_polymod(&values);
+    if !matches!(constant, BECH32_CONST | BECH32M_CONST) {
+        return None;
+    }
+    Some((hrp.to_string(), data[..data.len() - 6].to_vec(), constant))
+}
+
+/// Accepts both bech32 and bech32m checksums
+pub(crate) fn bech32_valid(address: &str) -> bool {
+    bech32_decode(address).is_some()
+}
+
+/// Regroup bits, e.g. 5-bit bech32 values into bytes; padding must be zero
+fn convert_bits(data: &[u8], from: u32, to: u32) -> Option<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let mut out = Vec::new();
+    let max = (1u32 << to) - 1;
+    let max_acc = (1u32 << (from + to - 1)) - 1;
+
+    for &value in data {
+        acc = ((acc << from) | value as u32) & max_acc;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            out.push(((acc >> bits) & max) as u8);
+        }
+    }
+
+    if bits >= from || (acc << (to - bits)) & max != 0 {
+        return None;
+    }
+    Some(out)
+}
+
+/// EIP-55 mixed-case checksum over the Keccak-256 of the lowercase hex.
+/// All-lowercase / all-uppercase hex carries no checksum and is accepted.
+pub(crate) fn eip55_valid(address: &str) -> bool {
+    let Some(hex) = address.strip_prefix("0x") else {
+        return false;
+    };
+    if hex.len() != 40 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return false;
+    }
+    if hex == hex.to_lowercase() || hex == hex.to_uppercase() {
+        return true;
+    }
+
+    let hash = Keccak256::digest(hex.to_lowercase().as_bytes());
+    hex.chars().enumerate().all(|(i, c)| {
+        if !c.is_ascii_alphabetic() {
+            return true;
+        }
+        let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0x0f };
+        (nibble >= 8) == c.is_ascii_uppercase()
+    })
+}
@@ -0,0 +1,177 @@
+//! Admission control for swap creation.
+//!
+//! A create holds a provider call open for seconds, so a burst of them can
+//! push every request past its SLO. At most SWAP_CREATE_MAX_CONCURRENT
+//! creates run at once on an instance; the next SWAP_CREATE_MAX_QUEUED wait
+//! for a slot, for up to SWAP_CREATE_MAX_WAIT_MS. A create that waited
+//! answers with `X-Queue-Position` (its place in line on arrival) and
+//! `X-Queue-Eta-Ms` (the wait expected then). One that would not get a slot
+//! in time is refused straight away with 429 and a Retry-After of the
+//! expected wait, which keeps the creates we do accept fast.
+//!
+//! The expected wait is the line ahead shared among the slots, times a
+//! moving average of how long creates take. Callers exempt from rate limits
+//! are exempt here too.
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, HeaderValue, Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::config::environment::AdmissionConfig;
+use crate::services::metrics::metrics;
+use crate::services::rate_limit::is_exempt;
+use crate::AppState;
+
+pub const QUEUE_POSITION_HEADER: &str = "x-queue-position";
+pub const QUEUE_ETA_HEADER: &str = "x-queue-eta-ms";
+
+/// Assumed create duration until one has been measured
+const INITIAL_SERVICE_MS: u64 = 1000;
+
+/// Weight of the latest create in the moving average, in tenths
+const SMOOTHING: u64 = 2;
+
+/// Place in line of a create that had to wait
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QueueTicket {
+    pub position: usize, // 1 = next to get a slot
+    pub eta: Duration,   // Expected wait on arrival
+}
+
+/// A create holding a slot; the slot frees when this is dropped
+#[derive(Debug)]
+pub struct Admitted {
+    pub queued: Option<QueueTicket>, // None when a slot was free on arrival
+    started: Instant,
+    _slot: OwnedSemaphorePermit,
+}
+
+/// A create refused because no slot would free up in time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rejected {
+    pub retry_after: Duration,
+}
+
+/// Leaves the line when the wait ends, however it ends
+struct InLine<'a>(&'a AtomicUsize);
+
+impl Drop for InLine<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub struct AdmissionController {
+    config: AdmissionConfig,
+    slots: Arc<Semaphore>,
+    waiting: AtomicUsize,
+    service_ms: AtomicU64, // Moving average of create durations
+}
+
+impl AdmissionController {
+    pub fn new(config: AdmissionConfig) -> Self {
+        let slots = Arc::new(Semaphore::new(config.max_concurrent.max(1)));
+        Self { config, slots, waiting: AtomicUsize::new(0), service_ms: AtomicU64::new(INITIAL_SERVICE_MS) }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Creates holding a slot
+    pub fn in_flight(&self) -> usize {
+        self.config.max_concurrent.max(1) - self.slots.available_permits()
+    }
+
+    /// Creates waiting for a slot
+    pub fn waiting(&self) -> usize {
+        self.waiting.load(Ordering::SeqCst)
+    }
+
+    /// Expected wait for a slot at `position` in line
+    pub fn eta(&self, position: usize) -> Duration {
+        let rounds = position.div_ceil(self.config.max_concurrent.max(1)) as u64;
+        Duration::from_millis(rounds * self.service_ms.load(Ordering::Relaxed))
+    }
+
+    /// Take a slot, waiting in line for one if that is expected to be quick enough
+    pub async fn admit(&self) -> Result<Admitted, Rejected> {
+        if let Ok(slot) = self.slots.clone().try_acquire_owned() {
+            return Ok(Admitted { queued: None, started: Instant::now(), _slot: slot });
+        }
+
+        let position = self.waiting.fetch_add(1, Ordering::SeqCst) + 1;
+        let _in_line = InLine(&self.waiting);
+        let eta = self.eta(position);
+        if position > self.config.max_queued || eta > self.config.max_wait {
+            return Err(Rejected { retry_after: eta });
+        }
+
+        match tokio::time::timeout(self.config.max_wait, self.slots.clone().acquire_owned()).await {
+            Ok(Ok(slot)) => Ok(Admitted {
+                queued: Some(QueueTicket { position, eta }),
+                started: Instant::now(),
+                _slot: slot,
+            }),
+            // Timed out (the semaphore is never closed)
+            _ => Err(Rejected { retry_after: self.eta(self.waiting()) }),
+        }
+    }
+
+    /// Release the slot and fold the create's duration into the average
+    pub fn finish(&self, admitted: Admitted) {
+        let took = admitted.started.elapsed().as_millis() as u64;
+        let _ = self.service_ms.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |avg| {
+            Some((avg * (10 - SMOOTHING) + took * SMOOTHING) / 10)
+        });
+    }
+}
+
+fn is_swap_create(request: &Request<Body>) -> bool {
+    request.method() == Method::POST && matches!(request.uri().path(), "/swap/create" | "/swap/routes" | "/widget/swap")
+}
+
+/// Queue or refuse swap creates beyond the concurrency limit
+pub async fn admit_swap_creates(State(state): State<Arc<AppState>>, request: Request<Body>, next: Next) -> Response {
+    if !state.admission.enabled() || !is_swap_create(&request) || is_exempt(&state.rate_limit_bypass, &request) {
+        return next.run(request).await;
+    }
+
+    let admitted = match state.admission.admit().await {
+        Ok(admitted) => admitted,
+        Err(rejected) => {
+            metrics().swap_admission.inc("rejected");
+            let retry_after = rejected.retry_after.as_secs_f64().ceil().max(1.0) as u64;
+            return (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after.to_string())],
+                Json(serde_json::json!({
+                    "error": "Too many swaps being created, try again shortly",
+                    "retry_after": retry_after,
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    let queued = admitted.queued;
+    metrics().swap_admission.inc(if queued.is_some() { "queued" } else { "admitted" });
+    let mut response = next.run(request).await;
+    state.admission.finish(admitted);
+
+    if let Some(ticket) = queued {
+        let headers = response.headers_mut();
+        headers.insert(QUEUE_POSITION_HEADER, HeaderValue::from(ticket.position));
+        headers.insert(QUEUE_ETA_HEADER, HeaderValue::from(ticket.eta.as_millis() as u64));
+    }
+    response
+}
@@ -0,0 +1,310 @@
+//! Swap funnel analytics.
+//!
+//! Events are queued in memory and published in batches by a background task,
+//! so emitting never blocks a request. When the queue is full events are
+//! dropped with a warning rather than applying backpressure.
+
+use async_trait::async_trait;
+use axum::{extract::FromRequestParts, http::request::Parts};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+use crate::config::environment::{AnalyticsConfig, AnalyticsSinkKind};
+use crate::config::DbPool;
+use crate::services::jobs::{self, JobKind, JobOutcome};
+use crate::services::tenant::TenantId;
+
+// =============================================================================
+// EVENTS
+// =============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FunnelEvent {
+    QuoteViewed,
+    SwapCreated,
+    DepositDetected,
+    Completed,
+    Failed, // Also emitted for expired swaps; `properties.status` tells them apart
+}
+
+impl FunnelEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FunnelEvent::QuoteViewed => "quote_viewed",
+            FunnelEvent::SwapCreated => "swap_created",
+            FunnelEvent::DepositDetected => "deposit_detected",
+            FunnelEvent::Completed => "completed",
+            FunnelEvent::Failed => "failed",
+        }
+    }
+}
+
+/// Who triggered an event and where they came from
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnalyticsContext {
+    #[serde(default)]
+    pub tenant: TenantId,
+    pub session_id: Option<String>,
+    pub user_id: Option<String>,
+    pub utm_source: Option<String>,
+    pub utm_medium: Option<String>,
+    pub utm_campaign: Option<String>,
+    pub referrer: Option<String>,
+}
+
+impl AnalyticsContext {
+    pub fn with_user(mut self, user_id: Option<String>) -> Self {
+        self.user_id = user_id;
+        self
+    }
+
+    pub fn with_tenant(mut self, tenant: TenantId) -> Self {
+        self.tenant = tenant;
+        self
+    }
+}
+
+/// Reads X-Session-Id, X-UTM-Source/Medium/Campaign and Referer
+impl<S: Send + Sync> FromRequestParts<S> for AnalyticsContext {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let header = |name: &str| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim().chars().take(255).collect::<String>())
+                .filter(|v| !v.is_empty())
+        };
+
+        Ok(Self {
+            tenant: TenantId::default(), // Set by whoever knows the request's brand
+            session_id: header("x-session-id"),
+            user_id: None,
+            utm_source: header("x-utm-source"),
+            utm_medium: header("x-utm-medium"),
+            utm_campaign: header("x-utm-campaign"),
+            referrer: header("referer"),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyticsEvent {
+    pub event: FunnelEvent,
+    pub swap_id: Option<String>,
+    #[serde(flatten)]
+    pub context: AnalyticsContext,
+    pub properties: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+}
+
+// =============================================================================
+// SINKS
+// =============================================================================
+
+#[async_trait]
+pub trait AnalyticsSink: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn publish(&self, events: &[AnalyticsEvent]) -> Result<(), String>;
+}
+
+/// Rows in `analytics_events`
+pub struct DbSink {
+    pool: DbPool,
+}
+
+#[async_trait]
+impl AnalyticsSink for DbSink {
+    fn name(&self) -> &'static str {
+        "db"
+    }
+
+    async fn publish(&self, events: &[AnalyticsEvent]) -> Result<(), String> {
+        let mut builder = sqlx::QueryBuilder::<sqlx::MySql>::new(
+            "INSERT INTO analytics_events
+             (event, tenant_id, swap_id, user_id, session_id, utm_source, utm_medium, utm_campaign, referrer,
+              properties, occurred_at) ",
+        );
+        builder.push_values(events, |mut row, e| {
+            row.push_bind(e.event.as_str())
+                .push_bind(e.context.tenant.as_str())
+                .push_bind(&e.swap_id)
+                .push_bind(&e.context.user_id)
+                .push_bind(&e.context.session_id)
+                .push_bind(&e.context.utm_source)
+                .push_bind(&e.context.utm_medium)
+                .push_bind(&e.context.utm_campaign)
+                .push_bind(&e.context.referrer)
+                .push_bind(e.properties.to_string())
+                .push_bind(e.occurred_at);
+        });
+
+        builder
+            .build()
+            .execute(&self.pool)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// JSON array POSTed to an HTTP endpoint
+pub struct WebhookSink {
+    http: reqwest::Client,
+    url: String,
+}
+
+#[async_trait]
+impl AnalyticsSink for WebhookSink {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    async fn publish(&self, events: &[AnalyticsEvent]) -> Result<(), String> {
+        self.http
+            .post(&self.url)
+            .json(events)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Kafka through a Confluent-compatible REST Proxy; records are keyed by swap id
+pub struct KafkaRestSink {
+    http: reqwest::Client,
+    url: String,
+}
+
+#[async_trait]
+impl AnalyticsSink for KafkaRestSink {
+    fn name(&self) -> &'static str {
+        "kafka"
+    }
+
+    async fn publish(&self, events: &[AnalyticsEvent]) -> Result<(), String> {
+        let records: Vec<serde_json::Value> = events
+            .iter()
+            .map(|e| serde_json::json!({ "key": e.swap_id, "value": e }))
+            .collect();
+
+        self.http
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/vnd.kafka.json.v2+json")
+            .body(serde_json::json!({ "records": records }).to_string())
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+// =============================================================================
+// ANALYTICS HANDLE
+// =============================================================================
+
+/// Cheap to clone; a disabled handle ignores every event
+#[derive(Clone, Default)]
+pub struct Analytics {
+    queue: Option<mpsc::Sender<AnalyticsEvent>>,
+}
+
+impl Analytics {
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Build the configured sink and spawn its publisher task
+    pub fn from_config(config: &AnalyticsConfig, pool: DbPool) -> Self {
+        let http = reqwest::Client::new();
+        let sink: Arc<dyn AnalyticsSink> = match config.sink {
+            AnalyticsSinkKind::Off => return Self::disabled(),
+            AnalyticsSinkKind::Db => Arc::new(DbSink { pool }),
+            AnalyticsSinkKind::Webhook => Arc::new(WebhookSink { http, url: config.webhook_url.clone() }),
+            AnalyticsSinkKind::Kafka => Arc::new(KafkaRestSink {
+                http,
+                url: format!("{}/topics/{}", config.kafka_rest_url.trim_end_matches('/'), config.kafka_topic),
+            }),
+        };
+
+        Self::with_sink(sink, config)
+    }
+
+    pub fn with_sink(sink: Arc<dyn AnalyticsSink>, config: &AnalyticsConfig) -> Self {
+        let (tx, rx) = mpsc::channel(config.queue_capacity.max(1));
+        tokio::spawn(run_publisher(sink, rx, config.batch_size.max(1), config.flush_interval));
+        Self { queue: Some(tx) }
+    }
+
+    pub fn track(
+        &self,
+        event: FunnelEvent,
+        swap_id: Option<&str>,
+        context: &AnalyticsContext,
+        properties: serde_json::Value,
+    ) {
+        let Some(queue) = &self.queue else {
+            return;
+        };
+
+        let event = AnalyticsEvent {
+            event,
+            swap_id: swap_id.map(str::to_string),
+            context: context.clone(),
+            properties,
+            occurred_at: Utc::now(),
+        };
+
+        if let Err(e) = queue.try_send(event) {
+            tracing::warn!(target: "analytics", "Dropping analytics event: {}", e);
+        }
+    }
+}
+
+/// Publish whenever a batch fills up or `flush_interval` passes with events pending
+async fn run_publisher(
+    sink: Arc<dyn AnalyticsSink>,
+    mut rx: mpsc::Receiver<AnalyticsEvent>,
+    batch_size: usize,
+    flush_interval: std::time::Duration,
+) {
+    let job = jobs::registry().register(
+        "analytics_publisher",
+        "Publish buffered funnel events to the analytics sink",
+        JobKind::QueueConsumer,
+    );
+
+    while let Some(first) = rx.recv().await {
+        let mut batch = vec![first];
+        let deadline = tokio::time::Instant::now() + flush_interval;
+
+        // A trigger flushes whatever is buffered without waiting for the deadline
+        while batch.len() < batch_size {
+            tokio::select! {
+                received = tokio::time::timeout_at(deadline, rx.recv()) => match received {
+                    Ok(Some(event)) => batch.push(event),
+                    Ok(None) | Err(_) => break,
+                },
+                _ = job.triggered() => break,
+            }
+        }
+
+        let run = job.start();
+        match sink.publish(&batch).await {
+            Ok(()) => run.finish(JobOutcome::Success, None),
+            Err(e) => {
+                tracing::warn!(target: "analytics", "Failed to publish {} events to {}: {}", batch.len(), sink.name(), e);
+                run.finish(JobOutcome::Failed, Some(e));
+            }
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! Per-brand API usage rollup.
+//!
+//! Requests whose `X-API-Key` matches a partner brand are counted by endpoint
+//! (method plus route template) and hour. Counts are buffered in memory and
+//! added to `api_usage_hourly` once a minute, so recording never blocks a
+//! request and every instance sums into the same rows. Integrators read their
+//! own counts back at `GET /account/analytics`.
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::Request,
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use crate::config::DbPool;
+use crate::services::branding::BrandRegistry;
+use crate::services::jobs::{self, JobKind, JobOutcome};
+use crate::AppState;
+
+/// Hits buffered before new ones are dropped
+const QUEUE_CAPACITY: usize = 10_000;
+
+/// How often buffered counts are written out
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Endpoint label for requests that matched no route
+const UNMATCHED_ENDPOINT: &str = "unmatched";
+
+#[derive(Debug)]
+struct UsageHit {
+    brand: String,
+    endpoint: String,
+    status: u16,
+    at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct UsageKey {
+    brand: String,
+    hour: DateTime<Utc>,
+    endpoint: String,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct UsageCount {
+    requests: u32,
+    client_errors: u32, // 4xx
+    server_errors: u32, // 5xx
+}
+
+/// Cheap to clone; a default handle ignores every hit
+#[derive(Clone, Default)]
+pub struct ApiUsage {
+    queue: Option<mpsc::Sender<UsageHit>>,
+}
+
+impl ApiUsage {
+    /// Spawn the rollup task writing to `api_usage_hourly`
+    pub fn spawn(pool: DbPool) -> Self {
+        let (tx, rx) = mpsc::channel(QUEUE_CAPACITY);
+        tokio::spawn(run_rollup(pool, rx));
+        Self { queue: Some(tx) }
+    }
+
+    pub fn record(&self, brand: &str, endpoint: &str, status: u16) {
+        let Some(queue) = &self.queue else {
+            return;
+        };
+
+        let hit = UsageHit { brand: brand.to_string(), endpoint: endpoint.to_string(), status, at: Utc::now() };
+        if let Err(e) = queue.try_send(hit) {
+            tracing::warn!("Dropping API usage hit: {}", e);
+        }
+    }
+}
+
+/// Count requests made with a partner brand's API key
+pub async fn track_api_usage(State(state): State<Arc<AppState>>, request: Request<Body>, next: Next) -> Response {
+    let api_key = request
+        .headers()
+        .get("x-api-key")
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string);
+    let Some(api_key) = api_key else {
+        return next.run(request).await;
+    };
+
+    let endpoint = format!(
+        "{} {}",
+        request.method(),
+        request.extensions().get::<MatchedPath>().map_or(UNMATCHED_ENDPOINT, |p| p.as_str())
+    );
+
+    let response = next.run(request).await;
+
+    let brand = BrandRegistry::new(state.db.clone(), Some(state.redis.clone()))
+        .resolve(Some(&api_key), None)
+        .await;
+    if let Some(brand) = brand {
+        state.api_usage.record(&brand.slug, &endpoint, response.status().as_u16());
+    }
+
+    response
+}
+
+/// Fold hits into hourly counts and flush them every FLUSH_INTERVAL
+async fn run_rollup(pool: DbPool, mut rx: mpsc::Receiver<UsageHit>) {
+    let job = jobs::registry().register(
+        "api_usage_rollup",
+        "Write buffered per-brand API usage counts to api_usage_hourly",
+        JobKind::QueueConsumer,
+    );
+
+    let mut pending: HashMap<UsageKey, UsageCount> = HashMap::new();
+    let mut ticker = tokio::time::interval(FLUSH_INTERVAL);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        let closed = tokio::select! {
+            hit = rx.recv() => match hit {
+                Some(hit) => {
+                    add_hit(&mut pending, hit);
+                    continue;
+                }
+                None => true,
+            },
+            _ = ticker.tick() => false,
+            _ = job.triggered() => false,
+        };
+
+        if !pending.is_empty() {
+            let run = job.start();
+            match flush(&pool, &pending).await {
+                Ok(()) => {
+                    pending.clear();
+                    run.finish(JobOutcome::Success, None);
+                }
+                // Kept for the next flush; counts only ever add up
+                Err(e) => {
+                    tracing::warn!("Failed to write {} API usage rows: {}", pending.len(), e);
+                    run.finish(JobOutcome::Failed, Some(e.to_string()));
+                }
+            }
+        }
+
+        if closed {
+            break;
+        }
+    }
+}
+
+fn add_hit(pending: &mut HashMap<UsageKey, UsageCount>, hit: UsageHit) {
+    let hour = hit.at.duration_trunc(TimeDelta::hours(1)).unwrap_or(hit.at);
+    let count = pending
+        .entry(UsageKey { brand: hit.brand, hour, endpoint: hit.endpoint })
+        .or_default();
+
+    count.requests += 1;
+    match hit.status {
+        400..=499 => count.client_errors += 1,
+        500..=599 => count.server_errors += 1,
+        _ => {}
+    }
+}
+
+async fn flush(pool: &DbPool, pending: &HashMap<UsageKey, UsageCount>) -> Result<(), sqlx::Error> {
+    let rows: Vec<(&UsageKey, &UsageCount)> = pending.iter().collect();
+
+    // All or nothing, so a failed flush can be retried without double counting
+    let mut tx = pool.begin().await?;
+    for chunk in rows.chunks(500) {
+        let mut builder = sqlx::QueryBuilder::<sqlx::MySql>::new(
+            "INSERT INTO api_usage_hourly (brand, hour_start, endpoint, requests, client_errors, server_errors) ",
+        );
+        builder.push_values(chunk, |mut b, (key, count)| {
+            b.push_bind(&key.brand)
+                .push_bind(key.hour)
+                .push_bind(&key.endpoint)
+                .push_bind(count.requests)
+                .push_bind(count.client_errors)
+                .push_bind(count.server_errors);
+        });
+        builder.push(
+            " ON DUPLICATE KEY UPDATE
+                requests = requests + VALUES(requests),
+                client_errors = client_errors + VALUES(client_errors),
+                server_errors = server_errors + VALUES(server_errors)",
+        );
+        builder.build().execute(&mut *tx).await?;
+    }
+
+    tx.commit().await
+}
@@ -0,0 +1,189 @@
+//! Audit log of mutating requests.
+//!
+//! Every POST, PUT, PATCH and DELETE that reaches a route is recorded in
+//! `audit_log`: who made it (the signed-in user, or anonymous for webhooks
+//! and public calls), from which address, which route it hit ("PATCH
+//! /admin/providers/{id}" for an admin toggling a provider, "POST
+//! /swap/create" for a new swap), the concrete path, the response status and
+//! a SHA-256 of the request body. Bodies themselves are not kept: they carry
+//! passwords and addresses, and the hash is enough to match a request
+//! against one produced elsewhere. Lookups that merely use POST (batch
+//! status, address validation, routing dry runs) are left out.
+//!
+//! The table is append-only; triggers refuse updates and deletes. Entries are
+//! served at `GET /admin/audit`, newest first.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{MatchedPath, State},
+    http::{Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+use crate::config::DbPool;
+use crate::services::request_logging::caller_id;
+use crate::{AppState, MAX_BODY_BYTES};
+
+/// POST routes that only read
+const READ_ONLY_ROUTES: &[&str] = &["/swap/status/batch", "/swap/validate-address", "/admin/routing-rules/dry-run"];
+
+/// Entries returned when the query sets no limit, and at most
+const DEFAULT_LIMIT: u32 = 100;
+const MAX_LIMIT: u32 = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AuditEntry {
+    pub id: u64,
+    pub actor: String, // "user:<id>" or "anonymous"
+    pub ip: Option<String>,
+    pub action: String, // Method and route, e.g. "PUT /admin/fee-rules/{id}"
+    pub path: String,
+    pub status: u16,
+    pub payload_hash: Option<String>, // Hex SHA-256 of the request body; None when empty
+    pub created_at: DateTime<Utc>,
+}
+
+/// GET /admin/audit filters; all optional
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AuditQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_prefix: Option<String>, // e.g. "/admin/fee-rules" or "/swap/3f2a"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_id: Option<u64>, // Page back from the last id seen
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>, // Default 100, at most 500
+}
+
+/// An entry before it is stored
+#[derive(Debug, Clone)]
+pub struct NewAuditEntry {
+    pub actor: String,
+    pub ip: Option<String>,
+    pub action: String,
+    pub path: String,
+    pub status: u16,
+    pub payload_hash: Option<String>,
+}
+
+/// Hex SHA-256 of a request body; None for an empty one
+pub fn payload_hash(body: &[u8]) -> Option<String> {
+    (!body.is_empty()).then(|| Sha256::digest(body).iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Whether a request with this method to this route is audited
+pub fn is_audited(method: &Method, route: &str) -> bool {
+    matches!(*method, Method::POST | Method::PUT | Method::PATCH | Method::DELETE) && !READ_ONLY_ROUTES.contains(&route)
+}
+
+pub struct AuditLog {
+    pool: DbPool,
+}
+
+impl AuditLog {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record(&self, entry: &NewAuditEntry) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO audit_log (actor, ip, action, path, status, payload_hash, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, NOW(3))",
+        )
+        .bind(&entry.actor)
+        .bind(&entry.ip)
+        .bind(&entry.action)
+        .bind(&entry.path)
+        .bind(entry.status)
+        .bind(&entry.payload_hash)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Entries matching `query`, newest first
+    pub async fn list(&self, query: &AuditQuery) -> Result<Vec<AuditEntry>, sqlx::Error> {
+        let mut builder = sqlx::QueryBuilder::<sqlx::MySql>::new(
+            "SELECT id, actor, ip, action, path, status, payload_hash, created_at FROM audit_log WHERE 1 = 1",
+        );
+        if let Some(actor) = &query.actor {
+            builder.push(" AND actor = ").push_bind(actor.clone());
+        }
+        if let Some(action) = &query.action {
+            builder.push(" AND action = ").push_bind(action.clone());
+        }
+        if let Some(prefix) = &query.path_prefix {
+            let escaped = prefix.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+            builder.push(" AND path LIKE ").push_bind(format!("{}%", escaped));
+        }
+        if let Some(ip) = &query.ip {
+            builder.push(" AND ip = ").push_bind(ip.clone());
+        }
+        if let Some(since) = query.since {
+            builder.push(" AND created_at >= ").push_bind(since);
+        }
+        if let Some(until) = query.until {
+            builder.push(" AND created_at < ").push_bind(until);
+        }
+        if let Some(before_id) = query.before_id {
+            builder.push(" AND id < ").push_bind(before_id);
+        }
+        builder
+            .push(" ORDER BY id DESC LIMIT ")
+            .push_bind(query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT));
+
+        builder.build_query_as::<AuditEntry>().fetch_all(&self.pool).await
+    }
+}
+
+// =============================================================================
+// MIDDLEWARE
+// =============================================================================
+
+/// Record each audited request once its response is known
+pub async fn audit_mutations(State(state): State<Arc<AppState>>, request: Request<Body>, next: Next) -> Response {
+    let Some(route) = request.extensions().get::<MatchedPath>().map(|p| p.as_str().to_string()) else {
+        return next.run(request).await;
+    };
+    if !is_audited(request.method(), &route) {
+        return next.run(request).await;
+    }
+
+    let action = format!("{} {}", request.method(), route);
+    let path = request.uri().path().to_string();
+    let actor = caller_id(&state, &request).unwrap_or_else(|| "anonymous".to_string());
+    let ip = state.route_limiter.client_ip(request.headers(), request.extensions());
+
+    let (parts, body) = request.into_parts();
+    let bytes = match to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(_) => return StatusCode::PAYLOAD_TOO_LARGE.into_response(),
+    };
+    let payload_hash = payload_hash(&bytes);
+
+    let response = next.run(Request::from_parts(parts, Body::from(bytes))).await;
+
+    let entry = NewAuditEntry { actor, ip, action, path, status: response.status().as_u16(), payload_hash };
+    let audit = AuditLog::new(state.db.clone());
+    tokio::spawn(async move {
+        if let Err(e) = audit.record(&entry).await {
+            tracing::error!("Failed to record audit entry for {} by {}: {}", entry.action, entry.actor, e);
+        }
+    });
+
+    response
+}
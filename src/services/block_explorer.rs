@@ -0,0 +1,42 @@
+//! Block explorer links for the deposit and payout transactions of a swap.
+//!
+//! Templates come from BLOCK_EXPLORER_URLS: native coins (network `Mainnet`)
+//! are looked up by ticker, tokens by their network, so USDT on ERC20 links
+//! to the same explorer as ETH. Currencies with neither get no link; the
+//! hash is still served.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::config::environment::BlockExplorerConfig;
+
+static EXPLORERS: LazyLock<BlockExplorers> =
+    LazyLock::new(|| BlockExplorers::from_config(&BlockExplorerConfig::from_env()));
+
+/// Process-wide explorers, read from the environment on first use
+pub fn explorers() -> &'static BlockExplorers {
+    &EXPLORERS
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BlockExplorers {
+    tx_urls: HashMap<String, String>,
+}
+
+impl BlockExplorers {
+    pub fn from_config(config: &BlockExplorerConfig) -> Self {
+        Self { tx_urls: config.tx_urls.clone() }
+    }
+
+    /// Explorer page of transaction `hash` on the currency's chain. Hashes
+    /// are hex or base58, so anything else is not put in a link.
+    pub fn tx_url(&self, ticker: &str, network: &str, hash: &str) -> Option<String> {
+        if hash.is_empty() || !hash.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+
+        let network = network.trim().to_lowercase();
+        let key = if network == "mainnet" { ticker.trim().to_lowercase() } else { network };
+        self.tx_urls.get(&key).map(|template| template.replace("{tx}", hash))
+    }
+}
@@ -0,0 +1,415 @@
+//! White-label brands.
+//!
+//! A partner brand is a row in `brands` managed through `/admin/brands`.
+//! Requests are matched to a brand by their `X-API-Key` header, then by the
+//! host they were sent to; anything else gets the deployment's own brand
+//! (BRAND_NAME / BRAND_SUPPORT_EMAIL). The brand decides the name and support
+//! address users see, the Trocador markup on quotes and trades, and which
+//! providers and pairs are offered. Swaps record the brand they were created
+//! under, and swap events carry it so notifications can be branded; their
+//! status changes also go to the brand's webhook (see `modules::brand`).
+//! Each brand belongs to a tenant, whose data it shares (see `tenant`).
+
+use axum::{
+    extract::{FromRef, FromRequestParts},
+    http::{header, request::Parts},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+use crate::config::environment::BrandingConfig;
+use crate::config::DbPool;
+use crate::services::redis_cache::RedisService;
+use crate::services::tenant::TenantId;
+use crate::AppState;
+
+const CACHE_KEY: &str = "brands:all";
+const CACHE_TTL_SECS: u64 = 300;
+
+/// Slug of the deployment's own brand; never stored in `brands`
+pub const DEFAULT_BRAND: &str = "default";
+
+/// Matches every ticker in a pair rule
+pub const ANY_TICKER: &str = "*";
+
+/// Highest markup Trocador accepts, in percent
+pub const MAX_MARKUP_PERCENT: f64 = 3.0;
+
+/// Shortest partner API key accepted
+pub const MIN_API_KEY_LENGTH: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PairRule {
+    pub from: String, // Ticker, or `*`
+    pub to: String,
+}
+
+impl PairRule {
+    fn matches(&self, from: &str, to: &str) -> bool {
+        let side = |rule: &str, ticker: &str| rule == ANY_TICKER || rule.eq_ignore_ascii_case(ticker);
+        side(&self.from, from) && side(&self.to, to)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Brand {
+    pub slug: String,
+    pub name: String,
+    pub support_email: String,
+    #[serde(default)]
+    pub tenant: TenantId, // Users, swaps and fee rules are shared by the tenant's brands
+    #[serde(default)]
+    pub domains: Vec<String>,
+    #[serde(default)]
+    pub markup_percent: Option<f64>, // None uses the Trocador account default
+    #[serde(default)]
+    pub enabled_providers: Vec<String>, // Empty offers every provider
+    #[serde(default)]
+    pub allowed_pairs: Vec<PairRule>, // Empty allows every pair
+    #[serde(default)]
+    pub has_api_key: bool,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// What a client may learn about the brand it is talking to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicBrand {
+    pub name: String,
+    pub support_email: String,
+    pub enabled_providers: Vec<String>,
+    pub allowed_pairs: Vec<PairRule>,
+}
+
+impl Brand {
+    /// The deployment's own brand: no markup, no restrictions
+    pub fn from_config(config: &BrandingConfig) -> Self {
+        Self {
+            slug: DEFAULT_BRAND.to_string(),
+            name: config.name.clone(),
+            support_email: config.support_email.clone(),
+            tenant: TenantId::default(),
+            domains: Vec::new(),
+            markup_percent: None,
+            enabled_providers: Vec::new(),
+            allowed_pairs: Vec::new(),
+            has_api_key: false,
+            updated_at: None,
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.slug == DEFAULT_BRAND
+    }
+
+    /// Slug recorded on swaps; `None` for the deployment's own brand
+    pub fn stored_slug(&self) -> Option<&str> {
+        (!self.is_default()).then_some(self.slug.as_str())
+    }
+
+    pub fn allows_provider(&self, provider: &str) -> bool {
+        self.enabled_providers.is_empty() || self.enabled_providers.iter().any(|p| p.eq_ignore_ascii_case(provider))
+    }
+
+    pub fn allows_pair(&self, from: &str, to: &str) -> bool {
+        self.allowed_pairs.is_empty() || self.allowed_pairs.iter().any(|rule| rule.matches(from, to))
+    }
+
+    pub fn public(&self) -> PublicBrand {
+        PublicBrand {
+            name: self.name.clone(),
+            support_email: self.support_email.clone(),
+            enabled_providers: self.enabled_providers.clone(),
+            allowed_pairs: self.allowed_pairs.clone(),
+        }
+    }
+
+    /// Reject brands that could never be matched or would break quotes
+    pub fn check_rules(&self) -> Result<(), String> {
+        let valid_slug = !self.slug.is_empty()
+            && self.slug.len() <= 50
+            && self.slug.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        if !valid_slug || self.is_default() {
+            return Err(format!(
+                "slug must be 1-50 lowercase letters, digits or '-', and not '{}'",
+                DEFAULT_BRAND
+            ));
+        }
+        if self.name.trim().is_empty() {
+            return Err("name is required".to_string());
+        }
+        if !self.support_email.contains('@') {
+            return Err("support_email must be an email address".to_string());
+        }
+        if let Some(domain) = self.domains.iter().find(|d| normalize_host(d) != **d || d.is_empty()) {
+            return Err(format!("domain '{}' must be a lowercase hostname without scheme or port", domain));
+        }
+        if self.markup_percent.is_some_and(|m| !(0.0..=MAX_MARKUP_PERCENT).contains(&m)) {
+            return Err(format!("markup_percent must be between 0 and {}", MAX_MARKUP_PERCENT));
+        }
+        if self.allowed_pairs.iter().any(|r| r.from.trim().is_empty() || r.to.trim().is_empty()) {
+            return Err("allowed_pairs entries need both from and to (use '*' for any)".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Lowercase host without port or trailing dot
+fn normalize_host(host: &str) -> String {
+    let host = host.trim().to_lowercase();
+    let host = match host.rsplit_once(':') {
+        Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) => name.to_string(),
+        _ => host,
+    };
+    host.trim_end_matches('.').to_string()
+}
+
+pub fn hash_api_key(api_key: &str) -> String {
+    Sha256::digest(api_key.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// A brand plus the key hash used to match it; cached, never served
+#[derive(Serialize, Deserialize)]
+struct BrandEntry {
+    brand: Brand,
+    api_key_hash: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct BrandRow {
+    slug: String,
+    name: String,
+    support_email: String,
+    tenant_id: String,
+    api_key_hash: Option<String>,
+    domains: String,
+    markup_percent: Option<f64>,
+    enabled_providers: String,
+    allowed_pairs: String,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<BrandRow> for BrandEntry {
+    fn from(row: BrandRow) -> Self {
+        Self {
+            brand: Brand {
+                slug: row.slug,
+                name: row.name,
+                support_email: row.support_email,
+                tenant: TenantId::new(&row.tenant_id).unwrap_or_default(),
+                domains: serde_json::from_str(&row.domains).unwrap_or_default(),
+                markup_percent: row.markup_percent,
+                enabled_providers: serde_json::from_str(&row.enabled_providers).unwrap_or_default(),
+                allowed_pairs: serde_json::from_str(&row.allowed_pairs).unwrap_or_default(),
+                has_api_key: row.api_key_hash.is_some(),
+                updated_at: Some(row.updated_at),
+            },
+            api_key_hash: row.api_key_hash,
+        }
+    }
+}
+
+// =============================================================================
+// REGISTRY
+// =============================================================================
+
+#[derive(Clone)]
+pub struct BrandRegistry {
+    pool: DbPool,
+    redis: Option<RedisService>,
+}
+
+impl BrandRegistry {
+    pub fn new(pool: DbPool, redis: Option<RedisService>) -> Self {
+        Self { pool, redis }
+    }
+
+    /// Every partner brand, ordered by slug
+    pub async fn all(&self) -> Result<Vec<Brand>, sqlx::Error> {
+        Ok(self.entries().await?.into_iter().map(|e| e.brand).collect())
+    }
+
+    /// The brand for a request's API key, falling back to its host.
+    /// Lookup failures are logged and treated as "no partner brand".
+    pub async fn resolve(&self, api_key: Option<&str>, host: Option<&str>) -> Option<Brand> {
+        let entries = match self.entries().await {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!("Failed to load brands: {}", e);
+                return None;
+            }
+        };
+
+        if let Some(hash) = api_key.map(hash_api_key) {
+            if let Some(entry) = entries.iter().find(|e| e.api_key_hash.as_deref() == Some(hash.as_str())) {
+                return Some(entry.brand.clone());
+            }
+        }
+
+        let host = normalize_host(host?);
+        entries.into_iter().map(|e| e.brand).find(|b| b.domains.contains(&host))
+    }
+
+    /// The brand holding `api_key`; unlike `resolve`, never matched by host
+    pub async fn find_by_api_key(&self, api_key: &str) -> Result<Option<Brand>, sqlx::Error> {
+        let hash = hash_api_key(api_key);
+        Ok(self
+            .entries()
+            .await?
+            .into_iter()
+            .find(|e| e.api_key_hash.as_deref() == Some(hash.as_str()))
+            .map(|e| e.brand))
+    }
+
+    /// Create or replace a brand; `api_key` replaces the stored key, `None` keeps it
+    pub async fn upsert(&self, brand: &Brand, api_key: Option<&str>) -> Result<Brand, sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO brands (
+                slug, name, support_email, tenant_id, api_key_hash, domains, markup_percent, enabled_providers,
+                allowed_pairs
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                name = VALUES(name), support_email = VALUES(support_email), tenant_id = VALUES(tenant_id),
+                api_key_hash = COALESCE(VALUES(api_key_hash), api_key_hash),
+                domains = VALUES(domains), markup_percent = VALUES(markup_percent),
+                enabled_providers = VALUES(enabled_providers), allowed_pairs = VALUES(allowed_pairs)
+            "#,
+        )
+        .bind(&brand.slug)
+        .bind(brand.name.trim())
+        .bind(brand.support_email.trim())
+        .bind(brand.tenant.as_str())
+        .bind(api_key.map(hash_api_key))
+        .bind(json_list(&brand.domains))
+        .bind(brand.markup_percent)
+        .bind(json_list(&brand.enabled_providers))
+        .bind(json_list(&brand.allowed_pairs))
+        .execute(&self.pool)
+        .await?;
+
+        self.invalidate().await;
+
+        self.find(&brand.slug)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Remove a brand, returning it; `None` when no such brand exists
+    pub async fn delete(&self, slug: &str) -> Result<Option<Brand>, sqlx::Error> {
+        let Some(existing) = self.find(slug).await? else {
+            return Ok(None);
+        };
+
+        sqlx::query("DELETE FROM brands WHERE slug = ?")
+            .bind(slug)
+            .execute(&self.pool)
+            .await?;
+        // A brand created later under the same slug starts without secrets
+        sqlx::query("DELETE FROM brand_webhook_secrets WHERE brand = ?")
+            .bind(slug)
+            .execute(&self.pool)
+            .await?;
+
+        self.invalidate().await;
+        Ok(Some(existing))
+    }
+
+    async fn find(&self, slug: &str) -> Result<Option<Brand>, sqlx::Error> {
+        let row = sqlx::query_as::<_, BrandRow>(&format!("{} WHERE slug = ?", BRAND_SELECT))
+            .bind(slug)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(|row| BrandEntry::from(row).brand))
+    }
+
+    /// Every brand with its key hash, from Redis when cached
+    async fn entries(&self) -> Result<Vec<BrandEntry>, sqlx::Error> {
+        if let Some(redis) = &self.redis {
+            if let Ok(Some(entries)) = redis.get_json::<Vec<BrandEntry>>(CACHE_KEY).await {
+                return Ok(entries);
+            }
+        }
+
+        let entries: Vec<BrandEntry> = sqlx::query_as::<_, BrandRow>(&format!("{} ORDER BY slug", BRAND_SELECT))
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(BrandEntry::from)
+            .collect();
+
+        if let Some(redis) = &self.redis {
+            let _ = redis.set_json(CACHE_KEY, &entries, CACHE_TTL_SECS).await;
+        }
+
+        Ok(entries)
+    }
+
+    async fn invalidate(&self) {
+        if let Some(redis) = &self.redis {
+            if let Err(e) = redis.delete(CACHE_KEY).await {
+                // Instances keep the old brands until the cached copy expires
+                tracing::warn!("Failed to invalidate brand cache: {}", e);
+            }
+        }
+    }
+}
+
+const BRAND_SELECT: &str = r#"
+    SELECT slug, name, support_email, tenant_id, api_key_hash,
+           CAST(domains AS CHAR) AS domains,
+           CAST(markup_percent AS DOUBLE) AS markup_percent,
+           CAST(enabled_providers AS CHAR) AS enabled_providers,
+           CAST(allowed_pairs AS CHAR) AS allowed_pairs,
+           updated_at
+    FROM brands
+"#;
+
+fn json_list<T: Serialize>(items: &[T]) -> String {
+    serde_json::to_string(items).unwrap_or_else(|_| "[]".to_string())
+}
+
+// =============================================================================
+// EXTRACTOR
+// =============================================================================
+
+/// The brand a request is served under; the deployment's own brand (in the
+/// deployment's tenant) when no partner brand matches
+pub struct CurrentBrand(pub Brand);
+
+impl<S> FromRequestParts<S> for CurrentBrand
+where
+    Arc<AppState>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let state = Arc::from_ref(state);
+        let header = |name: &str| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
+
+        let api_key = header("x-api-key");
+        let host = header("x-forwarded-host").or_else(|| header(header::HOST.as_str()));
+
+        let brand = BrandRegistry::new(state.db.clone(), Some(state.redis.clone()))
+            .resolve(api_key, host)
+            .await
+            .unwrap_or_else(|| Brand {
+                tenant: state.tenant.clone(),
+                ..Brand::from_config(&state.branding)
+            });
+
+        Ok(CurrentBrand(brand))
+    }
+}
@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How many one-minute buckets are kept in memory
+const RETAINED_MINUTES: u64 = 60;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Counters {
+    hits: u64,
+    misses: u64,
+    errors: u64,
+    payload_bytes: u64,
+    payloads: u64,
+}
+
+struct MinuteBucket {
+    minute: u64,
+    prefixes: HashMap<String, Counters>,
+}
+
+/// Per-prefix cache counters aggregated over a window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefixCacheStats {
+    pub prefix: String,
+    pub hits: u64,
+    pub misses: u64,
+    pub errors: u64,
+    pub hit_rate: Option<f64>,
+    pub avg_payload_bytes: Option<u64>,
+}
+
+/// In-process cache hit/miss counters, bucketed per minute.
+/// Counts are local to this instance and reset on restart.
+#[derive(Clone, Default)]
+pub struct CacheStats {
+    buckets: Arc<Mutex<VecDeque<MinuteBucket>>>,
+}
+
+impl CacheStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_hit(&self, key: &str, payload_bytes: usize) {
+        self.record(key, |c| {
+            c.hits += 1;
+            c.payload_bytes += payload_bytes as u64;
+            c.payloads += 1;
+        });
+    }
+
+    pub fn record_miss(&self, key: &str) {
+        self.record(key, |c| c.misses += 1);
+    }
+
+    pub fn record_error(&self, key: &str) {
+        self.record(key, |c| c.errors += 1);
+    }
+
+    pub fn record_write(&self, key: &str, payload_bytes: usize) {
+        self.record(key, |c| {
+            c.payload_bytes += payload_bytes as u64;
+            c.payloads += 1;
+        });
+    }
+
+    /// Aggregate counters for the last `minutes` minutes (including the current one)
+    pub fn snapshot(&self, minutes: u64) -> Vec<PrefixCacheStats> {
+        let since = current_minute().saturating_sub(minutes.saturating_sub(1));
+        let mut totals: HashMap<String, Counters> = HashMap::new();
+
+        {
+            let buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+            for bucket in buckets.iter().filter(|b| b.minute >= since) {
+                for (prefix, counters) in &bucket.prefixes {
+                    let total = totals.entry(prefix.clone()).or_default();
+                    total.hits += counters.hits;
+                    total.misses += counters.misses;
+                    total.errors += counters.errors;
+                    total.payload_bytes += counters.payload_bytes;
+                    total.payloads += counters.payloads;
+                }
+            }
+        }
+
+        let mut stats: Vec<PrefixCacheStats> = totals
+            .into_iter()
+            .map(|(prefix, c)| {
+                let lookups = c.hits + c.misses;
+                PrefixCacheStats {
+                    prefix,
+                    hits: c.hits,
+                    misses: c.misses,
+                    errors: c.errors,
+                    hit_rate: (lookups > 0).then(|| c.hits as f64 / lookups as f64),
+                    avg_payload_bytes: (c.payloads > 0).then(|| c.payload_bytes / c.payloads),
+                }
+            })
+            .collect();
+
+        stats.sort_by(|a, b| a.prefix.cmp(&b.prefix));
+        stats
+    }
+
+    fn record(&self, key: &str, update: impl FnOnce(&mut Counters)) {
+        let minute = current_minute();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        if buckets.back().map(|b| b.minute) != Some(minute) {
+            buckets.push_back(MinuteBucket { minute, prefixes: HashMap::new() });
+        }
+        while buckets.front().is_some_and(|b| b.minute + RETAINED_MINUTES <= minute) {
+            buckets.pop_front();
+        }
+
+        if let Some(bucket) = buckets.back_mut() {
+            update(bucket.prefixes.entry(key_prefix(key).to_string()).or_default());
+        }
+    }
+}
+
+/// "rates:btc:eth:..." -> "rates:"; keys without a separator are reported as-is
+fn key_prefix(key: &str) -> &str {
+    match key.find(':') {
+        Some(idx) => &key[..=idx],
+        None => key,
+    }
+}
+
+fn current_minute() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() / 60)
+        .unwrap_or(0)
+}
@@ -0,0 +1,149 @@
+//! Startup cache priming.
+//!
+//! After a deploy every cache is cold, and the first wave of requests would
+//! all fall through to MySQL (and Trocador) at once. On startup the server
+//! fills the shared caches — currency lists, provider list, maintenance mode,
+//! address formats — while `/ready` answers 503 `warming`, so the load
+//! balancer only sends traffic once the fast paths are hot. A failed or slow
+//! warmup still ends with the instance ready; the caches fill on demand.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+use crate::config::DbPool;
+use crate::modules::swap::crud::SwapCrud;
+use crate::modules::swap::schema::{CurrenciesQuery, ProvidersQuery};
+use crate::services::address_format::AddressFormatRegistry;
+use crate::services::maintenance::MaintenanceService;
+use crate::services::redis_cache::RedisService;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarmupStatus {
+    #[default]
+    NotStarted, // Warmup disabled (or not this process's job); readiness is unaffected
+    Warming,
+    Done,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WarmupEntry {
+    pub cache: String,
+    pub ok: bool,
+    pub duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WarmupSnapshot {
+    pub status: WarmupStatus,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub timed_out: bool,
+    pub entries: Vec<WarmupEntry>,
+}
+
+#[derive(Default)]
+pub struct CacheWarmup {
+    state: Mutex<WarmupSnapshot>,
+}
+
+static WARMUP: LazyLock<CacheWarmup> = LazyLock::new(CacheWarmup::default);
+
+/// Process-wide warmup state read by `/ready`
+pub fn warmup() -> &'static CacheWarmup {
+    &WARMUP
+}
+
+impl CacheWarmup {
+    pub fn snapshot(&self) -> WarmupSnapshot {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Mark the process warming; a no-op while a warmup is already running
+    pub fn begin(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.status != WarmupStatus::Warming {
+            *state = WarmupSnapshot {
+                status: WarmupStatus::Warming,
+                started_at: Some(Utc::now()),
+                ..WarmupSnapshot::default()
+            };
+        }
+    }
+
+    pub fn finish(&self, entries: Vec<WarmupEntry>, timed_out: bool) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.status = WarmupStatus::Done;
+        state.finished_at = Some(Utc::now());
+        state.timed_out = timed_out;
+        state.entries = entries;
+    }
+}
+
+/// Mark the process warming now, before the listener accepts `/ready`
+/// probes, and prime the caches in the background
+pub fn spawn_cache_warmup(pool: DbPool, redis: RedisService, timeout: Duration) -> JoinHandle<WarmupSnapshot> {
+    warmup().begin();
+    tokio::spawn(prime_caches(pool, redis, timeout))
+}
+
+/// Prime every shared cache, giving up after `timeout`. Marks the process
+/// warming for the duration and returns what was primed.
+pub async fn prime_caches(pool: DbPool, redis: RedisService, timeout: Duration) -> WarmupSnapshot {
+    warmup().begin();
+    tracing::info!("Priming caches (timeout {:?})", timeout);
+
+    let swaps = SwapCrud::new(pool.clone(), Some(redis.clone()));
+    let maintenance = MaintenanceService::new(pool.clone(), redis.clone());
+    let formats = AddressFormatRegistry::new(pool, Some(redis));
+
+    let primed = tokio::time::timeout(timeout, async {
+        let (currencies, grouped, providers, maintenance, formats) = tokio::join!(
+            prime("currencies", swaps.get_currencies_optimized(CurrenciesQuery::default())),
+            prime("currencies_grouped", swaps.get_currencies_grouped(CurrenciesQuery::default())),
+            prime("providers", swaps.get_providers_optimized(ProvidersQuery { rating: None, markup_enabled: None, sort: None })),
+            prime("maintenance", async { Ok::<_, String>(maintenance.current().await) }),
+            prime("address_formats", formats.all()),
+        );
+        vec![currencies, grouped, providers, maintenance, formats]
+    })
+    .await;
+
+    let (entries, timed_out) = match primed {
+        Ok(entries) => (entries, false),
+        Err(_) => {
+            tracing::warn!("Cache priming exceeded {:?}; serving with a partly cold cache", timeout);
+            (Vec::new(), true)
+        }
+    };
+
+    for entry in entries.iter().filter(|e| !e.ok) {
+        tracing::warn!("Failed to prime {} cache: {}", entry.cache, entry.error.as_deref().unwrap_or(""));
+    }
+
+    warmup().finish(entries, timed_out);
+    let snapshot = warmup().snapshot();
+    tracing::info!(
+        "Cache priming finished: {}/{} caches primed",
+        snapshot.entries.iter().filter(|e| e.ok).count(),
+        snapshot.entries.len()
+    );
+    snapshot
+}
+
+async fn prime<T, E: std::fmt::Display>(cache: &str, load: impl Future<Output = Result<T, E>>) -> WarmupEntry {
+    let started = Instant::now();
+    let result = load.await;
+    WarmupEntry {
+        cache: cache.to_string(),
+        ok: result.is_ok(),
+        duration_ms: started.elapsed().as_millis() as u64,
+        error: result.err().map(|e| e.to_string()),
+    }
+}
@@ -0,0 +1,146 @@
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use rust_decimal::Decimal;
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::modules::swap::schema::{RateType, RatesQuery};
+use crate::services::swap_provider::{AggregatorQuote, AggregatorQuotes, SwapProviderClient};
+
+/// Name ChangeNOW quotes are listed under, matching Trocador's provider name
+const PROVIDER_NAME: &str = "ChangeNOW";
+
+/// ChangeNOW API (v2) client
+/// Quotes ChangeNOW directly as a second aggregator next to Trocador
+pub struct ChangeNowClient {
+    client: Client,
+    api_key: String,
+    base_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EstimatedAmount {
+    from_amount: Decimal,
+    to_amount: Decimal,
+    transaction_speed_forecast: Option<String>, // Minutes, e.g. "10-60"
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExchangeRange {
+    min_amount: Decimal,
+    max_amount: Option<Decimal>,
+}
+
+impl ChangeNowClient {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            client: Client::new(),
+            api_key,
+            base_url: "https://api.changenow.io".to_string(),
+        }
+    }
+
+    /// GET a v2 endpoint; `Ok(None)` when ChangeNOW rejects the pair or amount
+    async fn get<T: DeserializeOwned>(&self, path: &str, params: &[(&str, String)]) -> Result<Option<T>, String> {
+        let response = self
+            .client
+            .get(format!("{}{}", self.base_url, path))
+            .header("x-changenow-api-key", &self.api_key)
+            .query(params)
+            .send()
+            .await
+            .map_err(|e| format!("HTTP error: {}", e))?;
+
+        match response.status() {
+            StatusCode::BAD_REQUEST => return Ok(None),
+            status if !status.is_success() => {
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(format!("API error: {} {}", status, error_text));
+            }
+            _ => {}
+        }
+
+        response.json().await.map(Some).map_err(|e| format!("Parse error: {}", e))
+    }
+}
+
+#[async_trait]
+impl SwapProviderClient for ChangeNowClient {
+    fn aggregator(&self) -> &'static str {
+        "changenow"
+    }
+
+    async fn get_quotes(&self, query: &RatesQuery) -> Result<AggregatorQuotes, String> {
+        // Reverse estimates (by receive amount) exist for fixed rates only
+        let flow = if query.amount_to.is_some() || query.rate_type == Some(RateType::Fixed) {
+            "fixed-rate"
+        } else {
+            "standard"
+        };
+        let pair = vec![
+            ("fromCurrency", query.from.to_lowercase()),
+            ("toCurrency", query.to.to_lowercase()),
+            ("fromNetwork", network_code(&query.from, &query.network_from)),
+            ("toNetwork", network_code(&query.to, &query.network_to)),
+            ("flow", flow.to_string()),
+        ];
+        let mut estimate_params = pair.clone();
+        match query.amount_to {
+            Some(amount_to) => {
+                estimate_params.push(("toAmount", amount_to.to_string()));
+                estimate_params.push(("type", "reverse".to_string()));
+            }
+            None => {
+                estimate_params.push(("fromAmount", query.amount.to_string()));
+                estimate_params.push(("type", "direct".to_string()));
+            }
+        }
+
+        let (estimate, range) = tokio::join!(
+            self.get::<EstimatedAmount>("/v2/exchange/estimated-amount", &estimate_params),
+            self.get::<ExchangeRange>("/v2/exchange/range", &pair),
+        );
+        let Some(estimate) = estimate? else {
+            return Ok(AggregatorQuotes::default());
+        };
+        // Limits only inform the quote; without them it still stands
+        let range = range.ok().flatten();
+
+        Ok(AggregatorQuotes {
+            trade_id: None,
+            quotes: vec![AggregatorQuote {
+                provider: PROVIDER_NAME.to_string(),
+                amount_to: estimate.to_amount,
+                amount_from: query.amount_to.map(|_| estimate.from_amount),
+                min_amount: range.as_ref().map_or(Decimal::ZERO, |r| r.min_amount),
+                max_amount: range.and_then(|r| r.max_amount).unwrap_or_default(),
+                provider_fee: Decimal::ZERO, // Already taken out of to_amount
+                kyc_rating: None,
+                eta_minutes: estimate.transaction_speed_forecast.as_deref().and_then(eta_upper_bound),
+            }],
+        })
+    }
+}
+
+/// ChangeNOW names a network by its native ticker, e.g. "eth" for ERC20
+/// tokens; Trocador's "Mainnet" is the coin's own chain
+fn network_code(ticker: &str, network: &str) -> String {
+    let code = match network.to_ascii_lowercase().as_str() {
+        "mainnet" => return ticker.to_lowercase(),
+        "erc20" => "eth",
+        "trc20" => "trx",
+        "bep20" | "bsc" => "bsc",
+        "bep2" => "bnb",
+        "polygon" | "matic" => "matic",
+        "optimism" => "op",
+        "solana" | "spl" => "sol",
+        other => return other.to_string(),
+    };
+    code.to_string()
+}
+
+/// Upper end of a "10-60" minute forecast
+fn eta_upper_bound(forecast: &str) -> Option<u32> {
+    forecast.rsplit('-').next()?.trim().parse().ok()
+}
@@ -0,0 +1,165 @@
+//! Circuit breakers around external provider calls.
+//!
+//! One breaker per provider API ("trocador", "changenow"), with its state in
+//! Redis so every instance sees the same circuit:
+//!
+//! - **Closed**: calls go out. Outages (transport errors, 5xx answers) are
+//!   counted; `failure_threshold` of them within `window_secs` of each other
+//!   open the circuit.
+//! - **Open**: calls fail fast for `open_secs` without reaching the provider.
+//! - **Half-open**: once the open period lapses, one caller across all
+//!   instances is let through as a probe. Any answer from the provider closes
+//!   the circuit; another outage opens it again.
+//!
+//! Rejections the provider answers itself (unknown pair, amount out of
+//! range) say nothing about its health and never count. Without Redis, or
+//! when Redis errors, the circuit stays closed.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::services::redis_cache::RedisService;
+
+/// How long a tripped circuit keeps half-opening before it is forgotten
+const TRIPPED_TTL_SECS: u64 = 86_400;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl std::fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitState::Closed => write!(f, "closed"),
+            CircuitState::Open => write!(f, "open"),
+            CircuitState::HalfOpen => write!(f, "half-open"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CircuitSettings {
+    pub failure_threshold: u32,
+    pub window_secs: u64,
+    pub open_secs: u64,
+}
+
+impl CircuitSettings {
+    /// CIRCUIT_BREAKER_FAILURE_THRESHOLD (default 5), CIRCUIT_BREAKER_WINDOW_SECS
+    /// (default 60), CIRCUIT_BREAKER_OPEN_SECS (default 30)
+    pub fn from_env() -> Self {
+        let env = |name: &str, default: u64| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(default)
+        };
+        Self {
+            failure_threshold: env("CIRCUIT_BREAKER_FAILURE_THRESHOLD", 5) as u32,
+            window_secs: env("CIRCUIT_BREAKER_WINDOW_SECS", 60),
+            open_secs: env("CIRCUIT_BREAKER_OPEN_SECS", 30),
+        }
+    }
+}
+
+/// Leave to send a call; pass it back with the outcome
+#[derive(Debug, Clone, Copy)]
+pub struct Permit {
+    probe: bool, // This call decides whether a half-open circuit closes
+}
+
+pub struct CircuitBreaker {
+    redis: Option<RedisService>,
+    name: String,
+    settings: CircuitSettings,
+}
+
+impl CircuitBreaker {
+    pub fn new(redis: Option<RedisService>, name: &str) -> Self {
+        Self { redis, name: name.to_string(), settings: CircuitSettings::from_env() }
+    }
+
+    fn key(&self, part: &str) -> String {
+        format!("circuit:{}:{}", self.name, part)
+    }
+
+    async fn exists(redis: &RedisService, key: &str) -> bool {
+        matches!(redis.get_string(key).await, Ok(Some(_)))
+    }
+
+    pub async fn state(&self) -> CircuitState {
+        let Some(redis) = &self.redis else {
+            return CircuitState::Closed;
+        };
+        if Self::exists(redis, &self.key("open")).await {
+            CircuitState::Open
+        } else if Self::exists(redis, &self.key("tripped")).await {
+            CircuitState::HalfOpen
+        } else {
+            CircuitState::Closed
+        }
+    }
+
+    /// Whether a call may go out now; the state that refused it otherwise.
+    /// A half-open circuit lets one caller through and refuses the rest.
+    pub async fn acquire(&self) -> Result<Permit, CircuitState> {
+        let Some(redis) = &self.redis else {
+            return Ok(Permit { probe: false });
+        };
+
+        match self.state().await {
+            CircuitState::Closed => Ok(Permit { probe: false }),
+            CircuitState::Open => Err(CircuitState::Open),
+            CircuitState::HalfOpen => match redis.try_lock(&self.key("probe"), self.settings.open_secs).await {
+                Ok(true) => Ok(Permit { probe: true }),
+                Ok(false) => Err(CircuitState::HalfOpen),
+                Err(_) => Ok(Permit { probe: false }),
+            },
+        }
+    }
+
+    /// The provider answered; a successful probe closes the circuit
+    pub async fn record_success(&self, permit: Permit) {
+        let Some(redis) = &self.redis else {
+            return;
+        };
+        if permit.probe {
+            for part in ["tripped", "failures", "probe"] {
+                let _ = redis.delete(&self.key(part)).await;
+            }
+            tracing::info!("Circuit for {} closed", self.name);
+        }
+    }
+
+    /// The provider could not be reached; enough of these open the circuit
+    pub async fn record_failure(&self, permit: Permit) {
+        let Some(redis) = &self.redis else {
+            return;
+        };
+
+        if !permit.probe {
+            let failures = redis.incr_with_ttl(&self.key("failures"), self.settings.window_secs).await;
+            if !matches!(failures, Ok(n) if n >= self.settings.failure_threshold as i64) {
+                return;
+            }
+        }
+
+        let _ = redis.set_string(&self.key("open"), "1", self.settings.open_secs).await;
+        let _ = redis.set_string(&self.key("tripped"), "1", TRIPPED_TTL_SECS).await;
+        let _ = redis.delete(&self.key("failures")).await;
+        let _ = redis.delete(&self.key("probe")).await;
+        tracing::warn!("Circuit for {} opened for {}s", self.name, self.settings.open_secs);
+    }
+}
+
+/// Whether an error means the provider is unreachable or failing, as opposed
+/// to answering with a rejection: transport errors and 5xx statuses
+pub fn is_outage(error: &str) -> bool {
+    static SERVER_ERROR: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"(?:status:|API error:)\s*5\d\d\b").expect("valid regex"));
+    error.starts_with("HTTP error") || SERVER_ERROR.is_match(error)
+}
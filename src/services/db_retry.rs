@@ -0,0 +1,76 @@
+//! Retries of transient MySQL errors.
+//!
+//! A deadlock (1213) or lock wait timeout (1205) rolls the statement back,
+//! and a dropped connection fails it before the server answers; running it
+//! again usually succeeds. Such failures are retried a few times with
+//! jittered exponential backoff so a brief database hiccup does not reach
+//! the user as a 500. Every other error is returned at once. Statements
+//! retried after a dropped connection may already have been applied, so
+//! callers only wrap statements that are safe to run twice.
+
+use std::future::Future;
+use std::time::Duration;
+
+use sqlx::mysql::MySqlDatabaseError;
+
+use crate::config::environment::DbRetryConfig;
+use crate::services::metrics::metrics;
+
+/// MySQL error numbers worth retrying
+const ER_LOCK_DEADLOCK: u16 = 1213;
+const ER_LOCK_WAIT_TIMEOUT: u16 = 1205;
+
+/// Why an error is worth retrying ("deadlock", "lock_wait_timeout",
+/// "connection"); `None` when it is not
+pub fn transient_reason(error: &sqlx::Error) -> Option<&'static str> {
+    match error {
+        sqlx::Error::Database(db) => match db.try_downcast_ref::<MySqlDatabaseError>()?.number() {
+            ER_LOCK_DEADLOCK => Some("deadlock"),
+            ER_LOCK_WAIT_TIMEOUT => Some("lock_wait_timeout"),
+            _ => None,
+        },
+        sqlx::Error::Io(_) => Some("connection"),
+        _ => None,
+    }
+}
+
+/// Delay before retry `attempt` (1-based): the base doubled per earlier
+/// retry, capped, then a random point in its upper half so writers that
+/// collided do not collide again
+pub fn backoff(config: &DbRetryConfig, attempt: u32) -> Duration {
+    let ceiling = config.base_delay.saturating_mul(1 << attempt.saturating_sub(1).min(16)).min(config.max_delay);
+    let millis = ceiling.as_millis() as u64;
+    Duration::from_millis(rand::random_range(millis / 2..=millis))
+}
+
+/// Run `f`, running it again on a transient error up to `max_attempts` in all
+pub async fn retry_transient<T, F, Fut>(config: &DbRetryConfig, operation: &str, f: F) -> Result<T, sqlx::Error>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, sqlx::Error>>,
+{
+    let mut attempt = 1;
+    loop {
+        match f().await {
+            Err(e) if attempt < config.max_attempts => {
+                let Some(reason) = transient_reason(&e) else {
+                    return Err(e);
+                };
+                metrics().db_retries.inc(reason);
+                let delay = backoff(config, attempt);
+                tracing::warn!(
+                    "{} failed ({}), retrying in {:?} (attempt {}/{}): {}",
+                    operation,
+                    reason,
+                    delay,
+                    attempt,
+                    config.max_attempts,
+                    e
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! Deposit checks against public block explorers.
+//!
+//! When a provider reports a swap as underpaid, the deposit address is looked
+//! up on a public explorer to confirm what actually arrived before anyone
+//! asks the user to send more. Nothing is signed or held: explorers are only
+//! read. The evidence (amounts received, unspent outputs, shortfall) is kept
+//! in `swap_deposit_checks`, one row per swap replaced by each new check, and
+//! served at `GET /admin/swaps/{id}/deposit-check` so support does not have
+//! to look the address up by hand.
+//!
+//! Explorers sit behind [`DepositExplorer`]; Bitcoin and Litecoin are read
+//! from Esplora instances. Currencies without an explorer are not checked.
+//! Provider deposit addresses are normally single-use, so everything the
+//! address ever received counts towards the swap.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::config::environment::DepositCheckConfig;
+use crate::config::DbPool;
+use crate::modules::swap::model::Swap;
+
+/// Decimal places of a satoshi (and litoshi) amount in coins
+const SATS_SCALE: u32 = 8;
+
+/// Unspent outputs kept as evidence; the totals still count every output
+const MAX_LISTED_UTXOS: usize = 50;
+
+#[async_trait]
+pub trait DepositExplorer: Send + Sync {
+    /// Name recorded with the evidence, e.g. "esplora:btc"
+    fn name(&self) -> &str;
+
+    /// Whether this explorer reads the chain of a currency on a network
+    fn supports(&self, currency: &str, network: &str) -> bool;
+
+    /// What `address` has received so far
+    async fn lookup(&self, address: &str) -> Result<AddressActivity, String>;
+}
+
+/// An address as an explorer sees it, in coin units
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AddressActivity {
+    pub received_confirmed: Decimal,
+    pub received_unconfirmed: Decimal, // Still in the mempool
+    pub tx_count: u32,
+    pub utxos: Vec<Utxo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Utxo {
+    pub txid: String,
+    pub vout: u32,
+    pub value: Decimal,
+    pub confirmed: bool,
+}
+
+/// Why a check was run
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckTrigger {
+    Underpayment, // The provider reported the swap paid partially
+    Admin,        // POST /admin/swaps/{id}/deposit-check
+}
+
+/// What an explorer showed for a swap's deposit address
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DepositEvidence {
+    pub swap_id: String,
+    pub explorer: String,
+    pub currency: String,
+    pub network: String,
+    pub address: String,
+    pub expected_amount: Decimal,
+    pub received_confirmed: Decimal,
+    pub received_unconfirmed: Decimal,
+    pub shortfall: Decimal, // Expected minus everything received; 0 when fully paid
+    pub tx_count: u32,
+    pub utxos: Vec<Utxo>,
+    pub trigger: CheckTrigger,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl DepositEvidence {
+    pub fn new(swap: &Swap, explorer: &str, mut activity: AddressActivity, trigger: CheckTrigger) -> Self {
+        let received = activity.received_confirmed + activity.received_unconfirmed;
+        activity.utxos.truncate(MAX_LISTED_UTXOS);
+        Self {
+            swap_id: swap.id.clone(),
+            explorer: explorer.to_string(),
+            currency: swap.from_currency.clone(),
+            network: swap.from_network.clone(),
+            address: swap.deposit_address.clone(),
+            expected_amount: swap.amount,
+            received_confirmed: activity.received_confirmed,
+            received_unconfirmed: activity.received_unconfirmed,
+            shortfall: (swap.amount - received).max(Decimal::ZERO),
+            tx_count: activity.tx_count,
+            utxos: activity.utxos,
+            trigger,
+            checked_at: Utc::now(),
+        }
+    }
+
+    /// The address received less than the swap expects
+    pub fn is_short(&self) -> bool {
+        self.shortfall > Decimal::ZERO
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DepositCheckError {
+    #[error("Deposit checks are disabled")]
+    Disabled,
+
+    #[error("No explorer for {0} on {1}")]
+    Unsupported(String, String),
+
+    #[error("Explorer error: {0}")]
+    Explorer(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+// =============================================================================
+// ESPLORA
+// =============================================================================
+
+/// Esplora REST API (blockstream.info, mempool.space and their Litecoin forks)
+pub struct EsploraExplorer {
+    client: reqwest::Client,
+    name: String,
+    ticker: String,
+    base_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct EsploraAddress {
+    chain_stats: EsploraStats,
+    mempool_stats: EsploraStats,
+}
+
+#[derive(Debug, Deserialize)]
+struct EsploraStats {
+    funded_txo_sum: u64,
+    tx_count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct EsploraUtxo {
+    txid: String,
+    vout: u32,
+    value: u64,
+    status: EsploraTxStatus,
+}
+
+#[derive(Debug, Deserialize)]
+struct EsploraTxStatus {
+    confirmed: bool,
+}
+
+impl EsploraExplorer {
+    /// Explorer for the native coin `ticker` at `base_url`, e.g.
+    /// "https://blockstream.info/api"
+    pub fn new(client: reqwest::Client, ticker: &str, base_url: &str) -> Self {
+        Self {
+            client,
+            name: format!("esplora:{}", ticker.to_lowercase()),
+            ticker: ticker.to_lowercase(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, String> {
+        let response = self
+            .client
+            .get(format!("{}{}", self.base_url, path))
+            .send()
+            .await
+            .map_err(|e| format!("HTTP error: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("API error: {}", response.status()));
+        }
+        response.json().await.map_err(|e| format!("Parse error: {}", e))
+    }
+}
+
+#[async_trait]
+impl DepositExplorer for EsploraExplorer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn supports(&self, currency: &str, network: &str) -> bool {
+        currency.eq_ignore_ascii_case(&self.ticker) && network.eq_ignore_ascii_case("mainnet")
+    }
+
+    async fn lookup(&self, address: &str) -> Result<AddressActivity, String> {
+        let (stats_path, utxo_path) = (format!("/address/{}", address), format!("/address/{}/utxo", address));
+        let (stats, utxos) =
+            tokio::try_join!(self.get::<EsploraAddress>(&stats_path), self.get::<Vec<EsploraUtxo>>(&utxo_path))?;
+
+        Ok(AddressActivity {
+            received_confirmed: coins(stats.chain_stats.funded_txo_sum),
+            received_unconfirmed: coins(stats.mempool_stats.funded_txo_sum),
+            tx_count: stats.chain_stats.tx_count + stats.mempool_stats.tx_count,
+            utxos: utxos
+                .into_iter()
+                .map(|u| Utxo { txid: u.txid, vout: u.vout, value: coins(u.value), confirmed: u.status.confirmed })
+                .collect(),
+        })
+    }
+}
+
+/// A satoshi amount in coins, exactly
+fn coins(sats: u64) -> Decimal {
+    Decimal::from(sats) / Decimal::from(10u64.pow(SATS_SCALE))
+}
+
+/// Explorers for DEPOSIT_CHECK_BTC_URL and DEPOSIT_CHECK_LTC_URL; an empty
+/// URL leaves that chain unchecked
+pub fn configured_explorers(config: &DepositCheckConfig) -> Vec<Box<dyn DepositExplorer>> {
+    let client = reqwest::Client::builder().timeout(config.timeout).build().unwrap_or_default();
+    let mut explorers: Vec<Box<dyn DepositExplorer>> = Vec::new();
+
+    if let Some(url) = &config.btc_url {
+        explorers.push(Box::new(EsploraExplorer::new(client.clone(), "btc", url)));
+    }
+    if let Some(url) = &config.ltc_url {
+        explorers.push(Box::new(EsploraExplorer::new(client, "ltc", url)));
+    }
+
+    explorers
+}
+
+// =============================================================================
+// CHECKS
+// =============================================================================
+
+pub struct DepositChecker {
+    pool: DbPool,
+    enabled: bool,
+    explorers: Vec<Box<dyn DepositExplorer>>,
+}
+
+impl DepositChecker {
+    pub fn new(pool: DbPool, explorers: Vec<Box<dyn DepositExplorer>>) -> Self {
+        Self { pool, enabled: true, explorers }
+    }
+
+    pub fn from_config(config: &DepositCheckConfig, pool: DbPool) -> Self {
+        Self { pool, enabled: config.enabled, explorers: configured_explorers(config) }
+    }
+
+    /// Look up a swap's deposit address and store what was found in place of
+    /// the previous check
+    pub async fn check(&self, swap: &Swap, trigger: CheckTrigger) -> Result<DepositEvidence, DepositCheckError> {
+        if !self.enabled {
+            return Err(DepositCheckError::Disabled);
+        }
+        let explorer = self
+            .explorers
+            .iter()
+            .find(|e| e.supports(&swap.from_currency, &swap.from_network))
+            .ok_or_else(|| DepositCheckError::Unsupported(swap.from_currency.clone(), swap.from_network.clone()))?;
+
+        let activity = explorer.lookup(&swap.deposit_address).await.map_err(DepositCheckError::Explorer)?;
+        let evidence = DepositEvidence::new(swap, explorer.name(), activity, trigger);
+        self.save(&evidence).await?;
+
+        Ok(evidence)
+    }
+
+    async fn save(&self, evidence: &DepositEvidence) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO swap_deposit_checks (swap_id, explorer, shortfall, evidence, checked_at) VALUES (?, ?, ?, ?, ?)
+             ON DUPLICATE KEY UPDATE explorer = VALUES(explorer), shortfall = VALUES(shortfall),
+                                     evidence = VALUES(evidence), checked_at = VALUES(checked_at)",
+        )
+        .bind(&evidence.swap_id)
+        .bind(&evidence.explorer)
+        .bind(evidence.shortfall)
+        .bind(serde_json::to_string(evidence).unwrap_or_default())
+        .bind(evidence.checked_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// The latest check of a swap's deposit, if it was ever checked
+    pub async fn get(&self, swap_id: &str) -> Result<Option<DepositEvidence>, sqlx::Error> {
+        let evidence: Option<String> =
+            sqlx::query_scalar("SELECT CAST(evidence AS CHAR) FROM swap_deposit_checks WHERE swap_id = ?")
+                .bind(swap_id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(evidence.and_then(|e| serde_json::from_str(&e).ok()))
+    }
+}
@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+
+use super::{EmailError, EmailMessage, EmailSender};
+use crate::config::environment::EmailBackend;
+
+/// Development backend: logs the message instead of delivering it
+pub struct LogSender;
+
+#[async_trait]
+impl EmailSender for LogSender {
+    fn backend(&self) -> EmailBackend {
+        EmailBackend::Log
+    }
+
+    async fn send(&self, from: &str, message: &EmailMessage) -> Result<(), EmailError> {
+        tracing::info!(
+            target: "email",
+            from = from,
+            to = %message.to,
+            subject = %message.subject,
+            body = %message.text_body,
+            "Email (log backend, not sent)"
+        );
+        Ok(())
+    }
+}
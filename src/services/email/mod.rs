@@ -0,0 +1,191 @@
+//! Outgoing email behind a pluggable backend (log, SMTP, SES).
+//!
+//! `EmailService` wraps the configured backend with the suppression list
+//! (addresses that hard-bounced or complained) and a per-backend send rate
+//! limit shared by all instances through Redis.
+
+mod log;
+mod ses;
+mod smtp;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::config::environment::{EmailBackend, EmailConfig};
+use crate::config::DbPool;
+use crate::services::branding::Brand;
+use crate::services::redis_cache::RedisService;
+
+pub use log::LogSender;
+pub use ses::SesSender;
+pub use smtp::SmtpSender;
+
+// =============================================================================
+// MESSAGE & ERRORS
+// =============================================================================
+
+#[derive(Debug, Clone)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: String,
+    pub text_body: String,
+}
+
+#[derive(Debug)]
+pub enum EmailError {
+    Suppressed(String),
+    RateLimited(EmailBackend),
+    Config(String),
+    Transport(String),
+    DatabaseError(String),
+}
+
+impl std::fmt::Display for EmailError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmailError::Suppressed(to) => write!(f, "{} is on the suppression list", to),
+            EmailError::RateLimited(backend) => write!(f, "Email rate limit reached for {}", backend.as_str()),
+            EmailError::Config(e) => write!(f, "Email configuration error: {}", e),
+            EmailError::Transport(e) => write!(f, "Email transport error: {}", e),
+            EmailError::DatabaseError(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for EmailError {}
+
+// =============================================================================
+// SENDER TRAIT
+// =============================================================================
+
+#[async_trait]
+pub trait EmailSender: Send + Sync {
+    fn backend(&self) -> EmailBackend;
+
+    async fn send(&self, from: &str, message: &EmailMessage) -> Result<(), EmailError>;
+}
+
+// =============================================================================
+// SUPPRESSION
+// =============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum SuppressionReason {
+    Bounce,
+    Complaint,
+}
+
+// =============================================================================
+// EMAIL SERVICE
+// =============================================================================
+
+#[derive(Clone)]
+pub struct EmailService {
+    sender: Arc<dyn EmailSender>,
+    from_address: String,
+    rate_limit_per_minute: u32,
+    db: DbPool,
+    redis: RedisService,
+}
+
+impl EmailService {
+    pub fn from_config(config: &EmailConfig, db: DbPool, redis: RedisService) -> Result<Self, EmailError> {
+        let sender: Arc<dyn EmailSender> = match config.backend {
+            EmailBackend::Log => Arc::new(LogSender),
+            EmailBackend::Smtp => Arc::new(SmtpSender::new(&config.smtp)?),
+            EmailBackend::Ses => Arc::new(SesSender::new(&config.ses)?),
+        };
+
+        Ok(Self::with_sender(sender, config, db, redis))
+    }
+
+    pub fn with_sender(sender: Arc<dyn EmailSender>, config: &EmailConfig, db: DbPool, redis: RedisService) -> Self {
+        Self {
+            sender,
+            from_address: config.from_address.clone(),
+            rate_limit_per_minute: config.rate_limit_per_minute,
+            db,
+            redis,
+        }
+    }
+
+    pub fn backend(&self) -> EmailBackend {
+        self.sender.backend()
+    }
+
+    /// Send unless the recipient is suppressed or the backend's rate limit is used up
+    pub async fn send(&self, message: &EmailMessage) -> Result<(), EmailError> {
+        self.send_from(&self.from_address, message).await
+    }
+
+    /// `send` under a white-label brand: the brand's name on the From header
+    /// and its support address at the end of the body
+    pub async fn send_branded(&self, brand: &Brand, message: &EmailMessage) -> Result<(), EmailError> {
+        let display_name: String = brand.name.chars().filter(|c| !matches!(c, '"' | '<' | '>' | '\\')).collect();
+        let from = format!("\"{}\" <{}>", display_name.trim(), self.from_address);
+
+        let mut branded = message.clone();
+        branded.text_body = format!(
+            "{}\n\n--\n{}\nQuestions? Contact {}\n",
+            message.text_body.trim_end(),
+            brand.name,
+            brand.support_email
+        );
+
+        self.send_from(&from, &branded).await
+    }
+
+    async fn send_from(&self, from: &str, message: &EmailMessage) -> Result<(), EmailError> {
+        if self.is_suppressed(&message.to).await? {
+            return Err(EmailError::Suppressed(message.to.clone()));
+        }
+
+        let backend = self.sender.backend();
+        let key = format!("rate_limit:email:{}", backend.as_str());
+        match self.redis.check_rate_limit(&key, self.rate_limit_per_minute, 60).await {
+            Ok(true) => {}
+            Ok(false) => return Err(EmailError::RateLimited(backend)),
+            // Redis down: fail open rather than dropping transactional mail
+            Err(e) => tracing::warn!("Email rate limit check failed: {}", e),
+        }
+
+        self.sender.send(from, message).await
+    }
+
+    pub async fn is_suppressed(&self, email: &str) -> Result<bool, EmailError> {
+        let row: Option<(String,)> = sqlx::query_as("SELECT email FROM email_suppressions WHERE email = ?")
+            .bind(email.trim().to_lowercase())
+            .fetch_optional(&self.db)
+            .await
+            .map_err(|e| EmailError::DatabaseError(e.to_string()))?;
+
+        Ok(row.is_some())
+    }
+
+    /// Stop sending to an address; a later event for the same address replaces the reason
+    pub async fn suppress(
+        &self,
+        email: &str,
+        reason: SuppressionReason,
+        source: EmailBackend,
+        detail: Option<&str>,
+    ) -> Result<(), EmailError> {
+        sqlx::query(
+            "INSERT INTO email_suppressions (email, reason, source, detail) VALUES (?, ?, ?, ?)
+             ON DUPLICATE KEY UPDATE reason = VALUES(reason), source = VALUES(source), detail = VALUES(detail)",
+        )
+        .bind(email.trim().to_lowercase())
+        .bind(reason)
+        .bind(source.as_str())
+        .bind(detail)
+        .execute(&self.db)
+        .await
+        .map_err(|e| EmailError::DatabaseError(e.to_string()))?;
+
+        tracing::info!(target: "email", reason = ?reason, source = source.as_str(), "Suppressed email address");
+        Ok(())
+    }
+}
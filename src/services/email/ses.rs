@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use super::{EmailError, EmailMessage, EmailSender};
+use crate::config::environment::{EmailBackend, SesConfig};
+
+/// Amazon SES v2 `SendEmail` over HTTPS, signed with SigV4
+pub struct SesSender {
+    http: reqwest::Client,
+    config: SesConfig,
+    host: String,
+}
+
+const SEND_EMAIL_PATH: &str = "/v2/email/outbound-emails";
+
+impl SesSender {
+    pub fn new(config: &SesConfig) -> Result<Self, EmailError> {
+        if config.access_key_id.is_empty() || config.secret_access_key.is_empty() {
+            return Err(EmailError::Config(
+                "AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY must be set for the ses backend".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            http: reqwest::Client::new(),
+            host: format!("email.{}.amazonaws.com", config.region),
+            config: config.clone(),
+        })
+    }
+
+    /// Headers to send, including `authorization`, for a POST of `body` to SEND_EMAIL_PATH
+    fn signed_headers(&self, body: &str) -> Vec<(&'static str, String)> {
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date_stamp = now.format("%Y%m%d").to_string();
+
+        // Sorted by header name, as SigV4 requires
+        let mut headers = vec![
+            ("content-type", "application/json".to_string()),
+            ("host", self.host.clone()),
+            ("x-amz-date", amz_date.clone()),
+        ];
+        if let Some(token) = &self.config.session_token {
+            headers.push(("x-amz-security-token", token.clone()));
+        }
+
+        let canonical_headers: String = headers.iter().map(|(k, v)| format!("{}:{}\n", k, v)).collect();
+        let signed_header_names = headers.iter().map(|(k, _)| *k).collect::<Vec<_>>().join(";");
+
+        let canonical_request = format!(
+            "POST\n{}\n\n{}\n{}\n{}",
+            SEND_EMAIL_PATH,
+            canonical_headers,
+            signed_header_names,
+            hex(&Sha256::digest(body.as_bytes()))
+        );
+
+        let scope = format!("{}/{}/ses/aws4_request", date_stamp, self.config.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let key = [date_stamp.as_str(), self.config.region.as_str(), "ses", "aws4_request"]
+            .iter()
+            .fold(format!("AWS4{}", self.config.secret_access_key).into_bytes(), |key, part| {
+                hmac_sha256(&key, part.as_bytes())
+            });
+        let signature = hex(&hmac_sha256(&key, string_to_sign.as_bytes()));
+
+        headers.push((
+            "authorization",
+            format!(
+                "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+                self.config.access_key_id, scope, signed_header_names, signature
+            ),
+        ));
+        headers
+    }
+}
+
+#[async_trait]
+impl EmailSender for SesSender {
+    fn backend(&self) -> EmailBackend {
+        EmailBackend::Ses
+    }
+
+    async fn send(&self, from: &str, message: &EmailMessage) -> Result<(), EmailError> {
+        let mut payload = json!({
+            "FromEmailAddress": from,
+            "Destination": { "ToAddresses": [message.to] },
+            "Content": {
+                "Simple": {
+                    "Subject": { "Data": message.subject, "Charset": "UTF-8" },
+                    "Body": { "Text": { "Data": message.text_body, "Charset": "UTF-8" } }
+                }
+            }
+        });
+        if let Some(set) = &self.config.configuration_set {
+            payload["ConfigurationSetName"] = json!(set);
+        }
+        let body = payload.to_string();
+
+        let mut request = self.http.post(format!("https://{}{}", self.host, SEND_EMAIL_PATH));
+        for (name, value) in self.signed_headers(&body) {
+            if name != "host" {
+                request = request.header(name, value);
+            }
+        }
+
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| EmailError::Transport(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(EmailError::Transport(format!("SES returned {}: {}", status, error_text)));
+        }
+
+        Ok(())
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use super::{EmailError, EmailMessage, EmailSender};
+use crate::config::environment::{EmailBackend, SmtpConfig};
+
+pub struct SmtpSender {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl SmtpSender {
+    pub fn new(config: &SmtpConfig) -> Result<Self, EmailError> {
+        if config.host.is_empty() {
+            return Err(EmailError::Config("SMTP_HOST must be set for the smtp backend".to_string()));
+        }
+
+        let mut builder = if config.starttls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+                .map_err(|e| EmailError::Config(e.to_string()))?
+        } else {
+            // Plaintext, for local relays and mail catchers only
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)
+        }
+        .port(config.port);
+
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Self { transport: builder.build() })
+    }
+}
+
+#[async_trait]
+impl EmailSender for SmtpSender {
+    fn backend(&self) -> EmailBackend {
+        EmailBackend::Smtp
+    }
+
+    async fn send(&self, from: &str, message: &EmailMessage) -> Result<(), EmailError> {
+        let email = Message::builder()
+            .from(from.parse().map_err(|e| EmailError::Config(format!("Invalid from address: {}", e)))?)
+            .to(message.to.parse().map_err(|e| EmailError::Transport(format!("Invalid recipient: {}", e)))?)
+            .subject(message.subject.clone())
+            .header(ContentType::TEXT_PLAIN)
+            .body(message.text_body.clone())
+            .map_err(|e| EmailError::Transport(e.to_string()))?;
+
+        self.transport
+            .send(email)
+            .await
+            .map(|_| ())
+            .map_err(|e| EmailError::Transport(e.to_string()))
+    }
+}
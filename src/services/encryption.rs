@@ -0,0 +1,74 @@
+//! Secrets kept in the database.
+//!
+//! Values we must read back in the clear (webhook signing secrets, provider
+//! keys) are sealed with AES-256-GCM under a key from the environment. Each
+//! value gets a fresh nonce and is stored as base64 of nonce followed by
+//! ciphertext. The row it belongs to (a brand slug, a provider name) is bound
+//! in as associated data, so a value copied onto another row fails to decrypt.
+
+use aes_gcm::aead::{Aead, Payload};
+use aes_gcm::{Aes256Gcm, KeyInit};
+use base64::Engine;
+use rand::RngCore;
+
+const NONCE_BYTES: usize = 12;
+
+#[derive(Debug, thiserror::Error)]
+pub enum CipherError {
+    #[error("Invalid encryption key: {0}")]
+    InvalidKey(String),
+
+    #[error("Stored secret for {0} could not be decrypted")]
+    Decrypt(String),
+}
+
+/// AES-256-GCM over stored secrets
+#[derive(Clone)]
+pub struct SecretCipher {
+    cipher: Aes256Gcm,
+}
+
+impl SecretCipher {
+    pub fn new(key: &[u8; 32]) -> Self {
+        Self { cipher: Aes256Gcm::new(key.into()) }
+    }
+
+    /// Cipher for a base64 encoded 32-byte key
+    pub fn from_base64(key: &str) -> Result<Self, CipherError> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(key.trim())
+            .map_err(|e| CipherError::InvalidKey(e.to_string()))?;
+        let key: [u8; 32] = bytes
+            .try_into()
+            .map_err(|b: Vec<u8>| CipherError::InvalidKey(format!("expected 32 bytes, got {}", b.len())))?;
+        Ok(Self::new(&key))
+    }
+
+    /// Base64 of nonce followed by ciphertext; `context` names the owning row
+    pub fn encrypt(&self, context: &str, secret: &str) -> String {
+        let mut nonce = [0u8; NONCE_BYTES];
+        rand::rng().fill_bytes(&mut nonce);
+        let sealed = self
+            .cipher
+            .encrypt((&nonce).into(), Payload { msg: secret.as_bytes(), aad: context.as_bytes() })
+            .expect("AES-GCM encryption of an in-memory buffer cannot fail");
+
+        base64::engine::general_purpose::STANDARD.encode([nonce.as_slice(), &sealed].concat())
+    }
+
+    pub fn decrypt(&self, context: &str, stored: &str) -> Result<String, CipherError> {
+        let failed = || CipherError::Decrypt(context.to_string());
+        let bytes = base64::engine::general_purpose::STANDARD.decode(stored).map_err(|_| failed())?;
+        if bytes.len() <= NONCE_BYTES {
+            return Err(failed());
+        }
+        let (nonce, sealed) = bytes.split_at(NONCE_BYTES);
+        let nonce: [u8; NONCE_BYTES] = nonce.try_into().map_err(|_| failed())?;
+        let plain = self
+            .cipher
+            .decrypt((&nonce).into(), Payload { msg: sealed, aad: context.as_bytes() })
+            .map_err(|_| failed())?;
+
+        String::from_utf8(plain).map_err(|_| failed())
+    }
+}
@@ -0,0 +1,101 @@
+//! Domain event publishers for the outbox relay (see `services::outbox`).
+
+use async_trait::async_trait;
+
+use crate::config::environment::{EventBusConfig, EventBusKind};
+
+#[async_trait]
+pub trait EventPublisher: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Publish one serialized envelope; `key` keeps events for the same aggregate ordered
+    async fn publish(&self, topic: &str, key: &str, payload: &str) -> Result<(), String>;
+}
+
+/// Build the publisher for EVENT_BUS; `Ok(None)` when publishing is off
+pub async fn publisher_from_config(config: &EventBusConfig) -> Result<Option<Box<dyn EventPublisher>>, String> {
+    match config.kind {
+        EventBusKind::Off => Ok(None),
+        EventBusKind::Kafka => Ok(Some(Box::new(KafkaRestPublisher::new(&config.kafka_rest_url)?))),
+        #[cfg(feature = "nats")]
+        EventBusKind::Nats => Ok(Some(Box::new(NatsPublisher::connect(&config.nats_url).await?))),
+        #[cfg(not(feature = "nats"))]
+        EventBusKind::Nats => Err("EVENT_BUS=nats requires building with the `nats` feature".to_string()),
+    }
+}
+
+/// Kafka through a Confluent-compatible REST Proxy
+pub struct KafkaRestPublisher {
+    http: reqwest::Client,
+    base_url: String,
+}
+
+impl KafkaRestPublisher {
+    pub fn new(base_url: &str) -> Result<Self, String> {
+        if base_url.is_empty() {
+            return Err("EVENT_BUS_KAFKA_REST_URL must be set for the kafka event bus".to_string());
+        }
+
+        Ok(Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl EventPublisher for KafkaRestPublisher {
+    fn name(&self) -> &'static str {
+        "kafka"
+    }
+
+    async fn publish(&self, topic: &str, key: &str, payload: &str) -> Result<(), String> {
+        let value: serde_json::Value = serde_json::from_str(payload).map_err(|e| e.to_string())?;
+        let body = serde_json::json!({ "records": [{ "key": key, "value": value }] });
+
+        self.http
+            .post(format!("{}/topics/{}", self.base_url, topic))
+            .header(reqwest::header::CONTENT_TYPE, "application/vnd.kafka.json.v2+json")
+            .body(body.to_string())
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// NATS core publish; the key travels in the `Nats-Msg-Key` header
+#[cfg(feature = "nats")]
+pub struct NatsPublisher {
+    client: async_nats::Client,
+}
+
+#[cfg(feature = "nats")]
+impl NatsPublisher {
+    pub async fn connect(url: &str) -> Result<Self, String> {
+        let client = async_nats::connect(url).await.map_err(|e| e.to_string())?;
+        Ok(Self { client })
+    }
+}
+
+#[cfg(feature = "nats")]
+#[async_trait]
+impl EventPublisher for NatsPublisher {
+    fn name(&self) -> &'static str {
+        "nats"
+    }
+
+    async fn publish(&self, topic: &str, key: &str, payload: &str) -> Result<(), String> {
+        let mut headers = async_nats::HeaderMap::new();
+        headers.insert("Nats-Msg-Key", key);
+
+        self.client
+            .publish_with_headers(topic.to_string(), headers, payload.to_string().into())
+            .await
+            .map_err(|e| e.to_string())?;
+
+        // Surface connection problems now so the row is retried instead of marked published
+        self.client.flush().await.map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,228 @@
+//! Network fee estimates for the payout side of a quote.
+//!
+//! Bitcoin-style chains are read from a mempool.space-compatible
+//! `/v1/fees/recommended` endpoint (FEE_ESTIMATOR_MEMPOOL_URLS, by ticker),
+//! EVM networks from `eth_gasPrice` on a JSON-RPC endpoint
+//! (FEE_ESTIMATOR_EVM_RPC_URLS, by network). Fee rates are cached in Redis
+//! for FEE_ESTIMATOR_CACHE_SECS so instances share them. A payout is priced
+//! as one typical transaction: PAYOUT_VBYTES on UTXO chains, a plain or token
+//! transfer's gas on EVM networks. Estimates are informational; providers
+//! already take the fee out of the amount they quote. Chains without a
+//! source, or whose source fails, get no estimate, and a failed source is
+//! not asked again for `FAILURE_BACKOFF`.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use utoipa::ToSchema;
+
+use crate::config::environment::FeeEstimatorConfig;
+use crate::services::money;
+use crate::services::redis_cache::RedisService;
+
+/// Pause after a failed fetch before the same source is asked again
+const FAILURE_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Virtual size of a one-input, two-output segwit payout
+const PAYOUT_VBYTES: u64 = 140;
+
+/// Gas of a plain native-coin transfer
+const NATIVE_TRANSFER_GAS: u64 = 21_000;
+
+/// Gas of a typical ERC20-style token transfer
+const TOKEN_TRANSFER_GAS: u64 = 65_000;
+
+/// Coin EVM network fees are paid in, by network
+const EVM_FEE_CURRENCIES: &[(&str, &str)] = &[
+    ("erc20", "eth"),
+    ("bep20", "bnb"),
+    ("bsc", "bnb"),
+    ("polygon", "pol"),
+    ("matic", "pol"),
+    ("arbitrum", "eth"),
+    ("optimism", "eth"),
+    ("base", "eth"),
+    ("avaxc", "avax"),
+];
+
+/// Estimated cost of the transaction paying out a quote
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct NetworkFeeEstimate {
+    pub currency: String, // Coin the fee is paid in, e.g. "eth" for an ERC20 payout
+    pub amount: Decimal,  // In `currency`
+    pub fee_rate: f64,    // In `unit`
+    pub unit: String,     // "sat/vB" or "gwei"
+    pub size: u64,        // Transaction size in vbytes, or gas
+}
+
+/// Where a chain's fee rate comes from
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeeSource {
+    Mempool { ticker: String, base_url: String },
+    Evm { network: String, rpc_url: String, fee_currency: String, gas: u64 },
+}
+
+impl FeeSource {
+    /// The configured source for payouts of `ticker` on `network`. Native
+    /// coins use the `Mainnet` network; native ETH shares ERC20's source.
+    pub fn resolve(config: &FeeEstimatorConfig, ticker: &str, network: &str) -> Option<Self> {
+        let ticker = ticker.trim().to_lowercase();
+        let network = network.trim().to_lowercase();
+
+        if network == "mainnet" {
+            if let Some(base_url) = config.mempool_urls.get(&ticker) {
+                return Some(FeeSource::Mempool { ticker, base_url: base_url.clone() });
+            }
+        }
+
+        let (network, native) = match network.as_str() {
+            "mainnet" if ticker == "eth" => ("erc20".to_string(), true),
+            "mainnet" => return None,
+            _ => (network, false),
+        };
+        let fee_currency = EVM_FEE_CURRENCIES.iter().find(|(n, _)| *n == network)?.1.to_string();
+        let rpc_url = config.evm_rpc_urls.get(&network)?.clone();
+        // A network's own coin moves with a plain transfer; anything else is a token
+        let gas = if native || ticker == fee_currency { NATIVE_TRANSFER_GAS } else { TOKEN_TRANSFER_GAS };
+
+        Some(FeeSource::Evm { network, rpc_url, fee_currency, gas })
+    }
+
+    /// Shared by every currency priced from this source
+    fn cache_key(&self) -> String {
+        match self {
+            FeeSource::Mempool { ticker, .. } => format!("fee_estimator:{}", ticker),
+            FeeSource::Evm { network, .. } => format!("fee_estimator:{}", network),
+        }
+    }
+
+    /// One payout at `fee_rate` (sat/vB or gwei)
+    pub fn estimate(&self, fee_rate: f64) -> NetworkFeeEstimate {
+        match self {
+            FeeSource::Mempool { ticker, .. } => NetworkFeeEstimate {
+                currency: ticker.clone(),
+                amount: round_to_units(fee_rate * PAYOUT_VBYTES as f64 / 1e8),
+                fee_rate,
+                unit: "sat/vB".to_string(),
+                size: PAYOUT_VBYTES,
+            },
+            FeeSource::Evm { fee_currency, gas, .. } => NetworkFeeEstimate {
+                currency: fee_currency.clone(),
+                amount: round_to_units(fee_rate * *gas as f64 / 1e9),
+                fee_rate,
+                unit: "gwei".to_string(),
+                size: *gas,
+            },
+        }
+    }
+}
+
+fn round_to_units(amount: f64) -> Decimal {
+    money::from_f64(amount).round_dp(8).normalize()
+}
+
+/// Half-hour fee rate in sat/vB from a `/v1/fees/recommended` response
+pub fn parse_recommended_fees(body: &serde_json::Value) -> Option<f64> {
+    body.get("halfHourFee")?.as_f64().filter(|rate| *rate > 0.0)
+}
+
+/// Gas price in gwei from an `eth_gasPrice` JSON-RPC response
+pub fn parse_gas_price(body: &serde_json::Value) -> Option<f64> {
+    let hex = body.get("result")?.as_str()?.trim_start_matches("0x");
+    let wei = u128::from_str_radix(hex, 16).ok()?;
+    Some(wei as f64 / 1e9).filter(|gwei| *gwei > 0.0)
+}
+
+#[derive(Clone)]
+pub struct FeeEstimator {
+    config: Option<Arc<FeeEstimatorConfig>>, // None when disabled
+    client: reqwest::Client,
+    redis: Option<RedisService>,
+    failed_at: Arc<Mutex<HashMap<String, Instant>>>, // By cache key
+}
+
+impl FeeEstimator {
+    pub fn from_config(config: &FeeEstimatorConfig, redis: Option<RedisService>) -> Self {
+        if !config.enabled {
+            return Self::disabled();
+        }
+        let client = reqwest::Client::builder().timeout(config.timeout).build().unwrap_or_default();
+        Self { config: Some(Arc::new(config.clone())), client, redis, failed_at: Arc::default() }
+    }
+
+    /// An estimator that never has estimates
+    pub fn disabled() -> Self {
+        Self { config: None, client: reqwest::Client::new(), redis: None, failed_at: Arc::default() }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.is_some()
+    }
+
+    /// Fee of paying out `ticker` on `network`, from the cache when fresh;
+    /// None when disabled, the chain has no source or the source is down
+    pub async fn estimate(&self, ticker: &str, network: &str) -> Option<NetworkFeeEstimate> {
+        let config = self.config.as_ref()?;
+        let source = FeeSource::resolve(config, ticker, network)?;
+        let key = source.cache_key();
+
+        if let Some(redis) = &self.redis {
+            if let Ok(Some(fee_rate)) = redis.get_json::<f64>(&key).await {
+                return Some(source.estimate(fee_rate));
+            }
+        }
+        if self.backing_off(&key) {
+            return None;
+        }
+
+        match self.fetch(&source).await {
+            Ok(fee_rate) => {
+                if let Some(redis) = &self.redis {
+                    let _ = redis.set_json(&key, &fee_rate, config.cache_ttl.as_secs()).await;
+                }
+                Some(source.estimate(fee_rate))
+            }
+            Err(e) => {
+                tracing::warn!("Fee estimate for {} unavailable: {}", key, e);
+                self.failed_at.lock().unwrap_or_else(|e| e.into_inner()).insert(key, Instant::now());
+                None
+            }
+        }
+    }
+
+    fn backing_off(&self, key: &str) -> bool {
+        self.failed_at
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(key)
+            .is_some_and(|at| at.elapsed() < FAILURE_BACKOFF)
+    }
+
+    async fn fetch(&self, source: &FeeSource) -> Result<f64, String> {
+        let request = match source {
+            FeeSource::Mempool { base_url, .. } => {
+                self.client.get(format!("{}/v1/fees/recommended", base_url.trim_end_matches('/')))
+            }
+            FeeSource::Evm { rpc_url, .. } => self.client.post(rpc_url).json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "eth_gasPrice",
+                "params": [],
+            })),
+        };
+
+        let response = request.send().await.map_err(|e| format!("HTTP error: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("API error: {}", response.status()));
+        }
+        let body: serde_json::Value = response.json().await.map_err(|e| format!("Parse error: {}", e))?;
+
+        let fee_rate = match source {
+            FeeSource::Mempool { .. } => parse_recommended_fees(&body),
+            FeeSource::Evm { .. } => parse_gas_price(&body),
+        };
+        fee_rate.ok_or_else(|| "no fee rate in response".to_string())
+    }
+}
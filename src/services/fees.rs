@@ -0,0 +1,309 @@
+//! Platform fee engine.
+//!
+//! Fee rules live in `fee_rules` and are managed through `/admin/fee-rules`.
+//! A rule can be narrowed to a source ticker, a receive ticker, a provider
+//! and a user fee tier (`users.fee_tier`); unset fields match anything. For
+//! each quote the most specific matching rule sets the fee: a percentage of
+//! the receive amount plus a flat amount in the receive currency. Without a
+//! matching rule the fee is zero. The same rules price quotes and the swaps
+//! created from them, so the fee shown on `/swap/rates` is the one recorded.
+//! Rules belong to a tenant and only price that tenant's quotes and swaps.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::config::DbPool;
+use crate::services::redis_cache::RedisService;
+use crate::services::tenant::TenantId;
+
+const CACHE_KEY: &str = "fee_rules:all";
+const CACHE_TTL_SECS: u64 = 300;
+
+/// Tier of new users
+pub const DEFAULT_FEE_TIER: &str = "standard";
+
+/// Highest percentage fee a rule may take
+pub const MAX_FEE_PERCENT: Decimal = dec!(10);
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FeeRule {
+    pub id: u64,
+    pub tenant_id: String,
+    pub from_currency: Option<String>, // None matches every source ticker
+    pub to_currency: Option<String>,
+    pub provider: Option<String>,
+    pub user_tier: Option<String>, // None also applies to anonymous requests
+    pub fee_percent: Decimal,
+    pub flat_fee: Decimal, // In to_currency
+    pub note: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Fields an admin sets; the id comes from the path on update
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeeRuleInput {
+    #[serde(default)]
+    pub from_currency: Option<String>,
+    #[serde(default)]
+    pub to_currency: Option<String>,
+    #[serde(default)]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub user_tier: Option<String>,
+    #[serde(default)]
+    pub fee_percent: Decimal,
+    #[serde(default)]
+    pub flat_fee: Decimal,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// What a quote is priced for
+pub struct FeeScope<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    pub provider: &'a str,
+    pub user_tier: Option<&'a str>, // None for anonymous requests
+}
+
+impl FeeRule {
+    fn matches(&self, scope: &FeeScope) -> bool {
+        let field = |rule: &Option<String>, value: &str| rule.as_deref().is_none_or(|r| r.eq_ignore_ascii_case(value));
+        field(&self.from_currency, scope.from)
+            && field(&self.to_currency, scope.to)
+            && field(&self.provider, scope.provider)
+            && self.user_tier.as_deref().is_none_or(|t| scope.user_tier == Some(t))
+    }
+
+    fn specificity(&self) -> usize {
+        [&self.from_currency, &self.to_currency, &self.provider, &self.user_tier]
+            .iter()
+            .filter(|f| f.is_some())
+            .count()
+    }
+
+    /// Fee on a receive amount, never more than the amount itself
+    pub fn fee_on(&self, receive_amount: Decimal) -> Decimal {
+        (receive_amount * self.fee_percent / Decimal::ONE_HUNDRED + self.flat_fee)
+            .clamp(Decimal::ZERO, receive_amount.max(Decimal::ZERO))
+    }
+}
+
+/// The most specific rule for a quote; the newest wins a tie
+pub fn select_rule<'a>(rules: &'a [FeeRule], scope: &FeeScope) -> Option<&'a FeeRule> {
+    rules
+        .iter()
+        .filter(|rule| rule.matches(scope))
+        .max_by_key(|rule| (rule.specificity(), rule.id))
+}
+
+/// Platform fee on a receive amount, in the receive currency
+pub fn platform_fee(rules: &[FeeRule], scope: &FeeScope, receive_amount: Decimal) -> Decimal {
+    select_rule(rules, scope).map_or(Decimal::ZERO, |rule| rule.fee_on(receive_amount))
+}
+
+impl FeeRuleInput {
+    /// Lowercase tickers and tiers, drop blank fields
+    pub fn normalized(self) -> Self {
+        let clean = |v: Option<String>, lower: bool| {
+            v.map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .map(|v| if lower { v.to_lowercase() } else { v })
+        };
+        Self {
+            from_currency: clean(self.from_currency, true),
+            to_currency: clean(self.to_currency, true),
+            provider: clean(self.provider, false),
+            user_tier: clean(self.user_tier, true),
+            note: clean(self.note, false),
+            ..self
+        }
+    }
+
+    pub fn check_rules(&self) -> Result<(), String> {
+        if !(Decimal::ZERO..=MAX_FEE_PERCENT).contains(&self.fee_percent) {
+            return Err(format!("fee_percent must be between 0 and {}", MAX_FEE_PERCENT));
+        }
+        if self.flat_fee.is_sign_negative() && !self.flat_fee.is_zero() {
+            return Err("flat_fee must not be negative".to_string());
+        }
+        // A flat amount only means something in a known receive currency
+        if self.flat_fee > Decimal::ZERO && self.to_currency.is_none() {
+            return Err("flat_fee needs to_currency".to_string());
+        }
+        if let Some(tier) = &self.user_tier {
+            check_tier(tier)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn check_tier(tier: &str) -> Result<(), String> {
+    let valid = !tier.is_empty()
+        && tier.len() <= 20
+        && tier.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err("fee tier must be 1-20 lowercase letters, digits or '-'".to_string())
+    }
+}
+
+// =============================================================================
+// REGISTRY
+// =============================================================================
+
+#[derive(Clone)]
+pub struct FeeRules {
+    pool: DbPool,
+    redis: Option<RedisService>,
+    tenant: TenantId,
+}
+
+impl FeeRules {
+    /// Rules of the default tenant
+    pub fn new(pool: DbPool, redis: Option<RedisService>) -> Self {
+        Self { pool, redis, tenant: TenantId::default() }
+    }
+
+    /// Read and manage another tenant's rules
+    pub fn with_tenant(mut self, tenant: TenantId) -> Self {
+        self.tenant = tenant;
+        self
+    }
+
+    fn cache_key(&self) -> String {
+        self.tenant.cache_key(CACHE_KEY)
+    }
+
+    /// Every rule of the tenant, ordered by id, from Redis when cached
+    pub async fn all(&self) -> Result<Vec<FeeRule>, sqlx::Error> {
+        let cache_key = self.cache_key();
+        if let Some(redis) = &self.redis {
+            if let Ok(Some(rules)) = redis.get_json::<Vec<FeeRule>>(&cache_key).await {
+                return Ok(rules);
+            }
+        }
+
+        let rules = sqlx::query_as::<_, FeeRule>(&format!("{} WHERE tenant_id = ? ORDER BY id", FEE_RULE_SELECT))
+            .bind(self.tenant.as_str())
+            .fetch_all(&self.pool)
+            .await?;
+
+        if let Some(redis) = &self.redis {
+            let _ = redis.set_json(&cache_key, &rules, CACHE_TTL_SECS).await;
+        }
+
+        Ok(rules)
+    }
+
+    pub async fn create(&self, rule: &FeeRuleInput) -> Result<FeeRule, sqlx::Error> {
+        let id = sqlx::query(
+            r#"
+            INSERT INTO fee_rules (tenant_id, from_currency, to_currency, provider, user_tier, fee_percent, flat_fee, note)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(self.tenant.as_str())
+        .bind(&rule.from_currency)
+        .bind(&rule.to_currency)
+        .bind(&rule.provider)
+        .bind(&rule.user_tier)
+        .bind(rule.fee_percent)
+        .bind(rule.flat_fee)
+        .bind(&rule.note)
+        .execute(&self.pool)
+        .await?
+        .last_insert_id();
+
+        self.invalidate().await;
+        self.find(id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Replace a rule; `None` when no such rule exists
+    pub async fn update(&self, id: u64, rule: &FeeRuleInput) -> Result<Option<FeeRule>, sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE fee_rules
+            SET from_currency = ?, to_currency = ?, provider = ?, user_tier = ?,
+                fee_percent = ?, flat_fee = ?, note = ?
+            WHERE id = ? AND tenant_id = ?
+            "#,
+        )
+        .bind(&rule.from_currency)
+        .bind(&rule.to_currency)
+        .bind(&rule.provider)
+        .bind(&rule.user_tier)
+        .bind(rule.fee_percent)
+        .bind(rule.flat_fee)
+        .bind(&rule.note)
+        .bind(id)
+        .bind(self.tenant.as_str())
+        .execute(&self.pool)
+        .await?;
+
+        // rows_affected is 0 for an unchanged row too, so look the rule up instead
+        self.invalidate().await;
+        self.find(id).await
+    }
+
+    /// Remove a rule, returning it; `None` when no such rule exists
+    pub async fn delete(&self, id: u64) -> Result<Option<FeeRule>, sqlx::Error> {
+        let Some(existing) = self.find(id).await? else {
+            return Ok(None);
+        };
+
+        sqlx::query("DELETE FROM fee_rules WHERE id = ? AND tenant_id = ?")
+            .bind(id)
+            .bind(self.tenant.as_str())
+            .execute(&self.pool)
+            .await?;
+
+        self.invalidate().await;
+        Ok(Some(existing))
+    }
+
+    /// Move a user to a fee tier; false when no such user exists
+    pub async fn set_user_tier(&self, user_id: &str, tier: &str) -> Result<bool, sqlx::Error> {
+        let exists: Option<(String,)> = sqlx::query_as("SELECT id FROM users WHERE id = ?")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        if exists.is_none() {
+            return Ok(false);
+        }
+
+        sqlx::query("UPDATE users SET fee_tier = ? WHERE id = ?")
+            .bind(tier)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(true)
+    }
+
+    async fn find(&self, id: u64) -> Result<Option<FeeRule>, sqlx::Error> {
+        sqlx::query_as::<_, FeeRule>(&format!("{} WHERE id = ? AND tenant_id = ?", FEE_RULE_SELECT))
+            .bind(id)
+            .bind(self.tenant.as_str())
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn invalidate(&self) {
+        if let Some(redis) = &self.redis {
+            if let Err(e) = redis.delete(&self.cache_key()).await {
+                // Instances keep the old rules until the cached copy expires
+                tracing::warn!("Failed to invalidate fee rule cache: {}", e);
+            }
+        }
+    }
+}
+
+const FEE_RULE_SELECT: &str = r#"
+    SELECT id, tenant_id, from_currency, to_currency, provider, user_tier,
+           fee_percent, flat_fee,
+           note, updated_at
+    FROM fee_rules
+"#;
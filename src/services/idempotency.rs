@@ -0,0 +1,244 @@
+//! Idempotent request replay.
+//!
+//! Clients that retry on flaky networks send an `Idempotency-Key` header on
+//! requests that must not run twice (`POST /swap/create`). The first request
+//! claims the key in MySQL (the primary key makes the claim atomic across
+//! instances) and stores its response once it succeeds; replays with the same
+//! key and body get that response back instead of running again. Completed
+//! responses are also cached in Redis so replays skip the database.
+//!
+//! Keys are scoped to the caller (user id, or `anonymous`) and to the
+//! endpoint, and are honoured for [`REPLAY_WINDOW_SECS`]. A key reused with a
+//! different body is rejected, and a claim whose request never finished (the
+//! instance died mid-request) is given up after [`CLAIM_TIMEOUT_SECS`].
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::DbPool;
+use crate::services::redis_cache::RedisService;
+
+pub const IDEMPOTENCY_HEADER: &str = "idempotency-key";
+pub const MAX_KEY_LEN: usize = 255;
+
+/// How long a completed response is replayed for
+pub const REPLAY_WINDOW_SECS: u64 = 86_400;
+
+/// An unfinished claim older than this is treated as abandoned
+pub const CLAIM_TIMEOUT_SECS: u64 = 120;
+
+/// Scope for requests without a signed-in user
+pub const ANONYMOUS_SCOPE: &str = "anonymous";
+
+#[derive(Debug)]
+pub enum IdempotencyError {
+    InvalidKey,
+    KeyReused,  // Same key, different request body
+    InProgress, // The first request with this key hasn't finished
+    Storage(String),
+}
+
+impl std::fmt::Display for IdempotencyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdempotencyError::InvalidKey => write!(
+                f,
+                "Idempotency-Key must be 1-{} printable ASCII characters",
+                MAX_KEY_LEN
+            ),
+            IdempotencyError::KeyReused => {
+                write!(f, "Idempotency-Key was already used with a different request")
+            }
+            IdempotencyError::InProgress => {
+                write!(f, "A request with this Idempotency-Key is still being processed")
+            }
+            IdempotencyError::Storage(e) => write!(f, "Idempotency store error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for IdempotencyError {}
+
+/// Outcome of claiming a key
+pub enum Claim<T> {
+    New,       // First use: run the request, then `complete` or `release`
+    Replay(T), // Already done: return this instead
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredResponse {
+    request_hash: String,
+    response: serde_json::Value,
+}
+
+#[derive(Clone)]
+pub struct IdempotencyStore {
+    pool: DbPool,
+    redis: Option<RedisService>,
+    endpoint: &'static str,
+}
+
+impl IdempotencyStore {
+    pub fn new(pool: DbPool, redis: Option<RedisService>, endpoint: &'static str) -> Self {
+        Self { pool, redis, endpoint }
+    }
+
+    /// Reject empty, oversized or non-printable keys
+    pub fn check_key(key: &str) -> Result<(), IdempotencyError> {
+        if key.is_empty() || key.len() > MAX_KEY_LEN || !key.bytes().all(|b| b.is_ascii_graphic()) {
+            return Err(IdempotencyError::InvalidKey);
+        }
+        Ok(())
+    }
+
+    /// Fingerprint of a request body, compared on replay
+    pub fn request_hash<B: Serialize>(body: &B) -> String {
+        let bytes = serde_json::to_vec(body).unwrap_or_default();
+        Sha256::digest(&bytes).iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn cache_key(&self, scope: &str, key: &str) -> String {
+        format!("idempotency:{}:{}:{}", self.endpoint, scope, key)
+    }
+
+    /// Claim `key` for a new request, or return the response stored for it
+    pub async fn claim<T: DeserializeOwned>(
+        &self,
+        scope: &str,
+        key: &str,
+        request_hash: &str,
+    ) -> Result<Claim<T>, IdempotencyError> {
+        Self::check_key(key)?;
+
+        if let Some(redis) = &self.redis {
+            if let Ok(Some(stored)) = redis.get_json::<StoredResponse>(&self.cache_key(scope, key)).await {
+                return replay(stored, request_hash);
+            }
+        }
+
+        let db_error = |e: sqlx::Error| IdempotencyError::Storage(e.to_string());
+
+        // Free the key if its replay window passed or its first request died
+        sqlx::query(
+            "DELETE FROM idempotency_keys
+             WHERE endpoint = ? AND scope = ? AND idempotency_key = ?
+               AND (created_at < NOW() - INTERVAL ? SECOND
+                    OR (response IS NULL AND created_at < NOW() - INTERVAL ? SECOND))",
+        )
+        .bind(self.endpoint)
+        .bind(scope)
+        .bind(key)
+        .bind(REPLAY_WINDOW_SECS)
+        .bind(CLAIM_TIMEOUT_SECS)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        let claimed = sqlx::query(
+            "INSERT IGNORE INTO idempotency_keys (endpoint, scope, idempotency_key, request_hash)
+             VALUES (?, ?, ?, ?)",
+        )
+        .bind(self.endpoint)
+        .bind(scope)
+        .bind(key)
+        .bind(request_hash)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?
+        .rows_affected()
+            == 1;
+        if claimed {
+            return Ok(Claim::New);
+        }
+
+        let existing: Option<(String, Option<String>)> = sqlx::query_as(
+            "SELECT request_hash, CAST(response AS CHAR) FROM idempotency_keys
+             WHERE endpoint = ? AND scope = ? AND idempotency_key = ?",
+        )
+        .bind(self.endpoint)
+        .bind(scope)
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        match existing {
+            Some((stored_hash, Some(response))) => {
+                let response = serde_json::from_str(&response)
+                    .map_err(|e| IdempotencyError::Storage(e.to_string()))?;
+                replay(StoredResponse { request_hash: stored_hash, response }, request_hash)
+            }
+            Some((stored_hash, None)) if stored_hash != request_hash => Err(IdempotencyError::KeyReused),
+            // Still running, or released between our insert and select
+            _ => Err(IdempotencyError::InProgress),
+        }
+    }
+
+    /// Store the response for a claimed key so replays return it
+    pub async fn complete<T: Serialize>(
+        &self,
+        scope: &str,
+        key: &str,
+        resource_id: &str,
+        response: &T,
+    ) -> Result<(), IdempotencyError> {
+        let response = serde_json::to_value(response).map_err(|e| IdempotencyError::Storage(e.to_string()))?;
+
+        let request_hash: Option<String> = sqlx::query_scalar(
+            "SELECT request_hash FROM idempotency_keys
+             WHERE endpoint = ? AND scope = ? AND idempotency_key = ?",
+        )
+        .bind(self.endpoint)
+        .bind(scope)
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| IdempotencyError::Storage(e.to_string()))?;
+
+        sqlx::query(
+            "UPDATE idempotency_keys SET resource_id = ?, response = ?, completed_at = NOW()
+             WHERE endpoint = ? AND scope = ? AND idempotency_key = ?",
+        )
+        .bind(resource_id)
+        .bind(response.to_string())
+        .bind(self.endpoint)
+        .bind(scope)
+        .bind(key)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| IdempotencyError::Storage(e.to_string()))?;
+
+        if let (Some(redis), Some(request_hash)) = (&self.redis, request_hash) {
+            let stored = StoredResponse { request_hash, response };
+            let _ = redis.set_json(&self.cache_key(scope, key), &stored, REPLAY_WINDOW_SECS).await;
+        }
+
+        Ok(())
+    }
+
+    /// Give up a claim whose request failed, so the client can retry with the same key
+    pub async fn release(&self, scope: &str, key: &str) -> Result<(), IdempotencyError> {
+        sqlx::query(
+            "DELETE FROM idempotency_keys
+             WHERE endpoint = ? AND scope = ? AND idempotency_key = ? AND response IS NULL",
+        )
+        .bind(self.endpoint)
+        .bind(scope)
+        .bind(key)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| IdempotencyError::Storage(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+fn replay<T: DeserializeOwned>(stored: StoredResponse, request_hash: &str) -> Result<Claim<T>, IdempotencyError> {
+    if stored.request_hash != request_hash {
+        return Err(IdempotencyError::KeyReused);
+    }
+    serde_json::from_value(stored.response)
+        .map(Claim::Replay)
+        .map_err(|e| IdempotencyError::Storage(e.to_string()))
+}
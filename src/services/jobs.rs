@@ -0,0 +1,177 @@
+//! Background job registry.
+//!
+//! Long-running loops register here when they are spawned so operators can
+//! see them at `GET /admin/jobs` and wake one with `POST /admin/jobs/{name}/run`.
+//! State is per process: each instance reports the jobs it is running.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    Scheduled,     // Runs on an interval; a trigger runs it now
+    QueueConsumer, // Drains a queue; a trigger flushes what is buffered
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobOutcome {
+    Success,
+    Failed,
+    Skipped, // Nothing to do, or another instance holds the lock
+    Timeout,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobStatus {
+    pub name: String,
+    pub description: String,
+    pub kind: JobKind,
+    pub running: bool,
+    pub runs: u64,
+    pub failures: u64,
+    pub last_started_at: Option<DateTime<Utc>>,
+    pub last_duration_ms: Option<u64>,
+    pub last_outcome: Option<JobOutcome>,
+    pub last_error: Option<String>,
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub trigger_pending: bool,
+}
+
+struct JobEntry {
+    status: Mutex<JobStatus>,
+    trigger: Notify,
+}
+
+impl JobEntry {
+    fn update(&self, f: impl FnOnce(&mut JobStatus)) {
+        f(&mut self.status.lock().unwrap_or_else(|e| e.into_inner()));
+    }
+
+    fn snapshot(&self) -> JobStatus {
+        self.status.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+#[derive(Default)]
+pub struct JobRegistry {
+    jobs: Mutex<BTreeMap<String, Arc<JobEntry>>>,
+}
+
+static REGISTRY: LazyLock<JobRegistry> = LazyLock::new(JobRegistry::default);
+
+/// Process-wide registry shared by every background loop
+pub fn registry() -> &'static JobRegistry {
+    &REGISTRY
+}
+
+impl JobRegistry {
+    /// Register a job, replacing any earlier one with the same name
+    pub fn register(&self, name: &str, description: &str, kind: JobKind) -> JobHandle {
+        let entry = Arc::new(JobEntry {
+            status: Mutex::new(JobStatus {
+                name: name.to_string(),
+                description: description.to_string(),
+                kind,
+                running: false,
+                runs: 0,
+                failures: 0,
+                last_started_at: None,
+                last_duration_ms: None,
+                last_outcome: None,
+                last_error: None,
+                next_run_at: None,
+                trigger_pending: false,
+            }),
+            trigger: Notify::new(),
+        });
+
+        self.jobs
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.to_string(), entry.clone());
+
+        JobHandle { entry }
+    }
+
+    pub fn list(&self) -> Vec<JobStatus> {
+        let jobs = self.jobs.lock().unwrap_or_else(|e| e.into_inner());
+        jobs.values().map(|entry| entry.snapshot()).collect()
+    }
+
+    /// Ask a job to run as soon as it can; `None` when no such job is registered
+    pub fn trigger(&self, name: &str) -> Option<JobStatus> {
+        let entry = self.jobs.lock().unwrap_or_else(|e| e.into_inner()).get(name).cloned()?;
+
+        entry.update(|s| s.trigger_pending = true);
+        // Stores a permit when the job is mid-run, so it goes again right after
+        entry.trigger.notify_one();
+        Some(entry.snapshot())
+    }
+}
+
+/// Held by the loop that runs a job
+#[derive(Clone)]
+pub struct JobHandle {
+    entry: Arc<JobEntry>,
+}
+
+impl JobHandle {
+    /// Sleep until the next scheduled run, returning early when triggered
+    pub async fn wait(&self, delay: Duration) {
+        let next_run_at = Utc::now() + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX);
+        self.entry.update(|s| s.next_run_at = Some(next_run_at));
+
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = self.entry.trigger.notified() => {}
+        }
+    }
+
+    /// Resolves when an operator triggers the job
+    pub async fn triggered(&self) {
+        self.entry.trigger.notified().await
+    }
+
+    /// Mark a run as started; finish it with [`JobRun::finish`]
+    pub fn start(&self) -> JobRun {
+        self.entry.update(|s| {
+            s.running = true;
+            s.trigger_pending = false;
+            s.next_run_at = None;
+            s.last_started_at = Some(Utc::now());
+        });
+
+        JobRun {
+            entry: self.entry.clone(),
+            started: std::time::Instant::now(),
+        }
+    }
+}
+
+pub struct JobRun {
+    entry: Arc<JobEntry>,
+    started: std::time::Instant,
+}
+
+impl JobRun {
+    pub fn finish(self, outcome: JobOutcome, error: Option<String>) {
+        let duration_ms = self.started.elapsed().as_millis() as u64;
+
+        self.entry.update(|s| {
+            s.running = false;
+            s.runs += 1;
+            if matches!(outcome, JobOutcome::Failed | JobOutcome::Timeout) {
+                s.failures += 1;
+            }
+            s.last_duration_ms = Some(duration_ms);
+            s.last_outcome = Some(outcome);
+            s.last_error = error;
+        });
+    }
+}
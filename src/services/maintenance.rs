@@ -0,0 +1,272 @@
+//! Runtime maintenance mode.
+//!
+//! The flag lives in the `maintenance_mode` table and is mirrored in Redis, so
+//! it can still be read while the database is the thing under maintenance.
+//! Write endpoints take a [`WritesAllowed`] extractor, which answers 503 with a
+//! localized message and `Retry-After` while maintenance is on.
+
+use axum::{
+    extract::{FromRef, FromRequestParts},
+    http::{header, request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::config::DbPool;
+use crate::services::redis_cache::RedisService;
+use crate::AppState;
+
+const CACHE_KEY: &str = "maintenance:state";
+const CACHE_TTL_SECS: u64 = 3600;
+const DEFAULT_RETRY_AFTER_SECS: u32 = 300;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MaintenanceState {
+    pub enabled: bool,
+    pub messages: HashMap<String, String>, // Language code -> message; overrides the built-in text
+    pub retry_after_secs: u32,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub updated_by: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl MaintenanceState {
+    /// Seconds clients should wait: until `ends_at` when known, else `retry_after_secs`
+    pub fn retry_after(&self) -> u32 {
+        self.ends_at
+            .map(|ends_at| (ends_at - Utc::now()).num_seconds())
+            .filter(|secs| *secs > 0)
+            .map(|secs| secs.min(u32::MAX as i64) as u32)
+            .unwrap_or(self.retry_after_secs)
+            .max(1)
+    }
+
+    /// Message in the best language the client accepts
+    pub fn message_for(&self, accept_language: Option<&str>) -> String {
+        let languages = preferred_languages(accept_language.unwrap_or(""));
+
+        languages
+            .iter()
+            .find_map(|lang| self.messages.get(lang))
+            .or_else(|| self.messages.get("en"))
+            .cloned()
+            .unwrap_or_else(|| {
+                let lang = languages
+                    .iter()
+                    .find(|lang| default_message(lang).is_some())
+                    .map(String::as_str)
+                    .unwrap_or("en");
+                default_message(lang).unwrap_or_default().to_string()
+            })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct MaintenanceRow {
+    enabled: bool,
+    messages: Option<String>,
+    retry_after_secs: u32,
+    ends_at: Option<DateTime<Utc>>,
+    updated_by: Option<String>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<MaintenanceRow> for MaintenanceState {
+    fn from(row: MaintenanceRow) -> Self {
+        Self {
+            enabled: row.enabled,
+            messages: row
+                .messages
+                .and_then(|m| serde_json::from_str(&m).ok())
+                .unwrap_or_default(),
+            retry_after_secs: row.retry_after_secs,
+            ends_at: row.ends_at,
+            updated_by: row.updated_by,
+            updated_at: Some(row.updated_at),
+        }
+    }
+}
+
+pub struct MaintenanceService {
+    pool: DbPool,
+    redis: RedisService,
+}
+
+impl MaintenanceService {
+    pub fn new(pool: DbPool, redis: RedisService) -> Self {
+        Self { pool, redis }
+    }
+
+    /// Current state from Redis, falling back to the database. Fails open
+    /// (maintenance off) when neither can be read.
+    pub async fn current(&self) -> MaintenanceState {
+        if let Ok(Some(state)) = self.redis.get_json::<MaintenanceState>(CACHE_KEY).await {
+            return state;
+        }
+
+        match self.load().await {
+            Ok(state) => {
+                let _ = self.redis.set_json(CACHE_KEY, &state, CACHE_TTL_SECS).await;
+                state
+            }
+            Err(e) => {
+                tracing::warn!("Failed to read maintenance mode: {}", e);
+                MaintenanceState::default()
+            }
+        }
+    }
+
+    async fn load(&self) -> Result<MaintenanceState, sqlx::Error> {
+        let row = sqlx::query_as::<_, MaintenanceRow>(
+            "SELECT enabled, CAST(messages AS CHAR) AS messages, retry_after_secs, ends_at, updated_by, updated_at
+             FROM maintenance_mode WHERE id = 1",
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(MaintenanceState::from).unwrap_or_else(|| MaintenanceState {
+            retry_after_secs: DEFAULT_RETRY_AFTER_SECS,
+            ..Default::default()
+        }))
+    }
+
+    /// Persist a new state and publish it to Redis for every instance
+    pub async fn set(
+        &self,
+        enabled: bool,
+        messages: HashMap<String, String>,
+        retry_after_secs: Option<u32>,
+        ends_at: Option<DateTime<Utc>>,
+        updated_by: &str,
+    ) -> Result<MaintenanceState, sqlx::Error> {
+        let messages: HashMap<String, String> =
+            messages.into_iter().map(|(lang, text)| (lang.to_lowercase(), text)).collect();
+
+        sqlx::query(
+            r#"
+            INSERT INTO maintenance_mode (id, enabled, messages, retry_after_secs, ends_at, updated_by)
+            VALUES (1, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                enabled = VALUES(enabled), messages = VALUES(messages),
+                retry_after_secs = VALUES(retry_after_secs), ends_at = VALUES(ends_at),
+                updated_by = VALUES(updated_by)
+            "#,
+        )
+        .bind(enabled)
+        .bind(serde_json::to_string(&messages).unwrap_or_else(|_| "{}".to_string()))
+        .bind(retry_after_secs.unwrap_or(DEFAULT_RETRY_AFTER_SECS))
+        .bind(ends_at)
+        .bind(updated_by)
+        .execute(&self.pool)
+        .await?;
+
+        let state = self.load().await?;
+        if let Err(e) = self.redis.set_json(CACHE_KEY, &state, CACHE_TTL_SECS).await {
+            // Other instances pick the change up when their cached copy expires
+            tracing::warn!("Failed to publish maintenance mode to Redis: {}", e);
+        }
+
+        Ok(state)
+    }
+}
+
+// =============================================================================
+// EXTRACTOR
+// =============================================================================
+
+/// Rejects the request with 503 while maintenance mode is on
+pub struct WritesAllowed;
+
+#[derive(Debug, Serialize)]
+pub struct MaintenanceResponse {
+    pub error: String,
+    pub code: &'static str,
+    pub retry_after: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+pub struct MaintenanceRejection {
+    body: MaintenanceResponse,
+}
+
+impl IntoResponse for MaintenanceRejection {
+    fn into_response(self) -> Response {
+        let retry_after = self.body.retry_after.to_string();
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::RETRY_AFTER, retry_after)],
+            Json(self.body),
+        )
+            .into_response()
+    }
+}
+
+impl<S> FromRequestParts<S> for WritesAllowed
+where
+    Arc<AppState>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = MaintenanceRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let state = Arc::from_ref(state);
+        let maintenance = MaintenanceService::new(state.db.clone(), state.redis.clone()).current().await;
+
+        if !maintenance.enabled {
+            return Ok(WritesAllowed);
+        }
+
+        let accept_language = parts.headers.get(header::ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok());
+        Err(MaintenanceRejection {
+            body: MaintenanceResponse {
+                error: maintenance.message_for(accept_language),
+                code: "MAINTENANCE_MODE",
+                retry_after: maintenance.retry_after(),
+                ends_at: maintenance.ends_at,
+            },
+        })
+    }
+}
+
+// =============================================================================
+// LOCALIZATION
+// =============================================================================
+
+/// Primary language subtags from an Accept-Language header, highest q first
+fn preferred_languages(header: &str) -> Vec<String> {
+    let mut languages: Vec<(String, f32)> = header
+        .split(',')
+        .filter_map(|part| {
+            let mut pieces = part.trim().split(';');
+            let tag = pieces.next()?.trim();
+            let primary = tag.split('-').next()?.to_lowercase();
+            if primary.is_empty() || primary == "*" {
+                return None;
+            }
+            let q = pieces
+                .find_map(|p| p.trim().strip_prefix("q=").and_then(|q| q.parse::<f32>().ok()))
+                .unwrap_or(1.0);
+            Some((primary, q))
+        })
+        .collect();
+
+    languages.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    languages.into_iter().map(|(lang, _)| lang).collect()
+}
+
+fn default_message(lang: &str) -> Option<&'static str> {
+    Some(match lang {
+        "en" => "We're performing scheduled maintenance. New swaps are paused for now; existing swaps are not affected. Please try again shortly.",
+        "es" => "Estamos realizando un mantenimiento programado. Los nuevos intercambios están en pausa; los intercambios existentes no se ven afectados. Vuelve a intentarlo en breve.",
+        "fr" => "Une maintenance programmée est en cours. Les nouveaux échanges sont suspendus ; les échanges en cours ne sont pas affectés. Veuillez réessayer sous peu.",
+        "de" => "Wir führen geplante Wartungsarbeiten durch. Neue Tauschvorgänge sind vorübergehend pausiert; bestehende sind nicht betroffen. Bitte versuche es in Kürze erneut.",
+        "pt" => "Estamos realizando uma manutenção programada. Novas trocas estão pausadas; as trocas existentes não são afetadas. Tente novamente em breve.",
+        "ru" => "Проводятся плановые технические работы. Создание новых обменов временно приостановлено; текущие обмены не затронуты. Пожалуйста, повторите попытку позже.",
+        _ => return None,
+    })
+}
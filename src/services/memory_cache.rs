@@ -0,0 +1,202 @@
+//! Process-local stand-in for Redis (REDIS_URL=memory://).
+//!
+//! Covers what RedisService needs from Redis: strings with expiry, counters
+//! and SET NX locks. Nothing is shared between processes, so it only suits a
+//! single instance, and there is no pub/sub: live status streams are closed
+//! with an error and clients fall back to polling GET /swap/{id}.
+//!
+//! The same store serves as the fallback while Redis is unreachable (see
+//! REDIS_DEGRADED_MODE), bounded to REDIS_FALLBACK_MAX_KEYS by dropping the
+//! least recently used keys.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Expired entries are swept after this many writes
+const SWEEP_EVERY: u64 = 1024;
+
+#[derive(Clone, Default)]
+pub struct MemoryStore {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: HashMap<String, Entry>,
+    writes: u64,
+    clock: u64,              // Ticks on every read and write, for LRU order
+    max_keys: Option<usize>, // None keeps every key until it expires
+}
+
+struct Entry {
+    value: String,
+    expires_at: Option<Instant>,
+    used: u64, // Clock at the last read or write
+}
+
+impl Entry {
+    fn is_live(&self, now: Instant) -> bool {
+        self.expires_at.is_none_or(|at| at > now)
+    }
+}
+
+impl Inner {
+    fn live(&mut self, key: &str) -> Option<&mut Entry> {
+        let now = Instant::now();
+        if self.entries.get(key).is_some_and(|e| !e.is_live(now)) {
+            self.entries.remove(key);
+        }
+        self.clock += 1;
+        let clock = self.clock;
+        let entry = self.entries.get_mut(key)?;
+        entry.used = clock;
+        Some(entry)
+    }
+
+    fn insert(&mut self, key: &str, value: String, expires_at: Option<Instant>) {
+        self.clock += 1;
+        self.entries.insert(key.to_string(), Entry { value, expires_at, used: self.clock });
+        self.writes += 1;
+        if self.writes.is_multiple_of(SWEEP_EVERY) {
+            let now = Instant::now();
+            self.entries.retain(|_, e| e.is_live(now));
+        }
+        self.evict();
+    }
+
+    /// Over the key limit, drop expired keys and then the least recently
+    /// used ones, down to 7/8 of the limit so this does not run on every write
+    fn evict(&mut self) {
+        let Some(max_keys) = self.max_keys else {
+            return;
+        };
+        if self.entries.len() <= max_keys {
+            return;
+        }
+        let now = Instant::now();
+        self.entries.retain(|_, e| e.is_live(now));
+
+        let excess = self.entries.len().saturating_sub(max_keys - max_keys / 8);
+        if self.entries.len() <= max_keys || excess == 0 {
+            return;
+        }
+        let mut by_use: Vec<(u64, String)> = self.entries.iter().map(|(k, e)| (e.used, k.clone())).collect();
+        by_use.select_nth_unstable(excess - 1);
+        for (_, key) in by_use.into_iter().take(excess) {
+            self.entries.remove(&key);
+        }
+    }
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store holding at most `max_keys`, least recently used dropped first
+    pub fn with_max_keys(max_keys: usize) -> Self {
+        let store = Self::new();
+        store.lock().max_keys = Some(max_keys.max(1));
+        store
+    }
+
+    /// Live and not yet swept keys
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.lock().live(key).map(|e| e.value.clone())
+    }
+
+    /// SETEX; a zero TTL leaves nothing behind, as the key would expire at once
+    pub fn set(&self, key: &str, value: &str, ttl_seconds: u64) {
+        let mut inner = self.lock();
+        if ttl_seconds == 0 {
+            inner.entries.remove(key);
+            return;
+        }
+        inner.insert(key, value.to_string(), Some(expiry(ttl_seconds)));
+    }
+
+    /// SET NX EX; true when the key was free and is now held
+    pub fn set_nx(&self, key: &str, value: &str, ttl_seconds: u64) -> bool {
+        let mut inner = self.lock();
+        if inner.live(key).is_some() {
+            return false;
+        }
+        inner.insert(key, value.to_string(), Some(expiry(ttl_seconds)));
+        true
+    }
+
+    /// INCR; a missing key counts from zero and keeps no expiry until `expire`
+    pub fn incr(&self, key: &str) -> Result<i64, String> {
+        let mut inner = self.lock();
+        let (current, expires_at) = match inner.live(key) {
+            Some(entry) => {
+                let current = entry
+                    .value
+                    .parse::<i64>()
+                    .map_err(|_| "value is not an integer or out of range".to_string())?;
+                (current, entry.expires_at)
+            }
+            None => (0, None),
+        };
+        let count = current + 1;
+        inner.insert(key, count.to_string(), expires_at);
+        Ok(count)
+    }
+
+    pub fn expire(&self, key: &str, ttl_seconds: u64) {
+        if let Some(entry) = self.lock().live(key) {
+            entry.expires_at = Some(expiry(ttl_seconds));
+        }
+    }
+
+    /// Remaining TTL in seconds (-1 = no expiry, -2 = key missing)
+    pub fn ttl(&self, key: &str) -> i64 {
+        match self.lock().live(key) {
+            None => -2,
+            Some(Entry { expires_at: None, .. }) => -1,
+            Some(Entry { expires_at: Some(at), .. }) => {
+                at.saturating_duration_since(Instant::now()).as_secs_f64().round() as i64
+            }
+        }
+    }
+
+    pub fn delete(&self, key: &str) {
+        self.lock().entries.remove(key);
+    }
+
+    /// Replace `key`'s value with the one `f` computes from it (None when
+    /// missing), in one step, keeping it for `ttl_seconds`; returns what `f`
+    /// returns alongside the value
+    pub fn update<T>(&self, key: &str, ttl_seconds: u64, f: impl FnOnce(Option<String>) -> (String, T)) -> T {
+        let mut inner = self.lock();
+        let current = inner.live(key).map(|e| e.value.clone());
+        let (value, result) = f(current);
+        inner.insert(key, value, Some(expiry(ttl_seconds)));
+        result
+    }
+
+    /// Drop every key starting with `prefix`; returns how many were dropped
+    pub fn delete_prefix(&self, prefix: &str) -> u64 {
+        let mut inner = self.lock();
+        let before = inner.entries.len();
+        inner.entries.retain(|key, _| !key.starts_with(prefix));
+        (before - inner.entries.len()) as u64
+    }
+}
+
+fn expiry(ttl_seconds: u64) -> Instant {
+    Instant::now() + Duration::from_secs(ttl_seconds)
+}
@@ -0,0 +1,195 @@
+//! Signed message verification for address ownership.
+//!
+//! Wallets sign a plain text message with the key behind an address; the
+//! public key recovered from the signature must hash to that same address.
+//! Bitcoin uses the BIP-137 format ("Sign message" in Bitcoin Core,
+//! Electrum and most hardware wallets): a base64 65-byte signature whose
+//! header byte carries the recovery id. EVM chains use EIP-191
+//! `personal_sign`: a 0x-prefixed 65-byte hex signature ending in `v`.
+//!
+//! Only single-key addresses can be proven this way: P2PKH, P2SH-wrapped
+//! and native P2WPKH on Bitcoin, and externally owned accounts on EVM.
+
+use base64::Engine;
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+use crate::services::address_validator::{decode_bitcoin_address, BitcoinAddress, Chain};
+
+const BITCOIN_MESSAGE_PREFIX: &[u8] = b"\x18Bitcoin Signed Message:\n";
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum SignatureError {
+    #[error("Message signing is not supported for {0}")]
+    Unsupported(String),
+
+    #[error("Malformed signature: {0}")]
+    Malformed(String),
+
+    #[error("Signature was not made by the key behind this address")]
+    Mismatch,
+}
+
+/// Check that `signature` over `message` was made by the key controlling
+/// `address` on `chain`
+pub fn verify_signed_message(
+    chain: Chain,
+    address: &str,
+    message: &str,
+    signature: &str,
+) -> Result<(), SignatureError> {
+    match chain {
+        Chain::Bitcoin => verify_bitcoin(address, message, signature.trim()),
+        Chain::Evm => verify_evm(address, message, signature.trim()),
+        other => Err(SignatureError::Unsupported(format!("{} addresses", other.as_str()))),
+    }
+}
+
+/// The form an address is stored and compared in: EVM hex and bech32 are
+/// case-insensitive, so they are lowercased; base58 is case-sensitive
+pub fn normalize_address(chain: Chain, address: &str) -> String {
+    let address = address.trim();
+    match chain {
+        Chain::Evm => address.to_lowercase(),
+        Chain::Bitcoin if address.get(..3).is_some_and(|hrp| hrp.eq_ignore_ascii_case("bc1")) => address.to_lowercase(),
+        _ => address.to_string(),
+    }
+}
+
+// =============================================================================
+// BITCOIN (BIP-137)
+// =============================================================================
+
+fn verify_bitcoin(address: &str, message: &str, signature: &str) -> Result<(), SignatureError> {
+    let decoded = decode_bitcoin_address(address)
+        .ok_or_else(|| SignatureError::Malformed("not a valid bitcoin address".to_string()))?;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(signature)
+        .map_err(|_| SignatureError::Malformed("expected base64".to_string()))?;
+    if bytes.len() != 65 {
+        return Err(SignatureError::Malformed("expected 65 bytes".to_string()));
+    }
+
+    // 27-30: uncompressed key, 31-34: compressed, 35-42: segwit (compressed)
+    let header = bytes[0];
+    if !(27..=42).contains(&header) {
+        return Err(SignatureError::Malformed(format!("unknown header byte {}", header)));
+    }
+    let compressed = header >= 31;
+    let key = recover(&bitcoin_message_hash(message), &bytes[1..], (header - 27) & 3)?;
+    let pubkey = key.to_encoded_point(compressed);
+
+    let matches = match decoded {
+        BitcoinAddress::P2pkh(hash) => hash160(pubkey.as_bytes()) == hash,
+        // P2SH-P2WPKH: the redeem script is a v0 witness program of the key hash
+        BitcoinAddress::P2sh(hash) => {
+            let mut script = vec![0x00, 0x14];
+            script.extend_from_slice(&hash160(key.to_encoded_point(true).as_bytes()));
+            hash160(&script) == hash
+        }
+        BitcoinAddress::Segwit { version: 0, program } if program.len() == 20 => {
+            hash160(key.to_encoded_point(true).as_bytes()).as_slice() == program.as_slice()
+        }
+        BitcoinAddress::Segwit { .. } => {
+            return Err(SignatureError::Unsupported("script and taproot addresses".to_string()))
+        }
+    };
+
+    if matches {
+        Ok(())
+    } else {
+        Err(SignatureError::Mismatch)
+    }
+}
+
+/// Double SHA-256 of the prefixed, length-prefixed message
+fn bitcoin_message_hash(message: &str) -> [u8; 32] {
+    let message = message.as_bytes();
+    let mut data = BITCOIN_MESSAGE_PREFIX.to_vec();
+    // Compact size length prefix
+    match message.len() {
+        len if len < 0xfd => data.push(len as u8),
+        len if len <= 0xffff => {
+            data.push(0xfd);
+            data.extend_from_slice(&(len as u16).to_le_bytes());
+        }
+        len => {
+            data.push(0xfe);
+            data.extend_from_slice(&(len as u32).to_le_bytes());
+        }
+    }
+    data.extend_from_slice(message);
+
+    Sha256::digest(Sha256::digest(&data)).into()
+}
+
+fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(Sha256::digest(data)).into()
+}
+
+// =============================================================================
+// EVM (EIP-191)
+// =============================================================================
+
+fn verify_evm(address: &str, message: &str, signature: &str) -> Result<(), SignatureError> {
+    let bytes = signature
+        .strip_prefix("0x")
+        .and_then(decode_hex)
+        .filter(|bytes| bytes.len() == 65)
+        .ok_or_else(|| SignatureError::Malformed("expected 0x-prefixed 65-byte hex".to_string()))?;
+
+    let recovery_id = match bytes[64] {
+        v @ (27 | 28) => v - 27,
+        v @ (0 | 1) => v,
+        v => return Err(SignatureError::Malformed(format!("unknown recovery byte {}", v))),
+    };
+
+    let prefixed = format!("\x19Ethereum Signed Message:\n{}{}", message.len(), message);
+    let hash: [u8; 32] = Keccak256::digest(prefixed.as_bytes()).into();
+    let key = recover(&hash, &bytes[..64], recovery_id)?;
+
+    // The address is the last 20 bytes of the Keccak-256 of the uncompressed key
+    let pubkey = key.to_encoded_point(false);
+    let recovered: String = Keccak256::digest(&pubkey.as_bytes()[1..])[12..]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+
+    if address.strip_prefix("0x").is_some_and(|a| a.eq_ignore_ascii_case(&recovered)) {
+        Ok(())
+    } else {
+        Err(SignatureError::Mismatch)
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| hex.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect()
+}
+
+// =============================================================================
+// RECOVERY
+// =============================================================================
+
+/// Public key behind a 64-byte (r, s) signature over `hash`
+fn recover(hash: &[u8; 32], rs: &[u8], recovery_id: u8) -> Result<VerifyingKey, SignatureError> {
+    let mut signature =
+        Signature::from_slice(rs).map_err(|_| SignatureError::Malformed("invalid r or s".to_string()))?;
+    let mut recovery_id =
+        RecoveryId::from_byte(recovery_id).ok_or_else(|| SignatureError::Malformed("invalid recovery id".to_string()))?;
+
+    // Verification only takes low-s signatures; negating s flips the y parity
+    if let Some(normalized) = signature.normalize_s() {
+        signature = normalized;
+        recovery_id = RecoveryId::new(!recovery_id.is_y_odd(), recovery_id.is_x_reduced());
+    }
+
+    VerifyingKey::recover_from_prehash(hash, &signature, recovery_id).map_err(|_| SignatureError::Mismatch)
+}
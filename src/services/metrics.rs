@@ -0,0 +1,270 @@
+//! Prometheus metrics.
+//!
+//! Process-wide counters and histograms, rendered in the Prometheus text
+//! exposition format at `GET /metrics`. Each instance reports its own
+//! counts since it started; the scraper aggregates across instances.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{LazyLock, Mutex};
+use std::time::Instant;
+
+/// Upper bounds, in seconds, for request and provider call latency
+const LATENCY_BUCKETS: &[f64] = &[0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+// =============================================================================
+// METRIC TYPES
+// =============================================================================
+
+/// Counter with a single label
+pub struct CounterVec {
+    name: &'static str,
+    help: &'static str,
+    label: &'static str,
+    values: Mutex<BTreeMap<String, u64>>,
+}
+
+impl CounterVec {
+    const fn new(name: &'static str, help: &'static str, label: &'static str) -> Self {
+        Self { name, help, label, values: Mutex::new(BTreeMap::new()) }
+    }
+
+    pub fn inc(&self, label_value: &str) {
+        *self.lock().entry(label_value.to_string()).or_default() += 1;
+    }
+
+    pub fn get(&self, label_value: &str) -> u64 {
+        self.lock().get(label_value).copied().unwrap_or(0)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, u64>> {
+        self.values.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn render(&self, out: &mut String) {
+        let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+        let _ = writeln!(out, "# TYPE {} counter", self.name);
+        for (value, count) in self.lock().iter() {
+            let _ = writeln!(out, "{}{{{}=\"{}\"}} {}", self.name, self.label, escape(value), count);
+        }
+    }
+}
+
+#[derive(Default)]
+struct HistogramSeries {
+    buckets: Vec<u64>, // Per bucket, not cumulative; summed on render
+    sum: f64,
+    count: u64,
+}
+
+/// Histogram with a single label
+pub struct HistogramVec {
+    name: &'static str,
+    help: &'static str,
+    label: &'static str,
+    bounds: &'static [f64],
+    series: Mutex<BTreeMap<String, HistogramSeries>>,
+}
+
+impl HistogramVec {
+    const fn new(name: &'static str, help: &'static str, label: &'static str, bounds: &'static [f64]) -> Self {
+        Self { name, help, label, bounds, series: Mutex::new(BTreeMap::new()) }
+    }
+
+    pub fn observe(&self, label_value: &str, value: f64) {
+        let mut series = self.lock();
+        let series = series.entry(label_value.to_string()).or_insert_with(|| HistogramSeries {
+            buckets: vec![0; self.bounds.len()],
+            ..Default::default()
+        });
+        if let Some(i) = self.bounds.iter().position(|bound| value <= *bound) {
+            series.buckets[i] += 1;
+        }
+        series.sum += value;
+        series.count += 1;
+    }
+
+    /// Observations recorded for `label_value`
+    pub fn count(&self, label_value: &str) -> u64 {
+        self.lock().get(label_value).map_or(0, |s| s.count)
+    }
+
+    /// Observe the time from now until the returned timer is dropped
+    pub fn start_timer(&'static self, label_value: &'static str) -> HistogramTimer {
+        HistogramTimer { histogram: self, label_value, started: Instant::now() }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, HistogramSeries>> {
+        self.series.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn render(&self, out: &mut String) {
+        let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+        let _ = writeln!(out, "# TYPE {} histogram", self.name);
+        for (value, series) in self.lock().iter() {
+            let value = escape(value);
+            let mut cumulative = 0;
+            for (bound, count) in self.bounds.iter().zip(&series.buckets) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "{}_bucket{{{}=\"{}\",le=\"{}\"}} {}",
+                    self.name, self.label, value, bound, cumulative
+                );
+            }
+            let _ = writeln!(out, "{}_bucket{{{}=\"{}\",le=\"+Inf\"}} {}", self.name, self.label, value, series.count);
+            let _ = writeln!(out, "{}_sum{{{}=\"{}\"}} {}", self.name, self.label, value, series.sum);
+            let _ = writeln!(out, "{}_count{{{}=\"{}\"}} {}", self.name, self.label, value, series.count);
+        }
+    }
+}
+
+/// Records the elapsed time into its histogram when dropped
+pub struct HistogramTimer {
+    histogram: &'static HistogramVec,
+    label_value: &'static str,
+    started: Instant,
+}
+
+impl Drop for HistogramTimer {
+    fn drop(&mut self) {
+        self.histogram.observe(self.label_value, self.started.elapsed().as_secs_f64());
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+// =============================================================================
+// REGISTRY
+// =============================================================================
+
+pub struct Metrics {
+    /// Handled requests by route ("GET /swap/{id}"); unmatched paths are not recorded
+    pub http_request_seconds: HistogramVec,
+    /// Trocador HTTP calls by endpoint, including reading the body
+    pub trocador_request_seconds: HistogramVec,
+    /// Rate-limited provider calls retried after a backoff
+    pub provider_retries: CounterVec,
+    /// Database statements retried after a transient error, by reason
+    pub db_retries: CounterVec,
+    /// Rates lookups by outcome: hit, coalesced (waited on another caller's fetch) or miss
+    pub rates_cache: CounterVec,
+    /// Swaps created, by the status they were created in
+    pub swaps_created: CounterVec,
+    /// Status changes written to swaps, by new status
+    pub swap_status_changes: CounterVec,
+    /// Failed Redis commands, by command
+    pub redis_errors: CounterVec,
+    /// Cache calls served by the degraded mode while Redis was unreachable, by command
+    pub redis_degraded_calls: CounterVec,
+    /// Requests refused by a per-route limit, by route group
+    pub route_rate_limited: CounterVec,
+    /// Swap creates by admission outcome: admitted, queued (waited for a slot) or rejected
+    pub swap_admission: CounterVec,
+    /// Rate guard outcomes per quote: passed, flagged, rejected; unchecked per response
+    pub rate_guard_decisions: CounterVec,
+    /// Quotes not served for their provider's health, by score or admin override, by provider
+    pub provider_health_hidden: CounterVec,
+    /// Cached entries compared with the database by the consistency checker, by cache
+    pub cache_consistency_checks: CounterVec,
+    /// Checked cache entries that no longer matched the database, by cache
+    pub cache_divergence: CounterVec,
+}
+
+static METRICS: LazyLock<Metrics> = LazyLock::new(|| Metrics {
+    http_request_seconds: HistogramVec::new(
+        "exchange_http_request_duration_seconds",
+        "Latency of handled HTTP requests",
+        "route",
+        LATENCY_BUCKETS,
+    ),
+    trocador_request_seconds: HistogramVec::new(
+        "exchange_trocador_request_duration_seconds",
+        "Latency of Trocador API calls",
+        "endpoint",
+        LATENCY_BUCKETS,
+    ),
+    provider_retries: CounterVec::new(
+        "exchange_provider_retries_total",
+        "Provider calls retried after a rate limit",
+        "provider",
+    ),
+    db_retries: CounterVec::new(
+        "exchange_db_retries_total",
+        "Database statements retried after a transient error",
+        "reason",
+    ),
+    rates_cache: CounterVec::new("exchange_rates_cache_requests_total", "Rates cache lookups", "result"),
+    swaps_created: CounterVec::new("exchange_swaps_created_total", "Swaps created", "status"),
+    swap_status_changes: CounterVec::new(
+        "exchange_swap_status_changes_total",
+        "Swap status changes recorded",
+        "status",
+    ),
+    redis_errors: CounterVec::new("exchange_redis_errors_total", "Failed Redis commands", "command"),
+    redis_degraded_calls: CounterVec::new(
+        "exchange_redis_degraded_calls_total",
+        "Cache calls served without Redis while it was unreachable",
+        "command",
+    ),
+    route_rate_limited: CounterVec::new(
+        "exchange_route_rate_limited_total",
+        "Requests refused by a per-route rate limit",
+        "route",
+    ),
+    swap_admission: CounterVec::new(
+        "exchange_swap_admission_total",
+        "Swap creates by admission outcome",
+        "outcome",
+    ),
+    rate_guard_decisions: CounterVec::new(
+        "exchange_rate_guard_decisions_total",
+        "Quotes checked against the reference rate",
+        "decision",
+    ),
+    provider_health_hidden: CounterVec::new(
+        "exchange_provider_health_hidden_total",
+        "Quotes hidden for their provider's health",
+        "provider",
+    ),
+    cache_consistency_checks: CounterVec::new(
+        "exchange_cache_consistency_checks_total",
+        "Cached entries compared with the database",
+        "cache",
+    ),
+    cache_divergence: CounterVec::new(
+        "exchange_cache_divergence_total",
+        "Cached entries found out of date with the database",
+        "cache",
+    ),
+});
+
+/// Process-wide metrics shared by every module
+pub fn metrics() -> &'static Metrics {
+    &METRICS
+}
+
+impl Metrics {
+    /// Every metric in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        self.http_request_seconds.render(&mut out);
+        self.trocador_request_seconds.render(&mut out);
+        self.provider_retries.render(&mut out);
+        self.db_retries.render(&mut out);
+        self.rates_cache.render(&mut out);
+        self.swaps_created.render(&mut out);
+        self.swap_status_changes.render(&mut out);
+        self.redis_errors.render(&mut out);
+        self.redis_degraded_calls.render(&mut out);
+        self.route_rate_limited.render(&mut out);
+        self.swap_admission.render(&mut out);
+        self.rate_guard_decisions.render(&mut out);
+        self.provider_health_hidden.render(&mut out);
+        self.cache_consistency_checks.render(&mut out);
+        self.cache_divergence.render(&mut out);
+        out
+    }
+}
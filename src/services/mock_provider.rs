@@ -0,0 +1,184 @@
+//! Mock provider behind sandbox swaps.
+//!
+//! Rates requested with `sandbox=true` and swaps created with
+//! `sandbox: true` never reach Trocador: their quotes, trade and status all
+//! come from here. Quotes use the reference prices in HIGH_VALUE_USD_PRICES
+//! (1:1 between unpriced currencies) less a flat fee. The trade then walks
+//! through the usual statuses, one every SANDBOX_STEP_SECS from creation:
+//! the deposit is detected after one step (confirming), paid out after two
+//! (sending) and finished after three. The status is derived from the
+//! swap's age, so nothing is kept here and every instance agrees on it.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::config::environment::{HighValueConfig, SandboxConfig};
+use crate::modules::swap::model::Swap;
+use crate::modules::swap::schema::{CreateSwapRequest, RatesQuery, TrocadorTradeResponse};
+use crate::services::money;
+use crate::services::swap_provider::{AggregatorQuote, AggregatorQuotes, SwapProviderClient};
+
+/// Aggregator tag of sandbox quotes
+pub const SANDBOX_AGGREGATOR: &str = "sandbox";
+
+/// The one provider the mock quotes and trades as; its `providers` row is
+/// inactive, so it is never listed or quoted outside the sandbox
+pub const SANDBOX_PROVIDER: &str = "sandbox";
+
+/// Share of each sandbox quote kept as the provider fee
+const SANDBOX_FEE_PERCENT: Decimal = dec!(0.5);
+
+/// Simulated trade statuses in order, one per step
+const SANDBOX_STATUSES: [&str; 4] = ["waiting", "confirming", "sending", "finished"];
+
+#[derive(Debug, Clone)]
+pub struct MockProviderClient {
+    step: Duration,
+    usd_prices: HashMap<String, f64>, // Reference USD price per ticker (lowercase)
+}
+
+impl MockProviderClient {
+    pub fn new(config: &SandboxConfig, usd_prices: HashMap<String, f64>) -> Self {
+        Self { step: config.step, usd_prices }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(&SandboxConfig::from_env(), HighValueConfig::from_env().usd_prices)
+    }
+
+    /// What `amount` of `from` buys of `to`, after the sandbox fee
+    pub fn quote(&self, from: &str, to: &str, amount: Decimal) -> Decimal {
+        amount * self.rate(from, to)
+    }
+
+    /// What it takes of `from` to receive `amount_to` of `to`, after the sandbox fee
+    pub fn quote_for(&self, from: &str, to: &str, amount_to: Decimal) -> Decimal {
+        amount_to.checked_div(self.rate(from, to)).unwrap_or_default()
+    }
+
+    fn rate(&self, from: &str, to: &str) -> Decimal {
+        let price = |ticker: &str| self.usd_prices.get(&ticker.to_lowercase()).copied().map(money::from_f64);
+        let rate = match (price(from), price(to)) {
+            (Some(from), Some(to)) => from.checked_div(to).unwrap_or(Decimal::ONE),
+            _ => Decimal::ONE,
+        };
+        rate * (Decimal::ONE - SANDBOX_FEE_PERCENT / Decimal::ONE_HUNDRED)
+    }
+
+    /// Open a simulated trade, returned as Trocador's new_trade would be
+    /// along with the raw payload
+    pub fn create_trade(&self, request: &CreateSwapRequest) -> (TrocadorTradeResponse, String) {
+        let trade_id = format!("sandbox-{}", uuid::Uuid::new_v4().simple());
+        let (amount_from, amount_to) = match request.amount_to {
+            Some(amount_to) => (self.quote_for(&request.from, &request.to, amount_to), amount_to),
+            None => (request.amount, self.quote(&request.from, &request.to, request.amount)),
+        };
+        let trade = TrocadorTradeResponse {
+            status: SANDBOX_STATUSES[0].to_string(),
+            ticker_from: request.from.clone(),
+            network_from: request.network_from.clone(),
+            ticker_to: request.to.clone(),
+            network_to: request.network_to.clone(),
+            amount_from,
+            amount_to,
+            provider: SANDBOX_PROVIDER.to_string(),
+            address_provider: format!("sandbox_deposit_{}", &trade_id[8..24]),
+            address_provider_memo: None,
+            address_user: request.recipient_address.clone(),
+            address_user_memo: request.recipient_extra_id.clone(),
+            refund_address: request.refund_address.clone(),
+            refund_address_memo: request.refund_extra_id.clone(),
+            id_provider: None,
+            date: Some(Utc::now().to_rfc3339()),
+            details: None,
+            unknown: serde_json::Map::new(),
+            trade_id,
+        };
+        let raw = raw_trade(&trade);
+        (trade, raw)
+    }
+
+    /// Simulated status `age` after a trade was opened
+    pub fn status_after(&self, age: Duration) -> &'static str {
+        let steps = age.as_secs() / self.step.as_secs().max(1);
+        SANDBOX_STATUSES[(steps as usize).min(SANDBOX_STATUSES.len() - 1)]
+    }
+
+    /// The provider's view of a sandbox swap as of now, as Trocador's trade
+    /// endpoint would return it along with the raw payload
+    pub fn trade_status(&self, swap: &Swap) -> (TrocadorTradeResponse, String) {
+        let age = (Utc::now() - swap.created_at).to_std().unwrap_or_default();
+        let trade = TrocadorTradeResponse {
+            trade_id: swap.provider_swap_id.clone().unwrap_or_default(),
+            status: self.status_after(age).to_string(),
+            ticker_from: swap.from_currency.clone(),
+            network_from: swap.from_network.clone(),
+            ticker_to: swap.to_currency.clone(),
+            network_to: swap.to_network.clone(),
+            amount_from: swap.amount,
+            amount_to: swap.estimated_receive + swap.platform_fee,
+            provider: SANDBOX_PROVIDER.to_string(),
+            address_provider: swap.deposit_address.clone(),
+            address_provider_memo: swap.deposit_extra_id.clone(),
+            address_user: swap.recipient_address.clone(),
+            address_user_memo: swap.recipient_extra_id.clone(),
+            refund_address: swap.refund_address.clone(),
+            refund_address_memo: swap.refund_extra_id.clone(),
+            id_provider: None,
+            date: Some(swap.created_at.to_rfc3339()),
+            details: None,
+            unknown: serde_json::Map::new(),
+        };
+        let raw = raw_trade(&trade);
+        (trade, raw)
+    }
+}
+
+fn raw_trade(trade: &TrocadorTradeResponse) -> String {
+    serde_json::json!({
+        "trade_id": trade.trade_id,
+        "status": trade.status,
+        "ticker_from": trade.ticker_from,
+        "network_from": trade.network_from,
+        "ticker_to": trade.ticker_to,
+        "network_to": trade.network_to,
+        "amount_from": trade.amount_from,
+        "amount_to": trade.amount_to,
+        "provider": trade.provider,
+        "address_provider": trade.address_provider,
+        "address_user": trade.address_user,
+        "date": trade.date,
+        "sandbox": true,
+    })
+    .to_string()
+}
+
+#[async_trait]
+impl SwapProviderClient for MockProviderClient {
+    fn aggregator(&self) -> &'static str {
+        SANDBOX_AGGREGATOR
+    }
+
+    async fn get_quotes(&self, query: &RatesQuery) -> Result<AggregatorQuotes, String> {
+        let (amount_from, amount_to) = match query.amount_to {
+            Some(amount_to) => (Some(self.quote_for(&query.from, &query.to, amount_to)), amount_to),
+            None => (None, self.quote(&query.from, &query.to, query.amount)),
+        };
+        let quote = AggregatorQuote {
+            provider: SANDBOX_PROVIDER.to_string(),
+            amount_to,
+            amount_from,
+            min_amount: Decimal::ZERO,
+            max_amount: Decimal::ZERO,
+            provider_fee: amount_to * SANDBOX_FEE_PERCENT / (Decimal::ONE_HUNDRED - SANDBOX_FEE_PERCENT),
+            kyc_rating: Some("A".to_string()),
+            eta_minutes: Some((self.step.as_secs() * 3).div_ceil(60) as u32),
+        };
+
+        Ok(AggregatorQuotes { trade_id: None, quotes: vec![quote] })
+    }
+}
@@ -1,7 +1,51 @@
+pub mod address_format;
+pub mod address_validator;
+pub mod admission;
+pub mod analytics;
+pub mod api_usage;
+pub mod audit;
+pub mod block_explorer;
+pub mod branding;
+pub mod cache_stats;
+pub mod cache_warmup;
+pub mod changenow;
+pub mod circuit_breaker;
+pub mod db_retry;
+pub mod deposit_check;
+pub mod email;
+pub mod encryption;
+pub mod event_bus;
+pub mod fee_estimator;
+pub mod fees;
 pub mod hashing;
+pub mod idempotency;
+pub mod jobs;
 pub mod jwt;
+pub mod maintenance;
+pub mod memory_cache;
+pub mod message_signing;
+pub mod metrics;
+pub mod mock_provider;
+pub mod money;
+pub mod notifications;
+pub mod outbox;
+pub mod payload_codec;
+pub mod price_feed;
+pub mod provider_credentials;
+pub mod provider_health;
+pub mod rate_guard;
 pub mod rate_limit;
 pub mod rate_limiter;
+pub mod reconciliation;
 pub mod redis_cache;
+pub mod request_id;
+pub mod request_logging;
+pub mod retention;
+pub mod routing;
+pub mod schema_drift;
 pub mod security;
+pub mod single_flight;
+pub mod slo;
+pub mod swap_provider;
+pub mod tenant;
 pub mod trocador;
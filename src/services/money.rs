@@ -0,0 +1,76 @@
+//! Exact money math.
+//!
+//! Crypto amounts, rates and fees are `Decimal` from the aggregator's JSON
+//! through the DECIMAL columns and out of the API, where they are written as
+//! strings so clients keep every digit too (numbers are still accepted on
+//! input). Amounts are rounded to the currency's `decimals`: what a user is
+//! promised rounds down and what they are asked to send rounds up, so
+//! rounding never promises more than the quote. Rates keep `RATE_SCALE`
+//! places. USD estimates, percentages of health metrics and timings stay
+//! `f64`; `to_f64` is the one way across.
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::{Decimal, RoundingStrategy};
+
+/// Decimal places kept on rates (quotes.rate is DECIMAL(30, 12))
+pub const RATE_SCALE: u32 = 12;
+
+/// Places assumed for a currency that isn't listed
+pub const DEFAULT_DECIMALS: u32 = 8;
+
+/// Most places an amount keeps (swaps' amount columns are DECIMAL(20, 8)),
+/// whatever a currency's `decimals` says
+pub const MAX_AMOUNT_DECIMALS: u32 = 8;
+
+/// Parse an amount an aggregator sent as a string, e.g. "0.0123" or "1.5e-7"
+pub fn parse_amount(value: &str) -> Option<Decimal> {
+    let value = value.trim();
+    value
+        .parse::<Decimal>()
+        .or_else(|_| Decimal::from_scientific(value))
+        .ok()
+        .map(|amount| amount.normalize())
+}
+
+/// Amount for a value only available as a float (on-chain sums, legacy
+/// sources); the float's shortest representation, so 0.1 stays 0.1
+pub fn from_f64(value: f64) -> Decimal {
+    if !value.is_finite() {
+        return Decimal::ZERO;
+    }
+    value.to_string().parse().ok().or_else(|| Decimal::from_f64(value)).unwrap_or_default()
+}
+
+/// For pricing in USD, metrics and other estimates
+pub fn to_f64(amount: Decimal) -> f64 {
+    amount.to_f64().unwrap_or(0.0)
+}
+
+/// What `amount_from` buys of the receive currency per unit; zero when
+/// there is nothing to divide by
+pub fn rate(amount_to: Decimal, amount_from: Decimal) -> Decimal {
+    amount_to
+        .checked_div(amount_from)
+        .map(|rate| rate.round_dp_with_strategy(RATE_SCALE, RoundingStrategy::MidpointNearestEven).normalize())
+        .unwrap_or_default()
+}
+
+/// An amount the user receives, rounded down to what the currency can carry
+pub fn round_receive(amount: Decimal, decimals: u32) -> Decimal {
+    amount.round_dp_with_strategy(decimals, RoundingStrategy::ToZero).normalize()
+}
+
+/// An amount the user sends, rounded up so it still covers the quote
+pub fn round_send(amount: Decimal, decimals: u32) -> Decimal {
+    amount.round_dp_with_strategy(decimals, RoundingStrategy::AwayFromZero).normalize()
+}
+
+/// The amount has digits past what the currency can carry
+pub fn exceeds_decimals(amount: Decimal, decimals: u32) -> bool {
+    amount.normalize().scale() > decimals
+}
+
+/// Currency `decimals` column as the places amounts are kept to
+pub fn scale_of(decimals: i32) -> u32 {
+    (decimals.max(0) as u32).min(MAX_AMOUNT_DECIMALS)
+}
@@ -0,0 +1,506 @@
+//! User notifications over pluggable channels (email, Telegram).
+//!
+//! Producers queue rows in `user_notifications` with `enqueue`; the sender
+//! drains pending rows and delivers each over the channels the user turned on
+//! in `notification_preferences` (email only for users without a row). A
+//! notification is sent once any channel delivered it; when every channel
+//! fails it is retried on the next drain, up to NOTIFICATIONS_MAX_ATTEMPTS.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+
+use crate::config::environment::{BrandingConfig, NotificationConfig};
+use crate::config::DbPool;
+use crate::modules::swap::model::Swap;
+use crate::modules::swap::schema::SwapStatus;
+use crate::services::branding::{Brand, BrandRegistry};
+use crate::services::email::{EmailMessage, EmailService};
+use crate::services::jobs::{self, JobKind, JobOutcome};
+use crate::services::redis_cache::RedisService;
+
+// =============================================================================
+// NOTICES
+// =============================================================================
+
+#[derive(Debug, Clone)]
+pub struct Notice {
+    pub kind: String, // e.g. "swap_completed"; selects the preference toggle
+    pub title: String,
+    pub body: String,
+}
+
+/// What to tell a swap's owner when it moves to `status`; None for the
+/// in-between statuses, which only show on the status page
+pub fn swap_status_notice(swap: &Swap, status: &SwapStatus) -> Option<Notice> {
+    let pair = format!("{} {} to {}", swap.amount, swap.from_currency.to_uppercase(), swap.to_currency.to_uppercase());
+    let (kind, title, body) = match status {
+        SwapStatus::Completed => (
+            "swap_completed",
+            "Your swap is complete",
+            format!("Swap {} ({}) is complete and the funds have been sent to your address.", swap.id, pair),
+        ),
+        SwapStatus::Failed => (
+            "swap_failed",
+            "Your swap failed",
+            format!(
+                "Swap {} ({}) failed. If you sent a deposit, the provider refunds it to your refund address; \
+                 contact support with the swap id if it does not arrive.",
+                swap.id, pair
+            ),
+        ),
+        SwapStatus::Refunded => (
+            "swap_refunded",
+            "Your swap was refunded",
+            format!("Swap {} ({}) was refunded to your refund address.", swap.id, pair),
+        ),
+        SwapStatus::Expired => (
+            "swap_expired",
+            "Your swap expired without a deposit",
+            format!(
+                "No {} deposit arrived for swap {} before it expired. Do not send funds to its deposit address; \
+                 retry the swap to get a new one.",
+                swap.from_currency.to_uppercase(),
+                swap.id
+            ),
+        ),
+        _ => return None,
+    };
+
+    Some(Notice { kind: kind.to_string(), title: title.to_string(), body })
+}
+
+/// Queue a notification for the sender
+pub async fn enqueue(
+    pool: &DbPool,
+    user_id: &str,
+    swap_id: Option<&str>,
+    brand: Option<&str>,
+    notice: &Notice,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO user_notifications (user_id, swap_id, brand, kind, title, body) VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(user_id)
+    .bind(swap_id)
+    .bind(brand)
+    .bind(&notice.kind)
+    .bind(&notice.title)
+    .bind(&notice.body)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+// =============================================================================
+// PREFERENCES
+// =============================================================================
+
+/// A user's row in `notification_preferences`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotificationPreferences {
+    pub email_enabled: bool,
+    pub telegram_enabled: bool,
+    pub telegram_chat_id: Option<String>, // From the bot's /start chat
+    pub swap_completed: bool,
+    pub swap_failed: bool,     // Also refunds
+    pub action_required: bool, // Expired without a deposit, deposit lost
+}
+
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            email_enabled: true,
+            telegram_enabled: false,
+            telegram_chat_id: None,
+            swap_completed: true,
+            swap_failed: true,
+            action_required: true,
+        }
+    }
+}
+
+impl NotificationPreferences {
+    /// Whether the user wants notifications of `kind`; kinds outside the
+    /// swap toggles (e.g. delisting notices) always go out
+    pub fn wants(&self, kind: &str) -> bool {
+        match kind {
+            "swap_completed" => self.swap_completed,
+            "swap_failed" | "swap_refunded" => self.swap_failed,
+            "swap_expired" | "deposit_lost" => self.action_required,
+            _ => true,
+        }
+    }
+
+    pub async fn load(pool: &DbPool, user_id: &str) -> Result<Self, sqlx::Error> {
+        let row: Option<(bool, bool, Option<String>, bool, bool, bool)> = sqlx::query_as(
+            "SELECT email_enabled, telegram_enabled, telegram_chat_id, swap_completed, swap_failed, action_required
+             FROM notification_preferences WHERE user_id = ?",
+        )
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row
+            .map(|(email_enabled, telegram_enabled, telegram_chat_id, swap_completed, swap_failed, action_required)| {
+                Self { email_enabled, telegram_enabled, telegram_chat_id, swap_completed, swap_failed, action_required }
+            })
+            .unwrap_or_default())
+    }
+
+    pub async fn save(&self, pool: &DbPool, user_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO notification_preferences
+                (user_id, email_enabled, telegram_enabled, telegram_chat_id, swap_completed, swap_failed, action_required)
+             VALUES (?, ?, ?, ?, ?, ?, ?)
+             ON DUPLICATE KEY UPDATE
+                email_enabled = VALUES(email_enabled), telegram_enabled = VALUES(telegram_enabled),
+                telegram_chat_id = VALUES(telegram_chat_id), swap_completed = VALUES(swap_completed),
+                swap_failed = VALUES(swap_failed), action_required = VALUES(action_required)",
+        )
+        .bind(user_id)
+        .bind(self.email_enabled)
+        .bind(self.telegram_enabled)
+        .bind(&self.telegram_chat_id)
+        .bind(self.swap_completed)
+        .bind(self.swap_failed)
+        .bind(self.action_required)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
+
+// =============================================================================
+// CHANNELS
+// =============================================================================
+
+/// Who a notification goes to, with their preferences and the brand to sign with
+#[derive(Debug, Clone)]
+pub struct Recipient {
+    pub user_id: String,
+    pub email: String,
+    pub preferences: NotificationPreferences,
+    pub brand: Brand,
+}
+
+#[async_trait]
+pub trait NotificationChannel: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// The recipient turned this channel on and can be reached on it
+    fn reaches(&self, recipient: &Recipient) -> bool;
+
+    async fn deliver(&self, recipient: &Recipient, notice: &Notice) -> Result<(), String>;
+}
+
+/// Branded email through the configured email backend
+pub struct EmailChannel {
+    email: EmailService,
+}
+
+impl EmailChannel {
+    pub fn new(email: EmailService) -> Self {
+        Self { email }
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for EmailChannel {
+    fn name(&self) -> &'static str {
+        "email"
+    }
+
+    fn reaches(&self, recipient: &Recipient) -> bool {
+        recipient.preferences.email_enabled
+    }
+
+    async fn deliver(&self, recipient: &Recipient, notice: &Notice) -> Result<(), String> {
+        let message = EmailMessage {
+            to: recipient.email.clone(),
+            subject: notice.title.clone(),
+            text_body: notice.body.clone(),
+        };
+        self.email.send_branded(&recipient.brand, &message).await.map_err(|e| e.to_string())
+    }
+}
+
+/// Telegram Bot API `sendMessage` to the chat the user linked
+pub struct TelegramChannel {
+    http: reqwest::Client,
+    api_url: String,
+    bot_token: String,
+}
+
+impl TelegramChannel {
+    pub fn new(api_url: &str, bot_token: &str) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            bot_token: bot_token.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for TelegramChannel {
+    fn name(&self) -> &'static str {
+        "telegram"
+    }
+
+    fn reaches(&self, recipient: &Recipient) -> bool {
+        recipient.preferences.telegram_enabled && recipient.preferences.telegram_chat_id.is_some()
+    }
+
+    async fn deliver(&self, recipient: &Recipient, notice: &Notice) -> Result<(), String> {
+        let body = serde_json::json!({
+            "chat_id": recipient.preferences.telegram_chat_id,
+            "text": format!("{}\n\n{}\n\n{}", notice.title, notice.body, recipient.brand.name),
+        });
+
+        self.http
+            .post(format!("{}/bot{}/sendMessage", self.api_url, self.bot_token))
+            .json(&body)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map(|_| ())
+            // reqwest errors carry the URL, which carries the token
+            .map_err(|e| e.without_url().to_string())
+    }
+}
+
+// =============================================================================
+// SENDER
+// =============================================================================
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct DrainStats {
+    pub sent: usize,
+    pub skipped: usize,  // Muted, or no channel reaches the user
+    pub retrying: usize, // Every channel failed; tried again next drain
+    pub failed: usize,   // Out of attempts
+}
+
+#[derive(sqlx::FromRow)]
+struct PendingNotification {
+    id: i64,
+    user_id: String,
+    brand: Option<String>,
+    kind: String,
+    title: String,
+    body: String,
+    attempts: i32,
+    email: String,
+    email_enabled: Option<bool>,
+    telegram_enabled: Option<bool>,
+    telegram_chat_id: Option<String>,
+    swap_completed: Option<bool>,
+    swap_failed: Option<bool>,
+    action_required: Option<bool>,
+}
+
+impl PendingNotification {
+    fn preferences(&self) -> NotificationPreferences {
+        let defaults = NotificationPreferences::default();
+        NotificationPreferences {
+            email_enabled: self.email_enabled.unwrap_or(defaults.email_enabled),
+            telegram_enabled: self.telegram_enabled.unwrap_or(defaults.telegram_enabled),
+            telegram_chat_id: self.telegram_chat_id.clone().filter(|id| !id.is_empty()),
+            swap_completed: self.swap_completed.unwrap_or(defaults.swap_completed),
+            swap_failed: self.swap_failed.unwrap_or(defaults.swap_failed),
+            action_required: self.action_required.unwrap_or(defaults.action_required),
+        }
+    }
+}
+
+pub struct NotificationSender {
+    pool: DbPool,
+    channels: Vec<Box<dyn NotificationChannel>>,
+    brands: BrandRegistry,
+    default_brand: Brand,
+    max_attempts: u32,
+}
+
+impl NotificationSender {
+    /// Email always; Telegram when TELEGRAM_BOT_TOKEN is set
+    pub fn from_config(
+        pool: DbPool,
+        redis: RedisService,
+        email: EmailService,
+        branding: &BrandingConfig,
+        config: &NotificationConfig,
+    ) -> Self {
+        let mut channels: Vec<Box<dyn NotificationChannel>> = vec![Box::new(EmailChannel::new(email))];
+        if let Some(token) = &config.telegram_bot_token {
+            channels.push(Box::new(TelegramChannel::new(&config.telegram_api_url, token)));
+        }
+
+        Self::with_channels(pool, Some(redis), channels, Brand::from_config(branding), config.max_attempts)
+    }
+
+    pub fn with_channels(
+        pool: DbPool,
+        redis: Option<RedisService>,
+        channels: Vec<Box<dyn NotificationChannel>>,
+        default_brand: Brand,
+        max_attempts: u32,
+    ) -> Self {
+        Self {
+            brands: BrandRegistry::new(pool.clone(), redis),
+            pool,
+            channels,
+            default_brand,
+            max_attempts: max_attempts.max(1),
+        }
+    }
+
+    pub fn channel_names(&self) -> Vec<&'static str> {
+        self.channels.iter().map(|c| c.name()).collect()
+    }
+
+    /// Deliver up to `batch_size` pending notifications, oldest first
+    pub async fn drain(&self, batch_size: u32) -> Result<DrainStats, sqlx::Error> {
+        let pending = sqlx::query_as::<_, PendingNotification>(
+            "SELECT n.id, n.user_id, n.brand, n.kind, n.title, n.body, n.attempts, u.email,
+                    p.email_enabled, p.telegram_enabled, p.telegram_chat_id,
+                    p.swap_completed, p.swap_failed, p.action_required
+             FROM user_notifications n
+             JOIN users u ON u.id = n.user_id
+             LEFT JOIN notification_preferences p ON p.user_id = n.user_id
+             WHERE n.status = 'pending'
+             ORDER BY n.id
+             LIMIT ?",
+        )
+        .bind(batch_size)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut stats = DrainStats::default();
+        if pending.is_empty() {
+            return Ok(stats);
+        }
+
+        let brands = self.brands.all().await.unwrap_or_else(|e| {
+            tracing::warn!("Failed to load brands for notifications: {}", e);
+            Vec::new()
+        });
+
+        for row in pending {
+            let recipient = Recipient {
+                user_id: row.user_id.clone(),
+                email: row.email.clone(),
+                preferences: row.preferences(),
+                brand: row
+                    .brand
+                    .as_deref()
+                    .and_then(|slug| brands.iter().find(|b| b.slug == slug))
+                    .unwrap_or(&self.default_brand)
+                    .clone(),
+            };
+            let notice = Notice { kind: row.kind.clone(), title: row.title.clone(), body: row.body.clone() };
+
+            let channels: Vec<&dyn NotificationChannel> = if recipient.preferences.wants(&notice.kind) {
+                self.channels.iter().map(|c| c.as_ref()).filter(|c| c.reaches(&recipient)).collect()
+            } else {
+                Vec::new()
+            };
+            if channels.is_empty() {
+                self.mark(row.id, "skipped", None).await?;
+                stats.skipped += 1;
+                continue;
+            }
+
+            let mut errors = Vec::new();
+            for channel in &channels {
+                if let Err(e) = channel.deliver(&recipient, &notice).await {
+                    tracing::warn!(
+                        "Failed to send notification {} to user {} by {}: {}",
+                        row.id, recipient.user_id, channel.name(), e
+                    );
+                    errors.push(format!("{}: {}", channel.name(), e));
+                }
+            }
+
+            if errors.len() < channels.len() {
+                self.mark(row.id, "sent", None).await?;
+                stats.sent += 1;
+            } else if row.attempts as u32 + 1 >= self.max_attempts {
+                self.mark(row.id, "failed", Some(&errors.join("; "))).await?;
+                stats.failed += 1;
+            } else {
+                self.mark(row.id, "pending", Some(&errors.join("; "))).await?;
+                stats.retrying += 1;
+            }
+        }
+
+        Ok(stats)
+    }
+
+    async fn mark(&self, id: i64, status: &str, error: Option<&str>) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE user_notifications
+             SET status = ?, attempts = attempts + 1, last_error = LEFT(?, 500),
+                 sent_at = IF(? = 'sent', NOW(), sent_at)
+             WHERE id = ?",
+        )
+        .bind(status)
+        .bind(error)
+        .bind(status)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+const LOCK_KEY: &str = "lock:notification_sender";
+
+/// Spawn the loop that drains the notification queue; one instance drains at a time
+pub fn spawn_notification_sender(
+    sender: NotificationSender,
+    redis: RedisService,
+    config: NotificationConfig,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        tracing::info!("Notification sender started ({})", sender.channel_names().join(", "));
+        let job = jobs::registry().register(
+            "notification_sender",
+            "Deliver queued user notifications by email and Telegram",
+            JobKind::Scheduled,
+        );
+
+        loop {
+            let run = job.start();
+            let lock_ttl = config.interval.as_secs().max(60);
+            let mut drained = 0;
+            match redis.try_lock(LOCK_KEY, lock_ttl).await {
+                Ok(true) => {
+                    match sender.drain(config.batch_size).await {
+                        Ok(stats) => {
+                            drained = stats.sent + stats.skipped + stats.retrying + stats.failed;
+                            if drained > 0 {
+                                tracing::info!(
+                                    "Notifications: {} sent, {} skipped, {} retrying, {} failed",
+                                    stats.sent, stats.skipped, stats.retrying, stats.failed
+                                );
+                            }
+                            run.finish(JobOutcome::Success, None);
+                        }
+                        Err(e) => {
+                            tracing::error!("Notification drain failed: {}", e);
+                            run.finish(JobOutcome::Failed, Some(e.to_string()));
+                        }
+                    }
+                    let _ = redis.delete(LOCK_KEY).await;
+                }
+                _ => run.finish(JobOutcome::Skipped, None),
+            }
+
+            // Keep draining while there is a backlog
+            if drained < config.batch_size as usize {
+                job.wait(config.interval).await;
+            }
+        }
+    })
+}
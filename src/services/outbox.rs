@@ -0,0 +1,209 @@
+//! Domain event outbox.
+//!
+//! Producers append rows to `event_outbox` next to the change they describe;
+//! the relay publishes pending rows in id order to the configured event bus
+//! and marks them published. Delivery is at-least-once: consumers should
+//! dedupe on the envelope `id`.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::task::JoinHandle;
+
+use crate::config::environment::{EventBusConfig, EventBusKind};
+use crate::config::DbPool;
+use crate::services::event_bus::EventPublisher;
+use crate::services::jobs::{self, JobKind, JobOutcome};
+use crate::services::redis_cache::RedisService;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DomainEventType {
+    SwapCreated,
+    SwapStatusChanged,
+    SyncCompleted,
+    AlertRaised,
+}
+
+impl DomainEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DomainEventType::SwapCreated => "swap.created",
+            DomainEventType::SwapStatusChanged => "swap.status_changed",
+            DomainEventType::SyncCompleted => "sync.completed",
+            DomainEventType::AlertRaised => "alert.raised",
+        }
+    }
+
+    /// Bump when the shape of this event's `data` changes incompatibly
+    pub fn schema_version(&self) -> u16 {
+        match self {
+            DomainEventType::SwapCreated
+            | DomainEventType::SwapStatusChanged
+            | DomainEventType::SyncCompleted
+            | DomainEventType::AlertRaised => 1,
+        }
+    }
+}
+
+/// What consumers receive on the bus
+#[derive(Debug, Serialize)]
+pub struct EventEnvelope {
+    pub id: u64,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub schema_version: u16,
+    pub aggregate_id: String,
+    pub occurred_at: DateTime<Utc>,
+    pub data: serde_json::Value,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct OutboxRow {
+    id: u64,
+    event_type: String,
+    schema_version: u16,
+    aggregate_id: String,
+    payload: String,
+    created_at: DateTime<Utc>,
+}
+
+/// Cheap to clone; a disabled outbox records nothing
+#[derive(Clone, Default)]
+pub struct Outbox {
+    pool: Option<DbPool>,
+}
+
+impl Outbox {
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Rows are only written while an event bus is configured to drain them
+    pub fn from_config(config: &EventBusConfig, pool: DbPool) -> Self {
+        match config.kind {
+            EventBusKind::Off => Self::disabled(),
+            _ => Self { pool: Some(pool) },
+        }
+    }
+
+    /// Append an event; failures are logged, never surfaced to the caller
+    pub async fn record(&self, event_type: DomainEventType, aggregate_id: &str, data: serde_json::Value) {
+        let Some(pool) = &self.pool else {
+            return;
+        };
+
+        let result = sqlx::query(
+            "INSERT INTO event_outbox (event_type, schema_version, aggregate_id, payload) VALUES (?, ?, ?, ?)",
+        )
+        .bind(event_type.as_str())
+        .bind(event_type.schema_version())
+        .bind(aggregate_id)
+        .bind(data.to_string())
+        .execute(pool)
+        .await;
+
+        if let Err(e) = result {
+            tracing::warn!("Failed to record {} event for {}: {}", event_type.as_str(), aggregate_id, e);
+        }
+    }
+}
+
+// =============================================================================
+// RELAY
+// =============================================================================
+
+const RELAY_LOCK_KEY: &str = "lock:outbox_relay";
+
+/// Spawn the loop that drains `event_outbox` into `publisher`
+pub fn spawn_outbox_relay(
+    pool: DbPool,
+    redis: RedisService,
+    publisher: Box<dyn EventPublisher>,
+    config: EventBusConfig,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        tracing::info!("Outbox relay started ({} publisher)", publisher.name());
+        let job = jobs::registry().register(
+            "outbox_relay",
+            "Publish pending domain events to the event bus",
+            JobKind::Scheduled,
+        );
+
+        loop {
+            // One relay at a time across instances keeps per-aggregate ordering
+            let run = job.start();
+            let lock_ttl = config.relay_interval.as_secs().max(30);
+            let published = match redis.try_lock(RELAY_LOCK_KEY, lock_ttl).await {
+                Ok(true) => {
+                    let published = relay_batch(&pool, publisher.as_ref(), &config).await;
+                    let _ = redis.delete(RELAY_LOCK_KEY).await;
+                    run.finish(JobOutcome::Success, None);
+                    published
+                }
+                _ => {
+                    run.finish(JobOutcome::Skipped, None);
+                    0
+                }
+            };
+
+            // Keep draining while there is a backlog
+            if published < config.batch_size as usize {
+                job.wait(config.relay_interval).await;
+            }
+        }
+    })
+}
+
+/// Publish up to `batch_size` pending rows; stops at the first failure so
+/// later events are not delivered ahead of it. Returns how many were published.
+async fn relay_batch(pool: &DbPool, publisher: &dyn EventPublisher, config: &EventBusConfig) -> usize {
+    let rows = sqlx::query_as::<_, OutboxRow>(
+        "SELECT id, event_type, schema_version, aggregate_id, CAST(payload AS CHAR) AS payload, created_at
+         FROM event_outbox WHERE published_at IS NULL ORDER BY id LIMIT ?",
+    )
+    .bind(config.batch_size)
+    .fetch_all(pool)
+    .await;
+
+    let rows = match rows {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::error!("Failed to read event outbox: {}", e);
+            return 0;
+        }
+    };
+
+    let mut published = 0;
+    for row in rows {
+        let topic = config.topic_for(&row.event_type);
+        let envelope = EventEnvelope {
+            id: row.id,
+            event_type: row.event_type,
+            schema_version: row.schema_version,
+            aggregate_id: row.aggregate_id,
+            occurred_at: row.created_at,
+            data: serde_json::from_str(&row.payload).unwrap_or(serde_json::Value::Null),
+        };
+        let payload = serde_json::to_string(&envelope).unwrap_or_default();
+
+        match publisher.publish(&topic, &envelope.aggregate_id, &payload).await {
+            Ok(()) => {
+                let _ = sqlx::query("UPDATE event_outbox SET published_at = NOW(3), attempts = attempts + 1 WHERE id = ?")
+                    .bind(row.id)
+                    .execute(pool)
+                    .await;
+                published += 1;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to publish outbox event {} to {}: {}", row.id, topic, e);
+                let _ = sqlx::query("UPDATE event_outbox SET attempts = attempts + 1, last_error = ? WHERE id = ?")
+                    .bind(e)
+                    .bind(row.id)
+                    .execute(pool)
+                    .await;
+                break;
+            }
+        }
+    }
+
+    published
+}
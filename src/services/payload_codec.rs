@@ -0,0 +1,39 @@
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+
+/// Raw payloads larger than this are truncated before compression
+pub const MAX_PAYLOAD_BYTES: usize = 64 * 1024;
+
+/// A gzip-compressed, size-capped copy of a provider response body
+pub struct EncodedPayload {
+    pub compressed: Vec<u8>,
+    pub original_bytes: usize,
+    pub truncated: bool,
+}
+
+pub fn encode(raw: &str) -> Result<EncodedPayload, String> {
+    let mut cut = raw.len().min(MAX_PAYLOAD_BYTES);
+    while !raw.is_char_boundary(cut) {
+        cut -= 1;
+    }
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&raw.as_bytes()[..cut]).map_err(|e| e.to_string())?;
+    let compressed = encoder.finish().map_err(|e| e.to_string())?;
+
+    Ok(EncodedPayload {
+        compressed,
+        original_bytes: raw.len(),
+        truncated: cut < raw.len(),
+    })
+}
+
+pub fn decode(compressed: &[u8]) -> Result<String, String> {
+    let mut raw = String::new();
+    GzDecoder::new(compressed)
+        .read_to_string(&mut raw)
+        .map_err(|e| e.to_string())?;
+    Ok(raw)
+}
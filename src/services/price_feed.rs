@@ -0,0 +1,149 @@
+//! Fiat prices for the USD and EUR amounts served next to crypto amounts.
+//!
+//! Prices come from a CoinGecko-compatible `/simple/price` endpoint, one
+//! request for every coin in PRICE_FEED_IDS, and are cached in Redis for
+//! PRICE_FEED_CACHE_SECS so instances share them. They are for display only:
+//! swap limits and high-value tagging keep using HIGH_VALUE_USD_PRICES. When
+//! the feed is disabled, unreachable or does not list a ticker, the fiat
+//! fields are simply left out; a failed fetch is not retried for
+//! `FAILURE_BACKOFF`, so rates never wait on a feed that is down.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use utoipa::ToSchema;
+
+use crate::config::environment::PriceFeedConfig;
+use crate::services::money;
+use crate::services::redis_cache::RedisService;
+
+const CACHE_KEY: &str = "price_feed:prices";
+
+/// Pause after a failed fetch before the feed is asked again
+const FAILURE_BACKOFF: Duration = Duration::from_secs(30);
+
+/// An amount (or the price of one coin) in fiat
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FiatAmount {
+    pub usd: f64,
+    pub eur: f64,
+}
+
+impl FiatAmount {
+    /// `amount` coins at this price per coin, rounded to cents
+    pub fn times(&self, amount: Decimal) -> Self {
+        let amount = money::to_f64(amount);
+        let cents = |value: f64| (value * amount * 100.0).round() / 100.0;
+        Self { usd: cents(self.usd), eur: cents(self.eur) }
+    }
+}
+
+/// Price per coin by ticker (lowercase)
+pub type FiatPrices = HashMap<String, FiatAmount>;
+
+/// Prices from a `/simple/price?vs_currencies=usd,eur` response, keyed by
+/// the tickers in `coin_ids`. Coins missing either currency are skipped.
+pub fn parse_simple_price(coin_ids: &HashMap<String, String>, body: &serde_json::Value) -> FiatPrices {
+    coin_ids
+        .iter()
+        .filter_map(|(ticker, id)| {
+            let quote = body.get(id)?;
+            let usd = quote.get("usd")?.as_f64()?;
+            let eur = quote.get("eur")?.as_f64()?;
+            (usd > 0.0 && eur > 0.0).then(|| (ticker.clone(), FiatAmount { usd, eur }))
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+pub struct PriceFeed {
+    config: Option<Arc<PriceFeedConfig>>, // None when disabled
+    client: reqwest::Client,
+    redis: Option<RedisService>,
+    failed_at: Arc<Mutex<Option<Instant>>>,
+}
+
+impl PriceFeed {
+    pub fn from_config(config: &PriceFeedConfig, redis: Option<RedisService>) -> Self {
+        if !config.enabled || config.coin_ids.is_empty() {
+            return Self::disabled();
+        }
+        let client = reqwest::Client::builder().timeout(config.timeout).build().unwrap_or_default();
+        Self { config: Some(Arc::new(config.clone())), client, redis, failed_at: Arc::new(Mutex::new(None)) }
+    }
+
+    /// A feed that never has prices
+    pub fn disabled() -> Self {
+        Self { config: None, client: reqwest::Client::new(), redis: None, failed_at: Arc::new(Mutex::new(None)) }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.is_some()
+    }
+
+    /// Current prices, from the cache when fresh; empty when disabled or
+    /// the feed cannot be reached
+    pub async fn prices(&self) -> FiatPrices {
+        let Some(config) = &self.config else {
+            return FiatPrices::new();
+        };
+        if let Some(redis) = &self.redis {
+            if let Ok(Some(prices)) = redis.get_json::<FiatPrices>(CACHE_KEY).await {
+                return prices;
+            }
+        }
+        if self.backing_off() {
+            return FiatPrices::new();
+        }
+
+        match self.fetch(config).await {
+            Ok(prices) => {
+                if let Some(redis) = &self.redis {
+                    let _ = redis.set_json(CACHE_KEY, &prices, config.cache_ttl.as_secs()).await;
+                }
+                prices
+            }
+            Err(e) => {
+                tracing::warn!("Price feed unavailable, serving amounts without fiat values: {}", e);
+                *self.failed_at.lock().unwrap_or_else(|e| e.into_inner()) = Some(Instant::now());
+                FiatPrices::new()
+            }
+        }
+    }
+
+    fn backing_off(&self) -> bool {
+        self.failed_at
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_some_and(|at| at.elapsed() < FAILURE_BACKOFF)
+    }
+
+    async fn fetch(&self, config: &PriceFeedConfig) -> Result<FiatPrices, String> {
+        let mut ids: Vec<&str> = config.coin_ids.values().map(String::as_str).collect();
+        ids.sort_unstable();
+        ids.dedup();
+
+        let mut request = self
+            .client
+            .get(format!("{}/simple/price", config.base_url.trim_end_matches('/')))
+            .query(&[("ids", ids.join(",").as_str()), ("vs_currencies", "usd,eur")]);
+        if let Some(key) = &config.api_key {
+            request = request.header("x-cg-demo-api-key", key);
+        }
+
+        let response = request.send().await.map_err(|e| format!("HTTP error: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("API error: {}", response.status()));
+        }
+        let body: serde_json::Value = response.json().await.map_err(|e| format!("Parse error: {}", e))?;
+
+        Ok(parse_simple_price(&config.coin_ids, &body))
+    }
+}
+
+/// Fiat value of `amount` of `ticker`, if it has a price
+pub fn fiat_value(prices: &FiatPrices, ticker: &str, amount: Decimal) -> Option<FiatAmount> {
+    prices.get(&ticker.to_lowercase()).map(|price| price.times(amount))
+}
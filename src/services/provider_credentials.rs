@@ -0,0 +1,242 @@
+//! Provider API keys kept in the database.
+//!
+//! Keys live in `provider_credentials`, one row per provider, sealed with a
+//! [`SecretCipher`] under PROVIDER_CREDENTIALS_KEY; the provider name is the
+//! cipher context so a row cannot be copied onto another provider. Only the
+//! last four characters of a key are ever shown back.
+//!
+//! At startup a stored key takes the place of the provider's environment key
+//! (TROCADOR_API_KEY for Trocador). `PUT /admin/provider-credentials/{provider}`
+//! stores a new one and switches the running client to it at once; other
+//! instances pick it up within PROVIDER_CREDENTIALS_REFRESH_SECS. Without
+//! PROVIDER_CREDENTIALS_KEY nothing is read or stored and the environment
+//! keys are used as before.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use crate::config::environment::ProviderCredentialsConfig;
+use crate::config::DbPool;
+use crate::services::encryption::{CipherError, SecretCipher};
+use crate::services::trocador::TrocadorClient;
+
+/// Providers whose keys can be managed here
+pub const SUPPORTED_PROVIDERS: &[&str] = &["trocador"];
+
+/// Characters of a key shown back to admins
+const HINT_CHARS: usize = 4;
+
+#[derive(Debug, thiserror::Error)]
+pub enum CredentialError {
+    #[error("PROVIDER_CREDENTIALS_KEY is not set")]
+    Disabled,
+
+    #[error("Unknown provider: {0}")]
+    UnknownProvider(String),
+
+    #[error(transparent)]
+    Cipher(#[from] CipherError),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// A provider's stored key, decrypted
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderCredential {
+    pub provider: String,
+    pub api_key: String,
+    pub base_url: Option<String>, // None uses the provider's default API root
+    pub rotated_at: DateTime<Utc>,
+}
+
+/// What admins see of a stored key
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct ProviderCredentialSummary {
+    pub provider: String,
+    pub key_hint: String, // Last characters of the key
+    pub base_url: Option<String>,
+    pub rotated_by: Option<String>,
+    pub rotated_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct StoredCredential {
+    provider: String,
+    api_key_encrypted: String,
+    base_url: Option<String>,
+    rotated_at: DateTime<Utc>,
+}
+
+/// Last characters of a key, the rest masked
+pub fn key_hint(api_key: &str) -> String {
+    let chars: Vec<char> = api_key.chars().collect();
+    let shown: String = chars[chars.len().saturating_sub(HINT_CHARS)..].iter().collect();
+    format!("…{}", shown)
+}
+
+#[derive(Clone)]
+pub struct ProviderCredentials {
+    pool: DbPool,
+    cipher: Option<SecretCipher>,
+}
+
+impl ProviderCredentials {
+    pub fn new(pool: DbPool, cipher: Option<SecretCipher>) -> Self {
+        Self { pool, cipher }
+    }
+
+    /// Store for PROVIDER_CREDENTIALS_KEY; an invalid key is logged and
+    /// leaves the store disabled
+    pub fn from_config(config: &ProviderCredentialsConfig, pool: DbPool) -> Self {
+        let cipher = config.key.as_deref().and_then(|key| {
+            SecretCipher::from_base64(key)
+                .inspect_err(|e| tracing::error!("Stored provider credentials disabled: {}", e))
+                .ok()
+        });
+        Self::new(pool, cipher)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.cipher.is_some()
+    }
+
+    fn cipher(&self) -> Result<&SecretCipher, CredentialError> {
+        self.cipher.as_ref().ok_or(CredentialError::Disabled)
+    }
+
+    fn supported(provider: &str) -> Result<(), CredentialError> {
+        if SUPPORTED_PROVIDERS.contains(&provider) {
+            Ok(())
+        } else {
+            Err(CredentialError::UnknownProvider(provider.to_string()))
+        }
+    }
+
+    /// Replace a provider's key, and its API root (None for the default)
+    pub async fn rotate(
+        &self,
+        provider: &str,
+        api_key: &str,
+        base_url: Option<&str>,
+        rotated_by: Option<&str>,
+    ) -> Result<ProviderCredentialSummary, CredentialError> {
+        Self::supported(provider)?;
+        let encrypted = self.cipher()?.encrypt(provider, api_key);
+
+        sqlx::query(
+            "INSERT INTO provider_credentials (provider, api_key_encrypted, key_hint, base_url, rotated_by, rotated_at)
+             VALUES (?, ?, ?, ?, ?, NOW())
+             ON DUPLICATE KEY UPDATE api_key_encrypted = VALUES(api_key_encrypted), key_hint = VALUES(key_hint),
+                                     base_url = VALUES(base_url), rotated_by = VALUES(rotated_by), rotated_at = NOW()",
+        )
+        .bind(provider)
+        .bind(&encrypted)
+        .bind(key_hint(api_key))
+        .bind(base_url)
+        .bind(rotated_by)
+        .execute(&self.pool)
+        .await?;
+
+        self.summary(provider).await?.ok_or_else(|| CredentialError::UnknownProvider(provider.to_string()))
+    }
+
+    /// A provider's stored key, if one was stored
+    pub async fn load(&self, provider: &str) -> Result<Option<ProviderCredential>, CredentialError> {
+        let cipher = self.cipher()?;
+        let stored: Option<StoredCredential> = sqlx::query_as(
+            "SELECT provider, api_key_encrypted, base_url, rotated_at FROM provider_credentials WHERE provider = ?",
+        )
+        .bind(provider)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        stored
+            .map(|row| {
+                Ok(ProviderCredential {
+                    api_key: cipher.decrypt(&row.provider, &row.api_key_encrypted)?,
+                    provider: row.provider,
+                    base_url: row.base_url,
+                    rotated_at: row.rotated_at,
+                })
+            })
+            .transpose()
+    }
+
+    pub async fn summary(&self, provider: &str) -> Result<Option<ProviderCredentialSummary>, CredentialError> {
+        Ok(sqlx::query_as(
+            "SELECT provider, key_hint, base_url, rotated_by, rotated_at FROM provider_credentials WHERE provider = ?",
+        )
+        .bind(provider)
+        .fetch_optional(&self.pool)
+        .await?)
+    }
+
+    pub async fn list(&self) -> Result<Vec<ProviderCredentialSummary>, CredentialError> {
+        Ok(sqlx::query_as(
+            "SELECT provider, key_hint, base_url, rotated_by, rotated_at FROM provider_credentials ORDER BY provider",
+        )
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Switch `client` to the stored Trocador key, if there is one. Returns
+    /// when that key was rotated.
+    pub async fn apply_trocador(&self, client: &TrocadorClient) -> Result<Option<DateTime<Utc>>, CredentialError> {
+        let Some(credential) = self.load("trocador").await? else {
+            return Ok(None);
+        };
+        client.rotate(credential.api_key, credential.base_url);
+        Ok(Some(credential.rotated_at))
+    }
+
+    /// Trocador client with the stored key, or the environment one; None
+    /// when neither is set
+    pub async fn trocador_client(&self) -> Option<TrocadorClient> {
+        let client = TrocadorClient::from_env();
+        if !self.is_enabled() {
+            return client;
+        }
+
+        let credential = match self.load("trocador").await {
+            Ok(credential) => credential?,
+            Err(e) => {
+                tracing::error!("Failed to load stored Trocador key, using TROCADOR_API_KEY: {}", e);
+                return client;
+            }
+        };
+        let client = client.unwrap_or_else(|| {
+            let webhook_url = std::env::var("TROCADOR_WEBHOOK_URL").ok().filter(|u| !u.is_empty());
+            TrocadorClient::new(credential.api_key.clone()).with_webhook_url(webhook_url)
+        });
+        client.rotate(credential.api_key, credential.base_url);
+        Some(client)
+    }
+}
+
+/// Reload the stored Trocador key into `client` every `interval`, so keys
+/// rotated on another instance are picked up here
+pub fn spawn_credential_refresh(
+    credentials: ProviderCredentials,
+    client: TrocadorClient,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut applied = None;
+        loop {
+            tokio::time::sleep(interval).await;
+            match credentials.apply_trocador(&client).await {
+                Ok(Some(rotated_at)) if applied != Some(rotated_at) => {
+                    if applied.is_some() {
+                        tracing::info!("Picked up Trocador key rotated at {}", rotated_at);
+                    }
+                    applied = Some(rotated_at);
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Failed to refresh provider credentials: {}", e),
+            }
+        }
+    })
+}
@@ -0,0 +1,213 @@
+//! Provider health, scored on our own swap outcomes.
+//!
+//! Every swap that ends with a provider is counted in `provider_stats` by
+//! outcome: completed, failed, refunded, or rejected when the provider
+//! refused to create the trade. A provider's score is the share of its
+//! outcomes over the last PROVIDER_HEALTH_WINDOW_DAYS that completed, from 0
+//! to 100; with fewer than PROVIDER_HEALTH_MIN_SWAPS outcomes it is not
+//! scored. Quotes from a provider scoring under PROVIDER_HEALTH_DEMOTE_BELOW
+//! are ranked last and under PROVIDER_HEALTH_HIDE_BELOW are not served at
+//! all. An admin can pin a provider over its score with `health_override`.
+//!
+//! This is the slow signal next to the per-pair failure counts kept in
+//! Redis, which demote a provider within the hour for one pair and amount
+//! band.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+use crate::config::environment::ProviderHealthConfig;
+use crate::modules::swap::schema::SwapStatus;
+
+/// How a swap ended with its provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapOutcome {
+    Completed,
+    Failed,
+    Refunded,
+    Rejected, // The provider refused to create the trade
+}
+
+impl SwapOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SwapOutcome::Completed => "completed",
+            SwapOutcome::Failed => "failed",
+            SwapOutcome::Refunded => "refunded",
+            SwapOutcome::Rejected => "rejected",
+        }
+    }
+
+    /// The outcome a final status counts as; an expired swap never got a
+    /// deposit, which says nothing about the provider
+    pub fn from_status(status: &SwapStatus) -> Option<Self> {
+        match status {
+            SwapStatus::Completed => Some(SwapOutcome::Completed),
+            SwapStatus::Failed => Some(SwapOutcome::Failed),
+            SwapStatus::Refunded => Some(SwapOutcome::Refunded),
+            _ => None,
+        }
+    }
+}
+
+/// Admin pin over a provider's score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthOverride {
+    Trusted, // Never demoted or hidden for its score
+    Demoted,
+    Hidden,
+}
+
+impl HealthOverride {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HealthOverride::Trusted => "trusted",
+            HealthOverride::Demoted => "demoted",
+            HealthOverride::Hidden => "hidden",
+        }
+    }
+}
+
+impl FromStr for HealthOverride {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "trusted" => Ok(HealthOverride::Trusted),
+            "demoted" => Ok(HealthOverride::Demoted),
+            "hidden" => Ok(HealthOverride::Hidden),
+            other => Err(format!("Unknown health override '{}'", other)),
+        }
+    }
+}
+
+/// What happens to a provider's quotes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthAction {
+    Serve,
+    Demote, // Ranked after every other quote
+    Hide,
+}
+
+/// Outcomes of one kind for a provider within the window
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct OutcomeCount {
+    pub provider: String,
+    pub outcome: String,
+    pub swaps: u64,
+    pub completion_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderHealth {
+    pub provider: String,
+    pub swaps: u64, // Outcomes within the window
+    pub completed: u64,
+    pub success_rate: Option<f64>, // Percent of outcomes that completed
+    pub avg_completion_minutes: Option<f64>,
+    pub failures: BTreeMap<String, u64>, // By outcome: failed, refunded, rejected
+    pub score: Option<f64>,              // Unscored below PROVIDER_HEALTH_MIN_SWAPS outcomes
+    pub health_override: Option<HealthOverride>,
+    pub action: HealthAction,
+}
+
+impl ProviderHealthConfig {
+    /// The score for `completed` of `swaps` outcomes, once there are enough
+    pub fn score(&self, completed: u64, swaps: u64) -> Option<f64> {
+        if swaps == 0 || swaps < u64::from(self.min_swaps) {
+            return None;
+        }
+        Some(percent(completed, swaps))
+    }
+
+    /// An override wins; otherwise an unscored provider is served as usual
+    pub fn action(&self, score: Option<f64>, health_override: Option<HealthOverride>) -> HealthAction {
+        match health_override {
+            Some(HealthOverride::Trusted) => return HealthAction::Serve,
+            Some(HealthOverride::Demoted) => return HealthAction::Demote,
+            Some(HealthOverride::Hidden) => return HealthAction::Hide,
+            None => {}
+        }
+        match score {
+            _ if !self.enabled => HealthAction::Serve,
+            Some(score) if score < self.hide_below => HealthAction::Hide,
+            Some(score) if score < self.demote_below => HealthAction::Demote,
+            _ => HealthAction::Serve,
+        }
+    }
+}
+
+/// Health of every provider with outcomes in the window or an override,
+/// worst score first
+pub fn summarize(
+    counts: &[OutcomeCount],
+    overrides: &[(String, HealthOverride)],
+    config: &ProviderHealthConfig,
+) -> Vec<ProviderHealth> {
+    // Providers are quoted with varying case; the first spelling seen is kept
+    let mut by_provider: HashMap<String, (ProviderHealth, u64)> = HashMap::new();
+    fn entry<'a>(
+        by_provider: &'a mut HashMap<String, (ProviderHealth, u64)>,
+        provider: &str,
+    ) -> &'a mut (ProviderHealth, u64) {
+        by_provider.entry(provider.to_lowercase()).or_insert_with(|| {
+            let health = ProviderHealth {
+                provider: provider.to_string(),
+                swaps: 0,
+                completed: 0,
+                success_rate: None,
+                avg_completion_minutes: None,
+                failures: BTreeMap::new(),
+                score: None,
+                health_override: None,
+                action: HealthAction::Serve,
+            };
+            (health, 0)
+        })
+    }
+
+    for count in counts {
+        let (health, completion_secs) = entry(&mut by_provider, &count.provider);
+        health.swaps += count.swaps;
+        if count.outcome == SwapOutcome::Completed.as_str() {
+            health.completed += count.swaps;
+            *completion_secs += count.completion_secs;
+        } else {
+            *health.failures.entry(count.outcome.clone()).or_default() += count.swaps;
+        }
+    }
+    for (provider, health_override) in overrides {
+        entry(&mut by_provider, provider).0.health_override = Some(*health_override);
+    }
+
+    let mut providers: Vec<ProviderHealth> = by_provider
+        .into_values()
+        .map(|(mut health, completion_secs)| {
+            if health.swaps > 0 {
+                health.success_rate = Some(percent(health.completed, health.swaps));
+            }
+            if health.completed > 0 {
+                let minutes = completion_secs as f64 / health.completed as f64 / 60.0;
+                health.avg_completion_minutes = Some((minutes * 10.0).round() / 10.0);
+            }
+            health.score = config.score(health.completed, health.swaps);
+            health.action = config.action(health.score, health.health_override);
+            health
+        })
+        .collect();
+
+    // Unscored providers last
+    providers.sort_by(|a, b| {
+        let score = |h: &ProviderHealth| h.score.unwrap_or(f64::INFINITY);
+        score(a).total_cmp(&score(b)).then_with(|| a.provider.cmp(&b.provider))
+    });
+    providers
+}
+
+/// `part` of `whole` in percent, to one decimal place
+fn percent(part: u64, whole: u64) -> f64 {
+    (part as f64 / whole as f64 * 1000.0).round() / 10.0
+}
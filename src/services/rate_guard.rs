@@ -0,0 +1,127 @@
+//! Exchange-rate sanity guard.
+//!
+//! Provider quotes are compared with a reference mid-market rate and quotes
+//! too far from it, in either direction, are dropped or flagged. There is no
+//! external price feed, so the reference is the median of the quotes served
+//! for the same pair and amount: one provider with broken pricing (or a
+//! fat-fingered markup) stands out against the rest.
+
+use crate::config::environment::{RateGuardAction, RateGuardConfig};
+use crate::modules::swap::schema::RatesResponse;
+use crate::services::metrics::metrics;
+use crate::services::money;
+
+#[derive(Clone)]
+pub struct RateGuard {
+    config: RateGuardConfig,
+}
+
+impl RateGuard {
+    pub fn new(config: RateGuardConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(RateGuardConfig::from_env())
+    }
+
+    /// Whether out-of-bounds quotes are refused rather than flagged
+    pub fn rejects(&self) -> bool {
+        self.config.enabled && self.config.action == RateGuardAction::Reject
+    }
+
+    /// Median of the quoted rates, when there are enough quotes to trust it
+    pub fn reference_rate(&self, rates: &[f64]) -> Option<f64> {
+        let mut rates: Vec<f64> = rates.iter().copied().filter(|r| r.is_finite() && *r > 0.0).collect();
+        if rates.is_empty() || rates.len() < self.config.min_quotes {
+            return None;
+        }
+
+        rates.sort_by(f64::total_cmp);
+        let mid = rates.len() / 2;
+        Some(if rates.len().is_multiple_of(2) { (rates[mid - 1] + rates[mid]) / 2.0 } else { rates[mid] })
+    }
+
+    /// Signed distance of `rate` from `reference`, in percent
+    pub fn deviation_pct(rate: f64, reference: f64) -> f64 {
+        (rate - reference) / reference * 100.0
+    }
+
+    fn out_of_bounds(&self, deviation_pct: f64) -> bool {
+        deviation_pct.abs() > self.config.max_deviation_pct
+    }
+
+    /// Drop or flag quotes too far from the reference rate. Runs on provider
+    /// rates before platform fees, which would otherwise skew the comparison.
+    pub fn screen_quotes(&self, rates: &mut RatesResponse) {
+        if !self.config.enabled || rates.rates.is_empty() {
+            return;
+        }
+        let quoted: Vec<f64> = rates.rates.iter().map(|r| money::to_f64(r.rate)).collect();
+        let Some(reference) = self.reference_rate(&quoted) else {
+            metrics().rate_guard_decisions.inc("unchecked");
+            return;
+        };
+
+        let pair = format!("{}/{}", rates.from, rates.to);
+        let action = self.config.action;
+        rates.rates.retain_mut(|quote| {
+            let deviation = Self::deviation_pct(money::to_f64(quote.rate), reference);
+            if !self.out_of_bounds(deviation) {
+                metrics().rate_guard_decisions.inc("passed");
+                return true;
+            }
+
+            let decision = match action {
+                RateGuardAction::Reject => "rejected",
+                RateGuardAction::Flag => "flagged",
+            };
+            metrics().rate_guard_decisions.inc(decision);
+            tracing::warn!(
+                target: "rate_guard",
+                pair = %pair,
+                provider = %quote.provider,
+                rate = %quote.rate,
+                reference,
+                deviation_pct = deviation,
+                decision,
+                "Quote deviates from the reference rate"
+            );
+
+            quote.rate_warning = true;
+            action == RateGuardAction::Flag
+        });
+    }
+
+    /// Refuse a swap at `provider`'s quote when it is out of bounds. Err is
+    /// the signed deviation in percent.
+    pub fn check_provider(&self, rates: &RatesResponse, provider: &str) -> Result<(), f64> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+        let Some(quote) = rates.rates.iter().find(|r| r.provider.eq_ignore_ascii_case(provider)) else {
+            return Ok(());
+        };
+        let quoted: Vec<f64> = rates.rates.iter().map(|r| money::to_f64(r.rate)).collect();
+        let Some(reference) = self.reference_rate(&quoted) else {
+            return Ok(());
+        };
+
+        let deviation = Self::deviation_pct(money::to_f64(quote.rate), reference);
+        if !self.out_of_bounds(deviation) {
+            return Ok(());
+        }
+
+        metrics().rate_guard_decisions.inc("rejected");
+        tracing::warn!(
+            target: "rate_guard",
+            pair = %format!("{}/{}", rates.from, rates.to),
+            provider = %quote.provider,
+            rate = %quote.rate,
+            reference,
+            deviation_pct = deviation,
+            "Refusing swap at a rate that deviates from the reference rate"
+        );
+        Err(deviation)
+    }
+}
@@ -8,11 +8,19 @@ use governor::{
     state::{InMemoryState, NotKeyed},
     Quota, RateLimiter,
 };
+use serde::{Deserialize, Serialize};
 use std::{num::NonZeroU32, sync::Arc, future::Future, pin::Pin};
+use std::sync::atomic::{AtomicU64, Ordering};
 use tower::{Layer, Service};
 
+use crate::config::environment::RateLimitBypassConfig;
+use crate::services::security::constant_time_eq;
+
 pub type GlobalRateLimiter = Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>;
 
+/// Header carrying an internal service token
+pub const INTERNAL_TOKEN_HEADER: &str = "x-internal-service-token";
+
 pub fn create_rate_limiter(burst: u32) -> GlobalRateLimiter {
     // 1 token per minute refill, with burst capacity
     // Effectively limits to `burst` requests, then 1 per minute after
@@ -21,14 +29,57 @@ pub fn create_rate_limiter(burst: u32) -> GlobalRateLimiter {
     Arc::new(RateLimiter::direct(quota))
 }
 
+/// Request counters kept by the rate limit layer
+#[derive(Default)]
+pub struct RateLimitMetrics {
+    allowed: AtomicU64,
+    limited: AtomicU64,
+    exempt: AtomicU64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RateLimitMetricsSnapshot {
+    pub allowed: u64,
+    pub limited: u64,
+    pub exempt: u64,
+}
+
+impl RateLimitMetrics {
+    pub fn snapshot(&self) -> RateLimitMetricsSnapshot {
+        RateLimitMetricsSnapshot {
+            allowed: self.allowed.load(Ordering::Relaxed),
+            limited: self.limited.load(Ordering::Relaxed),
+            exempt: self.exempt.load(Ordering::Relaxed),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct RateLimitLayer {
     limiter: GlobalRateLimiter,
+    bypass: Arc<RateLimitBypassConfig>,
+    metrics: Arc<RateLimitMetrics>,
 }
 
 impl RateLimitLayer {
     pub fn new(limiter: GlobalRateLimiter) -> Self {
-        Self { limiter }
+        Self {
+            limiter,
+            bypass: Arc::new(RateLimitBypassConfig::default()),
+            metrics: Arc::new(RateLimitMetrics::default()),
+        }
+    }
+
+    /// Exempt allowlisted internal callers and paths from the limit
+    pub fn with_bypass(mut self, bypass: RateLimitBypassConfig) -> Self {
+        self.bypass = Arc::new(bypass);
+        self
+    }
+
+    /// Record counts into shared metrics (e.g. the ones held in AppState)
+    pub fn with_metrics(mut self, metrics: Arc<RateLimitMetrics>) -> Self {
+        self.metrics = metrics;
+        self
     }
 }
 
@@ -39,6 +90,8 @@ impl<S> Layer<S> for RateLimitLayer {
         RateLimitService {
             inner,
             limiter: self.limiter.clone(),
+            bypass: self.bypass.clone(),
+            metrics: self.metrics.clone(),
         }
     }
 }
@@ -47,6 +100,8 @@ impl<S> Layer<S> for RateLimitLayer {
 pub struct RateLimitService<S> {
     inner: S,
     limiter: GlobalRateLimiter,
+    bypass: Arc<RateLimitBypassConfig>,
+    metrics: Arc<RateLimitMetrics>,
 }
 
 impl<S> Service<Request<Body>> for RateLimitService<S>
@@ -64,13 +119,34 @@ where
 
     fn call(&mut self, request: Request<Body>) -> Self::Future {
         let limiter = self.limiter.clone();
+        let metrics = self.metrics.clone();
+        let exempt = is_exempt(&self.bypass, &request);
         let mut inner = self.inner.clone();
 
         Box::pin(async move {
-            if limiter.check().is_err() {
+            if exempt {
+                metrics.exempt.fetch_add(1, Ordering::Relaxed);
+            } else if limiter.check().is_err() {
+                metrics.limited.fetch_add(1, Ordering::Relaxed);
                 return Ok(StatusCode::TOO_MANY_REQUESTS.into_response());
+            } else {
+                metrics.allowed.fetch_add(1, Ordering::Relaxed);
             }
             inner.call(request).await
         })
     }
 }
+
+pub(crate) fn is_exempt(bypass: &RateLimitBypassConfig, request: &Request<Body>) -> bool {
+    if bypass.exempt_paths.iter().any(|p| p == request.uri().path()) {
+        return true;
+    }
+
+    match request.headers().get(INTERNAL_TOKEN_HEADER).and_then(|v| v.to_str().ok()) {
+        Some(token) => bypass
+            .internal_tokens
+            .iter()
+            .any(|allowed| constant_time_eq(allowed.as_bytes(), token.as_bytes())),
+        None => false,
+    }
+}
@@ -1,33 +1,102 @@
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, State},
+    http::{header, Extensions, HeaderMap, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
 use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::sync::{Arc, LazyLock};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use crate::services::redis_cache::RedisService;
+
+use crate::config::environment::RouteRateLimitConfig;
+use crate::services::metrics::metrics;
+use crate::services::rate_limit::is_exempt;
+use crate::services::redis_cache::{RedisService, RedisServiceError};
+use crate::services::request_logging::caller_id;
+use crate::AppState;
+
+const MILLIS_PER_MINUTE: u64 = 60_000;
+
+/// Buckets are stored as "tokens:last_refill". The prefix differs from the
+/// `rate_limit:` one that held JSON buckets, so old values are never read.
+const BUCKET_KEY_PREFIX: &str = "token_bucket:";
+const BUCKET_TTL_SECS: u64 = 3600;
+
+/// TokenBucket::try_consume as one Redis step, so concurrent requests cannot
+/// both spend the same token. ARGV: capacity, refill per minute, tokens to
+/// take, now (Unix millis), TTL. Returns 1 when the tokens were taken.
+static TAKE_TOKENS: LazyLock<redis::Script> = LazyLock::new(|| {
+    redis::Script::new(
+        r#"
+        local capacity = tonumber(ARGV[1])
+        local per_minute = tonumber(ARGV[2])
+        local cost = tonumber(ARGV[3])
+        local now = tonumber(ARGV[4])
+
+        local tokens, last = capacity, now
+        local state = redis.call('GET', KEYS[1])
+        if state then
+            local stored_tokens, stored_last = string.match(state, '^(%d+):(%d+)$')
+            if stored_tokens then
+                tokens = math.min(tonumber(stored_tokens), capacity)
+                last = tonumber(stored_last)
+            end
+        end
+
+        if per_minute > 0 then
+            local added = math.floor(math.max(now - last, 0) * per_minute / 60000)
+            if added > 0 then
+                tokens = math.min(tokens + added, capacity)
+                if tokens == capacity then
+                    last = now
+                else
+                    last = last + math.floor(added * 60000 / per_minute)
+                end
+            end
+        end
+
+        local allowed = 0
+        if tokens >= cost then
+            tokens = tokens - cost
+            allowed = 1
+        end
+        redis.call('SET', KEYS[1], string.format('%d:%d', tokens, last), 'EX', ARGV[5])
+        return allowed
+        "#,
+    )
+});
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenBucket {
     pub tokens: u32,
-    pub last_refill: u64,
+    pub last_refill: u64, // Unix millis
     pub capacity: u32,
-    pub refill_rate: u32, // tokens per second
+    pub refill_per_minute: u32,
 }
 
 impl TokenBucket {
-    pub fn new(capacity: u32, refill_rate: u32) -> Self {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        
+    pub fn new(capacity: u32, refill_per_minute: u32) -> Self {
         Self {
             tokens: capacity,
-            last_refill: now,
+            last_refill: now_millis(),
             capacity,
-            refill_rate,
+            refill_per_minute,
         }
     }
 
     pub fn try_consume(&mut self, tokens: u32) -> bool {
         self.refill();
-        
+
         if self.tokens >= tokens {
             self.tokens -= tokens;
             true
@@ -36,18 +105,43 @@ impl TokenBucket {
         }
     }
 
+    /// Stored form, "tokens:last_refill"
+    pub fn encode(&self) -> String {
+        format!("{}:{}", self.tokens, self.last_refill)
+    }
+
+    /// A stored bucket under the current limits; None when `value` is not one
+    pub fn decode(value: &str, capacity: u32, refill_per_minute: u32) -> Option<Self> {
+        let (tokens, last_refill) = value.split_once(':')?;
+        Some(Self {
+            tokens: tokens.parse::<u32>().ok()?.min(capacity),
+            last_refill: last_refill.parse().ok()?,
+            capacity,
+            refill_per_minute,
+        })
+    }
+
+    /// Time until the next token is added (zero while tokens remain)
+    pub fn wait_time(&self) -> Duration {
+        if self.tokens > 0 || self.refill_per_minute == 0 {
+            return Duration::ZERO;
+        }
+
+        let interval = MILLIS_PER_MINUTE / self.refill_per_minute as u64;
+        let elapsed = now_millis().saturating_sub(self.last_refill);
+        Duration::from_millis(interval.saturating_sub(elapsed))
+    }
+
     fn refill(&mut self) {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        
-        let time_passed = now - self.last_refill;
-        let tokens_to_add = (time_passed * self.refill_rate as u64) as u32;
-        
+        let now = now_millis();
+        let time_passed = now.saturating_sub(self.last_refill);
+        let tokens_to_add = time_passed * self.refill_per_minute as u64 / MILLIS_PER_MINUTE;
+
         if tokens_to_add > 0 {
-            self.tokens = (self.tokens + tokens_to_add).min(self.capacity);
-            self.last_refill = now;
+            self.tokens = (self.tokens as u64 + tokens_to_add).min(self.capacity as u64) as u32;
+            // Carry the partial token over instead of dropping it
+            let used = tokens_to_add * MILLIS_PER_MINUTE / self.refill_per_minute as u64;
+            self.last_refill = if self.tokens == self.capacity { now } else { self.last_refill + used };
         }
     }
 }
@@ -55,7 +149,7 @@ impl TokenBucket {
 pub struct DistributedRateLimiter {
     redis: RedisService,
     default_capacity: u32,
-    default_refill_rate: u32,
+    default_refill_per_minute: u32,
 }
 
 impl DistributedRateLimiter {
@@ -63,45 +157,146 @@ impl DistributedRateLimiter {
         Self {
             redis,
             default_capacity: 10, // 10 requests per bucket
-            default_refill_rate: 1, // 1 token per second
+            default_refill_per_minute: 60, // 1 token per second
         }
     }
 
-    pub async fn try_acquire(&self, key: &str, tokens: u32) -> Result<bool, String> {
-        let bucket_key = format!("rate_limit:{}", key);
-        
-        // Get current bucket state
-        let mut bucket: TokenBucket = match self.redis.get_json(&bucket_key).await? {
-            Some(bucket) => bucket,
-            None => TokenBucket::new(self.default_capacity, self.default_refill_rate),
-        };
+    /// Buckets created by this limiter hold `capacity` tokens, refilled at `per_minute`
+    pub fn with_limit(mut self, capacity: u32, per_minute: u32) -> Self {
+        self.default_capacity = capacity;
+        self.default_refill_per_minute = per_minute;
+        self
+    }
+
+    /// Take `tokens` from `key`'s bucket in one atomic step; a missing or
+    /// unreadable bucket starts full
+    pub async fn try_acquire(&self, key: &str, tokens: u32) -> Result<bool, RedisServiceError> {
+        let bucket_key = format!("{}{}", BUCKET_KEY_PREFIX, key);
+        let (capacity, per_minute) = (self.default_capacity, self.default_refill_per_minute);
+        let args = [capacity as u64, per_minute as u64, tokens as u64, now_millis()];
 
-        // Try to consume tokens
-        let allowed = bucket.try_consume(tokens);
-        
-        // Save updated bucket state with TTL
-        self.redis.set_json(&bucket_key, &bucket, 3600).await?;
-        
-        Ok(allowed)
+        let allowed: i64 = self
+            .redis
+            .eval_atomic(&TAKE_TOKENS, &bucket_key, &args, BUCKET_TTL_SECS, |stored| {
+                let mut bucket = stored
+                    .and_then(|value| TokenBucket::decode(&value, capacity, per_minute))
+                    .unwrap_or_else(|| TokenBucket::new(capacity, per_minute));
+                let allowed = bucket.try_consume(tokens);
+                (bucket.encode(), allowed as i64)
+            })
+            .await?;
+
+        Ok(allowed == 1)
     }
 
-    pub async fn get_wait_time(&self, key: &str) -> Result<Duration, String> {
-        let bucket_key = format!("rate_limit:{}", key);
-        
-        let bucket: TokenBucket = match self.redis.get_json::<TokenBucket>(&bucket_key).await? {
+    pub async fn get_wait_time(&self, key: &str) -> Result<Duration, RedisServiceError> {
+        let bucket_key = format!("{}{}", BUCKET_KEY_PREFIX, key);
+
+        let stored = self.redis.get_string(&bucket_key).await?;
+        match stored.and_then(|value| TokenBucket::decode(&value, self.default_capacity, self.default_refill_per_minute)) {
             Some(mut bucket) => {
                 bucket.refill();
-                bucket
-            },
-            None => return Ok(Duration::from_secs(0)),
+                Ok(bucket.wait_time())
+            }
+            None => Ok(Duration::ZERO),
+        }
+    }
+}
+
+// =============================================================================
+// PER-ROUTE LIMITS
+// =============================================================================
+
+/// Token-bucket limits per route group and caller, shared across instances through Redis
+#[derive(Clone)]
+pub struct RouteRateLimiter {
+    redis: RedisService,
+    config: Arc<RouteRateLimitConfig>,
+}
+
+impl RouteRateLimiter {
+    pub fn new(redis: RedisService, config: RouteRateLimitConfig) -> Self {
+        Self { redis, config: Arc::new(config) }
+    }
+
+    /// Take a token from `caller`'s bucket for the route group covering `path`.
+    /// Err carries how long until the next token; Redis failures let the request through.
+    pub async fn check(&self, path: &str, caller: &str) -> Result<(), Duration> {
+        let Some((group, per_minute)) = self.config.limit_for(path) else {
+            return Ok(());
         };
 
-        if bucket.tokens > 0 {
-            Ok(Duration::from_secs(0))
-        } else {
-            // Calculate time needed for next token
-            let time_for_token = 1.0 / bucket.refill_rate as f64;
-            Ok(Duration::from_secs_f64(time_for_token))
+        let limiter = DistributedRateLimiter::new(self.redis.clone()).with_limit(per_minute, per_minute);
+        let key = format!("route:{}:{}", group, caller);
+
+        match limiter.try_acquire(&key, 1).await {
+            Ok(true) => Ok(()),
+            Ok(false) => {
+                metrics().route_rate_limited.inc(group);
+                let wait = limiter.get_wait_time(&key).await.unwrap_or(Duration::from_secs(1));
+                Err(wait)
+            }
+            Err(e) => {
+                tracing::warn!("Route rate limit check failed for {}: {}", key, e);
+                Ok(())
+            }
+        }
+    }
+
+    /// "user:<id>" for signed-in callers, otherwise "ip:<addr>" when the address is known
+    fn caller(&self, state: &AppState, request: &Request<Body>) -> Option<String> {
+        if let Some(user) = caller_id(state, request) {
+            return Some(user);
         }
+
+        self.client_ip(request.headers(), request.extensions()).map(|ip| format!("ip:{}", ip))
     }
+
+    /// The caller's address: the first X-Forwarded-For hop when that header
+    /// is trusted, otherwise the peer address when known
+    pub fn client_ip(&self, headers: &HeaderMap, extensions: &Extensions) -> Option<String> {
+        let forwarded = self
+            .config
+            .trust_forwarded_for
+            .then(|| headers.get("x-forwarded-for"))
+            .flatten()
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(',').next())
+            .map(str::trim)
+            .filter(|v| !v.is_empty());
+        if let Some(ip) = forwarded {
+            return Some(ip.to_string());
+        }
+
+        extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip().to_string())
+    }
+}
+
+/// Enforce route group limits; 429 with Retry-After once a caller's bucket is empty
+pub async fn limit_by_route(State(state): State<Arc<AppState>>, request: Request<Body>, next: Next) -> Response {
+    if is_exempt(&state.rate_limit_bypass, &request) {
+        return next.run(request).await;
+    }
+
+    // Callers with no user and no known address share nothing, so aren't limited here
+    let Some(caller) = state.route_limiter.caller(&state, &request) else {
+        return next.run(request).await;
+    };
+
+    if let Err(wait) = state.route_limiter.check(request.uri().path(), &caller).await {
+        let retry_after = wait.as_secs_f64().ceil().max(1.0) as u64;
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, retry_after.to_string())],
+            Json(serde_json::json!({
+                "error": "Too many requests",
+                "retry_after": retry_after,
+            })),
+        )
+            .into_response();
+    }
+
+    next.run(request).await
 }
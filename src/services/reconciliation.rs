@@ -0,0 +1,520 @@
+//! End-of-day reconciliation.
+//!
+//! Once a day the previous UTC day is checked against itself: swaps created,
+//! completed, refunded, failed and expired; the platform fee earned on
+//! completed swaps per currency against what the status ledger
+//! (`swap_status_history`) confirms; and successful new_trade calls per
+//! provider (counted in `provider_uptime_daily`) against the swaps we
+//! stored. Anything that does not add up is listed as a discrepancy.
+//! Sandbox swaps are left out throughout, imported swaps from the provider
+//! trade counts (we never opened their trade).
+//!
+//! Reports are kept in `reconciliation_reports`, one per day, mailed to
+//! RECONCILIATION_EMAILS and posted to RECONCILIATION_WEBHOOK_URL. Past
+//! reports are served at `GET /admin/reconciliation`.
+
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, NaiveDateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use crate::config::environment::ReconciliationConfig;
+use crate::config::DbPool;
+use crate::services::email::{EmailMessage, EmailService};
+use crate::services::jobs::{self, JobKind, JobOutcome};
+use crate::services::redis_cache::RedisService;
+
+const LOCK_KEY: &str = "lock:reconciliation";
+const LOCK_TTL_SECS: u64 = 600;
+
+/// Swap ids listed per kind of discrepancy; the rest are only counted
+const MAX_LISTED_SWAPS: usize = 50;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SwapTotals {
+    pub created: u64,
+    pub completed: u64,
+    pub refunded: u64,
+    pub failed: u64,
+    pub expired: u64,
+}
+
+/// Platform fee on the day's completed swaps, in one receive currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarkupTotal {
+    pub currency: String,
+    pub swaps: u64,
+    pub earned: Decimal,   // Sum of platform_fee
+    pub ledgered: Decimal, // Part of it whose completion is in the status ledger
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderTradeCount {
+    pub provider: String,
+    pub provider_trades: u64, // Successful new_trade calls
+    pub recorded_swaps: u64,  // Swaps stored with this provider
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscrepancyKind {
+    CompletionNotInLedger,   // Completed swap without a `completed` status history row
+    LedgerWithoutCompletion, // `completed` history row, swap in another status
+    RefundNotRecorded,       // Refunded swap without a swap_refunds row
+    ProviderTradeCount,      // Provider trades and stored swaps differ
+}
+
+impl DiscrepancyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CompletionNotInLedger => "completion_not_in_ledger",
+            Self::LedgerWithoutCompletion => "ledger_without_completion",
+            Self::RefundNotRecorded => "refund_not_recorded",
+            Self::ProviderTradeCount => "provider_trade_count",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Discrepancy {
+    pub kind: DiscrepancyKind,
+    pub subject: String, // Swap id or provider
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    pub day: NaiveDate, // UTC
+    pub generated_at: DateTime<Utc>,
+    pub swaps: SwapTotals,
+    pub markup: Vec<MarkupTotal>,
+    pub providers: Vec<ProviderTradeCount>,
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+impl ReconciliationReport {
+    /// Plain text digest for the ops mailbox and channel
+    pub fn summary(&self) -> String {
+        let mut lines = vec![
+            format!("Reconciliation for {}", self.day),
+            format!(
+                "Swaps: {} created, {} completed, {} refunded, {} failed, {} expired",
+                self.swaps.created, self.swaps.completed, self.swaps.refunded, self.swaps.failed, self.swaps.expired
+            ),
+        ];
+
+        if !self.markup.is_empty() {
+            lines.push("Markup earned (ledgered):".to_string());
+            for m in &self.markup {
+                lines.push(format!(
+                    "  {} {:.8} ({:.8}) over {} swap(s)",
+                    m.currency.to_uppercase(),
+                    m.earned,
+                    m.ledgered,
+                    m.swaps
+                ));
+            }
+        }
+
+        if self.discrepancies.is_empty() {
+            lines.push("No discrepancies.".to_string());
+        } else {
+            lines.push(format!("{} discrepancies:", self.discrepancies.len()));
+            for d in &self.discrepancies {
+                lines.push(format!("  [{}] {}: {}", d.kind.as_str(), d.subject, d.detail));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// A stored report without its details, as listed by the admin endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationSummary {
+    pub day: NaiveDate,
+    pub generated_at: DateTime<Utc>,
+    pub discrepancies: u32,
+}
+
+/// Match provider trade counts with stored swaps per provider (names compare
+/// case-insensitively); every provider that differs is a discrepancy
+pub fn compare_provider_trades(
+    provider_trades: &[(String, u64)],
+    recorded_swaps: &[(String, u64)],
+) -> (Vec<ProviderTradeCount>, Vec<Discrepancy>) {
+    let mut counts: BTreeMap<String, ProviderTradeCount> = BTreeMap::new();
+    let rows = provider_trades.iter().map(|row| (row, true)).chain(recorded_swaps.iter().map(|row| (row, false)));
+    for ((provider, count), is_trade) in rows {
+        let entry = counts.entry(provider.to_lowercase()).or_insert_with(|| ProviderTradeCount {
+            provider: provider.clone(),
+            provider_trades: 0,
+            recorded_swaps: 0,
+        });
+        if is_trade {
+            entry.provider_trades += count;
+        } else {
+            entry.recorded_swaps += count;
+        }
+    }
+
+    let counts: Vec<ProviderTradeCount> = counts.into_values().collect();
+    let discrepancies = counts
+        .iter()
+        .filter(|c| c.provider_trades != c.recorded_swaps)
+        .map(|c| Discrepancy {
+            kind: DiscrepancyKind::ProviderTradeCount,
+            subject: c.provider.clone(),
+            detail: format!("{} trade(s) opened, {} swap(s) stored", c.provider_trades, c.recorded_swaps),
+        })
+        .collect();
+
+    (counts, discrepancies)
+}
+
+/// When the next run is due: `hour_utc` today, or tomorrow once it has passed
+pub fn next_run(now: DateTime<Utc>, hour_utc: u32) -> DateTime<Utc> {
+    let today = now.date_naive().and_hms_opt(hour_utc.min(23), 0, 0).unwrap_or_default().and_utc();
+    if today > now {
+        today
+    } else {
+        today + ChronoDuration::days(1)
+    }
+}
+
+fn day_bounds(day: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+    let start = day.and_hms_opt(0, 0, 0).unwrap_or_default();
+    (start, start + ChronoDuration::days(1))
+}
+
+// =============================================================================
+// REPORTS
+// =============================================================================
+
+pub struct ReconciliationService {
+    pool: DbPool,
+}
+
+impl ReconciliationService {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Reconcile one UTC day; reads only
+    pub async fn generate(&self, day: NaiveDate) -> Result<ReconciliationReport, sqlx::Error> {
+        let (start, end) = day_bounds(day);
+        let mut discrepancies = Vec::new();
+
+        let created: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM swaps WHERE is_sandbox = FALSE AND created_at >= ? AND created_at < ?",
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_one(&self.pool)
+        .await?;
+
+        // Terminal statuses reached during the day, as the status ledger has them
+        let reached: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT h.status, COUNT(DISTINCT h.swap_id)
+             FROM swap_status_history h JOIN swaps s ON s.id = h.swap_id
+             WHERE s.is_sandbox = FALSE AND h.status IN ('refunded', 'failed', 'expired')
+               AND h.created_at >= ? AND h.created_at < ?
+             GROUP BY h.status",
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+        let reached_count =
+            |status: &str| reached.iter().find(|(s, _)| s == status).map_or(0, |(_, n)| (*n).max(0) as u64);
+
+        let markup: Vec<(String, i64, Decimal, Decimal)> = sqlx::query_as(
+            "SELECT s.to_currency, COUNT(*),
+                    COALESCE(SUM(s.platform_fee), 0),
+                    COALESCE(SUM(IF(h.swap_id IS NULL, 0, s.platform_fee)), 0)
+             FROM swaps s
+             LEFT JOIN (SELECT DISTINCT swap_id FROM swap_status_history WHERE status = 'completed') h
+               ON h.swap_id = s.id
+             WHERE s.is_sandbox = FALSE AND s.status = 'completed' AND s.completed_at >= ? AND s.completed_at < ?
+             GROUP BY s.to_currency
+             ORDER BY s.to_currency",
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+        let markup: Vec<MarkupTotal> = markup
+            .into_iter()
+            .map(|(currency, swaps, earned, ledgered)| MarkupTotal {
+                currency,
+                swaps: swaps.max(0) as u64,
+                earned,
+                ledgered,
+            })
+            .collect();
+
+        let unledgered: Vec<(String, String)> = sqlx::query_as(
+            "SELECT s.id, s.to_currency FROM swaps s
+             WHERE s.is_sandbox = FALSE AND s.status = 'completed' AND s.completed_at >= ? AND s.completed_at < ?
+               AND NOT EXISTS (SELECT 1 FROM swap_status_history h WHERE h.swap_id = s.id AND h.status = 'completed')
+             ORDER BY s.completed_at",
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+        push_swaps(&mut discrepancies, DiscrepancyKind::CompletionNotInLedger, unledgered, |currency| {
+            format!("Completed without a status history entry; its {} fee is unconfirmed", currency.to_uppercase())
+        });
+
+        let reverted: Vec<(String, String)> = sqlx::query_as(
+            "SELECT DISTINCT s.id, CAST(s.status AS CHAR) FROM swap_status_history h JOIN swaps s ON s.id = h.swap_id
+             WHERE s.is_sandbox = FALSE AND h.status = 'completed' AND s.status <> 'completed'
+               AND h.created_at >= ? AND h.created_at < ?
+             ORDER BY s.id",
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+        push_swaps(&mut discrepancies, DiscrepancyKind::LedgerWithoutCompletion, reverted, |status| {
+            format!("Status history has it completed, the swap is {}", status)
+        });
+
+        let unrecorded_refunds: Vec<(String, String)> = sqlx::query_as(
+            "SELECT DISTINCT s.id, s.from_currency FROM swap_status_history h JOIN swaps s ON s.id = h.swap_id
+             WHERE s.is_sandbox = FALSE AND h.status = 'refunded' AND s.status = 'refunded'
+               AND h.created_at >= ? AND h.created_at < ?
+               AND NOT EXISTS (SELECT 1 FROM swap_refunds r WHERE r.swap_id = s.id)
+             ORDER BY s.id",
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+        push_swaps(&mut discrepancies, DiscrepancyKind::RefundNotRecorded, unrecorded_refunds, |currency| {
+            format!("Refunded, but the provider's {} refund was never recorded", currency.to_uppercase())
+        });
+
+        let provider_trades: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT provider, CAST(trades - trades_failed AS SIGNED) FROM provider_uptime_daily
+             WHERE day = ? AND trades > 0",
+        )
+        .bind(day)
+        .fetch_all(&self.pool)
+        .await?;
+        let recorded_swaps: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT provider_id, COUNT(*) FROM swaps
+             WHERE is_sandbox = FALSE AND imported = FALSE AND created_at >= ? AND created_at < ?
+             GROUP BY provider_id",
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+        let unsigned = |rows: Vec<(String, i64)>| rows.into_iter().map(|(p, n)| (p, n.max(0) as u64)).collect::<Vec<_>>();
+        let (providers, provider_discrepancies) =
+            compare_provider_trades(&unsigned(provider_trades), &unsigned(recorded_swaps));
+        discrepancies.extend(provider_discrepancies);
+
+        Ok(ReconciliationReport {
+            day,
+            generated_at: Utc::now(),
+            swaps: SwapTotals {
+                created: created.max(0) as u64,
+                completed: markup.iter().map(|m| m.swaps).sum(),
+                refunded: reached_count("refunded"),
+                failed: reached_count("failed"),
+                expired: reached_count("expired"),
+            },
+            markup,
+            providers,
+            discrepancies,
+        })
+    }
+
+    /// Store a report, replacing an earlier one for the same day
+    pub async fn save(&self, report: &ReconciliationReport) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO reconciliation_reports (day, discrepancies, report, generated_at) VALUES (?, ?, ?, ?)
+             ON DUPLICATE KEY UPDATE discrepancies = VALUES(discrepancies), report = VALUES(report),
+                                     generated_at = VALUES(generated_at)",
+        )
+        .bind(report.day)
+        .bind(report.discrepancies.len() as u32)
+        .bind(serde_json::to_string(report).unwrap_or_default())
+        .bind(report.generated_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// The latest `limit` stored reports, newest first
+    pub async fn list(&self, limit: u32) -> Result<Vec<ReconciliationSummary>, sqlx::Error> {
+        let rows: Vec<(NaiveDate, DateTime<Utc>, u32)> = sqlx::query_as(
+            "SELECT day, generated_at, discrepancies FROM reconciliation_reports ORDER BY day DESC LIMIT ?",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(day, generated_at, discrepancies)| ReconciliationSummary { day, generated_at, discrepancies })
+            .collect())
+    }
+
+    pub async fn get(&self, day: NaiveDate) -> Result<Option<ReconciliationReport>, sqlx::Error> {
+        let report: Option<String> =
+            sqlx::query_scalar("SELECT CAST(report AS CHAR) FROM reconciliation_reports WHERE day = ?")
+                .bind(day)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(report.and_then(|r| serde_json::from_str(&r).ok()))
+    }
+}
+
+/// One discrepancy per swap, up to MAX_LISTED_SWAPS, then one for the rest
+fn push_swaps(
+    discrepancies: &mut Vec<Discrepancy>,
+    kind: DiscrepancyKind,
+    swaps: Vec<(String, String)>,
+    detail: impl Fn(&str) -> String,
+) {
+    let total = swaps.len();
+    for (id, context) in swaps.into_iter().take(MAX_LISTED_SWAPS) {
+        discrepancies.push(Discrepancy { kind, subject: id, detail: detail(&context) });
+    }
+    if total > MAX_LISTED_SWAPS {
+        discrepancies.push(Discrepancy {
+            kind,
+            subject: "more".to_string(),
+            detail: format!("{} more swap(s) not listed", total - MAX_LISTED_SWAPS),
+        });
+    }
+}
+
+// =============================================================================
+// DELIVERY
+// =============================================================================
+
+/// Mails a report to the ops addresses and posts it to the ops channel
+pub struct ReportDelivery {
+    email: EmailService,
+    http: reqwest::Client,
+    emails: Vec<String>,
+    webhook_url: Option<String>,
+}
+
+impl ReportDelivery {
+    pub fn new(email: EmailService, config: &ReconciliationConfig) -> Self {
+        Self {
+            email,
+            http: reqwest::Client::new(),
+            emails: config.emails.clone(),
+            webhook_url: config.webhook_url.clone(),
+        }
+    }
+
+    /// Send everywhere configured; a failed channel does not stop the others
+    pub async fn deliver(&self, report: &ReconciliationReport) -> Result<(), String> {
+        let subject = match report.discrepancies.len() {
+            0 => format!("Reconciliation {}: no discrepancies", report.day),
+            n => format!("Reconciliation {}: {} discrepancies", report.day, n),
+        };
+        let summary = report.summary();
+        let mut errors = Vec::new();
+
+        for to in &self.emails {
+            let message = EmailMessage { to: to.clone(), subject: subject.clone(), text_body: summary.clone() };
+            if let Err(e) = self.email.send(&message).await {
+                errors.push(format!("email to {}: {}", to, e));
+            }
+        }
+
+        if let Some(url) = &self.webhook_url {
+            let result = self
+                .http
+                .post(url)
+                .json(&serde_json::json!({ "text": summary }))
+                .send()
+                .await
+                .and_then(|r| r.error_for_status());
+            // Webhook URLs embed their secret
+            if let Err(e) = result {
+                errors.push(format!("webhook: {}", e.without_url()));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+}
+
+/// Spawn the nightly run at RECONCILIATION_HOUR_UTC; one instance runs it.
+/// Triggering the job from `/admin/jobs` reconciles the previous day again.
+pub fn spawn_reconciliation_worker(
+    pool: DbPool,
+    redis: RedisService,
+    delivery: ReportDelivery,
+    config: ReconciliationConfig,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        tracing::info!("Reconciliation worker started (daily at {:02}:00 UTC)", config.hour_utc);
+
+        let service = ReconciliationService::new(pool);
+        let job = jobs::registry().register(
+            "reconciliation",
+            "Reconcile the previous day's swaps, markup and provider trades",
+            JobKind::Scheduled,
+        );
+
+        loop {
+            let now = Utc::now();
+            let delay = (next_run(now, config.hour_utc) - now).to_std().unwrap_or(Duration::from_secs(60));
+            job.wait(delay).await;
+
+            let run = job.start();
+            match redis.try_lock(LOCK_KEY, LOCK_TTL_SECS).await {
+                Ok(true) => {
+                    let day = Utc::now().date_naive() - ChronoDuration::days(1);
+                    match reconcile(&service, &delivery, day).await {
+                        Ok(report) => {
+                            tracing::info!(
+                                "Reconciliation for {}: {} discrepancies",
+                                day,
+                                report.discrepancies.len()
+                            );
+                            run.finish(JobOutcome::Success, None);
+                        }
+                        Err(e) => {
+                            tracing::error!("Reconciliation for {} failed: {}", day, e);
+                            run.finish(JobOutcome::Failed, Some(e));
+                        }
+                    }
+                    // Left to expire, so other instances waking at the same time skip
+                }
+                _ => run.finish(JobOutcome::Skipped, None),
+            }
+        }
+    })
+}
+
+async fn reconcile(
+    service: &ReconciliationService,
+    delivery: &ReportDelivery,
+    day: NaiveDate,
+) -> Result<ReconciliationReport, String> {
+    let report = service.generate(day).await.map_err(|e| e.to_string())?;
+    service.save(&report).await.map_err(|e| e.to_string())?;
+    if let Err(e) = delivery.deliver(&report).await {
+        tracing::warn!("Reconciliation report for {} not fully delivered: {}", day, e);
+    }
+    Ok(report)
+}
@@ -1,122 +1,629 @@
-use redis::{AsyncCommands, Client};
+//! Cache, counters and locks, on Redis or in process memory.
+//!
+//! When Redis stops answering (refused or dropped connections, timeouts),
+//! calls are served according to REDIS_DEGRADED_MODE rather than failing
+//! one by one: `memory` (the default) moves them to a bounded in-process
+//! store, so caching carries on and rate limits and locks hold per instance;
+//! `disabled` makes reads miss, drops writes and lets every rate limit check
+//! and lock through. Either way callers see the same answers whichever path
+//! they take. Every REDIS_HEALTH_CHECK_SECS one call tries Redis again, and
+//! the first success moves everything back. Keys written to the fallback
+//! are not copied to Redis on recovery; they expire on their own.
+//!
+//! Commands share one multiplexed connection per service (and its clones),
+//! opened on first use and reopened after a connection-level failure. A
+//! connection that had been working and then drops (Redis restarted, idle
+//! timeout) is reopened by the next call without counting as an outage.
+//! Pub/sub subscriptions still get connections of their own.
+//!
+//! Each command, connecting included, is bounded by REDIS_COMMAND_TIMEOUT_MS;
+//! a timeout counts as Redis being unreachable. Failures come back as
+//! [`RedisServiceError`], so callers can tell an outage from a bad value.
+
+use redis::aio::MultiplexedConnection;
+use redis::{AsyncCommands, Client, ErrorKind, RedisError, RedisResult};
 use serde::{de::DeserializeOwned, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::cache_stats::CacheStats;
+use super::memory_cache::MemoryStore;
+use super::metrics::metrics;
+use crate::config::environment::{CacheDegradationConfig, DegradedMode};
+
+/// REDIS_URL that keeps the cache in process memory instead of Redis
+pub const MEMORY_URL: &str = "memory://";
+
+/// Where cache calls are served from right now
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheBackend {
+    Redis,
+    Memory,   // REDIS_URL=memory://, or the fallback while Redis is unreachable
+    Disabled, // Redis is unreachable and REDIS_DEGRADED_MODE=disabled
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RedisServiceError {
+    /// Redis could not be reached, or there is no Redis to reach
+    #[error("Redis connection error: {0}")]
+    Connection(String),
+
+    /// A value did not encode or decode
+    #[error("Cache serialization error: {0}")]
+    Serialization(String),
+
+    #[error("Redis {command} timed out after {after:?}")]
+    Timeout { command: &'static str, after: Duration },
+
+    /// Redis answered nil where a value was required
+    #[error("Redis returned nil: {0}")]
+    Nil(String),
+
+    /// Redis refused the command itself (wrong type, not an integer, ...)
+    #[error("Redis command error: {0}")]
+    Command(String),
+}
+
+impl RedisServiceError {
+    /// Redis did not answer in time or at all
+    pub fn is_unavailable(&self) -> bool {
+        matches!(self, Self::Connection(_) | Self::Timeout { .. })
+    }
+}
+
+impl From<RedisError> for RedisServiceError {
+    fn from(e: RedisError) -> Self {
+        if is_unreachable(&e) {
+            return Self::Connection(e.to_string());
+        }
+        match e.kind() {
+            ErrorKind::UnexpectedReturnType if e.to_string().contains("nil") => Self::Nil(e.to_string()),
+            ErrorKind::UnexpectedReturnType | ErrorKind::Parse => Self::Serialization(e.to_string()),
+            _ => Self::Command(e.to_string()),
+        }
+    }
+}
+
+impl From<serde_json::Error> for RedisServiceError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Serialization(e.to_string())
+    }
+}
 
 #[derive(Clone)]
 pub struct RedisService {
+    backend: Backend,
+    stats: CacheStats,
+    health: Arc<RedisHealth>,
+}
+
+#[derive(Clone)]
+enum Backend {
+    Redis(SharedConnection),
+    Memory(MemoryStore), // Single-instance deployments without Redis
+}
+
+/// One multiplexed connection shared by every command, opened lazily
+#[derive(Clone)]
+struct SharedConnection {
     client: Client,
+    slot: Arc<tokio::sync::Mutex<Option<MultiplexedConnection>>>,
+}
+
+impl SharedConnection {
+    fn new(client: Client) -> Self {
+        Self { client, slot: Arc::new(tokio::sync::Mutex::new(None)) }
+    }
+
+    /// The open connection, connecting first when there is none; callers
+    /// arriving meanwhile wait for that one connect. The flag is true when
+    /// the connection was already open.
+    async fn get(&self) -> RedisResult<(MultiplexedConnection, bool)> {
+        let mut slot = self.slot.lock().await;
+        if let Some(conn) = slot.as_ref() {
+            return Ok((conn.clone(), true));
+        }
+        let conn = self.client.get_multiplexed_async_connection().await?;
+        tracing::debug!("Opened shared Redis connection");
+        *slot = Some(conn.clone());
+        Ok((conn, false))
+    }
+
+    /// Drop the connection so the next command opens a new one
+    async fn reset(&self) {
+        self.slot.lock().await.take();
+    }
+}
+
+/// Where one call goes
+enum Route<'a> {
+    Redis(&'a SharedConnection),
+    Local(&'a MemoryStore),            // REDIS_URL=memory://
+    Degraded(Option<&'a MemoryStore>), // Redis is down; None when caching is disabled
+}
+
+/// Whether Redis answers, and what stands in for it when it does not
+struct RedisHealth {
+    config: CacheDegradationConfig,
+    fallback: MemoryStore,
+    down: AtomicBool,                   // Fast path for the healthy case
+    retry_at: Mutex<Option<Instant>>,   // When the next call may try Redis again
+}
+
+impl RedisHealth {
+    fn new(config: CacheDegradationConfig) -> Self {
+        Self {
+            fallback: MemoryStore::with_max_keys(config.fallback_max_keys),
+            config,
+            down: AtomicBool::new(false),
+            retry_at: Mutex::new(None),
+        }
+    }
+
+    /// Whether this call should go to Redis. While Redis is down, one call
+    /// per REDIS_HEALTH_CHECK_SECS is let through to find out if it is back.
+    fn try_redis(&self) -> bool {
+        if !self.down.load(Ordering::Relaxed) {
+            return true;
+        }
+        let mut retry_at = self.retry_at.lock().unwrap_or_else(|e| e.into_inner());
+        match *retry_at {
+            Some(at) if Instant::now() < at => false,
+            _ => {
+                *retry_at = Some(Instant::now() + self.config.retry_after);
+                true
+            }
+        }
+    }
+
+    fn mark_up(&self) {
+        if self.down.swap(false, Ordering::Relaxed) {
+            tracing::info!("Redis is reachable again; cache calls are back on Redis");
+        }
+    }
+
+    /// Note a failed command; true when it means Redis is unreachable and
+    /// the call should be served by the fallback instead
+    fn mark_down(&self, e: &RedisServiceError) -> bool {
+        if !e.is_unavailable() {
+            return false;
+        }
+        *self.retry_at.lock().unwrap_or_else(|e| e.into_inner()) = Some(Instant::now() + self.config.retry_after);
+        if !self.down.swap(true, Ordering::Relaxed) {
+            tracing::warn!("Redis is unreachable ({}); cache calls degrade to {:?}", e, self.config.mode);
+        }
+        true
+    }
+
+    fn fallback(&self) -> Option<&MemoryStore> {
+        match self.config.mode {
+            DegradedMode::Memory => Some(&self.fallback),
+            DegradedMode::Disabled => None,
+        }
+    }
+}
+
+/// Connection-level failures, as opposed to errors in the command itself
+fn is_unreachable(e: &RedisError) -> bool {
+    e.is_io_error() || e.is_connection_refusal() || e.is_timeout() || e.is_connection_dropped()
 }
 
 impl RedisService {
     pub fn new(redis_url: &str) -> Self {
+        if redis_url.starts_with(MEMORY_URL) {
+            return Self::in_memory();
+        }
         let client = Client::open(redis_url).expect("Invalid Redis URL");
-        Self { client }
+        Self {
+            backend: Backend::Redis(SharedConnection::new(client)),
+            stats: CacheStats::new(),
+            health: Arc::new(RedisHealth::new(CacheDegradationConfig::from_env())),
+        }
     }
 
-    pub fn get_client(&self) -> Client {
-        self.client.clone()
+    /// Cache held in this process; see services::memory_cache
+    pub fn in_memory() -> Self {
+        Self {
+            backend: Backend::Memory(MemoryStore::new()),
+            stats: CacheStats::new(),
+            health: Arc::new(RedisHealth::new(CacheDegradationConfig::default())),
+        }
     }
 
-    pub async fn set_json<T: Serialize>(&self, key: &str, value: &T, ttl_seconds: u64) -> Result<(), String> {
-        let json = serde_json::to_string(value).map_err(|e| e.to_string())?;
-        
-        let mut conn = self.client.get_multiplexed_async_connection()
-            .await
-            .map_err(|e| e.to_string())?;
-        
-        conn.set_ex(key, json, ttl_seconds)
-            .await
-            .map_err(|e: redis::RedisError| e.to_string())
+    /// What to do while Redis is unreachable, in place of REDIS_DEGRADED_MODE
+    /// and friends
+    pub fn with_degradation(mut self, config: CacheDegradationConfig) -> Self {
+        self.health = Arc::new(RedisHealth::new(config));
+        self
     }
 
-    pub async fn get_json<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, String> {
-        let mut conn = self.client.get_multiplexed_async_connection()
-            .await
-            .map_err(|e| e.to_string())?;
-            
-        let result: Option<String> = conn.get(key)
-            .await
-            .map_err(|e: redis::RedisError| e.to_string())?;
+    pub fn is_in_memory(&self) -> bool {
+        matches!(self.backend, Backend::Memory(_))
+    }
+
+    /// The Redis client; `None` for the in-memory cache
+    pub fn get_client(&self) -> Option<Client> {
+        match &self.backend {
+            Backend::Redis(shared) => Some(shared.client.clone()),
+            Backend::Memory(_) => None,
+        }
+    }
+
+    /// Hit/miss counters for reads and writes made through this service
+    pub fn stats(&self) -> &CacheStats {
+        &self.stats
+    }
+
+    /// Where calls go at the moment
+    pub fn backend(&self) -> CacheBackend {
+        match &self.backend {
+            Backend::Memory(_) => CacheBackend::Memory,
+            Backend::Redis(_) if !self.health.down.load(Ordering::Relaxed) => CacheBackend::Redis,
+            Backend::Redis(_) => match self.health.config.mode {
+                DegradedMode::Memory => CacheBackend::Memory,
+                DegradedMode::Disabled => CacheBackend::Disabled,
+            },
+        }
+    }
+
+    /// PING Redis now, whatever the retry schedule, and report where calls go
+    pub async fn check_health(&self) -> CacheBackend {
+        if let Backend::Redis(shared) = &self.backend {
+            let ping = self
+                .bounded("PING", async {
+                    let (mut conn, _) = shared.get().await?;
+                    redis::cmd("PING").query_async::<String>(&mut conn).await
+                })
+                .await;
+            match ping {
+                Ok(_) => self.health.mark_up(),
+                Err(e) => {
+                    if self.health.mark_down(&e) {
+                        shared.reset().await;
+                    }
+                }
+            }
+        }
+        self.backend()
+    }
+
+    /// `command` within REDIS_COMMAND_TIMEOUT_MS
+    async fn bounded<T>(
+        &self,
+        command: &'static str,
+        call: impl Future<Output = RedisResult<T>>,
+    ) -> Result<T, RedisServiceError> {
+        let after = self.health.config.command_timeout;
+        match tokio::time::timeout(after, call).await {
+            Ok(result) => result.map_err(RedisServiceError::from),
+            Err(_) => Err(RedisServiceError::Timeout { command, after }),
+        }
+    }
+
+    fn route(&self) -> Route<'_> {
+        match &self.backend {
+            Backend::Memory(store) => Route::Local(store),
+            Backend::Redis(shared) if self.health.try_redis() => Route::Redis(shared),
+            Backend::Redis(_) => Route::Degraded(self.health.fallback()),
+        }
+    }
+
+    /// Run `on_redis` against Redis when it is up, otherwise (or when the
+    /// connection fails) `on_local` against the memory store standing in for
+    /// it; `None` there means the degraded mode is `disabled`
+    async fn run<T, F, Fut>(
+        &self,
+        command: &'static str,
+        on_redis: F,
+        on_local: impl FnOnce(Option<&MemoryStore>) -> Result<T, RedisServiceError>,
+    ) -> Result<T, RedisServiceError>
+    where
+        F: FnOnce(MultiplexedConnection) -> Fut,
+        Fut: Future<Output = RedisResult<T>>,
+    {
+        let local = match self.route() {
+            Route::Local(store) => return on_local(Some(store)),
+            Route::Degraded(store) => store,
+            Route::Redis(shared) => {
+                let reused = AtomicBool::new(false);
+                let call = async {
+                    let (conn, was_open) = shared.get().await?;
+                    reused.store(was_open, Ordering::Relaxed);
+                    on_redis(conn).await
+                };
+                match self.bounded(command, call).await {
+                    Ok(value) => {
+                        self.health.mark_up();
+                        return Ok(value);
+                    }
+                    Err(e) if !e.is_unavailable() => {
+                        metrics().redis_errors.inc(command);
+                        return Err(e);
+                    }
+                    Err(e) => {
+                        metrics().redis_errors.inc(command);
+                        shared.reset().await;
+                        // A connection that had been working may only have been closed
+                        // (Redis restarted, idle timeout): the next call reconnects
+                        // before Redis counts as down
+                        let dropped = reused.load(Ordering::Relaxed) && matches!(e, RedisServiceError::Connection(_));
+                        if !dropped {
+                            self.health.mark_down(&e);
+                        }
+                        self.health.fallback()
+                    }
+                }
+            }
+        };
+
+        metrics().redis_degraded_calls.inc(command);
+        on_local(local)
+    }
 
-        match result {
-            Some(json) => serde_json::from_str(&json).map(Some).map_err(|e| e.to_string()),
+    pub async fn set_json<T: Serialize>(&self, key: &str, value: &T, ttl_seconds: u64) -> Result<(), RedisServiceError> {
+        let json = serde_json::to_string(value)?;
+        self.set_string(key, &json, ttl_seconds).await
+    }
+
+    pub async fn get_json<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, RedisServiceError> {
+        match self.get_string(key).await? {
+            Some(json) => serde_json::from_str(&json).map(Some).map_err(|e| {
+                self.stats.record_error(key);
+                RedisServiceError::from(e)
+            }),
             None => Ok(None),
         }
     }
 
-    // Rate limiting with simple counter
-    pub async fn check_rate_limit(&self, key: &str, limit: u32, window_seconds: u64) -> Result<bool, String> {
-        let mut conn = self.client.get_multiplexed_async_connection()
-            .await
-            .map_err(|e| e.to_string())?;
+    // Rate limiting with simple counter; lets everything through when caching is disabled
+    pub async fn check_rate_limit(&self, key: &str, limit: u32, window_seconds: u64) -> Result<bool, RedisServiceError> {
+        self.run(
+            "INCR",
+            |mut conn| async move {
+                let count: u32 = conn.get(key).await.unwrap_or(0);
+                if count >= limit {
+                    return Ok(false);
+                }
+                let _: () = conn.incr(key, 1).await?;
+                let _: () = conn.expire(key, window_seconds as i64).await?;
+                Ok(true)
+            },
+            |store| {
+                let Some(store) = store else {
+                    return Ok(true);
+                };
+                let count = store.get(key).and_then(|v| v.parse::<u32>().ok()).unwrap_or(0);
+                if count >= limit {
+                    return Ok(false);
+                }
+                store.incr(key).map_err(RedisServiceError::Command)?;
+                store.expire(key, window_seconds);
+                Ok(true)
+            },
+        )
+        .await
+    }
 
-        let count: u32 = conn.get(key)
-            .await
-            .unwrap_or(0);
-        
-        if count < limit {
-            let _: () = conn.incr(key, 1)
-                .await
-                .map_err(|e: redis::RedisError| e.to_string())?;
-            
-            let _: () = conn.expire(key, window_seconds as i64)
-                .await
-                .map_err(|e: redis::RedisError| e.to_string())?;
-            
-            Ok(true)
-        } else {
-            Ok(false)
-        }
-    }
-
-    // Distributed Lock: Set key only if it doesn't exist
-    pub async fn try_lock(&self, key: &str, ttl_seconds: u64) -> Result<bool, String> {
-        let mut conn = self.client.get_multiplexed_async_connection()
-            .await
-            .map_err(|e| e.to_string())?;
-
-        // SET key value NX EX ttl
-        // Returns OK if set, Null if not set
-        let result: Option<String> = redis::cmd("SET")
-            .arg(key)
-            .arg("locked")
-            .arg("NX")
-            .arg("EX")
-            .arg(ttl_seconds)
-            .query_async(&mut conn)
-            .await
-            .map_err(|e: redis::RedisError| e.to_string())?;
+    /// Increment a counter and (re)start its expiry window; returns the new
+    /// count (always 1 when caching is disabled)
+    pub async fn incr_with_ttl(&self, key: &str, ttl_seconds: u64) -> Result<i64, RedisServiceError> {
+        self.run(
+            "INCR",
+            |mut conn| async move {
+                let count: i64 = conn.incr(key, 1).await?;
+                let _: () = conn.expire(key, ttl_seconds as i64).await?;
+                Ok(count)
+            },
+            |store| {
+                let Some(store) = store else {
+                    return Ok(1);
+                };
+                let count = store.incr(key).map_err(RedisServiceError::Command)?;
+                store.expire(key, ttl_seconds);
+                Ok(count)
+            },
+        )
+        .await
+    }
 
-        Ok(result.is_some())
+    // Distributed Lock: Set key only if it doesn't exist. While Redis is down
+    // the lock only holds within this instance, or not at all when disabled.
+    pub async fn try_lock(&self, key: &str, ttl_seconds: u64) -> Result<bool, RedisServiceError> {
+        self.run(
+            "SET",
+            |mut conn| async move {
+                // SET key value NX EX ttl
+                // Returns OK if set, Null if not set
+                let result: Option<String> = redis::cmd("SET")
+                    .arg(key)
+                    .arg("locked")
+                    .arg("NX")
+                    .arg("EX")
+                    .arg(ttl_seconds)
+                    .query_async(&mut conn)
+                    .await?;
+                Ok(result.is_some())
+            },
+            |store| Ok(store.is_none_or(|store| store.set_nx(key, "locked", ttl_seconds))),
+        )
+        .await
     }
 
-    pub async fn set_string(&self, key: &str, value: &str, ttl_seconds: u64) -> Result<(), String> {
-        let mut conn = self.client.get_multiplexed_async_connection()
-            .await
-            .map_err(|e| e.to_string())?;
-        
-        conn.set_ex(key, value, ttl_seconds)
-            .await
-            .map_err(|e: redis::RedisError| e.to_string())
+    pub async fn set_string(&self, key: &str, value: &str, ttl_seconds: u64) -> Result<(), RedisServiceError> {
+        let result = self
+            .run(
+                "SETEX",
+                |mut conn| async move { conn.set_ex(key, value, ttl_seconds).await },
+                |store| {
+                    if let Some(store) = store {
+                        store.set(key, value, ttl_seconds);
+                    }
+                    Ok(())
+                },
+            )
+            .await;
+
+        match &result {
+            Ok(()) => self.stats.record_write(key, value.len()),
+            Err(_) => self.stats.record_error(key),
+        }
+        result
     }
 
-    pub async fn get_string(&self, key: &str) -> Result<Option<String>, String> {
-        let mut conn = self.client.get_multiplexed_async_connection()
-            .await
-            .map_err(|e| e.to_string())?;
-            
-        let result: Option<String> = conn.get(key)
+    pub async fn get_string(&self, key: &str) -> Result<Option<String>, RedisServiceError> {
+        let result = self
+            .run(
+                "GET",
+                |mut conn| async move { conn.get(key).await },
+                |store| Ok(store.and_then(|store| store.get(key))),
+            )
+            .await;
+
+        match &result {
+            Ok(Some(value)) => self.stats.record_hit(key, value.len()),
+            Ok(None) => self.stats.record_miss(key),
+            Err(_) => self.stats.record_error(key),
+        }
+        result
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<(), RedisServiceError> {
+        self.run(
+            "DEL",
+            |mut conn| async move { conn.del(key).await },
+            |store| {
+                if let Some(store) = store {
+                    store.delete(key);
+                }
+                Ok(())
+            },
+        )
+        .await
+    }
+
+    /// Run the Lua `script` on `key` in one atomic step, with `args` and then
+    /// `ttl_seconds` as its arguments. Without Redis, `local` makes the same
+    /// update under the memory store's lock: it gets the current value and
+    /// returns the one to keep for `ttl_seconds` with the result. With caching
+    /// disabled it sees no value and nothing is kept.
+    pub async fn eval_atomic<T, F>(
+        &self,
+        script: &redis::Script,
+        key: &str,
+        args: &[u64],
+        ttl_seconds: u64,
+        local: F,
+    ) -> Result<T, RedisServiceError>
+    where
+        T: redis::FromRedisValue,
+        F: FnOnce(Option<String>) -> (String, T),
+    {
+        self.run(
+            "EVALSHA",
+            |mut conn| async move { script.key(key).arg(args).arg(ttl_seconds).invoke_async(&mut conn).await },
+            |store| {
+                Ok(match store {
+                    Some(store) => store.update(key, ttl_seconds, local),
+                    None => local(None).1,
+                })
+            },
+        )
+        .await
+    }
+
+    /// Delete every key starting with `prefix` (SCAN, then DEL per batch);
+    /// returns how many were deleted
+    pub async fn delete_prefix(&self, prefix: &str) -> Result<u64, RedisServiceError> {
+        // The prefix is matched literally, not as a glob
+        let mut pattern = String::with_capacity(prefix.len() + 1);
+        for c in prefix.chars() {
+            if matches!(c, '\\' | '*' | '?' | '[' | ']') {
+                pattern.push('\\');
+            }
+            pattern.push(c);
+        }
+        pattern.push('*');
+        self.run(
+            "SCAN",
+            |mut conn| async move {
+                let mut deleted = 0;
+                let mut cursor: u64 = 0;
+                loop {
+                    let (next, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+                        .arg(cursor)
+                        .arg("MATCH")
+                        .arg(&pattern)
+                        .arg("COUNT")
+                        .arg(500)
+                        .query_async(&mut conn)
+                        .await?;
+                    if !keys.is_empty() {
+                        let removed: u64 = conn.del(&keys).await?;
+                        deleted += removed;
+                    }
+                    if next == 0 {
+                        return Ok(deleted);
+                    }
+                    cursor = next;
+                }
+            },
+            |store| Ok(store.map_or(0, |store| store.delete_prefix(prefix))),
+        )
+        .await
+    }
+
+    /// Publish on a pub/sub channel; returns how many subscribers received it
+    /// (always none without Redis)
+    pub async fn publish(&self, channel: &str, message: &str) -> Result<u64, RedisServiceError> {
+        self.run("PUBLISH", |mut conn| async move { conn.publish(channel, message).await }, |_| Ok(0))
             .await
-            .map_err(|e: redis::RedisError| e.to_string())?;
+    }
+
+    /// A dedicated connection subscribed to `channel`; read it with `on_message()`
+    pub async fn subscribe(&self, channel: &str) -> Result<redis::aio::PubSub, RedisServiceError> {
+        let client = match self.route() {
+            Route::Redis(shared) => &shared.client,
+            Route::Local(_) => {
+                return Err(RedisServiceError::Connection("Pub/sub needs Redis; the in-memory cache has none".to_string()))
+            }
+            Route::Degraded(_) => {
+                return Err(RedisServiceError::Connection("Pub/sub is unavailable while Redis is unreachable".to_string()))
+            }
+        };
+        let subscribed = self
+            .bounded("SUBSCRIBE", async {
+                let mut pubsub = client.get_async_pubsub().await?;
+                pubsub.subscribe(channel).await?;
+                Ok(pubsub)
+            })
+            .await;
+        if let Err(e) = &subscribed {
+            metrics().redis_errors.inc("SUBSCRIBE");
+            self.health.mark_down(e);
+        }
+        subscribed
+    }
 
-        Ok(result)
+    /// Remaining TTL in seconds (-1 = no expiry, -2 = key missing)
+    pub async fn ttl(&self, key: &str) -> Result<i64, RedisServiceError> {
+        self.run(
+            "TTL",
+            |mut conn| async move { conn.ttl(key).await },
+            |store| Ok(store.map_or(-2, |store| store.ttl(key))),
+        )
+        .await
     }
 
     // Cache with deduplication
-    pub async fn get_or_set_json<T, F, Fut>(&self, key: &str, ttl_seconds: u64, fetch_fn: F) -> Result<T, String>
+    pub async fn get_or_set_json<T, E, F, Fut>(&self, key: &str, ttl_seconds: u64, fetch_fn: F) -> Result<T, E>
     where
         T: Serialize + DeserializeOwned,
+        E: From<RedisServiceError>,
         F: FnOnce() -> Fut,
-        Fut: std::future::Future<Output = Result<T, String>>,
+        Fut: std::future::Future<Output = Result<T, E>>,
     {
         // Try to get from cache first
         if let Some(cached) = self.get_json::<T>(key).await? {
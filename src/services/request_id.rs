@@ -0,0 +1,61 @@
+//! Correlation IDs for stitching one request together across logs.
+//!
+//! Every request gets an `X-Request-Id`: the caller's, when it sends a usable
+//! one, or a fresh UUID. The ID is echoed on the response, recorded on a
+//! `request` span wrapping the whole request (so every event logged while
+//! handling it carries `request_id`), added to `SwapErrorResponse` bodies and
+//! forwarded on outbound Trocador calls. Code running for a request reads it
+//! with `current_request_id`; work spawned onto other tasks does not see it.
+
+use axum::{
+    body::Body,
+    http::{HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Instrument;
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest caller-supplied ID kept; longer ones are replaced
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// ID of the request the current task is handling, if any
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// Run `future` as part of request `id`, e.g. from a non-HTTP entry point
+pub async fn with_request_id<F: std::future::Future>(id: String, future: F) -> F::Output {
+    REQUEST_ID.scope(id, future).await
+}
+
+/// The caller's ID when it is short and made of safe characters, so it can
+/// be logged and forwarded as-is
+pub fn accept_request_id(value: Option<&str>) -> Option<String> {
+    let value = value?.trim();
+    let usable = !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'));
+    usable.then(|| value.to_string())
+}
+
+/// Assign or take over the request's ID, handle the request inside its span
+/// and echo the ID on the response
+pub async fn propagate_request_id(mut request: Request<Body>, next: Next) -> Response {
+    let id = accept_request_id(request.headers().get(REQUEST_ID_HEADER).and_then(|v| v.to_str().ok()))
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let header = HeaderValue::from_str(&id).expect("request ids are visible ASCII");
+    // Handlers and the access log see the ID actually used
+    request.headers_mut().insert(REQUEST_ID_HEADER, header.clone());
+
+    let span = tracing::info_span!("request", request_id = %id, method = %request.method(), path = %request.uri().path());
+    let mut response = with_request_id(id, next.run(request)).instrument(span).await;
+
+    response.headers_mut().insert(REQUEST_ID_HEADER, header);
+    response
+}
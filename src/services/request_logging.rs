@@ -0,0 +1,159 @@
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    extract::State,
+    http::{header, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::config::environment::RouteLogLevel;
+use crate::{AppState, MAX_BODY_BYTES};
+
+/// JSON keys whose values are never logged
+const SECRET_KEYS: &[&str] = &[
+    "password",
+    "password_confirm",
+    "token",
+    "access_token",
+    "refresh_token",
+    "two_factor_code",
+    "two_factor_token",
+    "backup_code",
+    "secret",
+    "api_key",
+];
+
+/// Structured access log: one `http_request` event per request, fields per the route's log level
+pub async fn log_requests(
+    State(state): State<Arc<AppState>>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let config = &state.request_log;
+    let level = config.level_for(request.uri().path());
+
+    if level == RouteLogLevel::Off {
+        return next.run(request).await;
+    }
+
+    let started = Instant::now();
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let caller = caller_id(&state, &request);
+
+    let (request, request_body) = if level == RouteLogLevel::Bodies {
+        let (parts, body) = request.into_parts();
+        let bytes = match to_bytes(body, MAX_BODY_BYTES).await {
+            Ok(bytes) => bytes,
+            Err(_) => return StatusCode::PAYLOAD_TOO_LARGE.into_response(),
+        };
+        let logged = render_body(&bytes, config.max_body_bytes);
+        (Request::from_parts(parts, Body::from(bytes)), Some(logged))
+    } else {
+        (request, None)
+    };
+
+    let response = next.run(request).await;
+
+    let (response, response_body) = if level == RouteLogLevel::Bodies {
+        let (parts, body) = response.into_parts();
+        let bytes = to_bytes(body, usize::MAX).await.unwrap_or_default();
+        let logged = render_body(&bytes, config.max_body_bytes);
+        (Response::from_parts(parts, Body::from(bytes)), Some(logged))
+    } else {
+        (response, None)
+    };
+
+    tracing::info!(
+        target: "http_request",
+        method = %method,
+        path = %path,
+        status = response.status().as_u16(),
+        latency_ms = started.elapsed().as_millis() as u64,
+        caller = caller.as_deref().unwrap_or("anonymous"),
+        request_body = request_body.as_deref(),
+        response_body = response_body.as_deref(),
+    );
+
+    response
+}
+
+/// "user:<id>" when the request carries a valid access token
+pub(crate) fn caller_id(state: &AppState, request: &Request<Body>) -> Option<String> {
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))?;
+
+    state
+        .jwt_service
+        .verify_access_token(token)
+        .ok()
+        .map(|data| format!("user:{}", data.claims.sub))
+}
+
+/// Redacted, truncated rendering of a body for the log
+fn render_body(bytes: &Bytes, max_bytes: usize) -> String {
+    if bytes.is_empty() {
+        return String::new();
+    }
+
+    let rendered = match serde_json::from_slice::<Value>(bytes) {
+        Ok(mut json) => {
+            redact(&mut json);
+            json.to_string()
+        }
+        Err(_) => format!("<{} bytes, not JSON>", bytes.len()),
+    };
+
+    truncate(rendered, max_bytes)
+}
+
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                let key = key.to_lowercase();
+                if SECRET_KEYS.contains(&key.as_str()) {
+                    *field = Value::String("[REDACTED]".to_string());
+                } else if key.contains("address") || key.contains("extra_id") {
+                    if let Value::String(s) = field {
+                        *s = mask(s);
+                    }
+                } else {
+                    redact(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+/// Keep just enough of an address to correlate: "bc1q…0wlh"
+fn mask(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() <= 8 {
+        return "*".repeat(chars.len());
+    }
+    let head: String = chars[..4].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}…{}", head, tail)
+}
+
+fn truncate(mut s: String, max_bytes: usize) -> String {
+    if s.len() <= max_bytes {
+        return s;
+    }
+    let mut cut = max_bytes;
+    while !s.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    s.truncate(cut);
+    s.push_str("…[truncated]");
+    s
+}
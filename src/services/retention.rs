@@ -0,0 +1,316 @@
+//! Data retention.
+//!
+//! Once a record is old enough that we only keep it for accounting, the
+//! fields that identify a person are scrubbed: deposit/recipient/refund
+//! addresses and extra ids on finished swaps, wallet addresses on finished
+//! on-ramp orders, session/referrer/user ids on funnel events. NOT NULL
+//! address columns become `redacted`. Raw provider payloads and webhook
+//! deliveries, which embed the same data, are deleted, as are stored
+//! idempotent responses once their replay window has passed. Amounts,
+//! statuses and timestamps are never touched.
+//!
+//! [`RetentionService::report`] counts what a run would change without
+//! changing anything (served at `GET /admin/retention/report`).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+
+use crate::config::environment::RetentionConfig;
+use crate::config::DbPool;
+use crate::services::jobs::{self, JobKind, JobOutcome};
+use crate::services::redis_cache::RedisService;
+
+const LOCK_KEY: &str = "lock:retention";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RetentionAction {
+    Scrub,  // Personal columns blanked, row kept
+    Delete, // Row removed
+}
+
+/// One retention rule over one table
+struct RetentionPolicy {
+    name: &'static str,
+    table: &'static str,
+    action: RetentionAction,
+    fields: &'static [&'static str], // Columns cleared (or the reason the row is removed)
+    window: fn(&RetentionConfig) -> u32,
+    eligible: &'static str, // WHERE clause; `?` is the window in days
+    scrub: &'static str,    // SET clause for RetentionAction::Scrub
+}
+
+const POLICIES: &[RetentionPolicy] = &[
+    RetentionPolicy {
+        name: "swap_addresses",
+        table: "swaps",
+        action: RetentionAction::Scrub,
+        fields: &[
+            "deposit_address",
+            "deposit_extra_id",
+            "recipient_address",
+            "recipient_extra_id",
+            "refund_address",
+            "refund_extra_id",
+        ],
+        window: |c| c.swap_days,
+        eligible: "status IN ('completed', 'failed', 'refunded', 'expired')
+                   AND anonymized_at IS NULL
+                   AND COALESCE(completed_at, updated_at) < NOW() - INTERVAL ? DAY",
+        scrub: "deposit_address = 'redacted', deposit_extra_id = NULL,
+                recipient_address = 'redacted', recipient_extra_id = NULL,
+                refund_address = NULL, refund_extra_id = NULL,
+                anonymized_at = NOW(), updated_at = updated_at",
+    },
+    RetentionPolicy {
+        name: "swap_refund_addresses",
+        table: "swap_refunds",
+        action: RetentionAction::Scrub,
+        fields: &["refund_address", "refund_extra_id"],
+        window: |c| c.swap_days,
+        eligible: "anonymized_at IS NULL
+                   AND updated_at < NOW() - INTERVAL ? DAY",
+        scrub: "refund_address = NULL, refund_extra_id = NULL,
+                anonymized_at = NOW(), updated_at = updated_at",
+    },
+    RetentionPolicy {
+        name: "swap_provider_payloads",
+        table: "swap_provider_payloads",
+        action: RetentionAction::Delete,
+        fields: &["payload_gz"],
+        window: |c| c.swap_days,
+        eligible: "created_at < NOW() - INTERVAL ? DAY",
+        scrub: "",
+    },
+    RetentionPolicy {
+        name: "swap_webhook_events",
+        table: "swap_webhook_events",
+        action: RetentionAction::Delete,
+        fields: &["payload", "signature"],
+        window: |c| c.swap_days,
+        eligible: "received_at < NOW() - INTERVAL ? DAY",
+        scrub: "",
+    },
+    RetentionPolicy {
+        name: "brand_webhook_deliveries",
+        table: "brand_webhook_deliveries",
+        action: RetentionAction::Delete,
+        fields: &["payload"],
+        window: |c| c.swap_days,
+        eligible: "created_at < NOW() - INTERVAL ? DAY",
+        scrub: "",
+    },
+    RetentionPolicy {
+        name: "idempotency_responses",
+        table: "idempotency_keys",
+        action: RetentionAction::Delete,
+        fields: &["response"],
+        window: |_| 1, // Replays are only honoured for a day
+        eligible: "created_at < NOW() - INTERVAL ? DAY",
+        scrub: "",
+    },
+    RetentionPolicy {
+        name: "quote_reservations",
+        table: "quotes",
+        action: RetentionAction::Delete,
+        fields: &["trade_id", "rate"],
+        window: |_| 1, // Reservations hold for minutes
+        eligible: "expires_at < NOW() - INTERVAL ? DAY",
+        scrub: "",
+    },
+    RetentionPolicy {
+        name: "shadow_quotes",
+        table: "shadow_quotes",
+        action: RetentionAction::Delete,
+        fields: &["from_currency", "to_currency", "amount"],
+        window: |c| c.analytics_days,
+        eligible: "created_at < NOW() - INTERVAL ? DAY",
+        scrub: "",
+    },
+    RetentionPolicy {
+        name: "api_usage",
+        table: "api_usage_hourly",
+        action: RetentionAction::Delete,
+        fields: &["endpoint", "requests"],
+        window: |c| c.analytics_days,
+        eligible: "hour_start < NOW() - INTERVAL ? DAY",
+        scrub: "",
+    },
+    RetentionPolicy {
+        name: "onramp_wallets",
+        table: "onramp_orders",
+        action: RetentionAction::Scrub,
+        fields: &["wallet_address", "payment_url", "chain_swap"],
+        window: |c| c.swap_days,
+        eligible: "status IN ('completed', 'failed', 'expired')
+                   AND anonymized_at IS NULL
+                   AND updated_at < NOW() - INTERVAL ? DAY",
+        scrub: "wallet_address = 'redacted', payment_url = NULL, chain_swap = NULL,
+                anonymized_at = NOW(), updated_at = updated_at",
+    },
+    RetentionPolicy {
+        name: "analytics_identifiers",
+        table: "analytics_events",
+        action: RetentionAction::Scrub,
+        fields: &["user_id", "session_id", "referrer"],
+        window: |c| c.analytics_days,
+        eligible: "occurred_at < NOW() - INTERVAL ? DAY
+                   AND (user_id IS NOT NULL OR session_id IS NOT NULL OR referrer IS NOT NULL)",
+        scrub: "user_id = NULL, session_id = NULL, referrer = NULL",
+    },
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionPolicyReport {
+    pub policy: String,
+    pub table: String,
+    pub action: RetentionAction,
+    pub fields: Vec<String>,
+    pub retention_days: u32, // 0 when the policy is off
+    pub rows: u64,           // Rows a run would change (report) or changed (run)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionReport {
+    pub dry_run: bool,
+    pub generated_at: DateTime<Utc>,
+    pub policies: Vec<RetentionPolicyReport>,
+    pub total_rows: u64,
+}
+
+pub struct RetentionService {
+    pool: DbPool,
+    config: RetentionConfig,
+}
+
+impl RetentionService {
+    pub fn new(pool: DbPool, config: RetentionConfig) -> Self {
+        Self { pool, config }
+    }
+
+    /// What a run would scrub or delete right now; changes nothing
+    pub async fn report(&self) -> Result<RetentionReport, sqlx::Error> {
+        let mut policies = Vec::with_capacity(POLICIES.len());
+        for policy in POLICIES {
+            let days = (policy.window)(&self.config);
+            let rows = if days == 0 {
+                0
+            } else {
+                let count: i64 =
+                    sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {} WHERE {}", policy.table, policy.eligible))
+                        .bind(days)
+                        .fetch_one(&self.pool)
+                        .await?;
+                count.max(0) as u64
+            };
+            policies.push(policy_report(policy, days, rows));
+        }
+
+        Ok(finish_report(true, policies))
+    }
+
+    /// Apply every policy, in batches of `batch_size` rows
+    pub async fn run(&self) -> Result<RetentionReport, sqlx::Error> {
+        let batch_size = self.config.batch_size.max(1);
+        let mut policies = Vec::with_capacity(POLICIES.len());
+
+        for policy in POLICIES {
+            let days = (policy.window)(&self.config);
+            let mut rows = 0;
+
+            if days > 0 {
+                let sql = match policy.action {
+                    RetentionAction::Scrub => {
+                        format!("UPDATE {} SET {} WHERE {} LIMIT ?", policy.table, policy.scrub, policy.eligible)
+                    }
+                    RetentionAction::Delete => {
+                        format!("DELETE FROM {} WHERE {} LIMIT ?", policy.table, policy.eligible)
+                    }
+                };
+
+                loop {
+                    let affected = sqlx::query(&sql)
+                        .bind(days)
+                        .bind(batch_size)
+                        .execute(&self.pool)
+                        .await?
+                        .rows_affected();
+                    rows += affected;
+                    if affected < batch_size as u64 {
+                        break;
+                    }
+                }
+            }
+
+            if rows > 0 {
+                tracing::info!("Retention policy {} changed {} row(s) in {}", policy.name, rows, policy.table);
+            }
+            policies.push(policy_report(policy, days, rows));
+        }
+
+        Ok(finish_report(false, policies))
+    }
+}
+
+fn policy_report(policy: &RetentionPolicy, days: u32, rows: u64) -> RetentionPolicyReport {
+    RetentionPolicyReport {
+        policy: policy.name.to_string(),
+        table: policy.table.to_string(),
+        action: policy.action,
+        fields: policy.fields.iter().map(|f| f.to_string()).collect(),
+        retention_days: days,
+        rows,
+    }
+}
+
+fn finish_report(dry_run: bool, policies: Vec<RetentionPolicyReport>) -> RetentionReport {
+    RetentionReport {
+        dry_run,
+        generated_at: Utc::now(),
+        total_rows: policies.iter().map(|p| p.rows).sum(),
+        policies,
+    }
+}
+
+/// Spawn the scheduled retention run; one instance runs it at a time
+pub fn spawn_retention_worker(pool: DbPool, redis: RedisService, config: RetentionConfig) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        tracing::info!(
+            "Retention worker started (swaps {} days, analytics {} days)",
+            config.swap_days,
+            config.analytics_days
+        );
+
+        let interval = config.interval;
+        let lock_ttl = interval.as_secs().clamp(60, 3600);
+        let service = RetentionService::new(pool, config);
+        let job = jobs::registry().register(
+            "retention",
+            "Scrub personal data past its retention window",
+            JobKind::Scheduled,
+        );
+
+        loop {
+            let run = job.start();
+            match redis.try_lock(LOCK_KEY, lock_ttl).await {
+                Ok(true) => {
+                    match service.run().await {
+                        Ok(report) => {
+                            tracing::info!("Retention run complete: {} row(s) changed", report.total_rows);
+                            run.finish(JobOutcome::Success, None);
+                        }
+                        Err(e) => {
+                            tracing::error!("Retention run failed: {}", e);
+                            run.finish(JobOutcome::Failed, Some(e.to_string()));
+                        }
+                    }
+                    let _ = redis.delete(LOCK_KEY).await;
+                }
+                _ => run.finish(JobOutcome::Skipped, None),
+            }
+
+            job.wait(interval).await;
+        }
+    })
+}
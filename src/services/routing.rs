@@ -0,0 +1,480 @@
+//! Provider routing rules.
+//!
+//! Rules live in `routing_rules` and are managed through
+//! `/admin/routing-rules`. A rule can be narrowed to pairs touching one of
+//! its `currencies`, to swaps worth at least `min_usd` (at the reference
+//! prices in HIGH_VALUE_USD_PRICES) and to callers from one of its
+//! `countries`; unset fields match anything. The caller's country is the
+//! ISO code in the ROUTING_COUNTRY_HEADER request header (Cloudflare's
+//! `CF-IPCountry` by default), and "EU" stands for every member state.
+//!
+//! Every enabled rule that matches applies: `block` drops the provider's
+//! quotes and refuses swaps with it, `prefer` ranks its quotes first and
+//! makes best-provider selection take them over better quotes elsewhere,
+//! and `prefer_fixed` / `prefer_floating` pick the rate type when the
+//! caller left it open. Between rate-type rules the most specific wins, the
+//! newest on a tie. Rules belong to a tenant and are cached like fee rules.
+
+use axum::{
+    extract::FromRequestParts,
+    http::{request::Parts, HeaderMap},
+};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::config::environment::HighValueConfig;
+use crate::config::DbPool;
+use crate::modules::swap::schema::RateType;
+use crate::services::redis_cache::RedisService;
+use crate::services::tenant::TenantId;
+
+const CACHE_KEY: &str = "routing_rules:all";
+const CACHE_TTL_SECS: u64 = 300;
+
+/// Member states matched by the "EU" country code
+const EU_COUNTRIES: [&str; 27] = [
+    "AT", "BE", "BG", "CY", "CZ", "DE", "DK", "EE", "ES", "FI", "FR", "GR", "HR", "HU", "IE", "IT", "LT", "LU", "LV",
+    "MT", "NL", "PL", "PT", "RO", "SE", "SI", "SK",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(rename_all = "snake_case")]
+pub enum RoutingAction {
+    Block,          // Never quote or trade with the provider
+    Prefer,         // Rank the provider first
+    PreferFixed,    // Fixed rate unless the caller chose
+    PreferFloating, // Floating rate unless the caller chose
+}
+
+impl RoutingAction {
+    fn targets_provider(self) -> bool {
+        matches!(self, Self::Block | Self::Prefer)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RoutingRule {
+    pub id: u64,
+    pub tenant_id: String,
+    pub name: String,
+    pub currencies: Option<String>, // Comma-separated tickers; None matches every pair
+    pub min_usd: Option<f64>,       // None matches every amount
+    pub countries: Option<String>,  // Comma-separated ISO codes or EU; None matches every caller
+    pub provider: Option<String>,   // Set exactly for block and prefer
+    pub action: RoutingAction,
+    pub enabled: bool,
+    pub note: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Fields an admin sets; the id comes from the path on update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutingRuleInput {
+    pub name: String,
+    #[serde(default)]
+    pub currencies: Option<String>,
+    #[serde(default)]
+    pub min_usd: Option<f64>,
+    #[serde(default)]
+    pub countries: Option<String>,
+    #[serde(default)]
+    pub provider: Option<String>,
+    pub action: RoutingAction,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+/// What a quote request or swap is routed for
+pub struct RoutingScope<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    pub usd_value: Option<f64>, // None when neither side has a reference price
+    pub country: Option<&'a str>,
+}
+
+impl<'a> RoutingScope<'a> {
+    /// Scope of `amount` of `from` swapped to `to`, valued at the
+    /// HIGH_VALUE_USD_PRICES reference prices
+    pub fn new(
+        from: &'a str,
+        to: &'a str,
+        amount: Decimal,
+        country: Option<&'a str>,
+        high_value: &HighValueConfig,
+    ) -> Self {
+        let usd_value = high_value.usd_amount(from, amount);
+        Self { from, to, usd_value, country }
+    }
+}
+
+/// What the matching rules decided for a scope
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoutingDecision {
+    pub matched: Vec<u64>,      // Ids of the matching rules
+    pub blocked: Vec<String>,   // Providers whose quotes are dropped
+    pub preferred: Vec<String>, // Providers ranked first
+    pub rate_type: Option<RateType>,
+}
+
+impl RoutingDecision {
+    pub fn allows(&self, provider: &str) -> bool {
+        !self.blocked.iter().any(|p| p.eq_ignore_ascii_case(provider))
+    }
+
+    pub fn prefers(&self, provider: &str) -> bool {
+        self.preferred.iter().any(|p| p.eq_ignore_ascii_case(provider))
+    }
+}
+
+fn list(field: &Option<String>) -> impl Iterator<Item = &str> {
+    field.as_deref().unwrap_or_default().split(',').map(str::trim).filter(|v| !v.is_empty())
+}
+
+impl RoutingRule {
+    fn matches(&self, scope: &RoutingScope) -> bool {
+        let pair = self.currencies.is_none()
+            || list(&self.currencies).any(|c| c.eq_ignore_ascii_case(scope.from) || c.eq_ignore_ascii_case(scope.to));
+        let amount = self.min_usd.is_none_or(|min| scope.usd_value.is_some_and(|usd| usd >= min));
+        let country = self.countries.is_none()
+            || scope.country.is_some_and(|country| {
+                list(&self.countries).any(|c| {
+                    c.eq_ignore_ascii_case(country)
+                        || (c.eq_ignore_ascii_case("EU") && EU_COUNTRIES.iter().any(|eu| eu.eq_ignore_ascii_case(country)))
+                })
+            });
+
+        self.enabled && pair && amount && country
+    }
+
+    fn specificity(&self) -> usize {
+        [self.currencies.is_some(), self.min_usd.is_some(), self.countries.is_some()]
+            .iter()
+            .filter(|set| **set)
+            .count()
+    }
+}
+
+/// Apply every rule matching `scope`
+pub fn evaluate(rules: &[RoutingRule], scope: &RoutingScope) -> RoutingDecision {
+    let matching: Vec<&RoutingRule> = rules.iter().filter(|rule| rule.matches(scope)).collect();
+    let providers = |action: RoutingAction| -> Vec<String> {
+        matching
+            .iter()
+            .filter(|rule| rule.action == action)
+            .filter_map(|rule| rule.provider.clone())
+            .collect()
+    };
+    let rate_type = matching
+        .iter()
+        .filter(|rule| !rule.action.targets_provider())
+        .max_by_key(|rule| (rule.specificity(), rule.id))
+        .map(|rule| match rule.action {
+            RoutingAction::PreferFixed => RateType::Fixed,
+            _ => RateType::Floating,
+        });
+
+    RoutingDecision {
+        matched: matching.iter().map(|rule| rule.id).collect(),
+        blocked: providers(RoutingAction::Block),
+        preferred: providers(RoutingAction::Prefer),
+        rate_type,
+    }
+}
+
+impl RoutingRuleInput {
+    /// Lowercase tickers, uppercase countries, drop blank fields
+    pub fn normalized(self) -> Self {
+        let clean = |v: Option<String>| v.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        let clean_list = |v: Option<String>, upper: bool| {
+            clean(v)
+                .map(|v| {
+                    v.split(',')
+                        .map(|item| if upper { item.trim().to_uppercase() } else { item.trim().to_lowercase() })
+                        .filter(|item| !item.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(",")
+                })
+                .filter(|v| !v.is_empty())
+        };
+        Self {
+            name: self.name.trim().to_string(),
+            currencies: clean_list(self.currencies, false),
+            countries: clean_list(self.countries, true),
+            provider: clean(self.provider),
+            note: clean(self.note),
+            ..self
+        }
+    }
+
+    pub fn check_rules(&self) -> Result<(), String> {
+        if self.name.is_empty() || self.name.len() > 100 {
+            return Err("name must be 1-100 characters".to_string());
+        }
+        if self.action.targets_provider() != self.provider.is_some() {
+            return Err("block and prefer rules need a provider; rate type rules take none".to_string());
+        }
+        if self.min_usd.is_some_and(|min| !min.is_finite() || min < 0.0) {
+            return Err("min_usd must not be negative".to_string());
+        }
+        let valid_country = |c: &str| c.len() == 2 && c.chars().all(|ch| ch.is_ascii_uppercase());
+        if let Some(bad) = list(&self.countries).find(|c| !valid_country(c)) {
+            return Err(format!("{} is not a two-letter country code or EU", bad));
+        }
+        Ok(())
+    }
+
+    /// The rule as it would be stored, for dry runs of unsaved rules
+    pub fn preview(self, id: u64) -> RoutingRule {
+        RoutingRule {
+            id,
+            tenant_id: String::new(),
+            name: self.name,
+            currencies: self.currencies,
+            min_usd: self.min_usd,
+            countries: self.countries,
+            provider: self.provider,
+            action: self.action,
+            enabled: self.enabled,
+            note: self.note,
+            updated_at: Utc::now(),
+        }
+    }
+}
+
+// =============================================================================
+// DRY RUN
+// =============================================================================
+
+/// A request to route, evaluated against the stored rules or `rules`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutingDryRunRequest {
+    pub from: String,
+    pub to: String,
+    #[serde(default)]
+    pub amount: Decimal, // In `from`
+    #[serde(default)]
+    pub country: Option<String>,
+    #[serde(default)]
+    pub providers: Vec<String>, // Providers to report on, e.g. those quoting the pair
+    #[serde(default)]
+    pub rules: Option<Vec<RoutingRuleInput>>, // Unsaved rules to try instead of the stored ones
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderRoute {
+    pub provider: String,
+    pub allowed: bool,
+    pub preferred: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutingDryRunResponse {
+    pub usd_value: Option<f64>,
+    pub matched: Vec<RoutingRule>,
+    pub decision: RoutingDecision,
+    pub providers: Vec<ProviderRoute>,
+}
+
+/// Route `request` through `rules` without touching any quote or swap
+pub fn dry_run(
+    rules: &[RoutingRule],
+    request: &RoutingDryRunRequest,
+    high_value: &HighValueConfig,
+) -> RoutingDryRunResponse {
+    let scope = RoutingScope::new(&request.from, &request.to, request.amount, request.country.as_deref(), high_value);
+    let decision = evaluate(rules, &scope);
+    let providers = request
+        .providers
+        .iter()
+        .map(|provider| ProviderRoute {
+            provider: provider.clone(),
+            allowed: decision.allows(provider),
+            preferred: decision.allows(provider) && decision.prefers(provider),
+        })
+        .collect();
+
+    RoutingDryRunResponse {
+        usd_value: scope.usd_value,
+        matched: rules.iter().filter(|rule| decision.matched.contains(&rule.id)).cloned().collect(),
+        decision,
+        providers,
+    }
+}
+
+// =============================================================================
+// CALLER COUNTRY
+// =============================================================================
+
+/// Country of the caller, from the header named by ROUTING_COUNTRY_HEADER
+#[derive(Debug, Clone, Default)]
+pub struct ClientCountry(pub Option<String>);
+
+impl ClientCountry {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = std::env::var("ROUTING_COUNTRY_HEADER").unwrap_or_else(|_| "cf-ipcountry".to_string());
+        let country = headers
+            .get(header.as_str())
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.trim().to_uppercase())
+            // Cloudflare sends XX for unknown and T1 for Tor
+            .filter(|v| v.len() == 2 && v.chars().all(|c| c.is_ascii_uppercase()) && v != "XX");
+        Self(country)
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ClientCountry {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
+
+// =============================================================================
+// REGISTRY
+// =============================================================================
+
+#[derive(Clone)]
+pub struct RoutingRules {
+    pool: DbPool,
+    redis: Option<RedisService>,
+    tenant: TenantId,
+}
+
+impl RoutingRules {
+    /// Rules of the default tenant
+    pub fn new(pool: DbPool, redis: Option<RedisService>) -> Self {
+        Self { pool, redis, tenant: TenantId::default() }
+    }
+
+    /// Read and manage another tenant's rules
+    pub fn with_tenant(mut self, tenant: TenantId) -> Self {
+        self.tenant = tenant;
+        self
+    }
+
+    fn cache_key(&self) -> String {
+        self.tenant.cache_key(CACHE_KEY)
+    }
+
+    /// Every rule of the tenant, ordered by id, from Redis when cached
+    pub async fn all(&self) -> Result<Vec<RoutingRule>, sqlx::Error> {
+        let cache_key = self.cache_key();
+        if let Some(redis) = &self.redis {
+            if let Ok(Some(rules)) = redis.get_json::<Vec<RoutingRule>>(&cache_key).await {
+                return Ok(rules);
+            }
+        }
+
+        let rules = sqlx::query_as::<_, RoutingRule>(&format!("{} WHERE tenant_id = ? ORDER BY id", ROUTING_RULE_SELECT))
+            .bind(self.tenant.as_str())
+            .fetch_all(&self.pool)
+            .await?;
+
+        if let Some(redis) = &self.redis {
+            let _ = redis.set_json(&cache_key, &rules, CACHE_TTL_SECS).await;
+        }
+
+        Ok(rules)
+    }
+
+    pub async fn create(&self, rule: &RoutingRuleInput) -> Result<RoutingRule, sqlx::Error> {
+        let id = sqlx::query(
+            r#"
+            INSERT INTO routing_rules (tenant_id, name, currencies, min_usd, countries, provider, action, enabled, note)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(self.tenant.as_str())
+        .bind(&rule.name)
+        .bind(&rule.currencies)
+        .bind(rule.min_usd)
+        .bind(&rule.countries)
+        .bind(&rule.provider)
+        .bind(rule.action)
+        .bind(rule.enabled)
+        .bind(&rule.note)
+        .execute(&self.pool)
+        .await?
+        .last_insert_id();
+
+        self.invalidate().await;
+        self.find(id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Replace a rule; `None` when no such rule exists
+    pub async fn update(&self, id: u64, rule: &RoutingRuleInput) -> Result<Option<RoutingRule>, sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE routing_rules
+            SET name = ?, currencies = ?, min_usd = ?, countries = ?, provider = ?,
+                action = ?, enabled = ?, note = ?
+            WHERE id = ? AND tenant_id = ?
+            "#,
+        )
+        .bind(&rule.name)
+        .bind(&rule.currencies)
+        .bind(rule.min_usd)
+        .bind(&rule.countries)
+        .bind(&rule.provider)
+        .bind(rule.action)
+        .bind(rule.enabled)
+        .bind(&rule.note)
+        .bind(id)
+        .bind(self.tenant.as_str())
+        .execute(&self.pool)
+        .await?;
+
+        // rows_affected is 0 for an unchanged row too, so look the rule up instead
+        self.invalidate().await;
+        self.find(id).await
+    }
+
+    /// Remove a rule, returning it; `None` when no such rule exists
+    pub async fn delete(&self, id: u64) -> Result<Option<RoutingRule>, sqlx::Error> {
+        let Some(existing) = self.find(id).await? else {
+            return Ok(None);
+        };
+
+        sqlx::query("DELETE FROM routing_rules WHERE id = ? AND tenant_id = ?")
+            .bind(id)
+            .bind(self.tenant.as_str())
+            .execute(&self.pool)
+            .await?;
+
+        self.invalidate().await;
+        Ok(Some(existing))
+    }
+
+    async fn find(&self, id: u64) -> Result<Option<RoutingRule>, sqlx::Error> {
+        sqlx::query_as::<_, RoutingRule>(&format!("{} WHERE id = ? AND tenant_id = ?", ROUTING_RULE_SELECT))
+            .bind(id)
+            .bind(self.tenant.as_str())
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn invalidate(&self) {
+        if let Some(redis) = &self.redis {
+            if let Err(e) = redis.delete(&self.cache_key()).await {
+                // Instances keep the old rules until the cached copy expires
+                tracing::warn!("Failed to invalidate routing rule cache: {}", e);
+            }
+        }
+    }
+}
+
+const ROUTING_RULE_SELECT: &str = r#"
+    SELECT id, tenant_id, name, currencies,
+           CAST(min_usd AS DOUBLE) AS min_usd,
+           countries, provider, action, enabled, note, updated_at
+    FROM routing_rules
+"#;
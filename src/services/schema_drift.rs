@@ -0,0 +1,240 @@
+//! Provider response schema drift detection.
+//!
+//! Trocador responses are parsed through [`parse_one`] / [`parse_list`], which
+//! record fields we do not map (captured by `#[serde(flatten)]` on the
+//! Trocador structs) and, in lenient mode, skip list items that no longer
+//! deserialize instead of failing the whole response. Counters are kept per
+//! endpoint for this process; the first occurrence of each new kind of drift
+//! raises an internal alert.
+
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{LazyLock, Mutex, OnceLock};
+
+use crate::services::outbox::{DomainEventType, Outbox};
+
+/// Implemented by provider structs that capture unmapped fields
+pub trait UnknownFields {
+    /// Dotted paths of fields present in the response but not mapped
+    fn unknown_fields(&self) -> Vec<String>;
+}
+
+/// Keys of a flattened catch-all map, prefixed with `path`
+pub fn unknown_keys(path: &str, extra: &serde_json::Map<String, serde_json::Value>) -> Vec<String> {
+    extra.keys().map(|k| format!("{}{}", path, k)).collect()
+}
+
+#[derive(Debug, Default)]
+struct EndpointDrift {
+    parses: u64,
+    unknown_fields: BTreeMap<String, u64>, // Responses each unmapped field appeared in
+    fallbacks: u64, // Items dropped by lenient parsing
+    failures: u64,  // Responses that could not be parsed at all
+    last_error: Option<String>,
+    last_drift_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EndpointDriftSnapshot {
+    pub endpoint: String,
+    pub parses: u64,
+    pub unknown_fields: BTreeMap<String, u64>,
+    pub fallbacks: u64,
+    pub failures: u64,
+    pub last_error: Option<String>,
+    pub last_drift_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SchemaDriftSnapshot {
+    pub lenient_parsing: bool,
+    pub drift_detected: bool,
+    pub endpoints: Vec<EndpointDriftSnapshot>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ParseIssue {
+    Fallback, // A list item was dropped
+    Failure,  // The response was rejected
+}
+
+impl ParseIssue {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ParseIssue::Fallback => "fallback",
+            ParseIssue::Failure => "failure",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SchemaDriftMonitor {
+    endpoints: Mutex<HashMap<&'static str, EndpointDrift>>,
+    alerted: Mutex<HashSet<String>>,
+    outbox: OnceLock<Outbox>,
+}
+
+static MONITOR: LazyLock<SchemaDriftMonitor> = LazyLock::new(SchemaDriftMonitor::default);
+
+/// Process-wide monitor shared by every TrocadorClient
+pub fn monitor() -> &'static SchemaDriftMonitor {
+    &MONITOR
+}
+
+/// TROCADOR_LENIENT_PARSING (default on): skip list items that fail to parse
+pub fn lenient_parsing() -> bool {
+    std::env::var("TROCADOR_LENIENT_PARSING")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(true)
+}
+
+impl SchemaDriftMonitor {
+    /// Also publish drift alerts as `alert.raised` domain events
+    pub fn set_outbox(&self, outbox: Outbox) {
+        let _ = self.outbox.set(outbox);
+    }
+
+    pub fn snapshot(&self) -> SchemaDriftSnapshot {
+        let endpoints = self.endpoints.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut snapshots: Vec<EndpointDriftSnapshot> = endpoints
+            .iter()
+            .map(|(endpoint, d)| EndpointDriftSnapshot {
+                endpoint: endpoint.to_string(),
+                parses: d.parses,
+                unknown_fields: d.unknown_fields.clone(),
+                fallbacks: d.fallbacks,
+                failures: d.failures,
+                last_error: d.last_error.clone(),
+                last_drift_at: d.last_drift_at,
+            })
+            .collect();
+        snapshots.sort_by(|a, b| a.endpoint.cmp(&b.endpoint));
+
+        SchemaDriftSnapshot {
+            lenient_parsing: lenient_parsing(),
+            drift_detected: snapshots
+                .iter()
+                .any(|e| !e.unknown_fields.is_empty() || e.fallbacks > 0 || e.failures > 0),
+            endpoints: snapshots,
+        }
+    }
+
+    fn with_endpoint(&self, endpoint: &'static str, f: impl FnOnce(&mut EndpointDrift)) {
+        let mut endpoints = self.endpoints.lock().unwrap_or_else(|e| e.into_inner());
+        f(endpoints.entry(endpoint).or_default());
+    }
+
+    fn record_parse(&self, endpoint: &'static str, unknown: &[String]) {
+        self.with_endpoint(endpoint, |d| {
+            d.parses += 1;
+            for field in unknown {
+                *d.unknown_fields.entry(field.clone()).or_default() += 1;
+            }
+            if !unknown.is_empty() {
+                d.last_drift_at = Some(Utc::now());
+            }
+        });
+
+        for field in unknown {
+            self.alert(endpoint, "unknown_field", field);
+        }
+    }
+
+    fn record_error(&self, endpoint: &'static str, issue: ParseIssue, error: &str) {
+        self.with_endpoint(endpoint, |d| {
+            match issue {
+                ParseIssue::Fallback => d.fallbacks += 1,
+                ParseIssue::Failure => d.failures += 1,
+            }
+            d.last_error = Some(error.to_string());
+            d.last_drift_at = Some(Utc::now());
+        });
+
+        self.alert(endpoint, issue.as_str(), error);
+    }
+
+    /// Raise once per (endpoint, kind, detail) for the life of the process
+    fn alert(&self, endpoint: &'static str, kind: &'static str, detail: &str) {
+        let signature = format!("{}:{}:{}", endpoint, kind, detail);
+        if !self.alerted.lock().unwrap_or_else(|e| e.into_inner()).insert(signature) {
+            return;
+        }
+
+        tracing::error!(
+            target: "alert",
+            endpoint = endpoint,
+            kind = kind,
+            detail = detail,
+            "Trocador response schema drift detected"
+        );
+
+        let (Some(outbox), Ok(runtime)) = (self.outbox.get(), tokio::runtime::Handle::try_current()) else {
+            return;
+        };
+        let outbox = outbox.clone();
+        let data = serde_json::json!({
+            "alert": "provider_schema_drift",
+            "severity": "warning",
+            "provider": "trocador",
+            "endpoint": endpoint,
+            "kind": kind,
+            "detail": detail,
+        });
+        runtime.spawn(async move {
+            outbox.record(DomainEventType::AlertRaised, "trocador", data).await;
+        });
+    }
+}
+
+/// Parse a single response object, recording unmapped fields
+pub fn parse_one<T>(endpoint: &'static str, value: serde_json::Value) -> Result<T, String>
+where
+    T: DeserializeOwned + UnknownFields,
+{
+    match serde_json::from_value::<T>(value) {
+        Ok(parsed) => {
+            monitor().record_parse(endpoint, &parsed.unknown_fields());
+            Ok(parsed)
+        }
+        Err(e) => {
+            monitor().record_error(endpoint, ParseIssue::Failure, &e.to_string());
+            Err(e.to_string())
+        }
+    }
+}
+
+/// Parse a list item by item. In lenient mode items that fail are dropped and
+/// counted as fallbacks; otherwise the first failure fails the whole list.
+pub fn parse_list<T>(endpoint: &'static str, items: Vec<serde_json::Value>) -> Result<Vec<T>, String>
+where
+    T: DeserializeOwned + UnknownFields,
+{
+    let lenient = lenient_parsing();
+    let mut parsed = Vec::with_capacity(items.len());
+    let mut unknown: Vec<String> = Vec::new();
+
+    for item in items {
+        match serde_json::from_value::<T>(item) {
+            Ok(value) => {
+                for field in value.unknown_fields() {
+                    if !unknown.contains(&field) {
+                        unknown.push(field);
+                    }
+                }
+                parsed.push(value);
+            }
+            Err(e) if lenient => monitor().record_error(endpoint, ParseIssue::Fallback, &e.to_string()),
+            Err(e) => {
+                monitor().record_error(endpoint, ParseIssue::Failure, &e.to_string());
+                return Err(e.to_string());
+            }
+        }
+    }
+
+    monitor().record_parse(endpoint, &unknown);
+    Ok(parsed)
+}
@@ -4,6 +4,8 @@ use axum::{
     middleware::Next,
     response::Response,
 };
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
 
 pub async fn security_headers(request: Request<Body>, next: Next) -> Response {
     let mut response = next.run(request).await;
@@ -32,3 +34,21 @@ pub async fn security_headers(request: Request<Body>, next: Next) -> Response {
 
     response
 }
+
+/// Compare secrets without leaking the position of the first mismatch
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Hex HMAC-SHA256 of `body`, as carried in webhook signature headers
+pub fn hmac_sha256_hex(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Check a hex HMAC-SHA256 signature of a raw request body
+pub fn verify_hmac_sha256(secret: &str, body: &[u8], signature: &str) -> bool {
+    let expected = hmac_sha256_hex(secret, body);
+    constant_time_eq(expected.as_bytes(), signature.trim().to_lowercase().as_bytes())
+}
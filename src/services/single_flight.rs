@@ -0,0 +1,63 @@
+//! In-process request coalescing.
+//!
+//! Identical calls that overlap share one execution: the first caller for a
+//! key starts the work in its own task, later callers for the same key wait
+//! on that task instead of starting another. The task runs to completion
+//! even when every caller has given up waiting, so its result can still land
+//! in a cache, and the key is released as soon as it finishes; the next call
+//! after that starts fresh. Across instances the Redis lock in
+//! `get_rates_cached` does the same job.
+
+use futures_util::future::{BoxFuture, FutureExt, Shared};
+use std::collections::HashMap;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Mutex};
+
+/// Output of a flight; `None` when its task panicked
+pub type Flight<T> = Shared<BoxFuture<'static, Option<T>>>;
+
+pub struct SingleFlight<T> {
+    flights: Arc<Mutex<HashMap<String, Flight<T>>>>,
+}
+
+impl<T: Clone + Send + Sync + 'static> SingleFlight<T> {
+    pub fn new() -> Self {
+        Self { flights: Arc::new(Mutex::new(HashMap::new())) }
+    }
+
+    /// The flight running for `key`, or a new one running `work`. The flag
+    /// is true when the caller joined a flight that was already running.
+    pub fn join<F>(&self, key: &str, work: F) -> (Flight<T>, bool)
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        let mut flights = self.flights.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(flight) = flights.get(key) {
+            return (flight.clone(), true);
+        }
+
+        let registry = self.flights.clone();
+        let owned_key = key.to_string();
+        let task = tokio::spawn(async move {
+            let output = AssertUnwindSafe(work).catch_unwind().await.ok();
+            registry.lock().unwrap_or_else(|e| e.into_inner()).remove(&owned_key);
+            output
+        });
+        let flight = task.map(|joined| joined.ok().flatten()).boxed().shared();
+        flights.insert(key.to_string(), flight.clone());
+
+        (flight, false)
+    }
+
+    /// Keys with a flight running
+    pub fn in_flight(&self) -> usize {
+        self.flights.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Default for SingleFlight<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
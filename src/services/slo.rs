@@ -0,0 +1,306 @@
+//! Response time SLOs.
+//!
+//! Each objective (see `SloConfig`) names a route and the share of its
+//! requests that must finish within a latency threshold: `p95:800` means 95%
+//! within 800ms. A request is bad when it is slower or answers with a 5xx;
+//! the other 5% is the error budget. Requests are counted per minute in this
+//! process, next to the request latency histogram served at `/metrics`.
+//!
+//! The burn rate is the bad share divided by the budget share; at 1 the
+//! budget lasts exactly the SLO window. A route that burns faster than
+//! SLO_BURN_RATE_ALERT over both the last hour and the last five minutes
+//! raises an `slo_burn_rate` alert, once until it recovers.
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::Request,
+    middleware::Next,
+    response::Response,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+use crate::config::environment::{SloConfig, SloObjective};
+use crate::services::jobs::{self, JobKind, JobOutcome};
+use crate::services::metrics::metrics;
+use crate::services::outbox::{DomainEventType, Outbox};
+use crate::AppState;
+
+/// Long and short burn-rate windows, in minutes; both must exceed the limit
+const LONG_WINDOW_MINUTES: i64 = 60;
+const SHORT_WINDOW_MINUTES: i64 = 5;
+
+/// Routes with fewer requests than this in the last hour never alert
+const MIN_ALERT_REQUESTS: u64 = 20;
+
+#[derive(Debug, Clone, Copy)]
+struct MinuteBucket {
+    minute: i64, // Minutes since the Unix epoch
+    total: u64,
+    bad: u64,
+}
+
+struct Series {
+    objective: SloObjective,
+    buckets: VecDeque<MinuteBucket>, // Oldest first, only minutes with requests
+}
+
+impl Series {
+    /// Requests and bad requests from `since` (a minute) on
+    fn counts(&self, since: i64) -> (u64, u64) {
+        self.buckets
+            .iter()
+            .filter(|b| b.minute >= since)
+            .fold((0, 0), |(total, bad), b| (total + b.total, bad + b.bad))
+    }
+
+    /// Share of requests allowed to be bad
+    fn budget(&self) -> f64 {
+        (100.0 - self.objective.percentile) / 100.0
+    }
+
+    fn burn_rate(&self, since: i64) -> Option<f64> {
+        let (total, bad) = self.counts(since);
+        (total > 0).then(|| bad as f64 / total as f64 / self.budget())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SloReport {
+    pub window_hours: u64,
+    pub burn_rate_alert: f64,
+    pub slos: Vec<SloStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SloStatus {
+    pub name: String, // e.g. "GET /swap/rates"
+    pub objective_percent: f64,
+    pub threshold_ms: u64,
+    pub requests: u64,     // Over the window
+    pub bad_requests: u64, // Slower than the threshold or answered with a 5xx
+    pub compliance_percent: Option<f64>, // None without requests
+    pub compliant: bool,
+    /// Share of the window's error budget left; negative once overspent
+    pub error_budget_remaining_percent: Option<f64>,
+    pub burn_rate_1h: Option<f64>,
+    pub burn_rate_5m: Option<f64>,
+    pub alerting: bool,
+}
+
+/// An SLO that started burning its budget too fast
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SloAlert {
+    pub name: String,
+    pub objective_percent: f64,
+    pub threshold_ms: u64,
+    pub burn_rate_1h: f64,
+    pub burn_rate_5m: f64,
+}
+
+/// Per-minute good/bad counts for each configured SLO
+pub struct SloTracker {
+    series: Mutex<Vec<Series>>,
+    window: Duration,
+    burn_rate_alert: f64,
+    alerting: Mutex<HashSet<String>>, // SLOs alerted on and not yet recovered
+}
+
+fn current_minute() -> i64 {
+    Utc::now().timestamp().div_euclid(60)
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+impl SloTracker {
+    pub fn new(config: &SloConfig) -> Self {
+        let objectives = if config.enabled { config.objectives.clone() } else { Vec::new() };
+        Self {
+            series: Mutex::new(
+                objectives
+                    .into_iter()
+                    .map(|objective| Series { objective, buckets: VecDeque::new() })
+                    .collect(),
+            ),
+            window: config.window,
+            burn_rate_alert: config.burn_rate_alert,
+            alerting: Mutex::new(HashSet::new()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Series>> {
+        self.series.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn window_minutes(&self) -> i64 {
+        (self.window.as_secs() / 60).max(1) as i64
+    }
+
+    /// Count one request to `route` (as registered, e.g. /swap/{id}); routes without an SLO are ignored
+    pub fn record(&self, method: &str, route: &str, latency: Duration, server_error: bool) {
+        let mut all = self.lock();
+        let Some(series) = all
+            .iter_mut()
+            .find(|s| s.objective.method == method && s.objective.route == route)
+        else {
+            return;
+        };
+
+        let minute = current_minute();
+        let bad = u64::from(server_error || latency > series.objective.threshold);
+        match series.buckets.back_mut() {
+            Some(bucket) if bucket.minute == minute => {
+                bucket.total += 1;
+                bucket.bad += bad;
+            }
+            _ => series.buckets.push_back(MinuteBucket { minute, total: 1, bad }),
+        }
+
+        let oldest = minute - self.window_minutes() + 1;
+        while series.buckets.front().is_some_and(|b| b.minute < oldest) {
+            series.buckets.pop_front();
+        }
+    }
+
+    /// Compliance, remaining budget and burn rates of every SLO
+    pub fn report(&self) -> SloReport {
+        let now = current_minute();
+        let alerting = self.alerting.lock().unwrap_or_else(|e| e.into_inner()).clone();
+
+        let slos = self
+            .lock()
+            .iter()
+            .map(|series| {
+                let (requests, bad_requests) = series.counts(now - self.window_minutes() + 1);
+                let bad_share = (requests > 0).then(|| bad_requests as f64 / requests as f64);
+                let compliance_percent = bad_share.map(|share| round2((1.0 - share) * 100.0));
+                let name = series.objective.name();
+
+                SloStatus {
+                    objective_percent: series.objective.percentile,
+                    threshold_ms: series.objective.threshold.as_millis() as u64,
+                    requests,
+                    bad_requests,
+                    compliant: compliance_percent.is_none_or(|c| c >= series.objective.percentile),
+                    compliance_percent,
+                    error_budget_remaining_percent: bad_share
+                        .map(|share| round2((1.0 - share / series.budget()) * 100.0)),
+                    burn_rate_1h: series.burn_rate(now - LONG_WINDOW_MINUTES + 1).map(round2),
+                    burn_rate_5m: series.burn_rate(now - SHORT_WINDOW_MINUTES + 1).map(round2),
+                    alerting: alerting.contains(&name),
+                    name,
+                }
+            })
+            .collect();
+
+        SloReport {
+            window_hours: self.window.as_secs() / 3600,
+            burn_rate_alert: self.burn_rate_alert,
+            slos,
+        }
+    }
+
+    /// SLOs that crossed the burn-rate limit since the last check. One that
+    /// stays over it is not returned again until it has dropped below.
+    pub fn check(&self) -> Vec<SloAlert> {
+        let now = current_minute();
+        let mut alerting = self.alerting.lock().unwrap_or_else(|e| e.into_inner());
+        let mut raised = Vec::new();
+
+        for series in self.lock().iter() {
+            let name = series.objective.name();
+            let (requests, _) = series.counts(now - LONG_WINDOW_MINUTES + 1);
+            let long = series.burn_rate(now - LONG_WINDOW_MINUTES + 1).unwrap_or(0.0);
+            let short = series.burn_rate(now - SHORT_WINDOW_MINUTES + 1).unwrap_or(0.0);
+            let burning = requests >= MIN_ALERT_REQUESTS && long >= self.burn_rate_alert && short >= self.burn_rate_alert;
+
+            if !burning {
+                if alerting.remove(&name) {
+                    tracing::info!("SLO {} recovered (burn rate {:.2} over 1h)", name, long);
+                }
+                continue;
+            }
+
+            if alerting.insert(name.clone()) {
+                raised.push(SloAlert {
+                    name,
+                    objective_percent: series.objective.percentile,
+                    threshold_ms: series.objective.threshold.as_millis() as u64,
+                    burn_rate_1h: round2(long),
+                    burn_rate_5m: round2(short),
+                });
+            }
+        }
+
+        raised
+    }
+}
+
+// =============================================================================
+// MIDDLEWARE
+// =============================================================================
+
+/// Time each routed request into the latency histogram and its SLO, if any
+pub async fn track_latency(State(state): State<Arc<AppState>>, request: Request<Body>, next: Next) -> Response {
+    let Some(route) = request.extensions().get::<MatchedPath>().map(|p| p.as_str().to_string()) else {
+        return next.run(request).await;
+    };
+    let method = request.method().clone();
+    let started = Instant::now();
+
+    let response = next.run(request).await;
+
+    let latency = started.elapsed();
+    metrics()
+        .http_request_seconds
+        .observe(&format!("{} {}", method, route), latency.as_secs_f64());
+    state.slo.record(method.as_str(), &route, latency, response.status().is_server_error());
+
+    response
+}
+
+// =============================================================================
+// BURN-RATE ALERTS
+// =============================================================================
+
+/// Check burn rates every `interval` and raise an alert for each SLO that
+/// crosses the limit. Counts are per instance, so each instance alerts on
+/// its own traffic.
+pub fn spawn_slo_monitor(tracker: Arc<SloTracker>, outbox: Outbox, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let job = jobs::registry().register(
+            "slo_monitor",
+            "Alert when a response time SLO burns its error budget too fast",
+            JobKind::Scheduled,
+        );
+
+        loop {
+            let run = job.start();
+            for alert in tracker.check() {
+                tracing::error!(
+                    target: "alert",
+                    slo = %alert.name,
+                    burn_rate_1h = alert.burn_rate_1h,
+                    burn_rate_5m = alert.burn_rate_5m,
+                    "SLO error budget burning too fast"
+                );
+                let data = serde_json::json!({
+                    "alert": "slo_burn_rate",
+                    "severity": "critical",
+                    "slo": &alert,
+                });
+                outbox.record(DomainEventType::AlertRaised, &alert.name, data).await;
+            }
+            run.finish(JobOutcome::Success, None);
+
+            job.wait(interval).await;
+        }
+    })
+}
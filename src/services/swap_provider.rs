@@ -0,0 +1,70 @@
+//! Quote aggregators behind one interface.
+//!
+//! Every aggregator with an API key configured is asked for quotes on each
+//! rates request, concurrently, and the quotes are merged with each one
+//! tagged by the aggregator it came from. Trocador reaches many providers
+//! through one call; ChangeNOW quotes itself directly. Trades are still
+//! opened through Trocador, which also routes to ChangeNOW.
+//!
+//! Aggregators listed in `SHADOW_AGGREGATORS` run in shadow mode: they are
+//! asked alongside the others, but their quotes are only recorded against
+//! the served ones (`shadow_quotes`) and never returned to users.
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+
+use crate::modules::swap::schema::RatesQuery;
+use crate::services::changenow::ChangeNowClient;
+use crate::services::trocador::TrocadorClient;
+
+#[async_trait]
+pub trait SwapProviderClient: Send + Sync {
+    /// Tag carried by this aggregator's quotes, e.g. "trocador"
+    fn aggregator(&self) -> &'static str;
+
+    /// Quotes from every provider this aggregator reaches for the pair and
+    /// amount (the receive amount when `query.amount_to` is set); a pair it
+    /// doesn't offer is no quotes rather than an error
+    async fn get_quotes(&self, query: &RatesQuery) -> Result<AggregatorQuotes, String>;
+}
+
+#[derive(Debug, Default)]
+pub struct AggregatorQuotes {
+    pub trade_id: Option<String>, // Trocador's rate id, passed back on new_trade
+    pub quotes: Vec<AggregatorQuote>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AggregatorQuote {
+    pub provider: String,
+    pub amount_to: Decimal,
+    pub amount_from: Option<Decimal>, // What the provider asks to be sent; quotes by amount_to only
+    pub min_amount: Decimal,
+    pub max_amount: Decimal, // 0 when the aggregator gives no maximum
+    pub provider_fee: Decimal,
+    pub kyc_rating: Option<String>,
+    pub eta_minutes: Option<u32>,
+}
+
+/// Configured aggregators: the shared Trocador client when there is one, then
+/// ChangeNOW when CHANGENOW_API_KEY is set. `markup` is the partner markup on
+/// Trocador quotes.
+pub fn configured_aggregators(trocador: Option<&TrocadorClient>, markup: Option<f64>) -> Vec<Box<dyn SwapProviderClient>> {
+    let mut aggregators: Vec<Box<dyn SwapProviderClient>> = Vec::new();
+
+    if let Some(trocador) = trocador {
+        aggregators.push(Box::new(trocador.clone().with_markup(markup)));
+    }
+    if let Some(api_key) = std::env::var("CHANGENOW_API_KEY").ok().filter(|k| !k.is_empty()) {
+        aggregators.push(Box::new(ChangeNowClient::new(api_key)));
+    }
+
+    aggregators
+}
+
+/// True when `aggregator` is listed in `SHADOW_AGGREGATORS` (comma separated)
+pub fn is_shadowed(aggregator: &str) -> bool {
+    std::env::var("SHADOW_AGGREGATORS")
+        .map(|list| list.split(',').any(|a| a.trim().eq_ignore_ascii_case(aggregator)))
+        .unwrap_or(false)
+}
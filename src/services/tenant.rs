@@ -0,0 +1,118 @@
+//! Tenants.
+//!
+//! A tenant is the isolation boundary for one white-label partner: its
+//! users, swaps, fee rules and analytics events carry its id, and requests
+//! only see rows of the tenant they are served under. A request's tenant is
+//! that of the brand it matched (see `branding`); requests matching no
+//! partner brand belong to the deployment's tenant (TENANT_ID, `default`
+//! unless set), so a single-partner deployment behaves as before.
+//!
+//! Cache entries holding tenant data are keyed with `TenantId::cache_key`,
+//! which leaves keys of the default tenant unchanged.
+
+use axum::{
+    extract::{FromRef, FromRequestParts},
+    http::request::Parts,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::services::branding::CurrentBrand;
+use crate::AppState;
+
+/// Tenant of rows created before tenants existed, and of single-tenant deployments
+pub const DEFAULT_TENANT: &str = "default";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TenantId(String);
+
+impl TenantId {
+    /// 1-50 lowercase letters, digits or '-'
+    pub fn new(id: &str) -> Result<Self, String> {
+        let id = id.trim();
+        let valid = !id.is_empty()
+            && id.len() <= 50
+            && id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        if valid {
+            Ok(Self(id.to_string()))
+        } else {
+            Err(format!("tenant '{}' must be 1-50 lowercase letters, digits or '-'", id))
+        }
+    }
+
+    /// The deployment's tenant: TENANT_ID, or `default` when unset or invalid
+    pub fn from_env() -> Self {
+        match std::env::var("TENANT_ID") {
+            Ok(id) => Self::new(&id).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring TENANT_ID: {}", e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.0 == DEFAULT_TENANT
+    }
+
+    /// `key` for the default tenant, `tenant:<id>:<key>` for any other
+    pub fn cache_key(&self, key: &str) -> String {
+        if self.is_default() {
+            key.to_string()
+        } else {
+            format!("tenant:{}:{}", self.0, key)
+        }
+    }
+}
+
+impl Default for TenantId {
+    fn default() -> Self {
+        Self(DEFAULT_TENANT.to_string())
+    }
+}
+
+impl fmt::Display for TenantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl TryFrom<String> for TenantId {
+    type Error = String;
+
+    fn try_from(id: String) -> Result<Self, Self::Error> {
+        Self::new(&id)
+    }
+}
+
+impl From<TenantId> for String {
+    fn from(id: TenantId) -> Self {
+        id.0
+    }
+}
+
+// =============================================================================
+// EXTRACTOR
+// =============================================================================
+
+/// The tenant a request is served under: its brand's tenant
+pub struct CurrentTenant(pub TenantId);
+
+impl<S> FromRequestParts<S> for CurrentTenant
+where
+    Arc<AppState>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let CurrentBrand(brand) = CurrentBrand::from_request_parts(parts, state).await?;
+        Ok(CurrentTenant(brand.tenant))
+    }
+}
@@ -1,11 +1,31 @@
+use async_trait::async_trait;
 use reqwest::Client;
+use rust_decimal::Decimal;
+use std::sync::{Arc, RwLock};
 
-use crate::modules::swap::schema::{TrocadorCurrency, TrocadorProvider, TrocadorTradeResponse};
+use crate::modules::swap::schema::{RatesQuery, TrocadorCurrency, TrocadorProvider, TrocadorTradeResponse};
+use crate::services::metrics::metrics;
+use crate::services::money::parse_amount;
+use crate::services::request_id::{current_request_id, REQUEST_ID_HEADER};
+use crate::services::schema_drift;
+use crate::services::swap_provider::{AggregatorQuote, AggregatorQuotes, SwapProviderClient};
 
 /// Trocador API client
-/// Handles all communication with Trocador.app API
+/// Handles all communication with Trocador.app API. Clones share one
+/// connection pool, so a single client is built at startup and handed out.
+#[derive(Clone)]
 pub struct TrocadorClient {
     client: Client,
+    endpoint: Arc<RwLock<TrocadorEndpoint>>, // Shared by clones, so a rotated key reaches every holder
+    webhook_url: Option<String>, // Passed on new_trade so Trocador reports status changes
+    markup: Option<f64>,         // Partner markup percent on new_rate and new_trade
+}
+
+/// Default API root; PUT /admin/provider-credentials/trocador can point elsewhere
+pub const TROCADOR_BASE_URL: &str = "https://api.trocador.app";
+
+#[derive(Clone)]
+struct TrocadorEndpoint {
     api_key: String,
     base_url: String,
 }
@@ -29,23 +49,81 @@ impl std::fmt::Display for TrocadorError {
 
 impl std::error::Error for TrocadorError {}
 
+/// The side of a pair an amount is given for. Trocador takes `To` amounts as
+/// payments: fixed-rate, with each provider saying how much to send.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TradeAmount {
+    From(Decimal),
+    To(Decimal),
+}
+
+impl TradeAmount {
+    fn params(self) -> Vec<(&'static str, String)> {
+        match self {
+            TradeAmount::From(amount) => vec![("amount_from", amount.to_string())],
+            TradeAmount::To(amount) => vec![("amount_to", amount.to_string()), ("payment", "True".to_string())],
+        }
+    }
+}
+
 impl TrocadorClient {
     pub fn new(api_key: String) -> Self {
         Self {
             client: Client::new(),
-            api_key,
-            base_url: "https://api.trocador.app".to_string(),
+            endpoint: Arc::new(RwLock::new(TrocadorEndpoint { api_key, base_url: TROCADOR_BASE_URL.to_string() })),
+            webhook_url: None,
+            markup: None,
         }
     }
 
+    /// Client for TROCADOR_API_KEY, reporting trades to TROCADOR_WEBHOOK_URL
+    /// when set; `None` without a key
+    pub fn from_env() -> Option<Self> {
+        let api_key = std::env::var("TROCADOR_API_KEY").ok().filter(|k| !k.is_empty())?;
+        let webhook_url = std::env::var("TROCADOR_WEBHOOK_URL").ok().filter(|u| !u.is_empty());
+        Some(Self::new(api_key).with_webhook_url(webhook_url))
+    }
+
+    /// Switch this client and every clone of it to a new key, and API root
+    /// when given; calls already sent finish with the old ones
+    pub fn rotate(&self, api_key: String, base_url: Option<String>) {
+        let mut endpoint = self.endpoint.write().unwrap_or_else(|e| e.into_inner());
+        endpoint.api_key = api_key;
+        endpoint.base_url = base_url.unwrap_or_else(|| TROCADOR_BASE_URL.to_string());
+    }
+
+    fn endpoint(&self) -> TrocadorEndpoint {
+        self.endpoint.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// GET with the API key, carrying the X-Request-Id of the request it is made for
+    fn get(&self, url: &str, endpoint: &TrocadorEndpoint) -> reqwest::RequestBuilder {
+        let request = self.client.get(url).header("API-Key", &endpoint.api_key);
+        match current_request_id() {
+            Some(id) => request.header(REQUEST_ID_HEADER, id),
+            None => request,
+        }
+    }
+
+    pub fn with_webhook_url(mut self, webhook_url: Option<String>) -> Self {
+        self.webhook_url = webhook_url;
+        self
+    }
+
+    /// Markup percent added to quotes and trades; `None` uses the account default
+    pub fn with_markup(mut self, markup: Option<f64>) -> Self {
+        self.markup = markup;
+        self
+    }
+
     /// Fetch all currencies from Trocador /coins endpoint
     pub async fn get_currencies(&self) -> Result<Vec<TrocadorCurrency>, TrocadorError> {
-        let url = format!("{}/coins", self.base_url);
+        let _timer = metrics().trocador_request_seconds.start_timer("coins");
+        let endpoint = self.endpoint();
+        let url = format!("{}/coins", endpoint.base_url);
 
         let response = self
-            .client
-            .get(&url)
-            .header("API-Key", &self.api_key)
+            .get(&url, &endpoint)
             .send()
             .await
             .map_err(|e| TrocadorError::HttpError(e.to_string()))?;
@@ -57,22 +135,22 @@ impl TrocadorClient {
             )));
         }
 
-        let currencies: Vec<TrocadorCurrency> = response
+        let items: Vec<serde_json::Value> = response
             .json()
             .await
             .map_err(|e| TrocadorError::ParseError(e.to_string()))?;
 
-        Ok(currencies)
+        schema_drift::parse_list::<TrocadorCurrency>("coins", items).map_err(TrocadorError::ParseError)
     }
 
     /// Fetch all providers from Trocador /exchanges endpoint
     pub async fn get_providers(&self) -> Result<Vec<TrocadorProvider>, TrocadorError> {
-        let url = format!("{}/exchanges", self.base_url);
+        let _timer = metrics().trocador_request_seconds.start_timer("exchanges");
+        let endpoint = self.endpoint();
+        let url = format!("{}/exchanges", endpoint.base_url);
 
         let response = self
-            .client
-            .get(&url)
-            .header("API-Key", &self.api_key)
+            .get(&url, &endpoint)
             .send()
             .await
             .map_err(|e| TrocadorError::HttpError(e.to_string()))?;
@@ -94,10 +172,12 @@ impl TrocadorClient {
             .get("list")
             .ok_or_else(|| TrocadorError::ParseError("Missing 'list' key".to_string()))?;
 
-        let providers: Vec<TrocadorProvider> = serde_json::from_value(providers_array.clone())
-            .map_err(|e| TrocadorError::ParseError(e.to_string()))?;
+        let items = providers_array
+            .as_array()
+            .cloned()
+            .ok_or_else(|| TrocadorError::ParseError("'list' is not an array".to_string()))?;
 
-        Ok(providers)
+        schema_drift::parse_list::<TrocadorProvider>("exchanges", items).map_err(TrocadorError::ParseError)
     }
 
     /// Get rates from Trocador (new_rate)
@@ -107,23 +187,27 @@ impl TrocadorClient {
         network_from: &str,
         ticker_to: &str,
         network_to: &str,
-        amount: f64,
+        amount: TradeAmount,
     ) -> Result<crate::modules::swap::schema::TrocadorRatesResponse, TrocadorError> {
-        let url = format!("{}/new_rate", self.base_url);
+        let _timer = metrics().trocador_request_seconds.start_timer("new_rate");
+        let endpoint = self.endpoint();
+        let url = format!("{}/new_rate", endpoint.base_url);
         
-        let params = [
+        let mut params = vec![
             ("ticker_from", ticker_from.to_string()),
             ("network_from", network_from.to_string()),
             ("ticker_to", ticker_to.to_string()),
             ("network_to", network_to.to_string()),
-            ("amount_from", amount.to_string()),
             ("best_only", "false".to_string()),
         ];
+        params.extend(amount.params());
+
+        if let Some(markup) = self.markup {
+            params.push(("markup", markup.to_string()));
+        }
 
         let response = self
-            .client
-            .get(&url)
-            .header("API-Key", &self.api_key)
+            .get(&url, &endpoint)
             .query(&params)
             .send()
             .await
@@ -137,15 +221,27 @@ impl TrocadorClient {
             )));
         }
 
-        let rates_response: crate::modules::swap::schema::TrocadorRatesResponse = response
+        let mut body: serde_json::Value = response
             .json()
             .await
             .map_err(|e| TrocadorError::ParseError(e.to_string()))?;
 
+        // Quotes are parsed one by one so a single malformed quote doesn't hide the rest
+        let quotes = match body.pointer_mut("/quotes/quotes") {
+            Some(serde_json::Value::Array(items)) => std::mem::take(items),
+            _ => Vec::new(), // Missing or mistyped; left in place for parse_one to report
+        };
+
+        let mut rates_response: crate::modules::swap::schema::TrocadorRatesResponse =
+            schema_drift::parse_one("new_rate", body).map_err(TrocadorError::ParseError)?;
+        rates_response.quotes.quotes =
+            schema_drift::parse_list("new_rate.quotes", quotes).map_err(TrocadorError::ParseError)?;
+
         Ok(rates_response)
     }
 
     /// Create a new trade on Trocador (new_trade)
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_trade(
         &self,
         trade_id: Option<&str>,
@@ -153,24 +249,26 @@ impl TrocadorClient {
         network_from: &str,
         ticker_to: &str,
         network_to: &str,
-        amount: f64,
+        amount: TradeAmount,
         address: &str,
         refund: Option<&str>,
         provider: &str,
         fixed: bool,
-    ) -> Result<TrocadorTradeResponse, TrocadorError> {
-        let url = format!("{}/new_trade", self.base_url);
+    ) -> Result<(TrocadorTradeResponse, String), TrocadorError> {
+        let _timer = metrics().trocador_request_seconds.start_timer("new_trade");
+        let endpoint = self.endpoint();
+        let url = format!("{}/new_trade", endpoint.base_url);
 
         let mut params = vec![
             ("ticker_from", ticker_from.to_string()),
             ("network_from", network_from.to_string()),
             ("ticker_to", ticker_to.to_string()),
             ("network_to", network_to.to_string()),
-            ("amount_from", amount.to_string()),
             ("address", address.to_string()),
             ("provider", provider.to_string()),
             ("fixed", fixed.to_string()),
         ];
+        params.extend(amount.params());
 
         if let Some(id) = trade_id {
             params.push(("id", id.to_string()));
@@ -180,10 +278,16 @@ impl TrocadorClient {
             params.push(("refund", r.to_string()));
         }
 
+        if let Some(webhook) = &self.webhook_url {
+            params.push(("webhook", webhook.clone()));
+        }
+
+        if let Some(markup) = self.markup {
+            params.push(("markup", markup.to_string()));
+        }
+
         let response = self
-            .client
-            .get(&url)
-            .header("API-Key", &self.api_key)
+            .get(&url, &endpoint)
             .query(&params)
             .send()
             .await
@@ -197,24 +301,19 @@ impl TrocadorClient {
             )));
         }
 
-        let trade_response: TrocadorTradeResponse = response
-            .json()
-            .await
-            .map_err(|e| TrocadorError::ParseError(e.to_string()))?;
-
-        Ok(trade_response)
+        parse_trade_response("new_trade", response).await
     }
 
     /// Get trade status from Trocador (trade)
-    pub async fn get_trade_status(&self, trade_id: &str) -> Result<TrocadorTradeResponse, TrocadorError> {
-        let url = format!("{}/trade", self.base_url);
+    pub async fn get_trade_status(&self, trade_id: &str) -> Result<(TrocadorTradeResponse, String), TrocadorError> {
+        let _timer = metrics().trocador_request_seconds.start_timer("trade");
+        let endpoint = self.endpoint();
+        let url = format!("{}/trade", endpoint.base_url);
         
         let params = [("id", trade_id.to_string())];
 
         let response = self
-            .client
-            .get(&url)
-            .header("API-Key", &self.api_key)
+            .get(&url, &endpoint)
             .query(&params)
             .send()
             .await
@@ -228,12 +327,7 @@ impl TrocadorClient {
             )));
         }
 
-        let trade_response: TrocadorTradeResponse = response
-            .json()
-            .await
-            .map_err(|e| TrocadorError::ParseError(e.to_string()))?;
-
-        Ok(trade_response)
+        parse_trade_response("trade", response).await
     }
 
     /// Validate address for a specific coin and network
@@ -243,7 +337,9 @@ impl TrocadorClient {
         network: &str,
         address: &str,
     ) -> Result<bool, TrocadorError> {
-        let url = format!("{}/validateaddress", self.base_url);
+        let _timer = metrics().trocador_request_seconds.start_timer("validateaddress");
+        let endpoint = self.endpoint();
+        let url = format!("{}/validateaddress", endpoint.base_url);
         
         let params = [
             ("ticker", ticker.to_string()),
@@ -252,9 +348,7 @@ impl TrocadorClient {
         ];
 
         let response = self
-            .client
-            .get(&url)
-            .header("API-Key", &self.api_key)
+            .get(&url, &endpoint)
             .query(&params)
             .send()
             .await
@@ -282,3 +376,57 @@ impl TrocadorClient {
         Ok(is_valid)
     }
 }
+
+/// Parsed trade plus the raw body, kept for dispute resolution
+async fn parse_trade_response(
+    endpoint: &'static str,
+    response: reqwest::Response,
+) -> Result<(TrocadorTradeResponse, String), TrocadorError> {
+    let raw = response
+        .text()
+        .await
+        .map_err(|e| TrocadorError::HttpError(e.to_string()))?;
+
+    let value: serde_json::Value =
+        serde_json::from_str(&raw).map_err(|e| TrocadorError::ParseError(e.to_string()))?;
+    let trade_response: TrocadorTradeResponse =
+        schema_drift::parse_one(endpoint, value).map_err(TrocadorError::ParseError)?;
+
+    Ok((trade_response, raw))
+}
+
+#[async_trait]
+impl SwapProviderClient for TrocadorClient {
+    fn aggregator(&self) -> &'static str {
+        "trocador"
+    }
+
+    async fn get_quotes(&self, query: &RatesQuery) -> Result<AggregatorQuotes, String> {
+        let amount = match query.amount_to {
+            Some(amount_to) => TradeAmount::To(amount_to),
+            None => TradeAmount::From(query.amount),
+        };
+        let rates = self
+            .get_rates(&query.from, &query.network_from, &query.to, &query.network_to, amount)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let quotes = rates
+            .quotes
+            .quotes
+            .into_iter()
+            .map(|quote| AggregatorQuote {
+                amount_to: parse_amount(&quote.amount_to).unwrap_or_default(),
+                amount_from: quote.amount_from.as_deref().and_then(parse_amount),
+                min_amount: quote.min_amount.unwrap_or_default(),
+                max_amount: quote.max_amount.unwrap_or_default(),
+                provider_fee: quote.waste.as_deref().and_then(parse_amount).unwrap_or_default(),
+                kyc_rating: quote.kycrating,
+                eta_minutes: quote.eta.map(|e| e as u32),
+                provider: quote.provider,
+            })
+            .collect();
+
+        Ok(AggregatorQuotes { trade_id: Some(rates.trade_id), quotes })
+    }
+}
@@ -0,0 +1,96 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{create_admin_token, TestContext};
+
+// Registers a brand with its own key so parallel tests don't share counts
+async fn register_brand(ctx: &TestContext) -> (String, String) {
+    let token = create_admin_token(ctx).await;
+    let suffix = &uuid::Uuid::new_v4().simple().to_string()[..8];
+    let slug = format!("test-{}", suffix);
+    let api_key = format!("test-key-{}", uuid::Uuid::new_v4().simple());
+
+    ctx.server
+        .put(&format!("/admin/brands/{}", slug))
+        .authorization_bearer(&token)
+        .json(&json!({ "name": "Partner", "support_email": "help@partner.example", "api_key": api_key }))
+        .await
+        .assert_status_ok();
+
+    (slug, api_key)
+}
+
+async fn delete_brand(ctx: &TestContext, slug: &str) {
+    sqlx::query("DELETE FROM brands WHERE slug = ?").bind(slug).execute(&ctx.db).await.unwrap();
+    sqlx::query("DELETE FROM api_usage_hourly WHERE brand = ?").bind(slug).execute(&ctx.db).await.unwrap();
+}
+
+#[tokio::test]
+async fn analytics_require_a_brand_api_key() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/account/analytics").await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+
+    let response = ctx.server.get("/account/analytics").add_header("x-api-key", "not-a-real-key").await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn analytics_window_is_bounded() {
+    let ctx = TestContext::new().await;
+    let (slug, api_key) = register_brand(&ctx).await;
+
+    for days in [0, 91] {
+        let response = ctx
+            .server
+            .get(&format!("/account/analytics?days={}", days))
+            .add_header("x-api-key", api_key.as_str())
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    delete_brand(&ctx, &slug).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn analytics_report_only_the_callers_usage_and_swaps() {
+    let ctx = TestContext::new().await;
+    let (slug, api_key) = register_brand(&ctx).await;
+
+    sqlx::query(
+        "INSERT INTO api_usage_hourly (brand, hour_start, endpoint, requests, client_errors, server_errors)
+         VALUES (?, UTC_TIMESTAMP() - INTERVAL 1 HOUR, 'GET /swap/rates', 40, 3, 1),
+                (?, UTC_TIMESTAMP() - INTERVAL 1 HOUR, 'POST /swap/create', 10, 2, 0),
+                ('some-other-brand', UTC_TIMESTAMP() - INTERVAL 1 HOUR, 'GET /swap/rates', 999, 0, 0)",
+    )
+    .bind(&slug)
+    .bind(&slug)
+    .execute(&ctx.db)
+    .await
+    .unwrap();
+
+    let response = ctx.server.get("/account/analytics").add_header("x-api-key", api_key.as_str()).await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+
+    assert_eq!(body["brand"], slug.as_str());
+    assert_eq!(body["days"], 30);
+    let endpoints = body["endpoints"].as_array().unwrap();
+    assert_eq!(endpoints.len(), 2);
+    assert_eq!(endpoints[0]["endpoint"], "GET /swap/rates");
+    assert_eq!(endpoints[0]["requests"], 40);
+    assert_eq!(endpoints[0]["error_rate"], 0.1);
+    assert_eq!(body["conversion"]["quote_requests"], 40);
+    assert_eq!(body["conversion"]["swaps_created"], 0);
+
+    sqlx::query("DELETE FROM api_usage_hourly WHERE brand = 'some-other-brand'")
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    delete_brand(&ctx, &slug).await;
+    ctx.cleanup().await;
+}
@@ -0,0 +1 @@
+mod analytics_test;
@@ -0,0 +1,111 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{create_admin_token, create_user_token, TestContext};
+
+// Each test registers formats on its own network name so parallel tests and
+// the seeded rows don't interfere.
+fn test_network() -> String {
+    format!("TestNet{}", &uuid::Uuid::new_v4().simple().to_string()[..8])
+}
+
+#[tokio::test]
+async fn address_format_endpoints_require_admin() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx
+        .server
+        .get("/admin/address-formats")
+        .authorization_bearer(&token)
+        .await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn invalid_address_regex_is_rejected() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+
+    let response = ctx
+        .server
+        .put(&format!("/admin/address-formats/testcoin/{}", test_network()))
+        .authorization_bearer(&token)
+        .json(&json!({ "address_regex": "T[0-9" }))
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn registered_format_drives_validation() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let network = test_network();
+    let path = format!("/admin/address-formats/TESTCOIN/{}", network);
+
+    let response = ctx
+        .server
+        .put(&path)
+        .authorization_bearer(&token)
+        .json(&json!({
+            "address_regex": "T[0-9]{8}",
+            "memo_name": "Tag",
+            "memo_format": "integer",
+            "memo_max_value": 999,
+            "uri_scheme": "testcoin",
+            "uri_memo_param": "tag"
+        }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["ticker"], "testcoin");
+    assert_eq!(body["checksum"], "none");
+    assert_eq!(body["memo_format"], "integer");
+
+    let response = ctx.server.get("/admin/address-formats").authorization_bearer(&token).await;
+    response.assert_status_ok();
+    let formats: Vec<Value> = response.json();
+    assert!(formats.iter().any(|f| f["network"] == network.as_str()));
+
+    // Rejected locally, without asking the provider
+    let response = ctx
+        .server
+        .post("/swap/validate-address")
+        .json(&json!({ "ticker": "testcoin", "network": network, "address": "X12345678" }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["valid"], false);
+
+    let response = ctx.server.delete(&path).authorization_bearer(&token).await;
+    response.assert_status_ok();
+    let response = ctx.server.delete(&path).authorization_bearer(&token).await;
+    response.assert_status(StatusCode::NOT_FOUND);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn seeded_erc20_format_checks_eip55_checksum() {
+    let ctx = TestContext::new().await;
+
+    // Valid EIP-55 address with the case of the final letter flipped
+    let response = ctx
+        .server
+        .post("/swap/validate-address")
+        .json(&json!({
+            "ticker": "usdt",
+            "network": "ERC20",
+            "address": "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"
+        }))
+        .await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["valid"], false);
+    ctx.cleanup().await;
+}
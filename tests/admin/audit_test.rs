@@ -0,0 +1,142 @@
+use axum::http::{Method, StatusCode};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use exchange_shared::services::audit::{is_audited, payload_hash, AuditLog, AuditQuery, NewAuditEntry};
+
+use crate::common::{create_admin_token, TestContext};
+
+fn entry(actor: &str, action: &str, path: &str) -> NewAuditEntry {
+    NewAuditEntry {
+        actor: actor.to_string(),
+        ip: Some("203.0.113.7".to_string()),
+        action: action.to_string(),
+        path: path.to_string(),
+        status: 200,
+        payload_hash: payload_hash(b"{}"),
+    }
+}
+
+#[test]
+fn payload_hash_is_hex_sha256() {
+    assert_eq!(
+        payload_hash(b"abc").as_deref(),
+        Some("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+    );
+    assert_eq!(payload_hash(b""), None);
+}
+
+#[test]
+fn only_mutations_are_audited() {
+    assert!(is_audited(&Method::POST, "/swap/create"));
+    assert!(is_audited(&Method::PATCH, "/admin/providers/{id}"));
+    assert!(is_audited(&Method::DELETE, "/admin/fee-rules/{id}"));
+    assert!(!is_audited(&Method::GET, "/admin/audit"));
+    assert!(!is_audited(&Method::POST, "/swap/status/batch"));
+    assert!(!is_audited(&Method::POST, "/admin/routing-rules/dry-run"));
+}
+
+#[tokio::test]
+async fn entries_are_filtered_and_newest_first() {
+    let ctx = TestContext::new().await;
+    let audit = AuditLog::new(ctx.db.clone());
+    let actor = format!("user:audit-{}", uuid::Uuid::new_v4());
+
+    audit.record(&entry(&actor, "POST /swap/create", "/swap/create")).await.unwrap();
+    audit.record(&entry(&actor, "POST /swap/{id}/cancel", "/swap/abc/cancel")).await.unwrap();
+    audit.record(&entry("anonymous", "POST /swap/create", "/swap/create")).await.unwrap();
+
+    let mine = audit.list(&AuditQuery { actor: Some(actor.clone()), ..Default::default() }).await.unwrap();
+    assert_eq!(mine.len(), 2);
+    assert_eq!(mine[0].action, "POST /swap/{id}/cancel");
+    assert!(mine[0].id > mine[1].id);
+
+    let cancels = audit
+        .list(&AuditQuery { actor: Some(actor.clone()), path_prefix: Some("/swap/abc".to_string()), ..Default::default() })
+        .await
+        .unwrap();
+    assert_eq!(cancels.len(), 1);
+    assert_eq!(cancels[0].ip.as_deref(), Some("203.0.113.7"));
+
+    let older = audit
+        .list(&AuditQuery { actor: Some(actor), before_id: Some(mine[0].id), ..Default::default() })
+        .await
+        .unwrap();
+    assert_eq!(older.len(), 1);
+    assert_eq!(older[0].id, mine[1].id);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn audit_log_is_append_only() {
+    let ctx = TestContext::new().await;
+    let actor = format!("user:audit-{}", uuid::Uuid::new_v4());
+    AuditLog::new(ctx.db.clone()).record(&entry(&actor, "POST /swap/create", "/swap/create")).await.unwrap();
+
+    let update = sqlx::query("UPDATE audit_log SET status = 500 WHERE actor = ?").bind(&actor).execute(&ctx.db).await;
+    let delete = sqlx::query("DELETE FROM audit_log WHERE actor = ?").bind(&actor).execute(&ctx.db).await;
+
+    assert!(update.is_err());
+    assert!(delete.is_err());
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn audit_requires_admin() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/admin/audit").await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn admin_changes_are_recorded() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+
+    // Rejected changes are recorded too, with their status
+    ctx.server
+        .patch("/admin/providers/no-such-provider")
+        .authorization_bearer(&token)
+        .json(&json!({ "is_active": false }))
+        .await;
+
+    let mut entries = Vec::new();
+    for _ in 0..50 {
+        let response = ctx
+            .server
+            .get("/admin/audit?action=PATCH%20/admin/providers/%7Bid%7D&path_prefix=/admin/providers/no-such-provider")
+            .authorization_bearer(&token)
+            .await;
+        response.assert_status_ok();
+        entries = response.json::<Vec<Value>>();
+        if !entries.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    assert!(!entries.is_empty());
+    assert!(entries[0]["actor"].as_str().unwrap().starts_with("user:"));
+    assert_eq!(entries[0]["payload_hash"].as_str().unwrap().len(), 64);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn inverted_window_is_rejected() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+
+    let response = ctx
+        .server
+        .get("/admin/audit?since=2026-03-10T00:00:00Z&until=2026-03-09T00:00:00Z")
+        .authorization_bearer(&token)
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    ctx.cleanup().await;
+}
@@ -0,0 +1,365 @@
+use axum::{http::HeaderMap, http::StatusCode, routing::post, Router};
+use exchange_shared::config::environment::BrandWebhookConfig;
+use exchange_shared::modules::brand::schema::{SetWebhookRequest, StartRotationRequest};
+use exchange_shared::modules::brand::webhooks::{
+    check_target, enqueue_swap_status, is_public_address, signature_headers, BrandWebhookSender, PublicResolver,
+    PREVIOUS_SIGNATURE_HEADER, SIGNATURE_HEADER,
+};
+use reqwest::dns::{Name, Resolve};
+use std::str::FromStr;
+use exchange_shared::services::encryption::SecretCipher;
+use exchange_shared::services::security::verify_hmac_sha256;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+use crate::common::{create_admin_token, delete_swap, insert_swap, TestContext};
+
+// Base64 of bytes 0..32
+const KEY: &str = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=";
+
+type Received = Arc<Mutex<Vec<(HeaderMap, String)>>>;
+
+async fn setup() -> TestContext {
+    std::env::set_var("BRAND_WEBHOOKS_KEY", KEY);
+    // The fake receivers listen on 127.0.0.1 over http
+    std::env::set_var("BRAND_WEBHOOKS_ALLOW_PRIVATE", "true");
+    TestContext::new().await
+}
+
+/// A brand with its own slug and API key
+async fn create_brand(ctx: &TestContext) -> (String, String) {
+    let suffix = &uuid::Uuid::new_v4().simple().to_string()[..8];
+    let (slug, api_key) = (format!("hook-{}", suffix), format!("hook-key-{}", uuid::Uuid::new_v4().simple()));
+    let admin_token = create_admin_token(ctx).await;
+    ctx.server
+        .put(&format!("/admin/brands/{}", slug))
+        .authorization_bearer(&admin_token)
+        .json(&json!({ "name": "Hook Partner", "support_email": "help@partner.example", "api_key": api_key }))
+        .await
+        .assert_status_ok();
+    (slug, api_key)
+}
+
+async fn delete_brand(ctx: &TestContext, slug: &str) {
+    for table in ["brand_webhook_deliveries", "brand_webhook_secrets"] {
+        sqlx::query(&format!("DELETE FROM {} WHERE brand = ?", table))
+            .bind(slug)
+            .execute(&ctx.db)
+            .await
+            .ok();
+    }
+    sqlx::query("DELETE FROM brands WHERE slug = ?").bind(slug).execute(&ctx.db).await.ok();
+}
+
+/// Local receiver answering every POST with `status` and keeping what it got
+async fn fake_receiver(status: StatusCode) -> (String, Received) {
+    let received: Received = Arc::default();
+    let sink = received.clone();
+    let app = Router::new().route(
+        "/hook",
+        post(move |headers: HeaderMap, body: String| async move {
+            sink.lock().unwrap().push((headers, body));
+            status
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (url, received)
+}
+
+/// A swap of `slug` whose status change is queued for its webhook
+async fn queue_status_change(ctx: &TestContext, slug: &str) -> String {
+    let swap_id = insert_swap(ctx, "waiting", None).await;
+    sqlx::query("UPDATE swaps SET brand = ? WHERE id = ?")
+        .bind(slug)
+        .bind(&swap_id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    enqueue_swap_status(&ctx.db, slug, &swap_id, &json!({ "swap_id": swap_id, "status": "confirming" })).await;
+    swap_id
+}
+
+fn sender(ctx: &TestContext) -> BrandWebhookSender {
+    let config = BrandWebhookConfig { allow_private_targets: true, ..BrandWebhookConfig::default() };
+    BrandWebhookSender::from_config(ctx.db.clone(), SecretCipher::from_base64(KEY).unwrap(), &config)
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+// =============================================================================
+// UNIT TESTS - SIGNING AND INPUT
+// =============================================================================
+
+#[test]
+fn test_every_signing_secret_signs_the_body() {
+    let body = br#"{"swap_id":"abc","status":"completed"}"#;
+
+    let headers = signature_headers(&["new-secret"], body);
+    assert_eq!(headers.len(), 1);
+    assert_eq!(headers[0].0, SIGNATURE_HEADER);
+    assert!(verify_hmac_sha256("new-secret", body, &headers[0].1));
+
+    let headers = signature_headers(&["new-secret", "old-secret"], body);
+    assert_eq!(headers.len(), 2);
+    assert!(verify_hmac_sha256("new-secret", body, &headers[0].1));
+    assert_eq!(headers[1].0, PREVIOUS_SIGNATURE_HEADER);
+    assert!(verify_hmac_sha256("old-secret", body, &headers[1].1));
+
+    assert!(signature_headers(&[], body).is_empty());
+}
+
+#[test]
+fn test_webhook_url_and_grace_period_are_checked() {
+    let url = |u: &str| SetWebhookRequest { url: Some(u.to_string()) }.url(false);
+    assert_eq!(url(" https://partner.example/hooks ").unwrap().as_deref(), Some("https://partner.example/hooks"));
+    assert_eq!(url("").unwrap(), None);
+    assert_eq!(SetWebhookRequest { url: None }.url(false).unwrap(), None);
+    assert!(url("ftp://partner.example/hooks").is_err());
+    assert!(url("partner.example/hooks").is_err());
+    assert!(url(&format!("https://partner.example/{}", "a".repeat(600))).is_err());
+
+    let grace = |h: Option<u32>| StartRotationRequest { grace_hours: h }.grace_hours();
+    assert_eq!(grace(None).unwrap(), 72);
+    assert_eq!(grace(Some(1)).unwrap(), 1);
+    assert!(grace(Some(0)).is_err());
+    assert!(grace(Some(721)).is_err());
+}
+
+#[test]
+fn test_webhooks_only_target_public_https_hosts() {
+    for url in [
+        "http://partner.example/hooks",
+        "https://localhost/hooks",
+        "https://api.localhost./hooks",
+        "https://127.0.0.1/hooks",
+        "https://2130706433/hooks", // 127.0.0.1 in decimal
+        "https://10.1.2.3/hooks",
+        "https://192.168.0.10/hooks",
+        "https://169.254.169.254/latest/meta-data",
+        "https://100.64.0.1/hooks",
+        "https://[::1]/hooks",
+        "https://[fd00::1]/hooks",
+        "https://[fe80::1]/hooks",
+        "https://[::ffff:10.0.0.1]/hooks",
+    ] {
+        assert!(check_target(url, false).is_err(), "{} should be refused", url);
+    }
+    assert!(check_target("https://partner.example/hooks", false).is_ok());
+    assert!(check_target("https://93.184.215.14/hooks", false).is_ok());
+
+    // Local development may point at a receiver on this machine
+    assert!(check_target("http://127.0.0.1:8080/hooks", true).is_ok());
+    assert!(check_target("ftp://127.0.0.1/hooks", true).is_err());
+
+    assert!(is_public_address("1.1.1.1".parse().unwrap()));
+    assert!(is_public_address("2606:4700::1111".parse().unwrap()));
+    assert!(!is_public_address("0.0.0.0".parse().unwrap()));
+    assert!(!is_public_address("172.16.5.4".parse().unwrap()));
+}
+
+#[tokio::test]
+async fn test_names_resolving_to_internal_addresses_are_not_resolved() {
+    let resolve = |name: &str| PublicResolver.resolve(Name::from_str(name).unwrap());
+    assert!(resolve("localhost").await.is_err());
+
+    let addrs: Vec<_> = resolve("1.1.1.1").await.expect("a public address").collect();
+    assert!(addrs.iter().all(|a| is_public_address(a.ip())));
+}
+
+// =============================================================================
+// INTEGRATION TESTS - SECRET ROTATION
+// =============================================================================
+
+#[tokio::test]
+async fn test_webhook_settings_need_a_brand_api_key() {
+    let ctx = setup().await;
+
+    let response = ctx.server.get("/brand/webhook").await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+
+    let response = ctx.server.get("/brand/webhook").add_header("x-api-key", "no-such-brand-key-0123456789abcdef").await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_rotation_signs_with_both_secrets_until_completed() {
+    let ctx = setup().await;
+    let (slug, api_key) = create_brand(&ctx).await;
+    let (url, received) = fake_receiver(StatusCode::OK).await;
+
+    // Rotating before there is a webhook has nothing to rotate
+    let response = ctx.server.post("/brand/webhook/rotation").add_header("x-api-key", &api_key).await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    // The first URL comes with the first secret, shown once and stored encrypted
+    let response = ctx
+        .server
+        .put("/brand/webhook")
+        .add_header("x-api-key", &api_key)
+        .json(&json!({ "url": url }))
+        .await;
+    response.assert_status_ok();
+    let json: Value = response.json();
+    let old_secret = json["secret"].as_str().expect("first secret").to_string();
+    assert_eq!(json["secrets"].as_array().unwrap().len(), 1);
+
+    let (stored,): (String,) = sqlx::query_as("SELECT secret_encrypted FROM brand_webhook_secrets WHERE brand = ?")
+        .bind(&slug)
+        .fetch_one(&ctx.db)
+        .await
+        .unwrap();
+    assert!(!stored.contains(&old_secret));
+
+    let response = ctx
+        .server
+        .put("/brand/webhook")
+        .add_header("x-api-key", &api_key)
+        .json(&json!({ "url": url }))
+        .await;
+    response.assert_status_ok();
+    assert!(response.json::<Value>().get("secret").is_none(), "the secret is only shown when created");
+
+    // Start a rotation: the new secret is current, the old one retires later
+    let response = ctx
+        .server
+        .post("/brand/webhook/rotation")
+        .add_header("x-api-key", &api_key)
+        .json(&json!({ "grace_hours": 24 }))
+        .await;
+    response.assert_status_ok();
+    let json: Value = response.json();
+    let new_secret = json["secret"].as_str().expect("new secret").to_string();
+    assert_ne!(new_secret, old_secret);
+    let secrets = json["secrets"].as_array().unwrap();
+    assert_eq!(secrets.len(), 2);
+    assert!(secrets[0]["retires_at"].is_null());
+    assert!(secrets[1]["retires_at"].is_string());
+    assert_eq!(secrets[1]["hint"], &old_secret[old_secret.len() - 4..]);
+
+    let response = ctx.server.post("/brand/webhook/rotation").add_header("x-api-key", &api_key).await;
+    response.assert_status(StatusCode::CONFLICT);
+
+    // During the rotation every delivery carries both signatures
+    let first_swap = queue_status_change(&ctx, &slug).await;
+    sender(&ctx).drain(100).await.unwrap();
+    {
+        let received = received.lock().unwrap();
+        let (headers, body) = received.iter().find(|(_, b)| b.contains(&first_swap)).expect("a delivery");
+        assert!(verify_hmac_sha256(&new_secret, body.as_bytes(), header(headers, SIGNATURE_HEADER).unwrap()));
+        assert!(verify_hmac_sha256(&old_secret, body.as_bytes(), header(headers, PREVIOUS_SIGNATURE_HEADER).unwrap()));
+    }
+
+    // Completing it retires the old secret at once
+    let response = ctx.server.post("/brand/webhook/rotation/complete").add_header("x-api-key", &api_key).await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Value>()["secrets"].as_array().unwrap().len(), 1);
+
+    let response = ctx.server.post("/brand/webhook/rotation/complete").add_header("x-api-key", &api_key).await;
+    response.assert_status(StatusCode::CONFLICT);
+
+    let second_swap = queue_status_change(&ctx, &slug).await;
+    sender(&ctx).drain(100).await.unwrap();
+    {
+        let received = received.lock().unwrap();
+        let (headers, body) = received.iter().find(|(_, b)| b.contains(&second_swap)).expect("a delivery");
+        assert!(verify_hmac_sha256(&new_secret, body.as_bytes(), header(headers, SIGNATURE_HEADER).unwrap()));
+        assert!(header(headers, PREVIOUS_SIGNATURE_HEADER).is_none());
+    }
+
+    delete_swap(&ctx, &first_swap).await;
+    delete_swap(&ctx, &second_swap).await;
+    delete_brand(&ctx, &slug).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_failed_delivery_backs_off_and_is_retried() {
+    let ctx = setup().await;
+    let (slug, api_key) = create_brand(&ctx).await;
+    let (url, received) = fake_receiver(StatusCode::INTERNAL_SERVER_ERROR).await;
+
+    ctx.server
+        .put("/brand/webhook")
+        .add_header("x-api-key", &api_key)
+        .json(&json!({ "url": url }))
+        .await
+        .assert_status_ok();
+    let swap_id = queue_status_change(&ctx, &slug).await;
+
+    sender(&ctx).drain(100).await.unwrap();
+    assert_eq!(received.lock().unwrap().len(), 1);
+
+    let (status, attempts, waiting, claimed): (String, i32, bool, Option<String>) = sqlx::query_as(
+        "SELECT status, attempts, next_attempt_at > NOW(), claimed_by FROM brand_webhook_deliveries WHERE brand = ?",
+    )
+    .bind(&slug)
+    .fetch_one(&ctx.db)
+    .await
+    .unwrap();
+    assert_eq!((status.as_str(), attempts, waiting, claimed), ("pending", 1, true, None));
+
+    // Not due yet, so the next drain leaves it alone
+    sender(&ctx).drain(100).await.unwrap();
+    assert_eq!(received.lock().unwrap().len(), 1);
+
+    delete_swap(&ctx, &swap_id).await;
+    delete_brand(&ctx, &slug).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_concurrent_drains_deliver_each_row_once() {
+    let ctx = setup().await;
+    let (slug, api_key) = create_brand(&ctx).await;
+    let (url, received) = fake_receiver(StatusCode::OK).await;
+
+    ctx.server
+        .put("/brand/webhook")
+        .add_header("x-api-key", &api_key)
+        .json(&json!({ "url": url }))
+        .await
+        .assert_status_ok();
+    let mut swaps = Vec::new();
+    for _ in 0..5 {
+        swaps.push(queue_status_change(&ctx, &slug).await);
+    }
+
+    let (a, b) = (sender(&ctx), sender(&ctx));
+    let (first, second) = tokio::join!(a.drain(100), b.drain(100));
+    first.unwrap();
+    second.unwrap();
+
+    // Other tests drain the same queue; wait out any delivery they claimed
+    for _ in 0..50 {
+        let (pending,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM brand_webhook_deliveries WHERE brand = ? AND status <> 'sent'",
+        )
+        .bind(&slug)
+        .fetch_one(&ctx.db)
+        .await
+        .unwrap();
+        if pending == 0 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+
+    {
+        let received = received.lock().unwrap();
+        for swap_id in &swaps {
+            assert_eq!(received.iter().filter(|(_, b)| b.contains(swap_id.as_str())).count(), 1);
+        }
+    }
+
+    for swap_id in &swaps {
+        delete_swap(&ctx, swap_id).await;
+    }
+    delete_brand(&ctx, &slug).await;
+    ctx.cleanup().await;
+}
@@ -0,0 +1,145 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{create_admin_token, create_user_token, TestContext};
+
+// Each test registers its own brand slug, domain and key so parallel tests
+// don't match each other's brands.
+fn test_brand() -> (String, String, String) {
+    let suffix = &uuid::Uuid::new_v4().simple().to_string()[..8];
+    (
+        format!("test-{}", suffix),
+        format!("swap.{}.example.com", suffix),
+        format!("test-key-{}", uuid::Uuid::new_v4().simple()),
+    )
+}
+
+#[tokio::test]
+async fn brand_endpoints_require_admin() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx.server.get("/admin/brands").authorization_bearer(&token).await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn invalid_brands_are_rejected() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let (slug, _, _) = test_brand();
+    let valid = json!({ "name": "Partner", "support_email": "help@partner.example" });
+
+    let cases = [
+        ("default".to_string(), valid.clone()),
+        ("Not_A_Slug".to_string(), valid.clone()),
+        (slug.clone(), json!({ "name": "Partner", "support_email": "help@partner.example", "markup_percent": 5.0 })),
+        (slug.clone(), json!({ "name": "Partner", "support_email": "help@partner.example", "domains": ["https://partner.example"] })),
+        (slug.clone(), json!({ "name": "Partner", "support_email": "help@partner.example", "api_key": "short" })),
+    ];
+    for (slug, body) in cases {
+        let response = ctx
+            .server
+            .put(&format!("/admin/brands/{}", slug))
+            .authorization_bearer(&token)
+            .json(&body)
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn registered_brand_is_applied_by_api_key_and_domain() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let (slug, domain, api_key) = test_brand();
+    let path = format!("/admin/brands/{}", slug);
+
+    let response = ctx
+        .server
+        .put(&path)
+        .authorization_bearer(&token)
+        .json(&json!({
+            "name": "Partner Swap",
+            "support_email": "help@partner.example",
+            "domains": [domain],
+            "markup_percent": 1.0,
+            "enabled_providers": ["ChangeNOW"],
+            "allowed_pairs": [{ "from": "btc", "to": "*" }],
+            "api_key": api_key
+        }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["slug"], slug.as_str());
+    assert_eq!(body["has_api_key"], true);
+    assert!(body.get("api_key_hash").is_none(), "key hash must not be served");
+
+    // Matched by API key, then by host; anything else gets the deployment's brand
+    let response = ctx.server.get("/brand").add_header("x-api-key", api_key.as_str()).await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["name"], "Partner Swap");
+    assert_eq!(body["support_email"], "help@partner.example");
+
+    let response = ctx.server.get("/brand").add_header("host", format!("{}:443", domain)).await;
+    let body: Value = response.json();
+    assert_eq!(body["name"], "Partner Swap");
+
+    let response = ctx.server.get("/brand").await;
+    let body: Value = response.json();
+    assert_ne!(body["name"], "Partner Swap");
+
+    // Pairs and providers outside the brand are refused before reaching Trocador
+    let swap = |to: &str, provider: &str| {
+        json!({
+            "from": if to == "btc" { "xmr" } else { "btc" },
+            "network_from": "Mainnet",
+            "to": to,
+            "network_to": "Mainnet",
+            "amount": 0.01,
+            "provider": provider,
+            "recipient_address": "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"
+        })
+    };
+
+    let response = ctx
+        .server
+        .post("/swap/create")
+        .add_header("x-api-key", api_key.as_str())
+        .json(&swap("btc", "ChangeNOW"))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "PAIR_NOT_ALLOWED");
+
+    let response = ctx
+        .server
+        .post("/swap/create")
+        .add_header("x-api-key", api_key.as_str())
+        .json(&swap("xmr", "FixedFloat"))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "PROVIDER_NOT_ALLOWED");
+
+    let response = ctx
+        .server
+        .get("/swap/rates?from=xmr&network_from=Mainnet&to=btc&network_to=Mainnet&amount=1")
+        .add_header("x-api-key", api_key.as_str())
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "PAIR_NOT_ALLOWED");
+
+    let response = ctx.server.delete(&path).authorization_bearer(&token).await;
+    response.assert_status_ok();
+    let response = ctx.server.delete(&path).authorization_bearer(&token).await;
+    response.assert_status(StatusCode::NOT_FOUND);
+
+    ctx.cleanup().await;
+}
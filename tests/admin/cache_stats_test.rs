@@ -0,0 +1,46 @@
+use axum::http::StatusCode;
+use serde_json::Value;
+
+use crate::common::{create_admin_token, TestContext};
+
+#[tokio::test]
+async fn cache_stats_requires_authentication() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/admin/cache/stats").await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn cache_stats_reports_currency_prefix_after_lookups() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+
+    // Two listings: at least one cache lookup under "currencies:"
+    ctx.server.get("/swap/currencies").await.assert_status_ok();
+    ctx.server.get("/swap/currencies").await.assert_status_ok();
+
+    let response = ctx
+        .server
+        .get("/admin/cache/stats")
+        .authorization_bearer(&token)
+        .await;
+    response.assert_status_ok();
+
+    let body: Value = response.json();
+    let windows = body["windows"].as_array().unwrap();
+    assert_eq!(windows.len(), 4);
+    assert_eq!(windows[0]["window_minutes"], 1);
+
+    let last_hour = windows.last().unwrap()["prefixes"].as_array().unwrap();
+    let currencies = last_hour
+        .iter()
+        .find(|p| p["prefix"] == "currencies:")
+        .expect("currencies: prefix should be tracked");
+
+    let lookups = currencies["hits"].as_u64().unwrap() + currencies["misses"].as_u64().unwrap();
+    assert!(lookups >= 2, "Expected at least 2 lookups, got {}", lookups);
+
+    ctx.cleanup().await;
+}
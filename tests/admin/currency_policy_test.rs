@@ -0,0 +1,162 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{create_admin_token, delete_currency, insert_currency, unique_symbol, TestContext};
+
+const XMR_ADDRESS: &str =
+    "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A";
+
+#[tokio::test]
+async fn update_policy_requires_admin() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx
+        .server
+        .patch("/admin/currencies/1/policy")
+        .json(&json!({ "requires_refund_address": true }))
+        .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn refund_address_policy_is_listed_and_enforced() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let symbol = unique_symbol();
+    let id = insert_currency(&ctx, &symbol).await;
+
+    let response = ctx
+        .server
+        .patch(&format!("/admin/currencies/{}/policy", id))
+        .authorization_bearer(&token)
+        .json(&json!({ "requires_refund_address": true }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["requires_refund_address"], true);
+
+    let listing: Vec<Value> = ctx
+        .server
+        .get(&format!("/swap/currencies?ticker={}", symbol))
+        .await
+        .json();
+    assert_eq!(listing[0]["refund_address_required"], true);
+
+    let response = ctx
+        .server
+        .post("/swap/create")
+        .json(&json!({
+            "from": symbol,
+            "network_from": "Mainnet",
+            "to": "xmr",
+            "network_to": "Mainnet",
+            "amount": 1.0,
+            "provider": "changenow",
+            "recipient_address": XMR_ADDRESS
+        }))
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "REFUND_ADDRESS_REQUIRED");
+
+    delete_currency(&ctx, id).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn disabled_currency_stays_off_and_refuses_swaps() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let symbol = unique_symbol();
+    let id = insert_currency(&ctx, &symbol).await;
+
+    let response = ctx
+        .server
+        .patch(&format!("/admin/currencies/{}/policy", id))
+        .authorization_bearer(&token)
+        .json(&json!({ "is_active": false }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["is_active"], false);
+
+    let (admin_disabled,): (bool,) = sqlx::query_as("SELECT admin_disabled FROM currencies WHERE id = ?")
+        .bind(id)
+        .fetch_one(&ctx.db)
+        .await
+        .unwrap();
+    assert!(admin_disabled, "the next sync must not switch it back on");
+
+    let response = ctx
+        .server
+        .post("/swap/create")
+        .json(&json!({
+            "from": symbol,
+            "network_from": "Mainnet",
+            "to": "xmr",
+            "network_to": "Mainnet",
+            "amount": 1.0,
+            "provider": "changenow",
+            "recipient_address": XMR_ADDRESS
+        }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "CURRENCY_DELISTED");
+
+    delete_currency(&ctx, id).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn update_policy_for_unknown_currency_returns_not_found() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+
+    let response = ctx
+        .server
+        .patch("/admin/currencies/999999999/policy")
+        .authorization_bearer(&token)
+        .json(&json!({ "requires_refund_address": true }))
+        .await;
+
+    response.assert_status(StatusCode::NOT_FOUND);
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn missing_extra_id_is_rejected_with_its_name() {
+    let ctx = TestContext::new().await;
+    let symbol = unique_symbol();
+    let id = insert_currency(&ctx, &symbol).await;
+
+    sqlx::query("UPDATE currencies SET requires_extra_id = TRUE, extra_id_name = 'Destination Tag' WHERE id = ?")
+        .bind(id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    let response = ctx
+        .server
+        .post("/swap/create")
+        .json(&json!({
+            "from": "xmr",
+            "network_from": "Mainnet",
+            "to": symbol,
+            "network_to": "Mainnet",
+            "amount": 1.0,
+            "provider": "changenow",
+            "recipient_address": "some-address"
+        }))
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "EXTRA_ID_REQUIRED");
+    assert_eq!(body["extra_id_name"], "Destination Tag");
+
+    delete_currency(&ctx, id).await;
+    ctx.cleanup().await;
+}
@@ -0,0 +1,235 @@
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+
+use crate::common::{
+    create_admin_token, create_user_token, delete_currency, insert_currency, test_email, test_password, unique_symbol,
+    TestContext,
+};
+
+#[tokio::test]
+async fn schedule_delisting_requires_authentication() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx
+        .server
+        .post("/admin/currencies/1/delisting")
+        .json(&json!({ "delisting_at": Utc::now() + Duration::days(7) }))
+        .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn schedule_delisting_rejects_non_admin() {
+    let ctx = TestContext::new().await;
+    let email = test_email();
+
+    ctx.server
+        .post("/auth/register")
+        .json(&json!({
+            "email": &email,
+            "password": test_password(),
+            "password_confirm": test_password()
+        }))
+        .await;
+    let login: Value = ctx
+        .server
+        .post("/auth/login")
+        .json(&json!({ "email": &email, "password": test_password() }))
+        .await
+        .json();
+    let token = login["access_token"].as_str().unwrap();
+
+    let response = ctx
+        .server
+        .post("/admin/currencies/1/delisting")
+        .authorization_bearer(token)
+        .json(&json!({ "delisting_at": Utc::now() + Duration::days(7) }))
+        .await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn scheduled_delisting_is_flagged_in_currency_listing() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let symbol = unique_symbol();
+    let id = insert_currency(&ctx, &symbol).await;
+
+    let response = ctx
+        .server
+        .post(&format!("/admin/currencies/{}/delisting", id))
+        .authorization_bearer(&token)
+        .json(&json!({ "delisting_at": Utc::now() + Duration::days(7), "reason": "Low liquidity" }))
+        .await;
+    response.assert_status_ok();
+
+    let body: Value = response.json();
+    assert_eq!(body["currency_id"], id);
+    assert!(body["delisting_at"].is_string());
+
+    let listing: Vec<Value> = ctx
+        .server
+        .get(&format!("/swap/currencies?ticker={}", symbol))
+        .await
+        .json();
+    assert_eq!(listing.len(), 1);
+    assert!(listing[0]["delisting_at"].is_string(), "Listing should announce the delisting date");
+
+    // Cancelling removes the flag again
+    ctx.server
+        .delete(&format!("/admin/currencies/{}/delisting", id))
+        .authorization_bearer(&token)
+        .await
+        .assert_status_ok();
+
+    let listing: Vec<Value> = ctx
+        .server
+        .get(&format!("/swap/currencies?ticker={}", symbol))
+        .await
+        .json();
+    assert!(listing[0].get("delisting_at").is_none());
+
+    delete_currency(&ctx, id).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn delisting_notifies_users_who_only_favorited_or_saved_the_currency() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let symbol = unique_symbol();
+    let id = insert_currency(&ctx, &symbol).await;
+
+    // Neither user has ever swapped the currency
+    let (fan, _) = create_user_token(&ctx).await;
+    sqlx::query(
+        "INSERT INTO favorite_pairs (id, user_id, from_currency, from_network, to_currency, to_network)
+         VALUES (UUID(), ?, 'xmr', 'Mainnet', ?, 'Mainnet')",
+    )
+    .bind(&fan)
+    .bind(&symbol)
+    .execute(&ctx.db)
+    .await
+    .unwrap();
+    let (saver, _) = create_user_token(&ctx).await;
+    sqlx::query("INSERT INTO saved_addresses (id, user_id, currency, network, address) VALUES (UUID(), ?, ?, 'Mainnet', 'addr')")
+        .bind(&saver)
+        .bind(&symbol)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    let response = ctx
+        .server
+        .post(&format!("/admin/currencies/{}/delisting", id))
+        .authorization_bearer(&token)
+        .json(&json!({ "delisting_at": Utc::now() + Duration::days(7) }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["notified_users"], 2);
+
+    for user in [&fan, &saver] {
+        let (notices,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM user_notifications WHERE user_id = ? AND kind = 'currency_delisting'",
+        )
+        .bind(user)
+        .fetch_one(&ctx.db)
+        .await
+        .unwrap();
+        assert_eq!(notices, 1);
+    }
+
+    delete_currency(&ctx, id).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn schedule_delisting_rejects_past_dates() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let id = insert_currency(&ctx, &unique_symbol()).await;
+
+    let response = ctx
+        .server
+        .post(&format!("/admin/currencies/{}/delisting", id))
+        .authorization_bearer(&token)
+        .json(&json!({ "delisting_at": Utc::now() - Duration::days(1) }))
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    delete_currency(&ctx, id).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn create_swap_is_blocked_after_delisting_date() {
+    let ctx = TestContext::new().await;
+    let symbol = unique_symbol();
+    let id = insert_currency(&ctx, &symbol).await;
+
+    sqlx::query("UPDATE currencies SET delisting_at = NOW() - INTERVAL 1 HOUR WHERE id = ?")
+        .bind(id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    let response = ctx
+        .server
+        .post("/swap/create")
+        .json(&json!({
+            "from": symbol,
+            "network_from": "Mainnet",
+            "to": "xmr",
+            "network_to": "Mainnet",
+            "amount": 1.0,
+            "provider": "changenow",
+            "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A"
+        }))
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "CURRENCY_DELISTED");
+
+    delete_currency(&ctx, id).await;
+}
+
+#[tokio::test]
+async fn create_swap_treats_currency_no_longer_listed_as_unknown() {
+    let ctx = TestContext::new().await;
+    let symbol = unique_symbol();
+    let id = insert_currency(&ctx, &symbol).await;
+
+    // What the sync does to a currency Trocador stopped listing
+    sqlx::query("UPDATE currencies SET is_active = FALSE WHERE id = ?")
+        .bind(id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    let response = ctx
+        .server
+        .post("/swap/create")
+        .json(&json!({
+            "from": symbol,
+            "network_from": "Mainnet",
+            "to": "xmr",
+            "network_to": "Mainnet",
+            "amount": 1.0,
+            "provider": "changenow",
+            "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A"
+        }))
+        .await;
+
+    response.assert_status(StatusCode::NOT_FOUND);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "CURRENCY_NOT_FOUND");
+
+    delete_currency(&ctx, id).await;
+}
@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use axum::http::StatusCode;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde_json::Value;
+
+use exchange_shared::modules::swap::crud::SwapCrud;
+use exchange_shared::services::deposit_check::{
+    AddressActivity, CheckTrigger, DepositCheckError, DepositChecker, DepositExplorer, Utxo,
+};
+
+use crate::common::{amount, create_admin_token, delete_swap, insert_swap, TestContext};
+
+/// Explorer that reports a fixed amount received by every BTC address
+struct FixedExplorer(Decimal);
+
+#[async_trait]
+impl DepositExplorer for FixedExplorer {
+    fn name(&self) -> &str {
+        "fixed"
+    }
+
+    fn supports(&self, currency: &str, _network: &str) -> bool {
+        currency == "btc"
+    }
+
+    async fn lookup(&self, _address: &str) -> Result<AddressActivity, String> {
+        Ok(AddressActivity {
+            received_confirmed: self.0,
+            received_unconfirmed: Decimal::ZERO,
+            tx_count: 1,
+            utxos: vec![Utxo { txid: "ab".repeat(32), vout: 0, value: self.0, confirmed: true }],
+        })
+    }
+}
+
+#[tokio::test]
+async fn deposit_check_records_the_shortfall() {
+    let ctx = TestContext::new().await;
+    let swap_id = insert_swap(&ctx, "waiting", None).await;
+    let swap = SwapCrud::new(ctx.db.clone(), None).find_swap(&swap_id).await.unwrap().unwrap();
+    let checker = DepositChecker::new(ctx.db.clone(), vec![Box::new(FixedExplorer(dec!(0.0004)))]);
+
+    let evidence = checker.check(&swap, CheckTrigger::Underpayment).await.unwrap();
+
+    assert_eq!(evidence.explorer, "fixed");
+    assert_eq!(evidence.address, swap.deposit_address);
+    assert_eq!(evidence.shortfall, dec!(0.0006));
+    assert!(evidence.is_short());
+    assert_eq!(checker.get(&swap_id).await.unwrap(), Some(evidence));
+
+    delete_swap(&ctx, &swap_id).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn a_new_check_replaces_the_previous_one() {
+    let ctx = TestContext::new().await;
+    let swap_id = insert_swap(&ctx, "waiting", None).await;
+    let swap = SwapCrud::new(ctx.db.clone(), None).find_swap(&swap_id).await.unwrap().unwrap();
+
+    DepositChecker::new(ctx.db.clone(), vec![Box::new(FixedExplorer(dec!(0.0004)))])
+        .check(&swap, CheckTrigger::Underpayment)
+        .await
+        .unwrap();
+    let checker = DepositChecker::new(ctx.db.clone(), vec![Box::new(FixedExplorer(dec!(0.001)))]);
+    checker.check(&swap, CheckTrigger::Admin).await.unwrap();
+
+    let stored = checker.get(&swap_id).await.unwrap().unwrap();
+    assert_eq!(stored.trigger, CheckTrigger::Admin);
+    assert_eq!(stored.shortfall, Decimal::ZERO);
+    assert!(!stored.is_short());
+
+    delete_swap(&ctx, &swap_id).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn currencies_without_an_explorer_are_not_checked() {
+    let ctx = TestContext::new().await;
+    let swap_id = insert_swap(&ctx, "waiting", None).await;
+    let mut swap = SwapCrud::new(ctx.db.clone(), None).find_swap(&swap_id).await.unwrap().unwrap();
+    swap.from_currency = "xmr".to_string();
+    let checker = DepositChecker::new(ctx.db.clone(), vec![Box::new(FixedExplorer(Decimal::ONE))]);
+
+    let result = checker.check(&swap, CheckTrigger::Admin).await;
+
+    assert!(matches!(result, Err(DepositCheckError::Unsupported(..))));
+    assert_eq!(checker.get(&swap_id).await.unwrap(), None);
+
+    delete_swap(&ctx, &swap_id).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn deposit_check_requires_admin() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/admin/swaps/anything/deposit-check").await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn stored_deposit_check_is_shown_to_admins() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let swap_id = insert_swap(&ctx, "waiting", None).await;
+    let path = format!("/admin/swaps/{}/deposit-check", swap_id);
+
+    let response = ctx.server.get(&path).authorization_bearer(&token).await;
+    response.assert_status(StatusCode::NOT_FOUND);
+
+    let swap = SwapCrud::new(ctx.db.clone(), None).find_swap(&swap_id).await.unwrap().unwrap();
+    DepositChecker::new(ctx.db.clone(), vec![Box::new(FixedExplorer(dec!(0.0004)))])
+        .check(&swap, CheckTrigger::Underpayment)
+        .await
+        .unwrap();
+
+    let response = ctx.server.get(&path).authorization_bearer(&token).await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["swap_id"], swap_id);
+    assert_eq!(body["trigger"], "underpayment");
+    assert_eq!(amount(&body["received_confirmed"]), dec!(0.0004));
+    assert_eq!(body["utxos"].as_array().unwrap().len(), 1);
+
+    delete_swap(&ctx, &swap_id).await;
+    ctx.cleanup().await;
+}
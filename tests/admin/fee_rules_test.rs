@@ -0,0 +1,135 @@
+use axum::http::StatusCode;
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+
+use crate::common::{amount, create_admin_token, create_user_token, TestContext};
+
+// Rules are scoped to a random ticker so they never price other tests' quotes
+fn test_ticker() -> String {
+    format!("tf{}", &uuid::Uuid::new_v4().simple().to_string()[..8])
+}
+
+#[tokio::test]
+async fn fee_rule_endpoints_require_admin() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx.server.get("/admin/fee-rules").authorization_bearer(&token).await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn invalid_fee_rules_are_rejected() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let ticker = test_ticker();
+
+    let cases = [
+        json!({ "from_currency": ticker, "fee_percent": 25.0 }),
+        json!({ "from_currency": ticker, "fee_percent": -1.0 }),
+        json!({ "from_currency": ticker, "to_currency": "btc", "flat_fee": -0.1 }),
+        json!({ "from_currency": ticker, "flat_fee": 0.001 }), // Flat fee without a receive currency
+        json!({ "from_currency": ticker, "user_tier": "Gold Tier", "fee_percent": 0.5 }),
+    ];
+    for body in cases {
+        let response = ctx.server.post("/admin/fee-rules").authorization_bearer(&token).json(&body).await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn fee_rule_lifecycle() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let ticker = test_ticker();
+
+    let response = ctx
+        .server
+        .post("/admin/fee-rules")
+        .authorization_bearer(&token)
+        .json(&json!({
+            "from_currency": ticker.to_uppercase(),
+            "to_currency": "BTC",
+            "user_tier": "vip",
+            "fee_percent": 0.25,
+            "flat_fee": 0.0001,
+            "note": "VIP pricing"
+        }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let rule: Value = response.json();
+    let id = rule["id"].as_u64().expect("rule id");
+    assert_eq!(rule["from_currency"], ticker.as_str(), "tickers are stored lowercase");
+    assert_eq!(rule["to_currency"], "btc");
+    assert_eq!(amount(&rule["fee_percent"]), dec!(0.25));
+
+    let response = ctx.server.get("/admin/fee-rules").authorization_bearer(&token).await;
+    response.assert_status_ok();
+    let rules: Vec<Value> = response.json();
+    assert!(rules.iter().any(|r| r["id"] == id));
+
+    let path = format!("/admin/fee-rules/{}", id);
+    let response = ctx
+        .server
+        .put(&path)
+        .authorization_bearer(&token)
+        .json(&json!({ "from_currency": ticker, "fee_percent": 0.5 }))
+        .await;
+    response.assert_status_ok();
+    let rule: Value = response.json();
+    assert_eq!(amount(&rule["fee_percent"]), dec!(0.5));
+    assert!(rule["to_currency"].is_null());
+    assert!(rule["user_tier"].is_null());
+
+    let response = ctx.server.delete(&path).authorization_bearer(&token).await;
+    response.assert_status_ok();
+    let response = ctx.server.delete(&path).authorization_bearer(&token).await;
+    response.assert_status(StatusCode::NOT_FOUND);
+    let response = ctx
+        .server
+        .put(&path)
+        .authorization_bearer(&token)
+        .json(&json!({ "fee_percent": 0.5 }))
+        .await;
+    response.assert_status(StatusCode::NOT_FOUND);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn admin_moves_user_to_fee_tier() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let (user_id, _) = create_user_token(&ctx).await;
+
+    let tier: (String,) = sqlx::query_as("SELECT fee_tier FROM users WHERE id = ?")
+        .bind(&user_id)
+        .fetch_one(&ctx.db)
+        .await
+        .unwrap();
+    assert_eq!(tier.0, "standard");
+
+    let response = ctx
+        .server
+        .put(&format!("/admin/users/{}/fee-tier", user_id))
+        .authorization_bearer(&token)
+        .json(&json!({ "fee_tier": "VIP" }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["fee_tier"], "vip");
+
+    let response = ctx
+        .server
+        .put("/admin/users/no-such-user/fee-tier")
+        .authorization_bearer(&token)
+        .json(&json!({ "fee_tier": "vip" }))
+        .await;
+    response.assert_status(StatusCode::NOT_FOUND);
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,64 @@
+use axum::http::StatusCode;
+use serde_json::Value;
+
+use crate::common::{create_admin_token, create_user_token, delete_swap, insert_swap, TestContext};
+
+#[tokio::test]
+async fn high_value_queue_requires_admin() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    ctx.server
+        .get("/admin/swaps/high-value")
+        .authorization_bearer(&token)
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn high_value_queue_lists_in_flight_swaps_stalled_first() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+
+    let fresh = insert_swap(&ctx, "confirming", None).await;
+    let stalled = insert_swap(&ctx, "sending", None).await;
+    let completed = insert_swap(&ctx, "completed", None).await;
+    let regular = insert_swap(&ctx, "sending", None).await;
+    for id in [&fresh, &stalled, &completed] {
+        sqlx::query("UPDATE swaps SET high_value = TRUE, usd_value = 25000 WHERE id = ?")
+            .bind(id)
+            .execute(&ctx.db)
+            .await
+            .unwrap();
+    }
+    sqlx::query("UPDATE swaps SET updated_at = NOW() - INTERVAL 1 DAY WHERE id = ?")
+        .bind(&stalled)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    let response = ctx
+        .server
+        .get("/admin/swaps/high-value")
+        .authorization_bearer(&token)
+        .await;
+    response.assert_status_ok();
+    let body: Vec<Value> = response.json();
+    let position = |id: &str| body.iter().position(|s| s["id"] == id);
+
+    let stalled_at = position(&stalled).expect("stalled swap missing");
+    let fresh_at = position(&fresh).expect("fresh swap missing");
+    assert!(stalled_at < fresh_at);
+    assert_eq!(body[stalled_at]["stalled"], true);
+    assert_eq!(body[fresh_at]["stalled"], false);
+    assert_eq!(body[fresh_at]["usd_value"], 25000.0);
+    assert!(position(&completed).is_none());
+    assert!(position(&regular).is_none());
+
+    for id in [&fresh, &stalled, &completed, &regular] {
+        delete_swap(&ctx, id).await;
+    }
+    ctx.cleanup().await;
+}
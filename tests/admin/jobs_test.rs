@@ -0,0 +1,67 @@
+use axum::http::StatusCode;
+use serde_json::Value;
+
+use exchange_shared::services::jobs::{self, JobKind, JobOutcome};
+
+use crate::common::{create_admin_token, create_user_token, TestContext};
+
+#[tokio::test]
+async fn jobs_endpoints_require_admin() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx.server.get("/admin/jobs").authorization_bearer(&token).await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn registered_job_is_listed_and_can_be_triggered() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+
+    let name = format!("test_job_{}", &uuid::Uuid::new_v4().simple().to_string()[..8]);
+    let job = jobs::registry().register(&name, "Test job", JobKind::Scheduled);
+    job.start().finish(JobOutcome::Failed, Some("boom".to_string()));
+
+    let response = ctx.server.get("/admin/jobs").authorization_bearer(&token).await;
+    response.assert_status_ok();
+    let body: Vec<Value> = response.json();
+    let listed = body.iter().find(|j| j["name"] == name.as_str()).expect("job listed");
+    assert_eq!(listed["kind"], "scheduled");
+    assert_eq!(listed["last_outcome"], "failed");
+    assert_eq!(listed["last_error"], "boom");
+    assert_eq!(listed["failures"], 1);
+
+    let response = ctx
+        .server
+        .post(&format!("/admin/jobs/{}/run", name))
+        .authorization_bearer(&token)
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["trigger_pending"], true);
+
+    // The trigger cuts the wait short
+    tokio::time::timeout(std::time::Duration::from_secs(1), job.wait(std::time::Duration::from_secs(3600)))
+        .await
+        .expect("triggered job should wake up");
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn unknown_job_is_not_found() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+
+    let response = ctx
+        .server
+        .post("/admin/jobs/no_such_job/run")
+        .authorization_bearer(&token)
+        .await;
+
+    response.assert_status(StatusCode::NOT_FOUND);
+    ctx.cleanup().await;
+}
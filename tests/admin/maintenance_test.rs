@@ -0,0 +1,100 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{create_admin_token, create_user_token, delete_swap, insert_swap, TestContext};
+
+// Maintenance mode is global, so everything that toggles it lives in one test
+// to keep parallel tests from seeing each other's state.
+
+#[tokio::test]
+async fn maintenance_endpoints_require_admin() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx
+        .server
+        .put("/admin/maintenance")
+        .authorization_bearer(&token)
+        .json(&json!({ "enabled": true }))
+        .await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn invalid_retry_after_is_rejected() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+
+    let response = ctx
+        .server
+        .put("/admin/maintenance")
+        .authorization_bearer(&token)
+        .json(&json!({ "enabled": true, "retry_after_secs": 0 }))
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn maintenance_blocks_writes_and_keeps_reads() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let swap_id = insert_swap(&ctx, "waiting", None).await;
+
+    let response = ctx
+        .server
+        .put("/admin/maintenance")
+        .authorization_bearer(&token)
+        .json(&json!({ "enabled": true, "retry_after_secs": 120, "messages": { "de": "Wartung läuft" } }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["enabled"], true);
+
+    // Writes get 503 with Retry-After and the built-in localized message
+    let response = ctx
+        .server
+        .post("/swap/create")
+        .add_header("Accept-Language", "es-ES,es;q=0.9,en;q=0.8")
+        .json(&json!({}))
+        .await;
+    response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.header("retry-after"), "120");
+    let body: Value = response.json();
+    assert_eq!(body["code"], "MAINTENANCE_MODE");
+    assert!(body["error"].as_str().unwrap().starts_with("Estamos"));
+
+    // Admin-provided text wins for its language
+    let response = ctx
+        .server
+        .post(&format!("/swap/{}/retry", swap_id))
+        .add_header("Accept-Language", "de")
+        .await;
+    response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    let body: Value = response.json();
+    assert_eq!(body["error"], "Wartung läuft");
+
+    // Reads are served from stored data
+    let response = ctx.server.get(&format!("/swap/{}", swap_id)).await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["status"], "waiting");
+
+    let response = ctx
+        .server
+        .put("/admin/maintenance")
+        .authorization_bearer(&token)
+        .json(&json!({ "enabled": false }))
+        .await;
+    response.assert_status_ok();
+
+    // Past the guard again: an empty body now fails validation instead
+    let response = ctx.server.post("/swap/create").json(&json!({})).await;
+    assert_ne!(response.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+
+    delete_swap(&ctx, &swap_id).await;
+    ctx.cleanup().await;
+}
@@ -0,0 +1,25 @@
+mod delisting_test;
+mod sync_status_test;
+mod cache_stats_test;
+mod rate_limit_test;
+mod currency_policy_test;
+mod provider_payloads_test;
+mod maintenance_test;
+mod schema_drift_test;
+mod address_formats_test;
+mod jobs_test;
+mod retention_test;
+mod brands_test;
+mod brand_webhooks_test;
+mod fee_rules_test;
+mod shadow_quotes_test;
+mod providers_test;
+mod high_value_test;
+mod tenants_test;
+mod slo_test;
+mod reconciliation_test;
+mod routing_rules_test;
+mod deposit_check_test;
+mod audit_test;
+mod provider_credentials_test;
+mod provider_health_test;
@@ -0,0 +1,105 @@
+use axum::http::StatusCode;
+use base64::Engine;
+use serde_json::json;
+
+use exchange_shared::services::encryption::{CipherError, SecretCipher};
+use exchange_shared::services::provider_credentials::{key_hint, CredentialError, ProviderCredentials};
+use exchange_shared::services::trocador::TrocadorClient;
+
+use crate::common::{create_admin_token, TestContext};
+
+fn cipher() -> SecretCipher {
+    SecretCipher::new(&[7u8; 32])
+}
+
+#[test]
+fn keys_round_trip_and_are_bound_to_their_provider() {
+    let sealed = cipher().encrypt("trocador", "secret-key-1234");
+
+    assert!(!sealed.contains("secret-key"));
+    assert_eq!(cipher().decrypt("trocador", &sealed).unwrap(), "secret-key-1234");
+    assert!(matches!(cipher().decrypt("changenow", &sealed), Err(CipherError::Decrypt(_))));
+    assert!(SecretCipher::new(&[8u8; 32]).decrypt("trocador", &sealed).is_err());
+}
+
+#[test]
+fn each_encryption_uses_a_fresh_nonce() {
+    assert_ne!(cipher().encrypt("trocador", "k"), cipher().encrypt("trocador", "k"));
+}
+
+#[test]
+fn credentials_key_must_be_32_bytes() {
+    let short = base64::engine::general_purpose::STANDARD.encode([1u8; 16]);
+    let full = base64::engine::general_purpose::STANDARD.encode([1u8; 32]);
+
+    assert!(matches!(SecretCipher::from_base64(&short), Err(CipherError::InvalidKey(_))));
+    assert!(matches!(SecretCipher::from_base64("not base64!"), Err(CipherError::InvalidKey(_))));
+    assert!(SecretCipher::from_base64(&full).is_ok());
+}
+
+#[test]
+fn only_the_end_of_a_key_is_shown() {
+    assert_eq!(key_hint("abcdefgh1234"), "…1234");
+    assert_eq!(key_hint("ab"), "…ab");
+}
+
+#[tokio::test]
+async fn rotated_key_is_stored_encrypted_and_applied() {
+    let ctx = TestContext::new().await;
+    let credentials = ProviderCredentials::new(ctx.db.clone(), Some(cipher()));
+
+    let summary = credentials.rotate("trocador", "rotated-key-9876", None, None).await.unwrap();
+    assert_eq!(summary.key_hint, "…9876");
+
+    let stored: String =
+        sqlx::query_scalar("SELECT api_key_encrypted FROM provider_credentials WHERE provider = 'trocador'")
+            .fetch_one(&ctx.db)
+            .await
+            .unwrap();
+    assert!(!stored.contains("rotated-key"));
+
+    let loaded = credentials.load("trocador").await.unwrap().unwrap();
+    assert_eq!(loaded.api_key, "rotated-key-9876");
+    assert!(credentials.apply_trocador(&TrocadorClient::new("old".to_string())).await.unwrap().is_some());
+
+    sqlx::query("DELETE FROM provider_credentials WHERE provider = 'trocador'").execute(&ctx.db).await.unwrap();
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn unknown_providers_and_missing_key_are_refused() {
+    let ctx = TestContext::new().await;
+
+    let result = ProviderCredentials::new(ctx.db.clone(), Some(cipher())).rotate("nope", "k", None, None).await;
+    assert!(matches!(result, Err(CredentialError::UnknownProvider(_))));
+
+    let result = ProviderCredentials::new(ctx.db.clone(), None).rotate("trocador", "k", None, None).await;
+    assert!(matches!(result, Err(CredentialError::Disabled)));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn provider_credentials_require_admin() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/admin/provider-credentials").await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn rotation_rejects_an_empty_key() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+
+    let response = ctx
+        .server
+        .put("/admin/provider-credentials/trocador")
+        .authorization_bearer(&token)
+        .json(&json!({ "api_key": "  " }))
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    ctx.cleanup().await;
+}
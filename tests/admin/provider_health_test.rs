@@ -0,0 +1,124 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{create_admin_token, create_user_token, TestContext};
+
+// A provider of its own per test, with `completed` of `swaps` outcomes today
+async fn insert_provider(ctx: &TestContext, completed: u32, swaps: u32) -> String {
+    let slug = format!("test-{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    sqlx::query("INSERT INTO providers (id, name, slug, kyc_rating, eta_minutes) VALUES (?, ?, ?, 'C', 10)")
+        .bind(&slug)
+        .bind(&slug)
+        .bind(&slug)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    for (outcome, count, secs) in [("completed", completed, completed * 1200), ("failed", swaps - completed, 0)] {
+        sqlx::query(
+            "INSERT INTO provider_stats (provider, day, outcome, swaps, completion_secs)
+             VALUES (?, UTC_DATE(), ?, ?, ?)",
+        )
+        .bind(&slug)
+        .bind(outcome)
+        .bind(count)
+        .bind(secs)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    }
+    slug
+}
+
+async fn delete_provider(ctx: &TestContext, id: &str) {
+    sqlx::query("DELETE FROM provider_stats WHERE provider = ?")
+        .bind(id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM providers WHERE id = ?")
+        .bind(id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+}
+
+async fn health_of(ctx: &TestContext, token: &str, id: &str) -> Value {
+    let response = ctx.server.get("/admin/providers/health").authorization_bearer(token).await;
+    response.assert_status_ok();
+    let listing: Vec<Value> = response.json();
+    listing.into_iter().find(|h| h["provider"] == id).expect("provider is listed")
+}
+
+#[tokio::test]
+async fn provider_health_requires_admin() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx.server.get("/admin/providers/health").authorization_bearer(&token).await;
+    response.assert_status(StatusCode::FORBIDDEN);
+
+    let response = ctx.server.get("/admin/providers/health").await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn failing_provider_is_scored_and_hidden() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let id = insert_provider(&ctx, 10, 40).await;
+
+    let health = health_of(&ctx, &token, &id).await;
+    assert_eq!(health["swaps"], 40);
+    assert_eq!(health["completed"], 10);
+    assert_eq!(health["score"], 25.0);
+    assert_eq!(health["avg_completion_minutes"], 20.0);
+    assert_eq!(health["failures"]["failed"], 30);
+    assert_eq!(health["action"], "hide");
+
+    delete_provider(&ctx, &id).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn health_override_is_applied_and_cleared() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let id = insert_provider(&ctx, 10, 40).await;
+    let path = format!("/admin/providers/{}", id);
+
+    let response = ctx
+        .server
+        .patch(&path)
+        .authorization_bearer(&token)
+        .json(&json!({ "health_override": "banned" }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    let response = ctx
+        .server
+        .patch(&path)
+        .authorization_bearer(&token)
+        .json(&json!({ "health_override": "trusted" }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["health_override"], "trusted");
+
+    let health = health_of(&ctx, &token, &id).await;
+    assert_eq!(health["health_override"], "trusted");
+    assert_eq!(health["action"], "serve");
+
+    let response = ctx.server.delete(&format!("{}/overrides", path)).authorization_bearer(&token).await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert!(body["health_override"].is_null());
+
+    let health = health_of(&ctx, &token, &id).await;
+    assert_eq!(health["action"], "hide");
+
+    delete_provider(&ctx, &id).await;
+    ctx.cleanup().await;
+}
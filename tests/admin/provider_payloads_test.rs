@@ -0,0 +1,67 @@
+use axum::http::StatusCode;
+use serde_json::Value;
+
+use crate::common::{create_admin_token, delete_swap, insert_swap, TestContext};
+use exchange_shared::services::payload_codec;
+
+#[tokio::test]
+async fn provider_payloads_require_admin() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/admin/swaps/anything/provider-payloads").await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn provider_payloads_for_unknown_swap_return_not_found() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+
+    let response = ctx
+        .server
+        .get("/admin/swaps/00000000-0000-0000-0000-000000000000/provider-payloads")
+        .authorization_bearer(&token)
+        .await;
+
+    response.assert_status(StatusCode::NOT_FOUND);
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn stored_payloads_are_returned_decompressed() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let swap_id = insert_swap(&ctx, "waiting", None).await;
+
+    let raw = r#"{"trade_id":"abc123","status":"waiting","amount_to":0.1}"#;
+    let encoded = payload_codec::encode(raw).unwrap();
+    sqlx::query(
+        "INSERT INTO swap_provider_payloads (swap_id, call_type, payload_gz, original_bytes, truncated)
+         VALUES (?, 'create_trade', ?, ?, ?)",
+    )
+    .bind(&swap_id)
+    .bind(&encoded.compressed)
+    .bind(encoded.original_bytes as u32)
+    .bind(encoded.truncated)
+    .execute(&ctx.db)
+    .await
+    .unwrap();
+
+    let response = ctx
+        .server
+        .get(&format!("/admin/swaps/{}/provider-payloads", swap_id))
+        .authorization_bearer(&token)
+        .await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    let payloads = body["payloads"].as_array().unwrap();
+    assert_eq!(payloads.len(), 1);
+    assert_eq!(payloads[0]["call_type"], "create_trade");
+    assert_eq!(payloads[0]["payload"]["trade_id"], "abc123");
+    assert_eq!(payloads[0]["truncated"], false);
+
+    delete_swap(&ctx, &swap_id).await;
+    ctx.cleanup().await;
+}
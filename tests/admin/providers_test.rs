@@ -0,0 +1,147 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{create_admin_token, create_user_token, TestContext};
+
+// Each test inserts its own provider so toggles never touch real ones
+async fn insert_provider(ctx: &TestContext) -> String {
+    let slug = format!("test-{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    sqlx::query("INSERT INTO providers (id, name, slug, kyc_rating, eta_minutes) VALUES (?, ?, ?, 'C', 10)")
+        .bind(&slug)
+        .bind(&slug)
+        .bind(&slug)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    slug
+}
+
+async fn delete_provider(ctx: &TestContext, id: &str) {
+    sqlx::query("DELETE FROM providers WHERE id = ?")
+        .bind(id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn provider_endpoints_require_admin() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx.server.get("/admin/providers").authorization_bearer(&token).await;
+    response.assert_status(StatusCode::FORBIDDEN);
+
+    let response = ctx
+        .server
+        .patch("/admin/providers/changenow")
+        .json(&json!({ "is_active": false }))
+        .await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn invalid_updates_are_rejected() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let id = insert_provider(&ctx).await;
+
+    for body in [json!({ "kyc_rating": "E" }), json!({ "eta_minutes": -5 })] {
+        let response = ctx
+            .server
+            .patch(&format!("/admin/providers/{}", id))
+            .authorization_bearer(&token)
+            .json(&body)
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    let response = ctx
+        .server
+        .patch("/admin/providers/no-such-provider")
+        .authorization_bearer(&token)
+        .json(&json!({ "is_active": false }))
+        .await;
+    response.assert_status(StatusCode::NOT_FOUND);
+
+    delete_provider(&ctx, &id).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn overrides_are_applied_listed_and_cleared() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let id = insert_provider(&ctx).await;
+    let path = format!("/admin/providers/{}", id);
+
+    let response = ctx
+        .server
+        .patch(&path)
+        .authorization_bearer(&token)
+        .json(&json!({ "kyc_rating": "A", "eta_minutes": 45 }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["kyc_rating"], "A");
+    assert_eq!(body["kyc_rating_override"], "A");
+    assert_eq!(body["eta_minutes"], 45);
+    assert_eq!(body["eta_minutes_override"], 45);
+
+    let response = ctx.server.delete(&format!("{}/overrides", path)).authorization_bearer(&token).await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert!(body["kyc_rating_override"].is_null());
+    assert!(body["eta_minutes_override"].is_null());
+
+    // Disabled providers stay listed for admins
+    let response = ctx
+        .server
+        .patch(&path)
+        .authorization_bearer(&token)
+        .json(&json!({ "is_active": false }))
+        .await;
+    response.assert_status_ok();
+    let listing: Vec<Value> = ctx.server.get("/admin/providers").authorization_bearer(&token).await.json();
+    let listed = listing.iter().find(|p| p["id"] == id.as_str()).expect("disabled provider is listed");
+    assert_eq!(listed["is_active"], false);
+
+    delete_provider(&ctx, &id).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn disabled_provider_is_refused_for_new_swaps() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let id = insert_provider(&ctx).await;
+
+    ctx.server
+        .patch(&format!("/admin/providers/{}", id))
+        .authorization_bearer(&token)
+        .json(&json!({ "is_active": false }))
+        .await
+        .assert_status_ok();
+
+    let response = ctx
+        .server
+        .post("/swap/create")
+        .json(&json!({
+            "from": "btc",
+            "network_from": "Mainnet",
+            "to": "xmr",
+            "network_to": "Mainnet",
+            "amount": 0.01,
+            "provider": id,
+            "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A"
+        }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "PROVIDER_NOT_ALLOWED");
+
+    delete_provider(&ctx, &id).await;
+    ctx.cleanup().await;
+}
@@ -0,0 +1,168 @@
+use axum::http::StatusCode;
+use exchange_shared::config::environment::RouteRateLimitConfig;
+use exchange_shared::services::rate_limiter::{DistributedRateLimiter, RouteRateLimiter};
+use exchange_shared::services::redis_cache::RedisService;
+use serde_json::{json, Value};
+
+use crate::common::{create_admin_token, create_user_token, test_email, TestContext};
+
+const INTERNAL_TOKEN: &str = "test-internal-service-token";
+
+#[tokio::test]
+async fn health_checks_are_never_rate_limited() {
+    let ctx = TestContext::new().await;
+
+    for _ in 0..15 {
+        ctx.server.get("/health").await.assert_status_ok();
+    }
+}
+
+#[tokio::test]
+async fn internal_service_token_bypasses_rate_limit() {
+    std::env::set_var("RATE_LIMIT_INTERNAL_TOKENS", INTERNAL_TOKEN);
+    let ctx = TestContext::new().await;
+
+    for _ in 0..15 {
+        ctx.server
+            .get("/")
+            .add_header("x-internal-service-token", INTERNAL_TOKEN)
+            .await
+            .assert_status_ok();
+    }
+
+    // Without the token the public limit still applies
+    let mut limited = false;
+    for _ in 0..15 {
+        if ctx.server.get("/").await.status_code() == StatusCode::TOO_MANY_REQUESTS {
+            limited = true;
+            break;
+        }
+    }
+    assert!(limited, "Public traffic should still be rate limited");
+}
+
+#[tokio::test]
+async fn rate_limit_stats_count_exempt_traffic() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+
+    ctx.server.get("/health").await.assert_status_ok();
+
+    let response = ctx
+        .server
+        .get("/admin/rate-limit/stats")
+        .authorization_bearer(&token)
+        .await;
+    response.assert_status_ok();
+
+    let body: Value = response.json();
+    assert!(body["exempt"].as_u64().unwrap() >= 1);
+    assert!(body["allowed"].as_u64().unwrap() >= 1);
+    assert!(body.get("limited").is_some());
+
+    ctx.cleanup().await;
+}
+
+fn route_limits(routes: &[(&str, u32)]) -> RouteRateLimitConfig {
+    RouteRateLimitConfig {
+        enabled: true,
+        trust_forwarded_for: false,
+        routes: routes.iter().map(|(prefix, limit)| (prefix.to_string(), *limit)).collect(),
+    }
+}
+
+fn redis() -> RedisService {
+    RedisService::new(&std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string()))
+}
+
+#[test]
+fn longest_route_prefix_wins() {
+    let config = route_limits(&[("/swap", 100), ("/swap/create", 10)]);
+
+    assert_eq!(config.limit_for("/swap/create"), Some(("/swap/create", 10)));
+    assert_eq!(config.limit_for("/swap/rates"), Some(("/swap", 100)));
+    assert_eq!(config.limit_for("/auth/login"), None);
+
+    let disabled = RouteRateLimitConfig { enabled: false, ..config };
+    assert_eq!(disabled.limit_for("/swap/create"), None);
+}
+
+#[tokio::test]
+async fn route_limit_is_per_caller_and_per_group() {
+    let limiter = RouteRateLimiter::new(redis(), route_limits(&[("/limited", 3), ("/other", 3)]));
+    let caller = format!("user:{}", test_email());
+
+    for _ in 0..3 {
+        assert!(limiter.check("/limited/thing", &caller).await.is_ok());
+    }
+    let wait = limiter.check("/limited/thing", &caller).await.unwrap_err();
+    assert!(wait.as_secs() <= 20, "3/min refills a token every 20s, got {:?}", wait);
+
+    // Other callers and other groups have their own buckets
+    assert!(limiter.check("/limited/thing", &format!("user:{}", test_email())).await.is_ok());
+    assert!(limiter.check("/other", &caller).await.is_ok());
+    // Unlisted routes aren't limited at all
+    assert!(limiter.check("/unlisted", &caller).await.is_ok());
+}
+
+#[tokio::test]
+async fn exhausted_route_limit_returns_retry_after() {
+    let ctx = TestContext::new().await;
+    let (user_id, token) = create_user_token(&ctx).await;
+
+    // Drain the user's /swap/create bucket (default 10/min) without creating swaps
+    let limiter = RouteRateLimiter::new(redis(), route_limits(&[("/swap/create", 10)]));
+    let caller = format!("user:{}", user_id);
+    while limiter.check("/swap/create", &caller).await.is_ok() {}
+
+    let response = ctx
+        .server
+        .post("/swap/create")
+        .authorization_bearer(&token)
+        .json(&json!({
+            "from": "btc",
+            "network_from": "Mainnet",
+            "to": "xmr",
+            "network_to": "Mainnet",
+            "amount": 0.1,
+            "recipient_address": "888tNkZrPN6JsEgekjMnABU4TBzc2Dt29EPAvkRxbANsAnjyPbb3iQ1YBRk1UXcdRsiKc9dhwMVgN5S9cQUiyoogDavup3H",
+        }))
+        .await;
+
+    response.assert_status(StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = response.header("retry-after").to_str().unwrap().parse().unwrap();
+    assert!((1..=6).contains(&retry_after));
+    let body: Value = response.json();
+    assert_eq!(body["retry_after"], retry_after);
+
+    ctx.cleanup().await;
+}
+
+/// Fires `attempts` acquisitions at once at a bucket of 5 tokens that never
+/// refills, returning how many got a token
+async fn concurrent_acquisitions(redis: RedisService, attempts: usize) -> usize {
+    let limiter = DistributedRateLimiter::new(redis).with_limit(5, 0);
+    let key = format!("concurrent:{}", test_email());
+    let results = futures::future::join_all((0..attempts).map(|_| limiter.try_acquire(&key, 1))).await;
+    results.into_iter().filter(|r| matches!(r, Ok(true))).count()
+}
+
+#[tokio::test]
+async fn concurrent_requests_cannot_spend_the_same_token() {
+    assert_eq!(concurrent_acquisitions(redis(), 50).await, 5);
+    assert_eq!(concurrent_acquisitions(RedisService::in_memory(), 50).await, 5);
+}
+
+#[tokio::test]
+async fn unreadable_bucket_starts_full_instead_of_letting_everything_through() {
+    let redis = RedisService::in_memory();
+    let limiter = DistributedRateLimiter::new(redis.clone()).with_limit(1, 0);
+    // A bucket in the old JSON shape
+    redis
+        .set_string("token_bucket:legacy", r#"{"tokens":3,"last_refill":0}"#, 60)
+        .await
+        .unwrap();
+
+    assert!(limiter.try_acquire("legacy", 1).await.unwrap());
+    assert!(!limiter.try_acquire("legacy", 1).await.unwrap());
+}
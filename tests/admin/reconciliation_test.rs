@@ -0,0 +1,134 @@
+use axum::http::StatusCode;
+use chrono::{NaiveDate, TimeZone, Utc};
+use rust_decimal_macros::dec;
+use serde_json::Value;
+
+use exchange_shared::services::reconciliation::{
+    compare_provider_trades, next_run, Discrepancy, DiscrepancyKind, ReconciliationReport, ReconciliationService, SwapTotals,
+};
+
+use crate::common::{create_admin_token, create_user_token, delete_swap, insert_swap, TestContext};
+
+fn report(discrepancies: Vec<Discrepancy>) -> ReconciliationReport {
+    ReconciliationReport {
+        day: NaiveDate::from_ymd_opt(2026, 3, 3).unwrap(),
+        generated_at: Utc::now(),
+        swaps: SwapTotals { created: 4, completed: 2, refunded: 1, failed: 0, expired: 1 },
+        markup: Vec::new(),
+        providers: Vec::new(),
+        discrepancies,
+    }
+}
+
+#[test]
+fn provider_trades_are_matched_case_insensitively() {
+    let (counts, discrepancies) = compare_provider_trades(
+        &[("ChangeNOW".to_string(), 3), ("FixedFloat".to_string(), 2)],
+        &[("changenow".to_string(), 3), ("FixedFloat".to_string(), 1), ("Exolix".to_string(), 1)],
+    );
+
+    assert_eq!(counts.len(), 3);
+    let changenow = counts.iter().find(|c| c.provider == "ChangeNOW").unwrap();
+    assert_eq!((changenow.provider_trades, changenow.recorded_swaps), (3, 3));
+
+    let subjects: Vec<&str> = discrepancies.iter().map(|d| d.subject.as_str()).collect();
+    assert_eq!(subjects, vec!["Exolix", "FixedFloat"]);
+    assert!(discrepancies.iter().all(|d| d.kind == DiscrepancyKind::ProviderTradeCount));
+    assert_eq!(discrepancies[1].detail, "2 trade(s) opened, 1 swap(s) stored");
+}
+
+#[test]
+fn next_run_is_today_until_the_hour_passes() {
+    let before = Utc.with_ymd_and_hms(2026, 3, 4, 0, 30, 0).unwrap();
+    assert_eq!(next_run(before, 1), Utc.with_ymd_and_hms(2026, 3, 4, 1, 0, 0).unwrap());
+
+    let at = Utc.with_ymd_and_hms(2026, 3, 4, 1, 0, 0).unwrap();
+    assert_eq!(next_run(at, 1), Utc.with_ymd_and_hms(2026, 3, 5, 1, 0, 0).unwrap());
+}
+
+#[test]
+fn summary_lists_totals_and_discrepancies() {
+    let clean = report(Vec::new()).summary();
+    assert!(clean.contains("Reconciliation for 2026-03-03"));
+    assert!(clean.contains("4 created, 2 completed, 1 refunded, 0 failed, 1 expired"));
+    assert!(clean.contains("No discrepancies."));
+
+    let (_, discrepancies) = compare_provider_trades(&[("ChangeNOW".to_string(), 1)], &[]);
+    let summary = report(discrepancies).summary();
+    assert!(summary.contains("1 discrepancies:"));
+    assert!(summary.contains("[provider_trade_count] ChangeNOW: 1 trade(s) opened, 0 swap(s) stored"));
+}
+
+#[tokio::test]
+async fn reconciliation_reports_require_admin() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx.server.get("/admin/reconciliation").authorization_bearer(&token).await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn completion_missing_from_status_ledger_is_a_discrepancy() {
+    let ctx = TestContext::new().await;
+    let swap_id = insert_swap(&ctx, "completed", None).await;
+    sqlx::query("UPDATE swaps SET completed_at = NOW(), platform_fee = 0.002 WHERE id = ?")
+        .bind(&swap_id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    let service = ReconciliationService::new(ctx.db.clone());
+    let report = service.generate(Utc::now().date_naive()).await.expect("reconciliation failed");
+
+    assert!(report.swaps.created >= 1);
+    assert!(report.swaps.completed >= 1);
+    let xmr = report.markup.iter().find(|m| m.currency == "xmr").unwrap();
+    assert!(xmr.earned - xmr.ledgered >= dec!(0.002));
+    assert!(report
+        .discrepancies
+        .iter()
+        .any(|d| d.kind == DiscrepancyKind::CompletionNotInLedger && d.subject == swap_id));
+
+    delete_swap(&ctx, &swap_id).await;
+}
+
+#[tokio::test]
+async fn stored_reports_are_listed_and_served() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let service = ReconciliationService::new(ctx.db.clone());
+    let (_, discrepancies) = compare_provider_trades(&[("ChangeNOW".to_string(), 1)], &[]);
+    service.save(&report(discrepancies)).await.expect("save failed");
+
+    let response = ctx.server.get("/admin/reconciliation").authorization_bearer(&token).await;
+    response.assert_status_ok();
+    let list: Value = response.json();
+    let stored = list.as_array().unwrap().iter().find(|r| r["day"] == "2026-03-03").unwrap();
+    assert_eq!(stored["discrepancies"], 1);
+
+    let response = ctx.server.get("/admin/reconciliation/2026-03-03").authorization_bearer(&token).await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["swaps"]["created"], 4);
+    assert_eq!(body["discrepancies"][0]["kind"], "provider_trade_count");
+
+    ctx.server
+        .get("/admin/reconciliation/2001-01-01")
+        .authorization_bearer(&token)
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    ctx.server
+        .get("/admin/reconciliation/yesterday")
+        .authorization_bearer(&token)
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    sqlx::query("DELETE FROM reconciliation_reports WHERE day = '2026-03-03'")
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    ctx.cleanup().await;
+}
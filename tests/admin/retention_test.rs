@@ -0,0 +1,115 @@
+use axum::http::StatusCode;
+use serde_json::Value;
+
+use exchange_shared::config::environment::RetentionConfig;
+use exchange_shared::services::retention::RetentionService;
+
+use crate::common::{create_admin_token, create_user_token, delete_swap, insert_swap, TestContext};
+
+async fn insert_old_completed_swap(ctx: &TestContext) -> String {
+    let id = insert_swap(ctx, "completed", None).await;
+    sqlx::query("UPDATE swaps SET completed_at = NOW() - INTERVAL 400 DAY, deposit_extra_id = 'memo-1' WHERE id = ?")
+        .bind(&id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    id
+}
+
+#[tokio::test]
+async fn retention_report_requires_admin() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx.server.get("/admin/retention/report").authorization_bearer(&token).await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn retention_report_is_a_dry_run() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let swap_id = insert_old_completed_swap(&ctx).await;
+
+    let response = ctx.server.get("/admin/retention/report").authorization_bearer(&token).await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+
+    assert_eq!(body["dry_run"], true);
+    let swaps = body["policies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["policy"] == "swap_addresses")
+        .expect("swap_addresses policy missing");
+    assert_eq!(swaps["action"], "scrub");
+    assert!(swaps["rows"].as_u64().unwrap() >= 1);
+
+    let (address, anonymized): (String, Option<chrono::DateTime<chrono::Utc>>) =
+        sqlx::query_as("SELECT deposit_address, anonymized_at FROM swaps WHERE id = ?")
+            .bind(&swap_id)
+            .fetch_one(&ctx.db)
+            .await
+            .unwrap();
+    assert_ne!(address, "redacted");
+    assert!(anonymized.is_none());
+
+    delete_swap(&ctx, &swap_id).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn retention_run_scrubs_addresses_and_keeps_accounting_fields() {
+    let ctx = TestContext::new().await;
+    let old = insert_old_completed_swap(&ctx).await;
+    let recent = insert_swap(&ctx, "completed", None).await;
+    let in_flight = insert_swap(&ctx, "waiting", None).await;
+    sqlx::query("UPDATE swaps SET created_at = NOW() - INTERVAL 400 DAY WHERE id = ?")
+        .bind(&in_flight)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    let config = RetentionConfig {
+        swap_days: 365,
+        analytics_days: 0,
+        ..RetentionConfig::default()
+    };
+    let report = RetentionService::new(ctx.db.clone(), config).run().await.expect("retention run failed");
+    assert!(!report.dry_run);
+    assert!(report.total_rows >= 1);
+
+    let addresses: (String, String, Option<String>, Option<String>) = sqlx::query_as(
+        "SELECT deposit_address, recipient_address, refund_address, deposit_extra_id FROM swaps WHERE id = ?",
+    )
+    .bind(&old)
+    .fetch_one(&ctx.db)
+    .await
+    .unwrap();
+    assert_eq!(addresses, ("redacted".to_string(), "redacted".to_string(), None, None));
+
+    let (amount, status, anonymized): (f64, String, Option<chrono::DateTime<chrono::Utc>>) =
+        sqlx::query_as("SELECT CAST(amount AS DOUBLE), status, anonymized_at FROM swaps WHERE id = ?")
+            .bind(&old)
+            .fetch_one(&ctx.db)
+            .await
+            .unwrap();
+    assert_eq!(amount, 0.001);
+    assert_eq!(status, "completed");
+    assert!(anonymized.is_some());
+
+    for untouched in [&recent, &in_flight] {
+        let address: String = sqlx::query_scalar("SELECT deposit_address FROM swaps WHERE id = ?")
+            .bind(untouched)
+            .fetch_one(&ctx.db)
+            .await
+            .unwrap();
+        assert_ne!(address, "redacted");
+    }
+
+    for id in [&old, &recent, &in_flight] {
+        delete_swap(&ctx, id).await;
+    }
+}
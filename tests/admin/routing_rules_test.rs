@@ -0,0 +1,158 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use exchange_shared::config::environment::HighValueConfig;
+use exchange_shared::modules::swap::schema::RateType;
+use exchange_shared::services::routing::{dry_run, evaluate, RoutingDryRunRequest, RoutingRule, RoutingRuleInput, RoutingScope};
+
+use crate::common::{create_admin_token, create_user_token, TestContext};
+
+fn rule(id: u64, body: Value) -> RoutingRule {
+    let input: RoutingRuleInput = serde_json::from_value(body).unwrap();
+    input.normalized().preview(id)
+}
+
+fn scope<'a>(from: &'a str, to: &'a str, usd_value: Option<f64>, country: Option<&'a str>) -> RoutingScope<'a> {
+    RoutingScope { from, to, usd_value, country }
+}
+
+#[test]
+fn block_applies_to_pairs_touching_the_currency() {
+    let rules = [rule(1, json!({ "name": "No XMR at exolix", "currencies": "XMR", "provider": "exolix", "action": "block" }))];
+
+    let decision = evaluate(&rules, &scope("btc", "xmr", None, None));
+    assert_eq!(decision.matched, vec![1]);
+    assert!(!decision.allows("Exolix"));
+    assert!(decision.allows("changenow"));
+
+    assert!(evaluate(&rules, &scope("btc", "eth", None, None)).allows("exolix"));
+}
+
+#[test]
+fn amount_and_country_narrow_a_rule() {
+    let rules = [
+        rule(1, json!({ "name": "Large stablecoin swaps", "currencies": "usdt,usdc", "min_usd": 5000, "action": "prefer_fixed" })),
+        rule(2, json!({ "name": "EU away from fixedfloat", "countries": "eu", "provider": "fixedfloat", "action": "block" })),
+    ];
+
+    assert_eq!(evaluate(&rules, &scope("usdt", "btc", Some(6000.0), None)).rate_type, Some(RateType::Fixed));
+    assert_eq!(evaluate(&rules, &scope("usdt", "btc", Some(100.0), None)).rate_type, None);
+    // Without a reference price the amount is unknown, so min_usd never matches
+    assert_eq!(evaluate(&rules, &scope("usdt", "btc", None, None)).rate_type, None);
+
+    assert!(!evaluate(&rules, &scope("btc", "eth", None, Some("DE"))).allows("fixedfloat"));
+    assert!(evaluate(&rules, &scope("btc", "eth", None, Some("US"))).allows("fixedfloat"));
+    assert!(evaluate(&rules, &scope("btc", "eth", None, None)).allows("fixedfloat"));
+}
+
+#[test]
+fn most_specific_rate_type_rule_wins() {
+    let rules = [
+        rule(1, json!({ "name": "Fixed for USDT", "currencies": "usdt", "action": "prefer_fixed" })),
+        rule(2, json!({ "name": "Floating everywhere", "action": "prefer_floating" })),
+        rule(3, json!({ "name": "Disabled", "currencies": "usdt", "action": "prefer_floating", "enabled": false })),
+    ];
+
+    let decision = evaluate(&rules, &scope("usdt", "btc", None, None));
+    assert_eq!(decision.matched, vec![1, 2]);
+    assert_eq!(decision.rate_type, Some(RateType::Fixed));
+    assert_eq!(evaluate(&rules, &scope("btc", "eth", None, None)).rate_type, Some(RateType::Floating));
+}
+
+#[test]
+fn invalid_rule_inputs_are_rejected() {
+    let cases = [
+        json!({ "name": "", "provider": "exolix", "action": "block" }),
+        json!({ "name": "Block nobody", "action": "block" }),
+        json!({ "name": "Fixed at a provider", "provider": "exolix", "action": "prefer_fixed" }),
+        json!({ "name": "Negative", "min_usd": -1.0, "action": "prefer_fixed" }),
+        json!({ "name": "Bad country", "countries": "Germany", "provider": "exolix", "action": "block" }),
+    ];
+    for body in cases {
+        let input: RoutingRuleInput = serde_json::from_value(body.clone()).unwrap();
+        assert!(input.normalized().check_rules().is_err(), "{} should be rejected", body);
+    }
+}
+
+#[test]
+fn dry_run_reports_each_provider() {
+    let rules = [
+        rule(1, json!({ "name": "No XMR at exolix", "currencies": "xmr", "provider": "exolix", "action": "block" })),
+        rule(2, json!({ "name": "Prefer changenow", "provider": "changenow", "action": "prefer" })),
+    ];
+    let request: RoutingDryRunRequest = serde_json::from_value(json!({
+        "from": "btc",
+        "to": "xmr",
+        "amount": 0.1,
+        "providers": ["exolix", "changenow", "fixedfloat"]
+    }))
+    .unwrap();
+
+    let response = dry_run(&rules, &request, &HighValueConfig::default());
+
+    assert_eq!(response.matched.len(), 2);
+    let routes: Vec<(&str, bool, bool)> =
+        response.providers.iter().map(|p| (p.provider.as_str(), p.allowed, p.preferred)).collect();
+    assert_eq!(routes, vec![("exolix", false, false), ("changenow", true, true), ("fixedfloat", true, false)]);
+}
+
+#[tokio::test]
+async fn routing_rule_endpoints_require_admin() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx.server.get("/admin/routing-rules").authorization_bearer(&token).await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn routing_rule_lifecycle() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    // Scoped to a random ticker so the rule never routes other tests' quotes
+    let ticker = format!("tr{}", &uuid::Uuid::new_v4().simple().to_string()[..8]);
+
+    let response = ctx
+        .server
+        .post("/admin/routing-rules")
+        .authorization_bearer(&token)
+        .json(&json!({
+            "name": "Keep test pair off exolix",
+            "currencies": ticker.to_uppercase(),
+            "provider": "exolix",
+            "action": "block"
+        }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let rule: Value = response.json();
+    let id = rule["id"].as_u64().expect("rule id");
+    assert_eq!(rule["currencies"], ticker.as_str(), "tickers are stored lowercase");
+    assert_eq!(rule["enabled"], true);
+
+    let response = ctx
+        .server
+        .post("/admin/routing-rules/dry-run")
+        .authorization_bearer(&token)
+        .json(&json!({ "from": ticker, "to": "btc", "providers": ["exolix"] }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["providers"][0]["allowed"], false);
+
+    let path = format!("/admin/routing-rules/{}", id);
+    let response = ctx
+        .server
+        .put(&path)
+        .authorization_bearer(&token)
+        .json(&json!({ "name": "Disabled", "currencies": ticker, "provider": "exolix", "action": "block", "enabled": false }))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Value>()["enabled"], false);
+
+    ctx.server.delete(&path).authorization_bearer(&token).await.assert_status_ok();
+    ctx.server.delete(&path).authorization_bearer(&token).await.assert_status(StatusCode::NOT_FOUND);
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,60 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{create_admin_token, TestContext};
+use exchange_shared::modules::swap::schema::{TrocadorCurrency, TrocadorTradeResponse};
+use exchange_shared::services::schema_drift;
+
+#[tokio::test]
+async fn schema_drift_requires_admin() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/admin/providers/schema-drift").await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn unknown_fields_and_fallbacks_are_reported() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+
+    let currencies = vec![
+        json!({
+            "name": "Bitcoin", "ticker": "btc", "network": "Mainnet", "memo": false,
+            "image": "", "minimum": 0.0001, "maximum": 10.0, "drift_test_new_field": true
+        }),
+        // `maximum` renamed upstream: dropped in lenient mode
+        json!({
+            "name": "Monero", "ticker": "xmr", "network": "Mainnet", "memo": false,
+            "image": "", "minimum": 0.01, "max": 100.0
+        }),
+    ];
+    let parsed = schema_drift::parse_list::<TrocadorCurrency>("drift_test.coins", currencies).unwrap();
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(parsed[0].ticker, "btc");
+
+    let trade = json!({ "trade_id": "abc", "status": "waiting" });
+    assert!(schema_drift::parse_one::<TrocadorTradeResponse>("drift_test.trade", trade).is_err());
+
+    let response = ctx
+        .server
+        .get("/admin/providers/schema-drift")
+        .authorization_bearer(&token)
+        .await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["drift_detected"], true);
+    let endpoints = body["endpoints"].as_array().unwrap();
+
+    let coins = endpoints.iter().find(|e| e["endpoint"] == "drift_test.coins").unwrap();
+    assert_eq!(coins["unknown_fields"]["drift_test_new_field"], 1);
+    assert_eq!(coins["fallbacks"], 1);
+
+    let trade = endpoints.iter().find(|e| e["endpoint"] == "drift_test.trade").unwrap();
+    assert_eq!(trade["failures"], 1);
+    assert!(trade["last_error"].as_str().unwrap().contains("missing field"));
+
+    ctx.cleanup().await;
+}
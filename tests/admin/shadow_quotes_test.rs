@@ -0,0 +1,81 @@
+use axum::http::StatusCode;
+use serde_json::Value;
+
+use crate::common::{create_admin_token, create_user_token, TestContext};
+
+// Rows are recorded under a made-up aggregator so other tests' rows don't count
+async fn insert_shadow_quote(
+    ctx: &TestContext,
+    aggregator: &str,
+    best: Option<f64>,
+    served: Option<f64>,
+    error: Option<&str>,
+) {
+    sqlx::query(
+        "INSERT INTO shadow_quotes (
+            aggregator, from_currency, from_network, to_currency, to_network, amount, succeeded, error,
+            quote_count, best_provider, best_amount, served_provider, served_amount, latency_ms
+        ) VALUES (?, 'btc', 'Mainnet', 'xmr', 'Mainnet', 1, ?, ?, ?, 'ShadowSwap', ?, 'ChangeNOW', ?, 200)",
+    )
+    .bind(aggregator)
+    .bind(error.is_none())
+    .bind(error)
+    .bind(if best.is_some() { 1 } else { 0 })
+    .bind(best)
+    .bind(served)
+    .execute(&ctx.db)
+    .await
+    .expect("Failed to insert shadow quote");
+}
+
+#[tokio::test]
+async fn shadow_quotes_require_admin() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx.server.get("/admin/providers/shadow-quotes").authorization_bearer(&token).await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn shadow_quotes_are_summarized_per_aggregator() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let aggregator = format!("shadow-{}", &uuid::Uuid::new_v4().simple().to_string()[..8]);
+
+    insert_shadow_quote(&ctx, &aggregator, Some(110.0), Some(100.0), None).await; // +10%
+    insert_shadow_quote(&ctx, &aggregator, Some(95.0), Some(100.0), None).await; // -5%
+    insert_shadow_quote(&ctx, &aggregator, None, Some(100.0), None).await;
+    insert_shadow_quote(&ctx, &aggregator, None, Some(100.0), Some("timeout")).await;
+
+    let response = ctx
+        .server
+        .get("/admin/providers/shadow-quotes?hours=1")
+        .authorization_bearer(&token)
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["hours"], 1);
+
+    let summary = body["aggregators"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|a| a["aggregator"] == aggregator.as_str())
+        .expect("aggregator missing from report");
+    assert_eq!(summary["requests"], 4);
+    assert_eq!(summary["failures"], 1);
+    assert_eq!(summary["no_quotes"], 1);
+    assert_eq!(summary["compared"], 2);
+    assert_eq!(summary["beat_served"], 1);
+    assert!((summary["avg_diff_percent"].as_f64().unwrap() - 2.5).abs() < 1e-6);
+
+    sqlx::query("DELETE FROM shadow_quotes WHERE aggregator = ?")
+        .bind(&aggregator)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    ctx.cleanup().await;
+}
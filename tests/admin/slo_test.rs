@@ -0,0 +1,147 @@
+use axum::http::StatusCode;
+use exchange_shared::config::environment::{SloConfig, SloObjective};
+use exchange_shared::services::slo::SloTracker;
+use serde_json::Value;
+use std::time::Duration;
+
+use crate::common::{create_admin_token, TestContext};
+
+fn tracker(objectives: &str) -> SloTracker {
+    SloTracker::new(&SloConfig {
+        objectives: objectives.split(',').map(|o| o.parse().unwrap()).collect(),
+        ..SloConfig::default()
+    })
+}
+
+#[test]
+fn slo_objectives_parse_from_config() {
+    let objective: SloObjective = "get /swap/rates=p95:800ms".parse().unwrap();
+    assert_eq!(objective.name(), "GET /swap/rates");
+    assert_eq!(objective.percentile, 95.0);
+    assert_eq!(objective.threshold, Duration::from_millis(800));
+
+    let objective: SloObjective = "POST /swap/create = p99.9:2000".parse().unwrap();
+    assert_eq!(objective.percentile, 99.9);
+
+    assert!("GET /swap/rates".parse::<SloObjective>().is_err());
+    assert!("GET /swap/rates=95:800".parse::<SloObjective>().is_err());
+    assert!("GET /swap/rates=p100:800".parse::<SloObjective>().is_err());
+    assert!("/swap/rates=p95:800".parse::<SloObjective>().is_err());
+}
+
+#[test]
+fn compliance_and_error_budget_follow_slow_and_failed_requests() {
+    let slo = tracker("GET /swap/rates=p90:800");
+
+    for _ in 0..17 {
+        slo.record("GET", "/swap/rates", Duration::from_millis(100), false);
+    }
+    slo.record("GET", "/swap/rates", Duration::from_millis(900), false); // Too slow
+    slo.record("GET", "/swap/rates", Duration::from_millis(50), true); // Server error
+    slo.record("GET", "/swap/rates", Duration::from_millis(800), false); // At the threshold is fine
+    // Other routes and methods have no SLO
+    slo.record("POST", "/swap/rates", Duration::from_secs(5), false);
+    slo.record("GET", "/swap/{id}", Duration::from_secs(5), false);
+
+    let report = slo.report();
+    assert_eq!(report.slos.len(), 1);
+    let status = &report.slos[0];
+    assert_eq!(status.name, "GET /swap/rates");
+    assert_eq!(status.requests, 20);
+    assert_eq!(status.bad_requests, 2);
+    assert_eq!(status.compliance_percent, Some(90.0));
+    assert!(status.compliant);
+    assert_eq!(status.error_budget_remaining_percent, Some(0.0));
+    assert_eq!(status.burn_rate_1h, Some(1.0));
+    assert_eq!(status.burn_rate_5m, Some(1.0));
+}
+
+#[test]
+fn unused_slo_is_reported_without_figures() {
+    let report = tracker("POST /swap/create=p95:2000").report();
+
+    let status = &report.slos[0];
+    assert_eq!(status.requests, 0);
+    assert_eq!(status.compliance_percent, None);
+    assert_eq!(status.burn_rate_1h, None);
+    assert!(status.compliant);
+    assert!(!status.alerting);
+}
+
+#[test]
+fn fast_burn_alerts_once_until_recovered() {
+    let slo = tracker("POST /swap/create=p95:2000");
+
+    // Below the minimum traffic nothing alerts, however bad
+    for _ in 0..5 {
+        slo.record("POST", "/swap/create", Duration::from_secs(3), false);
+    }
+    assert!(slo.check().is_empty());
+
+    for _ in 0..20 {
+        slo.record("POST", "/swap/create", Duration::from_secs(3), false);
+    }
+    let alerts = slo.check();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].name, "POST /swap/create");
+    assert_eq!(alerts[0].burn_rate_1h, 20.0);
+    assert!(slo.report().slos[0].alerting);
+    assert!(slo.check().is_empty(), "An ongoing burn is alerted on once");
+
+    // Enough good traffic brings the burn rate under the limit
+    for _ in 0..200 {
+        slo.record("POST", "/swap/create", Duration::from_millis(300), false);
+    }
+    assert!(slo.check().is_empty());
+    assert!(!slo.report().slos[0].alerting);
+
+    for _ in 0..600 {
+        slo.record("POST", "/swap/create", Duration::from_secs(3), false);
+    }
+    assert_eq!(slo.check().len(), 1);
+}
+
+#[test]
+fn disabled_tracker_has_no_slos() {
+    let slo = SloTracker::new(&SloConfig {
+        enabled: false,
+        objectives: vec!["GET /swap/rates=p95:800".parse().unwrap()],
+        ..SloConfig::default()
+    });
+
+    slo.record("GET", "/swap/rates", Duration::from_secs(5), false);
+
+    assert!(slo.report().slos.is_empty());
+}
+
+#[tokio::test]
+async fn slo_report_requires_admin() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/admin/slo").await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn slo_report_counts_requests_by_route() {
+    std::env::set_var("SLOS", "GET /health=p95:5000,GET /swap/{id}=p95:5000");
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+
+    ctx.server.get("/health").await.assert_status_ok();
+    ctx.server.get("/health").await.assert_status_ok();
+
+    let response = ctx.server.get("/admin/slo").authorization_bearer(&token).await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["window_hours"], 24);
+    let slos = body["slos"].as_array().unwrap();
+    let health = slos.iter().find(|s| s["name"] == "GET /health").unwrap();
+    assert_eq!(health["requests"], 2);
+    assert_eq!(health["compliance_percent"], 100.0);
+    // Matched by route, not by the requested path
+    let status = slos.iter().find(|s| s["name"] == "GET /swap/{id}").unwrap();
+    assert_eq!(status["requests"], 0);
+}
@@ -0,0 +1,92 @@
+use axum::http::StatusCode;
+use serde_json::Value;
+
+use crate::common::{create_admin_token, TestContext};
+
+async fn insert_sync_run(ctx: &TestContext, kind: &str, status: &str) -> i64 {
+    sqlx::query(
+        "INSERT INTO sync_runs (kind, status, rows_fetched, rows_changed, duration_ms, started_at, finished_at)
+         VALUES (?, ?, 10, 2, 150, NOW() + INTERVAL 1 MINUTE, NOW() + INTERVAL 1 MINUTE)"
+    )
+    .bind(kind)
+    .bind(status)
+    .execute(&ctx.db)
+    .await
+    .unwrap()
+    .last_insert_id() as i64
+}
+
+async fn delete_sync_run(ctx: &TestContext, id: i64) {
+    sqlx::query("DELETE FROM sync_runs WHERE id = ?")
+        .bind(id)
+        .execute(&ctx.db)
+        .await
+        .ok();
+}
+
+#[tokio::test]
+async fn sync_status_requires_authentication() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/admin/sync/status").await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn sync_status_returns_latest_run_per_kind() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let run_id = insert_sync_run(&ctx, "providers", "timeout").await;
+
+    let response = ctx
+        .server
+        .get("/admin/sync/status")
+        .authorization_bearer(&token)
+        .await;
+    response.assert_status_ok();
+
+    let body: Value = response.json();
+    assert!(body.get("currencies").is_some());
+    assert_eq!(body["providers"]["id"], run_id);
+    assert_eq!(body["providers"]["status"], "timeout");
+    assert_eq!(body["providers"]["rows_fetched"], 10);
+
+    delete_sync_run(&ctx, run_id).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn ready_reports_database_and_sync_status() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/ready").await;
+    response.assert_status_ok();
+
+    let body: Value = response.json();
+    assert_eq!(body["status"], "ready");
+    assert_eq!(body["database"], true);
+    assert!(body.get("sync").is_some());
+}
+
+#[tokio::test]
+async fn sync_status_includes_pairs_runs() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let run_id = insert_sync_run(&ctx, "pairs", "success").await;
+
+    let response = ctx
+        .server
+        .get("/admin/sync/status")
+        .authorization_bearer(&token)
+        .await;
+    response.assert_status_ok();
+
+    let body: Value = response.json();
+    assert_eq!(body["pairs"]["id"], run_id);
+    assert_eq!(body["pairs"]["kind"], "pairs");
+    assert_eq!(body["pairs"]["rows_changed"], 2);
+
+    delete_sync_run(&ctx, run_id).await;
+    ctx.cleanup().await;
+}
@@ -0,0 +1,161 @@
+use axum::http::StatusCode;
+use exchange_shared::services::tenant::{TenantId, DEFAULT_TENANT};
+use serde_json::{json, Value};
+
+use crate::common::{create_admin_token, delete_swap, insert_swap, test_email, test_password, TestContext};
+
+// =============================================================================
+// UNIT TESTS - TENANT IDS
+// =============================================================================
+
+#[test]
+fn tenant_ids_are_validated() {
+    assert_eq!(TenantId::new(" partner-1 ").unwrap().as_str(), "partner-1");
+    assert!(TenantId::new("").is_err());
+    assert!(TenantId::new("Partner").is_err());
+    assert!(TenantId::new("partner_1").is_err());
+    assert!(TenantId::new(&"a".repeat(51)).is_err());
+
+    assert!(serde_json::from_value::<TenantId>(json!("Not Valid")).is_err());
+    assert_eq!(serde_json::to_value(TenantId::new("acme").unwrap()).unwrap(), json!("acme"));
+}
+
+#[test]
+fn default_tenant_keeps_cache_keys() {
+    let default = TenantId::default();
+    assert_eq!(default.as_str(), DEFAULT_TENANT);
+    assert_eq!(default.cache_key("fee_rules:all"), "fee_rules:all");
+
+    let partner = TenantId::new("acme").unwrap();
+    assert_eq!(partner.cache_key("fee_rules:all"), "tenant:acme:fee_rules:all");
+}
+
+// =============================================================================
+// INTEGRATION TESTS - TENANT ISOLATION
+// =============================================================================
+
+/// A brand in its own tenant, matched by the returned API key
+async fn partner_brand(ctx: &TestContext, admin_token: &str) -> (String, TenantId, String) {
+    let suffix = &uuid::Uuid::new_v4().simple().to_string()[..8];
+    let slug = format!("tenant-{}", suffix);
+    let tenant = TenantId::new(&format!("t-{}", suffix)).unwrap();
+    let api_key = format!("tenant-key-{}", uuid::Uuid::new_v4().simple());
+
+    let response = ctx
+        .server
+        .put(&format!("/admin/brands/{}", slug))
+        .authorization_bearer(admin_token)
+        .json(&json!({
+            "name": "Tenant Partner",
+            "support_email": "help@tenant.example",
+            "tenant": tenant,
+            "api_key": api_key
+        }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["tenant"], tenant.as_str());
+
+    (slug, tenant, api_key)
+}
+
+#[tokio::test]
+async fn users_are_scoped_to_their_tenant() {
+    let ctx = TestContext::new().await;
+    let admin_token = create_admin_token(&ctx).await;
+    let (slug, _, api_key) = partner_brand(&ctx, &admin_token).await;
+    let email = test_email();
+    let register = json!({ "email": email, "password": test_password(), "password_confirm": test_password() });
+    let login = json!({ "email": email, "password": test_password() });
+
+    let response = ctx.server.post("/auth/register").json(&register).await;
+    response.assert_status(StatusCode::CREATED);
+
+    // The same address registers separately with the partner
+    let response = ctx.server.post("/auth/register").add_header("x-api-key", api_key.as_str()).json(&register).await;
+    response.assert_status(StatusCode::CREATED);
+    let response = ctx.server.post("/auth/register").add_header("x-api-key", api_key.as_str()).json(&register).await;
+    response.assert_status(StatusCode::CONFLICT);
+
+    let other_email = test_email();
+    let response = ctx
+        .server
+        .post("/auth/register")
+        .add_header("x-api-key", api_key.as_str())
+        .json(&json!({ "email": other_email, "password": test_password(), "password_confirm": test_password() }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let response = ctx.server.post("/auth/login").json(&json!({ "email": other_email, "password": test_password() })).await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+
+    let response = ctx.server.post("/auth/login").add_header("x-api-key", api_key.as_str()).json(&login).await;
+    response.assert_status_ok();
+
+    ctx.server.delete(&format!("/admin/brands/{}", slug)).authorization_bearer(&admin_token).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn swaps_of_another_tenant_are_not_found() {
+    let ctx = TestContext::new().await;
+    let admin_token = create_admin_token(&ctx).await;
+    let (slug, tenant, api_key) = partner_brand(&ctx, &admin_token).await;
+    let swap_id = insert_swap(&ctx, "completed", None).await;
+    sqlx::query("UPDATE swaps SET tenant_id = ? WHERE id = ?")
+        .bind(tenant.as_str())
+        .bind(&swap_id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    let path = format!("/swap/{}", swap_id);
+    let response = ctx.server.get(&path).await;
+    response.assert_status(StatusCode::NOT_FOUND);
+
+    let response = ctx.server.get(&path).add_header("x-api-key", api_key.as_str()).await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["swap_id"], swap_id.as_str());
+    assert!(body.get("tenant").is_none(), "the tenant is not served");
+
+    delete_swap(&ctx, &swap_id).await;
+    ctx.server.delete(&format!("/admin/brands/{}", slug)).authorization_bearer(&admin_token).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn fee_rules_are_scoped_to_a_tenant() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let tenant = format!("t-{}", &uuid::Uuid::new_v4().simple().to_string()[..8]);
+
+    let response = ctx
+        .server
+        .post(&format!("/admin/fee-rules?tenant={}", tenant))
+        .authorization_bearer(&token)
+        .json(&json!({ "from_currency": "tfscope", "fee_percent": 0.3 }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let rule: Value = response.json();
+    let id = rule["id"].as_u64().expect("rule id");
+    assert_eq!(rule["tenant_id"], tenant.as_str());
+
+    let response = ctx.server.get("/admin/fee-rules").authorization_bearer(&token).await;
+    let rules: Vec<Value> = response.json();
+    assert!(!rules.iter().any(|r| r["id"] == id), "other tenants' rules are not listed");
+
+    let response = ctx.server.get(&format!("/admin/fee-rules?tenant={}", tenant)).authorization_bearer(&token).await;
+    let rules: Vec<Value> = response.json();
+    assert!(rules.iter().any(|r| r["id"] == id));
+
+    let response = ctx.server.delete(&format!("/admin/fee-rules/{}", id)).authorization_bearer(&token).await;
+    response.assert_status(StatusCode::NOT_FOUND);
+    let response = ctx
+        .server
+        .delete(&format!("/admin/fee-rules/{}?tenant={}", id, tenant))
+        .authorization_bearer(&token)
+        .await;
+    response.assert_status_ok();
+
+    ctx.cleanup().await;
+}
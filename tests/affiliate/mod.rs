@@ -0,0 +1 @@
+mod referrals_test;
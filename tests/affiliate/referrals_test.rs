@@ -0,0 +1,210 @@
+use axum::http::StatusCode;
+use chrono::Utc;
+use exchange_shared::modules::affiliate::crud::commission_balances;
+use exchange_shared::modules::affiliate::model::Affiliate;
+use exchange_shared::modules::affiliate::schema::{check_ref_code, RecordPayoutRequest, UpsertAffiliateRequest};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+
+use crate::common::{amount, create_admin_token, create_user_token, delete_swap, insert_swap, TestContext};
+
+const API_KEY: &str = "affiliate-test-key-0123456789abcdef";
+
+fn affiliate(commission_percent: Decimal) -> Affiliate {
+    Affiliate {
+        ref_code: "partner".to_string(),
+        tenant_id: "default".to_string(),
+        name: "Partner".to_string(),
+        commission_percent,
+        is_active: true,
+        has_api_key: true,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn unique_ref_code() -> String {
+    format!("ref-{}", &uuid::Uuid::new_v4().simple().to_string()[..8])
+}
+
+async fn create_affiliate(ctx: &TestContext, admin_token: &str, ref_code: &str, api_key: &str) -> Value {
+    let response = ctx
+        .server
+        .put(&format!("/admin/affiliates/{}", ref_code))
+        .authorization_bearer(admin_token)
+        .json(&json!({ "name": "Test Partner", "commission_percent": 25.0, "api_key": api_key }))
+        .await;
+    response.assert_status_ok();
+    response.json()
+}
+
+async fn delete_affiliate(ctx: &TestContext, ref_code: &str) {
+    sqlx::query("DELETE FROM affiliate_payouts WHERE affiliate = ?").bind(ref_code).execute(&ctx.db).await.ok();
+    sqlx::query("DELETE FROM affiliates WHERE ref_code = ?").bind(ref_code).execute(&ctx.db).await.ok();
+}
+
+async fn attribute_swap(ctx: &TestContext, swap_id: &str, ref_code: &str, commission: Decimal) {
+    sqlx::query("UPDATE swaps SET affiliate = ?, affiliate_commission = ? WHERE id = ?")
+        .bind(ref_code)
+        .bind(commission)
+        .bind(swap_id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+}
+
+// =============================================================================
+// UNIT TESTS - COMMISSION
+// =============================================================================
+
+#[test]
+fn test_commission_is_a_share_of_the_platform_fee() {
+    assert_eq!(affiliate(dec!(25)).commission_on(dec!(0.004)), dec!(0.001));
+    assert_eq!(affiliate(dec!(0)).commission_on(dec!(0.004)), dec!(0));
+    assert_eq!(affiliate(dec!(100)).commission_on(dec!(0.004)), dec!(0.004));
+    // Rounded down to 8 decimals, never negative
+    assert_eq!(affiliate(dec!(33)).commission_on(dec!(0.00000001)), dec!(0));
+    assert_eq!(affiliate(dec!(50)).commission_on(dec!(-1)), dec!(0));
+    assert_eq!(affiliate(dec!(12.5)).commission_on(dec!(0.00000123)), dec!(0.00000015));
+}
+
+#[test]
+fn test_balances_merge_earned_and_paid_per_currency() {
+    let earned = vec![("xmr".to_string(), dec!(1.5)), ("BTC".to_string(), dec!(0.01))];
+    let paid = vec![("xmr".to_string(), dec!(1)), ("eth".to_string(), dec!(0.2))];
+
+    let balances = commission_balances(&earned, &paid);
+    let currencies: Vec<_> = balances.iter().map(|b| b.currency.as_str()).collect();
+    assert_eq!(currencies, ["btc", "eth", "xmr"]);
+    assert_eq!(balances[0].owed, dec!(0.01));
+    assert_eq!(balances[1].owed, dec!(-0.2));
+    assert_eq!(balances[2].owed, dec!(0.5));
+}
+
+// =============================================================================
+// UNIT TESTS - ADMIN REQUESTS
+// =============================================================================
+
+#[test]
+fn test_ref_codes_are_lowercase_slugs() {
+    assert!(check_ref_code("partner-1").is_ok());
+    assert!(check_ref_code("").is_err());
+    assert!(check_ref_code("Partner").is_err());
+    assert!(check_ref_code("part ner").is_err());
+    assert!(check_ref_code(&"a".repeat(33)).is_err());
+}
+
+#[test]
+fn test_upsert_request_rules() {
+    let request: UpsertAffiliateRequest =
+        serde_json::from_value(json!({ "name": "Partner", "commission_percent": 20.0 })).unwrap();
+    assert!(request.is_active);
+    assert!(request.check_rules("partner").is_ok());
+
+    let over = UpsertAffiliateRequest { commission_percent: dec!(101), ..request.clone() };
+    assert!(over.check_rules("partner").is_err());
+    let short_key = UpsertAffiliateRequest { api_key: Some("short".to_string()), ..request.clone() };
+    assert!(short_key.check_rules("partner").is_err());
+    let unnamed = UpsertAffiliateRequest { name: " ".to_string(), ..request };
+    assert!(unnamed.check_rules("partner").is_err());
+}
+
+#[test]
+fn test_payout_amount_must_be_positive() {
+    let payout = |amount: Decimal| RecordPayoutRequest { currency: "xmr".to_string(), amount, tx_hash: None, note: None };
+    assert!(payout(dec!(0.5)).check_rules().is_ok());
+    assert!(payout(dec!(0)).check_rules().is_err());
+    assert!(payout(dec!(-0.5)).check_rules().is_err());
+    // affiliate_payouts.amount keeps 8 places
+    assert!(payout(dec!(0.000000001)).check_rules().is_err());
+}
+
+// =============================================================================
+// INTEGRATION TESTS - PARTNER ENDPOINTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_partner_endpoints_require_an_affiliate_key() {
+    let ctx = TestContext::new().await;
+
+    ctx.server.get("/affiliate/stats").await.assert_status(StatusCode::UNAUTHORIZED);
+    ctx.server
+        .get("/affiliate/payouts")
+        .add_header("x-api-key", "not-an-affiliate-key-000000000000")
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_admin_affiliates_require_admin() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx.server.get("/admin/affiliates").authorization_bearer(&token).await;
+    response.assert_status(StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_stats_count_completed_commission_and_payouts() {
+    let ctx = TestContext::new().await;
+    let admin = create_admin_token(&ctx).await;
+    let ref_code = unique_ref_code();
+    let api_key = format!("{}-{}", API_KEY, ref_code);
+
+    let created = create_affiliate(&ctx, &admin, &ref_code, &api_key).await;
+    assert_eq!(created["has_api_key"], true);
+    assert!(created.get("api_key_hash").is_none());
+
+    let completed = insert_swap(&ctx, "completed", None).await;
+    let waiting = insert_swap(&ctx, "waiting", None).await;
+    let failed = insert_swap(&ctx, "failed", None).await;
+    attribute_swap(&ctx, &completed, &ref_code, dec!(0.002)).await;
+    attribute_swap(&ctx, &waiting, &ref_code, dec!(0.003)).await;
+    attribute_swap(&ctx, &failed, &ref_code, dec!(0.004)).await;
+
+    let response = ctx
+        .server
+        .post(&format!("/admin/affiliates/{}/payouts", ref_code))
+        .authorization_bearer(&admin)
+        .json(&json!({ "currency": "XMR", "amount": 0.0015, "tx_hash": "abc123" }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+
+    let response = ctx.server.get("/affiliate/stats").add_header("x-api-key", &api_key).await;
+    response.assert_status_ok();
+    let stats: Value = response.json();
+    assert_eq!(stats["ref_code"], ref_code.as_str());
+    assert_eq!(stats["swaps"], 3);
+    assert_eq!(stats["completed_swaps"], 1);
+    assert_eq!(stats["commissions"][0]["currency"], "xmr");
+    assert_eq!(amount(&stats["commissions"][0]["earned"]), dec!(0.002));
+    assert_eq!(amount(&stats["commissions"][0]["pending"]), dec!(0.003));
+    assert_eq!(amount(&stats["balances"][0]["paid"]), dec!(0.0015));
+
+    let response = ctx.server.get("/affiliate/payouts").add_header("x-api-key", &api_key).await;
+    response.assert_status_ok();
+    let payouts: Value = response.json();
+    assert_eq!(payouts["payouts"].as_array().unwrap().len(), 1);
+    assert_eq!(payouts["payouts"][0]["currency"], "xmr");
+    assert_eq!(payouts["payouts"][0]["tx_hash"], "abc123");
+
+    for id in [completed, waiting, failed] {
+        delete_swap(&ctx, &id).await;
+    }
+    delete_affiliate(&ctx, &ref_code).await;
+}
+
+#[tokio::test]
+async fn test_payout_to_unknown_affiliate_is_not_found() {
+    let ctx = TestContext::new().await;
+    let admin = create_admin_token(&ctx).await;
+
+    let response = ctx
+        .server
+        .post(&format!("/admin/affiliates/{}/payouts", unique_ref_code()))
+        .authorization_bearer(&admin)
+        .json(&json!({ "currency": "xmr", "amount": 1.0 }))
+        .await;
+    response.assert_status(StatusCode::NOT_FOUND);
+}
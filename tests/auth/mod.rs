@@ -7,3 +7,4 @@ mod reset_password_test;
 mod two_factor_test;
 mod backup_codes_test;
 mod email_verification_test;
+mod swap_preferences_test;
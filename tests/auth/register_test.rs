@@ -262,9 +262,7 @@ async fn register_handles_concurrent_duplicate_emails() {
         assert_eq!(count.0, 1, "Only one user should exist despite both returning 201");
     } else {
         assert!(
-            (has_created && has_conflict) ||
-            (has_created && has_rate_limited) ||
-            (has_rate_limited),
+            (has_created && has_conflict) || has_rate_limited,
             "Unexpected statuses: {:?}", statuses
         );
     }
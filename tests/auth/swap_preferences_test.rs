@@ -0,0 +1,69 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{create_user_token, TestContext};
+
+#[tokio::test]
+async fn swap_preferences_require_auth() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/auth/swap-preferences").await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn swap_preferences_default_to_any_kyc_rating() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx.server.get("/auth/swap-preferences").authorization_bearer(&token).await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert!(body["max_kyc_rating"].is_null());
+}
+
+#[tokio::test]
+async fn max_kyc_rating_is_saved_and_cleared() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx
+        .server
+        .put("/auth/swap-preferences")
+        .authorization_bearer(&token)
+        .json(&json!({ "max_kyc_rating": "b" }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["max_kyc_rating"], "B");
+
+    let body: Value = ctx.server.get("/auth/swap-preferences").authorization_bearer(&token).await.json();
+    assert_eq!(body["max_kyc_rating"], "B");
+
+    let response = ctx
+        .server
+        .put("/auth/swap-preferences")
+        .authorization_bearer(&token)
+        .json(&json!({ "max_kyc_rating": null }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert!(body["max_kyc_rating"].is_null());
+}
+
+#[tokio::test]
+async fn invalid_max_kyc_rating_is_rejected() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx
+        .server
+        .put("/auth/swap-preferences")
+        .authorization_bearer(&token)
+        .json(&json!({ "max_kyc_rating": "E" }))
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "client")]
+
+mod common;
+
+use exchange_shared::client::{ClientError, ExchangeClient};
+use exchange_shared::modules::auth::schema::{LoginRequest, RegisterRequest};
+use exchange_shared::modules::swap::schema::{CurrenciesQuery, ProvidersQuery};
+
+use common::{test_email, test_password, TestContext};
+
+fn client_for(ctx: &TestContext) -> ExchangeClient {
+    let address = ctx.server.server_address().expect("HTTP transport should expose an address");
+    ExchangeClient::new(address.as_str())
+}
+
+#[tokio::test]
+async fn client_registers_and_logs_in() {
+    let ctx = TestContext::new_http().await;
+    let client = client_for(&ctx);
+    let email = test_email();
+
+    let registered = client
+        .register(&RegisterRequest {
+            email: email.clone(),
+            password: test_password().to_string(),
+            password_confirm: test_password().to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(registered.user.email, email);
+
+    let login = client
+        .login(&LoginRequest {
+            email,
+            password: test_password().to_string(),
+            two_factor_code: None,
+            backup_code: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(login.token_type, "Bearer");
+    assert!(!login.access_token.is_empty());
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn client_decodes_public_listings() {
+    let ctx = TestContext::new_http().await;
+    let client = client_for(&ctx);
+
+    assert!(client.is_ready().await.unwrap());
+
+    let currencies = client
+        .get_currencies(&CurrenciesQuery { ticker: Some("btc".to_string()), ..Default::default() })
+        .await
+        .unwrap();
+    assert!(currencies.iter().all(|c| c.ticker == "btc"));
+
+    let providers = client
+        .get_providers(&ProvidersQuery { rating: None, markup_enabled: None, sort: None })
+        .await
+        .unwrap();
+    assert!(!providers.is_empty());
+}
+
+#[tokio::test]
+async fn client_surfaces_api_errors() {
+    let ctx = TestContext::new_http().await;
+    let client = client_for(&ctx);
+
+    let err = client.get_sync_status().await.unwrap_err();
+
+    match err {
+        ClientError::ApiError { status, .. } => assert_eq!(status.as_u16(), 401),
+        other => panic!("Expected an API error, got {}", other),
+    }
+}
@@ -1,5 +1,6 @@
 use axum_test::TestServer;
 use exchange_shared::services::redis_cache::RedisService;
+use rust_decimal::Decimal;
 use sqlx::{MySql, Pool};
 
 // Allow dead_code for utilities used by other test files
@@ -12,7 +13,18 @@ pub struct TestContext {
 #[allow(dead_code)]
 impl TestContext {
     pub async fn new() -> Self {
+        Self::create(false).await
+    }
+
+    // Same as `new`, but served over a real TCP socket (for the HTTP client tests)
+    pub async fn new_http() -> Self {
+        Self::create(true).await
+    }
+
+    async fn create(http_transport: bool) -> Self {
         dotenvy::dotenv().ok();
+        // Tests drive the status poller directly (see swap::poller_test)
+        std::env::set_var("SWAP_POLLER_ENABLED", "false");
 
         let database_url = std::env::var("TEST_DATABASE_URL")
             .unwrap_or_else(|_| std::env::var("DATABASE_URL").expect("DATABASE_URL must be set"));
@@ -37,7 +49,12 @@ impl TestContext {
         let redis_service = RedisService::new(&redis_url);
 
         let app = exchange_shared::create_app(db.clone(), redis_service, jwt_service).await;
-        let server = TestServer::new(app).expect("Failed to create test server");
+        let server = if http_transport {
+            TestServer::builder().http_transport().build(app)
+        } else {
+            TestServer::new(app)
+        }
+        .expect("Failed to create test server");
 
         Self { server, db }
     }
@@ -86,6 +103,12 @@ pub async fn setup_test_server() -> TestServer {
     ctx.server
 }
 
+// Helper to read an amount, which the API writes as a decimal string
+#[allow(dead_code)]
+pub fn amount(value: &serde_json::Value) -> Decimal {
+    value.as_str().and_then(|s| s.parse().ok()).unwrap_or_else(|| panic!("not a decimal string: {}", value))
+}
+
 // Helper to measure and print request duration
 #[allow(dead_code)]
 pub async fn timed_get(server: &TestServer, path: &str) -> axum_test::TestResponse {
@@ -104,3 +127,127 @@ pub async fn timed_post<T: serde::Serialize>(server: &TestServer, path: &str, bo
     println!("⏱️ POST {} took {:?}", path, duration);
     response
 }
+
+// Helper to register a user, promote it to admin and return an access token
+#[allow(dead_code)]
+pub async fn create_admin_token(ctx: &TestContext) -> String {
+    let email = test_email();
+
+    ctx.server
+        .post("/auth/register")
+        .json(&serde_json::json!({
+            "email": &email,
+            "password": test_password(),
+            "password_confirm": test_password()
+        }))
+        .await;
+
+    sqlx::query("UPDATE users SET role = 'admin' WHERE email = ?")
+        .bind(&email)
+        .execute(&ctx.db)
+        .await
+        .expect("Failed to promote admin");
+
+    let response = ctx
+        .server
+        .post("/auth/login")
+        .json(&serde_json::json!({
+            "email": &email,
+            "password": test_password()
+        }))
+        .await;
+
+    let body: serde_json::Value = response.json();
+    body["access_token"].as_str().unwrap().to_string()
+}
+
+// Helper to register a regular user and return (user_id, access_token)
+#[allow(dead_code)]
+pub async fn create_user_token(ctx: &TestContext) -> (String, String) {
+    let email = test_email();
+
+    ctx.server
+        .post("/auth/register")
+        .json(&serde_json::json!({
+            "email": &email,
+            "password": test_password(),
+            "password_confirm": test_password()
+        }))
+        .await;
+
+    let (user_id,): (String,) = sqlx::query_as("SELECT id FROM users WHERE email = ?")
+        .bind(&email)
+        .fetch_one(&ctx.db)
+        .await
+        .expect("Failed to find registered user");
+
+    let response = ctx
+        .server
+        .post("/auth/login")
+        .json(&serde_json::json!({
+            "email": &email,
+            "password": test_password()
+        }))
+        .await;
+
+    let body: serde_json::Value = response.json();
+    (user_id, body["access_token"].as_str().unwrap().to_string())
+}
+
+// Helper to insert a throwaway Mainnet currency row, returning its id
+#[allow(dead_code)]
+pub async fn insert_currency(ctx: &TestContext, symbol: &str) -> i64 {
+    sqlx::query(
+        "INSERT INTO currencies (symbol, name, network, is_active) VALUES (?, 'Test Currency', 'Mainnet', TRUE)"
+    )
+    .bind(symbol)
+    .execute(&ctx.db)
+    .await
+    .unwrap()
+    .last_insert_id() as i64
+}
+
+#[allow(dead_code)]
+pub async fn delete_currency(ctx: &TestContext, id: i64) {
+    sqlx::query("DELETE FROM currencies WHERE id = ?")
+        .bind(id)
+        .execute(&ctx.db)
+        .await
+        .ok();
+}
+
+// Short random ticker that cannot collide with real currencies
+#[allow(dead_code)]
+pub fn unique_symbol() -> String {
+    format!("tc{}", &uuid::Uuid::new_v4().simple().to_string()[..8])
+}
+
+// Helper to insert a BTC -> XMR swap row directly, returning its id
+#[allow(dead_code)]
+pub async fn insert_swap(ctx: &TestContext, status: &str, user_id: Option<&str>) -> String {
+    let id = uuid::Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO swaps (id, user_id, provider_id, from_currency, from_network, to_currency, to_network,
+                            amount, estimated_receive, rate, deposit_address, recipient_address, refund_address, status)
+         VALUES (?, ?, 'changenow', 'btc', 'Mainnet', 'xmr', 'Mainnet', 0.001, 0.1, 100,
+                 'bc1qdeposit000000000000000000000000000000', ?, 'bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh', ?)"
+    )
+    .bind(&id)
+    .bind(user_id)
+    .bind("44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A")
+    .bind(status)
+    .execute(&ctx.db)
+    .await
+    .expect("Failed to insert swap");
+    id
+}
+
+#[allow(dead_code)]
+pub async fn delete_swap(ctx: &TestContext, id: &str) {
+    sqlx::query("DELETE FROM swaps WHERE id = ? OR retried_from = ?")
+        .bind(id)
+        .bind(id)
+        .execute(&ctx.db)
+        .await
+        .ok();
+}
@@ -0,0 +1,2 @@
+mod webhook_test;
+mod notifications_test;
@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+use crate::common::{create_user_token, TestContext};
+use exchange_shared::config::environment::BrandingConfig;
+use exchange_shared::services::branding::Brand;
+use exchange_shared::services::notifications::{
+    enqueue, Notice, NotificationChannel, NotificationPreferences, NotificationSender, Recipient,
+};
+
+/// Records deliveries instead of sending; deliveries to `failing_user` fail
+#[derive(Clone, Default)]
+struct RecordingChannel {
+    delivered: Arc<Mutex<Vec<(String, String)>>>, // (user_id, kind)
+    failing_user: Option<String>,
+}
+
+#[async_trait]
+impl NotificationChannel for RecordingChannel {
+    fn name(&self) -> &'static str {
+        "recording"
+    }
+
+    fn reaches(&self, recipient: &Recipient) -> bool {
+        recipient.preferences.email_enabled
+    }
+
+    async fn deliver(&self, recipient: &Recipient, notice: &Notice) -> Result<(), String> {
+        if self.failing_user.as_deref() == Some(recipient.user_id.as_str()) {
+            return Err("channel down".to_string());
+        }
+        self.delivered.lock().unwrap().push((recipient.user_id.clone(), notice.kind.clone()));
+        Ok(())
+    }
+}
+
+fn sender(ctx: &TestContext, channel: RecordingChannel, max_attempts: u32) -> NotificationSender {
+    NotificationSender::with_channels(
+        ctx.db.clone(),
+        None,
+        vec![Box::new(channel)],
+        Brand::from_config(&BrandingConfig::default()),
+        max_attempts,
+    )
+}
+
+fn notice(kind: &str) -> Notice {
+    Notice { kind: kind.to_string(), title: "Title".to_string(), body: "Body".to_string() }
+}
+
+async fn statuses(ctx: &TestContext, user_id: &str) -> Vec<(String, String, i32)> {
+    sqlx::query_as("SELECT kind, status, attempts FROM user_notifications WHERE user_id = ? ORDER BY id")
+        .bind(user_id)
+        .fetch_all(&ctx.db)
+        .await
+        .unwrap()
+}
+
+#[test]
+fn preferences_gate_swap_notices_only() {
+    let preferences = NotificationPreferences { swap_failed: false, action_required: false, ..Default::default() };
+
+    assert!(preferences.wants("swap_completed"));
+    assert!(!preferences.wants("swap_failed"));
+    assert!(!preferences.wants("swap_refunded"));
+    assert!(!preferences.wants("swap_expired"));
+    assert!(!preferences.wants("deposit_lost"));
+    assert!(preferences.wants("currency_delisting"));
+}
+
+#[tokio::test]
+async fn preferences_default_to_email_and_update_partially() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx.server.get("/auth/notifications").authorization_bearer(&token).await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["email_enabled"], true);
+    assert_eq!(body["telegram_enabled"], false);
+
+    // Telegram needs a chat to send to
+    let response = ctx
+        .server
+        .put("/auth/notifications")
+        .authorization_bearer(&token)
+        .json(&json!({ "telegram_enabled": true }))
+        .await;
+    response.assert_status_bad_request();
+
+    let response = ctx
+        .server
+        .put("/auth/notifications")
+        .authorization_bearer(&token)
+        .json(&json!({ "telegram_enabled": true, "telegram_chat_id": "12345", "swap_completed": false }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["telegram_chat_id"], "12345");
+    assert_eq!(body["swap_completed"], false);
+    assert_eq!(body["email_enabled"], true);
+}
+
+// One test drives the sender: each drain also picks up other tests' pending rows
+#[tokio::test]
+async fn sender_delivers_skips_muted_and_retries_failures() {
+    let ctx = TestContext::new().await;
+    let (user_id, _) = create_user_token(&ctx).await;
+    let (unreachable_id, _) = create_user_token(&ctx).await;
+    NotificationPreferences { swap_failed: false, ..Default::default() }
+        .save(&ctx.db, &user_id)
+        .await
+        .unwrap();
+
+    enqueue(&ctx.db, &user_id, None, None, &notice("swap_completed")).await.unwrap();
+    enqueue(&ctx.db, &user_id, None, None, &notice("swap_failed")).await.unwrap();
+    enqueue(&ctx.db, &unreachable_id, Some("swap-1"), None, &notice("swap_expired")).await.unwrap();
+
+    let channel = RecordingChannel { failing_user: Some(unreachable_id.clone()), ..Default::default() };
+    let sender = sender(&ctx, channel.clone(), 2);
+
+    sender.drain(1000).await.unwrap();
+    let delivered: Vec<_> = channel.delivered.lock().unwrap().iter().filter(|(u, _)| *u == user_id).cloned().collect();
+    assert_eq!(delivered, vec![(user_id.clone(), "swap_completed".to_string())]);
+    assert_eq!(
+        statuses(&ctx, &user_id).await,
+        vec![
+            ("swap_completed".to_string(), "sent".to_string(), 1),
+            ("swap_failed".to_string(), "skipped".to_string(), 1),
+        ]
+    );
+    assert_eq!(statuses(&ctx, &unreachable_id).await, vec![("swap_expired".to_string(), "pending".to_string(), 1)]);
+
+    sender.drain(1000).await.unwrap();
+    assert_eq!(statuses(&ctx, &unreachable_id).await, vec![("swap_expired".to_string(), "failed".to_string(), 2)]);
+}
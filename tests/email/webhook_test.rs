@@ -0,0 +1,133 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{test_email, TestContext};
+use exchange_shared::config::environment::EmailConfig;
+use exchange_shared::services::email::{EmailError, EmailMessage, EmailService};
+use exchange_shared::services::redis_cache::RedisService;
+
+const TOKEN: &str = "test-email-webhook-token";
+
+// The token is read when the app is built
+async fn context() -> TestContext {
+    std::env::set_var("EMAIL_WEBHOOK_TOKEN", TOKEN);
+    TestContext::new().await
+}
+
+async fn is_suppressed(ctx: &TestContext, email: &str) -> bool {
+    sqlx::query_as::<_, (String,)>("SELECT email FROM email_suppressions WHERE email = ?")
+        .bind(email.to_lowercase())
+        .fetch_optional(&ctx.db)
+        .await
+        .unwrap()
+        .is_some()
+}
+
+async fn unsuppress(ctx: &TestContext, email: &str) {
+    sqlx::query("DELETE FROM email_suppressions WHERE email = ?")
+        .bind(email.to_lowercase())
+        .execute(&ctx.db)
+        .await
+        .ok();
+}
+
+fn sns_notification(message: Value) -> String {
+    json!({
+        "Type": "Notification",
+        "MessageId": "test",
+        "Message": message.to_string()
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn webhook_rejects_wrong_token() {
+    let ctx = context().await;
+
+    let response = ctx
+        .server
+        .post("/webhooks/email/events?token=wrong")
+        .json(&json!({ "events": [] }))
+        .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn ses_permanent_bounce_suppresses_address() {
+    let ctx = context().await;
+    let email = test_email();
+
+    let body = sns_notification(json!({
+        "notificationType": "Bounce",
+        "bounce": {
+            "bounceType": "Permanent",
+            "bouncedRecipients": [{ "emailAddress": email, "diagnosticCode": "smtp; 550 5.1.1 user unknown" }]
+        }
+    }));
+
+    let response = ctx
+        .server
+        .post(&format!("/webhooks/email/ses?token={}", TOKEN))
+        .text(body)
+        .await;
+
+    response.assert_status_ok();
+    let result: Value = response.json();
+    assert_eq!(result["suppressed"], 1);
+    assert!(is_suppressed(&ctx, &email).await);
+
+    unsuppress(&ctx, &email).await;
+}
+
+#[tokio::test]
+async fn ses_transient_bounce_is_ignored() {
+    let ctx = context().await;
+    let email = test_email();
+
+    let body = sns_notification(json!({
+        "notificationType": "Bounce",
+        "bounce": {
+            "bounceType": "Transient",
+            "bouncedRecipients": [{ "emailAddress": email }]
+        }
+    }));
+
+    let response = ctx
+        .server
+        .post(&format!("/webhooks/email/ses?token={}", TOKEN))
+        .text(body)
+        .await;
+
+    response.assert_status_ok();
+    assert!(!is_suppressed(&ctx, &email).await);
+}
+
+#[tokio::test]
+async fn complaint_event_blocks_further_sends() {
+    let ctx = context().await;
+    let email = test_email();
+
+    let response = ctx
+        .server
+        .post(&format!("/webhooks/email/events?token={}", TOKEN))
+        .json(&json!({ "events": [{ "type": "complaint", "email": email, "detail": "abuse" }] }))
+        .await;
+    response.assert_status_ok();
+
+    let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+    let service = EmailService::from_config(&EmailConfig::from_env(), ctx.db.clone(), RedisService::new(&redis_url))
+        .expect("log backend needs no configuration");
+
+    let result = service
+        .send(&EmailMessage {
+            to: email.clone(),
+            subject: "Hello".to_string(),
+            text_body: "Should not be sent".to_string(),
+        })
+        .await;
+
+    assert!(matches!(result, Err(EmailError::Suppressed(_))));
+
+    unsuppress(&ctx, &email).await;
+}
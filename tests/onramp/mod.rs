@@ -0,0 +1,2 @@
+mod orders_test;
+mod webhook_test;
@@ -0,0 +1,89 @@
+use axum::http::StatusCode;
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+
+use crate::common::{amount, create_user_token, TestContext};
+
+async fn insert_order(ctx: &TestContext, user_id: &str) -> String {
+    let id = uuid::Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO onramp_orders
+            (id, user_id, provider, provider_order_id, fiat_currency, fiat_amount, crypto_currency,
+             crypto_network, wallet_address, status)
+         VALUES (?, ?, 'transak', ?, 'USD', 100, 'btc', 'Mainnet', 'bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq', 'pending')",
+    )
+    .bind(&id)
+    .bind(user_id)
+    .bind(format!("prov-{}", id))
+    .execute(&ctx.db)
+    .await
+    .unwrap();
+    id
+}
+
+#[tokio::test]
+async fn quote_is_unavailable_without_provider() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx
+        .server
+        .get("/onramp/quote?fiat_currency=USD&fiat_amount=100&crypto_currency=btc&network=Mainnet")
+        .await;
+
+    response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn create_order_requires_authentication() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx
+        .server
+        .post("/onramp/orders")
+        .json(&json!({
+            "fiat_currency": "USD",
+            "fiat_amount": 100.0,
+            "crypto_currency": "btc",
+            "network": "Mainnet",
+            "wallet_address": "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"
+        }))
+        .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn get_order_returns_own_order() {
+    let ctx = TestContext::new().await;
+    let (user_id, token) = create_user_token(&ctx).await;
+    let order_id = insert_order(&ctx, &user_id).await;
+
+    let response = ctx
+        .server
+        .get(&format!("/onramp/orders/{}", order_id))
+        .authorization_bearer(&token)
+        .await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["order_id"], order_id);
+    assert_eq!(body["status"], "pending");
+    assert_eq!(amount(&body["fiat_amount"]), dec!(100));
+    assert!(body["swap_id"].is_null());
+}
+
+#[tokio::test]
+async fn get_order_hides_other_users_orders() {
+    let ctx = TestContext::new().await;
+    let (owner_id, _) = create_user_token(&ctx).await;
+    let (_, other_token) = create_user_token(&ctx).await;
+    let order_id = insert_order(&ctx, &owner_id).await;
+
+    let response = ctx
+        .server
+        .get(&format!("/onramp/orders/{}", order_id))
+        .authorization_bearer(&other_token)
+        .await;
+
+    response.assert_status(StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,108 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{create_user_token, TestContext};
+use exchange_shared::services::security::hmac_sha256_hex;
+
+const SECRET: &str = "test-onramp-webhook-secret";
+
+// The secret is read when the app is built
+async fn context() -> TestContext {
+    std::env::set_var("ONRAMP_WEBHOOK_SECRET", SECRET);
+    TestContext::new().await
+}
+
+async fn insert_order(ctx: &TestContext, status: &str) -> (String, String) {
+    let (user_id, _) = create_user_token(ctx).await;
+    let id = uuid::Uuid::new_v4().to_string();
+    let provider_order_id = format!("prov-{}", id);
+    sqlx::query(
+        "INSERT INTO onramp_orders
+            (id, user_id, provider, provider_order_id, fiat_currency, fiat_amount, crypto_currency,
+             crypto_network, wallet_address, status)
+         VALUES (?, ?, 'transak', ?, 'USD', 100, 'btc', 'Mainnet', 'bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq', ?)",
+    )
+    .bind(&id)
+    .bind(&user_id)
+    .bind(&provider_order_id)
+    .bind(status)
+    .execute(&ctx.db)
+    .await
+    .unwrap();
+    (id, provider_order_id)
+}
+
+async fn order_status(ctx: &TestContext, id: &str) -> (String, Option<String>) {
+    sqlx::query_as("SELECT status, tx_hash FROM onramp_orders WHERE id = ?")
+        .bind(id)
+        .fetch_one(&ctx.db)
+        .await
+        .unwrap()
+}
+
+async fn post_signed(ctx: &TestContext, payload: Value) -> axum_test::TestResponse {
+    let body = payload.to_string();
+    ctx.server
+        .post("/onramp/webhook")
+        .add_header("X-Signature", hmac_sha256_hex(SECRET, body.as_bytes()))
+        .text(body)
+        .await
+}
+
+#[tokio::test]
+async fn webhook_rejects_bad_signature() {
+    let ctx = context().await;
+
+    let response = ctx
+        .server
+        .post("/onramp/webhook")
+        .add_header("X-Signature", "deadbeef")
+        .json(&json!({ "order_id": "x", "status": "completed" }))
+        .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn webhook_updates_order_status() {
+    let ctx = context().await;
+    let (id, provider_order_id) = insert_order(&ctx, "pending").await;
+
+    let response = post_signed(
+        &ctx,
+        json!({
+            "order_id": provider_order_id,
+            "status": "COMPLETED",
+            "crypto_amount": 0.0015,
+            "tx_hash": "abc123"
+        }),
+    )
+    .await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["status"], "completed");
+    assert!(body["swap_id"].is_null());
+    assert_eq!(order_status(&ctx, &id).await, ("completed".to_string(), Some("abc123".to_string())));
+}
+
+#[tokio::test]
+async fn webhook_does_not_reopen_finished_orders() {
+    let ctx = context().await;
+    let (id, _) = insert_order(&ctx, "completed").await;
+
+    let response = post_signed(&ctx, json!({ "order_id": "unused", "partner_order_id": id, "status": "failed" })).await;
+
+    response.assert_status_ok();
+    assert_eq!(order_status(&ctx, &id).await.0, "completed");
+}
+
+#[tokio::test]
+async fn webhook_rejects_unknown_status() {
+    let ctx = context().await;
+    let (_, provider_order_id) = insert_order(&ctx, "pending").await;
+
+    let response = post_signed(&ctx, json!({ "order_id": provider_order_id, "status": "teleported" })).await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+}
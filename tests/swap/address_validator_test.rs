@@ -0,0 +1,82 @@
+use exchange_shared::services::address_validator::Chain;
+
+// =============================================================================
+// UNIT TESTS - BUILT-IN ADDRESS VALIDATION
+// =============================================================================
+
+const XMR_ADDRESS: &str =
+    "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A";
+
+fn valid(chain: Chain, address: &str) -> bool {
+    chain.check_address(address).is_ok()
+}
+
+#[test]
+fn test_chains_are_detected_from_ticker_and_network() {
+    assert_eq!(Chain::detect("BTC", "Mainnet"), Some(Chain::Bitcoin));
+    assert_eq!(Chain::detect("eth", "Mainnet"), Some(Chain::Evm));
+    assert_eq!(Chain::detect("usdt", "ERC20"), Some(Chain::Evm));
+    assert_eq!(Chain::detect("usdt", "BEP20"), Some(Chain::Evm));
+    assert_eq!(Chain::detect("usdt", "TRC20"), Some(Chain::Tron));
+    assert_eq!(Chain::detect("xmr", "Mainnet"), Some(Chain::Monero));
+    assert_eq!(Chain::detect("xrp", "Mainnet"), Some(Chain::Ripple));
+    assert_eq!(Chain::detect("ltc", "Mainnet"), None, "exotic chains go to the provider");
+    assert_eq!(Chain::detect("btc", "Lightning"), None);
+}
+
+#[test]
+fn test_bitcoin_addresses() {
+    assert!(valid(Chain::Bitcoin, "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"));
+    assert!(valid(Chain::Bitcoin, "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy"));
+    assert!(valid(Chain::Bitcoin, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"));
+    assert!(valid(Chain::Bitcoin, "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh"));
+    assert!(valid(Chain::Bitcoin, "bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297"));
+
+    assert!(!valid(Chain::Bitcoin, "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb"), "base58check mismatch");
+    assert!(!valid(Chain::Bitcoin, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5"), "bech32 mismatch");
+    assert!(!valid(Chain::Bitcoin, "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"), "testnet");
+    assert!(!valid(Chain::Bitcoin, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+}
+
+#[test]
+fn test_evm_addresses() {
+    assert!(valid(Chain::Evm, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+    assert!(valid(Chain::Evm, "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"), "lowercase has no checksum");
+    assert!(!valid(Chain::Evm, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"), "EIP-55 mismatch");
+    assert!(!valid(Chain::Evm, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA"));
+}
+
+#[test]
+fn test_monero_solana_and_tron_addresses() {
+    assert!(valid(Chain::Monero, XMR_ADDRESS));
+    assert!(!valid(Chain::Monero, &XMR_ADDRESS.replace("BEP3A", "BEP3B")), "Keccak checksum mismatch");
+    assert!(!valid(Chain::Monero, &XMR_ADDRESS[..94]));
+
+    assert!(valid(Chain::Solana, "So11111111111111111111111111111111111111112"));
+    assert!(!valid(Chain::Solana, "So1111111111111111111111111111111111111111"));
+
+    assert!(valid(Chain::Tron, "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t"));
+    assert!(!valid(Chain::Tron, "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6u"));
+}
+
+#[test]
+fn test_xrp_addresses_and_destination_tags() {
+    let classic = "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh";
+    assert!(valid(Chain::Ripple, classic));
+    assert!(!valid(Chain::Ripple, "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTj"));
+
+    assert!(Chain::Ripple.check_extra_id(classic, "12345").is_ok());
+    assert!(Chain::Ripple.check_extra_id(classic, "4294967295").is_ok());
+    assert!(Chain::Ripple.check_extra_id(classic, "4294967296").is_err());
+    assert!(Chain::Ripple.check_extra_id(classic, "memo").is_err());
+
+    // X-addresses carry the destination tag themselves
+    let untagged = "X7AcgcsBL6XDcUb289X4mJ8djcdyKaB5hJDWMArnXr61cqZ";
+    let tagged = "X7AcgcsBL6XDcUb289X4mJ8djcdyKaGZMhc9YTE92ehJ2Fu";
+    assert!(valid(Chain::Ripple, untagged));
+    assert!(valid(Chain::Ripple, tagged));
+    assert!(Chain::Ripple.check_extra_id(untagged, "1").is_ok());
+    assert!(Chain::Ripple.check_extra_id(tagged, "1").is_err());
+
+    assert!(Chain::Bitcoin.check_extra_id("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa", "anything").is_ok());
+}
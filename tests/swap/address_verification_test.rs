@@ -0,0 +1,304 @@
+use axum::http::StatusCode;
+use base64::Engine;
+use exchange_shared::config::environment::AddressVerificationConfig;
+use exchange_shared::modules::swap::crud::SwapError;
+use exchange_shared::services::address_validator::Chain;
+use exchange_shared::services::message_signing::{normalize_address, verify_signed_message, SignatureError};
+use k256::ecdsa::SigningKey;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{create_user_token, TestContext};
+
+// Private key 1 and its addresses
+const KEY_ONE_P2PKH: &str = "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH";
+const KEY_ONE_P2PKH_UNCOMPRESSED: &str = "1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm";
+const KEY_ONE_P2SH_P2WPKH: &str = "3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN";
+const KEY_ONE_P2WPKH: &str = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+
+// web3.js documentation account
+const EVM_KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+const EVM_ADDRESS: &str = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+
+fn key_one() -> SigningKey {
+    let mut bytes = [0u8; 32];
+    bytes[31] = 1;
+    SigningKey::from_bytes(&bytes.into()).unwrap()
+}
+
+fn evm_key() -> SigningKey {
+    let bytes: Vec<u8> = (0..EVM_KEY.len()).step_by(2).map(|i| u8::from_str_radix(&EVM_KEY[i..i + 2], 16).unwrap()).collect();
+    SigningKey::from_slice(&bytes).unwrap()
+}
+
+/// BIP-137 signature; `header` is 27 (uncompressed), 31 (compressed),
+/// 35 (P2SH-P2WPKH) or 39 (P2WPKH)
+fn sign_bitcoin(key: &SigningKey, message: &str, header: u8) -> String {
+    let mut data = b"\x18Bitcoin Signed Message:\n".to_vec();
+    data.push(message.len() as u8);
+    data.extend_from_slice(message.as_bytes());
+    let (signature, recovery_id) = key.sign_prehash_recoverable(&Sha256::digest(Sha256::digest(&data))).unwrap();
+
+    let mut bytes = vec![header + recovery_id.to_byte()];
+    bytes.extend_from_slice(&signature.to_bytes());
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+/// EIP-191 personal_sign signature
+fn sign_evm(key: &SigningKey, message: &str) -> String {
+    let prefixed = format!("\x19Ethereum Signed Message:\n{}{}", message.len(), message);
+    let (signature, recovery_id) = key.sign_prehash_recoverable(&Keccak256::digest(prefixed.as_bytes())).unwrap();
+
+    let mut bytes = signature.to_bytes().to_vec();
+    bytes.push(27 + recovery_id.to_byte());
+    format!("0x{}", bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>())
+}
+
+// =============================================================================
+// UNIT TESTS - SIGNATURE VERIFICATION
+// =============================================================================
+
+#[test]
+fn test_bitcoin_core_signature_verifies() {
+    // Signed with Bitcoin Core's signmessage
+    let result = verify_signed_message(
+        Chain::Bitcoin,
+        "1F3sAm6ZtwLAUnj7d38pGFxtP3RVEvtsbV",
+        "This is an example of a signed message.",
+        "H9L5yLFjti0QTHhPyFrZCT1V/MMnBtXKmoiKDZ78NDBjERki6ZTQZdSMCtkgoNmp17By9ItJr8o7ChX0XxY91nk=",
+    );
+
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn test_every_single_key_bitcoin_address_type_verifies() {
+    let key = key_one();
+
+    for (address, header) in [
+        (KEY_ONE_P2PKH, 31),
+        (KEY_ONE_P2PKH_UNCOMPRESSED, 27),
+        (KEY_ONE_P2SH_P2WPKH, 35),
+        (KEY_ONE_P2WPKH, 39),
+        (&KEY_ONE_P2WPKH.to_uppercase(), 39),
+    ] {
+        let signature = sign_bitcoin(&key, "hello", header);
+        assert_eq!(verify_signed_message(Chain::Bitcoin, address, "hello", &signature), Ok(()), "{}", address);
+    }
+}
+
+#[test]
+fn test_bitcoin_signature_must_match_address_and_message() {
+    let signature = sign_bitcoin(&key_one(), "hello", 31);
+
+    assert_eq!(
+        verify_signed_message(Chain::Bitcoin, KEY_ONE_P2PKH, "hello!", &signature),
+        Err(SignatureError::Mismatch)
+    );
+    assert_eq!(
+        verify_signed_message(Chain::Bitcoin, "1F3sAm6ZtwLAUnj7d38pGFxtP3RVEvtsbV", "hello", &signature),
+        Err(SignatureError::Mismatch)
+    );
+    // An uncompressed-key header recovers a different P2PKH address
+    let signature = sign_bitcoin(&key_one(), "hello", 27);
+    assert_eq!(
+        verify_signed_message(Chain::Bitcoin, KEY_ONE_P2PKH, "hello", &signature),
+        Err(SignatureError::Mismatch)
+    );
+}
+
+#[test]
+fn test_malformed_and_unsupported_bitcoin_signatures() {
+    let signature = sign_bitcoin(&key_one(), "hello", 31);
+
+    assert!(matches!(
+        verify_signed_message(Chain::Bitcoin, KEY_ONE_P2PKH, "hello", "not base64!"),
+        Err(SignatureError::Malformed(_))
+    ));
+    assert!(matches!(
+        verify_signed_message(Chain::Bitcoin, KEY_ONE_P2PKH, "hello", &signature[..40]),
+        Err(SignatureError::Malformed(_))
+    ));
+    // Taproot keys sign with BIP-322, not BIP-137
+    assert!(matches!(
+        verify_signed_message(
+            Chain::Bitcoin,
+            "bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297",
+            "hello",
+            &signature
+        ),
+        Err(SignatureError::Unsupported(_))
+    ));
+    assert!(matches!(
+        verify_signed_message(Chain::Monero, "4", "hello", &signature),
+        Err(SignatureError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn test_evm_personal_sign_signature_verifies() {
+    // web3.eth.accounts.sign("Some data", EVM_KEY)
+    let signature = "0xb91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c";
+
+    assert_eq!(verify_signed_message(Chain::Evm, EVM_ADDRESS, "Some data", signature), Ok(()));
+    assert_eq!(verify_signed_message(Chain::Evm, &EVM_ADDRESS.to_lowercase(), "Some data", signature), Ok(()));
+    assert_eq!(
+        verify_signed_message(Chain::Evm, EVM_ADDRESS, "Other data", signature),
+        Err(SignatureError::Mismatch)
+    );
+    assert!(matches!(
+        verify_signed_message(Chain::Evm, EVM_ADDRESS, "Some data", &signature[2..]),
+        Err(SignatureError::Malformed(_))
+    ));
+}
+
+#[test]
+fn test_evm_signature_accepts_zero_based_recovery_byte() {
+    let signature = sign_evm(&evm_key(), "hello");
+    let v = u8::from_str_radix(&signature[130..], 16).unwrap();
+    let zero_based = format!("{}{:02x}", &signature[..130], v - 27);
+
+    assert_eq!(verify_signed_message(Chain::Evm, EVM_ADDRESS, "hello", &signature), Ok(()));
+    assert_eq!(verify_signed_message(Chain::Evm, EVM_ADDRESS, "hello", &zero_based), Ok(()));
+}
+
+#[test]
+fn test_addresses_normalize_case_insensitive_encodings() {
+    assert_eq!(normalize_address(Chain::Evm, EVM_ADDRESS), EVM_ADDRESS.to_lowercase());
+    assert_eq!(normalize_address(Chain::Bitcoin, &KEY_ONE_P2WPKH.to_uppercase()), KEY_ONE_P2WPKH);
+    assert_eq!(normalize_address(Chain::Bitcoin, &format!(" {} ", KEY_ONE_P2PKH)), KEY_ONE_P2PKH);
+}
+
+#[test]
+fn test_swap_limit_depends_on_recipient_verification() {
+    let config = AddressVerificationConfig { max_swap_usd: 1_000.0, verified_max_swap_usd: 50_000.0, ..Default::default() };
+    assert_eq!(config.swap_limit_usd(false), Some(1_000.0));
+    assert_eq!(config.swap_limit_usd(true), Some(50_000.0));
+
+    let unlimited = AddressVerificationConfig::default();
+    assert_eq!(unlimited.swap_limit_usd(false), None);
+
+    let error = SwapError::SwapLimitExceeded { limit: 1_000.0, verified: false };
+    assert_eq!(error.status_code(), StatusCode::BAD_REQUEST);
+    assert_eq!(error.error_code(), "SWAP_LIMIT_EXCEEDED");
+    assert!(error.to_string().contains("verify the recipient address"));
+}
+
+// =============================================================================
+// INTEGRATION TESTS - POST /swap/addresses/challenge, POST /swap/addresses/verify
+// =============================================================================
+
+async fn challenge(ctx: &TestContext, token: &str, ticker: &str, network: &str, address: &str) -> axum_test::TestResponse {
+    ctx.server
+        .post("/swap/addresses/challenge")
+        .authorization_bearer(token)
+        .json(&json!({ "ticker": ticker, "network": network, "address": address }))
+        .await
+}
+
+async fn verify(ctx: &TestContext, token: &str, address: &str, signature: &str) -> axum_test::TestResponse {
+    ctx.server
+        .post("/swap/addresses/verify")
+        .authorization_bearer(token)
+        .json(&json!({ "ticker": "eth", "network": "Mainnet", "address": address, "signature": signature }))
+        .await
+}
+
+#[tokio::test]
+async fn test_address_verification_requires_auth() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx
+        .server
+        .post("/swap/addresses/challenge")
+        .json(&json!({ "ticker": "eth", "network": "Mainnet", "address": EVM_ADDRESS }))
+        .await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+
+    ctx.server.get("/swap/addresses/verified").await.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_signed_challenge_verifies_address() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = challenge(&ctx, &token, "eth", "Mainnet", EVM_ADDRESS).await;
+    response.assert_status(StatusCode::CREATED);
+    let body: Value = response.json();
+    assert_eq!(body["chain"], "evm");
+    assert_eq!(body["address"], EVM_ADDRESS.to_lowercase());
+    let message = body["message"].as_str().unwrap();
+    assert!(message.contains(EVM_ADDRESS));
+
+    let response = verify(&ctx, &token, EVM_ADDRESS, &sign_evm(&evm_key(), message)).await;
+    response.assert_status_ok();
+    let verified: Value = response.json();
+    assert_eq!(verified["address"], EVM_ADDRESS.to_lowercase());
+
+    // The challenge is used up
+    let response = verify(&ctx, &token, EVM_ADDRESS, &sign_evm(&evm_key(), message)).await;
+    response.assert_status(StatusCode::NOT_FOUND);
+    assert_eq!(response.json::<Value>()["code"], "CHALLENGE_NOT_FOUND");
+
+    let response = ctx.server.get("/swap/addresses/verified").authorization_bearer(&token).await;
+    response.assert_status_ok();
+    let list: Value = response.json();
+    assert_eq!(list["addresses"].as_array().unwrap().len(), 1);
+    assert_eq!(list["addresses"][0]["id"], verified["id"]);
+
+    let path = format!("/swap/addresses/verified/{}", verified["id"].as_str().unwrap());
+    ctx.server.delete(&path).authorization_bearer(&token).await.assert_status_ok();
+    ctx.server.delete(&path).authorization_bearer(&token).await.assert_status(StatusCode::NOT_FOUND);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_signature_from_another_key_is_rejected() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = challenge(&ctx, &token, "usdt", "ERC20", EVM_ADDRESS).await;
+    let message = response.json::<Value>()["message"].as_str().unwrap().to_string();
+
+    let response = verify(&ctx, &token, EVM_ADDRESS, &sign_evm(&key_one(), &message)).await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    assert_eq!(response.json::<Value>()["code"], "INVALID_SIGNATURE");
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_challenges_are_per_user() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+    let (_, other_token) = create_user_token(&ctx).await;
+
+    let response = challenge(&ctx, &token, "eth", "Mainnet", EVM_ADDRESS).await;
+    let message = response.json::<Value>()["message"].as_str().unwrap().to_string();
+
+    let response = verify(&ctx, &other_token, EVM_ADDRESS, &sign_evm(&evm_key(), &message)).await;
+
+    response.assert_status(StatusCode::NOT_FOUND);
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_challenge_rejects_invalid_and_unsupported_addresses() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = challenge(&ctx, &token, "btc", "Mainnet", "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMX").await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    assert_eq!(response.json::<Value>()["code"], "INVALID_ADDRESS");
+
+    let response = challenge(&ctx, &token, "xrp", "Mainnet", "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh").await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    assert_eq!(response.json::<Value>()["code"], "VERIFICATION_NOT_SUPPORTED");
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use exchange_shared::config::environment::AdmissionConfig;
+use exchange_shared::services::admission::{AdmissionController, QueueTicket};
+
+fn controller(max_concurrent: usize, max_queued: usize, max_wait_ms: u64) -> AdmissionController {
+    AdmissionController::new(AdmissionConfig {
+        enabled: true,
+        max_concurrent,
+        max_queued,
+        max_wait: Duration::from_millis(max_wait_ms),
+    })
+}
+
+// =============================================================================
+// UNIT TESTS - ADMISSION
+// =============================================================================
+
+#[tokio::test]
+async fn test_free_slot_admits_without_queueing() {
+    let admission = controller(2, 4, 2000);
+
+    let first = admission.admit().await.unwrap();
+    let second = admission.admit().await.unwrap();
+
+    assert_eq!(first.queued, None);
+    assert_eq!(second.queued, None);
+    assert_eq!(admission.in_flight(), 2);
+
+    drop(first);
+    assert_eq!(admission.in_flight(), 1);
+    admission.finish(second);
+    assert_eq!(admission.in_flight(), 0);
+}
+
+#[tokio::test]
+async fn test_queued_create_gets_the_next_free_slot() {
+    let admission = std::sync::Arc::new(controller(1, 4, 2000));
+    let running = admission.admit().await.unwrap();
+
+    let waiter = {
+        let admission = admission.clone();
+        tokio::spawn(async move { admission.admit().await.map(|admitted| admitted.queued) })
+    };
+    while admission.waiting() == 0 {
+        tokio::task::yield_now().await;
+    }
+    admission.finish(running);
+
+    let queued = waiter.await.unwrap().unwrap();
+    assert_eq!(queued, Some(QueueTicket { position: 1, eta: Duration::from_millis(1000) }));
+    assert_eq!(admission.waiting(), 0);
+}
+
+#[tokio::test]
+async fn test_full_queue_is_rejected() {
+    let admission = controller(1, 0, 2000);
+    let _running = admission.admit().await.unwrap();
+
+    let rejected = admission.admit().await.unwrap_err();
+
+    assert_eq!(rejected.retry_after, Duration::from_millis(1000));
+    assert_eq!(admission.waiting(), 0, "a refused create leaves the line");
+}
+
+#[tokio::test]
+async fn test_expected_wait_over_the_limit_is_rejected_up_front() {
+    // With the assumed 1s per create, the first in line already waits too long
+    let admission = controller(1, 4, 500);
+    let _running = admission.admit().await.unwrap();
+
+    let rejected = admission.admit().await.unwrap_err();
+
+    assert_eq!(rejected.retry_after, Duration::from_millis(1000));
+}
+
+#[tokio::test]
+async fn test_wait_is_cut_off_at_the_limit() {
+    let admission = controller(1, 4, 1000);
+    let _running = admission.admit().await.unwrap();
+
+    let started = std::time::Instant::now();
+    assert!(admission.admit().await.is_err());
+    assert!(started.elapsed() >= Duration::from_millis(1000));
+}
+
+#[test]
+fn test_eta_shares_the_line_among_slots() {
+    let admission = controller(4, 16, 2000);
+
+    assert_eq!(admission.eta(1), Duration::from_millis(1000));
+    assert_eq!(admission.eta(4), Duration::from_millis(1000));
+    assert_eq!(admission.eta(5), Duration::from_millis(2000));
+}
@@ -0,0 +1,171 @@
+use axum::http::StatusCode;
+use exchange_shared::config::environment::{ProviderSelectionConfig, SandboxConfig};
+use exchange_shared::modules::swap::crud::{select_best_quote, SwapError};
+use exchange_shared::modules::swap::schema::{CreateSwapRequest, RateResponse, RatesQuery};
+use exchange_shared::services::mock_provider::{MockProviderClient, SANDBOX_PROVIDER};
+use exchange_shared::services::swap_provider::SwapProviderClient;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{amount, setup_test_server, timed_get, timed_post};
+
+fn mock() -> MockProviderClient {
+    let prices = HashMap::from([("btc".to_string(), 60_000.0), ("eth".to_string(), 3_000.0)]);
+    MockProviderClient::new(&SandboxConfig { enabled: true, step: Duration::from_secs(30) }, prices)
+}
+
+/// A quote for 10 ETH asking `amount_from` BTC
+fn quote(provider: &str, amount_from: f64, min_amount: f64) -> RateResponse {
+    serde_json::from_value(json!({
+        "provider": provider,
+        "provider_name": provider,
+        "rate": 10.0 / amount_from,
+        "estimated_amount": 10.0,
+        "amount_from": amount_from,
+        "min_amount": min_amount,
+        "max_amount": 2.0,
+        "network_fee": 0.0,
+        "provider_fee": 0.0,
+        "platform_fee": 0.0,
+        "total_fee": 0.0,
+        "rate_type": "fixed",
+        "kyc_required": false,
+        "kyc_rating": "A"
+    }))
+    .unwrap()
+}
+
+fn create_request() -> CreateSwapRequest {
+    serde_json::from_value(json!({
+        "from": "btc",
+        "network_from": "Mainnet",
+        "to": "eth",
+        "network_to": "ERC20",
+        "amount_to": 9.95,
+        "provider": SANDBOX_PROVIDER,
+        "recipient_address": "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23",
+        "refund_address": "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh",
+        "sandbox": true
+    }))
+    .unwrap()
+}
+
+// =============================================================================
+// UNIT TESTS - QUOTES BY RECEIVE AMOUNT
+// =============================================================================
+
+#[test]
+fn test_sandbox_quote_for_inverts_quote() {
+    let mock = mock();
+
+    assert_eq!(mock.quote_for("btc", "eth", dec!(9.95)), dec!(0.5));
+    assert!((mock.quote("btc", "eth", mock.quote_for("btc", "eth", dec!(3))) - dec!(3)).abs() < dec!(1e-20));
+}
+
+#[tokio::test]
+async fn test_sandbox_quotes_by_amount_to_say_what_to_send() {
+    let query: RatesQuery = serde_json::from_value(json!({
+        "from": "btc",
+        "network_from": "Mainnet",
+        "to": "eth",
+        "network_to": "ERC20",
+        "amount_to": 9.95,
+        "sandbox": true
+    }))
+    .unwrap();
+    assert_eq!(query.amount, Decimal::ZERO);
+
+    let quotes = mock().get_quotes(&query).await.unwrap();
+
+    assert_eq!(quotes.quotes[0].amount_to, dec!(9.95));
+    assert_eq!(quotes.quotes[0].amount_from, Some(dec!(0.5)));
+}
+
+#[test]
+fn test_sandbox_trade_by_amount_to_pays_out_exactly_that() {
+    let (trade, _) = mock().create_trade(&create_request());
+
+    assert_eq!(trade.amount_to, dec!(9.95));
+    assert_eq!(trade.amount_from, dec!(0.5));
+}
+
+#[test]
+fn test_cheapest_quote_by_amount_to_wins() {
+    let rates = vec![quote("changenow", 0.52, 0.01), quote("fixedfloat", 0.5, 0.01), quote("exolix", 0.51, 0.01)];
+
+    let best = select_best_quote(&rates, &ProviderSelectionConfig::default(), Decimal::ZERO).unwrap();
+
+    assert_eq!(best.provider, "fixedfloat");
+}
+
+#[test]
+fn test_quote_by_amount_to_must_accept_what_it_asks_for() {
+    // fixedfloat's minimum is above what it asks to be sent
+    let rates = vec![quote("changenow", 0.52, 0.01), quote("fixedfloat", 0.5, 0.6)];
+
+    let best = select_best_quote(&rates, &ProviderSelectionConfig::default(), Decimal::ZERO).unwrap();
+
+    assert_eq!(best.provider, "changenow");
+}
+
+#[test]
+fn test_invalid_amount_error() {
+    let error = SwapError::InvalidAmount("amount_to must be positive".to_string());
+
+    assert_eq!(error.status_code(), StatusCode::BAD_REQUEST);
+    assert_eq!(error.error_code(), "INVALID_AMOUNT");
+}
+
+// =============================================================================
+// INTEGRATION TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_sandbox_rates_by_amount_to_are_fixed() {
+    let server = setup_test_server().await;
+
+    let response = timed_get(
+        &server,
+        "/swap/rates?from=btc&network_from=Mainnet&to=eth&network_to=ERC20&amount_to=9.95&sandbox=true",
+    )
+    .await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["amount_to"], "9.95");
+    let rate = &body["rates"][0];
+    assert_eq!(rate["rate_type"], "fixed");
+    assert!(amount(&rate["amount_from"]) > Decimal::ZERO);
+}
+
+#[tokio::test]
+async fn test_rates_take_amount_or_amount_to_not_both() {
+    let server = setup_test_server().await;
+
+    let response = timed_get(
+        &server,
+        "/swap/rates?from=btc&network_from=Mainnet&to=eth&network_to=ERC20&amount=0.5&amount_to=9.95&sandbox=true",
+    )
+    .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "INVALID_AMOUNT");
+}
+
+#[tokio::test]
+async fn test_sandbox_swap_by_amount_to_sets_deposit_amount() {
+    let server = setup_test_server().await;
+
+    let response = timed_post(&server, "/swap/create", &serde_json::to_value(create_request()).unwrap()).await;
+
+    response.assert_status(StatusCode::CREATED);
+    let body: Value = response.json();
+    assert_eq!(body["rate_type"], "fixed");
+    assert!(amount(&body["deposit_amount"]) > Decimal::ZERO);
+}
@@ -0,0 +1,53 @@
+#[path = "../common/mod.rs"]
+mod common;
+use common::TestContext;
+use std::time::Duration;
+use tokio::time::sleep;
+
+// =============================================================================
+// INTEGRATION TESTS - FUNNEL ANALYTICS (db sink)
+// Calls the actual Trocador API for the quote
+// =============================================================================
+
+#[tokio::test]
+async fn test_rates_request_records_quote_viewed_with_attribution() {
+    let ctx = TestContext::new().await;
+    let session_id = format!("test-session-{}", uuid::Uuid::new_v4());
+
+    let response = ctx
+        .server
+        .get("/swap/rates?from=btc&to=xmr&amount=0.01&network_from=Mainnet&network_to=Mainnet")
+        .add_header("X-Session-Id", session_id.as_str())
+        .add_header("X-UTM-Source", "newsletter")
+        .add_header("Referer", "https://example.com/landing")
+        .await;
+    response.assert_status_ok();
+
+    // Events are flushed in the background
+    let mut row: Option<(String, Option<String>, Option<String>)> = None;
+    for _ in 0..20 {
+        row = sqlx::query_as(
+            "SELECT event, utm_source, referrer FROM analytics_events WHERE session_id = ?",
+        )
+        .bind(&session_id)
+        .fetch_optional(&ctx.db)
+        .await
+        .unwrap();
+        if row.is_some() {
+            break;
+        }
+        sleep(Duration::from_millis(250)).await;
+    }
+
+    let (event, utm_source, referrer) = row.expect("quote_viewed event should be stored");
+    assert_eq!(event, "quote_viewed");
+    assert_eq!(utm_source.as_deref(), Some("newsletter"));
+    assert_eq!(referrer.as_deref(), Some("https://example.com/landing"));
+
+    sqlx::query("DELETE FROM analytics_events WHERE session_id = ?")
+        .bind(&session_id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    ctx.cleanup().await;
+}
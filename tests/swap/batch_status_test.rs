@@ -0,0 +1,74 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{create_user_token, delete_swap, insert_swap, TestContext};
+
+// =============================================================================
+// INTEGRATION TESTS - BATCH STATUS ENDPOINT (POST /swap/status/batch)
+// =============================================================================
+
+#[tokio::test]
+async fn test_batch_status_requires_auth() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx
+        .server
+        .post("/swap/status/batch")
+        .json(&json!({ "swap_ids": ["anything"] }))
+        .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_batch_status_returns_only_callers_swaps_in_order() {
+    let ctx = TestContext::new().await;
+    let (user_id, token) = create_user_token(&ctx).await;
+    let first = insert_swap(&ctx, "waiting", Some(&user_id)).await;
+    let second = insert_swap(&ctx, "completed", Some(&user_id)).await;
+    let (other_user_id, _) = create_user_token(&ctx).await;
+    let foreign = insert_swap(&ctx, "waiting", Some(&other_user_id)).await;
+
+    let response = ctx
+        .server
+        .post("/swap/status/batch")
+        .authorization_bearer(&token)
+        .json(&json!({ "swap_ids": [second, foreign, first, second] }))
+        .await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    let swaps = body["swaps"].as_array().unwrap();
+    assert_eq!(swaps.len(), 2);
+    assert_eq!(swaps[0]["swap_id"], second.as_str());
+    assert_eq!(swaps[0]["status"], "completed");
+    assert_eq!(swaps[1]["swap_id"], first.as_str());
+    assert_eq!(body["not_found"], json!([foreign]));
+
+    for id in [&first, &second, &foreign] {
+        delete_swap(&ctx, id).await;
+    }
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_batch_status_rejects_more_than_fifty_ids() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+    let ids: Vec<String> = (0..51).map(|i| format!("swap-{}", i)).collect();
+
+    let response = ctx
+        .server
+        .post("/swap/status/batch")
+        .authorization_bearer(&token)
+        .json(&json!({ "swap_ids": ids }))
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "TOO_MANY_SWAP_IDS");
+
+    ctx.cleanup().await;
+}
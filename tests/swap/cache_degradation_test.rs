@@ -0,0 +1,259 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+use exchange_shared::config::environment::{CacheDegradationConfig, DegradedMode};
+use exchange_shared::services::memory_cache::MemoryStore;
+use exchange_shared::modules::swap::crud::SwapError;
+use exchange_shared::services::redis_cache::{CacheBackend, RedisService, RedisServiceError};
+
+// Nothing listens on port 1, so every connection is refused
+const UNREACHABLE: &str = "redis://127.0.0.1:1/";
+
+fn unreachable(mode: DegradedMode) -> RedisService {
+    RedisService::new(UNREACHABLE).with_degradation(CacheDegradationConfig {
+        mode,
+        retry_after: Duration::from_secs(60),
+        fallback_max_keys: 100,
+        ..Default::default()
+    })
+}
+
+/// Minimal Redis stand-in answering every command with +PONG. Each
+/// connection is closed after answering `gets_per_connection` GETs. Returns the URL and
+/// the number of connections accepted so far.
+async fn fake_redis(gets_per_connection: usize) -> (String, Arc<AtomicUsize>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("redis://{}/", listener.local_addr().unwrap());
+    let accepted = Arc::new(AtomicUsize::new(0));
+    let counter = accepted.clone();
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            counter.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                let (read, mut write) = socket.into_split();
+                let mut reader = BufReader::new(read);
+                let mut gets = 0;
+                while gets < gets_per_connection {
+                    // *<args>, then $<len> and the argument for each
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).await.unwrap_or(0) == 0 {
+                        return;
+                    }
+                    let args: usize = line.trim_start_matches('*').trim().parse().unwrap_or(0);
+                    let mut command = Vec::new();
+                    for i in 0..args {
+                        line.clear();
+                        reader.read_line(&mut line).await.unwrap();
+                        let len: usize = line.trim_start_matches('$').trim().parse().unwrap();
+                        let mut arg = vec![0; len + 2];
+                        reader.read_exact(&mut arg).await.unwrap();
+                        if i == 0 {
+                            command = arg[..len].to_ascii_uppercase();
+                        }
+                    }
+                    if command == b"GET" {
+                        gets += 1;
+                    }
+                    write.write_all(b"+PONG\r\n").await.unwrap();
+                }
+            });
+        }
+    });
+    (url, accepted)
+}
+
+// =============================================================================
+// UNIT TESTS - REDIS DEGRADATION POLICY
+// =============================================================================
+
+#[tokio::test]
+async fn test_memory_mode_keeps_caching_when_redis_is_unreachable() {
+    let cache = unreachable(DegradedMode::Memory);
+    assert_eq!(cache.backend(), CacheBackend::Redis);
+
+    cache.set_string("k", "v", 60).await.unwrap();
+    assert_eq!(cache.backend(), CacheBackend::Memory);
+    assert_eq!(cache.get_string("k").await.unwrap().as_deref(), Some("v"));
+
+    // Locks and limits hold within the instance
+    assert!(cache.try_lock("lock", 60).await.unwrap());
+    assert!(!cache.try_lock("lock", 60).await.unwrap());
+    assert!(cache.check_rate_limit("rl", 1, 60).await.unwrap());
+    assert!(!cache.check_rate_limit("rl", 1, 60).await.unwrap());
+    assert_eq!(cache.incr_with_ttl("n", 60).await.unwrap(), 1);
+    assert_eq!(cache.incr_with_ttl("n", 60).await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_disabled_mode_misses_and_lets_everything_through() {
+    let cache = unreachable(DegradedMode::Disabled);
+
+    cache.set_string("k", "v", 60).await.unwrap();
+    assert_eq!(cache.backend(), CacheBackend::Disabled);
+    assert_eq!(cache.get_string("k").await.unwrap(), None);
+    assert_eq!(cache.ttl("k").await.unwrap(), -2);
+
+    assert!(cache.try_lock("lock", 60).await.unwrap());
+    assert!(cache.try_lock("lock", 60).await.unwrap());
+    assert!(cache.check_rate_limit("rl", 1, 60).await.unwrap());
+    assert!(cache.check_rate_limit("rl", 1, 60).await.unwrap());
+    assert_eq!(cache.publish("channel", "m").await.unwrap(), 0);
+    assert!(cache.subscribe("channel").await.is_err());
+}
+
+#[tokio::test]
+async fn test_health_check_reports_the_degraded_backend() {
+    assert_eq!(unreachable(DegradedMode::Memory).check_health().await, CacheBackend::Memory);
+    assert_eq!(unreachable(DegradedMode::Disabled).check_health().await, CacheBackend::Disabled);
+    assert_eq!(RedisService::in_memory().check_health().await, CacheBackend::Memory);
+}
+
+#[tokio::test]
+async fn test_configured_memory_cache_is_never_degraded() {
+    let cache = RedisService::in_memory().with_degradation(CacheDegradationConfig {
+        mode: DegradedMode::Disabled,
+        ..Default::default()
+    });
+
+    cache.set_string("k", "v", 60).await.unwrap();
+
+    assert_eq!(cache.get_string("k").await.unwrap().as_deref(), Some("v"));
+}
+
+#[test]
+fn test_fallback_store_drops_least_recently_used_keys() {
+    let store = MemoryStore::with_max_keys(8);
+    for i in 0..8 {
+        store.set(&format!("k{}", i), "v", 60);
+    }
+    store.get("k0");
+
+    store.set("k8", "v", 60);
+
+    assert!(store.len() <= 8);
+    assert!(store.get("k0").is_some());
+    assert!(store.get("k8").is_some());
+    assert!(store.get("k1").is_none());
+}
+
+#[test]
+fn test_degraded_mode_parses_from_env_values() {
+    assert_eq!("memory".parse::<DegradedMode>().unwrap(), DegradedMode::Memory);
+    assert_eq!("Disabled".parse::<DegradedMode>().unwrap(), DegradedMode::Disabled);
+    assert!("redis".parse::<DegradedMode>().is_err());
+}
+
+// =============================================================================
+// UNIT TESTS - TYPED ERRORS AND TIMEOUTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_bad_values_are_serialization_errors() {
+    let cache = RedisService::in_memory();
+    cache.set_string("j", "not json", 60).await.unwrap();
+
+    let result = cache.get_json::<Vec<i32>>("j").await;
+
+    assert!(matches!(result, Err(RedisServiceError::Serialization(_))));
+}
+
+#[tokio::test]
+async fn test_refused_commands_are_command_errors() {
+    let cache = RedisService::in_memory();
+    cache.set_string("n", "abc", 60).await.unwrap();
+
+    let result = cache.incr_with_ttl("n", 60).await;
+
+    assert!(matches!(result, Err(RedisServiceError::Command(_))));
+    assert!(!result.unwrap_err().is_unavailable());
+}
+
+#[tokio::test]
+async fn test_pub_sub_without_redis_is_a_connection_error() {
+    let result = RedisService::in_memory().subscribe("channel").await;
+
+    assert!(matches!(result, Err(RedisServiceError::Connection(_))));
+}
+
+#[tokio::test]
+async fn test_slow_redis_times_out_and_degrades() {
+    // Accepts connections and never answers
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("redis://{}/", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let mut held = Vec::new();
+        while let Ok((socket, _)) = listener.accept().await {
+            held.push(socket);
+        }
+    });
+    let cache = RedisService::new(&url).with_degradation(CacheDegradationConfig {
+        command_timeout: Duration::from_millis(100),
+        retry_after: Duration::from_secs(60),
+        ..Default::default()
+    });
+
+    let started = std::time::Instant::now();
+    cache.set_string("k", "v", 60).await.unwrap();
+
+    assert!(started.elapsed() < Duration::from_secs(2));
+    assert_eq!(cache.backend(), CacheBackend::Memory);
+    assert_eq!(cache.get_string("k").await.unwrap().as_deref(), Some("v"));
+}
+
+#[test]
+fn test_redis_errors_reach_swap_errors_typed() {
+    let error = SwapError::from(RedisServiceError::Timeout { command: "GET", after: Duration::from_millis(100) });
+
+    assert!(matches!(error, SwapError::RedisError(RedisServiceError::Timeout { .. })));
+    assert!(error.to_string().contains("GET timed out"));
+}
+
+#[tokio::test]
+async fn test_clones_share_one_connection() {
+    let (url, accepted) = fake_redis(usize::MAX).await;
+    let cache = RedisService::new(&url);
+    let clone = cache.clone();
+
+    for _ in 0..3 {
+        assert_eq!(cache.check_health().await, CacheBackend::Redis);
+        assert_eq!(clone.get_string("k").await.unwrap().as_deref(), Some("PONG"));
+    }
+
+    assert_eq!(accepted.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_dropped_connection_is_reopened_without_degrading() {
+    // Redis closes each connection after one command, like a restart would
+    let (url, accepted) = fake_redis(1).await;
+    let cache = RedisService::new(&url).with_degradation(CacheDegradationConfig {
+        retry_after: Duration::from_secs(60),
+        ..Default::default()
+    });
+
+    assert_eq!(cache.get_string("k").await.unwrap().as_deref(), Some("PONG"));
+    // Served from the fallback while the closed connection is dropped
+    cache.get_string("k").await.unwrap();
+    assert_eq!(cache.backend(), CacheBackend::Redis);
+
+    assert_eq!(cache.get_string("k").await.unwrap().as_deref(), Some("PONG"));
+    assert_eq!(accepted.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_delete_prefix_drops_only_matching_keys() {
+    let cache = RedisService::in_memory();
+    cache.set_string("pairs:btc:*:*:*", "[]", 60).await.unwrap();
+    cache.set_string("pairs:*:*:*:*", "[]", 60).await.unwrap();
+    cache.set_string("pairsx", "kept", 60).await.unwrap();
+    cache.set_string("currencies:all", "kept", 60).await.unwrap();
+
+    assert_eq!(cache.delete_prefix("pairs:").await.unwrap(), 2);
+    assert_eq!(cache.get_string("pairs:btc:*:*:*").await.unwrap(), None);
+    assert_eq!(cache.get_string("pairs:*:*:*:*").await.unwrap(), None);
+    assert_eq!(cache.get_string("pairsx").await.unwrap().as_deref(), Some("kept"));
+    assert_eq!(cache.get_string("currencies:all").await.unwrap().as_deref(), Some("kept"));
+}
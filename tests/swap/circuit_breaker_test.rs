@@ -0,0 +1,82 @@
+use exchange_shared::services::circuit_breaker::{is_outage, CircuitBreaker, CircuitState};
+use exchange_shared::services::redis_cache::RedisService;
+
+// =============================================================================
+// INTEGRATION TESTS - PROVIDER CIRCUIT BREAKER
+// Each test uses its own circuit name so live provider circuits are untouched.
+// =============================================================================
+
+fn redis() -> RedisService {
+    let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+    RedisService::new(&redis_url)
+}
+
+fn unique_circuit() -> String {
+    format!("test-{}", &uuid::Uuid::new_v4().simple().to_string()[..8])
+}
+
+#[tokio::test]
+async fn test_outages_open_the_circuit_and_a_probe_closes_it() {
+    let redis = redis();
+    let name = unique_circuit();
+    let breaker = CircuitBreaker::new(Some(redis.clone()), &name);
+
+    for _ in 0..5 {
+        let permit = breaker.acquire().await.expect("closed circuit refused a call");
+        breaker.record_failure(permit).await;
+    }
+    assert_eq!(breaker.state().await, CircuitState::Open);
+    assert_eq!(breaker.acquire().await.unwrap_err(), CircuitState::Open);
+
+    // The open period lapses: one probe goes out, everyone else still fails fast
+    redis.delete(&format!("circuit:{}:open", name)).await.unwrap();
+    assert_eq!(breaker.state().await, CircuitState::HalfOpen);
+    let probe = breaker.acquire().await.expect("half-open circuit refused the probe");
+    assert_eq!(breaker.acquire().await.unwrap_err(), CircuitState::HalfOpen);
+
+    breaker.record_success(probe).await;
+    assert_eq!(breaker.state().await, CircuitState::Closed);
+    assert!(breaker.acquire().await.is_ok());
+}
+
+#[tokio::test]
+async fn test_failed_probe_reopens_the_circuit() {
+    let redis = redis();
+    let name = unique_circuit();
+    let breaker = CircuitBreaker::new(Some(redis.clone()), &name);
+
+    for _ in 0..5 {
+        let permit = breaker.acquire().await.unwrap();
+        breaker.record_failure(permit).await;
+    }
+    redis.delete(&format!("circuit:{}:open", name)).await.unwrap();
+
+    let probe = breaker.acquire().await.unwrap();
+    breaker.record_failure(probe).await;
+    assert_eq!(breaker.state().await, CircuitState::Open);
+
+    for part in ["open", "tripped"] {
+        redis.delete(&format!("circuit:{}:{}", name, part)).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_circuit_without_redis_stays_closed() {
+    let breaker = CircuitBreaker::new(None, &unique_circuit());
+
+    for _ in 0..10 {
+        let permit = breaker.acquire().await.unwrap();
+        breaker.record_failure(permit).await;
+    }
+    assert_eq!(breaker.state().await, CircuitState::Closed);
+}
+
+#[test]
+fn test_only_outages_count_against_the_circuit() {
+    assert!(is_outage("HTTP error: error sending request for url"));
+    assert!(is_outage("API error: API returned status: 503 Service Unavailable"));
+    assert!(is_outage("API error: 502 Bad Gateway <html>"));
+    assert!(!is_outage("API error: 400 Bad Request amount below 500"));
+    assert!(!is_outage("API error: Provider rejected the trade"));
+    assert!(!is_outage("Parse error: missing field `rate`"));
+}
@@ -0,0 +1,66 @@
+use exchange_shared::modules::swap::crud::SwapCrud;
+use exchange_shared::services::metrics::metrics;
+use exchange_shared::services::redis_cache::RedisService;
+use serde_json::json;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{delete_swap, insert_swap, TestContext};
+
+// =============================================================================
+// INTEGRATION TESTS - CACHE CONSISTENCY CHECKER
+// =============================================================================
+
+fn redis() -> RedisService {
+    let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+    RedisService::new(&redis_url)
+}
+
+#[tokio::test]
+async fn test_stale_swap_status_is_reported_then_repaired() {
+    let ctx = TestContext::new().await;
+    let redis = redis();
+    let swap_id = insert_swap(&ctx, "completed", None).await;
+    let key = format!("swap_status:{}", swap_id);
+
+    // The cache still holds the status from before an update that forgot to invalidate it
+    let response = ctx.server.get(&format!("/swap/{}", swap_id)).await;
+    response.assert_status_ok();
+    let mut cached: serde_json::Value = response.json();
+    cached["status"] = json!("waiting");
+    redis.set_json(&key, &cached, 60).await.unwrap();
+
+    let crud = SwapCrud::new(ctx.db.clone(), Some(redis.clone()));
+    let diverged_before = metrics().cache_divergence.get("swap_status");
+
+    let stats = crud.check_cache_consistency(100, false).await.unwrap();
+    assert!(stats.diverged >= 1);
+    assert_eq!(stats.repaired, 0);
+    assert!(metrics().cache_divergence.get("swap_status") > diverged_before);
+    assert!(redis.get_string(&key).await.unwrap().is_some(), "only reported without repair");
+
+    let stats = crud.check_cache_consistency(100, true).await.unwrap();
+    assert!(stats.repaired >= 1);
+    assert_eq!(redis.get_string(&key).await.unwrap(), None, "the stale entry is dropped");
+
+    delete_swap(&ctx, &swap_id).await;
+}
+
+#[tokio::test]
+async fn test_matching_swap_status_is_kept() {
+    let ctx = TestContext::new().await;
+    let redis = redis();
+    let swap_id = insert_swap(&ctx, "completed", None).await;
+    let key = format!("swap_status:{}", swap_id);
+
+    // Reading a finished swap caches its status
+    ctx.server.get(&format!("/swap/{}", swap_id)).await.assert_status_ok();
+    assert!(redis.get_string(&key).await.unwrap().is_some());
+
+    let crud = SwapCrud::new(ctx.db.clone(), Some(redis.clone()));
+    crud.check_cache_consistency(100, true).await.unwrap();
+
+    assert!(redis.get_string(&key).await.unwrap().is_some(), "an up to date entry is kept");
+
+    delete_swap(&ctx, &swap_id).await;
+}
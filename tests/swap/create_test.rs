@@ -1,8 +1,10 @@
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
 use serde_json::{json, Value};
 
 #[path = "../common/mod.rs"]
 mod common;
-use common::{setup_test_server, timed_post, timed_get};
+use common::{amount, setup_test_server, timed_post, timed_get};
 use std::time::Duration;
 use tokio::time::sleep;
 
@@ -30,7 +32,7 @@ async fn test_create_swap_successful() {
 
     // 2. Create the swap using the trade_id
     let create_url = "/swap/create";
-    let recipient_address = "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve"; // Standard XMR address
+    let recipient_address = "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A"; // Standard XMR address
     let refund_address = "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh"; // Standard BTC address
 
     let payload = json!({
@@ -84,7 +86,7 @@ async fn test_create_swap_without_trade_id_should_fail() {
         "network_to": "Mainnet",
         "amount": 0.001,
         "provider": "changenow",
-        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
         // "trade_id" missing - should cause API error
     });
 
@@ -137,7 +139,7 @@ async fn test_create_swap_small_amount() {
         "network_to": "Mainnet",
         "amount": 0.00000001,
         "provider": "changenow", 
-        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
     });
 
     let response = timed_post(&server, create_url, &payload).await;
@@ -180,7 +182,7 @@ async fn test_create_swap_fixed_rate() {
         "network_to": "Mainnet",
         "amount": 0.001,
         "provider": provider,
-        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
         "rate_type": "fixed"
     });
 
@@ -218,14 +220,16 @@ async fn test_create_swap_invalid_refund_address() {
         "network_to": "Mainnet",
         "amount": 0.001,
         "provider": "changenow",
-        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
         "refund_address": "INVALID_REFUND_ADDRESS" 
     });
 
     let response = timed_post(&server, create_url, &payload).await;
     
-    // Should fail validation
-    assert!(response.status_code().as_u16() >= 400);
+    // Should fail validation against the source currency's network
+    response.assert_status_bad_request();
+    let json: Value = response.json();
+    assert_eq!(json["code"], "INVALID_REFUND_ADDRESS");
 }
 
 #[tokio::test]
@@ -263,7 +267,7 @@ async fn test_create_swap_amount_too_high() {
         "network_to": "Mainnet",
         "amount": 5000.0, // Excessive amount
         "provider": "changenow",
-        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
     });
 
     let response = timed_post(&server, create_url, &payload).await;
@@ -304,6 +308,28 @@ async fn test_create_swap_missing_memo_for_xrp() {
     }
 }
 
+#[tokio::test]
+async fn test_create_swap_non_numeric_xrp_tag() {
+    let server = setup_test_server().await;
+
+    let payload = json!({
+        "from": "btc",
+        "network_from": "Mainnet",
+        "to": "xrp",
+        "network_to": "Mainnet",
+        "amount": 0.001,
+        "provider": "changenow",
+        "recipient_address": "rEb8TK3gBgk5auZkwc6sHnwrGVJH8DuaLh",
+        "recipient_extra_id": "not-a-tag"
+    });
+
+    let response = timed_post(&server, "/swap/create", &payload).await;
+
+    response.assert_status_bad_request();
+    let body: Value = response.json();
+    assert_eq!(body["code"], "INVALID_EXTRA_ID");
+}
+
 // =============================================================================
 // NEW EDGE CASE TESTS
 // =============================================================================
@@ -330,7 +356,7 @@ async fn test_create_swap_invalid_provider() {
         "network_to": "Mainnet",
         "amount": 0.001,
         "provider": "nonexistent_provider_xyz", // Invalid provider
-        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
     });
 
     let response = timed_post(&server, create_url, &payload).await;
@@ -352,7 +378,7 @@ async fn test_create_swap_wrong_network_for_currency() {
         "network_to": "Mainnet",
         "amount": 0.001,
         "provider": "changenow",
-        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
     });
 
     let response = timed_post(&server, create_url, &payload).await;
@@ -375,7 +401,7 @@ async fn test_create_swap_expired_trade_id() {
         "network_to": "Mainnet",
         "amount": 0.001,
         "provider": "changenow",
-        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
     });
 
     let response = timed_post(&server, create_url, &payload).await;
@@ -407,7 +433,7 @@ async fn test_create_swap_rate_type_mismatch() {
         "network_to": "Mainnet",
         "amount": 0.001,
         "provider": provider,
-        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
         "rate_type": "fixed" // Request fixed when we got floating quote
     });
 
@@ -416,7 +442,7 @@ async fn test_create_swap_rate_type_mismatch() {
     // May succeed (if provider supports both) or fail (if strict validation)
     // Just verify we get a valid response
     let status = response.status_code().as_u16();
-    assert!(status >= 200 && status < 600, "Should get valid HTTP status");
+    assert!((200..600).contains(&status), "Should get valid HTTP status");
 }
 
 #[tokio::test]
@@ -482,11 +508,11 @@ async fn test_create_swap_exactly_minimum_amount() {
         return;
     }
     
-    let min_amount = rates[0]["min_amount"].as_f64().unwrap_or(0.0);
+    let min_amount = amount(&rates[0]["min_amount"]);
     let provider = rates[0]["provider"].as_str().expect("Should have provider");
     
     // Skip test if min_amount is 0 or invalid
-    if min_amount <= 0.0 {
+    if min_amount <= Decimal::ZERO {
         println!("Min amount is {} (invalid), skipping test", min_amount);
         return;
     }
@@ -502,7 +528,7 @@ async fn test_create_swap_exactly_minimum_amount() {
         "network_to": "Mainnet",
         "amount": min_amount,
         "provider": provider,
-        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
     });
 
     let response = timed_post(&server, create_url, &payload).await;
@@ -535,18 +561,18 @@ async fn test_create_swap_exactly_maximum_amount() {
         return;
     }
     
-    let max_amount = rates[0]["max_amount"].as_f64().unwrap_or(0.0);
+    let max_amount = amount(&rates[0]["max_amount"]);
     let provider = rates[0]["provider"].as_str().expect("Should have provider");
     
     // Skip test if max_amount is 0 or invalid
-    if max_amount <= 0.0 {
+    if max_amount <= Decimal::ZERO {
         println!("Max amount is {} (invalid), skipping test", max_amount);
         return;
     }
     
     // Use a reasonable test amount (not the full max which might be too high)
     // Use the smaller of: max_amount or 0.1 BTC
-    let test_amount = max_amount.min(0.1);
+    let test_amount = max_amount.min(dec!(0.1));
     println!("Testing with amount: {} (max: {})", test_amount, max_amount);
 
     let create_url = "/swap/create";
@@ -558,7 +584,7 @@ async fn test_create_swap_exactly_maximum_amount() {
         "network_to": "Mainnet",
         "amount": test_amount,
         "provider": provider,
-        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
     });
 
     let response = timed_post(&server, create_url, &payload).await;
@@ -595,7 +621,7 @@ async fn test_create_swap_concurrent_same_trade_id() {
         "network_to": "Mainnet",
         "amount": 0.001,
         "provider": provider,
-        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
     });
 
     // First swap
@@ -619,4 +645,37 @@ async fn test_create_swap_concurrent_same_trade_id() {
         status2.as_u16() >= 200 && status2.as_u16() < 600,
         "Second swap should return valid status"
     );
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_create_swap_falls_back_when_provider_rejects() {
+    sleep(Duration::from_secs(1)).await;
+    let server = setup_test_server().await;
+
+    let rate_url = "/swap/rates?from=btc&to=xmr&amount=0.001&network_from=Mainnet&network_to=Mainnet";
+    let rate_response = timed_get(&server, rate_url).await;
+    rate_response.assert_status_ok();
+    let rate_json: Value = rate_response.json();
+    let trade_id = rate_json["trade_id"].as_str().expect("Should have trade_id");
+
+    // No such provider, so Trocador rejects the trade and the best quote is used instead
+    let payload = json!({
+        "trade_id": trade_id,
+        "from": "btc",
+        "network_from": "Mainnet",
+        "to": "xmr",
+        "network_to": "Mainnet",
+        "amount": 0.001,
+        "provider": "no-such-provider",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
+        "refund_address": "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh",
+        "rate_type": "floating",
+        "allow_fallback": true
+    });
+
+    let response = timed_post(&server, "/swap/create", &payload).await;
+    assert_eq!(response.status_code(), 201, "Body: {}", response.text());
+
+    let json: Value = response.json();
+    assert_ne!(json["provider"], "no-such-provider");
+    assert_eq!(json["fallback_chain"][0]["provider"], "no-such-provider");
+}
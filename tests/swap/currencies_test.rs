@@ -1,8 +1,11 @@
+use exchange_shared::modules::swap::schema::CurrenciesQuery;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
 use serde_json::Value;
 
 #[path = "../common/mod.rs"]
 mod common;
-use common::{setup_test_server, timed_get};
+use common::{amount, setup_test_server, timed_get};
 
 
 // =============================================================================
@@ -193,15 +196,15 @@ async fn test_filter_currencies_by_ticker_and_network() {
     assert_eq!(btc["ticker"].as_str().unwrap().to_lowercase(), "btc");
     assert_eq!(btc["network"].as_str().unwrap(), "Mainnet");
     assert_eq!(btc["name"].as_str().unwrap(), "Bitcoin");
-    assert_eq!(btc["memo"].as_bool().unwrap(), false);
+    assert!(!btc["memo"].as_bool().unwrap());
 
     // Bitcoin should have reasonable min/max
-    let minimum = btc["minimum"].as_f64().unwrap();
-    let maximum = btc["maximum"].as_f64().unwrap();
-    assert!(minimum > 0.0, "Bitcoin minimum should be > 0");
+    let minimum = amount(&btc["minimum"]);
+    let maximum = amount(&btc["maximum"]);
+    assert!(minimum > Decimal::ZERO, "Bitcoin minimum should be > 0");
     assert!(maximum > minimum, "Bitcoin maximum should be > minimum");
     assert!(
-        maximum >= 1.0,
+        maximum >= Decimal::ONE,
         "Bitcoin maximum should be at least 1 BTC, got {}",
         maximum
     );
@@ -225,9 +228,8 @@ async fn test_currencies_with_memo_required() {
 
     // All results should have memo = true
     for currency in &currencies {
-        assert_eq!(
+        assert!(
             currency["memo"].as_bool().unwrap(),
-            true,
             "Expected memo=true for {}",
             currency["name"]
         );
@@ -264,7 +266,7 @@ async fn test_currencies_without_memo() {
 
     // All results should have memo = false
     for currency in &currencies {
-        assert_eq!(currency["memo"].as_bool().unwrap(), false);
+        assert!(!currency["memo"].as_bool().unwrap());
     }
 
     // Should include BTC
@@ -321,11 +323,11 @@ async fn test_currency_minimum_maximum_values() {
 
     let xmr = &currencies[0];
 
-    let minimum = xmr["minimum"].as_f64().unwrap();
-    let maximum = xmr["maximum"].as_f64().unwrap();
+    let minimum = amount(&xmr["minimum"]);
+    let maximum = amount(&xmr["maximum"]);
 
     // Minimum should be positive
-    assert!(minimum > 0.0, "Minimum should be positive, got {}", minimum);
+    assert!(minimum > Decimal::ZERO, "Minimum should be positive, got {}", minimum);
 
     // Maximum should be greater than minimum
     assert!(
@@ -337,7 +339,7 @@ async fn test_currency_minimum_maximum_values() {
 
     // Maximum should be reasonable (not infinity or unrealistic)
     assert!(
-        maximum < 1_000_000.0,
+        maximum < dec!(1_000_000),
         "Maximum seems unrealistic: {}",
         maximum
     );
@@ -378,7 +380,7 @@ async fn test_filter_by_network_case_sensitivity() {
 
     // At least one should return results
     assert!(
-        currencies1.len() > 0 || currencies2.len() > 0,
+        !currencies1.is_empty() || !currencies2.is_empty(),
         "Network filtering should work"
     );
 }
@@ -604,3 +606,158 @@ async fn test_pagination_performance_is_under_50ms() {
     assert!(!currencies.is_empty());
     assert!(currencies.len() <= 20);
 }
+
+// =============================================================================
+// GROUPED CURRENCIES (GET /swap/currencies/grouped)
+// =============================================================================
+
+#[tokio::test]
+async fn test_grouped_currencies_lists_each_ticker_once() {
+    let server = setup_test_server().await;
+
+    let response = timed_get(&server, "/swap/currencies/grouped").await;
+    response.assert_status_ok();
+
+    let assets: Vec<Value> = response.json();
+    assert!(!assets.is_empty(), "Expected at least one grouped asset");
+
+    let mut tickers: Vec<String> = assets
+        .iter()
+        .map(|a| a["ticker"].as_str().unwrap().to_string())
+        .collect();
+    let total = tickers.len();
+    tickers.sort();
+    tickers.dedup();
+    assert_eq!(tickers.len(), total, "Each ticker should appear only once");
+
+    let first = &assets[0];
+    assert!(first["name"].is_string());
+    assert!(first["image"].is_string());
+    let networks = first["networks"].as_array().expect("Should have 'networks' array");
+    assert!(!networks.is_empty());
+    assert!(networks[0]["network"].is_string());
+    assert!(networks[0]["memo"].is_boolean());
+    assert!(networks[0]["minimum"].is_number());
+    assert!(networks[0]["maximum"].is_number());
+}
+
+#[tokio::test]
+async fn test_grouped_currencies_btc_has_multiple_networks() {
+    let server = setup_test_server().await;
+
+    let response = server.get("/swap/currencies/grouped?ticker=btc").await;
+    response.assert_status_ok();
+
+    let assets: Vec<Value> = response.json();
+    assert_eq!(assets.len(), 1, "Expected a single BTC entry, got {}", assets.len());
+    assert_eq!(assets[0]["ticker"], "btc");
+
+    let networks = assets[0]["networks"].as_array().unwrap();
+    assert!(
+        networks.len() >= 2,
+        "Expected BTC on multiple networks, got {}",
+        networks.len()
+    );
+}
+
+#[tokio::test]
+async fn test_grouped_currencies_pagination_counts_assets() {
+    let server = setup_test_server().await;
+
+    // Warm the cached full list first; the paginated request must not be served from it
+    let full = timed_get(&server, "/swap/currencies/grouped").await;
+    full.assert_status_ok();
+    let all_assets: Vec<Value> = full.json();
+
+    let response = server.get("/swap/currencies/grouped?page=1&limit=5").await;
+    response.assert_status_ok();
+
+    let assets: Vec<Value> = response.json();
+    assert!(assets.len() <= 5, "Expected at most 5 assets, got {}", assets.len());
+    assert_eq!(assets.len(), all_assets.len().min(5));
+}
+
+// =============================================================================
+// PAGED CURRENCIES (per_page, sort, search, fields)
+// =============================================================================
+
+#[test]
+fn test_only_new_params_ask_for_a_page() {
+    let legacy = CurrenciesQuery { ticker: Some("btc".to_string()), page: Some(2), limit: Some(10), ..Default::default() };
+    assert!(!legacy.wants_page());
+
+    assert!(CurrenciesQuery { per_page: Some(5), ..Default::default() }.wants_page());
+    assert!(CurrenciesQuery { sort: Some("-name".to_string()), ..Default::default() }.wants_page());
+    assert!(CurrenciesQuery { search: Some("bit".to_string()), ..Default::default() }.wants_page());
+    assert!(CurrenciesQuery { fields: Some("ticker".to_string()), ..Default::default() }.wants_page());
+}
+
+#[tokio::test]
+async fn test_per_page_returns_an_envelope_with_totals() {
+    let server = setup_test_server().await;
+
+    let response = timed_get(&server, "/swap/currencies?per_page=5&page=1").await;
+    response.assert_status_ok();
+
+    let body: Value = response.json();
+    let items = body["items"].as_array().expect("Expected items array");
+    assert!(items.len() <= 5, "Expected at most 5 items, got {}", items.len());
+    assert_eq!(body["page"], 1);
+    assert_eq!(body["per_page"], 5);
+    let total = body["total"].as_u64().unwrap();
+    assert_eq!(body["total_pages"].as_u64().unwrap(), total.div_ceil(5));
+}
+
+#[tokio::test]
+async fn test_fields_projects_each_item() {
+    let server = setup_test_server().await;
+
+    let response = server.get("/swap/currencies?fields=ticker,network&per_page=20").await;
+    response.assert_status_ok();
+
+    let body: Value = response.json();
+    for item in body["items"].as_array().unwrap() {
+        let keys: Vec<&String> = item.as_object().unwrap().keys().collect();
+        assert_eq!(keys.len(), 2, "Expected only ticker and network, got {:?}", keys);
+        assert!(item.get("ticker").is_some() && item.get("network").is_some());
+    }
+}
+
+#[tokio::test]
+async fn test_search_matches_ticker_prefix() {
+    let server = setup_test_server().await;
+
+    let response = server.get("/swap/currencies?search=btc").await;
+    response.assert_status_ok();
+
+    let body: Value = response.json();
+    let items = body["items"].as_array().unwrap();
+    assert!(!items.is_empty(), "Expected BTC to match");
+    assert!(items.iter().any(|c| c["ticker"] == "btc"));
+}
+
+#[tokio::test]
+async fn test_sort_descending_by_name() {
+    let server = setup_test_server().await;
+
+    let response = server.get("/swap/currencies?sort=-name&per_page=50").await;
+    response.assert_status_ok();
+
+    let body: Value = response.json();
+    let names: Vec<String> =
+        body["items"].as_array().unwrap().iter().map(|c| c["name"].as_str().unwrap_or("").to_lowercase()).collect();
+    let mut sorted = names.clone();
+    sorted.sort_by(|a, b| b.cmp(a));
+    assert_eq!(names, sorted);
+}
+
+#[tokio::test]
+async fn test_legacy_params_still_return_a_list() {
+    let server = setup_test_server().await;
+
+    let response = server.get("/swap/currencies?page=1&limit=5").await;
+    response.assert_status_ok();
+
+    let body: Value = response.json();
+    assert!(body.is_array(), "Expected a plain array without the new params");
+}
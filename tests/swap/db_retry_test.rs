@@ -0,0 +1,86 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+use exchange_shared::config::environment::DbRetryConfig;
+use exchange_shared::services::db_retry::{backoff, retry_transient, transient_reason};
+
+fn config(max_attempts: u32) -> DbRetryConfig {
+    DbRetryConfig { max_attempts, base_delay: Duration::from_millis(1), max_delay: Duration::from_millis(5) }
+}
+
+fn connection_reset() -> sqlx::Error {
+    sqlx::Error::Io(std::io::Error::new(std::io::ErrorKind::ConnectionReset, "connection reset by peer"))
+}
+
+// =============================================================================
+// UNIT TESTS - DATABASE RETRIES
+// =============================================================================
+
+#[test]
+fn test_only_transient_errors_are_retried() {
+    assert_eq!(transient_reason(&connection_reset()), Some("connection"));
+    assert_eq!(transient_reason(&sqlx::Error::RowNotFound), None);
+    assert_eq!(transient_reason(&sqlx::Error::PoolClosed), None);
+}
+
+#[tokio::test]
+async fn test_transient_error_is_retried_until_it_succeeds() {
+    let calls = AtomicU32::new(0);
+
+    let result = retry_transient(&config(3), "insert swap", || async {
+        match calls.fetch_add(1, Ordering::SeqCst) {
+            0 | 1 => Err(connection_reset()),
+            _ => Ok("stored"),
+        }
+    })
+    .await;
+
+    assert_eq!(result.unwrap(), "stored");
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_retries_stop_at_max_attempts() {
+    let calls = AtomicU32::new(0);
+
+    let result: Result<(), _> = retry_transient(&config(2), "insert swap", || async {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Err(connection_reset())
+    })
+    .await;
+
+    assert!(matches!(result, Err(sqlx::Error::Io(_))));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_other_errors_are_returned_at_once() {
+    let calls = AtomicU32::new(0);
+
+    let result: Result<(), _> = retry_transient(&config(5), "find swap", || async {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Err(sqlx::Error::RowNotFound)
+    })
+    .await;
+
+    assert!(matches!(result, Err(sqlx::Error::RowNotFound)));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_backoff_doubles_within_the_cap() {
+    let config = DbRetryConfig {
+        max_attempts: 5,
+        base_delay: Duration::from_millis(100),
+        max_delay: Duration::from_millis(300),
+    };
+
+    for _ in 0..20 {
+        let first = backoff(&config, 1);
+        assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100));
+        let second = backoff(&config, 2);
+        assert!(second >= Duration::from_millis(100) && second <= Duration::from_millis(200));
+        let capped = backoff(&config, 4);
+        assert!(capped >= Duration::from_millis(150) && capped <= Duration::from_millis(300));
+    }
+}
@@ -0,0 +1,61 @@
+use axum::http::StatusCode;
+use serde_json::Value;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{delete_currency, insert_currency, unique_symbol, TestContext};
+
+// =============================================================================
+// INTEGRATION TESTS - LIQUIDITY DEPTH (GET /swap/depth)
+// Requests here are rejected before any provider is asked for quotes.
+// =============================================================================
+
+fn depth_path(from: &str, amount: Option<&str>) -> String {
+    let mut path = format!("/swap/depth?from={}&network_from=Mainnet&to=xmr&network_to=Mainnet", from);
+    if let Some(amount) = amount {
+        path.push_str(&format!("&amount={}", amount));
+    }
+    path
+}
+
+#[tokio::test]
+async fn test_depth_unknown_currency_is_not_found() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get(&depth_path(&unique_symbol(), Some("1"))).await;
+
+    response.assert_status(StatusCode::NOT_FOUND);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "CURRENCY_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_depth_rejects_non_positive_amount() {
+    let ctx = TestContext::new().await;
+    let symbol = unique_symbol();
+    let id = insert_currency(&ctx, &symbol).await;
+
+    for amount in ["0", "-1"] {
+        let response = ctx.server.get(&depth_path(&symbol, Some(amount))).await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+        let body: Value = response.json();
+        assert_eq!(body["code"], "INVALID_DEPTH_QUERY");
+    }
+
+    delete_currency(&ctx, id).await;
+}
+
+#[tokio::test]
+async fn test_depth_needs_amount_without_known_minimum() {
+    let ctx = TestContext::new().await;
+    let symbol = unique_symbol();
+    let id = insert_currency(&ctx, &symbol).await;
+
+    let response = ctx.server.get(&depth_path(&symbol, None)).await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "INVALID_DEPTH_QUERY");
+
+    delete_currency(&ctx, id).await;
+}
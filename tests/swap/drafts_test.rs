@@ -0,0 +1,92 @@
+use axum::http::StatusCode;
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{amount, TestContext};
+
+// =============================================================================
+// INTEGRATION TESTS - SWAP DRAFTS (/swap/drafts)
+// =============================================================================
+
+#[tokio::test]
+async fn test_draft_can_be_resumed_updated_and_discarded() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx
+        .server
+        .post("/swap/drafts")
+        .json(&json!({
+            "from": "btc",
+            "network_from": "Mainnet",
+            "to": "xmr",
+            "network_to": "Mainnet",
+            "amount": 0.05,
+            "quote": { "trade_id": "abc123", "provider": "ChangeNOW", "estimated_amount": 7.5, "rate": 150.0 }
+        }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let created: Value = response.json();
+    let token = created["token"].as_str().unwrap().to_string();
+    assert_eq!(token.len(), 48);
+
+    // Another device resumes with the token alone, quote intact
+    let path = format!("/swap/drafts/{}", token);
+    let response = ctx.server.get(&path).await;
+    response.assert_status_ok();
+    let resumed: Value = response.json();
+    assert_eq!(amount(&resumed["draft"]["amount"]), dec!(0.05));
+    assert_eq!(resumed["draft"]["quote"]["trade_id"], "abc123");
+
+    let response = ctx
+        .server
+        .put(&path)
+        .json(&json!({
+            "from": "btc",
+            "network_from": "Mainnet",
+            "to": "xmr",
+            "network_to": "Mainnet",
+            "amount": 0.05,
+            "quote": { "trade_id": "abc123", "provider": "ChangeNOW", "estimated_amount": 7.5, "rate": 150.0 },
+            "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A"
+        }))
+        .await;
+    response.assert_status_ok();
+    let updated: Value = response.json();
+    assert_eq!(updated["token"], token.as_str());
+    assert_eq!(updated["created_at"], created["created_at"]);
+    assert!(updated["draft"]["recipient_address"].is_string());
+
+    ctx.server.delete(&path).await.assert_status_ok();
+    let response = ctx.server.get(&path).await;
+    response.assert_status(StatusCode::NOT_FOUND);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "DRAFT_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_invalid_drafts_are_rejected() {
+    let ctx = TestContext::new().await;
+
+    let cases = [
+        json!({ "from": "", "network_from": "Mainnet", "to": "xmr", "network_to": "Mainnet" }),
+        json!({ "from": "btc", "network_from": "Mainnet", "to": "xmr", "network_to": "Mainnet", "amount": -1.0 }),
+        json!({ "from": "btc", "network_from": "Mainnet", "to": "xmr", "network_to": "Mainnet", "refund_address": "x".repeat(300) }),
+    ];
+    for body in cases {
+        let response = ctx.server.post("/swap/drafts").json(&body).await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+        let error: Value = response.json();
+        assert_eq!(error["code"], "INVALID_DRAFT");
+    }
+}
+
+#[tokio::test]
+async fn test_unknown_draft_token_is_not_found() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/swap/drafts/0000000000000000").await;
+
+    response.assert_status(StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,67 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{delete_currency, insert_currency, unique_symbol, TestContext};
+
+// =============================================================================
+// INTEGRATION TESTS - DRY RUN (POST /swap/create with dry_run: true)
+// A dry run runs the same checks as a real create and never writes a swap.
+// =============================================================================
+
+fn swap_request(from: &str, dry_run: bool) -> Value {
+    json!({
+        "from": from,
+        "network_from": "Mainnet",
+        "to": "btc",
+        "network_to": "Mainnet",
+        "amount": 1.0,
+        "provider": "ChangeNOW",
+        "recipient_address": "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+        "dry_run": dry_run
+    })
+}
+
+async fn swaps_from(ctx: &TestContext, symbol: &str) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM swaps WHERE from_currency = ?")
+        .bind(symbol)
+        .fetch_one(&ctx.db)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_dry_run_reports_the_same_errors_as_create() {
+    let ctx = TestContext::new().await;
+    let symbol = unique_symbol();
+    let id = insert_currency(&ctx, &symbol).await;
+    sqlx::query("UPDATE currencies SET delisting_at = NOW() - INTERVAL 1 DAY WHERE id = ?")
+        .bind(id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    for dry_run in [true, false] {
+        let response = ctx.server.post("/swap/create").json(&swap_request(&symbol, dry_run)).await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+        let body: Value = response.json();
+        assert_eq!(body["code"], "CURRENCY_DELISTED", "dry_run: {}", dry_run);
+    }
+    assert_eq!(swaps_from(&ctx, &symbol).await, 0);
+
+    delete_currency(&ctx, id).await;
+}
+
+#[tokio::test]
+async fn test_dry_run_rejects_invalid_recipient_without_writing() {
+    let ctx = TestContext::new().await;
+    let symbol = unique_symbol();
+
+    let mut request = swap_request(&symbol, true);
+    request["recipient_address"] = json!("not-an-address");
+    let response = ctx.server.post("/swap/create").json(&request).await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    assert_eq!(swaps_from(&ctx, &symbol).await, 0);
+}
@@ -0,0 +1,68 @@
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use exchange_shared::modules::swap::crud::SwapError;
+use exchange_shared::modules::swap::schema::SwapStatus;
+use rust_decimal_macros::dec;
+use serde_json::Value;
+
+// =============================================================================
+// UNIT TESTS - SWAP ERROR RESPONSES
+// Every error maps to one status and code, whichever endpoint returns it.
+// =============================================================================
+
+async fn body_of(error: SwapError) -> (StatusCode, Value) {
+    let response = error.into_response();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_errors_map_to_status_and_code() {
+    let cases = [
+        (SwapError::SwapNotFound, StatusCode::NOT_FOUND, "SWAP_NOT_FOUND"),
+        (SwapError::PairNotAvailable, StatusCode::BAD_REQUEST, "PAIR_NOT_AVAILABLE"),
+        (SwapError::ProviderNotQuoting("FixedFloat".to_string()), StatusCode::BAD_REQUEST, "PROVIDER_NOT_QUOTING"),
+        (SwapError::SwapNotRetryable(SwapStatus::Waiting), StatusCode::CONFLICT, "SWAP_NOT_RETRYABLE"),
+        (SwapError::ProviderUnavailable("trocador circuit is open".to_string()), StatusCode::SERVICE_UNAVAILABLE, "PROVIDER_UNAVAILABLE"),
+        (SwapError::ExternalApiError("API error: 400".to_string()), StatusCode::BAD_GATEWAY, "EXTERNAL_API_ERROR"),
+        (SwapError::Database(sqlx::Error::RowNotFound), StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR"),
+    ];
+
+    for (error, status, code) in cases {
+        let message = error.to_string();
+        let (actual_status, body) = body_of(error).await;
+        assert_eq!(actual_status, status, "{}", code);
+        assert_eq!(body["code"], code);
+        assert_eq!(body["error"], message.as_str());
+    }
+}
+
+#[tokio::test]
+async fn test_amount_out_of_range_carries_limits() {
+    let (status, body) = body_of(SwapError::AmountOutOfRange { min: dec!(0.001), max: dec!(5) }).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "AMOUNT_OUT_OF_RANGE");
+    assert_eq!(body["min_amount"], "0.001");
+    assert_eq!(body["max_amount"], "5");
+}
+
+#[tokio::test]
+async fn test_memo_errors_name_the_field() {
+    let error = SwapError::ExtraIdRequired { ticker: "xrp".to_string(), extra_id_name: Some("Destination Tag".to_string()) };
+
+    let (status, body) = body_of(error).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "EXTRA_ID_REQUIRED");
+    assert_eq!(body["extra_id_name"], "Destination Tag");
+    assert_eq!(body["error"], "Destination Tag is required when sending xrp");
+}
+
+#[test]
+fn test_database_errors_keep_their_source() {
+    let error = SwapError::from(sqlx::Error::RowNotFound);
+
+    assert!(std::error::Error::source(&error).is_some());
+}
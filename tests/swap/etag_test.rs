@@ -0,0 +1,77 @@
+use axum::http::StatusCode;
+use exchange_shared::modules::swap::controller::etag_matches;
+use exchange_shared::modules::swap::crud::content_etag;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::setup_test_server;
+
+// =============================================================================
+// UNIT TESTS - LISTING ETAGS
+// =============================================================================
+
+#[test]
+fn test_content_etag_is_a_stable_strong_tag() {
+    let etag = content_etag(r#"[{"ticker":"btc"}]"#);
+
+    assert_eq!(etag, content_etag(r#"[{"ticker":"btc"}]"#));
+    assert_ne!(etag, content_etag(r#"[{"ticker":"eth"}]"#));
+    assert!(etag.starts_with('"') && etag.ends_with('"'));
+    assert_eq!(etag.len(), 34);
+}
+
+#[test]
+fn test_if_none_match_lists_and_weak_tags() {
+    let etag = "\"abc\"";
+
+    assert!(etag_matches("\"abc\"", etag));
+    assert!(etag_matches("\"xyz\", \"abc\"", etag));
+    assert!(etag_matches("W/\"abc\"", etag));
+    assert!(etag_matches("*", etag));
+    assert!(!etag_matches("\"xyz\"", etag));
+    assert!(!etag_matches("abc", etag));
+}
+
+// =============================================================================
+// INTEGRATION TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_currencies_answer_304_for_a_matching_etag() {
+    let server = setup_test_server().await;
+
+    let response = server.get("/swap/currencies").await;
+    response.assert_status_ok();
+    let etag = response.header("etag").to_str().unwrap().to_string();
+
+    let response = server.get("/swap/currencies").add_header("if-none-match", etag.as_str()).await;
+    response.assert_status(StatusCode::NOT_MODIFIED);
+    assert_eq!(response.header("etag").to_str().unwrap(), etag);
+    assert!(response.as_bytes().is_empty());
+
+    let response = server.get("/swap/currencies").add_header("if-none-match", "\"stale\"").await;
+    response.assert_status_ok();
+    assert!(!response.as_bytes().is_empty());
+}
+
+#[tokio::test]
+async fn test_providers_answer_304_for_a_matching_etag() {
+    let server = setup_test_server().await;
+
+    let response = server.get("/swap/providers").await;
+    response.assert_status_ok();
+    let etag = response.header("etag").to_str().unwrap().to_string();
+    assert_eq!(etag, content_etag(&response.text()));
+
+    let response = server.get("/swap/providers").add_header("if-none-match", etag.as_str()).await;
+    response.assert_status(StatusCode::NOT_MODIFIED);
+}
+
+#[tokio::test]
+async fn test_filtered_listings_carry_no_etag() {
+    let server = setup_test_server().await;
+
+    let response = server.get("/swap/currencies?ticker=btc").await;
+    response.assert_status_ok();
+    assert!(response.maybe_header("etag").is_none());
+}
@@ -0,0 +1,174 @@
+use axum::http::StatusCode;
+use exchange_shared::modules::swap::crud::SwapError;
+use exchange_shared::modules::swap::schema::SaveFavoriteRequest;
+use serde_json::{json, Value};
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{create_user_token, delete_currency, delete_swap, insert_currency, insert_swap, unique_symbol, TestContext};
+
+const ADDRESS: &str = "tc1qfavorite0000000000000000000000000000";
+
+async fn favorites(ctx: &TestContext, token: &str) -> Value {
+    let response = ctx.server.get("/swap/favorites").authorization_bearer(token).await;
+    response.assert_status_ok();
+    response.json()
+}
+
+async fn save(ctx: &TestContext, token: &str, body: Value) -> axum_test::TestResponse {
+    ctx.server.post("/swap/favorites").authorization_bearer(token).json(&body).await
+}
+
+// =============================================================================
+// UNIT TESTS - REQUEST SHAPE
+// =============================================================================
+
+#[test]
+fn test_favorite_kind_selects_the_variant() {
+    let pair: SaveFavoriteRequest = serde_json::from_value(json!({
+        "kind": "pair", "from": "btc", "network_from": "Mainnet", "to": "xmr", "network_to": "Mainnet"
+    }))
+    .unwrap();
+    assert!(matches!(pair, SaveFavoriteRequest::Pair { .. }));
+
+    let address: SaveFavoriteRequest = serde_json::from_value(json!({
+        "kind": "address", "ticker": "xmr", "network": "Mainnet", "address": ADDRESS
+    }))
+    .unwrap();
+    assert!(matches!(address, SaveFavoriteRequest::Address { extra_id: None, label: None, .. }));
+
+    assert!(serde_json::from_value::<SaveFavoriteRequest>(json!({ "kind": "swap" })).is_err());
+}
+
+#[test]
+fn test_favorite_errors_have_codes() {
+    assert_eq!(SwapError::FavoriteNotFound.status_code(), StatusCode::NOT_FOUND);
+    assert_eq!(SwapError::TooManyFavorites(50).error_code(), "TOO_MANY_FAVORITES");
+    assert_eq!(SwapError::InvalidFavorite("x".to_string()).status_code(), StatusCode::BAD_REQUEST);
+}
+
+// =============================================================================
+// INTEGRATION TESTS - FAVORITES (GET/POST /swap/favorites)
+// =============================================================================
+
+#[tokio::test]
+async fn test_favorites_require_auth() {
+    let ctx = TestContext::new().await;
+
+    ctx.server.get("/swap/favorites").await.assert_status(StatusCode::UNAUTHORIZED);
+    let response = ctx.server.post("/swap/favorites").json(&json!({ "kind": "pair" })).await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_saved_pair_is_listed_until_removed() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+    let (from, to) = (unique_symbol(), unique_symbol());
+    let currencies = [insert_currency(&ctx, &from).await, insert_currency(&ctx, &to).await];
+    let pair = json!({ "kind": "pair", "from": from.to_uppercase(), "network_from": "Mainnet", "to": to, "network_to": "Mainnet" });
+
+    let response = save(&ctx, &token, pair.clone()).await;
+    response.assert_status_ok();
+    let saved: Value = response.json();
+    assert_eq!(saved["kind"], "pair");
+    assert_eq!(saved["from"], from);
+
+    // Saving it again is a no-op
+    let again: Value = save(&ctx, &token, pair.clone()).await.json();
+    assert_eq!(again["id"], saved["id"]);
+    assert_eq!(favorites(&ctx, &token).await["pairs"].as_array().unwrap().len(), 1);
+
+    let path = format!("/swap/favorites/pairs/{}", saved["id"].as_str().unwrap());
+    ctx.server.delete(&path).authorization_bearer(&token).await.assert_status_ok();
+    ctx.server.delete(&path).authorization_bearer(&token).await.assert_status(StatusCode::NOT_FOUND);
+    assert!(favorites(&ctx, &token).await["pairs"].as_array().unwrap().is_empty());
+
+    // Starring it again restores the same row
+    let restored: Value = save(&ctx, &token, pair).await.json();
+    assert_eq!(restored["id"], saved["id"]);
+
+    for id in currencies {
+        delete_currency(&ctx, id).await;
+    }
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_saved_address_keeps_its_label() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+    let (_, other_token) = create_user_token(&ctx).await;
+    let ticker = unique_symbol();
+    let currency = insert_currency(&ctx, &ticker).await;
+    let address = |label: Option<&str>| {
+        json!({ "kind": "address", "ticker": ticker, "network": "Mainnet", "address": ADDRESS, "label": label })
+    };
+
+    let saved: Value = save(&ctx, &token, address(Some("Cold wallet"))).await.json();
+    assert_eq!(saved["kind"], "address");
+    assert_eq!(saved["label"], "Cold wallet");
+    assert_eq!(saved["times_used"], 0);
+
+    // Omitting the label keeps it, an empty one clears it
+    let kept: Value = save(&ctx, &token, address(None)).await.json();
+    assert_eq!(kept["label"], "Cold wallet");
+    let cleared: Value = save(&ctx, &token, address(Some(""))).await.json();
+    assert!(cleared.get("label").is_none());
+
+    let list = favorites(&ctx, &token).await;
+    assert_eq!(list["addresses"][0]["id"], saved["id"]);
+
+    // Other users neither see nor remove it
+    assert!(favorites(&ctx, &other_token).await["addresses"].as_array().unwrap().is_empty());
+    let path = format!("/swap/favorites/addresses/{}", saved["id"].as_str().unwrap());
+    ctx.server.delete(&path).authorization_bearer(&other_token).await.assert_status(StatusCode::NOT_FOUND);
+
+    ctx.server.delete(&path).authorization_bearer(&token).await.assert_status_ok();
+    assert!(favorites(&ctx, &token).await["addresses"].as_array().unwrap().is_empty());
+
+    delete_currency(&ctx, currency).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_invalid_favorites_are_rejected() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+    let ticker = unique_symbol();
+    let currency = insert_currency(&ctx, &ticker).await;
+
+    let response = save(&ctx, &token, json!({ "kind": "address", "ticker": unique_symbol(), "network": "Mainnet", "address": ADDRESS })).await;
+    response.assert_status(StatusCode::NOT_FOUND);
+    assert_eq!(response.json::<Value>()["code"], "CURRENCY_NOT_FOUND");
+
+    let label = "x".repeat(65);
+    let response = save(&ctx, &token, json!({ "kind": "address", "ticker": ticker, "network": "Mainnet", "address": ADDRESS, "label": label })).await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    assert_eq!(response.json::<Value>()["code"], "INVALID_FAVORITE");
+
+    let response = save(&ctx, &token, json!({ "kind": "pair", "from": ticker, "network_from": "Mainnet", "to": "", "network_to": "Mainnet" })).await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    delete_currency(&ctx, currency).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_recent_pairs_come_from_callers_swaps() {
+    let ctx = TestContext::new().await;
+    let (user_id, token) = create_user_token(&ctx).await;
+    let swaps = [insert_swap(&ctx, "completed", Some(&user_id)).await, insert_swap(&ctx, "waiting", Some(&user_id)).await];
+
+    let list = favorites(&ctx, &token).await;
+    let recent = list["recent_pairs"].as_array().unwrap();
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0]["from"], "btc");
+    assert_eq!(recent[0]["to"], "xmr");
+    assert_eq!(recent[0]["times_used"], 2);
+
+    for id in &swaps {
+        delete_swap(&ctx, id).await;
+    }
+    ctx.cleanup().await;
+}
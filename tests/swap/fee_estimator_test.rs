@@ -0,0 +1,131 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{routing::{get, post}, Json, Router};
+use exchange_shared::config::environment::FeeEstimatorConfig;
+use exchange_shared::services::fee_estimator::{parse_gas_price, parse_recommended_fees, FeeEstimator, FeeSource};
+use exchange_shared::services::redis_cache::RedisService;
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+
+fn config(url: &str) -> FeeEstimatorConfig {
+    FeeEstimatorConfig {
+        enabled: true,
+        mempool_urls: [("btc".to_string(), url.to_string())].into_iter().collect(),
+        evm_rpc_urls: [("erc20".to_string(), format!("{}/rpc", url))].into_iter().collect(),
+        cache_ttl: Duration::from_secs(60),
+        timeout: Duration::from_millis(500),
+    }
+}
+
+/// Local mempool API and JSON-RPC node answering with fixed fees; returns
+/// their URL and the number of requests they served
+async fn fake_sources() -> (String, Arc<AtomicUsize>) {
+    let served = Arc::new(AtomicUsize::new(0));
+    let (mempool, rpc) = (served.clone(), served.clone());
+    let app = Router::new()
+        .route(
+            "/v1/fees/recommended",
+            get(move || {
+                mempool.fetch_add(1, Ordering::SeqCst);
+                async { Json(json!({ "fastestFee": 20, "halfHourFee": 10, "hourFee": 5 })) }
+            }),
+        )
+        .route(
+            "/rpc",
+            post(move |Json(request): Json<Value>| {
+                rpc.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    assert_eq!(request["method"], "eth_gasPrice");
+                    // 20 gwei
+                    Json(json!({ "jsonrpc": "2.0", "id": 1, "result": "0x4a817c800" }))
+                }
+            }),
+        );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (url, served)
+}
+
+// =============================================================================
+// UNIT TESTS - FEE ESTIMATOR
+// =============================================================================
+
+#[test]
+fn test_fee_rates_are_parsed_from_source_responses() {
+    assert_eq!(parse_recommended_fees(&json!({ "fastestFee": 20, "halfHourFee": 12 })), Some(12.0));
+    assert_eq!(parse_recommended_fees(&json!({ "halfHourFee": 0 })), None);
+
+    assert_eq!(parse_gas_price(&json!({ "result": "0x4a817c800" })), Some(20.0));
+    assert_eq!(parse_gas_price(&json!({ "error": { "code": -32000 } })), None);
+}
+
+#[test]
+fn test_sources_resolve_by_ticker_and_network() {
+    let config = config("http://fees");
+
+    assert!(matches!(FeeSource::resolve(&config, "BTC", "Mainnet"), Some(FeeSource::Mempool { .. })));
+    assert!(matches!(
+        FeeSource::resolve(&config, "eth", "Mainnet"),
+        Some(FeeSource::Evm { ref network, gas: 21_000, .. }) if network == "erc20"
+    ));
+    assert!(matches!(
+        FeeSource::resolve(&config, "usdt", "ERC20"),
+        Some(FeeSource::Evm { ref fee_currency, gas: 65_000, .. }) if fee_currency == "eth"
+    ));
+    assert_eq!(FeeSource::resolve(&config, "xmr", "Mainnet"), None);
+    assert_eq!(FeeSource::resolve(&config, "usdt", "trc20"), None);
+}
+
+#[test]
+fn test_estimates_price_one_payout() {
+    let config = config("http://fees");
+
+    let btc = FeeSource::resolve(&config, "btc", "Mainnet").unwrap().estimate(10.0);
+    assert_eq!((btc.currency.as_str(), btc.amount, btc.size), ("btc", dec!(0.000014), 140));
+    assert_eq!(btc.unit, "sat/vB");
+
+    let usdt = FeeSource::resolve(&config, "usdt", "erc20").unwrap().estimate(20.0);
+    assert_eq!((usdt.currency.as_str(), usdt.amount, usdt.size), ("eth", dec!(0.0013), 65_000));
+    assert_eq!(usdt.unit, "gwei");
+}
+
+#[tokio::test]
+async fn test_disabled_estimator_has_no_estimates() {
+    let estimator = FeeEstimator::from_config(&FeeEstimatorConfig { enabled: false, ..config("http://127.0.0.1:1") }, None);
+
+    assert!(!estimator.is_enabled());
+    assert!(estimator.estimate("btc", "Mainnet").await.is_none());
+}
+
+#[tokio::test]
+async fn test_fee_rates_are_cached_per_source() {
+    let (url, served) = fake_sources().await;
+    let estimator = FeeEstimator::from_config(&config(&url), Some(RedisService::in_memory()));
+
+    let btc = estimator.estimate("btc", "Mainnet").await.unwrap();
+    assert_eq!(btc.fee_rate, 10.0);
+    assert_eq!(estimator.estimate("btc", "Mainnet").await, Some(btc));
+
+    // ETH and USDT on ERC20 share one gas price
+    let eth = estimator.estimate("eth", "Mainnet").await.unwrap();
+    let usdt = estimator.estimate("usdt", "erc20").await.unwrap();
+    assert_eq!((eth.amount, usdt.amount), (dec!(0.00042), dec!(0.0013)));
+
+    assert_eq!(served.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_unreachable_source_leaves_estimate_out() {
+    // Nothing listens on port 1
+    let estimator = FeeEstimator::from_config(&config("http://127.0.0.1:1"), None);
+
+    let started = std::time::Instant::now();
+    assert!(estimator.estimate("btc", "Mainnet").await.is_none());
+    // Backing off: the second call does not try again
+    assert!(estimator.estimate("btc", "Mainnet").await.is_none());
+
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
@@ -0,0 +1,145 @@
+use exchange_shared::modules::swap::crud::SwapError;
+use exchange_shared::modules::swap::grpc::proto::swap_service_client::SwapServiceClient;
+use exchange_shared::modules::swap::grpc::proto::{self, GetRatesRequest, GetSwapStatusRequest};
+use exchange_shared::modules::swap::grpc::{create_swap_request, rates_query, swap_status, GrpcSwapService};
+use exchange_shared::modules::swap::schema::RateType;
+use exchange_shared::services::jwt::JwtService;
+use exchange_shared::services::redis_cache::RedisService;
+use tonic::transport::{server::TcpIncoming, Channel, Server};
+use tonic::Code;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::TestContext;
+
+fn rates_request() -> GetRatesRequest {
+    GetRatesRequest {
+        from: "btc".to_string(),
+        network_from: "Mainnet".to_string(),
+        to: "eth".to_string(),
+        network_to: "ERC20".to_string(),
+        amount: "0.5".to_string(),
+        sandbox: true,
+        ..Default::default()
+    }
+}
+
+fn create_request() -> proto::CreateSwapRequest {
+    proto::CreateSwapRequest {
+        from: "btc".to_string(),
+        network_from: "Mainnet".to_string(),
+        to: "eth".to_string(),
+        network_to: "ERC20".to_string(),
+        amount: "0.01".to_string(),
+        recipient_address: "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23".to_string(),
+        refund_address: Some("bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh".to_string()),
+        sandbox: true,
+        ..Default::default()
+    }
+}
+
+/// SwapService on a local port, sharing state with a fresh test app
+async fn grpc_client() -> SwapServiceClient<Channel> {
+    let ctx = TestContext::new().await;
+    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret-key-for-testing-only".to_string());
+    let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+    let state =
+        exchange_shared::create_state(ctx.db.clone(), RedisService::new(&redis_url), JwtService::new(jwt_secret)).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(
+        Server::builder()
+            .add_service(GrpcSwapService::new(state).into_server())
+            .serve_with_incoming(TcpIncoming::from(listener)),
+    );
+    SwapServiceClient::connect(url).await.unwrap()
+}
+
+// =============================================================================
+// UNIT TESTS - GRPC SWAP SERVICE
+// =============================================================================
+
+#[test]
+fn test_rates_request_maps_to_rates_query() {
+    let query = rates_query(GetRatesRequest {
+        rate_type: Some("Fixed".to_string()),
+        provider: Some(String::new()),
+        ..rates_request()
+    })
+    .unwrap();
+
+    assert_eq!(query.rate_type, Some(RateType::Fixed));
+    assert_eq!(query.provider, None);
+    assert!(query.sandbox);
+
+    let unknown = rates_query(GetRatesRequest { rate_type: Some("spot".to_string()), ..rates_request() });
+    assert_eq!(unknown.unwrap_err().code(), Code::InvalidArgument);
+}
+
+#[test]
+fn test_create_request_is_never_a_dry_run() {
+    let request = create_swap_request(create_request()).unwrap();
+
+    assert!(!request.dry_run);
+    assert_eq!(request.rate_type, RateType::Floating);
+    assert!(request.wants_best_provider());
+}
+
+#[test]
+fn test_swap_errors_map_to_grpc_codes() {
+    let not_found = swap_status(SwapError::SwapNotFound);
+    assert_eq!(not_found.code(), Code::NotFound);
+    assert_eq!(not_found.metadata().get("error-code").unwrap(), "SWAP_NOT_FOUND");
+
+    assert_eq!(swap_status(SwapError::CurrencyNotFound).code(), Code::NotFound);
+    assert_eq!(swap_status(SwapError::InvalidAmount("zero".to_string())).code(), Code::InvalidArgument);
+    assert_eq!(swap_status(SwapError::Internal("boom".to_string())).code(), Code::Internal);
+}
+
+// =============================================================================
+// INTEGRATION TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_grpc_sandbox_rates() {
+    let mut client = grpc_client().await;
+
+    let response = client.get_rates(rates_request()).await.unwrap().into_inner();
+
+    assert_eq!(response.from, "btc");
+    assert!(!response.rates.is_empty());
+    assert!(response.rates[0].estimated_amount.parse::<f64>().unwrap() > 0.0);
+}
+
+#[tokio::test]
+async fn test_grpc_create_then_watch_sandbox_swap() {
+    let mut client = grpc_client().await;
+
+    let created = client.create_swap(create_request()).await.unwrap().into_inner();
+    assert!(created.is_sandbox);
+    assert!(created.deposit_address.starts_with("sandbox_deposit_"));
+
+    let request = GetSwapStatusRequest { swap_id: created.swap_id.clone() };
+    let status = client.get_swap_status(request.clone()).await.unwrap().into_inner();
+    assert_eq!(status.swap_id, created.swap_id);
+
+    // The first message is the current status
+    let mut updates = client.watch_swap(request).await.unwrap().into_inner();
+    let first = updates.message().await.unwrap().unwrap();
+    assert_eq!(first.swap_id, created.swap_id);
+    assert_eq!(first.status, status.status);
+}
+
+#[tokio::test]
+async fn test_grpc_unknown_swap_is_not_found() {
+    let mut client = grpc_client().await;
+    let request = GetSwapStatusRequest { swap_id: uuid::Uuid::new_v4().to_string() };
+
+    let error = client.get_swap_status(request.clone()).await.unwrap_err();
+    assert_eq!(error.code(), Code::NotFound);
+    assert_eq!(error.metadata().get("error-code").unwrap(), "SWAP_NOT_FOUND");
+
+    let error = client.watch_swap(request).await.unwrap_err();
+    assert_eq!(error.code(), Code::NotFound);
+}
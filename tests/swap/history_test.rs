@@ -1 +1,110 @@
-// History endpoint tests - to be implemented
+use axum::http::StatusCode;
+use serde_json::Value;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{create_user_token, delete_swap, insert_swap, TestContext};
+
+// =============================================================================
+// INTEGRATION TESTS - SWAP HISTORY ENDPOINT (GET /swap/history)
+// =============================================================================
+
+#[tokio::test]
+async fn test_history_requires_auth() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/swap/history").await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_history_pages_through_callers_swaps_newest_first() {
+    let ctx = TestContext::new().await;
+    let (user_id, token) = create_user_token(&ctx).await;
+    let (other_user_id, _) = create_user_token(&ctx).await;
+
+    let mut ids = Vec::new();
+    for (i, status) in ["completed", "waiting", "completed"].iter().enumerate() {
+        let id = insert_swap(&ctx, status, Some(&user_id)).await;
+        sqlx::query("UPDATE swaps SET created_at = NOW() - INTERVAL ? HOUR WHERE id = ?")
+            .bind(3 - i as i64)
+            .bind(&id)
+            .execute(&ctx.db)
+            .await
+            .unwrap();
+        ids.push(id);
+    }
+    let foreign = insert_swap(&ctx, "completed", Some(&other_user_id)).await;
+
+    let first: Value = ctx
+        .server
+        .get("/swap/history")
+        .add_query_param("limit", 2)
+        .authorization_bearer(&token)
+        .await
+        .json();
+
+    assert_eq!(first["total"], 3);
+    let page: Vec<&str> = first["swaps"].as_array().unwrap().iter().map(|s| s["swap_id"].as_str().unwrap()).collect();
+    assert_eq!(page, vec![ids[2].as_str(), ids[1].as_str()]);
+    let cursor = first["next_cursor"].as_str().expect("expected another page");
+
+    let second: Value = ctx
+        .server
+        .get("/swap/history")
+        .add_query_param("limit", 2)
+        .add_query_param("cursor", cursor)
+        .authorization_bearer(&token)
+        .await
+        .json();
+
+    let page: Vec<&str> = second["swaps"].as_array().unwrap().iter().map(|s| s["swap_id"].as_str().unwrap()).collect();
+    assert_eq!(page, vec![ids[0].as_str()]);
+    assert!(second["next_cursor"].is_null());
+
+    // Filters apply to the page and the total alike
+    let completed: Value = ctx
+        .server
+        .get("/swap/history")
+        .add_query_param("status", "completed")
+        .add_query_param("from", "BTC")
+        .authorization_bearer(&token)
+        .await
+        .json();
+
+    assert_eq!(completed["total"], 2);
+    assert!(completed["swaps"].as_array().unwrap().iter().all(|s| s["status"] == "completed"));
+
+    let none: Value = ctx
+        .server
+        .get("/swap/history")
+        .add_query_param("provider", "some-other-provider")
+        .authorization_bearer(&token)
+        .await
+        .json();
+    assert_eq!(none["total"], 0);
+
+    for id in ids.iter().chain([&foreign]) {
+        delete_swap(&ctx, id).await;
+    }
+}
+
+#[tokio::test]
+async fn test_history_rejects_bad_cursor_and_dates() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    for (key, value) in [("cursor", "garbage"), ("from_date", "yesterday")] {
+        let response = ctx
+            .server
+            .get("/swap/history")
+            .add_query_param(key, value)
+            .authorization_bearer(&token)
+            .await;
+
+        response.assert_status(StatusCode::BAD_REQUEST);
+        let body: Value = response.json();
+        assert_eq!(body["code"], "INVALID_HISTORY_QUERY");
+    }
+}
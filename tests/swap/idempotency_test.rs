@@ -0,0 +1,156 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use exchange_shared::modules::swap::schema::CreateSwapRequest;
+use exchange_shared::services::idempotency::{IdempotencyStore, ANONYMOUS_SCOPE};
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::TestContext;
+
+// =============================================================================
+// INTEGRATION TESTS - IDEMPOTENCY-KEY ON POST /swap/create
+// Keys are claimed directly in the table, so no trade reaches Trocador.
+// =============================================================================
+
+fn swap_request() -> Value {
+    json!({
+        "from": "btc",
+        "network_from": "Mainnet",
+        "to": "xmr",
+        "network_to": "Mainnet",
+        "amount": 0.01,
+        "provider": "ChangeNOW",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A"
+    })
+}
+
+fn request_hash(body: &Value) -> String {
+    let request: CreateSwapRequest = serde_json::from_value(body.clone()).unwrap();
+    IdempotencyStore::request_hash(&request)
+}
+
+fn unique_key() -> String {
+    format!("test-{}", uuid::Uuid::new_v4())
+}
+
+async fn store_key(ctx: &TestContext, key: &str, request_hash: &str, response: Option<Value>) {
+    sqlx::query(
+        "INSERT INTO idempotency_keys (endpoint, scope, idempotency_key, request_hash, response)
+         VALUES ('swap_create', ?, ?, ?, ?)",
+    )
+    .bind(ANONYMOUS_SCOPE)
+    .bind(key)
+    .bind(request_hash)
+    .bind(response.map(|r| r.to_string()))
+    .execute(&ctx.db)
+    .await
+    .expect("Failed to insert idempotency key");
+}
+
+async fn delete_key(ctx: &TestContext, key: &str) {
+    sqlx::query("DELETE FROM idempotency_keys WHERE idempotency_key = ?")
+        .bind(key)
+        .execute(&ctx.db)
+        .await
+        .ok();
+}
+
+#[tokio::test]
+async fn test_replayed_key_returns_original_response() {
+    let ctx = TestContext::new().await;
+    let key = unique_key();
+    let body = swap_request();
+    let stored = json!({
+        "swap_id": "00000000-0000-0000-0000-000000000001",
+        "provider": "ChangeNOW",
+        "from": "btc",
+        "to": "xmr",
+        "deposit_address": "bc1qdeposit000000000000000000000000000000",
+        "deposit_amount": 0.01,
+        "recipient_address": body["recipient_address"],
+        "estimated_receive": 1.5,
+        "rate": 150.0,
+        "status": "waiting",
+        "rate_type": "floating",
+        "is_sandbox": false,
+        "expires_at": "2026-02-20T13:00:00Z",
+        "created_at": "2026-02-20T12:00:00Z"
+    });
+    store_key(&ctx, &key, &request_hash(&body), Some(stored)).await;
+
+    let response = ctx
+        .server
+        .post("/swap/create")
+        .add_header("idempotency-key", key.as_str())
+        .json(&body)
+        .await;
+
+    response.assert_status(StatusCode::CREATED);
+    assert_eq!(response.header("idempotent-replayed"), "true");
+    let replayed: Value = response.json();
+    assert_eq!(replayed["swap_id"], "00000000-0000-0000-0000-000000000001");
+    assert_eq!(replayed["deposit_address"], "bc1qdeposit000000000000000000000000000000");
+
+    delete_key(&ctx, &key).await;
+}
+
+#[tokio::test]
+async fn test_key_reused_with_different_body_is_rejected() {
+    let ctx = TestContext::new().await;
+    let key = unique_key();
+    let body = swap_request();
+    store_key(&ctx, &key, &request_hash(&body), Some(json!({}))).await;
+
+    let mut changed = body.clone();
+    changed["amount"] = json!(0.02);
+    let response = ctx
+        .server
+        .post("/swap/create")
+        .add_header("idempotency-key", key.as_str())
+        .json(&changed)
+        .await;
+
+    response.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    let error: Value = response.json();
+    assert_eq!(error["code"], "IDEMPOTENCY_KEY_REUSED");
+
+    delete_key(&ctx, &key).await;
+}
+
+#[tokio::test]
+async fn test_key_still_in_progress_conflicts() {
+    let ctx = TestContext::new().await;
+    let key = unique_key();
+    let body = swap_request();
+    store_key(&ctx, &key, &request_hash(&body), None).await;
+
+    let response = ctx
+        .server
+        .post("/swap/create")
+        .add_header("idempotency-key", key.as_str())
+        .json(&body)
+        .await;
+
+    response.assert_status(StatusCode::CONFLICT);
+    let error: Value = response.json();
+    assert_eq!(error["code"], "IDEMPOTENCY_KEY_IN_PROGRESS");
+
+    delete_key(&ctx, &key).await;
+}
+
+#[tokio::test]
+async fn test_invalid_key_is_rejected() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx
+        .server
+        .post("/swap/create")
+        .add_header("idempotency-key", "x".repeat(256))
+        .json(&swap_request())
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let error: Value = response.json();
+    assert_eq!(error["code"], "INVALID_IDEMPOTENCY_KEY");
+}
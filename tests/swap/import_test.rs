@@ -0,0 +1,67 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{create_user_token, delete_swap, insert_swap, TestContext};
+
+// =============================================================================
+// INTEGRATION TESTS - SWAP IMPORT (POST /swap/import)
+// Requests that fail before the provider is called; a successful import
+// needs a real trade id
+// =============================================================================
+
+#[tokio::test]
+async fn test_import_requires_account() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx
+        .server
+        .post("/swap/import")
+        .json(&json!({ "provider": "ChangeNow", "trade_id": "abc123" }))
+        .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_import_rejects_blank_trade_id() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx
+        .server
+        .post("/swap/import")
+        .authorization_bearer(&token)
+        .json(&json!({ "provider": "ChangeNow", "trade_id": "  " }))
+        .await;
+
+    response.assert_status_bad_request();
+    assert_eq!(response.json::<Value>()["code"], "INVALID_IMPORT");
+}
+
+#[tokio::test]
+async fn test_import_refuses_tracked_trade() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+    let swap_id = insert_swap(&ctx, "waiting", None).await;
+    let trade_id = format!("trade-{}", uuid::Uuid::new_v4().simple());
+    sqlx::query("UPDATE swaps SET provider_swap_id = ? WHERE id = ?")
+        .bind(&trade_id)
+        .bind(&swap_id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    let response = ctx
+        .server
+        .post("/swap/import")
+        .authorization_bearer(&token)
+        .json(&json!({ "provider": "ChangeNow", "trade_id": trade_id }))
+        .await;
+
+    response.assert_status(StatusCode::CONFLICT);
+    assert_eq!(response.json::<Value>()["code"], "TRADE_ALREADY_TRACKED");
+
+    delete_swap(&ctx, &swap_id).await;
+}
@@ -0,0 +1,150 @@
+use axum::http::StatusCode;
+use exchange_shared::config::environment::ProviderSelectionConfig;
+use exchange_shared::modules::swap::crud::{kyc_rating_allows, parse_kyc_rating, select_best_quote, SwapError};
+use exchange_shared::modules::swap::schema::RateResponse;
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{create_user_token, TestContext};
+
+fn quote(provider: &str, estimated_amount: f64, kyc_rating: Option<&str>) -> RateResponse {
+    serde_json::from_value(json!({
+        "provider": provider,
+        "provider_name": provider,
+        "rate": estimated_amount,
+        "estimated_amount": estimated_amount,
+        "min_amount": 0.001,
+        "max_amount": 10.0,
+        "network_fee": 0.0,
+        "provider_fee": 0.0,
+        "platform_fee": 0.0,
+        "total_fee": 0.0,
+        "rate_type": "floating",
+        "kyc_required": kyc_rating != Some("A"),
+        "kyc_rating": kyc_rating
+    }))
+    .unwrap()
+}
+
+// =============================================================================
+// UNIT TESTS - KYC RATINGS
+// =============================================================================
+
+#[test]
+fn test_kyc_ratings_parse_one_letter_a_to_d() {
+    assert_eq!(parse_kyc_rating("A").unwrap(), 'A');
+    assert_eq!(parse_kyc_rating(" c ").unwrap(), 'C');
+
+    for invalid in ["", "E", "AB", "1"] {
+        let error = parse_kyc_rating(invalid).unwrap_err();
+        assert_eq!(error.error_code(), "INVALID_KYC_RATING");
+        assert_eq!(error.status_code(), StatusCode::BAD_REQUEST);
+    }
+}
+
+#[test]
+fn test_kyc_rating_allows_same_or_better() {
+    assert!(kyc_rating_allows(Some("A"), 'B'));
+    assert!(kyc_rating_allows(Some("b"), 'B'));
+    assert!(!kyc_rating_allows(Some("C"), 'B'));
+    // Unrated providers could ask for anything
+    assert!(!kyc_rating_allows(None, 'D'));
+}
+
+#[test]
+fn test_selection_policy_keeps_to_the_kyc_limit() {
+    let rates = vec![quote("changenow", 1.2, None), quote("exolix", 1.1, Some("C")), quote("fixedfloat", 1.0, Some("A"))];
+    let policy = ProviderSelectionConfig { min_kyc_rating: Some('A'), ..Default::default() };
+
+    let best = select_best_quote(&rates, &policy, dec!(0.5)).unwrap();
+
+    assert_eq!(best.provider, "fixedfloat");
+}
+
+#[test]
+fn test_kyc_rating_not_accepted_error() {
+    let error = SwapError::KycRatingNotAccepted { provider: "exolix".to_string(), rating: "C".to_string(), max: 'A' };
+
+    assert_eq!(error.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error.error_code(), "KYC_RATING_NOT_ACCEPTED");
+    assert_eq!(error.to_string(), "exolix has KYC rating C, worse than the A you accept");
+}
+
+// =============================================================================
+// INTEGRATION TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_rates_reject_an_invalid_max_kyc_rating() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx
+        .server
+        .get("/swap/rates?from=btc&network_from=Mainnet&to=eth&network_to=ERC20&amount=0.5&max_kyc_rating=Z&sandbox=true")
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "INVALID_KYC_RATING");
+}
+
+#[tokio::test]
+async fn test_sandbox_rates_within_max_kyc_rating() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx
+        .server
+        .get("/swap/rates?from=btc&network_from=Mainnet&to=eth&network_to=ERC20&amount=0.5&max_kyc_rating=A&sandbox=true")
+        .await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    let rates = body["rates"].as_array().unwrap();
+    assert!(!rates.is_empty());
+    assert!(rates.iter().all(|r| r["kyc_rating"] == "A"));
+}
+
+#[tokio::test]
+async fn test_create_swap_refuses_a_provider_past_the_users_kyc_limit() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+    let slug = format!("test-{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    sqlx::query("INSERT INTO providers (id, name, slug, kyc_rating) VALUES (?, ?, ?, 'C')")
+        .bind(&slug)
+        .bind(&slug)
+        .bind(&slug)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    let response = ctx
+        .server
+        .put("/auth/swap-preferences")
+        .authorization_bearer(&token)
+        .json(&json!({ "max_kyc_rating": "a" }))
+        .await;
+    response.assert_status_ok();
+
+    let response = ctx
+        .server
+        .post("/swap/create")
+        .authorization_bearer(&token)
+        .json(&json!({
+            "from": "btc",
+            "network_from": "Mainnet",
+            "to": "eth",
+            "network_to": "ERC20",
+            "amount": 0.01,
+            "provider": slug,
+            "recipient_address": "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23",
+            "refund_address": "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh"
+        }))
+        .await;
+
+    sqlx::query("DELETE FROM providers WHERE id = ?").bind(&slug).execute(&ctx.db).await.unwrap();
+    response.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "KYC_RATING_NOT_ACCEPTED");
+}
@@ -0,0 +1,274 @@
+use axum_test::TestServer;
+use chrono::Utc;
+use exchange_shared::modules::swap::lite::{lite_app, LiteState};
+use exchange_shared::modules::swap::model::Swap;
+use exchange_shared::modules::swap::repository::{SqliteSwapRepository, SwapRepository};
+use exchange_shared::modules::swap::schema::{RateType, SwapStatus, TrocadorCurrency, TrocadorProvider};
+use exchange_shared::services::redis_cache::RedisService;
+use exchange_shared::services::tenant::TenantId;
+use exchange_shared::services::trocador::TrocadorClient;
+use rust_decimal_macros::dec;
+use serde_json::json;
+use std::sync::Arc;
+
+// =============================================================================
+// INTEGRATION TESTS - LIGHTWEIGHT MODE (SQLITE, IN-MEMORY CACHE)
+// =============================================================================
+
+const XMR_ADDRESS: &str =
+    "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A";
+
+fn currency(ticker: &str, network: &str, memo: bool) -> TrocadorCurrency {
+    TrocadorCurrency {
+        name: ticker.to_uppercase(),
+        ticker: ticker.to_string(),
+        network: network.to_string(),
+        memo,
+        image: String::new(),
+        minimum: dec!(0.001),
+        maximum: dec!(100),
+        unknown: Default::default(),
+    }
+}
+
+fn finished_swap(id: &str) -> Swap {
+    let now = Utc::now();
+    Swap {
+        id: id.to_string(),
+        tenant_id: "default".to_string(),
+        user_id: None,
+        brand: None,
+        provider_id: "changenow".to_string(),
+        provider_swap_id: Some("trade-1".to_string()),
+        retried_from: None,
+        from_currency: "btc".to_string(),
+        from_network: "Mainnet".to_string(),
+        to_currency: "xmr".to_string(),
+        to_network: "Mainnet".to_string(),
+        amount: dec!(0.1),
+        estimated_receive: dec!(15),
+        actual_receive: Some(dec!(14.9)),
+        rate: dec!(150),
+        network_fee: dec!(0),
+        provider_fee: dec!(0),
+        platform_fee: dec!(0),
+        total_fee: dec!(0),
+        deposit_address: "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string(),
+        deposit_extra_id: None,
+        recipient_address: XMR_ADDRESS.to_string(),
+        recipient_extra_id: None,
+        refund_address: None,
+        refund_extra_id: None,
+        tx_hash_in: None,
+        tx_hash_out: None,
+        deposit_confirmations: None,
+        status: SwapStatus::Completed,
+        rate_type: RateType::Floating,
+        is_sandbox: false,
+        imported: false,
+        error: None,
+        version: 0,
+        expires_at: None,
+        completed_at: Some(now),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+async fn repo() -> SqliteSwapRepository {
+    SqliteSwapRepository::connect("sqlite::memory:").await.expect("Failed to open SQLite")
+}
+
+async fn server(repo: SqliteSwapRepository) -> TestServer {
+    let state = Arc::new(LiteState {
+        repo: Arc::new(repo),
+        redis: RedisService::in_memory(),
+        trocador: TrocadorClient::new("test".to_string()),
+        tenant: TenantId::default(),
+        address_verification: Default::default(),
+        high_value: Default::default(),
+    });
+    TestServer::new(lite_app(state)).expect("Failed to create test server")
+}
+
+#[tokio::test]
+async fn test_currency_sync_upserts_and_deactivates() {
+    let repo = repo().await;
+
+    repo.sync_currencies(&[currency("btc", "Mainnet", false), currency("xmr", "Mainnet", false)])
+        .await
+        .unwrap();
+    assert_eq!(repo.active_currencies().await.unwrap().len(), 2);
+
+    // XMR is no longer listed
+    repo.sync_currencies(&[currency("btc", "Mainnet", false)]).await.unwrap();
+    let active = repo.active_currencies().await.unwrap();
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].symbol, "btc");
+    assert!(!repo.find_currency("XMR", "mainnet").await.unwrap().unwrap().is_active);
+
+    // An empty listing is a bad response, not a mass delisting
+    repo.sync_currencies(&[]).await.unwrap();
+    assert_eq!(repo.active_currencies().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_swaps_round_trip_and_status_updates() {
+    let repo = repo().await;
+    let mut swap = finished_swap("lite-swap-1");
+    swap.status = SwapStatus::Waiting;
+    swap.actual_receive = None;
+    swap.completed_at = None;
+    repo.insert_swap(&swap).await.unwrap();
+
+    let stored = repo.find_swap("lite-swap-1").await.unwrap().unwrap();
+    assert_eq!(stored.status, SwapStatus::Waiting);
+    assert_eq!(stored.recipient_address, XMR_ADDRESS);
+
+    repo.update_swap_status("lite-swap-1", &SwapStatus::Completed, Some(dec!(14.9))).await.unwrap();
+    let stored = repo.find_swap("lite-swap-1").await.unwrap().unwrap();
+    assert_eq!(stored.status, SwapStatus::Completed);
+    assert_eq!(stored.actual_receive, Some(dec!(14.9)));
+    assert_eq!(stored.version, 1);
+    assert!(stored.completed_at.is_some());
+
+    assert!(repo.find_swap("missing").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_listings_are_served() {
+    let repo = repo().await;
+    repo.sync_currencies(&[currency("btc", "Mainnet", false), currency("xrp", "Mainnet", true)])
+        .await
+        .unwrap();
+    repo.sync_providers(&[TrocadorProvider {
+        name: "Change Now".to_string(),
+        rating: "A".to_string(),
+        insurance: 0.015,
+        enabled_markup: true,
+        eta: 12.0,
+        unknown: Default::default(),
+    }])
+    .await
+    .unwrap();
+    let server = server(repo).await;
+
+    let response = server.get("/swap/currencies").add_query_param("memo", true).await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body, json!([{
+        "name": "XRP", "ticker": "xrp", "network": "Mainnet", "memo": true,
+        "image": "", "minimum": "0.001", "maximum": "100"
+    }]));
+
+    let response = server.get("/swap/providers").await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body[0]["name"], "Change Now");
+    assert_eq!(body[0]["eta"], 12);
+}
+
+#[tokio::test]
+async fn test_create_swap_is_checked_before_the_provider() {
+    let repo = repo().await;
+    repo.sync_currencies(&[currency("btc", "Mainnet", false), currency("xmr", "Mainnet", false)])
+        .await
+        .unwrap();
+    let server = server(repo).await;
+
+    let request = json!({
+        "from": "btc", "network_from": "Mainnet",
+        "to": "xmr", "network_to": "Mainnet",
+        "amount": 0.1, "provider": "changenow",
+        "recipient_address": XMR_ADDRESS
+    });
+
+    let mut unknown = request.clone();
+    unknown["to"] = json!("doge");
+    let response = server.post("/swap/create").json(&unknown).await;
+    response.assert_status_not_found();
+    assert_eq!(response.json::<serde_json::Value>()["code"], "CURRENCY_NOT_FOUND");
+
+    let mut too_small = request.clone();
+    too_small["amount"] = json!(0.0001);
+    let response = server.post("/swap/create").json(&too_small).await;
+    response.assert_status_bad_request();
+    assert_eq!(response.json::<serde_json::Value>()["code"], "AMOUNT_OUT_OF_RANGE");
+
+    let mut bad_address = request.clone();
+    bad_address["recipient_address"] = json!(XMR_ADDRESS.replace("BEP3A", "BEP3B"));
+    let response = server.post("/swap/create").json(&bad_address).await;
+    response.assert_status_bad_request();
+    assert_eq!(response.json::<serde_json::Value>()["code"], "INVALID_ADDRESS");
+
+    let mut too_precise = request.clone();
+    too_precise["amount"] = json!("0.123456789");
+    let response = server.post("/swap/create").json(&too_precise).await;
+    response.assert_status_bad_request();
+    assert_eq!(response.json::<serde_json::Value>()["code"], "INVALID_AMOUNT");
+
+    let mut dry_run = request.clone();
+    dry_run["dry_run"] = json!(true);
+    let response = server.post("/swap/create").json(&dry_run).await;
+    response.assert_status(axum::http::StatusCode::NOT_IMPLEMENTED);
+    assert_eq!(response.json::<serde_json::Value>()["code"], "NOT_SUPPORTED");
+}
+
+#[tokio::test]
+async fn test_create_swap_refuses_a_delisted_currency() {
+    let repo = repo().await;
+    repo.sync_currencies(&[currency("btc", "Mainnet", false), currency("xmr", "Mainnet", false)])
+        .await
+        .unwrap();
+    sqlx::query("UPDATE currencies SET delisting_at = ? WHERE symbol = 'xmr'")
+        .bind(Utc::now() - chrono::Duration::hours(1))
+        .execute(repo.pool())
+        .await
+        .unwrap();
+    let server = server(repo).await;
+
+    let response = server
+        .post("/swap/create")
+        .json(&json!({
+            "from": "btc", "network_from": "Mainnet",
+            "to": "xmr", "network_to": "Mainnet",
+            "amount": 0.1, "provider": "changenow",
+            "recipient_address": XMR_ADDRESS
+        }))
+        .await;
+    response.assert_status_bad_request();
+    assert_eq!(response.json::<serde_json::Value>()["code"], "CURRENCY_DELISTED");
+}
+
+#[tokio::test]
+async fn test_fee_rules_are_read_per_tenant() {
+    let repo = repo().await;
+    sqlx::query(
+        "INSERT INTO fee_rules (tenant_id, to_currency, fee_percent, flat_fee)
+         VALUES ('default', 'xmr', 0.5, 0.01), ('other', NULL, 1, 0)",
+    )
+    .execute(repo.pool())
+    .await
+    .unwrap();
+
+    let rules = repo.fee_rules(&TenantId::default()).await.unwrap();
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0].to_currency.as_deref(), Some("xmr"));
+    assert_eq!(rules[0].fee_percent, dec!(0.5));
+    assert_eq!(rules[0].flat_fee, dec!(0.01));
+}
+
+#[tokio::test]
+async fn test_finished_swap_status_is_served_from_storage() {
+    let repo = repo().await;
+    repo.insert_swap(&finished_swap("lite-swap-2")).await.unwrap();
+    let server = server(repo).await;
+
+    let response = server.get("/swap/lite-swap-2").await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["status"], "completed");
+    assert_eq!(body["actual_receive"], "14.9");
+
+    server.get("/swap/missing").await.assert_status_not_found();
+}
@@ -0,0 +1,57 @@
+use exchange_shared::services::redis_cache::RedisService;
+
+// =============================================================================
+// UNIT TESTS - IN-MEMORY CACHE (REDIS_URL=memory://)
+// =============================================================================
+
+#[tokio::test]
+async fn test_memory_url_selects_the_in_memory_cache() {
+    assert!(RedisService::new("memory://").is_in_memory());
+    assert!(RedisService::new("memory://").get_client().is_none());
+    assert!(!RedisService::new("redis://127.0.0.1/").is_in_memory());
+}
+
+#[tokio::test]
+async fn test_strings_json_and_expiry() {
+    let cache = RedisService::in_memory();
+
+    cache.set_string("a", "1", 60).await.unwrap();
+    assert_eq!(cache.get_string("a").await.unwrap().as_deref(), Some("1"));
+    assert!((59..=60).contains(&cache.ttl("a").await.unwrap()));
+
+    cache.set_json("j", &vec![1, 2, 3], 60).await.unwrap();
+    assert_eq!(cache.get_json::<Vec<i32>>("j").await.unwrap(), Some(vec![1, 2, 3]));
+
+    // A zero TTL leaves nothing behind, as with SETEX's immediate expiry
+    cache.set_string("a", "", 0).await.unwrap();
+    assert_eq!(cache.get_string("a").await.unwrap(), None);
+    assert_eq!(cache.ttl("a").await.unwrap(), -2);
+
+    cache.set_string("gone", "x", 1).await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+    assert_eq!(cache.get_string("gone").await.unwrap(), None);
+
+    cache.set_string("d", "x", 60).await.unwrap();
+    cache.delete("d").await.unwrap();
+    assert_eq!(cache.get_string("d").await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_counters_locks_and_rate_limits() {
+    let cache = RedisService::in_memory();
+
+    assert_eq!(cache.incr_with_ttl("n", 60).await.unwrap(), 1);
+    assert_eq!(cache.incr_with_ttl("n", 60).await.unwrap(), 2);
+    assert!(cache.ttl("n").await.unwrap() > 0);
+
+    assert!(cache.try_lock("lock", 60).await.unwrap());
+    assert!(!cache.try_lock("lock", 60).await.unwrap(), "held until it expires");
+
+    assert!(cache.check_rate_limit("rl", 2, 60).await.unwrap());
+    assert!(cache.check_rate_limit("rl", 2, 60).await.unwrap());
+    assert!(!cache.check_rate_limit("rl", 2, 60).await.unwrap());
+
+    // No pub/sub without Redis; status streams report it instead of hanging
+    assert_eq!(cache.publish("channel", "message").await.unwrap(), 0);
+    assert!(cache.subscribe("channel").await.is_err());
+}
@@ -0,0 +1,70 @@
+use exchange_shared::services::metrics::metrics;
+use exchange_shared::services::redis_cache::RedisService;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::TestContext;
+
+// =============================================================================
+// INTEGRATION TESTS - PROMETHEUS METRICS
+// =============================================================================
+
+#[tokio::test]
+async fn test_metrics_endpoint_serves_prometheus_text() {
+    let ctx = TestContext::new().await;
+
+    metrics().swaps_created.inc("waiting");
+    metrics().trocador_request_seconds.observe("coins", 0.3);
+
+    let response = ctx.server.get("/metrics").await;
+    response.assert_status_ok();
+    let content_type = response.header("content-type");
+    assert!(content_type.to_str().unwrap().starts_with("text/plain"));
+
+    let body = response.text();
+    assert!(body.contains("# TYPE exchange_swaps_created_total counter"));
+    assert!(body.contains("exchange_swaps_created_total{status=\"waiting\"}"));
+    assert!(body.contains("# TYPE exchange_trocador_request_duration_seconds histogram"));
+    assert!(body.contains("exchange_trocador_request_duration_seconds_bucket{endpoint=\"coins\",le=\"0.25\"}"));
+    assert!(body.contains("exchange_trocador_request_duration_seconds_bucket{endpoint=\"coins\",le=\"+Inf\"}"));
+}
+
+#[test]
+fn test_histogram_buckets_are_cumulative() {
+    let histogram = &metrics().trocador_request_seconds;
+    let before = histogram.count("metrics_test");
+
+    histogram.observe("metrics_test", 0.07);
+    histogram.observe("metrics_test", 3.0);
+    histogram.observe("metrics_test", 120.0);
+    assert_eq!(histogram.count("metrics_test"), before + 3);
+
+    let rendered = metrics().render();
+    let bucket = |le: &str| {
+        let prefix = format!(
+            "exchange_trocador_request_duration_seconds_bucket{{endpoint=\"metrics_test\",le=\"{}\"}} ",
+            le
+        );
+        rendered
+            .lines()
+            .find_map(|line| line.strip_prefix(prefix.as_str()))
+            .and_then(|count| count.parse::<u64>().ok())
+            .unwrap()
+    };
+    assert_eq!(bucket("0.05"), 0);
+    assert_eq!(bucket("0.1"), 1);
+    assert_eq!(bucket("5"), 2);
+    assert_eq!(bucket("30"), 2);
+    assert_eq!(bucket("+Inf"), 3);
+}
+
+#[tokio::test]
+async fn test_failed_redis_commands_are_counted() {
+    // Nothing listens on port 1, so the command fails to connect and the
+    // fallback answers it instead
+    let redis = RedisService::new("redis://127.0.0.1:1/");
+    let before = metrics().redis_errors.get("GET");
+
+    assert_eq!(redis.get_string("metrics:test").await.unwrap(), None);
+    assert_eq!(metrics().redis_errors.get("GET"), before + 1);
+}
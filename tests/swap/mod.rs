@@ -1,8 +1,60 @@
 pub mod currencies_test;
+pub mod version_test;
 pub mod pairs_test;
 pub mod rates_test;
 pub mod estimate_test;
 pub mod create_test;
+pub mod routes_test;
 pub mod status_test;
 pub mod history_test;
 pub mod providers_test;
+pub mod sql_filters_test;
+pub mod retry_test;
+pub mod batch_status_test;
+pub mod analytics_test;
+pub mod outbox_test;
+pub mod webhook_test;
+pub mod poller_test;
+pub mod warmup_test;
+pub mod stream_test;
+pub mod dry_run_test;
+pub mod uptime_test;
+pub mod sync_test;
+pub mod refund_addresses_test;
+pub mod favorites_test;
+pub mod fee_estimator_test;
+pub mod price_feed_test;
+pub mod transactions_test;
+pub mod idempotency_test;
+pub mod circuit_breaker_test;
+pub mod drafts_test;
+pub mod errors_test;
+pub mod depth_test;
+pub mod quote_test;
+pub mod metrics_test;
+pub mod rate_guard_test;
+pub mod consistency_test;
+pub mod address_validator_test;
+pub mod memory_cache_test;
+pub mod cache_degradation_test;
+pub mod share_test;
+pub mod import_test;
+pub mod openapi_test;
+pub mod address_verification_test;
+pub mod sandbox_test;
+pub mod provider_selection_test;
+pub mod admission_test;
+pub mod amount_to_test;
+pub mod etag_test;
+pub mod kyc_test;
+pub mod volume_limit_test;
+#[cfg(feature = "grpc")]
+pub mod grpc_test;
+pub mod request_id_test;
+pub mod money_test;
+pub mod provider_health_test;
+pub mod state_test;
+pub mod single_flight_test;
+pub mod db_retry_test;
+#[cfg(feature = "sqlite")]
+pub mod lite_test;
@@ -0,0 +1,93 @@
+use exchange_shared::services::money::{
+    exceeds_decimals, from_f64, parse_amount, rate, round_receive, round_send, scale_of, MAX_AMOUNT_DECIMALS,
+};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde_json::Value;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{amount, setup_test_server, timed_get};
+
+// =============================================================================
+// UNIT TESTS - MONEY
+// =============================================================================
+
+#[test]
+fn test_parses_plain_and_scientific_amounts() {
+    assert_eq!(parse_amount("0.0123"), Some(dec!(0.0123)));
+    assert_eq!(parse_amount(" 12.50 "), Some(dec!(12.5)));
+    assert_eq!(parse_amount("1.5e-7"), Some(dec!(0.00000015)));
+    assert_eq!(parse_amount("not a number"), None);
+}
+
+#[test]
+fn test_floats_convert_by_their_shortest_form() {
+    assert_eq!(from_f64(0.1), dec!(0.1));
+    assert_eq!(from_f64(0.1 + 0.2), dec!(0.30000000000000004));
+    assert_eq!(from_f64(f64::NAN), Decimal::ZERO);
+}
+
+#[test]
+fn test_rate_keeps_twelve_places_and_survives_zero() {
+    assert_eq!(rate(dec!(1), dec!(3)), dec!(0.333333333333));
+    assert_eq!(rate(dec!(150), dec!(0.01)), dec!(15000));
+    assert_eq!(rate(dec!(150), Decimal::ZERO), Decimal::ZERO);
+}
+
+#[test]
+fn test_receive_rounds_down_and_send_rounds_up() {
+    assert_eq!(round_receive(dec!(1.234567899), 8), dec!(1.23456789));
+    assert_eq!(round_send(dec!(1.234567891), 8), dec!(1.23456790));
+    assert_eq!(round_receive(dec!(0.129), 2), dec!(0.12));
+    assert_eq!(round_send(dec!(0.121), 2), dec!(0.13));
+    assert_eq!(round_send(dec!(0.12), 2), dec!(0.12));
+}
+
+#[test]
+fn test_detects_amounts_past_the_currency_places() {
+    assert!(exceeds_decimals(dec!(0.123456789), 8));
+    assert!(!exceeds_decimals(dec!(0.12345678), 8));
+    // Trailing zeros are not real places
+    assert!(!exceeds_decimals(dec!(1.500), 1));
+}
+
+#[test]
+fn test_currency_decimals_are_clamped_to_the_column() {
+    assert_eq!(scale_of(6), 6);
+    assert_eq!(scale_of(18), MAX_AMOUNT_DECIMALS);
+    assert_eq!(scale_of(-1), 0);
+}
+
+// =============================================================================
+// INTEGRATION TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_rates_reject_amounts_with_too_many_places() {
+    let server = setup_test_server().await;
+    let url = "/swap/rates?from=btc&network_from=Mainnet&to=eth&network_to=ERC20&amount=0.123456789&sandbox=true";
+
+    let response = timed_get(&server, url).await;
+    response.assert_status_bad_request();
+    let body: Value = response.json();
+    assert_eq!(body["code"], "INVALID_AMOUNT");
+}
+
+#[tokio::test]
+async fn test_rates_are_served_as_decimal_strings() {
+    let server = setup_test_server().await;
+    let url = "/swap/rates?from=btc&network_from=Mainnet&to=eth&network_to=ERC20&amount=0.5&sandbox=true";
+
+    let response = timed_get(&server, url).await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    let rates = body["rates"].as_array().unwrap();
+    assert!(!rates.is_empty());
+    for quote in rates {
+        let estimated = amount(&quote["estimated_amount"]);
+        assert!(estimated > Decimal::ZERO);
+        assert!(!exceeds_decimals(estimated, MAX_AMOUNT_DECIMALS));
+        assert!(!exceeds_decimals(amount(&quote["rate"]), 12));
+    }
+}
@@ -0,0 +1,115 @@
+use exchange_shared::modules::swap::openapi::SwapApiDoc;
+use serde_json::Value;
+use utoipa::OpenApi;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::TestContext;
+
+// =============================================================================
+// UNIT TESTS - GENERATED DOCUMENT
+// =============================================================================
+
+fn spec() -> Value {
+    serde_json::to_value(SwapApiDoc::openapi()).unwrap()
+}
+
+#[test]
+fn test_spec_lists_every_swap_route() {
+    let spec = spec();
+    let paths = spec["paths"].as_object().unwrap();
+
+    for (path, method) in [
+        ("/swap/currencies", "get"),
+        ("/swap/currencies/grouped", "get"),
+        ("/swap/providers", "get"),
+        ("/swap/providers/{id}/uptime", "get"),
+        ("/swap/pairs", "get"),
+        ("/swap/rates", "get"),
+        ("/swap/depth", "get"),
+        ("/swap/quote", "post"),
+        ("/swap/create", "post"),
+        ("/swap/routes", "post"),
+        ("/swap/routes/plan", "get"),
+        ("/swap/routes/{id}", "get"),
+        ("/swap/import", "post"),
+        ("/swap/drafts", "post"),
+        ("/swap/drafts/{token}", "get"),
+        ("/swap/drafts/{token}", "put"),
+        ("/swap/drafts/{token}", "delete"),
+        ("/swap/status/batch", "post"),
+        ("/swap/history", "get"),
+        ("/swap/refund-addresses", "get"),
+        ("/swap/{id}", "get"),
+        ("/swap/{id}/refund", "get"),
+        ("/swap/{id}/retry", "post"),
+        ("/swap/{id}/share", "post"),
+        ("/swap/shared/{id}", "get"),
+        ("/swap/{id}/ws", "get"),
+        ("/swap/validate-address", "post"),
+        ("/swap/webhook/trocador", "post"),
+    ] {
+        assert!(paths.get(path).and_then(|p| p.get(method)).is_some(), "{} {} missing", method, path);
+    }
+}
+
+#[test]
+fn test_spec_describes_request_and_response_shapes() {
+    let spec = spec();
+    let schemas = &spec["components"]["schemas"];
+
+    let create = &schemas["CreateSwapRequest"];
+    let required: Vec<&str> = create["required"].as_array().unwrap().iter().map(|v| v.as_str().unwrap()).collect();
+    assert!(required.contains(&"recipient_address"));
+    assert!(!required.contains(&"refund_address"));
+
+    let statuses: Vec<&str> = schemas["SwapStatus"]["enum"].as_array().unwrap().iter().map(|v| v.as_str().unwrap()).collect();
+    assert!(statuses.contains(&"waiting") && statuses.contains(&"refunded"));
+
+    // The tenant keys the status cache and is never served
+    assert!(schemas["SwapStatusResponse"]["properties"].get("swap_id").is_some());
+    assert!(schemas["SwapStatusResponse"]["properties"].get("tenant").is_none());
+
+    let create_responses = &spec["paths"]["/swap/create"]["post"]["responses"];
+    assert!(create_responses["201"]["content"]["application/json"]["schema"]["$ref"]
+        .as_str()
+        .unwrap()
+        .ends_with("/CreateSwapResponse"));
+    assert!(create_responses["400"]["content"]["application/json"]["schema"]["$ref"]
+        .as_str()
+        .unwrap()
+        .ends_with("/SwapErrorResponse"));
+}
+
+#[test]
+fn test_spec_documents_query_parameters_and_auth() {
+    let spec = spec();
+
+    let params = spec["paths"]["/swap/rates"]["get"]["parameters"].as_array().unwrap();
+    let amount = params.iter().find(|p| p["name"] == "amount").unwrap();
+    assert_eq!(amount["in"], "query");
+    // Either amount or amount_to is given
+    let amount_to = params.iter().find(|p| p["name"] == "amount_to").unwrap();
+    assert_eq!(amount_to["required"], false);
+
+    assert_eq!(spec["components"]["securitySchemes"]["bearer_auth"]["scheme"], "bearer");
+    assert!(spec["paths"]["/swap/history"]["get"]["security"][0].get("bearer_auth").is_some());
+}
+
+// =============================================================================
+// INTEGRATION TESTS - SERVED DOCUMENT (GET /api-docs/openapi.json)
+// =============================================================================
+
+#[tokio::test]
+async fn test_openapi_json_is_served() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/api-docs/openapi.json").await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert!(body["openapi"].as_str().unwrap().starts_with("3."));
+    assert!(body["paths"].get("/swap/create").is_some());
+
+    let response = ctx.server.get("/swagger-ui/").await;
+    response.assert_status_ok();
+}
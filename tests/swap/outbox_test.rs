@@ -0,0 +1,125 @@
+use async_trait::async_trait;
+use exchange_shared::config::environment::{EventBusConfig, EventBusKind};
+use exchange_shared::services::event_bus::EventPublisher;
+use exchange_shared::services::outbox::{spawn_outbox_relay, DomainEventType, Outbox};
+use exchange_shared::services::redis_cache::RedisService;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::TestContext;
+
+// =============================================================================
+// INTEGRATION TESTS - DOMAIN EVENT OUTBOX RELAY
+// =============================================================================
+
+#[derive(Clone, Default)]
+struct CapturingPublisher {
+    published: Arc<Mutex<Vec<(String, String, Value)>>>,
+}
+
+#[async_trait]
+impl EventPublisher for CapturingPublisher {
+    fn name(&self) -> &'static str {
+        "capture"
+    }
+
+    async fn publish(&self, topic: &str, key: &str, payload: &str) -> Result<(), String> {
+        let payload = serde_json::from_str(payload).map_err(|e| e.to_string())?;
+        self.published.lock().unwrap().push((topic.to_string(), key.to_string(), payload));
+        Ok(())
+    }
+}
+
+fn kafka_config() -> EventBusConfig {
+    EventBusConfig {
+        kind: EventBusKind::Kafka,
+        kafka_rest_url: "http://unused".to_string(),
+        nats_url: String::new(),
+        topic_prefix: "test.".to_string(),
+        topic_overrides: vec![("swap.status_changed".to_string(), "test-swaps".to_string())],
+        relay_interval: Duration::from_millis(100),
+        batch_size: 100,
+    }
+}
+
+#[tokio::test]
+async fn test_outbox_relay_publishes_versioned_envelopes_and_marks_rows() {
+    let ctx = TestContext::new().await;
+    let config = kafka_config();
+    let aggregate_id = uuid::Uuid::new_v4().to_string();
+
+    let outbox = Outbox::from_config(&config, ctx.db.clone());
+    outbox
+        .record(DomainEventType::SwapCreated, &aggregate_id, json!({ "swap_id": aggregate_id }))
+        .await;
+    outbox
+        .record(DomainEventType::SwapStatusChanged, &aggregate_id, json!({ "status": "confirming" }))
+        .await;
+
+    let publisher = CapturingPublisher::default();
+    let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+    let relay = spawn_outbox_relay(ctx.db.clone(), RedisService::new(&redis_url), Box::new(publisher.clone()), config);
+
+    let mut ours = Vec::new();
+    for _ in 0..50 {
+        ours = publisher
+            .published
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, key, _)| key == &aggregate_id)
+            .cloned()
+            .collect::<Vec<_>>();
+        if ours.len() == 2 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    relay.abort();
+
+    assert_eq!(ours.len(), 2, "both events should be relayed");
+    assert_eq!(ours[0].0, "test.swap.created");
+    assert_eq!(ours[0].2["type"], "swap.created");
+    assert_eq!(ours[0].2["schema_version"], 1);
+    assert_eq!(ours[0].2["data"]["swap_id"], aggregate_id.as_str());
+    assert_eq!(ours[1].0, "test-swaps");
+    assert_eq!(ours[1].2["data"]["status"], "confirming");
+
+    let pending: (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM event_outbox WHERE aggregate_id = ? AND published_at IS NULL",
+    )
+    .bind(&aggregate_id)
+    .fetch_one(&ctx.db)
+    .await
+    .unwrap();
+    assert_eq!(pending.0, 0);
+
+    sqlx::query("DELETE FROM event_outbox WHERE aggregate_id = ?")
+        .bind(&aggregate_id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_disabled_outbox_records_nothing() {
+    let ctx = TestContext::new().await;
+    let aggregate_id = uuid::Uuid::new_v4().to_string();
+
+    Outbox::disabled()
+        .record(DomainEventType::AlertRaised, &aggregate_id, json!({}))
+        .await;
+
+    let rows: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM event_outbox WHERE aggregate_id = ?")
+        .bind(&aggregate_id)
+        .fetch_one(&ctx.db)
+        .await
+        .unwrap();
+    assert_eq!(rows.0, 0);
+
+    ctx.cleanup().await;
+}
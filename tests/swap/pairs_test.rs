@@ -1 +1,139 @@
-// Pairs endpoint tests - to be implemented
+use chrono::{Duration, Utc};
+use exchange_shared::modules::swap::crud::{group_pairs, PAIR_MAX_AGE_DAYS};
+use exchange_shared::modules::swap::model::PairQuote;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde_json::Value;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{create_admin_token, delete_currency, insert_currency, unique_symbol, TestContext};
+
+fn row(to: &str, provider: &str, min_amount: Decimal, max_amount: Option<Decimal>) -> PairQuote {
+    PairQuote {
+        from_currency: "btc".to_string(),
+        from_network: "Mainnet".to_string(),
+        to_currency: to.to_string(),
+        to_network: "Mainnet".to_string(),
+        provider: provider.to_string(),
+        min_amount,
+        max_amount,
+        is_active: true,
+        last_quoted_at: Utc::now(),
+    }
+}
+
+async fn insert_pair(ctx: &TestContext, from: &str, to: &str, provider: &str, max_amount: Option<f64>, days_ago: i64) {
+    sqlx::query(
+        "INSERT INTO pairs (from_currency, from_network, to_currency, to_network, provider, min_amount, max_amount, last_quoted_at)
+         VALUES (?, 'Mainnet', ?, 'Mainnet', ?, 0.01, ?, ?)",
+    )
+    .bind(from)
+    .bind(to)
+    .bind(provider)
+    .bind(max_amount)
+    .bind(Utc::now() - Duration::days(days_ago))
+    .execute(&ctx.db)
+    .await
+    .unwrap();
+}
+
+// =============================================================================
+// UNIT TESTS - GROUPING
+// =============================================================================
+
+#[test]
+fn test_providers_fold_into_one_pair_with_widest_limits() {
+    let pairs = group_pairs(vec![
+        row("eth", "changenow", dec!(0.001), Some(dec!(5))),
+        row("eth", "fixedfloat", dec!(0.002), Some(dec!(10))),
+        row("xmr", "changenow", dec!(0.01), Some(dec!(2))),
+        row("xmr", "exolix", dec!(0.005), None),
+    ]);
+
+    assert_eq!(pairs.len(), 2);
+    assert_eq!(pairs[0].to, "eth");
+    assert_eq!(pairs[0].providers.len(), 2);
+    assert_eq!(pairs[0].min_amount, dec!(0.001));
+    assert_eq!(pairs[0].max_amount, Some(dec!(10)));
+
+    // A provider without a maximum leaves the pair without one
+    assert_eq!(pairs[1].min_amount, dec!(0.005));
+    assert_eq!(pairs[1].max_amount, None);
+}
+
+#[test]
+fn test_no_rows_no_pairs() {
+    assert!(group_pairs(Vec::new()).is_empty());
+}
+
+// =============================================================================
+// INTEGRATION TESTS - GET /swap/pairs
+// =============================================================================
+
+#[tokio::test]
+async fn test_pairs_filtered_by_from_currency() {
+    let ctx = TestContext::new().await;
+    let from = unique_symbol();
+    let to = unique_symbol();
+    let from_id = insert_currency(&ctx, &from).await;
+    let to_id = insert_currency(&ctx, &to).await;
+    insert_pair(&ctx, &from, &to, "changenow", Some(5.0), 0).await;
+    insert_pair(&ctx, &from, &to, "exolix", None, 1).await;
+    insert_pair(&ctx, &from, "btc", "changenow", Some(1.0), PAIR_MAX_AGE_DAYS as i64 + 1).await;
+
+    let response = ctx.server.get(&format!("/swap/pairs?from={}", from.to_uppercase())).await;
+
+    response.assert_status_ok();
+    let pairs: Vec<Value> = response.json();
+    assert_eq!(pairs.len(), 1, "pairs not quoted recently are left out");
+    assert_eq!(pairs[0]["to"], to.as_str());
+    assert_eq!(pairs[0]["is_active"], true);
+    assert_eq!(pairs[0]["max_amount"], Value::Null);
+    assert_eq!(pairs[0]["providers"].as_array().unwrap().len(), 2);
+
+    sqlx::query("DELETE FROM pairs WHERE from_currency = ?")
+        .bind(&from)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    delete_currency(&ctx, from_id).await;
+    delete_currency(&ctx, to_id).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_cached_filtered_pairs_follow_a_disabled_currency() {
+    let ctx = TestContext::new().await;
+    let token = create_admin_token(&ctx).await;
+    let from = unique_symbol();
+    let to = unique_symbol();
+    let from_id = insert_currency(&ctx, &from).await;
+    let to_id = insert_currency(&ctx, &to).await;
+    insert_pair(&ctx, &from, &to, "changenow", Some(5.0), 0).await;
+
+    // Warm the cache for this filter
+    let path = format!("/swap/pairs?from={}&to={}", from, to);
+    let pairs: Vec<Value> = ctx.server.get(&path).await.json();
+    assert_eq!(pairs[0]["is_active"], true);
+
+    ctx.server
+        .patch(&format!("/admin/currencies/{}/policy", to_id))
+        .authorization_bearer(&token)
+        .json(&serde_json::json!({ "is_active": false }))
+        .await
+        .assert_status_ok();
+
+    let pairs: Vec<Value> = ctx.server.get(&path).await.json();
+    assert_eq!(pairs.len(), 1);
+    assert_eq!(pairs[0]["is_active"], false, "the cached listing must be dropped");
+
+    sqlx::query("DELETE FROM pairs WHERE from_currency = ?")
+        .bind(&from)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    delete_currency(&ctx, from_id).await;
+    delete_currency(&ctx, to_id).await;
+    ctx.cleanup().await;
+}
@@ -0,0 +1,192 @@
+use exchange_shared::config::environment::HighValueConfig;
+use exchange_shared::modules::swap::crud::SwapCrud;
+use rust_decimal_macros::dec;
+use std::time::Duration;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{delete_swap, insert_swap, TestContext};
+
+// =============================================================================
+// INTEGRATION TESTS - BACKGROUND STATUS POLLER
+// =============================================================================
+
+async fn status_of(ctx: &TestContext, swap_id: &str) -> String {
+    sqlx::query_scalar("SELECT status FROM swaps WHERE id = ?")
+        .bind(swap_id)
+        .fetch_one(&ctx.db)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_poller_expires_abandoned_waiting_swaps() {
+    let ctx = TestContext::new().await;
+    let crud = SwapCrud::new(ctx.db.clone(), None);
+
+    // No stored expiry, created two days ago
+    let abandoned = insert_swap(&ctx, "waiting", None).await;
+    sqlx::query("UPDATE swaps SET created_at = NOW() - INTERVAL 2 DAY WHERE id = ?")
+        .bind(&abandoned)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    // Explicit expiry already passed, even though the swap is new
+    let past_expiry = insert_swap(&ctx, "waiting", None).await;
+    sqlx::query("UPDATE swaps SET expires_at = NOW() - INTERVAL 1 MINUTE WHERE id = ?")
+        .bind(&past_expiry)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    let fresh = insert_swap(&ctx, "waiting", None).await;
+
+    // Old, but the deposit already arrived
+    let funded = insert_swap(&ctx, "confirming", None).await;
+    sqlx::query("UPDATE swaps SET created_at = NOW() - INTERVAL 2 DAY WHERE id = ?")
+        .bind(&funded)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    let expired = crud
+        .expire_abandoned_swaps(Duration::from_secs(86400), 1000)
+        .await
+        .expect("expiry pass failed");
+    assert!(expired >= 2);
+
+    assert_eq!(status_of(&ctx, &abandoned).await, "expired");
+    assert_eq!(status_of(&ctx, &past_expiry).await, "expired");
+    assert_eq!(status_of(&ctx, &fresh).await, "waiting");
+    assert_eq!(status_of(&ctx, &funded).await, "confirming");
+
+    let history: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM swap_status_history WHERE swap_id = ? AND status = 'expired'",
+    )
+    .bind(&abandoned)
+    .fetch_one(&ctx.db)
+    .await
+    .unwrap();
+    assert_eq!(history, 1);
+
+    // Already expired swaps are not touched again
+    crud.expire_abandoned_swaps(Duration::from_secs(86400), 1000).await.unwrap();
+    assert_eq!(status_of(&ctx, &abandoned).await, "expired");
+
+    for id in [&abandoned, &past_expiry, &fresh, &funded] {
+        delete_swap(&ctx, id).await;
+    }
+}
+
+async fn mark_high_value(ctx: &TestContext, swap_id: &str, usd_value: f64) {
+    sqlx::query("UPDATE swaps SET high_value = TRUE, usd_value = ? WHERE id = ?")
+        .bind(usd_value)
+        .bind(swap_id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+}
+
+#[test]
+fn test_high_value_uses_whichever_side_has_a_price() {
+    let config = HighValueConfig::default();
+
+    // 12,000 USDC in, priced on the sending side
+    let usd = config.usd_value("USDC", dec!(12_000), "xmr", dec!(80));
+    assert_eq!(usd, Some(12_000.0));
+    assert!(config.is_high_value(usd));
+
+    // No price for BTC, so the USDT received is used
+    let usd = config.usd_value("btc", dec!(0.1), "usdt", dec!(6_000));
+    assert_eq!(usd, Some(6_000.0));
+    assert!(!config.is_high_value(usd));
+
+    // Neither side priced: never tagged
+    assert_eq!(config.usd_value("btc", dec!(100), "xmr", dec!(40_000)), None);
+    assert!(!config.is_high_value(None));
+
+    let disabled = HighValueConfig { threshold_usd: 0.0, ..config };
+    assert!(!disabled.is_high_value(Some(1_000_000.0)));
+}
+
+#[tokio::test]
+async fn test_high_value_swaps_are_polled_sooner() {
+    let ctx = TestContext::new().await;
+    let crud = SwapCrud::new(ctx.db.clone(), None);
+
+    let high_value = insert_swap(&ctx, "confirming", None).await;
+    let regular = insert_swap(&ctx, "confirming", None).await;
+    mark_high_value(&ctx, &high_value, 50_000.0).await;
+    for id in [&high_value, &regular] {
+        sqlx::query(
+            "UPDATE swaps SET provider_swap_id = ?, last_polled_at = NOW() - INTERVAL 60 SECOND WHERE id = ?",
+        )
+        .bind(format!("trade-{}", id))
+        .bind(id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    }
+
+    let due: Vec<String> = crud
+        .swaps_due_for_poll(Duration::from_secs(120), Duration::from_secs(30), 1000)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    assert!(due.contains(&high_value));
+    assert!(!due.contains(&regular));
+
+    for id in [&high_value, &regular] {
+        delete_swap(&ctx, id).await;
+    }
+}
+
+#[tokio::test]
+async fn test_stalled_high_value_swaps_alert_once_per_stall() {
+    let ctx = TestContext::new().await;
+    let crud = SwapCrud::new(ctx.db.clone(), None);
+
+    let stalled = insert_swap(&ctx, "exchanging", None).await;
+    let regular = insert_swap(&ctx, "exchanging", None).await;
+    let unfunded = insert_swap(&ctx, "waiting", None).await;
+    for id in [&stalled, &unfunded] {
+        mark_high_value(&ctx, id, 50_000.0).await;
+    }
+    for id in [&stalled, &regular, &unfunded] {
+        sqlx::query("UPDATE swaps SET updated_at = NOW() - INTERVAL 2 HOUR WHERE id = ?")
+            .bind(id)
+            .execute(&ctx.db)
+            .await
+            .unwrap();
+    }
+
+    let alerted_at = |id: String| {
+        let db = ctx.db.clone();
+        async move {
+            sqlx::query_scalar::<_, Option<chrono::DateTime<chrono::Utc>>>(
+                "SELECT stall_alerted_at FROM swaps WHERE id = ?",
+            )
+            .bind(id)
+            .fetch_one(&db)
+            .await
+            .unwrap()
+        }
+    };
+
+    assert!(crud.report_stalled_high_value_swaps(Duration::from_secs(1800), 1000).await.unwrap() >= 1);
+    let first = alerted_at(stalled.clone()).await;
+    assert!(first.is_some());
+    assert!(alerted_at(regular.clone()).await.is_none());
+    assert!(alerted_at(unfunded.clone()).await.is_none());
+
+    // Still stuck in the same status: no second alert
+    crud.report_stalled_high_value_swaps(Duration::from_secs(1800), 1000).await.unwrap();
+    assert_eq!(alerted_at(stalled.clone()).await, first);
+
+    for id in [&stalled, &regular, &unfunded] {
+        delete_swap(&ctx, id).await;
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{extract::Query, routing::get, Json, Router};
+use exchange_shared::config::environment::PriceFeedConfig;
+use exchange_shared::services::price_feed::{fiat_value, parse_simple_price, FiatAmount, FiatPrices, PriceFeed};
+use exchange_shared::services::redis_cache::RedisService;
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+
+fn config(base_url: &str) -> PriceFeedConfig {
+    PriceFeedConfig {
+        enabled: true,
+        base_url: base_url.to_string(),
+        api_key: None,
+        coin_ids: [("btc", "bitcoin"), ("xmr", "monero")].into_iter().map(|(t, id)| (t.to_string(), id.to_string())).collect(),
+        cache_ttl: Duration::from_secs(60),
+        timeout: Duration::from_millis(500),
+    }
+}
+
+/// Local `/simple/price` answering with fixed prices; returns its URL and
+/// the number of requests it served
+async fn fake_feed() -> (String, Arc<AtomicUsize>) {
+    let served = Arc::new(AtomicUsize::new(0));
+    let counter = served.clone();
+    let app = Router::new().route(
+        "/simple/price",
+        get(move |Query(params): Query<HashMap<String, String>>| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                assert_eq!(params["vs_currencies"], "usd,eur");
+                assert_eq!(params["ids"], "bitcoin,monero");
+                Json(json!({
+                    "bitcoin": { "usd": 60000.0, "eur": 55000.0 },
+                    "monero": { "usd": 150.0, "eur": 140.0 }
+                }))
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (url, served)
+}
+
+// =============================================================================
+// UNIT TESTS - PRICE FEED
+// =============================================================================
+
+#[test]
+fn test_simple_price_response_is_keyed_by_ticker() {
+    let body: Value = json!({
+        "bitcoin": { "usd": 60000.0, "eur": 55000.0 },
+        "monero": { "usd": 150.0 }
+    });
+
+    let prices = parse_simple_price(&config("").coin_ids, &body);
+
+    assert_eq!(prices.len(), 1);
+    assert_eq!(prices["btc"], FiatAmount { usd: 60000.0, eur: 55000.0 });
+}
+
+#[test]
+fn test_fiat_values_are_rounded_to_cents() {
+    let prices: FiatPrices = [("btc".to_string(), FiatAmount { usd: 60000.0, eur: 55000.0 })].into_iter().collect();
+
+    assert_eq!(fiat_value(&prices, "BTC", dec!(0.0123456)), Some(FiatAmount { usd: 740.74, eur: 679.01 }));
+    assert_eq!(fiat_value(&prices, "xmr", dec!(1)), None);
+}
+
+#[tokio::test]
+async fn test_disabled_feed_has_no_prices() {
+    let feed = PriceFeed::from_config(&PriceFeedConfig { enabled: false, ..config("http://127.0.0.1:1") }, None);
+
+    assert!(!feed.is_enabled());
+    assert!(feed.prices().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_prices_are_cached_between_calls() {
+    let (url, served) = fake_feed().await;
+    let feed = PriceFeed::from_config(&config(&url), Some(RedisService::in_memory()));
+
+    let prices = feed.prices().await;
+    assert_eq!(prices["xmr"], FiatAmount { usd: 150.0, eur: 140.0 });
+    assert_eq!(feed.prices().await, prices);
+
+    assert_eq!(served.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_unreachable_feed_leaves_fiat_out() {
+    // Nothing listens on port 1
+    let feed = PriceFeed::from_config(&config("http://127.0.0.1:1"), None);
+
+    let started = std::time::Instant::now();
+    assert!(feed.prices().await.is_empty());
+    // Backing off: the second call does not try again
+    assert!(feed.prices().await.is_empty());
+
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
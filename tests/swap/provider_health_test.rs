@@ -0,0 +1,128 @@
+use exchange_shared::config::environment::ProviderHealthConfig;
+use exchange_shared::modules::swap::schema::SwapStatus;
+use exchange_shared::services::provider_health::{
+    summarize, HealthAction, HealthOverride, OutcomeCount, SwapOutcome,
+};
+
+// =============================================================================
+// UNIT TESTS - PROVIDER HEALTH
+// =============================================================================
+
+fn config() -> ProviderHealthConfig {
+    ProviderHealthConfig { min_swaps: 10, ..Default::default() }
+}
+
+fn count(provider: &str, outcome: &str, swaps: u64, completion_secs: u64) -> OutcomeCount {
+    OutcomeCount {
+        provider: provider.to_string(),
+        outcome: outcome.to_string(),
+        swaps,
+        completion_secs,
+    }
+}
+
+#[test]
+fn test_only_provider_outcomes_are_counted() {
+    assert_eq!(SwapOutcome::from_status(&SwapStatus::Completed), Some(SwapOutcome::Completed));
+    assert_eq!(SwapOutcome::from_status(&SwapStatus::Refunded), Some(SwapOutcome::Refunded));
+    assert_eq!(SwapOutcome::from_status(&SwapStatus::Failed), Some(SwapOutcome::Failed));
+    // Never funded, so not the provider's doing
+    assert_eq!(SwapOutcome::from_status(&SwapStatus::Expired), None);
+    assert_eq!(SwapOutcome::from_status(&SwapStatus::Exchanging), None);
+}
+
+#[test]
+fn test_score_needs_enough_swaps() {
+    let config = config();
+
+    assert_eq!(config.score(9, 9), None);
+    assert_eq!(config.score(0, 0), None);
+    assert_eq!(config.score(9, 10), Some(90.0));
+    assert_eq!(config.score(2, 30), Some(6.7));
+}
+
+#[test]
+fn test_low_scores_demote_then_hide() {
+    let config = config();
+
+    assert_eq!(config.action(Some(95.0), None), HealthAction::Serve);
+    assert_eq!(config.action(Some(80.0), None), HealthAction::Serve);
+    assert_eq!(config.action(Some(79.9), None), HealthAction::Demote);
+    assert_eq!(config.action(Some(49.9), None), HealthAction::Hide);
+    // Unscored providers get the benefit of the doubt
+    assert_eq!(config.action(None, None), HealthAction::Serve);
+
+    let never_hide = ProviderHealthConfig { hide_below: 0.0, ..config.clone() };
+    assert_eq!(never_hide.action(Some(0.0), None), HealthAction::Demote);
+
+    let disabled = ProviderHealthConfig { enabled: false, ..config };
+    assert_eq!(disabled.action(Some(0.0), None), HealthAction::Serve);
+}
+
+#[test]
+fn test_admin_override_wins_over_the_score() {
+    let config = config();
+
+    assert_eq!(config.action(Some(10.0), Some(HealthOverride::Trusted)), HealthAction::Serve);
+    assert_eq!(config.action(Some(99.0), Some(HealthOverride::Demoted)), HealthAction::Demote);
+    assert_eq!(config.action(None, Some(HealthOverride::Hidden)), HealthAction::Hide);
+
+    let disabled = ProviderHealthConfig { enabled: false, ..config };
+    assert_eq!(disabled.action(None, Some(HealthOverride::Hidden)), HealthAction::Hide);
+    assert_eq!("Trusted".parse::<HealthOverride>(), Ok(HealthOverride::Trusted));
+    assert!("banned".parse::<HealthOverride>().is_err());
+}
+
+#[test]
+fn test_summary_merges_outcomes_per_provider() {
+    let counts = [
+        count("ChangeNow", "completed", 6, 6 * 900),
+        count("changenow", "completed", 2, 2 * 1500),
+        count("ChangeNow", "refunded", 1, 0),
+        count("ChangeNow", "rejected", 1, 0),
+        count("Exolix", "completed", 3, 3 * 600),
+        count("Exolix", "failed", 9, 0),
+    ];
+    let health = summarize(&counts, &[], &config());
+
+    assert_eq!(health.len(), 2);
+    // Worst score first
+    let exolix = &health[0];
+    assert_eq!(exolix.provider, "Exolix");
+    assert_eq!(exolix.swaps, 12);
+    assert_eq!(exolix.score, Some(25.0));
+    assert_eq!(exolix.action, HealthAction::Hide);
+    assert_eq!(exolix.avg_completion_minutes, Some(10.0));
+
+    let changenow = &health[1];
+    assert_eq!(changenow.provider, "ChangeNow");
+    assert_eq!(changenow.swaps, 10);
+    assert_eq!(changenow.completed, 8);
+    assert_eq!(changenow.success_rate, Some(80.0));
+    assert_eq!(changenow.avg_completion_minutes, Some(17.5));
+    assert_eq!(changenow.failures.get("refunded"), Some(&1));
+    assert_eq!(changenow.failures.get("rejected"), Some(&1));
+    assert_eq!(changenow.action, HealthAction::Serve);
+}
+
+#[test]
+fn test_summary_lists_overridden_providers_without_swaps() {
+    let counts = [count("Exolix", "failed", 20, 0)];
+    let overrides = [
+        ("exolix".to_string(), HealthOverride::Trusted),
+        ("FixedFloat".to_string(), HealthOverride::Hidden),
+    ];
+    let health = summarize(&counts, &overrides, &config());
+
+    let exolix = health.iter().find(|h| h.provider == "Exolix").unwrap();
+    assert_eq!(exolix.score, Some(0.0));
+    assert_eq!(exolix.health_override, Some(HealthOverride::Trusted));
+    assert_eq!(exolix.action, HealthAction::Serve);
+
+    // Unscored providers are listed after scored ones
+    let fixedfloat = health.last().unwrap();
+    assert_eq!(fixedfloat.provider, "FixedFloat");
+    assert_eq!(fixedfloat.swaps, 0);
+    assert_eq!(fixedfloat.success_rate, None);
+    assert_eq!(fixedfloat.action, HealthAction::Hide);
+}
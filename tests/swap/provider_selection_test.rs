@@ -0,0 +1,148 @@
+use axum::http::StatusCode;
+use exchange_shared::config::environment::ProviderSelectionConfig;
+use exchange_shared::modules::swap::crud::{select_best_quote, SwapError};
+use exchange_shared::modules::swap::schema::{CreateSwapRequest, RateResponse};
+use exchange_shared::services::mock_provider::SANDBOX_PROVIDER;
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{setup_test_server, timed_post};
+
+fn quote(provider: &str, estimated_amount: f64, kyc_rating: Option<&str>, eta_minutes: Option<u32>) -> RateResponse {
+    serde_json::from_value(json!({
+        "provider": provider,
+        "provider_name": provider,
+        "rate": estimated_amount / 0.5,
+        "estimated_amount": estimated_amount,
+        "min_amount": 0.01,
+        "max_amount": 2.0,
+        "network_fee": 0.0,
+        "provider_fee": 0.0,
+        "platform_fee": 0.0,
+        "total_fee": 0.0,
+        "rate_type": "floating",
+        "kyc_required": false,
+        "kyc_rating": kyc_rating,
+        "eta_minutes": eta_minutes
+    }))
+    .unwrap()
+}
+
+fn create_request(provider: Option<&str>) -> CreateSwapRequest {
+    let mut body = json!({
+        "from": "btc",
+        "network_from": "Mainnet",
+        "to": "eth",
+        "network_to": "ERC20",
+        "amount": 0.5,
+        "recipient_address": "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23",
+        "refund_address": "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh",
+        "rate_type": "floating",
+        "sandbox": true
+    });
+    if let Some(provider) = provider {
+        body["provider"] = json!(provider);
+    }
+    serde_json::from_value(body).unwrap()
+}
+
+// =============================================================================
+// UNIT TESTS - SELECTION POLICY
+// =============================================================================
+
+#[test]
+fn test_provider_omitted_or_best_is_selected() {
+    assert!(create_request(None).wants_best_provider());
+    assert!(create_request(Some("")).wants_best_provider());
+    assert!(create_request(Some("Best")).wants_best_provider());
+    assert!(!create_request(Some("changenow")).wants_best_provider());
+}
+
+#[test]
+fn test_highest_net_amount_wins() {
+    let rates = vec![
+        quote("changenow", 9.90, Some("B"), Some(20)),
+        quote("fixedfloat", 9.95, Some("C"), Some(10)),
+        quote("exolix", 9.80, Some("A"), Some(5)),
+    ];
+
+    let best = select_best_quote(&rates, &ProviderSelectionConfig::default(), dec!(0.5)).unwrap();
+
+    assert_eq!(best.provider, "fixedfloat");
+}
+
+#[test]
+fn test_kyc_and_eta_limits_narrow_the_choice() {
+    let rates = vec![
+        quote("changenow", 9.90, Some("B"), Some(20)),
+        quote("fixedfloat", 9.95, Some("C"), Some(10)),
+        quote("exolix", 9.80, Some("A"), Some(5)),
+        quote("unrated", 9.99, None, None),
+    ];
+
+    let kyc = ProviderSelectionConfig { min_kyc_rating: Some('B'), max_eta_minutes: None };
+    assert_eq!(select_best_quote(&rates, &kyc, dec!(0.5)).unwrap().provider, "changenow");
+
+    let eta = ProviderSelectionConfig { min_kyc_rating: None, max_eta_minutes: Some(15) };
+    assert_eq!(select_best_quote(&rates, &eta, dec!(0.5)).unwrap().provider, "fixedfloat");
+
+    let both = ProviderSelectionConfig { min_kyc_rating: Some('B'), max_eta_minutes: Some(15) };
+    assert_eq!(select_best_quote(&rates, &both, dec!(0.5)).unwrap().provider, "exolix");
+
+    let none = ProviderSelectionConfig { min_kyc_rating: Some('A'), max_eta_minutes: Some(1) };
+    assert!(select_best_quote(&rates, &none, dec!(0.5)).is_none());
+}
+
+#[test]
+fn test_demoted_flagged_and_out_of_range_quotes_are_skipped() {
+    let mut demoted = quote("changenow", 9.99, Some("A"), Some(5));
+    demoted.demoted = true;
+    let mut flagged = quote("fixedfloat", 9.98, Some("A"), Some(5));
+    flagged.rate_warning = true;
+    let rates = vec![demoted, flagged, quote("exolix", 9.80, Some("A"), Some(5))];
+
+    let policy = ProviderSelectionConfig::default();
+    assert_eq!(select_best_quote(&rates, &policy, dec!(0.5)).unwrap().provider, "exolix");
+    assert!(select_best_quote(&rates, &policy, dec!(5)).is_none());
+}
+
+#[test]
+fn test_preferred_provider_wins_when_it_qualifies() {
+    let mut preferred = quote("exolix", 9.80, Some("A"), Some(5));
+    preferred.preferred = true;
+    let rates = vec![quote("changenow", 9.90, Some("B"), Some(20)), preferred];
+
+    let policy = ProviderSelectionConfig::default();
+    assert_eq!(select_best_quote(&rates, &policy, dec!(0.5)).unwrap().provider, "exolix");
+
+    let strict = ProviderSelectionConfig { min_kyc_rating: None, max_eta_minutes: Some(1) };
+    assert!(select_best_quote(&rates, &strict, dec!(0.5)).is_none());
+}
+
+#[test]
+fn test_no_quote_matches_policy_error() {
+    let error = SwapError::NoQuoteMatchesPolicy;
+
+    assert_eq!(error.status_code(), StatusCode::BAD_REQUEST);
+    assert_eq!(error.error_code(), "NO_QUOTE_MATCHES_POLICY");
+}
+
+// =============================================================================
+// INTEGRATION TESTS - SWAP WITHOUT A PROVIDER
+// =============================================================================
+
+#[tokio::test]
+async fn test_swap_without_provider_records_selection() {
+    let server = setup_test_server().await;
+
+    let response = timed_post(&server, "/swap/create", &serde_json::to_value(create_request(None)).unwrap()).await;
+
+    response.assert_status(StatusCode::CREATED);
+    let body: Value = response.json();
+    assert_eq!(body["provider"], SANDBOX_PROVIDER);
+    assert_eq!(body["provider_selection"]["policy"], "best_net_amount");
+    assert_eq!(body["provider_selection"]["candidates"], 1);
+    assert_eq!(body["provider_selection"]["quote"]["provider"], SANDBOX_PROVIDER);
+}
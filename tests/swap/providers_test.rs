@@ -163,9 +163,8 @@ async fn test_filter_providers_with_markup_enabled() {
 
     // All should have markup_enabled = true
     for provider in &providers {
-        assert_eq!(
+        assert!(
             provider["markup_enabled"].as_bool().unwrap(),
-            true,
             "Provider {} should have markup enabled",
             provider["name"]
         );
@@ -184,7 +183,7 @@ async fn test_filter_providers_without_markup() {
     assert!(!providers.is_empty(), "Should have providers without markup");
 
     for provider in &providers {
-        assert_eq!(provider["markup_enabled"].as_bool().unwrap(), false);
+        assert!(!provider["markup_enabled"].as_bool().unwrap());
     }
 }
 
@@ -201,7 +200,7 @@ async fn test_providers_insurance_values() {
     for provider in &providers {
         let insurance = provider["insurance"].as_f64().unwrap();
         assert!(
-            insurance >= 0.005 && insurance <= 0.05,
+            (0.005..=0.05).contains(&insurance),
             "Provider {} has unrealistic insurance: {}",
             provider["name"],
             insurance
@@ -237,7 +236,7 @@ async fn test_providers_eta_values() {
     for provider in &providers {
         let eta = provider["eta"].as_i64().unwrap();
         assert!(
-            eta >= 1 && eta <= 120,
+            (1..=120).contains(&eta),
             "Provider {} has unrealistic ETA: {} minutes",
             provider["name"],
             eta
@@ -388,7 +387,7 @@ async fn test_combined_filters_rating_and_markup() {
     // All should match both criteria
     for provider in &providers {
         assert_eq!(provider["rating"].as_str().unwrap(), "B");
-        assert_eq!(provider["markup_enabled"].as_bool().unwrap(), true);
+        assert!(provider["markup_enabled"].as_bool().unwrap());
     }
 }
 
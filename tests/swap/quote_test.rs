@@ -0,0 +1,105 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::TestContext;
+
+// =============================================================================
+// INTEGRATION TESTS - RESERVED QUOTES ON POST /swap/create
+// Reservations are inserted directly, so every request here is refused
+// before a trade reaches Trocador.
+// =============================================================================
+
+fn swap_request(quote_id: &str, amount: f64) -> Value {
+    json!({
+        "from": "btc",
+        "network_from": "Mainnet",
+        "to": "xmr",
+        "network_to": "Mainnet",
+        "amount": amount,
+        "provider": "ChangeNOW",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
+        "quote_id": quote_id
+    })
+}
+
+/// Reservation for 0.01 BTC -> XMR expiring `expires_in_secs` from now
+async fn insert_quote(ctx: &TestContext, expires_in_secs: i64, used: bool) -> String {
+    let quote_id = uuid::Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO quotes (id, trade_id, provider, from_currency, from_network, to_currency, to_network,
+                             amount, rate, estimated_amount, expires_at, used_at)
+         VALUES (?, 'test-trade', 'ChangeNOW', 'btc', 'Mainnet', 'xmr', 'Mainnet', 0.01, 150.0, 1.5,
+                 NOW() + INTERVAL ? SECOND, IF(?, NOW(), NULL))",
+    )
+    .bind(&quote_id)
+    .bind(expires_in_secs)
+    .bind(used)
+    .execute(&ctx.db)
+    .await
+    .expect("Failed to insert quote");
+    quote_id
+}
+
+async fn delete_quote(ctx: &TestContext, quote_id: &str) {
+    sqlx::query("DELETE FROM quotes WHERE id = ?")
+        .bind(quote_id)
+        .execute(&ctx.db)
+        .await
+        .ok();
+}
+
+async fn create_with_quote(ctx: &TestContext, quote_id: &str, amount: f64) -> (StatusCode, Value) {
+    let response = ctx.server.post("/swap/create").json(&swap_request(quote_id, amount)).await;
+    (response.status_code(), response.json())
+}
+
+#[tokio::test]
+async fn test_unknown_quote_is_not_found() {
+    let ctx = TestContext::new().await;
+
+    let (status, body) = create_with_quote(&ctx, &uuid::Uuid::new_v4().to_string(), 0.01).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "QUOTE_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_expired_quote_is_rejected() {
+    let ctx = TestContext::new().await;
+    let quote_id = insert_quote(&ctx, -60, false).await;
+
+    let (status, body) = create_with_quote(&ctx, &quote_id, 0.01).await;
+
+    assert_eq!(status, StatusCode::GONE);
+    assert_eq!(body["code"], "RATE_EXPIRED");
+
+    delete_quote(&ctx, &quote_id).await;
+}
+
+#[tokio::test]
+async fn test_quote_for_other_amount_is_rejected() {
+    let ctx = TestContext::new().await;
+    let quote_id = insert_quote(&ctx, 300, false).await;
+
+    let (status, body) = create_with_quote(&ctx, &quote_id, 0.02).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "QUOTE_MISMATCH");
+
+    delete_quote(&ctx, &quote_id).await;
+}
+
+#[tokio::test]
+async fn test_used_quote_is_rejected() {
+    let ctx = TestContext::new().await;
+    let quote_id = insert_quote(&ctx, 300, true).await;
+
+    let (status, body) = create_with_quote(&ctx, &quote_id, 0.01).await;
+
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], "QUOTE_ALREADY_USED");
+
+    delete_quote(&ctx, &quote_id).await;
+}
@@ -0,0 +1,122 @@
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use exchange_shared::config::environment::{RateGuardAction, RateGuardConfig};
+use exchange_shared::modules::swap::crud::SwapError;
+use exchange_shared::modules::swap::schema::{RateResponse, RatesResponse};
+use exchange_shared::services::rate_guard::RateGuard;
+use rust_decimal_macros::dec;
+use serde_json::json;
+
+// =============================================================================
+// UNIT TESTS - EXCHANGE-RATE SANITY GUARD
+// =============================================================================
+
+fn guard(action: RateGuardAction) -> RateGuard {
+    RateGuard::new(RateGuardConfig { action, ..Default::default() })
+}
+
+fn quote(provider: &str, rate: f64) -> RateResponse {
+    serde_json::from_value(json!({
+        "provider": provider,
+        "provider_name": provider,
+        "rate": rate,
+        "estimated_amount": rate,
+        "min_amount": 0.001,
+        "max_amount": 10.0,
+        "network_fee": 0.0,
+        "provider_fee": 0.0,
+        "platform_fee": 0.0,
+        "total_fee": 0.0,
+        "rate_type": "floating",
+        "kyc_required": false,
+        "kyc_rating": null,
+    }))
+    .unwrap()
+}
+
+fn rates(quotes: &[(&str, f64)]) -> RatesResponse {
+    RatesResponse {
+        trade_id: "guard-test".to_string(),
+        from: "btc".to_string(),
+        network_from: "Mainnet".to_string(),
+        to: "xmr".to_string(),
+        network_to: "Mainnet".to_string(),
+        amount: dec!(1),
+        amount_to: None,
+        amount_fiat: None,
+        rates: quotes.iter().map(|(provider, rate)| quote(provider, *rate)).collect(),
+        meta: Default::default(),
+    }
+}
+
+#[test]
+fn test_reference_rate_is_the_median() {
+    let guard = guard(RateGuardAction::Reject);
+
+    assert_eq!(guard.reference_rate(&[100.0, 300.0, 101.0]), Some(101.0));
+    assert_eq!(guard.reference_rate(&[100.0, 102.0, 104.0, 500.0]), Some(103.0));
+    // Too few usable quotes to judge any of them
+    assert_eq!(guard.reference_rate(&[100.0, 101.0]), None);
+    assert_eq!(guard.reference_rate(&[100.0, 0.0, f64::NAN]), None);
+}
+
+#[test]
+fn test_reject_drops_quotes_out_of_bounds() {
+    let mut response = rates(&[("ChangeNow", 100.0), ("Exolix", 101.0), ("FixedFloat", 99.5), ("Broken", 150.0)]);
+
+    guard(RateGuardAction::Reject).screen_quotes(&mut response);
+
+    let providers: Vec<&str> = response.rates.iter().map(|r| r.provider.as_str()).collect();
+    assert_eq!(providers, ["ChangeNow", "Exolix", "FixedFloat"]);
+    assert!(response.rates.iter().all(|r| !r.rate_warning));
+}
+
+#[test]
+fn test_flag_keeps_quotes_with_a_warning() {
+    let mut response = rates(&[("ChangeNow", 100.0), ("Exolix", 101.0), ("FixedFloat", 99.5), ("Cheap", 40.0)]);
+
+    guard(RateGuardAction::Flag).screen_quotes(&mut response);
+
+    assert_eq!(response.rates.len(), 4);
+    let flagged: Vec<&str> = response.rates.iter().filter(|r| r.rate_warning).map(|r| r.provider.as_str()).collect();
+    assert_eq!(flagged, ["Cheap"]);
+    let body = serde_json::to_value(&response).unwrap();
+    assert_eq!(body["rates"][3]["rate_warning"], true);
+    assert!(body["rates"][0].get("rate_warning").is_none());
+}
+
+#[test]
+fn test_disabled_guard_leaves_quotes_alone() {
+    let guard = RateGuard::new(RateGuardConfig { enabled: false, ..Default::default() });
+    let mut response = rates(&[("ChangeNow", 100.0), ("Exolix", 101.0), ("Broken", 1000.0)]);
+
+    guard.screen_quotes(&mut response);
+
+    assert_eq!(response.rates.len(), 3);
+    assert!(guard.check_provider(&response, "broken").is_ok());
+    assert!(!guard.rejects());
+}
+
+#[test]
+fn test_check_provider_reports_signed_deviation() {
+    let guard = guard(RateGuardAction::Reject);
+    let response = rates(&[("ChangeNow", 100.0), ("Exolix", 100.0), ("Broken", 80.0)]);
+
+    assert!(guard.check_provider(&response, "changenow").is_ok());
+    let deviation = guard.check_provider(&response, "Broken").unwrap_err();
+    assert!((deviation + 20.0).abs() < 1e-9);
+    // A provider without a quote can't be judged
+    assert!(guard.check_provider(&response, "Unknown").is_ok());
+}
+
+#[tokio::test]
+async fn test_rate_out_of_bounds_error_response() {
+    let error = SwapError::RateOutOfBounds { provider: "Broken".to_string(), deviation_pct: -20.0 };
+
+    let response = error.into_response();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["code"], "RATE_OUT_OF_BOUNDS");
+    assert_eq!(body["error"], "Broken's rate is -20.0% from the market rate");
+}
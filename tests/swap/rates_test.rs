@@ -1,8 +1,9 @@
+use rust_decimal::Decimal;
 use serde_json::Value;
 
 #[path = "../common/mod.rs"]
 mod common;
-use common::{setup_test_server, timed_get};
+use common::{amount, setup_test_server, timed_get, TestContext};
 use std::time::Duration;
 use tokio::time::sleep;
 
@@ -31,7 +32,7 @@ async fn test_get_rates_successful() {
     assert_eq!(json["from"], "btc");
     assert_eq!(json["to"], "xmr");
     // Handle floating point comparison loosely or exact if preserved
-    assert!(json["amount"].as_f64().unwrap() > 0.0);
+    assert!(amount(&json["amount"]) > Decimal::ZERO);
 
     // 2. CRUCIAL: Verify trade_id (needed for next step)
     // Trocador returns a "trade_id" (or we might wrap it)
@@ -56,8 +57,8 @@ async fn test_get_rates_successful() {
     assert!(best_rate.get("max_amount").is_some());
     
     // Check values are reasonable
-    assert!(best_rate["rate"].as_f64().unwrap() > 0.0);
-    assert!(best_rate["estimated_amount"].as_f64().unwrap() > 0.0);
+    assert!(amount(&best_rate["rate"]) > Decimal::ZERO);
+    assert!(amount(&best_rate["estimated_amount"]) > Decimal::ZERO);
 }
 
 #[tokio::test]
@@ -74,8 +75,8 @@ async fn test_get_rates_sorted_by_best_price() {
     let rates = json["rates"].as_array().unwrap();
 
     if rates.len() >= 2 {
-        let first_rate = rates[0]["estimated_amount"].as_f64().unwrap();
-        let second_rate = rates[1]["estimated_amount"].as_f64().unwrap();
+        let first_rate = amount(&rates[0]["estimated_amount"]);
+        let second_rate = amount(&rates[1]["estimated_amount"]);
 
         // Best rate (highest estimated amount) should be first
         assert!(
@@ -162,4 +163,135 @@ async fn test_get_rates_includes_network_fees() {
         assert!(first_rate.get("network_fee").is_some());
         assert!(first_rate.get("total_fee").is_some());
     }
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_get_rates_demotes_provider_with_recent_failures() {
+    sleep(Duration::from_secs(1)).await; // Prevent Rate Limit
+    let server = setup_test_server().await;
+
+    let response = timed_get(&server, "/swap/rates?from=btc&to=xmr&amount=0.02&network_from=Mainnet&network_to=Mainnet").await;
+    response.assert_status_ok();
+    let json: Value = response.json();
+    let Some(provider) = json["rates"][0]["provider"].as_str().map(str::to_string) else {
+        return;
+    };
+
+    // Two failed trades in the 0.01-0.1 band, as create_swap would record them
+    let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+    let redis = exchange_shared::services::redis_cache::RedisService::new(&redis_url);
+    let key = format!("provider_failures:{}:btc:mainnet:xmr:mainnet:-2", provider.to_lowercase());
+    redis.set_string(&key, "2", 60).await.unwrap();
+
+    // Different amount in the same band, so the cached response is not reused
+    let response = timed_get(&server, "/swap/rates?from=btc&to=xmr&amount=0.03&network_from=Mainnet&network_to=Mainnet").await;
+    redis.delete(&key).await.ok();
+    response.assert_status_ok();
+
+    let json: Value = response.json();
+    let rates = json["rates"].as_array().unwrap();
+    let position = rates.iter().position(|r| r["provider"] == provider.as_str());
+
+    if let Some(position) = position {
+        assert_eq!(rates[position]["recent_failures"], 2);
+        assert_eq!(rates[position]["demoted"], true);
+        assert!(rates[position..].iter().all(|r| r["demoted"] == true));
+    }
+}
+
+#[tokio::test]
+async fn test_get_rates_hides_provider_with_failing_swaps() {
+    sleep(Duration::from_secs(1)).await; // Prevent Rate Limit
+    let ctx = TestContext::new().await;
+
+    let response = timed_get(&ctx.server, "/swap/rates?from=btc&to=xmr&amount=0.04&network_from=Mainnet&network_to=Mainnet").await;
+    response.assert_status_ok();
+    let json: Value = response.json();
+    let Some(provider) = json["rates"][0]["provider"].as_str().map(str::to_string) else {
+        return;
+    };
+
+    // 5 of 40 swaps completed today, well under PROVIDER_HEALTH_HIDE_BELOW
+    for (outcome, swaps) in [("completed", 5), ("failed", 35)] {
+        sqlx::query(
+            "INSERT INTO provider_stats (provider, day, outcome, swaps) VALUES (?, UTC_DATE(), ?, ?)
+             ON DUPLICATE KEY UPDATE swaps = swaps + VALUES(swaps)",
+        )
+        .bind(&provider)
+        .bind(outcome)
+        .bind(swaps)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    }
+    let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+    let redis = exchange_shared::services::redis_cache::RedisService::new(&redis_url);
+    redis.delete("providers:health").await.ok();
+
+    let response = timed_get(&ctx.server, "/swap/rates?from=btc&to=xmr&amount=0.04&network_from=Mainnet&network_to=Mainnet").await;
+
+    for (outcome, swaps) in [("completed", 5), ("failed", 35)] {
+        sqlx::query("UPDATE provider_stats SET swaps = swaps - ? WHERE provider = ? AND day = UTC_DATE() AND outcome = ?")
+            .bind(swaps)
+            .bind(&provider)
+            .bind(outcome)
+            .execute(&ctx.db)
+            .await
+            .unwrap();
+    }
+    redis.delete("providers:health").await.ok();
+
+    response.assert_status_ok();
+    let json: Value = response.json();
+    let rates = json["rates"].as_array().unwrap();
+    assert!(rates.iter().all(|r| r["provider"] != provider.as_str()));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_get_rates_reports_latency_budget() {
+    sleep(Duration::from_secs(1)).await;
+    let server = setup_test_server().await;
+
+    let url = "/swap/rates?from=btc&to=xmr&amount=0.02&network_from=Mainnet&network_to=Mainnet";
+    let response = timed_get(&server, url).await;
+    response.assert_status_ok();
+
+    let json: Value = response.json();
+    let meta = &json["meta"];
+    let budget_ms = meta["budget_ms"].as_u64().expect("meta.budget_ms");
+    assert!(budget_ms > 0);
+
+    // Quotes that miss the budget are reported, never waited for
+    let elapsed_ms = meta["elapsed_ms"].as_u64().expect("meta.elapsed_ms");
+    assert!(elapsed_ms <= budget_ms + 500, "took {}ms with a {}ms budget", elapsed_ms, budget_ms);
+
+    if json["rates"].as_array().unwrap().is_empty() {
+        assert!(!meta["timed_out"].as_array().unwrap().is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_get_rates_tags_quotes_with_aggregator() {
+    sleep(Duration::from_secs(1)).await;
+    let server = setup_test_server().await;
+
+    let url = "/swap/rates?from=btc&to=xmr&amount=0.03&network_from=Mainnet&network_to=Mainnet";
+    let response = timed_get(&server, url).await;
+    response.assert_status_ok();
+
+    let json: Value = response.json();
+    let rates = json["rates"].as_array().unwrap();
+    for rate in rates {
+        let aggregator = rate["aggregator"].as_str().expect("aggregator tag");
+        assert!(["trocador", "changenow"].contains(&aggregator), "unknown aggregator {}", aggregator);
+    }
+
+    // Quotes stay ranked best-first across aggregators
+    let amounts: Vec<Decimal> = rates
+        .iter()
+        .filter(|r| r["demoted"] != true)
+        .map(|r| amount(&r["estimated_amount"]))
+        .collect();
+    assert!(amounts.windows(2).all(|w| w[0] >= w[1]));
+}
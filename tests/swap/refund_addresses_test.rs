@@ -0,0 +1,69 @@
+use axum::http::StatusCode;
+use serde_json::Value;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{create_user_token, delete_swap, insert_swap, TestContext};
+
+// =============================================================================
+// INTEGRATION TESTS - REFUND ADDRESS SUGGESTIONS (GET /swap/refund-addresses)
+// insert_swap sends BTC (refund bc1qxy2...) and receives XMR.
+// =============================================================================
+
+const REFUND_ADDRESS: &str = "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh";
+
+async fn suggestions(ctx: &TestContext, token: &str, ticker: &str) -> Value {
+    let response = ctx
+        .server
+        .get("/swap/refund-addresses")
+        .add_query_param("ticker", ticker)
+        .add_query_param("network", "Mainnet")
+        .authorization_bearer(token)
+        .await;
+    response.assert_status_ok();
+    response.json()
+}
+
+#[tokio::test]
+async fn test_refund_addresses_require_auth() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/swap/refund-addresses?ticker=btc&network=Mainnet").await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_refund_addresses_come_from_callers_swaps() {
+    let ctx = TestContext::new().await;
+    let (user_id, token) = create_user_token(&ctx).await;
+    let first = insert_swap(&ctx, "waiting", Some(&user_id)).await;
+    let second = insert_swap(&ctx, "completed", Some(&user_id)).await;
+    let (other_user_id, other_token) = create_user_token(&ctx).await;
+    let foreign = insert_swap(&ctx, "completed", Some(&other_user_id)).await;
+
+    // Both swaps gave the same refund address; it's suggested once
+    let body = suggestions(&ctx, &token, "btc").await;
+    let list = body["suggestions"].as_array().unwrap();
+    assert_eq!(list.len(), 1);
+    assert_eq!(list[0]["address"], REFUND_ADDRESS);
+    assert_eq!(list[0]["times_used"], 2);
+    assert_eq!(list[0]["sources"], serde_json::json!(["refund"]));
+
+    // Only the completed swap's recipient address counts for the received currency
+    let body = suggestions(&ctx, &token, "xmr").await;
+    let list = body["suggestions"].as_array().unwrap();
+    assert_eq!(list.len(), 1);
+    assert_eq!(list[0]["times_used"], 1);
+    assert_eq!(list[0]["sources"], serde_json::json!(["recipient"]));
+
+    // Another user's swaps never leak into the list
+    delete_swap(&ctx, &foreign).await;
+    let body = suggestions(&ctx, &other_token, "btc").await;
+    assert!(body["suggestions"].as_array().unwrap().is_empty());
+
+    for id in [&first, &second] {
+        delete_swap(&ctx, id).await;
+    }
+    ctx.cleanup().await;
+}
@@ -0,0 +1,104 @@
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use exchange_shared::modules::swap::crud::SwapError;
+use exchange_shared::modules::swap::schema::SwapErrorResponse;
+use exchange_shared::services::request_id::{accept_request_id, current_request_id, with_request_id};
+use rust_decimal_macros::dec;
+use serde_json::Value;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::setup_test_server;
+
+const BAD_RATES: &str =
+    "/swap/rates?from=btc&network_from=Mainnet&to=eth&network_to=ERC20&amount=0.5&amount_to=9.95&sandbox=true";
+
+// =============================================================================
+// UNIT TESTS - REQUEST IDS
+// =============================================================================
+
+#[test]
+fn test_accepts_short_safe_request_ids() {
+    assert_eq!(accept_request_id(Some("req-42_a.b:c")), Some("req-42_a.b:c".to_string()));
+    assert_eq!(accept_request_id(Some("  trimmed  ")), Some("trimmed".to_string()));
+}
+
+#[test]
+fn test_rejects_unusable_request_ids() {
+    assert_eq!(accept_request_id(None), None);
+    assert_eq!(accept_request_id(Some("")), None);
+    assert_eq!(accept_request_id(Some("has space")), None);
+    assert_eq!(accept_request_id(Some("line\nbreak")), None);
+    assert_eq!(accept_request_id(Some(&"a".repeat(129))), None);
+}
+
+#[tokio::test]
+async fn test_request_id_is_scoped_to_the_request() {
+    assert_eq!(current_request_id(), None);
+
+    let inside = with_request_id("req-1".to_string(), async { current_request_id() }).await;
+
+    assert_eq!(inside, Some("req-1".to_string()));
+    assert_eq!(current_request_id(), None);
+}
+
+#[tokio::test]
+async fn test_error_bodies_carry_the_request_id() {
+    let error = with_request_id("req-2".to_string(), async {
+        SwapErrorResponse::with_limits("Amount out of range", dec!(0.01), dec!(2))
+    })
+    .await;
+    assert_eq!(error.request_id.as_deref(), Some("req-2"));
+    assert_eq!(error.min_amount, Some(dec!(0.01)));
+
+    let response =
+        with_request_id("req-3".to_string(), async { SwapError::SwapNotFound.into_response() }).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["request_id"], "req-3");
+}
+
+#[test]
+fn test_error_bodies_outside_a_request_omit_the_id() {
+    let body = serde_json::to_value(SwapErrorResponse::new("Swap not found")).unwrap();
+
+    assert!(body.get("request_id").is_none());
+}
+
+// =============================================================================
+// INTEGRATION TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_response_echoes_the_callers_request_id() {
+    let server = setup_test_server().await;
+
+    let response = server.get("/health").add_header("X-Request-Id", "client-abc-123").await;
+
+    assert_eq!(response.header("x-request-id"), "client-abc-123");
+}
+
+#[tokio::test]
+async fn test_response_gets_a_fresh_request_id() {
+    let server = setup_test_server().await;
+
+    let first = server.get("/health").await.header("x-request-id");
+    let second = server.get("/health").add_header("X-Request-Id", "not valid!").await.header("x-request-id");
+
+    assert!(uuid::Uuid::parse_str(first.to_str().unwrap()).is_ok());
+    assert!(uuid::Uuid::parse_str(second.to_str().unwrap()).is_ok());
+    assert_ne!(first, second);
+}
+
+#[tokio::test]
+async fn test_error_response_body_matches_the_header() {
+    let server = setup_test_server().await;
+
+    let response = server.get(BAD_RATES).add_header("X-Request-Id", "client-err-1").await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "INVALID_AMOUNT");
+    assert_eq!(body["request_id"], "client-err-1");
+}
@@ -0,0 +1,134 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{create_user_token, delete_swap, insert_swap, TestContext};
+
+// =============================================================================
+// INTEGRATION TESTS - RETRY SWAP ENDPOINT (POST /swap/{id}/retry)
+// =============================================================================
+
+#[tokio::test]
+async fn test_retry_unknown_swap_returns_not_found() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.post("/swap/00000000-0000-0000-0000-000000000000/retry").await;
+
+    response.assert_status(StatusCode::NOT_FOUND);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "SWAP_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_retry_active_swap_is_rejected() {
+    let ctx = TestContext::new().await;
+    let swap_id = insert_swap(&ctx, "waiting", None).await;
+
+    let response = ctx.server.post(&format!("/swap/{}/retry", swap_id)).await;
+
+    response.assert_status(StatusCode::CONFLICT);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "SWAP_NOT_RETRYABLE");
+
+    delete_swap(&ctx, &swap_id).await;
+}
+
+#[tokio::test]
+async fn test_retry_other_users_swap_returns_not_found() {
+    let ctx = TestContext::new().await;
+    let (owner, _) = create_user_token(&ctx).await;
+    let swap_id = insert_swap(&ctx, "failed", Some(&owner)).await;
+
+    let response = ctx.server.post(&format!("/swap/{}/retry", swap_id)).await;
+
+    response.assert_status(StatusCode::NOT_FOUND);
+
+    delete_swap(&ctx, &swap_id).await;
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_retry_twice_is_rejected() {
+    let ctx = TestContext::new().await;
+    let swap_id = insert_swap(&ctx, "expired", None).await;
+    let earlier_retry = insert_swap(&ctx, "waiting", None).await;
+    sqlx::query("UPDATE swaps SET retried_from = ? WHERE id = ?")
+        .bind(&swap_id)
+        .bind(&earlier_retry)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    let response = ctx.server.post(&format!("/swap/{}/retry", swap_id)).await;
+
+    response.assert_status(StatusCode::CONFLICT);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "ALREADY_RETRIED");
+
+    delete_swap(&ctx, &swap_id).await;
+}
+
+/// Calls Trocador for a fresh quote and a new trade
+#[tokio::test]
+async fn test_retry_failed_swap_creates_linked_swap() {
+    let ctx = TestContext::new().await;
+    let swap_id = insert_swap(&ctx, "failed", None).await;
+
+    let response = ctx
+        .server
+        .post(&format!("/swap/{}/retry", swap_id))
+        .json(&json!({ "switch_provider": true }))
+        .await;
+
+    response.assert_status(StatusCode::CREATED);
+    let body: Value = response.json();
+    assert_eq!(body["retried_from"], swap_id.as_str());
+    assert_ne!(body["swap_id"], swap_id.as_str());
+    assert_ne!(body["provider"], "changenow");
+    assert!(body["deposit_address"].as_str().is_some_and(|a| !a.is_empty()));
+
+    delete_swap(&ctx, &swap_id).await;
+}
+
+#[tokio::test]
+async fn test_retry_in_flight_is_rejected() {
+    let ctx = TestContext::new().await;
+    let swap_id = insert_swap(&ctx, "failed", None).await;
+    // Another request has claimed the swap and is still creating its retry
+    sqlx::query("UPDATE swaps SET retry_claimed_at = NOW() WHERE id = ?")
+        .bind(&swap_id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    let response = ctx.server.post(&format!("/swap/{}/retry", swap_id)).await;
+
+    response.assert_status(StatusCode::CONFLICT);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "RETRY_IN_PROGRESS");
+
+    delete_swap(&ctx, &swap_id).await;
+}
+
+#[tokio::test]
+async fn test_swap_can_only_be_retried_once_in_the_database() {
+    let ctx = TestContext::new().await;
+    let swap_id = insert_swap(&ctx, "expired", None).await;
+    let first = insert_swap(&ctx, "waiting", None).await;
+    let second = insert_swap(&ctx, "waiting", None).await;
+
+    let link = |retry: String| {
+        sqlx::query("UPDATE swaps SET retried_from = ? WHERE id = ?")
+            .bind(swap_id.clone())
+            .bind(retry)
+            .execute(&ctx.db)
+    };
+    link(first.clone()).await.unwrap();
+    let err = link(second.clone()).await.expect_err("retried_from must be unique");
+    assert!(err.as_database_error().is_some_and(|e| e.is_unique_violation()));
+
+    delete_swap(&ctx, &first).await;
+    delete_swap(&ctx, &second).await;
+    delete_swap(&ctx, &swap_id).await;
+}
@@ -0,0 +1,93 @@
+use axum::http::StatusCode;
+use exchange_shared::modules::swap::schema::{CreateRouteRequest, RateType, RouteStatus, SwapStatus};
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::TestContext;
+
+// =============================================================================
+// UNIT TESTS - STATUS ROLLUP
+// =============================================================================
+
+#[test]
+fn test_route_waits_for_and_follows_the_first_leg() {
+    assert_eq!(RouteStatus::rollup(&SwapStatus::Waiting, &SwapStatus::Waiting), RouteStatus::Waiting);
+    assert_eq!(RouteStatus::rollup(&SwapStatus::Confirming, &SwapStatus::Waiting), RouteStatus::Processing);
+    assert_eq!(RouteStatus::rollup(&SwapStatus::Expired, &SwapStatus::Waiting), RouteStatus::Expired);
+    assert_eq!(RouteStatus::rollup(&SwapStatus::Refunded, &SwapStatus::Waiting), RouteStatus::Refunded);
+    assert_eq!(RouteStatus::rollup(&SwapStatus::Failed, &SwapStatus::Waiting), RouteStatus::Failed);
+}
+
+#[test]
+fn test_route_completes_with_the_second_leg() {
+    assert_eq!(RouteStatus::rollup(&SwapStatus::Completed, &SwapStatus::Exchanging), RouteStatus::Processing);
+    assert_eq!(RouteStatus::rollup(&SwapStatus::Completed, &SwapStatus::Completed), RouteStatus::Completed);
+    assert_eq!(RouteStatus::rollup(&SwapStatus::Sending, &SwapStatus::Completed), RouteStatus::Completed);
+    assert_eq!(RouteStatus::rollup(&SwapStatus::Completed, &SwapStatus::Refunded), RouteStatus::Refunded);
+    // The first leg's payout is stuck with the second leg's provider
+    assert_eq!(RouteStatus::rollup(&SwapStatus::Completed, &SwapStatus::Expired), RouteStatus::Failed);
+}
+
+#[test]
+fn test_only_waiting_and_processing_routes_are_open() {
+    assert!(!RouteStatus::Waiting.is_final());
+    assert!(!RouteStatus::Processing.is_final());
+    assert!(RouteStatus::Completed.is_final());
+    assert!(RouteStatus::Expired.is_final());
+}
+
+#[test]
+fn test_create_request_plans_its_own_route() {
+    let request: CreateRouteRequest = serde_json::from_value(json!({
+        "from": "usdc", "network_from": "ERC20", "to": "xmr", "network_to": "Mainnet",
+        "amount": 100.0, "via": "btc", "via_network": "Mainnet",
+        "recipient_address": "4Recipient",
+    }))
+    .unwrap();
+
+    let query = request.plan_query();
+    assert_eq!(query.via.as_deref(), Some("btc"));
+    assert_eq!(query.via_network.as_deref(), Some("Mainnet"));
+    assert_eq!(query.amount, dec!(100));
+    assert_eq!(query.rate_type, RateType::Floating);
+}
+
+// =============================================================================
+// INTEGRATION TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_unknown_route_is_not_found() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/swap/routes/00000000-0000-0000-0000-000000000000").await;
+    response.assert_status(StatusCode::NOT_FOUND);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "ROUTE_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_plan_requires_a_network_for_via() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx
+        .server
+        .get("/swap/routes/plan?from=usdc&network_from=ERC20&to=xmr&network_to=Mainnet&amount=100&via=btc")
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "INVALID_ROUTE");
+}
+
+#[tokio::test]
+async fn test_plan_rejects_non_positive_amounts() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx
+        .server
+        .get("/swap/routes/plan?from=usdc&network_from=ERC20&to=xmr&network_to=Mainnet&amount=0")
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+}
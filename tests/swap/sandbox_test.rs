@@ -0,0 +1,163 @@
+use axum::http::StatusCode;
+use exchange_shared::config::environment::SandboxConfig;
+use exchange_shared::modules::swap::crud::SwapError;
+use exchange_shared::modules::swap::schema::{CreateSwapRequest, RatesQuery};
+use exchange_shared::services::mock_provider::{MockProviderClient, SANDBOX_AGGREGATOR, SANDBOX_PROVIDER};
+use exchange_shared::services::swap_provider::SwapProviderClient;
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{setup_test_server, timed_get, timed_post};
+
+fn mock() -> MockProviderClient {
+    let prices = HashMap::from([("btc".to_string(), 60_000.0), ("eth".to_string(), 3_000.0)]);
+    MockProviderClient::new(&SandboxConfig { enabled: true, step: Duration::from_secs(30) }, prices)
+}
+
+fn rates_query(sandbox: bool) -> RatesQuery {
+    serde_json::from_value(json!({
+        "from": "btc",
+        "network_from": "Mainnet",
+        "to": "eth",
+        "network_to": "ERC20",
+        "amount": 0.5,
+        "sandbox": sandbox
+    }))
+    .unwrap()
+}
+
+fn create_request() -> CreateSwapRequest {
+    serde_json::from_value(json!({
+        "from": "btc",
+        "network_from": "Mainnet",
+        "to": "eth",
+        "network_to": "ERC20",
+        "amount": 0.5,
+        "provider": "changenow",
+        "recipient_address": "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23",
+        "refund_address": "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh",
+        "rate_type": "floating",
+        "sandbox": true
+    }))
+    .unwrap()
+}
+
+// =============================================================================
+// UNIT TESTS - MOCK PROVIDER
+// =============================================================================
+
+#[test]
+fn test_sandbox_trade_progresses_one_status_per_step() {
+    let mock = mock();
+
+    assert_eq!(mock.status_after(Duration::ZERO), "waiting");
+    assert_eq!(mock.status_after(Duration::from_secs(29)), "waiting");
+    assert_eq!(mock.status_after(Duration::from_secs(30)), "confirming");
+    assert_eq!(mock.status_after(Duration::from_secs(60)), "sending");
+    assert_eq!(mock.status_after(Duration::from_secs(90)), "finished");
+    assert_eq!(mock.status_after(Duration::from_secs(86_400)), "finished");
+}
+
+#[test]
+fn test_sandbox_quote_uses_reference_prices_less_fee() {
+    let mock = mock();
+
+    assert_eq!(mock.quote("BTC", "eth", dec!(0.5)), dec!(9.95));
+    // Currencies without a reference price trade 1:1
+    assert_eq!(mock.quote("xmr", "ltc", dec!(2)), dec!(1.99));
+}
+
+#[tokio::test]
+async fn test_sandbox_quotes_come_from_sandbox_provider() {
+    let quotes = mock().get_quotes(&rates_query(true)).await.unwrap();
+
+    assert_eq!(mock().aggregator(), SANDBOX_AGGREGATOR);
+    assert_eq!(quotes.trade_id, None);
+    assert_eq!(quotes.quotes.len(), 1);
+    assert_eq!(quotes.quotes[0].provider, SANDBOX_PROVIDER);
+    assert_eq!(quotes.quotes[0].amount_to, dec!(9.95));
+}
+
+#[test]
+fn test_sandbox_trade_never_looks_like_a_live_one() {
+    let request = create_request();
+    let (trade, raw) = mock().create_trade(&request);
+
+    assert!(trade.trade_id.starts_with("sandbox-"));
+    assert!(trade.address_provider.starts_with("sandbox_deposit_"));
+    assert_eq!(trade.provider, SANDBOX_PROVIDER);
+    assert_eq!(trade.status, "waiting");
+    assert_eq!(trade.address_user, request.recipient_address);
+    assert_eq!(serde_json::from_str::<Value>(&raw).unwrap()["sandbox"], true);
+
+    let (other, _) = mock().create_trade(&request);
+    assert_ne!(trade.trade_id, other.trade_id);
+}
+
+#[test]
+fn test_rates_are_live_unless_sandbox_requested() {
+    let query: RatesQuery = serde_json::from_value(json!({
+        "from": "btc",
+        "network_from": "Mainnet",
+        "to": "eth",
+        "network_to": "ERC20",
+        "amount": 0.5
+    }))
+    .unwrap();
+
+    assert!(!query.sandbox);
+    assert!(rates_query(true).sandbox);
+}
+
+#[test]
+fn test_sandbox_disabled_error() {
+    let error = SwapError::SandboxDisabled;
+
+    assert_eq!(error.status_code(), StatusCode::BAD_REQUEST);
+    assert_eq!(error.error_code(), "SANDBOX_DISABLED");
+}
+
+// =============================================================================
+// INTEGRATION TESTS - SANDBOX RATES AND SWAPS
+// =============================================================================
+
+#[tokio::test]
+async fn test_sandbox_rates_only_quote_sandbox_provider() {
+    let server = setup_test_server().await;
+
+    let response = timed_get(
+        &server,
+        "/swap/rates?from=btc&network_from=Mainnet&to=eth&network_to=ERC20&amount=0.5&sandbox=true",
+    )
+    .await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    let rates = body["rates"].as_array().unwrap();
+    assert_eq!(rates.len(), 1);
+    assert_eq!(rates[0]["provider"], SANDBOX_PROVIDER);
+    assert_eq!(rates[0]["aggregator"], SANDBOX_AGGREGATOR);
+}
+
+#[tokio::test]
+async fn test_sandbox_swap_uses_mock_provider() {
+    let server = setup_test_server().await;
+
+    let response = timed_post(&server, "/swap/create", &serde_json::to_value(create_request()).unwrap()).await;
+
+    response.assert_status(StatusCode::CREATED);
+    let body: Value = response.json();
+    assert_eq!(body["provider"], SANDBOX_PROVIDER);
+    assert!(body["deposit_address"].as_str().unwrap().starts_with("sandbox_deposit_"));
+
+    // Status refreshes come from the mock provider as well
+    let response = timed_get(&server, &format!("/swap/{}", body["swap_id"].as_str().unwrap())).await;
+    response.assert_status_ok();
+    let status: Value = response.json();
+    assert_eq!(status["is_sandbox"], true);
+    assert_eq!(status["status"], "waiting");
+}
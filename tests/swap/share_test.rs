@@ -0,0 +1,94 @@
+use axum::http::StatusCode;
+use exchange_shared::services::security::hmac_sha256_hex;
+use serde_json::{json, Value};
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{create_user_token, delete_swap, insert_swap, TestContext};
+
+// =============================================================================
+// INTEGRATION TESTS - SHARE LINKS (POST /swap/{id}/share, GET /swap/shared/{id})
+// =============================================================================
+
+const SECRET: &str = "test-share-link-secret";
+
+// The key is read when the app is built
+async fn context() -> TestContext {
+    std::env::set_var("SHARE_LINK_SECRET", SECRET);
+    TestContext::new().await
+}
+
+#[tokio::test]
+async fn test_share_link_serves_redacted_status() {
+    let ctx = context().await;
+    let swap_id = insert_swap(&ctx, "exchanging", None).await;
+
+    let response = ctx
+        .server
+        .post(&format!("/swap/{}/share", swap_id))
+        .json(&json!({ "expires_in_hours": 2 }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let link: Value = response.json();
+    let url = link["url"].as_str().unwrap();
+    assert!(url.starts_with(&format!("/swap/shared/{}?expires=", swap_id)));
+
+    let response = ctx.server.get(url).await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["swap_id"], swap_id);
+    assert_eq!(body["status"], "exchanging");
+    assert_eq!(body["from"], "btc");
+    for field in ["deposit_address", "recipient_address", "refund_address", "provider_swap_id", "tx_hash_in"] {
+        assert!(body.get(field).is_none(), "{} leaked through the share view", field);
+    }
+
+    delete_swap(&ctx, &swap_id).await;
+}
+
+#[tokio::test]
+async fn test_share_link_rejects_tampering_and_expiry() {
+    let ctx = context().await;
+    let swap_id = insert_swap(&ctx, "waiting", None).await;
+
+    // Signed for a different expiry
+    let expires = chrono::Utc::now().timestamp() + 3600;
+    let sig = hmac_sha256_hex(SECRET, format!("{}.{}", swap_id, expires - 1).as_bytes());
+    let response = ctx
+        .server
+        .get(&format!("/swap/shared/{}?expires={}&sig={}", swap_id, expires, sig))
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    assert_eq!(response.json::<Value>()["code"], "INVALID_SHARE_LINK");
+
+    // Correctly signed, but in the past
+    let expired = chrono::Utc::now().timestamp() - 60;
+    let sig = hmac_sha256_hex(SECRET, format!("{}.{}", swap_id, expired).as_bytes());
+    let response = ctx
+        .server
+        .get(&format!("/swap/shared/{}?expires={}&sig={}", swap_id, expired, sig))
+        .await;
+    response.assert_status(StatusCode::GONE);
+    assert_eq!(response.json::<Value>()["code"], "SHARE_LINK_EXPIRED");
+
+    delete_swap(&ctx, &swap_id).await;
+}
+
+#[tokio::test]
+async fn test_share_link_only_by_owner() {
+    let ctx = context().await;
+    let (user_id, token) = create_user_token(&ctx).await;
+    let swap_id = insert_swap(&ctx, "waiting", Some(&user_id)).await;
+
+    let response = ctx.server.post(&format!("/swap/{}/share", swap_id)).await;
+    response.assert_status(StatusCode::NOT_FOUND);
+
+    let response = ctx
+        .server
+        .post(&format!("/swap/{}/share", swap_id))
+        .authorization_bearer(&token)
+        .await;
+    response.assert_status(StatusCode::CREATED);
+
+    delete_swap(&ctx, &swap_id).await;
+}
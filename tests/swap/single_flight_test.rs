@@ -0,0 +1,88 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use exchange_shared::services::single_flight::SingleFlight;
+
+// =============================================================================
+// UNIT TESTS - SINGLE FLIGHT
+// =============================================================================
+
+#[tokio::test]
+async fn test_overlapping_calls_share_one_execution() {
+    let flights = SingleFlight::<u32>::new();
+    let calls = Arc::new(AtomicU32::new(0));
+
+    let mut waiters = Vec::new();
+    let mut joined = 0;
+    for _ in 0..50 {
+        let calls = calls.clone();
+        let (flight, was_joined) = flights.join("rates:btc:xmr", async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            calls.fetch_add(1, Ordering::SeqCst) + 7
+        });
+        joined += was_joined as u32;
+        waiters.push(flight);
+    }
+
+    let results = futures::future::join_all(waiters).await;
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(joined, 49);
+    assert!(results.iter().all(|r| *r == Some(7)));
+    assert_eq!(flights.in_flight(), 0);
+}
+
+#[tokio::test]
+async fn test_different_keys_do_not_share() {
+    let flights = SingleFlight::<&'static str>::new();
+
+    let (btc, btc_joined) = flights.join("rates:btc:xmr", async { "btc" });
+    let (eth, eth_joined) = flights.join("rates:eth:xmr", async { "eth" });
+
+    assert!(!btc_joined && !eth_joined);
+    assert_eq!((btc.await, eth.await), (Some("btc"), Some("eth")));
+}
+
+#[tokio::test]
+async fn test_finished_flight_is_not_reused() {
+    let flights = SingleFlight::<u32>::new();
+
+    let (first, _) = flights.join("rates:btc:xmr", async { 1 });
+    assert_eq!(first.await, Some(1));
+
+    let (second, joined) = flights.join("rates:btc:xmr", async { 2 });
+    assert!(!joined);
+    assert_eq!(second.await, Some(2));
+}
+
+/// The work keeps running for the cache after every caller stopped waiting
+#[tokio::test]
+async fn test_flight_completes_without_waiters() {
+    let flights = SingleFlight::<()>::new();
+    let done = Arc::new(AtomicU32::new(0));
+
+    let marker = done.clone();
+    let (flight, _) = flights.join("rates:btc:xmr", async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        marker.store(1, Ordering::SeqCst);
+    });
+    drop(flight);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(done.load(Ordering::SeqCst), 1);
+    assert_eq!(flights.in_flight(), 0);
+}
+
+#[tokio::test]
+async fn test_panicking_flight_releases_its_key() {
+    let flights = SingleFlight::<u32>::new();
+
+    let (flight, _) = flights.join("rates:btc:xmr", async { panic!("aggregator blew up") });
+    assert_eq!(flight.await, None);
+    assert_eq!(flights.in_flight(), 0);
+
+    let (retry, joined) = flights.join("rates:btc:xmr", async { 3 });
+    assert!(!joined);
+    assert_eq!(retry.await, Some(3));
+}
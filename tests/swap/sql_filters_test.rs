@@ -0,0 +1,111 @@
+use serde_json::Value;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{delete_currency, insert_currency, unique_symbol, TestContext};
+
+// =============================================================================
+// INTEGRATION TESTS - HOSTILE FILTER VALUES (GET /swap/currencies, /swap/providers)
+// Filter values are bound as query parameters, so quotes, backslashes and
+// comments are matched literally and never change the query.
+// =============================================================================
+
+async fn get_list(ctx: &TestContext, path: &str, params: &[(&str, &str)]) -> Vec<Value> {
+    let mut request = ctx.server.get(path);
+    for (key, value) in params {
+        request = request.add_query_param(key, value);
+    }
+    let response = request.await;
+    response.assert_status_ok();
+    response.json()
+}
+
+async fn currency_exists(ctx: &TestContext, id: i64) -> bool {
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM currencies WHERE id = ?")
+        .bind(id)
+        .fetch_one(&ctx.db)
+        .await
+        .unwrap();
+    count == 1
+}
+
+#[tokio::test]
+async fn test_currency_ticker_injection_matches_nothing() {
+    let ctx = TestContext::new().await;
+    let symbol = unique_symbol();
+    let id = insert_currency(&ctx, &symbol).await;
+
+    let hostile = [
+        format!("{}' OR '1'='1", symbol),
+        format!("{}\\' OR 1=1 -- ", symbol), // Defeats quote doubling under MySQL backslash escapes
+        "' UNION SELECT * FROM currencies -- ".to_string(),
+    ];
+    for ticker in &hostile {
+        let currencies = get_list(&ctx, "/swap/currencies", &[("ticker", ticker)]).await;
+        assert!(currencies.is_empty(), "ticker {:?} matched {} currencies", ticker, currencies.len());
+    }
+
+    // The plain ticker still matches
+    let currencies = get_list(&ctx, "/swap/currencies", &[("ticker", &symbol)]).await;
+    assert_eq!(currencies.len(), 1);
+
+    delete_currency(&ctx, id).await;
+}
+
+#[tokio::test]
+async fn test_currency_network_injection_leaves_table_intact() {
+    let ctx = TestContext::new().await;
+    let symbol = unique_symbol();
+    let id = insert_currency(&ctx, &symbol).await;
+
+    let currencies = get_list(
+        &ctx,
+        "/swap/currencies",
+        &[("ticker", &symbol), ("network", "Mainnet'; DELETE FROM currencies; -- ")],
+    )
+    .await;
+    assert!(currencies.is_empty());
+    assert!(currency_exists(&ctx, id).await, "hostile network value modified the table");
+
+    delete_currency(&ctx, id).await;
+}
+
+#[tokio::test]
+async fn test_currency_ticker_with_quote_is_matched_literally() {
+    let ctx = TestContext::new().await;
+    let symbol = format!("{}'x", unique_symbol());
+    let id = insert_currency(&ctx, &symbol).await;
+
+    let currencies = get_list(&ctx, "/swap/currencies", &[("ticker", &symbol)]).await;
+    assert_eq!(currencies.len(), 1, "a ticker containing a quote should match its own row");
+
+    delete_currency(&ctx, id).await;
+}
+
+#[tokio::test]
+async fn test_currencies_page_zero_is_first_page() {
+    let ctx = TestContext::new().await;
+
+    let page_zero = get_list(&ctx, "/swap/currencies", &[("page", "0"), ("limit", "5")]).await;
+    let page_one = get_list(&ctx, "/swap/currencies", &[("page", "1"), ("limit", "5")]).await;
+    assert_eq!(page_zero, page_one);
+}
+
+#[tokio::test]
+async fn test_provider_rating_injection_matches_nothing() {
+    let ctx = TestContext::new().await;
+
+    for rating in ["A' OR '1'='1", "A\\' OR 1=1 -- ", "' OR ''='"] {
+        let providers = get_list(&ctx, "/swap/providers", &[("rating", rating)]).await;
+        assert!(providers.is_empty(), "rating {:?} matched {} providers", rating, providers.len());
+    }
+}
+
+#[tokio::test]
+async fn test_provider_unknown_sort_falls_back_to_name() {
+    let ctx = TestContext::new().await;
+
+    let hostile = get_list(&ctx, "/swap/providers", &[("sort", "name; DROP TABLE providers; --")]).await;
+    let by_name = get_list(&ctx, "/swap/providers", &[("sort", "name")]).await;
+    assert_eq!(hostile, by_name);
+}
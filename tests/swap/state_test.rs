@@ -0,0 +1,53 @@
+use exchange_shared::modules::swap::schema::SwapStatus;
+use exchange_shared::modules::swap::state::{StatusSource, SwapStateMachine, Transition};
+
+// =============================================================================
+// UNIT TESTS - SWAP STATE MACHINE
+// =============================================================================
+
+#[test]
+fn test_provider_statuses_map_to_swap_statuses() {
+    assert_eq!(SwapStateMachine::map_provider_status("new"), SwapStatus::Waiting);
+    assert_eq!(SwapStateMachine::map_provider_status("exchanging"), SwapStatus::Exchanging);
+    assert_eq!(SwapStateMachine::map_provider_status("finished"), SwapStatus::Completed);
+    assert_eq!(SwapStateMachine::map_provider_status("paid partially"), SwapStatus::Completed);
+    assert_eq!(SwapStateMachine::map_provider_status("halted"), SwapStatus::Failed);
+    assert_eq!(SwapStateMachine::map_provider_status("something new"), SwapStatus::Waiting);
+
+    assert!(SwapStateMachine::is_underpaid("paid partially"));
+    assert!(!SwapStateMachine::is_underpaid("finished"));
+}
+
+#[test]
+fn test_forward_moves_are_allowed() {
+    let advance = |from: SwapStatus, to: SwapStatus| SwapStateMachine::transition(&from, &to, StatusSource::Webhook);
+
+    assert_eq!(advance(SwapStatus::Waiting, SwapStatus::Confirming), Ok(Transition::Advance));
+    assert_eq!(advance(SwapStatus::Sending, SwapStatus::Completed), Ok(Transition::Advance));
+    assert_eq!(advance(SwapStatus::Waiting, SwapStatus::Expired), Ok(Transition::Advance));
+    assert_eq!(advance(SwapStatus::Expired, SwapStatus::Refunded), Ok(Transition::Advance));
+    assert_eq!(advance(SwapStatus::Failed, SwapStatus::Completed), Ok(Transition::Advance));
+    assert_eq!(advance(SwapStatus::Sending, SwapStatus::Sending), Ok(Transition::Unchanged));
+}
+
+#[test]
+fn test_backward_moves_are_refused() {
+    let err = SwapStateMachine::transition(&SwapStatus::Completed, &SwapStatus::Waiting, StatusSource::Poll).unwrap_err();
+    assert_eq!(err.from, SwapStatus::Completed);
+    assert_eq!(err.to, SwapStatus::Waiting);
+
+    assert!(SwapStateMachine::transition(&SwapStatus::Refunded, &SwapStatus::Completed, StatusSource::Poll).is_err());
+    assert!(SwapStateMachine::transition(&SwapStatus::Sending, &SwapStatus::Confirming, StatusSource::Poll).is_err());
+    assert!(SwapStateMachine::transition(&SwapStatus::Expired, &SwapStatus::Waiting, StatusSource::Poll).is_err());
+}
+
+/// Only a polled status is trusted to take a funded swap back to waiting
+#[test]
+fn test_lost_deposit_needs_a_polled_status() {
+    assert_eq!(
+        SwapStateMachine::transition(&SwapStatus::Confirming, &SwapStatus::Waiting, StatusSource::Poll),
+        Ok(Transition::DepositLost)
+    );
+    assert!(SwapStateMachine::transition(&SwapStatus::Confirming, &SwapStatus::Waiting, StatusSource::Webhook).is_err());
+    assert!(!SwapStateMachine::advances(&SwapStatus::Exchanging, &SwapStatus::Waiting, StatusSource::Poll));
+}
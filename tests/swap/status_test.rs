@@ -2,7 +2,7 @@ use serde_json::{json, Value};
 
 #[path = "../common/mod.rs"]
 mod common;
-use common::{setup_test_server, timed_get, timed_post};
+use common::{delete_swap, insert_swap, setup_test_server, timed_get, timed_post, TestContext};
 use std::time::Duration;
 use tokio::time::sleep;
 
@@ -27,7 +27,7 @@ async fn test_get_swap_status_successful() {
     let provider = rate_json["rates"][0]["provider"].as_str().expect("Should have provider");
 
     let create_url = "/swap/create";
-    let recipient_address = "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve";
+    let recipient_address = "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A";
     let refund_address = "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh";
 
     let payload = json!({
@@ -157,7 +157,7 @@ async fn test_get_swap_status_updates_from_trocador() {
         "network_to": "Mainnet",
         "amount": 0.001,
         "provider": provider,
-        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
         "rate_type": "floating"
     });
 
@@ -225,7 +225,7 @@ async fn test_get_swap_status_complete_response() {
         "network_to": "Mainnet",
         "amount": 0.001,
         "provider": provider,
-        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
         "refund_address": "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh",
         "rate_type": "floating"
     });
@@ -293,7 +293,7 @@ async fn test_get_swap_status_multiple_checks() {
         "network_to": "Mainnet",
         "amount": 0.001,
         "provider": provider,
-        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
         "rate_type": "floating"
     });
 
@@ -372,7 +372,7 @@ async fn test_get_swap_status_performance() {
         "network_to": "Mainnet",
         "amount": 0.001,
         "provider": provider,
-        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGvj85ngqVqWfdn4ufSXIzJRHWKJ32khHA7wGwwve",
+        "recipient_address": "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A",
         "rate_type": "floating"
     });
 
@@ -404,3 +404,32 @@ async fn test_get_swap_status_performance() {
     
     println!("Status retrieval completed in: {:?}", duration);
 }
+
+/// Status responses carry the row version writers compare-and-swap on
+#[tokio::test]
+async fn test_get_swap_status_reports_version() {
+    let ctx = TestContext::new().await;
+    let swap_id = insert_swap(&ctx, "waiting", None).await;
+
+    let response = ctx.server.get(&format!("/swap/{}", swap_id)).await;
+    response.assert_status_ok();
+    let json: Value = response.json();
+    assert_eq!(json["version"], 0);
+
+    delete_swap(&ctx, &swap_id).await;
+    ctx.cleanup().await;
+}
+
+/// A funded swap reported as waiting again lost its deposit; other moves did not
+#[test]
+fn test_deposit_lost_only_when_a_seen_deposit_disappears() {
+    use exchange_shared::modules::swap::schema::SwapStatus;
+
+    assert!(SwapStatus::Confirming.deposit_lost(&SwapStatus::Waiting));
+    assert!(SwapStatus::Exchanging.deposit_lost(&SwapStatus::Waiting));
+
+    assert!(!SwapStatus::Waiting.deposit_lost(&SwapStatus::Confirming));
+    assert!(!SwapStatus::Confirming.deposit_lost(&SwapStatus::Exchanging));
+    assert!(!SwapStatus::Expired.deposit_lost(&SwapStatus::Waiting));
+    assert!(!SwapStatus::Completed.deposit_lost(&SwapStatus::Waiting));
+}
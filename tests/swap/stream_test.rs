@@ -0,0 +1,61 @@
+use axum::http::StatusCode;
+use exchange_shared::modules::swap::crud::SwapCrud;
+use exchange_shared::services::redis_cache::RedisService;
+use serde_json::Value;
+use std::time::Duration;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{delete_swap, insert_swap, TestContext};
+
+// =============================================================================
+// INTEGRATION TESTS - LIVE STATUS STREAM (GET /swap/{id}/ws)
+// =============================================================================
+
+#[tokio::test]
+async fn test_status_stream_rejects_unknown_swap() {
+    let ctx = TestContext::new_http().await;
+
+    let response = ctx
+        .server
+        .get_websocket("/swap/00000000-0000-0000-0000-000000000000/ws")
+        .expect_failure()
+        .await;
+
+    response.assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_status_stream_sends_snapshot_then_changes_until_final() {
+    let ctx = TestContext::new_http().await;
+    let swap_id = insert_swap(&ctx, "waiting", None).await;
+    sqlx::query("UPDATE swaps SET created_at = NOW() - INTERVAL 2 DAY WHERE id = ?")
+        .bind(&swap_id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    let mut ws = ctx
+        .server
+        .get_websocket(&format!("/swap/{}/ws", swap_id))
+        .await
+        .into_websocket()
+        .await;
+
+    let snapshot: Value = ws.receive_json().await;
+    assert_eq!(snapshot["swap_id"], swap_id.as_str());
+    assert_eq!(snapshot["status"], "waiting");
+
+    // Any writer's change reaches the socket through Redis pub/sub
+    let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+    let crud = SwapCrud::new(ctx.db.clone(), Some(RedisService::new(&redis_url)));
+    crud.expire_abandoned_swaps(Duration::from_secs(86400), 1000).await.unwrap();
+
+    let update: Value = ws.receive_json().await;
+    assert_eq!(update["swap_id"], swap_id.as_str());
+    assert_eq!(update["status"], "expired");
+    assert!(update["version"].as_u64().unwrap() > snapshot["version"].as_u64().unwrap());
+
+    ws.close().await;
+    delete_swap(&ctx, &swap_id).await;
+}
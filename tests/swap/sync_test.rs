@@ -0,0 +1,207 @@
+use axum::{routing::get, Json, Router};
+use chrono::{DateTime, Utc};
+use exchange_shared::modules::swap::crud::SwapCrud;
+use exchange_shared::services::trocador::TrocadorClient;
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{delete_currency, insert_currency, unique_symbol, TestContext};
+
+type CurrencyRow = (
+    i64,
+    String,
+    String,
+    String,
+    bool,
+    Option<String>,
+    bool,
+    Option<Decimal>,
+    Option<Decimal>,
+    Option<DateTime<Utc>>,
+);
+
+/// Local Trocador serving `coins` at /coins and `exchanges` at /exchanges
+async fn fake_trocador(coins: Vec<Value>, exchanges: Vec<Value>) -> TrocadorClient {
+    let app = Router::new()
+        .route("/coins", get(move || async move { Json(Value::Array(coins)) }))
+        .route("/exchanges", get(move || async move { Json(json!({ "list": exchanges })) }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let client = TrocadorClient::new("test".to_string());
+    client.rotate("test".to_string(), Some(url));
+    client
+}
+
+fn coin(ticker: &str, name: &str) -> Value {
+    json!({
+        "name": name,
+        "ticker": ticker,
+        "network": "Mainnet",
+        "memo": false,
+        "image": "",
+        "minimum": "0.001",
+        "maximum": "10"
+    })
+}
+
+/// Every currency row as it is now, so a sync can list them all and the
+/// test can put them back afterwards
+async fn snapshot(ctx: &TestContext) -> Vec<CurrencyRow> {
+    sqlx::query_as(
+        "SELECT id, symbol, name, network, is_active, logo_url, requires_extra_id, min_amount, max_amount, last_synced_at
+         FROM currencies",
+    )
+    .fetch_all(&ctx.db)
+    .await
+    .unwrap()
+}
+
+fn listed(rows: &[CurrencyRow], except: i64) -> Vec<Value> {
+    rows.iter()
+        .filter(|row| row.0 != except)
+        .map(|(_, symbol, name, network, _, logo_url, memo, min_amount, max_amount, _)| {
+            json!({
+                "name": name,
+                "ticker": symbol,
+                "network": network,
+                "memo": memo,
+                "image": logo_url.clone().unwrap_or_default(),
+                "minimum": min_amount.unwrap_or_default(),
+                "maximum": max_amount.unwrap_or_default()
+            })
+        })
+        .collect()
+}
+
+async fn restore(ctx: &TestContext, rows: &[CurrencyRow]) {
+    for (id, _, name, _, is_active, logo_url, _, min_amount, max_amount, last_synced_at) in rows {
+        sqlx::query(
+            "UPDATE currencies SET name = ?, is_active = ?, logo_url = ?, min_amount = ?, max_amount = ?, last_synced_at = ?
+             WHERE id = ?",
+        )
+        .bind(name)
+        .bind(is_active)
+        .bind(logo_url)
+        .bind(min_amount)
+        .bind(max_amount)
+        .bind(last_synced_at)
+        .bind(id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    }
+}
+
+async fn is_active(ctx: &TestContext, symbol: &str) -> Option<bool> {
+    sqlx::query_as::<_, (bool,)>("SELECT is_active FROM currencies WHERE symbol = ? AND network = 'Mainnet'")
+        .bind(symbol)
+        .fetch_optional(&ctx.db)
+        .await
+        .unwrap()
+        .map(|(active,)| active)
+}
+
+// =============================================================================
+// INTEGRATION TESTS - BATCHED TROCADOR SYNC
+// =============================================================================
+
+#[tokio::test]
+async fn test_sync_deactivates_currencies_missing_from_the_feed() {
+    let ctx = TestContext::new().await;
+    let dropped = unique_symbol();
+    let dropped_id = insert_currency(&ctx, &dropped).await;
+    let added = unique_symbol();
+
+    // The feed lists every other currency, so the rest of the table is left as it was
+    let before = snapshot(&ctx).await;
+    let mut coins = listed(&before, dropped_id);
+    coins.push(coin(&added, "Added Coin"));
+    let trocador = fake_trocador(coins, vec![]).await;
+
+    let result = SwapCrud::new(ctx.db.clone(), None).sync_currencies_from_trocador(&trocador).await;
+    let (dropped_active, added_active) = (is_active(&ctx, &dropped).await, is_active(&ctx, &added).await);
+    restore(&ctx, &before).await;
+
+    let stats = result.expect("sync should succeed");
+    assert_eq!(stats.fetched, before.len());
+    assert_eq!(dropped_active, Some(false), "currencies the feed no longer lists are deactivated");
+    assert_eq!(added_active, Some(true), "listed currencies are active");
+
+    sqlx::query("DELETE FROM currencies WHERE symbol = ?")
+        .bind(&added)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    delete_currency(&ctx, dropped_id).await;
+}
+
+#[tokio::test]
+async fn test_failed_batch_rolls_back_the_whole_sync() {
+    let ctx = TestContext::new().await;
+    let existing = unique_symbol();
+    let existing_id = insert_currency(&ctx, &existing).await;
+
+    // The first batch of 500 is valid; the second fails on a name too long for the column
+    let prefix = unique_symbol();
+    let mut coins: Vec<Value> = (0..500).map(|i| coin(&format!("{}{}", prefix, i), "Batch Coin")).collect();
+    coins.push(coin(&format!("{}x", prefix), &"n".repeat(500)));
+    let trocador = fake_trocador(coins, vec![]).await;
+
+    let result = SwapCrud::new(ctx.db.clone(), None).sync_currencies_from_trocador(&trocador).await;
+
+    assert!(result.is_err(), "the second batch must fail");
+    let (inserted,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM currencies WHERE symbol LIKE ?")
+        .bind(format!("{}%", prefix))
+        .fetch_one(&ctx.db)
+        .await
+        .unwrap();
+    assert_eq!(inserted, 0, "the first batch must be rolled back");
+    assert_eq!(is_active(&ctx, &existing).await, Some(true), "nothing is deactivated");
+
+    delete_currency(&ctx, existing_id).await;
+}
+
+#[tokio::test]
+async fn test_provider_sync_keeps_the_id_of_an_existing_row() {
+    let ctx = TestContext::new().await;
+    let suffix = unique_symbol();
+    let name = format!("Sync Test {}", suffix);
+    let slug = format!("sync-test-{}", suffix);
+    // Created before providers were keyed by slug, under a different id
+    let legacy_id = format!("legacy-{}", suffix);
+    sqlx::query("INSERT INTO providers (id, name, slug, is_active, kyc_rating) VALUES (?, ?, ?, TRUE, 'C')")
+        .bind(&legacy_id)
+        .bind(&name)
+        .bind(&slug)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    let trocador = fake_trocador(
+        vec![],
+        vec![json!({ "name": name, "rating": "A", "insurance": 0.015, "enabledmarkup": true, "eta": 12.0 })],
+    )
+    .await;
+
+    let result = SwapCrud::new(ctx.db.clone(), None).sync_providers_from_trocador(&trocador).await;
+
+    let rows: Vec<(String, String)> = sqlx::query_as("SELECT id, kyc_rating FROM providers WHERE slug = ? OR id = ?")
+        .bind(&slug)
+        .bind(&slug)
+        .fetch_all(&ctx.db)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM providers WHERE slug = ? OR id = ?")
+        .bind(&slug)
+        .bind(&slug)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+
+    result.expect("sync should succeed");
+    assert_eq!(rows, vec![(legacy_id, "A".to_string())], "the row is updated in place, not duplicated");
+}
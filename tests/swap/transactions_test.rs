@@ -0,0 +1,84 @@
+use exchange_shared::config::environment::BlockExplorerConfig;
+use exchange_shared::modules::swap::schema::{SwapStatusResponse, TransactionReport};
+use exchange_shared::services::block_explorer::BlockExplorers;
+use serde_json::json;
+
+fn explorers() -> BlockExplorers {
+    BlockExplorers::from_config(&BlockExplorerConfig {
+        tx_urls: [("btc", "https://mempool.space/tx/{tx}"), ("erc20", "https://etherscan.io/tx/{tx}")]
+            .into_iter()
+            .map(|(key, template)| (key.to_string(), template.to_string()))
+            .collect(),
+    })
+}
+
+// =============================================================================
+// UNIT TESTS - TRANSACTION REPORTS
+// =============================================================================
+
+#[test]
+fn test_report_reads_hashes_and_confirmations() {
+    let details = json!({ "hashin": "abc123", "hashout": "def456", "confirmations": 3 });
+
+    let report = TransactionReport::from_details("sending", Some(&details));
+
+    assert_eq!(report.deposit_tx_hash.as_deref(), Some("abc123"));
+    assert_eq!(report.payout_tx_hash.as_deref(), Some("def456"));
+    assert_eq!(report.deposit_confirmations, Some(3));
+}
+
+#[test]
+fn test_refund_hash_is_not_a_payout() {
+    let details = json!({ "hashout": "refund123", "confirmations": "6" });
+
+    let report = TransactionReport::from_details("refunded", Some(&details));
+
+    assert_eq!(report.payout_tx_hash, None);
+    assert_eq!(report.deposit_confirmations, Some(6));
+}
+
+#[test]
+fn test_blank_details_report_nothing() {
+    let details = json!({ "hashin": "", "hashout": " ", "confirmations": null });
+
+    assert!(TransactionReport::from_details("confirming", Some(&details)).is_empty());
+    assert!(TransactionReport::from_details("confirming", None).is_empty());
+}
+
+// =============================================================================
+// UNIT TESTS - BLOCK EXPLORER LINKS
+// =============================================================================
+
+#[test]
+fn test_coins_link_by_ticker_and_tokens_by_network() {
+    let explorers = explorers();
+
+    assert_eq!(explorers.tx_url("BTC", "Mainnet", "ab12").as_deref(), Some("https://mempool.space/tx/ab12"));
+    assert_eq!(explorers.tx_url("usdt", "ERC20", "0xab12").as_deref(), Some("https://etherscan.io/tx/0xab12"));
+    assert_eq!(explorers.tx_url("xmr", "Mainnet", "ab12"), None);
+}
+
+#[test]
+fn test_unsafe_hashes_get_no_link() {
+    let explorers = explorers();
+
+    assert_eq!(explorers.tx_url("btc", "Mainnet", "ab12/../admin"), None);
+    assert_eq!(explorers.tx_url("btc", "Mainnet", ""), None);
+}
+
+#[test]
+fn test_statuses_cached_before_the_rename_still_read() {
+    let cached = json!({
+        "swap_id": "s1", "provider": "changenow", "provider_swap_id": null, "status": "sending",
+        "from": "btc", "to": "xmr", "amount": 0.1, "deposit_address": "bc1q", "recipient_address": "4A",
+        "rate": 150.0, "estimated_receive": 15.0, "network_fee": 0.0, "total_fee": 0.0,
+        "rate_type": "floating", "is_sandbox": false, "tx_hash_in": "ab12", "tx_hash_out": "cd34",
+        "created_at": "2026-03-13T00:00:00Z", "updated_at": "2026-03-13T00:00:00Z"
+    });
+
+    let status: SwapStatusResponse = serde_json::from_value(cached).unwrap();
+
+    assert_eq!(status.deposit_tx_hash.as_deref(), Some("ab12"));
+    assert_eq!(status.payout_tx_hash.as_deref(), Some("cd34"));
+    assert_eq!(status.deposit_confirmations, None);
+}
@@ -0,0 +1,94 @@
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use serde_json::Value;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::TestContext;
+
+// =============================================================================
+// INTEGRATION TESTS - PROVIDER UPTIME (GET /swap/providers/{id}/uptime)
+// =============================================================================
+
+async fn insert_provider(ctx: &TestContext) -> (String, String) {
+    let id = format!("test-{}", &uuid::Uuid::new_v4().simple().to_string()[..8]);
+    let name = format!("Test {}", id);
+    sqlx::query("INSERT INTO providers (id, name, slug, is_active) VALUES (?, ?, ?, TRUE)")
+        .bind(&id)
+        .bind(&name)
+        .bind(&id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    (id, name)
+}
+
+async fn insert_day(ctx: &TestContext, provider: &str, days_ago: i64, probes: u32, probes_up: u32) {
+    sqlx::query("INSERT INTO provider_uptime_daily (provider, day, probes, probes_up) VALUES (?, ?, ?, ?)")
+        .bind(provider)
+        .bind(Utc::now().date_naive() - Duration::days(days_ago))
+        .bind(probes)
+        .bind(probes_up)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+}
+
+async fn delete_provider(ctx: &TestContext, id: &str, name: &str) {
+    sqlx::query("DELETE FROM provider_uptime_daily WHERE provider = ?")
+        .bind(name)
+        .execute(&ctx.db)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM providers WHERE id = ?").bind(id).execute(&ctx.db).await.ok();
+}
+
+#[tokio::test]
+async fn test_uptime_unknown_provider_is_not_found() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/swap/providers/no-such-provider/uptime").await;
+
+    response.assert_status(StatusCode::NOT_FOUND);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "PROVIDER_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_uptime_without_history_has_no_percentages() {
+    let ctx = TestContext::new().await;
+    let (id, name) = insert_provider(&ctx).await;
+
+    let response = ctx.server.get(&format!("/swap/providers/{}/uptime", id)).await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["provider"], name.as_str());
+    assert!(body["last_30_days"]["availability_percent"].is_null());
+    assert_eq!(body["daily"].as_array().unwrap().len(), 0);
+
+    delete_provider(&ctx, &id, &name).await;
+}
+
+#[tokio::test]
+async fn test_uptime_windows_cover_30_and_90_days() {
+    let ctx = TestContext::new().await;
+    let (id, name) = insert_provider(&ctx).await;
+
+    insert_day(&ctx, &name, 0, 100, 100).await;
+    insert_day(&ctx, &name, 10, 100, 50).await;
+    insert_day(&ctx, &name, 60, 200, 0).await; // Only in the 90-day window
+    insert_day(&ctx, &name, 120, 100, 0).await; // Outside both
+
+    let response = ctx.server.get(&format!("/swap/providers/{}/uptime", id)).await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["last_30_days"]["probes"], 200);
+    assert_eq!(body["last_30_days"]["availability_percent"], 75.0);
+    assert_eq!(body["last_90_days"]["probes"], 400);
+    assert_eq!(body["last_90_days"]["availability_percent"], 37.5);
+    assert_eq!(body["daily"].as_array().unwrap().len(), 3);
+
+    delete_provider(&ctx, &id, &name).await;
+}
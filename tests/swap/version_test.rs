@@ -0,0 +1,30 @@
+use serde_json::Value;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::setup_test_server;
+
+// =============================================================================
+// INTEGRATION TESTS - GET /version
+// =============================================================================
+
+#[tokio::test]
+async fn test_version_lists_compiled_features_and_configured_integrations() {
+    let server = setup_test_server().await;
+
+    let response = server.get("/version").await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+
+    let features: Vec<&str> = body["features"].as_array().unwrap().iter().filter_map(Value::as_str).collect();
+    assert_eq!(features.contains(&"client"), cfg!(feature = "client"));
+    assert_eq!(features.contains(&"grpc"), cfg!(feature = "grpc"));
+    assert_eq!(features.contains(&"nats"), cfg!(feature = "nats"));
+    assert_eq!(features.contains(&"sqlite"), cfg!(feature = "sqlite"));
+
+    let known = ["trocador", "provider_credentials", "trocador_webhook", "onramp", "price_feed", "fee_estimator"];
+    for integration in body["integrations"].as_array().unwrap() {
+        assert!(known.contains(&integration.as_str().unwrap()), "unexpected integration {}", integration);
+    }
+}
@@ -0,0 +1,91 @@
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use exchange_shared::config::environment::VolumeLimitConfig;
+use exchange_shared::modules::swap::crud::SwapError;
+use exchange_shared::modules::swap::schema::VolumeAllowance;
+use serde_json::Value;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{create_user_token, TestContext};
+
+// =============================================================================
+// UNIT TESTS - ROLLING VOLUME LIMITS
+// =============================================================================
+
+#[test]
+fn test_zero_volume_limit_means_none() {
+    let config = VolumeLimitConfig { daily_usd: 5_000.0, monthly_usd: 0.0 };
+
+    assert_eq!(config.daily_limit(), Some(5_000.0));
+    assert_eq!(config.monthly_limit(), None);
+    assert_eq!(VolumeLimitConfig::default().daily_limit(), None);
+}
+
+#[test]
+fn test_allowance_remaining_never_goes_negative() {
+    let allowance = VolumeAllowance::new(24, Some(1_000.0), 400.0, 150.0);
+    assert_eq!(allowance.remaining_usd, Some(600.0));
+
+    let over = VolumeAllowance::new(24, Some(1_000.0), 1_250.0, 0.0);
+    assert_eq!(over.remaining_usd, Some(0.0));
+
+    let uncapped = VolumeAllowance::new(720, None, 1_250.0, 0.0);
+    assert_eq!(uncapped.remaining_usd, None);
+}
+
+#[tokio::test]
+async fn test_volume_limit_exceeded_error_response() {
+    let error = SwapError::VolumeLimitExceeded { window: "24 hours", limit: 5_000.0, remaining: 1_234.5 };
+
+    let response = error.into_response();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["code"], "VOLUME_LIMIT_EXCEEDED");
+    assert_eq!(body["error"], "Swaps are limited to 5000 USD per 24 hours; 1234.50 USD remains");
+}
+
+// =============================================================================
+// INTEGRATION TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_swap_limits_require_auth() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/swap/limits").await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_swap_limits_sum_volume_per_window() {
+    let ctx = TestContext::new().await;
+    let (user_id, token) = create_user_token(&ctx).await;
+    for (hours_ago, created, completed) in [(1, 300.0, 300.0), (3, 200.0, 0.0), (72, 1_000.0, 1_000.0), (24 * 40, 9_000.0, 0.0)] {
+        sqlx::query(
+            "INSERT INTO user_volume (user_id, hour_start, created_usd, completed_usd, swaps_created, swaps_completed)
+             VALUES (?, DATE_FORMAT(DATE_SUB(NOW(), INTERVAL ? HOUR), '%Y-%m-%d %H:00:00'), ?, ?, 1, ?)",
+        )
+        .bind(&user_id)
+        .bind(hours_ago)
+        .bind(created)
+        .bind(completed)
+        .bind(u32::from(completed > 0.0))
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    }
+
+    let response = ctx.server.get("/swap/limits").authorization_bearer(&token).await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["rolling_24h"]["window_hours"], 24);
+    assert_eq!(body["rolling_24h"]["used_usd"], 500.0);
+    assert_eq!(body["rolling_24h"]["completed_usd"], 300.0);
+    assert_eq!(body["rolling_30d"]["window_hours"], 720);
+    assert_eq!(body["rolling_30d"]["used_usd"], 1_500.0);
+    assert_eq!(body["rolling_30d"]["completed_usd"], 1_300.0);
+}
@@ -0,0 +1,43 @@
+use axum::http::StatusCode;
+use exchange_shared::services::cache_warmup::{self, prime_caches, WarmupStatus};
+use exchange_shared::services::redis_cache::RedisService;
+use serde_json::Value;
+use std::time::Duration;
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::TestContext;
+
+// =============================================================================
+// INTEGRATION TESTS - STARTUP CACHE PRIMING
+// =============================================================================
+
+#[tokio::test]
+async fn test_ready_reports_warming_until_caches_are_primed() {
+    let ctx = TestContext::new().await;
+    let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+    let redis = RedisService::new(&redis_url);
+
+    cache_warmup::warmup().begin();
+
+    let response = ctx.server.get("/ready").await;
+    response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    let body: Value = response.json();
+    assert_eq!(body["status"], "warming");
+
+    let snapshot = prime_caches(ctx.db.clone(), redis.clone(), Duration::from_secs(30)).await;
+    assert_eq!(snapshot.status, WarmupStatus::Done);
+    assert!(!snapshot.timed_out);
+
+    let primed: Vec<&str> = snapshot.entries.iter().map(|e| e.cache.as_str()).collect();
+    for cache in ["currencies", "providers", "maintenance", "address_formats"] {
+        assert!(primed.contains(&cache), "{} was not primed", cache);
+    }
+    assert!(redis.get_json::<Value>("maintenance:state").await.unwrap().is_some());
+
+    let response = ctx.server.get("/ready").await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["status"], "ready");
+    assert_eq!(body["cache_warmup"]["status"], "done");
+}
@@ -0,0 +1,222 @@
+use axum::http::StatusCode;
+use exchange_shared::services::security::hmac_sha256_hex;
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+
+#[path = "../common/mod.rs"]
+mod common;
+use common::{amount, delete_swap, insert_swap, TestContext};
+
+// =============================================================================
+// INTEGRATION TESTS - TROCADOR STATUS WEBHOOK (POST /swap/webhook/trocador)
+// =============================================================================
+
+const SECRET: &str = "test-trocador-webhook-secret";
+
+// The secret is read when the app is built
+async fn context() -> TestContext {
+    std::env::set_var("TROCADOR_WEBHOOK_SECRET", SECRET);
+    TestContext::new().await
+}
+
+/// Insert a swap with a unique Trocador trade id, returning (swap_id, trade_id)
+async fn insert_trade(ctx: &TestContext, status: &str) -> (String, String) {
+    let swap_id = insert_swap(ctx, status, None).await;
+    let trade_id = format!("trade-{}", uuid::Uuid::new_v4().simple());
+    sqlx::query("UPDATE swaps SET provider_swap_id = ? WHERE id = ?")
+        .bind(&trade_id)
+        .bind(&swap_id)
+        .execute(&ctx.db)
+        .await
+        .unwrap();
+    (swap_id, trade_id)
+}
+
+async fn post_signed(ctx: &TestContext, payload: Value) -> axum_test::TestResponse {
+    let body = payload.to_string();
+    ctx.server
+        .post("/swap/webhook/trocador")
+        .add_header("X-Signature", hmac_sha256_hex(SECRET, body.as_bytes()))
+        .text(body)
+        .await
+}
+
+async fn stored_status(ctx: &TestContext, swap_id: &str) -> (String, u32) {
+    sqlx::query_as("SELECT status, version FROM swaps WHERE id = ?")
+        .bind(swap_id)
+        .fetch_one(&ctx.db)
+        .await
+        .unwrap()
+}
+
+async fn recorded_outcomes(ctx: &TestContext, trade_id: &str) -> Vec<String> {
+    sqlx::query_as::<_, (String,)>(
+        "SELECT outcome FROM swap_webhook_events WHERE provider_swap_id = ? ORDER BY id",
+    )
+    .bind(trade_id)
+    .fetch_all(&ctx.db)
+    .await
+    .unwrap()
+    .into_iter()
+    .map(|(outcome,)| outcome)
+    .collect()
+}
+
+#[tokio::test]
+async fn test_trocador_webhook_applies_status_and_records_delivery() {
+    let ctx = context().await;
+    let (swap_id, trade_id) = insert_trade(&ctx, "waiting").await;
+
+    let response = post_signed(&ctx, json!({ "trade_id": trade_id, "status": "confirming", "amount_to": 1.5 })).await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["outcome"], "applied");
+    assert_eq!(body["swap_id"], swap_id);
+    assert_eq!(body["status"], "confirming");
+    assert!(body["event_id"].is_u64());
+
+    assert_eq!(stored_status(&ctx, &swap_id).await, ("confirming".to_string(), 1));
+    assert_eq!(recorded_outcomes(&ctx, &trade_id).await, vec!["applied"]);
+
+    delete_swap(&ctx, &swap_id).await;
+}
+
+#[tokio::test]
+async fn test_trocador_webhook_ignores_stale_delivery() {
+    let ctx = context().await;
+    let (swap_id, trade_id) = insert_trade(&ctx, "sending").await;
+
+    let response = post_signed(&ctx, json!({ "trade_id": trade_id, "status": "confirming" })).await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["outcome"], "superseded");
+    assert_eq!(body["status"], "sending");
+    assert_eq!(stored_status(&ctx, &swap_id).await.0, "sending");
+
+    delete_swap(&ctx, &swap_id).await;
+}
+
+#[tokio::test]
+async fn test_trocador_webhook_rejects_bad_signature() {
+    let ctx = context().await;
+    let (swap_id, trade_id) = insert_trade(&ctx, "waiting").await;
+
+    let signature = format!("0000{}", uuid::Uuid::new_v4().simple());
+    let response = ctx
+        .server
+        .post("/swap/webhook/trocador")
+        .add_header("X-Signature", signature.clone())
+        .json(&json!({ "trade_id": trade_id, "status": "finished" }))
+        .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+    assert_eq!(stored_status(&ctx, &swap_id).await.0, "waiting");
+
+    // Rejected deliveries are kept as metadata only; the unverified body is neither parsed nor stored
+    let (outcome, valid, error, payload_bytes, payload): (String, bool, Option<String>, u32, Option<String>) =
+        sqlx::query_as(
+            "SELECT outcome, signature_valid, error, payload_bytes, payload
+             FROM swap_webhook_events WHERE signature = ? ORDER BY id DESC LIMIT 1",
+        )
+        .bind(&signature)
+        .fetch_one(&ctx.db)
+        .await
+        .unwrap();
+    assert_eq!(outcome, "rejected");
+    assert!(!valid);
+    assert_eq!(error.as_deref(), Some("Invalid webhook signature"));
+    assert!(payload_bytes > 0);
+    assert_eq!(payload, None);
+
+    delete_swap(&ctx, &swap_id).await;
+}
+
+#[tokio::test]
+async fn test_trocador_webhook_unknown_trade() {
+    let ctx = context().await;
+
+    let response = post_signed(&ctx, json!({ "trade_id": "no-such-trade", "status": "finished" })).await;
+
+    response.assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_trocador_webhook_records_refund() {
+    let ctx = context().await;
+    let (swap_id, trade_id) = insert_trade(&ctx, "failed").await;
+
+    let response = post_signed(
+        &ctx,
+        json!({
+            "trade_id": trade_id,
+            "status": "refunded",
+            "amount_from": 0.001,
+            "refund_address": "",
+            "details": { "hashout": "refund-tx-1" },
+        }),
+    )
+    .await;
+    response.assert_status_ok();
+
+    let response = ctx.server.get(&format!("/swap/{}/refund", swap_id)).await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["swap_id"], swap_id);
+    assert_eq!(body["tx_hash"], "refund-tx-1");
+    assert_eq!(amount(&body["amount"]), dec!(0.001));
+    assert_eq!(body["currency"], "btc");
+    assert_eq!(body["source"], "webhook");
+    // Falls back to the address given at creation when the provider sends none
+    assert_eq!(body["refund_address"], "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh");
+
+    delete_swap(&ctx, &swap_id).await;
+}
+
+#[tokio::test]
+async fn test_trocador_webhook_records_transactions() {
+    let ctx = context().await;
+    let (swap_id, trade_id) = insert_trade(&ctx, "confirming").await;
+    let hash_in = "a1".repeat(32);
+
+    let details = json!({ "hashin": hash_in, "confirmations": "2" });
+    let response = post_signed(&ctx, json!({ "trade_id": trade_id, "status": "confirming", "details": details })).await;
+    assert_eq!(response.json::<Value>()["outcome"], "unchanged");
+
+    let body: Value = ctx.server.get(&format!("/swap/{}", swap_id)).await.json();
+    assert_eq!(body["deposit_tx_hash"], hash_in);
+    assert_eq!(body["deposit_confirmations"], 2);
+    assert_eq!(body["deposit_tx_url"], format!("https://mempool.space/tx/{}", hash_in));
+    assert!(body.get("payout_tx_hash").is_none());
+
+    // Later deliveries keep what they leave out
+    let details = json!({ "hashout": "b2".repeat(32) });
+    post_signed(&ctx, json!({ "trade_id": trade_id, "status": "finished", "details": details })).await.assert_status_ok();
+
+    let (tx_in, tx_out, confirmations): (Option<String>, Option<String>, Option<u32>) =
+        sqlx::query_as("SELECT tx_hash_in, tx_hash_out, deposit_confirmations FROM swaps WHERE id = ?")
+            .bind(&swap_id)
+            .fetch_one(&ctx.db)
+            .await
+            .unwrap();
+    assert_eq!(tx_in, Some(hash_in));
+    assert_eq!(tx_out, Some("b2".repeat(32)));
+    assert_eq!(confirmations, Some(2));
+
+    delete_swap(&ctx, &swap_id).await;
+}
+
+#[tokio::test]
+async fn test_swap_refund_not_found_before_refund() {
+    let ctx = context().await;
+    let swap_id = insert_swap(&ctx, "waiting", None).await;
+
+    let response = ctx.server.get(&format!("/swap/{}/refund", swap_id)).await;
+
+    response.assert_status(StatusCode::NOT_FOUND);
+    let body: Value = response.json();
+    assert_eq!(body["code"], "REFUND_NOT_FOUND");
+
+    delete_swap(&ctx, &swap_id).await;
+}
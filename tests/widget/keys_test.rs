@@ -0,0 +1,200 @@
+use axum::http::{header, Method, StatusCode};
+use chrono::Utc;
+use exchange_shared::modules::widget::model::WidgetKey;
+use exchange_shared::modules::widget::schema::{check_origin, WidgetKeyRequest};
+use serde_json::{json, Value};
+
+use crate::common::{create_admin_token, create_user_token, TestContext};
+
+const ORIGIN: &str = "https://partner.example";
+
+fn widget_key(allowed_origins: &[&str]) -> WidgetKey {
+    WidgetKey {
+        public_key: "wk_test".to_string(),
+        name: "Partner".to_string(),
+        brand: None,
+        affiliate: None,
+        allowed_origins: allowed_origins.iter().map(|o| o.to_string()).collect(),
+        rate_limit_per_minute: 20,
+        is_active: true,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+async fn issue_key(ctx: &TestContext, body: Value) -> String {
+    let admin = create_admin_token(ctx).await;
+    let response = ctx.server.post("/admin/widget-keys").authorization_bearer(&admin).json(&body).await;
+    response.assert_status(StatusCode::CREATED);
+    let key: Value = response.json();
+    key["public_key"].as_str().unwrap().to_string()
+}
+
+async fn delete_key(ctx: &TestContext, public_key: &str) {
+    sqlx::query("DELETE FROM widget_keys WHERE public_key = ?").bind(public_key).execute(&ctx.db).await.ok();
+}
+
+// =============================================================================
+// UNIT TESTS - ORIGINS
+// =============================================================================
+
+#[test]
+fn test_origins_match_exactly() {
+    let key = widget_key(&[ORIGIN]);
+    assert!(key.allows_origin("https://partner.example"));
+    assert!(key.allows_origin("https://Partner.example/"));
+    assert!(!key.allows_origin("http://partner.example"));
+    assert!(!key.allows_origin("https://partner.example:8443"));
+    assert!(!key.allows_origin("https://shop.partner.example"));
+}
+
+#[test]
+fn test_wildcard_origins_match_subdomains_only() {
+    let key = widget_key(&["https://*.partner.example"]);
+    assert!(key.allows_origin("https://shop.partner.example"));
+    assert!(key.allows_origin("https://a.b.partner.example"));
+    assert!(!key.allows_origin("https://partner.example"));
+    assert!(!key.allows_origin("https://evilpartner.example"));
+    assert!(!key.allows_origin("http://shop.partner.example"));
+}
+
+#[test]
+fn test_allowed_origins_are_scheme_and_host() {
+    assert!(check_origin("https://partner.example").is_ok());
+    assert!(check_origin("http://localhost:3000").is_ok());
+    assert!(check_origin("https://*.partner.example").is_ok());
+    assert!(check_origin("partner.example").is_err());
+    assert!(check_origin("https://partner.example/").is_err());
+    assert!(check_origin("https://Partner.example").is_err());
+    assert!(check_origin("https://*").is_err());
+}
+
+#[test]
+fn test_key_request_rules() {
+    let request: WidgetKeyRequest =
+        serde_json::from_value(json!({ "name": "Partner", "allowed_origins": [ORIGIN] })).unwrap();
+    assert_eq!(request.rate_limit_per_minute, 20);
+    assert!(request.check_rules().is_ok());
+
+    let no_origins = WidgetKeyRequest { allowed_origins: Vec::new(), ..request.clone() };
+    assert!(no_origins.check_rules().is_err());
+    let too_fast = WidgetKeyRequest { rate_limit_per_minute: 1000, ..request.clone() };
+    assert!(too_fast.check_rules().is_err());
+    let unlimited = WidgetKeyRequest { rate_limit_per_minute: 0, ..request };
+    assert!(unlimited.check_rules().is_err());
+}
+
+// =============================================================================
+// INTEGRATION TESTS - WIDGET ACCESS
+// =============================================================================
+
+#[tokio::test]
+async fn test_widget_requires_a_key() {
+    let ctx = TestContext::new().await;
+
+    let response = ctx.server.get("/widget/swap/some-id").add_header(header::ORIGIN, ORIGIN).await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+    assert!(response.maybe_header(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+
+    let response = ctx.server.get("/widget/swap/some-id?key=wk_unknown").add_header(header::ORIGIN, ORIGIN).await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_widget_answers_only_allowed_origins() {
+    let ctx = TestContext::new().await;
+    let key = issue_key(&ctx, json!({ "name": "Partner", "allowed_origins": [ORIGIN] })).await;
+    let path = format!("/widget/swap/{}?key={}", uuid::Uuid::new_v4(), key);
+
+    let response = ctx.server.get(&path).add_header(header::ORIGIN, ORIGIN).await;
+    response.assert_status(StatusCode::NOT_FOUND);
+    assert_eq!(response.header(header::ACCESS_CONTROL_ALLOW_ORIGIN), ORIGIN);
+
+    let response = ctx.server.get(&path).add_header(header::ORIGIN, "https://elsewhere.example").await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    assert!(response.maybe_header(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+    let body: Value = response.json();
+    assert_eq!(body["code"], "ORIGIN_NOT_ALLOWED");
+
+    // Server-side callers send no Origin
+    ctx.server.get(&path).await.assert_status(StatusCode::FORBIDDEN);
+
+    delete_key(&ctx, &key).await;
+}
+
+#[tokio::test]
+async fn test_preflight_is_answered_for_allowed_origins() {
+    let ctx = TestContext::new().await;
+    let key = issue_key(&ctx, json!({ "name": "Partner", "allowed_origins": [ORIGIN] })).await;
+    let path = format!("/widget/swap?key={}", key);
+
+    let response = ctx
+        .server
+        .method(Method::OPTIONS, &path)
+        .add_header(header::ORIGIN, ORIGIN)
+        .add_header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+        .await;
+    response.assert_status(StatusCode::NO_CONTENT);
+    assert_eq!(response.header(header::ACCESS_CONTROL_ALLOW_ORIGIN), ORIGIN);
+
+    let response = ctx
+        .server
+        .method(Method::OPTIONS, &path)
+        .add_header(header::ORIGIN, "https://elsewhere.example")
+        .add_header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    assert!(response.maybe_header(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+
+    delete_key(&ctx, &key).await;
+}
+
+#[tokio::test]
+async fn test_widget_key_rate_limit() {
+    let ctx = TestContext::new().await;
+    let key = issue_key(&ctx, json!({ "name": "Partner", "allowed_origins": [ORIGIN], "rate_limit_per_minute": 1 })).await;
+    let path = format!("/widget/swap/{}?key={}", uuid::Uuid::new_v4(), key);
+
+    ctx.server.get(&path).add_header(header::ORIGIN, ORIGIN).await.assert_status(StatusCode::NOT_FOUND);
+    let response = ctx.server.get(&path).add_header(header::ORIGIN, ORIGIN).await;
+    response.assert_status(StatusCode::TOO_MANY_REQUESTS);
+    assert!(response.maybe_header(header::RETRY_AFTER).is_some());
+
+    delete_key(&ctx, &key).await;
+}
+
+// =============================================================================
+// INTEGRATION TESTS - ADMIN (/admin/widget-keys)
+// =============================================================================
+
+#[tokio::test]
+async fn test_widget_keys_require_admin() {
+    let ctx = TestContext::new().await;
+    let (_, token) = create_user_token(&ctx).await;
+
+    let response = ctx.server.get("/admin/widget-keys").authorization_bearer(&token).await;
+    response.assert_status(StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_revoked_key_stops_working() {
+    let ctx = TestContext::new().await;
+    let admin = create_admin_token(&ctx).await;
+    let key = issue_key(&ctx, json!({ "name": "Partner", "allowed_origins": [ORIGIN] })).await;
+    assert!(key.starts_with("wk_"));
+
+    let response = ctx
+        .server
+        .put(&format!("/admin/widget-keys/{}", key))
+        .authorization_bearer(&admin)
+        .json(&json!({ "name": "Partner", "allowed_origins": [ORIGIN], "affiliate": "no-such-partner" }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    let response = ctx.server.delete(&format!("/admin/widget-keys/{}", key)).authorization_bearer(&admin).await;
+    response.assert_status_ok();
+
+    let path = format!("/widget/swap/{}?key={}", uuid::Uuid::new_v4(), key);
+    let response = ctx.server.get(&path).add_header(header::ORIGIN, ORIGIN).await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1 @@
+mod keys_test;